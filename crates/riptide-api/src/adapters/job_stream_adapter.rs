@@ -0,0 +1,422 @@
+//! Backpressure-aware bridge from worker job progress to client streams.
+//!
+//! Each streaming client gets its own bounded buffer between the task that
+//! watches the job and the HTTP response body. What happens when the buffer
+//! is full is negotiated per client through [`OverflowPolicy`]: the watcher
+//! either pauses until the client catches up, or discards updates and tells
+//! the client how many were lost. Either way a slow NDJSON/SSE consumer holds
+//! at most `buffer` events in memory.
+//!
+//! Terminal events (the job result, a job failure or a stream error) are
+//! never discarded.
+
+use crate::dto::workers::{JobResultResponse, JobStatusResponse};
+use futures::Stream;
+use riptide_workers::{JobStatus, WorkerService};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::Notify;
+use tracing::debug;
+use uuid::Uuid;
+
+/// Events buffered per client when none is requested
+pub const DEFAULT_JOB_STREAM_BUFFER: usize = 16;
+
+/// Upper bound on the buffer a client may request
+pub const MAX_JOB_STREAM_BUFFER: usize = 256;
+
+/// How often the watcher polls the job queue for status changes
+pub const JOB_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// What to do when a client's buffer is full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Stop watching the job until the client reads
+    #[default]
+    Pause,
+    /// Discard the oldest buffered update to make room
+    DropOldest,
+    /// Discard the incoming update
+    DropNewest,
+}
+
+/// Per-client stream options, taken from the query string
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct JobStreamOptions {
+    /// Maximum events buffered for this client
+    #[serde(default = "default_buffer")]
+    pub buffer: usize,
+
+    /// Behavior when the buffer is full
+    #[serde(default)]
+    pub overflow: OverflowPolicy,
+}
+
+fn default_buffer() -> usize {
+    DEFAULT_JOB_STREAM_BUFFER
+}
+
+impl Default for JobStreamOptions {
+    fn default() -> Self {
+        Self {
+            buffer: DEFAULT_JOB_STREAM_BUFFER,
+            overflow: OverflowPolicy::default(),
+        }
+    }
+}
+
+impl JobStreamOptions {
+    fn capacity(&self) -> usize {
+        self.buffer.clamp(1, MAX_JOB_STREAM_BUFFER)
+    }
+}
+
+/// Event sent to a client watching a job
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobStreamEvent {
+    /// The job changed status or was retried
+    Status(JobStatusResponse),
+    /// The job completed; always the last event
+    Result(JobResultResponse),
+    /// The job failed for good or was dead-lettered; always the last event
+    Failed {
+        status: JobStatus,
+        error: Option<String>,
+    },
+    /// Updates discarded because the client fell behind
+    Dropped { count: u64 },
+    /// The job could not be watched; always the last event
+    Error { message: String },
+}
+
+impl JobStreamEvent {
+    fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::Result(_) | Self::Failed { .. } | Self::Error { .. }
+        )
+    }
+}
+
+#[derive(Default)]
+struct BufferState {
+    queue: VecDeque<JobStreamEvent>,
+    dropped: u64,
+    producer_done: bool,
+    consumer_gone: bool,
+}
+
+struct Shared {
+    state: Mutex<BufferState>,
+    /// Signalled when an event is queued or the producer finishes
+    readable: Notify,
+    /// Signalled when an event is taken or the consumer goes away
+    writable: Notify,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, BufferState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Create a bounded event channel honoring `options`
+pub fn job_stream_channel(options: JobStreamOptions) -> (JobEventSender, JobEventReceiver) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(BufferState::default()),
+        readable: Notify::new(),
+        writable: Notify::new(),
+    });
+    let sender = JobEventSender {
+        shared: Arc::clone(&shared),
+        capacity: options.capacity(),
+        policy: options.overflow,
+    };
+    (sender, JobEventReceiver { shared })
+}
+
+/// Producing half of a job event channel
+pub struct JobEventSender {
+    shared: Arc<Shared>,
+    capacity: usize,
+    policy: OverflowPolicy,
+}
+
+impl JobEventSender {
+    /// Queue `event`, applying the overflow policy if the buffer is full
+    ///
+    /// Returns `false` once the client has gone away.
+    pub async fn send(&self, event: JobStreamEvent) -> bool {
+        let mut event = Some(event);
+        loop {
+            {
+                let mut state = self.shared.lock();
+                if state.consumer_gone {
+                    return false;
+                }
+                if state.queue.len() < self.capacity {
+                    state.queue.extend(event.take());
+                    self.shared.readable.notify_one();
+                    return true;
+                }
+                let terminal = event.as_ref().is_some_and(JobStreamEvent::is_terminal);
+                match (self.policy, terminal) {
+                    (OverflowPolicy::Pause, _) => {}
+                    (OverflowPolicy::DropNewest, false) => {
+                        state.dropped += 1;
+                        return true;
+                    }
+                    _ => {
+                        state.queue.pop_front();
+                        state.queue.extend(event.take());
+                        state.dropped += 1;
+                        self.shared.readable.notify_one();
+                        return true;
+                    }
+                }
+            }
+            self.shared.writable.notified().await;
+        }
+    }
+}
+
+impl Drop for JobEventSender {
+    fn drop(&mut self) {
+        self.shared.lock().producer_done = true;
+        self.shared.readable.notify_one();
+    }
+}
+
+/// Consuming half of a job event channel
+pub struct JobEventReceiver {
+    shared: Arc<Shared>,
+}
+
+impl JobEventReceiver {
+    /// Take the next event, or `None` once the producer is done and the
+    /// buffer is drained
+    ///
+    /// Discarded updates are reported as a [`JobStreamEvent::Dropped`]
+    /// before the next buffered event.
+    pub async fn recv(&mut self) -> Option<JobStreamEvent> {
+        loop {
+            {
+                let mut state = self.shared.lock();
+                if state.dropped > 0 {
+                    let count = std::mem::take(&mut state.dropped);
+                    debug!(count, "Job stream client fell behind, updates dropped");
+                    return Some(JobStreamEvent::Dropped { count });
+                }
+                if let Some(event) = state.queue.pop_front() {
+                    self.shared.writable.notify_one();
+                    return Some(event);
+                }
+                if state.producer_done {
+                    return None;
+                }
+            }
+            self.shared.readable.notified().await;
+        }
+    }
+
+    /// Adapt the receiver into a stream for a streaming response
+    pub fn into_stream(self) -> impl Stream<Item = JobStreamEvent> + Send + 'static {
+        futures::stream::unfold(self, |mut receiver| async move {
+            receiver.recv().await.map(|event| (event, receiver))
+        })
+    }
+}
+
+impl Drop for JobEventReceiver {
+    fn drop(&mut self) {
+        self.shared.lock().consumer_gone = true;
+        self.shared.writable.notify_one();
+    }
+}
+
+/// Stream status changes of `job_id` until it finishes or the client leaves
+///
+/// A watcher task polls the worker queue and feeds the client's buffer; it
+/// stops as soon as the returned stream is dropped.
+pub fn watch_job(
+    worker_service: Arc<WorkerService>,
+    job_id: Uuid,
+    options: JobStreamOptions,
+) -> impl Stream<Item = JobStreamEvent> + Send + 'static {
+    let (sender, receiver) = job_stream_channel(options);
+    tokio::spawn(async move {
+        let mut last_seen: Option<(JobStatus, u32)> = None;
+        let mut interval = tokio::time::interval(JOB_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            let job = match worker_service.get_job(job_id).await {
+                Ok(Some(job)) => job,
+                Ok(None) => {
+                    let message = format!("Job {} not found", job_id);
+                    sender.send(JobStreamEvent::Error { message }).await;
+                    return;
+                }
+                Err(e) => {
+                    let message = format!("Failed to read job status: {}", e);
+                    sender.send(JobStreamEvent::Error { message }).await;
+                    return;
+                }
+            };
+
+            let seen = (job.status.clone(), job.retry_count);
+            if last_seen.as_ref() != Some(&seen) {
+                last_seen = Some(seen);
+                let event = JobStreamEvent::Status(JobStatusResponse::from(&job));
+                if !sender.send(event).await {
+                    return;
+                }
+            }
+
+            match job.status {
+                JobStatus::Completed => {
+                    let event = match worker_service.get_job_result(job_id).await {
+                        Ok(Some(result)) => JobStreamEvent::Result(result.into()),
+                        Ok(None) => JobStreamEvent::Error {
+                            message: format!("Result for job {} not found", job_id),
+                        },
+                        Err(e) => JobStreamEvent::Error {
+                            message: format!("Failed to read job result: {}", e),
+                        },
+                    };
+                    sender.send(event).await;
+                    return;
+                }
+                JobStatus::Failed | JobStatus::DeadLetter => {
+                    let event = JobStreamEvent::Failed {
+                        status: job.status.clone(),
+                        error: job.last_error.clone(),
+                    };
+                    sender.send(event).await;
+                    return;
+                }
+                JobStatus::Pending | JobStatus::Processing | JobStatus::Retrying => {}
+            }
+        }
+    });
+    receiver.into_stream()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    fn error(message: &str) -> JobStreamEvent {
+        JobStreamEvent::Error {
+            message: message.to_string(),
+        }
+    }
+
+    fn dropped(count: u64) -> JobStreamEvent {
+        JobStreamEvent::Dropped { count }
+    }
+
+    fn options(buffer: usize, overflow: OverflowPolicy) -> JobStreamOptions {
+        JobStreamOptions { buffer, overflow }
+    }
+
+    fn message(event: &JobStreamEvent) -> &str {
+        match event {
+            JobStreamEvent::Error { message } => message,
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_drop_oldest_keeps_latest_events() {
+        let (sender, receiver) = job_stream_channel(options(2, OverflowPolicy::DropOldest));
+        for name in ["a", "b", "c"] {
+            assert!(sender.send(error(name)).await);
+        }
+        drop(sender);
+
+        let events: Vec<_> = receiver.into_stream().collect().await;
+        // One overflow notice, then the two most recent events
+        assert!(matches!(events[0], JobStreamEvent::Dropped { count: 1 }));
+        let rest: Vec<&str> = events[1..].iter().map(message).collect();
+        assert_eq!(rest, vec!["b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_drop_newest_never_discards_terminal_event() {
+        let (sender, mut receiver) = job_stream_channel(options(1, OverflowPolicy::DropNewest));
+        assert!(sender.send(dropped(1)).await);
+        assert!(sender.send(dropped(2)).await);
+        assert!(sender.send(error("done")).await);
+        drop(sender);
+
+        assert!(matches!(
+            receiver.recv().await,
+            Some(JobStreamEvent::Dropped { count: 2 })
+        ));
+        assert_eq!(message(&receiver.recv().await.unwrap()), "done");
+        assert!(receiver.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failure_is_terminal() {
+        let (sender, mut receiver) = job_stream_channel(options(1, OverflowPolicy::DropNewest));
+        assert!(sender.send(dropped(1)).await);
+        let failed = JobStreamEvent::Failed {
+            status: JobStatus::DeadLetter,
+            error: Some("timed out".to_string()),
+        };
+        assert!(sender.send(failed).await);
+        drop(sender);
+
+        assert!(matches!(
+            receiver.recv().await,
+            Some(JobStreamEvent::Dropped { count: 1 })
+        ));
+        let json = serde_json::to_value(receiver.recv().await.unwrap()).unwrap();
+        assert_eq!(json["type"], "failed");
+        assert_eq!(json["error"], "timed out");
+        assert!(receiver.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pause_waits_for_consumer() {
+        let (sender, mut receiver) = job_stream_channel(options(1, OverflowPolicy::Pause));
+        assert!(sender.send(error("first")).await);
+
+        let producer = tokio::spawn(async move { sender.send(error("second")).await });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!producer.is_finished());
+
+        assert_eq!(message(&receiver.recv().await.unwrap()), "first");
+        assert!(producer.await.unwrap());
+        assert_eq!(message(&receiver.recv().await.unwrap()), "second");
+    }
+
+    #[tokio::test]
+    async fn test_send_fails_after_client_leaves() {
+        let (sender, receiver) = job_stream_channel(options(1, OverflowPolicy::Pause));
+        assert!(sender.send(error("first")).await);
+
+        let producer = tokio::spawn(async move { sender.send(error("second")).await });
+        drop(receiver);
+        assert!(!producer.await.unwrap());
+    }
+
+    #[test]
+    fn test_options_clamp_buffer() {
+        let parsed: JobStreamOptions =
+            serde_json::from_str(r#"{"buffer":100000,"overflow":"drop_oldest"}"#).unwrap();
+        assert_eq!(parsed.capacity(), MAX_JOB_STREAM_BUFFER);
+        assert_eq!(parsed.overflow, OverflowPolicy::DropOldest);
+        assert_eq!(
+            JobStreamOptions::default().capacity(),
+            DEFAULT_JOB_STREAM_BUFFER
+        );
+        assert_eq!(options(0, OverflowPolicy::Pause).capacity(), 1);
+    }
+}
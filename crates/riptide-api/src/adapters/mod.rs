@@ -3,6 +3,10 @@
 //! These adapters bridge the gap between riptide-api's current architecture
 //! and the port-based interfaces required by riptide-facade.
 
+#[cfg(feature = "workers")]
+pub mod job_stream_adapter;
 pub mod resource_pool_adapter;
+pub mod session_provider_adapter;
 
 pub use resource_pool_adapter::{ResourceManagerPoolAdapter, ResourceSlot};
+pub use session_provider_adapter::ApiSessionProvider;
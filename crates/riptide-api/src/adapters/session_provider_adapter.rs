@@ -0,0 +1,125 @@
+//! SessionManager adapter for the SessionProvider port
+//!
+//! Lets spiders crawl behind logins using the cookies a client stored in a
+//! browser session (`/api/sessions/{id}/cookies`). The API cannot replay a
+//! login itself, so renewal re-reads the session store and only succeeds
+//! when the client has refreshed the cookies since they were last handed out.
+
+use crate::sessions::manager::SessionManager;
+use async_trait::async_trait;
+use riptide_types::error::{Result as RiptideResult, RiptideError};
+use riptide_types::ports::session_provider::{AuthSession, SessionProvider};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Session provider serving the cookies of one stored API session
+pub struct ApiSessionProvider {
+    manager: Arc<SessionManager>,
+    session_id: String,
+    /// Cookies last handed out per domain, to detect stale renewals
+    served: Mutex<HashMap<String, BTreeMap<String, String>>>,
+}
+
+impl ApiSessionProvider {
+    /// Serve cookies from the session `session_id`
+    pub fn new(manager: Arc<SessionManager>, session_id: impl Into<String>) -> Self {
+        Self {
+            manager,
+            session_id: session_id.into(),
+            served: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Unexpired cookies stored for `domain`
+    async fn cookies(&self, domain: &str) -> RiptideResult<BTreeMap<String, String>> {
+        let cookies = self
+            .manager
+            .get_cookies_for_domain(&self.session_id, domain)
+            .await
+            .map_err(|e| RiptideError::Custom(format!("Failed to load session cookies: {}", e)))?;
+        Ok(cookies
+            .into_iter()
+            .filter(|cookie| !cookie.is_expired())
+            .map(|cookie| (cookie.name, cookie.value))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl SessionProvider for ApiSessionProvider {
+    async fn session(&self, domain: &str) -> RiptideResult<Option<AuthSession>> {
+        let cookies = self.cookies(domain).await?;
+        if cookies.is_empty() {
+            return Ok(None);
+        }
+        self.served
+            .lock()
+            .await
+            .insert(domain.to_string(), cookies.clone());
+        Ok(Some(AuthSession::with_cookies(cookies)))
+    }
+
+    async fn renew(&self, domain: &str) -> RiptideResult<Option<AuthSession>> {
+        let cookies = self.cookies(domain).await?;
+        let mut served = self.served.lock().await;
+        if cookies.is_empty() || served.get(domain) == Some(&cookies) {
+            return Ok(None);
+        }
+        served.insert(domain.to_string(), cookies.clone());
+        Ok(Some(AuthSession::with_cookies(cookies)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sessions::types::{Cookie, SessionConfig};
+    use std::path::PathBuf;
+
+    async fn provider(test_name: &str) -> (Arc<SessionManager>, ApiSessionProvider) {
+        let config = SessionConfig {
+            base_data_dir: PathBuf::from(format!("/tmp/riptide-test-sessions-{}", test_name)),
+            ..Default::default()
+        };
+        let manager = Arc::new(SessionManager::new(config).await.unwrap());
+        manager.get_or_create_session("crawl").await.unwrap();
+        let provider = ApiSessionProvider::new(manager.clone(), "crawl");
+        (manager, provider)
+    }
+
+    #[tokio::test]
+    async fn test_serves_stored_cookies() {
+        let (manager, provider) = provider("provider_serves").await;
+        assert!(provider.session("example.com").await.unwrap().is_none());
+
+        manager
+            .set_cookie(
+                "crawl",
+                "example.com",
+                Cookie::new("sid".to_string(), "abc".to_string()),
+            )
+            .await
+            .unwrap();
+        let session = provider.session("example.com").await.unwrap().unwrap();
+        assert_eq!(session.cookie_header().as_deref(), Some("sid=abc"));
+    }
+
+    #[tokio::test]
+    async fn test_renew_requires_fresh_cookies() {
+        let (manager, provider) = provider("provider_renew").await;
+        let set_sid = |value: &str| {
+            let manager = manager.clone();
+            let cookie = Cookie::new("sid".to_string(), value.to_string());
+            async move { manager.set_cookie("crawl", "example.com", cookie).await }
+        };
+
+        set_sid("old").await.unwrap();
+        provider.session("example.com").await.unwrap();
+        assert!(provider.renew("example.com").await.unwrap().is_none());
+
+        set_sid("new").await.unwrap();
+        let renewed = provider.renew("example.com").await.unwrap().unwrap();
+        assert_eq!(renewed.cookie_header().as_deref(), Some("sid=new"));
+    }
+}
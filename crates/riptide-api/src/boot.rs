@@ -0,0 +1,320 @@
+//! Startup dependency orchestration
+//!
+//! Redis, the WASM extractor component and the worker queue are often still
+//! coming up when the API starts. Each is initialized with exponential
+//! backoff (`boot.max_attempts`, `boot.initial_backoff_ms`,
+//! `boot.max_backoff_ms`). A dependency that is still unavailable afterwards
+//! fails startup. Only with `boot.allow_degraded` (off by default) may the
+//! dependencies that can be swapped in later start without Redis:
+//!
+//! - **cache**: served from memory and swapped to Redis by a background task
+//!   once it answers, checked every `boot.recovery_interval_secs`
+//! - **rate_limiter**: tenant quotas are enforced per instance in memory
+//!   until Redis answers, retried like the cache
+//!
+//! The WASM extractor and the worker queue cannot be swapped in at runtime,
+//! so they always fail startup when configured but unavailable.
+//!
+//! `GET /health/capabilities` reports the capabilities actually available.
+
+use crate::config::BootConfig;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// Cache backend (Redis)
+pub const CACHE: &str = "cache";
+/// WASM extractor component
+pub const WASM_EXTRACTOR: &str = "wasm_extractor";
+/// Redis-backed tenant rate limiter
+pub const RATE_LIMITER: &str = "rate_limiter";
+/// Redis-backed worker job queue
+pub const WORKER_QUEUE: &str = "worker_queue";
+
+/// Whether a dependency is in use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyState {
+    /// Initialized, at startup or by a later recovery
+    Ready,
+    /// Unavailable; the server runs without it
+    Degraded,
+}
+
+/// Startup outcome of one dependency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyStatus {
+    /// Current state
+    pub state: DependencyState,
+    /// Initialization attempts made at startup
+    pub attempts: u32,
+    /// Last initialization error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// When a degraded dependency became available
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recovered_at: Option<DateTime<Utc>>,
+}
+
+/// Startup outcome of every orchestrated dependency
+#[derive(Debug, Default)]
+pub struct BootState {
+    dependencies: Mutex<BTreeMap<&'static str, DependencyStatus>>,
+}
+
+impl BootState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the result of initializing `dependency` with
+    /// [`retry_with_backoff`]
+    ///
+    /// Returns the initialized value, `None` when the server should start
+    /// without the dependency, or the error when degrading is not allowed.
+    /// Dependencies that cannot be swapped in once they `recover` are never
+    /// degraded.
+    pub fn settle<T>(
+        &self,
+        dependency: &'static str,
+        config: &BootConfig,
+        (outcome, attempts): (anyhow::Result<T>, u32),
+        recovers: bool,
+    ) -> anyhow::Result<Option<T>> {
+        match outcome {
+            Ok(value) => {
+                self.insert(dependency, DependencyState::Ready, attempts, None);
+                Ok(Some(value))
+            }
+            Err(e) if config.allow_degraded && recovers => {
+                warn!(
+                    dependency,
+                    attempts,
+                    error = %e,
+                    "Dependency unavailable, starting in degraded mode"
+                );
+                self.insert(
+                    dependency,
+                    DependencyState::Degraded,
+                    attempts,
+                    Some(format!("{:#}", e)),
+                );
+                Ok(None)
+            }
+            Err(e) => Err(e.context(format!(
+                "{} unavailable after {} attempts",
+                dependency, attempts
+            ))),
+        }
+    }
+
+    /// Mark a degraded dependency as available again
+    pub fn record_recovered(&self, dependency: &'static str) {
+        if let Some(status) = self.lock().get_mut(dependency) {
+            status.state = DependencyState::Ready;
+            status.recovered_at = Some(Utc::now());
+        }
+    }
+
+    /// Whether `dependency` is currently degraded
+    pub fn is_degraded(&self, dependency: &str) -> bool {
+        self.lock()
+            .get(dependency)
+            .is_some_and(|status| status.state == DependencyState::Degraded)
+    }
+
+    /// Names of the dependencies currently degraded
+    pub fn degraded(&self) -> Vec<&'static str> {
+        self.lock()
+            .iter()
+            .filter(|(_, status)| status.state == DependencyState::Degraded)
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    /// Status of every orchestrated dependency
+    pub fn dependencies(&self) -> BTreeMap<&'static str, DependencyStatus> {
+        self.lock().clone()
+    }
+
+    fn insert(
+        &self,
+        dependency: &'static str,
+        state: DependencyState,
+        attempts: u32,
+        error: Option<String>,
+    ) {
+        self.lock().insert(
+            dependency,
+            DependencyStatus {
+                state,
+                attempts,
+                error,
+                recovered_at: None,
+            },
+        );
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<&'static str, DependencyStatus>> {
+        self.dependencies
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Delay after the `attempt`-th failed attempt (1-based)
+pub fn backoff(config: &BootConfig, attempt: u32) -> Duration {
+    let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+    Duration::from_millis(
+        config
+            .initial_backoff_ms
+            .saturating_mul(factor)
+            .min(config.max_backoff_ms),
+    )
+}
+
+/// Run `init` until it succeeds or `config.max_attempts` is reached,
+/// backing off exponentially between attempts
+///
+/// Returns the last outcome with the number of attempts made.
+pub async fn retry_with_backoff<T, F, Fut>(
+    dependency: &str,
+    config: &BootConfig,
+    mut init: F,
+) -> (anyhow::Result<T>, u32)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = anyhow::Result<T>>,
+{
+    let max_attempts = config.max_attempts.max(1);
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match init().await {
+            Ok(value) => return (Ok(value), attempt),
+            Err(e) if attempt >= max_attempts => return (Err(e), attempt),
+            Err(e) => {
+                let delay = backoff(config, attempt);
+                warn!(
+                    dependency,
+                    attempt,
+                    max_attempts,
+                    retry_in_ms = delay.as_millis() as u64,
+                    error = %e,
+                    "Dependency unavailable, retrying"
+                );
+                tokio::time::sleep(delay).await;
+            }
+        }
+    }
+}
+
+/// Retry a degraded dependency every `interval` until `recover` succeeds,
+/// then mark it ready
+///
+/// `recover` initializes the dependency and swaps it in.
+pub fn spawn_recovery<F, Fut>(
+    boot: Arc<BootState>,
+    dependency: &'static str,
+    interval: Duration,
+    mut recover: F,
+) -> JoinHandle<()>
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = anyhow::Result<()>> + Send,
+{
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(interval).await;
+            match recover().await {
+                Ok(()) => {
+                    boot.record_recovered(dependency);
+                    info!(dependency, "Dependency recovered, leaving degraded mode");
+                    return;
+                }
+                Err(e) => debug!(dependency, error = %e, "Dependency still unavailable"),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn config() -> BootConfig {
+        BootConfig {
+            max_attempts: 3,
+            initial_backoff_ms: 1,
+            max_backoff_ms: 2,
+            allow_degraded: true,
+            recovery_interval_secs: 1,
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let config = BootConfig::default();
+        assert_eq!(backoff(&config, 1), Duration::from_millis(500));
+        assert_eq!(backoff(&config, 2), Duration::from_millis(1_000));
+        assert_eq!(backoff(&config, 4), Duration::from_millis(4_000));
+        assert_eq!(backoff(&config, 6), Duration::from_millis(8_000));
+        assert_eq!(backoff(&config, 100), Duration::from_millis(8_000));
+    }
+
+    #[tokio::test]
+    async fn test_retry_until_success() {
+        let calls = AtomicU32::new(0);
+        let (outcome, attempts) = retry_with_backoff(CACHE, &config(), || async {
+            match calls.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(anyhow::anyhow!("connection refused")),
+                _ => Ok("connected"),
+            }
+        })
+        .await;
+        assert_eq!(outcome.unwrap(), "connected");
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn test_settle_degrades_or_fails() {
+        let boot = BootState::new();
+        let failing = || async { Err::<(), _>(anyhow::anyhow!("connection refused")) };
+
+        let outcome = retry_with_backoff(CACHE, &config(), failing).await;
+        assert!(boot
+            .settle(CACHE, &config(), outcome, true)
+            .unwrap()
+            .is_none());
+        assert_eq!(boot.degraded(), vec![CACHE]);
+        let status = &boot.dependencies()[CACHE];
+        assert_eq!(status.attempts, 3);
+        assert_eq!(status.error.as_deref(), Some("connection refused"));
+
+        boot.record_recovered(CACHE);
+        assert!(!boot.is_degraded(CACHE));
+        assert!(boot.dependencies()[CACHE].recovered_at.is_some());
+
+        // Dependencies that cannot recover fail even when degrading is allowed
+        let outcome = retry_with_backoff(WORKER_QUEUE, &config(), failing).await;
+        let err = boot
+            .settle(WORKER_QUEUE, &config(), outcome, false)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("worker_queue unavailable after 3 attempts"));
+
+        let strict = BootConfig {
+            allow_degraded: false,
+            ..config()
+        };
+        let outcome = retry_with_backoff(CACHE, &strict, failing).await;
+        assert!(boot.settle(CACHE, &strict, outcome, true).is_err());
+    }
+}
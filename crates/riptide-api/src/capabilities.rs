@@ -3,7 +3,9 @@
 //! This module provides runtime detection of system capabilities to inform users
 //! about the current deployment configuration (minimal, enhanced, or distributed).
 
+use crate::boot::{self, BootState, DependencyStatus};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// System capabilities representing the current deployment configuration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -25,6 +27,14 @@ pub struct SystemCapabilities {
 
     /// Deployment mode: "minimal", "enhanced", or "distributed"
     pub deployment_mode: String,
+
+    /// Dependencies the server is currently running without
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub degraded: Vec<String>,
+
+    /// Startup outcome of each dependency initialized with retries
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, DependencyStatus>,
 }
 
 impl SystemCapabilities {
@@ -79,6 +89,31 @@ impl SystemCapabilities {
             persistent_cache: has_redis,
             session_persistence: has_redis,
             deployment_mode: deployment_mode.to_string(),
+            degraded: Vec::new(),
+            dependencies: BTreeMap::new(),
+        }
+    }
+
+    /// Capabilities actually available given the dependencies that are
+    /// degraded in `boot`
+    ///
+    /// A degraded cache runs in memory, which may lower the deployment mode.
+    pub fn with_boot_state(&self, boot: &BootState) -> Self {
+        let degraded = boot.degraded();
+        let cache_backend = if degraded.contains(&boot::CACHE) {
+            "memory"
+        } else {
+            &self.cache_backend
+        };
+
+        Self {
+            degraded: degraded.iter().map(ToString::to_string).collect(),
+            dependencies: boot
+                .dependencies()
+                .into_iter()
+                .map(|(name, status)| (name.to_string(), status))
+                .collect(),
+            ..Self::detect(cache_backend, self.async_jobs)
         }
     }
 }
@@ -135,6 +170,34 @@ mod tests {
         assert_eq!(caps.deployment_mode, "invalid");
     }
 
+    #[test]
+    fn test_degraded_dependencies_lower_capabilities() {
+        let config = crate::config::BootConfig {
+            allow_degraded: true,
+            ..Default::default()
+        };
+        let boot = BootState::new();
+        let caps = SystemCapabilities::detect("redis", false);
+        let unavailable = || (Err::<(), _>(anyhow::anyhow!("connection refused")), 5);
+
+        boot.settle(boot::CACHE, &config, unavailable(), true)
+            .unwrap();
+        boot.settle(boot::RATE_LIMITER, &config, unavailable(), true)
+            .unwrap();
+        let degraded = caps.with_boot_state(&boot);
+        assert_eq!(degraded.cache_backend, "memory");
+        assert!(!degraded.persistent_cache);
+        assert_eq!(degraded.deployment_mode, "minimal");
+        assert_eq!(degraded.degraded, vec!["cache", "rate_limiter"]);
+        assert_eq!(degraded.dependencies.len(), 2);
+
+        boot.record_recovered(boot::CACHE);
+        let recovered = caps.with_boot_state(&boot);
+        assert_eq!(recovered.cache_backend, "redis");
+        assert_eq!(recovered.deployment_mode, "enhanced");
+        assert_eq!(recovered.degraded, vec!["rate_limiter"]);
+    }
+
     #[test]
     fn test_case_insensitive_backend() {
         let caps = SystemCapabilities::detect("REDIS", false);
@@ -0,0 +1,262 @@
+//! Differential crawling support
+//!
+//! Keeps a [`ContentFingerprint`] per URL in the shared [`FingerprintStore`]
+//! and the start time of every crawl in the cache backend, so `/crawl`
+//! requests with `changed_since` can revalidate URLs with conditional
+//! requests and skip unchanged documents. Spider refresh crawls and cache
+//! revalidation use the same fingerprints.
+//!
+//! Each fresh extraction of a URL is also kept as a trimmed snapshot and
+//! diffed against the previous one; the latest non-empty
+//! [`ExtractionDiff`] is stored for `GET /api/v1/diff`.
+
+use crate::context::ApplicationContext;
+use crate::errors::{ApiError, ApiResult};
+use chrono::{DateTime, Utc};
+use riptide_types::diff::{self, ExtractionDiff};
+use riptide_types::ports::CacheStorage;
+use riptide_types::{ChangedSince, ContentFingerprint, ExtractedDoc, FingerprintStore};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Stores content fingerprints, document snapshots and crawl ids for
+/// differential crawls
+///
+/// Cache failures never fail a crawl: a missing fingerprint only means the
+/// URL is treated as changed, and a missing snapshot that no diff is made.
+#[derive(Clone)]
+pub struct ChangeTracker {
+    cache: Arc<dyn CacheStorage>,
+    fingerprints: FingerprintStore,
+    ttl: Duration,
+}
+
+impl ChangeTracker {
+    /// Create a tracker storing entries for `ttl`
+    pub fn new(cache: Arc<dyn CacheStorage>, ttl: Duration) -> Self {
+        Self {
+            fingerprints: FingerprintStore::new(cache.clone()).with_ttl(ttl),
+            cache,
+            ttl,
+        }
+    }
+
+    /// Create a tracker over the application cache
+    pub fn from_context(state: &ApplicationContext) -> Self {
+        Self::new(
+            state.cache.clone(),
+            Duration::from_secs(state.config.change_tracking_ttl),
+        )
+    }
+
+    /// The shared fingerprint store, for spider crawls
+    pub fn fingerprints(&self) -> &FingerprintStore {
+        &self.fingerprints
+    }
+
+    /// Load the stored fingerprint of a URL
+    pub async fn fingerprint(&self, url: &str) -> Option<ContentFingerprint> {
+        self.fingerprints
+            .get(url)
+            .await
+            .map_err(|e| warn!(url = %url, error = %e, "Failed to load content fingerprint"))
+            .ok()
+            .flatten()
+    }
+
+    /// Store the fingerprint of a URL
+    pub async fn record(&self, url: &str, fingerprint: &ContentFingerprint) {
+        if let Err(e) = self.fingerprints.save(url, fingerprint).await {
+            warn!(url = %url, error = %e, "Failed to store content fingerprint");
+        }
+    }
+
+    /// Diff a fresh extraction of a URL against the previous one
+    ///
+    /// Stores the new snapshot, and the diff when the document changed.
+    /// Returns `None` for the first extraction and for unchanged documents.
+    pub async fn record_document(&self, url: &str, doc: &ExtractedDoc) -> Option<ExtractionDiff> {
+        let snapshot = diff::snapshot(doc);
+        let previous: Option<ExtractedDoc> = self
+            .load(&snapshot_key(url), url, "document snapshot")
+            .await;
+        self.store(&snapshot_key(url), &snapshot, url, "document snapshot")
+            .await;
+
+        let diff = ExtractionDiff::between(&previous?, &snapshot);
+        if diff.is_empty() {
+            return None;
+        }
+        self.store(&diff_key(url), &diff, url, "extraction diff")
+            .await;
+        Some(diff)
+    }
+
+    /// Load the last change stored for a URL
+    pub async fn latest_diff(&self, url: &str) -> Option<ExtractionDiff> {
+        self.load(&diff_key(url), url, "extraction diff").await
+    }
+
+    async fn load<T: DeserializeOwned>(&self, key: &str, url: &str, what: &str) -> Option<T> {
+        match self.cache.get(key).await {
+            Ok(Some(bytes)) => serde_json::from_slice(&bytes)
+                .map_err(|e| warn!(url = %url, error = %e, "Discarding unreadable {}", what))
+                .ok(),
+            Ok(None) => None,
+            Err(e) => {
+                warn!(url = %url, error = %e, "Failed to load {}", what);
+                None
+            }
+        }
+    }
+
+    async fn store<T: Serialize>(&self, key: &str, value: &T, url: &str, what: &str) {
+        let Ok(bytes) = serde_json::to_vec(value) else {
+            return;
+        };
+        if let Err(e) = self.cache.set(key, &bytes, Some(self.ttl)).await {
+            warn!(url = %url, error = %e, "Failed to store {}", what);
+        }
+    }
+
+    /// Register a new crawl and return its id
+    pub async fn start_crawl(&self) -> String {
+        let crawl_id = uuid::Uuid::new_v4().to_string();
+        let started_at = Utc::now().to_rfc3339();
+        if let Err(e) = self
+            .cache
+            .set(&crawl_key(&crawl_id), started_at.as_bytes(), Some(self.ttl))
+            .await
+        {
+            warn!(crawl_id = %crawl_id, error = %e, "Failed to register crawl id");
+        }
+        debug!(crawl_id = %crawl_id, "Crawl registered");
+        crawl_id
+    }
+
+    /// Resolve a `changed_since` reference to an instant
+    ///
+    /// # Errors
+    ///
+    /// Returns `ApiError::InvalidParameter` for unknown or expired crawl ids.
+    pub async fn resolve(&self, since: &ChangedSince) -> ApiResult<DateTime<Utc>> {
+        let crawl_id = match since {
+            ChangedSince::Timestamp(ts) => return Ok(*ts),
+            ChangedSince::CrawlId(id) => id,
+        };
+        let unknown = || ApiError::InvalidParameter {
+            parameter: "changed_since".to_string(),
+            message: format!("unknown or expired crawl id '{}'", crawl_id),
+        };
+
+        let bytes = self
+            .cache
+            .get(&crawl_key(crawl_id))
+            .await
+            .map_err(|e| ApiError::cache(format!("Failed to resolve crawl id: {}", e)))?
+            .ok_or_else(unknown)?;
+        std::str::from_utf8(&bytes)
+            .ok()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|ts| ts.with_timezone(&Utc))
+            .ok_or_else(unknown)
+    }
+}
+
+fn snapshot_key(url: &str) -> String {
+    format!(
+        "riptide:v1:snapshot:{}",
+        ContentFingerprint::hash_content(url.as_bytes())
+    )
+}
+
+fn diff_key(url: &str) -> String {
+    format!(
+        "riptide:v1:diff:{}",
+        ContentFingerprint::hash_content(url.as_bytes())
+    )
+}
+
+fn crawl_key(crawl_id: &str) -> String {
+    format!("riptide:v1:crawl:{}", crawl_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::ports::InMemoryCache;
+
+    fn tracker() -> ChangeTracker {
+        ChangeTracker::new(Arc::new(InMemoryCache::new()), Duration::from_secs(60))
+    }
+
+    #[tokio::test]
+    async fn test_fingerprint_roundtrip() {
+        let tracker = tracker();
+        assert!(tracker.fingerprint("https://example.com").await.is_none());
+
+        let fingerprint = ContentFingerprint::observe(None, b"body", None, None, Utc::now());
+        tracker.record("https://example.com", &fingerprint).await;
+        assert_eq!(
+            tracker.fingerprint("https://example.com").await,
+            Some(fingerprint)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_document_stores_changes() {
+        let tracker = tracker();
+        let url = "https://example.com/pricing";
+        let page = |text: &str| ExtractedDoc {
+            url: url.to_string(),
+            text: text.to_string(),
+            html: Some("<html>...</html>".to_string()),
+            ..Default::default()
+        };
+
+        assert!(tracker
+            .record_document(url, &page("Basic $10"))
+            .await
+            .is_none());
+        assert!(tracker
+            .record_document(url, &page("Basic $10"))
+            .await
+            .is_none());
+        assert!(tracker.latest_diff(url).await.is_none());
+
+        let diff = tracker
+            .record_document(url, &page("Basic $12"))
+            .await
+            .unwrap();
+        assert_eq!(diff.text_changes.len(), 1);
+        assert_eq!(tracker.latest_diff(url).await, Some(diff.clone()));
+
+        // An unchanged re-crawl keeps the last change
+        assert!(tracker
+            .record_document(url, &page("Basic $12"))
+            .await
+            .is_none());
+        assert_eq!(tracker.latest_diff(url).await, Some(diff));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_crawl_id() {
+        let tracker = tracker();
+        let before = Utc::now();
+        let crawl_id = tracker.start_crawl().await;
+
+        let resolved = tracker
+            .resolve(&ChangedSince::CrawlId(crawl_id))
+            .await
+            .unwrap();
+        assert!(resolved >= before - chrono::Duration::seconds(1));
+
+        let unknown = tracker
+            .resolve(&ChangedSince::CrawlId("missing".to_string()))
+            .await;
+        assert!(matches!(unknown, Err(ApiError::InvalidParameter { .. })));
+    }
+}
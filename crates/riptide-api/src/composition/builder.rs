@@ -15,7 +15,7 @@
 //!     .build_for_testing();
 //! ```
 
-use super::{DiConfig, Event, User};
+use super::{DiConfig, Event, Subsystems, User};
 
 #[cfg(not(feature = "postgres"))]
 use super::{stubs::*, ApplicationContext};
@@ -23,10 +23,13 @@ use super::{stubs::*, ApplicationContext};
 #[cfg(not(feature = "postgres"))]
 use anyhow::Result;
 
+use riptide_intelligence::LlmProvider;
 use riptide_types::ports::infrastructure::{
     Clock, DeterministicEntropy, Entropy, FakeClock, SystemClock, SystemEntropy,
 };
-use riptide_types::ports::{CircuitBreaker, CircuitBreakerConfig};
+use riptide_types::ports::{
+    CheckpointStore, CircuitBreaker, CircuitBreakerConfig, PdfProcessor, StreamProcessor,
+};
 use riptide_types::{EventBus, IdempotencyStore, Repository};
 
 #[cfg(not(feature = "postgres"))]
@@ -55,6 +58,7 @@ pub struct ApplicationContextBuilder {
     event_bus: Option<Arc<dyn EventBus>>,
     idempotency_store: Option<Arc<dyn IdempotencyStore>>,
     circuit_breaker: Option<Arc<dyn CircuitBreaker>>,
+    subsystems: Subsystems,
     config: DiConfig,
 }
 
@@ -69,6 +73,7 @@ impl ApplicationContextBuilder {
             event_bus: None,
             idempotency_store: None,
             circuit_breaker: None,
+            subsystems: Subsystems::default(),
             config: DiConfig::for_testing(),
         }
     }
@@ -219,6 +224,62 @@ impl ApplicationContextBuilder {
         self
     }
 
+    // ========================================================================
+    // Optional Subsystems
+    // ========================================================================
+
+    /// Set the PDF processor used when the `pdf` feature is enabled
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder.with_feature("pdf", true).with_pdf_processor(processor);
+    /// ```
+    pub fn with_pdf_processor(mut self, processor: Arc<dyn PdfProcessor>) -> Self {
+        self.subsystems.pdf = Some(processor);
+        self
+    }
+
+    /// Set the checkpoint store used when the `persistence` feature is enabled
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder
+    ///     .with_feature("persistence", true)
+    ///     .with_checkpoint_store(Arc::new(InMemoryCheckpointStore::new()));
+    /// ```
+    pub fn with_checkpoint_store(mut self, store: Arc<dyn CheckpointStore>) -> Self {
+        self.subsystems.persistence = Some(store);
+        self
+    }
+
+    /// Set the LLM provider used when the `intelligence` feature is enabled
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder
+    ///     .with_feature("intelligence", true)
+    ///     .with_llm_provider(Arc::new(MockLlmProvider::new()));
+    /// ```
+    pub fn with_llm_provider(mut self, provider: Arc<dyn LlmProvider>) -> Self {
+        self.subsystems.intelligence = Some(provider);
+        self
+    }
+
+    /// Set the stream processor used when the `streaming` feature is enabled
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder.with_feature("streaming", true).with_stream_processor(processor);
+    /// ```
+    pub fn with_stream_processor(mut self, processor: Arc<dyn StreamProcessor>) -> Self {
+        self.subsystems.streaming = Some(processor);
+        self
+    }
+
     // ========================================================================
     // Configuration
     // ========================================================================
@@ -248,6 +309,9 @@ impl ApplicationContextBuilder {
             "browser" => self.config.features.enable_browser = enabled,
             "pdf" => self.config.features.enable_pdf = enabled,
             "search" => self.config.features.enable_search = enabled,
+            "persistence" => self.config.features.enable_persistence = enabled,
+            "intelligence" => self.config.features.enable_intelligence = enabled,
+            "streaming" => self.config.features.enable_streaming = enabled,
             _ => {}
         }
         self
@@ -260,6 +324,8 @@ impl ApplicationContextBuilder {
     /// Build ApplicationContext for testing with in-memory defaults
     ///
     /// Any dependencies not explicitly set will use in-memory implementations.
+    /// Optional subsystems are not validated; enabled subsystems without an
+    /// implementation are reported as not wired.
     ///
     /// Note: This builder is only available without the `postgres` feature.
     /// With `postgres` enabled, use `ApplicationContext::for_testing()` instead.
@@ -315,6 +381,7 @@ impl ApplicationContextBuilder {
             event_bus,
             idempotency_store,
             circuit_breaker,
+            subsystems: self.subsystems,
             config: self.config,
         }
     }
@@ -331,10 +398,12 @@ impl ApplicationContextBuilder {
     /// Returns error if:
     /// - Required dependencies are missing
     /// - Configuration validation fails
+    /// - An enabled subsystem has no implementation
     #[cfg(not(feature = "postgres"))]
     pub fn build(self) -> Result<ApplicationContext> {
         // Validate configuration
         self.config.validate()?;
+        self.subsystems.validate(&self.config.features)?;
 
         // Ensure all required dependencies are set
         if self.clock.is_none() {
@@ -375,6 +444,7 @@ impl ApplicationContextBuilder {
             event_bus: self.event_bus.unwrap(),
             idempotency_store: self.idempotency_store.unwrap(),
             circuit_breaker,
+            subsystems: self.subsystems,
             config: self.config,
         })
     }
@@ -443,6 +513,36 @@ mod tests {
         assert!(!ctx.is_feature_enabled("search"));
     }
 
+    #[test]
+    fn test_builder_wires_subsystems() {
+        use riptide_intelligence::MockLlmProvider;
+
+        let ctx = ApplicationContextBuilder::new()
+            .with_feature("intelligence", true)
+            .with_feature("streaming", true)
+            .with_llm_provider(Arc::new(MockLlmProvider::new()))
+            .build_for_testing();
+
+        assert!(ctx.llm_provider().is_some());
+        assert!(ctx.stream_processor().is_none());
+        let capabilities = ctx.capabilities();
+        assert!(capabilities.subsystems["intelligence"].is_active());
+        assert!(capabilities.subsystems["streaming"].enabled);
+        assert!(!capabilities.subsystems["streaming"].wired);
+        assert!(!capabilities.features["browser"]);
+
+        let result = ApplicationContextBuilder::new()
+            .with_fake_clock()
+            .with_deterministic_entropy(42)
+            .with_user_repository(Arc::new(InMemoryRepository::<User>::new()))
+            .with_event_repository(Arc::new(InMemoryRepository::<Event>::new()))
+            .with_event_bus(Arc::new(InMemoryEventBus::default()))
+            .with_idempotency_store(Arc::new(InMemoryIdempotencyStore::new()))
+            .with_feature("streaming", true)
+            .build();
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_builder_validation_fails_without_required_deps() {
         let result = ApplicationContextBuilder::new().build();
@@ -29,6 +29,7 @@
 //! enable_browser = true
 //! enable_pdf = true
 //! enable_search = false
+//! enable_intelligence = true
 //! ```
 
 use anyhow::{Context, Result};
@@ -167,6 +168,18 @@ pub struct FeatureFlags {
     /// Enable search provider integration
     #[serde(default)]
     pub enable_search: bool,
+
+    /// Enable durable crawl state (requires an injected checkpoint store)
+    #[serde(default)]
+    pub enable_persistence: bool,
+
+    /// Enable LLM-backed intelligence (requires an injected LLM provider)
+    #[serde(default)]
+    pub enable_intelligence: bool,
+
+    /// Enable result streaming (requires an injected stream processor)
+    #[serde(default)]
+    pub enable_streaming: bool,
 }
 
 impl DiConfig {
@@ -189,16 +202,22 @@ impl DiConfig {
     /// - `REDIS_URL` - Redis connection URL
     /// - `REDIS_POOL_SIZE` - Redis pool size
     /// - `REDIS_DEFAULT_TTL_SECS` - Default TTL
-    /// - `FEATURE_BROWSER` - Enable browser (true/false)
-    /// - `FEATURE_PDF` - Enable PDF (true/false)
-    /// - `FEATURE_SEARCH` - Enable search (true/false)
+    /// - `RIPTIDE_FEATURE_BROWSER` - Enable browser (true/false)
+    /// - `RIPTIDE_FEATURE_PDF` - Enable PDF (true/false)
+    /// - `RIPTIDE_FEATURE_SEARCH` - Enable search (true/false)
+    /// - `RIPTIDE_FEATURE_PERSISTENCE` - Enable persistence (true/false)
+    /// - `RIPTIDE_FEATURE_INTELLIGENCE` - Enable intelligence (true/false)
+    /// - `RIPTIDE_FEATURE_STREAMING` - Enable streaming (true/false)
+    ///
+    /// The unprefixed `FEATURE_*` names are still read, with a deprecation
+    /// warning, when the prefixed variable is not set.
     ///
     /// # Example
     ///
     /// ```bash
     /// export DATABASE_URL="postgresql://localhost:5432/riptide"
     /// export REDIS_URL="redis://localhost:6379"
-    /// export FEATURE_BROWSER="true"
+    /// export RIPTIDE_FEATURE_BROWSER="true"
     /// ```
     pub fn from_env() -> Result<Self> {
         let mut config = Self::default();
@@ -228,14 +247,23 @@ impl DiConfig {
         }
 
         // Feature flags
-        if let Ok(val) = std::env::var("FEATURE_BROWSER") {
-            config.features.enable_browser = val.to_lowercase() == "true";
+        if let Some(enabled) = feature_flag("BROWSER") {
+            config.features.enable_browser = enabled;
+        }
+        if let Some(enabled) = feature_flag("PDF") {
+            config.features.enable_pdf = enabled;
+        }
+        if let Some(enabled) = feature_flag("SEARCH") {
+            config.features.enable_search = enabled;
         }
-        if let Ok(val) = std::env::var("FEATURE_PDF") {
-            config.features.enable_pdf = val.to_lowercase() == "true";
+        if let Some(enabled) = feature_flag("PERSISTENCE") {
+            config.features.enable_persistence = enabled;
         }
-        if let Ok(val) = std::env::var("FEATURE_SEARCH") {
-            config.features.enable_search = val.to_lowercase() == "true";
+        if let Some(enabled) = feature_flag("INTELLIGENCE") {
+            config.features.enable_intelligence = enabled;
+        }
+        if let Some(enabled) = feature_flag("STREAMING") {
+            config.features.enable_streaming = enabled;
         }
 
         Ok(config)
@@ -334,6 +362,25 @@ impl Default for DiConfig {
     }
 }
 
+/// Read the `RIPTIDE_FEATURE_<name>` flag, falling back to the deprecated
+/// `FEATURE_<name>` variable
+fn feature_flag(name: &str) -> Option<bool> {
+    let value = match std::env::var(format!("RIPTIDE_FEATURE_{}", name)) {
+        Ok(value) => value,
+        Err(_) => {
+            let legacy = format!("FEATURE_{}", name);
+            let value = std::env::var(&legacy).ok()?;
+            tracing::warn!(
+                "{} is deprecated, use RIPTIDE_FEATURE_{} instead",
+                legacy,
+                name
+            );
+            value
+        }
+    };
+    Some(value.to_lowercase() == "true")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -418,6 +465,20 @@ mod tests {
         assert!(config.features.enable_browser);
         assert!(!config.features.enable_pdf);
         assert!(config.features.enable_search);
+        assert!(!config.features.enable_streaming);
+    }
+
+    #[test]
+    fn test_feature_flag_accepts_legacy_name() {
+        std::env::set_var("FEATURE_DI_CONFIG_TEST", "true");
+        assert_eq!(feature_flag("DI_CONFIG_TEST"), Some(true));
+
+        std::env::set_var("RIPTIDE_FEATURE_DI_CONFIG_TEST", "false");
+        assert_eq!(feature_flag("DI_CONFIG_TEST"), Some(false));
+
+        std::env::remove_var("FEATURE_DI_CONFIG_TEST");
+        std::env::remove_var("RIPTIDE_FEATURE_DI_CONFIG_TEST");
+        assert_eq!(feature_flag("DI_CONFIG_TEST"), None);
     }
 
     #[test]
@@ -8,6 +8,8 @@
 //! - **ApplicationContext**: Main DI container holding all wired dependencies
 //! - **DiConfig**: Configuration for database connections, Redis, and feature flags
 //! - **ApplicationContextBuilder**: Fluent API for test overrides
+//! - **Subsystems**: Optional PDF, persistence, intelligence and streaming
+//!   implementations, switched on by feature flags and injected at runtime
 //!
 //! # Example - Production
 //!
@@ -34,10 +36,14 @@
 
 pub mod builder;
 pub mod config;
+pub mod subsystems;
 
 use anyhow::Result;
+use riptide_intelligence::LlmProvider;
 use riptide_types::ports::infrastructure::{Clock, Entropy, SystemClock, SystemEntropy};
-use riptide_types::ports::{CircuitBreaker, CircuitBreakerConfig};
+use riptide_types::ports::{
+    CheckpointStore, CircuitBreaker, CircuitBreakerConfig, PdfProcessor, StreamProcessor,
+};
 use riptide_types::{EventBus, IdempotencyStore, Repository, TransactionManager};
 
 use serde::{Deserialize, Serialize};
@@ -64,6 +70,7 @@ use riptide_persistence::adapters::{
 
 pub use builder::ApplicationContextBuilder;
 pub use config::DiConfig;
+pub use subsystems::{capabilities_router, Capabilities, SubsystemStatus, Subsystems};
 
 /// Application Context - Composition Root for Dependency Injection
 ///
@@ -127,6 +134,11 @@ pub struct ApplicationContext {
     /// Circuit breaker for fault tolerance
     pub circuit_breaker: Arc<dyn CircuitBreaker>,
 
+    // === Optional Subsystems ===
+    /// Injected subsystem implementations; use the accessors, which also
+    /// check that the subsystem is enabled
+    pub subsystems: Subsystems,
+
     // === Configuration ===
     /// Feature flags and runtime configuration
     pub config: DiConfig,
@@ -155,6 +167,7 @@ impl ApplicationContext {
     /// - Database connection fails
     /// - Redis connection fails
     /// - Configuration validation fails
+    /// - An optional subsystem is enabled (see [`new_with_subsystems`](Self::new_with_subsystems))
     ///
     /// # Example
     ///
@@ -162,12 +175,31 @@ impl ApplicationContext {
     /// let config = DiConfig::from_env()?;
     /// let ctx = ApplicationContext::new(&config).await?;
     /// ```
-    #[instrument(skip(config))]
     pub async fn new(config: &DiConfig) -> Result<Self> {
+        Self::new_with_subsystems(config, Subsystems::default()).await
+    }
+
+    /// Create production ApplicationContext with injected optional subsystems
+    ///
+    /// Every subsystem enabled in `config.features` must have an
+    /// implementation in `subsystems`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let subsystems = Subsystems {
+    ///     intelligence: Some(registry.default_provider()?),
+    ///     ..Default::default()
+    /// };
+    /// let ctx = ApplicationContext::new_with_subsystems(&config, subsystems).await?;
+    /// ```
+    #[instrument(skip(config, subsystems))]
+    pub async fn new_with_subsystems(config: &DiConfig, subsystems: Subsystems) -> Result<Self> {
         info!("Initializing ApplicationContext");
 
         // Validate configuration
         config.validate()?;
+        subsystems.validate(&config.features)?;
 
         // === Wire System Ports ===
         let clock = Arc::new(SystemClock) as Arc<dyn Clock>;
@@ -229,6 +261,7 @@ impl ApplicationContext {
                 event_bus,
                 idempotency_store,
                 circuit_breaker,
+                subsystems,
                 config: config.clone(),
             });
         }
@@ -277,6 +310,7 @@ impl ApplicationContext {
                 event_bus,
                 idempotency_store,
                 circuit_breaker,
+                subsystems,
                 config: config.clone(),
             })
         }
@@ -384,6 +418,7 @@ impl ApplicationContext {
                 event_bus,
                 idempotency_store,
                 circuit_breaker,
+                subsystems: Subsystems::default(),
                 config,
             }
         }
@@ -417,9 +452,49 @@ impl ApplicationContext {
             "browser" => self.config.features.enable_browser,
             "pdf" => self.config.features.enable_pdf,
             "search" => self.config.features.enable_search,
+            "persistence" => self.config.features.enable_persistence,
+            "intelligence" => self.config.features.enable_intelligence,
+            "streaming" => self.config.features.enable_streaming,
             _ => false,
         }
     }
+
+    /// PDF processor, when the pdf subsystem is enabled and wired
+    pub fn pdf_processor(&self) -> Option<&Arc<dyn PdfProcessor>> {
+        self.subsystems
+            .pdf
+            .as_ref()
+            .filter(|_| self.config.features.enable_pdf)
+    }
+
+    /// Checkpoint store, when the persistence subsystem is enabled and wired
+    pub fn checkpoint_store(&self) -> Option<&Arc<dyn CheckpointStore>> {
+        self.subsystems
+            .persistence
+            .as_ref()
+            .filter(|_| self.config.features.enable_persistence)
+    }
+
+    /// LLM provider, when the intelligence subsystem is enabled and wired
+    pub fn llm_provider(&self) -> Option<&Arc<dyn LlmProvider>> {
+        self.subsystems
+            .intelligence
+            .as_ref()
+            .filter(|_| self.config.features.enable_intelligence)
+    }
+
+    /// Stream processor, when the streaming subsystem is enabled and wired
+    pub fn stream_processor(&self) -> Option<&Arc<dyn StreamProcessor>> {
+        self.subsystems
+            .streaming
+            .as_ref()
+            .filter(|_| self.config.features.enable_streaming)
+    }
+
+    /// What is enabled, as reported by `GET /capabilities`
+    pub fn capabilities(&self) -> Capabilities {
+        self.subsystems.capabilities(&self.config.features)
+    }
 }
 
 // ============================================================================
@@ -478,6 +553,7 @@ mod tests {
         assert!(!ctx.is_feature_enabled("browser"));
         assert!(!ctx.is_feature_enabled("pdf"));
         assert!(!ctx.is_feature_enabled("search"));
+        assert!(!ctx.is_feature_enabled("streaming"));
         assert!(!ctx.is_feature_enabled("unknown"));
     }
 
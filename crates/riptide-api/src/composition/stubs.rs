@@ -9,6 +9,7 @@ use riptide_types::{
     RepositoryFilter, Result as RiptideResult, RiptideError, SubscriptionId, Transaction,
     TransactionManager,
 };
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
@@ -35,6 +36,72 @@ impl<T> Default for InMemoryRepository<T> {
     }
 }
 
+impl<T> InMemoryRepository<T>
+where
+    T: Clone + serde::Serialize,
+{
+    /// Entities matching the filter's fields, sorted, without pagination
+    fn matching(&self, filter: &RepositoryFilter) -> RiptideResult<Vec<T>> {
+        let data = self.data.lock().unwrap();
+        let mut rows = Vec::new();
+        for entity in data.values() {
+            let json = serde_json::to_value(entity)
+                .map_err(|e| RiptideError::Custom(format!("Failed to serialize: {}", e)))?;
+            if filter
+                .fields
+                .iter()
+                .all(|(field, value)| json.get(field) == Some(value))
+            {
+                rows.push((json, entity.clone()));
+            }
+        }
+
+        rows.sort_by(|(a, _), (b, _)| {
+            filter
+                .sort
+                .iter()
+                .map(|(field, ascending)| {
+                    let order = compare_json(a.get(field), b.get(field));
+                    if *ascending {
+                        order
+                    } else {
+                        order.reverse()
+                    }
+                })
+                .find(|order| order.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        Ok(rows.into_iter().map(|(_, entity)| entity).collect())
+    }
+}
+
+/// Order JSON field values: missing and null first, then booleans, numbers
+/// (numerically) and strings
+fn compare_json(a: Option<&serde_json::Value>, b: Option<&serde_json::Value>) -> Ordering {
+    use serde_json::Value;
+
+    fn rank(value: Option<&Value>) -> u8 {
+        match value {
+            None | Some(Value::Null) => 0,
+            Some(Value::Bool(_)) => 1,
+            Some(Value::Number(_)) => 2,
+            Some(Value::String(_)) => 3,
+            Some(_) => 4,
+        }
+    }
+
+    match (a, b) {
+        (Some(Value::Bool(a)), Some(Value::Bool(b))) => a.cmp(b),
+        (Some(Value::Number(a)), Some(Value::Number(b))) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Some(Value::String(a)), Some(Value::String(b))) => a.cmp(b),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
 #[async_trait]
 impl<T> Repository<T> for InMemoryRepository<T>
 where
@@ -45,9 +112,15 @@ where
         Ok(data.get(id).cloned())
     }
 
-    async fn find_all(&self, _filter: RepositoryFilter) -> RiptideResult<Vec<T>> {
-        let data = self.data.lock().unwrap();
-        Ok(data.values().cloned().collect())
+    async fn find_all(&self, filter: RepositoryFilter) -> RiptideResult<Vec<T>> {
+        let entities = self
+            .matching(&filter)?
+            .into_iter()
+            .skip(filter.offset.unwrap_or(0));
+        Ok(match filter.limit {
+            Some(limit) => entities.take(limit).collect(),
+            None => entities.collect(),
+        })
     }
 
     async fn save(&self, entity: &T) -> RiptideResult<()> {
@@ -71,9 +144,8 @@ where
         Ok(())
     }
 
-    async fn count(&self, _filter: RepositoryFilter) -> RiptideResult<usize> {
-        let data = self.data.lock().unwrap();
-        Ok(data.len())
+    async fn count(&self, filter: RepositoryFilter) -> RiptideResult<usize> {
+        Ok(self.matching(&filter)?.len())
     }
 
     async fn exists(&self, id: &str) -> RiptideResult<bool> {
@@ -275,3 +347,15 @@ impl Transaction for InMemoryTransaction {
         f()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_test_utils::contracts::repository::{self, ContractEntity};
+
+    #[tokio::test]
+    async fn in_memory_repository_honours_repository_contract() {
+        let repo = InMemoryRepository::<ContractEntity>::new();
+        repository::run_all_tests(&repo).await.unwrap();
+    }
+}
@@ -0,0 +1,212 @@
+//! Optional subsystems wired at runtime
+//!
+//! PDF processing, persistence, intelligence and streaming are switched on by
+//! [`FeatureFlags`] and supplied as trait objects through
+//! [`ApplicationContextBuilder`](super::ApplicationContextBuilder), so a
+//! single build serves deployments with different subsystems instead of each
+//! combination needing its own set of compile-time features.
+//!
+//! A subsystem is *active* when it is both enabled and wired. Enabling one
+//! without injecting an implementation is a configuration error.
+//! [`capabilities_router`] serves `GET /capabilities` with what is active.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! let ctx = ApplicationContext::builder()
+//!     .with_feature("intelligence", true)
+//!     .with_llm_provider(Arc::new(MockLlmProvider::new()))
+//!     .build_for_testing();
+//!
+//! let app = Router::new().merge(capabilities_router(ctx.capabilities()));
+//! ```
+
+use super::config::FeatureFlags;
+use anyhow::Result;
+use axum::{routing::get, Json, Router};
+use riptide_intelligence::LlmProvider;
+use riptide_types::ports::{CheckpointStore, PdfProcessor, StreamProcessor};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Path of the capabilities endpoint
+pub const CAPABILITIES_PATH: &str = "/capabilities";
+
+/// Implementations injected for the optional subsystems
+#[derive(Clone, Default)]
+pub struct Subsystems {
+    /// PDF text, image and page extraction
+    pub pdf: Option<Arc<dyn PdfProcessor>>,
+    /// Durable crawl state
+    pub persistence: Option<Arc<dyn CheckpointStore>>,
+    /// LLM-backed extraction and enrichment
+    pub intelligence: Option<Arc<dyn LlmProvider>>,
+    /// Streaming of crawl results
+    pub streaming: Option<Arc<dyn StreamProcessor>>,
+}
+
+/// Whether a subsystem is switched on and has an implementation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SubsystemStatus {
+    /// Enabled by configuration
+    pub enabled: bool,
+    /// Implementation injected
+    pub wired: bool,
+}
+
+impl SubsystemStatus {
+    /// Enabled and wired
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.wired
+    }
+}
+
+/// Response of `GET /capabilities`
+#[derive(Debug, Clone, Serialize)]
+pub struct Capabilities {
+    /// Status of each optional subsystem
+    pub subsystems: BTreeMap<&'static str, SubsystemStatus>,
+    /// Feature flags that do not need an injected implementation
+    pub features: BTreeMap<&'static str, bool>,
+}
+
+impl Subsystems {
+    /// Status of every subsystem under `flags`
+    pub fn statuses(&self, flags: &FeatureFlags) -> BTreeMap<&'static str, SubsystemStatus> {
+        [
+            ("pdf", flags.enable_pdf, self.pdf.is_some()),
+            (
+                "persistence",
+                flags.enable_persistence,
+                self.persistence.is_some(),
+            ),
+            (
+                "intelligence",
+                flags.enable_intelligence,
+                self.intelligence.is_some(),
+            ),
+            (
+                "streaming",
+                flags.enable_streaming,
+                self.streaming.is_some(),
+            ),
+        ]
+        .into_iter()
+        .map(|(name, enabled, wired)| (name, SubsystemStatus { enabled, wired }))
+        .collect()
+    }
+
+    /// What is enabled under `flags`, as reported by `GET /capabilities`
+    pub fn capabilities(&self, flags: &FeatureFlags) -> Capabilities {
+        Capabilities {
+            subsystems: self.statuses(flags),
+            features: BTreeMap::from([
+                ("browser", flags.enable_browser),
+                ("search", flags.enable_search),
+            ]),
+        }
+    }
+
+    /// Fail if a subsystem is enabled without an implementation
+    pub fn validate(&self, flags: &FeatureFlags) -> Result<()> {
+        let missing: Vec<&str> = self
+            .statuses(flags)
+            .into_iter()
+            .filter(|(_, status)| status.enabled && !status.wired)
+            .map(|(name, _)| name)
+            .collect();
+        if !missing.is_empty() {
+            anyhow::bail!(
+                "Subsystems enabled without an implementation: {}",
+                missing.join(", ")
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Router serving `GET /capabilities` with `capabilities`
+///
+/// Subsystems are wired once at startup, so the response does not change
+/// and the router can be merged into an application with any state.
+pub fn capabilities_router<S>(capabilities: Capabilities) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new().route(
+        CAPABILITIES_PATH,
+        get(move || {
+            let capabilities = capabilities.clone();
+            async move { Json(capabilities) }
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_intelligence::MockLlmProvider;
+    use riptide_types::ports::InMemoryCheckpointStore;
+
+    #[test]
+    fn test_statuses_and_validation() {
+        let flags = FeatureFlags {
+            enable_persistence: true,
+            enable_intelligence: true,
+            ..Default::default()
+        };
+        let mut subsystems = Subsystems {
+            intelligence: Some(Arc::new(MockLlmProvider::new())),
+            ..Default::default()
+        };
+
+        let statuses = subsystems.statuses(&flags);
+        assert!(statuses["intelligence"].is_active());
+        assert!(!statuses["persistence"].is_active());
+        assert!(!statuses["pdf"].enabled);
+        let err = subsystems.validate(&flags).unwrap_err();
+        assert!(err.to_string().ends_with("persistence"));
+
+        subsystems.persistence = Some(Arc::new(InMemoryCheckpointStore::new()));
+        assert!(subsystems.validate(&flags).is_ok());
+        assert!(subsystems.validate(&FeatureFlags::default()).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_router_merges_into_stateful_app() {
+        use axum::body::Body;
+        use axum::http::{Request, StatusCode};
+        use tower::ServiceExt;
+
+        let flags = FeatureFlags {
+            enable_pdf: true,
+            enable_browser: true,
+            ..Default::default()
+        };
+        let app = Router::new()
+            .route("/state", get(|| async { "ok" }))
+            .merge(capabilities_router(
+                Subsystems::default().capabilities(&flags),
+            ))
+            .with_state(42u32);
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri(CAPABILITIES_PATH)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["subsystems"]["pdf"]["enabled"], true);
+        assert_eq!(json["subsystems"]["pdf"]["wired"], false);
+        assert_eq!(json["features"]["browser"], true);
+    }
+}
@@ -3,6 +3,7 @@
 //! This module provides centralized configuration for all API operations including
 //! resource limits, timeouts, rate limiting, and performance controls.
 
+use riptide_types::RegionRouter;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -27,6 +28,15 @@ pub struct RiptideApiConfig {
     pub wasm: WasmConfig,
     /// Search provider configuration
     pub search: SearchProviderConfig,
+    /// Multi-region deployment labels and routing rules
+    #[serde(default)]
+    pub region: RegionRouter,
+    /// Graceful drain on shutdown
+    #[serde(default)]
+    pub drain: DrainConfig,
+    /// Dependency retries and degraded startup
+    #[serde(default)]
+    pub boot: BootConfig,
 }
 
 /// Resource management configuration
@@ -59,6 +69,8 @@ pub struct PerformanceConfig {
     pub wasm_timeout_secs: u64,
     /// Timeout for HTTP requests
     pub http_timeout_secs: u64,
+    /// Timeout for fetching a page in the crawl pipeline
+    pub fetch_timeout_secs: u64,
     /// Memory cleanup threshold (MB)
     pub memory_cleanup_threshold_mb: usize,
     /// Enable automatic cleanup on timeouts
@@ -171,6 +183,35 @@ pub struct WasmConfig {
     pub restart_threshold: u32,
 }
 
+/// Graceful drain configuration for zero-downtime deploys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DrainConfig {
+    /// Time in-flight requests and jobs get to finish once draining starts
+    pub timeout_secs: u64,
+    /// Retry-After sent with requests rejected while draining
+    pub retry_after_secs: u64,
+    /// Directory spider checkpoints are written to when draining
+    pub checkpoint_dir: String,
+}
+
+/// Startup dependency orchestration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BootConfig {
+    /// Initialization attempts per dependency before giving up
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled after each failed attempt
+    pub initial_backoff_ms: u64,
+    /// Upper bound on the delay between attempts
+    pub max_backoff_ms: u64,
+    /// Start without a Redis cache and rate limiter that are still unavailable
+    /// instead of failing (opt-in)
+    pub allow_degraded: bool,
+    /// How often degraded dependencies are retried after startup
+    pub recovery_interval_secs: u64,
+}
+
 /// Search provider configuration for deep search functionality
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchProviderConfig {
@@ -209,6 +250,7 @@ impl Default for PerformanceConfig {
             pdf_timeout_secs: 10,
             wasm_timeout_secs: 5,
             http_timeout_secs: 10,
+            fetch_timeout_secs: 15,
             memory_cleanup_threshold_mb: 512,
             auto_cleanup_on_timeout: true,
             degradation_threshold: 0.8, // 80% degradation threshold
@@ -291,6 +333,28 @@ impl Default for WasmConfig {
     }
 }
 
+impl Default for DrainConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 30,
+            retry_after_secs: 5,
+            checkpoint_dir: "./data/checkpoints".to_string(),
+        }
+    }
+}
+
+impl Default for BootConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 8_000,
+            allow_degraded: false,
+            recovery_interval_secs: 30,
+        }
+    }
+}
+
 impl Default for SearchProviderConfig {
     fn default() -> Self {
         Self {
@@ -318,6 +382,9 @@ impl Default for RiptideApiConfig {
             pdf: PdfConfig::default(),
             wasm: WasmConfig::default(),
             search: SearchProviderConfig::default(),
+            region: RegionRouter::default(),
+            drain: DrainConfig::default(),
+            boot: BootConfig::default(),
         }
     }
 }
@@ -383,6 +450,11 @@ impl RiptideApiConfig {
                 config.performance.http_timeout_secs = val;
             }
         }
+        if let Ok(val) = std::env::var("RIPTIDE_FETCH_TIMEOUT_SECS") {
+            if let Ok(val) = val.parse() {
+                config.performance.fetch_timeout_secs = val;
+            }
+        }
         if let Ok(val) = std::env::var("RIPTIDE_MEMORY_CLEANUP_THRESHOLD_MB") {
             if let Ok(val) = val.parse() {
                 config.performance.memory_cleanup_threshold_mb = val;
@@ -619,6 +691,69 @@ impl RiptideApiConfig {
             }
         }
 
+        // Region configuration (3 fields)
+        if let Ok(val) = std::env::var("RIPTIDE_REGION") {
+            if !val.is_empty() {
+                config.region.local_region = Some(val);
+            }
+        }
+        // Format: "eu-central=https://eu.example.com,ap-south=https://ap.example.com"
+        if let Ok(val) = std::env::var("RIPTIDE_REGION_PEERS") {
+            for (name, endpoint) in parse_pairs(&val) {
+                config.region = config.region.with_peer(name, endpoint);
+            }
+        }
+        // Format: "DE=eu-central,JP=ap-northeast"
+        if let Ok(val) = std::env::var("RIPTIDE_REGION_AFFINITY") {
+            for (suffix, region) in parse_pairs(&val) {
+                config.region = config.region.with_affinity(suffix, region);
+            }
+        }
+
+        // Drain configuration (3 fields)
+        if let Ok(val) = std::env::var("RIPTIDE_DRAIN_TIMEOUT_SECS") {
+            if let Ok(val) = val.parse() {
+                config.drain.timeout_secs = val;
+            }
+        }
+        if let Ok(val) = std::env::var("RIPTIDE_DRAIN_RETRY_AFTER_SECS") {
+            if let Ok(val) = val.parse() {
+                config.drain.retry_after_secs = val;
+            }
+        }
+        if let Ok(val) = std::env::var("RIPTIDE_DRAIN_CHECKPOINT_DIR") {
+            if !val.is_empty() {
+                config.drain.checkpoint_dir = val;
+            }
+        }
+
+        // Boot configuration (5 fields)
+        if let Ok(val) = std::env::var("RIPTIDE_BOOT_MAX_ATTEMPTS") {
+            if let Ok(val) = val.parse() {
+                config.boot.max_attempts = val;
+            }
+        }
+        if let Ok(val) = std::env::var("RIPTIDE_BOOT_INITIAL_BACKOFF_MS") {
+            if let Ok(val) = val.parse() {
+                config.boot.initial_backoff_ms = val;
+            }
+        }
+        if let Ok(val) = std::env::var("RIPTIDE_BOOT_MAX_BACKOFF_MS") {
+            if let Ok(val) = val.parse() {
+                config.boot.max_backoff_ms = val;
+            }
+        }
+        if let Ok(val) = std::env::var("RIPTIDE_BOOT_ALLOW_DEGRADED") {
+            if let Ok(val) = val.parse() {
+                config.boot.allow_degraded = val;
+            }
+        }
+        if let Ok(val) = std::env::var("RIPTIDE_BOOT_RECOVERY_INTERVAL_SECS") {
+            if let Ok(val) = val.parse() {
+                config.boot.recovery_interval_secs = val;
+            }
+        }
+
         config
     }
 
@@ -637,6 +772,9 @@ impl RiptideApiConfig {
         if self.performance.render_timeout_secs == 0 {
             return Err("render_timeout_secs must be greater than 0".to_string());
         }
+        if self.performance.fetch_timeout_secs == 0 {
+            return Err("fetch_timeout_secs must be greater than 0".to_string());
+        }
 
         // Validate rate limiting
         if self.rate_limiting.enabled {
@@ -694,6 +832,20 @@ impl RiptideApiConfig {
             return Err("search circuit_breaker_failure_threshold must be <= 100".to_string());
         }
 
+        // Validate region settings
+        if let Some(peer) = self.region.peers.iter().find(|p| p.endpoint.is_empty()) {
+            return Err(format!("region peer '{}' has an empty endpoint", peer.name));
+        }
+
+        // Validate boot settings
+        if self.boot.max_attempts == 0 {
+            return Err("boot max_attempts must be greater than 0".to_string());
+        }
+
+        if self.boot.recovery_interval_secs == 0 {
+            return Err("boot recovery_interval_secs must be greater than 0".to_string());
+        }
+
         Ok(())
     }
 
@@ -704,7 +856,9 @@ impl RiptideApiConfig {
             "pdf" => Duration::from_secs(self.performance.pdf_timeout_secs),
             "wasm" => Duration::from_secs(self.performance.wasm_timeout_secs),
             "http" => Duration::from_secs(self.performance.http_timeout_secs),
+            "fetch" => Duration::from_secs(self.performance.fetch_timeout_secs),
             "search" => Duration::from_secs(self.search.timeout_secs),
+            "drain" => Duration::from_secs(self.drain.timeout_secs),
             _ => Duration::from_secs(self.resources.global_timeout_secs),
         }
     }
@@ -724,6 +878,15 @@ impl RiptideApiConfig {
     }
 }
 
+/// Parse a comma-separated list of `key=value` pairs, skipping malformed entries
+fn parse_pairs(val: &str) -> impl Iterator<Item = (&str, &str)> {
+    val.split(',').filter_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        let (key, value) = (key.trim(), value.trim());
+        (!key.is_empty() && !value.is_empty()).then_some((key, value))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -765,6 +928,10 @@ mod tests {
         assert_eq!(config.get_timeout("render"), Duration::from_secs(3));
 
         assert_eq!(config.get_timeout("pdf"), Duration::from_secs(10));
+
+        assert_eq!(config.get_timeout("fetch"), Duration::from_secs(15));
+
+        assert_eq!(config.get_timeout("drain"), Duration::from_secs(30));
     }
 
     #[test]
@@ -793,4 +960,10 @@ mod tests {
         assert!(delay2.as_secs_f64() > 0.0);
         assert!(delay1.as_secs_f64() < 2.0); // Should be around 1/1.5 = 0.67s ± jitter
     }
+
+    #[test]
+    fn test_parse_pairs() {
+        let pairs: Vec<_> = parse_pairs(" .de = eu-central ,bad,=x,.jp=ap-northeast").collect();
+        assert_eq!(pairs, vec![(".de", "eu-central"), (".jp", "ap-northeast")]);
+    }
 }
@@ -14,27 +14,43 @@
 /// including HTTP clients, cache connections, and content extractors. The state
 /// is wrapped in Arc for efficient sharing across async handlers.
 ///
+use crate::boot::{self, BootState};
 use crate::capabilities::SystemCapabilities;
 use crate::config::RiptideApiConfig;
+use crate::drain::DrainController;
+use crate::experiments::Experiments;
+use crate::gate_verticals::GateVerticals;
 use crate::health::HealthChecker;
+use crate::media_pipeline::{MediaDownloadConfig, MediaDownloader};
 use crate::metrics_integration::CombinedMetrics;
 use crate::metrics_transport::TransportMetrics;
 use crate::resource_manager::ResourceManager;
 use crate::sessions::{SessionConfig, SessionManager};
 use crate::streaming::StreamingModule;
 use anyhow::{Context, Result};
+use riptide_cache::RenderCache;
+use riptide_facade::authorization::GeoRestriction;
+use riptide_facade::facades::{AnnotationEnricher, ClassificationEnricher, TranslationEnricher};
 use riptide_facade::metrics::BusinessMetrics;
 use riptide_types::ports::cache::CacheStorage;
 use riptide_types::ports::http::HttpClient;
+use riptide_types::ports::BlobStorage;
+use riptide_types::ports::GeoIpLookup;
+use riptide_types::ports::TextAnnotator;
+use riptide_types::ports::WebArchive;
+use riptide_types::ports::{Experiment, FeatureFlags, InMemoryFeatureFlags};
+use riptide_types::ExtractionProvenance;
 // CacheWarmingConfig requires wasm-pool feature which is not available in riptide-api
 // #[cfg(feature = "wasm-pool")]
 // use riptide_cache::CacheWarmingConfig;
 use riptide_events::{EventBus, EventBusConfig, EventSeverity};
 #[cfg(feature = "fetch")]
-use riptide_fetch::FetchEngine;
+use riptide_fetch::{FetchEngine, PerHostFetchEngine};
 use riptide_pdf::PdfMetricsCollector;
+use riptide_reliability::gate::{ContentVertical, GateThresholds, VerticalThresholds};
 use riptide_reliability::CircuitBreakerState;
 use riptide_reliability::{ReliabilityConfig, ReliableExtractor};
+use riptide_security::{CostModel, Meter, MeteringConfig, PolicyEngine};
 #[cfg(feature = "spider")]
 use riptide_spider::{Spider, SpiderConfig};
 // TelemetrySystem is in riptide_monitoring, not riptide_core
@@ -50,10 +66,11 @@ use riptide_monitoring::{
 use riptide_performance::PerformanceManager;
 #[cfg(feature = "workers")]
 use riptide_workers::{WorkerService, WorkerServiceConfig};
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::middleware::AuthConfig;
 
@@ -70,12 +87,76 @@ pub struct ApplicationContext {
     /// Cache storage for storing and retrieving cached content (trait-based abstraction)
     pub cache: Arc<dyn CacheStorage>,
 
+    /// Headless render output cache, keyed by render configuration with a shorter TTL
+    pub render_cache: Arc<RenderCache>,
+
+    /// Blob storage for binary artifacts such as screenshots and thumbnails
+    pub blob_storage: Arc<dyn BlobStorage>,
+
+    /// WARC archive recording every exchange made through `http_client`
+    /// (None unless `warc_archive_all_fetches` is set)
+    pub fetch_archive: Option<Arc<dyn WebArchive>>,
+
+    /// GeoIP lookup used to annotate extraction provenance (None when no database is configured)
+    pub geoip: Option<Arc<dyn GeoIpLookup>>,
+
+    /// Denies fetches to servers in blocked countries or networks (None unless configured)
+    pub geo_restriction: Option<GeoRestriction>,
+
+    /// Translates extracted documents (None when no translation provider is configured)
+    pub translation: Option<Arc<TranslationEnricher>>,
+
+    /// Adds entities, keywords and topics to extracted documents (None unless enabled)
+    pub annotation: Option<Arc<AnnotationEnricher>>,
+
+    /// Tags extracted documents with taxonomy categories and sentiment (None unless enabled)
+    pub classification: Option<Arc<ClassificationEnricher>>,
+
+    /// Downloads, hashes and deduplicates the images of extracted documents (None unless enabled)
+    pub media_downloader: Option<Arc<MediaDownloader>>,
+
+    /// Per-tenant crawl policy consulted before fetching (permissive when unconfigured)
+    pub policy_engine: Arc<PolicyEngine>,
+
+    /// A/B experiments from the feature flags, with their per-arm statistics
+    pub experiments: Arc<Experiments>,
+
+    /// Rules redirecting pipeline fetches to mirrors, canonical hosts or APIs
+    /// (no rules when unconfigured)
+    pub url_rewriter: Arc<riptide_fetch::UrlRewriter>,
+
+    /// Per-domain client certificates and CA bundles of every fetch client
+    pub tls_profiles: Arc<Vec<riptide_fetch::DomainTlsConfig>>,
+
+    /// Gate thresholds per content vertical, with per-vertical decision counts
+    pub gate_verticals: Arc<GateVerticals>,
+
+    /// Cost metering of crawl work per tenant, crawl and domain
+    pub meter: Arc<Meter>,
+
+    /// Download bandwidth caps shared by the HTTP client, the fetch engines
+    /// and the spider, adjustable through `/admin/bandwidth`
+    pub bandwidth: Arc<riptide_fetch::BandwidthLimiter>,
+
+    /// robots.txt fetched once per origin for the spider, sitemap discovery
+    /// and the spider facade, shared between instances through the cache
+    pub robots_service: Arc<riptide_fetch::RobotsService>,
+
     /// Unified extractor for content processing (WASM or native)
     /// Trait-based for dependency inversion and testability
     #[cfg(feature = "extraction")]
     #[allow(dead_code)]
     pub extractor: Arc<dyn riptide_types::ports::ContentExtractor>,
 
+    /// SHA-256 of the loaded WASM extractor component (None for native
+    /// extraction); replaced when the component is reloaded
+    pub extractor_component_hash: Arc<std::sync::RwLock<Option<String>>>,
+
+    /// Instance pool serving the WASM extractor component, reloaded through
+    /// the admin API (None for native extraction)
+    #[cfg(feature = "wasm-extractor")]
+    pub extractor_pool: Option<Arc<riptide_pool::AdvancedInstancePool>>,
+
     /// Reliable extractor wrapper with retry and circuit breaker logic
     /// Trait-based for dependency inversion and testability
     #[allow(dead_code)]
@@ -150,6 +231,17 @@ pub struct ApplicationContext {
     #[allow(dead_code)] // Public API - used for HTTP fetch operations with rate limiting
     pub fetch_engine: Arc<FetchEngine>,
 
+    /// Per-host fetch engine behind the scraper facade; its per-host metrics
+    /// carry the server location when GeoIP is configured
+    #[cfg(feature = "fetch")]
+    pub per_host_fetch_engine: Arc<PerHostFetchEngine>,
+
+    /// DNS cache shared by the HTTP client, the per-host fetch engine and the
+    /// spider; `None` when it could not be created and the system resolver
+    /// is used
+    #[cfg(feature = "fetch")]
+    pub dns_resolver: Option<Arc<riptide_fetch::CachingResolver>>,
+
     /// Performance manager for resource limiting, monitoring, and optimization
     pub performance_manager: Arc<PerformanceManager>,
 
@@ -198,10 +290,13 @@ pub struct ApplicationContext {
     /// Trace backend for distributed trace storage and retrieval
     pub trace_backend: Option<Arc<dyn crate::handlers::trace_backend::TraceBackend>>,
 
-    /// Persistence adapter for multi-tenant operations (optional, requires persistence feature)
-    #[cfg(feature = "persistence")]
-    #[allow(dead_code)] // TODO: Replace with actual PersistenceAdapter type when available
-    pub persistence_adapter: Option<()>,
+    /// Graceful drain state shared by the drain middleware, the admin
+    /// endpoint, and the shutdown handler
+    pub drain: Arc<DrainController>,
+
+    /// Startup outcome of dependencies initialized with retries, and which
+    /// of them the server is running without
+    pub boot: Arc<BootState>,
 }
 
 /// Application configuration loaded from environment and config files.
@@ -219,13 +314,100 @@ pub struct AppConfig {
     /// Default cache TTL in seconds
     pub cache_ttl: u64,
 
+    /// TTL of cached render output in seconds (shorter than `cache_ttl`)
+    pub render_cache_ttl: u64,
+
+    /// How long a document with HTTP validators stays cached after `cache_ttl`
+    /// expires, in seconds; a stale entry is revalidated with a conditional
+    /// request and served again on `304 Not Modified` (0 disables)
+    pub cache_revalidate_ttl: u64,
+
+    /// How long content fingerprints and crawl ids are kept for differential crawls, in seconds
+    pub change_tracking_ttl: u64,
+
+    /// Largest response body fetched for extraction, in bytes (0 disables
+    /// body limits); longer downloads and non-document content types are
+    /// aborted while streaming
+    pub max_response_bytes: u64,
+
     /// Gate thresholds for content quality scoring
     pub gate_hi_threshold: f32,
     pub gate_lo_threshold: f32,
 
+    /// Gate thresholds of content verticals that differ from the global
+    /// pair, e.g. `GATE_THRESHOLDS_ECOMMERCE=0.8,0.4`
+    pub gate_vertical_thresholds: HashMap<ContentVertical, GateThresholds>,
+
     /// Headless service URL for dynamic content rendering
     pub headless_url: Option<String>,
 
+    /// Directory WARC archives are written to when a crawl requests `archive_warc`
+    pub warc_output_dir: String,
+
+    /// Compress WARC archives per record (`.warc.gz`)
+    pub warc_gzip: bool,
+
+    /// Archive every fetch made through the shared HTTP client, not only
+    /// crawls that request `archive_warc`
+    pub warc_archive_all_fetches: bool,
+
+    /// Directory screenshot artifacts are stored in
+    pub artifact_storage_dir: String,
+
+    /// Base URL artifact links are published under (served by `/artifacts/*key` by default)
+    pub artifact_base_url: String,
+
+    /// Path to a MaxMind-format Country database for GeoIP enrichment
+    pub geoip_country_db: Option<String>,
+
+    /// Path to a MaxMind-format ASN database for GeoIP enrichment
+    pub geoip_asn_db: Option<String>,
+
+    /// Country codes (ISO 3166-1 alpha-2) whose servers are never fetched
+    pub geo_blocked_countries: Vec<String>,
+
+    /// Autonomous system numbers whose servers are never fetched
+    pub geo_blocked_asns: Vec<u32>,
+
+    /// Path to a JSON crawl policy file (blocklists, jurisdiction rules, per-tenant overrides)
+    pub policy_file: Option<String>,
+
+    /// Path to a JSON file listing A/B experiments for the feature flags
+    pub experiments_file: Option<String>,
+
+    /// Path to a JSON file of URL rewrite rules applied before fetching
+    pub url_rewrite_file: Option<String>,
+
+    /// Path to a JSON array of per-domain TLS profiles (client certificates
+    /// and CA bundles)
+    pub tls_profiles_file: Option<String>,
+
+    /// Directory of extractor plugins: WASM components implementing the
+    /// extractor world, registered under their file stems
+    pub extractor_plugin_dir: Option<String>,
+
+    /// Prefix image sources in clean HTML output are rewritten to, followed by
+    /// the percent-encoded image URL (images load from the origin when unset)
+    pub image_proxy_url: Option<String>,
+
+    /// Translation enrichment: default target language and LLM provider
+    pub translation_config: riptide_facade::facades::TranslationConfig,
+
+    /// Entity, keyword and topic enrichment: rule-based, or an LLM provider
+    pub annotation_config: riptide_facade::facades::AnnotationConfig,
+
+    /// Category and sentiment classification: taxonomy, LLM or embedding centroids
+    pub classification_config: riptide_facade::facades::ClassificationConfig,
+
+    /// Image downloads for `ExtractedDoc::media`: caps, storage and duplicate detection
+    pub media_download_config: MediaDownloadConfig,
+
+    /// Prices and breakdown limits for per-tenant cost metering
+    pub metering_config: MeteringConfig,
+
+    /// Initial download bandwidth caps (global and per domain)
+    pub bandwidth_config: riptide_fetch::BandwidthConfig,
+
     /// Session configuration
     pub session_config: SessionConfig,
 
@@ -246,6 +428,9 @@ pub struct AppConfig {
     /// Reliability configuration for retry and fallback behavior
     pub reliability_config: ReliabilityConfig,
 
+    /// DNS cache bounds and per-host resolution overrides for outbound fetches
+    pub dns_config: riptide_config::DnsConfig,
+
     /// Monitoring system configuration
     #[allow(dead_code)] // Public API - monitoring configuration
     pub monitoring_config: MonitoringConfig,
@@ -399,6 +584,22 @@ impl Default for AppConfig {
                 .unwrap_or_else(|_| "3600".to_string())
                 .parse()
                 .unwrap_or(3600),
+            render_cache_ttl: std::env::var("RIPTIDE_RENDER_CACHE_TTL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(600),
+            cache_revalidate_ttl: std::env::var("RIPTIDE_CACHE_REVALIDATE_TTL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(24 * 3600),
+            change_tracking_ttl: std::env::var("RIPTIDE_CHANGE_TRACKING_TTL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30 * 24 * 3600),
+            max_response_bytes: std::env::var("RIPTIDE_MAX_RESPONSE_BYTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(20 * 1024 * 1024),
             gate_hi_threshold: std::env::var("GATE_HI_THRESHOLD")
                 .unwrap_or_else(|_| "0.7".to_string())
                 .parse()
@@ -407,7 +608,48 @@ impl Default for AppConfig {
                 .unwrap_or_else(|_| "0.3".to_string())
                 .parse()
                 .unwrap_or(0.3),
+            gate_vertical_thresholds: AppConfig::init_gate_vertical_thresholds(),
             headless_url: std::env::var("HEADLESS_URL").ok(),
+            warc_output_dir: std::env::var("RIPTIDE_WARC_DIR")
+                .unwrap_or_else(|_| "data/warc".to_string()),
+            warc_gzip: std::env::var("RIPTIDE_WARC_GZIP")
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(true),
+            warc_archive_all_fetches: std::env::var("RIPTIDE_WARC_ARCHIVE_ALL")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            artifact_storage_dir: std::env::var("RIPTIDE_ARTIFACT_DIR")
+                .unwrap_or_else(|_| "data/artifacts".to_string()),
+            artifact_base_url: std::env::var("RIPTIDE_ARTIFACT_BASE_URL")
+                .unwrap_or_else(|_| "/artifacts".to_string()),
+            geoip_country_db: std::env::var("RIPTIDE_GEOIP_COUNTRY_DB").ok(),
+            geoip_asn_db: std::env::var("RIPTIDE_GEOIP_ASN_DB").ok(),
+            geo_blocked_countries: std::env::var("RIPTIDE_GEO_BLOCKED_COUNTRIES")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect(),
+            geo_blocked_asns: std::env::var("RIPTIDE_GEO_BLOCKED_ASNS")
+                .unwrap_or_default()
+                .split(',')
+                .filter_map(|s| s.trim().trim_start_matches("AS").parse().ok())
+                .collect(),
+            policy_file: std::env::var("RIPTIDE_POLICY_FILE").ok(),
+            experiments_file: std::env::var("RIPTIDE_EXPERIMENTS_FILE").ok(),
+            url_rewrite_file: std::env::var("RIPTIDE_URL_REWRITE_FILE").ok(),
+            tls_profiles_file: std::env::var("RIPTIDE_TLS_PROFILES_FILE").ok(),
+            extractor_plugin_dir: std::env::var("RIPTIDE_EXTRACTOR_PLUGIN_DIR").ok(),
+            image_proxy_url: std::env::var("RIPTIDE_IMAGE_PROXY_URL")
+                .ok()
+                .filter(|v| !v.trim().is_empty()),
+            translation_config: riptide_facade::facades::TranslationConfig::from_env(),
+            annotation_config: riptide_facade::facades::AnnotationConfig::from_env(),
+            classification_config: riptide_facade::facades::ClassificationConfig::from_env(),
+            media_download_config: MediaDownloadConfig::from_env(),
+            metering_config: AppConfig::init_metering_config(),
+            bandwidth_config: AppConfig::init_bandwidth_config(),
             session_config: SessionConfig::default(),
             #[cfg(feature = "spider")]
             spider_config: AppConfig::init_spider_config(),
@@ -416,6 +658,7 @@ impl Default for AppConfig {
             event_bus_config: EventBusConfig::default(),
             circuit_breaker_config: CircuitBreakerConfig::default(),
             reliability_config: ReliabilityConfig::from_env(),
+            dns_config: riptide_config::DnsConfig::from_env(),
             monitoring_config: MonitoringConfig::default(),
             enhanced_pipeline_config: EnhancedPipelineConfig::default(),
             // cache_warming_config removed - requires wasm-pool feature
@@ -425,6 +668,380 @@ impl Default for AppConfig {
 }
 
 impl AppConfig {
+    /// Create a WARC archive writing to `warc_output_dir`
+    ///
+    /// Returns `None` when the `fetch` feature (which provides the writer) is disabled.
+    pub fn create_warc_archive(&self) -> Option<Arc<dyn WebArchive>> {
+        #[cfg(feature = "fetch")]
+        {
+            use riptide_fetch::warc::{WarcFileArchive, WarcWriterConfig};
+            let config = WarcWriterConfig {
+                output_dir: self.warc_output_dir.clone().into(),
+                gzip: self.warc_gzip,
+                ..Default::default()
+            };
+            Some(Arc::new(WarcFileArchive::new(config)))
+        }
+        #[cfg(not(feature = "fetch"))]
+        {
+            tracing::warn!("WARC output requires the 'fetch' feature");
+            None
+        }
+    }
+
+    /// Open the configured GeoIP databases
+    ///
+    /// Returns `None` when no database is configured, the `geoip` feature is
+    /// disabled, or the databases cannot be opened (GeoIP is advisory only).
+    pub fn create_geoip(&self) -> Option<Arc<dyn GeoIpLookup>> {
+        if self.geoip_country_db.is_none() && self.geoip_asn_db.is_none() {
+            return None;
+        }
+        #[cfg(feature = "geoip")]
+        {
+            use riptide_fetch::geoip::MaxMindGeoIp;
+            let asn_db = self.geoip_asn_db.as_deref().map(std::path::Path::new);
+            let opened = match self.geoip_country_db.as_deref() {
+                Some(country_db) => MaxMindGeoIp::open(country_db, asn_db),
+                None => MaxMindGeoIp::open_asn(asn_db?),
+            };
+            match opened {
+                Ok(geoip) => Some(Arc::new(geoip)),
+                Err(e) => {
+                    tracing::warn!(error = %e, "GeoIP enrichment disabled");
+                    None
+                }
+            }
+        }
+        #[cfg(not(feature = "geoip"))]
+        {
+            tracing::warn!("GeoIP databases configured but the 'geoip' feature is disabled");
+            None
+        }
+    }
+
+    /// Build the geo restriction for the configured blocked countries and ASNs
+    ///
+    /// Returns `None` when nothing is blocked. Blocked locations need a
+    /// GeoIP database; without `geoip` they are ignored with a warning.
+    pub fn create_geo_restriction(
+        &self,
+        geoip: Option<Arc<dyn GeoIpLookup>>,
+    ) -> Option<GeoRestriction> {
+        if self.geo_blocked_countries.is_empty() && self.geo_blocked_asns.is_empty() {
+            return None;
+        }
+        let Some(geoip) = geoip else {
+            tracing::warn!("Geo restrictions configured without a GeoIP database; ignoring them");
+            return None;
+        };
+        let restriction = self
+            .geo_blocked_countries
+            .iter()
+            .fold(GeoRestriction::new(geoip), |restriction, country| {
+                restriction.block_country(country)
+            });
+        Some(
+            self.geo_blocked_asns
+                .iter()
+                .fold(restriction, |restriction, asn| restriction.block_asn(*asn)),
+        )
+    }
+
+    /// Build the translation enricher for the configured provider
+    ///
+    /// Returns `None` when no provider is configured, the `llm` feature is
+    /// disabled, or the provider cannot be created (translation is advisory).
+    pub fn create_translation(&self) -> Option<Arc<TranslationEnricher>> {
+        self.translation_config.provider.as_ref()?;
+        #[cfg(feature = "llm")]
+        {
+            match self.translation_config.create_translator() {
+                Ok(translator) => translator.map(|translator| {
+                    Arc::new(TranslationEnricher::new(translator).with_limits(
+                        self.translation_config.max_chars,
+                        self.translation_config.chunk_chars,
+                    ))
+                }),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Translation enrichment disabled");
+                    None
+                }
+            }
+        }
+        #[cfg(not(feature = "llm"))]
+        {
+            tracing::warn!("Translation provider configured but the 'llm' feature is disabled");
+            None
+        }
+    }
+
+    /// Build the annotation enricher when annotation is enabled
+    ///
+    /// The rule-based annotator is used on its own, or as the fallback of
+    /// the configured LLM provider. A provider that cannot be created leaves
+    /// the rules in charge (annotation is advisory).
+    pub fn create_annotation(&self) -> Option<Arc<AnnotationEnricher>> {
+        if !self.annotation_config.enabled {
+            return None;
+        }
+        let rules: Arc<dyn TextAnnotator> = Arc::new(riptide_extraction::RuleBasedAnnotator::new());
+        let enricher = self
+            .create_llm_annotator()
+            .map(|annotator| AnnotationEnricher::new(annotator).with_fallback(rules.clone()))
+            .unwrap_or_else(|| AnnotationEnricher::new(rules));
+        Some(Arc::new(
+            enricher.with_max_chars(self.annotation_config.max_chars),
+        ))
+    }
+
+    fn create_llm_annotator(&self) -> Option<Arc<dyn TextAnnotator>> {
+        self.annotation_config.provider.as_ref()?;
+        #[cfg(feature = "llm")]
+        {
+            match self.annotation_config.create_annotator() {
+                Ok(annotator) => annotator,
+                Err(e) => {
+                    tracing::warn!(error = %e, "LLM annotation disabled, using rule-based annotation");
+                    None
+                }
+            }
+        }
+        #[cfg(not(feature = "llm"))]
+        {
+            tracing::warn!("Annotation provider configured but the 'llm' feature is disabled");
+            None
+        }
+    }
+
+    /// Build the classification enricher when classification is enabled
+    ///
+    /// Returns `None` when no provider is configured, the `llm` feature is
+    /// disabled, or the provider or taxonomy cannot be loaded (classification
+    /// is advisory). Documents then keep their meta-keyword categories.
+    pub fn create_classification(&self) -> Option<Arc<ClassificationEnricher>> {
+        if !self.classification_config.enabled {
+            return None;
+        }
+        if self.classification_config.provider.is_none() {
+            tracing::warn!("Classification enabled but no provider is configured");
+            return None;
+        }
+        #[cfg(feature = "llm")]
+        {
+            match self.classification_config.create_classifier() {
+                Ok(classifier) => classifier.map(|classifier| {
+                    Arc::new(
+                        ClassificationEnricher::new(classifier)
+                            .with_max_chars(self.classification_config.max_chars),
+                    )
+                }),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Classification disabled");
+                    None
+                }
+            }
+        }
+        #[cfg(not(feature = "llm"))]
+        {
+            tracing::warn!("Classification provider configured but the 'llm' feature is disabled");
+            None
+        }
+    }
+
+    /// Build the crawl policy engine from the configured policy file
+    ///
+    /// Without a policy file every URL is allowed. A policy file that cannot
+    /// be loaded is a startup error: silently crawling without the intended
+    /// restrictions would be worse than refusing to start.
+    pub fn create_policy_engine(&self) -> Result<Arc<PolicyEngine>> {
+        let Some(path) = self.policy_file.as_deref() else {
+            return Ok(Arc::new(PolicyEngine::permissive()));
+        };
+        let policy = riptide_security::PolicyConfig::from_file(path)?;
+        let audit_logger = Arc::new(riptide_security::AuditLogger::new(None)?);
+        let engine = PolicyEngine::new(policy, Some(audit_logger))
+            .with_context(|| format!("Invalid crawl policy in {}", path))?;
+        tracing::info!(policy_file = %path, "Crawl policy engine initialized");
+        Ok(Arc::new(engine))
+    }
+
+    /// Load the feature flags with the experiments from the experiments file
+    ///
+    /// Without an experiments file no experiment runs. Like the policy file,
+    /// an experiments file that cannot be loaded is a startup error.
+    pub fn create_feature_flags(&self) -> Result<Arc<dyn FeatureFlags>> {
+        let Some(path) = self.experiments_file.as_deref() else {
+            return Ok(Arc::new(InMemoryFeatureFlags::new()));
+        };
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read experiments file {}", path))?;
+        let experiments: Vec<Experiment> = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid experiments in {}", path))?;
+        tracing::info!(
+            experiments_file = %path,
+            experiments = experiments.len(),
+            "Experiments loaded"
+        );
+        Ok(Arc::new(InMemoryFeatureFlags::with_experiments(
+            experiments,
+        )))
+    }
+
+    /// Load the URL rewrite rules from the rewrite file
+    ///
+    /// Without a rewrite file URLs are fetched as requested. Like the policy
+    /// file, a rewrite file that cannot be loaded is a startup error.
+    pub fn create_url_rewriter(&self) -> Result<Arc<riptide_fetch::UrlRewriter>> {
+        let Some(path) = self.url_rewrite_file.as_deref() else {
+            return Ok(Arc::new(riptide_fetch::UrlRewriter::default()));
+        };
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read URL rewrite file {}", path))?;
+        let rewriter = riptide_fetch::UrlRewriter::from_json(&content)
+            .with_context(|| format!("Invalid URL rewrite rules in {}", path))?;
+        tracing::info!(
+            url_rewrite_file = %path,
+            rules = rewriter.len(),
+            "URL rewrite rules loaded"
+        );
+        Ok(Arc::new(rewriter))
+    }
+
+    /// Load the per-domain TLS profiles from the TLS profiles file
+    ///
+    /// Without one every host uses the default client. A profiles file that
+    /// cannot be loaded is a startup error, as are certificate files the
+    /// fetch clients cannot read.
+    pub fn create_tls_profiles(&self) -> Result<Arc<Vec<riptide_fetch::DomainTlsConfig>>> {
+        let Some(path) = self.tls_profiles_file.as_deref() else {
+            return Ok(Arc::default());
+        };
+        let profiles = riptide_fetch::DomainTlsConfig::load(path)?;
+        tracing::info!(
+            tls_profiles_file = %path,
+            profiles = profiles.len(),
+            "TLS profiles loaded"
+        );
+        Ok(Arc::new(profiles))
+    }
+
+    /// Load the extractor plugins in the plugin directory and register them
+    /// with `experiments`, so requests can pick one by name with the
+    /// `extraction_schema` pipeline hint and experiment arms can name one
+    ///
+    /// Components that fail validation are skipped, as are plugins named
+    /// like an extractor already registered. A plugin directory that cannot
+    /// be read is a startup error.
+    #[cfg(feature = "wasm-extractor")]
+    pub async fn register_extractor_plugins(
+        &self,
+        mut experiments: Experiments,
+    ) -> Result<Experiments> {
+        let Some(dir) = self.extractor_plugin_dir.as_deref() else {
+            return Ok(experiments);
+        };
+        let registry = riptide_extraction::ExtractorPluginRegistry::default();
+        let loaded = registry
+            .load_dir(dir)
+            .await
+            .with_context(|| format!("Failed to load extractor plugins from {}", dir))?;
+        for info in &loaded {
+            if experiments.extractor(&info.name).is_some() {
+                tracing::warn!(
+                    plugin = %info.name,
+                    "Extractor plugin shadows a built-in extractor, skipping"
+                );
+                continue;
+            }
+            if let Some(plugin) = registry.get(&info.name) {
+                experiments = experiments.with_extractor(
+                    info.name.clone(),
+                    plugin as Arc<dyn riptide_types::ports::ContentExtractor>,
+                );
+            }
+        }
+        tracing::info!(
+            extractor_plugin_dir = %dir,
+            plugins = loaded.len(),
+            "Extractor plugins loaded"
+        );
+        Ok(experiments)
+    }
+
+    /// Initialize per-vertical gate thresholds from `GATE_THRESHOLDS_<VERTICAL>`
+    /// variables holding `hi,lo`; invalid values are skipped
+    fn init_gate_vertical_thresholds() -> HashMap<ContentVertical, GateThresholds> {
+        let mut thresholds = HashMap::new();
+        for vertical in ContentVertical::ALL {
+            let name = format!("GATE_THRESHOLDS_{}", vertical.as_str().to_ascii_uppercase());
+            let Ok(value) = std::env::var(&name) else {
+                continue;
+            };
+            match GateThresholds::parse(&value) {
+                Some(pair) => {
+                    thresholds.insert(vertical, pair);
+                }
+                None => {
+                    warn!(variable = %name, value = %value, "Invalid gate thresholds, expected hi,lo")
+                }
+            }
+        }
+        thresholds
+    }
+
+    /// Gate thresholds by content vertical, the global pair as default
+    pub fn gate_thresholds(&self) -> VerticalThresholds {
+        VerticalThresholds {
+            default: GateThresholds {
+                hi: self.gate_hi_threshold,
+                lo: self.gate_lo_threshold,
+            },
+            verticals: self.gate_vertical_thresholds.clone(),
+        }
+    }
+
+    /// Initialize download bandwidth caps from environment variables,
+    /// falling back to no caps when they are invalid
+    fn init_bandwidth_config() -> riptide_fetch::BandwidthConfig {
+        let config = riptide_fetch::BandwidthConfig::from_env();
+        if let Err(e) = config.validate() {
+            warn!(error = %e, "Invalid bandwidth limits, downloads are not throttled");
+            return riptide_fetch::BandwidthConfig::default();
+        }
+        config
+    }
+
+    /// Initialize cost metering prices from environment variables (USD)
+    fn init_metering_config() -> MeteringConfig {
+        let price = |name: &str, default: f64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        };
+        let defaults = CostModel::default();
+        MeteringConfig {
+            cost_model: CostModel {
+                bandwidth_per_gb: price("RIPTIDE_COST_BANDWIDTH_PER_GB", defaults.bandwidth_per_gb),
+                render_per_second: price(
+                    "RIPTIDE_COST_RENDER_PER_SECOND",
+                    defaults.render_per_second,
+                ),
+                llm_per_1k_tokens: price(
+                    "RIPTIDE_COST_LLM_PER_1K_TOKENS",
+                    defaults.llm_per_1k_tokens,
+                ),
+                storage_per_gb: price("RIPTIDE_COST_STORAGE_PER_GB", defaults.storage_per_gb),
+            },
+            max_tenants: std::env::var("RIPTIDE_METERING_MAX_TENANTS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(MeteringConfig::default().max_tenants),
+            ..MeteringConfig::default()
+        }
+    }
+
     /// Initialize spider configuration based on environment variables
     #[cfg(feature = "spider")]
     fn init_spider_config() -> Option<SpiderConfig> {
@@ -579,6 +1196,8 @@ impl AppConfig {
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(10),
 
+            result_ttl_secs: riptide_workers::result_ttl_from_env(),
+
             wasm_path: std::env::var("WASM_EXTRACTOR_PATH").unwrap_or_else(|_| {
                 "./target/wasm32-wasip2/release/riptide_extractor_wasm.wasm".to_string()
             }),
@@ -587,10 +1206,34 @@ impl AppConfig {
                 .ok()
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(true),
+
+            elasticsearch_export: riptide_workers::ElasticsearchSinkConfig::from_env()
+                .unwrap_or_else(|e| {
+                    warn!(error = %e, "Ignoring invalid Elasticsearch export configuration");
+                    None
+                }),
+
+            postgres_export: riptide_workers::PostgresSinkConfig::from_env(),
+
+            email_reports: riptide_workers::ReportsConfig::from_env().unwrap_or_else(|e| {
+                warn!(error = %e, "Ignoring invalid report email configuration");
+                None
+            }),
         }
     }
 }
 
+/// Stream fetched bodies under `max_bytes` and the default content-type allow-list
+fn limit_response_bodies(
+    client: riptide_fetch::adapters::ReqwestHttpClient,
+    max_bytes: u64,
+) -> riptide_fetch::adapters::ReqwestHttpClient {
+    if max_bytes == 0 {
+        return client;
+    }
+    client.with_body_limits(riptide_fetch::BodyLimits::default().with_max_content_length(max_bytes))
+}
+
 impl ApplicationContext {
     /// Initialize the application state with all required components including facades.
     ///
@@ -709,30 +1352,106 @@ impl ApplicationContext {
             "Combined metrics collector created - business + transport registries merged for /metrics endpoint"
         );
 
+        // Compression counters shared by the HTTP client and the fetch engine
+        let compression_metrics = Arc::new(riptide_fetch::CompressionMetrics::default());
+
+        // Bandwidth caps shared by the HTTP client and the fetch engine
+        let bandwidth = Arc::new(riptide_fetch::BandwidthLimiter::new(
+            config.bandwidth_config.clone(),
+        ));
+        if config.bandwidth_config.is_limited() {
+            tracing::info!(
+                global_bytes_per_sec = config.bandwidth_config.global_bytes_per_sec,
+                per_domain_bytes_per_sec = config.bandwidth_config.per_domain_bytes_per_sec,
+                "Download bandwidth limits enabled"
+            );
+        }
+
+        // DNS cache shared by every outbound client
+        #[cfg(feature = "fetch")]
+        let dns_resolver = {
+            use riptide_fetch::dns::CachingResolver;
+            config
+                .dns_config
+                .validate()
+                .map_err(|e| anyhow::anyhow!("Invalid DNS configuration: {}", e))?;
+            match CachingResolver::new(config.dns_config.clone()) {
+                Ok(resolver) => {
+                    tracing::info!(
+                        cache_enabled = config.dns_config.cache_enabled,
+                        host_overrides = config.dns_config.host_overrides.len(),
+                        "DNS cache resolver initialized"
+                    );
+                    Some(Arc::new(resolver))
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "DNS cache unavailable, using the system resolver");
+                    None
+                }
+            }
+        };
+
         // Initialize HTTP client with trait-based abstraction
         #[cfg(feature = "fetch")]
         let http_client: Arc<dyn HttpClient> = {
             use riptide_fetch::adapters::ReqwestHttpClient;
-            Arc::new(ReqwestHttpClient::new().context("Failed to create HTTP client adapter")?)
+            let client = match dns_resolver.clone() {
+                Some(resolver) => ReqwestHttpClient::with_dns_resolver(resolver)
+                    .context("Failed to create HTTP client adapter")?,
+                None => ReqwestHttpClient::new().context("Failed to create HTTP client adapter")?,
+            };
+            let client = client
+                .with_compression_metrics(compression_metrics.clone())
+                .with_bandwidth_limiter(bandwidth.clone());
+            Arc::new(limit_response_bodies(client, config.max_response_bytes))
         };
         #[cfg(not(feature = "fetch"))]
         let http_client: Arc<dyn HttpClient> = {
             use riptide_fetch::adapters::ReqwestHttpClient;
-            Arc::new(ReqwestHttpClient::new().context("Failed to create HTTP client adapter")?)
+            let client =
+                ReqwestHttpClient::new().context("Failed to create HTTP client adapter")?;
+            let client = client
+                .with_compression_metrics(compression_metrics.clone())
+                .with_bandwidth_limiter(bandwidth.clone());
+            Arc::new(limit_response_bodies(client, config.max_response_bytes))
         };
         tracing::debug!("HTTP client initialized with trait-based abstraction");
 
-        // Establish cache storage with automatic backend selection and fallback
+        // Optionally tap the shared client so every fetch is archived
+        let fetch_archive = if config.warc_archive_all_fetches {
+            config.create_warc_archive()
+        } else {
+            None
+        };
+        let http_client: Arc<dyn HttpClient> = match &fetch_archive {
+            Some(archive) => {
+                use riptide_fetch::adapters::ArchivingHttpClient;
+                tracing::info!(
+                    output_dir = %config.warc_output_dir,
+                    gzip = config.warc_gzip,
+                    "Archiving every fetch to WARC"
+                );
+                Arc::new(ArchivingHttpClient::new(http_client, Arc::clone(archive)))
+            }
+            None => http_client,
+        };
+
+        // Dependencies that may still be starting are retried with backoff
+        // and, when allowed, left out until they recover
+        let boot = Arc::new(BootState::new());
+
+        // Establish cache storage with automatic backend selection
         use riptide_cache::factory::CacheFactory;
         use riptide_cache::storage_config::StorageConfig;
+        use riptide_cache::UpgradableCache;
 
         // Build storage configuration from environment/config
         let storage_config = if !config.redis_url.is_empty() {
             tracing::info!(
                 redis_url = %config.redis_url,
-                "Configuring Redis cache with automatic fallback to in-memory"
+                "Configuring Redis cache backend"
             );
-            StorageConfig::redis_with_fallback(&config.redis_url)
+            StorageConfig::redis(&config.redis_url)
                 .with_ttl_secs(config.cache_ttl)
                 .with_connection_timeout_secs(5)
         } else {
@@ -745,31 +1464,172 @@ impl ApplicationContext {
             .validate()
             .map_err(|e| anyhow::anyhow!("Invalid cache configuration: {}", e))?;
 
-        // Create cache via factory with graceful fallback
-        let cache: Arc<dyn CacheStorage> =
-            CacheFactory::create_with_fallback(&storage_config).await;
+        // An unreachable Redis leaves the cache in memory until the recovery
+        // task swaps Redis in
+        let outcome = boot::retry_with_backoff(boot::CACHE, &api_config.boot, || {
+            CacheFactory::create(&storage_config)
+        })
+        .await;
+        let cache = Arc::new(UpgradableCache::new(
+            match boot.settle(boot::CACHE, &api_config.boot, outcome, true)? {
+                Some(backend) => backend,
+                None => CacheFactory::memory(),
+            },
+        ));
+        if boot.is_degraded(boot::CACHE) {
+            let upgradable = cache.clone();
+            let storage_config = storage_config.clone();
+            boot::spawn_recovery(
+                boot.clone(),
+                boot::CACHE,
+                Duration::from_secs(api_config.boot.recovery_interval_secs),
+                move || {
+                    let upgradable = upgradable.clone();
+                    let storage_config = storage_config.clone();
+                    async move {
+                        upgradable.upgrade(CacheFactory::create(&storage_config).await?);
+                        anyhow::Ok(())
+                    }
+                },
+            );
+        }
+        let cache: Arc<dyn CacheStorage> = cache;
         tracing::info!(
             backend = %storage_config.backend,
             ttl_secs = storage_config.default_ttl_secs,
-            fallback_enabled = storage_config.enable_fallback,
+            degraded = boot.is_degraded(boot::CACHE),
             "Cache storage initialized successfully"
         );
+        let render_cache = Arc::new(
+            RenderCache::new(cache.clone()).with_ttl(Duration::from_secs(config.render_cache_ttl)),
+        );
+        let robots_service = Arc::new(
+            riptide_fetch::RobotsService::new(riptide_fetch::RobotsServiceConfig::default())?
+                .with_shared_cache(cache.clone()),
+        );
+
+        // Blob storage for screenshot artifacts (local filesystem)
+        let blob_storage: Arc<dyn BlobStorage> = Arc::new(riptide_cache::LocalBlobStorage::new(
+            &config.artifact_storage_dir,
+            &config.artifact_base_url,
+        ));
+
+        let geoip = config.create_geoip();
+        let geo_restriction = config.create_geo_restriction(geoip.clone());
+        let translation = config.create_translation();
+        let annotation = config.create_annotation();
+        let classification = config.create_classification();
+        let media_config = &config.media_download_config;
+        let media_downloader = if media_config.enabled || media_config.probe_images {
+            // A client of its own, reading nothing but images under the image cap
+            use riptide_fetch::adapters::ReqwestHttpClient;
+            #[cfg(feature = "fetch")]
+            let client = match dns_resolver.clone() {
+                Some(resolver) => ReqwestHttpClient::with_dns_resolver(resolver),
+                None => ReqwestHttpClient::new(),
+            };
+            #[cfg(not(feature = "fetch"))]
+            let client = ReqwestHttpClient::new();
+            let client = client
+                .context("Failed to create media HTTP client")?
+                .with_compression_metrics(compression_metrics.clone())
+                .with_bandwidth_limiter(bandwidth.clone())
+                .with_body_limits(media_config.body_limits());
+            let client: Arc<dyn HttpClient> = match &fetch_archive {
+                Some(archive) => Arc::new(riptide_fetch::adapters::ArchivingHttpClient::new(
+                    Arc::new(client),
+                    Arc::clone(archive),
+                )),
+                None => Arc::new(client),
+            };
+            Some(Arc::new(MediaDownloader::new(
+                client,
+                blob_storage.clone(),
+                config.media_download_config.clone(),
+            )))
+        } else {
+            None
+        };
+        let policy_engine = config.create_policy_engine()?;
+        let feature_flags = config.create_feature_flags()?;
+        let url_rewriter = config.create_url_rewriter()?;
+        let tls_profiles = config.create_tls_profiles()?;
+
+        // Initialize the WASM extractor pool when a component is configured
+        #[cfg(feature = "wasm-extractor")]
+        let extractor_pool = match std::env::var("WASM_EXTRACTOR_PATH").ok() {
+            Some(path) => {
+                let outcome =
+                    boot::retry_with_backoff(boot::WASM_EXTRACTOR, &api_config.boot, || async {
+                        let pool_config = riptide_pool::ExtractorConfig::default();
+                        let engine = wasmtime::Engine::new(&pool_config.wasmtime_config())?;
+                        let pool =
+                            riptide_pool::AdvancedInstancePool::new(pool_config, engine, &path)
+                                .await
+                                .with_context(|| {
+                                    format!("WASM component at {} could not be loaded", path)
+                                })?;
+                        // Hash the component so documents record which build produced them
+                        let hash =
+                            ExtractionProvenance::hash_component(&tokio::fs::read(&path).await?);
+                        Ok((Arc::new(pool), hash))
+                    })
+                    .await;
+                boot.settle(boot::WASM_EXTRACTOR, &api_config.boot, outcome, false)?
+            }
+            None => None,
+        };
 
-        // Initialize unified extractor with automatic fallback
+        // Serve extraction from the pool, or the unified (native) extractor
         #[cfg(feature = "extraction")]
-        let extractor = {
-            let wasm_path = std::env::var("WASM_EXTRACTOR_PATH").ok();
-            let ext = UnifiedExtractor::new(wasm_path.as_deref())
-                .await
-                .context("Failed to initialize content extractor")?;
+        let (extractor, extractor_component_hash) = {
+            #[cfg(feature = "wasm-extractor")]
+            let wasm = extractor_pool.clone().map(|(pool, hash)| {
+                (
+                    pool as Arc<dyn riptide_types::ports::ContentExtractor>,
+                    Some(hash),
+                )
+            });
+            #[cfg(not(feature = "wasm-extractor"))]
+            let wasm = None;
+            let (ext, component_hash) = match wasm {
+                Some(wasm) => wasm,
+                None => (
+                    Arc::new(
+                        UnifiedExtractor::new(None)
+                            .await
+                            .context("Failed to initialize content extractor")?,
+                    ) as Arc<dyn riptide_types::ports::ContentExtractor>,
+                    None,
+                ),
+            };
             tracing::info!(
                 extractor_type = ext.extractor_type(),
                 wasm_available = UnifiedExtractor::wasm_available(),
                 "Content extractor initialized"
             );
-            // Cast to trait object
-            Arc::new(ext) as Arc<dyn riptide_types::ports::ContentExtractor>
+            (ext, component_hash)
         };
+        #[cfg(not(feature = "extraction"))]
+        let extractor_component_hash = None;
+        #[cfg(feature = "wasm-extractor")]
+        let extractor_pool = extractor_pool.map(|(pool, _)| pool);
+
+        // Experiments may route a share of traffic to the native extractor
+        let experiments = Experiments::new(feature_flags);
+        #[cfg(feature = "extraction")]
+        let experiments = experiments.with_extractor(
+            "native",
+            Arc::new(
+                UnifiedExtractor::new(None)
+                    .await
+                    .context("Failed to initialize native extractor")?,
+            ) as Arc<dyn riptide_types::ports::ContentExtractor>,
+        );
+        #[cfg(feature = "wasm-extractor")]
+        let experiments = config.register_extractor_plugins(experiments).await?;
+        let experiments = Arc::new(experiments);
+        let gate_verticals = Arc::new(GateVerticals::new(config.gate_thresholds()));
 
         // Initialize ReliableExtractor with retry and circuit breaker logic
         let reliable_extractor = {
@@ -816,10 +1676,14 @@ impl ApplicationContext {
             match Spider::new(spider_config).await {
                 Ok(spider_engine) => {
                     let spider_with_integrations = spider_engine
+                        .with_robots_service(robots_service.clone())
+                        .with_bandwidth_limiter(bandwidth.clone())
                         .with_fetch_engine(Arc::new({
                             #[cfg(feature = "fetch")]
                             {
                                 riptide_fetch::FetchEngine::new()?
+                                    .with_bandwidth_limiter(bandwidth.clone())
+                                    .with_tls_profiles(&tls_profiles)?
                             }
                             #[cfg(not(feature = "fetch"))]
                             {
@@ -892,13 +1756,22 @@ impl ApplicationContext {
                     "Starting worker service with Redis queue"
                 );
 
-                let ws = WorkerService::new(config.worker_config.clone())
-                    .await
-                    .map_err(|e| anyhow::anyhow!("Failed to initialize worker service: {}", e))?;
-                tracing::info!(
-                    "Worker service initialized successfully - async job processing enabled"
-                );
-                Some(Arc::new(ws))
+                let outcome =
+                    boot::retry_with_backoff(boot::WORKER_QUEUE, &api_config.boot, || async {
+                        WorkerService::new(config.worker_config.clone())
+                            .await
+                            .map_err(|e| {
+                                anyhow::anyhow!("Failed to initialize worker service: {}", e)
+                            })
+                    })
+                    .await;
+                let ws = boot.settle(boot::WORKER_QUEUE, &api_config.boot, outcome, false)?;
+                if ws.is_some() {
+                    tracing::info!(
+                        "Worker service initialized successfully - async job processing enabled"
+                    );
+                }
+                ws.map(Arc::new)
             } else {
                 tracing::info!(
                     "Worker service disabled - running in single-process mode without job queue"
@@ -997,12 +1870,35 @@ impl ApplicationContext {
             );
             let fe = Arc::new(
                 FetchEngine::new()
-                    .map_err(|e| anyhow::anyhow!("Failed to initialize FetchEngine: {}", e))?,
+                    .map_err(|e| anyhow::anyhow!("Failed to initialize FetchEngine: {}", e))?
+                    .with_compression_metrics(compression_metrics.clone())
+                    .with_bandwidth_limiter(bandwidth.clone())
+                    .with_tls_profiles(&tls_profiles)?,
             );
             tracing::info!("FetchEngine initialized successfully");
             fe
         };
 
+        #[cfg(feature = "fetch")]
+        let per_host_fetch_engine = {
+            let mut engine = PerHostFetchEngine::new(
+                riptide_fetch::RetryConfig::default(),
+                riptide_fetch::CircuitBreakerConfig::default(),
+                riptide_fetch::RateLimitConfig::default(),
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to initialize PerHostFetchEngine: {}", e))?
+            .with_compression_metrics(compression_metrics.clone())
+            .with_bandwidth_limiter(bandwidth.clone())
+            .with_tls_profiles(tls_profiles.to_vec());
+            if let Some(resolver) = dns_resolver.clone() {
+                engine = engine.with_dns_resolver(resolver);
+            }
+            Arc::new(match geoip.clone() {
+                Some(geoip) => engine.with_geoip(geoip),
+                None => engine,
+            })
+        };
+
         // Initialize PerformanceManager for resource limiting and monitoring
         tracing::info!("Initializing PerformanceManager for resource limiting and profiling");
         let performance_manager = Arc::new(
@@ -1263,26 +2159,22 @@ impl ApplicationContext {
                 })?,
         );
 
-        let scraper_facade = Arc::new(
-            riptide_facade::facades::ScraperFacade::new(facade_config.clone())
-                .await
-                .map_err(|e| {
-                    anyhow::anyhow!("Failed to initialize placeholder ScraperFacade: {}", e)
-                })?,
-        );
+        let scraper_facade = riptide_facade::facades::ScraperFacade::new(facade_config.clone())
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!("Failed to initialize placeholder ScraperFacade: {}", e)
+            })?;
+        #[cfg(feature = "fetch")]
+        let scraper_facade = scraper_facade.with_per_host_engine(per_host_fetch_engine.clone());
+        let scraper_facade = Arc::new(match geo_restriction.clone() {
+            Some(restriction) => scraper_facade.with_geo_restriction(restriction),
+            None => scraper_facade,
+        });
 
-        // Initialize engine facade with cache storage adapter via factory
-        let engine_cache_config = if !config.redis_url.is_empty() {
-            StorageConfig::redis_with_fallback(&config.redis_url).with_ttl_secs(config.cache_ttl)
-        } else {
-            StorageConfig::memory().with_ttl_secs(config.cache_ttl)
-        };
-        let engine_cache_storage = CacheFactory::create_with_fallback(&engine_cache_config).await;
-        let engine_facade = Arc::new(riptide_facade::facades::EngineFacade::new(
-            engine_cache_storage,
-        ));
+        // Initialize engine facade on the shared cache so it follows a recovered Redis
+        let engine_facade = Arc::new(riptide_facade::facades::EngineFacade::new(cache.clone()));
         tracing::info!(
-            backend = %engine_cache_config.backend,
+            backend = %storage_config.backend,
             "EngineFacade initialized successfully with cache backend"
         );
 
@@ -1290,16 +2182,60 @@ impl ApplicationContext {
         use crate::adapters::ResourceManagerPoolAdapter;
         let resource_pool_adapter =
             Arc::new(ResourceManagerPoolAdapter::new(resource_manager.clone()));
-        let redis_manager = Arc::new(
-            riptide_cache::redis::RedisManager::new(&config.redis_url)
-                .await
-                .context("Failed to create Redis manager for rate limiting")?,
-        );
-        let redis_rate_limiter = Arc::new(riptide_cache::adapters::RedisRateLimiter::new(
-            redis_manager,
-            (api_config.rate_limiting.requests_per_second_per_host * 60.0) as usize, // requests per minute
-            std::time::Duration::from_secs(60),
-        ));
+        // Tenant quotas are counted per instance until Redis is reachable
+        let requests_per_minute =
+            (api_config.rate_limiting.requests_per_second_per_host * 60.0) as usize;
+        let connect_rate_limiter = {
+            let redis_url = config.redis_url.clone();
+            move || {
+                let redis_url = redis_url.clone();
+                async move {
+                    let redis_manager =
+                        riptide_cache::redis::RedisManager::new(&redis_url)
+                            .await
+                            .context("Failed to create Redis manager for rate limiting")?;
+                    anyhow::Ok(Arc::new(riptide_cache::adapters::RedisRateLimiter::new(
+                        Arc::new(redis_manager),
+                        requests_per_minute,
+                        std::time::Duration::from_secs(60),
+                    ))
+                        as Arc<dyn riptide_types::ports::RateLimiter>)
+                }
+            }
+        };
+        let redis_rate_limiter = Arc::new(riptide_cache::UpgradableRateLimiter::new(Arc::new(
+            riptide_cache::adapters::InMemoryRateLimiter::new(
+                requests_per_minute,
+                std::time::Duration::from_secs(60),
+            ),
+        )));
+        if !config.redis_url.is_empty() {
+            let outcome = boot::retry_with_backoff(
+                boot::RATE_LIMITER,
+                &api_config.boot,
+                connect_rate_limiter.clone(),
+            )
+            .await;
+            match boot.settle(boot::RATE_LIMITER, &api_config.boot, outcome, true)? {
+                Some(limiter) => redis_rate_limiter.upgrade(limiter),
+                None => {
+                    let upgradable = redis_rate_limiter.clone();
+                    boot::spawn_recovery(
+                        boot.clone(),
+                        boot::RATE_LIMITER,
+                        Duration::from_secs(api_config.boot.recovery_interval_secs),
+                        move || {
+                            let upgradable = upgradable.clone();
+                            let connect = connect_rate_limiter.clone();
+                            async move {
+                                upgradable.upgrade(connect().await?);
+                                anyhow::Ok(())
+                            }
+                        },
+                    );
+                }
+            }
+        }
         let resource_facade = Arc::new(riptide_facade::facades::ResourceFacade::new(
             resource_pool_adapter
                 as Arc<dyn riptide_types::ports::Pool<crate::adapters::ResourceSlot>>,
@@ -1333,20 +2269,45 @@ impl ApplicationContext {
         #[cfg(not(feature = "workers"))]
         let workers_enabled = false;
 
+        // Configured capabilities; degraded dependencies are applied when reported
         let capabilities = SystemCapabilities::detect(&cache_backend_str, workers_enabled);
+        let available = capabilities.with_boot_state(&boot);
         tracing::info!(
-            deployment_mode = %capabilities.deployment_mode,
-            cache_backend = %capabilities.cache_backend,
-            async_jobs = %capabilities.async_jobs,
-            distributed = %capabilities.distributed,
+            deployment_mode = %available.deployment_mode,
+            cache_backend = %available.cache_backend,
+            async_jobs = %available.async_jobs,
+            distributed = %available.distributed,
+            degraded = ?available.degraded,
             "System capabilities detected"
         );
 
+        let drain = Arc::new(DrainController::new(api_config.drain.clone()));
+
         Ok(Self {
             http_client,
+            render_cache,
             cache,
+            blob_storage,
+            fetch_archive,
+            geoip,
+            geo_restriction,
+            translation,
+            annotation,
+            classification,
+            media_downloader,
+            policy_engine,
+            experiments,
+            url_rewriter,
+            tls_profiles,
+            gate_verticals,
+            meter: Arc::new(Meter::new(config.metering_config.clone())),
+            bandwidth,
+            robots_service,
             #[cfg(feature = "extraction")]
             extractor,
+            extractor_component_hash: Arc::new(std::sync::RwLock::new(extractor_component_hash)),
+            #[cfg(feature = "wasm-extractor")]
+            extractor_pool,
             reliable_extractor,
             config,
             api_config,
@@ -1370,6 +2331,10 @@ impl ApplicationContext {
             monitoring_system,
             #[cfg(feature = "fetch")]
             fetch_engine,
+            #[cfg(feature = "fetch")]
+            per_host_fetch_engine,
+            #[cfg(feature = "fetch")]
+            dns_resolver,
             performance_manager,
             auth_config,
             cache_warmer_enabled,
@@ -1387,11 +2352,33 @@ impl ApplicationContext {
             // Resource facade (Phase 4 Sprint 4.4)
             resource_facade,
             trace_backend,
-            #[cfg(feature = "persistence")]
-            persistence_adapter: None, // TODO: Initialize actual persistence adapter when integrated
+            drain,
+            boot,
         })
     }
 
+    /// Client the spider sends page requests through, resolving hosts
+    /// through the shared DNS cache and presenting the TLS profiles
+    ///
+    /// The pipeline applies URL rewrite rules itself to record them with the
+    /// document; the spider's requests get them as middleware.
+    #[cfg(feature = "spider")]
+    fn spider_http_client(&self) -> Result<riptide_reliability::ReliableHttpClient> {
+        use riptide_reliability::{CircuitBreakerPreset, ReliableHttpClient};
+        let mut client = ReliableHttpClient::with_preset(CircuitBreakerPreset::WebScraping)?;
+        #[cfg(feature = "fetch")]
+        if let Some(resolver) = self.dns_resolver.clone() {
+            client = client.with_dns_resolver(resolver)?;
+        }
+        if !self.url_rewriter.is_empty() {
+            client = client.with_middleware(self.url_rewriter.clone());
+        }
+        if !self.tls_profiles.is_empty() {
+            client = client.with_tls_profiles(self.tls_profiles.to_vec())?;
+        }
+        Ok(client)
+    }
+
     /// Initialize facades for an existing base AppState.
     ///
     /// This method takes an existing base AppState (created with `new_base()`) and
@@ -1420,26 +2407,58 @@ impl ApplicationContext {
         tracing::info!("ExtractionFacade initialized successfully");
 
         // Initialize scraper facade
-        self.scraper_facade = Arc::new(
-            riptide_facade::facades::ScraperFacade::new(facade_config.clone())
-                .await
-                .map_err(|e| anyhow::anyhow!("Failed to initialize ScraperFacade: {}", e))?,
-        );
+        let scraper_facade = riptide_facade::facades::ScraperFacade::new(facade_config.clone())
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to initialize ScraperFacade: {}", e))?;
+        #[cfg(feature = "fetch")]
+        let scraper_facade =
+            scraper_facade.with_per_host_engine(self.per_host_fetch_engine.clone());
+        self.scraper_facade = Arc::new(match self.geo_restriction.clone() {
+            Some(restriction) => scraper_facade.with_geo_restriction(restriction),
+            None => scraper_facade,
+        });
         tracing::info!("ScraperFacade initialized successfully");
 
         // Initialize SpiderFacade with Development preset (suitable for local testing)
         #[cfg(feature = "spider")]
         {
+            use riptide_facade::facades::{SpiderFacade, SpiderPreset};
             use url::Url;
             let base_url =
                 Url::parse("https://example.com").expect("Failed to parse default spider base URL");
-            self.spider_facade = match riptide_facade::facades::SpiderFacade::from_preset(
-                riptide_facade::facades::SpiderPreset::Development,
+            self.spider_facade = match SpiderFacade::from_preset_with_robots(
+                SpiderPreset::Development,
                 base_url,
+                self.robots_service.clone(),
             )
             .await
             {
                 Ok(facade) => {
+                    let facade = facade
+                        .with_http_client(Arc::new(self.spider_http_client()?))
+                        .await
+                        .with_bandwidth_limiter(self.bandwidth.clone())
+                        .await;
+                    let store = riptide_persistence::CheckpointManager::open(
+                        &self.api_config.drain.checkpoint_dir,
+                        riptide_persistence::config::StateConfig::default(),
+                    )
+                    .await;
+                    let facade = match store {
+                        Ok(store) => {
+                            let facade = facade
+                                .with_control_store(Arc::new(store), "spider-control")
+                                .await;
+                            if let Err(e) = facade.restore_control().await {
+                                tracing::warn!(error = %e, "Failed to restore spider control state");
+                            }
+                            facade
+                        }
+                        Err(e) => {
+                            tracing::warn!(error = %e, "Spider control state will not be persisted");
+                            facade
+                        }
+                    };
                     tracing::info!("SpiderFacade initialized with Development preset");
                     Some(Arc::new(facade))
                 }
@@ -1454,6 +2473,35 @@ impl ApplicationContext {
         Ok(self)
     }
 
+    /// SHA-256 of the WASM extractor component serving extractions now
+    pub fn component_hash(&self) -> Option<String> {
+        self.extractor_component_hash
+            .read()
+            .ok()
+            .and_then(|hash| hash.clone())
+    }
+
+    /// GeoIP location of the server hosting `url`, when GeoIP is configured
+    pub async fn locate_server(&self, url: &str) -> Option<riptide_types::ports::GeoLocation> {
+        #[cfg(feature = "fetch")]
+        {
+            let geoip = self.geoip.as_ref()?;
+            let parsed = url::Url::parse(url).ok()?;
+            riptide_fetch::geoip::locate_host(geoip.as_ref(), parsed.host_str()?).await
+        }
+        #[cfg(not(feature = "fetch"))]
+        {
+            let _ = url;
+            None
+        }
+    }
+
+    /// Region routing hint for the server hosting `url`
+    pub async fn route_region(&self, url: &str) -> riptide_types::RoutingHint {
+        let location = self.locate_server(url).await;
+        self.api_config.region.route(location.as_ref())
+    }
+
     // ===== Phase 5.2: Transitional Helper Methods =====
     // These helpers allow gradual migration from deprecated metrics
 
@@ -1499,6 +2547,14 @@ impl ApplicationContext {
                 .business_metrics
                 .render_phase_duration
                 .observe(duration_secs),
+            "cache_write" => self
+                .business_metrics
+                .cache_write_phase_duration
+                .observe(duration_secs),
+            "event_emit" => self
+                .business_metrics
+                .event_emit_phase_duration
+                .observe(duration_secs),
             _ => {
                 tracing::warn!("Unknown phase type for timing: {}", phase);
             }
@@ -1844,6 +2900,17 @@ impl ApplicationContext {
 
         #[cfg(feature = "fetch")]
         let fetch_engine = Arc::new(FetchEngine::new().expect("Failed to create fetch engine"));
+        #[cfg(feature = "fetch")]
+        let dns_resolver = None;
+        #[cfg(feature = "fetch")]
+        let per_host_fetch_engine = Arc::new(
+            PerHostFetchEngine::new(
+                riptide_fetch::RetryConfig::default(),
+                riptide_fetch::CircuitBreakerConfig::default(),
+                riptide_fetch::RateLimitConfig::default(),
+            )
+            .expect("Failed to create per-host fetch engine"),
+        );
         let performance_manager =
             Arc::new(PerformanceManager::new().expect("Failed to create performance manager"));
         let auth_config = AuthConfig::default();
@@ -1864,11 +2931,12 @@ impl ApplicationContext {
                 .await
                 .expect("Failed to create extraction facade"),
         );
-        let scraper_facade = Arc::new(
-            riptide_facade::facades::ScraperFacade::new(facade_config.clone())
-                .await
-                .expect("Failed to create scraper facade"),
-        );
+        let scraper_facade = riptide_facade::facades::ScraperFacade::new(facade_config.clone())
+            .await
+            .expect("Failed to create scraper facade");
+        #[cfg(feature = "fetch")]
+        let scraper_facade = scraper_facade.with_per_host_engine(per_host_fetch_engine.clone());
+        let scraper_facade = Arc::new(scraper_facade);
 
         // Initialize SpiderFacade for tests
         #[cfg(feature = "spider")]
@@ -1938,12 +3006,38 @@ impl ApplicationContext {
         #[cfg(not(feature = "workers"))]
         let workers_enabled = false;
         let capabilities = SystemCapabilities::detect("redis", workers_enabled);
+        let drain = Arc::new(DrainController::new(api_config.drain.clone()));
 
         Self {
             http_client,
+            render_cache: Arc::new(RenderCache::new(cache.clone())),
             cache,
+            blob_storage: Arc::new(riptide_types::ports::InMemoryBlobStorage::with_base_url(
+                "/artifacts",
+            )),
+            fetch_archive: None,
+            geoip: None,
+            geo_restriction: None,
+            translation: None,
+            annotation: None,
+            classification: None,
+            media_downloader: None,
+            policy_engine: Arc::new(PolicyEngine::permissive()),
+            experiments: Arc::new(Experiments::disabled()),
+            url_rewriter: Arc::new(riptide_fetch::UrlRewriter::default()),
+            tls_profiles: Arc::default(),
+            gate_verticals: Arc::new(GateVerticals::new(config.gate_thresholds())),
+            meter: Arc::new(Meter::default()),
+            bandwidth: Arc::new(riptide_fetch::BandwidthLimiter::default()),
+            robots_service: Arc::new(
+                riptide_fetch::RobotsService::new(riptide_fetch::RobotsServiceConfig::default())
+                    .expect("Failed to create robots service"),
+            ),
             #[cfg(feature = "extraction")]
             extractor,
+            extractor_component_hash: Arc::default(),
+            #[cfg(feature = "wasm-extractor")]
+            extractor_pool: None,
             reliable_extractor,
             config,
             api_config,
@@ -1967,6 +3061,10 @@ impl ApplicationContext {
             monitoring_system,
             #[cfg(feature = "fetch")]
             fetch_engine,
+            #[cfg(feature = "fetch")]
+            per_host_fetch_engine,
+            #[cfg(feature = "fetch")]
+            dns_resolver,
             performance_manager,
             auth_config,
             cache_warmer_enabled,
@@ -1984,8 +3082,8 @@ impl ApplicationContext {
             // Resource facade (Phase 4 Sprint 4.4)
             resource_facade,
             trace_backend: None,
-            #[cfg(feature = "persistence")]
-            persistence_adapter: None, // TODO: Initialize actual persistence adapter when integrated
+            drain,
+            boot: Arc::new(BootState::new()),
         }
     }
 }
@@ -0,0 +1,420 @@
+//! Graceful drain for zero-downtime deploys
+//!
+//! A drain starts on SIGTERM/Ctrl+C or `POST /admin/drain` and runs once:
+//!
+//! 1. New requests are rejected with `503` and `Retry-After` by
+//!    [`drain_middleware`](crate::middleware::drain_middleware), and the
+//!    worker service stops taking jobs
+//! 2. In-flight requests and jobs get `drain.timeout_secs` to finish
+//! 3. A running spider crawl is stopped and its pending state is
+//!    checkpointed to `drain.checkpoint_dir` so another instance can resume it
+//!
+//! Progress is reported by `GET /admin/drain`, which stays reachable while
+//! draining.
+
+use crate::config::DrainConfig;
+use crate::context::ApplicationContext;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::{Notify, OnceCell};
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+/// Endpoint that starts a drain and reports its progress
+pub const DRAIN_PATH: &str = "/admin/drain";
+
+/// Time a stopping spider crawl gets to release its state for the checkpoint
+#[cfg(feature = "spider")]
+const CHECKPOINT_WAIT: Duration = Duration::from_secs(10);
+
+/// Stage of the drain
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DrainPhase {
+    /// Accepting requests
+    #[default]
+    Serving,
+    /// Rejecting new work and waiting for in-flight work
+    Draining,
+    /// Writing spider checkpoints
+    Checkpointing,
+    /// Finished; the process can exit
+    Drained,
+}
+
+/// Drain progress, as reported by `GET /admin/drain`
+#[derive(Debug, Clone, Serialize)]
+pub struct DrainStatus {
+    /// Current stage
+    pub phase: DrainPhase,
+    /// When the drain started
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    /// Time since the drain started in milliseconds
+    pub elapsed_ms: u64,
+    /// Time in-flight work is given to finish in milliseconds
+    pub budget_ms: u64,
+    /// Requests currently being served
+    pub in_flight_requests: usize,
+    /// Requests rejected since the drain started
+    pub rejected_requests: u64,
+    /// Requests still running when the budget ran out
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abandoned_requests: Option<usize>,
+    /// Worker jobs currently being processed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jobs_in_flight: Option<usize>,
+    /// Worker jobs still running when the budget ran out
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abandoned_jobs: Option<usize>,
+    /// Id of the spider checkpoint written while draining
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spider_checkpoint: Option<String>,
+    /// Problems that did not stop the drain
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Default)]
+struct Progress {
+    phase: DrainPhase,
+    started: Option<(Instant, DateTime<Utc>)>,
+    abandoned_requests: Option<usize>,
+    abandoned_jobs: Option<usize>,
+    spider_checkpoint: Option<String>,
+    warnings: Vec<String>,
+}
+
+/// Tracks in-flight requests and runs the drain sequence
+pub struct DrainController {
+    config: DrainConfig,
+    draining: AtomicBool,
+    in_flight: AtomicUsize,
+    rejected: AtomicU64,
+    /// Signalled when the last in-flight request finishes
+    idle: Notify,
+    progress: Mutex<Progress>,
+    finished: OnceCell<()>,
+}
+
+impl DrainController {
+    /// Create a controller in the serving phase
+    pub fn new(config: DrainConfig) -> Self {
+        Self {
+            config,
+            draining: AtomicBool::new(false),
+            in_flight: AtomicUsize::new(0),
+            rejected: AtomicU64::new(0),
+            idle: Notify::new(),
+            progress: Mutex::new(Progress::default()),
+            finished: OnceCell::new(),
+        }
+    }
+
+    /// Whether a drain has started
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Retry-After for requests rejected while draining
+    pub fn retry_after_secs(&self) -> u64 {
+        self.config.retry_after_secs
+    }
+
+    /// Requests currently being served
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Admit a request, or `None` (counted as rejected) once draining
+    ///
+    /// The request counts as in flight until the guard is dropped.
+    pub fn try_enter(&self) -> Option<InFlightGuard<'_>> {
+        // Count first so a drain that starts concurrently either sees this
+        // request or this request sees the drain
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = InFlightGuard { controller: self };
+        if self.is_draining() {
+            self.rejected.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        Some(guard)
+    }
+
+    /// Run the drain, or wait for the one already running, and return the
+    /// final status
+    pub async fn drain(&self, context: &ApplicationContext) -> DrainStatus {
+        self.finished.get_or_init(|| self.run(context)).await;
+        self.status(context)
+    }
+
+    /// Current drain progress
+    pub fn status(&self, context: &ApplicationContext) -> DrainStatus {
+        let progress = self.lock();
+        DrainStatus {
+            phase: progress.phase,
+            started_at: progress.started.map(|(_, at)| at),
+            elapsed_ms: progress
+                .started
+                .map_or(0, |(started, _)| started.elapsed().as_millis() as u64),
+            budget_ms: self.budget().as_millis() as u64,
+            in_flight_requests: self.in_flight(),
+            rejected_requests: self.rejected.load(Ordering::Relaxed),
+            abandoned_requests: progress.abandoned_requests,
+            jobs_in_flight: jobs_in_flight(context),
+            abandoned_jobs: progress.abandoned_jobs,
+            spider_checkpoint: progress.spider_checkpoint.clone(),
+            warnings: progress.warnings.clone(),
+        }
+    }
+
+    async fn run(&self, context: &ApplicationContext) {
+        let budget = self.budget();
+        let started = self.begin();
+        info!(
+            in_flight_requests = self.in_flight(),
+            budget_ms = budget.as_millis() as u64,
+            "Draining: rejecting new requests and jobs"
+        );
+
+        let (abandoned_requests, abandoned_jobs) = tokio::join!(
+            self.wait_for_requests(started + budget),
+            drain_workers(context, budget)
+        );
+        {
+            let mut progress = self.lock();
+            progress.abandoned_requests = Some(abandoned_requests);
+            progress.abandoned_jobs = abandoned_jobs;
+            progress.phase = DrainPhase::Checkpointing;
+            if abandoned_requests > 0 {
+                warn!(
+                    abandoned_requests,
+                    "Drain budget exhausted with requests still running"
+                );
+                progress.warnings.push(format!(
+                    "{} requests still running when the drain budget ran out",
+                    abandoned_requests
+                ));
+            }
+            if let Some(jobs) = abandoned_jobs.filter(|&jobs| jobs > 0) {
+                progress.warnings.push(format!(
+                    "{} worker jobs still running when the drain budget ran out",
+                    jobs
+                ));
+            }
+        }
+
+        let checkpoint = checkpoint_spider(context, &self.config).await;
+        let mut progress = self.lock();
+        match checkpoint {
+            Ok(checkpoint_id) => progress.spider_checkpoint = checkpoint_id,
+            Err(message) => {
+                warn!(error = %message, "Spider checkpoint not written while draining");
+                progress.warnings.push(message);
+            }
+        }
+        progress.phase = DrainPhase::Drained;
+        info!(
+            elapsed_ms = started.elapsed().as_millis() as u64,
+            abandoned_requests,
+            abandoned_jobs = ?abandoned_jobs,
+            spider_checkpoint = ?progress.spider_checkpoint,
+            "Drain complete"
+        );
+    }
+
+    /// Start rejecting new requests, returning when the drain started
+    ///
+    /// [`drain`](Self::drain) does this itself; calling it first makes the
+    /// drain visible in [`status`](Self::status) right away.
+    pub fn begin(&self) -> Instant {
+        let mut progress = self.lock();
+        let (started, _) = *progress
+            .started
+            .get_or_insert_with(|| (Instant::now(), Utc::now()));
+        if progress.phase == DrainPhase::Serving {
+            progress.phase = DrainPhase::Draining;
+        }
+        self.draining.store(true, Ordering::SeqCst);
+        started
+    }
+
+    /// Wait until no requests are in flight or `deadline` passes
+    ///
+    /// Returns the number of requests still in flight.
+    async fn wait_for_requests(&self, deadline: Instant) -> usize {
+        loop {
+            // Register before checking so the last guard's wakeup is not missed
+            let idle = self.idle.notified();
+            let in_flight = self.in_flight();
+            if in_flight == 0 {
+                return 0;
+            }
+            if tokio::time::timeout_at(deadline, idle).await.is_err() {
+                return self.in_flight();
+            }
+        }
+    }
+
+    fn budget(&self) -> Duration {
+        Duration::from_secs(self.config.timeout_secs)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Progress> {
+        self.progress.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Marks a request as in flight while alive
+pub struct InFlightGuard<'a> {
+    controller: &'a DrainController,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        if self.controller.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.controller.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(feature = "workers")]
+fn jobs_in_flight(context: &ApplicationContext) -> Option<usize> {
+    context
+        .worker_service
+        .as_ref()
+        .map(|worker_service| worker_service.jobs_in_flight())
+}
+
+#[cfg(not(feature = "workers"))]
+fn jobs_in_flight(_context: &ApplicationContext) -> Option<usize> {
+    None
+}
+
+/// Drain the worker service, returning the jobs left unfinished
+#[cfg(feature = "workers")]
+async fn drain_workers(context: &ApplicationContext, budget: Duration) -> Option<usize> {
+    let worker_service = context.worker_service.as_ref()?;
+    Some(worker_service.drain(budget).await.jobs_unfinished)
+}
+
+#[cfg(not(feature = "workers"))]
+async fn drain_workers(_context: &ApplicationContext, _budget: Duration) -> Option<usize> {
+    None
+}
+
+/// Checkpoint the spider if it has pending URLs, returning the checkpoint id
+#[cfg(feature = "spider")]
+async fn checkpoint_spider(
+    context: &ApplicationContext,
+    config: &DrainConfig,
+) -> Result<Option<String>, String> {
+    let Some(spider_facade) = context.spider_facade.as_ref() else {
+        return Ok(None);
+    };
+    let frontier = spider_facade
+        .inspect_frontier(0)
+        .await
+        .map_err(|e| format!("Failed to inspect spider frontier: {}", e))?;
+    if frontier.total_pending == 0 {
+        return Ok(None);
+    }
+
+    // A crawl still running holds the spider; stopping it keeps the frontier
+    if spider_facade.stop().await.is_ok() {
+        info!("Stopped running spider crawl for the drain checkpoint");
+    }
+    let store = riptide_persistence::CheckpointManager::open(
+        &config.checkpoint_dir,
+        riptide_persistence::config::StateConfig::default(),
+    )
+    .await
+    .map_err(|e| format!("Failed to open checkpoint directory: {}", e))?;
+    let checkpoint_id = format!("drain-{}", Utc::now().format("%Y%m%dT%H%M%S%.3fZ"));
+    match spider_facade
+        .checkpoint_within(&store, &checkpoint_id, CHECKPOINT_WAIT)
+        .await
+    {
+        Ok(true) => {
+            info!(
+                checkpoint_id = %checkpoint_id,
+                pending = frontier.total_pending,
+                "Spider state checkpointed"
+            );
+            Ok(Some(checkpoint_id))
+        }
+        Ok(false) => Err(format!(
+            "Spider crawl still running after {}s; checkpoint skipped",
+            CHECKPOINT_WAIT.as_secs()
+        )),
+        Err(e) => Err(format!("Failed to checkpoint spider: {}", e)),
+    }
+}
+
+#[cfg(not(feature = "spider"))]
+async fn checkpoint_spider(
+    _context: &ApplicationContext,
+    _config: &DrainConfig,
+) -> Result<Option<String>, String> {
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn controller() -> DrainController {
+        DrainController::new(DrainConfig::default())
+    }
+
+    #[test]
+    fn test_requests_rejected_once_draining() {
+        let drain = controller();
+        let guard = drain.try_enter();
+        assert!(guard.is_some());
+        assert_eq!(drain.in_flight(), 1);
+
+        drain.begin();
+        assert!(drain.try_enter().is_none());
+        assert_eq!(drain.in_flight(), 1);
+        assert_eq!(drain.rejected.load(Ordering::Relaxed), 1);
+
+        drop(guard);
+        assert_eq!(drain.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_requests_returns_when_idle() {
+        let drain = std::sync::Arc::new(controller());
+        let guard_owner = std::sync::Arc::clone(&drain);
+        let (entered, wait_entered) = tokio::sync::oneshot::channel();
+        let request = tokio::spawn(async move {
+            let _guard = guard_owner.try_enter().unwrap();
+            entered.send(()).unwrap();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        });
+        wait_entered.await.unwrap();
+
+        drain.begin();
+        let remaining = drain
+            .wait_for_requests(Instant::now() + Duration::from_secs(5))
+            .await;
+        assert_eq!(remaining, 0);
+        request.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_requests_stops_at_deadline() {
+        let drain = controller();
+        let _guard = drain.try_enter().unwrap();
+        drain.begin();
+
+        let remaining = drain
+            .wait_for_requests(Instant::now() + Duration::from_millis(10))
+            .await;
+        assert_eq!(remaining, 1);
+    }
+}
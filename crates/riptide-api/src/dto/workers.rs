@@ -7,7 +7,8 @@
 //! Not yet connected to handlers but required for future implementation
 
 use chrono::{DateTime, Utc};
-use riptide_workers::{Job, JobPriority, JobStatus, JobType, ScheduledJob};
+use riptide_types::ErrorCode;
+use riptide_workers::{Job, JobPriority, JobResult, JobStatus, JobType, ScheduledJob};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -63,6 +64,11 @@ pub enum JobTypeRequest {
         url: String,
         options: Option<riptide_types::config::CrawlOptions>,
     },
+    #[serde(rename = "reprocess")]
+    Reprocess {
+        urls: Vec<String>,
+        mode: Option<String>,
+    },
     #[serde(rename = "maintenance")]
     Maintenance {
         task_type: String,
@@ -80,6 +86,7 @@ impl From<JobTypeRequest> for JobType {
         match request {
             JobTypeRequest::BatchCrawl { urls, options } => JobType::BatchCrawl { urls, options },
             JobTypeRequest::SingleCrawl { url, options } => JobType::SingleCrawl { url, options },
+            JobTypeRequest::Reprocess { urls, mode } => JobType::Reprocess { urls, mode },
             JobTypeRequest::Maintenance {
                 task_type,
                 parameters,
@@ -194,11 +201,28 @@ pub struct JobResultResponse {
     pub success: bool,
     pub data: Option<serde_json::Value>,
     pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<ErrorCode>,
     pub processing_time_ms: u64,
     pub worker_id: String,
     pub completed_at: DateTime<Utc>,
 }
 
+impl From<JobResult> for JobResultResponse {
+    fn from(result: JobResult) -> Self {
+        Self {
+            job_id: result.job_id,
+            success: result.success,
+            data: result.data,
+            error: result.error,
+            error_code: result.error_code,
+            processing_time_ms: result.processing_time_ms,
+            worker_id: result.worker_id,
+            completed_at: result.completed_at,
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Serialize, Debug)]
 pub struct QueueStatsResponse {
@@ -319,6 +343,7 @@ pub fn format_job_type(job_type: &JobType) -> String {
         JobType::BatchCrawl { .. } => "batch_crawl".to_string(),
         JobType::SingleCrawl { .. } => "single_crawl".to_string(),
         JobType::PdfExtraction { .. } => "pdf_extraction".to_string(),
+        JobType::Reprocess { .. } => "reprocess".to_string(),
         JobType::Maintenance { task_type, .. } => format!("maintenance:{}", task_type),
         JobType::Custom { job_name, .. } => format!("custom:{}", job_name),
     }
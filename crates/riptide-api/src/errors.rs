@@ -3,9 +3,19 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use riptide_types::{ErrorCode, HasErrorCode};
 use serde_json::json;
 use thiserror::Error;
 
+/// Stage of request processing an operation timed out in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeoutPhase {
+    /// Fetching the target page
+    Fetch,
+    /// Any other operation (rendering, resource acquisition, storage, ...)
+    Other,
+}
+
 /// Comprehensive error types for the RipTide API with appropriate HTTP status codes.
 ///
 /// This enum covers all error scenarios that can occur during crawling operations,
@@ -75,7 +85,11 @@ pub enum ApiError {
 
     /// Timeout errors (408 Request Timeout)
     #[error("Operation timed out: {operation} - {message}")]
-    TimeoutError { operation: String, message: String },
+    TimeoutError {
+        operation: String,
+        phase: TimeoutPhase,
+        message: String,
+    },
 
     /// Resource not found errors (404 Not Found)
     #[error("Resource not found: {resource}")]
@@ -117,6 +131,18 @@ pub enum ApiError {
     /// Used when optional features are disabled at compile time
     #[error("Feature '{feature}' is not enabled in this build")]
     FeatureNotEnabled { feature: String },
+
+    /// URL denied by the tenant's crawl policy (403 Forbidden)
+    #[error("Policy denied {url} ({rule}): {reason}")]
+    PolicyDenied {
+        url: String,
+        rule: String,
+        reason: String,
+    },
+
+    /// URL not crawled because the crawl's budget ran out (429 Too Many Requests)
+    #[error("Crawl budget exceeded: {budget}")]
+    BudgetExceeded { budget: String },
 }
 
 impl ApiError {
@@ -184,6 +210,16 @@ impl ApiError {
     pub fn timeout<S1: Into<String>, S2: Into<String>>(operation: S1, message: S2) -> Self {
         Self::TimeoutError {
             operation: operation.into(),
+            phase: TimeoutPhase::Other,
+            message: message.into(),
+        }
+    }
+
+    /// Create a timeout error for fetching the target page.
+    pub fn fetch_timeout<S1: Into<String>, S2: Into<String>>(operation: S1, message: S2) -> Self {
+        Self::TimeoutError {
+            operation: operation.into(),
+            phase: TimeoutPhase::Fetch,
             message: message.into(),
         }
     }
@@ -248,6 +284,8 @@ impl ApiError {
             ApiError::InvalidParameter { .. } => StatusCode::BAD_REQUEST,
             ApiError::FeatureNotEnabled { .. } => StatusCode::NOT_IMPLEMENTED,
             ApiError::RateLimitExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::PolicyDenied { .. } => StatusCode::FORBIDDEN,
+            ApiError::BudgetExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
         }
     }
 
@@ -275,18 +313,53 @@ impl ApiError {
             ApiError::InvalidParameter { .. } => "invalid_parameter",
             ApiError::FeatureNotEnabled { .. } => "feature_not_enabled",
             ApiError::RateLimitExceeded { .. } => "rate_limit_exceeded",
+            ApiError::PolicyDenied { .. } => "policy_denied",
+            ApiError::BudgetExceeded { .. } => "budget_exceeded",
+        }
+    }
+
+    /// Get the machine-readable error code for this error.
+    ///
+    /// Timeouts in the [`TimeoutPhase::Fetch`] phase report
+    /// `RIPTIDE_FETCH_TIMEOUT`; other timeouts report `RIPTIDE_TIMEOUT`.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ApiError::ValidationError { .. } => ErrorCode::InvalidRequest,
+            ApiError::InvalidUrl { .. } => ErrorCode::InvalidUrl,
+            ApiError::RateLimited { .. } => ErrorCode::RateLimited,
+            ApiError::AuthenticationError { .. } => ErrorCode::Unauthorized,
+            ApiError::FetchError { .. } => ErrorCode::FetchFailed,
+            ApiError::CacheError { .. } => ErrorCode::CacheUnavailable,
+            ApiError::ExtractionError { .. } => ErrorCode::ExtractionFailed,
+            ApiError::RoutingError { .. } => ErrorCode::GateBlocked,
+            ApiError::PipelineError { .. } => ErrorCode::Internal,
+            ApiError::ConfigError { .. } => ErrorCode::ConfigInvalid,
+            ApiError::DependencyError { .. } => ErrorCode::DependencyUnavailable,
+            ApiError::InternalError { .. } => ErrorCode::Internal,
+            ApiError::TimeoutError {
+                phase: TimeoutPhase::Fetch,
+                ..
+            } => ErrorCode::FetchTimeout,
+            ApiError::TimeoutError { .. } => ErrorCode::Timeout,
+            ApiError::NotFound { .. } => ErrorCode::NotFound,
+            ApiError::PayloadTooLarge { .. } => ErrorCode::PayloadTooLarge,
+            ApiError::InvalidContentType { .. } => ErrorCode::UnsupportedMediaType,
+            ApiError::MissingRequiredHeader { .. } => ErrorCode::InvalidRequest,
+            ApiError::InvalidHeaderValue { .. } => ErrorCode::InvalidRequest,
+            ApiError::InvalidParameter { .. } => ErrorCode::InvalidRequest,
+            ApiError::FeatureNotEnabled { .. } => ErrorCode::FeatureDisabled,
+            ApiError::RateLimitExceeded { .. } => ErrorCode::RateLimited,
+            ApiError::PolicyDenied { .. } => ErrorCode::PolicyDenied,
+            ApiError::BudgetExceeded { .. } => ErrorCode::ResourceExhausted,
         }
     }
 
     /// Check if this error is retryable.
+    ///
+    /// Derived from the error's [`ErrorCode`], so responses and job results
+    /// never disagree about it.
     pub fn is_retryable(&self) -> bool {
-        matches!(
-            self,
-            ApiError::TimeoutError { .. }
-                | ApiError::CacheError { .. }
-                | ApiError::DependencyError { .. }
-                | ApiError::FetchError { .. }
-        )
+        self.code().is_retryable()
     }
 }
 
@@ -294,6 +367,7 @@ impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let status = self.status_code();
         let error_type = self.error_type();
+        let code = self.code();
         let message = self.to_string();
 
         // Log the error for internal monitoring
@@ -303,6 +377,7 @@ impl IntoResponse for ApiError {
             | StatusCode::BAD_GATEWAY => {
                 tracing::error!(
                     error_type = error_type,
+                    code = %code,
                     message = %message,
                     "API error occurred"
                 );
@@ -310,6 +385,7 @@ impl IntoResponse for ApiError {
             StatusCode::BAD_REQUEST | StatusCode::NOT_FOUND => {
                 tracing::warn!(
                     error_type = error_type,
+                    code = %code,
                     message = %message,
                     "Client error occurred"
                 );
@@ -317,6 +393,7 @@ impl IntoResponse for ApiError {
             _ => {
                 tracing::info!(
                     error_type = error_type,
+                    code = %code,
                     message = %message,
                     "API error occurred"
                 );
@@ -326,6 +403,7 @@ impl IntoResponse for ApiError {
         let body = Json(json!({
             "error": {
                 "type": error_type,
+                "code": code,
                 "message": message,
                 "retryable": self.is_retryable(),
                 "status": status.as_u16()
@@ -336,6 +414,12 @@ impl IntoResponse for ApiError {
     }
 }
 
+impl HasErrorCode for ApiError {
+    fn error_code(&self) -> ErrorCode {
+        self.code()
+    }
+}
+
 /// Convert common error types to ApiError
 impl From<anyhow::Error> for ApiError {
     fn from(err: anyhow::Error) -> Self {
@@ -348,10 +432,7 @@ impl From<anyhow::Error> for ApiError {
 impl From<reqwest::Error> for ApiError {
     fn from(err: reqwest::Error) -> Self {
         if err.is_timeout() {
-            ApiError::TimeoutError {
-                operation: "http_request".to_string(),
-                message: err.to_string(),
-            }
+            ApiError::fetch_timeout("http_request", err.to_string())
         } else if err.is_connect() {
             ApiError::FetchError {
                 url: err.url().map(|u| u.to_string()).unwrap_or_default(),
@@ -407,10 +488,7 @@ impl From<riptide_facade::RiptideError> for ApiError {
                 url,
                 message: source.to_string(),
             },
-            RiptideError::Timeout => ApiError::TimeoutError {
-                operation: "request".to_string(),
-                message: "Operation timed out".to_string(),
-            },
+            RiptideError::Timeout => ApiError::timeout("request", "Operation timed out"),
             RiptideError::Validation(msg) => ApiError::ValidationError { message: msg },
             RiptideError::Cache(msg) => ApiError::CacheError { message: msg },
             RiptideError::NotFound(msg) => ApiError::NotFound { resource: msg },
@@ -461,5 +539,60 @@ impl From<crate::resource_manager::errors::ResourceManagerError> for ApiError {
     }
 }
 
+impl From<riptide_security::SecurityError> for ApiError {
+    fn from(err: riptide_security::SecurityError) -> Self {
+        use riptide_security::SecurityError;
+        match err {
+            SecurityError::PolicyDenied { url, rule, reason } => ApiError::PolicyDenied {
+                url,
+                rule: rule.to_string(),
+                reason,
+            },
+            SecurityError::InvalidApiKey(msg)
+            | SecurityError::ApiKeyExpired(msg)
+            | SecurityError::InsufficientPermissions(msg) => {
+                ApiError::AuthenticationError { message: msg }
+            }
+            SecurityError::RateLimitExceeded(msg) | SecurityError::BudgetLimitExceeded(msg) => {
+                ApiError::RateLimited { message: msg }
+            }
+            SecurityError::ConfigurationError(msg) => ApiError::ConfigError { message: msg },
+            other => ApiError::InternalError {
+                message: other.to_string(),
+            },
+        }
+    }
+}
+
 /// Result type alias for API operations.
 pub type ApiResult<T> = Result<T, ApiError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(
+            ApiError::fetch_timeout("content_fetch", "slow").code(),
+            ErrorCode::FetchTimeout
+        );
+        assert_eq!(
+            ApiError::timeout("fetch_resources", "slow").code(),
+            ErrorCode::Timeout
+        );
+        assert_eq!(
+            ApiError::RoutingError {
+                message: "blocked".to_string()
+            }
+            .code(),
+            ErrorCode::GateBlocked
+        );
+        assert!(ApiError::rate_limited("slow down").is_retryable());
+        assert!(!ApiError::feature_not_enabled("pdf").is_retryable());
+        assert_eq!(
+            ApiError::fetch("https://example.com", "refused").code(),
+            ErrorCode::FetchFailed
+        );
+    }
+}
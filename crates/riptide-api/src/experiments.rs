@@ -0,0 +1,270 @@
+//! A/B experiments for extraction strategies
+//!
+//! Running experiments come from the [`FeatureFlags`] port. The pipeline
+//! asks for an assignment per URL, applies the treatment arm's alternative
+//! extractor or gate thresholds, tags the document's provenance with the
+//! experiment id and arm, and records the outcome here. The comparison
+//! report (quality, latency, headless rate per arm) is served by
+//! `GET /admin/experiments`.
+
+use riptide_types::ports::{
+    ContentExtractor, ExperimentArm, ExperimentAssignment, ExperimentTag, FeatureFlags,
+    InMemoryFeatureFlags,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+use tracing::warn;
+
+/// Outcome of one request processed under an experiment
+#[derive(Debug, Clone, Copy)]
+pub struct ExperimentOutcome {
+    /// Extraction quality (0.0-1.0)
+    pub quality: f64,
+    /// End-to-end processing time
+    pub latency_ms: u64,
+    /// Whether the gate sent the page to headless rendering
+    pub headless: bool,
+}
+
+#[derive(Debug, Default)]
+struct ArmAccumulator {
+    requests: u64,
+    failures: u64,
+    cache_hits: u64,
+    quality_sum: f64,
+    latency_ms_sum: u64,
+    headless: u64,
+}
+
+/// Aggregated results of one experiment arm
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ArmReport {
+    /// Successfully processed requests
+    pub requests: u64,
+    /// Requests that failed during extraction
+    pub failures: u64,
+    /// Requests served from the arm's cache, not part of the means
+    pub cache_hits: u64,
+    pub mean_quality: f64,
+    pub mean_latency_ms: f64,
+    /// Share of requests the gate sent to headless rendering
+    pub headless_rate: f64,
+}
+
+impl From<&ArmAccumulator> for ArmReport {
+    // Safe conversion: request counts and latency sums stay far below 2^52
+    #[allow(clippy::cast_precision_loss)]
+    fn from(acc: &ArmAccumulator) -> Self {
+        let mean = |sum: f64| {
+            if acc.requests == 0 {
+                0.0
+            } else {
+                sum / acc.requests as f64
+            }
+        };
+        Self {
+            requests: acc.requests,
+            failures: acc.failures,
+            cache_hits: acc.cache_hits,
+            mean_quality: mean(acc.quality_sum),
+            mean_latency_ms: mean(acc.latency_ms_sum as f64),
+            headless_rate: mean(acc.headless as f64),
+        }
+    }
+}
+
+/// Comparison of the control and treatment arms of an experiment
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExperimentReport {
+    pub experiment_id: String,
+    pub control: ArmReport,
+    pub treatment: ArmReport,
+    /// Treatment minus control mean quality
+    pub quality_delta: f64,
+    /// Treatment minus control mean latency
+    pub latency_delta_ms: f64,
+    /// Treatment minus control headless rate
+    pub headless_rate_delta: f64,
+}
+
+/// Experiment assignment, alternative extractors and per-arm statistics
+pub struct Experiments {
+    flags: Arc<dyn FeatureFlags>,
+    extractors: HashMap<String, Arc<dyn ContentExtractor>>,
+    stats: Mutex<HashMap<ExperimentTag, ArmAccumulator>>,
+}
+
+impl Experiments {
+    /// Run the experiments configured in `flags`
+    pub fn new(flags: Arc<dyn FeatureFlags>) -> Self {
+        Self {
+            flags,
+            extractors: HashMap::new(),
+            stats: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// No experiments running
+    pub fn disabled() -> Self {
+        Self::new(Arc::new(InMemoryFeatureFlags::new()))
+    }
+
+    /// Make an alternative extractor available to treatment arms
+    pub fn with_extractor(
+        mut self,
+        name: impl Into<String>,
+        extractor: Arc<dyn ContentExtractor>,
+    ) -> Self {
+        self.extractors.insert(name.into(), extractor);
+        self
+    }
+
+    /// Assign a URL to an experiment arm
+    ///
+    /// Flag backend failures leave the URL out of all experiments, as do
+    /// treatment arms naming an extractor that is not registered.
+    pub async fn assign(&self, url: &str) -> Option<ExperimentAssignment> {
+        let assignment = match self.flags.assign(url).await {
+            Ok(assignment) => assignment?,
+            Err(e) => {
+                warn!(url = %url, error = %e, "Experiment assignment failed");
+                return None;
+            }
+        };
+        if let Some(name) = assignment.extractor() {
+            if !self.extractors.contains_key(name) {
+                warn!(
+                    experiment = %assignment.experiment_id,
+                    extractor = %name,
+                    "Experiment names an unknown extractor, skipping"
+                );
+                return None;
+            }
+        }
+        Some(assignment)
+    }
+
+    /// Alternative extractor registered under `name`
+    pub fn extractor(&self, name: &str) -> Option<Arc<dyn ContentExtractor>> {
+        self.extractors.get(name).cloned()
+    }
+
+    /// Record a processed request
+    pub fn record(&self, tag: &ExperimentTag, outcome: ExperimentOutcome) {
+        let mut stats = self.lock();
+        let acc = stats.entry(tag.clone()).or_default();
+        acc.requests += 1;
+        acc.quality_sum += outcome.quality;
+        acc.latency_ms_sum = acc.latency_ms_sum.saturating_add(outcome.latency_ms);
+        if outcome.headless {
+            acc.headless += 1;
+        }
+    }
+
+    /// Record a request that failed during extraction
+    pub fn record_failure(&self, tag: &ExperimentTag) {
+        self.lock().entry(tag.clone()).or_default().failures += 1;
+    }
+
+    /// Record a request served from the cache
+    ///
+    /// Counted apart from processed requests, so cached latencies do not
+    /// skew the comparison.
+    pub fn record_cache_hit(&self, tag: &ExperimentTag) {
+        self.lock().entry(tag.clone()).or_default().cache_hits += 1;
+    }
+
+    /// Comparison report of every experiment with recorded traffic
+    pub fn report(&self) -> Vec<ExperimentReport> {
+        let stats = self.lock();
+        let mut arms: BTreeMap<&str, (ArmReport, ArmReport)> = BTreeMap::new();
+        for (tag, acc) in stats.iter() {
+            let entry = arms.entry(tag.experiment_id.as_str()).or_default();
+            match tag.arm {
+                ExperimentArm::Control => entry.0 = ArmReport::from(acc),
+                ExperimentArm::Treatment => entry.1 = ArmReport::from(acc),
+            }
+        }
+
+        arms.into_iter()
+            .map(|(id, (control, treatment))| ExperimentReport {
+                experiment_id: id.to_string(),
+                quality_delta: treatment.mean_quality - control.mean_quality,
+                latency_delta_ms: treatment.mean_latency_ms - control.mean_latency_ms,
+                headless_rate_delta: treatment.headless_rate - control.headless_rate,
+                control,
+                treatment,
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<ExperimentTag, ArmAccumulator>> {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::ports::{Experiment, ExperimentVariant};
+
+    fn tag(arm: ExperimentArm) -> ExperimentTag {
+        ExperimentTag {
+            experiment_id: "gate".to_string(),
+            arm,
+        }
+    }
+
+    #[test]
+    fn test_report_compares_arms() {
+        let experiments = Experiments::disabled();
+        for (quality, latency_ms, headless) in [(0.6, 100, true), (0.8, 300, false)] {
+            experiments.record(
+                &tag(ExperimentArm::Control),
+                ExperimentOutcome {
+                    quality,
+                    latency_ms,
+                    headless,
+                },
+            );
+        }
+        experiments.record(
+            &tag(ExperimentArm::Treatment),
+            ExperimentOutcome {
+                quality: 0.9,
+                latency_ms: 150,
+                headless: false,
+            },
+        );
+        experiments.record_failure(&tag(ExperimentArm::Treatment));
+        experiments.record_cache_hit(&tag(ExperimentArm::Control));
+
+        let report = experiments.report();
+        assert_eq!(report.len(), 1);
+        let report = &report[0];
+        assert_eq!(report.control.requests, 2);
+        assert_eq!(report.control.cache_hits, 1);
+        assert!((report.control.mean_quality - 0.7).abs() < 1e-9);
+        assert!((report.control.headless_rate - 0.5).abs() < 1e-9);
+        assert_eq!(report.treatment.failures, 1);
+        assert!((report.quality_delta - 0.2).abs() < 1e-9);
+        assert!((report.latency_delta_ms + 50.0).abs() < 1e-9);
+        assert!((report.headless_rate_delta + 0.5).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_extractor_skips_experiment() {
+        let flags = InMemoryFeatureFlags::with_experiments(vec![Experiment {
+            id: "llm".to_string(),
+            domains: Vec::new(),
+            traffic_percent: 100,
+            variant: ExperimentVariant {
+                extractor: Some("llm".to_string()),
+                ..ExperimentVariant::default()
+            },
+        }]);
+        let experiments = Experiments::new(Arc::new(flags));
+        assert!(experiments.assign("https://example.com/").await.is_none());
+    }
+}
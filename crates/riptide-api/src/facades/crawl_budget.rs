@@ -0,0 +1,242 @@
+//! Per-crawl resource budgets
+//!
+//! A [`CrawlBudgetEnforcer`] tracks what a batch crawl has used against the
+//! limits of its [`CrawlBudget`]: wall time, headless render time, bytes
+//! received on the wire and WASM extraction fuel. Every URL runs through
+//! [`CrawlBudgetEnforcer::run`]; once a limit is reached, URLs not yet
+//! started fail with [`ApiError::BudgetExceeded`], and when the wall time
+//! runs out, URLs in flight are cut off as well.
+//!
+//! The first limit that stops a URL is the binding one and is reported in
+//! the crawl summary, together with what the crawl used.
+
+use crate::errors::{ApiError, ApiResult};
+use crate::pipeline::PipelineResult;
+use riptide_extraction::wasm_fuel::FuelMeter;
+use riptide_types::config::CrawlBudget;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Limit of a [`CrawlBudget`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrawlBudgetKind {
+    WallTime,
+    RenderTime,
+    Bytes,
+    WasmFuel,
+}
+
+impl CrawlBudgetKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::WallTime => "wall_time",
+            Self::RenderTime => "render_time",
+            Self::Bytes => "bytes",
+            Self::WasmFuel => "wasm_fuel",
+        }
+    }
+}
+
+/// Budget section of a crawl summary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrawlBudgetReport {
+    /// Limits the crawl ran with
+    pub limits: CrawlBudget,
+    pub wall_time_secs: f64,
+    pub render_secs: f64,
+    pub bytes_fetched: u64,
+    pub wasm_fuel: u64,
+    /// URLs skipped or cut off because a limit was reached
+    pub urls_stopped: usize,
+    /// Limit that ended the crawl early, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminated_by: Option<CrawlBudgetKind>,
+}
+
+/// Tracks and enforces the budget of one crawl
+#[derive(Debug)]
+pub struct CrawlBudgetEnforcer {
+    limits: CrawlBudget,
+    started: Instant,
+    render_ms: AtomicU64,
+    bytes: AtomicU64,
+    fuel: Arc<FuelMeter>,
+    stopped: AtomicUsize,
+    binding: OnceLock<CrawlBudgetKind>,
+}
+
+impl CrawlBudgetEnforcer {
+    /// Start tracking a crawl limited by `limits`
+    pub fn new(limits: CrawlBudget) -> Self {
+        Self {
+            fuel: Arc::new(FuelMeter::new(limits.max_wasm_fuel)),
+            limits,
+            started: Instant::now(),
+            render_ms: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            stopped: AtomicUsize::new(0),
+            binding: OnceLock::new(),
+        }
+    }
+
+    /// Run the pipeline `work` for one URL within the budget
+    ///
+    /// # Errors
+    ///
+    /// Returns `ApiError::BudgetExceeded` when a limit was reached before
+    /// the URL started, or the wall time ran out while it was in flight.
+    pub async fn run<F>(&self, work: F) -> ApiResult<PipelineResult>
+    where
+        F: Future<Output = ApiResult<PipelineResult>>,
+    {
+        if let Some(kind) = self.exhausted() {
+            return Err(self.stop(kind));
+        }
+
+        let work = self.fuel.clone().scope(work);
+        let result = match self.remaining_wall_time() {
+            Some(remaining) => match tokio::time::timeout(remaining, work).await {
+                Ok(result) => result,
+                Err(_) => return Err(self.stop(CrawlBudgetKind::WallTime)),
+            },
+            None => work.await,
+        };
+
+        if let Ok(result) = &result {
+            self.bytes
+                .fetch_add(result.timings.fetch_bytes.unwrap_or(0), Ordering::Relaxed);
+            self.render_ms
+                .fetch_add(result.timings.render_ms.unwrap_or(0), Ordering::Relaxed);
+        }
+        result
+    }
+
+    /// What the crawl used so far and the limit that ended it, if any
+    pub fn report(&self) -> CrawlBudgetReport {
+        CrawlBudgetReport {
+            limits: self.limits.clone(),
+            wall_time_secs: self.started.elapsed().as_secs_f64(),
+            render_secs: self.render_ms.load(Ordering::Relaxed) as f64 / 1000.0,
+            bytes_fetched: self.bytes.load(Ordering::Relaxed),
+            wasm_fuel: self.fuel.consumed(),
+            urls_stopped: self.stopped.load(Ordering::Relaxed),
+            terminated_by: self.binding.get().copied(),
+        }
+    }
+
+    /// First limit reached, checked in the order of [`CrawlBudgetKind`]
+    fn exhausted(&self) -> Option<CrawlBudgetKind> {
+        let limits = &self.limits;
+        if self.remaining_wall_time() == Some(Duration::ZERO) {
+            return Some(CrawlBudgetKind::WallTime);
+        }
+        let render_secs = self.render_ms.load(Ordering::Relaxed) as f64 / 1000.0;
+        if limits.max_render_secs.is_some_and(|max| render_secs >= max) {
+            return Some(CrawlBudgetKind::RenderTime);
+        }
+        let bytes = self.bytes.load(Ordering::Relaxed);
+        if limits.max_bytes.is_some_and(|max| bytes >= max) {
+            return Some(CrawlBudgetKind::Bytes);
+        }
+        if self.fuel.is_exhausted() {
+            return Some(CrawlBudgetKind::WasmFuel);
+        }
+        None
+    }
+
+    fn remaining_wall_time(&self) -> Option<Duration> {
+        let max = Duration::try_from_secs_f64(self.limits.max_wall_time_secs?).ok()?;
+        Some(max.saturating_sub(self.started.elapsed()))
+    }
+
+    /// Count a URL stopped by `kind`; the first limit to stop one binds
+    fn stop(&self, kind: CrawlBudgetKind) -> ApiError {
+        self.stopped.fetch_add(1, Ordering::Relaxed);
+        self.binding.get_or_init(|| kind);
+        ApiError::BudgetExceeded {
+            budget: kind.as_str().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::{CacheStatus, ExtractedDoc, PhaseTimings};
+
+    /// A fetched page whose response took `fetch_bytes` on the wire
+    fn page(fetch_bytes: u64, render_ms: Option<u64>) -> ApiResult<PipelineResult> {
+        Ok(PipelineResult {
+            document: ExtractedDoc::default(),
+            from_cache: false,
+            cache_status: CacheStatus::Miss,
+            gate_decision: "raw".to_string(),
+            quality_score: 0.8,
+            processing_time_ms: 1,
+            cache_key: String::new(),
+            http_status: 200,
+            artifacts: Vec::new(),
+            timings: PhaseTimings {
+                render_ms,
+                fetch_bytes: Some(fetch_bytes),
+                ..Default::default()
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn test_first_limit_reached_binds() {
+        let enforcer = CrawlBudgetEnforcer::new(CrawlBudget {
+            max_bytes: Some(10),
+            max_render_secs: Some(60.0),
+            ..Default::default()
+        });
+
+        assert!(enforcer.run(async { page(7, Some(500)) }).await.is_ok());
+        assert!(enforcer.run(async { page(7, None) }).await.is_ok());
+        let err = enforcer.run(async { page(13, None) }).await.unwrap_err();
+        assert!(matches!(err, ApiError::BudgetExceeded { ref budget } if budget == "bytes"));
+        assert!(err.is_retryable());
+
+        let report = enforcer.report();
+        assert_eq!(report.bytes_fetched, 14);
+        assert_eq!(report.render_secs, 0.5);
+        assert_eq!(report.urls_stopped, 1);
+        assert_eq!(report.terminated_by, Some(CrawlBudgetKind::Bytes));
+    }
+
+    #[tokio::test]
+    async fn test_wall_time_cuts_off_urls_in_flight() {
+        let enforcer = CrawlBudgetEnforcer::new(CrawlBudget {
+            max_wall_time_secs: Some(0.05),
+            ..Default::default()
+        });
+
+        let slow = async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            page(8, None)
+        };
+        assert!(enforcer.run(slow).await.is_err());
+        assert!(enforcer.run(async { page(7, None) }).await.is_err());
+
+        let report = enforcer.report();
+        assert_eq!(report.urls_stopped, 2);
+        assert_eq!(report.bytes_fetched, 0);
+        assert_eq!(report.terminated_by, Some(CrawlBudgetKind::WallTime));
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_budget_only_measures() {
+        let enforcer = CrawlBudgetEnforcer::new(CrawlBudget::default());
+        for _ in 0..3 {
+            assert!(enforcer.run(async { page(4, Some(10)) }).await.is_ok());
+        }
+        let report = enforcer.report();
+        assert_eq!(report.bytes_fetched, 12);
+        assert_eq!(report.terminated_by, None);
+    }
+}
@@ -6,20 +6,30 @@
 //! - Statistics calculation
 //! - Chunking application
 //! - Spider mode routing
+//! - WARC archiving of fetched pages
+//! - Differential crawls (`changed_since`)
+//! - Failure manifests and retries of failed URLs
+//! - Per-crawl resource budgets
 //!
 //! **Purpose**: Reduce handler LOC from 395 to ~60 by moving all business logic here.
 
+use crate::change_tracking::ChangeTracker;
 use crate::context::ApplicationContext;
-use crate::errors::ApiError;
+use crate::errors::{ApiError, ApiResult};
+use crate::facades::crawl_budget::CrawlBudgetEnforcer;
 use crate::handlers::chunking::apply_content_chunking;
 use crate::models::{
-    CrawlResponse, CrawlResult, CrawlStatistics, ErrorInfo, GateDecisionBreakdown,
+    CrawlFailure, CrawlResponse, CrawlResult, CrawlStatistics, ErrorInfo, GateDecisionBreakdown,
+    RetryFailedBody,
 };
-use crate::pipeline::PipelineOrchestrator;
+use crate::pipeline::{PipelineOrchestrator, GATE_DECISION_UNCHANGED};
 use crate::pipeline_enhanced::EnhancedPipelineOrchestrator;
 use riptide_facade::facades::chunking::ChunkParameters;
+use riptide_security::TenantId;
 use riptide_types::config::CrawlOptions;
-use riptide_types::ExtractedDoc;
+use riptide_types::ports::WebArchive;
+use riptide_types::{CacheStatus, ChangedSince, ErrorCode, ExtractedDoc, PhaseTimings};
+use std::sync::Arc;
 use tracing::{debug, info};
 
 /// Facade for crawl handler business logic
@@ -48,6 +58,16 @@ impl CrawlHandlerFacade {
     /// 4. Apply chunking if requested
     /// 5. Transform results to API format
     /// 6. Calculate statistics
+    /// 7. Close WARC archive files if `archive_warc` was requested
+    ///
+    /// Every batch is registered under a crawl id returned in the response.
+    /// With `changed_since`, URLs whose content has not changed since that
+    /// timestamp or crawl id are reported as skipped, without a document.
+    /// A URL that fails is reported with its error code and listed in the
+    /// response's failure manifest; it never fails the batch.
+    /// With a `budget`, URLs left once a limit is reached fail with
+    /// `RIPTIDE_RESOURCE_EXHAUSTED`, and the response reports what the crawl
+    /// used and which limit ended it.
     ///
     /// # Arguments
     ///
@@ -69,8 +89,29 @@ impl CrawlHandlerFacade {
             "Executing batch crawl"
         );
 
+        // Resolve the differential reference point before registering this crawl
+        let tracker = ChangeTracker::from_context(&self.state);
+        let changed_since = match options.changed_since.as_deref() {
+            Some(since) => Some(tracker.resolve(&ChangedSince::parse(since)).await?),
+            None => None,
+        };
+        let crawl_id = tracker.start_crawl().await;
+
         // Select and execute pipeline
-        let (pipeline_results, stats) = self.execute_pipeline(urls, &options).await;
+        let archive = self.create_archive(&options);
+        let budget = options
+            .budget
+            .clone()
+            .map(|limits| Arc::new(CrawlBudgetEnforcer::new(limits)));
+        let (pipeline_results, stats) = self
+            .execute_pipeline(
+                urls,
+                &options,
+                archive.clone(),
+                changed_since,
+                budget.clone(),
+            )
+            .await;
 
         // Transform results to API format
         let (crawl_results, from_cache_count) = self
@@ -78,19 +119,72 @@ impl CrawlHandlerFacade {
             .await;
 
         // Build response with statistics
-        let response = self.build_response(urls, crawl_results, from_cache_count, stats);
+        let mut response = self.build_response(urls, crawl_results, from_cache_count, stats);
+        response.crawl_id = Some(crawl_id);
+
+        if let Some(budget) = budget {
+            let report = budget.report();
+            if let Some(kind) = report.terminated_by {
+                info!(
+                    budget = kind.as_str(),
+                    urls_stopped = report.urls_stopped,
+                    "Crawl budget ended the crawl early"
+                );
+            }
+            response.budget = Some(report);
+        }
+
+        if let Some(archive) = archive {
+            let files = archive
+                .finish()
+                .await
+                .map_err(|e| ApiError::InternalError {
+                    message: format!("Failed to finalize WARC archive: {}", e),
+                })?;
+            info!(files = files.len(), "WARC archive written");
+            response.warc_files = Some(files);
+        }
 
         Ok(response)
     }
 
+    /// Re-crawl the retryable URLs of a failure manifest
+    ///
+    /// Non-retryable entries are skipped. The retry is a regular batch
+    /// crawl, so it returns its own failure manifest for a further retry.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ApiError::ValidationError` when the manifest has no
+    /// retryable entries.
+    pub async fn retry_failed(&self, body: RetryFailedBody) -> Result<CrawlResponse, ApiError> {
+        let urls = body.retryable_urls();
+        if urls.is_empty() {
+            return Err(ApiError::validation(
+                "Failure manifest contains no retryable URLs",
+            ));
+        }
+        info!(
+            manifest_size = body.failures.len(),
+            retrying = urls.len(),
+            "Retrying failed URLs"
+        );
+        self.crawl_batch(&urls, body.options.unwrap_or_default())
+            .await
+    }
+
     /// Execute spider crawl mode
     ///
     /// Routes to spider facade for deep crawling with link following.
+    /// With `changed_since`, pages unchanged since that timestamp or crawl id
+    /// are still followed for links but reported as skipped.
     ///
     /// # Arguments
     ///
     /// * `seed_urls` - Starting URLs for spider crawl
-    /// * `options` - Crawl options (spider depth, strategy, etc.)
+    /// * `options` - Crawl options; `scope` and `spider_max_depth` bound
+    ///   which discovered links are crawled
+    /// * `tenant_id` - Tenant whose crawl policy applies to discovered links
     ///
     /// # Returns
     ///
@@ -99,9 +193,13 @@ impl CrawlHandlerFacade {
     pub async fn crawl_spider_mode(
         &self,
         seed_urls: &[String],
-        _options: &CrawlOptions,
+        options: &CrawlOptions,
+        tenant_id: &TenantId,
     ) -> Result<CrawlResponse, ApiError> {
+        use crate::handlers::shared::policy::TenantUrlPolicy;
         use crate::handlers::shared::spider::parse_seed_urls;
+        use riptide_facade::facades::CrawlOverrides;
+        use riptide_spider::ChangedSinceFilter;
 
         // Get spider facade from state
         let spider_facade = self
@@ -120,17 +218,36 @@ impl CrawlHandlerFacade {
             "Executing spider crawl via SpiderFacade"
         );
 
-        // Execute spider crawl
-        let spider_result =
-            spider_facade
-                .crawl(parsed_seed_urls)
-                .await
-                .map_err(|e| ApiError::InternalError {
-                    message: format!("Spider crawl failed: {}", e),
-                })?;
+        // Differential spider crawls hold back pages unchanged since the reference point
+        let tracker = ChangeTracker::from_context(&self.state);
+        let mut overrides = CrawlOverrides::new();
+        if let Some(since) = options.changed_since.as_deref() {
+            let since = tracker.resolve(&ChangedSince::parse(since)).await?;
+            overrides = overrides.with_changed_since(ChangedSinceFilter::new(
+                since,
+                tracker.fingerprints().clone(),
+            ));
+        }
+        let crawl_id = tracker.start_crawl().await;
+
+        // Execute spider crawl; discovered links are held to the tenant's policy
+        let policy = TenantUrlPolicy::new(self.state.clone(), tenant_id.clone());
+        let spider_result = spider_facade
+            .crawl_with(
+                parsed_seed_urls,
+                overrides
+                    .with_owner(tenant_id.0.clone())
+                    .with_url_policy(Arc::new(policy))
+                    .with_crawl_options(options),
+            )
+            .await
+            .map_err(|e| ApiError::InternalError {
+                message: format!("Spider crawl failed: {}", e),
+            })?;
 
         // Transform spider result to crawl response
-        let response = self.transform_spider_result(spider_result);
+        let mut response = self.transform_spider_result(spider_result);
+        response.crawl_id = Some(crawl_id);
 
         Ok(response)
     }
@@ -139,24 +256,43 @@ impl CrawlHandlerFacade {
     // Private Helper Methods
     // ========================================================================
 
+    /// Create a WARC archive for this crawl when `archive_warc` is requested
+    fn create_archive(&self, options: &CrawlOptions) -> Option<Arc<dyn WebArchive>> {
+        if options.archive_warc.unwrap_or(false) {
+            self.state.config.create_warc_archive()
+        } else {
+            None
+        }
+    }
+
     /// Select and execute the appropriate pipeline
+    ///
+    /// Differential crawls always use the standard pipeline, which owns
+    /// content fingerprinting; budgeted crawls do too, as it enforces the
+    /// budget per URL.
     async fn execute_pipeline(
         &self,
         urls: &[String],
         options: &CrawlOptions,
+        archive: Option<Arc<dyn WebArchive>>,
+        changed_since: Option<chrono::DateTime<chrono::Utc>>,
+        budget: Option<Arc<CrawlBudgetEnforcer>>,
     ) -> (
-        Vec<Option<crate::pipeline::PipelineResult>>,
+        Vec<ApiResult<crate::pipeline::PipelineResult>>,
         crate::pipeline::PipelineStats,
     ) {
-        if self
-            .state
-            .config
-            .enhanced_pipeline_config
-            .enable_enhanced_pipeline
+        if changed_since.is_none()
+            && budget.is_none()
+            && self
+                .state
+                .config
+                .enhanced_pipeline_config
+                .enable_enhanced_pipeline
         {
             info!("Using enhanced pipeline orchestrator with detailed phase timing");
             let enhanced_pipeline =
-                EnhancedPipelineOrchestrator::new(self.state.clone(), options.clone());
+                EnhancedPipelineOrchestrator::new(self.state.clone(), options.clone())
+                    .with_archive(archive);
             let (results, enhanced_stats) = enhanced_pipeline.execute_batch_enhanced(urls).await;
 
             // Convert enhanced results to standard format
@@ -166,7 +302,10 @@ impl CrawlHandlerFacade {
             (standard_results, standard_stats)
         } else {
             info!("Using standard pipeline orchestrator");
-            let pipeline = PipelineOrchestrator::new(self.state.clone(), options.clone());
+            let pipeline = PipelineOrchestrator::new(self.state.clone(), options.clone())
+                .with_archive(archive)
+                .with_changed_since(changed_since)
+                .with_budget(budget);
             pipeline.execute_batch(urls).await
         }
     }
@@ -174,12 +313,12 @@ impl CrawlHandlerFacade {
     /// Convert enhanced pipeline results to standard format
     fn convert_enhanced_results(
         &self,
-        enhanced_results: Vec<Option<crate::pipeline_enhanced::EnhancedPipelineResult>>,
-    ) -> Vec<Option<crate::pipeline::PipelineResult>> {
+        enhanced_results: Vec<ApiResult<crate::pipeline_enhanced::EnhancedPipelineResult>>,
+    ) -> Vec<ApiResult<crate::pipeline::PipelineResult>> {
         enhanced_results
             .into_iter()
-            .map(|opt_result| {
-                opt_result.map(|enhanced_result| {
+            .map(|result| {
+                result.map(|enhanced_result| {
                     let document =
                         enhanced_result
                             .document
@@ -194,6 +333,20 @@ impl CrawlHandlerFacade {
                                 markdown: None,
                                 media: Vec::new(),
                                 parser_metadata: None,
+                                provenance: None,
+                                translation: None,
+                                structured_data: Vec::new(),
+                                annotations: None,
+                                feed: None,
+                                feed_links: Vec::new(),
+                                classification: None,
+                                clean_html: None,
+                                media_assets: Vec::new(),
+                                embedded_media: Vec::new(),
+                                images: Vec::new(),
+                                references: Vec::new(),
+                                language_spans: Vec::new(),
+                                quality_report: None,
                                 language: None,
                                 reading_time: None,
                                 word_count: None,
@@ -206,11 +359,18 @@ impl CrawlHandlerFacade {
                     crate::pipeline::PipelineResult {
                         document,
                         from_cache: enhanced_result.cache_hit,
+                        cache_status: if enhanced_result.cache_hit {
+                            CacheStatus::Hit
+                        } else {
+                            CacheStatus::Miss
+                        },
                         gate_decision: enhanced_result.gate_decision,
                         quality_score: enhanced_result.quality_score,
                         processing_time_ms: enhanced_result.total_duration_ms,
                         cache_key: format!("riptide:v1:enhanced:{}", enhanced_result.url),
                         http_status: 200,
+                        artifacts: Vec::new(),
+                        timings: PhaseTimings::from(&enhanced_result.phase_timings),
                     }
                 })
             })
@@ -236,7 +396,7 @@ impl CrawlHandlerFacade {
     /// Transform pipeline results to API format
     async fn transform_pipeline_results(
         &self,
-        pipeline_results: Vec<Option<crate::pipeline::PipelineResult>>,
+        pipeline_results: Vec<ApiResult<crate::pipeline::PipelineResult>>,
         urls: &[String],
         options: &CrawlOptions,
     ) -> (Vec<CrawlResult>, usize) {
@@ -247,7 +407,23 @@ impl CrawlHandlerFacade {
             let url = &urls[index];
 
             match pipeline_result {
-                Some(result) => {
+                Ok(result) if result.gate_decision == GATE_DECISION_UNCHANGED => {
+                    crawl_results.push(CrawlResult {
+                        url: url.clone(),
+                        status: result.http_status,
+                        from_cache: false,
+                        cache_status: CacheStatus::Miss,
+                        gate_decision: result.gate_decision,
+                        quality_score: result.quality_score,
+                        processing_time_ms: result.processing_time_ms,
+                        document: None,
+                        error: None,
+                        cache_key: result.cache_key,
+                        artifacts: Vec::new(),
+                        timings: Some(result.timings),
+                    });
+                }
+                Ok(result) => {
                     if result.from_cache {
                         from_cache_count += 1;
                     }
@@ -259,29 +435,31 @@ impl CrawlHandlerFacade {
                         url: url.clone(),
                         status: result.http_status,
                         from_cache: result.from_cache,
+                        cache_status: result.cache_status,
                         gate_decision: result.gate_decision,
                         quality_score: result.quality_score,
                         processing_time_ms: result.processing_time_ms,
                         document: Some(document),
                         error: None,
                         cache_key: result.cache_key,
+                        artifacts: result.artifacts,
+                        timings: Some(result.timings),
                     });
                 }
-                None => {
+                Err(e) => {
                     crawl_results.push(CrawlResult {
                         url: url.clone(),
                         status: 0,
                         from_cache: false,
+                        cache_status: CacheStatus::Miss,
                         gate_decision: "failed".to_string(),
                         quality_score: 0.0,
                         processing_time_ms: 0,
                         document: None,
-                        error: Some(ErrorInfo {
-                            error_type: "pipeline_error".to_string(),
-                            message: "Failed to process URL".to_string(),
-                            retryable: true,
-                        }),
+                        error: Some(ErrorInfo::from(&e)),
                         cache_key: "".to_string(),
+                        artifacts: Vec::new(),
+                        timings: None,
                     });
                 }
             }
@@ -302,6 +480,8 @@ impl CrawlHandlerFacade {
                     min_chunk_size: 100,
                     preserve_sentences: true,
                     window_size: None,
+                    similarity_threshold: None,
+                    overlap_sentences: None,
                 }),
             )
             .await
@@ -319,6 +499,11 @@ impl CrawlHandlerFacade {
         from_cache_count: usize,
         stats: crate::pipeline::PipelineStats,
     ) -> CrawlResponse {
+        let skipped = crawl_results
+            .iter()
+            .filter(|r| r.gate_decision == GATE_DECISION_UNCHANGED)
+            .count();
+
         // Calculate cache hit rate
         let cache_hit_rate = if !urls.is_empty() {
             from_cache_count as f64 / urls.len() as f64
@@ -341,11 +526,17 @@ impl CrawlHandlerFacade {
 
         CrawlResponse {
             total_urls: urls.len(),
-            successful: stats.successful_extractions,
+            successful: stats.successful_extractions.saturating_sub(skipped),
             failed: stats.failed_extractions,
+            skipped,
             from_cache: from_cache_count,
+            failures: failure_manifest(&crawl_results),
             results: crawl_results,
             statistics,
+            warc_files: None,
+            compliance: None,
+            crawl_id: None,
+            budget: None,
         }
     }
 
@@ -363,6 +554,7 @@ impl CrawlHandlerFacade {
                 url: discovered_url.clone(),
                 status: if is_successful { 200 } else { 0 },
                 from_cache: false,
+                cache_status: CacheStatus::Miss,
                 gate_decision: "spider_crawl".to_string(),
                 quality_score: if is_successful { 0.8 } else { 0.0 },
                 processing_time_ms: (spider_result.duration_secs * 1000.0) as u64
@@ -371,13 +563,17 @@ impl CrawlHandlerFacade {
                 error: if !is_successful {
                     Some(ErrorInfo {
                         error_type: "spider_crawl_failed".to_string(),
+                        code: ErrorCode::FetchFailed,
                         message: "Page failed during spider crawl".to_string(),
                         retryable: true,
+                        retry_after_secs: None,
                     })
                 } else {
                     None
                 },
                 cache_key: format!("spider:v1:{}", index),
+                artifacts: Vec::new(),
+                timings: None,
             });
         }
 
@@ -399,11 +595,88 @@ impl CrawlHandlerFacade {
 
         CrawlResponse {
             total_urls: spider_result.discovered_urls.len(),
-            successful: spider_result.pages_crawled as usize,
+            successful: spider_result
+                .pages_crawled
+                .saturating_sub(spider_result.pages_unchanged) as usize,
             failed: spider_result.pages_failed as usize,
+            skipped: spider_result.pages_unchanged as usize,
             from_cache: 0,
+            failures: failure_manifest(&crawl_results),
             results: crawl_results,
             statistics,
+            warc_files: None,
+            compliance: Some(spider_result.compliance),
+            crawl_id: None,
+            budget: None,
         }
     }
 }
+
+/// Failure manifest for a set of crawl results
+fn failure_manifest(results: &[CrawlResult]) -> Vec<CrawlFailure> {
+    results
+        .iter()
+        .filter_map(CrawlFailure::from_result)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure(url: &str, code: ErrorCode) -> CrawlFailure {
+        CrawlFailure {
+            url: url.to_string(),
+            code,
+            retryable: code.is_retryable(),
+            retry_after_secs: None,
+        }
+    }
+
+    #[test]
+    fn test_failure_manifest_from_results() {
+        let error = ApiError::fetch("https://a.example", "connection reset");
+        let failed = CrawlResult {
+            url: "https://a.example".to_string(),
+            status: 0,
+            from_cache: false,
+            cache_status: CacheStatus::Miss,
+            gate_decision: "failed".to_string(),
+            quality_score: 0.0,
+            processing_time_ms: 0,
+            document: None,
+            error: Some(ErrorInfo::from(&error)),
+            cache_key: String::new(),
+            artifacts: Vec::new(),
+            timings: None,
+        };
+        let succeeded = CrawlResult {
+            url: "https://b.example".to_string(),
+            error: None,
+            ..failed.clone()
+        };
+
+        let manifest = failure_manifest(&[failed, succeeded]);
+        assert_eq!(manifest.len(), 1);
+        assert_eq!(manifest[0].url, "https://a.example");
+        assert_eq!(manifest[0].code, error.code());
+        assert_eq!(manifest[0].retryable, error.is_retryable());
+    }
+
+    #[test]
+    fn test_retryable_urls_skips_permanent_failures() {
+        let body = RetryFailedBody {
+            failures: vec![
+                failure("https://a.example", ErrorCode::FetchTimeout),
+                failure("https://b.example", ErrorCode::InvalidUrl),
+                failure("https://a.example", ErrorCode::FetchFailed),
+                failure("https://c.example", ErrorCode::RateLimited),
+            ],
+            options: None,
+        };
+        assert_eq!(
+            body.retryable_urls(),
+            vec!["https://a.example", "https://c.example"]
+        );
+    }
+}
@@ -6,6 +6,8 @@
 //! **Sprint 3.2 Refactoring**:
 //! - `CrawlHandlerFacade`: Extract crawl handler business logic (395 → 60 LOC)
 
+pub mod crawl_budget;
 pub mod crawl_handler_facade;
 
+pub use crawl_budget::{CrawlBudgetEnforcer, CrawlBudgetReport};
 pub use crawl_handler_facade::CrawlHandlerFacade;
@@ -0,0 +1,215 @@
+//! Gate thresholds per content vertical
+//!
+//! The gate's high/low thresholds can differ by vertical (news, e-commerce,
+//! docs, forums). A URL's vertical comes from its domain profile, either the
+//! profile's `vertical` or a tag naming one; unprofiled domains are
+//! `general` and use the global `GATE_HI_THRESHOLD`/`GATE_LO_THRESHOLD`.
+//! Decisions are counted per vertical so the headless rate of each can be
+//! compared in `GET /admin/gate/verticals`.
+
+use dashmap::DashMap;
+use riptide_intelligence::domain_profiling::ProfileManager;
+use riptide_reliability::gate::{ContentVertical, Decision, GateThresholds, VerticalThresholds};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+use url::Url;
+
+/// Hosts whose vertical is remembered at most
+const MAX_CACHED_HOSTS: usize = 10_000;
+
+#[derive(Debug, Default)]
+struct DecisionCounts {
+    raw: u64,
+    probes_first: u64,
+    headless: u64,
+}
+
+/// Gate thresholds and decisions of one vertical
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct VerticalGateReport {
+    pub vertical: ContentVertical,
+    pub thresholds: GateThresholds,
+    /// Pages gated
+    pub requests: u64,
+    pub raw: u64,
+    pub probes_first: u64,
+    pub headless: u64,
+    /// Share of pages the gate sent to headless rendering
+    pub headless_rate: f64,
+}
+
+/// Vertical lookup, thresholds and per-vertical decision counts
+pub struct GateVerticals {
+    thresholds: VerticalThresholds,
+    /// Vertical of each host looked up so far, up to [`MAX_CACHED_HOSTS`]
+    hosts: DashMap<String, ContentVertical>,
+    stats: Mutex<BTreeMap<ContentVertical, DecisionCounts>>,
+}
+
+impl GateVerticals {
+    pub fn new(thresholds: VerticalThresholds) -> Self {
+        Self {
+            thresholds,
+            hosts: DashMap::new(),
+            stats: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Vertical of the site `url` belongs to, from its domain profile
+    ///
+    /// Profiles are read once per host on the blocking pool;
+    /// [`Self::forget`] drops the cached vertical after a profile changes.
+    pub async fn vertical_for(&self, url: &str) -> ContentVertical {
+        let Some(host) = Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        else {
+            return ContentVertical::General;
+        };
+        if let Some(vertical) = self.hosts.get(&host) {
+            return *vertical;
+        }
+
+        let lookup = host.clone();
+        let vertical = tokio::task::spawn_blocking(move || {
+            let bare = lookup.strip_prefix("www.").unwrap_or(&lookup);
+            ProfileManager::load(&lookup)
+                .or_else(|_| ProfileManager::load(bare))
+                .map(|profile| profile.content_vertical())
+                .unwrap_or(ContentVertical::General)
+        })
+        .await
+        .unwrap_or(ContentVertical::General);
+
+        if self.hosts.len() >= MAX_CACHED_HOSTS {
+            // Make room by dropping an arbitrary host; it is reloaded when seen again
+            let evicted = self.hosts.iter().next().map(|entry| entry.key().clone());
+            if let Some(evicted) = evicted {
+                self.hosts.remove(&evicted);
+            }
+        }
+        self.hosts.insert(host, vertical);
+        vertical
+    }
+
+    /// Drop the cached vertical of `domain` and its `www.` host
+    pub fn forget(&self, domain: &str) {
+        let domain = domain.to_ascii_lowercase();
+        self.hosts.remove(&format!("www.{}", domain));
+        self.hosts.remove(&domain);
+    }
+
+    /// Thresholds applying to pages of `vertical`
+    pub fn thresholds(&self, vertical: ContentVertical) -> GateThresholds {
+        self.thresholds.for_vertical(vertical)
+    }
+
+    /// Count a gate decision for a page of `vertical`
+    pub fn record(&self, vertical: ContentVertical, decision: Decision) {
+        let mut stats = self.lock();
+        let counts = stats.entry(vertical).or_default();
+        match decision {
+            Decision::Raw => counts.raw += 1,
+            Decision::ProbesFirst => counts.probes_first += 1,
+            Decision::Headless => counts.headless += 1,
+        }
+    }
+
+    /// Thresholds and decisions of every vertical, in a fixed order
+    // Safe conversion: decision counts stay far below 2^52
+    #[allow(clippy::cast_precision_loss)]
+    pub fn report(&self) -> Vec<VerticalGateReport> {
+        let stats = self.lock();
+        ContentVertical::ALL
+            .into_iter()
+            .map(|vertical| {
+                let counts = stats.get(&vertical);
+                let count = |f: fn(&DecisionCounts) -> u64| counts.map_or(0, f);
+                let (raw, probes_first, headless) = (
+                    count(|c| c.raw),
+                    count(|c| c.probes_first),
+                    count(|c| c.headless),
+                );
+                let requests = raw + probes_first + headless;
+                VerticalGateReport {
+                    vertical,
+                    thresholds: self.thresholds(vertical),
+                    requests,
+                    raw,
+                    probes_first,
+                    headless,
+                    headless_rate: if requests == 0 {
+                        0.0
+                    } else {
+                        headless as f64 / requests as f64
+                    },
+                }
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<ContentVertical, DecisionCounts>> {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_per_vertical() {
+        let verticals = GateVerticals::new(VerticalThresholds::default().with_vertical(
+            ContentVertical::Ecommerce,
+            GateThresholds { hi: 0.8, lo: 0.5 },
+        ));
+        verticals.record(ContentVertical::Ecommerce, Decision::Headless);
+        verticals.record(ContentVertical::Ecommerce, Decision::Raw);
+        verticals.record(ContentVertical::General, Decision::ProbesFirst);
+
+        let report = verticals.report();
+        assert_eq!(report.len(), ContentVertical::ALL.len());
+        let shop = report
+            .iter()
+            .find(|r| r.vertical == ContentVertical::Ecommerce)
+            .unwrap();
+        assert_eq!(shop.thresholds, GateThresholds { hi: 0.8, lo: 0.5 });
+        assert_eq!((shop.requests, shop.headless), (2, 1));
+        assert!((shop.headless_rate - 0.5).abs() < 1e-9);
+        let news = report
+            .iter()
+            .find(|r| r.vertical == ContentVertical::News)
+            .unwrap();
+        assert_eq!(news.thresholds, GateThresholds::default());
+        assert_eq!(news.requests, 0);
+    }
+
+    #[tokio::test]
+    async fn test_unprofiled_hosts_are_general() {
+        let verticals = GateVerticals::new(VerticalThresholds::default());
+        assert_eq!(
+            verticals
+                .vertical_for("https://unprofiled.invalid/page")
+                .await,
+            ContentVertical::General
+        );
+        assert_eq!(
+            verticals.vertical_for("not a url").await,
+            ContentVertical::General
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cached_hosts_are_bounded() {
+        let verticals = GateVerticals::new(VerticalThresholds::default());
+        for i in 0..MAX_CACHED_HOSTS {
+            verticals
+                .hosts
+                .insert(format!("host{}.invalid", i), ContentVertical::News);
+        }
+        verticals.vertical_for("https://unprofiled.invalid/").await;
+        assert_eq!(verticals.hosts.len(), MAX_CACHED_HOSTS);
+        assert!(verticals.hosts.contains_key("unprofiled.invalid"));
+    }
+}
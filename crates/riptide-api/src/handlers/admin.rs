@@ -148,13 +148,6 @@ pub async fn delete_tenant(
     Err(not_implemented())
 }
 
-pub async fn get_tenant_usage(
-    _state: State<ApplicationContext>,
-    Path(_tenant_id): Path<String>,
-) -> ApiResult<Json<ResourceUsageResponse>> {
-    Err(not_implemented())
-}
-
 pub async fn get_tenant_billing(
     _state: State<ApplicationContext>,
     Path(_tenant_id): Path<String>,
@@ -0,0 +1,127 @@
+//! Tenant API key endpoints
+//!
+//! Operators issue API keys bound to a tenant here. Requests authenticated
+//! with a tenant key run under that tenant's crawl policy, metering and
+//! resource quotas; the `X-Tenant-ID` header can no longer choose another
+//! tenant. Keys are held in memory and must be issued again after a
+//! restart.
+
+use crate::context::ApplicationContext;
+use crate::errors::{ApiError, ApiResult};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use riptide_security::{ApiKey, TenantId};
+use serde::{Deserialize, Serialize};
+
+/// Body of a key creation request
+#[derive(Debug, Deserialize)]
+pub struct CreateApiKeyBody {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// A tenant API key without its secret
+#[derive(Debug, Serialize)]
+pub struct ApiKeyInfo {
+    pub id: String,
+    pub tenant_id: String,
+    pub name: String,
+    /// First characters of the key, to tell keys apart
+    pub prefix: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// False once revoked
+    pub active: bool,
+}
+
+impl From<ApiKey> for ApiKeyInfo {
+    fn from(key: ApiKey) -> Self {
+        Self {
+            id: key.id,
+            tenant_id: key.tenant_id.0,
+            name: key.name,
+            prefix: key.prefix,
+            created_at: key.created_at,
+            expires_at: key.expires_at,
+            last_used_at: key.last_used_at,
+            active: key.is_active,
+        }
+    }
+}
+
+/// A newly created key; `key` is only ever returned here
+#[derive(Debug, Serialize)]
+pub struct CreatedApiKey {
+    #[serde(flatten)]
+    pub info: ApiKeyInfo,
+    pub key: String,
+}
+
+/// POST /admin/tenants/:id/api-keys - Issue an API key for a tenant
+pub async fn create_api_key(
+    State(state): State<ApplicationContext>,
+    Path(tenant_id): Path<String>,
+    Json(body): Json<CreateApiKeyBody>,
+) -> ApiResult<(StatusCode, Json<CreatedApiKey>)> {
+    if tenant_id.trim().is_empty() || body.name.trim().is_empty() {
+        return Err(ApiError::validation("tenant id and key name are required"));
+    }
+    let (api_key, raw_key) = state
+        .auth_config
+        .tenant_keys()
+        .create_api_key(
+            TenantId(tenant_id),
+            body.name,
+            body.description,
+            Vec::new(),
+            None,
+            body.expires_at,
+        )
+        .await
+        .map_err(|e| ApiError::InternalError {
+            message: format!("Failed to create API key: {}", e),
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(CreatedApiKey {
+            info: api_key.into(),
+            key: raw_key,
+        }),
+    ))
+}
+
+/// GET /admin/tenants/:id/api-keys - List a tenant's API keys
+pub async fn list_api_keys(
+    State(state): State<ApplicationContext>,
+    Path(tenant_id): Path<String>,
+) -> Json<Vec<ApiKeyInfo>> {
+    let keys = state
+        .auth_config
+        .tenant_keys()
+        .get_tenant_keys(&TenantId(tenant_id))
+        .await;
+    Json(keys.into_iter().map(ApiKeyInfo::from).collect())
+}
+
+/// DELETE /admin/api-keys/:key_id - Revoke a tenant API key
+pub async fn revoke_api_key(
+    State(state): State<ApplicationContext>,
+    Path(key_id): Path<String>,
+) -> ApiResult<StatusCode> {
+    state
+        .auth_config
+        .tenant_keys()
+        .revoke_api_key(&key_id)
+        .await
+        .map_err(|_| ApiError::not_found(format!("API key '{}'", key_id)))?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,47 @@
+//! Download bandwidth limit endpoints
+//!
+//! Operators cap how fast this instance downloads, globally and per domain,
+//! and change the caps without a restart. Changes apply to reads from the
+//! next chunk on and are not persisted: a restart goes back to the
+//! `RIPTIDE_BANDWIDTH_*` settings.
+
+use crate::context::ApplicationContext;
+use crate::errors::{ApiError, ApiResult};
+use axum::{extract::State, Json};
+use riptide_fetch::{BandwidthConfig, BandwidthStats};
+use serde::Serialize;
+
+/// Current bandwidth limits and counters
+#[derive(Debug, Serialize)]
+pub struct BandwidthResponse {
+    pub limits: BandwidthConfig,
+    pub stats: BandwidthStats,
+}
+
+fn snapshot(state: &ApplicationContext) -> BandwidthResponse {
+    BandwidthResponse {
+        limits: state.bandwidth.config(),
+        stats: state.bandwidth.stats(),
+    }
+}
+
+/// GET /admin/bandwidth - Active limits and throttling counters
+pub async fn get_bandwidth(State(state): State<ApplicationContext>) -> Json<BandwidthResponse> {
+    Json(snapshot(&state))
+}
+
+/// PUT /admin/bandwidth - Replace the limits; omitted fields mean no cap
+pub async fn set_bandwidth(
+    State(state): State<ApplicationContext>,
+    Json(limits): Json<BandwidthConfig>,
+) -> ApiResult<Json<BandwidthResponse>> {
+    limits.validate().map_err(ApiError::validation)?;
+    tracing::info!(
+        global_bytes_per_sec = limits.global_bytes_per_sec,
+        per_domain_bytes_per_sec = limits.per_domain_bytes_per_sec,
+        domain_overrides = limits.domain_overrides.len(),
+        "Bandwidth limits updated"
+    );
+    state.bandwidth.set_config(limits);
+    Ok(Json(snapshot(&state)))
+}
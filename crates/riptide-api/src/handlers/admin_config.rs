@@ -0,0 +1,55 @@
+//! Admin configuration dump endpoint
+//!
+//! Exposes the effective runtime configuration for operators. Every value goes
+//! through `riptide_config::redaction` first, so API keys, passwords, and
+//! credentials embedded in connection URLs are masked in the response.
+
+use crate::context::ApplicationContext;
+use crate::errors::{ApiError, ApiResult};
+use axum::{extract::State, Json};
+use riptide_config::redaction::{redact_json, redacted_value};
+use serde::Serialize;
+
+/// Redacted configuration dump
+#[derive(Debug, Serialize)]
+pub struct ConfigDumpResponse {
+    /// Core application settings
+    pub app: serde_json::Value,
+    /// API resource controls
+    pub api: serde_json::Value,
+}
+
+/// GET /admin/config - Effective configuration with secrets masked
+pub async fn get_config(
+    State(state): State<ApplicationContext>,
+) -> ApiResult<Json<ConfigDumpResponse>> {
+    let config = &state.config;
+    let mut app = serde_json::json!({
+        "redis_url": config.redis_url,
+        "wasm_path": config.wasm_path,
+        "max_concurrency": config.max_concurrency,
+        "cache_ttl": config.cache_ttl,
+        "cache_revalidate_ttl": config.cache_revalidate_ttl,
+        "max_response_bytes": config.max_response_bytes,
+        "gate_hi_threshold": config.gate_hi_threshold,
+        "gate_lo_threshold": config.gate_lo_threshold,
+        "gate_vertical_thresholds": config.gate_vertical_thresholds,
+        "headless_url": config.headless_url,
+        "warc_output_dir": config.warc_output_dir,
+        "warc_gzip": config.warc_gzip,
+        "warc_archive_all_fetches": config.warc_archive_all_fetches,
+        "dns": config.dns_config,
+        "metering": config.metering_config,
+        "bandwidth": state.bandwidth.config(),
+        "translation": config.translation_config,
+        "annotation": config.annotation_config,
+        "classification": config.classification_config,
+        "media_download": config.media_download_config,
+    });
+    redact_json(&mut app);
+
+    let api = redacted_value(&state.api_config)
+        .map_err(|e| ApiError::internal(format!("Failed to serialize API config: {}", e)))?;
+
+    Ok(Json(ConfigDumpResponse { app, api }))
+}
@@ -0,0 +1,26 @@
+//! Admin drain endpoint
+//!
+//! Lets a deploy orchestrator drain an instance before sending SIGTERM: the
+//! drain runs in the background and its progress is polled with
+//! `GET /admin/drain` until the phase is `drained`.
+
+use crate::context::ApplicationContext;
+use crate::drain::DrainStatus;
+use axum::{extract::State, http::StatusCode, Json};
+
+/// POST /admin/drain - Start draining; 202 with the current progress
+pub async fn start_drain(
+    State(state): State<ApplicationContext>,
+) -> (StatusCode, Json<DrainStatus>) {
+    state.drain.begin();
+    let context = state.clone();
+    tokio::spawn(async move {
+        context.drain.drain(&context).await;
+    });
+    (StatusCode::ACCEPTED, Json(state.drain.status(&state)))
+}
+
+/// GET /admin/drain - Drain progress
+pub async fn get_drain_status(State(state): State<ApplicationContext>) -> Json<DrainStatus> {
+    Json(state.drain.status(&state))
+}
@@ -0,0 +1,85 @@
+//! WASM extractor component endpoints
+//!
+//! Operators upload a new build of the extractor component (multipart field
+//! `component`) and the instance pool switches to it without a restart. The
+//! upload is compiled, validated and probed before the switch; on failure
+//! the pool keeps serving the current component. Reloads are not persisted:
+//! a restart loads `WASM_EXTRACTOR_PATH` again.
+
+use crate::context::ApplicationContext;
+use crate::errors::{ApiError, ApiResult};
+use axum::{
+    extract::{Multipart, State},
+    Json,
+};
+use riptide_pool::{AdvancedInstancePool, ComponentReloadReport};
+use riptide_types::ExtractionProvenance;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Largest component accepted by [`reload_component`], matching the API's payload limit
+pub const MAX_COMPONENT_BYTES: usize = 50 * 1024 * 1024;
+
+/// Component serving extractions
+#[derive(Debug, Serialize)]
+pub struct ComponentInfo {
+    pub generation: u64,
+    /// Artifact path or upload label
+    pub source: String,
+    /// SHA-256 recorded in document provenance
+    pub component_hash: Option<String>,
+}
+
+fn pool(state: &ApplicationContext) -> ApiResult<&Arc<AdvancedInstancePool>> {
+    state
+        .extractor_pool
+        .as_ref()
+        .ok_or_else(|| ApiError::dependency("wasm_extractor", "no WASM component is loaded"))
+}
+
+/// GET /admin/extractor/component - Component serving extractions
+pub async fn get_component(
+    State(state): State<ApplicationContext>,
+) -> ApiResult<Json<ComponentInfo>> {
+    let pool = pool(&state)?;
+    Ok(Json(ComponentInfo {
+        generation: pool.component_generation().await,
+        source: pool.component_source().await,
+        component_hash: state.component_hash(),
+    }))
+}
+
+/// PUT /admin/extractor/component - Replace the component with the uploaded one
+pub async fn reload_component(
+    State(state): State<ApplicationContext>,
+    mut multipart: Multipart,
+) -> ApiResult<Json<ComponentReloadReport>> {
+    let pool = pool(&state)?;
+    let mut component = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::validation(format!("Invalid multipart data: {}", e)))?
+    {
+        if field.name() == Some("component") {
+            component =
+                Some(field.bytes().await.map_err(|e| {
+                    ApiError::validation(format!("Failed to read component: {}", e))
+                })?);
+        }
+    }
+    let component = component
+        .filter(|bytes| !bytes.is_empty())
+        .ok_or_else(|| ApiError::validation("No component file provided"))?;
+
+    let hash = ExtractionProvenance::hash_component(&component);
+    let report = pool
+        .reload_component_bytes(component.to_vec(), &format!("upload:{}", &hash[..12]))
+        .await
+        .map_err(|e| ApiError::validation(format!("Component rejected: {:#}", e)))?;
+
+    if let Ok(mut current) = state.extractor_component_hash.write() {
+        *current = Some(hash);
+    }
+    Ok(Json(report))
+}
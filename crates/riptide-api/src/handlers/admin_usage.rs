@@ -0,0 +1,35 @@
+//! Per-tenant cost usage endpoint
+//!
+//! Reports what a tenant's crawls have consumed since the instance started
+//! (bandwidth, render seconds, LLM tokens, stored bytes) with its cost under
+//! the configured prices, broken down by crawl and by domain.
+
+use crate::context::ApplicationContext;
+use crate::errors::{ApiError, ApiResult};
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use riptide_security::{TenantId, TenantUsageReport};
+use serde::Deserialize;
+
+/// Query parameters of the usage endpoint
+#[derive(Debug, Deserialize)]
+pub struct UsageQuery {
+    /// Keep only the N most expensive crawls and domains
+    pub limit: Option<usize>,
+}
+
+/// GET /admin/tenants/:id/usage - Metered usage and cost of a tenant
+pub async fn get_tenant_usage(
+    State(state): State<ApplicationContext>,
+    Path(tenant_id): Path<String>,
+    Query(query): Query<UsageQuery>,
+) -> ApiResult<Json<TenantUsageReport>> {
+    state
+        .meter
+        .tenant_usage(&TenantId(tenant_id.clone()), query.limit)
+        .await
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found(format!("usage for tenant '{}'", tenant_id)))
+}
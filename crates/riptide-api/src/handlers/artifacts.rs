@@ -0,0 +1,42 @@
+//! Artifact download endpoint
+//!
+//! Serves blobs written by the media pipeline (screenshots, thumbnails) from
+//! the configured `BlobStorage`, so the URLs returned in render and pipeline
+//! results resolve when the default local storage is used.
+
+use crate::context::ApplicationContext;
+use crate::errors::{ApiError, ApiResult};
+use axum::{
+    extract::{Path, State},
+    http::header,
+    response::IntoResponse,
+};
+use riptide_types::error::RiptideError;
+
+/// GET /artifacts/*key - Download a stored artifact
+pub async fn get_artifact(
+    State(state): State<ApplicationContext>,
+    Path(key): Path<String>,
+) -> ApiResult<impl IntoResponse> {
+    let (data, content_type) = state
+        .blob_storage
+        .get(&key)
+        .await
+        .map_err(|e| match e {
+            RiptideError::ValidationError(msg) => ApiError::validation(msg),
+            other => ApiError::internal(format!("Failed to read artifact: {}", other)),
+        })?
+        .ok_or_else(|| ApiError::not_found(format!("artifact {}", key)))?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            // Keys are unique per capture, so content never changes
+            (
+                header::CACHE_CONTROL,
+                "public, max-age=31536000, immutable".to_string(),
+            ),
+        ],
+        data,
+    ))
+}
@@ -2,7 +2,9 @@
 
 use crate::context::ApplicationContext;
 use crate::errors::ApiError;
+use crate::handlers::shared::policy::{tenant_session, RequestTenant, DEFAULT_TENANT};
 use axum::{extract::State, http::StatusCode, Json};
+use riptide_facade::facades::{SessionRecorder, SessionRecording};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize)]
@@ -82,6 +84,27 @@ pub async fn close_browser_session(
     Ok(StatusCode::NO_CONTENT)
 }
 
+/// GET /api/v1/browser/session/:id/recording - Replay bundle of a recorded session
+///
+/// Screenshot events reference blobs served by the artifacts endpoint.
+/// Recordings show what was typed and seen in the session, so tenants only
+/// get recordings of sessions they may use.
+pub async fn get_session_recording(
+    State(state): State<ApplicationContext>,
+    RequestTenant(tenant_id): RequestTenant,
+    axum::extract::Path(session_id): axum::extract::Path<String>,
+) -> Result<Json<SessionRecording>, ApiError> {
+    if tenant_id.0 != DEFAULT_TENANT {
+        tenant_session(&state, &tenant_id, &session_id).await?;
+    }
+    SessionRecorder::new(state.blob_storage.clone())
+        .load(&session_id)
+        .await
+        .map_err(|e| ApiError::internal(format!("Failed to load session recording: {}", e)))?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found(format!("recording for session {}", session_id)))
+}
+
 #[derive(Debug, Deserialize)]
 pub struct BrowserActionRequest {
     /// Session ID for browser action (API contract, reserved for future browser facade integration)
@@ -0,0 +1,115 @@
+//! Page captures pushed by the browser extension
+//!
+//! The extension serializes the DOM of the page the user is viewing, often
+//! one behind a login, and POSTs it to `/api/v1/capture` as a
+//! [`DomSnapshot`](riptide_types::DomSnapshot). The visible content is
+//! rendered back to HTML and goes through the same gate and extraction
+//! steps as a crawled page. Captures are never cached: they may hold
+//! content only the capturing user is allowed to see.
+
+use crate::context::ApplicationContext;
+use crate::errors::ApiError;
+use crate::handlers::shared::policy::{
+    enforce_crawl_policy, enforce_pipeline_hints, RequestTenant,
+};
+use crate::handlers::shared::usage::{crawl_result_usage, meter_fetch};
+use crate::models::{CaptureBody, CaptureResponse, CrawlResult};
+use crate::pipeline::PipelineOrchestrator;
+use axum::{extract::State, Json};
+use riptide_facade::facades::DomSnapshotFacade;
+use riptide_types::ports::HttpResponse;
+use std::collections::HashMap;
+use std::time::Instant;
+use tracing::info;
+
+/// POST /api/v1/capture - Extract content from a browser DOM snapshot
+#[tracing::instrument(
+    name = "capture_handler",
+    skip(state, body, tenant_id),
+    fields(http.method = "POST", http.route = "/api/v1/capture", url = %body.snapshot.url)
+)]
+pub async fn capture(
+    State(state): State<ApplicationContext>,
+    RequestTenant(tenant_id): RequestTenant,
+    Json(body): Json<CaptureBody>,
+) -> Result<Json<CaptureResponse>, ApiError> {
+    let start_time = Instant::now();
+
+    let prepared = DomSnapshotFacade::default().prepare(&body.snapshot)?;
+
+    enforce_crawl_policy(&state, &tenant_id, &[&prepared.url]).await?;
+    enforce_pipeline_hints(
+        &state,
+        &tenant_id,
+        &[&prepared.url],
+        body.options
+            .as_ref()
+            .and_then(|o| o.pipeline_hints.as_ref()),
+    )
+    .await?;
+
+    let max_bytes = state.config.max_response_bytes;
+    if max_bytes > 0 && prepared.html.len() as u64 > max_bytes {
+        return Err(ApiError::PayloadTooLarge {
+            message: format!(
+                "Rendered capture is {} bytes, over the {} byte limit",
+                prepared.html.len(),
+                max_bytes
+            ),
+        });
+    }
+
+    let mut options = body.options.unwrap_or_default();
+    options.cache_mode = "bypass".to_string();
+
+    let response = HttpResponse::new(
+        200,
+        HashMap::from([(
+            "content-type".to_string(),
+            "text/html; charset=utf-8".to_string(),
+        )]),
+        prepared.html.into_bytes(),
+    );
+    let captured_at = body.snapshot.captured_at.unwrap_or_else(chrono::Utc::now);
+
+    let result = PipelineOrchestrator::new(state.clone(), options)
+        .execute_ingested(&prepared.url, response, captured_at)
+        .await?;
+
+    let result = CrawlResult {
+        url: prepared.url,
+        status: result.http_status,
+        from_cache: false,
+        cache_status: result.cache_status,
+        gate_decision: result.gate_decision,
+        quality_score: result.quality_score,
+        processing_time_ms: result.processing_time_ms,
+        document: Some(result.document),
+        error: None,
+        cache_key: result.cache_key,
+        artifacts: result.artifacts,
+        timings: Some(result.timings),
+    };
+
+    meter_fetch(&state, &tenant_id, &result.url, crawl_result_usage(&result)).await;
+
+    info!(
+        url = %result.url,
+        nodes = prepared.node_count,
+        gate_decision = %result.gate_decision,
+        total_time_ms = start_time.elapsed().as_millis() as u64,
+        "Capture request completed"
+    );
+    state.record_http_request(
+        "POST",
+        "/api/v1/capture",
+        200,
+        start_time.elapsed().as_secs_f64(),
+    );
+
+    Ok(Json(CaptureResponse {
+        result,
+        node_count: prepared.node_count,
+        resources: prepared.resources,
+    }))
+}
@@ -23,6 +23,8 @@ pub struct ChunkParametersDTO {
     pub min_chunk_size: Option<usize>,
     pub preserve_sentences: Option<bool>,
     pub window_size: Option<usize>,
+    pub similarity_threshold: Option<f32>,
+    pub overlap_sentences: Option<usize>,
 }
 
 #[instrument(skip(_state))]
@@ -37,6 +39,8 @@ pub async fn handle_chunking(
         min_chunk_size: p.min_chunk_size.unwrap_or(100),
         preserve_sentences: p.preserve_sentences.unwrap_or(true),
         window_size: p.window_size,
+        similarity_threshold: p.similarity_threshold,
+        overlap_sentences: p.overlap_sentences,
     });
     let response = ChunkingFacade::new()
         .chunk_content(ChunkRequest {
@@ -44,8 +48,7 @@ pub async fn handle_chunking(
             chunking_mode: req.chunking_mode,
             parameters: params,
         })
-        .await
-        .map_err(|e| ApiError::internal(format!("Chunking failed: {}", e)))?;
+        .await?;
     Ok(Json(response))
 }
 
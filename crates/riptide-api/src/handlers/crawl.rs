@@ -1,11 +1,16 @@
 use crate::context::ApplicationContext;
 use crate::errors::ApiError;
 use crate::facades::CrawlHandlerFacade;
-use crate::models::{CrawlBody, CrawlResponse};
+use crate::handlers::shared::policy::{
+    enforce_crawl_policy, enforce_pipeline_hints, RequestTenant,
+};
+use crate::handlers::shared::usage::meter_crawl;
+use crate::models::{CrawlBody, CrawlResponse, ManifestRunResponse, RetryFailedBody};
 use crate::telemetry_config::extract_trace_context;
-use crate::validation::validate_crawl_request;
+use crate::validation::{validate_crawl_request, validate_retry_request};
 use axum::{extract::State, http::HeaderMap, Json};
 use opentelemetry::trace::SpanKind;
+use riptide_config::CrawlManifest;
 use riptide_events::{BaseEvent, EventSeverity};
 use std::time::Instant;
 use tracing::{debug, info, warn, Span};
@@ -22,7 +27,7 @@ use tracing::{debug, info, warn, Span};
 /// and extraction modes.
 #[tracing::instrument(
     name = "crawl_handler",
-    skip(state, body, headers),
+    skip(state, body, headers, tenant),
     fields(
         http.method = "POST",
         http.route = "/crawl",
@@ -36,6 +41,7 @@ use tracing::{debug, info, warn, Span};
 pub async fn crawl(
     State(state): State<ApplicationContext>,
     headers: HeaderMap,
+    tenant: RequestTenant,
     Json(body): Json<CrawlBody>,
 ) -> Result<Json<CrawlResponse>, ApiError> {
     let start_time = Instant::now();
@@ -74,8 +80,20 @@ pub async fn crawl(
     // Validate the request
     validate_crawl_request(&body)?;
 
+    // Consult the tenant's crawl policy before anything is fetched
+    let RequestTenant(tenant_id) = tenant;
+    enforce_crawl_policy(&state, &tenant_id, &body.urls).await?;
+
     // Use provided options or defaults
     let options = body.options.unwrap_or_default();
+    enforce_pipeline_hints(
+        &state,
+        &tenant_id,
+        &body.urls,
+        options.pipeline_hints.as_ref(),
+    )
+    .await?;
+    ensure_known_schema(&state, &options)?;
 
     // Create facade for business logic
     let facade = CrawlHandlerFacade::new(state.clone());
@@ -83,7 +101,9 @@ pub async fn crawl(
     // Check if spider mode is requested and route accordingly
     let response = if options.use_spider.unwrap_or(false) {
         info!("Spider mode requested, routing to spider crawl");
-        facade.crawl_spider_mode(&body.urls, &options).await?
+        facade
+            .crawl_spider_mode(&body.urls, &options, &tenant_id)
+            .await?
     } else {
         // Execute batch crawl through facade
         facade.crawl_batch(&body.urls, options).await?
@@ -103,6 +123,7 @@ pub async fn crawl(
         total_urls = body.urls.len(),
         successful = response.successful,
         failed = response.failed,
+        skipped = response.skipped,
         cache_hits = response.from_cache,
         total_time_ms = elapsed_ms,
         "Crawl request completed"
@@ -124,8 +145,230 @@ pub async fn crawl(
         warn!(error = %e, "Failed to emit crawl completion event");
     }
 
+    // Charge the work to the tenant, crawl and domains
+    meter_crawl(&state, &tenant_id, &response).await;
+
     // Record metrics for crawl request
     state.record_http_request("POST", "/crawl", 200, start_time.elapsed().as_secs_f64());
 
     Ok(Json(response))
 }
+
+/// Reject options naming an extraction schema no extractor is registered for
+fn ensure_known_schema(
+    state: &ApplicationContext,
+    options: &riptide_types::config::CrawlOptions,
+) -> Result<(), ApiError> {
+    match options
+        .pipeline_hints
+        .as_ref()
+        .and_then(|h| h.extraction_schema.as_deref())
+        .filter(|schema| state.experiments.extractor(schema).is_none())
+    {
+        Some(schema) => Err(ApiError::validation(format!(
+            "Unknown extraction_schema '{}'",
+            schema
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Crawl manifest endpoint: runs or schedules a crawl declared in YAML.
+///
+/// The body is a crawl manifest (YAML or JSON). It is validated, its seeds
+/// are filtered, and the crawl then goes through `/crawl` with the
+/// manifest's budget and extraction settings, so the same validation and
+/// tenant policy apply. A manifest with `max_depth` follows links in spider
+/// mode, scoped by its filters and page cap. A manifest with a `schedule` is registered as a
+/// scheduled batch crawl job instead, which needs the `workers` feature.
+///
+/// Output sinks are not written by the server: the crawl response is
+/// returned (or, for scheduled runs, stored as the job result) and
+/// `riptide run` delivers it to the manifest's outputs.
+pub async fn crawl_manifest(
+    State(state): State<ApplicationContext>,
+    headers: HeaderMap,
+    tenant: RequestTenant,
+    body: String,
+) -> Result<Json<ManifestRunResponse>, ApiError> {
+    let manifest =
+        CrawlManifest::from_yaml(&body).map_err(|e| ApiError::validation(e.to_string()))?;
+    let report = manifest.validate();
+    if !report.valid {
+        return Err(ApiError::validation(format!(
+            "Invalid manifest '{}': {}",
+            manifest.name,
+            report.errors.join("; ")
+        )));
+    }
+
+    let urls = manifest.seed_urls();
+    let options = manifest.crawl_options();
+    info!(
+        manifest = %manifest.name,
+        url_count = urls.len(),
+        scheduled = manifest.schedule.is_some(),
+        "Received crawl manifest"
+    );
+
+    if let Some(schedule) = &manifest.schedule {
+        let scheduled_job_id =
+            schedule_manifest(&state, &tenant, &manifest, schedule, urls, options).await?;
+        return Ok(Json(ManifestRunResponse {
+            manifest: manifest.name,
+            warnings: report.warnings,
+            crawl: None,
+            scheduled_job_id: Some(scheduled_job_id),
+        }));
+    }
+
+    let Json(response) = crawl(
+        State(state),
+        headers,
+        tenant,
+        Json(CrawlBody {
+            urls,
+            options: Some(options),
+        }),
+    )
+    .await?;
+
+    Ok(Json(ManifestRunResponse {
+        manifest: manifest.name,
+        warnings: report.warnings,
+        crawl: Some(response),
+        scheduled_job_id: None,
+    }))
+}
+
+/// Register a manifest's crawl as a scheduled batch crawl job
+#[cfg(feature = "workers")]
+async fn schedule_manifest(
+    state: &ApplicationContext,
+    RequestTenant(tenant_id): &RequestTenant,
+    manifest: &CrawlManifest,
+    schedule: &str,
+    urls: Vec<String>,
+    options: riptide_types::config::CrawlOptions,
+) -> Result<uuid::Uuid, ApiError> {
+    let worker_service =
+        state
+            .worker_service
+            .as_ref()
+            .ok_or_else(|| ApiError::DependencyError {
+                service: "workers".to_string(),
+                message: "Scheduled manifests need the worker service, which is not running"
+                    .to_string(),
+            })?;
+
+    enforce_crawl_policy(state, tenant_id, &urls).await?;
+    enforce_pipeline_hints(state, tenant_id, &urls, options.pipeline_hints.as_ref()).await?;
+    if let Some(schema) = manifest
+        .extraction
+        .schema
+        .as_deref()
+        .filter(|schema| state.experiments.extractor(schema).is_none())
+    {
+        return Err(ApiError::validation(format!(
+            "Unknown extraction_schema '{}'",
+            schema
+        )));
+    }
+
+    let mut job = riptide_workers::ScheduledJob::new(
+        manifest.name.clone(),
+        schedule.to_string(),
+        riptide_workers::JobType::BatchCrawl {
+            urls,
+            options: Some(options),
+        },
+    )
+    .map_err(|e| ApiError::validation(format!("Invalid schedule '{}': {:#}", schedule, e)))?;
+    if let Some(description) = &manifest.description {
+        job.metadata
+            .insert("description".to_string(), serde_json::json!(description));
+    }
+    job.metadata
+        .insert("manifest".to_string(), serde_json::json!(manifest.name));
+
+    let job_id =
+        worker_service
+            .add_scheduled_job(job)
+            .await
+            .map_err(|e| ApiError::InternalError {
+                message: format!("Failed to schedule manifest '{}': {}", manifest.name, e),
+            })?;
+    info!(manifest = %manifest.name, %job_id, schedule = %schedule, "Crawl manifest scheduled");
+    Ok(job_id)
+}
+
+#[cfg(not(feature = "workers"))]
+async fn schedule_manifest(
+    _state: &ApplicationContext,
+    _tenant: &RequestTenant,
+    manifest: &CrawlManifest,
+    _schedule: &str,
+    _urls: Vec<String>,
+    _options: riptide_types::config::CrawlOptions,
+) -> Result<uuid::Uuid, ApiError> {
+    Err(ApiError::ConfigError {
+        message: format!(
+            "Manifest '{}' has a schedule, which needs the 'workers' feature",
+            manifest.name
+        ),
+    })
+}
+
+/// Retry endpoint for the failed URLs of an earlier batch crawl.
+///
+/// Accepts the `failures` manifest of a `/crawl` response and crawls its
+/// retryable URLs again; failures marked non-retryable are skipped. The
+/// response is a regular crawl response with its own failure manifest.
+#[tracing::instrument(
+    name = "crawl_retry_handler",
+    skip(state, body, tenant_id),
+    fields(
+        http.method = "POST",
+        http.route = "/crawl/retry",
+        manifest_size = body.failures.len(),
+        otel.kind = ?SpanKind::Server
+    )
+)]
+pub async fn crawl_retry(
+    State(state): State<ApplicationContext>,
+    RequestTenant(tenant_id): RequestTenant,
+    Json(body): Json<RetryFailedBody>,
+) -> Result<Json<CrawlResponse>, ApiError> {
+    let start_time = Instant::now();
+
+    validate_retry_request(&body)?;
+
+    let urls = body.retryable_urls();
+    enforce_crawl_policy(&state, &tenant_id, &urls).await?;
+    if let Some(options) = &body.options {
+        enforce_pipeline_hints(&state, &tenant_id, &urls, options.pipeline_hints.as_ref()).await?;
+        ensure_known_schema(&state, options)?;
+    }
+
+    let response = CrawlHandlerFacade::new(state.clone())
+        .retry_failed(body)
+        .await?;
+
+    info!(
+        successful = response.successful,
+        failed = response.failed,
+        total_time_ms = start_time.elapsed().as_millis() as u64,
+        "Crawl retry completed"
+    );
+
+    meter_crawl(&state, &tenant_id, &response).await;
+
+    state.record_http_request(
+        "POST",
+        "/crawl/retry",
+        200,
+        start_time.elapsed().as_secs_f64(),
+    );
+
+    Ok(Json(response))
+}
@@ -0,0 +1,60 @@
+//! Diffs between two versions of a page
+//!
+//! `POST /api/v1/diff` compares two extracted documents, or extracts two
+//! HTML snapshots of a URL and compares those. `GET /api/v1/diff?url=`
+//! returns the last change the pipeline recorded for a URL: every fresh
+//! extraction is diffed against the previous one, so re-crawls (including
+//! `changed_since` crawls) keep it current.
+
+use crate::change_tracking::ChangeTracker;
+use crate::context::ApplicationContext;
+use crate::errors::ApiError;
+use crate::models::{DiffBody, DiffQuery};
+use crate::validation::validate_diff_request;
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use riptide_extraction::diff::{diff_html, ExtractionDiff};
+
+/// POST /api/v1/diff - Diff two documents or two HTML snapshots of a page
+#[tracing::instrument(
+    name = "diff_handler",
+    skip(body),
+    fields(http.method = "POST", http.route = "/api/v1/diff")
+)]
+pub async fn diff(Json(body): Json<DiffBody>) -> Result<Json<ExtractionDiff>, ApiError> {
+    validate_diff_request(&body)?;
+
+    if let (Some(old), Some(new)) = (&body.old, &body.new) {
+        return Ok(Json(ExtractionDiff::between(old, new)));
+    }
+
+    let (url, old_html, new_html) = (
+        body.url.unwrap_or_default(),
+        body.old_html.unwrap_or_default(),
+        body.new_html.unwrap_or_default(),
+    );
+    let diff = tokio::task::spawn_blocking(move || diff_html(&old_html, &new_html, &url))
+        .await
+        .map_err(|e| ApiError::internal(format!("Diff task failed: {}", e)))?
+        .map_err(|e| ApiError::validation(format!("Cannot extract snapshot: {}", e)))?;
+    Ok(Json(diff))
+}
+
+/// GET /api/v1/diff - Last change recorded for a URL
+#[tracing::instrument(
+    name = "latest_diff_handler",
+    skip(state),
+    fields(http.method = "GET", http.route = "/api/v1/diff", url = %query.url)
+)]
+pub async fn latest_diff(
+    State(state): State<ApplicationContext>,
+    Query(query): Query<DiffQuery>,
+) -> Result<Json<ExtractionDiff>, ApiError> {
+    ChangeTracker::from_context(&state)
+        .latest_diff(&query.url)
+        .await
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found(format!("No recorded change for {}", query.url)))
+}
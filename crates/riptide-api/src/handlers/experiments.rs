@@ -0,0 +1,22 @@
+//! A/B experiment comparison endpoint
+//!
+//! Reports, per running experiment, how the treatment arm compares with the
+//! control arm on extraction quality, latency and headless rate.
+
+use crate::context::ApplicationContext;
+use crate::experiments::ExperimentReport;
+use axum::{extract::State, Json};
+use serde::Serialize;
+
+/// Comparison reports of all experiments with recorded traffic
+#[derive(Debug, Serialize)]
+pub struct ExperimentsResponse {
+    pub experiments: Vec<ExperimentReport>,
+}
+
+/// GET /admin/experiments - Per-arm quality, latency and headless rate
+pub async fn get_experiments(State(state): State<ApplicationContext>) -> Json<ExperimentsResponse> {
+    Json(ExperimentsResponse {
+        experiments: state.experiments.report(),
+    })
+}
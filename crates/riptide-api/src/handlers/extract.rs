@@ -7,10 +7,19 @@ use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
 use std::time::Instant;
 
 use crate::context::ApplicationContext;
+use crate::errors::ApiError;
+use crate::handlers::shared::policy::{
+    enforce_crawl_policy, enforce_pipeline_hints, RequestTenant,
+};
 
 // Import HTTP DTOs from riptide-types (Phase 2C.1 - breaking circular dependency)
 use riptide_types::{ExtractRequest, ExtractionMethod};
 
+/// Strategy names [`parse_extraction_strategy`] understands
+const STRATEGIES: &[&str] = &[
+    "native", "css", "wasm", "regex", "fallback", "auto", "multi", "markdown",
+];
+
 /// Parse strategy string to ExtractionMethod enum
 pub(crate) fn parse_extraction_strategy(strategy_str: &str) -> Option<ExtractionMethod> {
     match strategy_str.to_lowercase().as_str() {
@@ -28,10 +37,17 @@ pub(crate) fn parse_extraction_strategy(strategy_str: &str) -> Option<Extraction
 ///
 /// This endpoint provides a unified interface for content extraction,
 /// delegating to the ExtractionFacade for all business logic.
+///
+/// The URL and any `pipeline_hints` are checked against the tenant's crawl
+/// policy before anything is fetched. A hint's `extraction_schema` replaces
+/// the requested strategy; pages are fetched without the cache or headless
+/// rendering, so `force_headless` and `stealth_preset` are rejected here
+/// and only apply to crawls.
 #[axum::debug_handler]
-#[tracing::instrument(skip(state), fields(url = %payload.url, mode = %payload.mode))]
+#[tracing::instrument(skip(state, tenant), fields(url = %payload.url, mode = %payload.mode))]
 pub async fn extract(
     State(state): State<ApplicationContext>,
+    tenant: RequestTenant,
     Json(payload): Json<ExtractRequest>,
 ) -> impl IntoResponse {
     let start = Instant::now();
@@ -42,12 +58,40 @@ pub async fn extract(
         return crate::errors::ApiError::invalid_url(&payload.url, e.to_string()).into_response();
     }
 
-    // Parse strategy from request
-    let extraction_strategy = parse_extraction_strategy(&payload.options.strategy);
+    let hints = payload.pipeline_hints.as_ref();
+    if let Some(hints) = hints {
+        if let Some(schema) = hints
+            .extraction_schema
+            .as_deref()
+            .filter(|s| !STRATEGIES.contains(&s.to_lowercase().as_str()))
+        {
+            return ApiError::validation(format!("Unknown extraction_schema '{}'", schema))
+                .into_response();
+        }
+        if hints.force_headless || hints.stealth_preset.is_some() {
+            return ApiError::validation(
+                "force_headless and stealth_preset hints are only supported by /crawl",
+            )
+            .into_response();
+        }
+    }
+    let RequestTenant(tenant_id) = tenant;
+    if let Err(e) = enforce_crawl_policy(&state, &tenant_id, &[&payload.url]).await {
+        return e.into_response();
+    }
+    if let Err(e) = enforce_pipeline_hints(&state, &tenant_id, &[&payload.url], hints).await {
+        return e.into_response();
+    }
+
+    // Parse strategy from request, unless a hint prefers another schema
+    let strategy = hints
+        .and_then(|h| h.extraction_schema.as_deref())
+        .unwrap_or(&payload.options.strategy);
+    let extraction_strategy = parse_extraction_strategy(strategy);
 
     // Build extraction options from request
     let html_options = riptide_facade::facades::HtmlExtractionOptions {
-        as_markdown: payload.options.strategy == "markdown",
+        as_markdown: strategy == "markdown",
         clean: true,
         include_metadata: true,
         extract_links: false,
@@ -121,5 +165,20 @@ mod tests {
         assert_eq!(req.options.strategy, "css");
         assert_eq!(req.options.quality_threshold, 0.8);
         assert_eq!(req.options.timeout_ms, 10000);
+        assert!(req.pipeline_hints.is_none());
+    }
+
+    #[test]
+    fn test_extract_request_with_pipeline_hints() {
+        let json = r#"{
+            "url": "https://example.com",
+            "pipeline_hints": {"skip_cache": true, "extraction_schema": "css"}
+        }"#;
+        let req: ExtractRequest = serde_json::from_str(json).unwrap();
+        let hints = req.pipeline_hints.unwrap();
+        assert!(hints.skip_cache);
+        assert!(!hints.force_headless);
+        assert_eq!(hints.extraction_schema.as_deref(), Some("css"));
+        assert!(STRATEGIES.contains(&"css"));
     }
 }
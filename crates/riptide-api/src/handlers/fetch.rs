@@ -2,7 +2,8 @@
 //!
 //! ## Facade Pattern Usage
 //!
-//! This handler uses `state.fetch_engine` directly for advanced metrics and per-host circuit breakers.
+//! This handler reads `state.per_host_fetch_engine`, the engine behind
+//! `state.scraper_facade`, for per-host metrics and circuit breakers.
 //!
 //! For simple HTTP operations without metrics, consider using `state.scraper_facade` which provides:
 //! - `scraper_facade.fetch_html(url)` - Fetch HTML content as string
@@ -21,14 +22,12 @@ use crate::{context::ApplicationContext, errors::ApiResult};
 /// - Per-host request counts, success/failure rates, average duration
 /// - Circuit breaker states for each host
 /// - Total requests across all hosts
-///
-/// Note: Basic FetchEngine doesn't track per-host metrics.
-/// For detailed metrics, consider using PerHostFetchEngine.
-/// For simple HTTP operations, consider using state.scraper_facade.
+/// - Content-Encoding counters and compression ratios
+/// - Server country and ASN per host, when GeoIP is configured
 pub async fn get_fetch_metrics(
     State(state): State<ApplicationContext>,
 ) -> ApiResult<Json<FetchMetricsResponse>> {
-    // Using fetch_engine directly (facade alternative: state.scraper_facade for simple operations)
-    let metrics = state.fetch_engine.get_all_metrics().await;
+    let mut metrics = state.per_host_fetch_engine.get_all_metrics().await;
+    metrics.robots = Some(state.robots_service.stats());
     Ok(Json(metrics))
 }
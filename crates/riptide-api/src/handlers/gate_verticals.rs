@@ -0,0 +1,24 @@
+//! Per-vertical gate report endpoint
+//!
+//! Reports, per content vertical, the gate thresholds in force and how the
+//! gate decided, including the share of pages sent to headless rendering.
+
+use crate::context::ApplicationContext;
+use crate::gate_verticals::VerticalGateReport;
+use axum::{extract::State, Json};
+use serde::Serialize;
+
+/// Gate thresholds and decisions of every content vertical
+#[derive(Debug, Serialize)]
+pub struct GateVerticalsResponse {
+    pub verticals: Vec<VerticalGateReport>,
+}
+
+/// GET /admin/gate/verticals - Per-vertical thresholds and headless rate
+pub async fn get_gate_verticals(
+    State(state): State<ApplicationContext>,
+) -> Json<GateVerticalsResponse> {
+    Json(GateVerticalsResponse {
+        verticals: state.gate_verticals.report(),
+    })
+}
@@ -151,6 +151,7 @@ pub async fn health(
         uptime,
         dependencies,
         metrics,
+        region: state.api_config.region.local_region.clone(),
     };
 
     info!(
@@ -432,6 +433,7 @@ pub async fn health_metrics_check(
 /// - Whether the system is distributed (multi-instance capable)
 /// - Cache and session persistence capabilities
 /// - Overall deployment mode (minimal, enhanced, or distributed)
+/// - Dependencies the server started without and whether they recovered
 ///
 /// This endpoint helps users understand their system configuration.
 #[tracing::instrument(
@@ -448,11 +450,12 @@ pub async fn health_capabilities(
 ) -> Result<impl IntoResponse, ApiError> {
     debug!("Retrieving system capabilities");
 
-    let capabilities = state.capabilities.clone();
+    let capabilities = state.capabilities.with_boot_state(&state.boot);
 
     info!(
         deployment_mode = %capabilities.deployment_mode,
         cache_backend = %capabilities.cache_backend,
+        degraded = ?capabilities.degraded,
         "System capabilities retrieved"
     );
 
@@ -0,0 +1,114 @@
+//! Push-mode ingestion of externally fetched content
+//!
+//! Teams with their own fetch infrastructure POST the responses they fetched
+//! to `/api/v1/ingest`; each one goes through the same gate and extraction
+//! steps as a crawled page. Like captures, pushed content is never cached
+//! and never updates change tracking: it is whatever the caller sent, not
+//! what the origin served. Tenant crawl policies and cost metering apply as
+//! for crawls.
+
+use crate::context::ApplicationContext;
+use crate::errors::ApiError;
+use crate::handlers::shared::policy::{
+    enforce_crawl_policy, enforce_pipeline_hints, RequestTenant,
+};
+use crate::handlers::shared::usage::{crawl_result_usage, meter_fetch};
+use crate::models::{CrawlResult, IngestBody};
+use crate::pipeline::PipelineOrchestrator;
+use crate::validation::validate_ingest_request;
+use axum::{extract::State, Json};
+use base64::Engine;
+use riptide_types::ports::HttpResponse;
+use std::time::Instant;
+use tracing::info;
+
+/// POST /api/v1/ingest - Extract and cache content fetched outside RipTide
+#[tracing::instrument(
+    name = "ingest_handler",
+    skip(state, body, tenant_id),
+    fields(http.method = "POST", http.route = "/api/v1/ingest", url = %body.url)
+)]
+pub async fn ingest(
+    State(state): State<ApplicationContext>,
+    RequestTenant(tenant_id): RequestTenant,
+    Json(body): Json<IngestBody>,
+) -> Result<Json<CrawlResult>, ApiError> {
+    let start_time = Instant::now();
+
+    validate_ingest_request(&body)?;
+
+    enforce_crawl_policy(&state, &tenant_id, &[&body.url]).await?;
+    enforce_pipeline_hints(
+        &state,
+        &tenant_id,
+        &[&body.url],
+        body.options
+            .as_ref()
+            .and_then(|o| o.pipeline_hints.as_ref()),
+    )
+    .await?;
+
+    let content = match body.content {
+        Some(content) => content.into_bytes(),
+        None => base64::engine::general_purpose::STANDARD
+            .decode(body.content_base64.as_deref().unwrap_or_default().trim())
+            .map_err(|e| ApiError::validation(format!("Invalid content_base64: {}", e)))?,
+    };
+    let max_bytes = state.config.max_response_bytes;
+    if max_bytes > 0 && content.len() as u64 > max_bytes {
+        return Err(ApiError::PayloadTooLarge {
+            message: format!(
+                "Ingested content is {} bytes, over the {} byte limit",
+                content.len(),
+                max_bytes
+            ),
+        });
+    }
+
+    let mut response_headers = body.headers;
+    if let Some(content_type) = body.content_type {
+        response_headers.retain(|name, _| !name.eq_ignore_ascii_case("content-type"));
+        response_headers.insert("content-type".to_string(), content_type);
+    }
+    let response = HttpResponse::new(body.status, response_headers, content);
+    let fetched_at = body.fetched_at.unwrap_or_else(chrono::Utc::now);
+
+    let mut options = body.options.unwrap_or_default();
+    options.cache_mode = "bypass".to_string();
+
+    let result = PipelineOrchestrator::new(state.clone(), options)
+        .execute_ingested(&body.url, response, fetched_at)
+        .await?;
+
+    let result = CrawlResult {
+        url: body.url,
+        status: result.http_status,
+        from_cache: false,
+        cache_status: result.cache_status,
+        gate_decision: result.gate_decision,
+        quality_score: result.quality_score,
+        processing_time_ms: result.processing_time_ms,
+        document: Some(result.document),
+        error: None,
+        cache_key: result.cache_key,
+        artifacts: result.artifacts,
+        timings: Some(result.timings),
+    };
+
+    meter_fetch(&state, &tenant_id, &result.url, crawl_result_usage(&result)).await;
+
+    info!(
+        url = %result.url,
+        gate_decision = %result.gate_decision,
+        total_time_ms = start_time.elapsed().as_millis() as u64,
+        "Ingest request completed"
+    );
+    state.record_http_request(
+        "POST",
+        "/api/v1/ingest",
+        200,
+        start_time.elapsed().as_secs_f64(),
+    );
+
+    Ok(Json(result))
+}
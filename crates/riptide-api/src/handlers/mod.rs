@@ -1,18 +1,33 @@
 // Module declarations
-#[cfg(feature = "persistence")]
 pub mod admin;
+pub mod admin_api_keys; // Tenant-scoped API keys
+pub mod admin_bandwidth; // Live download bandwidth limits
+pub mod admin_config; // Redacted configuration dump
+pub mod admin_drain; // Graceful drain trigger and progress
+#[cfg(feature = "wasm-extractor")]
+pub mod admin_extractor; // WASM extractor component hot reload
+pub mod admin_usage; // Per-tenant cost usage
+pub mod artifacts; // Stored screenshot/thumbnail downloads
 #[cfg(feature = "browser")]
 pub mod browser;
+#[cfg(feature = "fetch")]
+pub mod capture; // DOM snapshots pushed by the browser extension
 #[cfg(feature = "extraction")]
 pub mod chunking;
 #[cfg(feature = "spider")]
 pub mod crawl;
+#[cfg(feature = "extraction")]
+pub mod diff; // Diffs between two versions of a page
 pub mod engine_selection;
+pub mod experiments; // A/B experiment comparison reports
 #[cfg(feature = "extraction")]
 pub mod extract;
 #[cfg(feature = "fetch")]
 pub mod fetch;
+pub mod gate_verticals; // Per-vertical gate thresholds and headless rates
 pub mod health;
+#[cfg(feature = "fetch")]
+pub mod ingest; // Push-mode ingestion of externally fetched content
 #[cfg(feature = "llm")]
 pub mod llm;
 pub mod memory; // Memory profiling endpoint for production observability
@@ -45,7 +60,7 @@ pub mod workers; // Phase 10.4: Domain profile management API
 
 // Re-export main handlers for backward compatibility
 #[cfg(feature = "spider")]
-pub use crawl::crawl;
+pub use crawl::{crawl, crawl_manifest, crawl_retry};
 #[cfg(feature = "extraction")]
 pub use extract::extract;
 pub use health::{health, health_capabilities, health_detailed, init_startup_time, START_TIME};
@@ -16,7 +16,7 @@ use tracing::{debug, error, info};
 
 /// Create profile (ultra-thin - 4 LOC)
 pub async fn create_profile(
-    State(_state): State<ApplicationContext>,
+    State(state): State<ApplicationContext>,
     Json(request): Json<CreateProfileRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
     let facade = ProfileFacade::new();
@@ -30,6 +30,7 @@ pub async fn create_profile(
             error!(error = %e, domain = request.domain, "Failed to create profile");
             ApiError::from(e)
         })?;
+    state.gate_verticals.forget(&profile.domain);
     info!(domain = request.domain, "Profile created");
     Ok((
         StatusCode::CREATED,
@@ -52,7 +53,7 @@ pub async fn get_profile(
 
 /// Update profile (ultra-thin - 4 LOC)
 pub async fn update_profile(
-    State(_state): State<ApplicationContext>,
+    State(state): State<ApplicationContext>,
     Path(domain): Path<String>,
     Json(request): Json<UpdateProfileRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
@@ -67,18 +68,20 @@ pub async fn update_profile(
             error!(error = %e, domain = domain, "Failed to update profile");
             ApiError::from(e)
         })?;
+    state.gate_verticals.forget(&domain);
     info!(domain = domain, "Profile updated");
     Ok(Json(profile))
 }
 
 /// Delete profile (ultra-thin - 3 LOC)
 pub async fn delete_profile(
-    State(_state): State<ApplicationContext>,
+    State(state): State<ApplicationContext>,
     Path(domain): Path<String>,
 ) -> Result<impl IntoResponse, ApiError> {
     ProfileManager::delete(&domain).map_err(|_| ApiError::NotFound {
         resource: format!("Profile: {}", domain),
     })?;
+    state.gate_verticals.forget(&domain);
     info!(domain = domain, "Profile deleted");
     Ok(StatusCode::NO_CONTENT)
 }
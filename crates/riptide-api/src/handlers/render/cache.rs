@@ -0,0 +1,179 @@
+//! Render output caching for the render endpoint.
+
+use super::models::RenderRequest;
+use crate::context::ApplicationContext;
+use riptide_cache::RenderCacheKey;
+use riptide_headless::dynamic::DynamicRenderResult;
+use riptide_types::pipeline::PipelineArtifact;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+/// Render output as stored in the render cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct CachedRender {
+    pub final_url: String,
+    pub result: DynamicRenderResult,
+    /// Screenshot variants already moved to blob storage
+    pub stored_artifacts: Vec<PipelineArtifact>,
+}
+
+/// Render cache key for a request, `None` when the render must not be shared
+///
+/// Renders with an explicit browser session depend on that session's state
+/// and are never cached.
+pub(super) fn render_cache_key(request: &RenderRequest) -> Option<RenderCacheKey> {
+    if request.session_id.is_some() {
+        return None;
+    }
+
+    let viewport = request
+        .dynamic_config
+        .as_ref()
+        .and_then(|c| c.viewport.as_ref())
+        .map(|v| {
+            format!(
+                "{}x{}@{}x{}",
+                v.width,
+                v.height,
+                v.device_scale_factor,
+                if v.is_mobile { "-mobile" } else { "" }
+            )
+        })
+        .unwrap_or_else(|| "default".to_string());
+    let stealth_preset = request
+        .stealth_config
+        .as_ref()
+        .map(|c| format!("{:?}", c.preset).to_lowercase())
+        .unwrap_or_else(|| "none".to_string());
+    let action_script = serde_json::to_string(&(&request.mode, &request.dynamic_config)).ok()?;
+
+    Some(RenderCacheKey::new(
+        &request.url,
+        viewport,
+        stealth_preset,
+        &action_script,
+    ))
+}
+
+/// Cached render for `key`, unless the session holds cookies for the target host
+pub(super) async fn lookup(
+    state: &ApplicationContext,
+    key: &RenderCacheKey,
+    session_id: Option<&str>,
+) -> Option<CachedRender> {
+    if has_session_cookies(state, session_id, &key.url).await {
+        return None;
+    }
+    match state.render_cache.get::<CachedRender>(key).await {
+        Ok(cached) => {
+            if cached.is_some() {
+                debug!(url = %key.url, "Render cache hit");
+            }
+            cached
+        }
+        Err(e) => {
+            warn!(url = %key.url, error = %e, "Render cache lookup failed");
+            None
+        }
+    }
+}
+
+/// Store a successful render, unless the session holds cookies for the target host
+pub(super) async fn store(
+    state: &ApplicationContext,
+    key: &RenderCacheKey,
+    session_id: Option<&str>,
+    cached: &CachedRender,
+) {
+    if !cached.result.success || has_session_cookies(state, session_id, &key.url).await {
+        return;
+    }
+    if let Err(e) = state.render_cache.put(key, cached).await {
+        warn!(url = %key.url, error = %e, "Failed to cache render output");
+    }
+}
+
+/// Whether the render would carry session cookies, making its output private
+async fn has_session_cookies(
+    state: &ApplicationContext,
+    session_id: Option<&str>,
+    url: &str,
+) -> bool {
+    let (Some(session_id), Some(host)) = (
+        session_id,
+        url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string)),
+    ) else {
+        return false;
+    };
+    state
+        .session_manager
+        .get_cookies_for_domain(session_id, &host)
+        .await
+        .map(|cookies| !cookies.is_empty())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_headless::dynamic::{DynamicConfig, PageAction, ViewportConfig};
+
+    fn request() -> RenderRequest {
+        RenderRequest {
+            url: "https://example.com/app".to_string(),
+            mode: Some(riptide_types::RenderMode::Dynamic),
+            dynamic_config: Some(DynamicConfig::default()),
+            stealth_config: None,
+            pdf_config: None,
+            output_format: None,
+            capture_artifacts: None,
+            timeout: None,
+            session_id: None,
+            archive_warc: None,
+        }
+    }
+
+    #[test]
+    fn test_key_covers_render_config() {
+        let base = render_cache_key(&request()).unwrap();
+        assert_eq!(base.viewport, "default");
+        assert_eq!(base.stealth_preset, "none");
+        assert_eq!(render_cache_key(&request()), Some(base.clone()));
+
+        let mut mobile = request();
+        if let Some(config) = mobile.dynamic_config.as_mut() {
+            config.viewport = Some(ViewportConfig {
+                width: 390,
+                height: 844,
+                device_scale_factor: 3.0,
+                is_mobile: true,
+                user_agent: None,
+            });
+        }
+        let mobile = render_cache_key(&mobile).unwrap();
+        assert_eq!(mobile.viewport, "390x844@3x-mobile");
+
+        let mut stealth = request();
+        stealth.stealth_config = Some(riptide_stealth::StealthConfig::default());
+        assert_eq!(render_cache_key(&stealth).unwrap().stealth_preset, "medium");
+
+        let mut scripted = request();
+        if let Some(config) = scripted.dynamic_config.as_mut() {
+            config.actions.push(PageAction::Click {
+                selector: "#more".to_string(),
+                wait_after: None,
+            });
+        }
+        let scripted = render_cache_key(&scripted).unwrap();
+        assert_ne!(scripted.action_script_hash, base.action_script_hash);
+    }
+
+    #[test]
+    fn test_explicit_session_is_not_cached() {
+        let mut request = request();
+        request.session_id = Some("session-1".to_string());
+        assert!(render_cache_key(&request).is_none());
+    }
+}
@@ -1,5 +1,6 @@
+use super::models::FrameDocument;
 use crate::errors::ApiResult;
-use riptide_headless::dynamic::DynamicRenderResult;
+use riptide_headless::dynamic::{DynamicRenderResult, FrameProvenance};
 use riptide_types::{ExtractedDoc as CoreExtractedDoc, OutputFormat};
 use tracing::{debug, info, warn};
 
@@ -7,12 +8,14 @@ use tracing::{debug, info, warn};
 ///
 /// This function has been migrated to use the ExtractionFacade pattern for better
 /// consistency across the codebase. It now uses the facade's extract_html method
-/// with appropriate options based on the output format.
+/// with appropriate options based on the output format. `OutputFormat::CleanHtml`
+/// adds sanitized reader-mode HTML, with images proxied through `image_proxy`.
 pub(super) async fn extract_with_extraction_facade(
     facade: &riptide_facade::facades::ExtractionFacade,
     html: &str,
     url: &str,
     output_format: &OutputFormat,
+    image_proxy: Option<&str>,
 ) -> Result<CoreExtractedDoc, Box<dyn std::error::Error + Send + Sync>> {
     // Validate inputs before processing
     if html.trim().is_empty() {
@@ -61,6 +64,15 @@ pub(super) async fn extract_with_extraction_facade(
             ))) as Box<dyn std::error::Error + Send + Sync>
         })?;
 
+    let clean_html = matches!(output_format, OutputFormat::CleanHtml).then(|| {
+        let converter = riptide_extraction::CleanHtmlConverter::new().with_base_url(url);
+        match image_proxy {
+            Some(proxy) => converter.with_image_proxy(proxy),
+            None => converter,
+        }
+        .convert(html)
+    });
+
     // Convert facade result to CoreExtractedDoc
     let core_doc = CoreExtractedDoc {
         url: extracted.url.clone(),
@@ -73,8 +85,22 @@ pub(super) async fn extract_with_extraction_facade(
         byline: extracted.metadata.get("author").cloned(),
         published_iso: extracted.metadata.get("published_date").cloned(),
         markdown: extracted.markdown.clone(),
+        clean_html,
         media: extracted.images.clone(),
         parser_metadata: None,
+        provenance: None,
+        translation: None,
+        structured_data: Vec::new(),
+        annotations: None,
+        feed: None,
+        feed_links: Vec::new(),
+        classification: None,
+        media_assets: Vec::new(),
+        embedded_media: Vec::new(),
+        images: Vec::new(),
+        references: Vec::new(),
+        language_spans: Vec::new(),
+        quality_report: None,
         language: extracted.metadata.get("language").cloned(),
         reading_time: None,
         word_count: Some(crate::utils::safe_conversions::word_count_to_u32(
@@ -95,6 +121,7 @@ pub(super) async fn extract_content(
     render_result: &Option<DynamicRenderResult>,
     output_format: &OutputFormat,
     url: &str,
+    image_proxy: Option<&str>,
 ) -> ApiResult<Option<CoreExtractedDoc>> {
     if let Some(result) = render_result {
         if !result.success {
@@ -102,7 +129,9 @@ pub(super) async fn extract_content(
         }
 
         // Use ExtractionFacade to process the HTML
-        match extract_with_extraction_facade(facade, &result.html, url, output_format).await {
+        match extract_with_extraction_facade(facade, &result.html, url, output_format, image_proxy)
+            .await
+        {
             Ok(doc) => {
                 // Log extraction completion
                 info!(
@@ -141,6 +170,51 @@ pub(super) async fn extract_content(
     }
 }
 
+/// Extract cross-origin sub-documents as separate documents
+///
+/// Inlined same-origin frames are already part of the main document, so only
+/// frames carrying their own HTML are extracted. The raw HTML is dropped from
+/// the provenance once extracted.
+pub(super) async fn extract_frames(
+    facade: &riptide_facade::facades::ExtractionFacade,
+    frames: Vec<FrameProvenance>,
+    output_format: &OutputFormat,
+    image_proxy: Option<&str>,
+) -> Vec<FrameDocument> {
+    let mut documents = Vec::with_capacity(frames.len());
+    for mut provenance in frames {
+        let content = match provenance.html.take() {
+            Some(html) => {
+                match extract_with_extraction_facade(
+                    facade,
+                    &html,
+                    &provenance.src,
+                    output_format,
+                    image_proxy,
+                )
+                .await
+                {
+                    Ok(doc) => Some(doc),
+                    Err(e) => {
+                        warn!(
+                            error = %e,
+                            frame = %provenance.src,
+                            "Frame extraction failed"
+                        );
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+        documents.push(FrameDocument {
+            provenance,
+            content,
+        });
+    }
+    documents
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,6 +237,7 @@ mod tests {
             "",
             "https://example.com",
             &OutputFormat::Document,
+            None,
         )
         .await;
         assert!(result.is_err(), "Should reject empty HTML");
@@ -173,6 +248,7 @@ mod tests {
             "<html><body>Test</body></html>",
             "",
             &OutputFormat::Document,
+            None,
         )
         .await;
         assert!(result.is_err(), "Should reject empty URL");
@@ -183,6 +259,7 @@ mod tests {
             "<html><body>Test</body></html>",
             "not-a-url",
             &OutputFormat::Document,
+            None,
         )
         .await;
         assert!(result.is_err(), "Should reject invalid URL");
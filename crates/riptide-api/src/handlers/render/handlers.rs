@@ -1,10 +1,20 @@
 //! Minimal render handlers delegating to processing strategies.
 
+use super::cache::CachedRender;
 use super::models::{RenderRequest, RenderResponse, RenderStats};
 use crate::context::ApplicationContext;
 use crate::errors::ApiError;
+use crate::handlers::shared::policy::{enforce_crawl_policy, RequestTenant};
+use crate::handlers::shared::usage::meter_fetch;
+use crate::media_pipeline::{MediaPipeline, MediaPipelineConfig};
 use crate::sessions::middleware::SessionContext;
 use axum::{extract::State, response::IntoResponse, Json};
+use riptide_cache::RenderCacheKey;
+use riptide_security::{MeteredUsage, TenantId};
+use riptide_types::pipeline::PipelineArtifact;
+use riptide_types::usage::UsageScope;
+use riptide_types::RoutingHint;
+use std::sync::Arc;
 use std::time::Instant;
 use tracing::{info, warn};
 
@@ -12,49 +22,147 @@ use tracing::{info, warn};
 pub async fn render(
     State(state): State<ApplicationContext>,
     session_ctx: SessionContext,
+    RequestTenant(tenant_id): RequestTenant,
     Json(body): Json<RenderRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
     let start = Instant::now();
-    let _guard = state
-        .resource_manager
-        .acquire_render_resources(&body.url)
-        .await?;
+    enforce_crawl_policy(&state, &tenant_id, &[&body.url]).await?;
+    // Routed before any fetch so the hint reflects where the render should run
+    let routing = state.route_region(&body.url).await;
+    if !routing.is_local() {
+        info!(
+            url = %body.url,
+            region = %routing.local_region,
+            preferred_region = %routing.preferred_region,
+            preferred_endpoint = ?routing.preferred_endpoint,
+            "Render served outside the region closest to the target host"
+        );
+    }
+    let session_id = body
+        .session_id
+        .as_ref()
+        .map(|s| s.to_string())
+        .or_else(|| Some(session_ctx.session_id().to_string()));
+    if body.url.is_empty() {
+        return Err(ApiError::validation("URL cannot be empty"));
+    }
+    if let Some(auto_scroll) = body
+        .dynamic_config
+        .as_ref()
+        .and_then(|config| config.auto_scroll.as_ref())
+    {
+        auto_scroll.validate().map_err(ApiError::validation)?;
+    }
+
+    // Cache hits are served without waiting for a render slot
+    let cache_key = super::cache::render_cache_key(&body);
+    let cached = match &cache_key {
+        Some(key) => super::cache::lookup(&state, key, session_id.as_deref()).await,
+        None => None,
+    };
+    let _guard = if cached.is_some() {
+        None
+    } else {
+        Some(
+            state
+                .resource_manager
+                .acquire_render_resources(&body.url)
+                .await?,
+        )
+    };
     let timeout = body
         .timeout
         .map(std::time::Duration::from_secs)
         .unwrap_or_else(|| state.api_config.get_timeout("render"));
+    let render = RenderJob {
+        session_id,
+        cache_key,
+        cached,
+        routing,
+        start,
+    };
     tokio::time::timeout(
         timeout,
-        process_render(state.clone(), session_ctx, body, start),
+        process_render(state.clone(), tenant_id, body, render),
     )
     .await
     .map_err(|_| ApiError::timeout("Render", "Exceeded timeout"))?
 }
 
+/// Per-request render state resolved before the render slot is taken
+struct RenderJob {
+    session_id: Option<String>,
+    cache_key: Option<RenderCacheKey>,
+    cached: Option<CachedRender>,
+    routing: RoutingHint,
+    start: Instant,
+}
+
 async fn process_render(
     state: ApplicationContext,
-    session_ctx: SessionContext,
+    tenant_id: TenantId,
     body: RenderRequest,
-    start: Instant,
+    render: RenderJob,
 ) -> Result<impl IntoResponse, ApiError> {
-    let session_id = body
-        .session_id
-        .as_ref()
-        .map(|s| s.to_string())
-        .or_else(|| Some(session_ctx.session_id().to_string()));
-    if body.url.is_empty() {
-        return Err(ApiError::validation("URL cannot be empty"));
-    }
-
-    let (final_url, result, pdf) =
-        super::strategies::process_by_mode(&state, &body, session_id.as_deref()).await?;
+    let RenderJob {
+        session_id,
+        cache_key,
+        cached,
+        routing,
+        start,
+    } = render;
+    let cache_hit = cached.is_some();
+    // Wire bytes and LLM tokens of the render's own fetches; the headless
+    // browser's traffic is charged as render time
+    let usage = Arc::new(UsageScope::default());
+    let (final_url, mut result, pdf, stored_artifacts) = match cached {
+        Some(cached) => (
+            cached.final_url,
+            Some(cached.result),
+            None,
+            cached.stored_artifacts,
+        ),
+        None => {
+            let (final_url, mut result, pdf) = usage
+                .clone()
+                .scope(super::strategies::process_by_mode(
+                    &state,
+                    &body,
+                    session_id.as_deref(),
+                ))
+                .await?;
+            let stored_artifacts = store_screenshot(&state, &final_url, result.as_mut()).await;
+            if let (Some(key), Some(rendered), None) = (&cache_key, &result, &pdf) {
+                let cached = CachedRender {
+                    final_url: final_url.clone(),
+                    result: rendered.clone(),
+                    stored_artifacts: stored_artifacts.clone(),
+                };
+                super::cache::store(&state, key, session_id.as_deref(), &cached).await;
+            }
+            (final_url, result, pdf, stored_artifacts)
+        }
+    };
+    let output_format = body.output_format.clone().unwrap_or_default();
+    let image_proxy = state.config.image_proxy_url.as_deref();
     let content = super::extraction::extract_content(
         &state.extraction_facade,
         &result,
-        &body.output_format.unwrap_or_default(),
+        &output_format,
         &final_url,
+        image_proxy,
     )
     .await?;
+    let frames = super::extraction::extract_frames(
+        &state.extraction_facade,
+        result
+            .as_mut()
+            .map(|r| std::mem::take(&mut r.frames))
+            .unwrap_or_default(),
+        &output_format,
+        image_proxy,
+    )
+    .await;
 
     let stats = RenderStats {
         total_time_ms: start.elapsed().as_millis() as u64,
@@ -79,6 +187,32 @@ async fn process_render(
 
     let success = result.as_ref().map(|r| r.success).unwrap_or(false)
         && pdf.as_ref().map(|p| p.success).unwrap_or(true);
+
+    if !cache_hit {
+        // Rendered HTML is cached under the render cache key (PDFs are not)
+        let cached_bytes = match (&cache_key, &pdf) {
+            (Some(_), None) => stats.page_size_bytes,
+            _ => 0,
+        };
+        let metered = MeteredUsage {
+            bandwidth_bytes: usage.bandwidth_bytes(),
+            render_seconds: stats.dynamic_time_ms.unwrap_or(0) as f64 / 1000.0,
+            llm_tokens: usage.llm_tokens(),
+            storage_bytes: cached_bytes
+                + stored_artifacts.iter().map(|a| a.size_bytes).sum::<u64>(),
+        };
+        meter_fetch(&state, &tenant_id, &final_url, metered).await;
+    }
+
+    let warc_files = if body.archive_warc.unwrap_or(false) {
+        match &result {
+            Some(rendered) => archive_render(&state, &body, &final_url, rendered).await,
+            None => None,
+        }
+    } else {
+        None
+    };
+
     let response = RenderResponse {
         url: body.url,
         final_url,
@@ -87,10 +221,15 @@ async fn process_render(
         content,
         pdf_result: pdf,
         artifacts: result.and_then(|r| r.artifacts),
+        stored_artifacts,
         stats,
         error: None,
         stealth_applied: vec![],
         session_info: None,
+        warc_files,
+        routing,
+        cache_hit,
+        frames,
     };
 
     if let Err(e) = state
@@ -101,6 +240,96 @@ async fn process_render(
     {
         warn!("Metrics failed: {}", e);
     }
-    info!(url=%response.url, success, ms=response.stats.total_time_ms, "Render complete");
+    state.transport_metrics.record_region_render(
+        &response.routing.local_region,
+        &response.routing.preferred_region,
+        start.elapsed().as_secs_f64(),
+    );
+    info!(
+        url = %response.url,
+        success,
+        cache_hit,
+        ms = response.stats.total_time_ms,
+        "Render complete"
+    );
     Ok(Json(response))
 }
+
+/// Move a captured screenshot into blob storage, replacing the inline base64.
+///
+/// On failure the base64 screenshot is left in place so clients still receive it.
+async fn store_screenshot(
+    state: &ApplicationContext,
+    final_url: &str,
+    rendered: Option<&mut riptide_headless::dynamic::DynamicRenderResult>,
+) -> Vec<PipelineArtifact> {
+    let Some(artifacts) = rendered.and_then(|r| r.artifacts.as_mut()) else {
+        return Vec::new();
+    };
+    let Some(screenshot) = artifacts.screenshot.as_deref() else {
+        return Vec::new();
+    };
+
+    let pipeline = MediaPipeline::new(state.blob_storage.clone(), MediaPipelineConfig::default());
+    match pipeline
+        .process_screenshot_base64(final_url, screenshot)
+        .await
+    {
+        Ok(stored) => {
+            artifacts.screenshot = None;
+            stored
+        }
+        Err(e) => {
+            warn!(url = %final_url, error = %e, "Failed to store screenshot artifacts");
+            Vec::new()
+        }
+    }
+}
+
+/// Write the rendered DOM and render metadata to a new WARC archive.
+///
+/// Archive failures are logged and never fail the render.
+async fn archive_render(
+    state: &ApplicationContext,
+    body: &RenderRequest,
+    final_url: &str,
+    rendered: &riptide_headless::dynamic::DynamicRenderResult,
+) -> Option<Vec<String>> {
+    let archive = state.config.create_warc_archive()?;
+    let fields = vec![
+        ("via".to_string(), body.url.clone()),
+        (
+            "render-mode".to_string(),
+            format!("{:?}", body.mode.clone().unwrap_or_default()),
+        ),
+        (
+            "render-time-ms".to_string(),
+            rendered.render_time_ms.to_string(),
+        ),
+        (
+            "actions-executed".to_string(),
+            rendered.actions_executed.len().to_string(),
+        ),
+    ];
+
+    let written = async {
+        archive
+            .write_resource(
+                final_url,
+                "text/html; charset=utf-8",
+                rendered.html.as_bytes(),
+            )
+            .await?;
+        archive.write_metadata(final_url, &fields).await?;
+        archive.finish().await
+    }
+    .await;
+
+    match written {
+        Ok(files) => Some(files),
+        Err(e) => {
+            warn!(url = %final_url, error = %e, "Failed to write WARC records for render");
+            None
+        }
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! This module is organized into focused sub-modules for maintainability.
 
+pub mod cache;
 pub mod extraction;
 pub mod handlers;
 pub mod models;
@@ -33,6 +34,7 @@ mod tests {
             capture_artifacts: None,
             timeout: None,
             session_id: None,
+            archive_warc: None,
         };
 
         // This would be tested with actual state
@@ -93,6 +95,7 @@ mod tests {
             (OutputFormat::Document, ExtractionMode::Full),
             (OutputFormat::Text, ExtractionMode::Article),
             (OutputFormat::NdJson, ExtractionMode::Article),
+            (OutputFormat::CleanHtml, ExtractionMode::Article),
         ];
 
         for (output_format, expected_mode) in test_cases {
@@ -102,6 +105,7 @@ mod tests {
                 OutputFormat::Text => ExtractionMode::Article,
                 OutputFormat::NdJson => ExtractionMode::Article,
                 OutputFormat::Chunked => ExtractionMode::Article,
+                OutputFormat::CleanHtml => ExtractionMode::Article,
             };
 
             // Compare discriminants since ExtractionMode doesn't implement PartialEq for Custom variant
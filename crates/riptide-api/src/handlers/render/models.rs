@@ -1,4 +1,4 @@
-use riptide_types::{ExtractedDoc, OutputFormat, RenderMode};
+use riptide_types::{ExtractedDoc, OutputFormat, RenderMode, RoutingHint};
 use serde::{Deserialize, Serialize};
 
 /// Request body for enhanced render endpoint
@@ -31,6 +31,10 @@ pub struct RenderRequest {
 
     /// Session ID to use for persistent browser state (optional)
     pub session_id: Option<String>,
+
+    /// Write the rendered page to a WARC archive (resource + metadata records)
+    #[serde(default)]
+    pub archive_warc: Option<bool>,
 }
 
 /// Response for render endpoint
@@ -55,8 +59,14 @@ pub struct RenderResponse {
     pub pdf_result: Option<riptide_pdf::PdfProcessingResult>,
 
     /// Dynamic rendering artifacts
+    ///
+    /// `screenshot` is cleared once the screenshot has been moved to `stored_artifacts`.
     pub artifacts: Option<riptide_headless::dynamic::RenderArtifacts>,
 
+    /// Screenshot and thumbnail variants stored via blob storage, with durable URLs
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stored_artifacts: Vec<riptide_types::pipeline::PipelineArtifact>,
+
     /// Processing statistics
     pub stats: RenderStats,
 
@@ -68,6 +78,31 @@ pub struct RenderResponse {
 
     /// Session information used for rendering
     pub session_info: Option<SessionRenderInfo>,
+
+    /// WARC archive files written for this render, when `archive_warc` was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warc_files: Option<Vec<String>>,
+
+    /// Region routing hint for the target host in multi-region deployments
+    pub routing: RoutingHint,
+
+    /// Whether the rendered page was served from the render cache
+    pub cache_hit: bool,
+
+    /// Iframes handled during rendering, with cross-origin sub-documents extracted
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub frames: Vec<FrameDocument>,
+}
+
+/// An iframe with its provenance and, for sub-documents, the extracted content
+#[derive(Serialize, Debug)]
+pub struct FrameDocument {
+    /// Where the frame came from and how it was handled
+    #[serde(flatten)]
+    pub provenance: riptide_headless::dynamic::FrameProvenance,
+
+    /// Content extracted from a cross-origin sub-document
+    pub content: Option<ExtractedDoc>,
 }
 
 /// Rendering statistics
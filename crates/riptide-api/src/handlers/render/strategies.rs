@@ -241,6 +241,7 @@ async fn process_static(
             render_time_ms: 100,
             actions_executed: vec![],
             wait_conditions_met: vec![],
+            frames: Vec::new(),
         }),
         None,
     ))
@@ -4,6 +4,7 @@
 //! Handlers are pure HTTP mapping with no business logic.
 
 use crate::context::ApplicationContext;
+use crate::handlers::shared::policy::RequestTenant;
 use crate::{dto::sessions::*, errors::ApiError, sessions::Cookie};
 use axum::{
     extract::{Path, Query, State},
@@ -26,11 +27,16 @@ fn handle_error(
 
 pub async fn create_session(
     State(state): State<ApplicationContext>,
+    RequestTenant(tenant_id): RequestTenant,
 ) -> Result<impl IntoResponse, ApiError> {
-    let session = state.session_manager.create_session().await.map_err(|e| {
-        state.transport_metrics.record_redis_error();
-        ApiError::dependency("session_manager", e.to_string())
-    })?;
+    let session = state
+        .session_manager
+        .create_session_for(&tenant_id.0)
+        .await
+        .map_err(|e| {
+            state.transport_metrics.record_redis_error();
+            ApiError::dependency("session_manager", e.to_string())
+        })?;
     Ok(Json(CreateSessionResponse::from(&session)))
 }
 
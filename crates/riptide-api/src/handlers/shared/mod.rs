@@ -1,6 +1,8 @@
 //! Shared utilities for handlers to reduce code duplication
 #![allow(dead_code)]
+pub mod policy;
 pub mod spider;
+pub mod usage;
 
 use crate::context::ApplicationContext;
 #[cfg(feature = "spider")]
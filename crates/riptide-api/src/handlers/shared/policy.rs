@@ -0,0 +1,226 @@
+//! Crawl policy enforcement shared by fetching handlers
+//!
+//! Handlers call [`enforce_crawl_policy`] before handing URLs to a facade so
+//! that blocklisted, out-of-jurisdiction or excluded-category URLs are never
+//! fetched, and [`enforce_pipeline_hints`] before honouring a request's
+//! pipeline hints. The tenant comes from the authenticated API key (see
+//! [`RequestTenant`]), never from the client alone.
+
+use crate::context::ApplicationContext;
+use crate::errors::ApiError;
+use crate::middleware::auth::AuthenticatedTenant;
+use crate::sessions::Session;
+use crate::validation::validate_pipeline_hints;
+use async_trait::async_trait;
+use axum::extract::FromRequestParts;
+use axum::http::{request::Parts, HeaderMap};
+use riptide_security::{PolicyRequest, TenantId};
+use riptide_types::ports::UrlPolicy;
+use riptide_types::PipelineHints;
+
+/// Header a client may send to state the tenant it expects to act as
+pub const TENANT_HEADER: &str = "X-Tenant-ID";
+
+/// Tenant of requests not authenticated with a tenant API key
+pub const DEFAULT_TENANT: &str = "default";
+
+/// Tenant a request is made on behalf of
+///
+/// Taken from the tenant API key the request was authenticated with, or
+/// [`DEFAULT_TENANT`] for operator keys and unauthenticated requests. An
+/// `X-Tenant-ID` header naming a different tenant is rejected rather than
+/// honoured, so clients cannot pick another tenant's policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestTenant(pub TenantId);
+
+#[axum::async_trait]
+impl<S> FromRequestParts<S> for RequestTenant
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        resolve_tenant(
+            &parts.headers,
+            parts.extensions.get::<AuthenticatedTenant>(),
+        )
+        .map(RequestTenant)
+    }
+}
+
+/// Resolve the tenant of a request from its authenticated API key
+///
+/// # Errors
+///
+/// Returns `ApiError::AuthenticationError` when the `X-Tenant-ID` header
+/// names a tenant other than the key's.
+pub fn resolve_tenant(
+    headers: &HeaderMap,
+    authenticated: Option<&AuthenticatedTenant>,
+) -> Result<TenantId, ApiError> {
+    let tenant = authenticated.map_or(DEFAULT_TENANT, |t| t.0 .0.as_str());
+    let requested = headers
+        .get(TENANT_HEADER)
+        .map(|v| v.to_str().map(str::trim).unwrap_or_default())
+        .filter(|v| !v.is_empty());
+
+    match requested {
+        Some(requested) if requested != tenant => Err(ApiError::AuthenticationError {
+            message: format!(
+                "{} '{}' does not match the API key's tenant",
+                TENANT_HEADER, requested
+            ),
+        }),
+        _ => Ok(TenantId(tenant.to_string())),
+    }
+}
+
+/// Check every URL against the tenant's crawl policy
+///
+/// The server country is only resolved when the tenant's policy has
+/// jurisdiction rules, since it costs a DNS lookup per URL.
+///
+/// # Errors
+///
+/// Returns `ApiError::PolicyDenied` for the first URL the policy rejects.
+pub async fn enforce_crawl_policy<S: AsRef<str>>(
+    state: &ApplicationContext,
+    tenant_id: &TenantId,
+    urls: &[S],
+) -> Result<(), ApiError> {
+    let engine = &state.policy_engine;
+    let needs_jurisdiction = engine.needs_jurisdiction(tenant_id).await;
+
+    for url in urls {
+        let url = url.as_ref();
+        let server_country = if needs_jurisdiction {
+            server_country(state, url).await
+        } else {
+            None
+        };
+        engine
+            .check(
+                tenant_id,
+                &PolicyRequest {
+                    url,
+                    server_country: server_country.as_deref(),
+                },
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// A tenant's crawl policy, checked for every URL a spider crawl discovers
+///
+/// Seeds are checked up front with [`enforce_crawl_policy`]; attach this to
+/// the crawl so links found along the way get the same checks before they
+/// enter the frontier.
+#[derive(Clone)]
+pub struct TenantUrlPolicy {
+    state: ApplicationContext,
+    tenant_id: TenantId,
+}
+
+impl TenantUrlPolicy {
+    pub fn new(state: ApplicationContext, tenant_id: TenantId) -> Self {
+        Self { state, tenant_id }
+    }
+}
+
+#[async_trait]
+impl UrlPolicy for TenantUrlPolicy {
+    async fn denial(&self, url: &str) -> Option<String> {
+        enforce_crawl_policy(&self.state, &self.tenant_id, &[url])
+            .await
+            .err()
+            .map(|e| e.to_string())
+    }
+}
+
+/// Load a session the tenant may use
+///
+/// Sessions created by another tenant are reported as missing, so their
+/// cookies cannot be borrowed by guessing an id. Operator keys act as
+/// [`DEFAULT_TENANT`] and may use any session.
+///
+/// # Errors
+///
+/// Returns `ApiError::NotFound` for unknown and foreign sessions.
+pub async fn tenant_session(
+    state: &ApplicationContext,
+    tenant_id: &TenantId,
+    session_id: &str,
+) -> Result<Session, ApiError> {
+    state
+        .session_manager
+        .get_session(session_id)
+        .await
+        .map_err(|e| ApiError::dependency("session_manager", e.to_string()))?
+        .filter(|session| tenant_id.0 == DEFAULT_TENANT || session.is_usable_by(&tenant_id.0))
+        .ok_or_else(|| ApiError::not_found("Session not found"))
+}
+
+/// Validate a request's pipeline hints and check them against the tenant's
+/// crawl policy
+///
+/// # Errors
+///
+/// Returns `ApiError::ValidationError` for malformed hints and
+/// `ApiError::PolicyDenied` when the policy does not allow a hint.
+pub async fn enforce_pipeline_hints<S: AsRef<str>>(
+    state: &ApplicationContext,
+    tenant_id: &TenantId,
+    urls: &[S],
+    hints: Option<&PipelineHints>,
+) -> Result<(), ApiError> {
+    let (Some(hints), Some(url)) = (hints.filter(|h| !h.is_empty()), urls.first()) else {
+        return Ok(());
+    };
+    validate_pipeline_hints(hints)?;
+    state
+        .policy_engine
+        .check_hints(tenant_id, url.as_ref(), hints)
+        .await?;
+    Ok(())
+}
+
+#[cfg(feature = "fetch")]
+async fn server_country(state: &ApplicationContext, url: &str) -> Option<String> {
+    let geoip = state.geoip.as_ref()?;
+    let parsed = url::Url::parse(url).ok()?;
+    riptide_fetch::geoip::locate_host(geoip.as_ref(), parsed.host_str()?)
+        .await
+        .and_then(|location| location.country_code)
+}
+
+#[cfg(not(feature = "fetch"))]
+async fn server_country(_state: &ApplicationContext, _url: &str) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_resolve_tenant_from_api_key() {
+        let key = AuthenticatedTenant(TenantId("tenant-a".to_string()));
+        let mut headers = HeaderMap::new();
+        assert_eq!(resolve_tenant(&headers, None).unwrap().0, DEFAULT_TENANT);
+        assert_eq!(resolve_tenant(&headers, Some(&key)).unwrap().0, "tenant-a");
+
+        headers.insert(TENANT_HEADER, HeaderValue::from_static(" tenant-a "));
+        assert_eq!(resolve_tenant(&headers, Some(&key)).unwrap().0, "tenant-a");
+        // The header cannot select a tenant the key does not belong to
+        assert!(resolve_tenant(&headers, None).is_err());
+
+        headers.insert(TENANT_HEADER, HeaderValue::from_static("tenant-b"));
+        assert!(matches!(
+            resolve_tenant(&headers, Some(&key)),
+            Err(ApiError::AuthenticationError { .. })
+        ));
+    }
+}
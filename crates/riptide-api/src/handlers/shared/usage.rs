@@ -0,0 +1,146 @@
+//! Cost metering shared by fetching handlers
+//!
+//! Handlers pass their responses to these helpers once the work is done, so
+//! the bandwidth, render time, LLM tokens and stored bytes it took are
+//! charged to the authenticated tenant, the crawl and the target domain.
+//! Bandwidth is what the fetches received on the wire and tokens what the
+//! enrichment providers reported, both collected by the pipeline's
+//! [`UsageScope`](riptide_types::usage::UsageScope).
+
+use crate::context::ApplicationContext;
+use crate::models::{CrawlResponse, CrawlResult};
+use riptide_security::{MeteredUsage, TenantId, UsageAttribution};
+use riptide_types::ExtractedDoc;
+
+/// Host of `url`, the domain its usage is charged to
+pub fn domain_of(url: &str) -> Option<String> {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_string))
+}
+
+/// Bytes a document takes in the cache
+fn document_bytes(doc: &ExtractedDoc) -> u64 {
+    doc.html.as_ref().map_or(doc.text.len(), String::len) as u64
+}
+
+/// Resources one crawl result consumed
+///
+/// Cache hits cost nothing; stored bytes are the document written to the
+/// cache plus any stored artifacts.
+pub fn crawl_result_usage(result: &CrawlResult) -> MeteredUsage {
+    if result.from_cache {
+        return MeteredUsage::default();
+    }
+    let timings = result.timings.unwrap_or_default();
+    let cached = match timings.cache_write_ms {
+        Some(_) => result.document.as_ref().map_or(0, document_bytes),
+        None => 0,
+    };
+    let artifacts: u64 = result.artifacts.iter().map(|a| a.size_bytes).sum();
+    MeteredUsage {
+        bandwidth_bytes: timings.fetch_bytes.unwrap_or(0),
+        render_seconds: timings.render_ms.map_or(0.0, |ms| ms as f64 / 1000.0),
+        llm_tokens: timings.llm_tokens.unwrap_or(0),
+        storage_bytes: cached + artifacts,
+    }
+}
+
+/// Charge every result of a crawl to `tenant_id`
+pub async fn meter_crawl(
+    state: &ApplicationContext,
+    tenant_id: &TenantId,
+    response: &CrawlResponse,
+) {
+    for result in &response.results {
+        let mut attribution = UsageAttribution::new(tenant_id.clone());
+        attribution.crawl_id = response.crawl_id.clone();
+        attribution.domain = domain_of(&result.url);
+        state
+            .meter
+            .record(&attribution, crawl_result_usage(result))
+            .await;
+    }
+}
+
+/// Charge a single fetch or render of `url` to `tenant_id`
+pub async fn meter_fetch(
+    state: &ApplicationContext,
+    tenant_id: &TenantId,
+    url: &str,
+    usage: MeteredUsage,
+) {
+    let mut attribution = UsageAttribution::new(tenant_id.clone());
+    attribution.domain = domain_of(url);
+    state.meter.record(&attribution, usage).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::{CacheStatus, PhaseTimings};
+
+    fn result(document: Option<ExtractedDoc>, timings: PhaseTimings) -> CrawlResult {
+        CrawlResult {
+            url: "https://a.example/page".to_string(),
+            status: 200,
+            from_cache: false,
+            cache_status: CacheStatus::Miss,
+            gate_decision: "raw".to_string(),
+            quality_score: 0.8,
+            processing_time_ms: 10,
+            document,
+            error: None,
+            cache_key: String::new(),
+            artifacts: Vec::new(),
+            timings: Some(timings),
+        }
+    }
+
+    #[test]
+    fn test_domain_of() {
+        assert_eq!(
+            domain_of("https://a.example:8443/x?y").as_deref(),
+            Some("a.example")
+        );
+        assert_eq!(domain_of("not a url"), None);
+    }
+
+    #[test]
+    fn test_crawl_result_usage() {
+        let doc = ExtractedDoc {
+            html: Some("<p>hello world</p>".to_string()),
+            text: "hello world".to_string(),
+            ..Default::default()
+        };
+        let rendered = result(
+            Some(doc.clone()),
+            PhaseTimings {
+                render_ms: Some(1500),
+                cache_write_ms: Some(1),
+                fetch_bytes: Some(7),
+                llm_tokens: Some(42),
+                ..Default::default()
+            },
+        );
+        let usage = crawl_result_usage(&rendered);
+        assert_eq!(usage.bandwidth_bytes, 7, "wire bytes, not document size");
+        assert_eq!(usage.render_seconds, 1.5);
+        assert_eq!(usage.llm_tokens, 42);
+        assert_eq!(usage.storage_bytes, 18);
+
+        let text_only = result(
+            Some(ExtractedDoc { html: None, ..doc }),
+            PhaseTimings::default(),
+        );
+        let usage = crawl_result_usage(&text_only);
+        assert_eq!(usage.bandwidth_bytes, 0);
+        assert_eq!(usage.storage_bytes, 0, "nothing was written to the cache");
+
+        let cached = CrawlResult {
+            from_cache: true,
+            ..rendered
+        };
+        assert!(crawl_result_usage(&cached).is_empty());
+    }
+}
@@ -3,21 +3,32 @@
 //! This module provides HTTP handlers for deep crawling operations using
 //! the riptide-facade SpiderFacade for simplified spider engine access.
 #![allow(dead_code)]
+use crate::adapters::{ApiSessionProvider, ResourceSlot};
 use crate::context::ApplicationContext;
 use crate::errors::ApiError;
+use crate::handlers::shared::policy::{
+    enforce_crawl_policy, tenant_session, RequestTenant, TenantUrlPolicy, DEFAULT_TENANT,
+};
 use crate::models::*;
+use crate::streaming::response_helpers::{StreamingResponseBuilder, StreamingResponseType};
 use axum::{
     extract::{Query, State},
-    response::IntoResponse,
+    http::HeaderMap,
+    response::{IntoResponse, Response},
     Json,
 };
-use riptide_types::ResultMode;
-use serde::Deserialize;
+use futures::StreamExt;
+use riptide_facade::facades::{CrawlOverrides, CrawlSummary, SpiderFacade};
+use riptide_security::{MeteredUsage, TenantId, UsageAttribution};
+use riptide_spider::{CrawlRunState, CrawlStream, EnrichedCrawlResult};
+use riptide_types::ports::SessionProvider;
+use riptide_types::{CrawledPage, ResultMode};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 // Unused imports removed - parse_seed_urls and MetricsRecorder not used yet
 
 /// Default max content size in bytes (1MB)
-#[allow(dead_code)]
 const DEFAULT_MAX_CONTENT_BYTES: usize = 1_048_576;
 
 /// Query parameters for spider crawl endpoint
@@ -48,9 +59,12 @@ pub struct SpiderCrawlQuery {
 /// - Session persistence for authenticated crawling
 ///
 /// Query Parameters:
-/// - result_mode: "stats" (default) or "urls"
+/// - result_mode: "stats" (default), "urls" or "stream"
 ///   - stats: Returns statistics only (backward compatible)
 ///   - urls: Returns statistics plus discovered URLs list
+///   - stream: Streams each crawled page as it completes, as NDJSON or as
+///     SSE when the client accepts `text/event-stream`, ending with the
+///     crawl summary. Page content is only sent with `include=content`.
 #[tracing::instrument(
     name = "spider_crawl",
     skip_all,
@@ -66,11 +80,15 @@ pub struct SpiderCrawlQuery {
 pub async fn spider_crawl(
     State(_state): State<ApplicationContext>,
     Query(_query): Query<SpiderCrawlQuery>,
+    headers: HeaderMap,
+    RequestTenant(tenant_id): RequestTenant,
     Json(body): Json<SpiderCrawlBody>,
 ) -> Result<impl IntoResponse, ApiError> {
+    // Seeds are checked up front, discovered links as they are found
+    enforce_crawl_policy(&_state, &tenant_id, &body.seed_urls).await?;
+
     // Acquire resources via ResourceFacade (Phase 5 - Handler Integration)
-    let tenant_id = "spider-crawl"; // TODO: Extract from request context in Phase 6
-    let _resource_slot = acquire_spider_resources(&_state, tenant_id).await?;
+    let _resource_slot = acquire_spider_resources(&_state, &tenant_id.0).await?;
 
     // Get spider facade
     let spider_facade = _state
@@ -79,6 +97,11 @@ pub async fn spider_crawl(
         .ok_or_else(|| ApiError::ConfigError {
             message: "SpiderFacade not initialized".to_string(),
         })?;
+    if spider_facade.control_snapshot().state == CrawlRunState::Paused {
+        return Err(ApiError::service_unavailable(
+            "A paused spider crawl is holding the spider; it must be resumed, stopped or cancelled first",
+        ));
+    }
 
     // Parse seed URLs
     let seed_urls: Vec<url::Url> = body
@@ -91,20 +114,175 @@ pub async fn spider_crawl(
             message: format!("Invalid seed URL: {}", e),
         })?;
 
+    let policy = TenantUrlPolicy::new(_state.clone(), tenant_id.clone());
+    let mut overrides = CrawlOverrides::new()
+        .with_owner(tenant_id.0.clone())
+        .with_url_policy(Arc::new(policy));
+
+    // Authenticate the crawl when a session is given
+    if let Some(session_id) = &body.session_id {
+        tenant_session(&_state, &tenant_id, session_id).await?;
+        let provider: Arc<dyn SessionProvider> = Arc::new(ApiSessionProvider::new(
+            _state.session_manager.clone(),
+            session_id.clone(),
+        ));
+        overrides = overrides.with_session_provider(provider);
+    }
+
+    if _query.result_mode == ResultMode::Stream {
+        let response_type = StreamingResponseType::negotiate(&headers);
+        let stream = spider_facade.crawl_stream(seed_urls, overrides);
+        let options = StreamPageOptions::from_query(&_query);
+        return Ok(stream_crawl(
+            stream,
+            response_type,
+            options,
+            _resource_slot,
+            _state.clone(),
+            tenant_id,
+        ));
+    }
+
     // Execute crawl via facade
     let summary = spider_facade
-        .crawl(seed_urls)
+        .crawl_with(seed_urls, overrides)
         .await
         .map_err(|e| ApiError::InternalError {
             message: format!("Spider crawl failed: {}", e),
         })?;
+    meter_spider_crawl(&_state, &tenant_id, &summary).await;
+
+    Ok(Json(summary).into_response())
+}
+
+/// Charge the wire bytes of a finished crawl to `tenant_id`
+///
+/// The spider counts bandwidth per crawl rather than per page, so a crawl
+/// is charged to a domain only when it stayed on one.
+async fn meter_spider_crawl(
+    state: &ApplicationContext,
+    tenant_id: &TenantId,
+    summary: &CrawlSummary,
+) {
+    let mut attribution = UsageAttribution::new(tenant_id.clone());
+    if let [domain] = summary.domains.as_slice() {
+        attribution.domain = Some(domain.clone());
+    }
+    let usage = MeteredUsage {
+        bandwidth_bytes: summary.bytes_downloaded,
+        ..MeteredUsage::default()
+    };
+    state.meter.record(&attribution, usage).await;
+}
+
+/// Event sent for each line (NDJSON) or message (SSE) of a streamed crawl
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SpiderStreamEvent {
+    /// A page crawled successfully
+    Page(CrawledPage),
+    /// Final crawl statistics, sent once the crawl stops
+    Summary(CrawlSummary),
+    /// The crawl failed; no summary follows
+    Error { message: String },
+}
+
+/// How streamed pages are rendered from the crawl query
+#[derive(Debug, Clone, Copy)]
+struct StreamPageOptions {
+    include_content: bool,
+    max_content_bytes: usize,
+}
+
+impl StreamPageOptions {
+    fn from_query(query: &SpiderCrawlQuery) -> Self {
+        let listed = |fields: &Option<String>| {
+            fields
+                .as_deref()
+                .is_some_and(|f| f.split(',').any(|f| f.trim() == "content"))
+        };
+        Self {
+            include_content: listed(&query.include) && !listed(&query.exclude),
+            max_content_bytes: query.max_content_bytes.unwrap_or(DEFAULT_MAX_CONTENT_BYTES),
+        }
+    }
+}
+
+/// Convert a streamed crawl result to the page format of the spider API
+fn crawled_page(result: EnrichedCrawlResult, options: StreamPageOptions) -> CrawledPage {
+    let mut page = CrawledPage::new(
+        result.raw.url.to_string(),
+        result.depth,
+        result.raw.status.as_u16(),
+    );
+    page.links = result
+        .extracted_urls
+        .iter()
+        .map(|url| url.to_string())
+        .collect();
+    page.mime = result
+        .raw
+        .headers
+        .get(axum::http::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+    if options.include_content {
+        let mut html = result.raw.html;
+        if html.len() > options.max_content_bytes {
+            let mut end = options.max_content_bytes;
+            while !html.is_char_boundary(end) {
+                end -= 1;
+            }
+            html.truncate(end);
+            page.truncated = Some(true);
+        }
+        page.content = Some(html);
+    }
+    page
+}
 
-    Ok(Json(summary))
+/// Forward pages of a running crawl as they arrive, then the crawl summary
+///
+/// The resource slot is held until the crawl finishes. A client
+/// disconnect drops the stream, which stops the crawl.
+fn stream_crawl(
+    stream: CrawlStream,
+    response_type: StreamingResponseType,
+    options: StreamPageOptions,
+    slot: ResourceSlot,
+    context: ApplicationContext,
+    tenant_id: TenantId,
+) -> Response {
+    let initial = Some((stream, slot, context, tenant_id));
+    let events = futures::stream::unfold(initial, move |state| async move {
+        let (mut stream, slot, context, tenant_id) = state?;
+        match stream.next().await {
+            Some(result) => Some((
+                SpiderStreamEvent::Page(crawled_page(result, options)),
+                Some((stream, slot, context, tenant_id)),
+            )),
+            None => {
+                let event = match stream.finish().await {
+                    Ok(result) => {
+                        let summary = CrawlSummary::from(result);
+                        meter_spider_crawl(&context, &tenant_id, &summary).await;
+                        SpiderStreamEvent::Summary(summary)
+                    }
+                    Err(e) => SpiderStreamEvent::Error {
+                        message: format!("Spider crawl failed: {}", e),
+                    },
+                };
+                Some((event, None))
+            }
+        }
+    });
+    StreamingResponseBuilder::new(response_type).build(events)
 }
 
 /// Get spider status and metrics
 pub async fn spider_status(
     State(_state): State<ApplicationContext>,
+    RequestTenant(tenant_id): RequestTenant,
     Json(_body): Json<SpiderStatusRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
     // Get spider facade
@@ -114,6 +292,7 @@ pub async fn spider_status(
         .ok_or_else(|| ApiError::ConfigError {
             message: "SpiderFacade not initialized".to_string(),
         })?;
+    ensure_crawl_owner(spider_facade, &tenant_id)?;
 
     // Get status from facade
     let (state, performance) =
@@ -130,14 +309,110 @@ pub async fn spider_status(
         performance,
         frontier_stats: None,
         adaptive_stop_stats: None,
+        control: spider_facade.control_snapshot(),
     };
 
     Ok(Json(response))
 }
 
-/// Spider control endpoint for start/stop/reset operations
+/// Default number of pending URLs returned by frontier inspection
+const DEFAULT_FRONTIER_LIMIT: usize = 50;
+
+/// Upper bound on pending URLs returned by frontier inspection
+const MAX_FRONTIER_LIMIT: usize = 1000;
+
+/// Query parameters for frontier inspection endpoints
+#[derive(Debug, Deserialize)]
+pub struct FrontierQuery {
+    /// Maximum number of pending URLs to return (default: 50, max: 1000)
+    pub limit: Option<usize>,
+}
+
+/// Inspect the spider frontier: next pending URLs by score, per-domain
+/// depth distribution and rejection reasons.
+///
+/// Does not wait for a running crawl, so operators can see why a crawl is
+/// stuck (e.g. every discovered link rejected as a duplicate or over budget).
+pub async fn spider_frontier(
+    State(state): State<ApplicationContext>,
+    RequestTenant(tenant_id): RequestTenant,
+    Query(query): Query<FrontierQuery>,
+) -> Result<impl IntoResponse, ApiError> {
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_FRONTIER_LIMIT)
+        .min(MAX_FRONTIER_LIMIT);
+    Ok(Json(frontier_snapshot(&state, &tenant_id, limit).await?))
+}
+
+/// Pending URL counts per domain and depth
+pub async fn spider_frontier_domains(
+    State(state): State<ApplicationContext>,
+    RequestTenant(tenant_id): RequestTenant,
+) -> Result<impl IntoResponse, ApiError> {
+    let snapshot = frontier_snapshot(&state, &tenant_id, 0).await?;
+    Ok(Json(serde_json::json!({
+        "total_pending": snapshot.total_pending,
+        "domains": snapshot.depth_by_domain,
+    })))
+}
+
+/// Rejection counts by reason and the most recently rejected URLs
+pub async fn spider_frontier_rejections(
+    State(state): State<ApplicationContext>,
+    RequestTenant(tenant_id): RequestTenant,
+) -> Result<impl IntoResponse, ApiError> {
+    let snapshot = frontier_snapshot(&state, &tenant_id, 0).await?;
+    Ok(Json(serde_json::json!({
+        "rejections": snapshot.rejections,
+        "recent": snapshot.recent_rejections,
+    })))
+}
+
+async fn frontier_snapshot(
+    state: &ApplicationContext,
+    tenant_id: &TenantId,
+    limit: usize,
+) -> Result<riptide_spider::FrontierSnapshot, ApiError> {
+    let spider_facade = state
+        .spider_facade
+        .as_ref()
+        .ok_or_else(|| ApiError::ConfigError {
+            message: "SpiderFacade not initialized".to_string(),
+        })?;
+    ensure_crawl_owner(spider_facade, tenant_id)?;
+
+    spider_facade
+        .inspect_frontier(limit)
+        .await
+        .map_err(|e| ApiError::InternalError {
+            message: format!("Failed to inspect spider frontier: {}", e),
+        })
+}
+
+/// Reject tenants other than the one the spider's current or last crawl
+/// ran for
+///
+/// Operator keys act as [`DEFAULT_TENANT`] and may inspect any crawl.
+/// Other tenants get a 404 so they cannot tell whose crawl is running.
+fn ensure_crawl_owner(facade: &SpiderFacade, tenant_id: &TenantId) -> Result<(), ApiError> {
+    if tenant_id.0 == DEFAULT_TENANT
+        || facade.control_snapshot().owner.as_deref() == Some(tenant_id.0.as_str())
+    {
+        return Ok(());
+    }
+    Err(ApiError::not_found("Spider crawl"))
+}
+
+/// Spider control endpoint for pause/resume/stop/cancel/reset operations
+///
+/// Pause, resume, stop and cancel steer the running crawl without waiting
+/// for it; the resulting state is returned and shown by `/spider/status`.
+/// Only the tenant the crawl runs for may control it, and a request naming
+/// a `crawl_id` is refused once a different crawl has started.
 pub async fn spider_control(
     State(_state): State<ApplicationContext>,
+    RequestTenant(tenant_id): RequestTenant,
     Json(_body): Json<SpiderControlRequest>,
 ) -> Result<impl IntoResponse, ApiError> {
     // Get spider facade
@@ -147,6 +422,12 @@ pub async fn spider_control(
         .ok_or_else(|| ApiError::ConfigError {
             message: "SpiderFacade not initialized".to_string(),
         })?;
+    ensure_crawl_owner(spider_facade, &tenant_id)?;
+    if let Some(crawl_id) = &_body.crawl_id {
+        if spider_facade.control_snapshot().crawl_id.as_ref() != Some(crawl_id) {
+            return Err(ApiError::not_found(format!("Spider crawl {}", crawl_id)));
+        }
+    }
 
     // Execute control action via facade
     let message =
@@ -156,10 +437,13 @@ pub async fn spider_control(
             .map_err(|e| ApiError::ValidationError {
                 message: e.to_string(),
             })?;
+    let snapshot = spider_facade.control_snapshot();
 
     Ok(Json(serde_json::json!({
         "success": true,
-        "message": message
+        "message": message,
+        "state": snapshot.state,
+        "crawl_id": snapshot.crawl_id,
     })))
 }
 
@@ -185,12 +469,73 @@ async fn acquire_spider_resources(
         Ok(FacadeResult::ResourceExhausted) => Err(ApiError::InternalError {
             message: "Spider resources exhausted".to_string(),
         }),
-        Ok(FacadeResult::Timeout) => Err(ApiError::TimeoutError {
-            operation: "Resource acquisition".to_string(),
-            message: "Timeout acquiring spider resources".to_string(),
-        }),
+        Ok(FacadeResult::Timeout) => Err(ApiError::timeout(
+            "Resource acquisition",
+            "Timeout acquiring spider resources",
+        )),
         Err(e) => Err(ApiError::InternalError {
             message: format!("Resource facade error: {}", e),
         }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_spider::RawCrawlResult;
+
+    fn query(include: Option<&str>, exclude: Option<&str>) -> SpiderCrawlQuery {
+        SpiderCrawlQuery {
+            result_mode: ResultMode::Stream,
+            include: include.map(str::to_string),
+            exclude: exclude.map(str::to_string),
+            max_content_bytes: Some(4),
+        }
+    }
+
+    #[test]
+    fn test_stream_page_options() {
+        assert!(!StreamPageOptions::from_query(&query(None, None)).include_content);
+        assert!(
+            StreamPageOptions::from_query(&query(Some("title, content"), None)).include_content
+        );
+        assert!(
+            !StreamPageOptions::from_query(&query(Some("content"), Some("content")))
+                .include_content
+        );
+    }
+
+    #[test]
+    fn test_crawled_page_truncates_content() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            axum::http::header::CONTENT_TYPE,
+            "text/html".parse().unwrap(),
+        );
+        let result = EnrichedCrawlResult {
+            raw: RawCrawlResult {
+                url: url::Url::parse("https://example.com/a").unwrap(),
+                html: "<p>é</p>".to_string(),
+                status: axum::http::StatusCode::OK,
+                headers,
+            },
+            depth: 1,
+            extracted_urls: vec![url::Url::parse("https://example.com/b").unwrap()],
+            text_content: None,
+            content_check: None,
+        };
+        let options = StreamPageOptions::from_query(&query(Some("content"), None));
+
+        let page = crawled_page(result, options);
+        assert_eq!(page.depth, 1);
+        assert_eq!(page.links, vec!["https://example.com/b"]);
+        assert_eq!(page.mime.as_deref(), Some("text/html"));
+        // The 4-byte limit falls inside "é", so the cut moves back to the char boundary
+        assert_eq!(page.content.as_deref(), Some("<p>"));
+        assert_eq!(page.truncated, Some(true));
+
+        let json = serde_json::to_value(SpiderStreamEvent::Page(page)).unwrap();
+        assert_eq!(json["type"], "page");
+        assert_eq!(json["url"], "https://example.com/a");
+    }
+}
@@ -4,12 +4,20 @@
 //! Handlers are <50 LOC total, focused only on HTTP transport concerns.
 //! Phase 1: Workers are optional - handlers return SERVICE_UNAVAILABLE if workers disabled.
 
+use crate::adapters::job_stream_adapter::{watch_job, JobStreamOptions};
+use crate::errors::ApiError;
+use crate::handlers::shared::policy::{
+    enforce_crawl_policy, enforce_pipeline_hints, RequestTenant,
+};
+use crate::streaming::response_helpers::{StreamingResponseBuilder, StreamingResponseType};
 use crate::{context::ApplicationContext, dto::workers::*};
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
 };
+use riptide_security::TenantId;
+use riptide_workers::JobType;
 use uuid::Uuid;
 
 /// Check if workers are enabled, return 503 if not
@@ -21,18 +29,45 @@ fn check_workers_enabled(
         .ok_or(StatusCode::SERVICE_UNAVAILABLE)
 }
 
+/// Check the URLs a crawl job will fetch, and its pipeline hints, against
+/// the tenant's crawl policy
+async fn enforce_job_policy(
+    state: &ApplicationContext,
+    tenant_id: &TenantId,
+    job_type: &JobType,
+) -> Result<(), ApiError> {
+    let (urls, options) = match job_type {
+        JobType::BatchCrawl { urls, options } => (urls.as_slice(), options.as_ref()),
+        JobType::SingleCrawl { url, options } => (std::slice::from_ref(url), options.as_ref()),
+        _ => return Ok(()),
+    };
+    enforce_crawl_policy(state, tenant_id, urls).await?;
+    let hints = options.and_then(|o| o.pipeline_hints.as_ref());
+    enforce_pipeline_hints(state, tenant_id, urls, hints).await
+}
+
 /// Submit a job (ultra-thin - 5 LOC)
 pub async fn submit_job(
     State(state): State<ApplicationContext>,
+    RequestTenant(tenant_id): RequestTenant,
     Json(request): Json<SubmitJobRequest>,
-) -> Result<Json<SubmitJobResponse>, StatusCode> {
-    let worker_service = check_workers_enabled(&state.worker_service)?;
-    let job = request.into_job().map_err(|_| StatusCode::BAD_REQUEST)?;
+) -> Result<Json<SubmitJobResponse>, Response> {
+    let worker_service =
+        check_workers_enabled(&state.worker_service).map_err(IntoResponse::into_response)?;
+    let job = request
+        .into_job()
+        .map_err(|_| StatusCode::BAD_REQUEST.into_response())?;
+    enforce_job_policy(&state, &tenant_id, &job.job_type)
+        .await
+        .map_err(IntoResponse::into_response)?;
     let job_type = format_job_type(&job.job_type);
+    if worker_service.is_draining() {
+        return Err(StatusCode::SERVICE_UNAVAILABLE.into_response());
+    }
     let job_id = worker_service
         .submit_job(job)
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
     // Phase D: Worker metrics now tracked via business_metrics
     state
         .business_metrics
@@ -65,15 +100,30 @@ pub async fn get_job_result(
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::NOT_FOUND)?;
-    Ok(Json(JobResultResponse {
-        job_id: result.job_id,
-        success: result.success,
-        data: result.data,
-        error: result.error,
-        processing_time_ms: result.processing_time_ms,
-        worker_id: result.worker_id,
-        completed_at: result.completed_at,
-    }))
+    Ok(Json(JobResultResponse::from(result)))
+}
+
+/// Stream job status changes and the final result as NDJSON, or SSE when
+/// the client accepts `text/event-stream`
+///
+/// `buffer` and `overflow` bound how many updates are held for this client
+/// and what happens when it falls behind.
+pub async fn stream_job(
+    State(state): State<ApplicationContext>,
+    Path(job_id): Path<Uuid>,
+    Query(options): Query<JobStreamOptions>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let worker_service = check_workers_enabled(&state.worker_service)?;
+    worker_service
+        .get_job(job_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let events = watch_job(worker_service.clone(), job_id, options);
+    let response_type = StreamingResponseType::negotiate(&headers);
+    Ok(StreamingResponseBuilder::new(response_type).build(events))
 }
 
 /// Get queue stats (ultra-thin - 3 LOC)
@@ -124,15 +174,20 @@ pub async fn get_worker_stats(
 /// Create scheduled job (ultra-thin - 6 LOC)
 pub async fn create_scheduled_job(
     State(state): State<ApplicationContext>,
+    RequestTenant(tenant_id): RequestTenant,
     Json(request): Json<CreateScheduledJobRequest>,
-) -> Result<Json<ScheduledJobResponse>, StatusCode> {
-    let worker_service = check_workers_enabled(&state.worker_service)?;
+) -> Result<Json<ScheduledJobResponse>, Response> {
+    let worker_service =
+        check_workers_enabled(&state.worker_service).map_err(IntoResponse::into_response)?;
     let mut job = riptide_workers::ScheduledJob::new(
         request.name.clone(),
         request.cron_expression.clone(),
         request.job_template.into(),
     )
-    .map_err(|_| StatusCode::BAD_REQUEST)?;
+    .map_err(|_| StatusCode::BAD_REQUEST.into_response())?;
+    enforce_job_policy(&state, &tenant_id, &job.job_template)
+        .await
+        .map_err(IntoResponse::into_response)?;
     if let Some(p) = request.priority {
         job.priority = p;
     }
@@ -148,14 +203,14 @@ pub async fn create_scheduled_job(
     let job_id = worker_service
         .add_scheduled_job(job.clone())
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
     let jobs = worker_service
         .list_scheduled_jobs()
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
     let job = jobs
         .into_iter()
         .find(|j| j.id == job_id)
-        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+        .ok_or_else(|| StatusCode::INTERNAL_SERVER_ERROR.into_response())?;
     Ok(Json(ScheduledJobResponse::from(&job)))
 }
 
@@ -240,6 +295,7 @@ fn format_job_type(job_type: &riptide_workers::JobType) -> String {
         riptide_workers::JobType::SingleCrawl { .. } => "single_crawl".to_string(),
         riptide_workers::JobType::BatchCrawl { .. } => "batch_crawl".to_string(),
         riptide_workers::JobType::PdfExtraction { .. } => "pdf_extraction".to_string(),
+        riptide_workers::JobType::Reprocess { .. } => "reprocess".to_string(),
         riptide_workers::JobType::Maintenance { .. } => "maintenance".to_string(),
         riptide_workers::JobType::Custom { job_name, .. } => format!("custom_{}", job_name),
     }
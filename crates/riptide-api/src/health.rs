@@ -101,6 +101,7 @@ impl HealthChecker {
             uptime,
             dependencies,
             metrics: Some(metrics),
+            region: context.api_config.region.local_region.clone(),
         };
 
         // Add build information to response
@@ -129,7 +130,8 @@ impl HealthChecker {
                     "max_concurrency": context.config.max_concurrency,
                     "gate_thresholds": {
                         "high": context.config.gate_hi_threshold,
-                        "low": context.config.gate_lo_threshold
+                        "low": context.config.gate_lo_threshold,
+                        "verticals": context.config.gate_vertical_thresholds
                     }
                 });
                 obj.insert("bucket_config".to_string(), bucket_config);
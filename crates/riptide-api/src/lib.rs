@@ -1,15 +1,22 @@
 pub mod adapters; // Sprint 4.3: Transport adapters for streaming
+pub mod boot; // Startup dependency retries and degraded mode
 pub mod capabilities; // System capabilities detection for deployment mode reporting
+pub mod change_tracking; // Content fingerprints and crawl ids for differential crawls
 pub mod composition; // Sprint 1.3: Dependency Injection composition root
 pub mod config;
 pub mod context; // ApplicationContext type alias - clean replacement for AppState god object
+pub mod drain; // Graceful drain for zero-downtime deploys
 pub mod dto;
 pub mod errors;
+pub mod experiments; // A/B experiments for extraction strategies and gate thresholds
 pub mod facades; // Sprint 3.2: Handler business logic facades
+pub mod gate_verticals; // Gate thresholds and headless rates per content vertical
 pub mod handlers;
 pub mod health;
 #[cfg(all(feature = "jemalloc", not(target_env = "msvc")))]
 pub mod jemalloc_stats;
+pub mod log_redaction;
+pub mod media_pipeline; // Screenshot variants and page image downloads via BlobStorage
 pub mod metrics;
 pub mod metrics_integration; // Sprint 4.5: Merged metrics for /metrics endpoint
 pub mod metrics_transport; // Sprint 4.5: Transport-level metrics (HTTP, WebSocket, SSE)
@@ -1,14 +1,21 @@
 mod adapters;
+mod boot;
 mod capabilities; // System capabilities detection for deployment mode reporting
+mod change_tracking;
 mod config;
 mod context; // ApplicationContext - clean replacement for AppState
+mod drain;
 mod dto;
 mod errors;
+mod experiments;
 mod facades;
+mod gate_verticals;
 mod handlers;
 mod health;
 #[cfg(all(feature = "jemalloc", not(target_env = "msvc")))]
 mod jemalloc_stats;
+mod log_redaction;
+mod media_pipeline;
 mod metrics;
 mod metrics_integration;
 mod metrics_transport;
@@ -45,7 +52,7 @@ use crate::context::ApplicationContext;
 #[allow(deprecated)]
 use crate::metrics::{create_metrics_layer, RipTideMetrics};
 use crate::middleware::{
-    auth_middleware, rate_limit_middleware, request_validation_middleware,
+    auth_middleware, drain_middleware, rate_limit_middleware, request_validation_middleware,
     security_headers_middleware, PayloadLimitLayer,
 };
 use crate::sessions::middleware::SessionLayer;
@@ -96,7 +103,10 @@ async fn main() -> anyhow::Result<()> {
                             .into()
                     }),
             )
-            .with(tracing_subscriber::fmt::layer())
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_writer(log_redaction::RedactingMakeWriter::new(std::io::stdout)),
+            )
             .init();
         tracing::info!("OTEL_ENDPOINT not set, using basic tracing");
     }
@@ -115,7 +125,7 @@ async fn main() -> anyhow::Result<()> {
     // Load application configuration
     let config = AppConfig::default();
     tracing::info!(
-        redis_url = %config.redis_url,
+        redis_url = %riptide_config::redaction::redact_url(&config.redis_url),
         wasm_path = %config.wasm_path,
         max_concurrency = config.max_concurrency,
         cache_ttl = config.cache_ttl,
@@ -217,8 +227,21 @@ async fn main() -> anyhow::Result<()> {
         // Crawl endpoints - both root and v1 paths
         .route("/crawl", post(handlers::crawl))
         .route("/api/v1/crawl", post(handlers::crawl)) // v1 alias
+        .route("/crawl/retry", post(handlers::crawl_retry))
+        .route("/api/v1/crawl/retry", post(handlers::crawl_retry)) // v1 alias
+        .route("/crawl/manifest", post(handlers::crawl_manifest))
+        .route("/api/v1/crawl/manifest", post(handlers::crawl_manifest)) // v1 alias
         .route("/crawl/stream", post(handlers::crawl_stream))
         .route("/api/v1/crawl/stream", post(handlers::crawl_stream)) // v1 alias
+        // Push-mode ingestion of content fetched outside RipTide
+        .route("/api/v1/ingest", post(handlers::ingest::ingest))
+        // Logged-in page captures pushed by the browser extension
+        .route("/api/v1/capture", post(handlers::capture::capture))
+        // Diffs between two versions of a page, and the last recorded change
+        .route(
+            "/api/v1/diff",
+            post(handlers::diff::diff).get(handlers::diff::latest_diff),
+        )
         // Extract endpoint - NEW v1.1 feature
         .route("/api/v1/extract", post(handlers::extract))
         .route("/extract", post(handlers::extract)); // Root alias for backward compatibility
@@ -251,6 +274,15 @@ async fn main() -> anyhow::Result<()> {
         .route("/spider/crawl", post(handlers::spider::spider_crawl))
         .route("/spider/status", post(handlers::spider::spider_status))
         .route("/spider/control", post(handlers::spider::spider_control))
+        .route("/spider/frontier", get(handlers::spider::spider_frontier))
+        .route(
+            "/spider/frontier/domains",
+            get(handlers::spider::spider_frontier_domains),
+        )
+        .route(
+            "/spider/frontier/rejections",
+            get(handlers::spider::spider_frontier_rejections),
+        )
         // Session management endpoints
         .route("/sessions", post(handlers::sessions::create_session))
         .route("/sessions", get(handlers::sessions::list_sessions))
@@ -319,6 +351,10 @@ async fn main() -> anyhow::Result<()> {
         .route(
             "/api/v1/browser/session/:id",
             axum::routing::delete(handlers::browser::close_browser_session),
+        )
+        .route(
+            "/api/v1/browser/session/:id/recording",
+            get(handlers::browser::get_session_recording),
         );
 
     let app = app
@@ -371,6 +407,10 @@ async fn main() -> anyhow::Result<()> {
             "/workers/jobs/:job_id/result",
             get(handlers::workers::get_job_result),
         )
+        .route(
+            "/workers/jobs/:job_id/stream",
+            get(handlers::workers::stream_job),
+        )
         .route(
             "/workers/stats/queue",
             get(handlers::workers::get_queue_stats),
@@ -480,48 +520,121 @@ async fn main() -> anyhow::Result<()> {
         .route(
             "/api/telemetry/traces/:trace_id",
             get(handlers::telemetry::get_trace_tree),
-        );
-
-    // Persistence and Multi-tenancy Admin Endpoints (feature-gated)
-    #[cfg(feature = "persistence")]
-    let app = app
-        // Tenant management
-        .route("/admin/tenants", post(handlers::admin::create_tenant))
-        .route("/admin/tenants", get(handlers::admin::list_tenants))
-        .route("/admin/tenants/:id", get(handlers::admin::get_tenant))
-        .route(
-            "/admin/tenants/:id",
-            axum::routing::put(handlers::admin::update_tenant),
         )
+        // Effective configuration with secrets redacted
+        .route("/admin/config", get(handlers::admin_config::get_config))
+        // Graceful drain for zero-downtime deploys
         .route(
-            "/admin/tenants/:id",
-            axum::routing::delete(handlers::admin::delete_tenant),
+            drain::DRAIN_PATH,
+            post(handlers::admin_drain::start_drain).get(handlers::admin_drain::get_drain_status),
         )
+        // A/B experiment comparison reports
+        .route("/admin/experiments", get(handlers::experiments::get_experiments))
+        // Gate thresholds and headless rates per content vertical
         .route(
-            "/admin/tenants/:id/usage",
-            get(handlers::admin::get_tenant_usage),
+            "/admin/gate/verticals",
+            get(handlers::gate_verticals::get_gate_verticals),
         )
+        // Download bandwidth limits, adjustable at runtime
         .route(
-            "/admin/tenants/:id/billing",
-            get(handlers::admin::get_tenant_billing),
+            "/admin/bandwidth",
+            get(handlers::admin_bandwidth::get_bandwidth)
+                .put(handlers::admin_bandwidth::set_bandwidth),
         )
-        // Cache management
-        .route("/admin/cache/warm", post(handlers::admin::warm_cache))
+        // Metered crawl costs per tenant, crawl and domain
         .route(
-            "/admin/cache/invalidate",
-            post(handlers::admin::invalidate_cache),
+            "/admin/tenants/:id/usage",
+            get(handlers::admin_usage::get_tenant_usage),
         )
-        .route("/admin/cache/stats", get(handlers::admin::get_cache_stats))
-        // State management
-        .route("/admin/state/reload", post(handlers::admin::reload_state))
+        // API keys bound to a tenant's policy and quotas
         .route(
-            "/admin/state/checkpoint",
-            post(handlers::admin::create_checkpoint),
+            "/admin/tenants/:id/api-keys",
+            post(handlers::admin_api_keys::create_api_key)
+                .get(handlers::admin_api_keys::list_api_keys),
         )
         .route(
-            "/admin/state/restore/:id",
-            post(handlers::admin::restore_checkpoint),
-        );
+            "/admin/api-keys/:key_id",
+            axum::routing::delete(handlers::admin_api_keys::revoke_api_key),
+        )
+        // Stored render artifacts (screenshots, thumbnails)
+        .route("/artifacts/*key", get(handlers::artifacts::get_artifact));
+
+    // WASM extractor component hot reload
+    #[cfg(feature = "wasm-extractor")]
+    let app = app.route(
+        "/admin/extractor/component",
+        get(handlers::admin_extractor::get_component)
+            .put(handlers::admin_extractor::reload_component)
+            .layer(axum::extract::DefaultBodyLimit::max(
+                handlers::admin_extractor::MAX_COMPONENT_BYTES,
+            )),
+    );
+
+    // Optional subsystems are switched on by RIPTIDE_FEATURE_* flags and
+    // reported on /capabilities
+    let di_config = riptide_api::composition::DiConfig::from_env()?;
+    let subsystems = riptide_api::composition::Subsystems {
+        persistence: if di_config.features.enable_persistence {
+            Some(Arc::new(
+                riptide_persistence::CheckpointManager::open(
+                    &app_state.api_config.drain.checkpoint_dir,
+                    riptide_persistence::config::StateConfig::default(),
+                )
+                .await?,
+            ))
+        } else {
+            None
+        },
+        ..Default::default()
+    };
+    for (name, status) in subsystems.statuses(&di_config.features) {
+        if status.enabled && !status.wired {
+            tracing::warn!(subsystem = name, "Subsystem enabled but not wired");
+        }
+    }
+    let app = app.merge(riptide_api::composition::capabilities_router(
+        subsystems.capabilities(&di_config.features),
+    ));
+
+    // Persistence and Multi-tenancy Admin Endpoints
+    let app = if di_config.features.enable_persistence {
+        app
+            // Tenant management
+            .route("/admin/tenants", post(handlers::admin::create_tenant))
+            .route("/admin/tenants", get(handlers::admin::list_tenants))
+            .route("/admin/tenants/:id", get(handlers::admin::get_tenant))
+            .route(
+                "/admin/tenants/:id",
+                axum::routing::put(handlers::admin::update_tenant),
+            )
+            .route(
+                "/admin/tenants/:id",
+                axum::routing::delete(handlers::admin::delete_tenant),
+            )
+            .route(
+                "/admin/tenants/:id/billing",
+                get(handlers::admin::get_tenant_billing),
+            )
+            // Cache management
+            .route("/admin/cache/warm", post(handlers::admin::warm_cache))
+            .route(
+                "/admin/cache/invalidate",
+                post(handlers::admin::invalidate_cache),
+            )
+            .route("/admin/cache/stats", get(handlers::admin::get_cache_stats))
+            // State management
+            .route("/admin/state/reload", post(handlers::admin::reload_state))
+            .route(
+                "/admin/state/checkpoint",
+                post(handlers::admin::create_checkpoint),
+            )
+            .route(
+                "/admin/state/restore/:id",
+                post(handlers::admin::restore_checkpoint),
+            )
+    } else {
+        app
+    };
 
     let app = app.fallback(handlers::not_found);
 
@@ -552,6 +665,10 @@ async fn main() -> anyhow::Result<()> {
             app_state.clone(),
             rate_limit_middleware,
         )) // Rate limiting and concurrency control
+        .layer(axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            drain_middleware,
+        )) // Graceful drain - rejects new requests (503) once draining
         .layer(PayloadLimitLayer::with_limit(50 * 1024 * 1024)) // 50MB limit for large PDF/HTML payloads
         .layer(prometheus_layer)
         .layer(TraceLayer::new_for_http())
@@ -591,9 +708,11 @@ async fn main() -> anyhow::Result<()> {
 
 /// Graceful shutdown signal handler.
 ///
-/// Listens for SIGTERM and SIGINT signals to gracefully shutdown the server.
-/// This allows for proper cleanup of connections and resources including
-/// the session cleanup background task.
+/// Listens for SIGTERM and SIGINT signals, then drains the server: new
+/// requests are rejected, in-flight requests and worker jobs get the
+/// configured drain budget to finish, and pending spider state is
+/// checkpointed before connections are closed and the session cleanup
+/// background task is stopped.
 async fn shutdown_signal(app_state: Arc<ApplicationContext>) {
     use tokio::signal;
 
@@ -629,6 +748,25 @@ async fn shutdown_signal(app_state: Arc<ApplicationContext>) {
         },
     }
 
+    // Let in-flight work finish (or join a drain started via /admin/drain)
+    let status = app_state.drain.drain(&app_state).await;
+    tracing::info!(
+        elapsed_ms = status.elapsed_ms,
+        rejected_requests = status.rejected_requests,
+        abandoned_requests = ?status.abandoned_requests,
+        abandoned_jobs = ?status.abandoned_jobs,
+        spider_checkpoint = ?status.spider_checkpoint,
+        "Drain finished"
+    );
+
+    // Close the WARC files of the fetch archive so they are complete
+    if let Some(archive) = &app_state.fetch_archive {
+        match archive.finish().await {
+            Ok(files) => tracing::info!(files = ?files, "Fetch archive finalized"),
+            Err(e) => tracing::error!(error = %e, "Failed to finalize fetch archive"),
+        }
+    }
+
     // Shutdown session cleanup task
     tracing::info!("Shutting down session cleanup task");
     app_state.session_manager.shutdown();
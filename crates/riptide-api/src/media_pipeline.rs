@@ -0,0 +1,975 @@
+//! Media pipelines for render screenshots and page images
+//!
+//! Turns a captured screenshot into durable artifacts instead of shipping it
+//! as base64 inside JSON responses:
+//! 1. **Decode**: Validate the image (PNG/JPEG/WebP) and read its dimensions
+//! 2. **Variants**: Generate a WebP copy and a downscaled WebP thumbnail
+//! 3. **Store**: Upload every variant through the `BlobStorage` port
+//! 4. **Reference**: Return `PipelineArtifact`s carrying the durable URLs
+//!
+//! Fetched PDFs are stored unchanged as a single `Pdf` artifact when a crawl
+//! asks for it with `store_pdf`, up to `max_pdf_bytes`.
+//!
+//! [`MediaDownloader`] does the same for the images an extracted page
+//! references in `ExtractedDoc::media`: it downloads them within size and
+//! type caps, records their dimensions and a perceptual hash, stores them
+//! under that hash, and flags images already seen on an earlier page. It can
+//! also probe `ExtractedDoc::images` with HEAD requests to record their
+//! content type and size without downloading them. Image URLs come from the
+//! page, so those resolving to private or local addresses are never
+//! requested.
+//!
+//! Image work is CPU-bound and runs on the blocking thread pool.
+
+use crate::errors::{ApiError, ApiResult};
+use base64::Engine;
+use futures::stream::{self, StreamExt};
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
+use riptide_config::CommonValidator;
+use riptide_types::pipeline::{ArtifactKind, PipelineArtifact};
+use riptide_types::ports::{BlobStorage, HttpClient, HttpRequest};
+use riptide_types::{ExtractedDoc, MediaAsset, MediaDuplicate};
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, warn};
+use url::Url;
+
+/// Media pipeline configuration
+#[derive(Debug, Clone)]
+pub struct MediaPipelineConfig {
+    /// Thumbnail bounding box width in pixels
+    pub thumbnail_max_width: u32,
+    /// Thumbnail bounding box height in pixels
+    pub thumbnail_max_height: u32,
+    /// Store a full-size WebP variant alongside the original
+    pub webp_variant: bool,
+    /// Reject screenshots larger than this many bytes
+    pub max_input_bytes: usize,
+    /// Reject PDFs larger than this many bytes
+    pub max_pdf_bytes: usize,
+}
+
+impl Default for MediaPipelineConfig {
+    fn default() -> Self {
+        Self {
+            thumbnail_max_width: 320,
+            thumbnail_max_height: 240,
+            webp_variant: true,
+            max_input_bytes: 20 * 1024 * 1024, // 20MB
+            max_pdf_bytes: 20 * 1024 * 1024,   // 20MB
+        }
+    }
+}
+
+/// Encoded image variant ready for upload
+struct Variant {
+    kind: ArtifactKind,
+    name: &'static str,
+    content_type: &'static str,
+    data: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+/// Screenshot and PDF artifact pipeline backed by the `BlobStorage` port
+#[derive(Clone)]
+pub struct MediaPipeline {
+    storage: Arc<dyn BlobStorage>,
+    config: MediaPipelineConfig,
+}
+
+impl MediaPipeline {
+    /// Create a pipeline storing artifacts in `storage`
+    pub fn new(storage: Arc<dyn BlobStorage>, config: MediaPipelineConfig) -> Self {
+        Self { storage, config }
+    }
+
+    /// Process a base64-encoded screenshot as returned by the headless service
+    pub async fn process_screenshot_base64(
+        &self,
+        page_url: &str,
+        encoded: &str,
+    ) -> ApiResult<Vec<PipelineArtifact>> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| ApiError::validation(format!("Invalid screenshot encoding: {}", e)))?;
+        self.process_screenshot(page_url, bytes).await
+    }
+
+    /// Generate variants for a screenshot and store them
+    pub async fn process_screenshot(
+        &self,
+        page_url: &str,
+        bytes: Vec<u8>,
+    ) -> ApiResult<Vec<PipelineArtifact>> {
+        if bytes.len() > self.config.max_input_bytes {
+            return Err(ApiError::validation(format!(
+                "Screenshot exceeds {} bytes",
+                self.config.max_input_bytes
+            )));
+        }
+
+        let config = self.config.clone();
+        let variants = tokio::task::spawn_blocking(move || build_variants(bytes, &config))
+            .await
+            .map_err(|e| {
+                ApiError::internal(format!("Screenshot processing task failed: {}", e))
+            })??;
+
+        let prefix = artifact_prefix("screenshots", page_url);
+        let mut artifacts = Vec::with_capacity(variants.len());
+        for variant in variants {
+            let key = format!("{}/{}", prefix, variant.name);
+            let blob = self
+                .storage
+                .put(&key, &variant.data, variant.content_type)
+                .await
+                .map_err(|e| ApiError::internal(format!("Failed to store artifact: {}", e)))?;
+            artifacts.push(PipelineArtifact {
+                kind: variant.kind,
+                url: blob.url,
+                content_type: blob.content_type,
+                width: variant.width,
+                height: variant.height,
+                size_bytes: blob.size_bytes,
+            });
+        }
+
+        debug!(url = %page_url, count = artifacts.len(), "Stored screenshot artifacts");
+        Ok(artifacts)
+    }
+
+    /// Store a fetched PDF
+    pub async fn store_pdf(&self, page_url: &str, bytes: &[u8]) -> ApiResult<PipelineArtifact> {
+        if bytes.len() > self.config.max_pdf_bytes {
+            return Err(ApiError::validation(format!(
+                "PDF exceeds {} bytes",
+                self.config.max_pdf_bytes
+            )));
+        }
+        let key = format!("{}/source.pdf", artifact_prefix("pdfs", page_url));
+        let blob = self
+            .storage
+            .put(&key, bytes, "application/pdf")
+            .await
+            .map_err(|e| ApiError::internal(format!("Failed to store artifact: {}", e)))?;
+        Ok(PipelineArtifact {
+            kind: ArtifactKind::Pdf,
+            url: blob.url,
+            content_type: blob.content_type,
+            width: 0,
+            height: 0,
+            size_bytes: blob.size_bytes,
+        })
+    }
+}
+
+/// Storage key prefix under `dir`: one directory per page URL, one
+/// subdirectory per capture
+fn artifact_prefix(dir: &str, page_url: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    page_url.hash(&mut hasher);
+    format!(
+        "{}/{:016x}/{}",
+        dir,
+        hasher.finish(),
+        chrono::Utc::now().format("%Y%m%dT%H%M%S%3fZ")
+    )
+}
+
+fn build_variants(bytes: Vec<u8>, config: &MediaPipelineConfig) -> ApiResult<Vec<Variant>> {
+    let format = image::guess_format(&bytes)
+        .map_err(|e| ApiError::validation(format!("Unrecognized screenshot format: {}", e)))?;
+    let (original_name, original_type) = match format {
+        ImageFormat::Png => ("full.png", "image/png"),
+        ImageFormat::Jpeg => ("full.jpg", "image/jpeg"),
+        ImageFormat::WebP => ("full.webp", "image/webp"),
+        other => {
+            return Err(ApiError::validation(format!(
+                "Unsupported screenshot format: {:?}",
+                other
+            )))
+        }
+    };
+    let image = image::load_from_memory_with_format(&bytes, format)
+        .map_err(|e| ApiError::validation(format!("Failed to decode screenshot: {}", e)))?;
+
+    let mut variants = Vec::with_capacity(3);
+    if config.webp_variant && format != ImageFormat::WebP {
+        variants.push(Variant {
+            kind: ArtifactKind::ScreenshotWebp,
+            name: "full.webp",
+            content_type: "image/webp",
+            data: encode_webp(&image)?,
+            width: image.width(),
+            height: image.height(),
+        });
+    }
+
+    let thumbnail = image.thumbnail(config.thumbnail_max_width, config.thumbnail_max_height);
+    variants.push(Variant {
+        kind: ArtifactKind::Thumbnail,
+        name: "thumbnail.webp",
+        content_type: "image/webp",
+        data: encode_webp(&thumbnail)?,
+        width: thumbnail.width(),
+        height: thumbnail.height(),
+    });
+
+    variants.insert(
+        0,
+        Variant {
+            kind: ArtifactKind::Screenshot,
+            name: original_name,
+            content_type: original_type,
+            data: bytes,
+            width: image.width(),
+            height: image.height(),
+        },
+    );
+    Ok(variants)
+}
+
+fn encode_webp(image: &DynamicImage) -> ApiResult<Vec<u8>> {
+    // The WebP encoder only accepts 8-bit RGB(A)
+    let image = DynamicImage::ImageRgba8(image.to_rgba8());
+    let mut out = Cursor::new(Vec::new());
+    image
+        .write_to(&mut out, ImageFormat::WebP)
+        .map_err(|e| ApiError::internal(format!("WebP encoding failed: {}", e)))?;
+    Ok(out.into_inner())
+}
+
+/// Downloads of the images referenced by extracted documents
+#[derive(Debug, Clone, Serialize)]
+pub struct MediaDownloadConfig {
+    /// Download and fingerprint images of crawled pages
+    pub enabled: bool,
+    /// HEAD-request the images of crawled pages for content type and size
+    pub probe_images: bool,
+    /// Images fetched per page, in the order the page references them
+    pub max_images: usize,
+    /// Larger images are skipped
+    pub max_image_bytes: usize,
+    /// Accepted `Content-Type`s; PNG, JPEG and WebP are the decodable ones
+    pub allowed_types: Vec<String>,
+    /// Per-image download timeout
+    pub timeout_secs: u64,
+    /// Concurrent downloads per page
+    pub concurrency: usize,
+    /// Store downloaded images through `BlobStorage`
+    pub store: bool,
+    /// Images whose hashes differ in at most this many bits are duplicates
+    pub duplicate_distance: u32,
+    /// Hashes remembered for duplicate detection; later images are still
+    /// checked but no longer remembered
+    pub max_tracked_hashes: usize,
+}
+
+impl Default for MediaDownloadConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            probe_images: false,
+            max_images: 10,
+            max_image_bytes: 5 * 1024 * 1024, // 5MB
+            allowed_types: vec![
+                "image/png".to_string(),
+                "image/jpeg".to_string(),
+                "image/webp".to_string(),
+            ],
+            timeout_secs: 10,
+            concurrency: 4,
+            store: true,
+            duplicate_distance: 4,
+            max_tracked_hashes: 100_000,
+        }
+    }
+}
+
+impl MediaDownloadConfig {
+    /// Body limits for the downloader's client: images of at most
+    /// `max_image_bytes`
+    pub fn body_limits(&self) -> riptide_fetch::BodyLimits {
+        riptide_fetch::BodyLimits::default()
+            .with_max_content_length(self.max_image_bytes as u64)
+            .with_allowed_content_types(["image/*"])
+    }
+
+    /// Read `RIPTIDE_MEDIA_*` overrides of the defaults
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            enabled: std::env::var("RIPTIDE_MEDIA_DOWNLOAD_ENABLED")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(defaults.enabled),
+            probe_images: std::env::var("RIPTIDE_MEDIA_PROBE_IMAGES")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(defaults.probe_images),
+            max_images: env_parse("RIPTIDE_MEDIA_MAX_IMAGES").unwrap_or(defaults.max_images),
+            max_image_bytes: env_parse("RIPTIDE_MEDIA_MAX_IMAGE_BYTES")
+                .unwrap_or(defaults.max_image_bytes),
+            allowed_types: std::env::var("RIPTIDE_MEDIA_ALLOWED_TYPES")
+                .map(|v| {
+                    v.split(',')
+                        .map(|t| t.trim().to_ascii_lowercase())
+                        .filter(|t| !t.is_empty())
+                        .collect()
+                })
+                .unwrap_or(defaults.allowed_types),
+            timeout_secs: env_parse("RIPTIDE_MEDIA_TIMEOUT_SECS").unwrap_or(defaults.timeout_secs),
+            concurrency: env_parse("RIPTIDE_MEDIA_CONCURRENCY")
+                .filter(|&n: &usize| n > 0)
+                .unwrap_or(defaults.concurrency),
+            store: std::env::var("RIPTIDE_MEDIA_STORE")
+                .map(|v| v != "false" && v != "0")
+                .unwrap_or(defaults.store),
+            duplicate_distance: env_parse("RIPTIDE_MEDIA_DUPLICATE_DISTANCE")
+                .unwrap_or(defaults.duplicate_distance),
+            max_tracked_hashes: env_parse("RIPTIDE_MEDIA_MAX_TRACKED_HASHES")
+                .unwrap_or(defaults.max_tracked_hashes),
+        }
+    }
+}
+
+fn env_parse<T: std::str::FromStr>(name: &str) -> Option<T> {
+    std::env::var(name).ok().and_then(|v| v.trim().parse().ok())
+}
+
+/// First occurrence of an image hash
+#[derive(Debug, Clone)]
+struct SeenImage {
+    hash: u64,
+    url: String,
+    page_url: String,
+    stored_url: Option<String>,
+}
+
+/// Downloads, fingerprints and deduplicates the images of extracted pages
+pub struct MediaDownloader {
+    http: Arc<dyn HttpClient>,
+    storage: Arc<dyn BlobStorage>,
+    config: MediaDownloadConfig,
+    /// Checks image URLs before they are requested
+    validator: Arc<CommonValidator>,
+    /// Images seen so far, shared by every page this downloader processes
+    seen: Mutex<Vec<SeenImage>>,
+}
+
+impl MediaDownloader {
+    /// Create a downloader fetching through `http` and storing in `storage`
+    ///
+    /// `http` should only accept images up to `max_image_bytes`, so other
+    /// bodies are never read into memory.
+    pub fn new(
+        http: Arc<dyn HttpClient>,
+        storage: Arc<dyn BlobStorage>,
+        config: MediaDownloadConfig,
+    ) -> Self {
+        Self {
+            http,
+            storage,
+            config,
+            validator: Arc::new(CommonValidator::new_default()),
+            seen: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Check image URLs with `validator` instead of the default one
+    pub fn with_validator(mut self, validator: CommonValidator) -> Self {
+        self.validator = Arc::new(validator);
+        self
+    }
+
+    /// Reject URLs that are, or resolve to, private or local addresses
+    async fn check_url(&self, url: &str) -> ApiResult<()> {
+        let validator = self.validator.clone();
+        let target = url.to_string();
+        tokio::task::spawn_blocking(move || validator.validate_url_resolved(&target))
+            .await
+            .map_err(|e| ApiError::internal(format!("URL check task failed: {}", e)))?
+            .map_err(|e| ApiError::invalid_url(url, e.to_string()))?;
+        Ok(())
+    }
+
+    /// Fill `document.media_assets` from `document.media`.
+    ///
+    /// Images that fail to download, exceed the caps or do not decode are
+    /// skipped; the rest keep the order of `media`.
+    pub async fn enrich(&self, document: &mut ExtractedDoc) {
+        if !self.config.enabled {
+            return;
+        }
+        let base = Url::parse(&document.url).ok();
+        let mut unique = HashSet::new();
+        let urls: Vec<String> = document
+            .media
+            .iter()
+            .filter_map(|src| match &base {
+                Some(base) => base.join(src).ok(),
+                None => Url::parse(src).ok(),
+            })
+            .filter(|url| matches!(url.scheme(), "http" | "https"))
+            .map(String::from)
+            .filter(|url| unique.insert(url.clone()))
+            .take(self.config.max_images)
+            .collect();
+
+        let page_url = document.url.as_str();
+        let assets: Vec<Option<MediaAsset>> = stream::iter(urls)
+            .map(|url| async move {
+                match self.process(page_url, &url).await {
+                    Ok(asset) => Some(asset),
+                    Err(e) => {
+                        debug!(page = %page_url, image = %url, error = %e, "Skipping image");
+                        None
+                    }
+                }
+            })
+            .buffered(self.config.concurrency.max(1))
+            .collect()
+            .await;
+        document.media_assets = assets.into_iter().flatten().collect();
+    }
+
+    /// Fill the content type and size of `document.images`.
+    ///
+    /// Images already downloaded into `media_assets` take their values from
+    /// there; up to `max_images` others get a HEAD request. Images whose
+    /// probe fails are left unchanged.
+    pub async fn probe(&self, document: &mut ExtractedDoc) {
+        if !self.config.probe_images {
+            return;
+        }
+        for image in &mut document.images {
+            if let Some(asset) = document.media_assets.iter().find(|a| a.url == image.url) {
+                image.content_type = Some(asset.content_type.clone());
+                image.size_bytes = Some(asset.size_bytes);
+            }
+        }
+
+        let page_url = document.url.as_str();
+        let probes: Vec<(usize, Option<(Option<String>, Option<u64>)>)> = stream::iter(
+            document
+                .images
+                .iter()
+                .enumerate()
+                .filter(|(_, image)| image.content_type.is_none())
+                .take(self.config.max_images),
+        )
+        .map(|(index, image)| async move {
+            match self.head(&image.url).await {
+                Ok(probed) => (index, Some(probed)),
+                Err(e) => {
+                    debug!(page = %page_url, image = %image.url, error = %e, "Image probe failed");
+                    (index, None)
+                }
+            }
+        })
+        .buffered(self.config.concurrency.max(1))
+        .collect()
+        .await;
+
+        for (index, probed) in probes {
+            if let Some((content_type, size_bytes)) = probed {
+                let image = &mut document.images[index];
+                image.content_type = content_type;
+                image.size_bytes = size_bytes;
+            }
+        }
+    }
+
+    /// `Content-Type` essence and `Content-Length` of `url`
+    async fn head(&self, url: &str) -> ApiResult<(Option<String>, Option<u64>)> {
+        self.check_url(url).await?;
+        let request = HttpRequest::new("HEAD", url)
+            .with_timeout(Duration::from_secs(self.config.timeout_secs));
+        let response = self
+            .http
+            .request(request)
+            .await
+            .map_err(|e| ApiError::fetch(url, e.to_string()))?;
+        if !response.is_success() {
+            return Err(ApiError::fetch(url, format!("HTTP {}", response.status)));
+        }
+        let content_type = response
+            .header("content-type")
+            .and_then(|v| v.split(';').next())
+            .map(|v| v.trim().to_ascii_lowercase())
+            .filter(|v| !v.is_empty());
+        let size_bytes = response
+            .header("content-length")
+            .and_then(|v| v.trim().parse().ok());
+        Ok((content_type, size_bytes))
+    }
+
+    async fn process(&self, page_url: &str, url: &str) -> ApiResult<MediaAsset> {
+        self.check_url(url).await?;
+        let request = HttpRequest::new("GET", url)
+            .with_header("Accept", self.config.allowed_types.join(", "))
+            .with_timeout(Duration::from_secs(self.config.timeout_secs));
+        let response = self
+            .http
+            .request(request)
+            .await
+            .map_err(|e| ApiError::fetch(url, e.to_string()))?;
+        if !response.is_success() {
+            return Err(ApiError::fetch(url, format!("HTTP {}", response.status)));
+        }
+        let declared_type = response
+            .header("content-type")
+            .and_then(|v| v.split(';').next())
+            .map(|v| v.trim().to_ascii_lowercase());
+        if let Some(declared) = &declared_type {
+            if !self.config.allowed_types.contains(declared) {
+                return Err(ApiError::validation(format!(
+                    "Content type {} not allowed",
+                    declared
+                )));
+            }
+        }
+        if response.body.len() > self.config.max_image_bytes {
+            return Err(ApiError::validation(format!(
+                "Image exceeds {} bytes",
+                self.config.max_image_bytes
+            )));
+        }
+
+        let bytes = response.body;
+        let (bytes, fingerprint) =
+            tokio::task::spawn_blocking(move || fingerprint_image(&bytes).map(|f| (bytes, f)))
+                .await
+                .map_err(|e| {
+                    ApiError::internal(format!("Image processing task failed: {}", e))
+                })??;
+        if !self
+            .config
+            .allowed_types
+            .iter()
+            .any(|t| t == fingerprint.content_type)
+        {
+            return Err(ApiError::validation(format!(
+                "Content type {} not allowed",
+                fingerprint.content_type
+            )));
+        }
+
+        let phash = format!("{:016x}", fingerprint.hash);
+        let duplicate = self.check_duplicate(fingerprint.hash, url, page_url);
+        // Identical hashes share one stored copy
+        let mut stored_url = duplicate
+            .as_ref()
+            .filter(|(_, distance)| *distance == 0)
+            .and_then(|(seen, _)| seen.stored_url.clone());
+        if self.config.store && stored_url.is_none() {
+            let key = format!("media/{}.{}", phash, fingerprint.extension);
+            match self
+                .storage
+                .put(&key, &bytes, fingerprint.content_type)
+                .await
+            {
+                Ok(blob) => stored_url = Some(blob.url),
+                Err(e) => warn!(image = %url, error = %e, "Failed to store image"),
+            }
+            if duplicate.is_none() {
+                self.set_stored_url(fingerprint.hash, stored_url.clone());
+            }
+        }
+
+        Ok(MediaAsset {
+            url: url.to_string(),
+            content_type: fingerprint.content_type.to_string(),
+            size_bytes: bytes.len() as u64,
+            width: fingerprint.width,
+            height: fingerprint.height,
+            phash,
+            stored_url,
+            duplicate_of: duplicate.map(|(seen, distance)| MediaDuplicate {
+                url: seen.url,
+                page_url: seen.page_url,
+                distance,
+            }),
+        })
+    }
+
+    /// Closest earlier image within `duplicate_distance` bits; an image
+    /// without one is remembered as the first occurrence of its hash
+    fn check_duplicate(&self, hash: u64, url: &str, page_url: &str) -> Option<(SeenImage, u32)> {
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        let closest = seen
+            .iter()
+            .map(|image| (image, (image.hash ^ hash).count_ones()))
+            .filter(|(_, distance)| *distance <= self.config.duplicate_distance)
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(image, distance)| (image.clone(), distance));
+        if closest.is_none() && seen.len() < self.config.max_tracked_hashes {
+            seen.push(SeenImage {
+                hash,
+                url: url.to_string(),
+                page_url: page_url.to_string(),
+                stored_url: None,
+            });
+        }
+        closest
+    }
+
+    fn set_stored_url(&self, hash: u64, stored_url: Option<String>) {
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(image) = seen.iter_mut().find(|image| image.hash == hash) {
+            image.stored_url = stored_url;
+        }
+    }
+}
+
+/// Decoded format, dimensions and perceptual hash of an image
+struct Fingerprint {
+    content_type: &'static str,
+    extension: &'static str,
+    width: u32,
+    height: u32,
+    hash: u64,
+}
+
+fn fingerprint_image(bytes: &[u8]) -> ApiResult<Fingerprint> {
+    let format = image::guess_format(bytes)
+        .map_err(|e| ApiError::validation(format!("Unrecognized image format: {}", e)))?;
+    let (content_type, extension) = match format {
+        ImageFormat::Png => ("image/png", "png"),
+        ImageFormat::Jpeg => ("image/jpeg", "jpg"),
+        ImageFormat::WebP => ("image/webp", "webp"),
+        other => {
+            return Err(ApiError::validation(format!(
+                "Unsupported image format: {:?}",
+                other
+            )))
+        }
+    };
+    let image = image::load_from_memory_with_format(bytes, format)
+        .map_err(|e| ApiError::validation(format!("Failed to decode image: {}", e)))?;
+    Ok(Fingerprint {
+        content_type,
+        extension,
+        width: image.width(),
+        height: image.height(),
+        hash: difference_hash(&image),
+    })
+}
+
+/// 64-bit dHash: each bit says whether a pixel of a 9x8 grayscale
+/// downscale is brighter than its right neighbour. Resizing, recompression
+/// and small edits flip only a few bits.
+fn difference_hash(image: &DynamicImage) -> u64 {
+    let small = image.resize_exact(9, 8, FilterType::Triangle).to_luma8();
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y)[0] > small.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use image::{ImageBuffer, Rgba};
+    use riptide_types::ports::{HttpResponse, InMemoryBlobStorage};
+    use riptide_types::ImageMetadata;
+    use std::collections::HashMap;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = ImageBuffer::from_pixel(width, height, Rgba([30u8, 60, 90, 255]));
+        let mut out = Cursor::new(Vec::new());
+        DynamicImage::ImageRgba8(image)
+            .write_to(&mut out, ImageFormat::Png)
+            .unwrap();
+        out.into_inner()
+    }
+
+    #[tokio::test]
+    async fn test_screenshot_variants_stored() {
+        let storage = Arc::new(InMemoryBlobStorage::with_base_url("/artifacts"));
+        let pipeline = MediaPipeline::new(storage.clone(), MediaPipelineConfig::default());
+
+        let artifacts = pipeline
+            .process_screenshot("https://example.com/", png(1280, 960))
+            .await
+            .unwrap();
+
+        let kinds: Vec<_> = artifacts.iter().map(|a| a.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ArtifactKind::Screenshot,
+                ArtifactKind::ScreenshotWebp,
+                ArtifactKind::Thumbnail
+            ]
+        );
+        let thumbnail = &artifacts[2];
+        assert_eq!((thumbnail.width, thumbnail.height), (320, 240));
+        assert_eq!(thumbnail.content_type, "image/webp");
+        assert!(thumbnail.url.starts_with("/artifacts/screenshots/"));
+        assert_eq!(storage.len().await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_pdf_stored() {
+        let storage = Arc::new(InMemoryBlobStorage::with_base_url("/artifacts"));
+        let pipeline = MediaPipeline::new(storage.clone(), MediaPipelineConfig::default());
+
+        let artifact = pipeline
+            .store_pdf("https://example.com/report.pdf", b"%PDF-1.7 test")
+            .await
+            .unwrap();
+
+        assert_eq!(artifact.kind, ArtifactKind::Pdf);
+        assert_eq!(artifact.content_type, "application/pdf");
+        assert_eq!(artifact.size_bytes, 13);
+        assert!(artifact.url.starts_with("/artifacts/pdfs/"));
+        assert!(artifact.url.ends_with("/source.pdf"));
+
+        let capped = MediaPipeline::new(
+            storage.clone(),
+            MediaPipelineConfig {
+                max_pdf_bytes: 8,
+                ..Default::default()
+            },
+        );
+        assert!(capped
+            .store_pdf("https://example.com/report.pdf", b"%PDF-1.7 test")
+            .await
+            .is_err());
+        assert_eq!(storage.len().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_non_image() {
+        let storage = Arc::new(InMemoryBlobStorage::new());
+        let pipeline = MediaPipeline::new(storage, MediaPipelineConfig::default());
+        let encoded = base64::engine::general_purpose::STANDARD.encode(b"not an image");
+
+        assert!(pipeline
+            .process_screenshot_base64("https://example.com/", &encoded)
+            .await
+            .is_err());
+    }
+
+    /// Serves fixed bodies by URL, with a content type guessed from the
+    /// extension; HEAD requests get the length instead of the body
+    struct StaticClient(HashMap<String, Vec<u8>>);
+
+    #[async_trait]
+    impl HttpClient for StaticClient {
+        async fn get(&self, url: &str) -> riptide_types::Result<HttpResponse> {
+            self.request(HttpRequest::new("GET", url)).await
+        }
+
+        async fn post(&self, url: &str, _body: &[u8]) -> riptide_types::Result<HttpResponse> {
+            self.request(HttpRequest::new("POST", url)).await
+        }
+
+        async fn request(&self, req: HttpRequest) -> riptide_types::Result<HttpResponse> {
+            let Some(body) = self.0.get(&req.url) else {
+                return Ok(HttpResponse::new(404, HashMap::new(), Vec::new()));
+            };
+            let content_type = if req.url.ends_with(".png") {
+                "image/png"
+            } else {
+                "text/html"
+            };
+            let mut headers =
+                HashMap::from([("Content-Type".to_string(), content_type.to_string())]);
+            if req.method == "HEAD" {
+                headers.insert("Content-Length".to_string(), body.len().to_string());
+                return Ok(HttpResponse::new(200, headers, Vec::new()));
+            }
+            Ok(HttpResponse::new(200, headers, body.clone()))
+        }
+    }
+
+    /// Validator letting tests reach hosts without resolving them
+    fn allow_private_ips() -> CommonValidator {
+        CommonValidator::new(riptide_config::ValidationConfig {
+            block_private_ips: false,
+            ..Default::default()
+        })
+    }
+
+    /// Horizontal (or vertical) brightness gradient
+    fn gradient(width: u32, height: u32, horizontal: bool) -> Vec<u8> {
+        let image = ImageBuffer::from_fn(width, height, |x, y| {
+            let (pos, len) = if horizontal { (x, width) } else { (y, height) };
+            let v = 255 - (pos * 255 / len) as u8;
+            Rgba([v, v, v, 255])
+        });
+        let mut out = Cursor::new(Vec::new());
+        DynamicImage::ImageRgba8(image)
+            .write_to(&mut out, ImageFormat::Png)
+            .unwrap();
+        out.into_inner()
+    }
+
+    fn doc(url: &str, media: &[&str]) -> ExtractedDoc {
+        ExtractedDoc {
+            url: url.to_string(),
+            media: media.iter().map(|m| m.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_downloads_hashes_and_flags_duplicates_across_pages() {
+        let huge = gradient(900, 900, true);
+        let max_image_bytes = huge.len() - 1;
+        let client = StaticClient(HashMap::from([
+            ("https://a.com/logo.png".to_string(), gradient(64, 32, true)),
+            (
+                "https://a.com/photo.png".to_string(),
+                gradient(64, 32, false),
+            ),
+            // Same picture at another size and URL
+            (
+                "https://b.com/logo-small.png".to_string(),
+                gradient(32, 16, true),
+            ),
+            ("https://b.com/huge.png".to_string(), huge),
+            (
+                "https://b.com/page.html".to_string(),
+                b"<html></html>".to_vec(),
+            ),
+        ]));
+        let storage = Arc::new(InMemoryBlobStorage::with_base_url("/artifacts"));
+        let downloader = MediaDownloader::new(
+            Arc::new(client),
+            storage.clone(),
+            MediaDownloadConfig {
+                enabled: true,
+                max_image_bytes,
+                ..Default::default()
+            },
+        )
+        .with_validator(allow_private_ips());
+
+        let mut first = doc("https://a.com/", &["/logo.png", "photo.png", "/logo.png"]);
+        downloader.enrich(&mut first).await;
+        assert_eq!(first.media_assets.len(), 2);
+        let logo = &first.media_assets[0];
+        assert_eq!((logo.width, logo.height), (64, 32));
+        assert_eq!(logo.content_type, "image/png");
+        assert_eq!(logo.phash.len(), 16);
+        assert!(logo.duplicate_of.is_none());
+        assert!(logo
+            .stored_url
+            .as_deref()
+            .is_some_and(|url| url.starts_with("/artifacts/media/")));
+        assert_ne!(first.media_assets[1].phash, logo.phash);
+
+        let mut second = doc(
+            "https://b.com/",
+            &["/logo-small.png", "/huge.png", "/page.html", "/missing.png"],
+        );
+        downloader.enrich(&mut second).await;
+        assert_eq!(second.media_assets.len(), 1);
+        let duplicate = second.media_assets[0].duplicate_of.as_ref().unwrap();
+        assert_eq!(duplicate.url, "https://a.com/logo.png");
+        assert_eq!(duplicate.page_url, "https://a.com/");
+        assert!(duplicate.distance <= 4);
+        // An identical hash reuses the stored copy
+        if duplicate.distance == 0 {
+            assert_eq!(second.media_assets[0].stored_url, logo.stored_url);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_probe_fills_image_type_and_size() {
+        let logo = gradient(64, 32, true);
+        let logo_len = logo.len() as u64;
+        let client = StaticClient(HashMap::from([(
+            "https://a.com/logo.png".to_string(),
+            logo,
+        )]));
+        let downloader = MediaDownloader::new(
+            Arc::new(client),
+            Arc::new(InMemoryBlobStorage::with_base_url("/artifacts")),
+            MediaDownloadConfig {
+                probe_images: true,
+                ..Default::default()
+            },
+        )
+        .with_validator(allow_private_ips());
+
+        let mut page = doc("https://a.com/", &["/logo.png"]);
+        page.images = vec![
+            ImageMetadata::new("https://a.com/logo.png"),
+            ImageMetadata::new("https://a.com/missing.png"),
+        ];
+        // Downloads are off, so only the probe runs
+        downloader.enrich(&mut page).await;
+        assert!(page.media_assets.is_empty());
+        downloader.probe(&mut page).await;
+
+        assert_eq!(page.images[0].content_type.as_deref(), Some("image/png"));
+        assert_eq!(page.images[0].size_bytes, Some(logo_len));
+        assert!(page.images[1].content_type.is_none());
+        assert!(page.images[1].size_bytes.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_real_client_reads_only_images_under_the_cap() {
+        use wiremock::matchers::path;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let logo = gradient(64, 32, true);
+        let server = MockServer::start().await;
+        for (route, body, content_type) in [
+            ("/logo.png", logo.clone(), "image/png"),
+            ("/page.png", logo.clone(), "text/html"),
+            ("/huge.png", gradient(900, 900, true), "image/png"),
+        ] {
+            Mock::given(path(route))
+                .respond_with(ResponseTemplate::new(200).set_body_raw(body, content_type))
+                .mount(&server)
+                .await;
+        }
+
+        let config = MediaDownloadConfig {
+            enabled: true,
+            max_image_bytes: logo.len() + 16,
+            ..Default::default()
+        };
+        let client = riptide_fetch::adapters::ReqwestHttpClient::new()
+            .unwrap()
+            .with_body_limits(config.body_limits());
+        let downloader = MediaDownloader::new(
+            Arc::new(client),
+            Arc::new(InMemoryBlobStorage::with_base_url("/artifacts")),
+            config,
+        );
+
+        // Loopback image URLs are refused by default
+        let mut page = doc(&server.uri(), &["/logo.png"]);
+        downloader.enrich(&mut page).await;
+        assert!(page.media_assets.is_empty());
+        assert!(downloader
+            .check_url(&format!("{}/logo.png", server.uri()))
+            .await
+            .is_err());
+
+        let downloader = downloader.with_validator(allow_private_ips());
+        let mut page = doc(&server.uri(), &["/logo.png", "/page.png", "/huge.png"]);
+        downloader.enrich(&mut page).await;
+        assert_eq!(page.media_assets.len(), 1);
+        assert!(page.media_assets[0].url.ends_with("/logo.png"));
+        assert_eq!(page.media_assets[0].size_bytes, logo.len() as u64);
+    }
+}
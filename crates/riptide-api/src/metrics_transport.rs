@@ -33,6 +33,10 @@ pub struct TransportMetrics {
     /// HTTP error counter
     pub http_errors: Counter,
 
+    // ===== Region Metrics =====
+    /// Render duration by serving region and preferred region
+    pub region_render_duration_seconds: HistogramVec,
+
     // ===== Connection Metrics =====
     /// Active HTTP connections gauge
     pub active_connections: Gauge,
@@ -132,6 +136,17 @@ impl TransportMetrics {
                 .const_label("service", "riptide-transport"),
         )?;
 
+        // Region metrics
+        let region_render_duration_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "riptide_transport_region_render_duration_seconds",
+                "Render duration by serving region and preferred region",
+            )
+            .const_label("service", "riptide-transport")
+            .buckets(vec![0.1, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0, 60.0]),
+            &["region", "preferred_region"],
+        )?;
+
         // Connection metrics
         let active_connections = Gauge::with_opts(
             Opts::new(
@@ -315,6 +330,7 @@ impl TransportMetrics {
         registry.register(Box::new(http_requests_total.clone()))?;
         registry.register(Box::new(http_request_duration.clone()))?;
         registry.register(Box::new(http_errors.clone()))?;
+        registry.register(Box::new(region_render_duration_seconds.clone()))?;
         registry.register(Box::new(active_connections.clone()))?;
         registry.register(Box::new(streaming_active_connections.clone()))?;
         registry.register(Box::new(streaming_total_connections.clone()))?;
@@ -344,6 +360,7 @@ impl TransportMetrics {
             http_requests_total,
             http_request_duration,
             http_errors,
+            region_render_duration_seconds,
             active_connections,
             streaming_active_connections,
             streaming_total_connections,
@@ -403,6 +420,13 @@ impl TransportMetrics {
         }
     }
 
+    /// Record a render served by `region` for a target closest to `preferred_region`
+    pub fn record_region_render(&self, region: &str, preferred_region: &str, duration: f64) {
+        self.region_render_duration_seconds
+            .with_label_values(&[region, preferred_region])
+            .observe(duration);
+    }
+
     /// Update active connections
     #[allow(dead_code)] // For future connection tracking
     pub fn update_active_connections(&self, count: i64) {
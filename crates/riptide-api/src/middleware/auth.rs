@@ -7,6 +7,7 @@ use axum::{
     middleware::Next,
     response::{IntoResponse, Response},
 };
+use riptide_security::{ApiKeyManager, SecurityError, TenantId};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -374,8 +375,18 @@ pub struct AuthConfig {
     public_paths: Arc<Vec<String>>,
     /// Rate limiter for authentication attempts
     rate_limiter: Arc<AuthRateLimiter>,
+    /// Tenant-scoped API keys, issued via `/admin/tenants/:id/api-keys`
+    tenant_keys: Arc<ApiKeyManager>,
 }
 
+/// Tenant of the API key a request was authenticated with
+///
+/// Inserted into the request extensions by [`auth_middleware`] for
+/// tenant-scoped keys. Requests authenticated with an operator key from
+/// `API_KEYS`, or made with authentication disabled, carry none.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthenticatedTenant(pub TenantId);
+
 impl AuthConfig {
     /// Create new authentication configuration
     pub fn new() -> Self {
@@ -423,6 +434,7 @@ impl AuthConfig {
             require_auth,
             public_paths: Arc::new(public_paths),
             rate_limiter: Arc::new(rate_limiter),
+            tenant_keys: Arc::new(ApiKeyManager::new()),
         }
     }
 
@@ -444,6 +456,7 @@ impl AuthConfig {
                 "/api/health/detailed".to_string(),
             ]),
             rate_limiter: Arc::new(rate_limiter),
+            tenant_keys: Arc::new(ApiKeyManager::new()),
         }
     }
 
@@ -468,6 +481,7 @@ impl AuthConfig {
                 "/api/health/detailed".to_string(),
             ]),
             rate_limiter: Arc::new(rate_limiter),
+            tenant_keys: Arc::new(ApiKeyManager::new()),
         }
     }
 
@@ -536,6 +550,11 @@ impl AuthConfig {
         found.into()
     }
 
+    /// Tenant-scoped API keys
+    pub fn tenant_keys(&self) -> &Arc<ApiKeyManager> {
+        &self.tenant_keys
+    }
+
     /// Check if authentication is required
     pub fn requires_auth(&self) -> bool {
         self.require_auth
@@ -581,16 +600,16 @@ impl Default for AuthConfig {
 /// - **Audit logging**: All authentication attempts are logged for security monitoring
 pub async fn auth_middleware(
     State(state): State<ApplicationContext>,
-    request: Request,
+    mut request: Request,
     next: Next,
 ) -> Result<Response, Response> {
-    let path = request.uri().path();
-    let method = request.method().as_str();
+    let path = request.uri().path().to_string();
+    let method = request.method().as_str().to_string();
 
     debug!(path = %path, "Authentication check");
 
     // Check if this is a public path
-    if state.auth_config.is_public_path(path) {
+    if state.auth_config.is_public_path(&path) {
         debug!(path = %path, "Public path, skipping authentication");
         return Ok(next.run(request).await);
     }
@@ -598,6 +617,19 @@ pub async fn auth_middleware(
     // Check if authentication is required
     if !state.auth_config.requires_auth() {
         debug!("Authentication disabled, allowing request");
+        // A tenant key still selects its tenant
+        if let Some(api_key) = extract_api_key(&request) {
+            if let Ok((key, _)) = state
+                .auth_config
+                .tenant_keys
+                .validate_api_key(&api_key)
+                .await
+            {
+                request
+                    .extensions_mut()
+                    .insert(AuthenticatedTenant(key.tenant_id));
+            }
+        }
         return Ok(next.run(request).await);
     }
 
@@ -617,7 +649,7 @@ pub async fn auth_middleware(
             retry_after_secs = retry_after.as_secs(),
             "Rate limit exceeded - too many failed authentication attempts"
         );
-        AuditLogger::log_auth_blocked(&client_ip, method, path, retry_after.as_secs());
+        AuditLogger::log_auth_blocked(&client_ip, &method, &path, retry_after.as_secs());
         return Err(rate_limited_response(retry_after));
     }
 
@@ -631,26 +663,50 @@ pub async fn auth_middleware(
                 .rate_limiter
                 .record_failure(&client_ip)
                 .await;
-            AuditLogger::log_auth_failure(&client_ip, "missing_key", method, path);
+            AuditLogger::log_auth_failure(&client_ip, "missing_key", &method, &path);
             return Err(unauthorized_response("Missing API key"));
         }
     };
 
-    // Validate API key using constant-time comparison
+    // Operator keys are checked in constant time; anything else must be a
+    // tenant key, which binds the request to its tenant
     if !state.auth_config.is_valid_key(&api_key).await {
-        warn!(
-            path = %path,
-            ip = %client_ip,
-            key_prefix = &get_key_prefix(&api_key),
-            "Invalid API key - authentication failed"
-        );
-        state
+        let tenant = match state
             .auth_config
-            .rate_limiter
-            .record_failure(&client_ip)
-            .await;
-        AuditLogger::log_auth_failure(&client_ip, "invalid_key", method, path);
-        return Err(unauthorized_response("Invalid API key"));
+            .tenant_keys
+            .validate_api_key(&api_key)
+            .await
+        {
+            Ok((key, _)) => key.tenant_id,
+            Err(SecurityError::RateLimitExceeded(tenant)) => {
+                warn!(path = %path, tenant = %tenant, "Tenant API key rate limit exceeded");
+                return Err(rate_limited_response(Duration::from_secs(60)));
+            }
+            Err(_) => {
+                warn!(
+                    path = %path,
+                    ip = %client_ip,
+                    key_prefix = &get_key_prefix(&api_key),
+                    "Invalid API key - authentication failed"
+                );
+                state
+                    .auth_config
+                    .rate_limiter
+                    .record_failure(&client_ip)
+                    .await;
+                AuditLogger::log_auth_failure(&client_ip, "invalid_key", &method, &path);
+                return Err(unauthorized_response("Invalid API key"));
+            }
+        };
+
+        if is_admin_path(&path) {
+            warn!(path = %path, tenant = %tenant, "Tenant API key used on an admin endpoint");
+            AuditLogger::log_auth_failure(&client_ip, "tenant_key_admin", &method, &path);
+            return Err(forbidden_response(
+                "Tenant API keys cannot access admin endpoints",
+            ));
+        }
+        request.extensions_mut().insert(AuthenticatedTenant(tenant));
     }
 
     debug!(
@@ -666,12 +722,17 @@ pub async fn auth_middleware(
         .rate_limiter
         .record_success(&client_ip)
         .await;
-    AuditLogger::log_auth_success(&client_ip, &get_key_prefix(&api_key), method, path);
+    AuditLogger::log_auth_success(&client_ip, &get_key_prefix(&api_key), &method, &path);
 
     // Proceed with the request
     Ok(next.run(request).await)
 }
 
+/// Whether `path` is an operator-only admin endpoint
+fn is_admin_path(path: &str) -> bool {
+    path.starts_with("/admin") || path.starts_with("/api/v1/admin")
+}
+
 /// Extract API key from request headers
 pub(crate) fn extract_api_key(request: &Request) -> Option<String> {
     // Try X-API-Key header
@@ -718,6 +779,18 @@ fn unauthorized_response(message: &str) -> Response {
         .into_response()
 }
 
+/// Create forbidden response
+fn forbidden_response(message: &str) -> Response {
+    (
+        StatusCode::FORBIDDEN,
+        axum::Json(serde_json::json!({
+            "error": "Forbidden",
+            "message": message,
+        })),
+    )
+        .into_response()
+}
+
 /// Create rate limited response with Retry-After header
 fn rate_limited_response(retry_after: Duration) -> Response {
     let retry_after_secs = retry_after.as_secs().max(1); // Minimum 1 second
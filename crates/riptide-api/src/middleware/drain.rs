@@ -0,0 +1,41 @@
+//! Request admission while draining
+//!
+//! Counts every request as in flight so a drain can wait for it, and rejects
+//! new requests with `503 Service Unavailable` once a drain has started. The
+//! drain endpoint itself stays reachable so progress can be followed.
+
+use axum::{
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tracing::debug;
+
+use crate::context::ApplicationContext;
+use crate::drain::DRAIN_PATH;
+use crate::errors::ApiError;
+
+/// Reject new requests while draining and track in-flight ones otherwise
+pub async fn drain_middleware(
+    State(state): State<ApplicationContext>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.uri().path() == DRAIN_PATH {
+        return next.run(request).await;
+    }
+
+    let Some(_in_flight) = state.drain.try_enter() else {
+        debug!(path = %request.uri().path(), "Rejecting request while draining");
+        let mut response =
+            ApiError::service_unavailable("Server is draining, retry on another instance")
+                .into_response();
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, state.drain.retry_after_secs().into());
+        return response;
+    };
+
+    next.run(request).await
+}
@@ -42,6 +42,7 @@ use serde_json::json;
 use std::collections::HashSet;
 
 use crate::errors::ApiError;
+use riptide_types::ErrorCode;
 
 /// Middleware to validate request payloads and methods
 ///
@@ -148,6 +149,8 @@ pub(crate) fn get_allowed_methods(path: &str) -> HashSet<&'static str> {
         || path.starts_with("/api/v1/crawl")
         || path.starts_with("/extract")
         || path.starts_with("/api/v1/extract")
+        || path.starts_with("/api/v1/ingest")
+        || path.starts_with("/api/v1/capture")
         || path.starts_with("/deepsearch")
         || path.starts_with("/render")
         || path.starts_with("/api/v1/render")
@@ -441,6 +444,7 @@ fn validate_payload_size(headers: &axum::http::HeaderMap) -> Result<(), Response
                     let body = Json(json!({
                         "error": {
                             "type": "payload_too_large",
+                            "code": ErrorCode::PayloadTooLarge,
                             "message": error.to_string(),
                             "retryable": false,
                             "status": 413,
@@ -463,6 +467,7 @@ fn bad_request_response(error_type: &str, message: &str) -> Response {
     let body = Json(json!({
         "error": {
             "type": error_type,
+            "code": ErrorCode::InvalidRequest,
             "message": message,
             "retryable": false,
             "status": 400
@@ -488,6 +493,7 @@ fn method_not_allowed_response(allowed_methods: &HashSet<&str>) -> Response {
     let body = Json(json!({
         "error": {
             "type": "method_not_allowed",
+            "code": ErrorCode::InvalidRequest,
             "message": error.to_string(),
             "retryable": false,
             "status": 405,
@@ -513,6 +519,7 @@ fn unsupported_media_type_response(content_type: &str) -> Response {
     let body = Json(json!({
         "error": {
             "type": "unsupported_media_type",
+            "code": ErrorCode::UnsupportedMediaType,
             "message": error.to_string(),
             "retryable": false,
             "status": 415,
@@ -556,6 +563,7 @@ pub fn handle_json_rejection(rejection: axum::extract::rejection::JsonRejection)
     let body = Json(json!({
         "error": {
             "type": "invalid_request_body",
+            "code": ErrorCode::InvalidRequest,
             "message": message,
             "retryable": false,
             "status": status.as_u16()
@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+use crate::errors::ApiError;
 use crate::state::DependencyHealth;
 #[cfg(feature = "spider")]
 use riptide_spider::{CrawlState, PerformanceMetrics};
@@ -45,6 +46,94 @@ pub struct CrawlBody {
     pub options: Option<CrawlOptions>,
 }
 
+/// Request body for pushing externally fetched content through the pipeline
+///
+/// Exactly one of `content` and `content_base64` carries the response body.
+#[derive(Deserialize, Debug, Clone)]
+pub struct IngestBody {
+    /// URL the content was fetched from
+    pub url: String,
+
+    /// Response body as text (HTML, XML, JSON)
+    pub content: Option<String>,
+
+    /// Response body as base64, for binary content such as PDFs
+    pub content_base64: Option<String>,
+
+    /// Content-Type of the fetched response, overriding `headers`
+    pub content_type: Option<String>,
+
+    /// HTTP status of the fetched response
+    #[serde(default = "default_ingest_status")]
+    pub status: u16,
+
+    /// Response headers of the fetched response
+    #[serde(default)]
+    pub headers: std::collections::HashMap<String, String>,
+
+    /// When the content was fetched (defaults to now)
+    pub fetched_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// Crawl options, as for `/crawl` (skip extraction, translation, ...);
+    /// the cache is always bypassed
+    pub options: Option<CrawlOptions>,
+}
+
+fn default_ingest_status() -> u16 {
+    200
+}
+
+/// Request body for diffing two versions of a page
+///
+/// Either `old` and `new` carry extracted documents, as returned by
+/// `/crawl`, or `old_html` and `new_html` carry HTML snapshots of `url`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct DiffBody {
+    /// Older extracted document
+    pub old: Option<ExtractedDoc>,
+
+    /// Newer extracted document
+    pub new: Option<ExtractedDoc>,
+
+    /// URL of the HTML snapshots, for resolving links
+    pub url: Option<String>,
+
+    /// Older HTML snapshot
+    pub old_html: Option<String>,
+
+    /// Newer HTML snapshot
+    pub new_html: Option<String>,
+}
+
+/// Query parameters for the last stored change of a URL
+#[derive(Deserialize, Debug, Clone)]
+pub struct DiffQuery {
+    pub url: String,
+}
+
+/// Request body for a page capture pushed by the browser extension
+#[derive(Deserialize, Debug, Clone)]
+pub struct CaptureBody {
+    /// The captured page
+    pub snapshot: riptide_types::DomSnapshot,
+
+    /// Crawl options, as for `/crawl`; the cache is always bypassed
+    pub options: Option<CrawlOptions>,
+}
+
+/// Extraction result for a captured page
+#[derive(Serialize, Debug, Clone)]
+pub struct CaptureResponse {
+    #[serde(flatten)]
+    pub result: CrawlResult,
+
+    /// Number of DOM nodes in the capture
+    pub node_count: usize,
+
+    /// Resources the page loaded, with absolute URLs
+    pub resources: Vec<riptide_types::SnapshotResource>,
+}
+
 /// Individual crawl result for a single URL
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CrawlResult {
@@ -57,6 +146,10 @@ pub struct CrawlResult {
     /// Whether this result was served from cache
     pub from_cache: bool,
 
+    /// How the cache was used (miss, hit, or revalidated with the origin)
+    #[serde(default)]
+    pub cache_status: riptide_types::CacheStatus,
+
     /// Gate decision made for this URL (raw, probes_first, headless, cached)
     pub gate_decision: String,
 
@@ -74,6 +167,14 @@ pub struct CrawlResult {
 
     /// Cache key used for this URL
     pub cache_key: String,
+
+    /// Stored artifacts (screenshots, thumbnails) with durable URLs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<riptide_types::pipeline::PipelineArtifact>,
+
+    /// Per-phase timing breakdown from the pipeline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<riptide_types::PhaseTimings>,
 }
 
 /// Error information for failed operations
@@ -82,11 +183,90 @@ pub struct ErrorInfo {
     /// Error type identifier
     pub error_type: String,
 
+    /// Machine-readable error code
+    pub code: riptide_types::ErrorCode,
+
     /// Human-readable error message
     pub message: String,
 
     /// Whether this error is retryable
     pub retryable: bool,
+
+    /// Seconds to wait before retrying, when the server knows
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+}
+
+impl From<&ApiError> for ErrorInfo {
+    fn from(error: &ApiError) -> Self {
+        Self {
+            error_type: error.error_type().to_string(),
+            code: error.code(),
+            message: error.to_string(),
+            retryable: error.is_retryable(),
+            retry_after_secs: match error {
+                ApiError::RateLimitExceeded { retry_after } => Some(*retry_after),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Entry of a crawl's failure manifest
+///
+/// The manifest lists every URL of a batch that failed; posting it back to
+/// `/crawl/retry` re-crawls the retryable entries.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CrawlFailure {
+    /// URL that failed
+    pub url: String,
+
+    /// Machine-readable error code
+    pub code: riptide_types::ErrorCode,
+
+    /// Whether retrying this URL may succeed
+    pub retryable: bool,
+
+    /// Seconds to wait before retrying, when the server knows
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+}
+
+impl CrawlFailure {
+    /// Build the manifest entry for a failed result
+    pub fn from_result(result: &CrawlResult) -> Option<Self> {
+        result.error.as_ref().map(|error| Self {
+            url: result.url.clone(),
+            code: error.code,
+            retryable: error.retryable,
+            retry_after_secs: error.retry_after_secs,
+        })
+    }
+}
+
+/// Request body for re-crawling the failed URLs of an earlier batch
+#[derive(Deserialize, Debug, Clone)]
+pub struct RetryFailedBody {
+    /// Failure manifest returned by the earlier crawl
+    pub failures: Vec<CrawlFailure>,
+
+    /// Optional crawl configuration options for the retry
+    pub options: Option<CrawlOptions>,
+}
+
+impl RetryFailedBody {
+    /// Distinct retryable URLs of the manifest, in manifest order
+    ///
+    /// Non-retryable entries are dropped, since crawling them again would
+    /// fail the same way.
+    pub fn retryable_urls(&self) -> Vec<String> {
+        let mut seen = std::collections::HashSet::new();
+        self.failures
+            .iter()
+            .filter(|f| f.retryable && seen.insert(f.url.as_str()))
+            .map(|f| f.url.clone())
+            .collect()
+    }
 }
 
 /// Response for batch crawl operations
@@ -101,14 +281,57 @@ pub struct CrawlResponse {
     /// Number of failed extractions
     pub failed: usize,
 
+    /// Number of URLs skipped because their content did not change since `changed_since`
+    #[serde(default)]
+    pub skipped: usize,
+
     /// Number of results served from cache
     pub from_cache: usize,
 
     /// Individual results for each URL
     pub results: Vec<CrawlResult>,
 
+    /// Failure manifest: one entry per failed URL, accepted by `/crawl/retry`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<CrawlFailure>,
+
     /// Overall statistics for this batch
     pub statistics: CrawlStatistics,
+
+    /// WARC archive files written for this batch, when `archive_warc` was requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warc_files: Option<Vec<String>>,
+
+    /// Compliance report for spider crawls (robots.txt, crawl delays, rate limits)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compliance: Option<riptide_types::ComplianceReport>,
+
+    /// Id of this crawl, usable as `changed_since` in a later differential crawl
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crawl_id: Option<String>,
+
+    /// Resources used against the crawl's `budget`, when one was set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<crate::facades::CrawlBudgetReport>,
+}
+
+/// Response for a crawl manifest
+///
+/// A manifest without a schedule is crawled at once and carries `crawl`;
+/// a scheduled one carries the id of its scheduled job.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ManifestRunResponse {
+    /// Name of the manifest
+    pub manifest: String,
+
+    /// Validation warnings, such as seeds excluded by filters
+    pub warnings: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crawl: Option<CrawlResponse>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled_job_id: Option<uuid::Uuid>,
 }
 
 /// Statistics for crawl operations
@@ -229,6 +452,10 @@ pub struct HealthResponse {
 
     /// System metrics
     pub metrics: Option<SystemMetrics>,
+
+    /// Region label of the instance that served the check
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
 }
 
 /// Health status of individual dependencies
@@ -358,6 +585,10 @@ pub struct SpiderCrawlBody {
 
     /// Whether to follow redirects
     pub follow_redirects: Option<bool>,
+
+    /// Browser session whose cookies authenticate the crawl (optional)
+    #[serde(default)]
+    pub session_id: Option<String>,
 }
 
 /// Response for spider crawl operations with statistics
@@ -453,11 +684,20 @@ pub struct SpiderStatusResponse {
     /// Adaptive stop statistics
     #[cfg(feature = "spider")]
     pub adaptive_stop_stats: Option<riptide_spider::adaptive_stop::AdaptiveStopStats>,
+
+    /// Run state of the crawl and its recent transitions
+    #[cfg(feature = "spider")]
+    pub control: riptide_spider::ControlSnapshot,
 }
 
 /// Request body for spider control operations
 #[derive(Deserialize, Debug, Clone)]
 pub struct SpiderControlRequest {
-    /// Action to perform: "start", "stop", "reset"
+    /// Action to perform: "pause", "resume", "stop", "cancel", "reset"
     pub action: String,
+
+    /// Crawl the action is meant for, as reported by `/spider/status`;
+    /// refused when another crawl has started since
+    #[serde(default)]
+    pub crawl_id: Option<String>,
 }
@@ -1,14 +1,30 @@
+use crate::change_tracking::ChangeTracker;
 use crate::context::ApplicationContext;
 use crate::errors::{ApiError, ApiResult};
+use crate::experiments::ExperimentOutcome;
+use crate::facades::crawl_budget::CrawlBudgetEnforcer;
+use crate::media_pipeline::{MediaPipeline, MediaPipelineConfig};
 use async_trait::async_trait;
 use riptide_events::{BaseEvent, EventSeverity};
+use riptide_extraction::{BoilerplateTweaks, NativeExtractor, ParserConfig, UnifiedExtractor};
+use riptide_intelligence::domain_profiling::ProfileManager;
 #[cfg(feature = "llm")]
 use riptide_intelligence::smart_retry::{RetryConfig, SmartRetry, SmartRetryStrategy};
 use riptide_pdf::{self as pdf, utils as pdf_utils};
 use riptide_reliability::gate::{decide, score, Decision, GateFeatures};
-use riptide_types::config::CrawlOptions;
-use riptide_types::ports::HttpResponse;
-use riptide_types::{ExtractedDoc, RenderMode};
+use riptide_types::config::{CrawlOptions, EmbeddedMarkup};
+use riptide_types::ports::{
+    ArchivedExchange, ContentExtractor, ExperimentArm, ExperimentAssignment, HttpRequest,
+    HttpResponse, WebArchive,
+};
+use riptide_types::usage::UsageScope;
+use riptide_types::{
+    ContentFingerprint, ExtractedDoc, ExtractionProvenance, RenderMode, UrlRewrite,
+};
+use riptide_workers::{store_raw_html_snapshot, RawHtmlSnapshot, SnapshotResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::time::timeout;
 use tracing::{debug, error, info, warn};
@@ -54,6 +70,20 @@ fn convert_extracted_content(content: riptide_types::ExtractedContent, url: &str
         markdown: None,
         media: Vec::new(),
         parser_metadata: None, // ExtractedContent doesn't have parser metadata
+        provenance: None,
+        translation: None,
+        structured_data: Vec::new(),
+        annotations: None,
+        feed: None,
+        feed_links: Vec::new(),
+        classification: None,
+        clean_html: None,
+        media_assets: Vec::new(),
+        embedded_media: Vec::new(),
+        images: Vec::new(),
+        references: Vec::new(),
+        language_spans: Vec::new(),
+        quality_report: None,
         language: None,
         reading_time: None,
         word_count: Some(crate::utils::safe_conversions::word_count_to_u32(
@@ -66,11 +96,72 @@ fn convert_extracted_content(content: riptide_types::ExtractedContent, url: &str
     }
 }
 
+/// Boilerplate classifier tweaks from the domain profile of `url`'s site
+///
+/// The profile is read off the async runtime; unprofiled sites and
+/// profiles without tweaks give `None`.
+async fn domain_boilerplate(url: &str) -> Option<BoilerplateTweaks> {
+    let host = Url::parse(url).ok()?.host_str()?.to_ascii_lowercase();
+    tokio::task::spawn_blocking(move || {
+        let bare = host.strip_prefix("www.").unwrap_or(&host);
+        ProfileManager::load(&host)
+            .or_else(|_| ProfileManager::load(bare))
+            .ok()?
+            .boilerplate
+    })
+    .await
+    .ok()
+    .flatten()
+}
+
 // Re-export public types from riptide-types::pipeline to maintain API compatibility
 pub use riptide_types::pipeline::{
-    GateDecisionStats, PipelineResult, PipelineRetryConfig, PipelineStats,
+    CacheStatus, GateDecisionStats, PhaseTimings, PipelineResult, PipelineRetryConfig,
+    PipelineStats, GATE_DECISION_UNCHANGED,
 };
 
+/// Cache entry of an extracted document
+///
+/// Keeps the content hash of the response the document was extracted from.
+/// While the shared fingerprint of the URL still has that hash, an expired
+/// entry can be revalidated with the fingerprint's ETag or Last-Modified
+/// instead of being fetched and extracted again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedDocument {
+    document: ExtractedDoc,
+    /// Set only when the source response carried validators
+    #[serde(default)]
+    content_hash: Option<String>,
+    stored_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl CachedDocument {
+    /// Whether the source response carried an ETag or Last-Modified to
+    /// revalidate with
+    fn is_revalidatable(&self) -> bool {
+        self.content_hash.is_some()
+    }
+
+    /// `fingerprint`, when it still describes the entry's source response
+    fn validators<'a>(
+        &self,
+        fingerprint: Option<&'a ContentFingerprint>,
+    ) -> Option<&'a ContentFingerprint> {
+        fingerprint.filter(|fp| {
+            self.content_hash.as_deref() == Some(fp.content_hash.as_str())
+                && (fp.etag.is_some() || fp.last_modified.is_some())
+        })
+    }
+}
+
+/// Outcome of a cache lookup
+enum CacheLookup {
+    /// Within `cache_ttl`, served as is
+    Fresh(ExtractedDoc),
+    /// Past `cache_ttl`, served again only if the origin answers `304`
+    Stale(CachedDocument),
+}
+
 // Internal retry config extension for llm feature
 #[cfg(feature = "llm")]
 #[derive(Debug, Clone)]
@@ -95,6 +186,19 @@ impl From<PipelineRetryConfig> for InternalRetryConfig {
     }
 }
 
+/// A response ready for gate analysis and extraction
+#[cfg(feature = "fetch")]
+struct FetchedContent {
+    response: HttpResponse,
+    content_bytes: Vec<u8>,
+    content_type: Option<String>,
+    fetched_at: chrono::DateTime<chrono::Utc>,
+    /// Fingerprint recorded for differential crawls
+    fingerprint: Option<ContentFingerprint>,
+    /// Rewrite that redirected the fetch to another URL
+    url_rewrite: Option<UrlRewrite>,
+}
+
 /// Core pipeline orchestrator for the fetch -> gate -> extract workflow.
 ///
 /// This orchestrator handles the complete crawling pipeline:
@@ -106,6 +210,7 @@ impl From<PipelineRetryConfig> for InternalRetryConfig {
 ///
 /// The pipeline is designed for high throughput and includes comprehensive
 /// error handling, timeout management, and performance monitoring.
+#[derive(Clone)]
 pub struct PipelineOrchestrator {
     state: ApplicationContext,
     options: CrawlOptions,
@@ -113,6 +218,14 @@ pub struct PipelineOrchestrator {
     retry_config: InternalRetryConfig,
     #[cfg(not(feature = "llm"))]
     retry_config: PipelineRetryConfig,
+    /// Web archive receiving every fetched exchange, when `archive_warc` is requested
+    archive: Option<Arc<dyn WebArchive>>,
+    /// Stored content fingerprints for differential crawls
+    change_tracker: ChangeTracker,
+    /// Only return documents changed after this instant
+    changed_since: Option<chrono::DateTime<chrono::Utc>>,
+    /// Resource budget shared by every URL of the batch
+    budget: Option<Arc<CrawlBudgetEnforcer>>,
 }
 
 impl PipelineOrchestrator {
@@ -128,12 +241,57 @@ impl PipelineOrchestrator {
         retry_config: PipelineRetryConfig,
     ) -> Self {
         Self {
+            change_tracker: ChangeTracker::from_context(&state),
             state,
             options,
             #[cfg(feature = "llm")]
             retry_config: retry_config.into(),
             #[cfg(not(feature = "llm"))]
             retry_config,
+            archive: None,
+            changed_since: None,
+            budget: None,
+        }
+    }
+
+    /// Record every fetched response to the given web archive.
+    ///
+    /// Archiving bypasses the cache read so that each crawled page is actually
+    /// fetched and captured.
+    pub fn with_archive(mut self, archive: Option<Arc<dyn WebArchive>>) -> Self {
+        self.archive = archive;
+        self
+    }
+
+    /// Only return documents whose content changed after `since`.
+    ///
+    /// URLs unchanged at their last check are revalidated with a conditional
+    /// request; those still unchanged produce a result with the
+    /// [`GATE_DECISION_UNCHANGED`] gate decision and an empty document.
+    pub fn with_changed_since(mut self, since: Option<chrono::DateTime<chrono::Utc>>) -> Self {
+        self.changed_since = since;
+        self
+    }
+
+    /// Run every URL of a batch within `budget`.
+    ///
+    /// URLs not started once a limit is reached fail with
+    /// `ApiError::BudgetExceeded`.
+    pub fn with_budget(mut self, budget: Option<Arc<CrawlBudgetEnforcer>>) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Write a fetched response to the configured web archive, if any.
+    ///
+    /// Archive failures are logged and never fail the crawl.
+    pub(crate) async fn archive_response(&self, url: &str, response: &HttpResponse) {
+        let Some(archive) = &self.archive else {
+            return;
+        };
+        let exchange = ArchivedExchange::new(HttpRequest::new("GET", url), response.clone());
+        if let Err(e) = archive.write_exchange(&exchange).await {
+            warn!(url = %url, error = %e, "Failed to write WARC records");
         }
     }
 
@@ -229,10 +387,30 @@ impl PipelineOrchestrator {
     /// - Timeouts
     #[cfg(feature = "fetch")]
     pub async fn execute_single(&self, url: &str) -> ApiResult<PipelineResult> {
+        // Wire bytes and LLM tokens of the run, for metering and budgets
+        let usage = Arc::new(UsageScope::default());
+        let mut result = usage.clone().scope(self.run_single(url)).await?;
+        let recorded = |amount: u64| (amount > 0).then_some(amount);
+        result.timings.fetch_bytes = recorded(usage.bandwidth_bytes());
+        result.timings.llm_tokens = recorded(usage.llm_tokens());
+        Ok(result)
+    }
+
+    #[cfg(feature = "fetch")]
+    async fn run_single(&self, url: &str) -> ApiResult<PipelineResult> {
         let start_time = Instant::now();
-        let cache_key = self.generate_cache_key(url);
+        let experiment = self.state.experiments.assign(url).await;
+        let mut cache_key = self.generate_cache_key(url);
+        // Treatment results must not be served to control traffic, or vice versa
+        if let Some(assignment) = experiment
+            .as_ref()
+            .filter(|a| a.arm == ExperimentArm::Treatment)
+        {
+            cache_key.push_str(&format!(":exp:{}", assignment.experiment_id));
+        }
 
         info!(url = %url, cache_key = %cache_key, "Starting pipeline execution");
+        let mut timings = PhaseTimings::default();
 
         // Emit pipeline start event
         let mut start_event = BaseEvent::new(
@@ -242,13 +420,22 @@ impl PipelineOrchestrator {
         );
         start_event.add_metadata("url", url);
         start_event.add_metadata("cache_key", &cache_key);
-        if let Err(e) = self.state.event_bus.emit(start_event).await {
+        if let Err(e) = self.emit_timed(start_event, &mut timings).await {
             warn!(error = %e, "Failed to emit pipeline start event");
         }
 
         // Step 1: Check cache first
-        let cached_result = self.check_cache(&cache_key).await;
-        if let Ok(Some(cached)) = cached_result {
+        let mut stale = None;
+        let cached = match self.check_cache(&cache_key).await {
+            Ok(Some(CacheLookup::Fresh(cached))) => Some(cached),
+            Ok(Some(CacheLookup::Stale(entry))) => {
+                debug!(url = %url, "Cached document expired, revalidating");
+                stale = Some(entry);
+                None
+            }
+            _ => None,
+        };
+        if let Some(cached) = cached {
             info!(url = %url, "Cache hit, returning cached result");
 
             // Emit cache hit event
@@ -259,28 +446,257 @@ impl PipelineOrchestrator {
             );
             cache_event.add_metadata("url", url);
             cache_event.add_metadata("cache_key", &cache_key);
-            if let Err(e) = self.state.event_bus.emit(cache_event).await {
+            if let Err(e) = self.emit_timed(cache_event, &mut timings).await {
                 warn!(error = %e, "Failed to emit cache hit event");
             }
+            if let Some(assignment) = &experiment {
+                self.state.experiments.record_cache_hit(&assignment.tag());
+            }
 
-            return Ok(PipelineResult {
-                document: cached,
-                from_cache: true,
-                gate_decision: "cached".to_string(),
-                quality_score: 1.0, // Cached content is assumed to be good
-                processing_time_ms: start_time.elapsed().as_millis() as u64,
-                cache_key,
-                http_status: 200, // Assume success for cached content
-            });
+            return Ok(self.finish_execution(
+                url,
+                PipelineResult {
+                    document: cached,
+                    from_cache: true,
+                    cache_status: CacheStatus::Hit,
+                    gate_decision: "cached".to_string(),
+                    quality_score: 1.0, // Cached content is assumed to be good
+                    processing_time_ms: start_time.elapsed().as_millis() as u64,
+                    cache_key,
+                    http_status: 200, // Assume success for cached content
+                    artifacts: Vec::new(),
+                    timings,
+                },
+            ));
         }
 
-        // Step 2: Fetch content
+        // Step 2: Fetch content, from the URL the rewrite rules point to
         debug!(url = %url, "Cache miss, fetching content");
+        let url_rewrite = self.state.url_rewriter.rewrite(url);
+        let fetch_url = match &url_rewrite {
+            Some(rewrite) => {
+                info!(
+                    url = %url,
+                    fetched_url = %rewrite.fetched_url,
+                    rule = %rewrite.rule,
+                    "Rewrote fetch URL"
+                );
+                rewrite.fetched_url.as_str()
+            }
+            None => url,
+        };
+        if let Some(restriction) = &self.state.geo_restriction {
+            restriction
+                .check(fetch_url)
+                .await
+                .map_err(|e| ApiError::PolicyDenied {
+                    url: url.to_string(),
+                    rule: "geo_restriction".to_string(),
+                    reason: e.to_string(),
+                })?;
+        }
         let fetch_start = Instant::now();
-        let (response, content_bytes, content_type) = self.fetch_content_with_type(url).await?;
+        let fetched_at = chrono::Utc::now();
+        let previous = self.change_tracker.fingerprint(url).await;
+        let revalidate = match (self.changed_since, &previous) {
+            (Some(since), Some(fingerprint)) if !fingerprint.changed_since(since) => {
+                Some((since, fingerprint))
+            }
+            _ => None,
+        };
+        let validators = revalidate
+            .map(|(_, fingerprint)| fingerprint)
+            .or_else(|| stale.as_ref()?.validators(previous.as_ref()));
+        let (response, content_bytes, content_type) = match validators {
+            Some(fingerprint) => self.fetch_conditional(fetch_url, fingerprint).await?,
+            None => self.fetch_content_with_type(fetch_url).await?,
+        };
+        self.archive_response(fetch_url, &response).await;
+        let fingerprint = self
+            .track_content(url, previous.as_ref(), &response, fetched_at)
+            .await;
+        if let Some(entry) = stale.filter(|_| response.status == 304) {
+            info!(url = %url, "Cached document revalidated, returning cached result");
+            timings.fetch_ms = Some(fetch_start.elapsed().as_millis() as u64);
+
+            let cache_start = Instant::now();
+            if let Err(e) = self
+                .store_in_cache(&cache_key, &entry.document, fingerprint.as_ref())
+                .await
+            {
+                warn!(error = %e, "Failed to refresh revalidated cache entry");
+            }
+            timings.cache_write_ms = Some(cache_start.elapsed().as_millis() as u64);
+
+            let mut cache_event = BaseEvent::new(
+                "pipeline.cache.revalidated",
+                "pipeline_orchestrator",
+                EventSeverity::Info,
+            );
+            cache_event.add_metadata("url", url);
+            cache_event.add_metadata("cache_key", &cache_key);
+            if let Err(e) = self.emit_timed(cache_event, &mut timings).await {
+                warn!(error = %e, "Failed to emit cache revalidated event");
+            }
+
+            return Ok(self.finish_execution(
+                url,
+                PipelineResult {
+                    document: entry.document,
+                    from_cache: true,
+                    cache_status: CacheStatus::Revalidated,
+                    gate_decision: "cached".to_string(),
+                    quality_score: 1.0,
+                    processing_time_ms: start_time.elapsed().as_millis() as u64,
+                    cache_key,
+                    http_status: 200, // The origin confirmed the cached content
+                    artifacts: Vec::new(),
+                    timings,
+                },
+            ));
+        }
+        if let Some((since, _)) = revalidate {
+            if fingerprint
+                .as_ref()
+                .is_some_and(|fp| !fp.changed_since(since))
+            {
+                info!(url = %url, "Content unchanged, skipping");
+                timings.fetch_ms = Some(fetch_start.elapsed().as_millis() as u64);
+                return Ok(self.finish_execution(
+                    url,
+                    PipelineResult {
+                        document: ExtractedDoc {
+                            url: url.to_string(),
+                            ..Default::default()
+                        },
+                        from_cache: false,
+                        cache_status: CacheStatus::Miss,
+                        gate_decision: GATE_DECISION_UNCHANGED.to_string(),
+                        quality_score: 0.0,
+                        processing_time_ms: start_time.elapsed().as_millis() as u64,
+                        cache_key,
+                        http_status: response.status,
+                        artifacts: Vec::new(),
+                        timings,
+                    },
+                ));
+            }
+        }
+        let fetch_duration = fetch_start.elapsed();
+        timings.fetch_ms = Some(fetch_duration.as_millis() as u64);
+        self.state
+            .record_phase_timing("fetch", fetch_duration.as_secs_f64());
+
+        let result = self
+            .process_fetched(
+                url,
+                FetchedContent {
+                    response,
+                    content_bytes,
+                    content_type,
+                    fetched_at,
+                    fingerprint,
+                    url_rewrite,
+                },
+                experiment,
+                cache_key,
+                start_time,
+                timings,
+            )
+            .await?;
+        self.track_extraction(url, &result).await;
+        Ok(result)
+    }
+
+    /// Run content fetched outside RipTide through the pipeline.
+    ///
+    /// The response is archived (with [`Self::with_archive`]), gated and
+    /// extracted. Pushed content is not what RipTide saw at the origin, so it
+    /// never updates the change tracker's fingerprints or diffs; callers set
+    /// `cache_mode` to `bypass` so it is not cached either.
+    #[cfg(feature = "fetch")]
+    pub async fn execute_ingested(
+        &self,
+        url: &str,
+        response: HttpResponse,
+        fetched_at: chrono::DateTime<chrono::Utc>,
+    ) -> ApiResult<PipelineResult> {
+        let start_time = Instant::now();
+        let experiment = self.state.experiments.assign(url).await;
+        let mut cache_key = self.generate_cache_key(url);
+        if let Some(assignment) = experiment
+            .as_ref()
+            .filter(|a| a.arm == ExperimentArm::Treatment)
+        {
+            cache_key.push_str(&format!(":exp:{}", assignment.experiment_id));
+        }
+
+        info!(
+            url = %url,
+            cache_key = %cache_key,
+            bytes = response.body.len(),
+            "Ingesting pushed content"
+        );
+        let mut timings = PhaseTimings::default();
+
+        let mut start_event = BaseEvent::new(
+            "pipeline.ingest.started",
+            "pipeline_orchestrator",
+            EventSeverity::Info,
+        );
+        start_event.add_metadata("url", url);
+        start_event.add_metadata("cache_key", &cache_key);
+        if let Err(e) = self.emit_timed(start_event, &mut timings).await {
+            warn!(error = %e, "Failed to emit ingest start event");
+        }
+
+        self.archive_response(url, &response).await;
+        let content_type = response.header("content-type").map(|s| s.to_string());
+        let content_bytes = response.body.clone();
+
+        self.process_fetched(
+            url,
+            FetchedContent {
+                response,
+                content_bytes,
+                content_type,
+                fetched_at,
+                fingerprint: None,
+                url_rewrite: None,
+            },
+            experiment,
+            cache_key,
+            start_time,
+            timings,
+        )
+        .await
+    }
+
+    /// Gate, extract and cache a fetched response.
+    ///
+    /// Shared by crawled and ingested content; PDFs go through the PDF
+    /// pipeline, everything else is treated as HTML.
+    #[cfg(feature = "fetch")]
+    async fn process_fetched(
+        &self,
+        url: &str,
+        fetched: FetchedContent,
+        experiment: Option<ExperimentAssignment>,
+        cache_key: String,
+        start_time: Instant,
+        mut timings: PhaseTimings,
+    ) -> ApiResult<PipelineResult> {
+        let FetchedContent {
+            response,
+            content_bytes,
+            content_type,
+            fetched_at,
+            fingerprint,
+            url_rewrite,
+        } = fetched;
+        let fetched_url = url_rewrite.as_ref().map_or(url, |r| r.fetched_url.as_str());
+        let server_location = self.state.locate_server(fetched_url).await;
         let http_status = response.status;
-        let fetch_duration = fetch_start.elapsed().as_secs_f64();
-        self.state.record_phase_timing("fetch", fetch_duration);
 
         // Step 3: Check if this is PDF content
         if pdf_utils::is_pdf_content(content_type.as_deref(), &content_bytes)
@@ -296,36 +712,119 @@ impl PipelineOrchestrator {
             );
             pdf_event.add_metadata("url", url);
             pdf_event.add_metadata("content_size", &content_bytes.len().to_string());
-            if let Err(e) = self.state.event_bus.emit(pdf_event).await {
+            if let Err(e) = self.emit_timed(pdf_event, &mut timings).await {
                 warn!(error = %e, "Failed to emit PDF processing event");
             }
 
             let pdf_start = Instant::now();
-            let document = self.process_pdf_content(&content_bytes, url).await?;
-            let pdf_duration = pdf_start.elapsed().as_secs_f64();
+            let mut document = self.process_pdf_content(&content_bytes, url).await?;
+            document.provenance = Some(
+                self.provenance("pdf", "pdf", fetched_at, &response.headers)
+                    .with_server_location(server_location)
+                    .with_url_rewrite(url_rewrite),
+            );
+            let pdf_duration = pdf_start.elapsed();
+            timings.extract_ms = Some(pdf_duration.as_millis() as u64);
+            self.annotate(&mut document).await;
+            self.classify(&mut document).await;
+            self.download_media(&mut document).await;
+            self.translate(&mut document).await;
 
             // Record PDF processing metrics
             self.state.record_pdf_success(
                 (document.word_count.unwrap_or(0) / 250) as usize, // Estimate pages from word count
-                pdf_duration,
+                pdf_duration.as_secs_f64(),
             );
 
             // Cache the PDF result
-            if let Err(e) = self.store_in_cache(&cache_key, &document).await {
+            let cache_start = Instant::now();
+            if let Err(e) = self
+                .store_in_cache(&cache_key, &document, fingerprint.as_ref())
+                .await
+            {
                 warn!(error = %e, "Failed to cache PDF result, continuing anyway");
             }
+            timings.cache_write_ms = Some(cache_start.elapsed().as_millis() as u64);
+
+            let artifacts = if self.options.store_pdf == Some(true) {
+                match MediaPipeline::new(
+                    self.state.blob_storage.clone(),
+                    MediaPipelineConfig::default(),
+                )
+                .store_pdf(url, &content_bytes)
+                .await
+                {
+                    Ok(artifact) => vec![artifact],
+                    Err(e) => {
+                        warn!(url = %url, error = %e, "Failed to store PDF artifact");
+                        Vec::new()
+                    }
+                }
+            } else {
+                Vec::new()
+            };
+
+            let processing_time_ms = start_time.elapsed().as_millis() as u64;
+
+            return Ok(self.finish_execution(
+                url,
+                PipelineResult {
+                    document,
+                    from_cache: false,
+                    cache_status: CacheStatus::Miss,
+                    gate_decision: "pdf".to_string(),
+                    quality_score: 0.95, // PDFs typically have high quality
+                    processing_time_ms,
+                    cache_key,
+                    http_status,
+                    artifacts,
+                    timings,
+                },
+            ));
+        }
+
+        // Step 3b: Feeds become one document per entry, without the HTML gate
+        if riptide_extraction::detect_feed(content_type.as_deref(), &content_bytes).is_some()
+            || matches!(self.options.render_mode, RenderMode::Feed)
+        {
+            info!(url = %url, "Detected feed, parsing entries");
+
+            let feed_start = Instant::now();
+            let mut document =
+                riptide_extraction::parse_feed(&String::from_utf8_lossy(&content_bytes), url)
+                    .map_err(|e| ApiError::extraction(format!("Feed parsing failed: {}", e)))?;
+            document.provenance = Some(
+                self.provenance("feed", "feed", fetched_at, &response.headers)
+                    .with_server_location(server_location),
+            );
+            timings.extract_ms = Some(feed_start.elapsed().as_millis() as u64);
+
+            let cache_start = Instant::now();
+            if let Err(e) = self
+                .store_in_cache(&cache_key, &document, fingerprint.as_ref())
+                .await
+            {
+                warn!(error = %e, "Failed to cache feed result, continuing anyway");
+            }
+            timings.cache_write_ms = Some(cache_start.elapsed().as_millis() as u64);
 
             let processing_time_ms = start_time.elapsed().as_millis() as u64;
 
-            return Ok(PipelineResult {
-                document,
-                from_cache: false,
-                gate_decision: "pdf".to_string(),
-                quality_score: 0.95, // PDFs typically have high quality
-                processing_time_ms,
-                cache_key,
-                http_status,
-            });
+            return Ok(self.finish_execution(
+                url,
+                PipelineResult {
+                    document,
+                    from_cache: false,
+                    cache_status: CacheStatus::Miss,
+                    gate_decision: "feed".to_string(),
+                    quality_score: 1.0, // Feeds are structured, there is no boilerplate to judge
+                    processing_time_ms,
+                    cache_key,
+                    http_status,
+                    artifacts: Vec::new(),
+                    timings,
+                },
+            ));
         }
 
         // Convert bytes back to string for HTML processing
@@ -335,11 +834,27 @@ impl PipelineOrchestrator {
         let gate_start = Instant::now();
         let gate_features = self.analyze_content(&html_content, url).await?;
         let quality_score = score(&gate_features);
-        let decision = decide(
-            &gate_features,
-            self.state.config.gate_hi_threshold,
-            self.state.config.gate_lo_threshold,
-        );
+        let vertical = self.state.gate_verticals.vertical_for(url).await;
+        let thresholds = self.state.gate_verticals.thresholds(vertical);
+        // A forced headless render is the client's choice, not the gate's, so
+        // it is left out of the vertical's decision counts
+        let decision = if self.options.force_headless() {
+            Decision::Headless
+        } else {
+            let decision = decide(
+                &gate_features,
+                experiment
+                    .as_ref()
+                    .and_then(ExperimentAssignment::gate_hi_threshold)
+                    .unwrap_or(thresholds.hi),
+                experiment
+                    .as_ref()
+                    .and_then(ExperimentAssignment::gate_lo_threshold)
+                    .unwrap_or(thresholds.lo),
+            );
+            self.state.gate_verticals.record(vertical, decision);
+            decision
+        };
 
         let gate_decision_str = match decision {
             Decision::Raw => "raw",
@@ -349,6 +864,7 @@ impl PipelineOrchestrator {
         .to_string();
 
         let gate_duration = gate_start.elapsed();
+        timings.gate_ms = Some(gate_duration.as_millis() as u64);
         self.state
             .record_phase_timing("gate", gate_duration.as_secs_f64());
         self.state.record_gate_decision(&gate_decision_str);
@@ -361,6 +877,7 @@ impl PipelineOrchestrator {
             url = %url,
             decision = %gate_decision_str,
             score = %quality_score,
+            vertical = vertical.as_str(),
             "Gate analysis complete"
         );
 
@@ -373,13 +890,42 @@ impl PipelineOrchestrator {
         gate_event.add_metadata("url", url);
         gate_event.add_metadata("decision", &gate_decision_str);
         gate_event.add_metadata("quality_score", &quality_score.to_string());
-        if let Err(e) = self.state.event_bus.emit(gate_event).await {
+        gate_event.add_metadata("vertical", vertical.as_str());
+        if let Err(e) = self.emit_timed(gate_event, &mut timings).await {
             warn!(error = %e, "Failed to emit gate decision event");
         }
 
         // Step 5: Extract content based on gate decision or skip extraction
         let extract_start = Instant::now();
-        let document = if self.options.skip_extraction.unwrap_or(false) {
+        let skip_extraction = self.options.skip_extraction.unwrap_or(false);
+        let extractor = match self
+            .options
+            .pipeline_hints
+            .as_ref()
+            .and_then(|hints| hints.extraction_schema.as_deref())
+            .or_else(|| {
+                experiment
+                    .as_ref()
+                    .and_then(ExperimentAssignment::extractor)
+            })
+            .and_then(|name| self.state.experiments.extractor(name))
+        {
+            Some(extractor) => extractor,
+            None if skip_extraction => self.state.extractor.clone(),
+            // Sites with boilerplate tweaks in their profile get the native
+            // parser classifying blocks with them
+            None => match domain_boilerplate(url).await {
+                Some(tweaks) => Arc::new(UnifiedExtractor::Native(NativeExtractor::with_config(
+                    ParserConfig {
+                        min_quality_score: 0,
+                        boilerplate: Some(tweaks),
+                        ..ParserConfig::default()
+                    },
+                ))),
+                None => self.state.extractor.clone(),
+            },
+        };
+        let mut document = if skip_extraction {
             // Skip extraction and return raw HTML only
             info!(url = %url, "Skipping extraction, returning raw HTML");
             ExtractedDoc {
@@ -393,6 +939,20 @@ impl PipelineOrchestrator {
                 published_iso: None,
                 markdown: None,
                 parser_metadata: None,
+                provenance: None,
+                translation: None,
+                structured_data: Vec::new(),
+                annotations: None,
+                feed: None,
+                feed_links: Vec::new(),
+                classification: None,
+                clean_html: None,
+                media_assets: Vec::new(),
+                embedded_media: Vec::new(),
+                images: Vec::new(),
+                references: Vec::new(),
+                language_spans: Vec::new(),
+                quality_report: None,
                 media: Vec::new(),
                 language: None,
                 reading_time: None,
@@ -402,9 +962,38 @@ impl PipelineOrchestrator {
                 description: None,
             }
         } else {
-            self.extract_content(&html_content, url, decision).await?
+            let html = riptide_extraction::rewrite_embedded_markup(
+                &html_content,
+                self.options.embedded_markup.unwrap_or_default(),
+            );
+            match self
+                .extract_content(extractor.as_ref(), &html, url, decision)
+                .await
+            {
+                Ok(document) => document,
+                Err(e) => {
+                    if let Some(assignment) = &experiment {
+                        self.state.experiments.record_failure(&assignment.tag());
+                    }
+                    self.store_raw_html(url, http_status, &html_content, fetched_at, None, None)
+                        .await;
+                    return Err(e);
+                }
+            }
         };
         let extract_duration = extract_start.elapsed();
+        timings.extract_ms = Some(extract_duration.as_millis() as u64);
+        let strategy = if skip_extraction {
+            "raw"
+        } else {
+            extractor.extractor_type()
+        };
+        document.provenance = Some(
+            self.provenance(strategy, &gate_decision_str, fetched_at, &response.headers)
+                .with_server_location(server_location)
+                .with_experiment(experiment.as_ref().map(ExperimentAssignment::tag))
+                .with_url_rewrite(url_rewrite),
+        );
 
         // Record WASM extraction phase timing
         self.state
@@ -443,18 +1032,57 @@ impl PipelineOrchestrator {
             }
         });
 
+        if !skip_extraction {
+            self.annotate(&mut document).await;
+            self.classify(&mut document).await;
+            self.download_media(&mut document).await;
+            self.translate(&mut document).await;
+        }
+
+        // Reprocessing re-extracts the HTML alone, so it only replaces
+        // documents a plain extraction reproduces
+        let reprocessable = !skip_extraction
+            && experiment.is_none()
+            && self.translation_target().is_none()
+            && self.options.embedded_markup.unwrap_or_default() == EmbeddedMarkup::Text
+            && self
+                .options
+                .pipeline_hints
+                .as_ref()
+                .and_then(|hints| hints.extraction_schema.as_deref())
+                .is_none();
+        self.store_raw_html(
+            url,
+            http_status,
+            &html_content,
+            fetched_at,
+            Some(&document),
+            reprocessable.then_some(cache_key.as_str()),
+        )
+        .await;
+
         // Step 6: Cache the result
-        if let Err(e) = self.store_in_cache(&cache_key, &document).await {
+        let cache_start = Instant::now();
+        if let Err(e) = self
+            .store_in_cache(&cache_key, &document, fingerprint.as_ref())
+            .await
+        {
             warn!(error = %e, "Failed to cache result, continuing anyway");
         }
+        timings.cache_write_ms = Some(cache_start.elapsed().as_millis() as u64);
 
         let processing_time_ms = start_time.elapsed().as_millis() as u64;
 
-        info!(
-            url = %url,
-            processing_time_ms = %processing_time_ms,
-            "Pipeline execution complete"
-        );
+        if let Some(assignment) = experiment.as_ref().filter(|_| !skip_extraction) {
+            self.state.experiments.record(
+                &assignment.tag(),
+                ExperimentOutcome {
+                    quality: f64::from(document.quality_score.unwrap_or(0)) / 100.0,
+                    latency_ms: processing_time_ms,
+                    headless: matches!(decision, Decision::Headless),
+                },
+            );
+        }
 
         // Emit pipeline completion event
         let mut completion_event = BaseEvent::new(
@@ -465,21 +1093,72 @@ impl PipelineOrchestrator {
         completion_event.add_metadata("url", url);
         completion_event.add_metadata("gate_decision", &gate_decision_str);
         completion_event.add_metadata("quality_score", &quality_score.to_string());
+        if let Some(report) = &document.quality_report {
+            completion_event.add_metadata("weakest_quality_signals", &report.explain(3));
+        }
         completion_event.add_metadata("processing_time_ms", &processing_time_ms.to_string());
         completion_event.add_metadata("http_status", &http_status.to_string());
-        if let Err(e) = self.state.event_bus.emit(completion_event).await {
+        if let Some(assignment) = &experiment {
+            completion_event.add_metadata("experiment_id", &assignment.experiment_id);
+            completion_event.add_metadata("experiment_arm", assignment.arm.as_str());
+        }
+        if let Err(e) = self.emit_timed(completion_event, &mut timings).await {
             warn!(error = %e, "Failed to emit pipeline completion event");
         }
 
-        Ok(PipelineResult {
-            document,
-            from_cache: false,
-            gate_decision: gate_decision_str,
-            quality_score,
-            processing_time_ms,
-            cache_key,
-            http_status,
-        })
+        Ok(self.finish_execution(
+            url,
+            PipelineResult {
+                document,
+                from_cache: false,
+                cache_status: CacheStatus::Miss,
+                gate_decision: gate_decision_str,
+                quality_score,
+                processing_time_ms,
+                cache_key,
+                http_status,
+                artifacts: Vec::new(),
+                timings,
+            },
+        ))
+    }
+
+    /// Emit `event` on the event bus, adding the time spent to `timings`
+    async fn emit_timed(&self, event: BaseEvent, timings: &mut PhaseTimings) -> anyhow::Result<()> {
+        let emit_start = Instant::now();
+        let result = self.state.event_bus.emit(event).await;
+        timings.add_event_emit(emit_start.elapsed());
+        result
+    }
+
+    /// Log the phase breakdown of a completed execution and record the
+    /// phases not already tracked in metrics
+    fn finish_execution(&self, url: &str, result: PipelineResult) -> PipelineResult {
+        let timings = &result.timings;
+        if let Some(ms) = timings.cache_write_ms {
+            self.state
+                .record_phase_timing("cache_write", ms as f64 / 1000.0);
+        }
+        if let Some(ms) = timings.event_emit_ms {
+            self.state
+                .record_phase_timing("event_emit", ms as f64 / 1000.0);
+        }
+
+        info!(
+            url = %url,
+            gate_decision = %result.gate_decision,
+            from_cache = result.from_cache,
+            cache_status = ?result.cache_status,
+            processing_time_ms = result.processing_time_ms,
+            fetch_ms = ?timings.fetch_ms,
+            gate_ms = ?timings.gate_ms,
+            render_ms = ?timings.render_ms,
+            extract_ms = ?timings.extract_ms,
+            cache_write_ms = ?timings.cache_write_ms,
+            event_emit_ms = ?timings.event_emit_ms,
+            "Pipeline execution complete"
+        );
+        result
     }
 
     /// Execute the pipeline for multiple URLs concurrently.
@@ -494,7 +1173,8 @@ impl PipelineOrchestrator {
     /// # Returns
     ///
     /// A tuple containing:
-    /// - Vector of results (Some(result) for success, None for failure)
+    /// - Vector of per-URL results, in input order; a failed URL carries
+    ///   its error instead of failing the batch
     /// - Overall pipeline statistics
     ///
     /// # Performance
@@ -504,7 +1184,7 @@ impl PipelineOrchestrator {
     pub async fn execute_batch(
         &self,
         urls: &[String],
-    ) -> (Vec<Option<PipelineResult>>, PipelineStats) {
+    ) -> (Vec<ApiResult<PipelineResult>>, PipelineStats) {
         let start_time = Instant::now();
         let mut stats = PipelineStats {
             total_processed: urls.len(),
@@ -535,20 +1215,26 @@ impl PipelineOrchestrator {
                         Ok(permit) => permit,
                         Err(e) => {
                             error!(url = %url, index = index, error = %e, "Failed to acquire semaphore permit");
-                            return None;
+                            return Err(ApiError::internal(format!(
+                                "Failed to acquire concurrency permit: {}",
+                                e
+                            )));
                         }
                     };
 
-                    match pipeline.execute_single(&url).await {
-                        Ok(result) => {
+                    let result = match &pipeline.budget {
+                        Some(budget) => budget.run(pipeline.execute_single(&url)).await,
+                        None => pipeline.execute_single(&url).await,
+                    };
+                    match &result {
+                        Ok(_) => {
                             debug!(url = %url, index = index, "URL processed successfully");
-                            Some(result)
                         }
                         Err(e) => {
-                            error!(url = %url, index = index, error = %e, "URL processing failed");
-                            None
+                            error!(url = %url, index = index, error = %e, code = %e.code(), "URL processing failed");
                         }
                     }
+                    result
                 })
             })
             .collect();
@@ -562,7 +1248,7 @@ impl PipelineOrchestrator {
 
         for result in results {
             match result {
-                Ok(Some(pipeline_result)) => {
+                Ok(Ok(pipeline_result)) => {
                     stats.successful_extractions += 1;
                     total_time += pipeline_result.processing_time_ms;
 
@@ -577,11 +1263,18 @@ impl PipelineOrchestrator {
                         _ => {} // cached or unknown
                     }
 
-                    pipeline_results.push(Some(pipeline_result));
+                    pipeline_results.push(Ok(pipeline_result));
                 }
-                Ok(None) | Err(_) => {
+                Ok(Err(e)) => {
                     stats.failed_extractions += 1;
-                    pipeline_results.push(None);
+                    pipeline_results.push(Err(e));
+                }
+                Err(e) => {
+                    stats.failed_extractions += 1;
+                    pipeline_results.push(Err(ApiError::internal(format!(
+                        "URL processing task failed: {}",
+                        e
+                    ))));
                 }
             }
         }
@@ -615,7 +1308,7 @@ impl PipelineOrchestrator {
         // Clone url for closure
         let url_clone = url.to_string();
         let state_clone = self.state.clone();
-        let fetch_timeout = Duration::from_secs(15);
+        let fetch_timeout = self.state.api_config.get_timeout("fetch");
 
         // Wrapper to convert intelligence errors to API errors
         let fetch_operation = || async {
@@ -641,7 +1334,7 @@ impl PipelineOrchestrator {
         let retry = self.create_smart_retry(SmartRetryStrategy::Adaptive);
         retry.execute(fetch_operation).await.map_err(|e| match e {
             riptide_intelligence::IntelligenceError::Timeout { .. } => {
-                ApiError::timeout("content_fetch", format!("Timeout fetching {}", url))
+                ApiError::fetch_timeout("content_fetch", format!("Timeout fetching {}", url))
             }
             riptide_intelligence::IntelligenceError::Network(msg) => ApiError::fetch(url, msg),
             _ => ApiError::fetch(url, e.to_string()),
@@ -655,11 +1348,13 @@ impl PipelineOrchestrator {
         &self,
         url: &str,
     ) -> ApiResult<(HttpResponse, Vec<u8>, Option<String>)> {
-        let fetch_timeout = Duration::from_secs(15);
+        let fetch_timeout = self.state.api_config.get_timeout("fetch");
 
         let response = timeout(fetch_timeout, self.state.http_client.get(url))
             .await
-            .map_err(|_| ApiError::timeout("content_fetch", format!("Timeout fetching {}", url)))?
+            .map_err(|_| {
+                ApiError::fetch_timeout("content_fetch", format!("Timeout fetching {}", url))
+            })?
             .map_err(|e| ApiError::fetch(url, format!("Fetch failed: {}", e)))?;
 
         // Extract content type from response headers
@@ -671,6 +1366,91 @@ impl PipelineOrchestrator {
         Ok((response, content_bytes, content_type))
     }
 
+    /// Revalidate a URL with the validators of its stored fingerprint.
+    #[cfg(feature = "fetch")]
+    async fn fetch_conditional(
+        &self,
+        url: &str,
+        fingerprint: &ContentFingerprint,
+    ) -> ApiResult<(HttpResponse, Vec<u8>, Option<String>)> {
+        let fetch_timeout = self.state.api_config.get_timeout("fetch");
+        let request = fingerprint
+            .conditional_headers()
+            .into_iter()
+            .fold(HttpRequest::new("GET", url), |req, (name, value)| {
+                req.with_header(name, value)
+            });
+
+        let response = timeout(fetch_timeout, self.state.http_client.request(request))
+            .await
+            .map_err(|_| {
+                ApiError::fetch_timeout("content_fetch", format!("Timeout fetching {}", url))
+            })?
+            .map_err(|e| ApiError::fetch(url, format!("Fetch failed: {}", e)))?;
+
+        let content_type = response.header("content-type").map(|s| s.to_string());
+        let content_bytes = response.body.clone();
+
+        Ok((response, content_bytes, content_type))
+    }
+
+    /// Update the stored fingerprint of `url` from a fetched response.
+    ///
+    /// Error responses are not fingerprinted. A `304 Not Modified` refreshes
+    /// the previous fingerprint without changing its content hash.
+    #[cfg(feature = "fetch")]
+    async fn track_content(
+        &self,
+        url: &str,
+        previous: Option<&ContentFingerprint>,
+        response: &HttpResponse,
+        fetched_at: chrono::DateTime<chrono::Utc>,
+    ) -> Option<ContentFingerprint> {
+        let etag = response.header("etag").cloned();
+        let last_modified = response.header("last-modified").cloned();
+        let fingerprint = match (response.status, previous) {
+            (304, Some(prev)) => ContentFingerprint {
+                etag: etag.or_else(|| prev.etag.clone()),
+                last_modified: last_modified.or_else(|| prev.last_modified.clone()),
+                checked_at: fetched_at,
+                ..prev.clone()
+            },
+            _ if response.is_success() => ContentFingerprint::observe(
+                previous,
+                &response.body,
+                etag,
+                last_modified,
+                fetched_at,
+            ),
+            _ => return None,
+        };
+        self.change_tracker.record(url, &fingerprint).await;
+        Some(fingerprint)
+    }
+
+    /// Diff a fresh extraction of `url` against the previous one.
+    ///
+    /// Error pages are not tracked, like their fingerprints.
+    #[cfg(feature = "fetch")]
+    async fn track_extraction(&self, url: &str, result: &PipelineResult) {
+        if !(200..300).contains(&result.http_status) {
+            return;
+        }
+        if let Some(diff) = self
+            .change_tracker
+            .record_document(url, &result.document)
+            .await
+        {
+            debug!(
+                url = %url,
+                text_changes = diff.text_changes.len(),
+                links_added = diff.links_added.len(),
+                links_removed = diff.links_removed.len(),
+                "Document changed since previous extraction"
+            );
+        }
+    }
+
     /// Process PDF content using the PDF pipeline.
     async fn process_pdf_content(&self, pdf_bytes: &[u8], url: &str) -> ApiResult<ExtractedDoc> {
         info!(
@@ -754,6 +1534,20 @@ impl PipelineOrchestrator {
                     media: Vec::new(),
                     language: None,
                     parser_metadata: None,
+                    provenance: None,
+                    translation: None,
+                    structured_data: Vec::new(),
+                    annotations: None,
+                    feed: None,
+                    feed_links: Vec::new(),
+                    classification: None,
+                    clean_html: None,
+                    media_assets: Vec::new(),
+                    embedded_media: Vec::new(),
+                    images: Vec::new(),
+                    references: Vec::new(),
+                    language_spans: Vec::new(),
+                    quality_report: None,
                     reading_time: Some(1),
                     quality_score: Some(20), // Low quality due to error
                     word_count: Some(5),
@@ -900,19 +1694,20 @@ impl PipelineOrchestrator {
     /// - No retry overhead, direct extraction path
     async fn extract_content(
         &self,
+        extractor: &dyn ContentExtractor,
         html: &str,
         url: &str,
         _decision: Decision,
     ) -> ApiResult<ExtractedDoc> {
         // Primary path: Use UnifiedExtractor which provides native extraction
         // WASM is only used if feature is enabled and initialized
-        let extracted_content = self.state.extractor.extract(html, url).await.map_err(|e| {
+        let extracted_content = extractor.extract(html, url).await.map_err(|e| {
             error!(url = %url, error = %e, "Content extraction failed");
             ApiError::extraction(format!("Extraction failed: {}", e))
         })?;
 
         // Emit success event with strategy information
-        let strategy = self.state.extractor.extractor_type();
+        let strategy = extractor.extractor_type();
         let mut event = riptide_events::BaseEvent::new(
             "pipeline.extraction.success",
             "pipeline_orchestrator",
@@ -926,17 +1721,24 @@ impl PipelineOrchestrator {
         }
 
         // Convert ExtractionResult to ExtractedDoc
-        Ok(ExtractedDoc {
+        let language_spans =
+            riptide_types::language::detect_language_spans(&extracted_content.text);
+        let language = extracted_content
+            .metadata
+            .get("lang")
+            .cloned()
+            .or_else(|| riptide_types::language::dominant_language(&language_spans));
+        let mut doc = ExtractedDoc {
             url: url.to_string(),
             title: extracted_content.metadata.get("title").cloned(),
             text: extracted_content.text,
-            quality_score: Some((extracted_content.quality_score * 100.0) as u8),
+            quality_score: None,
             links: vec![],
             byline: extracted_content.metadata.get("author").cloned(),
             published_iso: extracted_content.metadata.get("published").cloned(),
             markdown: None,
             media: vec![],
-            language: extracted_content.metadata.get("lang").cloned(),
+            language,
             reading_time: None,
             word_count: None,
             categories: vec![],
@@ -944,23 +1746,107 @@ impl PipelineOrchestrator {
             description: extracted_content.metadata.get("description").cloned(),
             html: None,
             parser_metadata: None,
-        })
+            provenance: None,
+            translation: None,
+            structured_data: riptide_extraction::extract_structured_data(html, url),
+            annotations: None,
+            feed: None,
+            feed_links: riptide_extraction::discover_feeds(html, url),
+            classification: None,
+            clean_html: None,
+            media_assets: Vec::new(),
+            embedded_media: riptide_extraction::extract_media_metadata(html, url),
+            images: riptide_extraction::extract_images(html, url),
+            references: riptide_extraction::extract_references(html, url),
+            language_spans,
+            quality_report: None,
+        };
+
+        // Score with the explainable model whichever extractor ran
+        let report = riptide_extraction::assess_quality(html, &doc);
+        doc.quality_score = Some(report.score);
+        doc.quality_report = Some(report);
+        Ok(doc)
+    }
+
+    /// Keep fetched HTML for the reprocess and retention jobs.
+    ///
+    /// `extracted` is the document extracted from it, `None` when extraction
+    /// failed, and `result_key` the key it is cached under when reprocessing
+    /// should replace it. Failures are logged and ignored.
+    #[cfg(feature = "fetch")]
+    async fn store_raw_html(
+        &self,
+        url: &str,
+        status: u16,
+        html: &str,
+        fetched_at: chrono::DateTime<chrono::Utc>,
+        extracted: Option<&ExtractedDoc>,
+        result_key: Option<&str>,
+    ) {
+        if self.options.bypass_cache() {
+            return;
+        }
+        let snapshot = RawHtmlSnapshot {
+            url: url.to_string(),
+            status,
+            html: html.to_string(),
+            fetched_at,
+            quality_score: extracted.and_then(|doc| doc.quality_score),
+            extraction_failed: extracted.is_none(),
+            result: result_key.map(|key| SnapshotResult::Document {
+                key: key.to_string(),
+                ttl_secs: self.state.config.cache_ttl,
+            }),
+        };
+        if let Err(e) = store_raw_html_snapshot(self.state.cache.as_ref(), &snapshot).await {
+            debug!(url = %url, error = %e, "Failed to store raw HTML snapshot");
+        }
     }
 
     /// Check cache for existing content.
-    async fn check_cache(&self, cache_key: &str) -> ApiResult<Option<ExtractedDoc>> {
-        if self.options.cache_mode == "bypass" {
+    ///
+    /// Entries older than `cache_ttl` are returned as stale when they carry
+    /// validators to revalidate with, and treated as misses otherwise.
+    async fn check_cache(&self, cache_key: &str) -> ApiResult<Option<CacheLookup>> {
+        // Archived crawls must fetch every page so the capture is complete, and
+        // differential crawls must revalidate against the origin
+        if self.options.bypass_cache() || self.archive.is_some() || self.changed_since.is_some() {
             return Ok(None);
         }
 
         match self.state.cache.get(cache_key).await {
-            Ok(Some(bytes)) => match serde_json::from_slice::<ExtractedDoc>(&bytes) {
-                Ok(doc) => Ok(Some(doc)),
-                Err(e) => {
-                    warn!("Cache deserialization failed for key {}: {}", cache_key, e);
+            Ok(Some(bytes)) => {
+                let entry = match serde_json::from_slice::<CachedDocument>(&bytes) {
+                    Ok(entry) => entry,
+                    // Entries written before validators were stored hold the bare
+                    // document and expire with the cache TTL
+                    Err(_) => match serde_json::from_slice::<ExtractedDoc>(&bytes) {
+                        Ok(document) => CachedDocument {
+                            document,
+                            content_hash: None,
+                            stored_at: chrono::Utc::now(),
+                        },
+                        Err(e) => {
+                            warn!("Cache deserialization failed for key {}: {}", cache_key, e);
+                            return Ok(None);
+                        }
+                    },
+                };
+                if self.is_stale(&entry.document) {
+                    debug!(cache_key = %cache_key, "Cached document from older extractor, re-extracting");
+                    return Ok(None);
+                }
+
+                let age = chrono::Utc::now().signed_duration_since(entry.stored_at);
+                if age.num_seconds() < self.state.config.cache_ttl as i64 {
+                    Ok(Some(CacheLookup::Fresh(entry.document)))
+                } else if entry.is_revalidatable() {
+                    Ok(Some(CacheLookup::Stale(entry)))
+                } else {
                     Ok(None)
                 }
-            },
+            }
             Ok(None) => Ok(None),
             Err(e) => {
                 self.state.transport_metrics.record_redis_error();
@@ -969,22 +1855,126 @@ impl PipelineOrchestrator {
         }
     }
 
-    /// Store content in cache.
-    async fn store_in_cache(&self, cache_key: &str, document: &ExtractedDoc) -> ApiResult<()> {
-        if self.options.cache_mode == "bypass" {
+    /// Provenance record for a document produced by this pipeline.
+    fn provenance(
+        &self,
+        strategy: &str,
+        gate_decision: &str,
+        fetched_at: chrono::DateTime<chrono::Utc>,
+        headers: &HashMap<String, String>,
+    ) -> ExtractionProvenance {
+        let component_hash = if strategy == "wasm" {
+            self.state.component_hash()
+        } else {
+            None
+        };
+        ExtractionProvenance::new(riptide_extraction::EXTRACTOR_VERSION, strategy)
+            .with_component_hash(component_hash)
+            .with_gate_decision(gate_decision)
+            .with_fetched_at(fetched_at)
+            .with_source_headers(headers)
+    }
+
+    /// Language documents are translated to, when a translator is configured.
+    ///
+    /// The request's `translate_to` wins over `RIPTIDE_TRANSLATE_TO`.
+    fn translation_target(&self) -> Option<&str> {
+        self.state.translation.as_ref()?;
+        let default_target = &self.state.config.translation_config.target_language;
+        self.options
+            .translate_to
+            .as_deref()
+            .or(default_target.as_deref())
+            .filter(|lang| !lang.trim().is_empty())
+    }
+
+    /// Add a translation to `document`; failures leave it untranslated.
+    async fn translate(&self, document: &mut ExtractedDoc) {
+        let (Some(enricher), Some(target)) =
+            (self.state.translation.as_ref(), self.translation_target())
+        else {
+            return;
+        };
+        if let Err(e) = enricher.enrich(document, target).await {
+            warn!(url = %document.url, error = %e, "Translation failed, continuing without it");
+        }
+    }
+
+    /// Add entities, keywords and topics to `document`; failures leave it unannotated.
+    async fn annotate(&self, document: &mut ExtractedDoc) {
+        let Some(enricher) = self.state.annotation.as_ref() else {
+            return;
+        };
+        if let Err(e) = enricher.enrich(document).await {
+            warn!(url = %document.url, error = %e, "Annotation failed, continuing without it");
+        }
+    }
+
+    /// Tag `document` with categories and sentiment; failures leave its
+    /// meta-keyword categories in place.
+    async fn classify(&self, document: &mut ExtractedDoc) {
+        let Some(enricher) = self.state.classification.as_ref() else {
+            return;
+        };
+        if let Err(e) = enricher.enrich(document).await {
+            warn!(url = %document.url, error = %e, "Classification failed, continuing without it");
+        }
+    }
+
+    /// Download, hash and deduplicate the images of `document`, then probe
+    /// the rest for content type and size; images that fail are left out of
+    /// `media_assets` and unprobed in `images`.
+    async fn download_media(&self, document: &mut ExtractedDoc) {
+        if let Some(downloader) = self.state.media_downloader.as_ref() {
+            downloader.enrich(document).await;
+            downloader.probe(document).await;
+        }
+    }
+
+    /// Whether a cached document was produced by a different extractor build.
+    ///
+    /// Documents cached before provenance was recorded are treated as stale.
+    fn is_stale(&self, doc: &ExtractedDoc) -> bool {
+        match &doc.provenance {
+            Some(provenance) => provenance.is_stale(
+                riptide_extraction::EXTRACTOR_VERSION,
+                self.state.component_hash().as_deref(),
+            ),
+            None => true,
+        }
+    }
+
+    /// Store content in cache, with the content hash of its source response.
+    ///
+    /// Revalidatable entries are kept `cache_revalidate_ttl` past `cache_ttl`.
+    async fn store_in_cache(
+        &self,
+        cache_key: &str,
+        document: &ExtractedDoc,
+        validators: Option<&ContentFingerprint>,
+    ) -> ApiResult<()> {
+        if self.options.bypass_cache() {
             return Ok(());
         }
 
-        let doc_bytes = serde_json::to_vec(document)
+        let entry = CachedDocument {
+            document: document.clone(),
+            content_hash: validators
+                .filter(|v| v.etag.is_some() || v.last_modified.is_some())
+                .map(|v| v.content_hash.clone()),
+            stored_at: chrono::Utc::now(),
+        };
+        let doc_bytes = serde_json::to_vec(&entry)
             .map_err(|e| ApiError::cache(format!("Cache serialization failed: {}", e)))?;
 
+        let mut ttl = self.state.config.cache_ttl;
+        if entry.is_revalidatable() {
+            ttl += self.state.config.cache_revalidate_ttl;
+        }
+
         self.state
             .cache
-            .set(
-                cache_key,
-                &doc_bytes,
-                Some(Duration::from_secs(self.state.config.cache_ttl)),
-            )
+            .set(cache_key, &doc_bytes, Some(Duration::from_secs(ttl)))
             .await
             .map_err(|e| {
                 self.state.transport_metrics.record_redis_error();
@@ -1000,11 +1990,21 @@ impl PipelineOrchestrator {
         let mut hasher = DefaultHasher::new();
         url.hash(&mut hasher);
         self.options.cache_mode.hash(&mut hasher);
-        format!(
+        let mut key = format!(
             "riptide:v1:{}:{:x}",
             self.options.cache_mode,
             hasher.finish()
-        )
+        );
+        // Translated documents must not be served to requests for another language
+        if let Some(target) = self.translation_target() {
+            key.push_str(&format!(":tr:{}", target.to_ascii_lowercase()));
+        }
+        // Nor documents whose formulas and graphics were rewritten another way
+        match self.options.embedded_markup.unwrap_or_default() {
+            EmbeddedMarkup::Text => {}
+            handling => key.push_str(&format!(":em:{}", handling.as_str())),
+        }
+        key
     }
 }
 
@@ -1020,6 +2020,10 @@ impl Clone for PipelineOrchestrator {
                     base: self.retry_config.base.clone(),
                     strategy: self.retry_config.strategy,
                 },
+                archive: self.archive.clone(),
+                change_tracker: self.change_tracker.clone(),
+                changed_since: self.changed_since,
+                budget: self.budget.clone(),
             }
         }
         #[cfg(not(feature = "llm"))]
@@ -1028,6 +2032,10 @@ impl Clone for PipelineOrchestrator {
                 state: self.state.clone(),
                 options: self.options.clone(),
                 retry_config: self.retry_config.clone(),
+                archive: self.archive.clone(),
+                change_tracker: self.change_tracker.clone(),
+                changed_since: self.changed_since,
+                budget: self.budget.clone(),
             }
         }
     }
@@ -1067,7 +2075,133 @@ impl PipelineExecutor for PipelineOrchestrator {
         Vec<Option<riptide_types::pipeline::PipelineResult>>,
         riptide_types::pipeline::PipelineStats,
     ) {
-        // Delegate to existing implementation
-        PipelineOrchestrator::execute_batch(self, urls).await
+        // Delegate to existing implementation; the trait reports failures as `None`
+        let (results, stats) = PipelineOrchestrator::execute_batch(self, urls).await;
+        (results.into_iter().map(Result::ok).collect(), stats)
+    }
+
+    async fn execute_batch_changed_since(
+        &self,
+        urls: &[String],
+        since: &riptide_types::ChangedSince,
+    ) -> riptide_types::Result<(
+        Vec<Option<riptide_types::pipeline::PipelineResult>>,
+        riptide_types::pipeline::PipelineStats,
+    )> {
+        let since = self
+            .change_tracker
+            .resolve(since)
+            .await
+            .map_err(|e| riptide_types::RiptideError::ValidationError(e.to_string()))?;
+        let pipeline = self.clone().with_changed_since(Some(since));
+        let (results, stats) = PipelineOrchestrator::execute_batch(&pipeline, urls).await;
+        Ok((results.into_iter().map(Result::ok).collect(), stats))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fingerprint(etag: Option<&str>, last_modified: Option<&str>) -> ContentFingerprint {
+        ContentFingerprint::observe(
+            None,
+            b"<html></html>",
+            etag.map(str::to_string),
+            last_modified.map(str::to_string),
+            chrono::Utc::now(),
+        )
+    }
+
+    #[test]
+    fn test_cached_document_revalidatable_with_validators() {
+        let etag = fingerprint(Some("\"v1\""), None);
+        let mut entry = CachedDocument {
+            document: ExtractedDoc::default(),
+            content_hash: Some(etag.content_hash.clone()),
+            stored_at: chrono::Utc::now(),
+        };
+        assert!(entry.is_revalidatable());
+        assert_eq!(entry.validators(Some(&etag)), Some(&etag));
+
+        let last_modified = fingerprint(None, Some("Wed, 21 Oct 2015 07:28:00 GMT"));
+        assert!(entry.validators(Some(&last_modified)).is_some());
+        assert!(entry.validators(Some(&fingerprint(None, None))).is_none());
+
+        // The URL changed since the entry was stored
+        let changed = ContentFingerprint::observe(
+            None,
+            b"<html>new</html>",
+            Some("\"v2\"".to_string()),
+            None,
+            chrono::Utc::now(),
+        );
+        assert!(entry.validators(Some(&changed)).is_none());
+
+        entry.content_hash = None;
+        assert!(!entry.is_revalidatable());
+        assert!(entry.validators(Some(&etag)).is_none());
+    }
+
+    #[test]
+    fn test_cached_document_rejects_bare_document() {
+        // Entries written before validators were stored hold the bare document
+        let legacy = serde_json::to_vec(&ExtractedDoc::default()).unwrap();
+        assert!(serde_json::from_slice::<CachedDocument>(&legacy).is_err());
+        assert!(serde_json::from_slice::<ExtractedDoc>(&legacy).is_ok());
+    }
+
+    #[cfg(feature = "fetch")]
+    #[tokio::test]
+    async fn test_stale_entry_served_after_304() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/article"))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304).insert_header("ETag", "\"v1\""))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        let html = format!(
+            "<html><head><title>Article</title></head><body><article><h1>Article</h1>{}</article></body></html>",
+            "<p>Revalidated content stays the same between fetches.</p>".repeat(20)
+        );
+        Mock::given(method("GET"))
+            .and(path("/article"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v1\"")
+                    .insert_header("Content-Type", "text/html; charset=utf-8")
+                    .set_body_string(html),
+            )
+            .mount(&server)
+            .await;
+
+        let mut state = ApplicationContext::new_test_minimal().await;
+        // Every stored entry is stale at once and must be revalidated
+        state.config.cache_ttl = 0;
+        let orchestrator = PipelineOrchestrator::new(state, CrawlOptions::default());
+        let url = format!("{}/article", server.uri());
+
+        let first = orchestrator.execute_single(&url).await.unwrap();
+        assert_eq!(first.cache_status, CacheStatus::Miss);
+
+        let second = orchestrator.execute_single(&url).await.unwrap();
+        assert_eq!(second.cache_status, CacheStatus::Revalidated);
+        assert!(second.from_cache);
+        assert_eq!(second.document.text, first.document.text);
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[1]
+                .headers
+                .get("if-none-match")
+                .and_then(|value| value.to_str().ok()),
+            Some("\"v1\"")
+        );
     }
 }
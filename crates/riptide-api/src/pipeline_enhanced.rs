@@ -16,12 +16,13 @@ use crate::context::ApplicationContext;
 /// - ENHANCED_PIPELINE_DEBUG: Enable debug logging (default: false)
 /// - ENHANCED_PIPELINE_FETCH_TIMEOUT: Fetch phase timeout in seconds
 /// - ENHANCED_PIPELINE_RENDER_TIMEOUT: Render phase timeout in seconds
-use crate::errors::ApiResult;
+use crate::errors::{ApiError, ApiResult};
 use crate::pipeline::{PipelineOrchestrator, PipelineResult, PipelineStats};
 use crate::state::EnhancedPipelineConfig;
 use anyhow::Result;
 use riptide_types::config::CrawlOptions;
-use riptide_types::ExtractedDoc;
+use riptide_types::ports::WebArchive;
+use riptide_types::{ErrorCode, ExtractedDoc, PhaseTimings};
 use std::sync::Arc;
 use std::time::Instant;
 use tracing::{debug, info, warn};
@@ -61,6 +62,12 @@ impl EnhancedPipelineOrchestrator {
         }
     }
 
+    /// Record every fetched response to the given web archive
+    pub fn with_archive(mut self, archive: Option<Arc<dyn WebArchive>>) -> Self {
+        self.pipeline = self.pipeline.with_archive(archive);
+        self
+    }
+
     /// Execute single URL with enhanced metrics (delegates to standard pipeline)
     #[cfg(feature = "fetch")]
     pub async fn execute_single_enhanced(&self, url: &str) -> ApiResult<EnhancedPipelineResult> {
@@ -76,10 +83,13 @@ impl EnhancedPipelineOrchestrator {
     }
 
     /// Execute batch with enhanced metrics (delegates to standard pipeline)
+    ///
+    /// Results are returned in input order; a failed URL carries its error
+    /// instead of failing the batch.
     pub async fn execute_batch_enhanced(
         &self,
         urls: &[String],
-    ) -> (Vec<Option<EnhancedPipelineResult>>, EnhancedBatchStats) {
+    ) -> (Vec<ApiResult<EnhancedPipelineResult>>, EnhancedBatchStats) {
         let overall_start = Instant::now();
 
         if !self.config.enable_enhanced_pipeline {
@@ -87,7 +97,7 @@ impl EnhancedPipelineOrchestrator {
             let (results, stats) = self.pipeline.execute_batch(urls).await;
             let enhanced_results = results
                 .into_iter()
-                .map(|opt_result| opt_result.map(|result| self.convert_to_enhanced_result(result)))
+                .map(|result| result.map(|result| self.convert_to_enhanced_result(result)))
                 .collect();
             return (
                 enhanced_results,
@@ -106,8 +116,14 @@ impl EnhancedPipelineOrchestrator {
 
                 tokio::spawn(async move {
                     // Acquire semaphore permit and keep it alive for the duration of the task
-                    let _permit = semaphore.acquire().await.ok()?;
-                    orchestrator.execute_enhanced(&url).await.ok()
+                    let _permit = semaphore.acquire().await.map_err(|e| {
+                        ApiError::internal(format!("Failed to acquire concurrency permit: {}", e))
+                    })?;
+                    orchestrator
+                        .execute_enhanced(&url)
+                        .await
+                        .map_err(|e| ApiError::internal(format!("Enhanced pipeline failed: {}", e)))
+                        .and_then(EnhancedPipelineResult::into_api_result)
                 })
             })
             .collect();
@@ -115,7 +131,14 @@ impl EnhancedPipelineOrchestrator {
         let results = futures::future::join_all(tasks).await;
         let enhanced_results: Vec<_> = results
             .into_iter()
-            .map(|r| r.ok().and_then(|inner| inner))
+            .map(|r| {
+                r.unwrap_or_else(|e| {
+                    Err(ApiError::internal(format!(
+                        "URL processing task failed: {}",
+                        e
+                    )))
+                })
+            })
             .collect();
 
         let stats = self.compute_enhanced_batch_stats(&enhanced_results, overall_start);
@@ -128,9 +151,10 @@ impl EnhancedPipelineOrchestrator {
             url: result.document.url.clone(),
             success: true,
             total_duration_ms: result.processing_time_ms,
-            phase_timings: PhaseTiming::default(), // No detailed timings from standard pipeline
+            phase_timings: PhaseTiming::from(&result.timings),
             document: Some(result.document),
             error: None,
+            error_code: None,
             cache_hit: result.from_cache,
             gate_decision: result.gate_decision,
             quality_score: result.quality_score,
@@ -158,11 +182,11 @@ impl EnhancedPipelineOrchestrator {
     /// Compute enhanced batch statistics
     fn compute_enhanced_batch_stats(
         &self,
-        results: &[Option<EnhancedPipelineResult>],
+        results: &[ApiResult<EnhancedPipelineResult>],
         start: Instant,
     ) -> EnhancedBatchStats {
         let total_urls = results.len();
-        let successful = results.iter().filter(|r| r.is_some()).count();
+        let successful = results.iter().filter(|r| r.is_ok()).count();
         let failed = total_urls - successful;
 
         let mut total_fetch = 0u64;
@@ -258,6 +282,7 @@ impl EnhancedPipelineOrchestrator {
             phase_timings: PhaseTiming::default(),
             document: None,
             error: None,
+            error_code: None,
             cache_hit: false,
             gate_decision: "unknown".to_string(),
             quality_score: 0.0,
@@ -271,6 +296,7 @@ impl EnhancedPipelineOrchestrator {
             Ok(data) => data,
             Err(e) => {
                 result.error = Some(format!("Fetch phase failed: {}", e));
+                result.error_code = Some(ErrorCode::FetchFailed);
                 self.state.record_http_error();
                 return Ok(result);
             }
@@ -295,6 +321,7 @@ impl EnhancedPipelineOrchestrator {
             Ok(doc) => doc,
             Err(e) => {
                 result.error = Some(format!("WASM phase failed: {}", e));
+                result.error_code = Some(ErrorCode::ExtractionFailed);
                 self.state.record_wasm_error();
                 return Ok(result);
             }
@@ -422,6 +449,7 @@ impl EnhancedPipelineOrchestrator {
     async fn fetch_content(&self, url: &str) -> Result<(String, u16)> {
         // Use trait-based HTTP client
         let response = self.state.http_client.get(url).await?;
+        self.pipeline.archive_response(url, &response).await;
         let status = response.status;
         let content = response.text()?;
         Ok((content, status))
@@ -474,6 +502,20 @@ impl EnhancedPipelineOrchestrator {
             categories: vec![],
             site_name: None,
             parser_metadata: None,
+            provenance: None,
+            translation: None,
+            structured_data: Vec::new(),
+            annotations: None,
+            feed: None,
+            feed_links: Vec::new(),
+            classification: None,
+            clean_html: None,
+            media_assets: Vec::new(),
+            embedded_media: Vec::new(),
+            images: Vec::new(),
+            references: Vec::new(),
+            language_spans: Vec::new(),
+            quality_report: None,
             description: None,
             html: None,
         };
@@ -504,6 +546,20 @@ impl EnhancedPipelineOrchestrator {
             word_count: Some(100),
             categories: vec![],
             parser_metadata: None,
+            provenance: None,
+            translation: None,
+            structured_data: Vec::new(),
+            annotations: None,
+            feed: None,
+            feed_links: Vec::new(),
+            classification: None,
+            clean_html: None,
+            media_assets: Vec::new(),
+            embedded_media: Vec::new(),
+            images: Vec::new(),
+            references: Vec::new(),
+            language_spans: Vec::new(),
+            quality_report: None,
             site_name: None,
             description: None,
             html: None,
@@ -522,11 +578,29 @@ pub struct EnhancedPipelineResult {
     pub phase_timings: PhaseTiming,
     pub document: Option<ExtractedDoc>,
     pub error: Option<String>,
+    pub error_code: Option<ErrorCode>,
     pub cache_hit: bool,
     pub gate_decision: String,
     pub quality_score: f32,
 }
 
+impl EnhancedPipelineResult {
+    /// Convert a failed result into the error that caused it
+    pub fn into_api_result(self) -> ApiResult<Self> {
+        if self.success {
+            return Ok(self);
+        }
+        let message = self
+            .error
+            .unwrap_or_else(|| "Enhanced pipeline failed".to_string());
+        Err(match self.error_code {
+            Some(ErrorCode::FetchFailed) => ApiError::fetch(self.url, message),
+            Some(ErrorCode::ExtractionFailed) => ApiError::extraction(message),
+            _ => ApiError::internal(message),
+        })
+    }
+}
+
 /// Detailed phase timing information
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct PhaseTiming {
@@ -536,6 +610,30 @@ pub struct PhaseTiming {
     pub render_ms: Option<u64>,
 }
 
+impl From<&PhaseTimings> for PhaseTiming {
+    fn from(timings: &PhaseTimings) -> Self {
+        Self {
+            fetch_ms: timings.fetch_ms.unwrap_or(0),
+            gate_ms: timings.gate_ms.unwrap_or(0),
+            wasm_ms: timings.extract_ms.unwrap_or(0),
+            render_ms: timings.render_ms,
+        }
+    }
+}
+
+impl From<&PhaseTiming> for PhaseTimings {
+    fn from(timing: &PhaseTiming) -> Self {
+        let recorded = |ms: u64| (ms > 0).then_some(ms);
+        Self {
+            fetch_ms: recorded(timing.fetch_ms),
+            gate_ms: recorded(timing.gate_ms),
+            render_ms: timing.render_ms,
+            extract_ms: recorded(timing.wasm_ms),
+            ..Default::default()
+        }
+    }
+}
+
 /// Enhanced batch processing statistics
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct EnhancedBatchStats {
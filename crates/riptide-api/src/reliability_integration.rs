@@ -103,6 +103,20 @@ impl WasmExtractorTrait for WasmExtractorAdapter {
             categories: Vec::new(),
             site_name: None,
             parser_metadata: None, // ExtractedContent doesn't have parser metadata
+            provenance: None,
+            translation: None,
+            structured_data: Vec::new(),
+            annotations: None,
+            feed: None,
+            feed_links: Vec::new(),
+            classification: None,
+            clean_html: None,
+            media_assets: Vec::new(),
+            embedded_media: Vec::new(),
+            images: Vec::new(),
+            references: Vec::new(),
+            language_spans: Vec::new(),
+            quality_report: None,
             description: extracted_content.summary,
             html: None,
         })
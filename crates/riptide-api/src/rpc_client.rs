@@ -2,7 +2,10 @@
 use anyhow::{anyhow, Result};
 use reqwest::Client;
 #[cfg(feature = "browser")]
-use riptide_headless::dynamic::{DynamicConfig, DynamicRenderResult, PageAction, RenderArtifacts};
+use riptide_headless::dynamic::{
+    AutoScrollConfig, DynamicConfig, DynamicRenderResult, FrameProvenance, IframeConfig,
+    PageAction, RenderArtifacts,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 #[cfg(not(feature = "browser"))]
@@ -174,11 +177,15 @@ impl RpcClient {
         );
 
         // Convert dynamic config to headless browser format with session context
+        let mut actions = convert_actions(&config.actions);
+        if let Some(auto_scroll) = &config.auto_scroll {
+            actions.push(convert_auto_scroll(auto_scroll));
+        }
         let request = HeadlessRenderRequest {
             url: url.to_string(),
             session_id: session_id.map(|s| s.to_string()),
             user_data_dir: user_data_dir.map(|d| d.to_string()),
-            actions: Some(convert_actions(&config.actions)),
+            actions: Some(actions),
             timeouts: Some(HeadlessTimeouts {
                 nav_ms: Some(1000),
                 idle_after_dcl_ms: Some(1000),
@@ -189,6 +196,7 @@ impl RpcClient {
                 mhtml: config.capture_artifacts,
             }),
             stealth_config: stealth_config.cloned(),
+            iframes: config.iframes.clone(),
         };
 
         debug!(
@@ -197,16 +205,33 @@ impl RpcClient {
             "Sending render request to headless service"
         );
 
-        // Make HTTP request to headless service with timeout
+        // Make HTTP request to headless service with timeout, leaving room
+        // for the auto-scroll and iframe budgets on top of the regular render time
+        let request_timeout = self
+            .timeout
+            .saturating_add(
+                config
+                    .auto_scroll
+                    .as_ref()
+                    .map(|a| Duration::from_millis(a.max_duration_ms))
+                    .unwrap_or_default(),
+            )
+            .saturating_add(
+                config
+                    .iframes
+                    .as_ref()
+                    .map(|f| Duration::from_millis(f.time_budget_ms()))
+                    .unwrap_or_default(),
+            );
         let response = tokio::time::timeout(
-            self.timeout,
+            request_timeout,
             self.client
                 .post(format!("{}/render", self.base_url))
                 .json(&request)
                 .send(),
         )
         .await
-        .map_err(|_| anyhow!("RPC request timed out after {}s", self.timeout.as_secs()))?
+        .map_err(|_| anyhow!("RPC request timed out after {}s", request_timeout.as_secs()))?
         .map_err(|e| anyhow!("HTTP request failed: {}", e))?;
 
         if !response.status().is_success() {
@@ -223,6 +248,11 @@ impl RpcClient {
 
         let render_time_ms = start_time.elapsed().as_millis() as u64;
 
+        let mut actions_executed = extract_action_names(&config.actions);
+        if config.auto_scroll.is_some() {
+            actions_executed.push("auto_scroll".to_string());
+        }
+
         // Convert back to DynamicRenderResult
         let result = DynamicRenderResult {
             success: true,
@@ -230,8 +260,9 @@ impl RpcClient {
             artifacts: None, // Artifacts no longer returned by headless service
             error: None,
             render_time_ms,
-            actions_executed: extract_action_names(&config.actions),
+            actions_executed,
             wait_conditions_met: vec!["dom_content_loaded".to_string()],
+            frames: headless_response.frames,
         };
 
         info!(
@@ -313,6 +344,9 @@ struct HeadlessRenderRequest {
     timeouts: Option<HeadlessTimeouts>,
     artifacts: Option<HeadlessArtifacts>,
     stealth_config: Option<riptide_stealth::StealthConfig>,
+    #[cfg(feature = "browser")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    iframes: Option<IframeConfig>,
 }
 
 /// Timeout configuration for headless browser
@@ -345,6 +379,15 @@ enum HeadlessPageAction {
         expr: String,
         timeout_ms: Option<u64>,
     },
+    AutoScroll {
+        max_cycles: u32,
+        wait_ms: u64,
+        stable_cycles: u32,
+        max_duration_ms: u64,
+        max_height_px: Option<u64>,
+        item_selector: Option<String>,
+        max_items: Option<u64>,
+    },
     Js {
         code: String,
     },
@@ -365,6 +408,10 @@ struct HeadlessRenderResponse {
     #[allow(dead_code)] // May be used for URL tracking in future
     final_url: String,
     html: String,
+    /// Iframes inlined or rendered as sub-documents
+    #[cfg(feature = "browser")]
+    #[serde(default)]
+    frames: Vec<FrameProvenance>,
 }
 
 /// Output artifacts from headless browser
@@ -425,6 +472,20 @@ fn convert_actions(actions: &[PageAction]) -> Vec<HeadlessPageAction> {
         .collect()
 }
 
+/// Convert an auto-scroll config to the headless browser action
+#[cfg(feature = "browser")]
+fn convert_auto_scroll(config: &AutoScrollConfig) -> HeadlessPageAction {
+    HeadlessPageAction::AutoScroll {
+        max_cycles: config.max_cycles,
+        wait_ms: config.wait_ms,
+        stable_cycles: config.stable_cycles,
+        max_duration_ms: config.max_duration_ms,
+        max_height_px: config.max_height_px,
+        item_selector: config.item_selector.clone(),
+        max_items: config.max_items,
+    }
+}
+
 /// Convert headless artifacts to riptide-core format
 #[cfg(feature = "browser")]
 #[allow(dead_code)] // Artifacts conversion disabled, may be re-enabled later
@@ -473,7 +534,7 @@ fn extract_action_names(actions: &[PageAction]) -> Vec<String> {
 #[cfg(feature = "browser")]
 mod tests {
     use super::*;
-    use riptide_headless::dynamic::{PageAction, WaitCondition};
+    use riptide_headless::dynamic::{FrameDisposition, PageAction, WaitCondition};
     use std::time::Duration;
 
     #[test]
@@ -544,6 +605,46 @@ mod tests {
         assert_eq!(names, vec!["click", "type"]);
     }
 
+    #[test]
+    fn test_convert_auto_scroll() {
+        let config = AutoScrollConfig {
+            item_selector: Some("article".to_string()),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(convert_auto_scroll(&config)).unwrap();
+        assert_eq!(json["type"], "auto_scroll");
+        assert_eq!(json["max_cycles"], 10);
+        assert_eq!(json["item_selector"], "article");
+    }
+
+    #[test]
+    fn test_headless_response_frames() {
+        let response: HeadlessRenderResponse = serde_json::from_str(
+            r#"{"final_url": "https://example.com", "html": "<html></html>"}"#,
+        )
+        .unwrap();
+        assert!(response.frames.is_empty());
+
+        let response: HeadlessRenderResponse = serde_json::from_str(
+            r#"{
+                "final_url": "https://example.com",
+                "html": "<html></html>",
+                "frames": [{
+                    "path": "0",
+                    "src": "https://widgets.example.net/",
+                    "depth": 1,
+                    "same_origin": false,
+                    "disposition": "sub_document",
+                    "html": "<p>widget</p>"
+                }]
+            }"#,
+        )
+        .unwrap();
+        let frame = &response.frames[0];
+        assert_eq!(frame.disposition, FrameDisposition::SubDocument);
+        assert_eq!(frame.html.as_deref(), Some("<p>widget</p>"));
+    }
+
     #[tokio::test]
     async fn test_rpc_client_creation() {
         let client = RpcClient::new();
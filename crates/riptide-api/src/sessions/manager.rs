@@ -69,6 +69,17 @@ impl SessionManager {
             .map_err(|e| e.into())
     }
 
+    /// Create a new session only `tenant_id` may use
+    pub async fn create_session_for(&self, tenant_id: &str) -> Result<Session> {
+        let mut session = self
+            .storage
+            .create_session(Session::generate_session_id())
+            .await?;
+        session.metadata.tenant_id = Some(tenant_id.to_string());
+        self.storage.store_session(session.clone()).await?;
+        Ok(session)
+    }
+
     /// Update session metadata
     pub async fn update_session(&self, session: Session) -> Result<(), SessionError> {
         self.storage.store_session(session).await
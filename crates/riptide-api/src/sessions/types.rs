@@ -206,6 +206,10 @@ pub struct SessionMetadata {
 
     /// Session usage statistics
     pub stats: SessionUsageStats,
+
+    /// Tenant that created the session; sessions without one are shared
+    #[serde(default)]
+    pub tenant_id: Option<String>,
 }
 
 /// Browser configuration for the session
@@ -369,6 +373,14 @@ impl Session {
         SystemTime::now() > self.expires_at
     }
 
+    /// Whether `tenant_id` may use the session's cookies and recordings
+    pub fn is_usable_by(&self, tenant_id: &str) -> bool {
+        self.metadata
+            .tenant_id
+            .as_deref()
+            .is_none_or(|owner| owner == tenant_id)
+    }
+
     /// Update the last accessed time and extend expiry
     pub fn touch(&mut self, ttl: Duration) {
         let now = SystemTime::now();
@@ -1,11 +1,13 @@
 use crate::context::ApplicationContext;
 use crate::errors::{ApiError, ApiResult};
+use crate::validation::parse_stealth_preset;
 use async_trait::async_trait;
 use riptide_extraction::strategies::{
     ExtractionStrategyType, PerformanceMetrics, ProcessedContent, StrategyConfig, StrategyManager,
 };
 use riptide_pdf::{self as pdf, utils as pdf_utils};
 use riptide_reliability::gate::{decide, score, Decision, GateFeatures};
+use riptide_stealth::StealthConfig;
 use riptide_types::config::CrawlOptions;
 use riptide_types::RenderMode;
 use serde::{Deserialize, Serialize};
@@ -167,11 +169,15 @@ impl StrategiesPipelineOrchestrator {
         // Step 4: Gate analysis
         let gate_features = self.analyze_content(&html_content, url).await?;
         let quality_score = score(&gate_features);
-        let decision = decide(
-            &gate_features,
-            self.state.config.gate_hi_threshold,
-            self.state.config.gate_lo_threshold,
-        );
+        let vertical = self.state.gate_verticals.vertical_for(url).await;
+        let thresholds = self.state.gate_verticals.thresholds(vertical);
+        let decision = if self.options.force_headless() {
+            Decision::Headless
+        } else {
+            let decision = decide(&gate_features, thresholds.hi, thresholds.lo);
+            self.state.gate_verticals.record(vertical, decision);
+            decision
+        };
 
         let gate_decision_str = match decision {
             Decision::Raw => "raw",
@@ -347,7 +353,9 @@ impl StrategiesPipelineOrchestrator {
         let fetch_timeout = Duration::from_secs(15);
         let http_response = timeout(fetch_timeout, self.state.http_client.get(url))
             .await
-            .map_err(|_| ApiError::timeout("content_fetch", format!("Timeout fetching {}", url)))?
+            .map_err(|_| {
+                ApiError::fetch_timeout("content_fetch", format!("Timeout fetching {}", url))
+            })?
             .map_err(|e| ApiError::fetch(url, e.to_string()))?;
 
         let content_type = http_response.header("content-type").map(|s| s.to_string());
@@ -429,10 +437,18 @@ impl StrategiesPipelineOrchestrator {
     async fn extract_with_headless(&self, url: &str) -> ApiResult<String> {
         match &self.state.config.headless_url {
             Some(headless_url) => {
+                let stealth_config = self
+                    .options
+                    .pipeline_hints
+                    .as_ref()
+                    .and_then(|hints| hints.stealth_preset.as_deref())
+                    .and_then(parse_stealth_preset)
+                    .map(StealthConfig::from_preset);
                 let render_request = serde_json::json!({
                     "url": url,
                     "wait_for": self.options.dynamic_wait_for,
-                    "scroll_steps": self.options.scroll_steps
+                    "scroll_steps": self.options.scroll_steps,
+                    "stealth_config": stealth_config
                 });
 
                 // Serialize the JSON request body
@@ -470,7 +486,7 @@ impl StrategiesPipelineOrchestrator {
 
     /// Check cache for processed content
     async fn check_cache(&self, cache_key: &str) -> ApiResult<Option<ProcessedContent>> {
-        if self.options.cache_mode == "bypass" {
+        if self.options.bypass_cache() {
             return Ok(None);
         }
 
@@ -493,7 +509,7 @@ impl StrategiesPipelineOrchestrator {
 
     /// Store processed content in cache
     async fn store_in_cache(&self, cache_key: &str, content: &ProcessedContent) -> ApiResult<()> {
-        if self.options.cache_mode == "bypass" {
+        if self.options.bypass_cache() {
             return Ok(());
         }
 
@@ -5,6 +5,7 @@
 //! including buffer overflow, connection failures, and processing errors.
 
 use crate::errors::ApiError;
+use riptide_types::{ErrorCode, HasErrorCode};
 use std::fmt;
 
 /// Error types specific to streaming operations.
@@ -127,6 +128,22 @@ impl StreamingError {
     }
 }
 
+impl HasErrorCode for StreamingError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            Self::BufferOverflow { .. } | Self::BackpressureExceeded { .. } => {
+                ErrorCode::ResourceExhausted
+            }
+            Self::Connection { .. } | Self::Channel { .. } => ErrorCode::StreamFailed,
+            Self::ClientDisconnected { .. } => ErrorCode::Cancelled,
+            Self::Pipeline { source } => source.error_code(),
+            Self::InvalidRequest { .. } => ErrorCode::InvalidRequest,
+            Self::Timeout { .. } => ErrorCode::Timeout,
+            Self::Serialization { .. } => ErrorCode::Internal,
+        }
+    }
+}
+
 impl From<StreamingError> for ApiError {
     fn from(err: StreamingError) -> Self {
         match err {
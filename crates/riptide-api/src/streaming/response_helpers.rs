@@ -159,7 +159,7 @@
 //! ```
 
 use axum::{
-    http::{HeaderMap, HeaderValue, StatusCode},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     response::Response,
 };
 use futures_util::{stream::Stream, StreamExt};
@@ -181,6 +181,19 @@ pub enum StreamingResponseType {
 }
 
 impl StreamingResponseType {
+    /// Pick SSE when the request accepts `text/event-stream`, NDJSON otherwise
+    pub fn negotiate(request_headers: &HeaderMap) -> Self {
+        let wants_sse = request_headers
+            .get(header::ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.contains("text/event-stream"));
+        if wants_sse {
+            StreamingResponseType::Sse
+        } else {
+            StreamingResponseType::Ndjson
+        }
+    }
+
     /// Get the content type for this response type
     pub fn content_type(&self) -> &'static str {
         match self {
@@ -619,6 +632,23 @@ mod tests {
     use axum::body::to_bytes;
     use serde_json::Value;
 
+    #[test]
+    fn test_negotiate_response_type() {
+        let mut headers = HeaderMap::new();
+        assert!(matches!(
+            StreamingResponseType::negotiate(&headers),
+            StreamingResponseType::Ndjson
+        ));
+        headers.insert(
+            header::ACCEPT,
+            HeaderValue::from_static("text/event-stream"),
+        );
+        assert!(matches!(
+            StreamingResponseType::negotiate(&headers),
+            StreamingResponseType::Sse
+        ));
+    }
+
     #[test]
     fn test_streaming_response_types() {
         assert_eq!(
@@ -194,7 +194,9 @@ impl TelemetryConfig {
         // Initialize tracing subscriber with OpenTelemetry
         tracing_subscriber::registry()
             .with(tracing_subscriber::EnvFilter::from_default_env())
-            .with(tracing_subscriber::fmt::layer())
+            .with(tracing_subscriber::fmt::layer().with_writer(
+                crate::log_redaction::RedactingMakeWriter::new(std::io::stdout),
+            ))
             .with(telemetry_layer)
             .try_init()?;
 
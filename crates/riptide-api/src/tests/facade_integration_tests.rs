@@ -16,6 +16,8 @@ use crate::config::RiptideApiConfig;
 use crate::context::ApplicationContext;
 #[cfg(feature = "browser")]
 use crate::handlers::browser::CreateSessionRequest;
+#[cfg(feature = "extraction")]
+use crate::handlers::shared::policy::{RequestTenant, DEFAULT_TENANT};
 use crate::health::HealthChecker;
 use crate::state::AppConfig;
 use anyhow::Result;
@@ -270,6 +272,8 @@ async fn test_extract_request_validation() {
         url: "not-a-valid-url".to_string(),
         mode: "standard".to_string(),
         options: ExtractOptions::default(),
+        pipeline_hints: None,
+        budget: None,
     };
 
     let json_str = serde_json::to_string(&invalid_request).unwrap();
@@ -337,9 +341,12 @@ async fn test_extract_handler_with_mock_server() {
         url: format!("{}/test-page", mock_server.uri()),
         mode: "standard".to_string(),
         options: ExtractOptions::default(),
+        pipeline_hints: None,
+        budget: None,
     };
 
-    let _result = crate::handlers::extract::extract(State(state), Json(request)).await;
+    let tenant = RequestTenant(riptide_security::TenantId(DEFAULT_TENANT.to_string()));
+    let _result = crate::handlers::extract::extract(State(state), tenant, Json(request)).await;
 
     // Should succeed with extracted content
     // Note: Actual extraction requires WASM module, so we verify request handling
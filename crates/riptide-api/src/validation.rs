@@ -1,7 +1,12 @@
 #![allow(dead_code)]
 use crate::errors::{ApiError, ApiResult};
-use crate::models::{CrawlBody, DeepSearchBody};
+use crate::models::{
+    CrawlBody, CrawlOptions, DeepSearchBody, DiffBody, IngestBody, RetryFailedBody,
+};
+use regex::Regex;
 use riptide_config::CommonValidator;
+use riptide_stealth::StealthPreset;
+use riptide_types::{CrawlBudget, CrawlScope, PipelineHints};
 
 /// Maximum number of URLs allowed in a single crawl request
 const MAX_URLS_PER_REQUEST: usize = 100;
@@ -12,6 +17,9 @@ const MAX_QUERY_LENGTH: usize = 500;
 /// Maximum search result limit
 const MAX_SEARCH_LIMIT: u32 = 50;
 
+/// Maximum length for a `translate_to` language tag
+const MAX_LANGUAGE_TAG_LENGTH: usize = 35;
+
 /// Input validation for API requests to ensure data integrity and security.
 ///
 /// This module provides comprehensive validation for all API endpoints,
@@ -64,9 +72,62 @@ pub fn validate_crawl_request(body: &CrawlBody) -> ApiResult<()> {
         validate_url(url_str, index)?;
     }
 
+    if let Some(options) = &body.options {
+        validate_crawl_options(options)?;
+    }
+
+    Ok(())
+}
+
+/// Validate crawl options: translation target, pipeline hints, budget and
+/// scope.
+pub fn validate_crawl_options(options: &CrawlOptions) -> ApiResult<()> {
+    if let Some(language) = options.translate_to.as_deref() {
+        validate_language_tag(language)?;
+    }
+
+    if let Some(hints) = &options.pipeline_hints {
+        validate_pipeline_hints(hints)?;
+    }
+
+    if let Some(budget) = &options.budget {
+        if options.use_spider.unwrap_or(false) {
+            return Err(ApiError::validation(
+                "budget is not supported with use_spider; spider crawls use their own budgets",
+            ));
+        }
+        validate_crawl_budget(budget)?;
+    }
+    if let Some(scope) = &options.scope {
+        if !options.use_spider.unwrap_or(false) {
+            return Err(ApiError::validation(
+                "scope is only supported with use_spider",
+            ));
+        }
+        validate_crawl_scope(scope)?;
+    }
+
     Ok(())
 }
 
+/// Validate a crawl retry request payload.
+///
+/// The retryable URLs of the failure manifest and the retry's options go
+/// through the same checks as a crawl request, so a retry cannot carry
+/// anything `/crawl` would reject.
+pub fn validate_retry_request(body: &RetryFailedBody) -> ApiResult<()> {
+    let urls = body.retryable_urls();
+    if urls.is_empty() {
+        return Err(ApiError::validation(
+            "Failure manifest contains no retryable URLs",
+        ));
+    }
+    validate_crawl_request(&CrawlBody {
+        urls,
+        options: body.options.clone(),
+    })
+}
+
 /// Validate a deep search request payload.
 ///
 /// Checks:
@@ -114,6 +175,87 @@ pub fn validate_deepsearch_request(body: &DeepSearchBody) -> ApiResult<()> {
     Ok(())
 }
 
+/// Validate an ingest request payload.
+///
+/// Nothing is fetched from the URL, so unlike crawl requests private and
+/// internal hosts are accepted; the URL only has to be an absolute http(s)
+/// URL. Exactly one body field must be set, the status must be a valid
+/// HTTP status and `options` must pass the `/crawl` checks. Spider, budget
+/// and scope options are rejected: a single pushed page has no crawl.
+pub fn validate_ingest_request(body: &IngestBody) -> ApiResult<()> {
+    let parsed = url::Url::parse(&body.url)
+        .map_err(|e| ApiError::invalid_url(&body.url, format!("Invalid URL: {}", e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(ApiError::invalid_url(
+            &body.url,
+            "Ingested URLs must be absolute http(s) URLs",
+        ));
+    }
+
+    match (&body.content, &body.content_base64) {
+        (Some(_), Some(_)) => {
+            return Err(ApiError::validation(
+                "Set only one of content and content_base64",
+            ))
+        }
+        (None, None) => {
+            return Err(ApiError::validation(
+                "Either content or content_base64 is required",
+            ))
+        }
+        _ => {}
+    }
+
+    if !(100..=599).contains(&body.status) {
+        return Err(ApiError::validation(format!(
+            "Invalid HTTP status: {}",
+            body.status
+        )));
+    }
+
+    if let Some(options) = &body.options {
+        if options.use_spider.unwrap_or(false)
+            || options.budget.is_some()
+            || options.scope.is_some()
+        {
+            return Err(ApiError::validation(
+                "use_spider, budget and scope are not supported for ingested content",
+            ));
+        }
+        validate_crawl_options(options)?;
+    }
+
+    Ok(())
+}
+
+/// Validate a diff request: two documents, or two HTML snapshots of a URL.
+pub fn validate_diff_request(body: &DiffBody) -> ApiResult<()> {
+    let documents = body.old.is_some() || body.new.is_some();
+    let snapshots = body.url.is_some() || body.old_html.is_some() || body.new_html.is_some();
+    if documents == snapshots {
+        return Err(ApiError::validation(
+            "Set either old and new, or url, old_html and new_html",
+        ));
+    }
+    if documents {
+        if body.old.is_none() || body.new.is_none() {
+            return Err(ApiError::validation("Both old and new are required"));
+        }
+        return Ok(());
+    }
+
+    if body.old_html.is_none() || body.new_html.is_none() {
+        return Err(ApiError::validation(
+            "Both old_html and new_html are required",
+        ));
+    }
+    let Some(url) = &body.url else {
+        return Err(ApiError::validation("url is required with HTML snapshots"));
+    };
+    url::Url::parse(url).map_err(|e| ApiError::invalid_url(url, format!("Invalid URL: {}", e)))?;
+    Ok(())
+}
+
 /// Validate a single URL for crawling using common validation patterns.
 ///
 /// Uses the common validation module for consistent URL validation across
@@ -169,6 +311,104 @@ fn validate_url_patterns(url_str: &str, index: usize) -> ApiResult<()> {
     Ok(())
 }
 
+/// Validate a language tag such as `en` or `pt-BR`.
+fn validate_language_tag(language: &str) -> ApiResult<()> {
+    let valid = !language.is_empty()
+        && language.len() <= MAX_LANGUAGE_TAG_LENGTH
+        && language
+            .split('-')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()));
+    if valid {
+        Ok(())
+    } else {
+        Err(ApiError::validation(format!(
+            "Invalid translate_to language '{}' (expected a tag like 'en' or 'pt-BR')",
+            language
+        )))
+    }
+}
+
+/// Validate the values of per-request pipeline hints.
+///
+/// Whether the tenant may use them is checked separately against its crawl
+/// policy.
+pub fn validate_pipeline_hints(hints: &PipelineHints) -> ApiResult<()> {
+    if let Some(preset) = hints.stealth_preset.as_deref() {
+        if parse_stealth_preset(preset).is_none() {
+            return Err(ApiError::validation(format!(
+                "Invalid stealth_preset '{}' (expected none, low, medium or high)",
+                preset
+            )));
+        }
+    }
+    if let Some(budget) = hints.max_budget_usd {
+        if !budget.is_finite() || budget < 0.0 {
+            return Err(ApiError::validation(format!(
+                "Invalid max_budget_usd {} (expected a non-negative amount)",
+                budget
+            )));
+        }
+    }
+    if hints
+        .extraction_schema
+        .as_deref()
+        .is_some_and(|schema| schema.trim().is_empty())
+    {
+        return Err(ApiError::validation("extraction_schema must not be empty"));
+    }
+    Ok(())
+}
+
+/// Validate the limits of a per-crawl budget.
+pub fn validate_crawl_budget(budget: &CrawlBudget) -> ApiResult<()> {
+    let seconds = [
+        ("max_wall_time_secs", budget.max_wall_time_secs),
+        ("max_render_secs", budget.max_render_secs),
+    ];
+    for (name, value) in seconds {
+        if let Some(secs) = value {
+            if !secs.is_finite() || secs < 0.0 {
+                return Err(ApiError::validation(format!(
+                    "Invalid {} {} (expected a non-negative number of seconds)",
+                    name, secs
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Validate the URL patterns and page cap of a spider crawl scope
+pub fn validate_crawl_scope(scope: &CrawlScope) -> ApiResult<()> {
+    for (field, patterns) in [("include", &scope.include), ("exclude", &scope.exclude)] {
+        for pattern in patterns {
+            if let Err(e) = Regex::new(pattern) {
+                return Err(ApiError::validation(format!(
+                    "Invalid scope.{} pattern '{}': {}",
+                    field, pattern, e
+                )));
+            }
+        }
+    }
+    if scope.max_pages == Some(0) {
+        return Err(ApiError::validation(
+            "scope.max_pages must be greater than 0",
+        ));
+    }
+    Ok(())
+}
+
+/// Stealth preset named by a pipeline hint, case-insensitively
+pub fn parse_stealth_preset(name: &str) -> Option<StealthPreset> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "none" => Some(StealthPreset::None),
+        "low" => Some(StealthPreset::Low),
+        "medium" => Some(StealthPreset::Medium),
+        "high" => Some(StealthPreset::High),
+        _ => None,
+    }
+}
+
 /// Validate search query content using common validation patterns.
 fn validate_query_content(query: &str) -> ApiResult<()> {
     let validator = CommonValidator::new_default();
@@ -241,6 +481,221 @@ mod tests {
         assert!(validate_crawl_request(&body).is_err());
     }
 
+    #[test]
+    fn test_translate_to_language_tag() {
+        let body = |language: &str| CrawlBody {
+            urls: vec!["https://example.com".to_string()],
+            options: Some(crate::models::CrawlOptions {
+                translate_to: Some(language.to_string()),
+                ..Default::default()
+            }),
+        };
+
+        assert!(validate_crawl_request(&body("en")).is_ok());
+        assert!(validate_crawl_request(&body("pt-BR")).is_ok());
+        assert!(validate_crawl_request(&body("")).is_err());
+        assert!(validate_crawl_request(&body("en--US")).is_err());
+        assert!(validate_crawl_request(&body("en; drop")).is_err());
+    }
+
+    #[test]
+    fn test_pipeline_hints() {
+        let body = |hints: PipelineHints| CrawlBody {
+            urls: vec!["https://example.com".to_string()],
+            options: Some(crate::models::CrawlOptions {
+                pipeline_hints: Some(hints),
+                ..Default::default()
+            }),
+        };
+
+        let valid = PipelineHints {
+            force_headless: true,
+            stealth_preset: Some("High".to_string()),
+            max_budget_usd: Some(0.0),
+            ..Default::default()
+        };
+        assert!(validate_crawl_request(&body(valid)).is_ok());
+        assert!(validate_crawl_request(&body(PipelineHints {
+            stealth_preset: Some("maximum".to_string()),
+            ..Default::default()
+        }))
+        .is_err());
+        assert!(validate_crawl_request(&body(PipelineHints {
+            max_budget_usd: Some(-1.0),
+            ..Default::default()
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn test_crawl_budget() {
+        let body = |budget: CrawlBudget, use_spider: Option<bool>| CrawlBody {
+            urls: vec!["https://example.com".to_string()],
+            options: Some(crate::models::CrawlOptions {
+                budget: Some(budget),
+                use_spider,
+                ..Default::default()
+            }),
+        };
+
+        let valid = CrawlBudget {
+            max_wall_time_secs: Some(30.0),
+            max_bytes: Some(10_000_000),
+            ..Default::default()
+        };
+        assert!(validate_crawl_request(&body(valid.clone(), None)).is_ok());
+        assert!(validate_crawl_request(&body(valid, Some(true))).is_err());
+        assert!(validate_crawl_request(&body(
+            CrawlBudget {
+                max_render_secs: Some(f64::NAN),
+                ..Default::default()
+            },
+            None
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn test_crawl_scope() {
+        let body = |include: &str, use_spider: Option<bool>| CrawlBody {
+            urls: vec!["https://example.com".to_string()],
+            options: Some(crate::models::CrawlOptions {
+                scope: Some(CrawlScope {
+                    include: vec![include.to_string()],
+                    ..Default::default()
+                }),
+                use_spider,
+                ..Default::default()
+            }),
+        };
+
+        assert!(validate_crawl_request(&body("/docs/", Some(true))).is_ok());
+        assert!(validate_crawl_request(&body("/docs/", None)).is_err());
+        assert!(validate_crawl_request(&body("(", Some(true))).is_err());
+    }
+
+    #[test]
+    fn test_ingest_request() {
+        let body = |url: &str, content: Option<&str>, base64: Option<&str>| IngestBody {
+            url: url.to_string(),
+            content: content.map(str::to_string),
+            content_base64: base64.map(str::to_string),
+            content_type: None,
+            status: 200,
+            headers: Default::default(),
+            fetched_at: None,
+            options: None,
+        };
+
+        assert!(validate_ingest_request(&body("https://example.com", Some("<p>"), None)).is_ok());
+        // Nothing is fetched, so internal hosts are fine
+        assert!(
+            validate_ingest_request(&body("http://intranet:8080/a", Some("<p>"), None)).is_ok()
+        );
+        assert!(validate_ingest_request(&body("ftp://example.com", Some("<p>"), None)).is_err());
+        assert!(validate_ingest_request(&body("/relative", Some("<p>"), None)).is_err());
+        assert!(validate_ingest_request(&body("https://example.com", None, None)).is_err());
+        assert!(
+            validate_ingest_request(&body("https://example.com", Some("<p>"), Some("PHA+")))
+                .is_err()
+        );
+
+        let mut invalid_status = body("https://example.com", Some("<p>"), None);
+        invalid_status.status = 42;
+        assert!(validate_ingest_request(&invalid_status).is_err());
+
+        let with_options = |options: crate::models::CrawlOptions| IngestBody {
+            options: Some(options),
+            ..body("https://example.com", Some("<p>"), None)
+        };
+        assert!(
+            validate_ingest_request(&with_options(crate::models::CrawlOptions {
+                translate_to: Some("pt-BR".to_string()),
+                ..Default::default()
+            }))
+            .is_ok()
+        );
+        assert!(
+            validate_ingest_request(&with_options(crate::models::CrawlOptions {
+                translate_to: Some("en; drop".to_string()),
+                ..Default::default()
+            }))
+            .is_err()
+        );
+        assert!(
+            validate_ingest_request(&with_options(crate::models::CrawlOptions {
+                use_spider: Some(true),
+                ..Default::default()
+            }))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_diff_request() {
+        let snapshots = |url: Option<&str>, new_html: Option<&str>| DiffBody {
+            old: None,
+            new: None,
+            url: url.map(str::to_string),
+            old_html: Some("<html></html>".to_string()),
+            new_html: new_html.map(str::to_string),
+        };
+        let html = Some("<html></html>");
+        assert!(validate_diff_request(&snapshots(Some("https://example.com"), html)).is_ok());
+        assert!(validate_diff_request(&snapshots(Some("https://example.com"), None)).is_err());
+        assert!(validate_diff_request(&snapshots(None, html)).is_err());
+        assert!(validate_diff_request(&snapshots(Some("not a url"), html)).is_err());
+
+        let doc = Some(crate::models::ExtractedDoc::default());
+        let mut documents = snapshots(None, None);
+        documents.old_html = None;
+        documents.old = doc.clone();
+        assert!(validate_diff_request(&documents).is_err());
+        documents.new = doc;
+        assert!(validate_diff_request(&documents).is_ok());
+        documents.url = Some("https://example.com".to_string());
+        assert!(validate_diff_request(&documents).is_err());
+    }
+
+    #[test]
+    fn test_retry_request() {
+        use crate::models::CrawlFailure;
+        use riptide_types::ErrorCode;
+
+        let failure = |url: &str, retryable: bool| CrawlFailure {
+            url: url.to_string(),
+            code: ErrorCode::FetchFailed,
+            retryable,
+            retry_after_secs: None,
+        };
+        let body = RetryFailedBody {
+            failures: vec![failure("https://example.com", true)],
+            options: None,
+        };
+        assert!(validate_retry_request(&body).is_ok());
+
+        let body = RetryFailedBody {
+            failures: vec![failure("https://example.com", false)],
+            options: None,
+        };
+        assert!(validate_retry_request(&body).is_err());
+
+        let body = RetryFailedBody {
+            failures: vec![failure("http://localhost:8080", true)],
+            options: None,
+        };
+        assert!(validate_retry_request(&body).is_err());
+
+        let body = RetryFailedBody {
+            failures: vec![failure("https://example.com", true)],
+            options: Some(crate::models::CrawlOptions {
+                translate_to: Some("en; drop".to_string()),
+                ..Default::default()
+            }),
+        };
+        assert!(validate_retry_request(&body).is_err());
+    }
+
     #[test]
     fn test_valid_deepsearch() {
         let body = DeepSearchBody {
@@ -242,6 +242,49 @@ fn test_search_config_from_env() {
     );
 }
 
+#[test]
+#[serial]
+fn test_drain_config_from_env() {
+    with_env_vars(
+        vec![
+            ("RIPTIDE_DRAIN_TIMEOUT_SECS", "90"),
+            ("RIPTIDE_DRAIN_RETRY_AFTER_SECS", "15"),
+            (
+                "RIPTIDE_DRAIN_CHECKPOINT_DIR",
+                "/var/lib/riptide/checkpoints",
+            ),
+        ],
+        || {
+            let config = RiptideApiConfig::from_env();
+            assert_eq!(config.drain.timeout_secs, 90);
+            assert_eq!(config.drain.retry_after_secs, 15);
+            assert_eq!(config.drain.checkpoint_dir, "/var/lib/riptide/checkpoints");
+        },
+    );
+}
+
+#[test]
+#[serial]
+fn test_boot_config_from_env() {
+    with_env_vars(
+        vec![
+            ("RIPTIDE_BOOT_MAX_ATTEMPTS", "10"),
+            ("RIPTIDE_BOOT_INITIAL_BACKOFF_MS", "250"),
+            ("RIPTIDE_BOOT_MAX_BACKOFF_MS", "4000"),
+            ("RIPTIDE_BOOT_ALLOW_DEGRADED", "true"),
+            ("RIPTIDE_BOOT_RECOVERY_INTERVAL_SECS", "60"),
+        ],
+        || {
+            let config = RiptideApiConfig::from_env();
+            assert_eq!(config.boot.max_attempts, 10);
+            assert_eq!(config.boot.initial_backoff_ms, 250);
+            assert_eq!(config.boot.max_backoff_ms, 4000);
+            assert!(config.boot.allow_degraded);
+            assert_eq!(config.boot.recovery_interval_secs, 60);
+        },
+    );
+}
+
 #[test]
 fn test_default_config_when_no_env_vars() {
     // Clear any existing env vars
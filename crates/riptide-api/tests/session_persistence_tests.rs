@@ -54,6 +54,8 @@ async fn test_session_persistence_integration() {
         actions: vec![],
         wait_for: None,
         scroll: None,
+        auto_scroll: None,
+        iframes: None,
         capture_artifacts: false,
         timeout: Duration::from_secs(3),
         viewport: None,
@@ -1,4 +1,4 @@
-use riptide_api::errors::{ApiError, ApiResult};
+use riptide_api::errors::{ApiError, ApiResult, TimeoutPhase};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use serde_json::Value;
@@ -99,7 +99,12 @@ mod api_error_tests {
         let error = ApiError::timeout("http_request", "Request took too long");
 
         match error {
-            ApiError::TimeoutError { operation, message } => {
+            ApiError::TimeoutError {
+                operation,
+                phase,
+                message,
+            } => {
+                assert_eq!(phase, TimeoutPhase::Other);
                 assert_eq!(operation, "http_request");
                 assert_eq!(message, "Request took too long");
             }
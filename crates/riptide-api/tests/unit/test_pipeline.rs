@@ -1,5 +1,5 @@
-use riptide_api::pipeline::{PipelineResult, PipelineStats, GateDecisionStats};
-use riptide_core::types::{ExtractedDoc, CrawlOptions};
+use riptide_api::pipeline::{CacheStatus, GateDecisionStats, PipelineResult, PipelineStats};
+use riptide_core::types::{CrawlOptions, ExtractedDoc};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
@@ -23,11 +23,14 @@ mod pipeline_result_tests {
         let result = PipelineResult {
             document: doc.clone(),
             from_cache: false,
+            cache_status: CacheStatus::Miss,
             gate_decision: "raw".to_string(),
             quality_score: 0.8,
             processing_time_ms: 150,
             cache_key: "test_key".to_string(),
             http_status: 200,
+            artifacts: Vec::new(),
+            timings: Default::default(),
         };
 
         assert_eq!(result.document.url, "https://example.com");
@@ -55,11 +58,14 @@ mod pipeline_result_tests {
         let result = PipelineResult {
             document: doc,
             from_cache: true,
+            cache_status: CacheStatus::Hit,
             gate_decision: "cached".to_string(),
             quality_score: 1.0,
             processing_time_ms: 5,
             cache_key: "cache_123".to_string(),
             http_status: 304,
+            artifacts: Vec::new(),
+            timings: Default::default(),
         };
 
         let debug_str = format!("{:?}", result);
@@ -84,11 +90,14 @@ mod pipeline_result_tests {
         let original = PipelineResult {
             document: doc,
             from_cache: false,
+            cache_status: CacheStatus::Miss,
             gate_decision: "probes_first".to_string(),
             quality_score: 0.65,
             processing_time_ms: 250,
             cache_key: "clone_key".to_string(),
             http_status: 200,
+            artifacts: Vec::new(),
+            timings: Default::default(),
         };
 
         let cloned = original.clone();
@@ -120,11 +129,14 @@ mod pipeline_result_tests {
         let result = PipelineResult {
             document: doc,
             from_cache: true,
+            cache_status: CacheStatus::Hit,
             gate_decision: "headless".to_string(),
             quality_score: 0.92,
             processing_time_ms: 500,
             cache_key: "serialize_key".to_string(),
             http_status: 200,
+            artifacts: Vec::new(),
+            timings: Default::default(),
         };
 
         // Test serialization
@@ -564,11 +576,14 @@ mod pipeline_property_tests {
             let result = PipelineResult {
                 document: doc,
                 from_cache: false,
+                cache_status: CacheStatus::Miss,
                 gate_decision: "raw".to_string(),
                 quality_score: score,
                 processing_time_ms: 100,
                 cache_key: "test".to_string(),
                 http_status: 200,
+                artifacts: Vec::new(),
+                timings: Default::default(),
             };
 
             // Quality score should be within valid bounds
@@ -583,4 +598,4 @@ mod pipeline_property_tests {
         cache_mode.hash(&mut hasher);
         format!("riptide:v1:{}:{:x}", cache_mode, hasher.finish())
     }
-}
\ No newline at end of file
+}
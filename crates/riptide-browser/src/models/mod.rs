@@ -29,6 +29,125 @@ pub struct RenderReq {
 
     /// Stealth configuration for anti-detection
     pub stealth_config: Option<StealthConfig>,
+
+    /// Iframe handling; iframes are left untouched when unset
+    #[serde(default)]
+    pub iframes: Option<IframeConfig>,
+}
+
+/// Iframe handling for rendering and extraction
+///
+/// Same-origin iframes are readable from the parent page and are inlined
+/// into the rendered document in place of the `<iframe>` element, wrapped in
+/// a `div` carrying `data-riptide-frame` (frame path) and
+/// `data-riptide-frame-src` attributes. Cross-origin iframes can only be
+/// captured by rendering their `src` separately, which is opt-in.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct IframeConfig {
+    /// Inline same-origin iframe content into the rendered document
+    pub inline_same_origin: bool,
+
+    /// Render cross-origin iframes on their own page and return them as sub-documents
+    pub render_cross_origin: bool,
+
+    /// Maximum number of frames processed per page, across all nesting levels
+    pub max_frames: u32,
+
+    /// Maximum nesting depth followed into same-origin frames
+    pub max_depth: u32,
+
+    /// Time budget for rendering each cross-origin frame
+    pub frame_timeout_ms: u64,
+}
+
+impl Default for IframeConfig {
+    fn default() -> Self {
+        Self {
+            inline_same_origin: true,
+            render_cross_origin: false,
+            max_frames: 10,
+            max_depth: 3,
+            frame_timeout_ms: 2000,
+        }
+    }
+}
+
+impl IframeConfig {
+    /// Decide how a discovered frame is handled
+    pub fn disposition(&self, same_origin: bool, src: &str) -> FrameDisposition {
+        let renderable = src.starts_with("http://") || src.starts_with("https://");
+        if same_origin && self.inline_same_origin {
+            FrameDisposition::Inlined
+        } else if !same_origin && self.render_cross_origin && renderable {
+            FrameDisposition::SubDocument
+        } else {
+            FrameDisposition::Skipped
+        }
+    }
+
+    /// Worst-case time spent on frames, on top of the regular render time
+    pub fn time_budget_ms(&self) -> u64 {
+        let cross_origin = if self.render_cross_origin {
+            self.frame_timeout_ms
+                .saturating_mul(u64::from(self.max_frames))
+        } else {
+            0
+        };
+        FRAME_SCAN_TIMEOUT_MS.saturating_add(cross_origin)
+    }
+}
+
+/// Time allowed for discovering and inlining frames in the main page
+pub const FRAME_SCAN_TIMEOUT_MS: u64 = 1000;
+
+/// How an iframe ended up in the render result
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameDisposition {
+    /// Content was inlined into the parent document
+    Inlined,
+    /// Frame was rendered separately and returned as its own document
+    SubDocument,
+    /// Frame was recorded but its content not captured
+    Skipped,
+}
+
+impl FrameDisposition {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FrameDisposition::Inlined => "inlined",
+            FrameDisposition::SubDocument => "sub_document",
+            FrameDisposition::Skipped => "skipped",
+        }
+    }
+}
+
+/// Provenance of a single iframe found while rendering
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FrameProvenance {
+    /// Position in the frame tree, e.g. `0` or `0.2` for the third frame inside the first
+    pub path: String,
+
+    /// Resolved frame URL (`about:srcdoc` / `about:blank` for inline frames)
+    pub src: String,
+
+    /// Nesting depth, 1 for frames of the main page
+    pub depth: u32,
+
+    /// Whether the frame document was readable from its parent
+    pub same_origin: bool,
+
+    /// How the frame content was handled
+    pub disposition: FrameDisposition,
+
+    /// Rendered HTML of a cross-origin sub-document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
+
+    /// Why a sub-document could not be captured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 /// Timeout configurations for rendering
@@ -70,6 +189,17 @@ pub enum PageAction {
         delay_ms: u64,
     },
 
+    /// Scroll until infinite scroll / lazy-loaded content stops growing
+    AutoScroll {
+        max_cycles: u32,
+        wait_ms: u64,
+        stable_cycles: u32,
+        max_duration_ms: u64,
+        max_height_px: Option<u64>,
+        item_selector: Option<String>,
+        max_items: Option<u64>,
+    },
+
     /// Execute JavaScript code
     Js { code: String },
 
@@ -112,6 +242,10 @@ pub struct RenderResp {
 
     /// Captured artifacts
     pub artifacts: ArtifactsOut,
+
+    /// Iframes found on the page, when iframe handling was requested
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub frames: Vec<FrameProvenance>,
 }
 
 /// Output artifacts
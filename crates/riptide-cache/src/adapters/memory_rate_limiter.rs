@@ -0,0 +1,127 @@
+//! In-memory rate limiter adapter implementing RateLimiter port.
+//!
+//! Counts requests per tenant in fixed windows, like `RedisRateLimiter`, but
+//! only within this process. Used when Redis is not configured or not yet
+//! reachable, so quotas are still enforced per instance instead of not at all.
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+use riptide_types::{
+    error::riptide_error::RiptideError,
+    ports::rate_limit::{RateLimiter, Result},
+};
+
+/// Tenants tracked before expired windows are swept
+const SWEEP_THRESHOLD: usize = 10_000;
+
+/// Process-local rate limiter with a fixed window per tenant
+pub struct InMemoryRateLimiter {
+    windows: DashMap<String, (Instant, usize)>,
+    max_requests: usize,
+    window_duration: Duration,
+}
+
+impl InMemoryRateLimiter {
+    /// Create a new in-memory rate limiter
+    ///
+    /// # Arguments
+    /// * `max_requests` - Maximum requests allowed per window
+    /// * `window_duration` - Time window for rate limiting
+    pub fn new(max_requests: usize, window_duration: Duration) -> Self {
+        Self {
+            windows: DashMap::new(),
+            max_requests,
+            window_duration,
+        }
+    }
+
+    /// Requests counted for `tenant_id` in the current window
+    fn count(&self, tenant_id: &str) -> usize {
+        self.windows
+            .get(tenant_id)
+            .filter(|entry| entry.0.elapsed() < self.window_duration)
+            .map(|entry| entry.1)
+            .unwrap_or(0)
+    }
+
+    /// Drop tenants whose window has ended so idle tenants don't accumulate
+    fn sweep(&self) {
+        if self.windows.len() >= SWEEP_THRESHOLD {
+            self.windows
+                .retain(|_, (started, _)| started.elapsed() < self.window_duration);
+        }
+    }
+}
+
+#[async_trait]
+impl RateLimiter for InMemoryRateLimiter {
+    async fn check_quota(&self, tenant_id: &str) -> Result<()> {
+        let count = self.count(tenant_id);
+
+        if count >= self.max_requests {
+            debug!(
+                tenant_id = %tenant_id,
+                count = count,
+                max = self.max_requests,
+                "Rate limit quota exceeded"
+            );
+            Err(RiptideError::RateLimitExceeded {
+                tenant_id: tenant_id.to_string(),
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn consume(&self, tenant_id: &str, amount: usize) -> Result<()> {
+        self.check_quota(tenant_id).await?;
+        self.sweep();
+
+        let mut entry = self
+            .windows
+            .entry(tenant_id.to_string())
+            .or_insert_with(|| (Instant::now(), 0));
+        if entry.0.elapsed() >= self.window_duration {
+            *entry = (Instant::now(), 0);
+        }
+        entry.1 = entry.1.saturating_add(amount);
+
+        Ok(())
+    }
+
+    async fn reset(&self, tenant_id: &str) -> Result<()> {
+        self.windows.remove(tenant_id);
+        Ok(())
+    }
+
+    async fn get_remaining(&self, tenant_id: &str) -> Result<usize> {
+        Ok(self.max_requests.saturating_sub(self.count(tenant_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_quota_enforced_per_tenant_and_window() {
+        let limiter = InMemoryRateLimiter::new(2, Duration::from_millis(50));
+
+        limiter.consume("a", 1).await.unwrap();
+        limiter.consume("a", 1).await.unwrap();
+        assert!(limiter.consume("a", 1).await.is_err());
+        assert_eq!(limiter.get_remaining("a").await.unwrap(), 0);
+        assert_eq!(limiter.get_remaining("b").await.unwrap(), 2);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(limiter.check_quota("a").await.is_ok());
+        limiter.consume("a", 1).await.unwrap();
+        assert_eq!(limiter.get_remaining("a").await.unwrap(), 1);
+
+        limiter.reset("a").await.unwrap();
+        assert_eq!(limiter.get_remaining("a").await.unwrap(), 2);
+    }
+}
@@ -8,6 +8,7 @@
 //! - `redis_idempotency`: Redis implementation of `IdempotencyStore`
 //! - `redis_session_storage`: Redis implementation of `SessionStorage`
 //! - `redis_rate_limiter`: Redis implementation of rate limiting
+//! - `memory_rate_limiter`: In-memory implementation of rate limiting (single-process only)
 //! - `redis_coordination`: Redis implementation of `DistributedCoordination`
 //! - `memory_coordination`: In-memory implementation of `DistributedCoordination` (single-process only)
 //! - `standard_circuit_breaker`: Standard lock-free circuit breaker adapter
@@ -20,6 +21,7 @@ pub mod redis_idempotency;
 pub mod redis_session_storage;
 
 // Sprint 4.4: Rate limiting adapter
+pub mod memory_rate_limiter;
 pub mod redis_rate_limiter;
 
 // Distributed coordination adapters
@@ -37,6 +39,7 @@ pub use redis_idempotency::RedisIdempotencyStore;
 #[cfg(feature = "idempotency")]
 pub use redis_session_storage::RedisSessionStorage;
 
+pub use memory_rate_limiter::InMemoryRateLimiter;
 pub use redis_rate_limiter::{RedisPerHostRateLimiter, RedisRateLimiter};
 
 // Distributed coordination exports
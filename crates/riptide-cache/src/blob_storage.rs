@@ -0,0 +1,160 @@
+//! Local filesystem adapter implementing the BlobStorage trait
+//!
+//! Stores each blob as a file under a root directory, mirroring the key's
+//! `/`-separated path. Blob URLs are `<base_url>/<key>`; with the default base
+//! of `/artifacts` they are served by the API's artifact route.
+//!
+//! Content types are derived from the file extension on read, so keys should
+//! carry one (`full.png`, `thumbnail.webp`).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use riptide_cache::LocalBlobStorage;
+//! use riptide_types::ports::BlobStorage;
+//!
+//! let storage = LocalBlobStorage::new("data/artifacts", "/artifacts");
+//! let blob = storage.put("screenshots/abc/full.png", &png, "image/png").await?;
+//! assert_eq!(blob.url, "/artifacts/screenshots/abc/full.png");
+//! ```
+
+use async_trait::async_trait;
+use riptide_types::error::Result as RiptideResult;
+use riptide_types::ports::{validate_blob_key, BlobStorage, StoredBlob};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Distinguishes temporary files of concurrent writes to the same key
+static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Filesystem-backed blob storage
+#[derive(Debug, Clone)]
+pub struct LocalBlobStorage {
+    root: PathBuf,
+    base_url: String,
+}
+
+impl LocalBlobStorage {
+    /// Create storage rooted at `root`, publishing URLs under `base_url`
+    pub fn new(root: impl Into<PathBuf>, base_url: impl Into<String>) -> Self {
+        Self {
+            root: root.into(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Root directory blobs are written to
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path_for(&self, key: &str) -> RiptideResult<PathBuf> {
+        validate_blob_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+/// MIME type for a key based on its extension
+fn content_type_for(key: &str) -> &'static str {
+    match key.rsplit('.').next().map(|ext| ext.to_ascii_lowercase()) {
+        Some(ext) if ext == "png" => "image/png",
+        Some(ext) if ext == "webp" => "image/webp",
+        Some(ext) if ext == "jpg" || ext == "jpeg" => "image/jpeg",
+        Some(ext) if ext == "pdf" => "application/pdf",
+        Some(ext) if ext == "html" => "text/html",
+        Some(ext) if ext == "json" => "application/json",
+        _ => "application/octet-stream",
+    }
+}
+
+#[async_trait]
+impl BlobStorage for LocalBlobStorage {
+    async fn put(&self, key: &str, data: &[u8], content_type: &str) -> RiptideResult<StoredBlob> {
+        let path = self.path_for(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        // Write to a temporary file and rename so readers never see partial blobs
+        let tmp = path.with_extension(format!(
+            "tmp-{}-{}",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        tokio::fs::write(&tmp, data).await?;
+        tokio::fs::rename(&tmp, &path).await?;
+
+        Ok(StoredBlob {
+            key: key.to_string(),
+            url: self.url_for(key),
+            content_type: content_type.to_string(),
+            size_bytes: data.len() as u64,
+        })
+    }
+
+    async fn get(&self, key: &str) -> RiptideResult<Option<(Vec<u8>, String)>> {
+        let path = self.path_for(key)?;
+        match tokio::fs::read(&path).await {
+            Ok(data) => Ok(Some((data, content_type_for(key).to_string()))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> RiptideResult<()> {
+        let path = self.path_for(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn url_for(&self, key: &str) -> String {
+        format!("{}/{}", self.base_url, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = LocalBlobStorage::new(dir.path(), "/artifacts/");
+
+        let blob = storage
+            .put("screenshots/abc/full.png", b"png", "image/png")
+            .await
+            .unwrap();
+        assert_eq!(blob.url, "/artifacts/screenshots/abc/full.png");
+        assert!(dir.path().join("screenshots/abc/full.png").exists());
+
+        let (data, content_type) = storage
+            .get("screenshots/abc/full.png")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(data, b"png");
+        assert_eq!(content_type, "image/png");
+
+        storage.delete("screenshots/abc/full.png").await.unwrap();
+        assert!(storage
+            .get("screenshots/abc/full.png")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_path_traversal() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = LocalBlobStorage::new(dir.path(), "/artifacts");
+        assert!(storage
+            .put("../outside.png", b"x", "image/png")
+            .await
+            .is_err());
+        assert!(storage.get("/etc/passwd").await.is_err());
+    }
+}
@@ -18,6 +18,8 @@
 //! - [`factory`]: Cache factory for creating storage backends
 //! - [`manager`]: Redis-based cache manager with conditional request support
 //! - [`key`]: Deterministic cache key generation with SHA256 hashing
+//! - [`blob_storage`]: Local filesystem blob storage for binary artifacts
+//! - [`render_cache`]: Headless render output cache keyed by render configuration
 //!
 //! ## Quick Start
 //!
@@ -67,14 +69,17 @@
 //! }
 //! ```
 
+pub mod blob_storage; // BlobStorage port adapter (local filesystem)
 pub mod connection_pool;
 pub mod factory;
 pub mod key;
 pub mod pool; // Redis connection pooling
 pub mod redis; // Contains both CacheManager and RedisManager
 pub mod redis_storage;
+pub mod render_cache;
 pub mod storage_config; // CacheStorage trait adapter // Shared Redis connection pool for external crates
                         // pub mod integrated;  // Temporarily disabled: circular dependency with riptide-core
+pub mod upgradable;
 pub mod warming;
 pub mod warming_integration;
 pub mod wasm;
@@ -84,6 +89,7 @@ pub mod wasm;
 pub mod adapters;
 
 // Re-exports for convenience
+pub use blob_storage::LocalBlobStorage;
 pub use connection_pool::{PoolStats, RedisConnectionPool};
 pub use factory::CacheFactory;
 pub use key::{
@@ -93,11 +99,13 @@ pub use key::{
 pub use pool::{RedisConfig, RedisPool};
 pub use redis::{CacheEntry, CacheManager, CacheMetadata, ConditionalResult, RedisManager};
 pub use redis_storage::RedisStorage;
+pub use render_cache::{RenderCache, RenderCacheKey, DEFAULT_RENDER_TTL};
 pub use storage_config::{CacheBackend, StorageConfig}; // Port adapter for CacheStorage trait // Shared connection pool
                                                        // pub use integrated::{
                                                        //     CachedContent, CacheCheckResult, IntegratedCacheConfig, IntegratedCacheManager,
                                                        //     IntegratedCacheStats, create_optimized_integrated_cache_manager,
                                                        // };
+pub use upgradable::{UpgradableCache, UpgradableRateLimiter};
 #[cfg(feature = "wasm-pool")]
 pub use warming::{
     CacheWarmingConfig, CacheWarmingManager, CacheWarmingOperation, CacheWarmingPoolExt,
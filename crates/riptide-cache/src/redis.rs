@@ -366,6 +366,24 @@ impl CacheManager {
         Ok(())
     }
 
+    /// One SCAN step over keys matching a glob pattern
+    ///
+    /// Start with cursor 0 and pass the returned cursor back until it is 0
+    /// again. Each step returns a bounded batch, so large keyspaces neither
+    /// block Redis nor have to fit in memory; a key may be returned more
+    /// than once.
+    pub async fn scan_page(&mut self, cursor: u64, pattern: &str) -> Result<(u64, Vec<String>)> {
+        let page = redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(pattern)
+            .arg("COUNT")
+            .arg(500)
+            .query_async(&mut self.conn)
+            .await?;
+        Ok(page)
+    }
+
     /// Warm the cache with frequently accessed keys
     /// This should be called at startup or after cache clear operations
     pub async fn warm_cache(&mut self, frequent_keys: Vec<String>) -> Result<u32> {
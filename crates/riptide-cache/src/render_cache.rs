@@ -0,0 +1,190 @@
+//! Render Result Cache
+//!
+//! Caches the output of headless rendering (final DOM HTML plus artifacts)
+//! separately from the raw fetch cache. A render depends on more than its
+//! URL, so entries are keyed by URL, viewport, stealth preset and a hash of
+//! the action script that ran on the page. Rendered pages go stale faster
+//! than fetched documents (client-side content, session-dependent markup),
+//! so entries use a shorter TTL than the fetch cache.
+//!
+//! The payload type is chosen by the caller; this module only needs it to
+//! be serializable.
+
+use crate::key::CacheKeyBuilder;
+use riptide_types::ports::cache::CacheStorage;
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
+
+/// Default render cache TTL (10 minutes, vs. 1 hour for fetched content)
+pub const DEFAULT_RENDER_TTL: Duration = Duration::from_secs(600);
+
+/// Everything that makes two renders of the same URL differ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderCacheKey {
+    pub url: String,
+    /// Viewport description, e.g. `1920x1080@1x`
+    pub viewport: String,
+    /// Stealth preset the page was rendered with, e.g. `medium`
+    pub stealth_preset: String,
+    /// SHA256 of the action script (actions, waits, scrolling)
+    pub action_script_hash: String,
+}
+
+impl RenderCacheKey {
+    /// Key for a render of `url`, hashing `action_script`
+    pub fn new(
+        url: impl Into<String>,
+        viewport: impl Into<String>,
+        stealth_preset: impl Into<String>,
+        action_script: &str,
+    ) -> Self {
+        Self {
+            url: url.into(),
+            viewport: viewport.into(),
+            stealth_preset: stealth_preset.into(),
+            action_script_hash: format!("{:x}", Sha256::digest(action_script.as_bytes())),
+        }
+    }
+
+    /// Storage key in the `render` namespace
+    pub fn build(&self, version: &str) -> Result<String, anyhow::Error> {
+        CacheKeyBuilder::new()
+            .url(&self.url)
+            .method("render")
+            .version(version)
+            .option("viewport", &self.viewport)
+            .option("stealth", &self.stealth_preset)
+            .option("actions", &self.action_script_hash)
+            .namespace("render")
+            .build()
+    }
+}
+
+/// Render output cache over any [`CacheStorage`] backend
+pub struct RenderCache {
+    storage: Arc<dyn CacheStorage>,
+    ttl: Duration,
+    version: String,
+}
+
+impl RenderCache {
+    /// Create a render cache with the default TTL
+    pub fn new(storage: Arc<dyn CacheStorage>) -> Self {
+        Self {
+            storage,
+            ttl: DEFAULT_RENDER_TTL,
+            version: "v1".to_string(),
+        }
+    }
+
+    /// Set the entry TTL
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Set the key version (bump to invalidate all renders)
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    /// Entry TTL
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Cached render for `key`
+    ///
+    /// Entries that no longer deserialize (e.g. after a payload change) are
+    /// treated as misses.
+    pub async fn get<T: DeserializeOwned>(
+        &self,
+        key: &RenderCacheKey,
+    ) -> Result<Option<T>, anyhow::Error> {
+        let storage_key = key.build(&self.version)?;
+        let Some(bytes) = self.storage.get(&storage_key).await? else {
+            return Ok(None);
+        };
+        match serde_json::from_slice(&bytes) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) => {
+                debug!(
+                    key = %storage_key,
+                    error = %e,
+                    "Discarding undecodable render cache entry"
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    /// Store a render under `key`
+    pub async fn put<T: Serialize>(
+        &self,
+        key: &RenderCacheKey,
+        value: &T,
+    ) -> Result<(), anyhow::Error> {
+        let storage_key = key.build(&self.version)?;
+        let bytes = serde_json::to_vec(value)?;
+        self.storage
+            .set(&storage_key, &bytes, Some(self.ttl))
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::ports::memory_cache::InMemoryCache;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Rendered {
+        html: String,
+    }
+
+    fn key(viewport: &str, stealth: &str, script: &str) -> RenderCacheKey {
+        RenderCacheKey::new("https://example.com/app", viewport, stealth, script)
+    }
+
+    #[test]
+    fn test_key_varies_with_render_config() {
+        let base = key("1920x1080@1x", "none", "[]").build("v1").unwrap();
+        assert!(base.starts_with("riptide:render:v1:"));
+        assert_eq!(base, key("1920x1080@1x", "none", "[]").build("v1").unwrap());
+
+        for other in [
+            key("390x844@3x-mobile", "none", "[]"),
+            key("1920x1080@1x", "high", "[]"),
+            key("1920x1080@1x", "none", r##"[{"click":"#more"}]"##),
+        ] {
+            assert_ne!(base, other.build("v1").unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_with_short_ttl() {
+        let storage = Arc::new(InMemoryCache::new());
+        let cache = RenderCache::new(storage.clone());
+        let key = key("1920x1080@1x", "medium", "[]");
+        let rendered = Rendered {
+            html: "<html>rendered</html>".to_string(),
+        };
+
+        assert!(cache.get::<Rendered>(&key).await.unwrap().is_none());
+        cache.put(&key, &rendered).await.unwrap();
+        assert_eq!(cache.get::<Rendered>(&key).await.unwrap(), Some(rendered));
+
+        let ttl = storage
+            .ttl(&key.build("v1").unwrap())
+            .await
+            .unwrap()
+            .expect("Render entries expire");
+        assert!(ttl <= DEFAULT_RENDER_TTL);
+    }
+}
@@ -0,0 +1,202 @@
+//! Cache and rate limiter adapters whose backend can be replaced at runtime
+//!
+//! Lets a server start while Redis is unreachable and move to Redis once it
+//! recovers, without restarting or re-wiring every component that holds the
+//! adapter:
+//!
+//! - [`UpgradableCache`] starts on an in-memory cache. Entries written to the
+//!   previous backend are not migrated; the cache warms up again.
+//! - [`UpgradableRateLimiter`] starts on a process-local limiter, so quotas
+//!   are enforced per instance until the shared limiter is installed.
+
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use riptide_types::error::Result as RiptideResult;
+use riptide_types::ports::cache::{CacheStats, CacheStorage};
+use riptide_types::ports::rate_limit::{self, RateLimiter};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Cache storage delegating to a swappable backend
+pub struct UpgradableCache {
+    backend: RwLock<Arc<dyn CacheStorage>>,
+}
+
+impl UpgradableCache {
+    /// Start on `backend`
+    pub fn new(backend: Arc<dyn CacheStorage>) -> Self {
+        Self {
+            backend: RwLock::new(backend),
+        }
+    }
+
+    /// Route all further operations to `backend`
+    pub fn upgrade(&self, backend: Arc<dyn CacheStorage>) {
+        *self.backend.write() = backend;
+    }
+
+    fn current(&self) -> Arc<dyn CacheStorage> {
+        self.backend.read().clone()
+    }
+}
+
+#[async_trait]
+impl CacheStorage for UpgradableCache {
+    async fn get(&self, key: &str) -> RiptideResult<Option<Vec<u8>>> {
+        self.current().get(key).await
+    }
+
+    async fn set(&self, key: &str, value: &[u8], ttl: Option<Duration>) -> RiptideResult<()> {
+        self.current().set(key, value, ttl).await
+    }
+
+    async fn delete(&self, key: &str) -> RiptideResult<()> {
+        self.current().delete(key).await
+    }
+
+    async fn exists(&self, key: &str) -> RiptideResult<bool> {
+        self.current().exists(key).await
+    }
+
+    async fn mset(&self, items: Vec<(&str, &[u8])>, ttl: Option<Duration>) -> RiptideResult<()> {
+        self.current().mset(items, ttl).await
+    }
+
+    async fn mget(&self, keys: &[&str]) -> RiptideResult<Vec<Option<Vec<u8>>>> {
+        self.current().mget(keys).await
+    }
+
+    async fn expire(&self, key: &str, ttl: Duration) -> RiptideResult<bool> {
+        self.current().expire(key, ttl).await
+    }
+
+    async fn ttl(&self, key: &str) -> RiptideResult<Option<Duration>> {
+        self.current().ttl(key).await
+    }
+
+    async fn incr(&self, key: &str, delta: i64) -> RiptideResult<i64> {
+        self.current().incr(key, delta).await
+    }
+
+    async fn delete_many(&self, keys: &[&str]) -> RiptideResult<usize> {
+        self.current().delete_many(keys).await
+    }
+
+    async fn clear_pattern(&self, pattern: &str) -> RiptideResult<usize> {
+        self.current().clear_pattern(pattern).await
+    }
+
+    async fn stats(&self) -> RiptideResult<CacheStats> {
+        self.current().stats().await
+    }
+
+    async fn health_check(&self) -> RiptideResult<bool> {
+        self.current().health_check().await
+    }
+}
+
+/// Rate limiter delegating to a swappable limiter
+pub struct UpgradableRateLimiter {
+    limiter: RwLock<Arc<dyn RateLimiter>>,
+}
+
+impl UpgradableRateLimiter {
+    /// Enforce quotas with `limiter`
+    pub fn new(limiter: Arc<dyn RateLimiter>) -> Self {
+        Self {
+            limiter: RwLock::new(limiter),
+        }
+    }
+
+    /// Enforce quotas with `limiter` from now on
+    ///
+    /// Usage counted by the previous limiter is not carried over.
+    pub fn upgrade(&self, limiter: Arc<dyn RateLimiter>) {
+        *self.limiter.write() = limiter;
+    }
+
+    fn current(&self) -> Arc<dyn RateLimiter> {
+        self.limiter.read().clone()
+    }
+}
+
+#[async_trait]
+impl RateLimiter for UpgradableRateLimiter {
+    async fn check_quota(&self, tenant_id: &str) -> rate_limit::Result<()> {
+        self.current().check_quota(tenant_id).await
+    }
+
+    async fn consume(&self, tenant_id: &str, amount: usize) -> rate_limit::Result<()> {
+        self.current().consume(tenant_id, amount).await
+    }
+
+    async fn reset(&self, tenant_id: &str) -> rate_limit::Result<()> {
+        self.current().reset(tenant_id).await
+    }
+
+    async fn get_remaining(&self, tenant_id: &str) -> rate_limit::Result<usize> {
+        self.current().get_remaining(tenant_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::InMemoryRateLimiter;
+    use riptide_types::ports::memory_cache::InMemoryCache;
+
+    #[tokio::test]
+    async fn test_upgrade_switches_backend() {
+        let fallback: Arc<dyn CacheStorage> = Arc::new(InMemoryCache::new());
+        let cache = UpgradableCache::new(fallback.clone());
+        cache.set("key", b"before", None).await.unwrap();
+        assert_eq!(fallback.get("key").await.unwrap(), Some(b"before".to_vec()));
+
+        let upgraded: Arc<dyn CacheStorage> = Arc::new(InMemoryCache::new());
+        cache.upgrade(upgraded.clone());
+        assert_eq!(cache.get("key").await.unwrap(), None);
+
+        cache.set("key", b"after", None).await.unwrap();
+        assert_eq!(upgraded.get("key").await.unwrap(), Some(b"after".to_vec()));
+        assert_eq!(fallback.get("key").await.unwrap(), Some(b"before".to_vec()));
+    }
+
+    struct Exhausted;
+
+    #[async_trait]
+    impl RateLimiter for Exhausted {
+        async fn check_quota(&self, _tenant_id: &str) -> rate_limit::Result<()> {
+            Err(
+                riptide_types::error::riptide_error::RiptideError::RateLimitExceeded {
+                    tenant_id: "tenant".to_string(),
+                },
+            )
+        }
+
+        async fn consume(&self, tenant_id: &str, _amount: usize) -> rate_limit::Result<()> {
+            self.check_quota(tenant_id).await
+        }
+
+        async fn reset(&self, _tenant_id: &str) -> rate_limit::Result<()> {
+            Ok(())
+        }
+
+        async fn get_remaining(&self, _tenant_id: &str) -> rate_limit::Result<usize> {
+            Ok(0)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_enforces_locally_until_upgraded() {
+        let limiter = UpgradableRateLimiter::new(Arc::new(InMemoryRateLimiter::new(
+            1,
+            Duration::from_secs(60),
+        )));
+        assert!(limiter.consume("tenant", 1).await.is_ok());
+        assert!(limiter.consume("tenant", 1).await.is_err());
+
+        limiter.upgrade(Arc::new(Exhausted));
+        assert!(limiter.check_quota("tenant").await.is_err());
+        assert_eq!(limiter.get_remaining("tenant").await.unwrap(), 0);
+    }
+}
@@ -0,0 +1,60 @@
+//! Port contracts run against the Redis adapters
+//!
+//! Each test starts its own Redis testcontainer, so the suites' fixed keys
+//! never collide. The in-memory fakes run the same suites in
+//! `riptide-test-utils/tests/fake_contracts.rs`.
+//!
+//! Run with: `cargo test -p riptide-cache --test redis_contract_tests -- --ignored`
+
+use riptide_cache::RedisStorage;
+use riptide_test_utils::contracts::cache_storage;
+use testcontainers::{clients::Cli, Container};
+use testcontainers_modules::redis::Redis;
+
+/// Start a Redis container and return it with its connection URL
+fn start_redis(docker: &Cli) -> (Container<'_, Redis>, String) {
+    let container = docker.run(Redis);
+    let url = format!("redis://127.0.0.1:{}", container.get_host_port_ipv4(6379));
+    (container, url)
+}
+
+#[tokio::test]
+#[ignore = "requires Redis testcontainer"]
+async fn redis_storage_honours_cache_storage_contract() {
+    let docker = Cli::default();
+    let (_redis, url) = start_redis(&docker);
+    let cache = RedisStorage::new(&url).await.unwrap();
+
+    cache_storage::run_all_tests(&cache).await.unwrap();
+}
+
+#[cfg(feature = "idempotency")]
+mod idempotency_adapters {
+    use super::*;
+    use riptide_cache::adapters::{RedisIdempotencyStore, RedisSessionStorage};
+    use riptide_test_utils::contracts::{idempotency, session_storage};
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    #[tokio::test]
+    #[ignore = "requires Redis testcontainer"]
+    async fn redis_session_storage_honours_session_storage_contract() {
+        let docker = Cli::default();
+        let (_redis, url) = start_redis(&docker);
+        let storage = RedisSessionStorage::new(&url).unwrap();
+
+        session_storage::run_all_tests(&storage).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis testcontainer"]
+    async fn redis_idempotency_store_honours_idempotency_contract() {
+        let docker = Cli::default();
+        let (_redis, url) = start_redis(&docker);
+        let client = redis::Client::open(url).unwrap();
+        let conn = client.get_multiplexed_async_connection().await.unwrap();
+        let store = RedisIdempotencyStore::new(Arc::new(Mutex::new(conn)));
+
+        idempotency::run_all_tests(&store).await.unwrap();
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::ApiError;
 use anyhow::{Context, Result};
 use reqwest::{Client, Response};
 use serde_json::Value;
@@ -171,13 +172,8 @@ impl ApiClient {
             .context(format!("Failed to send POST request to {}", url))?;
 
         // Check for HTTP errors
-        let status = response.status();
-        if !status.is_success() {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| String::from("Unknown error"));
-            anyhow::bail!("API returned error {}: {}", status, error_text);
+        if !response.status().is_success() {
+            return Err(ApiError::from_response(response).await.into());
         }
 
         // Deserialize response
@@ -371,12 +367,7 @@ impl RipTideClient {
                         continue;
                     }
 
-                    let error_body = response
-                        .text()
-                        .await
-                        .unwrap_or_else(|_| "Unable to read error response".to_string());
-
-                    anyhow::bail!("API request failed with status {}: {}", status, error_body);
+                    return Err(ApiError::from_response(response).await.into());
                 }
                 Err(e) => {
                     if retry_count < MAX_RETRIES {
@@ -0,0 +1,147 @@
+//! Config command - Configuration schema export and validation
+//!
+//! Works entirely offline so configuration files can be checked in CI before
+//! deploys, without a running API server:
+//! - `riptide config schema` prints the JSON Schema for the configuration file
+//! - `riptide config validate <file>` checks a YAML or JSON file against the
+//!   schema and the cross-field invariants enforced by riptide-config
+
+use crate::output::{self, OutputFormat};
+use anyhow::{bail, Context, Result};
+use clap::{Args, Subcommand};
+use colored::Colorize;
+use riptide_config::{config_json_schema, validate_config_value, ValidationResult};
+use std::path::{Path, PathBuf};
+
+/// Config command arguments
+#[derive(Args, Clone, Debug)]
+pub struct ConfigArgs {
+    /// Config subcommand
+    #[command(subcommand)]
+    pub command: ConfigCommands,
+}
+
+/// Config subcommands
+#[derive(Subcommand, Clone, Debug)]
+pub enum ConfigCommands {
+    /// Print the JSON Schema for RipTide configuration files
+    Schema {
+        /// Write the schema to a file instead of stdout
+        #[arg(long, short = 'f')]
+        output_file: Option<PathBuf>,
+    },
+
+    /// Validate a configuration file against the schema and cross-field rules
+    Validate {
+        /// Path to a YAML or JSON configuration file
+        file: PathBuf,
+
+        /// Treat warnings as errors
+        #[arg(long)]
+        strict: bool,
+    },
+}
+
+/// Execute config command
+pub async fn execute(args: ConfigArgs, output_format: String) -> Result<()> {
+    match args.command {
+        ConfigCommands::Schema { output_file } => export_schema(output_file.as_deref()),
+        ConfigCommands::Validate { file, strict } => validate_file(&file, strict, &output_format),
+    }
+}
+
+fn export_schema(output_file: Option<&Path>) -> Result<()> {
+    let schema = serde_json::to_string_pretty(&config_json_schema())?;
+
+    match output_file {
+        Some(path) => {
+            std::fs::write(path, schema)
+                .with_context(|| format!("Failed to write schema to {}", path.display()))?;
+            output::print_success(&format!("Schema written to {}", path.display()));
+        }
+        None => println!("{}", schema),
+    }
+
+    Ok(())
+}
+
+fn validate_file(path: &Path, strict: bool, output_format: &str) -> Result<()> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file {}", path.display()))?;
+
+    // YAML is a superset of JSON, so one parser handles both formats
+    let value: serde_json::Value = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse config file {}", path.display()))?;
+
+    let result = validate_config_value(value);
+
+    match OutputFormat::parse(output_format)? {
+        OutputFormat::Json | OutputFormat::Stream => output::print_json(&result),
+        OutputFormat::Table | OutputFormat::Text => print_report(path, &result),
+    }
+
+    if !result.valid {
+        bail!(
+            "{} failed validation with {} error(s)",
+            path.display(),
+            result.errors.len()
+        );
+    }
+    if strict && !result.warnings.is_empty() {
+        bail!(
+            "{} has {} warning(s) and --strict was set",
+            path.display(),
+            result.warnings.len()
+        );
+    }
+
+    Ok(())
+}
+
+fn print_report(path: &Path, result: &ValidationResult) {
+    for error in &result.errors {
+        println!("{} {}", "✗".red().bold(), error);
+    }
+    for warning in &result.warnings {
+        println!("{} {}", "⚠".yellow().bold(), warning);
+    }
+
+    if result.valid {
+        output::print_success(&format!(
+            "{} is valid ({} warning(s))",
+            path.display(),
+            result.warnings.len()
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_config(content: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_validate_accepts_partial_yaml() {
+        let file = write_config("spider:\n  concurrency: 8\n  max_depth: 3\n");
+        assert!(validate_file(file.path(), false, "json").is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_section() {
+        let file = write_config("crawler:\n  concurrency: 8\n");
+        assert!(validate_file(file.path(), false, "text").is_err());
+    }
+
+    #[test]
+    fn test_strict_mode_fails_on_warnings() {
+        // Default api section warns that require_auth has no keys configured
+        let file = write_config("{}\n");
+        assert!(validate_file(file.path(), true, "json").is_err());
+    }
+}
@@ -6,6 +6,7 @@
 /// This command crawls multiple URLs with support for depth control, external links,
 /// and both streaming and batch modes.
 use crate::client::ApiClient;
+use crate::error::ApiError;
 use crate::output::{self, format_size, truncate_text, OutputFormat};
 use anyhow::{Context, Result};
 use clap::Args;
@@ -39,6 +40,10 @@ pub struct CrawlArgs {
     /// Save results to file
     #[arg(short = 'f', long)]
     pub output_file: Option<String>,
+
+    /// Write fetched pages to WARC archive files on the server (batch mode only)
+    #[arg(long, conflicts_with = "stream")]
+    pub warc: bool,
 }
 
 /// Request payload sent to the API
@@ -55,6 +60,8 @@ struct CrawlOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     max_pages: Option<u32>,
     follow_external: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    archive_warc: Option<bool>,
 }
 
 /// Response from the API
@@ -66,6 +73,8 @@ pub struct CrawlResponse {
     pub from_cache: usize,
     pub results: Vec<CrawlResult>,
     pub statistics: CrawlStatistics,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warc_files: Option<Vec<String>>,
 }
 
 /// Individual crawl result for a single URL
@@ -140,6 +149,7 @@ pub async fn execute(client: ApiClient, args: CrawlArgs, output_format: String)
             max_depth: args.depth,
             max_pages: args.max_pages,
             follow_external: args.external,
+            archive_warc: args.warc.then_some(true),
         },
     };
 
@@ -183,6 +193,10 @@ async fn execute_batch(
     let format = OutputFormat::parse(&output_format)?;
     print_results(&response, format)?;
 
+    for path in response.warc_files.iter().flatten() {
+        output::print_success(&format!("WARC archive written to {}", path));
+    }
+
     // Exit with error if any URLs failed
     if response.failed > 0 {
         anyhow::bail!("Crawl completed with {} failed URL(s)", response.failed);
@@ -207,11 +221,7 @@ async fn execute_streaming(
     // Check for HTTP errors
     let status = response.status();
     if !status.is_success() {
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| String::from("Unknown error"));
-        anyhow::bail!("API returned error {}: {}", status, error_text);
+        return Err(ApiError::from_response(response).await.into());
     }
 
     let format = OutputFormat::parse(&output_format)?;
@@ -374,6 +384,7 @@ fn build_summary_response(results: Vec<CrawlResult>) -> CrawlResponse {
             gate_decisions,
             cache_hit_rate,
         },
+        warc_files: None,
     }
 }
 
@@ -562,6 +573,7 @@ mod tests {
             max_pages: Some(10),
             external: false,
             output_file: None,
+            warc: false,
         };
         assert!(validate_args(&args).is_ok());
     }
@@ -575,6 +587,7 @@ mod tests {
             max_pages: None,
             external: false,
             output_file: None,
+            warc: false,
         };
         assert!(validate_args(&args).is_err());
     }
@@ -588,6 +601,7 @@ mod tests {
             max_pages: None,
             external: false,
             output_file: None,
+            warc: false,
         };
         assert!(validate_args(&args).is_err());
     }
@@ -601,6 +615,7 @@ mod tests {
             max_pages: None,
             external: false,
             output_file: None,
+            warc: false,
         };
         assert!(validate_args(&args).is_err());
     }
@@ -614,10 +629,30 @@ mod tests {
             max_pages: Some(0),
             external: false,
             output_file: None,
+            warc: false,
         };
         assert!(validate_args(&args).is_err());
     }
 
+    #[derive(clap::Parser)]
+    struct TestCli {
+        #[command(flatten)]
+        args: CrawlArgs,
+    }
+
+    #[test]
+    fn test_warc_flag() {
+        use clap::Parser;
+
+        let cli = TestCli::try_parse_from(["crawl", "https://example.com", "--warc"]).unwrap();
+        assert!(cli.args.warc);
+
+        // Archives are finalized per batch, so streaming mode cannot produce them
+        let result =
+            TestCli::try_parse_from(["crawl", "https://example.com", "--warc", "--stream"]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_build_summary_response() {
         let results = vec![
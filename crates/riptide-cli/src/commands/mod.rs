@@ -4,10 +4,12 @@
 /// - Args struct with clap derives
 /// - execute() function that uses ApiClient
 /// - Result formatting and output handling
+pub mod config; // Offline config schema/validation
 pub mod crawl;
 pub mod doctor;
 pub mod extract;
 pub mod render;
+pub mod run; // Crawl manifests
 pub mod search;
 pub mod session; // API-based session management
 pub mod session_api;
@@ -0,0 +1,216 @@
+//! Run command - Execute a crawl manifest
+//!
+//! `riptide run manifest.yaml` validates a crawl manifest locally with
+//! riptide-config, submits it to `/crawl/manifest` and writes the crawl
+//! response to the manifest's outputs:
+//! - `stdout` prints it in the selected output format (also the default
+//!   when the manifest lists no outputs)
+//! - `file` appends one JSON line per crawled URL
+//! - `webhook` POSTs the whole response as JSON
+//!
+//! Manifests with a `schedule` are registered as scheduled jobs on the
+//! server instead; their results are kept as job results.
+
+use crate::client::ApiClient;
+use crate::output::{self, OutputFormat};
+use anyhow::{bail, Context, Result};
+use clap::Args;
+use colored::Colorize;
+use riptide_config::{CrawlManifest, OutputSink};
+use serde::Deserialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Arguments for the run command
+#[derive(Args, Clone, Debug)]
+pub struct RunArgs {
+    /// Path to a crawl manifest (YAML or JSON)
+    pub manifest: PathBuf,
+
+    /// Validate the manifest and print the crawl it describes without running it
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Response from `/crawl/manifest`
+#[derive(Deserialize, Debug)]
+struct ManifestRunResponse {
+    manifest: String,
+    crawl: Option<serde_json::Value>,
+    scheduled_job_id: Option<String>,
+}
+
+/// Execute the run command
+pub async fn execute(client: ApiClient, args: RunArgs, output_format: String) -> Result<()> {
+    let manifest = load_manifest(&args.manifest)?;
+    let format = OutputFormat::parse(&output_format)?;
+
+    if args.dry_run {
+        print_plan(&manifest);
+        return Ok(());
+    }
+
+    output::print_info(&format!(
+        "Running manifest '{}' ({} seed URL(s))...",
+        manifest.name,
+        manifest.seed_urls().len()
+    ));
+
+    let response = client
+        .post::<CrawlManifest, ManifestRunResponse>("/crawl/manifest", &manifest)
+        .await
+        .context("Failed to run manifest via API")?;
+
+    if let Some(job_id) = &response.scheduled_job_id {
+        output::print_success(&format!(
+            "Manifest '{}' scheduled as job {} ({})",
+            response.manifest,
+            job_id,
+            manifest.schedule.as_deref().unwrap_or_default()
+        ));
+        return Ok(());
+    }
+    let crawl = response
+        .crawl
+        .context("API returned neither a crawl nor a scheduled job")?;
+
+    if manifest.outputs.is_empty() {
+        output::print(&crawl, format)?;
+    }
+    for sink in &manifest.outputs {
+        match sink {
+            OutputSink::Stdout => output::print(&crawl, format)?,
+            OutputSink::File { path } => {
+                let lines = append_results(path, &crawl)?;
+                output::print_success(&format!(
+                    "{} result(s) appended to {}",
+                    lines,
+                    path.display()
+                ));
+            }
+            OutputSink::Webhook { url } => {
+                post_webhook(url, &crawl).await?;
+                output::print_success(&format!("Results posted to {}", url));
+            }
+        }
+    }
+
+    let failed = crawl["failed"].as_u64().unwrap_or(0);
+    if failed > 0 {
+        bail!(
+            "Manifest '{}' completed with {} failed URL(s)",
+            response.manifest,
+            failed
+        );
+    }
+
+    Ok(())
+}
+
+/// Read and validate a manifest, printing its warnings
+fn load_manifest(path: &Path) -> Result<CrawlManifest> {
+    let manifest = CrawlManifest::from_path(path)?;
+    let report = manifest.validate();
+
+    for error in &report.errors {
+        eprintln!("{} {}", "✗".red().bold(), error);
+    }
+    for warning in &report.warnings {
+        eprintln!("{} {}", "⚠".yellow().bold(), warning);
+    }
+    if !report.valid {
+        bail!(
+            "{} failed validation with {} error(s)",
+            path.display(),
+            report.errors.len()
+        );
+    }
+
+    Ok(manifest)
+}
+
+/// Print the URLs and options a manifest would crawl with
+fn print_plan(manifest: &CrawlManifest) {
+    println!("Manifest: {}", manifest.name);
+    if let Some(schedule) = &manifest.schedule {
+        println!("Schedule: {}", schedule);
+    }
+    println!("Seeds:");
+    for url in manifest.seed_urls() {
+        println!("  {}", url);
+    }
+    println!("Options:");
+    output::print_json(&manifest.crawl_options());
+    println!("Outputs: {}", manifest.outputs.len().max(1));
+}
+
+/// Append one JSON line per crawl result to `path`, returning the line count
+fn append_results(path: &Path, crawl: &serde_json::Value) -> Result<usize> {
+    let results = crawl["results"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or(&[]);
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    for result in results {
+        writeln!(file, "{}", serde_json::to_string(result)?)
+            .with_context(|| format!("Failed to write results to {}", path.display()))?;
+    }
+
+    Ok(results.len())
+}
+
+async fn post_webhook(url: &str, crawl: &serde_json::Value) -> Result<()> {
+    let response = reqwest::Client::new()
+        .post(url)
+        .json(crawl)
+        .send()
+        .await
+        .with_context(|| format!("Failed to post results to {}", url))?;
+    if !response.status().is_success() {
+        bail!("Webhook {} answered {}", url, response.status());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_manifest(content: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_load_manifest() {
+        let file = write_manifest("version: 1\nname: docs\nseeds:\n  - https://example.com/docs\n");
+        assert_eq!(load_manifest(file.path()).unwrap().name, "docs");
+
+        let file = write_manifest("version: 1\nname: docs\nseeds:\n  - ftp://example.com\n");
+        assert!(load_manifest(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_append_results_writes_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.jsonl");
+        let crawl = serde_json::json!({
+            "failed": 0,
+            "results": [{ "url": "https://example.com/a" }, { "url": "https://example.com/b" }]
+        });
+
+        assert_eq!(append_results(&path, &crawl).unwrap(), 2);
+        assert_eq!(append_results(&path, &crawl).unwrap(), 2);
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = content.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], r#"{"url":"https://example.com/b"}"#);
+    }
+}
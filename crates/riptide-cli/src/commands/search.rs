@@ -3,6 +3,7 @@
 /// This command performs deep search queries using the /deepsearch endpoint.
 /// It supports both batch and streaming modes with real-time result output.
 use crate::client::ApiClient;
+use crate::error::ApiError;
 use crate::output::{self, OutputFormat};
 use anyhow::{Context, Result};
 use clap::Args;
@@ -184,11 +185,7 @@ async fn execute_streaming(
     // Check response status
     let status = response.status();
     if !status.is_success() {
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| String::from("Unknown error"));
-        anyhow::bail!("API returned error {}: {}", status, error_text);
+        return Err(ApiError::from_response(response).await.into());
     }
 
     // Process streaming response
@@ -1,4 +1,5 @@
 use crate::client::ApiClient;
+use crate::error::ApiError;
 use crate::output;
 use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
@@ -47,12 +48,7 @@ async fn check_response_status(response: reqwest::Response) -> Result<reqwest::R
     let status = response.status();
 
     if !status.is_success() {
-        let error_body = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unable to read error response".to_string());
-
-        bail!("API request failed with status {}: {}", status, error_body);
+        return Err(ApiError::from_response(response).await.into());
     }
 
     Ok(response)
@@ -3,6 +3,7 @@
 /// This command provides information about available extraction strategies
 /// and allows crawling URLs with specific strategies.
 use crate::client::ApiClient;
+use crate::error::ApiError;
 use crate::output::{self, OutputFormat};
 use anyhow::{Context, Result};
 use clap::{Args, Subcommand};
@@ -89,12 +90,7 @@ async fn execute_list(client: ApiClient, output_format: String) -> Result<()> {
         .context("Failed to fetch strategies from API")?;
 
     if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| String::from("Unknown error"));
-        anyhow::bail!("API returned error {}: {}", status, error_text);
+        return Err(ApiError::from_response(response).await.into());
     }
 
     let strategy_response = response
@@ -120,12 +116,7 @@ async fn execute_info(client: ApiClient, name: String, output_format: String) ->
         .context("Failed to fetch strategies from API")?;
 
     if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response
-            .text()
-            .await
-            .unwrap_or_else(|_| String::from("Unknown error"));
-        anyhow::bail!("API returned error {}: {}", status, error_text);
+        return Err(ApiError::from_response(response).await.into());
     }
 
     let strategy_response = response
@@ -2,6 +2,11 @@
 //!
 //! The thin CLI uses anyhow::Result for simple error handling.
 //! All business logic and complex error handling is in the API server.
+//! Non-success API responses are surfaced as [`ApiError`], which carries the
+//! server's machine-readable error code so `main` can pick an exit code.
+
+use reqwest::Response;
+use riptide_types::{ErrorCategory, ErrorCode};
 
 /// Exit codes for CLI following POSIX conventions
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,6 +16,27 @@ pub enum ExitCode {
 
     /// User error: 4xx status codes, network issues, config errors (1)
     UserError = 1,
+
+    /// The request was rejected as invalid (2)
+    InvalidInput = 2,
+
+    /// Authentication, authorization or crawl policy denied access (3)
+    AccessDenied = 3,
+
+    /// A rate limit was hit (4)
+    RateLimited = 4,
+
+    /// The target site could not be fetched or rendered (5)
+    TargetError = 5,
+
+    /// Content could not be extracted or processed (6)
+    ProcessingError = 6,
+
+    /// The server or one of its dependencies is unavailable (7)
+    Unavailable = 7,
+
+    /// The server is misconfigured or the feature is disabled (8)
+    Configuration = 8,
 }
 
 impl ExitCode {
@@ -18,4 +44,125 @@ impl ExitCode {
     pub fn as_i32(self) -> i32 {
         self as i32
     }
+
+    /// Pick the exit code for a failed command
+    ///
+    /// Errors carrying an API error code map by category; anything else is
+    /// a [`ExitCode::UserError`].
+    pub fn from_error(error: &anyhow::Error) -> Self {
+        error
+            .downcast_ref::<ApiError>()
+            .and_then(|e| e.code)
+            .map_or(ExitCode::UserError, Self::from)
+    }
+}
+
+impl From<ErrorCategory> for ExitCode {
+    fn from(category: ErrorCategory) -> Self {
+        match category {
+            ErrorCategory::Input => ExitCode::InvalidInput,
+            ErrorCategory::Access => ExitCode::AccessDenied,
+            ErrorCategory::RateLimit => ExitCode::RateLimited,
+            ErrorCategory::Target => ExitCode::TargetError,
+            ErrorCategory::Processing => ExitCode::ProcessingError,
+            ErrorCategory::Unavailable => ExitCode::Unavailable,
+            ErrorCategory::Configuration => ExitCode::Configuration,
+            ErrorCategory::Internal => ExitCode::UserError,
+        }
+    }
+}
+
+impl From<ErrorCode> for ExitCode {
+    fn from(code: ErrorCode) -> Self {
+        code.category().into()
+    }
+}
+
+/// Non-success response from the API server
+#[derive(Debug, thiserror::Error)]
+#[error("API returned error {status}: {message}")]
+pub struct ApiError {
+    /// HTTP status code
+    pub status: u16,
+    /// Machine-readable error code, when the server sent one
+    pub code: Option<ErrorCode>,
+    /// Error message, or the raw body if it was not a structured error
+    pub message: String,
+}
+
+impl ApiError {
+    /// Build an error from a non-success response, consuming its body
+    pub async fn from_response(response: Response) -> Self {
+        let status = response.status().as_u16();
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| String::from("Unknown error"));
+        Self::from_body(status, &body)
+    }
+
+    /// Build an error from a status code and response body
+    ///
+    /// Structured bodies (`{"error": {"code": ..., "message": ...}}`) are
+    /// parsed; other bodies become the message verbatim.
+    pub fn from_body(status: u16, body: &str) -> Self {
+        let error = serde_json::from_str::<serde_json::Value>(body)
+            .ok()
+            .and_then(|v| v.get("error").cloned());
+        let code = error
+            .as_ref()
+            .and_then(|e| e.get("code"))
+            .and_then(|c| c.as_str())
+            .and_then(|c| c.parse().ok());
+        let message = error
+            .as_ref()
+            .and_then(|e| e.get("message"))
+            .and_then(|m| m.as_str())
+            .map_or_else(|| body.to_string(), str::to_string);
+
+        Self {
+            status,
+            code,
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_error_from_structured_body() {
+        let body = r#"{"error":{"type":"timeout_error","code":"RIPTIDE_FETCH_TIMEOUT","message":"fetch timed out","retryable":true,"status":408}}"#;
+        let error = ApiError::from_body(408, body);
+        assert_eq!(error.code, Some(ErrorCode::FetchTimeout));
+        assert_eq!(error.message, "fetch timed out");
+        assert_eq!(ExitCode::from_error(&error.into()), ExitCode::TargetError);
+    }
+
+    #[test]
+    fn test_api_error_from_plain_body() {
+        let error = ApiError::from_body(502, "Bad Gateway");
+        assert_eq!(error.code, None);
+        assert_eq!(error.message, "Bad Gateway");
+        assert_eq!(ExitCode::from_error(&error.into()), ExitCode::UserError);
+    }
+
+    #[test]
+    fn test_exit_code_from_category() {
+        assert_eq!(
+            ExitCode::from(ErrorCode::InvalidUrl),
+            ExitCode::InvalidInput
+        );
+        assert_eq!(
+            ExitCode::from(ErrorCode::PolicyDenied),
+            ExitCode::AccessDenied
+        );
+        assert_eq!(
+            ExitCode::from(ErrorCode::RateLimited),
+            ExitCode::RateLimited
+        );
+        assert_eq!(ExitCode::from(ErrorCode::Internal), ExitCode::UserError);
+    }
 }
@@ -61,7 +61,7 @@ pub mod pdf_impl;
 
 // Re-export commonly used types for convenience
 pub use client::ApiClient;
-pub use error::ExitCode;
+pub use error::{ApiError, ExitCode};
 
 /// CLI version information
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
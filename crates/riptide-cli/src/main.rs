@@ -101,6 +101,16 @@ enum Commands {
     /// Perform a basic crawl of URLs with configurable depth and streaming support.
     Crawl(commands::crawl::CrawlArgs),
 
+    /// Configuration schema export and validation
+    ///
+    /// Print the JSON Schema for config files or validate a file offline before deploying.
+    Config(commands::config::ConfigArgs),
+
+    /// Run a crawl manifest
+    ///
+    /// Validate a YAML crawl manifest, run or schedule it through the API and write its outputs.
+    Run(commands::run::RunArgs),
+
     /// PDF extraction and processing
     ///
     /// Extract content from PDF files with optional OCR support.
@@ -114,8 +124,8 @@ async fn main() {
         Ok(()) => ExitCode::Success.as_i32(),
         Err(e) => {
             eprintln!("Error: {}", e);
-            // Map anyhow errors to exit codes
-            ExitCode::UserError.as_i32()
+            // API errors map to exit codes by error code category
+            ExitCode::from_error(&e).as_i32()
         }
     });
 }
@@ -148,6 +158,8 @@ async fn run() -> Result<()> {
         Commands::Session(args) => commands::session::execute(client, args, cli.output).await,
         Commands::Strategies(args) => commands::strategies::execute(client, args, cli.output).await,
         Commands::Crawl(args) => commands::crawl::execute(client, args, cli.output).await,
+        Commands::Config(args) => commands::config::execute(args, cli.output).await,
+        Commands::Run(args) => commands::run::execute(client, args, cli.output).await,
         #[cfg(feature = "pdf")]
         Commands::Pdf(args) => commands::pdf::execute(&args).await,
     }
@@ -211,5 +223,11 @@ mod tests {
 
         let session = Cli::parse_from(["riptide", "session", "list"]);
         assert!(matches!(session.command, Commands::Session(_)));
+
+        let config = Cli::parse_from(["riptide", "config", "validate", "riptide.yml"]);
+        assert!(matches!(config.command, Commands::Config(_)));
+
+        let run = Cli::parse_from(["riptide", "run", "crawl.yaml", "--dry-run"]);
+        assert!(matches!(run.command, Commands::Run(_)));
     }
 }
@@ -22,6 +22,8 @@
 //! let custom = ApiConfig::default();
 //! ```
 
+use crate::redaction::Redacted;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
@@ -196,7 +198,8 @@ pub mod validation {
 }
 
 /// Authentication configuration for the API
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
 pub struct AuthenticationConfig {
     /// Whether authentication is required (default: true)
     /// Set to false only in development environments
@@ -220,18 +223,7 @@ impl std::fmt::Debug for AuthenticationConfig {
             .field("require_auth", &self.require_auth)
             .field(
                 "api_keys",
-                &self
-                    .api_keys
-                    .iter()
-                    .map(|key| {
-                        let chars: Vec<char> = key.chars().collect();
-                        if chars.len() <= 4 {
-                            format!("{}...", key)
-                        } else {
-                            format!("{}...", chars[..4].iter().collect::<String>())
-                        }
-                    })
-                    .collect::<Vec<_>>(),
+                &self.api_keys.iter().map(Redacted).collect::<Vec<_>>(),
             )
             .field("constant_time_comparison", &self.constant_time_comparison)
             .field("public_paths", &self.public_paths)
@@ -345,7 +337,8 @@ impl AuthenticationConfig {
 }
 
 /// Rate limiting configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
 pub struct RateLimitConfig {
     /// Maximum number of concurrent requests (default: 100)
     /// Environment variable: MAX_CONCURRENT_REQUESTS
@@ -420,7 +413,7 @@ impl RateLimitConfig {
 }
 
 /// Custom rate limit for specific path or client
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CustomRateLimit {
     /// Path pattern (e.g., "/api/v1/crawl")
     pub path_pattern: Option<String>,
@@ -436,7 +429,8 @@ pub struct CustomRateLimit {
 }
 
 /// Request handling configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
 pub struct RequestConfig {
     /// Request timeout duration (default: 30 seconds)
     /// Environment variable: REQUEST_TIMEOUT_SECS
@@ -512,7 +506,8 @@ impl RequestConfig {
 }
 
 /// Complete API configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
 pub struct ApiConfig {
     /// Authentication settings
     pub auth: AuthenticationConfig,
@@ -595,6 +590,17 @@ mod tests {
         assert!(config.public_paths.contains(&"/healthz".to_string()));
     }
 
+    #[test]
+    fn test_auth_config_debug_hides_keys() {
+        let config = AuthenticationConfig::default()
+            .with_require_auth(false)
+            .with_api_keys(vec!["sk_live_abcdefgh12345678".to_string()]);
+
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains("sk_l"), "{}", debug);
+        assert!(debug.contains(crate::redaction::REDACTED));
+    }
+
     #[test]
     fn test_auth_config_builder() {
         // Disable auth first, then set weak keys (validation is skipped when auth disabled)
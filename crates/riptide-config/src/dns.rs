@@ -0,0 +1,219 @@
+//! DNS resolution configuration
+//!
+//! Settings for the in-process DNS cache used by the fetch layer: TTL bounds,
+//! negative caching, dual-stack address ordering, and static host overrides
+//! (e.g. pointing `example.com` at a staging server).
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+/// DNS cache and resolution configuration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct DnsConfig {
+    /// Cache resolved addresses in process (default: true)
+    /// Environment variable: RIPTIDE_DNS_CACHE
+    pub cache_enabled: bool,
+
+    /// Lower bound applied to record TTLs, in seconds (default: 5)
+    /// Environment variable: RIPTIDE_DNS_MIN_TTL_SECS
+    pub min_ttl_secs: u64,
+
+    /// Upper bound applied to record TTLs, in seconds (default: 300)
+    /// Environment variable: RIPTIDE_DNS_MAX_TTL_SECS
+    pub max_ttl_secs: u64,
+
+    /// How long failed lookups are remembered, in seconds (default: 10)
+    /// Environment variable: RIPTIDE_DNS_NEGATIVE_TTL_SECS
+    pub negative_ttl_secs: u64,
+
+    /// Maximum number of cached hosts (default: 10000)
+    /// Environment variable: RIPTIDE_DNS_MAX_ENTRIES
+    pub max_entries: usize,
+
+    /// Return IPv6 and IPv4 addresses interleaved so connections race both
+    /// families (happy eyeballs); when false only IPv4 is used if available
+    /// (default: true)
+    /// Environment variable: RIPTIDE_DNS_DUAL_STACK
+    pub dual_stack: bool,
+
+    /// Static addresses per host, bypassing DNS
+    /// Environment variable: RIPTIDE_DNS_OVERRIDES, as
+    /// `host=ip[,ip...];host=ip`
+    pub host_overrides: BTreeMap<String, Vec<IpAddr>>,
+}
+
+impl Default for DnsConfig {
+    fn default() -> Self {
+        Self {
+            cache_enabled: true,
+            min_ttl_secs: 5,
+            max_ttl_secs: 300,
+            negative_ttl_secs: 10,
+            max_entries: 10_000,
+            dual_stack: true,
+            host_overrides: BTreeMap::new(),
+        }
+    }
+}
+
+impl DnsConfig {
+    /// Load DNS configuration from environment variables
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let cache_enabled = std::env::var("RIPTIDE_DNS_CACHE")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(defaults.cache_enabled);
+
+        let min_ttl_secs = std::env::var("RIPTIDE_DNS_MIN_TTL_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(defaults.min_ttl_secs);
+
+        let max_ttl_secs = std::env::var("RIPTIDE_DNS_MAX_TTL_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(defaults.max_ttl_secs);
+
+        let negative_ttl_secs = std::env::var("RIPTIDE_DNS_NEGATIVE_TTL_SECS")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(defaults.negative_ttl_secs);
+
+        let max_entries = std::env::var("RIPTIDE_DNS_MAX_ENTRIES")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(defaults.max_entries);
+
+        let dual_stack = std::env::var("RIPTIDE_DNS_DUAL_STACK")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(defaults.dual_stack);
+
+        let host_overrides = match std::env::var("RIPTIDE_DNS_OVERRIDES") {
+            Ok(spec) => parse_host_overrides(&spec).unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Ignoring invalid RIPTIDE_DNS_OVERRIDES");
+                BTreeMap::new()
+            }),
+            Err(_) => BTreeMap::new(),
+        };
+
+        Self {
+            cache_enabled,
+            min_ttl_secs,
+            max_ttl_secs,
+            negative_ttl_secs,
+            max_entries,
+            dual_stack,
+            host_overrides,
+        }
+    }
+
+    /// Resolve `host` to `addresses` without querying DNS
+    pub fn with_host_override(mut self, host: &str, addresses: Vec<IpAddr>) -> Self {
+        self.host_overrides.insert(normalize_host(host), addresses);
+        self
+    }
+
+    /// Override addresses for `host`, if any
+    pub fn host_override(&self, host: &str) -> Option<&[IpAddr]> {
+        self.host_overrides
+            .get(&normalize_host(host))
+            .map(Vec::as_slice)
+    }
+
+    /// Check the TTL bounds and overrides
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_ttl_secs > self.max_ttl_secs {
+            return Err(format!(
+                "min_ttl_secs ({}) exceeds max_ttl_secs ({})",
+                self.min_ttl_secs, self.max_ttl_secs
+            ));
+        }
+        if self.cache_enabled && self.max_entries == 0 {
+            return Err("max_entries must be greater than 0 when the cache is enabled".to_string());
+        }
+        for (host, addresses) in &self.host_overrides {
+            if host.is_empty() {
+                return Err("host_overrides: empty host name".to_string());
+            }
+            if *host != normalize_host(host) {
+                return Err(format!(
+                    "host_overrides: '{}' must be lowercase without a trailing dot",
+                    host
+                ));
+            }
+            if addresses.is_empty() {
+                return Err(format!("host_overrides: no address for '{}'", host));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Lowercase `host` and strip a trailing root dot
+fn normalize_host(host: &str) -> String {
+    host.trim_end_matches('.').to_ascii_lowercase()
+}
+
+/// Parse `host=ip[,ip...];host=ip` into an override map
+pub fn parse_host_overrides(spec: &str) -> Result<BTreeMap<String, Vec<IpAddr>>, String> {
+    let mut overrides = BTreeMap::new();
+    for entry in spec.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+        let (host, addresses) = entry
+            .split_once('=')
+            .ok_or_else(|| format!("'{}' is not host=ip", entry))?;
+        let addresses = addresses
+            .split(',')
+            .map(|a| {
+                a.trim()
+                    .parse::<IpAddr>()
+                    .map_err(|e| format!("'{}' for {}: {}", a.trim(), host.trim(), e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        overrides.insert(normalize_host(host.trim()), addresses);
+    }
+    Ok(overrides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_host_overrides() {
+        let overrides =
+            parse_host_overrides("Example.com.=10.0.0.5; api.example.com=10.0.0.6,2001:db8::1")
+                .unwrap();
+        assert_eq!(
+            overrides["example.com"],
+            vec!["10.0.0.5".parse::<IpAddr>().unwrap()]
+        );
+        assert_eq!(overrides["api.example.com"].len(), 2);
+
+        assert!(parse_host_overrides("example.com").is_err());
+        assert!(parse_host_overrides("example.com=staging").is_err());
+    }
+
+    #[test]
+    fn test_host_override_lookup_is_case_insensitive() {
+        let config = DnsConfig::default()
+            .with_host_override("example.com", vec!["192.0.2.1".parse().unwrap()]);
+        assert!(config.host_override("EXAMPLE.com.").is_some());
+        assert!(config.host_override("www.example.com").is_none());
+    }
+
+    #[test]
+    fn test_validate_ttl_bounds() {
+        let config = DnsConfig {
+            min_ttl_secs: 600,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        assert!(DnsConfig::default().validate().is_ok());
+    }
+}
@@ -10,6 +10,10 @@
 //! - **Environment Variables**: Automatic loading from environment
 //! - **Validation**: Comprehensive security and format validation
 //! - **Spider Config**: Specialized spider crawling configurations
+//! - **DNS**: Resolver cache bounds and per-host resolution overrides
+//! - **Crawl Manifests**: Declarative YAML definitions of whole crawls
+//! - **JSON Schema**: Schema export and cross-field validation for config files
+//! - **Redaction**: Masking of secrets in Debug output, config dumps, and logs
 //! - **Type Safety**: Strong typing with compile-time guarantees
 //!
 //! # Example
@@ -28,7 +32,11 @@
 
 mod api;
 mod builder;
+mod dns;
 mod env;
+mod manifest;
+pub mod redaction;
+mod schema;
 mod spider;
 mod streaming;
 mod validation;
@@ -44,7 +52,17 @@ pub use builder::{
     ValidationPatterns,
 };
 
+pub use dns::{parse_host_overrides, DnsConfig};
+
 pub use env::{load_from_env, EnvConfigLoader, EnvError};
+pub use manifest::{
+    CrawlManifest, ManifestError, ManifestExtraction, ManifestFilters, OutputSink, MANIFEST_VERSION,
+};
+
+pub use schema::{
+    config_json_schema, validate_config_value, CacheSection, IntelligenceProviderSection,
+    IntelligenceSection, RiptideConfigFile, StealthSection,
+};
 
 pub use spider::{PerformanceConfig, SpiderConfig, SpiderPresets, UrlProcessingConfig};
 
@@ -0,0 +1,490 @@
+//! Crawl manifests
+//!
+//! A [`CrawlManifest`] declares a whole crawl in one YAML document: the seed
+//! URLs, how far links are followed, filters bounding the crawl, the resource
+//! budget, how pages are extracted, where the results go and, optionally, a
+//! schedule. Manifests can be kept in version control and reviewed like any
+//! other configuration, then run with `riptide run manifest.yaml`,
+//! `POST /api/v1/crawl/manifest` or `SpiderFacade::crawl_manifest`.
+//!
+//! Parsing only checks the document's shape; [`CrawlManifest::validate`]
+//! checks the values (URLs, patterns, limits, schedule) and warns about seeds
+//! the filters drop.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_config::{CrawlManifest, OutputSink};
+//!
+//! let manifest = CrawlManifest::from_yaml(r#"
+//! version: 1
+//! name: docs
+//! seeds:
+//!   - https://example.com/docs/intro
+//!   - https://example.com/blog/launch
+//! filters:
+//!   include: ["/docs/"]
+//! budget:
+//!   max_wall_time_secs: 300
+//! extraction:
+//!   output_format: Markdown
+//! outputs:
+//!   - type: file
+//!     path: docs.jsonl
+//! "#).unwrap();
+//!
+//! let report = manifest.validate();
+//! assert!(report.valid);
+//! assert_eq!(report.warnings.len(), 1); // the blog seed is filtered out
+//! assert_eq!(manifest.seed_urls(), vec!["https://example.com/docs/intro"]);
+//! assert!(matches!(manifest.outputs[0], OutputSink::File { .. }));
+//! ```
+
+use crate::validation::ValidationResult;
+use regex::Regex;
+use riptide_types::config::{CrawlBudget, CrawlOptions, CrawlScope, OutputFormat, PipelineHints};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use url::Url;
+
+/// Manifest format version this crate reads
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Manifest loading errors
+#[derive(Error, Debug)]
+pub enum ManifestError {
+    #[error("Failed to read manifest {path}: {source}")]
+    Io {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid manifest: {0}")]
+    Parse(#[from] serde_yaml::Error),
+}
+
+/// Declarative definition of a crawl
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CrawlManifest {
+    /// Manifest format version, [`MANIFEST_VERSION`]
+    pub version: u32,
+    /// Name of the crawl, also used for its scheduled job
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// URLs to crawl, before filtering
+    pub seeds: Vec<String>,
+    #[serde(default)]
+    pub filters: ManifestFilters,
+    /// Follow links up to this many hops from the seeds, staying within the
+    /// filters; only the seeds are crawled when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<u32>,
+    /// Pages fetched in parallel; the server default when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub budget: Option<CrawlBudget>,
+    #[serde(default)]
+    pub extraction: ManifestExtraction,
+    /// Where results are written; with none, `riptide run` prints them
+    #[serde(default)]
+    pub outputs: Vec<OutputSink>,
+    /// Cron expression with a leading seconds field
+    /// (`sec min hour day month weekday [year]`); when set, the crawl is
+    /// registered as a scheduled job instead of run once
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<String>,
+}
+
+/// Regex filters and a page cap bounding the crawl
+///
+/// A URL is crawled when it matches one of `include` (or `include` is
+/// empty) and none of `exclude`. The filters apply to the seeds and, with
+/// `max_depth`, to every discovered link.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ManifestFilters {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    /// Crawl at most this many pages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_pages: Option<usize>,
+}
+
+/// How crawled pages are extracted
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ManifestExtraction {
+    /// Extractor to prefer, as `pipeline_hints.extraction_schema`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<OutputFormat>,
+    /// Return raw HTML without extracting
+    pub skip: bool,
+}
+
+/// Destination of a crawl's results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum OutputSink {
+    /// Print the results
+    Stdout,
+    /// Append the results to a file, one JSON document per line
+    File { path: PathBuf },
+    /// POST the crawl response as JSON
+    Webhook { url: String },
+}
+
+impl ManifestFilters {
+    /// The filters as spider crawl scope rules
+    pub fn scope(&self) -> CrawlScope {
+        CrawlScope {
+            include: self.include.clone(),
+            exclude: self.exclude.clone(),
+            max_pages: self.max_pages,
+        }
+    }
+}
+
+impl CrawlManifest {
+    /// Parse a manifest from YAML (or JSON, a subset of YAML)
+    pub fn from_yaml(yaml: &str) -> Result<Self, ManifestError> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+
+    /// Read and parse the manifest file at `path`
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ManifestError> {
+        let path = path.as_ref();
+        let yaml = std::fs::read_to_string(path).map_err(|source| ManifestError::Io {
+            path: path.display().to_string(),
+            source,
+        })?;
+        Self::from_yaml(&yaml)
+    }
+
+    /// Check the manifest's values
+    ///
+    /// Seeds dropped by the filters or the page cap are reported as
+    /// warnings; a manifest left without any seed to crawl is invalid.
+    pub fn validate(&self) -> ValidationResult {
+        let mut result = ValidationResult::success();
+
+        if self.version != MANIFEST_VERSION {
+            result.add_error(format!(
+                "Unsupported manifest version {}, expected {}",
+                self.version, MANIFEST_VERSION
+            ));
+        }
+        if self.name.trim().is_empty() {
+            result.add_error("name must not be empty".to_string());
+        }
+
+        if self.seeds.is_empty() {
+            result.add_error("seeds must list at least one URL".to_string());
+        }
+        for (i, seed) in self.seeds.iter().enumerate() {
+            if let Err(reason) = check_http_url(seed) {
+                result.add_error(format!("seeds[{}]: {}", i, reason));
+            }
+        }
+
+        for (field, patterns) in [
+            ("include", &self.filters.include),
+            ("exclude", &self.filters.exclude),
+        ] {
+            for (i, pattern) in patterns.iter().enumerate() {
+                if let Err(e) = Regex::new(pattern) {
+                    result.add_error(format!("filters.{}[{}]: invalid pattern: {}", field, i, e));
+                }
+            }
+        }
+        if self.filters.max_pages == Some(0) {
+            result.add_error("filters.max_pages must be greater than 0".to_string());
+        }
+        if self.concurrency == Some(0) {
+            result.add_error("concurrency must be greater than 0".to_string());
+        }
+        if self.max_depth.is_some() {
+            if self.budget.is_some() {
+                result.add_error(
+                    "budget cannot be combined with max_depth; link-following crawls use the spider's budget"
+                        .to_string(),
+                );
+            }
+            if self.schedule.is_some() {
+                result.add_error(
+                    "max_depth cannot be combined with schedule; scheduled crawls only fetch the seeds"
+                        .to_string(),
+                );
+            }
+        }
+
+        for (i, sink) in self.outputs.iter().enumerate() {
+            match sink {
+                OutputSink::Stdout => {}
+                OutputSink::File { path } if path.as_os_str().is_empty() => {
+                    result.add_error(format!("outputs[{}]: path must not be empty", i));
+                }
+                OutputSink::File { .. } => {}
+                OutputSink::Webhook { url } => {
+                    if let Err(reason) = check_http_url(url) {
+                        result.add_error(format!("outputs[{}]: {}", i, reason));
+                    }
+                }
+            }
+        }
+
+        if let Some(schedule) = &self.schedule {
+            let fields = schedule.split_whitespace().count();
+            if !(6..=7).contains(&fields) {
+                result.add_error(format!(
+                    "schedule '{}' has {} fields, expected 6 or 7 (sec min hour day month weekday [year])",
+                    schedule, fields
+                ));
+            }
+        }
+
+        if !result.valid {
+            return result;
+        }
+
+        let matching = self.matching_seeds().len();
+        let crawled = self.seed_urls().len();
+        if matching == 0 {
+            result.add_error("No seed passes the filters".to_string());
+        } else {
+            if matching < self.seeds.len() {
+                result.add_warning(format!(
+                    "{} of {} seeds excluded by filters",
+                    self.seeds.len() - matching,
+                    self.seeds.len()
+                ));
+            }
+            if crawled < matching {
+                result.add_warning(format!(
+                    "{} seeds beyond filters.max_pages ({}) are not crawled",
+                    matching - crawled,
+                    crawled
+                ));
+            }
+        }
+
+        result
+    }
+
+    /// Seeds passing the filters, capped at `filters.max_pages`
+    ///
+    /// Patterns that do not compile are ignored; [`Self::validate`] reports
+    /// them.
+    pub fn seed_urls(&self) -> Vec<String> {
+        let mut seeds = self.matching_seeds();
+        if let Some(max_pages) = self.filters.max_pages {
+            seeds.truncate(max_pages);
+        }
+        seeds
+    }
+
+    /// Crawl options carrying the manifest's budget and extraction settings
+    ///
+    /// With `max_depth`, the crawl follows links in spider mode, scoped by
+    /// the filters.
+    pub fn crawl_options(&self) -> CrawlOptions {
+        let mut options = CrawlOptions {
+            budget: self.budget.clone(),
+            skip_extraction: self.extraction.skip.then_some(true),
+            ..CrawlOptions::default()
+        };
+        if let Some(concurrency) = self.concurrency {
+            options.concurrency = concurrency;
+        }
+        if let Some(max_depth) = self.max_depth {
+            options.use_spider = Some(true);
+            options.spider_max_depth = Some(max_depth as usize);
+            options.scope = Some(self.filters.scope());
+        }
+        if let Some(format) = &self.extraction.output_format {
+            options.output_format = format.clone();
+        }
+        if let Some(schema) = &self.extraction.schema {
+            options.pipeline_hints = Some(PipelineHints {
+                extraction_schema: Some(schema.clone()),
+                ..PipelineHints::default()
+            });
+        }
+        options
+    }
+
+    fn matching_seeds(&self) -> Vec<String> {
+        let include = compile(&self.filters.include);
+        let exclude = compile(&self.filters.exclude);
+        self.seeds
+            .iter()
+            .filter(|seed| include.is_empty() || include.iter().any(|re| re.is_match(seed)))
+            .filter(|seed| !exclude.iter().any(|re| re.is_match(seed)))
+            .cloned()
+            .collect()
+    }
+}
+
+fn compile(patterns: &[String]) -> Vec<Regex> {
+    patterns.iter().filter_map(|p| Regex::new(p).ok()).collect()
+}
+
+fn check_http_url(value: &str) -> Result<(), String> {
+    let url = Url::parse(value).map_err(|e| format!("invalid URL '{}': {}", value, e))?;
+    match url.scheme() {
+        "http" | "https" => Ok(()),
+        scheme => Err(format!(
+            "unsupported scheme '{}' in '{}', expected http or https",
+            scheme, value
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"
+version: 1
+name: shop
+seeds:
+  - https://shop.example.com/products/1
+  - https://shop.example.com/products/2
+  - https://shop.example.com/products/3
+  - https://shop.example.com/cart
+filters:
+  include: ["/products/"]
+  exclude: ["/3$"]
+concurrency: 4
+budget:
+  max_bytes: 1000000
+extraction:
+  schema: native
+  output_format: Markdown
+outputs:
+  - type: stdout
+  - type: webhook
+    url: https://hooks.example.com/crawl
+schedule: "0 0 3 * * *"
+"#;
+
+    #[test]
+    fn test_parse_and_validate() {
+        let manifest = CrawlManifest::from_yaml(MANIFEST).unwrap();
+        let report = manifest.validate();
+        assert!(report.valid, "errors: {:?}", report.errors);
+        assert_eq!(report.warnings, vec!["2 of 4 seeds excluded by filters"]);
+
+        assert_eq!(
+            manifest.seed_urls(),
+            vec![
+                "https://shop.example.com/products/1",
+                "https://shop.example.com/products/2"
+            ]
+        );
+        assert_eq!(manifest.outputs[0], OutputSink::Stdout);
+        assert_eq!(manifest.schedule.as_deref(), Some("0 0 3 * * *"));
+    }
+
+    #[test]
+    fn test_crawl_options() {
+        let options = CrawlManifest::from_yaml(MANIFEST).unwrap().crawl_options();
+        assert_eq!(options.concurrency, 4);
+        assert_eq!(options.budget.unwrap().max_bytes, Some(1_000_000));
+        assert!(matches!(options.output_format, OutputFormat::Markdown));
+        assert_eq!(
+            options.pipeline_hints.unwrap().extraction_schema.as_deref(),
+            Some("native")
+        );
+        assert_eq!(options.skip_extraction, None);
+    }
+
+    #[test]
+    fn test_max_pages_caps_seeds() {
+        let mut manifest = CrawlManifest::from_yaml(MANIFEST).unwrap();
+        manifest.filters.max_pages = Some(1);
+        assert_eq!(manifest.seed_urls().len(), 1);
+        assert_eq!(manifest.validate().warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_max_depth_scopes_the_spider() {
+        let mut manifest = CrawlManifest::from_yaml(MANIFEST).unwrap();
+        assert_eq!(manifest.crawl_options().scope, None);
+
+        manifest.max_depth = Some(2);
+        manifest.filters.max_pages = Some(50);
+        assert_eq!(
+            manifest.validate().errors,
+            vec!["budget cannot be combined with max_depth; link-following crawls use the spider's budget",
+                "max_depth cannot be combined with schedule; scheduled crawls only fetch the seeds"]
+        );
+
+        let options = manifest.crawl_options();
+        assert_eq!(options.use_spider, Some(true));
+        assert_eq!(options.spider_max_depth, Some(2));
+        assert_eq!(
+            options.scope,
+            Some(CrawlScope {
+                include: vec!["/products/".to_string()],
+                exclude: vec!["/3$".to_string()],
+                max_pages: Some(50),
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_values_are_reported() {
+        let manifest = CrawlManifest::from_yaml(
+            r#"
+version: 2
+name: ""
+seeds: ["ftp://example.com/file", "not a url"]
+filters:
+  include: ["("]
+outputs:
+  - type: webhook
+    url: file:///tmp/out
+schedule: "0 3 * * *"
+"#,
+        )
+        .unwrap();
+        let report = manifest.validate();
+        assert!(!report.valid);
+        assert_eq!(report.errors.len(), 7, "errors: {:?}", report.errors);
+        assert!(report.errors[0].contains("Unsupported manifest version 2"));
+        assert!(report
+            .errors
+            .iter()
+            .any(|e| e.starts_with("filters.include[0]")));
+        assert!(report.errors.iter().any(|e| e.starts_with("schedule")));
+    }
+
+    #[test]
+    fn test_filters_excluding_every_seed() {
+        let mut manifest = CrawlManifest::from_yaml(MANIFEST).unwrap();
+        manifest.filters.include = vec!["/blog/".to_string()];
+        let report = manifest.validate();
+        assert!(!report.valid);
+        assert_eq!(report.errors, vec!["No seed passes the filters"]);
+    }
+
+    #[test]
+    fn test_unknown_fields_are_rejected() {
+        let err = CrawlManifest::from_yaml("version: 1\nname: x\nseeds: []\nseed: []\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("unknown field `seed`"), "{}", err);
+
+        assert!(CrawlManifest::from_yaml(
+            "version: 1\nname: x\nseeds: []\noutputs:\n  - type: s3\n    bucket: b\n"
+        )
+        .is_err());
+    }
+}
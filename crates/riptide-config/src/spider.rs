@@ -12,11 +12,13 @@
 //! when used. This is an intentional design to avoid circular dependencies
 //! while still providing configuration management.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 /// Configuration for URL processing during crawling
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
 pub struct UrlProcessingConfig {
     /// Enable URL normalization
     pub enable_normalization: bool,
@@ -84,7 +86,8 @@ impl Default for UrlProcessingConfig {
 }
 
 /// Configuration for performance settings during crawling
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
 pub struct PerformanceConfig {
     /// Maximum concurrent requests globally
     pub max_concurrent_global: usize,
@@ -127,7 +130,8 @@ impl Default for PerformanceConfig {
 /// Note: This is a simplified version that contains only the configuration
 /// that can be managed independently. The full SpiderConfig in riptide-core
 /// includes additional dependencies on spider-specific types.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
 pub struct SpiderConfig {
     /// User agent string
     pub user_agent: String,
@@ -205,6 +205,38 @@ impl CommonValidator {
         Ok(url)
     }
 
+    /// Validate a URL and, when private IPs are blocked, every address its
+    /// host resolves to
+    ///
+    /// Catches public host names pointing at private or loopback addresses.
+    /// Resolution blocks the calling thread, so async callers should run this
+    /// on a blocking task.
+    pub fn validate_url_resolved(&self, url_str: &str) -> Result<Url> {
+        let url = self.validate_url(url_str)?;
+        if !self.config.block_private_ips {
+            return Ok(url);
+        }
+
+        let addrs = url
+            .socket_addrs(|| None)
+            .map_err(|e| anyhow!("Failed to resolve {}: {}", url_str, e))?;
+        if let Some(addr) = addrs.iter().find(|addr| {
+            let ip = addr.ip();
+            ip.is_unspecified() || self.is_private_or_local_address(&ip.to_string())
+        }) {
+            warn!(
+                url = url_str,
+                address = %addr.ip(),
+                "Host resolves to a private or local IP address"
+            );
+            return Err(anyhow!(
+                "Host resolves to a private or local IP address: {}",
+                addr.ip()
+            ));
+        }
+        Ok(url)
+    }
+
     /// Validate content type against allowlist
     pub fn validate_content_type(&self, content_type: &str) -> Result<()> {
         // Extract base content type (ignore charset and other parameters)
@@ -547,6 +579,26 @@ mod tests {
         assert!(validator.validate_url("https://192.168.1.1").is_err());
     }
 
+    #[test]
+    fn test_validate_url_resolved() {
+        let validator = CommonValidator::new_default();
+        let allowed = |url: &str| validator.validate_url_resolved(url).is_ok();
+
+        assert!(!allowed("http://[::1]:8080/"));
+        assert!(!allowed("http://0.0.0.0/"));
+        assert!(!allowed("https://10.0.0.1/"));
+        assert!(allowed("https://93.184.216.34/"));
+
+        let validator = CommonValidator::new(ValidationConfig {
+            block_private_ips: false,
+            blocked_patterns: HashSet::new(),
+            ..Default::default()
+        });
+        assert!(validator
+            .validate_url_resolved("http://[::1]:8080/")
+            .is_ok());
+    }
+
     #[test]
     fn test_content_type_validator() {
         assert!(ContentTypeValidator::is_html("text/html"));
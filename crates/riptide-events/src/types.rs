@@ -101,6 +101,7 @@ pub enum PoolOperation {
     PoolExhausted,
     CircuitBreakerTripped,
     CircuitBreakerReset,
+    ComponentReloaded,
 }
 
 impl PoolOperation {
@@ -119,6 +120,7 @@ impl PoolOperation {
             PoolOperation::PoolExhausted => "pool_exhausted",
             PoolOperation::CircuitBreakerTripped => "circuit_breaker_tripped",
             PoolOperation::CircuitBreakerReset => "circuit_breaker_reset",
+            PoolOperation::ComponentReloaded => "component_reloaded",
         }
     }
 }
@@ -0,0 +1,63 @@
+//! Compare extractor component builds against a baseline
+//!
+//! ```text
+//! cargo run -p riptide-extraction --features wasm-extractor --example wasm_conformance -- \
+//!     [--corpus DIR] [--mode MODE] [--iterations N] [--max-slowdown X] [--report FILE] \
+//!     BASELINE_LABEL=PATH CANDIDATE_LABEL=PATH...
+//! ```
+//!
+//! Prints a markdown summary, writes the full JSON report when `--report` is
+//! given, and exits with status 1 when any candidate is incompatible.
+
+use anyhow::{bail, Context, Result};
+use riptide_extraction::wasm_conformance::{
+    load_corpus, ComponentVersion, ConformanceConfig, ConformanceHarness,
+};
+use std::path::PathBuf;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let mut config = ConformanceConfig::default();
+    let mut corpus = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/corpus");
+    let mut report_path = None;
+    let mut versions = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .with_context(|| format!("{} needs a value", arg))
+        };
+        match arg.as_str() {
+            "--corpus" => corpus = value()?.into(),
+            "--mode" => config.mode = value()?,
+            "--iterations" => config.iterations = value()?.parse()?,
+            "--max-slowdown" => config.max_slowdown = value()?.parse()?,
+            "--report" => report_path = Some(PathBuf::from(value()?)),
+            version => {
+                let (label, path) = version
+                    .split_once('=')
+                    .with_context(|| format!("expected LABEL=PATH, got {}", version))?;
+                versions.push(ComponentVersion::new(label, path));
+            }
+        }
+    }
+    if versions.len() < 2 {
+        bail!("pass a baseline and at least one candidate as LABEL=PATH");
+    }
+
+    let cases = load_corpus(&corpus)?;
+    let report = ConformanceHarness::new(config)
+        .run(&versions, &cases)
+        .await?;
+
+    println!("{}", report.to_markdown());
+    if let Some(path) = report_path {
+        std::fs::write(&path, serde_json::to_string_pretty(&report)?)
+            .with_context(|| format!("writing {}", path.display()))?;
+    }
+    if !report.is_compatible() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,741 @@
+//! Boilerplate removal by text block classification
+//!
+//! In the style of boilerpipe, the content region is cut into text blocks at
+//! block-level elements, and each block is described by shallow features:
+//! its word count, link density and text density, the same densities of its
+//! neighbours, and hints from the tags and `class`/`id` tokens around it. A
+//! linear classifier over those features keeps the blocks that read as
+//! content. Menus, footers, share bars and related-article lists are short or
+//! link-dense and sit in tell-tale containers, so they score low.
+//!
+//! The weights are a [`BlockWeights`] that can be retrained from pages whose
+//! content is known ([`BoilerplateClassifier::label_blocks`] and
+//! [`BoilerplateClassifier::train`]). A site's adjustments, trained weights
+//! plus selectors that force a verdict, are a serializable
+//! [`BoilerplateTweaks`] kept in its domain profile.
+
+use scraper::node::Node;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::markdown::is_skipped;
+
+/// Elements that start and end a text block
+const BLOCK_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "caption",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "menu",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "td",
+    "th",
+    "tr",
+    "ul",
+];
+
+/// Containers whose blocks are usually page chrome
+const BOILERPLATE_TAGS: &[&str] = &["aside", "footer", "form", "header", "menu", "nav"];
+
+/// `class`/`id` tokens marking page chrome
+const BOILERPLATE_HINTS: &[&str] = &[
+    "ad",
+    "ads",
+    "advert",
+    "advertisement",
+    "banner",
+    "breadcrumb",
+    "breadcrumbs",
+    "comment",
+    "comments",
+    "cookie",
+    "footer",
+    "masthead",
+    "menu",
+    "nav",
+    "navbar",
+    "navigation",
+    "newsletter",
+    "pagination",
+    "popular",
+    "promo",
+    "recommended",
+    "related",
+    "share",
+    "sharing",
+    "sidebar",
+    "social",
+    "sponsored",
+    "subscribe",
+    "tags",
+    "widget",
+];
+
+/// Containers whose blocks are usually the article
+const CONTENT_TAGS: &[&str] = &["article", "main"];
+
+/// `class`/`id` tokens marking the article
+const CONTENT_HINTS: &[&str] = &[
+    "article", "body", "content", "entry", "post", "story", "text",
+];
+
+/// Line width text density is measured against, as in boilerpipe
+const WRAP_WIDTH: usize = 80;
+
+/// Shallow text features of one block
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BlockFeatures {
+    /// Words in the block
+    pub words: usize,
+    /// Share of the words inside links (0.0 - 1.0)
+    pub link_density: f64,
+    /// Words per line when wrapped at 80 characters
+    pub text_density: f64,
+    /// Mean link density of the previous and next blocks
+    pub context_link_density: f64,
+    /// Mean text density of the previous and next blocks
+    pub context_text_density: f64,
+    /// The block is a heading
+    pub heading: bool,
+    /// The block is a list item
+    pub list_item: bool,
+    /// The block sits in page chrome (`nav`, `footer`, `.sidebar`, ...)
+    pub boilerplate_hint: bool,
+    /// The block sits in the article (`article`, `main`, `.post-content`, ...)
+    pub content_hint: bool,
+}
+
+impl BlockFeatures {
+    /// Feature vector in [`BlockWeights`] field order, scaled to about 0..1
+    fn vector(&self) -> [f64; 10] {
+        let flag = |value: bool| if value { 1.0 } else { 0.0 };
+        [
+            1.0,
+            (self.words as f64 / 100.0).min(1.0),
+            self.link_density,
+            (self.text_density / 20.0).min(1.0),
+            self.context_link_density,
+            (self.context_text_density / 20.0).min(1.0),
+            flag(self.heading),
+            flag(self.list_item),
+            flag(self.boilerplate_hint),
+            flag(self.content_hint),
+        ]
+    }
+}
+
+/// Weights of the linear block classifier; a block is content when the
+/// weighted sum of its features is positive
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BlockWeights {
+    pub bias: f64,
+    /// Per 100 words, capped at 1
+    pub words: f64,
+    pub link_density: f64,
+    /// Per 20 words a line, capped at 1
+    pub text_density: f64,
+    pub context_link_density: f64,
+    /// Per 20 words a line, capped at 1
+    pub context_text_density: f64,
+    pub heading: f64,
+    pub list_item: f64,
+    pub boilerplate_hint: f64,
+    pub content_hint: f64,
+}
+
+impl Default for BlockWeights {
+    fn default() -> Self {
+        Self {
+            bias: -1.2,
+            words: 2.5,
+            link_density: -4.0,
+            text_density: 2.0,
+            context_link_density: -0.5,
+            context_text_density: 1.0,
+            heading: 0.8,
+            list_item: -0.3,
+            boilerplate_hint: -2.5,
+            content_hint: 1.5,
+        }
+    }
+}
+
+impl BlockWeights {
+    fn to_array(self) -> [f64; 10] {
+        [
+            self.bias,
+            self.words,
+            self.link_density,
+            self.text_density,
+            self.context_link_density,
+            self.context_text_density,
+            self.heading,
+            self.list_item,
+            self.boilerplate_hint,
+            self.content_hint,
+        ]
+    }
+
+    fn from_array(w: [f64; 10]) -> Self {
+        Self {
+            bias: w[0],
+            words: w[1],
+            link_density: w[2],
+            text_density: w[3],
+            context_link_density: w[4],
+            context_text_density: w[5],
+            heading: w[6],
+            list_item: w[7],
+            boilerplate_hint: w[8],
+            content_hint: w[9],
+        }
+    }
+
+    /// Content score of a block; positive means content
+    pub fn score(&self, features: &BlockFeatures) -> f64 {
+        self.to_array()
+            .iter()
+            .zip(features.vector())
+            .map(|(weight, value)| weight * value)
+            .sum()
+    }
+}
+
+/// Per-domain adjustments to the block classifier
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BoilerplateTweaks {
+    /// Trained weights replacing the defaults
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights: Option<BlockWeights>,
+    /// Added to every block score; positive keeps more, negative less
+    #[serde(default)]
+    pub bias_offset: f64,
+    /// CSS selectors whose blocks are always boilerplate
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub boilerplate_selectors: Vec<String>,
+    /// CSS selectors whose blocks are always content (wins when nested
+    /// inside a boilerplate selector)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content_selectors: Vec<String>,
+}
+
+/// A text block and its verdict
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextBlock {
+    /// Whitespace-collapsed text
+    pub text: String,
+    /// Tag of the element the block belongs to
+    pub tag: String,
+    pub features: BlockFeatures,
+    /// Classifier score, before any selector override
+    pub score: f64,
+    pub is_content: bool,
+}
+
+/// Classifies text blocks as content or boilerplate
+#[derive(Debug, Clone, Default)]
+pub struct BoilerplateClassifier {
+    tweaks: BoilerplateTweaks,
+    boilerplate_selectors: Vec<Selector>,
+    content_selectors: Vec<Selector>,
+}
+
+impl BoilerplateClassifier {
+    /// Classifier with the default weights
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Classifier with a site's tweaks; invalid selectors are ignored
+    pub fn with_tweaks(tweaks: BoilerplateTweaks) -> Self {
+        let parse = |selectors: &[String]| {
+            selectors
+                .iter()
+                .filter_map(|selector| match Selector::parse(selector) {
+                    Ok(parsed) => Some(parsed),
+                    Err(e) => {
+                        debug!(selector = %selector, error = ?e, "Ignoring invalid boilerplate selector");
+                        None
+                    }
+                })
+                .collect()
+        };
+        Self {
+            boilerplate_selectors: parse(&tweaks.boilerplate_selectors),
+            content_selectors: parse(&tweaks.content_selectors),
+            tweaks,
+        }
+    }
+
+    /// The tweaks in effect, including weights set by [`Self::train`]
+    pub fn tweaks(&self) -> &BoilerplateTweaks {
+        &self.tweaks
+    }
+
+    /// The weights in effect
+    pub fn weights(&self) -> BlockWeights {
+        self.tweaks.weights.unwrap_or_default()
+    }
+
+    /// Text blocks of `root` in document order, classified
+    pub fn blocks(&self, root: ElementRef<'_>) -> Vec<TextBlock> {
+        let weights = self.weights();
+        segment(root, self)
+            .into_iter()
+            .map(|(block, forced)| {
+                let score = weights.score(&block.features) + self.tweaks.bias_offset;
+                TextBlock {
+                    is_content: forced.unwrap_or(score > 0.0),
+                    score,
+                    ..block
+                }
+            })
+            .collect()
+    }
+
+    /// Text of the content blocks of `root`, one block per line
+    pub fn content_text(&self, root: ElementRef<'_>) -> String {
+        self.blocks(root)
+            .into_iter()
+            .filter(|block| block.is_content)
+            .map(|block| block.text)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Training samples from a page whose content is known: a block is
+    /// content when its text occurs in `content_text`
+    pub fn label_blocks(
+        &self,
+        root: ElementRef<'_>,
+        content_text: &str,
+    ) -> Vec<(BlockFeatures, bool)> {
+        let content = normalize(content_text);
+        segment(root, self)
+            .into_iter()
+            .map(|(block, _)| {
+                let is_content = content.contains(&normalize(&block.text));
+                (block.features, is_content)
+            })
+            .collect()
+    }
+
+    /// Retrain on a page whose content is known, see [`Self::label_blocks`]
+    /// and [`Self::train`]
+    pub fn learn_page(&mut self, html: &str, content_text: &str) -> f64 {
+        let document = Html::parse_document(html);
+        let samples = self.label_blocks(document.root_element(), content_text);
+        self.train(&samples, 100)
+    }
+
+    /// Fit the weights to labelled blocks by logistic regression, starting
+    /// from the current weights, and keep them in [`Self::tweaks`]
+    ///
+    /// Returns the share of samples the new weights classify correctly.
+    pub fn train(&mut self, samples: &[(BlockFeatures, bool)], epochs: usize) -> f64 {
+        const LEARNING_RATE: f64 = 0.1;
+
+        if samples.is_empty() {
+            return 0.0;
+        }
+        let mut weights = self.weights().to_array();
+        for _ in 0..epochs {
+            for (features, is_content) in samples {
+                let x = features.vector();
+                let z: f64 = weights.iter().zip(x).map(|(w, v)| w * v).sum();
+                let error = if *is_content { 1.0 } else { 0.0 } - 1.0 / (1.0 + (-z).exp());
+                for (weight, value) in weights.iter_mut().zip(x) {
+                    *weight += LEARNING_RATE * error * value;
+                }
+            }
+        }
+
+        let weights = BlockWeights::from_array(weights);
+        self.tweaks.weights = Some(weights);
+        let correct = samples
+            .iter()
+            .filter(|(features, is_content)| (weights.score(features) > 0.0) == *is_content)
+            .count();
+        correct as f64 / samples.len() as f64
+    }
+}
+
+/// Hints inherited from the containers around a text run
+#[derive(Debug, Clone, Copy, Default)]
+struct Context<'a> {
+    tag: &'a str,
+    in_link: bool,
+    boilerplate_hint: bool,
+    content_hint: bool,
+    forced: Option<bool>,
+}
+
+/// Block being assembled
+#[derive(Default)]
+struct PendingBlock {
+    text: String,
+    words: usize,
+    linked_words: usize,
+    context: Option<(String, bool, bool, Option<bool>)>,
+}
+
+struct Segmenter<'c> {
+    classifier: &'c BoilerplateClassifier,
+    blocks: Vec<(TextBlock, Option<bool>)>,
+    pending: PendingBlock,
+}
+
+/// Cut `root` into unclassified blocks with their selector overrides
+fn segment(
+    root: ElementRef<'_>,
+    classifier: &BoilerplateClassifier,
+) -> Vec<(TextBlock, Option<bool>)> {
+    let mut segmenter = Segmenter {
+        classifier,
+        blocks: Vec::new(),
+        pending: PendingBlock::default(),
+    };
+
+    // Containers above the region still say what it is
+    let mut context = Context {
+        tag: "body",
+        ..Context::default()
+    };
+    let mut ancestors: Vec<ElementRef> = root.ancestors().filter_map(ElementRef::wrap).collect();
+    ancestors.reverse();
+    for ancestor in ancestors {
+        context = segmenter.enter(ancestor, context);
+    }
+    segmenter.walk(root, context);
+    segmenter.flush();
+
+    let mut blocks = segmenter.blocks;
+    let densities: Vec<(f64, f64)> = blocks
+        .iter()
+        .map(|(block, _)| (block.features.link_density, block.features.text_density))
+        .collect();
+    for (index, (block, _)) in blocks.iter_mut().enumerate() {
+        let neighbours: Vec<(f64, f64)> = [index.checked_sub(1), Some(index + 1)]
+            .into_iter()
+            .flatten()
+            .filter_map(|i| densities.get(i).copied())
+            .collect();
+        let (link, text) = if neighbours.is_empty() {
+            densities[index]
+        } else {
+            let count = neighbours.len() as f64;
+            (
+                neighbours.iter().map(|n| n.0).sum::<f64>() / count,
+                neighbours.iter().map(|n| n.1).sum::<f64>() / count,
+            )
+        };
+        block.features.context_link_density = link;
+        block.features.context_text_density = text;
+    }
+    blocks
+}
+
+impl<'c> Segmenter<'c> {
+    /// Context inside `element`
+    fn enter<'a>(&self, element: ElementRef<'a>, mut context: Context<'a>) -> Context<'a> {
+        let value = element.value();
+        let tag = value.name();
+        let tokens: Vec<String> = value
+            .attr("class")
+            .into_iter()
+            .chain(value.attr("id"))
+            .flat_map(|attr| attr.split(|c: char| !c.is_ascii_alphanumeric()))
+            .filter(|token| !token.is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect();
+        let hinted = |hints: &[&str]| tokens.iter().any(|t| hints.contains(&t.as_str()));
+
+        if BOILERPLATE_TAGS.contains(&tag) || hinted(BOILERPLATE_HINTS) {
+            context.boilerplate_hint = true;
+        }
+        if CONTENT_TAGS.contains(&tag) || hinted(CONTENT_HINTS) {
+            context.content_hint = true;
+        }
+        let matches = |selectors: &[Selector]| selectors.iter().any(|s| s.matches(&element));
+        if matches(&self.classifier.boilerplate_selectors) {
+            context.forced = Some(false);
+        }
+        if matches(&self.classifier.content_selectors) {
+            context.forced = Some(true);
+        }
+        if tag == "a" {
+            context.in_link = true;
+        }
+        if BLOCK_TAGS.contains(&tag) {
+            context.tag = tag;
+        }
+        context
+    }
+
+    fn walk<'a>(&mut self, element: ElementRef<'a>, context: Context<'a>) {
+        if is_skipped(element) {
+            return;
+        }
+        let block = BLOCK_TAGS.contains(&element.value().name());
+        let context = self.enter(element, context);
+        if block {
+            self.flush();
+        }
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => self.text(text, &context),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.walk(child, context);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if block {
+            self.flush();
+        }
+    }
+
+    fn text(&mut self, text: &str, context: &Context<'_>) {
+        let words = text.split_whitespace().count();
+        if words == 0 {
+            return;
+        }
+        let pending = &mut self.pending;
+        pending.context.get_or_insert_with(|| {
+            (
+                context.tag.to_string(),
+                context.boilerplate_hint,
+                context.content_hint,
+                context.forced,
+            )
+        });
+        for word in text.split_whitespace() {
+            if !pending.text.is_empty() {
+                pending.text.push(' ');
+            }
+            pending.text.push_str(word);
+        }
+        pending.words += words;
+        if context.in_link {
+            pending.linked_words += words;
+        }
+    }
+
+    fn flush(&mut self) {
+        let pending = std::mem::take(&mut self.pending);
+        let Some((tag, boilerplate_hint, content_hint, forced)) = pending.context else {
+            return;
+        };
+        let features = BlockFeatures {
+            words: pending.words,
+            link_density: pending.linked_words as f64 / pending.words as f64,
+            text_density: text_density(&pending.text),
+            heading: matches!(tag.as_str(), "h1" | "h2" | "h3" | "h4" | "h5" | "h6"),
+            list_item: matches!(tag.as_str(), "li" | "dd" | "dt"),
+            boilerplate_hint,
+            content_hint,
+            ..BlockFeatures::default()
+        };
+        self.blocks.push((
+            TextBlock {
+                text: pending.text,
+                tag,
+                features,
+                score: 0.0,
+                is_content: false,
+            },
+            forced,
+        ));
+    }
+}
+
+/// Words per line of `text` wrapped at [`WRAP_WIDTH`], leaving out the last
+/// (usually partial) line when there are several
+fn text_density(text: &str) -> f64 {
+    let mut lines: Vec<usize> = vec![0];
+    let mut width = 0;
+    for word in text.split_whitespace() {
+        let length = word.chars().count();
+        if width > 0 && width + 1 + length > WRAP_WIDTH {
+            lines.push(0);
+            width = 0;
+        }
+        width += if width > 0 { length + 1 } else { length };
+        if let Some(words) = lines.last_mut() {
+            *words += 1;
+        }
+    }
+    if lines.len() > 1 {
+        lines.pop();
+    }
+    lines.iter().sum::<usize>() as f64 / lines.len() as f64
+}
+
+/// Lowercased text with whitespace collapsed, for containment checks
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scraper::Html;
+
+    const PAGE: &str = r##"<html><body>
+        <header><a href="/">Home</a> <a href="/news">News</a> <a href="/about">About</a></header>
+        <div class="story-body">
+          <h1>Rivers rise across the valley</h1>
+          <p>Heavy rain over the weekend pushed three rivers past their flood marks, and
+             residents of the lower valley were told to move to higher ground before dawn.</p>
+          <p>Officials said the water would peak on Tuesday afternoon and recede slowly
+             through the rest of the week, leaving roads closed for several days.</p>
+          <div class="share"><a href="#">Share</a> <a href="#">Tweet</a></div>
+          <p>Schools stay closed.</p>
+        </div>
+        <div class="related"><h3>Related</h3><ul>
+          <li><a href="/a">Storm damage in the north</a></li>
+          <li><a href="/b">How to prepare for floods</a></li>
+        </ul></div>
+        <footer>Copyright 2024 Valley News. All rights reserved.</footer>
+    </body></html>"##;
+
+    fn content(classifier: &BoilerplateClassifier) -> String {
+        let document = Html::parse_document(PAGE);
+        classifier.content_text(document.root_element())
+    }
+
+    #[test]
+    fn test_keeps_article_and_drops_chrome() {
+        let text = content(&BoilerplateClassifier::new());
+
+        assert!(text.contains("Rivers rise across the valley"));
+        assert!(text.contains("Heavy rain over the weekend"));
+        assert!(text.contains("recede slowly"));
+        assert!(text.contains("Schools stay closed."));
+        for noise in [
+            "Home",
+            "Tweet",
+            "Storm damage",
+            "prepare for floods",
+            "Copyright",
+        ] {
+            assert!(!text.contains(noise), "{noise} leaked into {text:?}");
+        }
+    }
+
+    #[test]
+    fn test_block_features() {
+        let document = Html::parse_document(PAGE);
+        let blocks = BoilerplateClassifier::new().blocks(document.root_element());
+
+        let header = &blocks[0];
+        assert_eq!(header.text, "Home News About");
+        assert_eq!(header.features.link_density, 1.0);
+        assert!(header.features.boilerplate_hint);
+
+        let heading = blocks.iter().find(|b| b.tag == "h1").unwrap();
+        assert!(heading.features.heading);
+        assert!(heading.features.content_hint);
+
+        let items: Vec<_> = blocks.iter().filter(|b| b.tag == "li").collect();
+        assert_eq!(items.len(), 2);
+        assert!(items.iter().all(|b| b.features.list_item && !b.is_content));
+    }
+
+    #[test]
+    fn test_selectors_force_verdicts() {
+        let tweaks = BoilerplateTweaks {
+            boilerplate_selectors: vec!["h1".to_string(), "not a [selector".to_string()],
+            content_selectors: vec![".related li".to_string()],
+            ..BoilerplateTweaks::default()
+        };
+        let text = content(&BoilerplateClassifier::with_tweaks(tweaks));
+
+        assert!(!text.contains("Rivers rise"));
+        assert!(text.contains("Storm damage in the north"));
+        assert!(text.contains("Heavy rain"));
+    }
+
+    #[test]
+    fn test_training_learns_a_site_layout() {
+        // This site puts its article in an <aside> and its teaser list in
+        // a dense, link-free <div class="content">
+        let page = r#"<html><body>
+            <div class="content"><p>Latest: markets open higher as investors weigh the
+               quarterly earnings season and the outlook for interest rates this year.</p></div>
+            <aside><p>The bridge reopened on Monday after two years of repairs, bringing
+               relief to commuters who had faced long detours through the old town.</p>
+               <p>Engineers replaced the deck and strengthened the piers against floods.</p></aside>
+        </body></html>"#;
+        let article = "The bridge reopened on Monday after two years of repairs, bringing \
+            relief to commuters who had faced long detours through the old town. \
+            Engineers replaced the deck and strengthened the piers against floods.";
+        let document = Html::parse_document(page);
+        let root = document.root_element();
+
+        let mut classifier = BoilerplateClassifier::new();
+        assert!(classifier
+            .content_text(root)
+            .contains("markets open higher"));
+
+        let samples = classifier.label_blocks(root, article);
+        assert_eq!(
+            samples.iter().map(|(_, c)| *c).collect::<Vec<_>>(),
+            vec![false, true, true]
+        );
+        let accuracy = classifier.train(&samples, 200);
+        assert_eq!(accuracy, 1.0);
+
+        let text = classifier.content_text(root);
+        assert!(text.contains("bridge reopened"));
+        assert!(text.contains("Engineers replaced the deck"));
+        assert!(!text.contains("markets open higher"));
+
+        // The trained weights survive a round trip through a profile
+        let tweaks: BoilerplateTweaks =
+            serde_json::from_str(&serde_json::to_string(classifier.tweaks()).unwrap()).unwrap();
+        let restored = BoilerplateClassifier::with_tweaks(tweaks);
+        assert_eq!(restored.content_text(root), text);
+    }
+
+    #[test]
+    fn test_text_density_ignores_last_line() {
+        assert_eq!(text_density("one two three"), 3.0);
+        let long = "word ".repeat(40);
+        // Four-letter words fill an 80 column line 16 at a time
+        assert_eq!(text_density(&long), 16.0);
+    }
+}
@@ -253,6 +253,7 @@ fn create_fixed_chunk(
         topic_keywords: topic_keywords.clone(),
         chunk_type: chunk_type.to_string(),
         custom: std::collections::HashMap::new(),
+        topic_label: None,
     };
 
     let quality_score = utils::calculate_quality_score(content, &metadata);
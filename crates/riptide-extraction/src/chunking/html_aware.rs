@@ -407,6 +407,7 @@ fn create_html_chunk(
         topic_keywords: topic_keywords.clone(),
         chunk_type: format!("html_{}_{}", chunk_type, element.value().name()),
         custom,
+        topic_label: None,
     };
 
     let quality_score = calculate_html_quality_score(content, &metadata);
@@ -453,6 +454,7 @@ fn create_simple_chunk(
         topic_keywords: topic_keywords.clone(),
         chunk_type: format!("html_{}", chunk_type),
         custom: HashMap::new(),
+        topic_label: None,
     };
 
     let quality_score = utils::calculate_quality_score(content, &metadata);
@@ -581,6 +583,7 @@ mod tests {
             topic_keywords: vec!["test".to_string()],
             chunk_type: "html_structure".to_string(),
             custom: HashMap::new(),
+            topic_label: None,
         };
 
         metadata
@@ -13,6 +13,7 @@ pub mod cache;
 pub mod fixed;
 pub mod html_aware;
 pub mod regex_chunker;
+pub mod semantic;
 pub mod sentence;
 pub mod sliding;
 pub mod topic;
@@ -21,6 +22,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Chunking strategy trait for HTML content processing
 #[async_trait]
@@ -75,6 +77,9 @@ pub struct ChunkMetadata {
     pub chunk_type: String,
     /// Additional custom metadata
     pub custom: HashMap<String, String>,
+    /// Topic label shared by chunks about the same topic (semantic chunking)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic_label: Option<String>,
 }
 
 /// Configuration for chunking strategies
@@ -135,6 +140,15 @@ pub enum ChunkingMode {
         /// Smoothing passes for boundary detection
         smoothing_passes: usize,
     },
+    /// Semantic chunking on sentence embeddings, split at topic shifts
+    Semantic {
+        /// Split where adjacent sentence windows fall below this cosine similarity
+        similarity_threshold: f32,
+        /// Sentences of the previous chunk repeated at the start of the next
+        overlap_sentences: usize,
+        /// Sentences averaged on each side of a candidate boundary
+        window_size: usize,
+    },
 }
 
 impl Default for ChunkingMode {
@@ -147,7 +161,21 @@ impl Default for ChunkingMode {
 }
 
 /// Create a chunking strategy based on the specified mode
+///
+/// [`ChunkingMode::Semantic`] needs an embedder; without one it falls back
+/// to topic (TextTiling) chunking. Use [`create_strategy_with_embedder`] to
+/// supply one.
 pub fn create_strategy(mode: ChunkingMode, config: ChunkingConfig) -> Box<dyn ChunkingStrategy> {
+    create_strategy_with_embedder(mode, config, None)
+}
+
+/// Create a chunking strategy, embedding sentences with `embedder` for
+/// [`ChunkingMode::Semantic`]
+pub fn create_strategy_with_embedder(
+    mode: ChunkingMode,
+    config: ChunkingConfig,
+    embedder: Option<Arc<dyn semantic::SentenceEmbedder>>,
+) -> Box<dyn ChunkingStrategy> {
     match mode {
         ChunkingMode::Sliding {
             window_size,
@@ -195,6 +223,23 @@ pub fn create_strategy(mode: ChunkingMode, config: ChunkingConfig) -> Box<dyn Ch
                 Box::new(sliding::SlidingWindowChunker::new(1000, 100, config))
             }
         }
+        ChunkingMode::Semantic {
+            similarity_threshold,
+            overlap_sentences,
+            window_size,
+        } => match embedder {
+            Some(embedder) => Box::new(
+                semantic::SemanticChunker::new(
+                    embedder,
+                    similarity_threshold,
+                    overlap_sentences,
+                    config,
+                )
+                .with_window_size(window_size),
+            ),
+            // Fallback to lexical topic chunking when no embedder is available
+            None => Box::new(topic::TopicChunker::new(window_size, 2, config)),
+        },
     }
 }
 
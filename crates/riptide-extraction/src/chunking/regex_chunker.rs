@@ -182,6 +182,7 @@ fn create_regex_chunk(
         topic_keywords: topic_keywords.clone(),
         chunk_type: format!("regex_{}", sanitize_pattern(pattern)),
         custom: std::collections::HashMap::new(),
+        topic_label: None,
     };
 
     let quality_score = utils::calculate_quality_score(content, &metadata);
@@ -357,6 +358,7 @@ mod tests {
                     topic_keywords: vec![],
                     chunk_type: "test".to_string(),
                     custom: std::collections::HashMap::new(),
+                    topic_label: None,
                 },
             },
             Chunk {
@@ -375,6 +377,7 @@ mod tests {
                     topic_keywords: vec![],
                     chunk_type: "test".to_string(),
                     custom: std::collections::HashMap::new(),
+                    topic_label: None,
                 },
             },
         ];
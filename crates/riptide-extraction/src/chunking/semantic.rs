@@ -0,0 +1,433 @@
+//! Semantic chunking on sentence embeddings
+//!
+//! Every sentence is embedded, and a chunk ends where the mean embedding of
+//! the sentences before a gap stops resembling the mean of the sentences
+//! after it, i.e. where the text changes topic. Chunks are still capped at
+//! `max_tokens`, can repeat the tail of the previous chunk, and carry a topic
+//! label; chunks whose embeddings land close together share a label, so a
+//! topic the document returns to is recognised.
+//!
+//! The embedding model is supplied through [`SentenceEmbedder`]; this crate
+//! carries no model dependency (`riptide-facade` adapts an LLM provider's
+//! `embed()`).
+
+use super::{utils, Chunk, ChunkMetadata, ChunkingConfig, ChunkingStrategy};
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Sentence embedding backend for semantic chunking
+#[async_trait]
+pub trait SentenceEmbedder: Send + Sync {
+    /// Embed `texts`, returning one vector per text in input order
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>>;
+
+    /// Embedder name for logging
+    fn name(&self) -> &str;
+}
+
+/// Topic-aware chunker splitting where sentence embeddings diverge
+pub struct SemanticChunker {
+    embedder: Arc<dyn SentenceEmbedder>,
+    /// Boundary when adjacent windows are less similar than this (cosine)
+    similarity_threshold: f32,
+    /// Sentences of the previous chunk repeated at the start of the next
+    overlap_sentences: usize,
+    /// Sentences averaged on each side of a candidate boundary
+    window_size: usize,
+    /// Sentences embedded per embedder call
+    batch_size: usize,
+    config: ChunkingConfig,
+}
+
+/// A label shared by chunks with similar embeddings
+struct Topic {
+    centroid: Vec<f32>,
+    label: String,
+}
+
+impl SemanticChunker {
+    /// Create a semantic chunker
+    pub fn new(
+        embedder: Arc<dyn SentenceEmbedder>,
+        similarity_threshold: f32,
+        overlap_sentences: usize,
+        config: ChunkingConfig,
+    ) -> Self {
+        Self {
+            embedder,
+            similarity_threshold: similarity_threshold.clamp(-1.0, 1.0),
+            overlap_sentences,
+            window_size: 2,
+            batch_size: 32,
+            config,
+        }
+    }
+
+    /// Sentences averaged on each side of a candidate boundary (default 2)
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size.max(1);
+        self
+    }
+
+    /// Sentences embedded per embedder call (default 32)
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Embed the sentences in batches, normalised to unit length
+    async fn embed(&self, sentences: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(sentences.len());
+        for batch in sentences.chunks(self.batch_size) {
+            let vectors = self.embedder.embed_batch(batch).await?;
+            if vectors.len() != batch.len() {
+                bail!(
+                    "Embedder '{}' returned {} embeddings for {} sentences",
+                    self.embedder.name(),
+                    vectors.len(),
+                    batch.len()
+                );
+            }
+            embeddings.extend(vectors.into_iter().map(normalized));
+        }
+
+        let dimensions = embeddings.first().map_or(0, Vec::len);
+        if dimensions == 0 || embeddings.iter().any(|v| v.len() != dimensions) {
+            bail!(
+                "Embedder '{}' returned empty or inconsistent embeddings",
+                self.embedder.name()
+            );
+        }
+        Ok(embeddings)
+    }
+
+    /// Similarity across each gap: `similarities[i]` compares the windows
+    /// ending at and starting from sentence `i + 1`
+    fn gap_similarities(&self, embeddings: &[Vec<f32>]) -> Vec<f32> {
+        (1..embeddings.len())
+            .map(|gap| {
+                let left = mean(&embeddings[gap.saturating_sub(self.window_size)..gap]);
+                let right = mean(&embeddings[gap..(gap + self.window_size).min(embeddings.len())]);
+                cosine(&left, &right)
+            })
+            .collect()
+    }
+
+    /// Group sentences into segments at topic shifts, without exceeding
+    /// `max_tokens` or ending a segment below `min_chunk_size` characters
+    fn segment(
+        &self,
+        spans: &[Range<usize>],
+        tokens: &[usize],
+        similarities: &[f32],
+    ) -> Vec<Range<usize>> {
+        let mut segments = Vec::new();
+        let mut start = 0;
+        let mut segment_tokens = tokens[0];
+
+        for sentence in 1..spans.len() {
+            let segment_chars = spans[sentence - 1].end - spans[start].start;
+            let topic_shift = similarities[sentence - 1] < self.similarity_threshold
+                && segment_chars >= self.config.min_chunk_size;
+            let full = segment_tokens + tokens[sentence] > self.config.max_tokens;
+            if topic_shift || full {
+                segments.push(start..sentence);
+                start = sentence;
+                segment_tokens = 0;
+            }
+            segment_tokens += tokens[sentence];
+        }
+        segments.push(start..spans.len());
+        segments
+    }
+
+    /// Label of the closest known topic, or a new one named after `content`
+    fn topic_for(&self, centroid: Vec<f32>, content: &str, topics: &mut Vec<Topic>) -> usize {
+        let closest = topics
+            .iter()
+            .enumerate()
+            .map(|(index, topic)| (index, cosine(&centroid, &topic.centroid)))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((index, similarity)) = closest {
+            if similarity >= self.similarity_threshold {
+                return index;
+            }
+        }
+
+        let keywords = utils::extract_topic_keywords(content);
+        let label = if keywords.is_empty() {
+            format!("topic {}", topics.len() + 1)
+        } else {
+            keywords.into_iter().take(3).collect::<Vec<_>>().join(" / ")
+        };
+        topics.push(Topic { centroid, label });
+        topics.len() - 1
+    }
+}
+
+#[async_trait]
+impl ChunkingStrategy for SemanticChunker {
+    async fn chunk(&self, text: &str) -> Result<Vec<Chunk>> {
+        let spans = sentence_spans(text);
+        if spans.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let sentences: Vec<String> = spans.iter().map(|s| text[s.clone()].to_string()).collect();
+        let tokens: Vec<usize> = sentences.iter().map(|s| utils::count_tokens(s)).collect();
+        let embeddings = self.embed(sentences).await?;
+        let similarities = self.gap_similarities(&embeddings);
+        let segments = self.segment(&spans, &tokens, &similarities);
+
+        let mut topics = Vec::new();
+        let total_chunks = segments.len();
+        let mut chunks = Vec::with_capacity(total_chunks);
+        let mut previous_start = 0;
+
+        for (chunk_index, segment) in segments.into_iter().enumerate() {
+            let overlap = self.overlap_sentences.min(segment.start - previous_start);
+            let first = segment.start - overlap;
+            previous_start = segment.start;
+
+            let start_pos = spans[first].start;
+            let end_pos = spans[segment.end - 1].end;
+            let content = text[start_pos..end_pos].to_string();
+            let centroid = normalized(mean(&embeddings[segment.clone()]));
+            let topic_index = self.topic_for(
+                centroid,
+                &content[spans[segment.start].start - start_pos..],
+                &mut topics,
+            );
+
+            let mut custom = HashMap::new();
+            custom.insert("topic_index".to_string(), topic_index.to_string());
+            custom.insert("overlap_sentences".to_string(), overlap.to_string());
+            if segment.start > 0 {
+                custom.insert(
+                    "boundary_similarity".to_string(),
+                    format!("{:.3}", similarities[segment.start - 1]),
+                );
+            }
+
+            let mut metadata = ChunkMetadata {
+                quality_score: 0.0,
+                sentence_count: segment.end - first,
+                word_count: content.split_whitespace().count(),
+                has_complete_sentences: content.ends_with(['.', '!', '?']),
+                topic_keywords: utils::extract_topic_keywords(&content),
+                chunk_type: "semantic".to_string(),
+                custom,
+                topic_label: Some(topics[topic_index].label.clone()),
+            };
+            metadata.quality_score = utils::calculate_quality_score(&content, &metadata);
+
+            chunks.push(Chunk {
+                id: Uuid::new_v4().to_string(),
+                token_count: tokens[first..segment.end].iter().sum(),
+                content,
+                start_pos,
+                end_pos,
+                chunk_index,
+                total_chunks,
+                metadata,
+            });
+        }
+
+        Ok(chunks)
+    }
+
+    fn name(&self) -> &str {
+        "semantic"
+    }
+
+    fn config(&self) -> ChunkingConfig {
+        self.config.clone()
+    }
+}
+
+/// Byte ranges of the trimmed sentences in `text`
+///
+/// A sentence ends at `.`, `!` or `?` followed by whitespace, or at a blank
+/// line, so headings and list items stand on their own.
+fn sentence_spans(text: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        let at_break = match c {
+            '.' | '!' | '?' => chars.peek().is_none_or(|(_, next)| next.is_whitespace()),
+            '\n' => chars.peek().is_some_and(|(_, next)| *next == '\n'),
+            _ => false,
+        };
+        if at_break {
+            let end = index + c.len_utf8();
+            push_trimmed(text, start..end, &mut spans);
+            start = end;
+        }
+    }
+    push_trimmed(text, start..text.len(), &mut spans);
+    spans
+}
+
+fn push_trimmed(text: &str, range: Range<usize>, spans: &mut Vec<Range<usize>>) {
+    let slice = &text[range.clone()];
+    let trimmed = slice.trim();
+    if !trimmed.is_empty() {
+        let start = range.start + (slice.len() - slice.trim_start().len());
+        spans.push(start..start + trimmed.len());
+    }
+}
+
+fn mean(vectors: &[Vec<f32>]) -> Vec<f32> {
+    let mut sum = vec![0.0; vectors.first().map_or(0, Vec::len)];
+    for vector in vectors {
+        for (total, value) in sum.iter_mut().zip(vector) {
+            *total += value;
+        }
+    }
+    let count = vectors.len().max(1) as f32;
+    sum.iter_mut().for_each(|value| *value /= count);
+    sum
+}
+
+fn normalized(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|value| *value /= norm);
+    }
+    vector
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|v| v * v).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Embeds a sentence as counts of cooking, football and finance words
+    struct VocabularyEmbedder;
+
+    #[async_trait]
+    impl SentenceEmbedder for VocabularyEmbedder {
+        async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            const TOPICS: [&[&str]; 3] = [
+                &["oven", "flour", "bake", "dough", "bread"],
+                &["goal", "match", "striker", "league", "referee"],
+                &["shares", "market", "dividend", "investors", "stock"],
+            ];
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let text = text.to_lowercase();
+                    TOPICS
+                        .iter()
+                        .map(|words| words.iter().filter(|w| text.contains(*w)).count() as f32)
+                        .collect()
+                })
+                .collect())
+        }
+
+        fn name(&self) -> &str {
+            "vocabulary"
+        }
+    }
+
+    const TEXT: &str = "Preheat the oven before you bake. Knead the dough with flour. \
+        Fresh bread needs a hot oven.\n\n\
+        The striker scored a late goal. The referee ended the match. \
+        The league title is still open. \
+        Bake the dough until the bread is golden.";
+
+    fn chunker(overlap_sentences: usize) -> SemanticChunker {
+        let config = ChunkingConfig {
+            min_chunk_size: 0,
+            ..ChunkingConfig::default()
+        };
+        SemanticChunker::new(Arc::new(VocabularyEmbedder), 0.5, overlap_sentences, config)
+            .with_window_size(1)
+    }
+
+    #[tokio::test]
+    async fn test_splits_on_topic_shifts_and_labels_recurring_topics() {
+        let chunks = chunker(0).chunk(TEXT).await.unwrap();
+
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[0].content.starts_with("Preheat"));
+        assert!(chunks[0].content.ends_with("hot oven."));
+        assert!(chunks[1].content.starts_with("The striker"));
+        assert_eq!(
+            chunks[2].content,
+            "Bake the dough until the bread is golden."
+        );
+        for chunk in &chunks {
+            assert_eq!(&TEXT[chunk.start_pos..chunk.end_pos], chunk.content);
+            assert_eq!(chunk.total_chunks, 3);
+            assert_eq!(chunk.metadata.chunk_type, "semantic");
+        }
+
+        let labels: Vec<_> = chunks
+            .iter()
+            .map(|c| c.metadata.topic_label.clone().unwrap())
+            .collect();
+        assert_ne!(labels[0], labels[1]);
+        assert_eq!(labels[0], labels[2]);
+        assert_eq!(chunks[2].metadata.custom["topic_index"], "0");
+    }
+
+    #[tokio::test]
+    async fn test_overlap_repeats_previous_sentences() {
+        let chunks = chunker(1).chunk(TEXT).await.unwrap();
+
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[1]
+            .content
+            .starts_with("Fresh bread needs a hot oven."));
+        assert_eq!(chunks[1].metadata.custom["overlap_sentences"], "1");
+        assert_eq!(chunks[1].metadata.sentence_count, 4);
+        // The overlap does not change which topic the chunk is labelled with
+        assert_ne!(
+            chunks[0].metadata.topic_label,
+            chunks[1].metadata.topic_label
+        );
+    }
+
+    #[tokio::test]
+    async fn test_token_cap_splits_within_a_topic() {
+        let config = ChunkingConfig {
+            max_tokens: 12,
+            min_chunk_size: 0,
+            ..ChunkingConfig::default()
+        };
+        let chunker = SemanticChunker::new(Arc::new(VocabularyEmbedder), 0.5, 0, config);
+        let chunks = chunker.chunk(TEXT).await.unwrap();
+
+        assert!(chunks.len() > 3);
+        assert!(chunks
+            .iter()
+            .all(|c| c.metadata.sentence_count == 1 || c.token_count <= 12));
+    }
+
+    #[test]
+    fn test_sentence_spans_skip_decimals_and_split_blank_lines() {
+        let text = "Version 2.5 ships today. Notes\n\nSecond part!";
+        let sentences: Vec<_> = sentence_spans(text).into_iter().map(|s| &text[s]).collect();
+        assert_eq!(
+            sentences,
+            vec!["Version 2.5 ships today.", "Notes", "Second part!"]
+        );
+    }
+}
@@ -135,6 +135,7 @@ fn create_sentence_chunk(
         topic_keywords: topic_keywords.clone(),
         chunk_type: "sentence".to_string(),
         custom: std::collections::HashMap::new(),
+        topic_label: None,
     };
 
     let quality_score = utils::calculate_quality_score(&content, &metadata);
@@ -151,6 +151,7 @@ fn create_chunk(
         topic_keywords: topic_keywords.clone(),
         chunk_type: "sliding".to_string(),
         custom: std::collections::HashMap::new(),
+        topic_label: None,
     };
 
     let quality_score = utils::calculate_quality_score(content, &metadata);
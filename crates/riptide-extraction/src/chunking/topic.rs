@@ -623,6 +623,7 @@ impl TopicChunker {
                 topic_keywords,
                 chunk_type: "topic".to_string(),
                 custom: HashMap::new(),
+                topic_label: None,
             };
 
             let chunk = Chunk {
@@ -783,6 +784,7 @@ impl TopicChunker {
             topic_keywords: crate::chunking::utils::extract_topic_keywords(text),
             chunk_type: "topic-single".to_string(),
             custom: HashMap::new(),
+            topic_label: None,
         };
 
         Ok(vec![Chunk {
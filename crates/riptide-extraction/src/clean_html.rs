@@ -0,0 +1,505 @@
+//! Sanitized, reader-mode HTML
+//!
+//! [`CleanHtmlConverter`] re-serializes the content of a page as HTML that is
+//! safe to render elsewhere: only an allowlist of content tags and
+//! attributes survives, scripts, styles, forms, embeds and hidden elements
+//! are dropped with their content, tracking pixels and `utm_*` style link
+//! parameters are removed, and link and image URLs are made absolute. Images
+//! can be routed through an image proxy so readers never hit the origin.
+//!
+//! Layout wrappers such as `<span>` and `<font>` are unwrapped and every
+//! `class`, `id`, `style` and event handler attribute is stripped, leaving the
+//! styling to the product re-rendering the article.
+
+use scraper::node::Node;
+use scraper::{ElementRef, Html, Selector};
+use url::Url;
+
+use crate::markdown::is_skipped;
+
+/// Elements kept, without attributes unless listed in [`ALLOWED_ATTRIBUTES`]
+const ALLOWED_TAGS: &[&str] = &[
+    // Blocks
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "caption",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "tr",
+    "ul",
+    // Inline
+    "a",
+    "abbr",
+    "b",
+    "br",
+    "cite",
+    "code",
+    "del",
+    "dfn",
+    "em",
+    "i",
+    "img",
+    "ins",
+    "kbd",
+    "mark",
+    "q",
+    "s",
+    "samp",
+    "small",
+    "strong",
+    "sub",
+    "sup",
+    "time",
+    "u",
+    "var",
+    "wbr",
+];
+
+/// Attributes kept per tag; URLs are resolved and filtered separately
+const ALLOWED_ATTRIBUTES: &[(&str, &[&str])] = &[
+    ("a", &["href", "title"]),
+    ("img", &["src", "alt", "title", "width", "height"]),
+    ("abbr", &["title"]),
+    ("dfn", &["title"]),
+    ("blockquote", &["cite"]),
+    ("q", &["cite"]),
+    ("del", &["cite", "datetime"]),
+    ("ins", &["cite", "datetime"]),
+    ("time", &["datetime"]),
+    ("ol", &["start", "reversed"]),
+    ("li", &["value"]),
+    ("td", &["colspan", "rowspan"]),
+    ("th", &["colspan", "rowspan", "scope"]),
+    ("details", &["open"]),
+];
+
+/// Elements dropped with their content on top of the Markdown converter's
+const SKIPPED_TAGS: &[&str] = &["form", "input", "label", "link", "meta", "audio", "video"];
+
+/// Elements written without a closing tag
+const VOID_TAGS: &[&str] = &["br", "hr", "img", "wbr"];
+
+/// Elements written after a line break, for readable output
+const BLOCK_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "caption",
+    "dd",
+    "details",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "main",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "tbody",
+    "tfoot",
+    "thead",
+    "tr",
+    "ul",
+];
+
+/// Hosts serving tracking pixels and beacons
+const TRACKER_HOSTS: &[&str] = &[
+    "doubleclick.net",
+    "google-analytics.com",
+    "googletagmanager.com",
+    "scorecardresearch.com",
+    "quantserve.com",
+    "pixel.wp.com",
+    "bat.bing.com",
+    "pixel.facebook.com",
+];
+
+/// Query parameters that only track the click
+const TRACKING_PARAMS: &[&str] = &["fbclid", "gclid", "msclkid", "mc_cid", "mc_eid", "igshid"];
+
+/// Regions tried in order by [`CleanHtmlConverter::convert`]
+const READER_ROOTS: &[&str] = &["article", "main", "[role='main']", "body"];
+
+/// Sanitize the main content of an HTML document
+pub fn html_to_clean_html(html: &str, url: &str) -> String {
+    CleanHtmlConverter::new().with_base_url(url).convert(html)
+}
+
+/// HTML sanitizer producing reader-mode HTML
+#[derive(Debug, Clone, Default)]
+pub struct CleanHtmlConverter {
+    base_url: Option<Url>,
+    image_proxy: Option<String>,
+}
+
+impl CleanHtmlConverter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve relative link and image URLs against `base_url`
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Url::parse(base_url).ok();
+        self
+    }
+
+    /// Rewrite image sources to `image_proxy` followed by the
+    /// percent-encoded image URL, e.g. `https://img.example.com/?url=`
+    pub fn with_image_proxy(mut self, image_proxy: impl Into<String>) -> Self {
+        self.image_proxy = Some(image_proxy.into()).filter(|proxy| !proxy.trim().is_empty());
+        self
+    }
+
+    /// Sanitize the main region of a document: its first `<article>`, else
+    /// `<main>`, else the body
+    pub fn convert(&self, html: &str) -> String {
+        let document = Html::parse_document(html);
+        let root = READER_ROOTS
+            .iter()
+            .filter_map(|selector| Selector::parse(selector).ok())
+            .find_map(|selector| document.select(&selector).next())
+            .unwrap_or_else(|| document.root_element());
+        self.convert_element(root)
+    }
+
+    /// Sanitize `element` and its descendants
+    pub fn convert_element(&self, element: ElementRef<'_>) -> String {
+        let mut out = String::new();
+        self.element(element, false, &mut out);
+        out.trim().to_string()
+    }
+
+    fn element(&self, element: ElementRef<'_>, preformatted: bool, out: &mut String) {
+        if is_skipped(element) || SKIPPED_TAGS.contains(&element.value().name()) {
+            return;
+        }
+        let tag = element.value().name();
+        if !ALLOWED_TAGS.contains(&tag) {
+            self.children(element, preformatted, out);
+            return;
+        }
+
+        let Some(attributes) = self.attributes(element) else {
+            // A link to nowhere keeps its text, a tracker or unusable image goes
+            if tag == "a" {
+                self.children(element, preformatted, out);
+            }
+            return;
+        };
+        if VOID_TAGS.contains(&tag) {
+            out.push_str(&format!("<{}{}>", tag, attributes));
+            if BLOCK_TAGS.contains(&tag) {
+                out.push('\n');
+            }
+            return;
+        }
+
+        let mut content = String::new();
+        self.children(element, preformatted || tag == "pre", &mut content);
+        if content.trim().is_empty() && !matches!(tag, "td" | "th") {
+            return;
+        }
+        let block = BLOCK_TAGS.contains(&tag);
+        if block {
+            let kept = out.trim_end_matches(' ').len();
+            out.truncate(kept);
+            if !out.is_empty() && !out.ends_with('\n') {
+                out.push('\n');
+            }
+        }
+        // Blocks holding blocks open and close on lines of their own; outside
+        // preformatted text only block children leave newlines behind
+        let holds_blocks = block && !preformatted && tag != "pre" && content.contains('\n');
+        if holds_blocks {
+            content.truncate(content.trim_end().len());
+            content.push('\n');
+        }
+        out.push_str(&format!("<{}{}>", tag, attributes));
+        if holds_blocks {
+            out.push('\n');
+        }
+        out.push_str(&content);
+        out.push_str(&format!("</{}>", tag));
+        if block {
+            out.push('\n');
+        }
+    }
+
+    fn children(&self, element: ElementRef<'_>, preformatted: bool, out: &mut String) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) if preformatted => out.push_str(&escape(text, false)),
+                Node::Text(text) => {
+                    let collapsed = collapse_whitespace(text);
+                    // Whitespace between blocks carries no content
+                    if collapsed == " " && (out.is_empty() || out.ends_with('\n')) {
+                        continue;
+                    }
+                    out.push_str(&escape(&collapsed, false));
+                }
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.element(child, preformatted, out);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Serialized allowed attributes of `element`, `None` when the element
+    /// must go: a link without a safe target, or an unusable image
+    fn attributes(&self, element: ElementRef<'_>) -> Option<String> {
+        let value = element.value();
+        let tag = value.name();
+        let allowed = ALLOWED_ATTRIBUTES
+            .iter()
+            .find(|(name, _)| *name == tag)
+            .map(|(_, attributes)| *attributes)
+            .unwrap_or_default();
+
+        let mut out = String::new();
+        for name in allowed {
+            let attribute = match (tag, *name) {
+                ("a", "href") => Some(self.link(value.attr("href")?)?),
+                ("img", "src") => Some(self.image(element)?),
+                (_, "cite") => value.attr("cite").and_then(|cite| self.link(cite)),
+                _ => value.attr(name).map(str::trim).map(str::to_string),
+            };
+            if let Some(attribute) = attribute {
+                out.push_str(&format!(" {}=\"{}\"", name, escape(&attribute, true)));
+            }
+        }
+        // Syntax highlighting hints survive for code blocks
+        if matches!(tag, "pre" | "code") {
+            if let Some(language) = value
+                .classes()
+                .find(|class| class.starts_with("language-") || class.starts_with("lang-"))
+            {
+                out.push_str(&format!(" class=\"{}\"", escape(language, true)));
+            }
+        }
+        Some(out)
+    }
+
+    /// Absolute link target without tracking parameters, `None` for
+    /// anything but web, mail and phone links
+    fn link(&self, href: &str) -> Option<String> {
+        let href = href.trim();
+        if href.is_empty() {
+            return None;
+        }
+        let mut url = match &self.base_url {
+            Some(base) => base.join(href).ok()?,
+            None => Url::parse(href).ok()?,
+        };
+        match url.scheme() {
+            "http" | "https" => {}
+            "mailto" | "tel" => return Some(url.to_string()),
+            _ => return None,
+        }
+        if url.query().is_some() {
+            let kept: Vec<(String, String)> = url
+                .query_pairs()
+                .filter(|(key, _)| {
+                    !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_ref())
+                })
+                .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                .collect();
+            if kept.is_empty() {
+                url.set_query(None);
+            } else {
+                url.query_pairs_mut().clear().extend_pairs(kept);
+            }
+        }
+        Some(url.to_string())
+    }
+
+    /// Absolute, possibly proxied image source; `None` for tracking pixels,
+    /// inline data and non-web sources
+    fn image(&self, element: ElementRef<'_>) -> Option<String> {
+        let value = element.value();
+        let dimension = |name: &str| {
+            value
+                .attr(name)
+                .and_then(|v| v.trim().trim_end_matches("px").parse::<u32>().ok())
+        };
+        if matches!((dimension("width"), dimension("height")), (Some(w), Some(h)) if w <= 1 && h <= 1)
+        {
+            return None;
+        }
+
+        // Lazy-loaded images keep the real source in a data attribute
+        let src = ["data-src", "data-lazy-src", "data-original", "src"]
+            .iter()
+            .filter_map(|name| value.attr(name).map(str::trim))
+            .find(|src| !src.is_empty() && !src.starts_with("data:"))?;
+        let url = match &self.base_url {
+            Some(base) => base.join(src).ok()?,
+            None => Url::parse(src).ok()?,
+        };
+        if !matches!(url.scheme(), "http" | "https") {
+            return None;
+        }
+        let host = url.host_str().unwrap_or_default();
+        if TRACKER_HOSTS
+            .iter()
+            .any(|tracker| host == *tracker || host.ends_with(&format!(".{}", tracker)))
+        {
+            return None;
+        }
+
+        Some(match &self.image_proxy {
+            Some(proxy) => format!(
+                "{}{}",
+                proxy,
+                url::form_urlencoded::byte_serialize(url.as_str().as_bytes()).collect::<String>()
+            ),
+            None => url.to_string(),
+        })
+    }
+}
+
+/// Collapse whitespace runs to one space, like a browser
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            space = true;
+            continue;
+        }
+        if space {
+            out.push(' ');
+            space = false;
+        }
+        out.push(c);
+    }
+    if space {
+        out.push(' ');
+    }
+    out
+}
+
+pub(crate) fn escape(text: &str, attribute: bool) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' if attribute => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strips_active_content_and_attributes() {
+        let html = r#"<html><head><style>p{}</style></head><body>
+            <nav><a href="/">Home</a></nav>
+            <article class="post" onclick="track()">
+              <h1 id="t" style="color:red">Title &amp; more</h1>
+              <script>alert(1)</script>
+              <p>Read <a href="javascript:alert(1)">this</a> and
+                 <a href="/next?id=7&utm_source=feed&fbclid=x" onmouseover="x()">that</a>.</p>
+              <div hidden><p>Hidden</p></div>
+              <form><input name="q"></form>
+              <p><span><font>Plain &lt;text&gt;</font></span></p>
+              <pre class="language-rust extra"><code>fn  main() {
+    x &lt; y
+}</code></pre>
+              <p>   </p>
+            </article></body></html>"#;
+
+        let clean = html_to_clean_html(html, "https://example.com/blog/post");
+        assert_eq!(
+            clean,
+            "<article>\n\
+             <h1>Title &amp; more</h1>\n\
+             <p>Read this and <a href=\"https://example.com/next?id=7\">that</a>.</p>\n\
+             <p>Plain &lt;text&gt;</p>\n\
+             <pre class=\"language-rust\"><code>fn  main() {\n    x &lt; y\n}</code></pre>\n\
+             </article>"
+        );
+    }
+
+    #[test]
+    fn test_images_absolutized_proxied_and_trackers_dropped() {
+        let html = r#"<main>
+            <figure><img data-src="img/a b.png" src="data:image/gif;base64,R0l" alt="A &quot;photo&quot;" class="lazy">
+              <figcaption>Caption</figcaption></figure>
+            <img src="https://www.google-analytics.com/collect?v=1" alt="">
+            <img src="/pixel.gif" width="1" height="1">
+            <p><a href="/gallery"><img src="//cdn.example.com/b.jpg" width="640"></a></p>
+        </main>"#;
+
+        let converter = CleanHtmlConverter::new().with_base_url("https://example.com/post/");
+        let clean = converter.convert(html);
+        assert!(clean.contains(
+            "<img src=\"https://example.com/post/img/a%20b.png\" alt=\"A &quot;photo&quot;\">"
+        ));
+        assert!(clean.contains("<figcaption>Caption</figcaption>"));
+        assert!(!clean.contains("google-analytics"));
+        assert!(!clean.contains("pixel.gif"));
+        assert!(clean.contains(
+            "<a href=\"https://example.com/gallery\"><img src=\"https://cdn.example.com/b.jpg\" width=\"640\"></a>"
+        ));
+
+        let proxied = converter
+            .with_image_proxy("https://img.example.net/?url=")
+            .convert(html);
+        assert!(proxied.contains(
+            "src=\"https://img.example.net/?url=https%3A%2F%2Fcdn.example.com%2Fb.jpg\""
+        ));
+    }
+}
@@ -6,6 +6,13 @@
 //! - **Fallback**: Try primary, fallback to secondary on failure
 //! - **Best**: Run all strategies and pick the one with highest confidence
 //!
+//! In parallel mode, [`StrategyComposer::with_field_merge`] picks each field
+//! (title, content, summary, author, published date) by its own
+//! [`FieldMergePolicy`] and reports where the strategies disagreed.
+//!
+//! With a [`ConfidenceCalibrator`] attached, each strategy's confidence is
+//! calibrated by strategy name before thresholds and comparisons apply.
+//!
 //! ## Example
 //!
 //! ```rust,no_run
@@ -18,16 +25,18 @@
 //! let result = composer.execute(html, url).await?;
 //! ```
 
+use crate::confidence::calibration::ConfidenceCalibrator;
 use crate::strategies::traits::{ExtractionResult, ExtractionStrategy};
 use crate::strategies::PerformanceMetrics;
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
-use riptide_types::ExtractedContent;
+use riptide_types::{ExtractedContent, ExtractedDoc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::time::timeout;
+use tracing::debug;
 
 /// Composition mode for combining multiple strategies
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
@@ -291,6 +300,435 @@ impl ResultMerger for BestContentMerger {
     }
 }
 
+/// How one field's value is chosen across strategy results
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldMergePolicy {
+    /// The value most strategies agree on, ties broken by summed confidence
+    MajorityVote,
+    /// The value from the most confident strategy
+    HighestConfidence,
+    /// The first non-empty value in strategy order
+    FirstNonEmpty,
+}
+
+/// Document fields merged by [`FieldMerger`]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeField {
+    Title,
+    Content,
+    Summary,
+    /// `author` metadata
+    Author,
+    /// `published_date` (or `date`) metadata
+    Published,
+}
+
+impl MergeField {
+    /// All merged fields, in report order
+    pub const ALL: [MergeField; 5] = [
+        MergeField::Title,
+        MergeField::Content,
+        MergeField::Summary,
+        MergeField::Author,
+        MergeField::Published,
+    ];
+
+    /// Field name as used in reports and metadata
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MergeField::Title => "title",
+            MergeField::Content => "content",
+            MergeField::Summary => "summary",
+            MergeField::Author => "author",
+            MergeField::Published => "published",
+        }
+    }
+
+    /// Non-empty value of this field in a result
+    fn value(self, result: &ExtractionResult) -> Option<&str> {
+        let value = match self {
+            MergeField::Title => Some(result.content.title.as_str()),
+            MergeField::Content => Some(result.content.content.as_str()),
+            MergeField::Summary => result.content.summary.as_deref(),
+            MergeField::Author => result.metadata.get("author").map(String::as_str),
+            MergeField::Published => result
+                .metadata
+                .get("published_date")
+                .or_else(|| result.metadata.get("date"))
+                .map(String::as_str),
+        };
+        value.map(str::trim).filter(|v| !v.is_empty())
+    }
+}
+
+/// Per-field policies for [`FieldMerger`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FieldMergeConfig {
+    pub title: FieldMergePolicy,
+    pub content: FieldMergePolicy,
+    pub summary: FieldMergePolicy,
+    pub author: FieldMergePolicy,
+    pub published: FieldMergePolicy,
+    /// Minimum confidence for a result to take part in the merge
+    pub min_confidence: f64,
+}
+
+impl Default for FieldMergeConfig {
+    fn default() -> Self {
+        Self {
+            title: FieldMergePolicy::MajorityVote,
+            content: FieldMergePolicy::HighestConfidence,
+            summary: FieldMergePolicy::FirstNonEmpty,
+            author: FieldMergePolicy::MajorityVote,
+            published: FieldMergePolicy::MajorityVote,
+            min_confidence: 0.5,
+        }
+    }
+}
+
+impl FieldMergeConfig {
+    /// Use one policy for every field
+    pub fn uniform(policy: FieldMergePolicy) -> Self {
+        Self {
+            title: policy,
+            content: policy,
+            summary: policy,
+            author: policy,
+            published: policy,
+            ..Self::default()
+        }
+    }
+
+    /// Policy applied to a field
+    pub fn policy(&self, field: MergeField) -> FieldMergePolicy {
+        match field {
+            MergeField::Title => self.title,
+            MergeField::Content => self.content,
+            MergeField::Summary => self.summary,
+            MergeField::Author => self.author,
+            MergeField::Published => self.published,
+        }
+    }
+}
+
+/// Candidate values of one field and the value the merge chose
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldDisagreement {
+    pub field: MergeField,
+    pub policy: FieldMergePolicy,
+    /// Strategy whose value was chosen
+    pub chosen_from: Option<String>,
+    /// Non-empty value reported by each strategy
+    pub candidates: BTreeMap<String, String>,
+    /// Share of candidates that agree with the chosen value (1.0 when unanimous)
+    pub agreement: f64,
+}
+
+impl FieldDisagreement {
+    /// Whether strategies reported different values for this field
+    pub fn is_disputed(&self) -> bool {
+        self.agreement < 1.0
+    }
+}
+
+/// Field-by-field agreement between the merged strategies
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DisagreementReport {
+    /// Strategies that took part in the merge, in order
+    pub strategies: Vec<String>,
+    /// One entry per field at least one strategy reported
+    pub fields: Vec<FieldDisagreement>,
+}
+
+impl DisagreementReport {
+    /// Whether any field was disputed
+    pub fn has_disagreements(&self) -> bool {
+        self.fields.iter().any(FieldDisagreement::is_disputed)
+    }
+
+    /// Fields the strategies disagreed on
+    pub fn disputed_fields(&self) -> Vec<MergeField> {
+        self.fields
+            .iter()
+            .filter(|f| f.is_disputed())
+            .map(|f| f.field)
+            .collect()
+    }
+
+    /// Report entry of a field
+    pub fn field(&self, field: MergeField) -> Option<&FieldDisagreement> {
+        self.fields.iter().find(|f| f.field == field)
+    }
+}
+
+/// Document merged field by field, with its disagreement report
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergedDocument {
+    pub doc: ExtractedDoc,
+    /// Confidence of the strategy that supplied the content
+    pub confidence: f64,
+    pub disagreements: DisagreementReport,
+}
+
+/// Field-level merger - picks each field by its own policy
+///
+/// Values are compared after trimming, case folding and collapsing
+/// whitespace, so formatting differences do not count as disagreements.
+pub struct FieldMerger {
+    config: FieldMergeConfig,
+}
+
+impl FieldMerger {
+    pub fn new(config: FieldMergeConfig) -> Self {
+        Self { config }
+    }
+
+    /// Merge results into a document and report where strategies disagreed
+    pub fn merge_fields(&self, results: &[ExtractionResult]) -> Result<MergedDocument> {
+        let filtered: Vec<&ExtractionResult> = results
+            .iter()
+            .filter(|r| r.content.extraction_confidence >= self.config.min_confidence)
+            .collect();
+        if filtered.is_empty() {
+            return Err(anyhow!("No results meet confidence threshold"));
+        }
+
+        let mut report = DisagreementReport {
+            strategies: filtered
+                .iter()
+                .map(|r| r.content.strategy_used.clone())
+                .collect(),
+            fields: Vec::new(),
+        };
+        let mut chosen: HashMap<MergeField, (usize, String)> = HashMap::new();
+        for field in MergeField::ALL {
+            if let Some((entry, winner)) = self.merge_field(field, &filtered) {
+                chosen.insert(field, winner);
+                report.fields.push(entry);
+            }
+        }
+
+        let content_source = chosen
+            .get(&MergeField::Content)
+            .map(|(idx, _)| *idx)
+            .unwrap_or(0);
+        let confidence = filtered
+            .get(content_source)
+            .map_or(0.0, |r| r.content.extraction_confidence);
+        let mut take = |field: MergeField| chosen.remove(&field).map(|(_, value)| value);
+
+        let text = take(MergeField::Content).unwrap_or_default();
+        // Safe conversion: word counts of extracted text fit in u32 in practice
+        #[allow(clippy::cast_possible_truncation)]
+        let word_count = text.split_whitespace().count() as u32;
+        // Safe conversion: confidence is clamped to 0.0..=1.0, so the score fits in u8
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let quality_score = (confidence.clamp(0.0, 1.0) * 100.0).round() as u8;
+        let doc = ExtractedDoc {
+            url: filtered
+                .first()
+                .map(|r| r.content.url.clone())
+                .unwrap_or_default(),
+            title: take(MergeField::Title),
+            description: take(MergeField::Summary),
+            byline: take(MergeField::Author),
+            published_iso: take(MergeField::Published),
+            word_count: Some(word_count),
+            quality_score: Some(quality_score),
+            text,
+            ..ExtractedDoc::default()
+        };
+
+        if report.has_disagreements() {
+            debug!(
+                url = %doc.url,
+                fields = ?report.disputed_fields(),
+                "Extraction strategies disagreed"
+            );
+        }
+
+        Ok(MergedDocument {
+            doc,
+            confidence,
+            disagreements: report,
+        })
+    }
+
+    /// Choose a field's value; returns the report entry and the (result index, value) chosen
+    fn merge_field(
+        &self,
+        field: MergeField,
+        results: &[&ExtractionResult],
+    ) -> Option<(FieldDisagreement, (usize, String))> {
+        let candidates: Vec<(usize, &str, String)> = results
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, r)| field.value(r).map(|v| (idx, v, normalize_field(v))))
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let confidence = |idx: usize| {
+            results
+                .get(idx)
+                .map_or(0.0, |r| r.content.extraction_confidence)
+        };
+        let most_confident = |a: &&(usize, &str, String), b: &&(usize, &str, String)| {
+            confidence(a.0)
+                .partial_cmp(&confidence(b.0))
+                .unwrap_or(std::cmp::Ordering::Equal)
+                // Prefer the earlier strategy on ties
+                .then(b.0.cmp(&a.0))
+        };
+
+        let policy = self.config.policy(field);
+        let winner = match policy {
+            FieldMergePolicy::FirstNonEmpty => candidates.first(),
+            FieldMergePolicy::HighestConfidence => candidates.iter().max_by(most_confident),
+            FieldMergePolicy::MajorityVote => {
+                // Group by normalized value: (votes, summed confidence, first index)
+                let mut groups: Vec<(&str, usize, f64, usize)> = Vec::new();
+                for (idx, _, key) in &candidates {
+                    match groups.iter_mut().find(|g| g.0 == key.as_str()) {
+                        Some(group) => {
+                            group.1 += 1;
+                            group.2 += confidence(*idx);
+                        }
+                        None => groups.push((key.as_str(), 1, confidence(*idx), *idx)),
+                    }
+                }
+                let top = groups.into_iter().max_by(|a, b| {
+                    a.1.cmp(&b.1)
+                        .then(a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal))
+                        .then(b.3.cmp(&a.3))
+                })?;
+                // Take the winning value as written by its most confident supporter
+                candidates
+                    .iter()
+                    .filter(|c| c.2 == top.0)
+                    .max_by(most_confident)
+            }
+        }?;
+
+        let agreeing = candidates.iter().filter(|c| c.2 == winner.2).count();
+        // Safe conversion: candidate counts are small
+        #[allow(clippy::cast_precision_loss)]
+        let agreement = agreeing as f64 / candidates.len() as f64;
+        let strategy = |idx: usize| {
+            results
+                .get(idx)
+                .map(|r| r.content.strategy_used.clone())
+                .unwrap_or_default()
+        };
+
+        let entry = FieldDisagreement {
+            field,
+            policy,
+            chosen_from: Some(strategy(winner.0)),
+            candidates: candidates
+                .iter()
+                .map(|(idx, value, _)| (strategy(*idx), (*value).to_string()))
+                .collect(),
+            agreement,
+        };
+        Some((entry, (winner.0, winner.1.to_string())))
+    }
+
+    /// Convert a merged document back into an extraction result
+    fn to_result(merged: MergedDocument, results: &[ExtractionResult]) -> Result<ExtractionResult> {
+        let quality = results
+            .iter()
+            .find(|r| {
+                merged
+                    .disagreements
+                    .field(MergeField::Content)
+                    .and_then(|f| f.chosen_from.as_deref())
+                    == Some(r.content.strategy_used.as_str())
+            })
+            .or_else(|| results.first())
+            .map(|r| r.quality.clone())
+            .ok_or_else(|| anyhow!("No valid content found in results"))?;
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "strategies_used".to_string(),
+            merged.disagreements.strategies.join(","),
+        );
+        metadata.insert(
+            "disputed_fields".to_string(),
+            merged
+                .disagreements
+                .disputed_fields()
+                .iter()
+                .map(|f| f.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+        if let Some(author) = &merged.doc.byline {
+            metadata.insert("author".to_string(), author.clone());
+        }
+        if let Some(published) = &merged.doc.published_iso {
+            metadata.insert("published_date".to_string(), published.clone());
+        }
+
+        Ok(ExtractionResult {
+            content: ExtractedContent {
+                title: merged.doc.title.unwrap_or_else(|| "Untitled".to_string()),
+                content: merged.doc.text,
+                summary: merged.doc.description,
+                url: merged.doc.url,
+                strategy_used: "field_merger".to_string(),
+                extraction_confidence: merged.confidence,
+            },
+            quality,
+            performance: None,
+            metadata,
+        })
+    }
+}
+
+impl Default for FieldMerger {
+    fn default() -> Self {
+        Self::new(FieldMergeConfig::default())
+    }
+}
+
+#[async_trait]
+impl ResultMerger for FieldMerger {
+    async fn merge(&self, results: Vec<ExtractionResult>) -> Result<ExtractionResult> {
+        if results.is_empty() {
+            return Err(anyhow!("No results to merge"));
+        }
+
+        let merged = self.merge_fields(&results)?;
+        Self::to_result(merged, &results)
+    }
+
+    fn name(&self) -> &str {
+        "field"
+    }
+
+    fn config(&self) -> MergerConfig {
+        MergerConfig {
+            min_confidence: self.config.min_confidence,
+            ..MergerConfig::default()
+        }
+    }
+}
+
+/// Comparison key of a field value: trimmed, lowercased, single-spaced
+fn normalize_field(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
 /// Strategy composer configuration
 #[derive(Debug, Clone)]
 pub struct ComposerConfig {
@@ -338,6 +776,8 @@ pub struct CompositionResult {
     pub strategy_times: HashMap<String, Duration>,
     /// Performance metrics
     pub metrics: Option<PerformanceMetrics>,
+    /// Field-level merge and disagreement report (parallel mode with field merge)
+    pub merged: Option<MergedDocument>,
 }
 
 /// Strategy composer for executing multiple strategies with different modes
@@ -345,6 +785,8 @@ pub struct StrategyComposer {
     strategies: Vec<Arc<dyn ExtractionStrategy>>,
     merger: Box<dyn ResultMerger>,
     config: ComposerConfig,
+    calibrator: Option<Arc<ConfidenceCalibrator>>,
+    field_merger: Option<FieldMerger>,
 }
 
 impl StrategyComposer {
@@ -357,6 +799,8 @@ impl StrategyComposer {
                 mode,
                 ..Default::default()
             },
+            calibrator: None,
+            field_merger: None,
         }
     }
 
@@ -366,6 +810,8 @@ impl StrategyComposer {
             strategies: Vec::new(),
             merger: Box::new(BestContentMerger::default()),
             config,
+            calibrator: None,
+            field_merger: None,
         }
     }
 
@@ -387,6 +833,15 @@ impl StrategyComposer {
         self
     }
 
+    /// Merge parallel results field by field instead of using the result merger
+    ///
+    /// The composition result then carries the merged document and the
+    /// disagreement report in [`CompositionResult::merged`].
+    pub fn with_field_merge(mut self, config: FieldMergeConfig) -> Self {
+        self.field_merger = Some(FieldMerger::new(config));
+        self
+    }
+
     /// Set timeout per strategy
     pub fn with_timeout(mut self, timeout_ms: u64) -> Self {
         self.config.timeout_ms = timeout_ms;
@@ -405,6 +860,21 @@ impl StrategyComposer {
         self
     }
 
+    /// Calibrate strategy confidences before thresholding and comparing them
+    pub fn with_calibration(mut self, calibrator: Arc<ConfidenceCalibrator>) -> Self {
+        self.calibrator = Some(calibrator);
+        self
+    }
+
+    /// Replace a result's confidence with its calibrated value
+    fn calibrate(&self, strategy: &str, mut result: ExtractionResult) -> ExtractionResult {
+        if let Some(calibrator) = &self.calibrator {
+            result.content.extraction_confidence =
+                calibrator.calibrate(strategy, result.content.extraction_confidence);
+        }
+        result
+    }
+
     /// Execute composition
     pub async fn execute(&self, html: &str, url: &str) -> Result<CompositionResult> {
         if self.strategies.is_empty() {
@@ -451,6 +921,7 @@ impl StrategyComposer {
                 Ok(Ok(result)) => {
                     let strategy_time = strategy_start.elapsed();
                     strategy_times.insert(strategy.name().to_string(), strategy_time);
+                    let result = self.calibrate(strategy.name(), result);
 
                     // Check confidence threshold
                     if result.content.extraction_confidence >= self.config.min_confidence {
@@ -462,6 +933,7 @@ impl StrategyComposer {
                             total_time: start.elapsed(),
                             strategy_times,
                             metrics: None,
+                            merged: None,
                         });
                     }
                     last_error = Some(anyhow!(
@@ -513,8 +985,8 @@ impl StrategyComposer {
         for handle in handles {
             match handle.await {
                 Ok((name, Ok(Ok(result)), elapsed)) => {
+                    results.push(self.calibrate(&name, result));
                     strategy_times.insert(name, elapsed);
-                    results.push(result);
                 }
                 Ok((name, Ok(Err(_)), elapsed)) | Ok((name, Err(_), elapsed)) => {
                     strategy_times.insert(name, elapsed);
@@ -528,7 +1000,16 @@ impl StrategyComposer {
         }
 
         // Merge results
-        let merged_result = self.merger.merge(results.clone()).await?;
+        let (merged_result, merged) = match &self.field_merger {
+            Some(field_merger) => {
+                let merged = field_merger.merge_fields(&results)?;
+                (
+                    FieldMerger::to_result(merged.clone(), &results)?,
+                    Some(merged),
+                )
+            }
+            None => (self.merger.merge(results.clone()).await?, None),
+        };
         let strategies_succeeded = results.len();
 
         Ok(CompositionResult {
@@ -539,6 +1020,7 @@ impl StrategyComposer {
             total_time: start.elapsed(),
             strategy_times,
             metrics: None,
+            merged,
         })
     }
 
@@ -567,6 +1049,7 @@ impl StrategyComposer {
             Ok(Ok(result)) => {
                 let primary_time = primary_start.elapsed();
                 strategy_times.insert(primary.name().to_string(), primary_time);
+                let result = self.calibrate(primary.name(), result);
 
                 if result.content.extraction_confidence >= self.config.min_confidence {
                     return Ok(CompositionResult {
@@ -577,6 +1060,7 @@ impl StrategyComposer {
                         total_time: start.elapsed(),
                         strategy_times,
                         metrics: None,
+                        merged: None,
                     });
                 }
             }
@@ -598,6 +1082,7 @@ impl StrategyComposer {
         )
         .await
         .map_err(|_| anyhow!("Fallback strategy timeout"))??;
+        let result = self.calibrate(secondary.name(), result);
 
         strategy_times.insert(secondary.name().to_string(), secondary_start.elapsed());
 
@@ -609,6 +1094,7 @@ impl StrategyComposer {
             total_time: start.elapsed(),
             strategy_times,
             metrics: None,
+            merged: None,
         })
     }
 
@@ -640,8 +1126,8 @@ impl StrategyComposer {
         for handle in handles {
             match handle.await {
                 Ok((name, Ok(Ok(result)), elapsed)) => {
+                    results.push(self.calibrate(&name, result));
                     strategy_times.insert(name, elapsed);
-                    results.push(result);
                 }
                 Ok((name, _, elapsed)) => {
                     strategy_times.insert(name, elapsed);
@@ -675,6 +1161,7 @@ impl StrategyComposer {
             total_time: start.elapsed(),
             strategy_times,
             metrics: None,
+            merged: None,
         })
     }
 }
@@ -801,4 +1288,159 @@ mod tests {
         assert_eq!(composition_result.strategies_executed, 2);
         assert_eq!(composition_result.result.content.strategy_used, "fallback");
     }
+
+    #[tokio::test]
+    async fn test_best_mode_uses_calibrated_confidence() {
+        // "llm" reports 0.95 but is right half the time; "css" reports 0.7
+        // and is right 90% of the time
+        let mut calibrator = ConfidenceCalibrator::default().with_min_samples(1);
+        for i in 0..20 {
+            calibrator.record("llm", 0.95, i % 2 == 0);
+            calibrator.record("css", 0.7, i % 10 != 0);
+        }
+        calibrator.fit();
+
+        let llm = Arc::new(MockStrategy {
+            name: "llm".to_string(),
+            should_succeed: true,
+            confidence: 0.95,
+            delay_ms: 0,
+        });
+        let css = Arc::new(MockStrategy {
+            name: "css".to_string(),
+            should_succeed: true,
+            confidence: 0.7,
+            delay_ms: 0,
+        });
+
+        let composer = StrategyComposer::new(CompositionMode::Best)
+            .add_strategy(llm)
+            .add_strategy(css)
+            .with_calibration(Arc::new(calibrator));
+
+        let result = composer
+            .execute("<html></html>", "https://example.com")
+            .await
+            .unwrap();
+        assert_eq!(result.result.content.strategy_used, "css");
+        assert!((result.result.content.extraction_confidence - 0.9).abs() < 1e-9);
+    }
+
+    fn result(strategy: &str, title: &str, content: &str, confidence: f64) -> ExtractionResult {
+        ExtractionResult {
+            content: ExtractedContent {
+                title: title.to_string(),
+                content: content.to_string(),
+                summary: None,
+                url: "https://example.com".to_string(),
+                strategy_used: strategy.to_string(),
+                extraction_confidence: confidence,
+            },
+            quality: crate::strategies::traits::ExtractionQuality {
+                content_length: content.len(),
+                title_quality: 0.8,
+                content_quality: 0.8,
+                structure_score: 0.8,
+                metadata_completeness: 0.8,
+            },
+            performance: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_field_merge_policies() {
+        let mut css = result("css", "Breaking News", "short body", 0.6);
+        css.metadata
+            .insert("author".to_string(), "Jane Doe".to_string());
+        let mut trek = result("trek", "breaking  news ", "the full article body", 0.9);
+        trek.content.summary = Some("Lead paragraph".to_string());
+        let llm = result("llm", "Site Name | Breaking News", "another body", 0.8);
+
+        let merged = FieldMerger::default()
+            .merge_fields(&[css, trek, llm])
+            .unwrap();
+
+        // Title: two strategies agree modulo case/whitespace, most confident spelling wins
+        assert_eq!(merged.doc.title.as_deref(), Some("breaking  news"));
+        // Content: highest confidence
+        assert_eq!(merged.doc.text, "the full article body");
+        assert_eq!(merged.doc.word_count, Some(4));
+        assert_eq!(merged.doc.quality_score, Some(90));
+        // Summary and author: only one strategy reported them
+        assert_eq!(merged.doc.description.as_deref(), Some("Lead paragraph"));
+        assert_eq!(merged.doc.byline.as_deref(), Some("Jane Doe"));
+
+        let report = &merged.disagreements;
+        assert_eq!(report.strategies, vec!["css", "trek", "llm"]);
+        assert_eq!(
+            report.disputed_fields(),
+            vec![MergeField::Title, MergeField::Content]
+        );
+        let title = report.field(MergeField::Title).unwrap();
+        assert_eq!(title.chosen_from.as_deref(), Some("trek"));
+        assert_eq!(title.candidates.len(), 3);
+        assert!((title.agreement - 2.0 / 3.0).abs() < 1e-9);
+        assert!(!report.field(MergeField::Author).unwrap().is_disputed());
+        assert!(report.field(MergeField::Published).is_none());
+    }
+
+    #[test]
+    fn test_field_merge_first_non_empty_and_threshold() {
+        let weak = result("weak", "Weak title", "weak body", 0.2);
+        let first = result("first", "", "first body", 0.6);
+        let second = result("second", "Second title", "second body", 0.9);
+
+        let merger = FieldMerger::new(FieldMergeConfig::uniform(FieldMergePolicy::FirstNonEmpty));
+        let merged = merger.merge_fields(&[weak.clone(), first, second]).unwrap();
+        assert_eq!(merged.doc.title.as_deref(), Some("Second title"));
+        assert_eq!(merged.doc.text, "first body");
+        assert!((merged.confidence - 0.6).abs() < 1e-9);
+        assert!(!merged
+            .disagreements
+            .strategies
+            .contains(&"weak".to_string()));
+
+        assert!(merger.merge_fields(&[weak]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_parallel_mode_with_field_merge() {
+        let strategy1 = Arc::new(MockStrategy {
+            name: "strategy1".to_string(),
+            should_succeed: true,
+            confidence: 0.7,
+            delay_ms: 0,
+        });
+        let strategy2 = Arc::new(MockStrategy {
+            name: "strategy2".to_string(),
+            should_succeed: true,
+            confidence: 0.9,
+            delay_ms: 0,
+        });
+
+        let composer = StrategyComposer::new(CompositionMode::Parallel)
+            .add_strategy(strategy1)
+            .add_strategy(strategy2)
+            .with_field_merge(FieldMergeConfig::default());
+
+        let result = composer
+            .execute("<html></html>", "https://example.com")
+            .await
+            .unwrap();
+        assert_eq!(result.result.content.strategy_used, "field_merger");
+        assert_eq!(result.result.content.content, "strategy2 content");
+        assert_eq!(
+            result
+                .result
+                .metadata
+                .get("disputed_fields")
+                .map(String::as_str),
+            Some("title,content,summary")
+        );
+
+        let merged = result.merged.unwrap();
+        assert_eq!(merged.doc.text, "strategy2 content");
+        assert!(merged.disagreements.has_disagreements());
+    }
 }
@@ -10,6 +10,8 @@
 //! - **Aggregatable**: Multiple strategies can be combined
 //! - **Transparent**: Track components and metadata for debugging
 //! - **Serializable**: JSON serialization for API responses
+//! - **Calibrated**: Per-method curves learned from labeled outcomes (see
+//!   [`calibration`]) make scores comparable across methods
 //!
 //! # Examples
 //!
@@ -37,6 +39,8 @@
 //!     .aggregate(&scores, Some(vec![0.7, 0.3]));
 //! ```
 
+pub mod calibration;
+
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use std::collections::HashMap;
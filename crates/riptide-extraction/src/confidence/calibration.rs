@@ -0,0 +1,463 @@
+//! Confidence calibration across extraction methods
+//!
+//! Raw confidence values are not comparable between methods: a CSS selector
+//! match reporting 0.8 may be right far more often than an LLM reporting 0.8.
+//! A [`ConfidenceCalibrator`] learns a monotone mapping per method from
+//! labeled outcomes (was the extraction actually correct?) so that a
+//! calibrated 0.8 means roughly "correct 80% of the time" whichever method
+//! produced it. Calibrated values can then be thresholded and compared when
+//! composing strategies.
+//!
+//! Two fitting methods are supported:
+//!
+//! - **Isotonic**: pool-adjacent-violators regression, a non-parametric
+//!   piecewise-linear curve. Needs more samples but makes no shape assumption.
+//! - **Platt**: a logistic curve `1 / (1 + e^-(a·x + b))`. Works with few
+//!   samples but assumes a sigmoid relationship.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use riptide_extraction::confidence::calibration::{CalibrationMethod, ConfidenceCalibrator};
+//!
+//! let mut calibrator =
+//!     ConfidenceCalibrator::new(CalibrationMethod::Isotonic).with_min_samples(1);
+//! for (raw, correct) in [(0.9, true), (0.9, false), (0.4, false), (0.4, false)] {
+//!     calibrator.record("llm", raw, correct);
+//! }
+//! calibrator.fit();
+//!
+//! // LLM was right half the time when it reported 0.9
+//! assert!((calibrator.calibrate("llm", 0.9) - 0.5).abs() < 1e-9);
+//! // Methods without a fitted curve pass through unchanged
+//! assert_eq!(calibrator.calibrate("css", 0.7), 0.7);
+//! ```
+
+use super::ConfidenceScore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Default minimum number of samples before a method is calibrated
+pub const DEFAULT_MIN_SAMPLES: usize = 30;
+
+/// Maximum Newton iterations when fitting a Platt curve
+const PLATT_MAX_ITERATIONS: usize = 100;
+
+/// A labeled outcome: the raw confidence and whether the extraction was correct
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationSample {
+    /// Raw confidence reported by the method (0.0 - 1.0)
+    pub raw: f64,
+    /// Whether the extraction turned out to be correct
+    pub correct: bool,
+}
+
+/// How calibration curves are fitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CalibrationMethod {
+    /// Pool-adjacent-violators isotonic regression
+    #[default]
+    Isotonic,
+    /// Platt scaling (logistic regression on the raw score)
+    Platt,
+}
+
+/// A fitted mapping from raw to calibrated confidence
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CalibrationCurve {
+    /// Raw scores are returned unchanged
+    Identity,
+    /// Logistic curve `1 / (1 + e^-(a·x + b))`
+    Platt { a: f64, b: f64 },
+    /// Non-decreasing `(raw, calibrated)` knots, linearly interpolated
+    Isotonic { points: Vec<(f64, f64)> },
+}
+
+impl CalibrationCurve {
+    /// Fit a curve to labeled samples
+    ///
+    /// Returns [`CalibrationCurve::Identity`] when there are no samples.
+    pub fn fit(method: CalibrationMethod, samples: &[CalibrationSample]) -> Self {
+        if samples.is_empty() {
+            return Self::Identity;
+        }
+        match method {
+            CalibrationMethod::Isotonic => Self::fit_isotonic(samples),
+            CalibrationMethod::Platt => Self::fit_platt(samples),
+        }
+    }
+
+    /// Fit an isotonic curve with pool-adjacent-violators
+    pub fn fit_isotonic(samples: &[CalibrationSample]) -> Self {
+        let mut sorted: Vec<(f64, f64)> = samples
+            .iter()
+            .map(|s| (s.raw.clamp(0.0, 1.0), if s.correct { 1.0 } else { 0.0 }))
+            .collect();
+        sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        // Identical raw scores must map to one value, so pool ties first
+        let mut ties: Vec<(f64, f64, f64)> = Vec::new();
+        for (raw, label) in sorted {
+            match ties.last_mut() {
+                Some(last) if last.0 == raw => {
+                    last.1 += label;
+                    last.2 += 1.0;
+                }
+                _ => ties.push((raw, label, 1.0)),
+            }
+        }
+
+        // Each block holds (sum of raw, sum of labels, weight)
+        let mut blocks: Vec<(f64, f64, f64)> = Vec::with_capacity(ties.len());
+        for (raw, label_sum, weight) in ties {
+            blocks.push((raw * weight, label_sum, weight));
+            while blocks.len() > 1 {
+                let n = blocks.len();
+                let (prev, last) = (blocks[n - 2], blocks[n - 1]);
+                if prev.1 / prev.2 <= last.1 / last.2 {
+                    break;
+                }
+                blocks.truncate(n - 2);
+                blocks.push((prev.0 + last.0, prev.1 + last.1, prev.2 + last.2));
+            }
+        }
+
+        Self::Isotonic {
+            points: blocks
+                .into_iter()
+                .map(|(raw_sum, label_sum, weight)| (raw_sum / weight, label_sum / weight))
+                .collect(),
+        }
+    }
+
+    /// Fit a Platt curve with Newton's method
+    ///
+    /// Uses Platt's smoothed targets so that separable samples do not drive
+    /// the curve to a hard step.
+    pub fn fit_platt(samples: &[CalibrationSample]) -> Self {
+        let positives = samples.iter().filter(|s| s.correct).count() as f64;
+        let negatives = samples.len() as f64 - positives;
+        let hi_target = (positives + 1.0) / (positives + 2.0);
+        let lo_target = 1.0 / (negatives + 2.0);
+
+        let mut a = 0.0;
+        let mut b = ((positives + 1.0) / (negatives + 1.0)).ln();
+        for _ in 0..PLATT_MAX_ITERATIONS {
+            let (mut g_a, mut g_b) = (0.0, 0.0);
+            let (mut h_aa, mut h_ab, mut h_bb) = (1e-9, 0.0, 1e-9);
+            for sample in samples {
+                let x = sample.raw.clamp(0.0, 1.0);
+                let target = if sample.correct { hi_target } else { lo_target };
+                let p = sigmoid(a * x + b);
+                let d = p - target;
+                let w = p * (1.0 - p);
+                g_a += d * x;
+                g_b += d;
+                h_aa += w * x * x;
+                h_ab += w * x;
+                h_bb += w;
+            }
+
+            let det = h_aa * h_bb - h_ab * h_ab;
+            if det.abs() < f64::EPSILON {
+                break;
+            }
+            let step_a = (h_bb * g_a - h_ab * g_b) / det;
+            let step_b = (h_aa * g_b - h_ab * g_a) / det;
+            a -= step_a;
+            b -= step_b;
+            if step_a.abs() < 1e-10 && step_b.abs() < 1e-10 {
+                break;
+            }
+        }
+
+        Self::Platt { a, b }
+    }
+
+    /// Map a raw confidence to its calibrated value
+    pub fn apply(&self, raw: f64) -> f64 {
+        let raw = raw.clamp(0.0, 1.0);
+        match self {
+            Self::Identity => raw,
+            Self::Platt { a, b } => sigmoid(a * raw + b),
+            Self::Isotonic { points } => interpolate(points, raw),
+        }
+    }
+}
+
+/// Learns and applies per-method calibration curves
+///
+/// Record outcomes with [`record`](Self::record), call [`fit`](Self::fit),
+/// then share the calibrator (e.g. behind an `Arc`) with a
+/// [`StrategyComposer`](crate::composition::StrategyComposer). The whole
+/// calibrator serializes, so samples and fitted curves can be persisted and
+/// refitted as outcomes accumulate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfidenceCalibrator {
+    method: CalibrationMethod,
+    min_samples: usize,
+    samples: HashMap<String, Vec<CalibrationSample>>,
+    curves: HashMap<String, CalibrationCurve>,
+}
+
+impl ConfidenceCalibrator {
+    /// Create an empty calibrator fitting curves with `method`
+    pub fn new(method: CalibrationMethod) -> Self {
+        Self {
+            method,
+            min_samples: DEFAULT_MIN_SAMPLES,
+            samples: HashMap::new(),
+            curves: HashMap::new(),
+        }
+    }
+
+    /// Set the number of samples a method needs before it is calibrated
+    pub fn with_min_samples(mut self, min_samples: usize) -> Self {
+        self.min_samples = min_samples.max(1);
+        self
+    }
+
+    /// Record a labeled outcome for a method
+    pub fn record(&mut self, method: impl Into<String>, raw: f64, correct: bool) {
+        self.samples
+            .entry(method.into())
+            .or_default()
+            .push(CalibrationSample {
+                raw: raw.clamp(0.0, 1.0),
+                correct,
+            });
+    }
+
+    /// Record several outcomes for a method
+    pub fn record_all(
+        &mut self,
+        method: impl Into<String>,
+        samples: impl IntoIterator<Item = CalibrationSample>,
+    ) {
+        let entry = self.samples.entry(method.into()).or_default();
+        entry.extend(samples.into_iter().map(|s| CalibrationSample {
+            raw: s.raw.clamp(0.0, 1.0),
+            correct: s.correct,
+        }));
+    }
+
+    /// Refit curves for every method with enough samples
+    ///
+    /// Methods below the sample threshold keep no curve and pass raw scores
+    /// through unchanged.
+    pub fn fit(&mut self) {
+        let method = self.method;
+        let min_samples = self.min_samples;
+        self.curves = self
+            .samples
+            .iter()
+            .filter(|(_, samples)| samples.len() >= min_samples)
+            .map(|(name, samples)| (name.clone(), CalibrationCurve::fit(method, samples)))
+            .collect();
+    }
+
+    /// Calibrate a raw confidence reported by `method`
+    pub fn calibrate(&self, method: &str, raw: f64) -> f64 {
+        match self.curves.get(method) {
+            Some(curve) => curve.apply(raw),
+            None => raw.clamp(0.0, 1.0),
+        }
+    }
+
+    /// Calibrate a confidence score, keeping the raw value as a component
+    pub fn calibrate_score(&self, score: &ConfidenceScore) -> ConfidenceScore {
+        let mut calibrated = score.clone();
+        // Adding a component recomputes the value, so calibrate afterwards
+        calibrated.add_component("uncalibrated", score.value());
+        calibrated.value = self.calibrate(score.method(), score.value());
+        calibrated
+    }
+
+    /// Whether a fitted curve exists for `method`
+    pub fn is_calibrated(&self, method: &str) -> bool {
+        self.curves.contains_key(method)
+    }
+
+    /// The fitted curve for `method`, if any
+    pub fn curve(&self, method: &str) -> Option<&CalibrationCurve> {
+        self.curves.get(method)
+    }
+
+    /// Number of recorded samples for `method`
+    pub fn sample_count(&self, method: &str) -> usize {
+        self.samples.get(method).map_or(0, Vec::len)
+    }
+
+    /// Brier score (mean squared error) of calibrated values on `samples`
+    ///
+    /// Lower is better; useful for checking a fit against held-out outcomes.
+    pub fn brier_score(&self, method: &str, samples: &[CalibrationSample]) -> Option<f64> {
+        if samples.is_empty() {
+            return None;
+        }
+        let total: f64 = samples
+            .iter()
+            .map(|s| {
+                let label = if s.correct { 1.0 } else { 0.0 };
+                (self.calibrate(method, s.raw) - label).powi(2)
+            })
+            .sum();
+        Some(total / samples.len() as f64)
+    }
+}
+
+impl Default for ConfidenceCalibrator {
+    fn default() -> Self {
+        Self::new(CalibrationMethod::default())
+    }
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+fn interpolate(points: &[(f64, f64)], raw: f64) -> f64 {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return raw;
+    };
+    if raw <= first.0 {
+        return first.1;
+    }
+    if raw >= last.0 {
+        return last.1;
+    }
+    let upper = points.partition_point(|p| p.0 < raw);
+    let (x0, y0) = points[upper - 1];
+    let (x1, y1) = points[upper];
+    if x1 - x0 <= f64::EPSILON {
+        return y1;
+    }
+    y0 + (y1 - y0) * (raw - x0) / (x1 - x0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(outcomes: &[(f64, bool)]) -> Vec<CalibrationSample> {
+        outcomes
+            .iter()
+            .map(|&(raw, correct)| CalibrationSample { raw, correct })
+            .collect()
+    }
+
+    /// An over-confident method: always reports 0.9 but is right 60% of the time
+    fn overconfident() -> Vec<CalibrationSample> {
+        (0..50)
+            .map(|i| CalibrationSample {
+                raw: 0.9,
+                correct: i % 5 < 3,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_isotonic_is_monotone() {
+        let curve = CalibrationCurve::fit_isotonic(&samples(&[
+            (0.1, false),
+            (0.2, true),
+            (0.3, false),
+            (0.5, true),
+            (0.6, false),
+            (0.9, true),
+        ]));
+        let CalibrationCurve::Isotonic { points } = &curve else {
+            panic!("expected isotonic curve");
+        };
+        assert!(points.windows(2).all(|w| w[0].1 <= w[1].1));
+
+        let mut previous = 0.0;
+        for step in 0..=20 {
+            let value = curve.apply(step as f64 / 20.0);
+            assert!(value >= previous);
+            previous = value;
+        }
+    }
+
+    #[test]
+    fn test_isotonic_matches_observed_accuracy() {
+        let curve = CalibrationCurve::fit_isotonic(&overconfident());
+        assert!((curve.apply(0.9) - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_platt_is_increasing() {
+        let mut outcomes = Vec::new();
+        for i in 0..100 {
+            let raw = i as f64 / 100.0;
+            outcomes.push((raw, i % 10 < i / 10));
+        }
+        let curve = CalibrationCurve::fit_platt(&samples(&outcomes));
+        let CalibrationCurve::Platt { a, .. } = curve else {
+            panic!("expected platt curve");
+        };
+        assert!(a > 0.0);
+        assert!(curve.apply(0.9) > curve.apply(0.1));
+        assert!(curve.apply(0.9) < 1.0);
+    }
+
+    #[test]
+    fn test_calibrator_makes_methods_comparable() {
+        let mut calibrator = ConfidenceCalibrator::new(CalibrationMethod::Isotonic);
+        calibrator.record_all("llm", overconfident());
+        calibrator.record_all(
+            "css",
+            (0..50).map(|i| CalibrationSample {
+                raw: 0.7,
+                correct: i % 10 != 0,
+            }),
+        );
+        calibrator.fit();
+
+        // css at 0.7 is right 90% of the time, llm at 0.9 only 60%
+        assert!(calibrator.calibrate("css", 0.7) > calibrator.calibrate("llm", 0.9));
+        assert_eq!(calibrator.calibrate("wasm", 0.42), 0.42);
+
+        let brier_raw = ConfidenceCalibrator::default().brier_score("llm", &overconfident());
+        let brier_fit = calibrator.brier_score("llm", &overconfident());
+        assert!(brier_fit < brier_raw);
+    }
+
+    #[test]
+    fn test_min_samples_threshold() {
+        let mut calibrator =
+            ConfidenceCalibrator::new(CalibrationMethod::Platt).with_min_samples(10);
+        for _ in 0..5 {
+            calibrator.record("css", 0.8, false);
+        }
+        calibrator.fit();
+        assert!(!calibrator.is_calibrated("css"));
+        assert_eq!(calibrator.sample_count("css"), 5);
+        assert_eq!(calibrator.calibrate("css", 0.8), 0.8);
+    }
+
+    #[test]
+    fn test_calibrate_score_keeps_raw_component() {
+        let mut calibrator = ConfidenceCalibrator::default().with_min_samples(1);
+        calibrator.record_all("llm", overconfident());
+        calibrator.fit();
+
+        let calibrated = calibrator.calibrate_score(&ConfidenceScore::new(0.9, "llm"));
+        assert!((calibrated.value() - 0.6).abs() < 1e-9);
+        assert_eq!(calibrated.components().get("uncalibrated"), Some(&0.9));
+        assert_eq!(calibrated.method(), "llm");
+    }
+
+    #[test]
+    fn test_calibrator_serde_roundtrip() {
+        let mut calibrator = ConfidenceCalibrator::default().with_min_samples(1);
+        calibrator.record_all("llm", overconfident());
+        calibrator.fit();
+
+        let json = serde_json::to_string(&calibrator).unwrap();
+        let restored: ConfidenceCalibrator = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.curve("llm"), calibrator.curve("llm"));
+    }
+}
@@ -9,11 +9,15 @@
 //! - CSS-002: :has-text() post-filter for text content matching
 //! - CSS-003: 12 content transformers (trim, normalize_ws, number, currency, etc.)
 //! - CSS-004: CSS-wins merge policy with conflict resolution
+//!
+//! Any selector may also be an XPath expression (see [`crate::xpath`]), so
+//! selector libraries written for Scrapy or lxml can be reused unchanged.
 
+use crate::xpath::{xpath_expression, XPath, XPathValue};
 use crate::ExtractedContent;
 use anyhow::{bail, Context, Result};
 use regex::Regex;
-use scraper::{Html, Selector};
+use scraper::{ElementRef, Html, Selector};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use url::Url;
@@ -21,7 +25,7 @@ use url::Url;
 /// Enhanced CSS selector configuration with transformers and post-filters
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CssSelectorConfig {
-    /// CSS selector string with enhanced support
+    /// CSS selector string with enhanced support, or an XPath expression
     pub selector: String,
     /// Optional transformers to apply to extracted content
     pub transformers: Vec<String>,
@@ -79,6 +83,8 @@ pub struct CssJsonExtractor {
     selectors: HashMap<String, CssSelectorConfig>,
     global_merge_policy: MergePolicy,
     transformers: TransformerRegistry,
+    /// Namespace prefixes bound for XPath selectors
+    namespaces: HashMap<String, String>,
 }
 
 /// Content transformer registry
@@ -143,6 +149,7 @@ impl CssJsonExtractor {
             selectors,
             global_merge_policy: MergePolicy::CssWins,
             transformers: TransformerRegistry::default(),
+            namespaces: HashMap::new(),
         }
     }
 
@@ -174,6 +181,12 @@ impl CssJsonExtractor {
         self
     }
 
+    /// Bind a namespace prefix for the XPath selectors
+    pub fn with_namespace(mut self, prefix: &str, uri: &str) -> Self {
+        self.namespaces.insert(prefix.to_string(), uri.to_string());
+        self
+    }
+
     /// Extract content using enhanced CSS selectors with transformers and post-filters
     pub async fn extract(&self, html: &str, url: &str) -> Result<ExtractedContent> {
         let document = Html::parse_document(html);
@@ -260,24 +273,10 @@ impl CssJsonExtractor {
         config: &CssSelectorConfig,
         base_url: &str,
     ) -> Result<Option<Vec<String>>> {
-        // Parse enhanced CSS selector (CSS-001)
-        let selector = self.parse_enhanced_selector(selector_str)?;
-
-        let mut values: Vec<String> = document
-            .select(&selector)
-            .filter_map(|element| {
-                // Extract text content
-                let text = if let Some(content) = element.value().attr("content") {
-                    content.to_string()
-                } else {
-                    element
-                        .text()
-                        .collect::<Vec<_>>()
-                        .join(" ")
-                        .trim()
-                        .to_string()
-                };
-
+        let mut values: Vec<String> = self
+            .select_values(document, selector_str)?
+            .into_iter()
+            .filter_map(|text| {
                 if text.is_empty() {
                     return None;
                 }
@@ -315,6 +314,32 @@ impl CssJsonExtractor {
         Ok(Some(values))
     }
 
+    /// Values matched by a CSS or XPath selector, before filters and transformers
+    fn select_values(&self, document: &Html, selector_str: &str) -> Result<Vec<String>> {
+        let Some(expression) = xpath_expression(selector_str) else {
+            // Parse enhanced CSS selector (CSS-001)
+            let selector = self.parse_enhanced_selector(selector_str)?;
+            return Ok(document.select(&selector).map(element_value).collect());
+        };
+
+        let value = XPath::parse(expression)
+            .with_context(|| format!("Invalid XPath selector '{}'", selector_str))?
+            .with_namespaces(self.namespaces.clone())
+            .evaluate(document)
+            .with_context(|| format!("Failed to evaluate XPath selector '{}'", selector_str))?;
+
+        Ok(match value {
+            XPathValue::Nodes(nodes) => nodes
+                .iter()
+                .map(|node| match node.as_element() {
+                    Some(element) => element_value(element),
+                    None => node.string_value().trim().to_string(),
+                })
+                .collect(),
+            scalar => vec![scalar.string().trim().to_string()],
+        })
+    }
+
     /// Parse enhanced CSS selector with support for advanced features
     fn parse_enhanced_selector(&self, selector_str: &str) -> Result<Selector> {
         // Handle custom :has-text() pseudo-selector by removing it for scraper parsing
@@ -463,12 +488,11 @@ impl CssJsonExtractor {
         let total_selectors = self.selectors.len();
 
         for (field, config) in &self.selectors {
-            if let Ok(selector) = self.parse_enhanced_selector(&config.selector) {
-                if let Some(element) = document.select(&selector).next() {
+            if let Ok(values) = self.select_values(&document, &config.selector) {
+                if let Some(content) = values.first() {
                     found_selectors = found_selectors.saturating_add(1);
 
                     // Calculate quality based on content length and field importance
-                    let content = element.text().collect::<String>();
                     let field_weight = match field.as_str() {
                         "title" => 0.3,
                         "content" | "body" => 0.4,
@@ -503,6 +527,20 @@ impl CssJsonExtractor {
     }
 }
 
+/// Text of a matched element, preferring a `content` attribute as on `<meta>` tags
+fn element_value(element: ElementRef<'_>) -> String {
+    if let Some(content) = element.value().attr("content") {
+        content.to_string()
+    } else {
+        element
+            .text()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .trim()
+            .to_string()
+    }
+}
+
 // CSS-003: 12 Content Transformers Implementation
 
 /// Transformer 1: Trim whitespace
@@ -1055,6 +1093,49 @@ mod tests {
         assert_eq!(result.content, "Custom content here");
     }
 
+    #[tokio::test]
+    async fn test_xpath_selectors() {
+        let html = r#"
+            <html>
+                <head><meta property="og:title" content="Meta Title"></head>
+                <body>
+                    <dl>
+                        <dt>Price</dt><dd> 19.99 </dd>
+                        <dt>Stock</dt><dd>12</dd>
+                    </dl>
+                </body>
+            </html>
+        "#;
+
+        let mut selectors = HashMap::new();
+        selectors.insert(
+            "title".to_string(),
+            "//meta[@property='og:title']".to_string(),
+        );
+        selectors.insert(
+            "content".to_string(),
+            "//dt[.='Price']/following-sibling::dd[1]".to_string(),
+        );
+        selectors.insert(
+            "description".to_string(),
+            "xpath:concat('In stock: ', //dt[.='Stock']/following-sibling::dd[1])".to_string(),
+        );
+
+        let result = extract_simple(html, "https://example.com", &selectors)
+            .await
+            .unwrap();
+
+        assert_eq!(result.title, "Meta Title");
+        assert_eq!(result.content, "19.99");
+        assert_eq!(result.summary, Some("In stock: 12".to_string()));
+
+        let mut invalid = HashMap::new();
+        invalid.insert("title".to_string(), "//h1[".to_string());
+        assert!(extract_simple(html, "https://example.com", &invalid)
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn test_confidence_score() {
         let extractor = CssJsonExtractor::new(default_selectors());
@@ -0,0 +1,41 @@
+//! Diffs between two HTML snapshots of a page
+//!
+//! Both snapshots are extracted with the native parser and compared with
+//! [`ExtractionDiff::between`]. The quality threshold does not apply, so
+//! thin pages are diffed too.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_extraction::diff::diff_html;
+//!
+//! let old = r#"<html><head><title>Menu</title></head><body><main>
+//!     <p>Soup of the day: tomato</p><a href="/wine">Wine list</a></main></body></html>"#;
+//! let new = r#"<html><head><title>Menu</title></head><body><main>
+//!     <p>Soup of the day: leek</p></main></body></html>"#;
+//!
+//! let diff = diff_html(old, new, "https://example.com/menu").unwrap();
+//! assert!(!diff.is_empty());
+//! assert_eq!(diff.links_removed, vec!["https://example.com/wine"]);
+//! ```
+
+use crate::native_parser::{NativeHtmlParser, ParserConfig, Result};
+
+pub use riptide_types::diff::{BlockChangeKind, ExtractionDiff, MetadataChange, TextBlockChange};
+
+/// Extract the `old_html` and `new_html` snapshots of `url` and diff them
+///
+/// # Errors
+///
+/// Returns an error when either snapshot is too large, is not an HTML
+/// document or has no text.
+pub fn diff_html(old_html: &str, new_html: &str, url: &str) -> Result<ExtractionDiff> {
+    let parser = NativeHtmlParser::with_config(ParserConfig {
+        enable_markdown: false,
+        min_quality_score: 0,
+        ..Default::default()
+    });
+    let old = parser.parse_headless_html(old_html, url)?;
+    let new = parser.parse_headless_html(new_html, url)?;
+    Ok(ExtractionDiff::between(&old, &new))
+}
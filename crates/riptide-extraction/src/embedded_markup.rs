@@ -0,0 +1,528 @@
+//! Inline MathML and SVG handling
+//!
+//! Formulas and graphics embedded in HTML carry little usable text: MathML
+//! tokens run together ("x2+1" for x²+1) and SVG is dropped from markdown.
+//! [`rewrite_document`] replaces each outermost `<math>` and `<svg>` element
+//! with text chosen by an [`EmbeddedMarkup`] mode before content is
+//! extracted:
+//!
+//! - [`EmbeddedMarkup::Latex`] converts MathML to LaTeX with
+//!   [`math_to_latex`], preferring a TeX annotation the page already carries
+//! - [`EmbeddedMarkup::Raw`] keeps the markup as text
+//! - [`EmbeddedMarkup::Alt`] uses the alt descriptions authors provide
+//!
+//! Elements with nothing to show for the mode, such as an SVG icon without
+//! a label, are removed.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_extraction::embedded_markup::rewrite_html;
+//! use riptide_types::EmbeddedMarkup;
+//!
+//! let html = "<html><body><p>Energy: <math><mi>E</mi><mo>=</mo><mi>m</mi>\
+//!     <msup><mi>c</mi><mn>2</mn></msup></math></p></body></html>";
+//!
+//! let rewritten = rewrite_html(html, EmbeddedMarkup::Latex);
+//! assert!(rewritten.contains("<p>Energy: $E=mc^2$</p>"));
+//! ```
+
+use std::borrow::Cow;
+
+use scraper::node::Text;
+use scraper::{ElementRef, Html, Node};
+
+pub use riptide_types::EmbeddedMarkup;
+
+/// LaTeX commands for Greek letters and mathematical symbols
+const SYMBOLS: &[(char, &str)] = &[
+    ('α', "\\alpha"),
+    ('β', "\\beta"),
+    ('γ', "\\gamma"),
+    ('δ', "\\delta"),
+    ('ε', "\\epsilon"),
+    ('ζ', "\\zeta"),
+    ('η', "\\eta"),
+    ('θ', "\\theta"),
+    ('ι', "\\iota"),
+    ('κ', "\\kappa"),
+    ('λ', "\\lambda"),
+    ('μ', "\\mu"),
+    ('ν', "\\nu"),
+    ('ξ', "\\xi"),
+    ('π', "\\pi"),
+    ('ρ', "\\rho"),
+    ('σ', "\\sigma"),
+    ('τ', "\\tau"),
+    ('υ', "\\upsilon"),
+    ('φ', "\\phi"),
+    ('χ', "\\chi"),
+    ('ψ', "\\psi"),
+    ('ω', "\\omega"),
+    ('Γ', "\\Gamma"),
+    ('Δ', "\\Delta"),
+    ('Θ', "\\Theta"),
+    ('Λ', "\\Lambda"),
+    ('Ξ', "\\Xi"),
+    ('Π', "\\Pi"),
+    ('Σ', "\\Sigma"),
+    ('Φ', "\\Phi"),
+    ('Ψ', "\\Psi"),
+    ('Ω', "\\Omega"),
+    ('∑', "\\sum"),
+    ('∏', "\\prod"),
+    ('∫', "\\int"),
+    ('∮', "\\oint"),
+    ('⋃', "\\bigcup"),
+    ('⋂', "\\bigcap"),
+    ('×', "\\times"),
+    ('·', "\\cdot"),
+    ('⋅', "\\cdot"),
+    ('÷', "\\div"),
+    ('±', "\\pm"),
+    ('∓', "\\mp"),
+    ('−', "-"),
+    ('≤', "\\leq"),
+    ('≥', "\\geq"),
+    ('≠', "\\neq"),
+    ('≈', "\\approx"),
+    ('≡', "\\equiv"),
+    ('∝', "\\propto"),
+    ('∞', "\\infty"),
+    ('→', "\\to"),
+    ('←', "\\leftarrow"),
+    ('↦', "\\mapsto"),
+    ('⇒', "\\Rightarrow"),
+    ('⇔', "\\Leftrightarrow"),
+    ('∈', "\\in"),
+    ('∉', "\\notin"),
+    ('⊂', "\\subset"),
+    ('⊆', "\\subseteq"),
+    ('∪', "\\cup"),
+    ('∩', "\\cap"),
+    ('∅', "\\emptyset"),
+    ('∂', "\\partial"),
+    ('∇', "\\nabla"),
+    ('∀', "\\forall"),
+    ('∃', "\\exists"),
+    ('¬', "\\neg"),
+    ('∧', "\\wedge"),
+    ('∨', "\\vee"),
+    ('∘', "\\circ"),
+    ('…', "\\ldots"),
+    ('⋯', "\\cdots"),
+    ('{', "\\{"),
+    ('}', "\\}"),
+    ('%', "\\%"),
+    ('&', "\\&"),
+    ('#', "\\#"),
+    // Invisible function application, times and separator
+    ('\u{2061}', ""),
+    ('\u{2062}', ""),
+    ('\u{2063}', ""),
+];
+
+/// Function names LaTeX sets upright with a command of the same name
+const FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan", "sinh", "cosh", "tanh",
+    "log", "ln", "lg", "exp", "lim", "max", "min", "sup", "inf", "det", "gcd", "deg", "dim", "ker",
+    "arg", "Pr",
+];
+
+/// Operators whose `munder`/`mover` scripts are limits
+const BIG_OPERATORS: &[&str] = &[
+    "\\sum", "\\prod", "\\int", "\\oint", "\\bigcup", "\\bigcap", "\\lim", "\\max", "\\min",
+    "\\sup", "\\inf",
+];
+
+/// `mover` scripts that are accents, and the LaTeX accent for each
+const ACCENTS: &[(&str, &str)] = &[
+    ("^", "\\hat"),
+    ("ˆ", "\\hat"),
+    ("¯", "\\bar"),
+    ("‾", "\\overline"),
+    ("\\to", "\\vec"),
+    ("⃗", "\\vec"),
+    ("~", "\\tilde"),
+    ("˜", "\\tilde"),
+    (".", "\\dot"),
+    ("˙", "\\dot"),
+    ("¨", "\\ddot"),
+];
+
+/// `html` with its inline MathML and SVG rewritten for `handling`, or
+/// `html` itself when there is nothing to rewrite
+pub fn rewrite_html(html: &str, handling: EmbeddedMarkup) -> Cow<'_, str> {
+    if handling == EmbeddedMarkup::Text || !mentions_embedded_markup(html) {
+        return Cow::Borrowed(html);
+    }
+    let mut document = Html::parse_document(html);
+    if rewrite_document(&mut document, handling) == 0 {
+        return Cow::Borrowed(html);
+    }
+    Cow::Owned(document.html())
+}
+
+/// Replace the outermost `<math>` and `<svg>` elements of `document` with
+/// text for `handling`; returns how many elements were replaced
+pub fn rewrite_document(document: &mut Html, handling: EmbeddedMarkup) -> usize {
+    if handling == EmbeddedMarkup::Text {
+        return 0;
+    }
+    let replacements: Vec<_> = document
+        .root_element()
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|element| {
+            is_embedded(element)
+                && !element
+                    .ancestors()
+                    .filter_map(ElementRef::wrap)
+                    .any(|ancestor| is_embedded(&ancestor))
+        })
+        .map(|element| (element.id(), replacement(element, handling)))
+        .collect();
+
+    for (id, text) in &replacements {
+        if let Some(mut node) = document.tree.get_mut(*id) {
+            if let Some(text) = text {
+                node.insert_before(Node::Text(Text {
+                    text: text.as_str().into(),
+                }));
+            }
+            node.detach();
+        }
+    }
+    replacements.len()
+}
+
+/// Text standing in for a `<math>` or `<svg>` element under `handling`;
+/// `None` when the element has nothing to show
+pub fn replacement(element: ElementRef<'_>, handling: EmbeddedMarkup) -> Option<String> {
+    let is_math = element.value().name() == "math";
+    let text = match handling {
+        EmbeddedMarkup::Text => collapse(&element.text().collect::<String>()),
+        EmbeddedMarkup::Raw => element.html(),
+        EmbeddedMarkup::Latex if is_math => {
+            let latex = math_to_latex(element);
+            if latex.is_empty() {
+                return None;
+            }
+            if element.value().attr("display") == Some("block") {
+                format!("$${}$$", latex)
+            } else {
+                format!("${}$", latex)
+            }
+        }
+        EmbeddedMarkup::Latex | EmbeddedMarkup::Alt if is_math => math_alt(element),
+        EmbeddedMarkup::Latex | EmbeddedMarkup::Alt => svg_alt(element)?,
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// LaTeX for the MathML `math` element, from its TeX annotation when it has
+/// one
+pub fn math_to_latex(math: ElementRef<'_>) -> String {
+    tex_annotation(math).unwrap_or_else(|| latex_children(math).trim().to_string())
+}
+
+fn mentions_embedded_markup(html: &str) -> bool {
+    let lower = html.to_ascii_lowercase();
+    lower.contains("<math") || lower.contains("<svg")
+}
+
+fn is_embedded(element: &ElementRef<'_>) -> bool {
+    matches!(element.value().name(), "math" | "svg")
+}
+
+fn tex_annotation(math: ElementRef<'_>) -> Option<String> {
+    math.descendants()
+        .filter_map(ElementRef::wrap)
+        .find(|element| {
+            element.value().name() == "annotation"
+                && element.value().attr("encoding").is_some_and(|encoding| {
+                    matches!(
+                        encoding.to_ascii_lowercase().as_str(),
+                        "application/x-tex" | "application/x-latex" | "tex" | "latex"
+                    )
+                })
+        })
+        .map(|annotation| annotation.text().collect::<String>().trim().to_string())
+        .filter(|tex| !tex.is_empty())
+}
+
+fn latex(element: ElementRef<'_>) -> String {
+    let children: Vec<ElementRef> = element.children().filter_map(ElementRef::wrap).collect();
+    let arg = |i: usize| {
+        children
+            .get(i)
+            .map(|child| latex(*child))
+            .unwrap_or_default()
+    };
+
+    match element.value().name() {
+        "mi" => identifier(&token(element)),
+        "mn" => token(element),
+        "mo" => operator(&token(element)),
+        "mtext" | "ms" => {
+            let text = token(element);
+            if text.is_empty() {
+                text
+            } else {
+                format!("\\text{{{}}}", text)
+            }
+        }
+        "mspace" => " ".to_string(),
+        "msup" => format!("{}^{}", group(&arg(0)), group(&arg(1))),
+        "msub" => format!("{}_{}", group(&arg(0)), group(&arg(1))),
+        "msubsup" => format!("{}_{}^{}", group(&arg(0)), group(&arg(1)), group(&arg(2))),
+        "munder" => under_over(&arg(0), Some(arg(1).as_str()), None),
+        "mover" => under_over(&arg(0), None, Some(arg(1).as_str())),
+        "munderover" => under_over(&arg(0), Some(arg(1).as_str()), Some(arg(2).as_str())),
+        "mfrac" => format!("\\frac{{{}}}{{{}}}", arg(0), arg(1)),
+        "msqrt" => format!("\\sqrt{{{}}}", latex_children(element)),
+        "mroot" => format!("\\sqrt[{}]{{{}}}", arg(1), arg(0)),
+        "mfenced" => {
+            let value = element.value();
+            let separator = value
+                .attr("separators")
+                .and_then(|s| s.trim().chars().next())
+                .map_or(",".to_string(), |c| c.to_string());
+            let inner = children
+                .iter()
+                .map(|child| latex(*child))
+                .collect::<Vec<_>>()
+                .join(&format!("{} ", separator));
+            format!(
+                "{}{}{}",
+                operator(value.attr("open").unwrap_or("(")),
+                inner,
+                operator(value.attr("close").unwrap_or(")"))
+            )
+        }
+        "mtable" => {
+            let rows = children
+                .iter()
+                .map(|row| {
+                    row.children()
+                        .filter_map(ElementRef::wrap)
+                        .map(latex)
+                        .collect::<Vec<_>>()
+                        .join(" & ")
+                })
+                .collect::<Vec<_>>()
+                .join(" \\\\ ");
+            format!("\\begin{{matrix}} {} \\end{{matrix}}", rows)
+        }
+        "semantics" => arg(0),
+        "annotation" | "annotation-xml" | "mphantom" | "none" | "mprescripts" => String::new(),
+        _ => latex_children(element),
+    }
+}
+
+fn latex_children(element: ElementRef<'_>) -> String {
+    let mut out = String::new();
+    for child in element.children().filter_map(ElementRef::wrap) {
+        let piece = latex(child);
+        // "\alpha x" must not run together into the unknown command "\alphax"
+        if ends_with_command(&out) && piece.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            out.push(' ');
+        }
+        out.push_str(&piece);
+    }
+    out
+}
+
+fn identifier(name: &str) -> String {
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (None, _) => String::new(),
+        (Some(c), None) => symbol(c).map_or_else(|| c.to_string(), str::to_string),
+        _ if FUNCTIONS.contains(&name) => format!("\\{}", name),
+        _ => format!("\\mathrm{{{}}}", name),
+    }
+}
+
+fn operator(op: &str) -> String {
+    if FUNCTIONS.contains(&op) {
+        return format!("\\{}", op);
+    }
+    let mut out = String::new();
+    for c in op.chars() {
+        let piece = symbol(c).map_or_else(|| c.to_string(), str::to_string);
+        if ends_with_command(&out) && piece.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            out.push(' ');
+        }
+        out.push_str(&piece);
+    }
+    out
+}
+
+fn under_over(base: &str, under: Option<&str>, over: Option<&str>) -> String {
+    if BIG_OPERATORS.contains(&base) {
+        let mut out = base.to_string();
+        if let Some(under) = under {
+            out.push_str(&format!("_{}", group(under)));
+        }
+        if let Some(over) = over {
+            out.push_str(&format!("^{}", group(over)));
+        }
+        return out;
+    }
+    let accent = over.and_then(|over| {
+        ACCENTS
+            .iter()
+            .find(|(script, _)| *script == over.trim())
+            .map(|(_, accent)| *accent)
+    });
+    let base = match (accent, over) {
+        (Some(accent), _) => format!("{}{{{}}}", accent, base),
+        (None, Some(over)) => format!("\\overset{{{}}}{{{}}}", over, base),
+        (None, None) => base.to_string(),
+    };
+    match under {
+        Some(under) => format!("\\underset{{{}}}{{{}}}", under, base),
+        None => base,
+    }
+}
+
+/// `latex` as a script or base: bare when a single character or command,
+/// braced otherwise
+fn group(latex: &str) -> String {
+    let single_command = latex.starts_with('\\')
+        && latex.len() > 1
+        && latex[1..].chars().all(|c| c.is_ascii_alphabetic());
+    if latex.chars().count() == 1 || single_command {
+        latex.to_string()
+    } else {
+        format!("{{{}}}", latex)
+    }
+}
+
+fn ends_with_command(latex: &str) -> bool {
+    latex.rfind('\\').is_some_and(|start| {
+        let name = &latex[start + 1..];
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphabetic())
+    })
+}
+
+fn symbol(c: char) -> Option<&'static str> {
+    SYMBOLS
+        .iter()
+        .find(|(symbol, _)| *symbol == c)
+        .map(|(_, command)| *command)
+}
+
+fn token(element: ElementRef<'_>) -> String {
+    collapse(&element.text().collect::<String>())
+}
+
+/// `alttext` or `aria-label` of a formula, or its tokens spaced out
+fn math_alt(math: ElementRef<'_>) -> String {
+    let value = math.value();
+    if let Some(alt) = value
+        .attr("alttext")
+        .or_else(|| value.attr("aria-label"))
+        .map(collapse)
+        .filter(|alt| !alt.is_empty())
+    {
+        return alt;
+    }
+    let tokens: Vec<String> = math
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|element| matches!(element.value().name(), "mi" | "mn" | "mo" | "mtext"))
+        .map(token)
+        .filter(|token| !token.is_empty())
+        .collect();
+    tokens.join(" ")
+}
+
+/// `aria-label`, `<title>` or `<desc>` of a graphic; `None` for decorative
+/// or unlabelled ones
+fn svg_alt(svg: ElementRef<'_>) -> Option<String> {
+    let value = svg.value();
+    if value.attr("aria-hidden") == Some("true") {
+        return None;
+    }
+    let child_text = |name: &str| {
+        svg.children()
+            .filter_map(ElementRef::wrap)
+            .find(|child| child.value().name() == name)
+            .map(token)
+    };
+    value
+        .attr("aria-label")
+        .map(collapse)
+        .filter(|label| !label.is_empty())
+        .or_else(|| child_text("title").filter(|title| !title.is_empty()))
+        .or_else(|| child_text("desc").filter(|desc| !desc.is_empty()))
+}
+
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn latex_of(mathml: &str) -> String {
+        let document = Html::parse_fragment(mathml);
+        let math = document
+            .root_element()
+            .descendants()
+            .filter_map(ElementRef::wrap)
+            .find(|element| element.value().name() == "math")
+            .unwrap();
+        math_to_latex(math)
+    }
+
+    #[test]
+    fn test_mathml_to_latex() {
+        assert_eq!(
+            latex_of(
+                "<math><mfrac><mrow><mo>-</mo><mi>b</mi><mo>±</mo>\
+                 <msqrt><msup><mi>b</mi><mn>2</mn></msup><mo>−</mo><mn>4</mn><mi>a</mi><mi>c</mi></msqrt>\
+                 </mrow><mrow><mn>2</mn><mi>a</mi></mrow></mfrac></math>"
+            ),
+            "\\frac{-b\\pm\\sqrt{b^2-4ac}}{2a}"
+        );
+        assert_eq!(
+            latex_of(
+                "<math><munderover><mo>∑</mo><mrow><mi>i</mi><mo>=</mo><mn>1</mn></mrow>\
+                 <mi>n</mi></munderover><msub><mi>x</mi><mi>i</mi></msub><mo>⋅</mo>\
+                 <mi>α</mi><mi>y</mi></math>"
+            ),
+            "\\sum_{i=1}^nx_i\\cdot\\alpha y"
+        );
+        assert_eq!(
+            latex_of(
+                "<math><semantics><mi>x</mi>\
+                 <annotation encoding=\"application/x-tex\">\\hat{x}</annotation></semantics></math>"
+            ),
+            "\\hat{x}"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_modes() {
+        let html = "<html><body><p>Area <math alttext=\"pi r squared\"><mi>π</mi>\
+            <msup><mi>r</mi><mn>2</mn></msup></math> of <svg aria-hidden=\"true\"><path d=\"M0 0\"/></svg>\
+            <svg><title>a circle</title><circle r=\"1\"/></svg></p></body></html>";
+
+        let latex = rewrite_html(html, EmbeddedMarkup::Latex);
+        assert!(latex.contains("<p>Area $\\pi r^2$ of a circle</p>"));
+
+        let alt = rewrite_html(html, EmbeddedMarkup::Alt);
+        assert!(alt.contains("<p>Area pi r squared of a circle</p>"));
+
+        let raw = rewrite_html(html, EmbeddedMarkup::Raw);
+        assert!(raw.contains("&lt;math alttext="));
+
+        assert!(matches!(
+            rewrite_html(html, EmbeddedMarkup::Text),
+            Cow::Borrowed(_)
+        ));
+    }
+}
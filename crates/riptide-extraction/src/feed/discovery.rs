@@ -0,0 +1,105 @@
+//! Feed auto-discovery from `<link rel="alternate">`
+
+use scraper::{Html, Selector};
+use std::sync::OnceLock;
+use url::Url;
+
+use super::{FeedFormat, FeedLink};
+
+/// Parse `html` and list the feeds it advertises
+pub fn discover_feeds(html: &str, url: &str) -> Vec<FeedLink> {
+    FeedDiscovery::discover(&Html::parse_document(html), url)
+}
+
+/// Finds feeds advertised by `<link rel="alternate" type="...">`
+pub struct FeedDiscovery;
+
+impl FeedDiscovery {
+    /// Feeds of a parsed page, in document order, with URLs resolved
+    /// against `url`
+    ///
+    /// Only links whose `type` is a feed media type count: pages also use
+    /// `rel="alternate"` for translations and print versions.
+    pub fn discover(document: &Html, url: &str) -> Vec<FeedLink> {
+        static SELECTOR: OnceLock<Selector> = OnceLock::new();
+        let selector = SELECTOR
+            .get_or_init(|| Selector::parse("link[rel][href][type]").expect("valid selector"));
+        let base = Url::parse(url).ok();
+
+        let mut feeds: Vec<FeedLink> = Vec::new();
+        for link in document.select(selector) {
+            let element = link.value();
+            let is_alternate = element
+                .attr("rel")
+                .unwrap_or_default()
+                .split_whitespace()
+                .any(|rel| rel.eq_ignore_ascii_case("alternate"));
+            let href = element.attr("href").unwrap_or_default().trim();
+            if !is_alternate || href.is_empty() {
+                continue;
+            }
+            let Some(format) = element.attr("type").and_then(FeedFormat::from_media_type) else {
+                continue;
+            };
+            let resolved = match &base {
+                Some(base) => base.join(href),
+                None => Url::parse(href),
+            };
+            let Ok(feed_url) = resolved.map(String::from) else {
+                continue;
+            };
+            if feeds.iter().any(|feed| feed.url == feed_url) {
+                continue;
+            }
+            feeds.push(FeedLink {
+                url: feed_url,
+                format,
+                title: element
+                    .attr("title")
+                    .map(str::trim)
+                    .filter(|title| !title.is_empty())
+                    .map(str::to_string),
+            });
+        }
+        feeds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discovers_advertised_feeds() {
+        let html = r#"<html><head>
+            <link rel="alternate" type="application/rss+xml" title="Posts" href="/feed.xml">
+            <link rel="Alternate" type="application/atom+xml" href="https://example.com/atom">
+            <link rel="alternate" type="application/feed+json" href="feed.json">
+            <link rel="alternate" type="application/rss+xml" href="/feed.xml">
+            <link rel="alternate" hreflang="de" type="text/html" href="/de/">
+            <link rel="stylesheet" type="text/css" href="/style.css">
+        </head><body></body></html>"#;
+
+        let feeds = discover_feeds(html, "https://example.com/blog/");
+        assert_eq!(
+            feeds,
+            vec![
+                FeedLink {
+                    url: "https://example.com/feed.xml".to_string(),
+                    format: FeedFormat::Rss,
+                    title: Some("Posts".to_string()),
+                },
+                FeedLink {
+                    url: "https://example.com/atom".to_string(),
+                    format: FeedFormat::Atom,
+                    title: None,
+                },
+                FeedLink {
+                    url: "https://example.com/blog/feed.json".to_string(),
+                    format: FeedFormat::JsonFeed,
+                    title: None,
+                },
+            ]
+        );
+    }
+}
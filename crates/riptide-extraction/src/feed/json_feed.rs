@@ -0,0 +1,84 @@
+//! JSON Feed 1.0 and 1.1 parsing
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use super::{RawEntry, RawFeed};
+
+#[derive(Deserialize)]
+struct JsonFeed {
+    version: String,
+    title: Option<String>,
+    home_page_url: Option<String>,
+    description: Option<String>,
+    language: Option<String>,
+    #[serde(default)]
+    items: Vec<JsonItem>,
+}
+
+#[derive(Deserialize)]
+struct JsonItem {
+    /// A string per the spec, but numbers are common in the wild
+    id: Option<serde_json::Value>,
+    url: Option<String>,
+    external_url: Option<String>,
+    title: Option<String>,
+    summary: Option<String>,
+    content_html: Option<String>,
+    content_text: Option<String>,
+    date_published: Option<String>,
+    date_modified: Option<String>,
+    /// JSON Feed 1.1
+    #[serde(default)]
+    authors: Vec<JsonAuthor>,
+    /// JSON Feed 1.0
+    author: Option<JsonAuthor>,
+    #[serde(default)]
+    tags: Vec<String>,
+    image: Option<String>,
+    banner_image: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JsonAuthor {
+    name: Option<String>,
+}
+
+pub(super) fn parse(body: &str) -> Result<RawFeed> {
+    let feed: JsonFeed = serde_json::from_str(body).context("Malformed JSON Feed")?;
+    if !feed.version.contains("jsonfeed.org/version") {
+        bail!("Unsupported JSON Feed version: {}", feed.version);
+    }
+
+    let entries = feed
+        .items
+        .into_iter()
+        .map(|item| RawEntry {
+            title: item.title,
+            link: item.url.or(item.external_url),
+            id: item.id.map(|id| match id {
+                serde_json::Value::String(id) => id,
+                other => other.to_string(),
+            }),
+            published: item.date_published,
+            updated: item.date_modified,
+            summary: item.summary,
+            content: item.content_html.or(item.content_text),
+            authors: item
+                .authors
+                .into_iter()
+                .chain(item.author)
+                .filter_map(|author| author.name)
+                .collect(),
+            categories: item.tags,
+            media: item.image.into_iter().chain(item.banner_image).collect(),
+        })
+        .collect();
+    Ok(RawFeed {
+        title: feed.title,
+        link: feed.home_page_url,
+        description: feed.description,
+        language: feed.language,
+        entries,
+    })
+}
@@ -0,0 +1,410 @@
+//! RSS 2.0, Atom and JSON Feed extraction
+//!
+//! [`parse_feed`] turns a feed into one [`ExtractedDoc`] for the feed itself
+//! (its title, description and entry links) whose `feed` field holds a
+//! document per entry with the entry's link, title, publication date and
+//! summary. [`detect_feed`] decides from the content type and the first
+//! bytes of a response whether it is a feed at all, and [`FeedDiscovery`]
+//! finds the feeds an HTML page advertises.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_extraction::feed::{parse_feed, FeedFormat};
+//!
+//! let rss = r#"<rss version="2.0"><channel><title>Blog</title>
+//!     <item><title>Hello</title><link>/hello</link>
+//!     <pubDate>Tue, 10 Jun 2025 09:00:00 GMT</pubDate></item>
+//! </channel></rss>"#;
+//!
+//! let doc = parse_feed(rss, "https://example.com/feed.xml").unwrap();
+//! let feed = doc.feed.unwrap();
+//! assert_eq!(feed.format, FeedFormat::Rss);
+//! assert_eq!(feed.entries[0].url, "https://example.com/hello");
+//! assert_eq!(feed.entries[0].published_iso.as_deref(), Some("2025-06-10T09:00:00+00:00"));
+//! ```
+
+mod discovery;
+mod json_feed;
+mod rss_atom;
+
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use scraper::Html;
+use url::Url;
+
+pub use discovery::{discover_feeds, FeedDiscovery};
+pub use riptide_types::feed::{DocumentFeed, FeedFormat, FeedLink};
+use riptide_types::ExtractedDoc;
+
+/// Bytes of a response inspected when sniffing for a feed
+const SNIFF_BYTES: usize = 2048;
+
+/// Feed metadata and entries, before conversion to documents
+#[derive(Debug, Default)]
+struct RawFeed {
+    title: Option<String>,
+    link: Option<String>,
+    description: Option<String>,
+    language: Option<String>,
+    entries: Vec<RawEntry>,
+}
+
+#[derive(Debug, Default)]
+struct RawEntry {
+    title: Option<String>,
+    link: Option<String>,
+    /// RSS `guid`, Atom `id` or JSON Feed `id`
+    id: Option<String>,
+    published: Option<String>,
+    updated: Option<String>,
+    /// Summary, possibly HTML
+    summary: Option<String>,
+    /// Full content, possibly HTML
+    content: Option<String>,
+    authors: Vec<String>,
+    categories: Vec<String>,
+    media: Vec<String>,
+}
+
+/// Format of a response, when it is a feed
+///
+/// Feed media types decide on their own. Feeds are often served as
+/// `text/xml`, `application/xml` or `application/json`, so responses with
+/// generic or missing types are sniffed; HTML never is.
+pub fn detect_feed(content_type: Option<&str>, body: &[u8]) -> Option<FeedFormat> {
+    if let Some(format) = content_type.and_then(FeedFormat::from_media_type) {
+        return Some(format);
+    }
+    let generic = content_type.is_none_or(|content_type| {
+        let essence = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        essence.ends_with("/xml")
+            || essence.ends_with("+xml")
+            || essence.ends_with("/json")
+            || essence == "text/plain"
+    });
+    if !generic {
+        return None;
+    }
+    sniff(&head(body))
+}
+
+/// First [`SNIFF_BYTES`] of a body, cutting a trailing character if need be
+fn head(body: &[u8]) -> std::borrow::Cow<'_, str> {
+    String::from_utf8_lossy(&body[..body.len().min(SNIFF_BYTES)])
+}
+
+/// Format of a feed from its first bytes
+fn sniff(head: &str) -> Option<FeedFormat> {
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+    if head.starts_with('{') {
+        return head
+            .contains("jsonfeed.org/version")
+            .then_some(FeedFormat::JsonFeed);
+    }
+    let head = head.to_ascii_lowercase();
+    if has_element(&head, "rss") || has_element(&head, "rdf:rdf") {
+        Some(FeedFormat::Rss)
+    } else if has_element(&head, "feed") {
+        Some(FeedFormat::Atom)
+    } else {
+        None
+    }
+}
+
+/// Whether `<name` opens an element (and is not a prefix of a longer name)
+fn has_element(head: &str, name: &str) -> bool {
+    let open = format!("<{}", name);
+    head.match_indices(&open).any(|(at, _)| {
+        head[at + open.len()..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_whitespace() || c == '>' || c == '/')
+    })
+}
+
+/// Parse an RSS, Atom or JSON Feed document fetched from `url`
+///
+/// Entry links are resolved against the feed's URL. Entries without a link
+/// fall back to their id and are dropped when they have neither.
+pub fn parse_feed(body: &str, url: &str) -> Result<ExtractedDoc> {
+    let format = sniff(&head(body.as_bytes())).context("Not an RSS, Atom or JSON feed")?;
+    let raw = match format {
+        FeedFormat::JsonFeed => json_feed::parse(body)?,
+        FeedFormat::Rss | FeedFormat::Atom => rss_atom::parse(body)?,
+    };
+    Ok(build_document(raw, format, url))
+}
+
+fn build_document(raw: RawFeed, format: FeedFormat, url: &str) -> ExtractedDoc {
+    let base = Url::parse(url).ok();
+    let site_name = raw.title.as_deref().map(html_to_text);
+    let language = raw.language.clone();
+
+    let entries: Vec<ExtractedDoc> = raw
+        .entries
+        .into_iter()
+        .filter_map(|entry| {
+            entry_document(entry, base.as_ref(), site_name.as_ref(), language.as_ref())
+        })
+        .collect();
+
+    let text = entries
+        .iter()
+        .filter_map(|entry| entry.title.as_deref())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let word_count = text.split_whitespace().count() as u32;
+    ExtractedDoc {
+        url: url.to_string(),
+        title: site_name.clone(),
+        description: raw.description.as_deref().map(html_to_text),
+        site_name: site_name.or_else(|| raw.link.as_deref().and_then(host_of)),
+        language,
+        links: entries.iter().map(|entry| entry.url.clone()).collect(),
+        text,
+        word_count: Some(word_count),
+        feed: Some(DocumentFeed { format, entries }),
+        ..Default::default()
+    }
+}
+
+fn entry_document(
+    entry: RawEntry,
+    base: Option<&Url>,
+    site_name: Option<&String>,
+    language: Option<&String>,
+) -> Option<ExtractedDoc> {
+    let href = entry
+        .link
+        .or_else(|| entry.id.filter(|id| id.starts_with("http")))?;
+    let url = match base {
+        Some(base) => base.join(href.trim()).ok()?.to_string(),
+        None => href.trim().to_string(),
+    };
+
+    let summary = entry.summary.as_deref().map(html_to_text);
+    let text = entry
+        .content
+        .as_deref()
+        .map(html_to_text)
+        .or_else(|| summary.clone())
+        .unwrap_or_default();
+    let word_count = text.split_whitespace().count();
+    Some(ExtractedDoc {
+        url,
+        title: entry.title.as_deref().map(html_to_text),
+        byline: (!entry.authors.is_empty()).then(|| entry.authors.join(", ")),
+        published_iso: entry.published.or(entry.updated).map(normalize_date),
+        description: summary,
+        reading_time: Some((word_count / 200).max(1) as u32),
+        word_count: Some(word_count as u32),
+        text,
+        categories: entry.categories,
+        media: entry.media,
+        site_name: site_name.cloned(),
+        language: language.cloned(),
+        ..Default::default()
+    })
+}
+
+/// Plain text of a feed field, which may hold escaped HTML
+fn html_to_text(value: &str) -> String {
+    let text = if value.contains('<') || value.contains('&') {
+        Html::parse_fragment(value)
+            .root_element()
+            .text()
+            .collect::<Vec<_>>()
+            .join(" ")
+    } else {
+        value.to_string()
+    };
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// RFC 3339 form of an RFC 3339 or RFC 2822 date, the raw value otherwise
+fn normalize_date(value: String) -> String {
+    let trimmed = value.trim();
+    DateTime::parse_from_rfc3339(trimmed)
+        .or_else(|_| DateTime::parse_from_rfc2822(trimmed))
+        .map(|date| date.to_rfc3339())
+        .unwrap_or_else(|_| trimmed.to_string())
+}
+
+fn host_of(link: &str) -> Option<String> {
+    Url::parse(link).ok()?.host_str().map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/"
+     xmlns:dc="http://purl.org/dc/elements/1.1/">
+  <channel>
+    <title>Example Blog</title>
+    <link>https://example.com/</link>
+    <description>News &amp; notes</description>
+    <language>en-us</language>
+    <image><title>Logo</title><url>https://example.com/logo.png</url></image>
+    <item>
+      <title>First post</title>
+      <link>https://example.com/first</link>
+      <guid isPermaLink="false">post-1</guid>
+      <pubDate>Mon, 02 Jun 2025 08:30:00 +0200</pubDate>
+      <dc:creator>Ada</dc:creator>
+      <category>rust</category>
+      <description>&lt;p&gt;Short &lt;b&gt;summary&lt;/b&gt;&lt;/p&gt;</description>
+      <content:encoded><![CDATA[<p>The full text of the first post.</p>]]></content:encoded>
+      <enclosure url="https://example.com/first.mp3" type="audio/mpeg" length="1"/>
+    </item>
+    <item>
+      <title>Second post</title>
+      <guid>https://example.com/second</guid>
+      <description>Plain summary</description>
+    </item>
+    <item><title>No link at all</title></item>
+  </channel>
+</rss>"#;
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <title type="text">Atom Site</title>
+  <subtitle>Updates</subtitle>
+  <link rel="self" href="https://example.org/atom.xml"/>
+  <link href="https://example.org/"/>
+  <entry>
+    <title>Entry one</title>
+    <link rel="alternate" href="/entries/1"/>
+    <link rel="edit" href="/edit/1"/>
+    <id>urn:uuid:1</id>
+    <updated>2025-06-03T10:00:00Z</updated>
+    <published>2025-06-01T10:00:00Z</published>
+    <author><name>Grace</name><email>grace@example.org</email></author>
+    <category term="news"/>
+    <summary type="html">&lt;em&gt;Entry&lt;/em&gt; summary</summary>
+    <content type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml"><p>Para one.</p><p>Para two.</p></div></content>
+  </entry>
+</feed>"#;
+
+    const JSON_FEED: &str = r#"{
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": "JSON Site",
+        "home_page_url": "https://example.net/",
+        "language": "de",
+        "items": [
+            {
+                "id": "1",
+                "url": "https://example.net/posts/1",
+                "title": "Beitrag",
+                "summary": "Kurz",
+                "content_html": "<p>Langer Text</p>",
+                "date_published": "2025-06-04T12:00:00+02:00",
+                "authors": [{"name": "Lin"}],
+                "tags": ["news"],
+                "image": "https://example.net/1.png"
+            },
+            {"id": "https://example.net/posts/2", "content_text": "Only text"}
+        ]
+    }"#;
+
+    #[test]
+    fn test_parses_rss() {
+        let doc = parse_feed(RSS, "https://example.com/feed").unwrap();
+        assert_eq!(doc.title.as_deref(), Some("Example Blog"));
+        assert_eq!(doc.description.as_deref(), Some("News & notes"));
+        assert_eq!(doc.language.as_deref(), Some("en-us"));
+        assert_eq!(
+            doc.links,
+            vec!["https://example.com/first", "https://example.com/second"]
+        );
+
+        let feed = doc.feed.unwrap();
+        assert_eq!(feed.format, FeedFormat::Rss);
+        assert_eq!(feed.entries.len(), 2);
+        let first = &feed.entries[0];
+        assert_eq!(first.title.as_deref(), Some("First post"));
+        assert_eq!(
+            first.published_iso.as_deref(),
+            Some("2025-06-02T08:30:00+02:00")
+        );
+        assert_eq!(first.description.as_deref(), Some("Short summary"));
+        assert_eq!(first.text, "The full text of the first post.");
+        assert_eq!(first.byline.as_deref(), Some("Ada"));
+        assert_eq!(first.categories, vec!["rust"]);
+        assert_eq!(first.media, vec!["https://example.com/first.mp3"]);
+        assert_eq!(first.site_name.as_deref(), Some("Example Blog"));
+        assert_eq!(feed.entries[1].text, "Plain summary");
+    }
+
+    #[test]
+    fn test_parses_atom() {
+        let doc = parse_feed(ATOM, "https://example.org/atom.xml").unwrap();
+        assert_eq!(doc.title.as_deref(), Some("Atom Site"));
+        assert_eq!(doc.description.as_deref(), Some("Updates"));
+
+        let feed = doc.feed.unwrap();
+        assert_eq!(feed.format, FeedFormat::Atom);
+        let entry = &feed.entries[0];
+        assert_eq!(entry.url, "https://example.org/entries/1");
+        assert_eq!(
+            entry.published_iso.as_deref(),
+            Some("2025-06-01T10:00:00+00:00")
+        );
+        assert_eq!(entry.byline.as_deref(), Some("Grace"));
+        assert_eq!(entry.categories, vec!["news"]);
+        assert_eq!(entry.description.as_deref(), Some("Entry summary"));
+        assert_eq!(entry.text, "Para one. Para two.");
+    }
+
+    #[test]
+    fn test_parses_json_feed() {
+        let doc = parse_feed(JSON_FEED, "https://example.net/feed.json").unwrap();
+        assert_eq!(doc.title.as_deref(), Some("JSON Site"));
+
+        let feed = doc.feed.unwrap();
+        assert_eq!(feed.format, FeedFormat::JsonFeed);
+        let first = &feed.entries[0];
+        assert_eq!(first.url, "https://example.net/posts/1");
+        assert_eq!(first.text, "Langer Text");
+        assert_eq!(first.description.as_deref(), Some("Kurz"));
+        assert_eq!(first.byline.as_deref(), Some("Lin"));
+        assert_eq!(first.language.as_deref(), Some("de"));
+        assert_eq!(first.media, vec!["https://example.net/1.png"]);
+        assert_eq!(feed.entries[1].url, "https://example.net/posts/2");
+        assert_eq!(feed.entries[1].text, "Only text");
+    }
+
+    #[test]
+    fn test_detect_feed() {
+        assert_eq!(
+            detect_feed(Some("application/atom+xml"), b""),
+            Some(FeedFormat::Atom)
+        );
+        assert_eq!(
+            detect_feed(Some("text/xml; charset=utf-8"), RSS.as_bytes()),
+            Some(FeedFormat::Rss)
+        );
+        assert_eq!(detect_feed(None, ATOM.as_bytes()), Some(FeedFormat::Atom));
+        assert_eq!(
+            detect_feed(Some("application/json"), JSON_FEED.as_bytes()),
+            Some(FeedFormat::JsonFeed)
+        );
+        assert_eq!(detect_feed(Some("text/html"), RSS.as_bytes()), None);
+        assert_eq!(
+            detect_feed(Some("application/xml"), b"<feedback>no</feedback>"),
+            None
+        );
+        assert_eq!(
+            detect_feed(Some("application/json"), br#"{"items": []}"#),
+            None
+        );
+        assert!(parse_feed("<html></html>", "https://example.com/").is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! RSS 2.0, RSS 1.0 and Atom parsing
+//!
+//! One streaming pass reads all three: fields are recognized by their local
+//! name and parent element, so namespaced extensions such as
+//! `content:encoded`, `dc:creator` and `media:content` need no special
+//! handling. Atom `type="xhtml"` content is flattened to its text.
+
+use anyhow::{bail, Context, Result};
+use tracing::warn;
+use xml::reader::{ParserConfig, XmlEvent};
+
+use super::{RawEntry, RawFeed};
+
+/// Elements that collect the text of their descendants
+const TEXT_CONTAINERS: &[&str] = &["title", "subtitle", "summary", "content", "description"];
+
+/// HTML entities common in hand-written feeds, which XML does not define
+const HTML_ENTITIES: &[(&str, &str)] = &[
+    ("nbsp", "\u{a0}"),
+    ("ndash", "\u{2013}"),
+    ("mdash", "\u{2014}"),
+    ("hellip", "\u{2026}"),
+    ("lsquo", "\u{2018}"),
+    ("rsquo", "\u{2019}"),
+    ("ldquo", "\u{201c}"),
+    ("rdquo", "\u{201d}"),
+    ("copy", "\u{a9}"),
+];
+
+struct Element {
+    name: String,
+    text: String,
+}
+
+pub(super) fn parse(body: &str) -> Result<RawFeed> {
+    let config = HTML_ENTITIES
+        .iter()
+        .fold(ParserConfig::new(), |config, (name, value)| {
+            config.add_entity(*name, *value)
+        });
+
+    let mut feed = RawFeed::default();
+    let mut stack: Vec<Element> = Vec::new();
+    let mut entry: Option<RawEntry> = None;
+    for event in config.create_reader(body.as_bytes()) {
+        let event = match event {
+            Ok(event) => event,
+            // Keep the entries of a feed that breaks off
+            Err(e) if !feed.entries.is_empty() => {
+                warn!(error = %e, entries = feed.entries.len(), "Malformed feed XML, keeping entries read so far");
+                break;
+            }
+            Err(e) => return Err(e).context("Malformed feed XML"),
+        };
+        match event {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => {
+                let local = name.local_name;
+                if stack.is_empty() && !matches!(local.as_str(), "rss" | "RDF" | "feed") {
+                    bail!("Not a feed: root element <{}>", local);
+                }
+                if matches!(local.as_str(), "item" | "entry") {
+                    entry = Some(RawEntry::default());
+                }
+
+                let attr = |key: &str| {
+                    attributes
+                        .iter()
+                        .find(|attribute| attribute.name.local_name == key)
+                        .map(|attribute| attribute.value.trim().to_string())
+                        .filter(|value| !value.is_empty())
+                };
+                match (local.as_str(), entry.as_mut()) {
+                    // Atom links carry the URL in `href`; the alternate one is the page
+                    ("link", entry) => {
+                        if let Some(href) = attr("href").filter(|_| {
+                            attr("rel").is_none_or(|rel| rel.eq_ignore_ascii_case("alternate"))
+                        }) {
+                            match entry {
+                                Some(entry) => entry.link.get_or_insert(href),
+                                None => feed.link.get_or_insert(href),
+                            };
+                        }
+                    }
+                    ("category", Some(entry)) => entry.categories.extend(attr("term")),
+                    ("enclosure" | "content" | "thumbnail", Some(entry)) => {
+                        if let Some(url) = attr("url") {
+                            if !entry.media.contains(&url) {
+                                entry.media.push(url);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                stack.push(Element {
+                    name: local,
+                    text: String::new(),
+                });
+            }
+            XmlEvent::Characters(data) | XmlEvent::CData(data) => {
+                if let Some(element) = stack.last_mut() {
+                    element.text.push_str(&data);
+                }
+            }
+            XmlEvent::EndElement { .. } => {
+                let Some(element) = stack.pop() else {
+                    continue;
+                };
+                let value = element.text.trim();
+                if stack
+                    .iter()
+                    .any(|ancestor| TEXT_CONTAINERS.contains(&ancestor.name.as_str()))
+                {
+                    if let Some(parent) = stack.last_mut() {
+                        parent.text.push(' ');
+                        parent.text.push_str(value);
+                    }
+                    continue;
+                }
+                if matches!(element.name.as_str(), "item" | "entry") {
+                    feed.entries.extend(entry.take());
+                    continue;
+                }
+                if value.is_empty() {
+                    continue;
+                }
+
+                let value = value.to_string();
+                let parent = stack.last().map(|parent| parent.name.as_str());
+                match (parent, entry.as_mut()) {
+                    (Some("item" | "entry"), Some(entry)) => match element.name.as_str() {
+                        "title" => entry.title = Some(value),
+                        "link" => {
+                            entry.link.get_or_insert(value);
+                        }
+                        "guid" | "id" => entry.id = Some(value),
+                        "pubDate" | "published" | "issued" | "date" => {
+                            entry.published.get_or_insert(value);
+                        }
+                        "updated" | "modified" => entry.updated = Some(value),
+                        "description" | "summary" => entry.summary = Some(value),
+                        "encoded" | "content" => entry.content = Some(value),
+                        "creator" | "author" => entry.authors.push(value),
+                        "category" | "subject" => entry.categories.push(value),
+                        _ => {}
+                    },
+                    // Atom `<author><name>`
+                    (Some("author"), Some(entry)) if element.name == "name" => {
+                        entry.authors.push(value)
+                    }
+                    (Some("channel" | "feed"), None) => match element.name.as_str() {
+                        "title" => feed.title = Some(value),
+                        "link" => {
+                            feed.link.get_or_insert(value);
+                        }
+                        "description" | "subtitle" => feed.description = Some(value),
+                        "language" => feed.language = Some(value),
+                        _ => {}
+                    },
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(feed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_entries_of_truncated_feed() {
+        let body = "<rss><channel><title>T &mdash; x</title>\
+                    <item><title>One&nbsp;entry</title><link>/1</link></item>\
+                    <item><title>Cut";
+        let feed = parse(body).unwrap();
+        assert_eq!(feed.title.as_deref(), Some("T \u{2014} x"));
+        assert_eq!(feed.entries.len(), 1);
+        assert_eq!(feed.entries[0].title.as_deref(), Some("One\u{a0}entry"));
+
+        assert!(parse("<rss><channel><item>").is_err());
+        assert!(parse("<html><body></body></html>").is_err());
+    }
+}
@@ -0,0 +1,283 @@
+//! Image metadata extraction
+//!
+//! Collects the images a page shows as [`ImageMetadata`] instead of bare
+//! URLs:
+//!
+//! - lazy-loaded images: the real source in `data-src`, `data-lazy-src`,
+//!   `data-original` or `data-srcset` replaces a placeholder `src`
+//! - responsive candidates from `srcset` and `<picture>` `<source>`s, with
+//!   their width/density descriptors, media queries and types; an image
+//!   with no usable `src` falls back to its largest candidate
+//! - declared `width`/`height`, `alt`, `title` and the `<figcaption>` of an
+//!   enclosing `<figure>`
+//!
+//! Tracking pixels and inline `data:` images are skipped. Content type and
+//! size need a request per image and are left to the caller.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_extraction::image_metadata::extract_images;
+//!
+//! let html = r#"<figure>
+//!     <img src="data:image/gif;base64,R0lGOD" data-src="/photos/harbour.jpg"
+//!          data-srcset="/photos/harbour-640.jpg 640w, /photos/harbour-1280.jpg 1280w"
+//!          alt="The harbour at dawn" width="640" height="427">
+//!     <figcaption>Boats leaving at 6am</figcaption>
+//! </figure>"#;
+//!
+//! let images = extract_images(html, "https://example.com/news/story");
+//! assert_eq!(images[0].url, "https://example.com/photos/harbour.jpg");
+//! assert!(images[0].lazy);
+//! assert_eq!(images[0].sources[1].width, Some(1280));
+//! assert_eq!(images[0].caption.as_deref(), Some("Boats leaving at 6am"));
+//! ```
+
+use scraper::{ElementRef, Html, Selector};
+use url::Url;
+
+pub use riptide_types::media::{ImageMetadata, ImageSource};
+
+/// Attributes lazy-loading scripts keep the real source in, most specific
+/// first
+const LAZY_SRC_ATTRS: &[&str] = &["data-src", "data-lazy-src", "data-original", "data-url"];
+
+/// Attributes lazy-loading scripts keep the real `srcset` in
+const LAZY_SRCSET_ATTRS: &[&str] = &["data-srcset", "data-lazy-srcset"];
+
+/// Parse `html` and extract the images it shows
+pub fn extract_images(html: &str, url: &str) -> Vec<ImageMetadata> {
+    ImageMetadataExtractor::extract(&Html::parse_document(html), url)
+}
+
+/// Extracts [`ImageMetadata`] from page markup
+pub struct ImageMetadataExtractor;
+
+impl ImageMetadataExtractor {
+    /// Extract images from a parsed document in document order, resolving
+    /// relative URLs against `url`; an image repeated with the same URL is
+    /// listed once
+    pub fn extract(document: &Html, url: &str) -> Vec<ImageMetadata> {
+        let base = Url::parse(url).ok();
+        let Ok(selector) = Selector::parse("img") else {
+            return Vec::new();
+        };
+
+        let mut images: Vec<ImageMetadata> = Vec::new();
+        for element in document.select(&selector) {
+            let Some(image) = Self::image(element, base.as_ref()) else {
+                continue;
+            };
+            match images.iter_mut().find(|i| i.url == image.url) {
+                Some(existing) => {
+                    existing.alt = existing.alt.take().or(image.alt);
+                    existing.caption = existing.caption.take().or(image.caption);
+                }
+                None => images.push(image),
+            }
+        }
+        images
+    }
+
+    fn image(element: ElementRef, base: Option<&Url>) -> Option<ImageMetadata> {
+        let value = element.value();
+        let width = value.attr("width").and_then(parse_dimension);
+        let height = value.attr("height").and_then(parse_dimension);
+        if matches!((width, height), (Some(w), Some(h)) if w <= 1 && h <= 1) {
+            return None;
+        }
+
+        // `<picture>` sources first, as browsers pick from them before the
+        // `<img>` itself
+        let mut sources = Vec::new();
+        if let Some(picture) = element
+            .parent()
+            .and_then(ElementRef::wrap)
+            .filter(|parent| parent.value().name() == "picture")
+        {
+            for source in picture
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|child| child.value().name() == "source")
+            {
+                let source_value = source.value();
+                let media = source_value.attr("media").map(|m| m.trim().to_string());
+                let mime_type = source_value.attr("type").map(|t| t.trim().to_string());
+                let srcset = LAZY_SRCSET_ATTRS
+                    .iter()
+                    .chain(["srcset"].iter())
+                    .find_map(|name| source_value.attr(name));
+                for mut candidate in srcset.map(|s| parse_srcset(s, base)).unwrap_or_default() {
+                    candidate.media = media.clone().filter(|m| !m.is_empty());
+                    candidate.mime_type = mime_type.clone().filter(|t| !t.is_empty());
+                    sources.push(candidate);
+                }
+            }
+        }
+        let lazy_srcset = LAZY_SRCSET_ATTRS.iter().find_map(|name| value.attr(name));
+        if let Some(srcset) = lazy_srcset.or_else(|| value.attr("srcset")) {
+            sources.extend(parse_srcset(srcset, base));
+        }
+
+        let lazy_src = LAZY_SRC_ATTRS
+            .iter()
+            .filter_map(|name| value.attr(name))
+            .find_map(|src| resolve(base, src));
+        let (url, lazy) = match lazy_src {
+            Some(url) => (url, true),
+            None => match value.attr("src").and_then(|src| resolve(base, src)) {
+                Some(url) => (url, false),
+                None => (
+                    largest(&sources)?.url.clone(),
+                    lazy_srcset.is_some() || value.attr("loading") == Some("lazy"),
+                ),
+            },
+        };
+
+        let mut image = ImageMetadata::new(url);
+        image.sources = sources;
+        image.alt = attr_text(value.attr("alt"));
+        image.title = attr_text(value.attr("title"));
+        image.caption = Self::caption(element);
+        image.width = width;
+        image.height = height;
+        image.lazy = lazy;
+        Some(image)
+    }
+
+    /// `<figcaption>` of the `<figure>` holding `element`
+    fn caption(element: ElementRef) -> Option<String> {
+        let figure = element
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .find(|ancestor| ancestor.value().name() == "figure")?;
+        let selector = Selector::parse("figcaption").ok()?;
+        let caption = figure.select(&selector).next()?;
+        attr_text(Some(&caption.text().collect::<String>()))
+    }
+}
+
+/// Candidates of a `srcset` ("a.jpg 480w, b.jpg 2x"); URLs may contain
+/// commas, so candidates are split at whitespace first
+fn parse_srcset(srcset: &str, base: Option<&Url>) -> Vec<ImageSource> {
+    let mut sources = Vec::new();
+    let mut rest = srcset.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+    while !rest.is_empty() {
+        let url_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (raw_url, after) = rest.split_at(url_end);
+        // A URL directly followed by a comma has no descriptor
+        let (raw_url, descriptor, after) = match raw_url.strip_suffix(',') {
+            Some(url) => (url.trim_end_matches(','), "", after),
+            None => {
+                let descriptor_end = after.find(',').unwrap_or(after.len());
+                (
+                    raw_url,
+                    after[..descriptor_end].trim(),
+                    &after[descriptor_end..],
+                )
+            }
+        };
+        if let Some(url) = resolve(base, raw_url) {
+            let mut source = ImageSource {
+                url,
+                width: None,
+                density: None,
+                media: None,
+                mime_type: None,
+            };
+            if let Some(w) = descriptor.strip_suffix('w') {
+                source.width = w.trim().parse().ok();
+            } else if let Some(x) = descriptor.strip_suffix('x') {
+                source.density = x.trim().parse().ok();
+            }
+            sources.push(source);
+        }
+        rest = after.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+    }
+    sources
+}
+
+/// Candidate with the widest width descriptor, else the highest density
+fn largest(sources: &[ImageSource]) -> Option<&ImageSource> {
+    sources.iter().max_by(|a, b| {
+        let key = |s: &ImageSource| (s.width.unwrap_or(0), s.density.unwrap_or(1.0));
+        let (aw, ad) = key(a);
+        let (bw, bd) = key(b);
+        aw.cmp(&bw).then(ad.total_cmp(&bd))
+    })
+}
+
+/// Pixels of a `width`/`height` attribute ("640", "640px")
+fn parse_dimension(value: &str) -> Option<u32> {
+    value.trim().trim_end_matches("px").trim().parse().ok()
+}
+
+fn attr_text(value: Option<&str>) -> Option<String> {
+    let text = value?.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+fn resolve(base: Option<&Url>, raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if raw.is_empty() || raw.starts_with("data:") || raw.starts_with("blob:") {
+        return None;
+    }
+    let url = match base {
+        Some(base) => base.join(raw).ok()?,
+        None => Url::parse(raw).ok()?,
+    };
+    matches!(url.scheme(), "http" | "https").then(|| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_picture_sources_and_srcset() {
+        let html = r#"<picture>
+            <source type="image/avif" srcset="/img/hero.avif 1x, /img/hero@2x.avif 2x">
+            <source media="(max-width: 600px)" srcset="https://cdn.example.com/w_600,q_80/hero.jpg">
+            <img src="/img/hero.jpg" srcset="/img/hero-800.jpg 800w,/img/hero-1600.jpg 1600w"
+                 alt="  Hero   image " title="Hero" width="800px" height="400">
+        </picture>"#;
+
+        let images = extract_images(html, "https://example.com/page");
+        assert_eq!(images.len(), 1);
+        let hero = &images[0];
+        assert_eq!(hero.url, "https://example.com/img/hero.jpg");
+        assert!(!hero.lazy);
+        assert_eq!(hero.alt.as_deref(), Some("Hero image"));
+        assert_eq!((hero.width, hero.height), (Some(800), Some(400)));
+
+        assert_eq!(hero.sources.len(), 5);
+        assert_eq!(hero.sources[0].mime_type.as_deref(), Some("image/avif"));
+        assert_eq!(hero.sources[1].density, Some(2.0));
+        assert_eq!(
+            hero.sources[2].url,
+            "https://cdn.example.com/w_600,q_80/hero.jpg"
+        );
+        assert_eq!(hero.sources[2].media.as_deref(), Some("(max-width: 600px)"));
+        assert_eq!(hero.sources[4].width, Some(1600));
+    }
+
+    #[test]
+    fn test_lazy_sources_and_fallback_to_largest_candidate() {
+        let html = r#"<body>
+            <img class="lazy" src="/static/blank.gif" data-lazy-src="/a.jpg">
+            <img loading="lazy" srcset="/b-small.jpg 320w, /b-large.jpg 960w">
+            <img src="/a.jpg" alt="Same image again">
+            <img src="https://tracker.example.com/pixel.gif" width="1" height="1">
+            <img src="data:image/png;base64,AAAA">
+        </body>"#;
+
+        let images = extract_images(html, "https://example.com/");
+        assert_eq!(images.len(), 2);
+        assert_eq!(images[0].url, "https://example.com/a.jpg");
+        assert!(images[0].lazy);
+        assert_eq!(images[0].alt.as_deref(), Some("Same image again"));
+        assert_eq!(images[1].url, "https://example.com/b-large.jpg");
+        assert!(images[1].lazy);
+        assert!(images[1].caption.is_none());
+    }
+}
@@ -7,9 +7,12 @@
 //! ## Features
 //!
 //! - **CSS Extraction**: Extract content using CSS selectors with JSON mapping
+//! - **XPath**: XPath 1.0 selectors with namespace support, usable wherever CSS selectors are
 //! - **Regex Extraction**: Pattern-based content extraction with configurable rules
 //! - **DOM Utils**: Utilities for DOM traversal and manipulation
 //! - **Table Extraction**: Interface for extracting structured data from HTML tables
+//! - **Media Metadata**: Video and audio duration, thumbnails, stream manifests and captions from page markup
+//! - **Boilerplate Removal**: Trainable text block classifier dropping navigation, footers and related links
 //! - **Chunking**: Content chunking interface for processing large documents
 //! - **Parallel Extraction**: High-performance parallel batch processing with progress tracking
 //!
@@ -52,18 +55,41 @@ pub mod extraction_strategies;
 #[cfg(feature = "wasm-extractor")]
 pub mod wasm_extraction;
 
+// Fuel accounting shared by WASM extractions
+pub mod wasm_fuel;
+
+// Cross-version conformance harness for the extractor component
+#[cfg(feature = "wasm-extractor")]
+pub mod wasm_conformance;
+
+// User-supplied extractor components, selected by name
+#[cfg(feature = "wasm-extractor")]
+pub mod wasm_plugins;
+
 // Unified extractor with three-tier fallback
 pub mod unified_extractor;
 
 // pub mod spider;  // Temporarily disabled due to compilation errors
 pub mod chunking;
 pub mod enhanced_extractor;
+pub mod feed;
 pub mod table_extraction;
 pub mod tables;
 
 // P1-C2: HTML parser and extraction strategies moved from riptide-core
+pub mod boilerplate;
+pub mod clean_html;
+pub mod diff;
+pub mod embedded_markup;
 pub mod html_parser;
+pub mod image_metadata;
+pub mod markdown;
+pub mod media_metadata;
+pub mod nlp;
+pub mod references;
 pub mod strategies;
+pub mod structured_data;
+pub mod xpath;
 
 // P2-F1 Day 3: WASM validation moved from riptide-core (only with wasm-extractor feature)
 #[cfg(feature = "wasm-extractor")]
@@ -96,6 +122,8 @@ pub use extraction_strategies::WasmExtractor as StrategyWasmExtractor;
 pub use wasm_extraction::{
     CmExtractor, ExtractorConfig, HostExtractionMode, WasmExtractor, WasmResourceTracker,
 };
+#[cfg(feature = "wasm-extractor")]
+pub use wasm_plugins::{ExtractorPlugin, ExtractorPluginInfo, ExtractorPluginRegistry};
 
 // Always export unified extractor and native extractor
 pub use unified_extractor::{NativeExtractor, UnifiedExtractor};
@@ -108,7 +136,19 @@ pub use regex_extraction::{default_patterns, extract as regex_extract, RegexExtr
 pub use riptide_types::ExtractedDoc;
 
 // Re-export HTML parser types (moved from riptide-core)
+pub use boilerplate::{BoilerplateClassifier, BoilerplateTweaks};
+pub use clean_html::{html_to_clean_html, CleanHtmlConverter};
+pub use diff::{diff_html, ExtractionDiff};
+pub use embedded_markup::{math_to_latex, rewrite_html as rewrite_embedded_markup, EmbeddedMarkup};
+pub use feed::{detect_feed, discover_feeds, parse_feed, FeedDiscovery};
 pub use html_parser::{Link, Media, MediaType, Metadata};
+pub use image_metadata::{extract_images, ImageMetadataExtractor};
+pub use markdown::{html_to_markdown, MarkdownConverter, MarkdownFlavor};
+pub use media_metadata::{extract_media_metadata, MediaMetadataExtractor};
+pub use nlp::RuleBasedAnnotator;
+pub use references::{extract_references, ReferencesExtractor};
+pub use structured_data::{extract_structured_data, StructuredDataExtractor};
+pub use xpath::{XPath, XPathError};
 
 // Enhanced link extraction with context and classification
 pub mod enhanced_link_extraction;
@@ -127,7 +167,10 @@ pub use strategies::{
 };
 
 // Re-export confidence (Phase 2D)
-pub use composition::{CompositionMode, StrategyComposer};
+pub use composition::{
+    CompositionMode, DisagreementReport, FieldMergeConfig, FieldMergePolicy, StrategyComposer,
+};
+pub use confidence::calibration::{CalibrationMethod, ConfidenceCalibrator};
 pub use confidence::{AggregationStrategy, ConfidenceScore, ConfidenceScorer};
 pub use confidence_integration::{CssConfidenceScorer, WasmConfidenceScorer};
 
@@ -150,7 +193,8 @@ pub use confidence_integration::{CssConfidenceScorer, WasmConfidenceScorer};
 // };
 // Re-export chunking functionality
 pub use chunking::{
-    create_strategy, utils as chunking_utils, Chunk, ChunkMetadata, ChunkingConfig,
+    create_strategy, create_strategy_with_embedder, semantic::SentenceEmbedder,
+    utils as chunking_utils, Chunk, ChunkMetadata, ChunkingConfig,
     ChunkingMode as ChunkingStrategyMode, ChunkingStrategy,
 };
 
@@ -171,15 +215,15 @@ pub use tables::{
 // Re-export schema functionality
 pub mod schema;
 pub use schema::{
-    ExtractionSchema, FieldSchema, SchemaAnalysis, SchemaComparator, SchemaExtractor,
-    SchemaGenerator, SchemaLearnRequest, SchemaLearnResponse, SchemaMetadata, SchemaRegistry,
-    SchemaTestRequest, SchemaTestResponse, SchemaValidator, SelectorRule, TestResult, TestSummary,
-    ValidationRules,
+    ExtractionSchema, FieldSchema, SchemaAnalysis, SchemaComparator, SchemaExample,
+    SchemaExampleRequest, SchemaExampleResponse, SchemaExtractor, SchemaGenerator,
+    SchemaLearnRequest, SchemaLearnResponse, SchemaMetadata, SchemaRegistry, SchemaTestRequest,
+    SchemaTestResponse, SchemaValidator, SelectorRule, TestResult, TestSummary, ValidationRules,
 };
 
 // Native HTML parser module (for headless-rendered content)
 pub mod native_parser;
-pub use native_parser::{NativeHtmlParser, ParserConfig};
+pub use native_parser::{assess_quality, NativeHtmlParser, ParserConfig, StreamingHtmlParser};
 
 // Parallel extraction for batch processing
 pub mod parallel;
@@ -202,3 +246,6 @@ pub struct RegexPattern {
     pub field: String,
     pub required: bool,
 }
+
+/// Extraction engine version recorded in document provenance
+pub const EXTRACTOR_VERSION: &str = env!("CARGO_PKG_VERSION");
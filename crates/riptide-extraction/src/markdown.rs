@@ -0,0 +1,826 @@
+//! HTML to Markdown conversion
+//!
+//! [`MarkdownConverter`] renders HTML as Markdown: headings, paragraphs,
+//! emphasis, links and images, nested lists, block quotes, fenced code blocks
+//! and tables. [`MarkdownFlavor::Gfm`] adds the GitHub extensions (pipe
+//! tables, strikethrough and task lists); under [`MarkdownFlavor::CommonMark`]
+//! tables become raw HTML blocks, which CommonMark passes through. Those are
+//! rebuilt from the table's rows with the cell text escaped, so no markup or
+//! attribute of the page reaches the output.
+//!
+//! Scripts, styles, navigation and hidden elements are dropped. The WASM
+//! extractor component carries a `tl`-based port of the same rules, so both
+//! extraction paths produce the same Markdown for the same page.
+
+use crate::clean_html::escape;
+use scraper::node::Node;
+use scraper::{ElementRef, Html};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use url::Url;
+
+/// Elements dropped together with their content
+const SKIPPED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "head", "iframe", "object", "embed", "svg",
+    "canvas", "nav", "button", "select", "textarea",
+];
+
+/// Elements rendered as blocks of their own
+const BLOCK_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "center",
+    "dd",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "li",
+    "main",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "ul",
+];
+
+/// Stands in for `<br>` until a paragraph is assembled
+const LINE_BREAK: char = '\0';
+
+/// Markdown dialect produced by [`MarkdownConverter`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MarkdownFlavor {
+    /// CommonMark; tables are emitted as raw HTML
+    CommonMark,
+    /// GitHub Flavored Markdown: pipe tables, strikethrough and task lists
+    #[default]
+    Gfm,
+}
+
+impl MarkdownFlavor {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MarkdownFlavor::CommonMark => "commonmark",
+            MarkdownFlavor::Gfm => "gfm",
+        }
+    }
+}
+
+impl fmt::Display for MarkdownFlavor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MarkdownFlavor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "commonmark" | "common_mark" | "cm" => Ok(MarkdownFlavor::CommonMark),
+            "gfm" | "github" => Ok(MarkdownFlavor::Gfm),
+            other => Err(format!(
+                "Unknown markdown flavor '{}' (expected commonmark or gfm)",
+                other
+            )),
+        }
+    }
+}
+
+/// Convert an HTML fragment or document to Markdown
+pub fn html_to_markdown(html: &str, flavor: MarkdownFlavor) -> String {
+    MarkdownConverter::new(flavor).convert(html)
+}
+
+/// HTML to Markdown converter
+#[derive(Debug, Clone, Default)]
+pub struct MarkdownConverter {
+    flavor: MarkdownFlavor,
+    base_url: Option<Url>,
+}
+
+/// A rendered block; lists nest tightly inside list items
+struct Block {
+    text: String,
+    list: bool,
+}
+
+impl MarkdownConverter {
+    pub fn new(flavor: MarkdownFlavor) -> Self {
+        Self {
+            flavor,
+            base_url: None,
+        }
+    }
+
+    /// Resolve relative link and image URLs against `base_url`
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = Url::parse(base_url).ok();
+        self
+    }
+
+    pub fn flavor(&self) -> MarkdownFlavor {
+        self.flavor
+    }
+
+    /// Convert an HTML fragment or document
+    pub fn convert(&self, html: &str) -> String {
+        let document = Html::parse_document(html);
+        self.convert_element(document.root_element())
+    }
+
+    /// Convert `element` and its descendants
+    pub fn convert_element(&self, element: ElementRef<'_>) -> String {
+        if is_skipped(element) {
+            return String::new();
+        }
+        let blocks = if BLOCK_TAGS.contains(&element.value().name()) {
+            self.block(element)
+        } else {
+            paragraph(&self.inline(element)).into_iter().collect()
+        };
+        join_blocks(blocks)
+    }
+
+    fn blocks(&self, element: ElementRef<'_>) -> Vec<Block> {
+        let mut blocks = Vec::new();
+        let mut inline = String::new();
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => inline.push_str(&self.text(text)),
+                Node::Element(_) => {
+                    let Some(child) = ElementRef::wrap(child) else {
+                        continue;
+                    };
+                    if is_skipped(child) {
+                        continue;
+                    }
+                    if BLOCK_TAGS.contains(&child.value().name()) {
+                        blocks.extend(paragraph(&inline));
+                        inline.clear();
+                        blocks.extend(self.block(child));
+                    } else {
+                        inline.push_str(&self.inline(child));
+                    }
+                }
+                _ => {}
+            }
+        }
+        blocks.extend(paragraph(&inline));
+        blocks
+    }
+
+    fn block(&self, element: ElementRef<'_>) -> Vec<Block> {
+        let tag = element.value().name();
+        let text = match tag {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let content = clean_inline(&self.inline_children(element), " ");
+                if content.is_empty() {
+                    return Vec::new();
+                }
+                let level = tag[1..].parse::<usize>().unwrap_or(1);
+                format!("{} {}", "#".repeat(level), content)
+            }
+            "p" | "dt" | "figcaption" | "summary" => {
+                return paragraph(&self.inline_children(element))
+                    .into_iter()
+                    .collect();
+            }
+            "hr" => "---".to_string(),
+            "pre" => self.code_block(element),
+            "blockquote" => {
+                let inner = join_blocks(self.blocks(element));
+                inner
+                    .lines()
+                    .map(|line| {
+                        if line.is_empty() {
+                            ">".to_string()
+                        } else {
+                            format!("> {}", line)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            "ul" | "ol" => return self.list(element).into_iter().collect(),
+            "table" => self.table(element),
+            _ => return self.blocks(element),
+        };
+        if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![Block { text, list: false }]
+        }
+    }
+
+    fn inline(&self, element: ElementRef<'_>) -> String {
+        match element.value().name() {
+            "br" => LINE_BREAK.to_string(),
+            "strong" | "b" => wrap(&self.inline_children(element), "**"),
+            "em" | "i" | "cite" | "dfn" => wrap(&self.inline_children(element), "*"),
+            "del" | "s" | "strike" if self.flavor == MarkdownFlavor::Gfm => {
+                wrap(&self.inline_children(element), "~~")
+            }
+            "code" | "kbd" | "samp" | "tt" => code_span(&element.text().collect::<String>()),
+            "a" => self.link(element),
+            "img" => self.image(element),
+            "input" => self.checkbox(element),
+            _ => self.inline_children(element),
+        }
+    }
+
+    fn inline_children(&self, element: ElementRef<'_>) -> String {
+        let mut out = String::new();
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => out.push_str(&self.text(text)),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child).filter(|c| !is_skipped(*c)) {
+                        // Blocks inside inline content are flattened into it
+                        if BLOCK_TAGS.contains(&child.value().name()) {
+                            out.push(' ');
+                            out.push_str(&self.inline(child));
+                            out.push(' ');
+                        } else {
+                            out.push_str(&self.inline(child));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        out
+    }
+
+    /// Collapse whitespace like a browser and escape Markdown syntax
+    fn text(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut space = false;
+        for c in text.chars() {
+            if c.is_whitespace() {
+                space = true;
+                continue;
+            }
+            if space {
+                out.push(' ');
+                space = false;
+            }
+            if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '<')
+                || (c == '~' && self.flavor == MarkdownFlavor::Gfm)
+            {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+        if space {
+            out.push(' ');
+        }
+        out
+    }
+
+    fn link(&self, element: ElementRef<'_>) -> String {
+        let text = self.inline_children(element);
+        let href = element.value().attr("href").map(str::trim).unwrap_or("");
+        if href.is_empty() || href.to_ascii_lowercase().starts_with("javascript:") {
+            return text;
+        }
+        let href = self.resolve(href);
+        if text.trim().is_empty() {
+            return if href.starts_with("http://") || href.starts_with("https://") {
+                format!("<{}>", href)
+            } else {
+                text
+            };
+        }
+        let title = element
+            .value()
+            .attr("title")
+            .map(|title| format!(" \"{}\"", title.trim().replace('"', "\\\"")))
+            .unwrap_or_default();
+        let link = format!(
+            "[{}]({}{})",
+            clean_inline(&text, " "),
+            destination(&href),
+            title
+        );
+        surround_like(&text, link)
+    }
+
+    fn image(&self, element: ElementRef<'_>) -> String {
+        let attr = |name: &str| element.value().attr(name).map(str::trim);
+        let src = attr("src")
+            .filter(|src| !src.is_empty())
+            .or_else(|| attr("data-src"))
+            .unwrap_or("");
+        // Inline data URIs would bloat the document with base64
+        if src.is_empty() || src.starts_with("data:") {
+            return String::new();
+        }
+        let alt = self.text(attr("alt").unwrap_or(""));
+        let title = attr("title")
+            .filter(|title| !title.is_empty())
+            .map(|title| format!(" \"{}\"", title.replace('"', "\\\"")))
+            .unwrap_or_default();
+        format!(
+            "![{}]({}{})",
+            alt.trim(),
+            destination(&self.resolve(src)),
+            title
+        )
+    }
+
+    /// Task list marker for a checkbox at the start of a list item
+    fn checkbox(&self, element: ElementRef<'_>) -> String {
+        let in_list_item = element
+            .parent()
+            .and_then(ElementRef::wrap)
+            .is_some_and(|parent| parent.value().name() == "li");
+        let is_checkbox = element
+            .value()
+            .attr("type")
+            .is_some_and(|kind| kind.eq_ignore_ascii_case("checkbox"));
+        if self.flavor != MarkdownFlavor::Gfm || !in_list_item || !is_checkbox {
+            return String::new();
+        }
+        if element.value().attr("checked").is_some() {
+            "[x] ".to_string()
+        } else {
+            "[ ] ".to_string()
+        }
+    }
+
+    fn code_block(&self, element: ElementRef<'_>) -> String {
+        let code: String = element.text().collect();
+        let code = code.trim_end();
+        if code.trim().is_empty() {
+            return String::new();
+        }
+        let language = std::iter::once(element)
+            .chain(
+                element
+                    .children()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|child| child.value().name() == "code"),
+            )
+            .find_map(|e| {
+                e.value().classes().find_map(|class| {
+                    class
+                        .strip_prefix("language-")
+                        .or_else(|| class.strip_prefix("lang-"))
+                })
+            })
+            .unwrap_or("");
+        let fence = "`".repeat(longest_run(code, '`').max(2) + 1);
+        format!("{}{}\n{}\n{}", fence, language, code, fence)
+    }
+
+    fn list(&self, element: ElementRef<'_>) -> Option<Block> {
+        let ordered = element.value().name() == "ol";
+        let mut number = element
+            .value()
+            .attr("start")
+            .and_then(|start| start.trim().parse::<u64>().ok())
+            .unwrap_or(1);
+        let mut items: Vec<(String, String)> = Vec::new();
+        for child in element.children().filter_map(ElementRef::wrap) {
+            match child.value().name() {
+                "li" => {
+                    let marker = if ordered {
+                        number += 1;
+                        format!("{}.", number - 1)
+                    } else {
+                        "-".to_string()
+                    };
+                    items.push((marker, join_list_item(self.blocks(child))));
+                }
+                // A list directly inside a list belongs to the previous item
+                "ul" | "ol" => {
+                    if let (Some(nested), Some((_, content))) = (self.list(child), items.last_mut())
+                    {
+                        content.push('\n');
+                        content.push_str(&nested.text);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if items.is_empty() {
+            return None;
+        }
+
+        let text = items
+            .iter()
+            .map(|(marker, content)| {
+                let indent = " ".repeat(marker.len() + 1);
+                let mut lines = content.lines();
+                let mut item = format!("{} {}", marker, lines.next().unwrap_or(""))
+                    .trim_end()
+                    .to_string();
+                for line in lines {
+                    item.push('\n');
+                    if !line.is_empty() {
+                        item.push_str(&indent);
+                        item.push_str(line);
+                    }
+                }
+                item
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some(Block { text, list: true })
+    }
+
+    fn table(&self, element: ElementRef<'_>) -> String {
+        if self.flavor == MarkdownFlavor::CommonMark {
+            return html_table(element);
+        }
+
+        let mut caption = String::new();
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        for child in element.children().filter_map(ElementRef::wrap) {
+            match child.value().name() {
+                "caption" => caption = clean_inline(&self.inline_children(child), " "),
+                "thead" | "tbody" | "tfoot" => {
+                    rows.extend(table_rows(child).map(|row| self.table_row(row)))
+                }
+                "tr" => rows.push(self.table_row(child)),
+                _ => {}
+            }
+        }
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if width == 0 {
+            return caption;
+        }
+
+        let line = |cells: &[String]| {
+            let cells: Vec<&str> = (0..width)
+                .map(|i| cells.get(i).map(String::as_str).unwrap_or(""))
+                .collect();
+            format!("| {} |", cells.join(" | "))
+        };
+        let mut lines = vec![line(&rows[0]), line(&vec!["---".to_string(); width])];
+        lines.extend(rows[1..].iter().map(|row| line(row)));
+        let table = lines.join("\n");
+        if caption.is_empty() {
+            table
+        } else {
+            format!("{}\n\n{}", caption, table)
+        }
+    }
+
+    fn table_row(&self, row: ElementRef<'_>) -> Vec<String> {
+        let mut cells = Vec::new();
+        for cell in row
+            .children()
+            .filter_map(ElementRef::wrap)
+            .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+        {
+            cells.push(clean_inline(&self.inline_children(cell), " ").replace('|', "\\|"));
+            let span = cell
+                .value()
+                .attr("colspan")
+                .and_then(|span| span.trim().parse::<usize>().ok())
+                .unwrap_or(1);
+            cells.resize(cells.len() + span.clamp(1, 100) - 1, String::new());
+        }
+        cells
+    }
+
+    fn resolve(&self, url: &str) -> String {
+        self.base_url
+            .as_ref()
+            .and_then(|base| base.join(url).ok())
+            .map(|url| url.to_string())
+            .unwrap_or_else(|| url.to_string())
+    }
+}
+
+/// Rows of a table section
+fn table_rows(section: ElementRef<'_>) -> impl Iterator<Item = ElementRef<'_>> {
+    section
+        .children()
+        .filter_map(ElementRef::wrap)
+        .filter(|row| row.value().name() == "tr")
+}
+
+/// A table as a raw HTML block of its rows and escaped cell text, one
+/// line per row since a blank line would end the block early
+fn html_table(element: ElementRef<'_>) -> String {
+    let mut lines = vec!["<table>".to_string()];
+    let mut rows = Vec::new();
+    for child in element.children().filter_map(ElementRef::wrap) {
+        match child.value().name() {
+            "caption" => lines.push(format!("<caption>{}</caption>", cell_text(child))),
+            "thead" | "tbody" | "tfoot" => rows.extend(table_rows(child)),
+            "tr" => rows.push(child),
+            _ => {}
+        }
+    }
+    for row in rows {
+        let mut line = "<tr>".to_string();
+        for cell in row
+            .children()
+            .filter_map(ElementRef::wrap)
+            .filter(|cell| matches!(cell.value().name(), "td" | "th"))
+        {
+            let tag = cell.value().name();
+            line.push('<');
+            line.push_str(tag);
+            for name in ["colspan", "rowspan"] {
+                if let Some(span) = cell
+                    .value()
+                    .attr(name)
+                    .and_then(|span| span.trim().parse::<usize>().ok())
+                {
+                    line.push_str(&format!(" {}=\"{}\"", name, span.clamp(1, 100)));
+                }
+            }
+            line.push_str(&format!(">{}</{}>", cell_text(cell), tag));
+        }
+        line.push_str("</tr>");
+        lines.push(line);
+    }
+    lines.push("</table>".to_string());
+    lines.join("\n")
+}
+
+/// Visible text of a table cell, whitespace collapsed and HTML-escaped
+fn cell_text(cell: ElementRef<'_>) -> String {
+    let mut text = String::new();
+    for node in cell.descendants() {
+        if node
+            .ancestors()
+            .take_while(|ancestor| ancestor.id() != cell.id())
+            .filter_map(ElementRef::wrap)
+            .any(is_skipped)
+        {
+            continue;
+        }
+        if let Node::Text(chunk) = node.value() {
+            text.push_str(chunk);
+            text.push(' ');
+        }
+    }
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    escape(&text, false)
+}
+
+pub(crate) fn is_skipped(element: ElementRef<'_>) -> bool {
+    let value = element.value();
+    SKIPPED_TAGS.contains(&value.name())
+        || value.attr("hidden").is_some()
+        || value.attr("aria-hidden") == Some("true")
+        || value.attr("style").is_some_and(|style| {
+            style
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .collect::<String>()
+                .to_ascii_lowercase()
+                .contains("display:none")
+        })
+}
+
+/// Collapse the whitespace of assembled inline content, rendering `<br>` as `line_break`
+fn clean_inline(inline: &str, line_break: &str) -> String {
+    inline
+        .split(LINE_BREAK)
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(line_break)
+}
+
+/// Paragraph block, escaping a start that would read as other syntax
+fn paragraph(inline: &str) -> Option<Block> {
+    let mut text = clean_inline(inline, "\\\n");
+    if text.is_empty() {
+        return None;
+    }
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    if text.starts_with(['#', '>', '-', '+', '=']) {
+        text.insert(0, '\\');
+    } else if digits > 0 && text[digits..].starts_with(['.', ')']) {
+        text.insert(digits, '\\');
+    }
+    Some(Block { text, list: false })
+}
+
+fn join_blocks(blocks: Vec<Block>) -> String {
+    blocks
+        .into_iter()
+        .map(|block| block.text)
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Join the blocks of a list item, keeping nested lists tight
+fn join_list_item(blocks: Vec<Block>) -> String {
+    let mut out = String::new();
+    for (i, block) in blocks.into_iter().enumerate() {
+        if i > 0 {
+            out.push_str(if block.list { "\n" } else { "\n\n" });
+        }
+        out.push_str(&block.text);
+    }
+    out
+}
+
+/// Wrap inline content in emphasis markers, keeping its outer whitespace outside
+fn wrap(content: &str, marker: &str) -> String {
+    let trimmed = content.trim();
+    if trimmed.is_empty() {
+        return content.to_string();
+    }
+    surround_like(content, format!("{}{}{}", marker, trimmed, marker))
+}
+
+/// `rendered` with the leading and trailing whitespace of `content`
+fn surround_like(content: &str, rendered: String) -> String {
+    let lead = if content.starts_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    let trail = if content.ends_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    format!("{}{}{}", lead, rendered, trail)
+}
+
+fn code_span(code: &str) -> String {
+    let code = code.replace(['\n', '\r'], " ");
+    if code.trim().is_empty() {
+        return String::new();
+    }
+    let ticks = "`".repeat(longest_run(&code, '`') + 1);
+    let pad = if code.starts_with('`') || code.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+    format!("{}{}{}{}{}", ticks, pad, code, pad, ticks)
+}
+
+/// Link destination, in angle brackets when it contains spaces or parentheses
+fn destination(url: &str) -> String {
+    if url.contains(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+        format!("<{}>", url.replace('<', "%3C").replace('>', "%3E"))
+    } else {
+        url.to_string()
+    }
+}
+
+fn longest_run(text: &str, target: char) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in text.chars() {
+        if c == target {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gfm(html: &str) -> String {
+        html_to_markdown(html, MarkdownFlavor::Gfm)
+    }
+
+    #[test]
+    fn test_headings_paragraphs_and_emphasis() {
+        let md = gfm(
+            "<h1>Title</h1><p>Some <strong>bold</strong> and <em>italic</em>\n   text.</p>\
+             <h3>Sub <code>x_y</code></h3><p>line one<br>line two</p>",
+        );
+        assert_eq!(
+            md,
+            "# Title\n\nSome **bold** and *italic* text.\n\n### Sub `x_y`\n\nline one\\\nline two"
+        );
+    }
+
+    #[test]
+    fn test_nested_lists() {
+        let md = gfm("<ul><li>one<ul><li>inner</li></ul></li><li>two</li></ul>\
+             <ol start=\"9\"><li>nine</li><li>ten<p>more</p></li></ol>");
+        assert_eq!(
+            md,
+            "- one\n  - inner\n- two\n\n9. nine\n10. ten\n\n    more"
+        );
+    }
+
+    #[test]
+    fn test_code_block_keeps_whitespace_and_language() {
+        let md = gfm("<pre><code class=\"language-rust\">fn main() {\n    let s = \"```\";\n}\n</code></pre>");
+        assert_eq!(md, "````rust\nfn main() {\n    let s = \"```\";\n}\n````");
+    }
+
+    #[test]
+    fn test_links_and_images_resolve_against_base_url() {
+        let converter = MarkdownConverter::new(MarkdownFlavor::Gfm)
+            .with_base_url("https://example.com/blog/post");
+        let md = converter.convert(
+            "<p>See <a href=\"../about\" title=\"About us\">the team</a>.\
+             <img src=\"/logo.png\" alt=\"Logo\"><img src=\"data:image/png;base64,AAAA\">\
+             <a href=\"javascript:void(0)\">noop</a></p>",
+        );
+        assert_eq!(
+            md,
+            "See [the team](https://example.com/about \"About us\").\
+             ![Logo](https://example.com/logo.png)noop"
+        );
+    }
+
+    #[test]
+    fn test_tables_by_flavor() {
+        let html = "<table><thead><tr><th>Name</th><th>Note</th></tr></thead>\
+                    <tbody><tr><td>a|b</td><td colspan=\"2\">wide</td></tr></tbody></table>";
+        assert_eq!(
+            gfm(html),
+            "| Name | Note |  |\n| --- | --- | --- |\n| a\\|b | wide |  |"
+        );
+
+        let md = html_to_markdown(html, MarkdownFlavor::CommonMark);
+        assert_eq!(
+            md,
+            "<table>\n<tr><th>Name</th><th>Note</th></tr>\n\
+             <tr><td>a|b</td><td colspan=\"2\">wide</td></tr>\n</table>"
+        );
+
+        // Page markup never reaches the raw HTML block
+        let md = html_to_markdown(
+            "<table><tr><td onclick=\"steal()\"><b>1 &lt; 2</b>\
+             <script>alert(1)</script><img src=x onerror=alert(1)></td></tr></table>",
+            MarkdownFlavor::CommonMark,
+        );
+        assert_eq!(md, "<table>\n<tr><td>1 &lt; 2</td></tr>\n</table>");
+    }
+
+    #[test]
+    fn test_gfm_extensions() {
+        let html = "<ul><li><input type=\"checkbox\" checked> done</li>\
+                    <li><input type=\"checkbox\"> todo</li></ul><p><del>old</del> new</p>";
+        assert_eq!(gfm(html), "- [x] done\n- [ ] todo\n\n~~old~~ new");
+        assert_eq!(
+            html_to_markdown(html, MarkdownFlavor::CommonMark),
+            "- done\n- todo\n\nold new"
+        );
+    }
+
+    #[test]
+    fn test_skips_hidden_and_non_content_elements() {
+        let md = gfm("<div><script>alert(1)</script><nav>Menu</nav>\
+             <p hidden>secret</p><p style=\"display: none\">gone</p>\
+             <blockquote><p>quoted</p><p>twice</p></blockquote></div>");
+        assert_eq!(md, "> quoted\n>\n> twice");
+    }
+
+    #[test]
+    fn test_escapes_markdown_syntax_in_text() {
+        assert_eq!(
+            gfm("<p># not a heading, 2*3 [x]</p><p>1. not a list</p>"),
+            "\\# not a heading, 2\\*3 \\[x\\]\n\n1\\. not a list"
+        );
+    }
+
+    #[test]
+    fn test_flavor_parsing() {
+        assert_eq!("GFM".parse(), Ok(MarkdownFlavor::Gfm));
+        assert_eq!("commonmark".parse(), Ok(MarkdownFlavor::CommonMark));
+        assert!("markdown".parse::<MarkdownFlavor>().is_err());
+        assert_eq!(
+            serde_json::to_string(&MarkdownFlavor::CommonMark).unwrap(),
+            "\"commonmark\""
+        );
+    }
+}
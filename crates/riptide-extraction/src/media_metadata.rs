@@ -0,0 +1,631 @@
+//! Video and audio metadata extraction
+//!
+//! Media-heavy pages often carry little text but describe their players in
+//! detail. This module collects those descriptions into [`EmbeddedMedia`]
+//! without downloading any media:
+//!
+//! - `og:video` / `og:audio` and `video:*` Open Graph tags
+//! - `twitter:player` card tags
+//! - schema.org `VideoObject` / `AudioObject` in JSON-LD, Microdata or RDFa
+//! - `<video>` / `<audio>` elements with their `<source>`s and `<track>`s,
+//!   and `<iframe>`s of well-known players
+//! - HLS and DASH manifest URLs in inline player scripts
+//!
+//! Descriptions sharing a media, player or manifest URL are merged.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_extraction::media_metadata::{extract_media_metadata, MediaKind};
+//!
+//! let html = r#"<video poster="/poster.jpg">
+//!     <source src="/live/master.m3u8" type="application/x-mpegURL">
+//!     <track kind="captions" src="/captions/en.vtt" srclang="en">
+//! </video>"#;
+//!
+//! let media = extract_media_metadata(html, "https://example.com/watch");
+//! assert_eq!(media[0].kind, MediaKind::Video);
+//! assert_eq!(media[0].manifests[0].url, "https://example.com/live/master.m3u8");
+//! assert_eq!(media[0].captions[0].language.as_deref(), Some("en"));
+//! ```
+
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+use std::sync::OnceLock;
+use url::Url;
+
+use crate::structured_data::{parse_duration, StructuredDataExtractor};
+
+pub use riptide_types::media::{
+    CaptionTrack, EmbeddedMedia, MediaKind, MediaMetadataSource, StreamFormat, StreamManifest,
+};
+
+/// Player `<iframe>` hosts and path prefixes, with the kind of media they play
+const KNOWN_PLAYERS: &[(&str, &str, MediaKind)] = &[
+    ("www.youtube.com", "/embed/", MediaKind::Video),
+    ("youtube.com", "/embed/", MediaKind::Video),
+    ("www.youtube-nocookie.com", "/embed/", MediaKind::Video),
+    ("player.vimeo.com", "/video/", MediaKind::Video),
+    ("www.dailymotion.com", "/embed/", MediaKind::Video),
+    ("fast.wistia.net", "/embed/", MediaKind::Video),
+    ("player.twitch.tv", "/", MediaKind::Video),
+    ("w.soundcloud.com", "/player", MediaKind::Audio),
+    ("open.spotify.com", "/embed", MediaKind::Audio),
+];
+
+/// Parse `html` and extract the video and audio it embeds
+pub fn extract_media_metadata(html: &str, url: &str) -> Vec<EmbeddedMedia> {
+    MediaMetadataExtractor::extract(&Html::parse_document(html), url)
+}
+
+/// Extracts [`EmbeddedMedia`] from page markup
+pub struct MediaMetadataExtractor;
+
+impl MediaMetadataExtractor {
+    /// Extract embedded media from a parsed document, resolving relative
+    /// URLs against `url`
+    ///
+    /// Items come in order of their first description: Open Graph, Twitter
+    /// card, schema.org, then elements in document order.
+    pub fn extract(document: &Html, url: &str) -> Vec<EmbeddedMedia> {
+        let base = Url::parse(url).ok();
+        let base = base.as_ref();
+
+        let mut found = Self::open_graph(document, base);
+        found.extend(Self::twitter_card(document, base));
+        found.extend(StructuredDataExtractor::media_objects(document, url));
+        found.extend(Self::elements(document, base));
+
+        let mut media: Vec<EmbeddedMedia> = Vec::new();
+        for item in found {
+            match media
+                .iter_mut()
+                .find(|m| m.kind == item.kind && m.same_item(&item))
+            {
+                Some(existing) => existing.merge(item),
+                None => media.push(item),
+            }
+        }
+
+        Self::script_manifests(document, base, &mut media);
+        media
+    }
+
+    fn open_graph(document: &Html, base: Option<&Url>) -> Vec<EmbeddedMedia> {
+        let mut media: Vec<EmbeddedMedia> = Vec::new();
+        let (mut title, mut description, mut image) = (None, None, None);
+        let mut duration = None;
+        let mut release_date = None;
+
+        // Structured properties describe the latest item of their kind
+        let current =
+            |media: &[EmbeddedMedia], kind: MediaKind| media.iter().rposition(|m| m.kind == kind);
+
+        for (property, content) in meta_tags(document) {
+            match property.as_str() {
+                "og:title" => title = title.or(Some(content)),
+                "og:description" => description = description.or(Some(content)),
+                "og:image" | "og:image:url" => image = image.or(resolve(base, &content)),
+                "video:duration" => duration = duration.or(parse_duration(&content)),
+                "video:release_date" => release_date = release_date.or(Some(content)),
+                "og:video" | "og:video:url" | "og:audio" | "og:audio:url" => {
+                    let kind = og_kind(&property);
+                    let Some(url) = resolve(base, &content) else {
+                        continue;
+                    };
+                    // Sites repeat the bare tag as `:url`; a new URL starts
+                    // the next item
+                    let index = match current(&media, kind) {
+                        Some(i) if media[i].url.is_none() || media[i].url == Some(url.clone()) => i,
+                        _ => {
+                            media.push(EmbeddedMedia::new(kind, MediaMetadataSource::OpenGraph));
+                            media.len() - 1
+                        }
+                    };
+                    media[index].url = Some(url);
+                }
+                _ => {
+                    let Some((kind, field)) = property
+                        .strip_prefix("og:video:")
+                        .map(|field| (MediaKind::Video, field))
+                        .or_else(|| {
+                            property
+                                .strip_prefix("og:audio:")
+                                .map(|field| (MediaKind::Audio, field))
+                        })
+                    else {
+                        continue;
+                    };
+                    let index = match current(&media, kind) {
+                        Some(index) => index,
+                        None => {
+                            media.push(EmbeddedMedia::new(kind, MediaMetadataSource::OpenGraph));
+                            media.len() - 1
+                        }
+                    };
+                    let item = &mut media[index];
+                    match field {
+                        "secure_url" => {
+                            if let Some(url) = resolve(base, &content) {
+                                item.url = Some(url);
+                            }
+                        }
+                        "type" => item.mime_type = Some(content),
+                        "width" => item.width = content.trim().parse().ok(),
+                        "height" => item.height = content.trim().parse().ok(),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        for item in &mut media {
+            if let Some(url) = item.url.take() {
+                place_url(item, url);
+            }
+            item.title = title.clone();
+            item.description = description.clone();
+            item.thumbnail_url = image.clone();
+            if item.kind == MediaKind::Video {
+                item.duration_secs = duration;
+                item.upload_date = release_date.clone();
+            }
+        }
+        media.retain(is_described);
+        media
+    }
+
+    fn twitter_card(document: &Html, base: Option<&Url>) -> Vec<EmbeddedMedia> {
+        let mut item = EmbeddedMedia::new(MediaKind::Video, MediaMetadataSource::TwitterCard);
+        let mut stream = None;
+        for (property, content) in meta_tags(document) {
+            match property.as_str() {
+                "twitter:player" => item.embed_url = resolve(base, &content),
+                "twitter:player:stream" => stream = resolve(base, &content),
+                "twitter:player:stream:content_type" => item.mime_type = Some(content),
+                "twitter:player:width" => item.width = content.trim().parse().ok(),
+                "twitter:player:height" => item.height = content.trim().parse().ok(),
+                "twitter:title" => item.title = item.title.take().or(Some(content)),
+                "twitter:description" => {
+                    item.description = item.description.take().or(Some(content))
+                }
+                "twitter:image" | "twitter:image:src" => {
+                    item.thumbnail_url = item.thumbnail_url.take().or(resolve(base, &content))
+                }
+                _ => {}
+            }
+        }
+        if let Some(stream) = stream {
+            if item
+                .mime_type
+                .as_deref()
+                .is_some_and(|t| t.starts_with("audio/"))
+            {
+                item.kind = MediaKind::Audio;
+            }
+            place_url(&mut item, stream);
+        }
+
+        if is_described(&item) {
+            vec![item]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// `<video>`, `<audio>` and known player `<iframe>` elements
+    fn elements(document: &Html, base: Option<&Url>) -> Vec<EmbeddedMedia> {
+        let Ok(selector) = Selector::parse("video, audio, iframe[src]") else {
+            return Vec::new();
+        };
+
+        let mut media = Vec::new();
+        for element in document.select(&selector) {
+            let item = match element.value().name() {
+                "iframe" => Self::player_iframe(element, base),
+                "audio" => Some(Self::media_element(element, MediaKind::Audio, base)),
+                _ => Some(Self::media_element(element, MediaKind::Video, base)),
+            };
+            if let Some(item) = item.filter(is_described) {
+                media.push(item);
+            }
+        }
+        media
+    }
+
+    fn media_element(element: ElementRef, kind: MediaKind, base: Option<&Url>) -> EmbeddedMedia {
+        let mut item = EmbeddedMedia::new(kind, MediaMetadataSource::Element);
+        let attr = |name: &str| {
+            element
+                .value()
+                .attr(name)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+
+        item.title = attr("title")
+            .or_else(|| attr("aria-label"))
+            .map(String::from);
+        item.thumbnail_url = attr("poster").and_then(|poster| resolve(base, poster));
+        item.width = attr("width").and_then(|w| w.parse().ok());
+        item.height = attr("height").and_then(|h| h.parse().ok());
+
+        let mut sources: Vec<(String, Option<String>)> = Vec::new();
+        if let Some(url) = attr("src").and_then(|src| resolve(base, src)) {
+            sources.push((url, attr("type").map(String::from)));
+        }
+        for child in element.children().filter_map(ElementRef::wrap) {
+            let child_attr = |name: &str| child.value().attr(name).map(str::trim);
+            match child.value().name() {
+                "source" => {
+                    if let Some(url) = child_attr("src").and_then(|src| resolve(base, src)) {
+                        sources.push((url, child_attr("type").map(String::from)));
+                    }
+                }
+                "track" => {
+                    let track_kind = child_attr("kind")
+                        .unwrap_or("subtitles")
+                        .to_ascii_lowercase();
+                    if track_kind != "captions" && track_kind != "subtitles" {
+                        continue;
+                    }
+                    if let Some(url) = child_attr("src").and_then(|src| resolve(base, src)) {
+                        item.captions.push(CaptionTrack {
+                            url,
+                            language: child_attr("srclang")
+                                .filter(|l| !l.is_empty())
+                                .map(String::from),
+                            label: child_attr("label")
+                                .filter(|l| !l.is_empty())
+                                .map(String::from),
+                            kind: track_kind,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for (url, mime_type) in sources {
+            let format = mime_type
+                .as_deref()
+                .and_then(StreamFormat::from_media_type)
+                .or_else(|| StreamFormat::from_url(&url));
+            match format {
+                Some(format) => item.add_manifest(StreamManifest { url, format }),
+                // The first playable file stands for the item
+                None if item.url.is_none() => {
+                    item.url = Some(url);
+                    item.mime_type = mime_type;
+                }
+                None => {}
+            }
+        }
+        item
+    }
+
+    fn player_iframe(element: ElementRef, base: Option<&Url>) -> Option<EmbeddedMedia> {
+        let src = resolve(base, element.value().attr("src")?)?;
+        let parsed = Url::parse(&src).ok()?;
+        let host = parsed.host_str()?;
+        let (_, _, kind) = KNOWN_PLAYERS
+            .iter()
+            .find(|(player, path, _)| host == *player && parsed.path().starts_with(path))?;
+
+        let mut item = EmbeddedMedia::new(*kind, MediaMetadataSource::Element);
+        item.embed_url = Some(src);
+        item.title = element
+            .value()
+            .attr("title")
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(String::from);
+        item.width = element.value().attr("width").and_then(|w| w.parse().ok());
+        item.height = element.value().attr("height").and_then(|h| h.parse().ok());
+        Some(item)
+    }
+
+    /// Manifest URLs in inline scripts, as JavaScript players configure them
+    ///
+    /// A manifest already known is skipped. With exactly one video on the
+    /// page the manifest is taken to be its stream; otherwise it becomes an
+    /// item of its own.
+    fn script_manifests(document: &Html, base: Option<&Url>, media: &mut Vec<EmbeddedMedia>) {
+        let Ok(selector) = Selector::parse("script:not([src])") else {
+            return;
+        };
+
+        for script in document.select(&selector) {
+            let text = script.text().collect::<String>().replace("\\/", "/");
+            for found in manifest_pattern().find_iter(&text) {
+                let Some(url) = resolve(base, found.as_str()) else {
+                    continue;
+                };
+                let Some(format) = StreamFormat::from_url(&url) else {
+                    continue;
+                };
+                if media
+                    .iter()
+                    .any(|m| m.manifests.iter().any(|known| known.url == url))
+                {
+                    continue;
+                }
+
+                let mut videos = media.iter_mut().filter(|m| m.kind == MediaKind::Video);
+                let only_video = match (videos.next(), videos.next()) {
+                    (Some(video), None) => Some(video),
+                    _ => None,
+                };
+                match only_video {
+                    Some(video) => {
+                        if !video.sources.contains(&MediaMetadataSource::Script) {
+                            video.sources.push(MediaMetadataSource::Script);
+                        }
+                        video.add_manifest(StreamManifest { url, format });
+                    }
+                    None => {
+                        let mut item =
+                            EmbeddedMedia::new(MediaKind::Video, MediaMetadataSource::Script);
+                        item.add_manifest(StreamManifest { url, format });
+                        media.push(item);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `(property, content)` of every `<meta>` with a `property` or `name`,
+/// lowercased property first
+fn meta_tags(document: &Html) -> Vec<(String, String)> {
+    let Ok(selector) = Selector::parse("meta[content]") else {
+        return Vec::new();
+    };
+
+    document
+        .select(&selector)
+        .filter_map(|meta| {
+            let property = meta
+                .value()
+                .attr("property")
+                .or_else(|| meta.value().attr("name"))?;
+            let content = meta.value().attr("content")?.trim();
+            (!content.is_empty())
+                .then(|| (property.trim().to_ascii_lowercase(), content.to_string()))
+        })
+        .collect()
+}
+
+fn og_kind(property: &str) -> MediaKind {
+    if property.starts_with("og:audio") {
+        MediaKind::Audio
+    } else {
+        MediaKind::Video
+    }
+}
+
+/// File the `url` of a meta tag as manifest, player page or media file
+fn place_url(item: &mut EmbeddedMedia, url: String) {
+    let mime_type = item.mime_type.as_deref().unwrap_or_default();
+    let format = StreamFormat::from_media_type(mime_type).or_else(|| StreamFormat::from_url(&url));
+    if let Some(format) = format {
+        item.add_manifest(StreamManifest { url, format });
+    } else if mime_type.starts_with("text/html") || mime_type.contains("shockwave") {
+        item.embed_url = item.embed_url.take().or(Some(url));
+    } else {
+        item.url = Some(url);
+    }
+}
+
+fn is_described(item: &EmbeddedMedia) -> bool {
+    item.url.is_some() || item.embed_url.is_some() || !item.manifests.is_empty()
+}
+
+/// Absolute URL, leaving out `data:` and `blob:` URLs that point at nothing
+/// fetchable
+fn resolve(base: Option<&Url>, raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    if raw.is_empty() || raw.starts_with("data:") || raw.starts_with("blob:") {
+        return None;
+    }
+    let url = match base {
+        Some(base) => base.join(raw).ok()?,
+        None => Url::parse(raw).ok()?,
+    };
+    matches!(url.scheme(), "http" | "https").then(|| url.to_string())
+}
+
+fn manifest_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(r#"(?i)(?:https?:)?//[^\s"'<>\\]+?\.(?:m3u8|mpd)(?:\?[^\s"'<>\\]*)?"#)
+            .expect("valid manifest pattern")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://videos.example.com/watch/42";
+
+    #[test]
+    fn test_open_graph_and_schema_org_merge() {
+        let html = r#"<html><head>
+            <meta property="og:title" content="Launch day">
+            <meta property="og:image" content="/thumbs/42.jpg">
+            <meta property="og:video" content="http://cdn.example.com/42.mp4">
+            <meta property="og:video:secure_url" content="https://cdn.example.com/42.mp4">
+            <meta property="og:video:type" content="video/mp4">
+            <meta property="og:video:width" content="1280">
+            <meta property="og:video:height" content="720">
+            <meta property="video:duration" content="95">
+            <script type="application/ld+json">
+            {"@type": "NewsArticle", "headline": "Launch", "video": {
+              "@type": "VideoObject", "name": "Launch day (full)",
+              "contentUrl": "https://cdn.example.com/42.mp4",
+              "embedUrl": "/embed/42", "duration": "PT1M35S",
+              "uploadDate": "2024-05-01",
+              "caption": {"@type": "MediaObject", "contentUrl": "/captions/42.de.vtt",
+                          "inLanguage": "de"}}}
+            </script>
+        </head><body></body></html>"#;
+
+        let media = extract_media_metadata(html, URL);
+        assert_eq!(media.len(), 1);
+        let video = &media[0];
+        assert_eq!(video.kind, MediaKind::Video);
+        assert_eq!(
+            video.sources,
+            vec![
+                MediaMetadataSource::OpenGraph,
+                MediaMetadataSource::SchemaOrg
+            ]
+        );
+        assert_eq!(video.url.as_deref(), Some("https://cdn.example.com/42.mp4"));
+        assert_eq!(
+            video.embed_url.as_deref(),
+            Some("https://videos.example.com/embed/42")
+        );
+        assert_eq!(video.title.as_deref(), Some("Launch day"));
+        assert_eq!(video.duration_secs, Some(95.0));
+        assert_eq!((video.width, video.height), (Some(1280), Some(720)));
+        assert_eq!(
+            video.thumbnail_url.as_deref(),
+            Some("https://videos.example.com/thumbs/42.jpg")
+        );
+        assert_eq!(video.upload_date.as_deref(), Some("2024-05-01"));
+        assert_eq!(video.captions.len(), 1);
+        assert_eq!(video.captions[0].language.as_deref(), Some("de"));
+    }
+
+    #[test]
+    fn test_video_element_sources_tracks_and_manifests() {
+        let html = r#"<body>
+            <video poster="poster.jpg" title="Keynote" width="640">
+              <source src="/hls/keynote.m3u8" type="application/vnd.apple.mpegurl">
+              <source src="/dash/keynote.mpd">
+              <source src="/files/keynote.webm" type="video/webm">
+              <source src="/files/keynote.mp4" type="video/mp4">
+              <track kind="subtitles" src="/subs/en.vtt" srclang="en" label="English">
+              <track kind="chapters" src="/chapters.vtt">
+            </video>
+            <audio src="/podcast/ep1.mp3"></audio>
+        </body>"#;
+
+        let media = extract_media_metadata(html, URL);
+        assert_eq!(media.len(), 2);
+
+        let video = &media[0];
+        assert_eq!(video.sources, vec![MediaMetadataSource::Element]);
+        assert_eq!(video.title.as_deref(), Some("Keynote"));
+        assert_eq!(
+            video.url.as_deref(),
+            Some("https://videos.example.com/files/keynote.webm")
+        );
+        assert_eq!(video.mime_type.as_deref(), Some("video/webm"));
+        assert_eq!(
+            video.manifests,
+            vec![
+                StreamManifest {
+                    url: "https://videos.example.com/hls/keynote.m3u8".to_string(),
+                    format: StreamFormat::Hls,
+                },
+                StreamManifest {
+                    url: "https://videos.example.com/dash/keynote.mpd".to_string(),
+                    format: StreamFormat::Dash,
+                },
+            ]
+        );
+        assert_eq!(video.captions.len(), 1);
+        assert_eq!(video.captions[0].kind, "subtitles");
+        assert_eq!(video.captions[0].label.as_deref(), Some("English"));
+        assert_eq!(
+            video.thumbnail_url.as_deref(),
+            Some("https://videos.example.com/watch/poster.jpg")
+        );
+        assert_eq!(video.width, Some(640));
+
+        assert_eq!(media[1].kind, MediaKind::Audio);
+        assert_eq!(
+            media[1].url.as_deref(),
+            Some("https://videos.example.com/podcast/ep1.mp3")
+        );
+    }
+
+    #[test]
+    fn test_player_iframes_and_twitter_card() {
+        let html = r#"<html><head>
+            <meta name="twitter:card" content="player">
+            <meta name="twitter:player" content="https://www.youtube.com/embed/abc">
+            <meta name="twitter:player:width" content="1280">
+            <meta name="twitter:image" content="https://i.ytimg.com/vi/abc/hq.jpg">
+        </head><body>
+            <iframe src="https://www.youtube.com/embed/abc" title="Trailer"></iframe>
+            <iframe src="https://w.soundcloud.com/player/?url=track"></iframe>
+            <iframe src="https://ads.example.com/frame"></iframe>
+        </body></html>"#;
+
+        let media = extract_media_metadata(html, URL);
+        assert_eq!(media.len(), 2);
+        assert_eq!(
+            media[0].sources,
+            vec![
+                MediaMetadataSource::TwitterCard,
+                MediaMetadataSource::Element
+            ]
+        );
+        assert_eq!(media[0].title.as_deref(), Some("Trailer"));
+        assert_eq!(media[0].width, Some(1280));
+        assert_eq!(
+            media[0].thumbnail_url.as_deref(),
+            Some("https://i.ytimg.com/vi/abc/hq.jpg")
+        );
+        assert_eq!(media[1].kind, MediaKind::Audio);
+    }
+
+    #[test]
+    fn test_script_manifest_attaches_to_only_video() {
+        let html = r#"<body>
+            <video poster="/poster.jpg"></video>
+            <div id="player"></div>
+            <script>
+              player.setup({"file": "https:\/\/stream.example.com\/live\/index.m3u8?token=x"});
+              loadAd("https://ads.example.com/vast.xml");
+            </script>
+        </body>"#;
+
+        // The bare <video> has no URL yet, so the script manifest becomes
+        // its own item
+        let media = extract_media_metadata(html, URL);
+        assert_eq!(media.len(), 1);
+        assert_eq!(media[0].sources, vec![MediaMetadataSource::Script]);
+        assert_eq!(
+            media[0].manifests[0].url,
+            "https://stream.example.com/live/index.m3u8?token=x"
+        );
+
+        let html = r#"<head>
+            <meta property="og:video:url" content="https://cdn.example.com/clip.mp4">
+        </head><body>
+            <script>var hls = "//stream.example.com/vod/clip.m3u8";</script>
+        </body>"#;
+        let media = extract_media_metadata(html, URL);
+        assert_eq!(media.len(), 1);
+        assert_eq!(
+            media[0].sources,
+            vec![MediaMetadataSource::OpenGraph, MediaMetadataSource::Script]
+        );
+        assert_eq!(
+            media[0].manifests[0].url,
+            "https://stream.example.com/vod/clip.m3u8"
+        );
+    }
+
+    #[test]
+    fn test_page_without_media() {
+        let html = r#"<head><meta property="og:title" content="Text only"></head>
+            <body><p>Words</p><video></video></body>"#;
+        assert!(extract_media_metadata(html, URL).is_empty());
+    }
+}
@@ -25,8 +25,13 @@ pub enum NativeParserError {
     #[error("No extractable content found")]
     NoContentFound,
 
-    #[error("Quality too low: {score} (threshold: {threshold})")]
-    LowQuality { score: f32, threshold: f32 },
+    #[error("Quality too low: {score} (threshold: {threshold}; weakest signals: {weakest})")]
+    LowQuality {
+        score: f32,
+        threshold: f32,
+        /// The signals that cost the most points
+        weakest: String,
+    },
 
     #[error("Internal error: {0}")]
     Internal(String),
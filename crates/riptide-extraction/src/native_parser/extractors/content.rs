@@ -1,49 +1,74 @@
 //! Content extraction from HTML documents
 
-use scraper::{Html, Selector};
+use scraper::{ElementRef, Html, Selector};
 
+use crate::boilerplate::BoilerplateClassifier;
+use crate::clean_html::CleanHtmlConverter;
+use crate::markdown::{MarkdownConverter, MarkdownFlavor};
 use crate::native_parser::error::{NativeParserError, Result};
 
+/// Selectors for article content
+const ARTICLE_SELECTORS: &[&str] = &[
+    "article",
+    "[role='article']",
+    ".article-content",
+    ".post-content",
+    ".entry-content",
+    "main article",
+];
+
+/// Selectors for the main content region
+const MAIN_SELECTORS: &[&str] = &["main", "[role='main']", ".main-content", "#content"];
+
 pub struct ContentExtractor;
 
 impl ContentExtractor {
-    /// Extract both text and markdown representation
-    pub fn extract(document: &Html, _url: &str) -> Result<(String, Option<String>)> {
+    /// Extract text, markdown of the same region when `markdown` names a
+    /// flavor, and sanitized HTML of it when a `clean_html` converter is given
+    ///
+    /// With a `boilerplate` classifier the text is the region's content
+    /// blocks only, unless it finds none.
+    pub fn extract(
+        document: &Html,
+        url: &str,
+        markdown: Option<MarkdownFlavor>,
+        clean_html: Option<&CleanHtmlConverter>,
+        boilerplate: Option<&BoilerplateClassifier>,
+    ) -> Result<(String, Option<String>, Option<String>)> {
+        let region = |content: String, selectors: &[&str]| {
+            let content = boilerplate
+                .and_then(|classifier| Self::content_blocks(document, selectors, classifier))
+                .unwrap_or(content);
+            let markdown = markdown
+                .and_then(|flavor| Self::convert_to_markdown(document, selectors, flavor, url));
+            let clean_html = clean_html
+                .and_then(|converter| Self::convert_to_clean_html(document, selectors, converter));
+            (content, markdown, clean_html)
+        };
+
         // Try article-specific selectors first
         if let Some(content) = Self::extract_article_content(document) {
-            let markdown = Self::convert_to_markdown(&content, document);
-            return Ok((content, Some(markdown)));
+            return Ok(region(content, ARTICLE_SELECTORS));
         }
 
         // Fallback to main content
         if let Some(content) = Self::extract_main_content(document) {
-            let markdown = Self::convert_to_markdown(&content, document);
-            return Ok((content, Some(markdown)));
+            return Ok(region(content, MAIN_SELECTORS));
         }
 
         // Last resort: body text
         if let Some(content) = Self::extract_body_content(document) {
-            let markdown = Self::convert_to_markdown(&content, document);
-            return Ok((content, Some(markdown)));
+            return Ok(region(content, &["body"]));
         }
 
         Err(NativeParserError::NoContentFound)
     }
 
     fn extract_article_content(document: &Html) -> Option<String> {
-        let selectors = [
-            "article",
-            "[role='article']",
-            ".article-content",
-            ".post-content",
-            ".entry-content",
-            "main article",
-        ];
-
         let mut all_content = Vec::new();
         let mut seen_text = std::collections::HashSet::new();
 
-        for selector_str in &selectors {
+        for selector_str in ARTICLE_SELECTORS {
             if let Ok(selector) = Selector::parse(selector_str) {
                 // CRITICAL FIX: Iterate ALL matching elements, not just first
                 for element in document.select(&selector) {
@@ -66,12 +91,10 @@ impl ContentExtractor {
     }
 
     fn extract_main_content(document: &Html) -> Option<String> {
-        let selectors = ["main", "[role='main']", ".main-content", "#content"];
-
         let mut all_content = Vec::new();
         let mut seen_text = std::collections::HashSet::new();
 
-        for selector_str in &selectors {
+        for selector_str in MAIN_SELECTORS {
             if let Ok(selector) = Selector::parse(selector_str) {
                 // CRITICAL FIX: Iterate ALL matching elements
                 for element in document.select(&selector) {
@@ -157,51 +180,67 @@ impl ContentExtractor {
         text.trim().to_string()
     }
 
-    fn convert_to_markdown(_text: &str, document: &Html) -> String {
-        let mut markdown = String::new();
-
-        // Extract title as heading
-        let h1_selector = Selector::parse("h1").ok();
-        if let Some(selector) = h1_selector {
-            if let Some(h1) = document.select(&selector).next() {
-                let title: String = h1.text().collect();
-                markdown.push_str(&format!("# {}\n\n", title.trim()));
-            }
-        }
-
-        // Extract paragraphs
-        let p_selector = Selector::parse("p").ok();
-        if let Some(selector) = p_selector {
-            for p in document.select(&selector) {
-                let para: String = p.text().collect();
-                let cleaned = para.trim();
-                if !cleaned.is_empty() {
-                    markdown.push_str(cleaned);
-                    markdown.push_str("\n\n");
+    /// Outermost elements matching `selectors`, in document order of discovery
+    fn region_roots<'a>(document: &'a Html, selectors: &[&str]) -> Vec<ElementRef<'a>> {
+        let mut roots: Vec<ElementRef> = Vec::new();
+        for selector in selectors.iter().filter_map(|s| Selector::parse(s).ok()) {
+            for element in document.select(&selector) {
+                let covered = roots.iter().any(|root| {
+                    root.id() == element.id() || element.ancestors().any(|a| a.id() == root.id())
+                });
+                if !covered {
+                    roots.retain(|root| !root.ancestors().any(|a| a.id() == element.id()));
+                    roots.push(element);
                 }
             }
         }
+        roots
+    }
 
-        // Extract headings
-        for level in 2..=6 {
-            let selector_str = format!("h{}", level);
-            let heading_selector = Selector::parse(&selector_str).ok();
-            if let Some(selector) = heading_selector {
-                for heading in document.select(&selector) {
-                    let heading_text: String = heading.text().collect();
-                    let cleaned = heading_text.trim();
-                    if !cleaned.is_empty() {
-                        markdown.push_str(&format!("{} {}\n\n", "#".repeat(level), cleaned));
-                    }
-                }
-            }
-        }
+    /// Text of the content blocks of the outermost elements matching `selectors`
+    fn content_blocks(
+        document: &Html,
+        selectors: &[&str],
+        classifier: &BoilerplateClassifier,
+    ) -> Option<String> {
+        let text = Self::region_roots(document, selectors)
+            .into_iter()
+            .map(|root| classifier.content_text(root))
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        (!text.is_empty()).then_some(text)
+    }
 
-        if markdown.is_empty() {
-            // Return the original text if no markdown was generated
-            String::new()
-        } else {
-            markdown
-        }
+    /// Markdown of the outermost elements matching `selectors`
+    fn convert_to_markdown(
+        document: &Html,
+        selectors: &[&str],
+        flavor: MarkdownFlavor,
+        url: &str,
+    ) -> Option<String> {
+        let converter = MarkdownConverter::new(flavor).with_base_url(url);
+        let markdown = Self::region_roots(document, selectors)
+            .into_iter()
+            .map(|root| converter.convert_element(root))
+            .filter(|markdown| !markdown.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        (!markdown.is_empty()).then_some(markdown)
+    }
+
+    /// Sanitized HTML of the outermost elements matching `selectors`
+    fn convert_to_clean_html(
+        document: &Html,
+        selectors: &[&str],
+        converter: &CleanHtmlConverter,
+    ) -> Option<String> {
+        let html = Self::region_roots(document, selectors)
+            .into_iter()
+            .map(|root| converter.convert_element(root))
+            .filter(|html| !html.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        (!html.is_empty()).then_some(html)
     }
 }
@@ -3,30 +3,29 @@
 use scraper::{Html, Selector};
 use url::Url;
 
+use crate::image_metadata::{ImageMetadata, ImageMetadataExtractor};
+
 pub struct MediaExtractor;
 
 impl MediaExtractor {
     /// Extract media (images, videos) from document
     pub fn extract(document: &Html, base_url: &str) -> Vec<String> {
+        Self::extract_with_images(document, base_url).0
+    }
+
+    /// Extract media URLs along with the metadata of each image
+    pub fn extract_with_images(
+        document: &Html,
+        base_url: &str,
+    ) -> (Vec<String>, Vec<ImageMetadata>) {
         let mut media = Vec::new();
         let base = Url::parse(base_url).ok();
 
-        // Extract images
-        if let Ok(selector) = Selector::parse("img[src]") {
-            for element in document.select(&selector) {
-                if let Some(src) = element.value().attr("src") {
-                    let resolved = if let Some(ref base) = base {
-                        base.join(src).ok().map(|u| u.to_string())
-                    } else {
-                        Some(src.to_string())
-                    };
-
-                    if let Some(url) = resolved {
-                        if Self::is_valid_media_url(&url) {
-                            media.push(url);
-                        }
-                    }
-                }
+        // Extract images, with lazy-loaded sources resolved
+        let images = ImageMetadataExtractor::extract(document, base_url);
+        for image in &images {
+            if Self::is_valid_media_url(&image.url) {
+                media.push(image.url.clone());
             }
         }
 
@@ -49,7 +48,7 @@ impl MediaExtractor {
             }
         }
 
-        media
+        (media, images)
     }
 
     fn is_valid_media_url(url: &str) -> bool {
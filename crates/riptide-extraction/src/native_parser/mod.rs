@@ -8,6 +8,7 @@ pub mod extractors;
 pub mod fallbacks;
 pub mod parser;
 pub mod quality;
+pub mod streaming;
 
 #[cfg(test)]
 mod tests;
@@ -15,3 +16,5 @@ mod tests;
 // Re-export main types
 pub use error::{NativeParserError, Result};
 pub use parser::{NativeHtmlParser, ParserConfig};
+pub use quality::assess_quality;
+pub use streaming::StreamingHtmlParser;
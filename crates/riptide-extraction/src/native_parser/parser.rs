@@ -2,30 +2,54 @@
 
 use anyhow::Result as AnyhowResult;
 use riptide_types::extractors::HtmlParser as HtmlParserTrait;
+use riptide_types::language::{detect_language_spans, dominant_language};
 use riptide_types::{ExtractedDoc, ParserMetadata};
 use scraper::Html;
 use tracing::{debug, warn};
 
+use crate::boilerplate::{BoilerplateClassifier, BoilerplateTweaks};
+use crate::clean_html::CleanHtmlConverter;
+use crate::embedded_markup::{self, EmbeddedMarkup};
+use crate::feed::FeedDiscovery;
+use crate::markdown::MarkdownFlavor;
+use crate::media_metadata::MediaMetadataExtractor;
 use crate::native_parser::{
     error::{NativeParserError, Result},
     extractors::*,
     fallbacks::FallbackStrategy,
     quality::QualityAssessor,
+    streaming::StreamingHtmlParser,
 };
+use crate::references::ReferencesExtractor;
+use crate::structured_data::StructuredDataExtractor;
 
 /// Parser configuration
 #[derive(Debug, Clone)]
 pub struct ParserConfig {
     /// Enable markdown generation
     pub enable_markdown: bool,
-    /// Enable link extraction
+    /// Markdown dialect generated when `enable_markdown` is set
+    pub markdown_flavor: MarkdownFlavor,
+    /// Enable sanitized reader-mode HTML of the main content
+    pub enable_clean_html: bool,
+    /// Prefix image sources in clean HTML are rewritten to, followed by
+    /// the percent-encoded image URL
+    pub image_proxy: Option<String>,
+    /// Classify text blocks and keep only content blocks in `text`, with a
+    /// site's classifier tweaks; `None` keeps every block
+    pub boilerplate: Option<BoilerplateTweaks>,
+    /// Enable link extraction, including citation references
     pub extract_links: bool,
-    /// Enable media extraction
+    /// Enable media extraction, including image, video and audio metadata
     pub extract_media: bool,
     /// Enable language detection
     pub detect_language: bool,
     /// Enable category extraction
     pub extract_categories: bool,
+    /// Enable JSON-LD, Microdata and RDFa extraction
+    pub extract_structured_data: bool,
+    /// Handling of inline MathML formulas and SVG graphics
+    pub embedded_markup: EmbeddedMarkup,
     /// Maximum content length (bytes)
     pub max_content_length: usize,
     /// Parse timeout (milliseconds)
@@ -38,10 +62,16 @@ impl Default for ParserConfig {
     fn default() -> Self {
         Self {
             enable_markdown: true,
+            markdown_flavor: MarkdownFlavor::default(),
+            enable_clean_html: false,
+            image_proxy: None,
+            boilerplate: None,
             extract_links: true,
             extract_media: true,
             detect_language: true,
             extract_categories: true,
+            extract_structured_data: true,
+            embedded_markup: EmbeddedMarkup::default(),
             max_content_length: 10_000_000, // 10MB
             parse_timeout_ms: 5000,         // 5 seconds
             min_quality_score: 15,          // Lowered from 30 to allow more content through
@@ -84,8 +114,10 @@ impl NativeHtmlParser {
             "Starting native HTML parsing"
         );
 
-        // 2. Parse HTML document (native Rust - won't crash!)
-        let document = Html::parse_document(html);
+        // 2. Parse HTML document (native Rust - won't crash!), with formulas
+        // and graphics rewritten before anything reads their text
+        let mut document = Html::parse_document(html);
+        embedded_markup::rewrite_document(&mut document, self.config.embedded_markup);
 
         // 3. Extract all components
         let title = TitleExtractor::extract(&document);
@@ -93,29 +125,69 @@ impl NativeHtmlParser {
         let published = MetadataExtractor::extract_published_date(&document);
         let description = MetadataExtractor::extract_description(&document);
         let site_name = MetadataExtractor::extract_site_name(&document);
+        // Video and audio metadata stands in for text on player pages
+        let embedded_media = if self.config.extract_media {
+            MediaMetadataExtractor::extract(&document, url)
+        } else {
+            Vec::new()
+        };
 
-        // 4. Extract content (text + markdown)
-        let (text, markdown) = ContentExtractor::extract(&document, url)?;
+        // 4. Extract content (text + markdown + clean HTML)
+        let markdown_flavor = self
+            .config
+            .enable_markdown
+            .then_some(self.config.markdown_flavor);
+        let clean_html_converter = self.config.enable_clean_html.then(|| {
+            let converter = CleanHtmlConverter::new().with_base_url(url);
+            match &self.config.image_proxy {
+                Some(proxy) => converter.with_image_proxy(proxy.as_str()),
+                None => converter,
+            }
+        });
+        let boilerplate = self
+            .config
+            .boilerplate
+            .clone()
+            .map(BoilerplateClassifier::with_tweaks);
+        let (text, markdown, clean_html) = match ContentExtractor::extract(
+            &document,
+            url,
+            markdown_flavor,
+            clean_html_converter.as_ref(),
+            boilerplate.as_ref(),
+        ) {
+            Err(NativeParserError::NoContentFound) if !embedded_media.is_empty() => {
+                (String::new(), None, None)
+            }
+            extracted => extracted?,
+        };
 
-        // 5. Extract links (conditional)
-        let links = if self.config.extract_links {
-            LinkExtractor::extract(&document, url)
+        // 5. Extract links and references (conditional)
+        let (links, references) = if self.config.extract_links {
+            (
+                LinkExtractor::extract(&document, url),
+                ReferencesExtractor::extract(&document, url),
+            )
         } else {
-            Vec::new()
+            (Vec::new(), Vec::new())
         };
 
         // 6. Extract media (conditional)
-        let media = if self.config.extract_media {
-            MediaExtractor::extract(&document, url)
+        let (media, images) = if self.config.extract_media {
+            MediaExtractor::extract_with_images(&document, url)
         } else {
-            Vec::new()
+            (Vec::new(), Vec::new())
         };
 
-        // 7. Detect language (conditional)
-        let language = if self.config.detect_language {
-            LanguageDetector::detect(&document, html)
+        // 7. Detect language (conditional): markup names the page language,
+        // the text detector finds the language of each block
+        let (language, language_spans) = if self.config.detect_language {
+            let spans = detect_language_spans(&text);
+            let language =
+                LanguageDetector::detect(&document, html).or_else(|| dominant_language(&spans));
+            (language, spans)
         } else {
-            None
+            (None, Vec::new())
         };
 
         // 8. Extract categories (conditional)
@@ -125,57 +197,97 @@ impl NativeHtmlParser {
             Vec::new()
         };
 
-        // 9. Calculate quality metrics
+        // 9. Extract schema.org structured data (conditional)
+        let structured_data = if self.config.extract_structured_data {
+            StructuredDataExtractor::extract(&document, url)
+        } else {
+            Vec::new()
+        };
+        // Feeds the page advertises, so crawls can switch to them
+        let feed_links = FeedDiscovery::discover(&document, url);
+
+        // 10. Reading metrics
         let word_count = text.split_whitespace().count();
         let reading_time = (word_count / 200).max(1); // 200 wpm
-        let quality_score = QualityAssessor::calculate(&text, &markdown, &title);
-
-        debug!(
-            url = %url,
-            word_count = word_count,
-            quality_score = quality_score,
-            "Extraction completed"
-        );
 
-        // 10. Build result
-        let doc = ExtractedDoc {
+        // 11. Build result
+        let mut doc = ExtractedDoc {
             url: url.to_string(),
             title,
             byline,
             published_iso: published,
             markdown,
+            clean_html,
             text,
             links,
             media,
             language,
             reading_time: Some(reading_time as u32),
-            quality_score: Some(quality_score as u8),
+            quality_score: None,
             word_count: Some(word_count as u32),
             parser_metadata: Some(ParserMetadata {
                 parser_used: "native".to_string(),
-                confidence_score: quality_score as f64 / 100.0,
+                confidence_score: 0.0,
                 fallback_occurred: false,
                 parse_time_ms: 0,
                 extraction_path: None,
                 primary_error: None,
             }),
+            provenance: None,
+            translation: None,
+            structured_data,
+            annotations: None,
+            feed: None,
+            feed_links,
+            classification: None,
+            media_assets: Vec::new(),
+            embedded_media,
+            images,
+            references,
+            language_spans,
+            quality_report: None,
             categories,
             site_name,
             description,
             html: None, // We don't store the original HTML
         };
 
-        // 11. Validate minimum quality
-        if quality_score < self.config.min_quality_score as usize {
+        // 12. Score the result
+        let report = QualityAssessor::assess(&document, html.len(), &doc);
+        let quality_score = report.score;
+        doc.quality_score = Some(quality_score);
+        if let Some(metadata) = doc.parser_metadata.as_mut() {
+            metadata.confidence_score = f64::from(quality_score) / 100.0;
+        }
+
+        debug!(
+            url = %url,
+            word_count = word_count,
+            quality_score = quality_score,
+            weakest = %report.explain(3),
+            "Extraction completed"
+        );
+
+        // 13. Validate minimum quality; pages built around a player carry
+        // little text, but their media metadata is still worth returning
+        if u32::from(quality_score) < self.config.min_quality_score && doc.embedded_media.is_empty()
+        {
             return Err(NativeParserError::LowQuality {
-                score: quality_score as f32,
+                score: f32::from(quality_score),
                 threshold: self.config.min_quality_score as f32,
+                weakest: report.explain(3),
             });
         }
+        doc.quality_report = Some(report);
 
         Ok(doc)
     }
 
+    /// Start a chunk-fed parse of the document at `url` with this config
+    pub fn streaming(&self, url: &str) -> StreamingHtmlParser {
+        StreamingHtmlParser::new(self.config.clone(), url)
+    }
+
     /// Extract document with quality-based fallbacks
     ///
     /// Tries multiple extraction strategies if initial attempt
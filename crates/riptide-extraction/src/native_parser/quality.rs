@@ -1,5 +1,24 @@
 //! Quality assessment for extracted content
 
+use riptide_types::quality::{ExtractionQualityReport, QualitySignal, QualitySignalKind};
+use riptide_types::ExtractedDoc;
+use scraper::{Html, Selector};
+
+/// Elements that hold ads: ad network slots and blocks named after ads or
+/// sponsors
+const AD_SELECTOR: &str = "ins.adsbygoogle, iframe[src*=doubleclick], iframe[id^=google_ads], \
+    [class~=ad], [class~=ads], [id^=ad-], [class*=advert], [id*=advert], [class*=sponsor]";
+
+/// Text length and number of paragraphs that earn full points
+const FULL_CONTENT_LENGTH: f64 = 2000.0;
+const FULL_PARAGRAPHS: f64 = 5.0;
+/// Share of the HTML that is extracted text on a page that earns full points
+const FULL_TEXT_DENSITY: f64 = 0.25;
+/// Share of the text inside links at which the link signal earns nothing
+const MAX_LINK_DENSITY: f64 = 0.5;
+/// Words a line of text needs to count as a paragraph
+const PARAGRAPH_WORDS: usize = 8;
+
 pub struct QualityAssessor;
 
 impl QualityAssessor {
@@ -54,4 +73,212 @@ impl QualityAssessor {
 
         score.min(100)
     }
+
+    /// Score `doc`, extracted from `document` (`html_len` bytes of HTML),
+    /// and explain the score signal by signal
+    ///
+    /// The weights of the signals add up to 100.
+    pub fn assess(document: &Html, html_len: usize, doc: &ExtractedDoc) -> ExtractionQualityReport {
+        let text_len = doc.text.chars().count();
+        let paragraphs = doc
+            .text
+            .lines()
+            .filter(|line| line.split_whitespace().count() >= PARAGRAPH_WORDS)
+            .count();
+
+        ExtractionQualityReport::from_signals(vec![
+            title_signal(&doc.title),
+            title_match_signal(&doc.title, &doc.text),
+            QualitySignal::new(
+                QualitySignalKind::ContentLength,
+                text_len as f64,
+                text_len as f64 / FULL_CONTENT_LENGTH,
+                25.0,
+                format!("{} characters", text_len),
+            ),
+            QualitySignal::new(
+                QualitySignalKind::Paragraphs,
+                paragraphs as f64,
+                paragraphs as f64 / FULL_PARAGRAPHS,
+                15.0,
+                plural(paragraphs, "paragraph"),
+            ),
+            text_density_signal(text_len, html_len),
+            link_density_signal(document),
+            structure_signal(document, &doc.markdown),
+            QualitySignal::new(
+                QualitySignalKind::Schema,
+                doc.structured_data.len() as f64,
+                if doc.structured_data.is_empty() {
+                    0.0
+                } else {
+                    1.0
+                },
+                5.0,
+                plural(doc.structured_data.len(), "schema.org item"),
+            ),
+            ad_density_signal(document),
+        ])
+    }
+}
+
+/// Quality report of `doc`, extracted by any extractor from `html`
+pub fn assess_quality(html: &str, doc: &ExtractedDoc) -> ExtractionQualityReport {
+    QualityAssessor::assess(&Html::parse_document(html), html.len(), doc)
+}
+
+fn title_signal(title: &Option<String>) -> QualitySignal {
+    let present = title.as_ref().is_some_and(|t| !t.trim().is_empty());
+    QualitySignal::new(
+        QualitySignalKind::Title,
+        f64::from(u8::from(present)),
+        f64::from(u8::from(present)),
+        10.0,
+        if present { "present" } else { "missing" },
+    )
+}
+
+/// Share of the title's words, without a trailing site name, that appear
+/// in the first 1000 characters of the text
+fn title_match_signal(title: &Option<String>, text: &str) -> QualitySignal {
+    let title = title.as_deref().unwrap_or_default();
+    let headline = [" | ", " - ", " \u{2013} ", " \u{2014} "]
+        .iter()
+        .filter_map(|sep| title.rsplit_once(sep).map(|(head, _)| head))
+        .max_by_key(|head| head.len())
+        .unwrap_or(title);
+    let words: Vec<String> = headline
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 3)
+        .map(str::to_lowercase)
+        .collect();
+    let lead: String = text.chars().take(1000).collect::<String>().to_lowercase();
+    let matched = words.iter().filter(|w| lead.contains(w.as_str())).count();
+    let share = if words.is_empty() {
+        0.0
+    } else {
+        matched as f64 / words.len() as f64
+    };
+
+    QualitySignal::new(
+        QualitySignalKind::TitleMatch,
+        share,
+        share,
+        10.0,
+        format!("{} of {} title words in the lead", matched, words.len()),
+    )
+}
+
+fn text_density_signal(text_len: usize, html_len: usize) -> QualitySignal {
+    let density = if html_len == 0 {
+        0.0
+    } else {
+        text_len as f64 / html_len as f64
+    };
+    QualitySignal::new(
+        QualitySignalKind::TextDensity,
+        density,
+        density / FULL_TEXT_DENSITY,
+        10.0,
+        format!("{:.0}% of the HTML is text", density * 100.0),
+    )
+}
+
+/// Share of the body's text inside links; menus, link farms and index
+/// pages score low
+fn link_density_signal(document: &Html) -> QualitySignal {
+    let text_len = |selector: &str| -> usize {
+        Selector::parse(selector)
+            .map(|s| {
+                document
+                    .select(&s)
+                    .flat_map(|el| el.text())
+                    .map(|t| t.trim().chars().count())
+                    .sum()
+            })
+            .unwrap_or(0)
+    };
+    let body = text_len("body");
+    if body == 0 {
+        return QualitySignal::new(QualitySignalKind::LinkDensity, 0.0, 0.0, 10.0, "no text");
+    }
+    let density = text_len("body a") as f64 / body as f64;
+
+    QualitySignal::new(
+        QualitySignalKind::LinkDensity,
+        density,
+        1.0 - density / MAX_LINK_DENSITY,
+        10.0,
+        format!("{:.0}% of text is links", density * 100.0),
+    )
+}
+
+/// Headings, list items and links in the markdown, or heading, list item,
+/// table and quote elements on the page when there is no markdown
+fn structure_signal(document: &Html, markdown: &Option<String>) -> QualitySignal {
+    let (indicators, what) = match markdown {
+        Some(md) => (
+            md.matches('#').count() + md.matches('*').count() + md.matches('[').count(),
+            "markdown structure marker",
+        ),
+        None => (
+            Selector::parse("h1, h2, h3, h4, h5, h6, li, table, blockquote")
+                .map(|s| document.select(&s).count())
+                .unwrap_or(0),
+            "structural element",
+        ),
+    };
+    let score = match indicators {
+        n if n > 10 => 1.0,
+        n if n > 5 => 0.6,
+        n if n > 2 => 0.3,
+        _ => 0.0,
+    };
+
+    QualitySignal::new(
+        QualitySignalKind::Structure,
+        indicators as f64,
+        score,
+        10.0,
+        plural(indicators, what),
+    )
+}
+
+/// Ad slots relative to paragraphs; the signal earns nothing once ads make
+/// up half of the blocks
+fn ad_density_signal(document: &Html) -> QualitySignal {
+    let count = |selector: &str| {
+        Selector::parse(selector)
+            .map(|s| document.select(&s).count())
+            .unwrap_or(0)
+    };
+    let ads = count(AD_SELECTOR);
+    let paragraphs = count("p");
+    let (density, score) = match ads + paragraphs {
+        0 => (0.0, 0.0),
+        blocks => {
+            let density = ads as f64 / blocks as f64;
+            (density, 1.0 - 2.0 * density)
+        }
+    };
+
+    QualitySignal::new(
+        QualitySignalKind::AdDensity,
+        density,
+        score,
+        5.0,
+        format!(
+            "{} per {}",
+            plural(ads, "ad slot"),
+            plural(paragraphs, "paragraph")
+        ),
+    )
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
 }
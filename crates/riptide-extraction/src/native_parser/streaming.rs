@@ -0,0 +1,149 @@
+//! Incremental input for the native parser
+//!
+//! Response bodies arrive in chunks; [`StreamingHtmlParser`] takes them as
+//! they come, validating UTF-8 across chunk boundaries and enforcing
+//! `max_content_length` per chunk, so the caller can abort a download at the
+//! first oversized or undecodable chunk instead of buffering the whole body
+//! first. The document is extracted by [`StreamingHtmlParser::finish`].
+
+use riptide_types::ExtractedDoc;
+
+use crate::native_parser::{
+    error::{NativeParserError, Result},
+    parser::{NativeHtmlParser, ParserConfig},
+};
+
+/// Chunk-fed front end of [`NativeHtmlParser`]
+pub struct StreamingHtmlParser {
+    config: ParserConfig,
+    url: String,
+    html: String,
+    /// Trailing bytes of an incomplete UTF-8 sequence
+    pending: Vec<u8>,
+    received: usize,
+}
+
+impl StreamingHtmlParser {
+    /// Start a document for `url`
+    pub fn new(config: ParserConfig, url: impl Into<String>) -> Self {
+        Self {
+            config,
+            url: url.into(),
+            html: String::new(),
+            pending: Vec::new(),
+            received: 0,
+        }
+    }
+
+    /// Append the next body chunk
+    ///
+    /// Fails as soon as the input passes `max_content_length` or contains
+    /// invalid UTF-8; the parser should then be dropped.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<()> {
+        let size = self.received + chunk.len();
+        if size > self.config.max_content_length {
+            return Err(NativeParserError::OversizedHtml {
+                size,
+                max: self.config.max_content_length,
+            });
+        }
+
+        self.pending.extend_from_slice(chunk);
+        let text = match std::str::from_utf8(&self.pending) {
+            Ok(text) => text,
+            // A multi-byte character split across chunks
+            Err(e) if e.error_len().is_none() => {
+                std::str::from_utf8(&self.pending[..e.valid_up_to()]).unwrap_or_default()
+            }
+            Err(e) => {
+                return Err(NativeParserError::EncodingError(format!(
+                    "Invalid UTF-8 at byte {}",
+                    self.received - (self.pending.len() - chunk.len()) + e.valid_up_to()
+                )));
+            }
+        };
+        self.html.push_str(text);
+        let consumed = text.len();
+        self.pending.drain(..consumed);
+        self.received = size;
+        Ok(())
+    }
+
+    /// Bytes fed so far
+    pub fn received(&self) -> usize {
+        self.received
+    }
+
+    /// Extract the document from everything fed
+    pub fn finish(self) -> Result<ExtractedDoc> {
+        if !self.pending.is_empty() {
+            return Err(NativeParserError::EncodingError(
+                "Input ends inside a UTF-8 sequence".to_string(),
+            ));
+        }
+        NativeHtmlParser::with_config(self.config).parse_headless_html(&self.html, &self.url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HTML: &str = "<html><head><title>Café résumé</title></head><body><article>\
+        <p>Naïve café owners in Zürich served crème brûlée to every visitor this year, \
+        and the local paper wrote a long piece about the queue outside the door.</p>\
+        <p>Visitors said the wait was worth it, and the owners plan a second shop.</p>\
+        </article></body></html>";
+
+    #[test]
+    fn test_chunked_input_matches_whole_document() {
+        let whole = NativeHtmlParser::new()
+            .parse_headless_html(HTML, "https://example.com")
+            .unwrap();
+
+        // Three-byte chunks split every multi-byte character somewhere
+        let mut parser = StreamingHtmlParser::new(ParserConfig::default(), "https://example.com");
+        for chunk in HTML.as_bytes().chunks(3) {
+            parser.feed(chunk).unwrap();
+        }
+        assert_eq!(parser.received(), HTML.len());
+        let streamed = parser.finish().unwrap();
+
+        assert_eq!(streamed.title, whole.title);
+        assert_eq!(streamed.text, whole.text);
+    }
+
+    #[test]
+    fn test_rejects_oversized_input_on_the_offending_chunk() {
+        let config = ParserConfig {
+            max_content_length: 64,
+            ..Default::default()
+        };
+        let mut parser = StreamingHtmlParser::new(config, "https://example.com");
+        parser.feed(&[b' '; 60]).unwrap();
+
+        let err = parser.feed(&[b' '; 8]).unwrap_err();
+        assert!(matches!(
+            err,
+            NativeParserError::OversizedHtml { size: 68, max: 64 }
+        ));
+    }
+
+    #[test]
+    fn test_rejects_invalid_and_truncated_utf8() {
+        let mut parser = StreamingHtmlParser::new(ParserConfig::default(), "https://example.com");
+        parser.feed(b"<html>").unwrap();
+        assert!(matches!(
+            parser.feed(b"\xff<body>"),
+            Err(NativeParserError::EncodingError(_))
+        ));
+
+        let mut parser = StreamingHtmlParser::new(ParserConfig::default(), "https://example.com");
+        parser.feed("<html>caf".as_bytes()).unwrap();
+        parser.feed(&"é".as_bytes()[..1]).unwrap();
+        assert!(matches!(
+            parser.finish(),
+            Err(NativeParserError::EncodingError(_))
+        ));
+    }
+}
@@ -2,6 +2,8 @@
 
 #[cfg(test)]
 mod native_parser_tests {
+    use crate::embedded_markup::EmbeddedMarkup;
+    use crate::markdown::MarkdownFlavor;
     use crate::native_parser::{NativeHtmlParser, ParserConfig};
 
     #[test]
@@ -200,4 +202,361 @@ mod native_parser_tests {
         let md = doc.markdown.unwrap();
         assert!(md.contains("# Main Title") || md.contains("Main Title"));
     }
+
+    #[test]
+    fn test_markdown_structure_and_flavor() {
+        let html = r#"
+            <html>
+            <body>
+                <nav><a href="/">Home</a></nav>
+                <article>
+                    <h1>Release notes</h1>
+                    <p>This release brings a set of changes described in the
+                    <a href="/docs/changelog">changelog</a> with plenty of detail.</p>
+                    <ul><li>Faster parsing</li><li>Smaller binaries</li></ul>
+                    <table><tr><th>Version</th><th>Date</th></tr><tr><td>2.0</td><td>May</td></tr></table>
+                </article>
+            </body>
+            </html>
+        "#;
+
+        let doc = NativeHtmlParser::new()
+            .parse_headless_html(html, "https://example.com/news/")
+            .unwrap();
+        let md = doc.markdown.unwrap();
+        assert!(md.starts_with("# Release notes\n\n"));
+        assert!(md.contains("[changelog](https://example.com/docs/changelog)"));
+        assert!(md.contains("- Faster parsing\n- Smaller binaries"));
+        assert!(md.contains("| Version | Date |\n| --- | --- |\n| 2.0 | May |"));
+        assert!(!md.contains("Home"));
+
+        let parser = NativeHtmlParser::with_config(ParserConfig {
+            markdown_flavor: MarkdownFlavor::CommonMark,
+            ..Default::default()
+        });
+        let md = parser
+            .parse_headless_html(html, "https://example.com/news/")
+            .unwrap()
+            .markdown
+            .unwrap();
+        assert!(md.contains("<table>"));
+
+        let parser = NativeHtmlParser::with_config(ParserConfig {
+            enable_markdown: false,
+            ..Default::default()
+        });
+        let doc = parser
+            .parse_headless_html(html, "https://example.com/news/")
+            .unwrap();
+        assert!(doc.markdown.is_none());
+    }
+
+    #[test]
+    fn test_structured_data_populated() {
+        let html = r#"
+            <html>
+            <head>
+                <script type="application/ld+json">
+                    {"@type": "Event", "name": "Rust meetup", "startDate": "2024-06-01"}
+                </script>
+            </head>
+            <body>
+                <article>
+                    <h1>Rust meetup</h1>
+                    <p>Join us for an evening of talks about crawling, parsing and
+                    everything in between. Doors open at six, talks start at seven.</p>
+                </article>
+            </body>
+            </html>
+        "#;
+
+        let doc = NativeHtmlParser::new()
+            .parse_headless_html(html, "https://example.com/events/")
+            .unwrap();
+        assert_eq!(doc.structured_data.len(), 1);
+        assert_eq!(doc.structured_data[0].entity.kind(), "event");
+
+        let parser = NativeHtmlParser::with_config(ParserConfig {
+            extract_structured_data: false,
+            ..Default::default()
+        });
+        let doc = parser
+            .parse_headless_html(html, "https://example.com/events/")
+            .unwrap();
+        assert!(doc.structured_data.is_empty());
+    }
+
+    #[test]
+    fn test_feed_links_discovered() {
+        let html = r#"
+            <html>
+            <head>
+                <link rel="alternate" type="application/rss+xml" href="/feed.xml">
+            </head>
+            <body>
+                <article>
+                    <h1>Release notes</h1>
+                    <p>Every release of the crawler is announced here first, with the
+                    full changelog and upgrade notes for each of the crates.</p>
+                </article>
+            </body>
+            </html>
+        "#;
+
+        let doc = NativeHtmlParser::new()
+            .parse_headless_html(html, "https://example.com/blog/")
+            .unwrap();
+        assert_eq!(doc.feed_links.len(), 1);
+        assert_eq!(doc.feed_links[0].url, "https://example.com/feed.xml");
+        assert_eq!(doc.feed_links[0].format, riptide_types::FeedFormat::Rss);
+    }
+
+    #[test]
+    fn test_video_page_keeps_media_metadata_despite_little_text() {
+        let html = r#"
+            <html>
+            <head>
+                <meta property="og:video" content="https://cdn.example.com/clip.mp4">
+                <meta property="og:video:type" content="video/mp4">
+            </head>
+            <body>
+                <video src="https://cdn.example.com/clip.mp4" poster="/clip.jpg"></video>
+            </body>
+            </html>
+        "#;
+
+        let doc = NativeHtmlParser::new()
+            .parse_headless_html(html, "https://example.com/watch")
+            .unwrap();
+        assert_eq!(doc.embedded_media.len(), 1);
+        assert_eq!(
+            doc.embedded_media[0].thumbnail_url.as_deref(),
+            Some("https://example.com/clip.jpg")
+        );
+
+        let parser = NativeHtmlParser::with_config(ParserConfig {
+            extract_media: false,
+            ..Default::default()
+        });
+        assert!(parser
+            .parse_headless_html(html, "https://example.com/watch")
+            .is_err());
+    }
+
+    #[test]
+    fn test_clean_html_of_content_region() {
+        let html = r#"
+            <html>
+            <body>
+                <nav><a href="/">Home</a></nav>
+                <article class="post">
+                    <h1>Release notes</h1>
+                    <p onclick="track()">Every release of the crawler is announced here first,
+                    with the <a href="changelog?utm_medium=web">full changelog</a>.</p>
+                    <img src="/img/chart.png" alt="Chart">
+                    <script>track()</script>
+                </article>
+            </body>
+            </html>
+        "#;
+
+        let doc = NativeHtmlParser::new()
+            .parse_headless_html(html, "https://example.com/blog/")
+            .unwrap();
+        assert!(doc.clean_html.is_none());
+
+        let parser = NativeHtmlParser::with_config(ParserConfig {
+            enable_clean_html: true,
+            image_proxy: Some("https://img.example.net/?url=".to_string()),
+            ..Default::default()
+        });
+        let clean = parser
+            .parse_headless_html(html, "https://example.com/blog/")
+            .unwrap()
+            .clean_html
+            .unwrap();
+        assert!(clean.starts_with("<article>\n<h1>Release notes</h1>"));
+        assert!(clean.contains("<a href=\"https://example.com/blog/changelog\">full changelog</a>"));
+        assert!(clean.contains("<img src=\"https://img.example.net/?url=https%3A%2F%2Fexample.com%2Fimg%2Fchart.png\" alt=\"Chart\">"));
+        assert!(!clean.contains("script") && !clean.contains("onclick") && !clean.contains("Home"));
+    }
+
+    #[test]
+    fn test_boilerplate_blocks_dropped_from_text() {
+        let html = r##"
+            <html>
+            <head><title>Rivers rise</title></head>
+            <body>
+                <article>
+                    <h1>Rivers rise across the valley</h1>
+                    <p>Heavy rain over the weekend pushed three rivers past their flood marks,
+                    and residents of the lower valley were told to move before dawn.</p>
+                    <div class="share"><a href="#">Share</a> <a href="#">Tweet</a></div>
+                    <div class="related"><ul>
+                        <li><a href="/a">Storm damage in the north</a></li>
+                        <li><a href="/b">How to prepare for floods</a></li>
+                    </ul></div>
+                </article>
+            </body>
+            </html>
+        "##;
+
+        let doc = NativeHtmlParser::new()
+            .parse_headless_html(html, "https://example.com/news/")
+            .unwrap();
+        assert!(doc.text.contains("Storm damage"));
+
+        let parser = NativeHtmlParser::with_config(ParserConfig {
+            boilerplate: Some(Default::default()),
+            ..Default::default()
+        });
+        let text = parser
+            .parse_headless_html(html, "https://example.com/news/")
+            .unwrap()
+            .text;
+        assert!(text.starts_with("Rivers rise across the valley\nHeavy rain"));
+        assert!(!text.contains("Storm damage") && !text.contains("Tweet"));
+    }
+
+    #[test]
+    fn test_language_spans_of_mixed_page() {
+        let html = r#"
+            <html lang="en">
+            <head><title>Cherry blossoms</title></head>
+            <body>
+                <article>
+                    <p>This story is available in Japanese only. Sign in to read the rest of the archive.</p>
+                    <p>東京の桜は今年、例年より早く満開になりました。週末には多くの人が公園を訪れています。</p>
+                    <p>気象庁によると、来週は気温が下がる見込みです。</p>
+                </article>
+            </body>
+            </html>
+        "#;
+
+        let doc = NativeHtmlParser::new()
+            .parse_headless_html(html, "https://example.com/ja/sakura")
+            .unwrap();
+        assert_eq!(doc.language.as_deref(), Some("en"));
+        let languages: Vec<_> = doc
+            .language_spans
+            .iter()
+            .map(|s| s.language.as_str())
+            .collect();
+        assert_eq!(languages, ["en", "ja"]);
+        let japanese = &doc.language_spans[1];
+        assert!(doc.text[japanese.start..japanese.end].starts_with("東京の桜"));
+
+        let parser = NativeHtmlParser::with_config(ParserConfig {
+            detect_language: false,
+            ..Default::default()
+        });
+        let doc = parser
+            .parse_headless_html(html, "https://example.com/ja/sakura")
+            .unwrap();
+        assert!(doc.language.is_none() && doc.language_spans.is_empty());
+    }
+
+    #[test]
+    fn test_embedded_markup_handling() {
+        let html = r#"
+            <html>
+            <head><title>Mass-energy equivalence</title></head>
+            <body>
+                <article>
+                    <p>The rest energy of a body is <math><mi>E</mi><mo>=</mo><mi>m</mi><msup><mi>c</mi><mn>2</mn></msup></math>, where c is the speed of light in vacuum and m the rest mass.</p>
+                    <p>Figure 1: <svg role="img"><title>Energy against mass</title><path d="M0 0L9 9"/></svg></p>
+                </article>
+            </body>
+            </html>
+        "#;
+        let text = |embedded_markup| {
+            NativeHtmlParser::with_config(ParserConfig {
+                embedded_markup,
+                ..Default::default()
+            })
+            .parse_headless_html(html, "https://example.com/emc2")
+            .unwrap()
+            .text
+        };
+
+        assert!(text(EmbeddedMarkup::Text).contains("is E=mc2,"));
+        assert!(text(EmbeddedMarkup::Latex).contains("is $E=mc^2$,"));
+        assert!(text(EmbeddedMarkup::Alt).contains("Energy against mass"));
+        assert!(text(EmbeddedMarkup::Raw).contains("<msup><mi>c</mi><mn>2</mn></msup>"));
+    }
+
+    #[test]
+    fn test_quality_report_explains_score() {
+        use riptide_types::quality::QualitySignalKind;
+
+        let html = r#"
+            <html>
+            <head><title>Tidal energy in the North Sea | Example News</title></head>
+            <body>
+                <article>
+                    <h1>Tidal energy in the North Sea</h1>
+                    <p>Tidal energy projects in the North Sea delivered more power this winter than in any year before.</p>
+                    <p>Engineers credit turbines that keep turning in slower currents and cables that are cheaper to lay.</p>
+                    <p>The operators expect the next round of projects to double the capacity installed along the coast.</p>
+                </article>
+                <div class="ad"><a href="/offer">Offer</a></div>
+            </body>
+            </html>
+        "#;
+
+        let doc = NativeHtmlParser::new()
+            .parse_headless_html(html, "https://example.com/tidal")
+            .unwrap();
+        let report = doc.quality_report.as_ref().unwrap();
+
+        assert_eq!(doc.quality_score, Some(report.score));
+        assert_eq!(report.signals.iter().map(|s| s.weight).sum::<f64>(), 100.0);
+        // The site name after the separator is not part of the headline
+        assert_eq!(
+            report.signal(QualitySignalKind::TitleMatch).unwrap().score,
+            1.0
+        );
+        assert_eq!(
+            report.signal(QualitySignalKind::Paragraphs).unwrap().value,
+            3.0
+        );
+        assert_eq!(
+            report.signal(QualitySignalKind::AdDensity).unwrap().value,
+            0.25
+        );
+        assert_eq!(
+            report.shortfalls()[0].kind,
+            QualitySignalKind::ContentLength
+        );
+    }
+
+    #[test]
+    fn test_low_quality_error_names_weakest_signals() {
+        let html = r#"
+            <html>
+            <head><title>Site map</title></head>
+            <body>
+                <ul>
+                    <li><a href="/a">First section of the site</a></li>
+                    <li><a href="/b">Second section of the site</a></li>
+                    <li><a href="/c">Third section of the site</a></li>
+                </ul>
+            </body>
+            </html>
+        "#;
+
+        let err = NativeHtmlParser::with_config(ParserConfig {
+            min_quality_score: 60,
+            ..Default::default()
+        })
+        .parse_headless_html(html, "https://example.com/links")
+        .unwrap_err();
+
+        assert!(matches!(
+            &err,
+            crate::native_parser::NativeParserError::LowQuality { weakest, .. }
+                if weakest.starts_with("content_length (")
+        ));
+        assert!(err.to_string().contains("link_density ("));
+    }
 }
@@ -0,0 +1,617 @@
+//! Heuristic named-entity recognition
+//!
+//! Dates and amounts of money are matched with patterns. Names are runs of
+//! capitalized words, classified by the words in and around them:
+//! honorifics and speech verbs mark people, legal suffixes and acronyms
+//! organizations, a small gazetteer and place suffixes locations. A later
+//! mention of a person's surname counts towards the full name.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use riptide_types::{EntityKind, NamedEntity};
+
+use super::keywords::is_stopword;
+
+/// Titles before a person's name; they are dropped from the entity text
+const HONORIFICS: &[&str] = &[
+    "mr",
+    "mrs",
+    "ms",
+    "miss",
+    "dr",
+    "prof",
+    "professor",
+    "sir",
+    "dame",
+    "lord",
+    "lady",
+    "rev",
+    "president",
+    "senator",
+    "governor",
+    "minister",
+    "chancellor",
+    "mayor",
+    "judge",
+    "justice",
+    "gen",
+    "general",
+    "capt",
+    "captain",
+    "king",
+    "queen",
+    "prince",
+    "princess",
+    "pope",
+];
+
+/// Verbs that follow a person's name in reported speech
+const SPEECH_VERBS: &[&str] = &[
+    "said",
+    "says",
+    "told",
+    "added",
+    "explained",
+    "wrote",
+    "argued",
+    "noted",
+    "asked",
+    "replied",
+];
+
+/// Last words of organization names
+const ORG_SUFFIXES: &[&str] = &[
+    "inc",
+    "corp",
+    "corporation",
+    "ltd",
+    "llc",
+    "llp",
+    "plc",
+    "gmbh",
+    "ag",
+    "sa",
+    "nv",
+    "co",
+    "company",
+    "group",
+    "holdings",
+    "partners",
+    "bank",
+    "university",
+    "college",
+    "institute",
+    "foundation",
+    "association",
+    "agency",
+    "ministry",
+    "department",
+    "council",
+    "committee",
+    "commission",
+    "party",
+    "club",
+    "fc",
+    "labs",
+    "technologies",
+    "systems",
+    "airlines",
+    "news",
+    "times",
+    "post",
+];
+
+/// First words of organization names ("University of ...", "Bank of ...")
+const ORG_HEADS: &[&str] = &[
+    "university",
+    "bank",
+    "ministry",
+    "department",
+    "institute",
+    "museum",
+    "federal",
+    "national",
+];
+
+/// Last words of place names
+const PLACE_SUFFIXES: &[&str] = &[
+    "city",
+    "county",
+    "state",
+    "province",
+    "river",
+    "lake",
+    "mountain",
+    "mountains",
+    "island",
+    "islands",
+    "valley",
+    "bay",
+    "ocean",
+    "sea",
+    "street",
+    "avenue",
+    "road",
+    "square",
+    "park",
+];
+
+/// Well-known places, checked before other rules since they look like
+/// acronyms ("US") or organizations ("United States")
+const PLACES: &[&str] = &[
+    "Africa",
+    "Amsterdam",
+    "Antarctica",
+    "Argentina",
+    "Asia",
+    "Australia",
+    "Austria",
+    "Beijing",
+    "Belgium",
+    "Berlin",
+    "Brazil",
+    "Brussels",
+    "Canada",
+    "Chicago",
+    "Chile",
+    "China",
+    "Colombia",
+    "Denmark",
+    "Dubai",
+    "Egypt",
+    "England",
+    "Europe",
+    "Finland",
+    "France",
+    "Germany",
+    "Greece",
+    "Hong Kong",
+    "India",
+    "Indonesia",
+    "Iran",
+    "Iraq",
+    "Ireland",
+    "Israel",
+    "Istanbul",
+    "Italy",
+    "Japan",
+    "Kenya",
+    "London",
+    "Los Angeles",
+    "Madrid",
+    "Mexico",
+    "Moscow",
+    "Mumbai",
+    "Netherlands",
+    "New York",
+    "New Zealand",
+    "Nigeria",
+    "North America",
+    "Norway",
+    "Pakistan",
+    "Paris",
+    "Poland",
+    "Portugal",
+    "Rome",
+    "Russia",
+    "San Francisco",
+    "Saudi Arabia",
+    "Scotland",
+    "Seoul",
+    "Shanghai",
+    "Singapore",
+    "South Africa",
+    "South America",
+    "South Korea",
+    "Spain",
+    "Sweden",
+    "Switzerland",
+    "Sydney",
+    "Tokyo",
+    "Toronto",
+    "Turkey",
+    "UK",
+    "US",
+    "USA",
+    "Ukraine",
+    "United Kingdom",
+    "United States",
+    "Vienna",
+    "Vietnam",
+    "Wales",
+    "Washington",
+];
+
+/// Lowercase words that may join capitalized words inside one name
+const CONNECTORS: &[&str] = &[
+    "of", "de", "del", "da", "van", "von", "der", "den", "la", "le", "&",
+];
+
+/// Month and weekday names, which are capitalized but not names on their own
+const CALENDAR_WORDS: &[&str] = &[
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+/// Abbreviations whose trailing period does not end a sentence
+const ABBREVIATIONS: &[&str] = &["inc", "corp", "co", "ltd", "st", "jr", "sr", "vs", "no"];
+
+fn date_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        let month = "(?:Jan(?:uary)?|Feb(?:ruary)?|Mar(?:ch)?|Apr(?:il)?|May|June?|July?|\
+                     Aug(?:ust)?|Sep(?:t(?:ember)?)?|Oct(?:ober)?|Nov(?:ember)?|Dec(?:ember)?)";
+        Regex::new(&format!(
+            r"\b(?:{month}\.?\s+\d{{1,2}}(?:st|nd|rd|th)?,?\s+\d{{4}}|\d{{1,2}}(?:st|nd|rd|th)?\s+{month}\.?,?\s+\d{{4}}|\d{{4}}-\d{{2}}-\d{{2}})\b"
+        ))
+        .expect("valid date pattern")
+    })
+}
+
+fn money_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        Regex::new(
+            r"(?:[$€£¥]\s?\d[\d,]*(?:\.\d+)?(?:\s?(?:million|billion|trillion|thousand|bn|[mbk])\b)?|\b\d[\d,]*(?:\.\d+)?\s?(?:(?:million|billion|trillion)\s)?(?:USD|EUR|GBP|JPY|dollars|euros|pounds|yen)\b)",
+        )
+        .expect("valid money pattern")
+    })
+}
+
+/// A whitespace-separated word with its punctuation stripped
+struct Token<'a> {
+    word: &'a str,
+    offset: usize,
+    starts_sentence: bool,
+    /// Leading quote or bracket: a name cannot continue into this token
+    opens: bool,
+    /// Trailing punctuation or possessive: a name cannot continue past it
+    closes: bool,
+}
+
+#[derive(Default)]
+struct Candidate {
+    first_offset: usize,
+    mentions: u32,
+    votes: HashMap<EntityKind, u32>,
+    words: usize,
+    /// Seen somewhere other than the first word of a sentence
+    seen_mid_sentence: bool,
+}
+
+impl Candidate {
+    /// Most voted kind, preferring any specific kind over `Other`
+    fn kind(&self) -> EntityKind {
+        self.votes
+            .iter()
+            .filter(|(kind, _)| **kind != EntityKind::Other)
+            .max_by_key(|(kind, votes)| (**votes, std::cmp::Reverse(kind.as_str())))
+            .map(|(kind, _)| *kind)
+            .unwrap_or(EntityKind::Other)
+    }
+}
+
+/// Entities of `text`, most mentioned first, at most `limit`
+pub(super) fn extract(text: &str, limit: usize) -> Vec<NamedEntity> {
+    let mut candidates: HashMap<String, Candidate> = HashMap::new();
+    let mut record = |name: String, kind: EntityKind, offset: usize, words: usize, mid: bool| {
+        let candidate = candidates.entry(name).or_insert_with(|| Candidate {
+            first_offset: offset,
+            words,
+            ..Default::default()
+        });
+        candidate.mentions += 1;
+        *candidate.votes.entry(kind).or_default() += 1;
+        candidate.seen_mid_sentence |= mid;
+    };
+
+    for (pattern, kind) in [
+        (date_pattern(), EntityKind::Date),
+        (money_pattern(), EntityKind::Money),
+    ] {
+        for m in pattern.find_iter(text) {
+            let name = m.as_str().split_whitespace().collect::<Vec<_>>().join(" ");
+            record(name, kind, m.start(), 1, true);
+        }
+    }
+
+    let tokens = tokenize(text);
+    let mut i = 0;
+    while i < tokens.len() {
+        if !is_capitalized(tokens[i].word) || tokens[i].word.chars().any(|c| c.is_numeric()) {
+            i += 1;
+            continue;
+        }
+        let end = run_end(&tokens, i);
+        let run = &tokens[i..end];
+        let previous = (i > 0 && !run[0].starts_sentence && !tokens[i - 1].closes)
+            .then(|| tokens[i - 1].word.to_lowercase());
+        let next = tokens
+            .get(end)
+            .filter(|_| !run[run.len() - 1].closes)
+            .map(|token| token.word.to_lowercase());
+        i = end;
+
+        let mut words: Vec<&str> = run.iter().map(|token| token.word).collect();
+        let mut honorific = false;
+        while let Some(first) = words.first() {
+            let lower = first.to_lowercase();
+            // "New" in "New York" is part of the name
+            if PLACES.contains(&words.join(" ").as_str()) {
+                break;
+            } else if HONORIFICS.contains(&lower.as_str()) && words.len() > 1 {
+                honorific = true;
+            } else if !(is_stopword(&lower) || CALENDAR_WORDS.contains(&lower.as_str())) {
+                break;
+            }
+            words.remove(0);
+        }
+        while let Some(last) = words.last() {
+            let lower = last.to_lowercase();
+            if !(is_stopword(&lower) || CALENDAR_WORDS.contains(&lower.as_str())) {
+                break;
+            }
+            words.pop();
+        }
+        if words.is_empty() {
+            continue;
+        }
+
+        let name = words.join(" ");
+        let stripped = run.len() - words.len();
+        let mid_sentence = !run[0].starts_sentence || stripped > 0;
+        let kind = classify(&words, honorific, previous.as_deref(), next.as_deref());
+        record(name, kind, run[0].offset, words.len(), mid_sentence);
+    }
+
+    finish(candidates, limit)
+}
+
+/// Merge surnames into full names, drop weak candidates and rank the rest
+fn finish(mut candidates: HashMap<String, Candidate>, limit: usize) -> Vec<NamedEntity> {
+    let people: Vec<(String, u32)> = candidates
+        .iter()
+        .filter(|(_, c)| c.words > 1 && c.kind() == EntityKind::Person)
+        .map(|(name, c)| (name.clone(), c.mentions))
+        .collect();
+    let singles: Vec<String> = candidates
+        .iter()
+        .filter(|(_, c)| c.words == 1)
+        .map(|(name, _)| name.clone())
+        .collect();
+    for single in singles {
+        // The most mentioned person with this first or last name
+        let owner = people
+            .iter()
+            .filter(|(name, _)| {
+                name.rsplit(' ').next() == Some(single.as_str())
+                    || name.split(' ').next() == Some(single.as_str())
+            })
+            .max_by_key(|(name, mentions)| (*mentions, std::cmp::Reverse(name.clone())))
+            .map(|(name, _)| name.clone());
+        if let Some(owner) = owner {
+            if let Some(short) = candidates.remove(&single) {
+                if let Some(full) = candidates.get_mut(&owner) {
+                    full.mentions += short.mentions;
+                }
+            }
+        }
+    }
+
+    let mut entities: Vec<(NamedEntity, usize)> = candidates
+        .into_iter()
+        .filter_map(|(text, candidate)| {
+            let kind = candidate.kind();
+            // A lone capitalized word only ever seen opening a sentence is
+            // most likely just a sentence opener
+            let unconfirmed = candidate.words == 1 && !candidate.seen_mid_sentence;
+            if kind == EntityKind::Other && (unconfirmed || candidate.mentions < 2) {
+                return None;
+            }
+            Some((
+                NamedEntity {
+                    text,
+                    kind,
+                    mentions: candidate.mentions,
+                },
+                candidate.first_offset,
+            ))
+        })
+        .collect();
+    entities.sort_by(|a, b| b.0.mentions.cmp(&a.0.mentions).then(a.1.cmp(&b.1)));
+    entities
+        .into_iter()
+        .take(limit)
+        .map(|(entity, _)| entity)
+        .collect()
+}
+
+fn classify(
+    words: &[&str],
+    honorific: bool,
+    previous: Option<&str>,
+    next: Option<&str>,
+) -> EntityKind {
+    let name = words.join(" ");
+    let first = words[0].to_lowercase();
+    let last = words[words.len() - 1].to_lowercase();
+
+    if honorific {
+        EntityKind::Person
+    } else if PLACES.contains(&name.as_str()) {
+        EntityKind::Location
+    } else if ORG_SUFFIXES.contains(&last.as_str())
+        || (words.len() > 1 && ORG_HEADS.contains(&first.as_str()))
+        || words.contains(&"&")
+        || (words.len() == 1 && is_acronym(words[0]))
+    {
+        EntityKind::Organization
+    } else if PLACE_SUFFIXES.contains(&last.as_str()) && words.len() > 1 {
+        EntityKind::Location
+    } else if (2..=3).contains(&words.len())
+        && (next.is_some_and(|next| SPEECH_VERBS.contains(&next)) || previous == Some("by"))
+    {
+        EntityKind::Person
+    } else if matches!(previous, Some("in" | "near" | "across" | "outside")) {
+        EntityKind::Location
+    } else {
+        EntityKind::Other
+    }
+}
+
+/// End (exclusive) of the name starting at `start`
+fn run_end(tokens: &[Token], start: usize) -> usize {
+    let mut end = start + 1;
+    while end < tokens.len() && !tokens[end - 1].closes {
+        let next = &tokens[end];
+        if next.opens || next.starts_sentence {
+            break;
+        }
+        if is_capitalized(next.word) {
+            end += 1;
+            continue;
+        }
+        let joins = CONNECTORS.contains(&next.word)
+            && !next.closes
+            && tokens
+                .get(end + 1)
+                .is_some_and(|after| !after.opens && is_capitalized(after.word));
+        if !joins {
+            break;
+        }
+        end += 2;
+    }
+    end
+}
+
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut previous_end = 0;
+    let mut sentence_ended = true;
+    for raw in text.split_whitespace() {
+        let offset = raw.as_ptr() as usize - text.as_ptr() as usize;
+        let starts_sentence = sentence_ended || text[previous_end..offset].contains('\n');
+        previous_end = offset + raw.len();
+
+        let core = raw.trim_matches(|c: char| !c.is_alphanumeric() && c != '&');
+        if core.is_empty() {
+            // Free-standing punctuation such as a dash ends any name
+            if let Some(last) = tokens.last_mut() {
+                last.closes = true;
+            }
+            sentence_ended |= raw.contains(['.', '!', '?']);
+            continue;
+        }
+        let word = core
+            .strip_suffix("'s")
+            .or_else(|| core.strip_suffix("’s"))
+            .unwrap_or(core);
+        let trailing = &raw[raw.find(core).unwrap_or(0) + core.len()..];
+        let abbreviation = trailing.starts_with('.')
+            && (ABBREVIATIONS.contains(&word.to_lowercase().as_str())
+                || HONORIFICS.contains(&word.to_lowercase().as_str())
+                || word.chars().count() == 1);
+
+        sentence_ended = !abbreviation && trailing.contains(['.', '!', '?']);
+        tokens.push(Token {
+            word,
+            offset,
+            starts_sentence,
+            opens: !raw.starts_with(core),
+            closes: word.len() < core.len() || (!trailing.is_empty() && !abbreviation),
+        });
+    }
+    tokens
+}
+
+fn is_capitalized(word: &str) -> bool {
+    let mut chars = word.chars();
+    chars.next().is_some_and(char::is_uppercase) && chars.any(char::is_alphabetic)
+}
+
+fn is_acronym(word: &str) -> bool {
+    (2..=6).contains(&word.len()) && word.chars().all(|c| c.is_ascii_uppercase() || c == '&')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(entities: &'a [NamedEntity], text: &str) -> Option<&'a NamedEntity> {
+        entities.iter().find(|entity| entity.text == text)
+    }
+
+    #[test]
+    fn test_people_organizations_and_places() {
+        let text = "Dr. Jane Goodall spoke at the University of Oxford on Monday. \
+                    Goodall said the Acme Corp. donation of $2.5 million would fund \
+                    research in Tanzania. Later, Acme Corp announced a partnership \
+                    with NASA in New York on March 3, 2024.\n\
+                    Goodall thanked the crowd.";
+        let entities = extract(text, 20);
+
+        let jane = find(&entities, "Jane Goodall").expect("person");
+        assert_eq!(jane.kind, EntityKind::Person);
+        assert_eq!(jane.mentions, 3);
+        assert!(find(&entities, "Goodall").is_none());
+
+        assert_eq!(
+            find(&entities, "University of Oxford").map(|e| e.kind),
+            Some(EntityKind::Organization)
+        );
+        let acme = find(&entities, "Acme Corp").expect("organization");
+        assert_eq!((acme.kind, acme.mentions), (EntityKind::Organization, 2));
+        assert_eq!(
+            find(&entities, "NASA").map(|e| e.kind),
+            Some(EntityKind::Organization)
+        );
+        assert_eq!(
+            find(&entities, "Tanzania").map(|e| e.kind),
+            Some(EntityKind::Location)
+        );
+        assert_eq!(
+            find(&entities, "New York").map(|e| e.kind),
+            Some(EntityKind::Location)
+        );
+        assert_eq!(
+            find(&entities, "$2.5 million").map(|e| e.kind),
+            Some(EntityKind::Money)
+        );
+        assert_eq!(
+            find(&entities, "March 3, 2024").map(|e| e.kind),
+            Some(EntityKind::Date)
+        );
+
+        assert_eq!(entities[0].text, "Jane Goodall");
+        assert!(find(&entities, "Later").is_none());
+        assert!(find(&entities, "Monday").is_none());
+    }
+
+    #[test]
+    fn test_sentence_openers_are_not_entities() {
+        let entities = extract(
+            "Yesterday it rained. Nothing else happened. Rain is wet.",
+            10,
+        );
+        assert!(entities.is_empty(), "{:?}", entities);
+    }
+}
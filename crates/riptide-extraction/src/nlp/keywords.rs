@@ -0,0 +1,308 @@
+//! RAKE-style keyword extraction
+//!
+//! Candidate phrases are runs of content words between stopwords and
+//! punctuation. Each word scores its degree (co-occurrences within
+//! candidates) over its frequency, a phrase the sum of its words, so
+//! multi-word phrases that recur together rank above frequent filler words.
+
+use std::collections::HashMap;
+
+use riptide_types::Keyword;
+
+/// Longest candidate phrase, in words
+const MAX_PHRASE_WORDS: usize = 3;
+
+/// Factor applied to phrases whose words all appear in the title
+const TITLE_BOOST: f32 = 1.5;
+
+/// English function words and filler that never start or end a key phrase
+pub(super) const STOPWORDS: &[&str] = &[
+    "a",
+    "about",
+    "above",
+    "after",
+    "again",
+    "against",
+    "all",
+    "also",
+    "am",
+    "an",
+    "and",
+    "any",
+    "are",
+    "as",
+    "at",
+    "be",
+    "because",
+    "been",
+    "before",
+    "being",
+    "below",
+    "between",
+    "both",
+    "but",
+    "by",
+    "can",
+    "could",
+    "did",
+    "do",
+    "does",
+    "doing",
+    "down",
+    "during",
+    "each",
+    "even",
+    "ever",
+    "every",
+    "few",
+    "for",
+    "from",
+    "further",
+    "get",
+    "gets",
+    "got",
+    "had",
+    "has",
+    "have",
+    "having",
+    "he",
+    "her",
+    "here",
+    "hers",
+    "herself",
+    "him",
+    "himself",
+    "his",
+    "how",
+    "however",
+    "i",
+    "if",
+    "in",
+    "into",
+    "is",
+    "it",
+    "its",
+    "itself",
+    "just",
+    "less",
+    "like",
+    "made",
+    "make",
+    "many",
+    "may",
+    "me",
+    "might",
+    "more",
+    "most",
+    "much",
+    "must",
+    "my",
+    "myself",
+    "new",
+    "no",
+    "nor",
+    "not",
+    "now",
+    "of",
+    "off",
+    "on",
+    "once",
+    "one",
+    "only",
+    "or",
+    "other",
+    "our",
+    "ours",
+    "ourselves",
+    "out",
+    "over",
+    "own",
+    "said",
+    "same",
+    "says",
+    "she",
+    "should",
+    "since",
+    "so",
+    "some",
+    "still",
+    "such",
+    "than",
+    "that",
+    "the",
+    "their",
+    "theirs",
+    "them",
+    "themselves",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "through",
+    "to",
+    "too",
+    "two",
+    "under",
+    "until",
+    "up",
+    "upon",
+    "us",
+    "use",
+    "used",
+    "using",
+    "very",
+    "was",
+    "way",
+    "we",
+    "well",
+    "were",
+    "what",
+    "when",
+    "where",
+    "whether",
+    "which",
+    "while",
+    "who",
+    "whom",
+    "whose",
+    "why",
+    "will",
+    "with",
+    "within",
+    "without",
+    "would",
+    "yet",
+    "you",
+    "your",
+    "yours",
+    "yourself",
+    "yourselves",
+];
+
+pub(super) fn is_stopword(word: &str) -> bool {
+    STOPWORDS.binary_search(&word).is_ok()
+}
+
+/// Top `limit` keywords of `text`, boosting phrases found in `title`
+pub(super) fn extract(title: Option<&str>, text: &str, limit: usize) -> Vec<Keyword> {
+    let phrases = candidates(text);
+    if phrases.is_empty() || limit == 0 {
+        return Vec::new();
+    }
+
+    let mut frequency: HashMap<&str, f32> = HashMap::new();
+    let mut degree: HashMap<&str, f32> = HashMap::new();
+    for phrase in &phrases {
+        for word in phrase {
+            *frequency.entry(word.as_str()).or_default() += 1.0;
+            *degree.entry(word.as_str()).or_default() += phrase.len() as f32;
+        }
+    }
+
+    let title_words: Vec<String> = title.map(words).unwrap_or_default();
+    let mut scores: HashMap<String, (f32, usize)> = HashMap::new();
+    for (position, phrase) in phrases.iter().enumerate() {
+        let term = phrase.join(" ");
+        if scores.contains_key(&term) {
+            continue;
+        }
+        let mut score: f32 = phrase
+            .iter()
+            .map(|word| degree[word.as_str()] / frequency[word.as_str()])
+            .sum();
+        // Phrases that occur once are rarely what a page is about
+        let occurrences = phrases.iter().filter(|p| *p == phrase).count();
+        if occurrences > 1 {
+            score *= 1.0 + (occurrences as f32).ln();
+        }
+        if phrase.iter().all(|word| title_words.contains(word)) {
+            score *= TITLE_BOOST;
+        }
+        scores.insert(term, (score, position));
+    }
+
+    let mut ranked: Vec<(String, f32, usize)> = scores
+        .into_iter()
+        .map(|(term, (score, position))| (term, score, position))
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.2.cmp(&b.2)));
+    ranked.truncate(limit);
+
+    let top = ranked.first().map(|(_, score, _)| *score).unwrap_or(1.0);
+    ranked
+        .into_iter()
+        .map(|(term, score, _)| Keyword {
+            term,
+            score: (score / top * 100.0).round() / 100.0,
+        })
+        .collect()
+}
+
+/// Candidate phrases: runs of content words, split at stopwords,
+/// punctuation and the phrase length limit
+fn candidates(text: &str) -> Vec<Vec<String>> {
+    let mut phrases = Vec::new();
+    for fragment in text.split(|c: char| {
+        !(c.is_alphanumeric() || c.is_whitespace() || c == '-' || c == '\'' || c == '’')
+    }) {
+        let mut phrase: Vec<String> = Vec::new();
+        for word in words(fragment) {
+            if is_content_word(&word) {
+                phrase.push(word);
+                if phrase.len() == MAX_PHRASE_WORDS {
+                    phrases.push(std::mem::take(&mut phrase));
+                }
+            } else if !phrase.is_empty() {
+                phrases.push(std::mem::take(&mut phrase));
+            }
+        }
+        if !phrase.is_empty() {
+            phrases.push(phrase);
+        }
+    }
+    phrases
+}
+
+/// Lowercased words, with surrounding apostrophes and hyphens trimmed
+fn words(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .trim_end_matches("'s")
+                .trim_end_matches("’s")
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn is_content_word(word: &str) -> bool {
+    word.chars().count() > 2 && !is_stopword(word) && !word.chars().all(|c| c.is_numeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stopwords_sorted_for_binary_search() {
+        assert!(STOPWORDS.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_recurring_phrases_rank_first() {
+        let text = "Memory safety is the default in Rust. With memory safety, the \
+                    borrow checker is the price. The borrow checker is strict, and \
+                    memory safety is worth it. The weather was nice.";
+        let keywords = extract(Some("Memory safety in Rust"), text, 5);
+
+        assert_eq!(keywords.len(), 5);
+        assert_eq!(keywords[0].term, "memory safety");
+        assert_eq!(keywords[0].score, 1.0);
+        assert_eq!(keywords[1].term, "borrow checker");
+        assert!(keywords[1].score < 1.0);
+        assert!(keywords.iter().all(|k| !k.term.split(' ').any(is_stopword)));
+    }
+}
@@ -0,0 +1,141 @@
+//! Rule-based named entity, keyword and topic extraction
+//!
+//! [`RuleBasedAnnotator`] implements the `TextAnnotator` port without a
+//! model: entities come from capitalization and surrounding-word rules,
+//! keywords from RAKE-style phrase scoring and topics from cue-word
+//! lexicons. The rules are tuned for English; other languages still get
+//! keywords and pattern entities (dates, money), with noisier names.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_extraction::nlp::RuleBasedAnnotator;
+//!
+//! let annotations = RuleBasedAnnotator::new().annotate_text(
+//!     Some("Rust in production"),
+//!     "Ferris Crab said Rust Foundation members ship memory safety to production.",
+//! );
+//! assert!(annotations.entities.iter().any(|e| e.text == "Ferris Crab"));
+//! ```
+
+mod entities;
+mod keywords;
+mod topics;
+
+use async_trait::async_trait;
+use riptide_types::ports::TextAnnotator;
+use riptide_types::TextAnnotations;
+
+/// Name recorded on documents annotated by [`RuleBasedAnnotator`]
+pub const RULE_BASED_ANNOTATOR: &str = "rules";
+
+/// Annotates text with heuristics, no model required
+#[derive(Debug, Clone)]
+pub struct RuleBasedAnnotator {
+    max_entities: usize,
+    max_keywords: usize,
+    max_topics: usize,
+}
+
+impl Default for RuleBasedAnnotator {
+    fn default() -> Self {
+        Self {
+            max_entities: 25,
+            max_keywords: 15,
+            max_topics: 3,
+        }
+    }
+}
+
+impl RuleBasedAnnotator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep at most this many entities, keywords and topics per document
+    pub fn with_limits(
+        mut self,
+        max_entities: usize,
+        max_keywords: usize,
+        max_topics: usize,
+    ) -> Self {
+        self.max_entities = max_entities;
+        self.max_keywords = max_keywords;
+        self.max_topics = max_topics;
+        self
+    }
+
+    /// Annotate `text`, using `title` to boost keywords and topics
+    pub fn annotate_text(&self, title: Option<&str>, text: &str) -> TextAnnotations {
+        TextAnnotations {
+            entities: entities::extract(text, self.max_entities),
+            keywords: keywords::extract(title, text, self.max_keywords),
+            topics: topics::detect(title, text, self.max_topics),
+            annotator: RULE_BASED_ANNOTATOR.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl TextAnnotator for RuleBasedAnnotator {
+    async fn annotate(
+        &self,
+        title: Option<&str>,
+        text: &str,
+        _language: Option<&str>,
+    ) -> riptide_types::Result<TextAnnotations> {
+        Ok(self.annotate_text(title, text))
+    }
+
+    fn name(&self) -> &str {
+        RULE_BASED_ANNOTATOR
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::EntityKind;
+
+    #[tokio::test]
+    async fn test_annotates_entities_keywords_and_topics() {
+        let text = "Researchers at the Max Planck Institute published a study on \
+                    climate emissions. Anna Weber said carbon emissions from \
+                    forest fires doubled. Weber presented the study in Berlin.";
+        let annotations = RuleBasedAnnotator::new()
+            .annotate(Some("Forest fires and carbon emissions"), text, Some("en"))
+            .await
+            .unwrap();
+
+        assert_eq!(annotations.annotator, "rules");
+        assert_eq!(
+            annotations
+                .entities_of(EntityKind::Person)
+                .collect::<Vec<_>>(),
+            vec!["Anna Weber"]
+        );
+        assert!(annotations
+            .entities_of(EntityKind::Organization)
+            .any(|org| org == "Max Planck Institute"));
+        assert!(annotations
+            .entities_of(EntityKind::Location)
+            .any(|place| place == "Berlin"));
+        assert!(annotations
+            .keywords
+            .iter()
+            .any(|k| k.term.contains("emissions")));
+        assert!(annotations.topics.contains(&"environment".to_string()));
+    }
+
+    #[test]
+    fn test_limits_and_empty_text() {
+        let annotator = RuleBasedAnnotator::new().with_limits(0, 1, 0);
+        let annotations = annotator.annotate_text(None, "Alpha beta gamma. Delta epsilon.");
+        assert!(annotations.entities.is_empty());
+        assert_eq!(annotations.keywords.len(), 1);
+        assert!(annotations.topics.is_empty());
+
+        let empty = RuleBasedAnnotator::new().annotate_text(None, "");
+        assert!(empty.entities.is_empty() && empty.keywords.is_empty());
+    }
+}
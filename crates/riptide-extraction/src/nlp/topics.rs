@@ -0,0 +1,320 @@
+//! Lexicon-based topic detection
+//!
+//! Each topic has a list of cue words. A topic applies when the text uses
+//! several of its cue words, more than once in total; title words count
+//! double.
+
+use std::collections::HashMap;
+
+/// Topics and their cue words (lowercase, matched against whole words)
+const TOPICS: &[(&str, &[&str])] = &[
+    (
+        "technology",
+        &[
+            "software",
+            "hardware",
+            "app",
+            "apps",
+            "computer",
+            "internet",
+            "cloud",
+            "data",
+            "algorithm",
+            "ai",
+            "startup",
+            "developer",
+            "developers",
+            "programming",
+            "smartphone",
+            "chip",
+            "chips",
+            "cybersecurity",
+            "digital",
+            "robot",
+            "robots",
+        ],
+    ),
+    (
+        "business",
+        &[
+            "company",
+            "companies",
+            "revenue",
+            "profit",
+            "profits",
+            "market",
+            "markets",
+            "ceo",
+            "customers",
+            "sales",
+            "merger",
+            "acquisition",
+            "startup",
+            "investors",
+            "shares",
+            "earnings",
+            "industry",
+            "retail",
+        ],
+    ),
+    (
+        "finance",
+        &[
+            "bank",
+            "banks",
+            "stocks",
+            "stock",
+            "bond",
+            "bonds",
+            "inflation",
+            "interest",
+            "investment",
+            "investors",
+            "currency",
+            "loan",
+            "loans",
+            "crypto",
+            "bitcoin",
+            "fund",
+            "funds",
+            "economy",
+            "economic",
+        ],
+    ),
+    (
+        "politics",
+        &[
+            "government",
+            "election",
+            "elections",
+            "minister",
+            "president",
+            "parliament",
+            "congress",
+            "senate",
+            "policy",
+            "vote",
+            "voters",
+            "campaign",
+            "party",
+            "law",
+            "legislation",
+            "democrats",
+            "republicans",
+        ],
+    ),
+    (
+        "science",
+        &[
+            "research",
+            "researchers",
+            "scientists",
+            "study",
+            "experiment",
+            "physics",
+            "chemistry",
+            "biology",
+            "species",
+            "laboratory",
+            "space",
+            "nasa",
+            "planet",
+            "telescope",
+            "genome",
+            "theory",
+        ],
+    ),
+    (
+        "health",
+        &[
+            "health",
+            "patients",
+            "patient",
+            "doctor",
+            "doctors",
+            "hospital",
+            "disease",
+            "vaccine",
+            "treatment",
+            "medical",
+            "medicine",
+            "symptoms",
+            "cancer",
+            "virus",
+            "clinical",
+            "diet",
+            "mental",
+        ],
+    ),
+    (
+        "sports",
+        &[
+            "match",
+            "game",
+            "season",
+            "team",
+            "coach",
+            "league",
+            "tournament",
+            "championship",
+            "goal",
+            "goals",
+            "players",
+            "player",
+            "score",
+            "football",
+            "soccer",
+            "basketball",
+            "tennis",
+            "olympic",
+            "cup",
+        ],
+    ),
+    (
+        "entertainment",
+        &[
+            "film",
+            "movie",
+            "music",
+            "album",
+            "song",
+            "actor",
+            "actress",
+            "series",
+            "show",
+            "celebrity",
+            "festival",
+            "concert",
+            "streaming",
+            "netflix",
+            "director",
+            "premiere",
+        ],
+    ),
+    (
+        "travel",
+        &[
+            "travel",
+            "flight",
+            "flights",
+            "hotel",
+            "hotels",
+            "tourism",
+            "tourists",
+            "destination",
+            "airport",
+            "trip",
+            "vacation",
+            "holiday",
+            "beach",
+            "passport",
+        ],
+    ),
+    (
+        "food",
+        &[
+            "recipe",
+            "recipes",
+            "cooking",
+            "restaurant",
+            "chef",
+            "dish",
+            "ingredients",
+            "flavor",
+            "bake",
+            "baking",
+            "meal",
+            "dinner",
+            "cuisine",
+            "wine",
+        ],
+    ),
+    (
+        "education",
+        &[
+            "school",
+            "schools",
+            "students",
+            "student",
+            "teachers",
+            "teacher",
+            "university",
+            "education",
+            "learning",
+            "course",
+            "courses",
+            "curriculum",
+            "classroom",
+            "exam",
+        ],
+    ),
+    (
+        "environment",
+        &[
+            "climate",
+            "emissions",
+            "carbon",
+            "pollution",
+            "environment",
+            "environmental",
+            "renewable",
+            "energy",
+            "solar",
+            "wildlife",
+            "biodiversity",
+            "sustainability",
+            "warming",
+            "forest",
+        ],
+    ),
+];
+
+/// Minimum weighted cue-word hits for a topic to apply
+const MIN_HITS: u32 = 3;
+
+/// Minimum distinct cue words for a topic to apply
+const MIN_DISTINCT: usize = 2;
+
+/// Up to `limit` topics of the document, strongest first
+pub(super) fn detect(title: Option<&str>, text: &str, limit: usize) -> Vec<String> {
+    let mut counts: HashMap<String, u32> = HashMap::new();
+    for (source, weight) in [(title.unwrap_or_default(), 2), (text, 1)] {
+        for word in source
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+        {
+            *counts.entry(word.to_lowercase()).or_default() += weight;
+        }
+    }
+
+    let mut topics: Vec<(&str, u32)> = TOPICS
+        .iter()
+        .filter_map(|(topic, cues)| {
+            let hits: Vec<u32> = cues
+                .iter()
+                .filter_map(|cue| counts.get(*cue).copied())
+                .collect();
+            let total: u32 = hits.iter().sum();
+            (hits.len() >= MIN_DISTINCT && total >= MIN_HITS).then_some((*topic, total))
+        })
+        .collect();
+    topics.sort_by_key(|(_, hits)| std::cmp::Reverse(*hits));
+    topics
+        .into_iter()
+        .take(limit)
+        .map(|(topic, _)| topic.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_strongest_topics() {
+        let text = "The team won the match after the coach changed the season plan. \
+                    Players from the league celebrated the championship goal.";
+        assert_eq!(detect(Some("Cup final"), text, 3), vec!["sports"]);
+        assert!(detect(None, "A quiet afternoon with a book.", 3).is_empty());
+    }
+}
@@ -0,0 +1,412 @@
+//! Citation and reference extraction
+//!
+//! Academic pages list what they cite in several shapes. This module turns
+//! them into typed [`Reference`]s:
+//!
+//! - reference sections: `role="doc-bibliography"`, common publisher
+//!   classes and lists under a "References" or "Bibliography" heading
+//! - footnotes and endnotes: `role="doc-endnotes"`, `.footnotes` and
+//!   Wikipedia-style `ol.references`
+//! - `citation_reference` meta tags publishers add for Google Scholar, when
+//!   the markup has no reference section
+//! - links in the body to DOI and arXiv resolvers
+//!
+//! References sharing a DOI, arXiv ID or URL are merged, so a body link to a
+//! paper listed in the bibliography only fills in that entry.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_extraction::references::{extract_references, ReferenceKind};
+//!
+//! let html = r#"<p>As shown by <a href="https://doi.org/10.1038/nature14539">LeCun</a>.</p>
+//!     <h2>References</h2>
+//!     <ol><li>LeCun, Y. et al. Deep learning. Nature 521 (2015).
+//!         <a href="https://doi.org/10.1038/nature14539">doi</a></li></ol>"#;
+//!
+//! let references = extract_references(html, "https://example.org/paper");
+//! assert_eq!(references.len(), 1);
+//! assert_eq!(references[0].kind, ReferenceKind::Bibliography);
+//! assert_eq!(references[0].doi.as_deref(), Some("10.1038/nature14539"));
+//! assert_eq!(references[0].year, Some(2015));
+//! ```
+
+use scraper::{ElementRef, Html, Selector};
+use url::Url;
+
+use riptide_types::references::{find_doi, is_reference_heading, push_reference};
+pub use riptide_types::references::{Reference, ReferenceKind};
+
+/// Footnote and endnote containers
+const FOOTNOTE_CONTAINERS: &str =
+    "[role=doc-endnotes], [role=doc-footnotes], .footnotes, .endnotes, ol.references";
+
+/// Reference section containers
+const BIBLIOGRAPHY_CONTAINERS: &str = "[role=doc-bibliography], #references, #bibliography, \
+     .references, .bibliography, .ref-list, .c-article-references";
+
+/// Characters footnotes use to link back to their marker
+const BACKLINK_MARKS: &[char] = &['^', '↑', '↩', '\u{fe0e}'];
+
+/// Parse `html` and extract the works it cites
+pub fn extract_references(html: &str, url: &str) -> Vec<Reference> {
+    ReferencesExtractor::extract(&Html::parse_document(html), url)
+}
+
+/// Extracts [`Reference`]s from page markup
+pub struct ReferencesExtractor;
+
+impl ReferencesExtractor {
+    /// Extract references from a parsed document, resolving relative links
+    /// against `url`
+    ///
+    /// Section entries come first in document order, then meta tag
+    /// references, then citation links from the body.
+    pub fn extract(document: &Html, url: &str) -> Vec<Reference> {
+        let base = Url::parse(url).ok();
+        let base = base.as_ref();
+
+        let mut references = Vec::new();
+        let sections = Self::sections(document);
+        for (entries, kind) in &sections {
+            for (index, entry) in entries.iter().enumerate() {
+                if let Some(reference) = Self::entry(*entry, *kind, index, base) {
+                    push_reference(&mut references, reference);
+                }
+            }
+        }
+
+        if references.is_empty() {
+            for reference in Self::citation_meta(document) {
+                push_reference(&mut references, reference);
+            }
+        }
+
+        let Ok(links) = Selector::parse("a[href]") else {
+            return references;
+        };
+        let listed: Vec<ElementRef> = sections.iter().flat_map(|(e, _)| e.clone()).collect();
+        for link in document.select(&links) {
+            if link
+                .ancestors()
+                .any(|a| listed.iter().any(|entry| entry.id() == a.id()))
+            {
+                continue;
+            }
+            let Some(href) = link.value().attr("href").and_then(|h| resolve(base, h)) else {
+                continue;
+            };
+            let text = link.text().collect::<String>();
+            if let Some(reference) = Reference::from_link(&href, Some(&text)) {
+                push_reference(&mut references, reference);
+            }
+        }
+        references
+    }
+
+    /// Entries of every reference section and footnote list, with the kind
+    /// of reference they hold; nested containers are only read once
+    fn sections(document: &Html) -> Vec<(Vec<ElementRef<'_>>, ReferenceKind)> {
+        let mut containers: Vec<(ElementRef, ReferenceKind)> = Vec::new();
+        for (selector, kind) in [
+            (FOOTNOTE_CONTAINERS, ReferenceKind::Footnote),
+            (BIBLIOGRAPHY_CONTAINERS, ReferenceKind::Bibliography),
+        ] {
+            let Ok(selector) = Selector::parse(selector) else {
+                continue;
+            };
+            for element in document.select(&selector) {
+                let overlaps = containers.iter().any(|(chosen, _)| {
+                    chosen.id() == element.id()
+                        || element.ancestors().any(|a| a.id() == chosen.id())
+                        || chosen.ancestors().any(|a| a.id() == element.id())
+                });
+                if !overlaps {
+                    containers.push((element, kind));
+                }
+            }
+        }
+
+        let mut sections: Vec<(Vec<ElementRef>, ReferenceKind)> = containers
+            .into_iter()
+            .map(|(container, kind)| (Self::entries(&[container]), kind))
+            .collect();
+
+        // Untagged sections: the blocks between a "References" heading and
+        // the next heading
+        if let Ok(headings) = Selector::parse("h1, h2, h3, h4, h5, h6") {
+            for heading in document.select(&headings) {
+                if !is_reference_heading(&heading.text().collect::<String>()) {
+                    continue;
+                }
+                let blocks: Vec<ElementRef> = heading
+                    .next_siblings()
+                    .filter_map(ElementRef::wrap)
+                    .take_while(|block| !is_heading_element(block))
+                    .collect();
+                let entries: Vec<ElementRef> = Self::entries(&blocks)
+                    .into_iter()
+                    .filter(|entry| {
+                        !sections
+                            .iter()
+                            .any(|(listed, _)| listed.iter().any(|e| e.id() == entry.id()))
+                    })
+                    .collect();
+                if !entries.is_empty() {
+                    sections.push((entries, ReferenceKind::Bibliography));
+                }
+            }
+        }
+        sections
+    }
+
+    /// List items of `blocks`, or their paragraphs when they hold no list
+    fn entries<'a>(blocks: &[ElementRef<'a>]) -> Vec<ElementRef<'a>> {
+        let select = |selector: &str| -> Vec<ElementRef<'a>> {
+            let Ok(selector) = Selector::parse(selector) else {
+                return Vec::new();
+            };
+            blocks
+                .iter()
+                .flat_map(|block| {
+                    let matches_self = selector.matches(block);
+                    matches_self
+                        .then_some(*block)
+                        .into_iter()
+                        .chain(block.select(&selector))
+                })
+                .collect()
+        };
+        let items = select("li, [role=doc-biblioentry], [role=doc-endnote]");
+        let mut entries: Vec<ElementRef<'a>> = Vec::new();
+        for item in if items.is_empty() { select("p") } else { items } {
+            // Keep the outermost entry when lists nest
+            if !entries
+                .iter()
+                .any(|e| e.id() == item.id() || item.ancestors().any(|a| a.id() == e.id()))
+            {
+                entries.push(item);
+            }
+        }
+        entries
+    }
+
+    fn entry(
+        element: ElementRef,
+        kind: ReferenceKind,
+        index: usize,
+        base: Option<&Url>,
+    ) -> Option<Reference> {
+        let text = element.text().collect::<String>();
+        let text = text
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .trim_matches(|c: char| c.is_whitespace() || BACKLINK_MARKS.contains(&c))
+            .to_string();
+
+        // "[12] Author ..." carries its own label; ordered lists number
+        // their items
+        let (label, text) = match text
+            .strip_prefix('[')
+            .and_then(|rest| rest.split_once(']'))
+            .filter(|(label, _)| !label.is_empty() && label.chars().all(|c| c.is_ascii_digit()))
+        {
+            Some((label, rest)) => (Some(label.to_string()), rest.trim().to_string()),
+            None => {
+                let ordered = element
+                    .parent()
+                    .and_then(ElementRef::wrap)
+                    .is_some_and(|parent| parent.value().name() == "ol");
+                (ordered.then(|| (index + 1).to_string()), text)
+            }
+        };
+
+        let mut reference = Reference::from_text(kind, &text);
+        reference.label = label;
+        if let Ok(links) = Selector::parse("a[href]") {
+            for link in element.select(&links) {
+                let Some(href) = link.value().attr("href") else {
+                    continue;
+                };
+                // Backlinks to the citing paragraph
+                if href.starts_with('#') {
+                    continue;
+                }
+                let Some(href) = resolve(base, href) else {
+                    continue;
+                };
+                match Reference::from_link(&href, None) {
+                    Some(citation) => {
+                        reference.doi = reference.doi.take().or(citation.doi);
+                        reference.arxiv_id = reference.arxiv_id.take().or(citation.arxiv_id);
+                        if reference.url.is_none() {
+                            reference.url = citation.url;
+                        }
+                    }
+                    None => reference.url = reference.url.take().or(Some(href)),
+                }
+            }
+        }
+
+        let identified = reference.doi.is_some() || reference.arxiv_id.is_some();
+        (identified || text.len() >= 10).then_some(reference)
+    }
+
+    /// References from `citation_reference` meta tags, e.g.
+    /// `citation_author=Doe, J.; citation_title=...; citation_doi=...`
+    fn citation_meta(document: &Html) -> Vec<Reference> {
+        let Ok(selector) = Selector::parse("meta[name=citation_reference][content]") else {
+            return Vec::new();
+        };
+
+        document
+            .select(&selector)
+            .filter_map(|meta| {
+                let content = meta.value().attr("content")?;
+                let mut reference = Reference::new(ReferenceKind::Bibliography);
+                let mut parts = Vec::new();
+                for field in content.split(';') {
+                    let Some((key, value)) = field.split_once('=') else {
+                        continue;
+                    };
+                    let value = value.trim();
+                    match key.trim() {
+                        "citation_doi" => reference.doi = find_doi(value),
+                        "citation_arxiv_id" => {
+                            reference.arxiv_id = Some(value.to_string()).filter(|v| !v.is_empty())
+                        }
+                        "citation_year" | "citation_publication_date" | "citation_date" => {
+                            reference.year = reference
+                                .year
+                                .or(value.get(..4).and_then(|year| year.parse().ok()))
+                        }
+                        "citation_author" | "citation_title" | "citation_journal_title" => {
+                            parts.push(value)
+                        }
+                        _ => {}
+                    }
+                }
+                let text = parts
+                    .into_iter()
+                    .map(|part| part.trim_end_matches('.'))
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(". ");
+                reference.text = (!text.is_empty()).then_some(text);
+                (reference.text.is_some() || reference.doi.is_some()).then_some(reference)
+            })
+            .collect()
+    }
+}
+
+fn is_heading_element(element: &ElementRef) -> bool {
+    matches!(
+        element.value().name(),
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6"
+    )
+}
+
+fn resolve(base: Option<&Url>, raw: &str) -> Option<String> {
+    let raw = raw.trim();
+    let url = match base {
+        Some(base) => base.join(raw).ok()?,
+        None => Url::parse(raw).ok()?,
+    };
+    matches!(url.scheme(), "http" | "https").then(|| url.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bibliography_section_merges_body_links() {
+        let html = r##"<html><body>
+            <p>Transformers <a href="https://arxiv.org/abs/1706.03762v5">[2]</a> and
+               <a href="https://doi.org/10.1145/3292500.3330701">a KDD paper</a>.</p>
+            <section role="doc-bibliography"><h2>References</h2><ol>
+              <li id="ref-1">LeCun, Y., Bengio, Y. (2015). Deep learning. Nature.
+                  doi:10.1038/nature14539 <a href="#cite-1">↩</a></li>
+              <li id="ref-2">Vaswani, A. et al. Attention is all you need. 2017.
+                  <a href="https://arxiv.org/abs/1706.03762">arXiv</a></li>
+            </ol></section>
+        </body></html>"##;
+
+        let references = extract_references(html, "https://example.org/paper");
+        assert_eq!(references.len(), 3);
+
+        assert_eq!(references[0].kind, ReferenceKind::Bibliography);
+        assert_eq!(references[0].label.as_deref(), Some("1"));
+        assert_eq!(references[0].doi.as_deref(), Some("10.1038/nature14539"));
+        assert_eq!(references[0].year, Some(2015));
+        assert!(!references[0].text.as_deref().unwrap().ends_with('↩'));
+
+        assert_eq!(references[1].arxiv_id.as_deref(), Some("1706.03762"));
+        assert_eq!(
+            references[1].url.as_deref(),
+            Some("https://arxiv.org/abs/1706.03762")
+        );
+
+        assert_eq!(references[2].kind, ReferenceKind::Link);
+        assert_eq!(
+            references[2].doi.as_deref(),
+            Some("10.1145/3292500.3330701")
+        );
+        assert_eq!(references[2].text.as_deref(), Some("a KDD paper"));
+    }
+
+    #[test]
+    fn test_footnotes_and_heading_sections() {
+        let html = r##"<article>
+            <p>Claim<sup><a href="#fn1">1</a></sup>.</p>
+            <div class="footnotes"><ol>
+              <li id="fn1">See the survey at <a href="/surveys/2020">our site</a>, 2020. <a href="#fnref1">↩</a></li>
+            </ol></div>
+            <h3>Works Cited</h3>
+            <p>[7] Doe, J. Field notes. Press, 1999.</p>
+            <h3>About</h3>
+            <p>Not a reference, 2001.</p>
+        </article>"##;
+
+        let references = extract_references(html, "https://blog.example.com/post");
+        assert_eq!(references.len(), 2);
+        assert_eq!(references[0].kind, ReferenceKind::Footnote);
+        assert_eq!(references[0].label.as_deref(), Some("1"));
+        assert_eq!(
+            references[0].url.as_deref(),
+            Some("https://blog.example.com/surveys/2020")
+        );
+        assert_eq!(references[1].kind, ReferenceKind::Bibliography);
+        assert_eq!(references[1].label.as_deref(), Some("7"));
+        assert_eq!(
+            references[1].text.as_deref(),
+            Some("Doe, J. Field notes. Press, 1999.")
+        );
+        assert_eq!(references[1].year, Some(1999));
+    }
+
+    #[test]
+    fn test_citation_meta_tags() {
+        let html = r#"<head>
+            <meta name="citation_reference" content="citation_author=Doe, J.; citation_title=A result; citation_journal_title=J. Things; citation_year=2011; citation_doi=10.1000/xyz123">
+            <meta name="citation_reference" content="citation_title=Preprint; citation_arxiv_id=2101.00001">
+        </head><body><p>No markup references.</p></body>"#;
+
+        let references = extract_references(html, "https://journal.example/article");
+        assert_eq!(references.len(), 2);
+        assert_eq!(
+            references[0].text.as_deref(),
+            Some("Doe, J. A result. J. Things")
+        );
+        assert_eq!(references[0].doi.as_deref(), Some("10.1000/xyz123"));
+        assert_eq!(references[0].year, Some(2011));
+        assert_eq!(references[1].arxiv_id.as_deref(), Some("2101.00001"));
+    }
+
+    #[test]
+    fn test_page_without_references() {
+        let html = r#"<p>Plain page with a <a href="https://example.com/">link</a>.</p>"#;
+        assert!(extract_references(html, "https://example.com/").is_empty());
+    }
+}
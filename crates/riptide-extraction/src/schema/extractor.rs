@@ -1,6 +1,7 @@
 //! Schema-based extraction implementation
 
 use super::types::{ExtractionSchema, SelectorRule, TestResult};
+use crate::xpath::XPath;
 use anyhow::Result;
 use scraper::{Html, Selector};
 use std::collections::HashMap;
@@ -77,48 +78,7 @@ impl SchemaExtractor {
         document: &Html,
         rule: &SelectorRule,
     ) -> Result<Option<serde_json::Value>> {
-        match rule.selector_type.as_str() {
-            "css" => self.apply_css_selector(document, &rule.selector),
-            "xpath" => {
-                // XPath not fully supported yet, return None
-                Ok(None)
-            }
-            "regex" => {
-                // Regex extraction would go here
-                Ok(None)
-            }
-            _ => Ok(None),
-        }
-    }
-
-    /// Apply CSS selector to document
-    fn apply_css_selector(
-        &self,
-        document: &Html,
-        selector_str: &str,
-    ) -> Result<Option<serde_json::Value>> {
-        let selector = Selector::parse(selector_str)
-            .map_err(|e| anyhow::anyhow!("Invalid CSS selector '{}': {:?}", selector_str, e))?;
-
-        let elements: Vec<_> = document.select(&selector).collect();
-
-        if elements.is_empty() {
-            return Ok(None);
-        }
-
-        // Extract text from first matching element
-        let text = elements[0]
-            .text()
-            .collect::<Vec<_>>()
-            .join(" ")
-            .trim()
-            .to_string();
-
-        if text.is_empty() {
-            Ok(None)
-        } else {
-            Ok(Some(serde_json::Value::String(text)))
-        }
+        Ok(rule_text(document, rule)?.map(serde_json::Value::String))
     }
 
     /// Test the schema against a URL and return test result
@@ -191,6 +151,28 @@ impl SchemaExtractor {
     }
 }
 
+/// Text a selector rule extracts from the document: the trimmed text of
+/// the first element matched, if not empty
+pub(super) fn rule_text(document: &Html, rule: &SelectorRule) -> Result<Option<String>> {
+    let text = match rule.selector_type.as_str() {
+        "css" => {
+            let selector = Selector::parse(&rule.selector).map_err(|e| {
+                anyhow::anyhow!("Invalid CSS selector '{}': {:?}", rule.selector, e)
+            })?;
+            document
+                .select(&selector)
+                .next()
+                .map(|element| element.text().collect::<Vec<_>>().join(" "))
+        }
+        "xpath" => Some(XPath::parse(&rule.selector)?.evaluate(document)?.string()),
+        // Regex extraction would go here
+        _ => None,
+    };
+    Ok(text
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,6 +232,21 @@ mod tests {
         assert_eq!(result.len(), 0);
     }
 
+    #[test]
+    fn test_xpath_extraction() {
+        let mut schema = create_test_schema();
+        schema.selectors.insert(
+            "title".to_string(),
+            vec![SelectorRule::xpath("/html/body/div[2]/h1", 10, 0.8)],
+        );
+        let extractor = SchemaExtractor::new(schema);
+
+        let html =
+            r#"<html><body><div><h1>Nav</h1></div><div><h1> Second </h1></div></body></html>"#;
+        let result = extractor.extract(html, "http://example.com").unwrap();
+        assert_eq!(result["title"], "Second");
+    }
+
     #[tokio::test]
     async fn test_extraction_with_test_result() {
         let schema = create_test_schema();
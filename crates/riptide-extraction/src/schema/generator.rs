@@ -1,11 +1,27 @@
 //! Schema generation and learning
 
+use super::inference::{self, Candidate};
+use super::registry::SchemaRegistry;
 use super::types::{
-    ExtractionSchema, FieldSchema, SchemaAnalysis, SchemaLearnRequest, SchemaLearnResponse,
-    SelectorRule,
+    ExtractionSchema, FieldSchema, SchemaAnalysis, SchemaExampleRequest, SchemaExampleResponse,
+    SchemaLearnRequest, SchemaLearnResponse, SchemaTestRequest, SelectorRule,
 };
+use super::validator::SchemaValidator;
 use anyhow::Result;
 use scraper::Html;
+use std::collections::BTreeSet;
+
+/// Examples needed to learn a schema from examples
+const MIN_EXAMPLES: usize = 2;
+
+/// Examples below which learned selectors are unlikely to generalise
+const RECOMMENDED_EXAMPLES: usize = 3;
+
+/// Selector rules kept per learned field
+const MAX_RULES_PER_FIELD: usize = 4;
+
+/// Priority of the best rule of a learned field; each further rule is one lower
+const LEARNED_PRIORITY: u32 = 10;
 
 /// Generates extraction schemas from HTML analysis
 pub struct SchemaGenerator {
@@ -91,6 +107,214 @@ impl SchemaGenerator {
         })
     }
 
+    /// Learn a schema from example pages and the output expected from them
+    ///
+    /// For every field of the examples, CSS and XPath selectors are proposed
+    /// for the elements holding its expected value and scored by the share
+    /// of examples they reproduce the value on, weighted by how specific
+    /// they are. The best selectors above the confidence threshold become
+    /// the field's rules, highest priority first, each falling back to the
+    /// next one of its type. Fields given in every example are required.
+    ///
+    /// `html_fetcher` returns the HTML of an example URL.
+    pub fn learn_from_examples(
+        &self,
+        request: &SchemaExampleRequest,
+        html_fetcher: impl Fn(&str) -> Result<String>,
+    ) -> Result<SchemaLearnResponse> {
+        if request.examples.len() < MIN_EXAMPLES {
+            anyhow::bail!(
+                "At least {} examples are required, got {}",
+                MIN_EXAMPLES,
+                request.examples.len()
+            );
+        }
+        let pages = request
+            .examples
+            .iter()
+            .map(|example| Ok((Html::parse_document(&html_fetcher(&example.url)?), example)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut schema = ExtractionSchema::new(
+            request.name.clone(),
+            "1.0.0".to_string(),
+            request.goal.clone(),
+        );
+        schema.metadata.tags.push("learned".to_string());
+
+        let mut analysis = SchemaAnalysis {
+            confidence: 0.0,
+            fields_detected: 0,
+            selectors_generated: 0,
+            patterns_found: vec!["examples".to_string()],
+            warnings: Vec::new(),
+        };
+
+        let fields: BTreeSet<&String> = request
+            .examples
+            .iter()
+            .flat_map(|example| example.expected.keys())
+            .collect();
+        let mut best_confidences = Vec::new();
+
+        for field in fields {
+            let values: Vec<(&Html, String)> = pages
+                .iter()
+                .filter_map(|(document, example)| {
+                    Some((
+                        document,
+                        inference::expected_text(example.expected.get(field)?)?,
+                    ))
+                })
+                .collect();
+            if values.is_empty() {
+                analysis.warnings.push(format!(
+                    "Field '{}' has no string, number or boolean example value",
+                    field
+                ));
+                continue;
+            }
+
+            let mut candidates: Vec<Candidate> = Vec::new();
+            for (document, expected) in &values {
+                for candidate in inference::candidates(document, expected) {
+                    if !candidates.contains(&candidate) {
+                        candidates.push(candidate);
+                    }
+                }
+            }
+
+            let mut scored: Vec<(Candidate, f64)> = candidates
+                .into_iter()
+                .filter_map(|candidate| {
+                    let hits = values
+                        .iter()
+                        .filter(|(document, expected)| {
+                            inference::reproduces(document, &candidate, expected)
+                        })
+                        .count();
+                    let confidence = hits as f64 / values.len() as f64 * candidate.weight;
+                    (hits > 0 && confidence >= request.confidence_threshold)
+                        .then_some((candidate, confidence))
+                })
+                .collect();
+            scored.sort_by(|a, b| {
+                b.1.total_cmp(&a.1)
+                    .then(a.0.selector.len().cmp(&b.0.selector.len()))
+            });
+            scored.truncate(MAX_RULES_PER_FIELD);
+
+            if scored.is_empty() {
+                analysis.warnings.push(format!(
+                    "No selector reproduced field '{}' above the confidence threshold",
+                    field
+                ));
+                continue;
+            }
+            if values.len() < request.examples.len() {
+                analysis.warnings.push(format!(
+                    "Field '{}' is missing from some examples and was learned as optional",
+                    field
+                ));
+            }
+
+            let field_type = match request
+                .examples
+                .iter()
+                .find_map(|example| example.expected.get(field))
+            {
+                Some(serde_json::Value::Number(_)) => "number",
+                Some(serde_json::Value::Bool(_)) => "boolean",
+                _ => "string",
+            };
+            let field_schema = if values.len() == request.examples.len() {
+                FieldSchema::required(field_type)
+            } else {
+                FieldSchema::optional(field_type)
+            };
+            schema.add_field(field.clone(), field_schema);
+
+            for (index, (candidate, confidence)) in scored.iter().enumerate() {
+                let mut rule =
+                    candidate.rule(LEARNED_PRIORITY.saturating_sub(index as u32), *confidence);
+                if let Some((fallback, _)) = scored[index + 1..]
+                    .iter()
+                    .find(|(next, _)| next.selector_type == candidate.selector_type)
+                {
+                    rule = rule.with_fallback(&fallback.selector);
+                }
+                schema.add_selector(field.clone(), rule);
+            }
+            best_confidences.push(scored[0].1);
+        }
+
+        analysis.fields_detected = schema.fields.len() as u32;
+        analysis.selectors_generated = schema
+            .selectors
+            .values()
+            .map(|rules| rules.len())
+            .sum::<usize>() as u32;
+        if !best_confidences.is_empty() {
+            analysis.confidence =
+                best_confidences.iter().sum::<f64>() / best_confidences.len() as f64;
+        }
+
+        let mut suggestions = self.generate_suggestions(&schema, &analysis);
+        if request.examples.len() < RECOMMENDED_EXAMPLES {
+            suggestions.push(format!(
+                "Learn from at least {} examples so selectors generalise across pages",
+                RECOMMENDED_EXAMPLES
+            ));
+        }
+
+        Ok(SchemaLearnResponse {
+            schema,
+            analysis,
+            suggestions,
+        })
+    }
+
+    /// Learn a schema from example pages, register it and test it on the
+    /// held-out pages
+    ///
+    /// The schema is registered under the next free version of its name,
+    /// with the share of held-out pages it passed as its success rate.
+    pub async fn learn_and_register(
+        &self,
+        request: &SchemaExampleRequest,
+        registry: &mut SchemaRegistry,
+        html_fetcher: impl Fn(&str) -> Result<String>,
+    ) -> Result<SchemaExampleResponse> {
+        let mut learned = self.learn_from_examples(request, &html_fetcher)?;
+        learned.schema.version = registry.next_version(&request.name);
+        registry.register(learned.schema.clone())?;
+
+        let validation = if request.held_out_urls.is_empty() {
+            None
+        } else {
+            let test_request = SchemaTestRequest {
+                schema: learned.schema.clone(),
+                urls: request.held_out_urls.clone(),
+                fail_fast: false,
+            };
+            let response = SchemaValidator::new()
+                .test_schema(test_request, &html_fetcher)
+                .await?;
+            registry.update_success_rate(
+                &learned.schema.name,
+                &learned.schema.version,
+                response.success_rate,
+            )?;
+            learned.schema.metadata.success_rate = Some(response.success_rate);
+            Some(response)
+        };
+
+        Ok(SchemaExampleResponse {
+            learned,
+            validation,
+        })
+    }
+
     /// Learn schema for article content
     fn learn_article_schema(
         &self,
@@ -467,4 +691,80 @@ mod tests {
         assert!(response.schema.fields.contains_key("name"));
         assert!(response.schema.fields.contains_key("price"));
     }
+
+    #[tokio::test]
+    async fn test_learn_from_examples_and_validate_held_out() {
+        use crate::schema::types::SchemaExample;
+        use std::collections::HashMap;
+
+        let page = |id: u32, banner: &str, name: &str, price: &str| {
+            format!(
+                r#"<html><body>{}<div class="product" id="p-{}">
+                   <h1 class="product-title">{}</h1>
+                   <p>Ships in 2 days</p><span class="price">{}</span></div></body></html>"#,
+                banner, id, name, price
+            )
+        };
+        let pages: HashMap<&str, String> = HashMap::from([
+            ("https://shop.test/1", page(1, "", "Blue Kettle", "$19.99")),
+            (
+                "https://shop.test/2",
+                page(2, "<h1>Sale!</h1>", "Red Toaster", "$24.50"),
+            ),
+            ("https://shop.test/3", page(3, "", "Steel Pan", "$1,099.00")),
+            (
+                "https://shop.test/4",
+                page(4, "<div><h1>News</h1></div>", "Oak Board", "$8.00"),
+            ),
+        ]);
+        let fetch = |url: &str| {
+            pages
+                .get(url)
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("no page {}", url))
+        };
+
+        let example = |url: &str, name: &str, price: f64| SchemaExample {
+            url: url.to_string(),
+            expected: HashMap::from([
+                ("name".to_string(), serde_json::json!(name)),
+                ("price".to_string(), serde_json::json!(price)),
+            ]),
+        };
+        let request = SchemaExampleRequest {
+            name: "shop-product".to_string(),
+            goal: "product".to_string(),
+            examples: vec![
+                example("https://shop.test/1", "Blue Kettle", 19.99),
+                example("https://shop.test/2", "Red Toaster", 24.5),
+                example("https://shop.test/3", "Steel Pan", 1099.0),
+            ],
+            held_out_urls: vec!["https://shop.test/4".to_string()],
+            confidence_threshold: 0.6,
+        };
+
+        let mut registry = SchemaRegistry::new();
+        let generator = SchemaGenerator::new(0.6);
+        let response = generator
+            .learn_and_register(&request, &mut registry, fetch)
+            .await
+            .unwrap();
+
+        let schema = &response.learned.schema;
+        let name_rules = &schema.selectors["name"];
+        assert_eq!(name_rules[0].selector, "h1.product-title");
+        assert_eq!(name_rules[0].confidence, 0.95);
+        assert_eq!(name_rules[0].fallback.as_deref(), Some("div.product > h1"));
+        // The banner heading on page 2 breaks the bare `h1` selector
+        assert!(name_rules.iter().all(|rule| rule.selector != "h1"));
+        assert_eq!(schema.selectors["price"][0].selector, "span.price");
+        assert_eq!(schema.fields["price"].field_type, "number");
+        assert!(schema.fields["name"].required);
+
+        let validation = response.validation.unwrap();
+        assert_eq!(validation.passed, 1);
+        let registered = registry.get("shop-product", None).unwrap();
+        assert_eq!(registered.version, "1.0.0");
+        assert_eq!(registered.metadata.success_rate, Some(1.0));
+    }
 }
@@ -0,0 +1,223 @@
+//! Selector inference from example values
+//!
+//! Finds the elements of a page whose text is an expected value and
+//! proposes CSS and XPath selectors for them, from the most specific (`id`,
+//! `itemprop`) to the most generic (tag name, positional XPath). Each
+//! selector carries a weight for how well it is expected to generalise to
+//! other pages; the generator scores the selectors against every example and
+//! keeps those that reproduce the expected values.
+
+use super::extractor::rule_text;
+use super::types::SelectorRule;
+use scraper::{ElementRef, Html, Selector};
+
+/// Matching elements considered per example value
+const MAX_ELEMENTS: usize = 3;
+
+/// Selector proposed for a field, before scoring
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Candidate {
+    pub selector: String,
+    pub selector_type: &'static str,
+    /// How well this kind of selector generalises, 0.0-1.0
+    pub weight: f64,
+}
+
+impl Candidate {
+    fn css(selector: String, weight: f64) -> Option<Self> {
+        Selector::parse(&selector).ok()?;
+        Some(Self {
+            selector,
+            selector_type: "css",
+            weight,
+        })
+    }
+
+    /// Rule for this selector with the given priority and confidence
+    pub fn rule(&self, priority: u32, confidence: f64) -> SelectorRule {
+        match self.selector_type {
+            "xpath" => SelectorRule::xpath(&self.selector, priority, confidence),
+            _ => SelectorRule::css(&self.selector, priority, confidence),
+        }
+    }
+}
+
+/// Text form of an expected field value; arrays, objects and null have none
+pub(super) fn expected_text(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(normalize(s)).filter(|s| !s.is_empty()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Whether extracted `text` is the `expected` value; numbers also match
+/// text that formats them ("$1,299.00" for 1299)
+pub(super) fn text_matches(text: &str, expected: &str) -> bool {
+    let text = normalize(text);
+    if text == expected {
+        return true;
+    }
+    let Ok(number) = expected.parse::<f64>() else {
+        return false;
+    };
+    let digits: String = text
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == '-')
+        .collect();
+    digits.parse::<f64>().is_ok_and(|n| n == number)
+}
+
+/// Whether `candidate`, applied as the schema extractor would, yields
+/// `expected` on `document`
+pub(super) fn reproduces(document: &Html, candidate: &Candidate, expected: &str) -> bool {
+    rule_text(document, &candidate.rule(0, 0.0))
+        .ok()
+        .flatten()
+        .is_some_and(|text| text_matches(&text, expected))
+}
+
+/// Selectors for the innermost elements of `document` whose text is
+/// `expected`
+pub(super) fn candidates(document: &Html, expected: &str) -> Vec<Candidate> {
+    let matching = |element: &ElementRef| text_matches(&element_text(element), expected);
+    let elements = document
+        .root_element()
+        .descendants()
+        .filter_map(ElementRef::wrap)
+        .filter(|element| matching(element))
+        .filter(|element| {
+            !element
+                .children()
+                .filter_map(ElementRef::wrap)
+                .any(|child| matching(&child))
+        })
+        .take(MAX_ELEMENTS);
+
+    let mut candidates: Vec<Candidate> = Vec::new();
+    for element in elements {
+        for candidate in element_candidates(element) {
+            if !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+    }
+    candidates
+}
+
+fn element_candidates(element: ElementRef) -> Vec<Candidate> {
+    let value = element.value();
+    let tag = value.name();
+    let mut candidates = Vec::new();
+
+    if let Some(id) = value.id() {
+        candidates.extend(Candidate::css(format!("#{}", id), 1.0));
+    }
+    if let Some(itemprop) = value.attr("itemprop") {
+        candidates.extend(Candidate::css(format!("[itemprop='{}']", itemprop), 1.0));
+    }
+    for class in classes(element) {
+        candidates.extend(Candidate::css(format!("{}.{}", tag, class), 0.95));
+    }
+    if let Some(parent) = element.parent().and_then(ElementRef::wrap) {
+        let parent_value = parent.value();
+        let parent_selectors = parent_value
+            .id()
+            .map(|id| format!("#{}", id))
+            .into_iter()
+            .chain(
+                classes(parent)
+                    .next()
+                    .map(|class| format!("{}.{}", parent_value.name(), class)),
+            );
+        for parent_selector in parent_selectors {
+            candidates.extend(Candidate::css(
+                format!("{} > {}", parent_selector, tag),
+                0.9,
+            ));
+        }
+    }
+    candidates.extend(Candidate::css(tag.to_string(), 0.8));
+    candidates.push(Candidate {
+        selector: positional_xpath(element),
+        selector_type: "xpath",
+        weight: 0.7,
+    });
+    candidates
+}
+
+/// Absolute XPath of `element` by tag name and position among siblings of
+/// the same name ("/html/body[1]/div[2]/h1[1]")
+fn positional_xpath(element: ElementRef) -> String {
+    let mut steps = Vec::new();
+    let mut current = Some(element);
+    while let Some(element) = current {
+        let name = element.value().name();
+        let parent = element.parent().and_then(ElementRef::wrap);
+        if parent.is_none() {
+            steps.push(name.to_string());
+            break;
+        }
+        let position = 1 + element
+            .prev_siblings()
+            .filter_map(ElementRef::wrap)
+            .filter(|sibling| sibling.value().name() == name)
+            .count();
+        steps.push(format!("{}[{}]", name, position));
+        current = parent;
+    }
+    steps.reverse();
+    format!("/{}", steps.join("/"))
+}
+
+/// Classes of `element` in the order of its `class` attribute
+fn classes<'a>(element: ElementRef<'a>) -> impl Iterator<Item = &'a str> {
+    element
+        .value()
+        .attr("class")
+        .unwrap_or_default()
+        .split_whitespace()
+}
+
+fn element_text(element: &ElementRef) -> String {
+    element.text().collect::<Vec<_>>().join(" ")
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidates_from_specific_to_generic() {
+        let html = Html::parse_document(
+            r#"<html><body><div class="product"><h1 class="title main">Blue   Kettle</h1>
+               <span itemprop="price">$1,299.00</span></div></body></html>"#,
+        );
+
+        let selectors: Vec<_> = candidates(&html, "Blue Kettle")
+            .into_iter()
+            .map(|c| c.selector)
+            .collect();
+        assert_eq!(
+            selectors,
+            vec![
+                "h1.title",
+                "h1.main",
+                "div.product > h1",
+                "h1",
+                "/html/body[1]/div[1]/h1[1]"
+            ]
+        );
+
+        let price = candidates(&html, "1299");
+        assert_eq!(price[0].selector, "[itemprop='price']");
+        assert!(price
+            .iter()
+            .all(|candidate| reproduces(&html, candidate, "1299")));
+    }
+}
@@ -6,15 +6,16 @@
 pub mod comparator;
 pub mod extractor;
 pub mod generator;
+mod inference;
 pub mod registry;
 pub mod types;
 pub mod validator;
 
 // Re-export main types and interfaces
 pub use types::{
-    ExtractionSchema, FieldSchema, SchemaAnalysis, SchemaLearnRequest, SchemaLearnResponse,
-    SchemaMetadata, SchemaTestRequest, SchemaTestResponse, SelectorRule, TestResult, TestSummary,
-    ValidationRules,
+    ExtractionSchema, FieldSchema, SchemaAnalysis, SchemaExample, SchemaExampleRequest,
+    SchemaExampleResponse, SchemaLearnRequest, SchemaLearnResponse, SchemaMetadata,
+    SchemaTestRequest, SchemaTestResponse, SelectorRule, TestResult, TestSummary, ValidationRules,
 };
 
 pub use comparator::SchemaComparator;
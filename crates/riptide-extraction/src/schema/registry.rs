@@ -201,6 +201,28 @@ impl SchemaRegistry {
         self.schemas.values().map(|v| v.len()).sum()
     }
 
+    /// Version to register the next schema named `name` under: "1.0.0" for a
+    /// new name, else the latest version with its minor version bumped
+    pub fn next_version(&self, name: &str) -> String {
+        let Ok(latest) = self.get(name, None) else {
+            return "1.0.0".to_string();
+        };
+        let parts: Vec<u64> = latest
+            .version
+            .split('.')
+            .map_while(|part| part.parse().ok())
+            .collect();
+        let (major, mut minor) = match parts.as_slice() {
+            [major, minor, ..] => (*major, minor + 1),
+            [major] => (*major, 1),
+            [] => (1, 0),
+        };
+        while self.exists(name, Some(&format!("{}.{}.0", major, minor))) {
+            minor += 1;
+        }
+        format!("{}.{}.0", major, minor)
+    }
+
     /// Check if a schema exists
     pub fn exists(&self, name: &str, version: Option<&str>) -> bool {
         if let Some(versions) = self.schemas.get(name) {
@@ -290,6 +312,19 @@ mod tests {
         assert_eq!(latest.version, "2.0.0");
     }
 
+    #[test]
+    fn test_next_version() {
+        let mut registry = SchemaRegistry::new();
+        assert_eq!(registry.next_version("test"), "1.0.0");
+        registry
+            .register(create_test_schema("test", "1.0.0"))
+            .unwrap();
+        registry
+            .register(create_test_schema("test", "2.3.1"))
+            .unwrap();
+        assert_eq!(registry.next_version("test"), "2.4.0");
+    }
+
     #[test]
     fn test_remove_schema() {
         let mut registry = SchemaRegistry::new();
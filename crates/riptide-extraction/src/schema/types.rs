@@ -85,6 +85,35 @@ pub struct SchemaAnalysis {
     pub warnings: Vec<String>,
 }
 
+/// Example page and the output a learned schema should extract from it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaExample {
+    pub url: String,
+    /// Expected value of each field; strings, numbers and booleans
+    pub expected: HashMap<String, serde_json::Value>,
+}
+
+/// Request for learning a schema from example pages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaExampleRequest {
+    pub name: String,
+    pub goal: String,
+    /// Pages to learn from, ideally 3 to 5
+    pub examples: Vec<SchemaExample>,
+    /// Pages left out of learning that the schema is tested against
+    #[serde(default)]
+    pub held_out_urls: Vec<String>,
+    pub confidence_threshold: f64,
+}
+
+/// Response from learning a schema from example pages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaExampleResponse {
+    pub learned: SchemaLearnResponse,
+    /// Results on the held-out pages, when any were given
+    pub validation: Option<SchemaTestResponse>,
+}
+
 /// Request for testing a schema
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchemaTestRequest {
@@ -0,0 +1,63 @@
+//! `<script type="application/ld+json">` blocks
+
+use scraper::{Html, Selector};
+use serde_json::Value;
+
+/// Top-level nodes of every parseable JSON-LD block, in document order
+///
+/// Arrays and `@graph` containers are unwrapped; invalid JSON is skipped.
+pub(super) fn nodes(document: &Html) -> Vec<Value> {
+    let Ok(selector) = Selector::parse("script[type]") else {
+        return Vec::new();
+    };
+
+    let mut nodes = Vec::new();
+    for script in document.select(&selector) {
+        let is_json_ld = script
+            .value()
+            .attr("type")
+            .is_some_and(|t| t.trim().eq_ignore_ascii_case("application/ld+json"));
+        if !is_json_ld {
+            continue;
+        }
+
+        let text = script.text().collect::<String>();
+        if let Ok(value) = serde_json::from_str::<Value>(strip_wrappers(&text)) {
+            unwrap_containers(value, &mut nodes);
+        }
+    }
+    nodes
+}
+
+/// Strip the HTML comment and CDATA wrappers some CMSes put around the JSON
+fn strip_wrappers(text: &str) -> &str {
+    let mut text = text.trim();
+    for (open, close) in [
+        ("<!--", "-->"),
+        ("//<![CDATA[", "//]]>"),
+        ("<![CDATA[", "]]>"),
+    ] {
+        if let Some(inner) = text
+            .strip_prefix(open)
+            .and_then(|rest| rest.strip_suffix(close))
+        {
+            text = inner.trim();
+        }
+    }
+    text
+}
+
+fn unwrap_containers(value: Value, nodes: &mut Vec<Value>) {
+    match value {
+        Value::Array(values) => {
+            for value in values {
+                unwrap_containers(value, nodes);
+            }
+        }
+        Value::Object(mut object) => match object.remove("@graph") {
+            Some(graph) => unwrap_containers(graph, nodes),
+            None => nodes.push(Value::Object(object)),
+        },
+        _ => {}
+    }
+}
@@ -0,0 +1,162 @@
+//! Microdata and RDFa Lite attributes
+//!
+//! Both syntaxes mark an element as an item, give it a type and attach
+//! properties from descendant elements; they differ only in attribute names.
+//! Items are read into JSON-LD shaped nodes, with nested items becoming
+//! nested objects.
+
+use scraper::{ElementRef, Html, Selector};
+use serde_json::{Map, Value};
+
+/// Attribute-based structured-data syntax
+#[derive(Debug, Clone, Copy)]
+pub(super) enum Syntax {
+    /// `itemscope` / `itemtype` / `itemprop`
+    Microdata,
+    /// `typeof` / `property`
+    Rdfa,
+}
+
+impl Syntax {
+    fn scope_selector(self) -> &'static str {
+        match self {
+            Syntax::Microdata => "[itemscope]",
+            Syntax::Rdfa => "[typeof]",
+        }
+    }
+
+    fn property_attr(self) -> &'static str {
+        match self {
+            Syntax::Microdata => "itemprop",
+            Syntax::Rdfa => "property",
+        }
+    }
+
+    fn is_item(self, element: &ElementRef) -> bool {
+        let attr = match self {
+            Syntax::Microdata => "itemscope",
+            Syntax::Rdfa => "typeof",
+        };
+        element.value().attr(attr).is_some()
+    }
+
+    /// Type names of an item, without vocabulary URL or prefix
+    fn types(self, element: &ElementRef) -> Vec<String> {
+        let attr = match self {
+            Syntax::Microdata => "itemtype",
+            Syntax::Rdfa => "typeof",
+        };
+        element
+            .value()
+            .attr(attr)
+            .map(|types| types.split_whitespace().map(local_name).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Top-level items, i.e. items that are not a property of another item
+pub(super) fn nodes(document: &Html, syntax: Syntax) -> Vec<Value> {
+    let Ok(selector) = Selector::parse(syntax.scope_selector()) else {
+        return Vec::new();
+    };
+
+    document
+        .select(&selector)
+        .filter(|element| element.value().attr(syntax.property_attr()).is_none())
+        .map(|element| item(element, syntax))
+        .collect()
+}
+
+fn item(element: ElementRef, syntax: Syntax) -> Value {
+    let mut object = Map::new();
+    match syntax.types(&element).as_slice() {
+        [] => {}
+        [single] => {
+            object.insert("@type".to_string(), Value::String(single.clone()));
+        }
+        many => {
+            object.insert(
+                "@type".to_string(),
+                Value::Array(many.iter().cloned().map(Value::String).collect()),
+            );
+        }
+    }
+    collect_properties(element, syntax, &mut object);
+    Value::Object(object)
+}
+
+/// Walk descendants of an item, stopping at nested items, which own the
+/// properties below them
+fn collect_properties(element: ElementRef, syntax: Syntax, object: &mut Map<String, Value>) {
+    for child in element.children().filter_map(ElementRef::wrap) {
+        let is_item = syntax.is_item(&child);
+        match child.value().attr(syntax.property_attr()) {
+            Some(names) => {
+                let value = if is_item {
+                    item(child, syntax)
+                } else {
+                    Value::String(property_value(&child))
+                };
+                for name in names.split_whitespace().map(local_name) {
+                    insert_property(object, name, value.clone());
+                }
+            }
+            // An item with no property is a top-level item of its own
+            None if is_item => continue,
+            None => {}
+        }
+
+        if !is_item {
+            collect_properties(child, syntax, object);
+        }
+    }
+}
+
+/// Property value per the Microdata spec, with `content` accepted on any
+/// element as both RDFa and common Microdata practice allow
+fn property_value(element: &ElementRef) -> String {
+    let el = element.value();
+    if let Some(content) = el.attr("content") {
+        return content.trim().to_string();
+    }
+
+    let attr = match el.name() {
+        "a" | "area" | "link" => el.attr("href").or_else(|| el.attr("resource")),
+        "img" | "audio" | "video" | "source" | "track" | "embed" | "iframe" => el.attr("src"),
+        "object" => el.attr("data"),
+        "data" | "meter" => el.attr("value"),
+        "time" => el.attr("datetime"),
+        _ => el.attr("resource"),
+    };
+    match attr {
+        Some(value) => value.trim().to_string(),
+        None => element
+            .text()
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+/// Append to a property, turning repeated properties into arrays
+fn insert_property(object: &mut Map<String, Value>, name: String, value: Value) {
+    match object.get_mut(&name) {
+        Some(Value::Array(values)) => values.push(value),
+        Some(existing) => {
+            let first = existing.take();
+            *existing = Value::Array(vec![first, value]);
+        }
+        None => {
+            object.insert(name, value);
+        }
+    }
+}
+
+/// "https://schema.org/Product" and "schema:Product" both name "Product"
+fn local_name(name: &str) -> String {
+    name.rsplit(['/', '#', ':'])
+        .find(|segment| !segment.is_empty())
+        .unwrap_or(name)
+        .to_string()
+}
@@ -0,0 +1,165 @@
+//! schema.org `VideoObject` and `AudioObject` entities
+//!
+//! These describe embedded media rather than the page, so they become
+//! [`EmbeddedMedia`] instead of a [`SchemaEntity`](super::SchemaEntity).
+
+use serde_json::Value;
+
+use riptide_types::media::{
+    CaptionTrack, EmbeddedMedia, MediaKind, MediaMetadataSource, StreamFormat, StreamManifest,
+};
+
+use super::schema::{number, plain_text, text, types, values, SchemaMapper, MAX_DEPTH};
+
+/// Collect the media objects in `node`, including ones nested in other
+/// entities (e.g. a `NewsArticle`'s `video` or a `PodcastEpisode`'s
+/// `associatedMedia`)
+pub(super) fn collect(
+    mapper: &SchemaMapper,
+    node: &Value,
+    depth: usize,
+    media: &mut Vec<EmbeddedMedia>,
+) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+
+    match node {
+        Value::Array(values) => {
+            for value in values {
+                collect(mapper, value, depth + 1, media);
+            }
+        }
+        Value::Object(object) => {
+            if let Some(item) = media_object(mapper, node) {
+                media.push(item);
+                return;
+            }
+            for (key, value) in object {
+                if !key.starts_with('@') {
+                    collect(mapper, value, depth + 1, media);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn media_object(mapper: &SchemaMapper, node: &Value) -> Option<EmbeddedMedia> {
+    let kind = types(node)
+        .iter()
+        .find_map(|schema_type| match schema_type.as_str() {
+            "VideoObject" => Some(MediaKind::Video),
+            "AudioObject" => Some(MediaKind::Audio),
+            _ => None,
+        })?;
+
+    let mut media = EmbeddedMedia::new(kind, MediaMetadataSource::SchemaOrg);
+    media.mime_type = text(node.get("encodingFormat")).filter(|format| format.contains('/'));
+    media.embed_url = mapper.url(node.get("embedUrl"));
+    if let Some(url) = mapper.url(node.get("contentUrl")) {
+        let format = media
+            .mime_type
+            .as_deref()
+            .and_then(StreamFormat::from_media_type)
+            .or_else(|| StreamFormat::from_url(&url));
+        match format {
+            Some(format) => media.add_manifest(StreamManifest { url, format }),
+            None => media.url = Some(url),
+        }
+    }
+    media.title = text(node.get("name")).or_else(|| text(node.get("headline")));
+    media.description = plain_text(node.get("description"));
+    media.duration_secs = text(node.get("duration")).and_then(|d| parse_duration(&d));
+    media.thumbnail_url = mapper
+        .url(node.get("thumbnailUrl"))
+        .or_else(|| mapper.url(node.get("thumbnail")));
+    media.width = number(node.get("width")).map(|w| w as u32);
+    media.height = number(node.get("height")).map(|h| h as u32);
+    media.upload_date = text(node.get("uploadDate")).or_else(|| text(node.get("datePublished")));
+
+    // `caption` holds either transcript text or a MediaObject with the file
+    for caption in values(node.get("caption")).filter(|c| c.is_object()) {
+        if let Some(url) = mapper.url(Some(caption)) {
+            media.captions.push(CaptionTrack {
+                url,
+                language: text(caption.get("inLanguage")),
+                label: text(caption.get("name")),
+                kind: "captions".to_string(),
+            });
+        }
+    }
+
+    let described = media.url.is_some() || media.embed_url.is_some() || !media.manifests.is_empty();
+    described.then_some(media)
+}
+
+/// Seconds in an ISO 8601 duration ("PT1H2M3.5S"), a clock time
+/// ("01:02:03") or a plain number of seconds, as sites use all three
+pub(crate) fn parse_duration(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if let Some(rest) = value.strip_prefix('P').or_else(|| value.strip_prefix('p')) {
+        return parse_iso_duration(rest);
+    }
+    if value.contains(':') {
+        let mut seconds = 0.0;
+        for part in value.split(':') {
+            seconds = seconds * 60.0 + part.trim().parse::<f64>().ok()?;
+        }
+        return Some(seconds);
+    }
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|s| s.is_finite() && *s >= 0.0)
+}
+
+/// Duration after the leading "P"; years and months are not meaningful for
+/// media and are rejected
+fn parse_iso_duration(value: &str) -> Option<f64> {
+    let mut seconds = 0.0;
+    let mut in_time = false;
+    let mut number = String::new();
+    let mut any = false;
+    for c in value.chars() {
+        match c.to_ascii_uppercase() {
+            'T' => in_time = true,
+            c if c.is_ascii_digit() || c == '.' || c == ',' => {
+                number.push(if c == ',' { '.' } else { c })
+            }
+            unit => {
+                let amount: f64 = number.parse().ok()?;
+                number.clear();
+                seconds += amount
+                    * match (unit, in_time) {
+                        ('W', false) => 604_800.0,
+                        ('D', false) => 86_400.0,
+                        ('H', true) => 3_600.0,
+                        ('M', true) => 60.0,
+                        ('S', true) => 1.0,
+                        _ => return None,
+                    };
+                any = true;
+            }
+        }
+    }
+    (any && number.is_empty()).then_some(seconds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_formats() {
+        assert_eq!(parse_duration("PT1H2M3.5S"), Some(3723.5));
+        assert_eq!(parse_duration("PT90S"), Some(90.0));
+        assert_eq!(parse_duration("P1DT1M"), Some(86_460.0));
+        assert_eq!(parse_duration("01:02:03"), Some(3723.0));
+        assert_eq!(parse_duration("4:05"), Some(245.0));
+        assert_eq!(parse_duration("312"), Some(312.0));
+        assert_eq!(parse_duration("P1M"), None);
+        assert_eq!(parse_duration("PT"), None);
+        assert_eq!(parse_duration("soon"), None);
+    }
+}
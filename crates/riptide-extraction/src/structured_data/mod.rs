@@ -0,0 +1,311 @@
+//! JSON-LD, Microdata and RDFa structured-data extraction
+//!
+//! Each syntax is first read into JSON-LD shaped nodes: a `serde_json::Value`
+//! object carrying `@type` and schema.org property names. [`schema`] then maps
+//! those nodes onto the typed entities from `riptide_types::structured_data`,
+//! so the three syntaxes share one interpretation of schema.org.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_extraction::structured_data::{extract_structured_data, SchemaEntity};
+//!
+//! let html = r#"<script type="application/ld+json">
+//!     {"@context": "https://schema.org", "@type": "Recipe", "name": "Pancakes"}
+//! </script>"#;
+//!
+//! let items = extract_structured_data(html, "https://example.com/pancakes");
+//! assert!(matches!(&items[0].entity, SchemaEntity::Recipe(r) if r.name.as_deref() == Some("Pancakes")));
+//! ```
+
+mod json_ld;
+mod markup;
+mod media;
+mod schema;
+
+pub(crate) use media::parse_duration;
+
+use riptide_types::media::EmbeddedMedia;
+use scraper::Html;
+use serde_json::Value;
+use url::Url;
+
+pub use riptide_types::structured_data::{
+    SchemaArticle, SchemaEntity, SchemaEvent, SchemaJobPosting, SchemaOffer, SchemaProduct,
+    SchemaRating, SchemaRecipe, SchemaSalary, StructuredDataItem, StructuredDataSource,
+};
+
+/// Parse `html` and extract its schema.org entities
+pub fn extract_structured_data(html: &str, url: &str) -> Vec<StructuredDataItem> {
+    StructuredDataExtractor::extract(&Html::parse_document(html), url)
+}
+
+/// Extracts schema.org entities from all three structured-data syntaxes
+pub struct StructuredDataExtractor;
+
+impl StructuredDataExtractor {
+    /// Extract entities from a parsed document, resolving relative URLs
+    /// against `url`
+    ///
+    /// Items come in document order per syntax: JSON-LD first, then
+    /// Microdata, then RDFa. Exact duplicates within a syntax are dropped.
+    pub fn extract(document: &Html, url: &str) -> Vec<StructuredDataItem> {
+        let base = Url::parse(url).ok();
+        let mapper = schema::SchemaMapper::new(base.as_ref());
+
+        let mut items = Vec::new();
+        for (source, nodes) in Self::nodes(document) {
+            for node in &nodes {
+                for item in mapper.items(node, source) {
+                    if !items.contains(&item) {
+                        items.push(item);
+                    }
+                }
+            }
+        }
+        items
+    }
+
+    /// schema.org `VideoObject` and `AudioObject` entities from all three
+    /// syntaxes, in the same order as [`Self::extract`]
+    pub(crate) fn media_objects(document: &Html, url: &str) -> Vec<EmbeddedMedia> {
+        let base = Url::parse(url).ok();
+        let mapper = schema::SchemaMapper::new(base.as_ref());
+
+        let mut media = Vec::new();
+        for (_, nodes) in Self::nodes(document) {
+            for node in &nodes {
+                media::collect(&mapper, node, 0, &mut media);
+            }
+        }
+        media
+    }
+
+    fn nodes(document: &Html) -> [(StructuredDataSource, Vec<Value>); 3] {
+        [
+            (StructuredDataSource::JsonLd, json_ld::nodes(document)),
+            (
+                StructuredDataSource::Microdata,
+                markup::nodes(document, markup::Syntax::Microdata),
+            ),
+            (
+                StructuredDataSource::Rdfa,
+                markup::nodes(document, markup::Syntax::Rdfa),
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://shop.example.com/items/42";
+
+    #[test]
+    fn test_json_ld_graph_and_nested_entities() {
+        let html = r#"<html><head>
+            <script type="application/ld+json">
+            {
+              "@context": "https://schema.org",
+              "@graph": [
+                {"@type": "WebSite", "name": "Example"},
+                {"@type": "WebPage", "mainEntity": {
+                  "@type": ["NewsArticle"],
+                  "headline": "Rates rise",
+                  "author": [{"@type": "Person", "name": "Ada"}, "Grace"],
+                  "publisher": {"@type": "Organization", "name": "Daily"},
+                  "datePublished": "2024-03-01T08:00:00Z",
+                  "image": {"@type": "ImageObject", "url": "/img/rates.jpg"}
+                }}
+              ]
+            }
+            </script>
+            <script type="application/ld+json">not json</script>
+        </head><body></body></html>"#;
+
+        let items = extract_structured_data(html, URL);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].source, StructuredDataSource::JsonLd);
+        assert_eq!(items[0].schema_type, "NewsArticle");
+        let SchemaEntity::Article(article) = &items[0].entity else {
+            panic!("expected article, got {:?}", items[0].entity);
+        };
+        assert_eq!(article.headline.as_deref(), Some("Rates rise"));
+        assert_eq!(article.authors, vec!["Ada", "Grace"]);
+        assert_eq!(article.publisher.as_deref(), Some("Daily"));
+        assert_eq!(
+            article.images,
+            vec!["https://shop.example.com/img/rates.jpg"]
+        );
+    }
+
+    #[test]
+    fn test_json_ld_product_offers_and_rating() {
+        let html = r#"<script type="application/ld+json">
+            <!--
+            {"@type": "Product", "name": "Kettle", "brand": {"@type": "Brand", "name": "Acme"},
+             "sku": "K-1", "gtin13": "4006381333931",
+             "offers": {"@type": "AggregateOffer", "lowPrice": "19.99", "priceCurrency": "EUR",
+                        "availability": "https://schema.org/InStock"},
+             "aggregateRating": {"ratingValue": "4.5", "reviewCount": 12}}
+            -->
+        </script>"#;
+
+        let items = extract_structured_data(html, URL);
+        let SchemaEntity::Product(product) = &items[0].entity else {
+            panic!("expected product");
+        };
+        assert_eq!(product.brand.as_deref(), Some("Acme"));
+        assert_eq!(product.gtin.as_deref(), Some("4006381333931"));
+        assert_eq!(product.offers[0].price.as_deref(), Some("19.99"));
+        assert_eq!(product.offers[0].currency.as_deref(), Some("EUR"));
+        assert_eq!(product.offers[0].availability.as_deref(), Some("InStock"));
+        let rating = product.rating.as_ref().unwrap();
+        assert_eq!(rating.value, Some(4.5));
+        assert_eq!(rating.count, Some(12));
+    }
+
+    #[test]
+    fn test_json_ld_recipe_and_job_posting() {
+        let html = r#"<script type="application/ld+json">[
+            {"@type": "Recipe", "name": "Pancakes", "recipeYield": ["4", "4 servings"],
+             "prepTime": "PT10M", "recipeIngredient": ["2 eggs", "1 cup flour"],
+             "recipeInstructions": [
+               {"@type": "HowToSection", "name": "Batter", "itemListElement": [
+                 {"@type": "HowToStep", "text": "Whisk the eggs."},
+                 {"@type": "HowToStep", "text": "Fold in the flour."}]},
+               "Fry in butter."]},
+            {"@type": "JobPosting", "title": "Rust Engineer",
+             "description": "<p>Build <b>crawlers</b>.</p>",
+             "hiringOrganization": {"@type": "Organization", "name": "RipTide"},
+             "employmentType": "FULL_TIME", "jobLocationType": "TELECOMMUTE",
+             "jobLocation": {"@type": "Place", "address": {"@type": "PostalAddress",
+               "addressLocality": "Berlin", "addressCountry": {"name": "DE"}}},
+             "baseSalary": {"@type": "MonetaryAmount", "currency": "EUR",
+               "value": {"@type": "QuantitativeValue", "minValue": 70000, "maxValue": 90000,
+                         "unitText": "YEAR"}}}
+        ]</script>"#;
+
+        let items = extract_structured_data(html, URL);
+        assert_eq!(items.len(), 2);
+
+        let SchemaEntity::Recipe(recipe) = &items[0].entity else {
+            panic!("expected recipe");
+        };
+        assert_eq!(recipe.recipe_yield.as_deref(), Some("4"));
+        assert_eq!(recipe.prep_time.as_deref(), Some("PT10M"));
+        assert_eq!(recipe.ingredients.len(), 2);
+        assert_eq!(
+            recipe.instructions,
+            vec!["Whisk the eggs.", "Fold in the flour.", "Fry in butter."]
+        );
+
+        let SchemaEntity::JobPosting(job) = &items[1].entity else {
+            panic!("expected job posting");
+        };
+        assert_eq!(job.description.as_deref(), Some("Build crawlers."));
+        assert_eq!(job.hiring_organization.as_deref(), Some("RipTide"));
+        assert_eq!(job.employment_types, vec!["FULL_TIME"]);
+        assert_eq!(job.location.as_deref(), Some("Berlin, DE"));
+        assert!(job.remote);
+        let salary = job.salary.as_ref().unwrap();
+        assert_eq!((salary.min, salary.max), (Some(70000.0), Some(90000.0)));
+        assert_eq!(salary.unit.as_deref(), Some("YEAR"));
+    }
+
+    #[test]
+    fn test_microdata_nested_items() {
+        let html = r#"<body>
+            <div itemscope itemtype="https://schema.org/Product">
+              <h1 itemprop="name">Kettle</h1>
+              <img itemprop="image" src="/img/kettle.png">
+              <div itemprop="brand" itemscope itemtype="https://schema.org/Brand">
+                <span itemprop="name">Acme</span>
+              </div>
+              <div itemprop="offers" itemscope itemtype="https://schema.org/Offer">
+                <meta itemprop="priceCurrency" content="USD">
+                <span itemprop="price" content="24.00">$24</span>
+                <link itemprop="availability" href="https://schema.org/OutOfStock">
+              </div>
+            </div>
+            <div itemscope itemtype="http://schema.org/Event">
+              <span itemprop="name">Launch party</span>
+              <time itemprop="startDate" datetime="2024-05-01T19:00">May 1</time>
+              <div itemprop="location" itemscope itemtype="http://schema.org/Place">
+                <span itemprop="name">Warehouse</span>
+              </div>
+            </div>
+        </body>"#;
+
+        let items = extract_structured_data(html, URL);
+        assert_eq!(items.len(), 2);
+        assert!(items
+            .iter()
+            .all(|item| item.source == StructuredDataSource::Microdata));
+
+        let SchemaEntity::Product(product) = &items[0].entity else {
+            panic!("expected product");
+        };
+        assert_eq!(product.name.as_deref(), Some("Kettle"));
+        assert_eq!(product.brand.as_deref(), Some("Acme"));
+        assert_eq!(
+            product.images,
+            vec!["https://shop.example.com/img/kettle.png"]
+        );
+        assert_eq!(product.offers[0].price.as_deref(), Some("24.00"));
+        assert_eq!(
+            product.offers[0].availability.as_deref(),
+            Some("OutOfStock")
+        );
+
+        let SchemaEntity::Event(event) = &items[1].entity else {
+            panic!("expected event");
+        };
+        assert_eq!(event.start_date.as_deref(), Some("2024-05-01T19:00"));
+        assert_eq!(event.location.as_deref(), Some("Warehouse"));
+    }
+
+    #[test]
+    fn test_rdfa_lite_article() {
+        let html = r#"<body vocab="https://schema.org/">
+            <article typeof="BlogPosting">
+              <h1 property="headline">Crawling politely</h1>
+              <span property="author" typeof="Person"><span property="name">Lin</span></span>
+              <meta property="datePublished" content="2024-02-10">
+              <a property="url" href="/blog/polite">permalink</a>
+            </article>
+            <div prefix="schema: https://schema.org/" typeof="schema:JobPosting">
+              <span property="schema:title">Crawler SRE</span>
+            </div>
+        </body>"#;
+
+        let items = extract_structured_data(html, URL);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].source, StructuredDataSource::Rdfa);
+
+        let SchemaEntity::Article(article) = &items[0].entity else {
+            panic!("expected article");
+        };
+        assert_eq!(article.headline.as_deref(), Some("Crawling politely"));
+        assert_eq!(article.authors, vec!["Lin"]);
+        assert_eq!(article.date_published.as_deref(), Some("2024-02-10"));
+        assert_eq!(
+            article.url.as_deref(),
+            Some("https://shop.example.com/blog/polite")
+        );
+
+        assert_eq!(items[1].schema_type, "JobPosting");
+    }
+
+    #[test]
+    fn test_unsupported_and_empty_entities_are_skipped() {
+        let html = r#"
+            <script type="application/ld+json">{"@type": "BreadcrumbList"}</script>
+            <script type="application/ld+json">{"@type": "Article"}</script>
+            <div itemscope itemtype="https://schema.org/Person"><span itemprop="name">X</span></div>
+        "#;
+
+        assert!(extract_structured_data(html, URL).is_empty());
+    }
+}
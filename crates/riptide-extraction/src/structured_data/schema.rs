@@ -0,0 +1,441 @@
+//! Mapping of JSON-LD shaped nodes onto typed schema.org entities
+//!
+//! schema.org values are loosely typed: an author may be a string, a
+//! `Person` or an array of either, a price a number or a string. The
+//! accessors here accept all of those shapes and reduce them to the plain
+//! values the entity types store.
+
+use scraper::Html;
+use serde_json::Value;
+use url::Url;
+
+use riptide_types::structured_data::{
+    SchemaArticle, SchemaEntity, SchemaEvent, SchemaJobPosting, SchemaOffer, SchemaProduct,
+    SchemaRating, SchemaRecipe, SchemaSalary, StructuredDataItem, StructuredDataSource,
+};
+
+/// Deepest nesting searched for supported entities inside unsupported ones
+pub(super) const MAX_DEPTH: usize = 8;
+
+const ARTICLE_TYPES: &[&str] = &[
+    "Article",
+    "NewsArticle",
+    "AnalysisNewsArticle",
+    "OpinionNewsArticle",
+    "ReportageNewsArticle",
+    "BackgroundNewsArticle",
+    "ReviewNewsArticle",
+    "BlogPosting",
+    "LiveBlogPosting",
+    "SocialMediaPosting",
+    "TechArticle",
+    "ScholarlyArticle",
+    "Report",
+];
+
+const PRODUCT_TYPES: &[&str] = &[
+    "Product",
+    "ProductGroup",
+    "ProductModel",
+    "IndividualProduct",
+];
+
+/// Maps JSON-LD shaped nodes onto [`StructuredDataItem`]s
+pub(super) struct SchemaMapper<'a> {
+    base: Option<&'a Url>,
+}
+
+impl<'a> SchemaMapper<'a> {
+    pub(super) fn new(base: Option<&'a Url>) -> Self {
+        Self { base }
+    }
+
+    /// Supported entities in `node`: the node itself when its type is
+    /// supported, otherwise supported entities nested in its properties
+    /// (e.g. a `WebPage`'s `mainEntity`)
+    pub(super) fn items(
+        &self,
+        node: &Value,
+        source: StructuredDataSource,
+    ) -> Vec<StructuredDataItem> {
+        let mut items = Vec::new();
+        self.collect(node, source, 0, &mut items);
+        items
+    }
+
+    fn collect(
+        &self,
+        node: &Value,
+        source: StructuredDataSource,
+        depth: usize,
+        items: &mut Vec<StructuredDataItem>,
+    ) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+
+        match node {
+            Value::Array(values) => {
+                for value in values {
+                    self.collect(value, source, depth + 1, items);
+                }
+            }
+            Value::Object(object) => {
+                if let Some(item) = self.item(node, source) {
+                    items.push(item);
+                    return;
+                }
+                for (key, value) in object {
+                    if !key.starts_with('@') {
+                        self.collect(value, source, depth + 1, items);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn item(&self, node: &Value, source: StructuredDataSource) -> Option<StructuredDataItem> {
+        let (schema_type, entity) = types(node).into_iter().find_map(|schema_type| {
+            let entity = self.entity(&schema_type, node)?;
+            Some((schema_type, entity))
+        })?;
+        Some(StructuredDataItem {
+            source,
+            schema_type,
+            entity,
+        })
+    }
+
+    /// Entity for a supported type, or `None` when the type is unsupported
+    /// or the node carries none of the fields RipTide keeps
+    fn entity(&self, schema_type: &str, node: &Value) -> Option<SchemaEntity> {
+        if ARTICLE_TYPES.contains(&schema_type) {
+            let article = self.article(node);
+            (article != SchemaArticle::default()).then_some(SchemaEntity::Article(article))
+        } else if PRODUCT_TYPES.contains(&schema_type) {
+            let product = self.product(node);
+            (product != SchemaProduct::default()).then_some(SchemaEntity::Product(product))
+        } else if schema_type.ends_with("Event") {
+            let event = self.event(node);
+            (event != SchemaEvent::default()).then_some(SchemaEntity::Event(event))
+        } else if schema_type == "Recipe" {
+            let recipe = self.recipe(node);
+            (recipe != SchemaRecipe::default()).then_some(SchemaEntity::Recipe(recipe))
+        } else if schema_type == "JobPosting" {
+            let job = self.job_posting(node);
+            (job != SchemaJobPosting::default()).then_some(SchemaEntity::JobPosting(job))
+        } else {
+            None
+        }
+    }
+
+    fn article(&self, node: &Value) -> SchemaArticle {
+        SchemaArticle {
+            headline: text(node.get("headline")).or_else(|| text(node.get("name"))),
+            description: plain_text(node.get("description")),
+            authors: names(node.get("author")),
+            publisher: name(node.get("publisher")),
+            date_published: text(node.get("datePublished")),
+            date_modified: text(node.get("dateModified")),
+            section: text(node.get("articleSection")),
+            images: self.urls(node.get("image")),
+            url: self.url(node.get("url")),
+        }
+    }
+
+    fn product(&self, node: &Value) -> SchemaProduct {
+        let gtin = ["gtin", "gtin13", "gtin12", "gtin8", "gtin14"]
+            .iter()
+            .find_map(|key| text(node.get(*key)));
+        SchemaProduct {
+            name: text(node.get("name")),
+            description: plain_text(node.get("description")),
+            brand: name(node.get("brand")),
+            sku: text(node.get("sku")),
+            gtin,
+            images: self.urls(node.get("image")),
+            offers: self.offers(node.get("offers")),
+            rating: rating(node.get("aggregateRating")),
+            url: self.url(node.get("url")),
+        }
+    }
+
+    fn event(&self, node: &Value) -> SchemaEvent {
+        SchemaEvent {
+            name: text(node.get("name")),
+            description: plain_text(node.get("description")),
+            start_date: text(node.get("startDate")),
+            end_date: text(node.get("endDate")),
+            location: first(node.get("location")).and_then(|location| {
+                name(Some(location))
+                    .or_else(|| address(location.get("address")))
+                    .or_else(|| self.url(location.get("url")))
+            }),
+            organizer: name(node.get("organizer")),
+            status: text(node.get("eventStatus")).map(|status| enum_name(&status)),
+            offers: self.offers(node.get("offers")),
+            images: self.urls(node.get("image")),
+            url: self.url(node.get("url")),
+        }
+    }
+
+    fn recipe(&self, node: &Value) -> SchemaRecipe {
+        let mut instructions = Vec::new();
+        collect_steps(node.get("recipeInstructions"), 0, &mut instructions);
+        SchemaRecipe {
+            name: text(node.get("name")),
+            description: plain_text(node.get("description")),
+            authors: names(node.get("author")),
+            prep_time: text(node.get("prepTime")),
+            cook_time: text(node.get("cookTime")),
+            total_time: text(node.get("totalTime")),
+            recipe_yield: text(node.get("recipeYield")),
+            ingredients: texts(
+                node.get("recipeIngredient")
+                    .or_else(|| node.get("ingredients")),
+            ),
+            instructions,
+            images: self.urls(node.get("image")),
+            rating: rating(node.get("aggregateRating")),
+        }
+    }
+
+    fn job_posting(&self, node: &Value) -> SchemaJobPosting {
+        let remote = texts(node.get("jobLocationType"))
+            .iter()
+            .any(|kind| enum_name(kind).eq_ignore_ascii_case("TELECOMMUTE"));
+        SchemaJobPosting {
+            title: text(node.get("title")).or_else(|| text(node.get("name"))),
+            description: plain_text(node.get("description")),
+            hiring_organization: name(node.get("hiringOrganization")),
+            date_posted: text(node.get("datePosted")),
+            valid_through: text(node.get("validThrough")),
+            employment_types: texts(node.get("employmentType")),
+            location: first(node.get("jobLocation")).and_then(|location| {
+                address(location.get("address")).or_else(|| name(Some(location)))
+            }),
+            remote,
+            salary: salary(node.get("baseSalary")),
+        }
+    }
+
+    fn offers(&self, value: Option<&Value>) -> Vec<SchemaOffer> {
+        let mut offers = Vec::new();
+        for offer in values(value) {
+            // AggregateOffer summarizes a price range, optionally listing
+            // the individual offers
+            if offer.get("offers").is_some() {
+                offers.extend(self.offers(offer.get("offers")));
+                continue;
+            }
+
+            let parsed = SchemaOffer {
+                price: text(offer.get("price")).or_else(|| text(offer.get("lowPrice"))),
+                currency: text(offer.get("priceCurrency")),
+                availability: text(offer.get("availability")).map(|a| enum_name(&a)),
+                url: self.url(offer.get("url")),
+            };
+            if parsed != SchemaOffer::default() {
+                offers.push(parsed);
+            }
+        }
+        offers
+    }
+
+    /// Absolute URL of a URL-valued property or an object's `url`
+    pub(super) fn url(&self, value: Option<&Value>) -> Option<String> {
+        let value = first(value)?;
+        let raw = match value {
+            Value::Object(_) => text(value.get("url"))
+                .or_else(|| text(value.get("contentUrl")))
+                .or_else(|| text(value.get("@id")))?,
+            _ => text(Some(value))?,
+        };
+        match self.base {
+            Some(base) => base.join(&raw).ok().map(String::from),
+            None => Url::parse(&raw).ok().map(String::from),
+        }
+    }
+
+    fn urls(&self, value: Option<&Value>) -> Vec<String> {
+        let mut urls: Vec<String> = Vec::new();
+        for value in values(value) {
+            if let Some(url) = self.url(Some(value)) {
+                if !url.starts_with("data:") && !urls.contains(&url) {
+                    urls.push(url);
+                }
+            }
+        }
+        urls
+    }
+}
+
+/// Declared types of a node, without vocabulary URL or prefix
+pub(super) fn types(node: &Value) -> Vec<String> {
+    values(node.get("@type"))
+        .filter_map(|t| t.as_str())
+        .map(enum_name)
+        .collect()
+}
+
+/// The values of a property, whether it holds one value or an array
+pub(super) fn values(value: Option<&Value>) -> impl Iterator<Item = &Value> {
+    let slice = match value {
+        Some(Value::Array(values)) => values.as_slice(),
+        Some(Value::Null) | None => &[],
+        Some(value) => std::slice::from_ref(value),
+    };
+    slice.iter()
+}
+
+fn first(value: Option<&Value>) -> Option<&Value> {
+    values(value).next()
+}
+
+/// Trimmed, non-empty text of a value, taking the first of an array and the
+/// `@value`, `name` or `text` of an object
+pub(super) fn text(value: Option<&Value>) -> Option<String> {
+    let text = match first(value)? {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) => n.to_string(),
+        object @ Value::Object(_) => {
+            return ["@value", "name", "text"]
+                .iter()
+                .find_map(|key| text(object.get(*key)))
+        }
+        _ => return None,
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+fn texts(value: Option<&Value>) -> Vec<String> {
+    values(value).filter_map(|v| text(Some(v))).collect()
+}
+
+/// Text with any HTML markup removed, for descriptions that embed HTML
+pub(super) fn plain_text(value: Option<&Value>) -> Option<String> {
+    let raw = text(value)?;
+    if !raw.contains('<') {
+        return Some(raw);
+    }
+    let fragment = Html::parse_fragment(&raw);
+    let plain = fragment
+        .root_element()
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!plain.is_empty()).then_some(plain)
+}
+
+/// Name of a `Person`/`Organization`/`Brand`/`Place`, or a plain string
+fn name(value: Option<&Value>) -> Option<String> {
+    match first(value)? {
+        object @ Value::Object(_) => text(object.get("name")),
+        other => text(Some(other)),
+    }
+}
+
+fn names(value: Option<&Value>) -> Vec<String> {
+    values(value).filter_map(|v| name(Some(v))).collect()
+}
+
+pub(super) fn number(value: Option<&Value>) -> Option<f64> {
+    match first(value)? {
+        Value::Number(n) => n.as_f64(),
+        object @ Value::Object(_) => number(object.get("@value")),
+        other => text(Some(other))?.replace(',', ".").parse().ok(),
+    }
+}
+
+fn rating(value: Option<&Value>) -> Option<SchemaRating> {
+    let value = first(value)?;
+    let rating = SchemaRating {
+        value: number(value.get("ratingValue")),
+        best: number(value.get("bestRating")),
+        count: number(value.get("ratingCount"))
+            .or_else(|| number(value.get("reviewCount")))
+            .map(|count| count as u64),
+    };
+    (rating != SchemaRating::default()).then_some(rating)
+}
+
+fn salary(value: Option<&Value>) -> Option<SchemaSalary> {
+    let value = first(value)?;
+    let salary = match value {
+        Value::Object(_) => {
+            let amount = value.get("value");
+            let quantitative = first(amount).filter(|v| v.is_object());
+            let exact = match quantitative {
+                Some(q) => number(q.get("value")),
+                None => number(amount),
+            };
+            SchemaSalary {
+                currency: text(value.get("currency")),
+                min: quantitative
+                    .and_then(|q| number(q.get("minValue")))
+                    .or(exact),
+                max: quantitative
+                    .and_then(|q| number(q.get("maxValue")))
+                    .or(exact),
+                unit: quantitative.and_then(|q| text(q.get("unitText"))),
+            }
+        }
+        other => {
+            let exact = number(Some(other));
+            SchemaSalary {
+                min: exact,
+                max: exact,
+                ..Default::default()
+            }
+        }
+    };
+    (salary != SchemaSalary::default()).then_some(salary)
+}
+
+/// One-line postal address, e.g. "Berlin, DE"
+fn address(value: Option<&Value>) -> Option<String> {
+    let value = first(value)?;
+    if !value.is_object() {
+        return text(Some(value));
+    }
+    let parts: Vec<String> = [
+        "streetAddress",
+        "addressLocality",
+        "addressRegion",
+        "postalCode",
+        "addressCountry",
+    ]
+    .iter()
+    .filter_map(|key| name(value.get(*key)))
+    .collect();
+    (!parts.is_empty()).then(|| parts.join(", "))
+}
+
+/// Recipe steps from strings, `HowToStep`s and `HowToSection`s, flattened
+fn collect_steps(value: Option<&Value>, depth: usize, steps: &mut Vec<String>) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    for value in values(value) {
+        if let Some(section) = value.get("itemListElement") {
+            collect_steps(Some(section), depth + 1, steps);
+        } else if let Some(step) = value
+            .get("text")
+            .map_or_else(|| plain_text(Some(value)), |t| plain_text(Some(t)))
+        {
+            steps.push(step);
+        }
+    }
+}
+
+/// Last segment of a schema.org IRI: "https://schema.org/InStock" -> "InStock"
+fn enum_name(value: &str) -> String {
+    value
+        .rsplit(['/', '#', ':'])
+        .find(|segment| !segment.is_empty())
+        .unwrap_or(value)
+        .to_string()
+}
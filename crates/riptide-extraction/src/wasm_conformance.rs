@@ -0,0 +1,532 @@
+//! Conformance harness for extractor component upgrades
+//!
+//! Loads several builds of `riptide-extractor-wasm`, runs a shared HTML
+//! corpus through each and reports how every candidate's output and timing
+//! differ from the first (baseline) build. The corpus uses the golden corpus
+//! layout: one directory per case holding `input.html` and a `case.json`
+//! with the page `url`.
+//!
+//! ```text
+//! cargo run -p riptide-extraction --features wasm-extractor --example wasm_conformance -- \
+//!     --corpus crates/riptide-extraction/tests/corpus \
+//!     current=/opt/riptide/extractor.wasm candidate=target/wasm32-wasip2/release/riptide_extractor_wasm.wasm
+//! ```
+
+use crate::wasm_extraction::{CmExtractor, ExtractorConfig};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Fields that vary between runs or builds and are never compared
+const VOLATILE_FIELDS: [&str; 3] = ["parser_metadata", "provenance", "html"];
+
+/// Fields compared as unordered sets
+const SET_FIELDS: [&str; 3] = ["links", "media", "categories"];
+
+/// Fields compared by word overlap
+const TEXT_FIELDS: [&str; 2] = ["text", "markdown"];
+
+/// A page run through every component version
+#[derive(Debug, Clone)]
+pub struct ConformanceCase {
+    pub name: String,
+    pub url: String,
+    pub html: String,
+}
+
+#[derive(Deserialize)]
+struct CaseFile {
+    url: String,
+}
+
+/// Load every case directory under `dir`, sorted by name
+pub fn load_corpus(dir: &Path) -> Result<Vec<ConformanceCase>> {
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("reading corpus {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+
+    dirs.into_iter()
+        .map(|case_dir| {
+            let name = case_dir
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let case: CaseFile = serde_json::from_str(
+                &std::fs::read_to_string(case_dir.join("case.json"))
+                    .with_context(|| format!("{}/case.json", name))?,
+            )
+            .with_context(|| format!("{}/case.json", name))?;
+            let html = std::fs::read_to_string(case_dir.join("input.html"))
+                .with_context(|| format!("{}/input.html", name))?;
+            Ok(ConformanceCase {
+                name,
+                url: case.url,
+                html,
+            })
+        })
+        .collect()
+}
+
+/// A component build under test
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentVersion {
+    /// Name used in the report, e.g. `current` or `v0.3.0`
+    pub label: String,
+    pub path: PathBuf,
+}
+
+impl ComponentVersion {
+    pub fn new(label: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            label: label.into(),
+            path: path.into(),
+        }
+    }
+}
+
+/// Harness settings
+#[derive(Debug, Clone)]
+pub struct ConformanceConfig {
+    /// Extraction mode passed to every component
+    pub mode: String,
+    /// Timed runs per case and version; the median is reported
+    pub iterations: usize,
+    /// Minimum word-overlap similarity of text fields (1.0 = same words)
+    pub min_text_similarity: f64,
+    /// Largest accepted ratio of candidate to baseline median time
+    pub max_slowdown: f64,
+    /// Component configuration shared by every version
+    pub extractor: ExtractorConfig,
+}
+
+impl Default for ConformanceConfig {
+    fn default() -> Self {
+        Self {
+            mode: "article".to_string(),
+            iterations: 5,
+            min_text_similarity: 0.97,
+            max_slowdown: 1.25,
+            extractor: ExtractorConfig::default(),
+        }
+    }
+}
+
+/// Output and median time of one version on one case
+struct CaseRun {
+    /// Comparable output: the extracted doc, or `{"error": ...}`
+    output: Value,
+    median: Duration,
+}
+
+/// How a candidate differs from the baseline on one case
+#[derive(Debug, Clone, Serialize)]
+pub struct CaseComparison {
+    pub case: String,
+    pub differences: Vec<String>,
+    pub baseline_median: Duration,
+    pub candidate_median: Duration,
+    /// Candidate median over baseline median
+    pub slowdown: f64,
+}
+
+/// Comparison of one candidate version against the baseline
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionReport {
+    pub version: ComponentVersion,
+    pub load_time: Duration,
+    pub cases: Vec<CaseComparison>,
+}
+
+impl VersionReport {
+    /// Cases whose output differs from the baseline
+    pub fn output_regressions(&self) -> impl Iterator<Item = &CaseComparison> {
+        self.cases.iter().filter(|c| !c.differences.is_empty())
+    }
+
+    /// Cases slower than `max_slowdown` times the baseline
+    pub fn performance_regressions(
+        &self,
+        max_slowdown: f64,
+    ) -> impl Iterator<Item = &CaseComparison> {
+        self.cases.iter().filter(move |c| c.slowdown > max_slowdown)
+    }
+
+    /// Candidate total median time over the baseline's
+    pub fn overall_slowdown(&self) -> f64 {
+        let baseline: Duration = self.cases.iter().map(|c| c.baseline_median).sum();
+        let candidate: Duration = self.cases.iter().map(|c| c.candidate_median).sum();
+        ratio(candidate, baseline)
+    }
+}
+
+/// Compatibility report for a set of component versions
+#[derive(Debug, Clone, Serialize)]
+pub struct ConformanceReport {
+    pub baseline: ComponentVersion,
+    pub baseline_load_time: Duration,
+    pub mode: String,
+    pub max_slowdown: f64,
+    pub candidates: Vec<VersionReport>,
+}
+
+impl ConformanceReport {
+    /// True when no candidate changes output or exceeds the slowdown limit
+    pub fn is_compatible(&self) -> bool {
+        self.candidates.iter().all(|v| {
+            v.output_regressions().next().is_none()
+                && v.performance_regressions(self.max_slowdown)
+                    .next()
+                    .is_none()
+        })
+    }
+
+    /// Human-readable summary for upgrade reviews
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "# Extractor conformance: baseline `{}` (mode `{}`)\n",
+            self.baseline.label, self.mode
+        );
+        for version in &self.candidates {
+            let regressions = version.output_regressions().count();
+            let slow = version.performance_regressions(self.max_slowdown).count();
+            let _ = writeln!(
+                out,
+                "## `{}`: {}\n\n{} of {} cases differ, {} slower than {:.2}x, overall {:.2}x, load {} ms vs {} ms\n",
+                version.version.label,
+                if regressions == 0 && slow == 0 { "compatible" } else { "INCOMPATIBLE" },
+                regressions,
+                version.cases.len(),
+                slow,
+                self.max_slowdown,
+                version.overall_slowdown(),
+                version.load_time.as_millis(),
+                self.baseline_load_time.as_millis(),
+            );
+            let _ = writeln!(
+                out,
+                "| case | baseline | candidate | slowdown | differences |"
+            );
+            let _ = writeln!(out, "|---|---|---|---|---|");
+            for case in &version.cases {
+                let _ = writeln!(
+                    out,
+                    "| {} | {:.2?} | {:.2?} | {:.2}x | {} |",
+                    case.case,
+                    case.baseline_median,
+                    case.candidate_median,
+                    case.slowdown,
+                    if case.differences.is_empty() {
+                        "-".to_string()
+                    } else {
+                        case.differences.join("; ").replace('|', "\\|")
+                    }
+                );
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Runs a corpus through several component versions
+pub struct ConformanceHarness {
+    config: ConformanceConfig,
+}
+
+impl ConformanceHarness {
+    pub fn new(config: ConformanceConfig) -> Self {
+        Self { config }
+    }
+
+    /// Compare every version after the first against the first
+    pub async fn run(
+        &self,
+        versions: &[ComponentVersion],
+        cases: &[ConformanceCase],
+    ) -> Result<ConformanceReport> {
+        let (baseline, candidates) = versions
+            .split_first()
+            .context("at least one component version is required")?;
+        let (baseline_load_time, baseline_runs) = self.run_version(baseline, cases).await?;
+
+        let mut reports = Vec::with_capacity(candidates.len());
+        for version in candidates {
+            let (load_time, runs) = self.run_version(version, cases).await?;
+            let comparisons = cases
+                .iter()
+                .zip(baseline_runs.iter().zip(&runs))
+                .map(|(case, (base, cand))| CaseComparison {
+                    case: case.name.clone(),
+                    differences: diff_outputs(
+                        &base.output,
+                        &cand.output,
+                        self.config.min_text_similarity,
+                    ),
+                    baseline_median: base.median,
+                    candidate_median: cand.median,
+                    slowdown: ratio(cand.median, base.median),
+                })
+                .collect();
+            reports.push(VersionReport {
+                version: version.clone(),
+                load_time,
+                cases: comparisons,
+            });
+        }
+
+        Ok(ConformanceReport {
+            baseline: baseline.clone(),
+            baseline_load_time,
+            mode: self.config.mode.clone(),
+            max_slowdown: self.config.max_slowdown,
+            candidates: reports,
+        })
+    }
+
+    async fn run_version(
+        &self,
+        version: &ComponentVersion,
+        cases: &[ConformanceCase],
+    ) -> Result<(Duration, Vec<CaseRun>)> {
+        let path = version.path.to_string_lossy();
+        let start = Instant::now();
+        let extractor = CmExtractor::with_config(&path, self.config.extractor.clone())
+            .await
+            .with_context(|| format!("loading {} from {}", version.label, path))?;
+        // Compile and instantiate once so the first case is not charged for it
+        let _ = extractor.extract("<html></html>", "https://example.com/", &self.config.mode);
+        let load_time = start.elapsed();
+
+        let runs = cases
+            .iter()
+            .map(|case| self.run_case(&extractor, case))
+            .collect();
+        Ok((load_time, runs))
+    }
+
+    fn run_case(&self, extractor: &CmExtractor, case: &ConformanceCase) -> CaseRun {
+        let mut timings = Vec::with_capacity(self.config.iterations.max(1));
+        let mut output = Value::Null;
+        for _ in 0..self.config.iterations.max(1) {
+            let start = Instant::now();
+            let result = extractor.extract(&case.html, &case.url, &self.config.mode);
+            timings.push(start.elapsed());
+            output = comparable_output(result);
+        }
+        timings.sort();
+        CaseRun {
+            output,
+            median: timings[timings.len() / 2],
+        }
+    }
+}
+
+fn comparable_output(result: Result<riptide_types::ExtractedDoc>) -> Value {
+    match result {
+        Ok(doc) => {
+            let mut value = serde_json::to_value(doc).unwrap_or(Value::Null);
+            if let Value::Object(fields) = &mut value {
+                for field in VOLATILE_FIELDS {
+                    fields.remove(field);
+                }
+            }
+            value
+        }
+        Err(e) => serde_json::json!({ "error": e.to_string() }),
+    }
+}
+
+fn ratio(candidate: Duration, baseline: Duration) -> f64 {
+    if baseline.is_zero() {
+        return 1.0;
+    }
+    candidate.as_secs_f64() / baseline.as_secs_f64()
+}
+
+/// Dice coefficient of the lowercased word multisets of `a` and `b`
+fn text_similarity(a: &str, b: &str) -> f64 {
+    let words = |s: &str| -> Vec<String> { s.split_whitespace().map(str::to_lowercase).collect() };
+    let (a, b) = (words(a), words(b));
+    if a == b {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for word in &a {
+        *counts.entry(word).or_default() += 1;
+    }
+    let common = b
+        .iter()
+        .filter(|word| match counts.get_mut(word.as_str()) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        })
+        .count();
+    (2 * common) as f64 / (a.len() + b.len()) as f64
+}
+
+fn as_set(value: Option<&Value>) -> BTreeSet<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|items| items.iter().map(Value::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Field-level differences between a baseline and a candidate output
+pub fn diff_outputs(baseline: &Value, candidate: &Value, min_text_similarity: f64) -> Vec<String> {
+    let empty = Map::new();
+    let baseline = baseline.as_object().unwrap_or(&empty);
+    let candidate = candidate.as_object().unwrap_or(&empty);
+    let fields: BTreeSet<&String> = baseline.keys().chain(candidate.keys()).collect();
+
+    let mut differences = Vec::new();
+    for field in fields {
+        let (base, cand) = (baseline.get(field), candidate.get(field));
+        if base == cand {
+            continue;
+        }
+        let field = field.as_str();
+        if TEXT_FIELDS.contains(&field) {
+            let similarity = text_similarity(
+                base.and_then(Value::as_str).unwrap_or_default(),
+                cand.and_then(Value::as_str).unwrap_or_default(),
+            );
+            if similarity < min_text_similarity {
+                differences.push(format!("{}: similarity {:.3}", field, similarity));
+            }
+        } else if SET_FIELDS.contains(&field) {
+            let (base, cand) = (as_set(base), as_set(cand));
+            if base != cand {
+                differences.push(format!(
+                    "{}: {} removed, {} added",
+                    field,
+                    base.difference(&cand).count(),
+                    cand.difference(&base).count()
+                ));
+            }
+        } else {
+            let show = |v: Option<&Value>| v.map_or("missing".to_string(), Value::to_string);
+            differences.push(format!("{}: {} -> {}", field, show(base), show(cand)));
+        }
+    }
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn comparison(
+        case: &str,
+        differences: Vec<String>,
+        base_ms: u64,
+        cand_ms: u64,
+    ) -> CaseComparison {
+        let (baseline_median, candidate_median) = (
+            Duration::from_millis(base_ms),
+            Duration::from_millis(cand_ms),
+        );
+        CaseComparison {
+            case: case.to_string(),
+            differences,
+            baseline_median,
+            candidate_median,
+            slowdown: ratio(candidate_median, baseline_median),
+        }
+    }
+
+    fn report(cases: Vec<CaseComparison>) -> ConformanceReport {
+        ConformanceReport {
+            baseline: ComponentVersion::new("current", "current.wasm"),
+            baseline_load_time: Duration::from_millis(40),
+            mode: "article".to_string(),
+            max_slowdown: 1.25,
+            candidates: vec![VersionReport {
+                version: ComponentVersion::new("next", "next.wasm"),
+                load_time: Duration::from_millis(45),
+                cases,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_identical_outputs_have_no_differences() {
+        let doc = json!({"title": "A", "text": "one two", "links": ["a", "b"]});
+        assert!(diff_outputs(&doc, &doc, 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_diff_tolerances() {
+        let base = json!({
+            "title": "A",
+            "text": "one two three four five six seven eight nine ten",
+            "links": ["a", "b"],
+        });
+        let cand = json!({
+            "title": "B",
+            "text": "one two three four five six seven eight nine eleven",
+            "links": ["b", "a"],
+            "byline": "X",
+        });
+
+        let differences = diff_outputs(&base, &cand, 0.85);
+        assert_eq!(
+            differences,
+            vec!["byline: missing -> \"X\"", "title: \"A\" -> \"B\""]
+        );
+
+        let strict = diff_outputs(&base, &cand, 1.0);
+        assert!(strict
+            .iter()
+            .any(|d| d.starts_with("text: similarity 0.900")));
+    }
+
+    #[test]
+    fn test_diff_reports_set_changes_and_errors() {
+        let base = json!({"links": ["a", "b"]});
+        let cand = json!({"links": ["a", "c", "d"]});
+        assert_eq!(
+            diff_outputs(&base, &cand, 1.0),
+            vec!["links: 1 removed, 2 added"]
+        );
+
+        let failed = json!({"error": "boom"});
+        let differences = diff_outputs(&base, &failed, 1.0);
+        assert!(differences.contains(&"error: missing -> \"boom\"".to_string()));
+    }
+
+    #[test]
+    fn test_report_compatibility() {
+        let clean = report(vec![
+            comparison("a", vec![], 10, 11),
+            comparison("b", vec![], 20, 20),
+        ]);
+        assert!(clean.is_compatible());
+        assert!((clean.candidates[0].overall_slowdown() - 31.0 / 30.0).abs() < 1e-9);
+        assert!(clean.to_markdown().contains("`next`: compatible"));
+
+        let changed = report(vec![comparison("a", vec!["title: 1 -> 2".into()], 10, 10)]);
+        assert!(!changed.is_compatible());
+
+        let slow = report(vec![comparison("a", vec![], 10, 20)]);
+        assert!(!slow.is_compatible());
+        let markdown = slow.to_markdown();
+        assert!(markdown.contains("INCOMPATIBLE"));
+        assert!(markdown.contains("| a | 10.00ms | 20.00ms | 2.00x | - |"));
+    }
+}
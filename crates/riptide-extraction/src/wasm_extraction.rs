@@ -8,11 +8,17 @@ use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use wasmtime::{component::*, Config, Engine, ResourceLimiter, Store};
+use wasmtime::{component::*, Config, Engine, EngineWeak, ResourceLimiter, Store, Trap};
 use wasmtime_wasi::{WasiCtx, WasiCtxBuilder, WasiCtxView, WasiView};
 
 // Import ExtractedDoc from riptide-types instead of duplicating
-use riptide_types::{ExtractedDoc, ParserMetadata};
+use riptide_types::{ExtractedDoc, ParserMetadata, RiptideError};
+
+use crate::markdown::MarkdownFlavor;
+use crate::wasm_fuel::{FuelMeter, DEFAULT_EXTRACTION_FUEL};
+
+/// Interval at which an extractor advances its engine epoch
+const EPOCH_TICK_MS: u64 = 10;
 
 // WIT bindings - Wasmtime 37 bindgen! macro
 // Generate bindings in a module to avoid namespace pollution
@@ -38,7 +44,7 @@ pub enum HostExtractionMode {
     /// Extract only metadata (title, description, structured data)
     Metadata,
 
-    /// Custom extraction using provided CSS selectors
+    /// Custom extraction using provided CSS selectors or XPath expressions
     Custom(Vec<String>),
 }
 
@@ -97,6 +103,20 @@ mod conversions {
                     extraction_path: None,
                     primary_error: None,
                 }),
+                provenance: None,
+                translation: None,
+                structured_data: Vec::new(),
+                annotations: None,
+                feed: None,
+                feed_links: Vec::new(),
+                classification: None,
+                clean_html: None,
+                media_assets: Vec::new(),
+                embedded_media: Vec::new(),
+                images: Vec::new(),
+                references: Vec::new(),
+                language_spans: Vec::new(),
+                quality_report: None,
                 word_count: wit.word_count,
                 categories: wit.categories,
                 site_name: wit.site_name,
@@ -363,7 +383,8 @@ impl WasiView for WasmResourceTracker {
 pub struct ExtractorConfig {
     /// Maximum memory pages for WASM instances (64KB per page)
     pub max_memory_pages: usize,
-    /// Timeout for extraction operations
+    /// CPU time one extraction may run before it is interrupted, enforced
+    /// with wasmtime epoch interruption
     pub extraction_timeout: Duration,
     /// Enable WASM SIMD optimizations
     pub enable_simd: bool,
@@ -373,6 +394,17 @@ pub struct ExtractorConfig {
     pub instance_pool_size: usize,
     /// Maximum idle time for pooled instances
     pub max_idle_time: Duration,
+    /// Markdown flavor of `ExtractedDoc::markdown`
+    pub markdown_flavor: MarkdownFlavor,
+}
+
+impl ExtractorConfig {
+    /// Epoch ticks an extraction may run for, given [`EPOCH_TICK_MS`]
+    fn epoch_deadline_ticks(&self) -> u64 {
+        (self.extraction_timeout.as_millis() as u64)
+            .div_ceil(EPOCH_TICK_MS)
+            .max(1)
+    }
 }
 
 impl Default for ExtractorConfig {
@@ -384,6 +416,7 @@ impl Default for ExtractorConfig {
             enable_aot_cache: true,
             instance_pool_size: 4,
             max_idle_time: Duration::from_secs(300),
+            markdown_flavor: MarkdownFlavor::default(),
         }
     }
 }
@@ -407,12 +440,24 @@ impl CmExtractor {
 
     /// Create a new WASM component extractor with custom configuration
     pub async fn with_config(wasm_path: &str, config: ExtractorConfig) -> Result<Self> {
+        let component_bytes = std::fs::read(wasm_path)?;
+        Self::from_bytes(&component_bytes, config)
+    }
+
+    /// Create a WASM component extractor from component bytes
+    ///
+    /// Compiling the component takes a while; call this off the async
+    /// runtime's worker threads.
+    pub fn from_bytes(component_bytes: &[u8], config: ExtractorConfig) -> Result<Self> {
         let mut wasmtime_config = Config::new();
         wasmtime_config.wasm_component_model(true);
 
         // Enable fuel consumption for execution limits
         wasmtime_config.consume_fuel(true);
 
+        // Interrupt extractions running past `extraction_timeout`
+        wasmtime_config.epoch_interruption(true);
+
         // Configure memory limits - convert pages to bytes (64KB per page)
         // This prevents "error while executing at wasm backtrace" memory allocation failures
         let max_memory_bytes = config.max_memory_pages * 65536; // 64KB per page
@@ -450,8 +495,8 @@ impl CmExtractor {
         }
 
         let engine = Engine::new(&wasmtime_config)?;
-        let component_bytes = std::fs::read(wasm_path)?;
         let component = Component::new(&engine, component_bytes)?;
+        spawn_epoch_ticker(engine.weak())?;
 
         // Create linker with WASI Preview 2 support
         let mut linker = Linker::new(&engine);
@@ -485,8 +530,14 @@ impl CmExtractor {
         // Use WasmResourceTracker which implements ResourceLimiter for memory control
         let resource_tracker = WasmResourceTracker::new(self.config.max_memory_pages);
 
+        // Fuel limit for execution, capped by any crawl budget in scope
+        let fuel = FuelMeter::grant(DEFAULT_EXTRACTION_FUEL);
+        if fuel == 0 {
+            anyhow::bail!("WASM fuel budget exhausted");
+        }
         let mut store = Store::new(&self.engine, resource_tracker);
-        store.set_fuel(1_000_000)?; // Set fuel limit for execution
+        store.set_fuel(fuel)?;
+        store.set_epoch_deadline(self.config.epoch_deadline_ticks());
 
         // CRITICAL: Set the resource limiter on the store to enable memory growth control
         store.limiter(|state| state);
@@ -507,7 +558,20 @@ impl CmExtractor {
         let extracted_doc = match result {
             Ok(Ok(wit_content)) => {
                 // Success: convert WIT type to host type
-                let doc: ExtractedDoc = wit_content.into();
+                let mut doc: ExtractedDoc = wit_content.into();
+
+                // The component renders GFM during extraction; re-render on request
+                if self.config.markdown_flavor == MarkdownFlavor::CommonMark {
+                    if let Ok(Ok(markdown)) = instance.call_to_markdown(
+                        &mut store,
+                        html,
+                        url,
+                        &wit_mode,
+                        wit_bindings::MarkdownFlavor::Commonmark,
+                    ) {
+                        doc.markdown = Some(markdown);
+                    }
+                }
 
                 // Update success statistics
                 if let Ok(mut stats) = self.stats.lock() {
@@ -542,13 +606,31 @@ impl CmExtractor {
                     stats.failed_extractions += 1;
                 }
 
-                Err(anyhow::anyhow!("WASM runtime error: {}", e))
+                match e.downcast_ref::<Trap>() {
+                    Some(Trap::OutOfFuel) => Err(RiptideError::ResourceExhausted {
+                        resource: "wasm_fuel".to_string(),
+                        limit: fuel,
+                    }
+                    .into()),
+                    Some(Trap::Interrupt) => Err(RiptideError::ResourceExhausted {
+                        resource: "cpu_time_ms".to_string(),
+                        limit: self.config.extraction_timeout.as_millis() as u64,
+                    }
+                    .into()),
+                    _ => Err(anyhow::anyhow!("WASM runtime error: {}", e)),
+                }
             }
         };
 
+        FuelMeter::charge(fuel.saturating_sub(store.get_fuel().unwrap_or(0)));
         extracted_doc
     }
 
+    /// The compiled component
+    pub(crate) fn component(&self) -> &Component {
+        &self.component
+    }
+
     /// Get component information
     pub fn component_info(&self) -> HostComponentInfo {
         HostComponentInfo {
@@ -583,6 +665,21 @@ impl CmExtractor {
     }
 }
 
+/// Advance the engine epoch every [`EPOCH_TICK_MS`] until the engine is
+/// dropped, so extractions past their epoch deadline are interrupted
+fn spawn_epoch_ticker(engine: EngineWeak) -> Result<()> {
+    std::thread::Builder::new()
+        .name("riptide-wasm-epoch".to_string())
+        .spawn(move || {
+            while let Some(strong) = engine.upgrade() {
+                strong.increment_epoch();
+                drop(strong);
+                std::thread::sleep(Duration::from_millis(EPOCH_TICK_MS));
+            }
+        })?;
+    Ok(())
+}
+
 /// Simple WASM extractor wrapper
 pub struct WasmExtractor {
     cm_extractor: CmExtractor,
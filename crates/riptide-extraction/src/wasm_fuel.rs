@@ -0,0 +1,146 @@
+//! Shared WASM fuel accounting
+//!
+//! Every WASM extraction runs with at most [`DEFAULT_EXTRACTION_FUEL`].
+//! Extractions inside [`FuelMeter::scope`] also draw from a shared
+//! [`FuelMeter`], so a caller can measure and cap what a group of
+//! extractions (such as one crawl) consumes. The meter follows the task, so
+//! extractions count only when they run on the task that entered the scope.
+//!
+//! Concurrent extractions are each granted what is left when they start, so
+//! a group can overshoot its limit by what those extractions use together.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_extraction::wasm_fuel::FuelMeter;
+//! use std::sync::Arc;
+//!
+//! # async fn example() {
+//! let meter = Arc::new(FuelMeter::new(Some(5_000_000)));
+//! meter.clone().scope(async {
+//!     // extractions here draw from `meter`
+//! }).await;
+//! println!("consumed {} fuel", meter.consumed());
+//! # }
+//! ```
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Fuel one WASM extraction may consume
+pub const DEFAULT_EXTRACTION_FUEL: u64 = 1_000_000;
+
+tokio::task_local! {
+    static CURRENT: Arc<FuelMeter>;
+}
+
+/// Fuel consumed by, and optionally allowed to, a group of extractions
+#[derive(Debug, Default)]
+pub struct FuelMeter {
+    limit: Option<u64>,
+    consumed: AtomicU64,
+}
+
+impl FuelMeter {
+    /// Meter allowing `limit` fuel in total, or any amount for `None`
+    pub fn new(limit: Option<u64>) -> Self {
+        Self {
+            limit,
+            consumed: AtomicU64::new(0),
+        }
+    }
+
+    pub fn limit(&self) -> Option<u64> {
+        self.limit
+    }
+
+    /// Fuel consumed so far
+    pub fn consumed(&self) -> u64 {
+        self.consumed.load(Ordering::Relaxed)
+    }
+
+    /// Whether the limit has been used up
+    pub fn is_exhausted(&self) -> bool {
+        self.limit.is_some_and(|limit| self.consumed() >= limit)
+    }
+
+    /// Run `future` with the extractions it performs drawing from this meter
+    pub async fn scope<F: Future>(self: Arc<Self>, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+
+    /// Run `f` with the extractions it performs drawing from this meter, for
+    /// carrying a scope into a blocking task
+    pub fn sync_scope<R>(self: Arc<Self>, f: impl FnOnce() -> R) -> R {
+        CURRENT.sync_scope(self, f)
+    }
+
+    /// The current scope's meter, if any
+    pub fn current() -> Option<Arc<FuelMeter>> {
+        CURRENT.try_with(Arc::clone).ok()
+    }
+
+    /// Fuel for an extraction starting now: `per_extraction`, capped by
+    /// what is left on the current scope's meter
+    pub fn grant(per_extraction: u64) -> u64 {
+        CURRENT
+            .try_with(|meter| match meter.limit {
+                Some(limit) => per_extraction.min(limit.saturating_sub(meter.consumed())),
+                None => per_extraction,
+            })
+            .unwrap_or(per_extraction)
+    }
+
+    /// Charge `fuel` an extraction consumed to the current scope's meter
+    pub fn charge(fuel: u64) {
+        let _ = CURRENT.try_with(|meter| meter.consumed.fetch_add(fuel, Ordering::Relaxed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scoped_grants_and_charges() {
+        // Outside a scope extractions get the default and charge nothing
+        assert_eq!(FuelMeter::grant(100), 100);
+        FuelMeter::charge(100);
+
+        let meter = Arc::new(FuelMeter::new(Some(250)));
+        meter
+            .clone()
+            .scope(async {
+                assert_eq!(FuelMeter::grant(100), 100);
+                FuelMeter::charge(100);
+                FuelMeter::charge(100);
+                assert_eq!(FuelMeter::grant(100), 50);
+                FuelMeter::charge(50);
+                assert_eq!(FuelMeter::grant(100), 0);
+            })
+            .await;
+        assert_eq!(meter.consumed(), 250);
+        assert!(meter.is_exhausted());
+
+        let unlimited = Arc::new(FuelMeter::new(None));
+        unlimited
+            .clone()
+            .scope(async { FuelMeter::charge(7) })
+            .await;
+        assert_eq!(unlimited.consumed(), 7);
+        assert!(!unlimited.is_exhausted());
+
+        // A scope carried into a blocking task keeps drawing from its meter
+        let carried = unlimited
+            .clone()
+            .scope(async {
+                let meter = FuelMeter::current().unwrap();
+                tokio::task::spawn_blocking(move || meter.sync_scope(|| FuelMeter::charge(3))).await
+            })
+            .await;
+        assert!(carried.is_ok());
+        assert!(FuelMeter::current().is_none());
+        assert_eq!(unlimited.consumed(), 10);
+    }
+}
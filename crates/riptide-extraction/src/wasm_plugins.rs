@@ -0,0 +1,379 @@
+//! Extractor plugins
+//!
+//! Loads user-supplied WASM components implementing the `extractor` WIT
+//! world next to the built-in extractor and routes extractions to them by
+//! name. Before a component is registered it is checked with the
+//! [`WitValidator`] and by running a probe extraction, so a component that
+//! does not implement the world, or traps on a simple page, is rejected at
+//! load time rather than on the first request.
+//!
+//! Plugins run on the blocking thread pool, drawing fuel from the caller's
+//! [`FuelMeter`] scope, and are interrupted once they exceed the registry's
+//! [`ExtractorConfig::extraction_timeout`]. Either limit fails the
+//! extraction with [`RiptideError::ResourceExhausted`].
+//!
+//! ```no_run
+//! use riptide_extraction::wasm_plugins::ExtractorPluginRegistry;
+//!
+//! # async fn example() -> anyhow::Result<()> {
+//! let plugins = ExtractorPluginRegistry::default();
+//! plugins.load("recipes", "/etc/riptide/plugins/recipes.wasm").await?;
+//!
+//! let doc = plugins.extract("recipes", "<html>...</html>", "https://example.com/pie", "article").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::validation::WitValidator;
+use crate::wasm_extraction::{CmExtractor, ExtractorConfig};
+use crate::wasm_fuel::FuelMeter;
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use riptide_types::ports::ExtractionResult;
+use riptide_types::{ExtractedContent, ExtractedDoc, ExtractionProvenance, RiptideError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use tracing::{info, warn};
+
+/// Page a plugin must extract without trapping to be registered
+const PROBE_HTML: &str = "<html><head><title>Plugin probe</title></head>\
+    <body><article><p>Extractor plugin probe.</p></article></body></html>";
+const PROBE_URL: &str = "https://example.com/plugin-probe";
+
+/// Longest plugin name accepted
+const MAX_NAME_LEN: usize = 64;
+
+/// Registered plugin, as reported by [`ExtractorPluginRegistry::list`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractorPluginInfo {
+    pub name: String,
+    /// Artifact path or upload label
+    pub source: String,
+    /// SHA-256 of the component binary
+    pub component_hash: String,
+    /// Non-fatal WIT validation findings
+    pub validation_warnings: Vec<String>,
+}
+
+/// A loaded extractor plugin
+pub struct ExtractorPlugin {
+    info: ExtractorPluginInfo,
+    extractor: Arc<CmExtractor>,
+}
+
+impl ExtractorPlugin {
+    pub fn name(&self) -> &str {
+        &self.info.name
+    }
+
+    pub fn info(&self) -> &ExtractorPluginInfo {
+        &self.info
+    }
+
+    /// Extract `html` with the plugin in `mode` ("article", "full" or
+    /// "metadata")
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the plugin fails or traps, and
+    /// [`RiptideError::ResourceExhausted`] when it runs out of fuel or CPU
+    /// time.
+    pub async fn extract(&self, html: &str, url: &str, mode: &str) -> Result<ExtractedDoc> {
+        let extractor = Arc::clone(&self.extractor);
+        let (html, url, mode) = (html.to_string(), url.to_string(), mode.to_string());
+        let meter = FuelMeter::current();
+        let mut doc = tokio::task::spawn_blocking(move || {
+            let run = || extractor.extract(&html, &url, &mode);
+            match meter {
+                Some(meter) => meter.sync_scope(run),
+                None => run(),
+            }
+        })
+        .await??;
+        if let Some(metadata) = doc.parser_metadata.as_mut() {
+            metadata.parser_used = format!("plugin:{}", self.info.name);
+        }
+        Ok(doc)
+    }
+}
+
+#[async_trait]
+impl riptide_types::ports::ContentExtractor for ExtractorPlugin {
+    async fn extract(
+        &self,
+        html: &str,
+        url: &str,
+    ) -> riptide_types::error::Result<ExtractionResult> {
+        let doc = ExtractorPlugin::extract(self, html, url, "article")
+            .await
+            .map_err(|e| match e.downcast::<RiptideError>() {
+                Ok(exhausted @ RiptideError::ResourceExhausted { .. }) => exhausted,
+                Ok(e) => {
+                    RiptideError::Extraction(format!("Plugin '{}' failed: {}", self.info.name, e))
+                }
+                Err(e) => {
+                    RiptideError::Extraction(format!("Plugin '{}' failed: {}", self.info.name, e))
+                }
+            })?;
+        let lang = doc.language.clone();
+        let content: ExtractedContent = doc.into();
+
+        let mut metadata = HashMap::new();
+        metadata.insert("title".to_string(), content.title);
+        if let Some(summary) = content.summary {
+            metadata.insert("summary".to_string(), summary);
+        }
+        if let Some(lang) = lang {
+            metadata.insert("lang".to_string(), lang);
+        }
+        metadata.insert("strategy".to_string(), format!("plugin:{}", self.info.name));
+        metadata.insert("url".to_string(), content.url);
+
+        Ok(ExtractionResult {
+            text: content.content,
+            metadata,
+            quality_score: content.extraction_confidence,
+        })
+    }
+
+    fn extractor_type(&self) -> &str {
+        "wasm_plugin"
+    }
+
+    async fn is_available(&self) -> bool {
+        true
+    }
+}
+
+/// Named extractor plugins
+///
+/// Loading a plugin under a name already in use replaces it; extractions
+/// running on the old plugin finish on it.
+pub struct ExtractorPluginRegistry {
+    config: ExtractorConfig,
+    plugins: RwLock<BTreeMap<String, Arc<ExtractorPlugin>>>,
+}
+
+impl Default for ExtractorPluginRegistry {
+    fn default() -> Self {
+        Self::new(ExtractorConfig::default())
+    }
+}
+
+impl ExtractorPluginRegistry {
+    /// Registry running plugins with the memory, engine and timeout settings
+    /// of `config`
+    pub fn new(config: ExtractorConfig) -> Self {
+        Self {
+            config,
+            plugins: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// Load the component at `path` as plugin `name`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving any plugin already registered under `name`
+    /// in place, when the name is invalid or the component cannot be read,
+    /// compiled, validated or fails the probe extraction.
+    pub async fn load(&self, name: &str, path: impl AsRef<Path>) -> Result<ExtractorPluginInfo> {
+        let path = path.as_ref();
+        let bytes = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("reading plugin {}", path.display()))?;
+        self.load_bytes(name, bytes, &path.display().to_string())
+            .await
+    }
+
+    /// Load uploaded component `bytes` as plugin `name`, reported under
+    /// `source`
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::load`].
+    pub async fn load_bytes(
+        &self,
+        name: &str,
+        bytes: Vec<u8>,
+        source: &str,
+    ) -> Result<ExtractorPluginInfo> {
+        validate_name(name)?;
+        let config = self.config.clone();
+        let component_hash = ExtractionProvenance::hash_component(&bytes);
+        let (extractor, validation_warnings) =
+            tokio::task::spawn_blocking(move || check_component(&bytes, config))
+                .await?
+                .with_context(|| format!("loading plugin '{}' from {}", name, source))?;
+
+        let plugin = Arc::new(ExtractorPlugin {
+            info: ExtractorPluginInfo {
+                name: name.to_string(),
+                source: source.to_string(),
+                component_hash,
+                validation_warnings,
+            },
+            extractor: Arc::new(extractor),
+        });
+        let info = plugin.info.clone();
+        let replaced = self
+            .plugins
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_string(), plugin)
+            .is_some();
+
+        info!(
+            plugin = %name,
+            source = %source,
+            component_hash = %info.component_hash,
+            replaced = replaced,
+            "Extractor plugin loaded"
+        );
+        Ok(info)
+    }
+
+    /// Load every `*.wasm` file in `dir`, each named after its file stem
+    ///
+    /// Files that fail to load are logged and skipped.
+    pub async fn load_dir(&self, dir: impl AsRef<Path>) -> Result<Vec<ExtractorPluginInfo>> {
+        let dir = dir.as_ref();
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .with_context(|| format!("reading plugin directory {}", dir.display()))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "wasm"))
+            .collect();
+        paths.sort();
+
+        let mut loaded = Vec::new();
+        for path in paths {
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            match self.load(name, &path).await {
+                Ok(info) => loaded.push(info),
+                Err(e) => {
+                    warn!(path = %path.display(), error = %format!("{:#}", e), "Skipping extractor plugin")
+                }
+            }
+        }
+        Ok(loaded)
+    }
+
+    /// Remove plugin `name`, returning whether it was registered
+    pub fn unload(&self, name: &str) -> bool {
+        let removed = self
+            .plugins
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(name)
+            .is_some();
+        if removed {
+            info!(plugin = %name, "Extractor plugin unloaded");
+        }
+        removed
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<ExtractorPlugin>> {
+        self.plugins
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(name)
+            .cloned()
+    }
+
+    /// Registered plugins, sorted by name
+    pub fn list(&self) -> Vec<ExtractorPluginInfo> {
+        self.plugins
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .map(|plugin| plugin.info.clone())
+            .collect()
+    }
+
+    /// Extract `html` with plugin `name`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when no plugin is registered under `name` or the
+    /// plugin's extraction fails.
+    pub async fn extract(
+        &self,
+        name: &str,
+        html: &str,
+        url: &str,
+        mode: &str,
+    ) -> Result<ExtractedDoc> {
+        self.get(name)
+            .ok_or_else(|| anyhow!("Unknown extractor plugin '{}'", name))?
+            .extract(html, url, mode)
+            .await
+    }
+}
+
+/// Compile `bytes`, validate the component and run the probe extraction,
+/// returning the extractor and the validation warnings
+fn check_component(bytes: &[u8], config: ExtractorConfig) -> Result<(CmExtractor, Vec<String>)> {
+    let extractor = CmExtractor::from_bytes(bytes, config)?;
+    let report = WitValidator::strict().validate_or_error(extractor.component())?;
+    extractor
+        .extract(PROBE_HTML, PROBE_URL, "article")
+        .map_err(|e| anyhow!("Probe extraction failed: {}", e))?;
+    Ok((extractor, report.warnings))
+}
+
+/// Plugin names are lowercase letters, digits, `-` and `_`, starting with a
+/// letter or digit, so they can be used in request options and file names
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        && !name.starts_with(['-', '_']);
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Invalid plugin name '{}': use up to {} lowercase letters, digits, '-' and '_'",
+            name,
+            MAX_NAME_LEN
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_names() {
+        for name in ["recipes", "news-v2", "shop_2"] {
+            assert!(validate_name(name).is_ok(), "{}", name);
+        }
+        for name in ["", "Recipes", "-x", "../evil", "a b", &"x".repeat(65)] {
+            assert!(validate_name(name).is_err(), "{}", name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unknown_and_invalid_plugins() {
+        let plugins = ExtractorPluginRegistry::default();
+        assert!(plugins
+            .extract("missing", "<html></html>", "https://example.com", "article")
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown extractor plugin 'missing'"));
+
+        assert!(plugins
+            .load_bytes("broken", b"not a component".to_vec(), "upload")
+            .await
+            .is_err());
+        assert!(plugins.list().is_empty());
+        assert!(!plugins.unload("broken"));
+    }
+}
@@ -0,0 +1,691 @@
+//! XPath 1.0 evaluation over a scraper document tree
+
+use std::collections::HashMap;
+use std::iter;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use scraper::{ElementRef, Node};
+
+use super::parser::{ArithmeticOp, Axis, CompareOp, Expr, Function, LocationPath, NodeTest, Step};
+use super::{XPathError, XPathNode, XPathValue};
+
+/// ego_tree's `NodeRef<Node>`, named through scraper which does not re-export it
+pub(crate) type TreeNode<'a> = <ElementRef<'a> as Deref>::Target;
+
+/// Attribute namespace of `xmlns` declarations, which XPath does not expose as attributes
+const XMLNS_NAMESPACE: &str = "http://www.w3.org/2000/xmlns/";
+
+/// Namespace the `xml` prefix is always bound to
+pub(crate) const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// Node in the tree, or attribute of an element by its index in the attribute map
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Item<'a> {
+    Node(TreeNode<'a>),
+    Attribute {
+        element: ElementRef<'a>,
+        index: usize,
+    },
+}
+
+struct Context<'a> {
+    node: XPathNode<'a>,
+    position: usize,
+    size: usize,
+}
+
+pub(crate) struct Evaluator<'a, 'x> {
+    namespaces: &'x HashMap<String, String>,
+    /// Document-order index of every node, keyed by the address of its value
+    order: HashMap<*const Node, usize>,
+    _document: PhantomData<&'a Node>,
+}
+
+impl<'a, 'x> Evaluator<'a, 'x> {
+    pub fn new(root: TreeNode<'a>, namespaces: &'x HashMap<String, String>) -> Self {
+        let order = root
+            .descendants()
+            .enumerate()
+            .map(|(index, node)| (node.value() as *const Node, index))
+            .collect();
+        Self {
+            namespaces,
+            order,
+            _document: PhantomData,
+        }
+    }
+
+    pub fn evaluate(&self, expr: &Expr, node: TreeNode<'a>) -> Result<XPathValue<'a>, XPathError> {
+        let context = Context {
+            node: XPathNode(Item::Node(node)),
+            position: 1,
+            size: 1,
+        };
+        self.eval(expr, &context)
+    }
+
+    fn eval(&self, expr: &Expr, context: &Context<'a>) -> Result<XPathValue<'a>, XPathError> {
+        Ok(match expr {
+            Expr::Or(left, right) => XPathValue::Boolean(
+                self.eval(left, context)?.boolean() || self.eval(right, context)?.boolean(),
+            ),
+            Expr::And(left, right) => XPathValue::Boolean(
+                self.eval(left, context)?.boolean() && self.eval(right, context)?.boolean(),
+            ),
+            Expr::Compare(op, left, right) => {
+                let left = self.eval(left, context)?;
+                let right = self.eval(right, context)?;
+                XPathValue::Boolean(compare(*op, &left, &right))
+            }
+            Expr::Arithmetic(op, left, right) => {
+                let left = self.eval(left, context)?.number();
+                let right = self.eval(right, context)?.number();
+                XPathValue::Number(match op {
+                    ArithmeticOp::Add => left + right,
+                    ArithmeticOp::Sub => left - right,
+                    ArithmeticOp::Mul => left * right,
+                    ArithmeticOp::Div => left / right,
+                    ArithmeticOp::Mod => left % right,
+                })
+            }
+            Expr::Negate(inner) => XPathValue::Number(-self.eval(inner, context)?.number()),
+            Expr::Union(left, right) => {
+                let mut nodes = self.node_set(left, context, "union")?;
+                nodes.extend(self.node_set(right, context, "union")?);
+                self.sort(&mut nodes);
+                XPathValue::Nodes(nodes)
+            }
+            Expr::Path(start, path) => {
+                let nodes = match start {
+                    Some(filter) => self.node_set(filter, context, "path")?,
+                    None if path.absolute => {
+                        let root = match context.node.0 {
+                            Item::Node(node) => node.tree().root(),
+                            Item::Attribute { element, .. } => element.tree().root(),
+                        };
+                        vec![XPathNode(Item::Node(root))]
+                    }
+                    None => vec![context.node],
+                };
+                XPathValue::Nodes(self.location_path(nodes, path)?)
+            }
+            Expr::Filter(primary, predicates) => {
+                let mut nodes = self.node_set(primary, context, "predicate")?;
+                for predicate in predicates {
+                    nodes = self.filter(nodes, predicate)?;
+                }
+                XPathValue::Nodes(nodes)
+            }
+            Expr::Literal(value) => XPathValue::String(value.clone()),
+            Expr::Number(value) => XPathValue::Number(*value),
+            Expr::Function(function, args) => self.call(*function, args, context)?,
+        })
+    }
+
+    fn node_set(
+        &self,
+        expr: &Expr,
+        context: &Context<'a>,
+        operation: &'static str,
+    ) -> Result<Vec<XPathNode<'a>>, XPathError> {
+        match self.eval(expr, context)? {
+            XPathValue::Nodes(nodes) => Ok(nodes),
+            _ => Err(XPathError::NotANodeSet(operation)),
+        }
+    }
+
+    fn location_path(
+        &self,
+        mut nodes: Vec<XPathNode<'a>>,
+        path: &LocationPath,
+    ) -> Result<Vec<XPathNode<'a>>, XPathError> {
+        for step in &path.steps {
+            let mut next = Vec::new();
+            for node in nodes {
+                next.extend(self.step(node, step)?);
+            }
+            self.sort(&mut next);
+            nodes = next;
+        }
+        Ok(nodes)
+    }
+
+    /// Nodes selected by one step from one context node, in axis order
+    fn step(&self, node: XPathNode<'a>, step: &Step) -> Result<Vec<XPathNode<'a>>, XPathError> {
+        let mut nodes: Vec<XPathNode<'a>> = axis_nodes(node.0, step.axis)
+            .into_iter()
+            .filter(|item| self.matches(*item, &step.test))
+            .map(XPathNode)
+            .collect();
+        for predicate in &step.predicates {
+            nodes = self.filter(nodes, predicate)?;
+        }
+        Ok(nodes)
+    }
+
+    /// Keep the nodes for which a predicate holds; numbers compare against the position
+    fn filter(
+        &self,
+        nodes: Vec<XPathNode<'a>>,
+        predicate: &Expr,
+    ) -> Result<Vec<XPathNode<'a>>, XPathError> {
+        let size = nodes.len();
+        let mut kept = Vec::new();
+        for (index, node) in nodes.into_iter().enumerate() {
+            let context = Context {
+                node,
+                position: index + 1,
+                size,
+            };
+            let keep = match self.eval(predicate, &context)? {
+                #[allow(clippy::cast_precision_loss)]
+                XPathValue::Number(n) => n == context.position as f64,
+                value => value.boolean(),
+            };
+            if keep {
+                kept.push(node);
+            }
+        }
+        Ok(kept)
+    }
+
+    fn matches(&self, item: Item<'a>, test: &NodeTest) -> bool {
+        match item {
+            Item::Attribute { element, index } => {
+                let Some((name, _)) = element.value().attrs.iter().nth(index) else {
+                    return false;
+                };
+                match test {
+                    NodeTest::Any | NodeTest::Node => true,
+                    NodeTest::Name { prefix, local } => {
+                        self.name_matches(&name.ns, &name.local, prefix.as_deref(), local)
+                    }
+                    NodeTest::Prefix(prefix) => self.prefix_matches(&name.ns, &name.local, prefix),
+                    _ => false,
+                }
+            }
+            Item::Node(node) => match (test, node.value()) {
+                (NodeTest::Node, _) => true,
+                (NodeTest::Text, Node::Text(_)) => true,
+                (NodeTest::Comment, Node::Comment(_)) => true,
+                (NodeTest::ProcessingInstruction(target), Node::ProcessingInstruction(pi)) => {
+                    target.as_ref().is_none_or(|target| *pi.target == **target)
+                }
+                (NodeTest::Any, Node::Element(_)) => true,
+                (NodeTest::Name { prefix, local }, Node::Element(element)) => self.name_matches(
+                    &element.name.ns,
+                    &element.name.local,
+                    prefix.as_deref(),
+                    local,
+                ),
+                (NodeTest::Prefix(prefix), Node::Element(element)) => {
+                    self.prefix_matches(&element.name.ns, &element.name.local, prefix)
+                }
+                _ => false,
+            },
+        }
+    }
+
+    /// Unprefixed names match in any namespace, as HTML elements all live in the
+    /// XHTML namespace. A prefix that is not bound matches the literal `prefix:name`
+    /// the HTML parser keeps for tags like `<fb:like>`.
+    fn name_matches(&self, ns: &str, actual: &str, prefix: Option<&str>, local: &str) -> bool {
+        match prefix {
+            None => actual == local,
+            Some(prefix) => {
+                let bound = self
+                    .namespace(prefix)
+                    .is_some_and(|uri| ns == uri && actual == local);
+                bound
+                    || actual
+                        .strip_prefix(prefix)
+                        .and_then(|rest| rest.strip_prefix(':'))
+                        == Some(local)
+            }
+        }
+    }
+
+    fn prefix_matches(&self, ns: &str, actual: &str, prefix: &str) -> bool {
+        self.namespace(prefix).is_some_and(|uri| ns == uri)
+            || actual
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with(':'))
+    }
+
+    fn namespace(&self, prefix: &str) -> Option<&str> {
+        match self.namespaces.get(prefix) {
+            Some(uri) => Some(uri),
+            None if prefix == "xml" => Some(XML_NAMESPACE),
+            None => None,
+        }
+    }
+
+    /// Document order position; attributes sort after their element and before its children
+    fn key(&self, node: &XPathNode<'a>) -> (usize, usize) {
+        let (tree_node, offset) = match node.0 {
+            Item::Node(node) => (node, 0),
+            Item::Attribute { element, index } => (*element, index + 1),
+        };
+        let index = self
+            .order
+            .get(&(tree_node.value() as *const Node))
+            .copied()
+            .unwrap_or(usize::MAX);
+        (index, offset)
+    }
+
+    fn sort(&self, nodes: &mut Vec<XPathNode<'a>>) {
+        nodes.sort_by_key(|node| self.key(node));
+        nodes.dedup_by_key(|node| self.key(node));
+    }
+
+    fn call(
+        &self,
+        function: Function,
+        args: &[Expr],
+        context: &Context<'a>,
+    ) -> Result<XPathValue<'a>, XPathError> {
+        let string_arg = |index: usize| -> Result<String, XPathError> {
+            Ok(match args.get(index) {
+                Some(arg) => self.eval(arg, context)?.string(),
+                None => context.node.string_value(),
+            })
+        };
+        let number_arg = |index: usize| -> Result<f64, XPathError> {
+            Ok(match args.get(index) {
+                Some(arg) => self.eval(arg, context)?.number(),
+                None => string_to_number(&context.node.string_value()),
+            })
+        };
+        // First node of an optional node-set argument, defaulting to the context node
+        let node_arg = |name: &'static str| -> Result<Option<XPathNode<'a>>, XPathError> {
+            Ok(match args.first() {
+                Some(arg) => self.node_set(arg, context, name)?.into_iter().next(),
+                None => Some(context.node),
+            })
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        Ok(match function {
+            Function::Last => XPathValue::Number(context.size as f64),
+            Function::Position => XPathValue::Number(context.position as f64),
+            Function::Count => {
+                XPathValue::Number(self.node_set(&args[0], context, "count")?.len() as f64)
+            }
+            Function::Id => {
+                let ids = match self.eval(&args[0], context)? {
+                    XPathValue::Nodes(nodes) => nodes
+                        .iter()
+                        .map(XPathNode::string_value)
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    value => value.string(),
+                };
+                let ids: Vec<&str> = ids.split_whitespace().collect();
+                let root = match context.node.0 {
+                    Item::Node(node) => node.tree().root(),
+                    Item::Attribute { element, .. } => element.tree().root(),
+                };
+                XPathValue::Nodes(
+                    root.descendants()
+                        .filter(|node| {
+                            node.value()
+                                .as_element()
+                                .and_then(|element| element.id())
+                                .is_some_and(|id| ids.contains(&id))
+                        })
+                        .map(|node| XPathNode(Item::Node(node)))
+                        .collect(),
+                )
+            }
+            Function::LocalName => XPathValue::String(
+                node_arg("local-name")?
+                    .map(|node| node.local_name().to_string())
+                    .unwrap_or_default(),
+            ),
+            Function::NamespaceUri => XPathValue::String(
+                node_arg("namespace-uri")?
+                    .map(|node| node.namespace_uri().to_string())
+                    .unwrap_or_default(),
+            ),
+            Function::Name => XPathValue::String(
+                node_arg("name")?
+                    .map(|node| node.name())
+                    .unwrap_or_default(),
+            ),
+            Function::String => XPathValue::String(string_arg(0)?),
+            Function::Concat => {
+                let mut out = String::new();
+                for index in 0..args.len() {
+                    out.push_str(&string_arg(index)?);
+                }
+                XPathValue::String(out)
+            }
+            Function::StartsWith => {
+                XPathValue::Boolean(string_arg(0)?.starts_with(&string_arg(1)?))
+            }
+            Function::Contains => XPathValue::Boolean(string_arg(0)?.contains(&string_arg(1)?)),
+            Function::SubstringBefore => {
+                let haystack = string_arg(0)?;
+                let needle = string_arg(1)?;
+                XPathValue::String(
+                    haystack
+                        .find(&needle)
+                        .map(|at| haystack[..at].to_string())
+                        .unwrap_or_default(),
+                )
+            }
+            Function::SubstringAfter => {
+                let haystack = string_arg(0)?;
+                let needle = string_arg(1)?;
+                XPathValue::String(
+                    haystack
+                        .find(&needle)
+                        .map(|at| haystack[at + needle.len()..].to_string())
+                        .unwrap_or_default(),
+                )
+            }
+            Function::Substring => {
+                let value = string_arg(0)?;
+                let start = round(number_arg(1)?);
+                let end = match args.get(2) {
+                    Some(_) => start + round(number_arg(2)?),
+                    None => f64::INFINITY,
+                };
+                XPathValue::String(
+                    value
+                        .chars()
+                        .enumerate()
+                        .filter(|&(index, _)| {
+                            let position = (index + 1) as f64;
+                            position >= start && position < end
+                        })
+                        .map(|(_, c)| c)
+                        .collect(),
+                )
+            }
+            Function::StringLength => XPathValue::Number(string_arg(0)?.chars().count() as f64),
+            Function::NormalizeSpace => XPathValue::String(
+                string_arg(0)?
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+            Function::Translate => {
+                let value = string_arg(0)?;
+                let from: Vec<char> = string_arg(1)?.chars().collect();
+                let to: Vec<char> = string_arg(2)?.chars().collect();
+                XPathValue::String(
+                    value
+                        .chars()
+                        .filter_map(|c| match from.iter().position(|&f| f == c) {
+                            Some(index) => to.get(index).copied(),
+                            None => Some(c),
+                        })
+                        .collect(),
+                )
+            }
+            Function::Boolean => XPathValue::Boolean(self.eval(&args[0], context)?.boolean()),
+            Function::Not => XPathValue::Boolean(!self.eval(&args[0], context)?.boolean()),
+            Function::True => XPathValue::Boolean(true),
+            Function::False => XPathValue::Boolean(false),
+            Function::Lang => {
+                let wanted = string_arg(0)?.to_ascii_lowercase();
+                let node = match context.node.0 {
+                    Item::Node(node) => node,
+                    Item::Attribute { element, .. } => *element,
+                };
+                let lang = iter::once(node).chain(node.ancestors()).find_map(|node| {
+                    let element = node.value().as_element()?;
+                    element
+                        .attrs
+                        .iter()
+                        .find(|(name, _)| {
+                            &*name.local == "xml:lang"
+                                || (&*name.local == "lang"
+                                    && (name.ns.is_empty() || &*name.ns == XML_NAMESPACE))
+                        })
+                        .map(|(_, value)| value.to_ascii_lowercase())
+                });
+                XPathValue::Boolean(lang.is_some_and(|lang| {
+                    lang == wanted
+                        || lang
+                            .strip_prefix(&wanted)
+                            .is_some_and(|rest| rest.starts_with('-'))
+                }))
+            }
+            Function::Number => XPathValue::Number(number_arg(0)?),
+            Function::Sum => XPathValue::Number(
+                self.node_set(&args[0], context, "sum")?
+                    .iter()
+                    .map(|node| string_to_number(&node.string_value()))
+                    .sum(),
+            ),
+            Function::Floor => XPathValue::Number(number_arg(0)?.floor()),
+            Function::Ceiling => XPathValue::Number(number_arg(0)?.ceil()),
+            Function::Round => XPathValue::Number(round(number_arg(0)?)),
+            Function::HasClass => {
+                let classes: Vec<&str> = match context.node.as_element() {
+                    Some(element) => element.value().classes().collect(),
+                    None => Vec::new(),
+                };
+                let mut all = !classes.is_empty();
+                for index in 0..args.len() {
+                    let wanted = string_arg(index)?;
+                    all &= classes.contains(&wanted.as_str());
+                }
+                XPathValue::Boolean(all)
+            }
+        })
+    }
+}
+
+fn axis_nodes(item: Item<'_>, axis: Axis) -> Vec<Item<'_>> {
+    let node = match item {
+        Item::Node(node) => node,
+        Item::Attribute { element, .. } => {
+            let owner: TreeNode<'_> = *element;
+            return match axis {
+                Axis::SelfNode | Axis::DescendantOrSelf => vec![item],
+                Axis::AncestorOrSelf => iter::once(item)
+                    .chain(iter::once(owner).chain(owner.ancestors()).map(Item::Node))
+                    .collect(),
+                Axis::Parent => vec![Item::Node(owner)],
+                Axis::Ancestor => iter::once(owner)
+                    .chain(owner.ancestors())
+                    .map(Item::Node)
+                    .collect(),
+                Axis::Following => owner
+                    .descendants()
+                    .skip(1)
+                    .chain(following(owner))
+                    .map(Item::Node)
+                    .collect(),
+                Axis::Preceding => preceding(owner).map(Item::Node).collect(),
+                _ => Vec::new(),
+            };
+        }
+    };
+
+    let nodes: Vec<TreeNode<'_>> = match axis {
+        Axis::Attribute => {
+            let Some(element) = ElementRef::wrap(node) else {
+                return Vec::new();
+            };
+            return element
+                .value()
+                .attrs
+                .iter()
+                .enumerate()
+                .filter(|(_, (name, _))| &*name.ns != XMLNS_NAMESPACE && &*name.local != "xmlns")
+                .map(|(index, _)| Item::Attribute { element, index })
+                .collect();
+        }
+        Axis::Child => node.children().collect(),
+        Axis::Descendant => node.descendants().skip(1).collect(),
+        Axis::DescendantOrSelf => node.descendants().collect(),
+        Axis::Parent => node.parent().into_iter().collect(),
+        Axis::Ancestor => node.ancestors().collect(),
+        Axis::AncestorOrSelf => iter::once(node).chain(node.ancestors()).collect(),
+        Axis::FollowingSibling => node.next_siblings().collect(),
+        Axis::PrecedingSibling => node.prev_siblings().collect(),
+        Axis::Following => following(node).collect(),
+        Axis::Preceding => preceding(node).collect(),
+        Axis::SelfNode => vec![node],
+    };
+    nodes.into_iter().map(Item::Node).collect()
+}
+
+/// Nodes after `node` in document order, excluding its descendants
+fn following(node: TreeNode<'_>) -> impl Iterator<Item = TreeNode<'_>> {
+    iter::once(node)
+        .chain(node.ancestors())
+        .flat_map(|node| node.next_siblings())
+        .flat_map(|sibling| sibling.descendants())
+}
+
+/// Nodes before `node` in reverse document order, excluding its ancestors
+fn preceding(node: TreeNode<'_>) -> impl Iterator<Item = TreeNode<'_>> {
+    iter::once(node)
+        .chain(node.ancestors())
+        .flat_map(|node| node.prev_siblings())
+        .flat_map(|sibling| sibling.descendants().collect::<Vec<_>>().into_iter().rev())
+}
+
+fn compare(op: CompareOp, left: &XPathValue<'_>, right: &XPathValue<'_>) -> bool {
+    match (left, right) {
+        (XPathValue::Nodes(left), XPathValue::Nodes(right)) => {
+            let right: Vec<String> = right.iter().map(XPathNode::string_value).collect();
+            left.iter().any(|l| {
+                let l = l.string_value();
+                right
+                    .iter()
+                    .any(|r| compare_atoms(op, &Atom::String(&l), &Atom::String(r)))
+            })
+        }
+        (XPathValue::Nodes(nodes), other) => compare_node_set(op, nodes, other, false),
+        (other, XPathValue::Nodes(nodes)) => compare_node_set(op, nodes, other, true),
+        (left, right) => compare_atoms(op, &Atom::from(left), &Atom::from(right)),
+    }
+}
+
+/// Compare a node-set with a scalar; `swapped` when the node-set is the right operand
+fn compare_node_set(
+    op: CompareOp,
+    nodes: &[XPathNode<'_>],
+    other: &XPathValue<'_>,
+    swapped: bool,
+) -> bool {
+    if let XPathValue::Boolean(_) = other {
+        let set = Atom::Boolean(!nodes.is_empty());
+        let other = Atom::from(other);
+        return if swapped {
+            compare_atoms(op, &other, &set)
+        } else {
+            compare_atoms(op, &set, &other)
+        };
+    }
+    let other_atom = Atom::from(other);
+    nodes.iter().any(|node| {
+        let value = node.string_value();
+        let node_atom = match other {
+            XPathValue::Number(_) => Atom::Number(string_to_number(&value)),
+            _ => Atom::String(&value),
+        };
+        if swapped {
+            compare_atoms(op, &other_atom, &node_atom)
+        } else {
+            compare_atoms(op, &node_atom, &other_atom)
+        }
+    })
+}
+
+enum Atom<'s> {
+    Boolean(bool),
+    Number(f64),
+    String(&'s str),
+}
+
+impl<'s> From<&'s XPathValue<'_>> for Atom<'s> {
+    fn from(value: &'s XPathValue<'_>) -> Self {
+        match value {
+            XPathValue::Boolean(b) => Atom::Boolean(*b),
+            XPathValue::Number(n) => Atom::Number(*n),
+            XPathValue::String(s) => Atom::String(s),
+            XPathValue::Nodes(nodes) => Atom::Boolean(!nodes.is_empty()),
+        }
+    }
+}
+
+impl Atom<'_> {
+    fn number(&self) -> f64 {
+        match self {
+            Atom::Boolean(b) => f64::from(u8::from(*b)),
+            Atom::Number(n) => *n,
+            Atom::String(s) => string_to_number(s),
+        }
+    }
+
+    fn boolean(&self) -> bool {
+        match self {
+            Atom::Boolean(b) => *b,
+            Atom::Number(n) => *n != 0.0 && !n.is_nan(),
+            Atom::String(s) => !s.is_empty(),
+        }
+    }
+}
+
+fn compare_atoms(op: CompareOp, left: &Atom<'_>, right: &Atom<'_>) -> bool {
+    match op {
+        CompareOp::Eq | CompareOp::Ne => {
+            let equal = match (left, right) {
+                (Atom::Boolean(_), _) | (_, Atom::Boolean(_)) => left.boolean() == right.boolean(),
+                (Atom::Number(_), _) | (_, Atom::Number(_)) => left.number() == right.number(),
+                (Atom::String(l), Atom::String(r)) => l == r,
+            };
+            equal == (op == CompareOp::Eq)
+        }
+        CompareOp::Lt => left.number() < right.number(),
+        CompareOp::Le => left.number() <= right.number(),
+        CompareOp::Gt => left.number() > right.number(),
+        CompareOp::Ge => left.number() >= right.number(),
+    }
+}
+
+/// XPath `round()`: halves round towards positive infinity
+fn round(n: f64) -> f64 {
+    if n.is_nan() || n.is_infinite() {
+        n
+    } else {
+        (n + 0.5).floor()
+    }
+}
+
+/// XPath `number()` of a string: optional minus, digits and one decimal point, else NaN
+pub(crate) fn string_to_number(s: &str) -> f64 {
+    let trimmed = s.trim();
+    let digits = trimmed.strip_prefix('-').unwrap_or(trimmed);
+    let valid = !digits.is_empty()
+        && digits.chars().any(|c| c.is_ascii_digit())
+        && digits.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && digits.matches('.').count() <= 1;
+    if valid {
+        trimmed.parse().unwrap_or(f64::NAN)
+    } else {
+        f64::NAN
+    }
+}
+
+/// XPath string form of a number: integers without a fraction, `NaN`, `Infinity`
+pub(crate) fn number_to_string(n: f64) -> String {
+    if n.is_nan() {
+        "NaN".to_string()
+    } else if n.is_infinite() {
+        if n > 0.0 { "Infinity" } else { "-Infinity" }.to_string()
+    } else if n == 0.0 {
+        "0".to_string()
+    } else {
+        n.to_string()
+    }
+}
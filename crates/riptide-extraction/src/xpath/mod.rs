@@ -0,0 +1,545 @@
+//! XPath 1.0 selectors over scraper documents
+//!
+//! [`XPath`] compiles an XPath 1.0 expression once and evaluates it against
+//! an [`Html`] document or from an [`ElementRef`], so selector libraries
+//! written for Scrapy or lxml can be used next to CSS selectors without being
+//! rewritten. All axes, the core function library and parsel's `has-class()`
+//! are supported; variables and the namespace axis are not.
+//!
+//! Namespace prefixes are bound with [`XPath::with_namespace`]. Unprefixed
+//! names match elements in any namespace, since the HTML parser puts every
+//! HTML element in the XHTML namespace, and a prefix that is not bound falls
+//! back to the literal `prefix:name` the parser keeps for tags like
+//! `<fb:like>`.
+//!
+//! [`xpath_expression`] decides whether a selector string is XPath: an
+//! explicit `xpath:` prefix, or an expression starting with `/`, `./`, `../`
+//! or `(`, none of which can begin a CSS selector.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_extraction::xpath::XPath;
+//! use scraper::Html;
+//!
+//! let html = Html::parse_document(
+//!     r#"<ul><li class="item">One</li><li class="item sale">Two</li></ul>"#,
+//! );
+//!
+//! let xpath = XPath::parse("//li[has-class('sale')]/text()").unwrap();
+//! assert_eq!(xpath.evaluate(&html).unwrap().strings(), vec!["Two"]);
+//!
+//! let count = XPath::parse("count(//li)").unwrap();
+//! assert_eq!(count.evaluate(&html).unwrap().number(), 2.0);
+//! ```
+
+mod eval;
+mod parser;
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use scraper::{ElementRef, Html, Node, Selector};
+use thiserror::Error;
+
+use eval::{number_to_string, string_to_number, Evaluator, Item, TreeNode};
+use parser::Expr;
+
+/// Prefix that marks a selector as XPath regardless of how it starts
+const XPATH_PREFIX: &str = "xpath:";
+
+/// Errors from compiling or evaluating an XPath expression
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum XPathError {
+    #[error("XPath syntax error at offset {position}: {message}")]
+    Syntax { position: usize, message: String },
+
+    #[error("Unknown XPath function: {0}()")]
+    UnknownFunction(String),
+
+    #[error("Wrong number of arguments for {function}(): {given}")]
+    Arity { function: String, given: usize },
+
+    #[error("XPath {0} requires a node-set")]
+    NotANodeSet(&'static str),
+}
+
+/// Compiled XPath 1.0 expression with its namespace bindings
+#[derive(Debug, Clone)]
+pub struct XPath {
+    source: String,
+    expr: Expr,
+    namespaces: HashMap<String, String>,
+}
+
+impl XPath {
+    /// Compile an XPath expression
+    pub fn parse(expression: &str) -> Result<Self, XPathError> {
+        Ok(Self {
+            source: expression.to_string(),
+            expr: parser::parse(expression)?,
+            namespaces: HashMap::new(),
+        })
+    }
+
+    /// Bind a namespace prefix used in the expression to a namespace URI
+    pub fn with_namespace(mut self, prefix: impl Into<String>, uri: impl Into<String>) -> Self {
+        self.namespaces.insert(prefix.into(), uri.into());
+        self
+    }
+
+    /// Bind several namespace prefixes at once
+    pub fn with_namespaces<I, P, U>(mut self, namespaces: I) -> Self
+    where
+        I: IntoIterator<Item = (P, U)>,
+        P: Into<String>,
+        U: Into<String>,
+    {
+        self.namespaces.extend(
+            namespaces
+                .into_iter()
+                .map(|(prefix, uri)| (prefix.into(), uri.into())),
+        );
+        self
+    }
+
+    /// The expression as written
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluate with the document root as the context node
+    pub fn evaluate<'a>(&self, document: &'a Html) -> Result<XPathValue<'a>, XPathError> {
+        self.evaluate_node(document.tree.root())
+    }
+
+    /// Evaluate with `element` as the context node; absolute paths still start at the root
+    pub fn evaluate_from<'a>(&self, element: ElementRef<'a>) -> Result<XPathValue<'a>, XPathError> {
+        self.evaluate_node(*element)
+    }
+
+    /// Nodes selected from the document root, in document order
+    pub fn select<'a>(&self, document: &'a Html) -> Result<Vec<XPathNode<'a>>, XPathError> {
+        self.evaluate(document)?
+            .into_nodes()
+            .ok_or(XPathError::NotANodeSet("select"))
+    }
+
+    /// Nodes selected from `element`, in document order
+    pub fn select_from<'a>(
+        &self,
+        element: ElementRef<'a>,
+    ) -> Result<Vec<XPathNode<'a>>, XPathError> {
+        self.evaluate_from(element)?
+            .into_nodes()
+            .ok_or(XPathError::NotANodeSet("select"))
+    }
+
+    fn evaluate_node<'a>(&self, node: TreeNode<'a>) -> Result<XPathValue<'a>, XPathError> {
+        Evaluator::new(node.tree().root(), &self.namespaces).evaluate(&self.expr, node)
+    }
+}
+
+impl FromStr for XPath {
+    type Err = XPathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for XPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Result of evaluating an XPath expression
+#[derive(Debug, Clone)]
+pub enum XPathValue<'a> {
+    Nodes(Vec<XPathNode<'a>>),
+    Boolean(bool),
+    Number(f64),
+    String(String),
+}
+
+impl<'a> XPathValue<'a> {
+    /// XPath `boolean()` of the value
+    pub fn boolean(&self) -> bool {
+        match self {
+            Self::Nodes(nodes) => !nodes.is_empty(),
+            Self::Boolean(b) => *b,
+            Self::Number(n) => *n != 0.0 && !n.is_nan(),
+            Self::String(s) => !s.is_empty(),
+        }
+    }
+
+    /// XPath `number()` of the value
+    pub fn number(&self) -> f64 {
+        match self {
+            Self::Boolean(b) => f64::from(u8::from(*b)),
+            Self::Number(n) => *n,
+            _ => string_to_number(&self.string()),
+        }
+    }
+
+    /// XPath `string()` of the value: the string-value of the first node of a node-set
+    pub fn string(&self) -> String {
+        match self {
+            Self::Nodes(nodes) => nodes
+                .first()
+                .map(XPathNode::string_value)
+                .unwrap_or_default(),
+            Self::Boolean(b) => b.to_string(),
+            Self::Number(n) => number_to_string(*n),
+            Self::String(s) => s.clone(),
+        }
+    }
+
+    /// String-value of every node of a node-set, or the single string of a scalar
+    pub fn strings(&self) -> Vec<String> {
+        match self {
+            Self::Nodes(nodes) => nodes.iter().map(XPathNode::string_value).collect(),
+            other => vec![other.string()],
+        }
+    }
+
+    /// The node-set, if the value is one
+    pub fn into_nodes(self) -> Option<Vec<XPathNode<'a>>> {
+        match self {
+            Self::Nodes(nodes) => Some(nodes),
+            _ => None,
+        }
+    }
+}
+
+/// Node selected by an XPath expression: a tree node or an element's attribute
+#[derive(Debug, Clone, Copy)]
+pub struct XPathNode<'a>(Item<'a>);
+
+impl<'a> XPathNode<'a> {
+    /// The element, if this node is one
+    pub fn as_element(&self) -> Option<ElementRef<'a>> {
+        match self.0 {
+            Item::Node(node) => ElementRef::wrap(node),
+            Item::Attribute { .. } => None,
+        }
+    }
+
+    /// The text, if this node is a text node
+    pub fn as_text(&self) -> Option<&'a str> {
+        match self.0 {
+            Item::Node(node) => node.value().as_text().map(|text| &**text),
+            Item::Attribute { .. } => None,
+        }
+    }
+
+    /// Name and value, if this node is an attribute
+    pub fn as_attribute(&self) -> Option<(&'a str, &'a str)> {
+        match self.0 {
+            Item::Attribute { element, index } => element
+                .value()
+                .attrs
+                .iter()
+                .nth(index)
+                .map(|(name, value)| (&*name.local, &**value)),
+            Item::Node(_) => None,
+        }
+    }
+
+    /// XPath string-value: the concatenated descendant text of elements and the
+    /// document, the value of attributes, the content of text and comment nodes
+    pub fn string_value(&self) -> String {
+        match self.0 {
+            Item::Attribute { .. } => self
+                .as_attribute()
+                .map(|(_, value)| value.to_string())
+                .unwrap_or_default(),
+            Item::Node(node) => match node.value() {
+                Node::Text(text) => text.to_string(),
+                Node::Comment(comment) => comment.to_string(),
+                Node::ProcessingInstruction(pi) => pi.data.to_string(),
+                Node::Doctype(_) => String::new(),
+                _ => node
+                    .descendants()
+                    .filter_map(|node| node.value().as_text())
+                    .map(|text| &**text)
+                    .collect(),
+            },
+        }
+    }
+
+    /// XPath `local-name()`
+    pub fn local_name(&self) -> &'a str {
+        match self.0 {
+            Item::Attribute { .. } => self.as_attribute().map_or("", |(name, _)| name),
+            Item::Node(node) => match node.value() {
+                Node::Element(element) => &element.name.local,
+                Node::ProcessingInstruction(pi) => &pi.target,
+                _ => "",
+            },
+        }
+    }
+
+    /// XPath `namespace-uri()`
+    pub fn namespace_uri(&self) -> &'a str {
+        match self.0 {
+            Item::Attribute { element, index } => element
+                .value()
+                .attrs
+                .iter()
+                .nth(index)
+                .map_or("", |(name, _)| &*name.ns),
+            Item::Node(node) => match node.value() {
+                Node::Element(element) => &element.name.ns,
+                _ => "",
+            },
+        }
+    }
+
+    /// XPath `name()`: the qualified name as written in the document
+    pub fn name(&self) -> String {
+        let qualified = |prefix: Option<&str>, local: &str| match prefix {
+            Some(prefix) => format!("{}:{}", prefix, local),
+            None => local.to_string(),
+        };
+        match self.0 {
+            Item::Attribute { element, index } => element
+                .value()
+                .attrs
+                .iter()
+                .nth(index)
+                .map(|(name, _)| qualified(name.prefix.as_deref(), &name.local))
+                .unwrap_or_default(),
+            Item::Node(node) => match node.value() {
+                Node::Element(element) => {
+                    qualified(element.name.prefix.as_deref(), &element.name.local)
+                }
+                _ => self.local_name().to_string(),
+            },
+        }
+    }
+}
+
+/// The XPath expression of a selector string, or `None` for a CSS selector
+pub fn xpath_expression(selector: &str) -> Option<&str> {
+    let selector = selector.trim();
+    if let Some(expression) = selector.strip_prefix(XPATH_PREFIX) {
+        return Some(expression.trim_start());
+    }
+    let is_xpath = selector.starts_with('/')
+        || selector.starts_with("./")
+        || selector.starts_with("../")
+        || selector.starts_with('(');
+    is_xpath.then_some(selector)
+}
+
+/// Whether a selector string is an XPath expression rather than CSS
+pub fn is_xpath(selector: &str) -> bool {
+    xpath_expression(selector).is_some()
+}
+
+/// Trimmed, non-empty text matched by a list of CSS and XPath selectors, in
+/// selector order
+///
+/// These are the semantics of `ExtractionMode::Custom`. Invalid CSS selectors
+/// match nothing, as in the extractor component, while invalid XPath is an
+/// error so migrated selector libraries fail loudly.
+pub fn select_text(document: &Html, selectors: &[String]) -> Result<Vec<String>, XPathError> {
+    let mut texts = Vec::new();
+    for selector in selectors {
+        let matched = match xpath_expression(selector) {
+            Some(expression) => XPath::parse(expression)?.evaluate(document)?.strings(),
+            None => match Selector::parse(selector) {
+                Ok(css) => document
+                    .select(&css)
+                    .map(|element| element.text().collect())
+                    .collect(),
+                Err(_) => Vec::new(),
+            },
+        };
+        texts.extend(
+            matched
+                .iter()
+                .map(|text| text.trim())
+                .filter(|text| !text.is_empty())
+                .map(str::to_string),
+        );
+    }
+    Ok(texts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<html lang="en-GB"><head><title>Catalogue</title></head><body>
+        <div id="main">
+            <h1>Products</h1>
+            <ul>
+                <li class="item" data-price="10"><a href="/a">Alpha</a></li>
+                <li class="item sale" data-price="5"><a href="/b">Beta</a></li>
+                <li class="item" data-price="20"><a href="/c">Gamma</a></li>
+            </ul>
+            <p>  Shipping   worldwide  </p>
+            <!-- promo -->
+        </div>
+        <fb:like href="/share"></fb:like>
+        <svg xmlns="http://www.w3.org/2000/svg"><a xlink:href="/icon"><title>Icon</title></a></svg>
+    </body></html>"#;
+
+    fn strings(expression: &str) -> Vec<String> {
+        let html = Html::parse_document(PAGE);
+        XPath::parse(expression)
+            .unwrap()
+            .evaluate(&html)
+            .unwrap()
+            .strings()
+    }
+
+    #[test]
+    fn test_paths_and_predicates() {
+        assert_eq!(strings("//li/a"), vec!["Alpha", "Beta", "Gamma"]);
+        assert_eq!(strings("//li[2]/a/text()"), vec!["Beta"]);
+        assert_eq!(strings("//li[last()]/a/@href"), vec!["/c"]);
+        assert_eq!(strings("(//a)[1]"), vec!["Alpha"]);
+        assert_eq!(strings("//li[@data-price > 8]/a"), vec!["Alpha", "Gamma"]);
+        assert_eq!(strings("//ul/li[position() < 3][2]//a"), vec!["Beta"]);
+        assert_eq!(
+            strings("//div[@id='main']/h1 | //head/title"),
+            vec!["Catalogue", "Products"]
+        );
+        assert_eq!(strings("//li[contains(@class, 'sale')]/a"), vec!["Beta"]);
+        assert_eq!(strings("//li[has-class('item', 'sale')]/a"), vec!["Beta"]);
+    }
+
+    #[test]
+    fn test_axes() {
+        assert_eq!(
+            strings("//a[.='Beta']/../following-sibling::li/a"),
+            vec!["Gamma"]
+        );
+        assert_eq!(
+            strings("//a[.='Beta']/../preceding-sibling::li[1]/a"),
+            vec!["Alpha"]
+        );
+        assert_eq!(strings("//a[.='Gamma']/ancestor::div/@id"), vec!["main"]);
+        assert_eq!(strings("//h1/following::a[1]"), vec!["Alpha"]);
+        assert_eq!(strings("//p/preceding::a[1]"), vec!["Gamma"]);
+        assert_eq!(strings("//div/comment()"), vec![" promo "]);
+        assert_eq!(strings("name(//li[1]/@*[. = '10'])"), vec!["data-price"]);
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!(strings("normalize-space(//p)"), vec!["Shipping worldwide"]);
+        assert_eq!(strings("count(//li)"), vec!["3"]);
+        assert_eq!(strings("sum(//li/@data-price) div 2"), vec!["17.5"]);
+        assert_eq!(strings("substring('riptide', 2, 3)"), vec!["ipt"]);
+        assert_eq!(strings("substring-after(//a[1]/@href, '/')"), vec!["a"]);
+        assert_eq!(strings("translate('abc', 'ab', 'B')"), vec!["Bc"]);
+        assert_eq!(
+            strings("concat(//h1, ': ', string-length(//h1))"),
+            vec!["Products: 8"]
+        );
+        assert_eq!(strings("boolean(//body[lang('en')])"), vec!["true"]);
+        assert_eq!(strings("round(2.5) + floor(-1.5) * 2"), vec!["-1"]);
+        assert_eq!(strings("not(//table)"), vec!["true"]);
+        assert_eq!(strings("id('main')/h1"), vec!["Products"]);
+    }
+
+    #[test]
+    fn test_namespaces() {
+        let html = Html::parse_document(PAGE);
+
+        let svg = XPath::parse("//svg:a/@xlink:href")
+            .unwrap()
+            .with_namespace("svg", "http://www.w3.org/2000/svg")
+            .with_namespace("xlink", "http://www.w3.org/1999/xlink");
+        assert_eq!(svg.evaluate(&html).unwrap().strings(), vec!["/icon"]);
+
+        let html_ns = XPath::parse("count(//h:li)")
+            .unwrap()
+            .with_namespace("h", "http://www.w3.org/1999/xhtml");
+        assert_eq!(html_ns.evaluate(&html).unwrap().number(), 3.0);
+
+        // Unbound prefix falls back to the literal tag name
+        assert_eq!(strings("//fb:like/@href"), vec!["/share"]);
+        assert_eq!(strings("count(//fb:*)"), vec!["1"]);
+    }
+
+    #[test]
+    fn test_select_from_element_and_node_kinds() {
+        let html = Html::parse_document(PAGE);
+        let list = XPath::parse("//ul").unwrap().select(&html).unwrap();
+        let ul = list[0].as_element().unwrap();
+
+        let hrefs = XPath::parse("./li/a/@href")
+            .unwrap()
+            .select_from(ul)
+            .unwrap();
+        assert_eq!(hrefs[1].as_attribute(), Some(("href", "/b")));
+        assert_eq!(hrefs[1].name(), "href");
+
+        let text = XPath::parse(".//a/text()")
+            .unwrap()
+            .select_from(ul)
+            .unwrap();
+        assert_eq!(text[2].as_text(), Some("Gamma"));
+
+        // Absolute paths start at the root even from an element
+        let titles = XPath::parse("//title").unwrap().select_from(ul).unwrap();
+        assert_eq!(titles[0].string_value(), "Catalogue");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(
+            XPath::parse("//li["),
+            Err(XPathError::Syntax { .. })
+        ));
+        assert!(matches!(
+            XPath::parse("//li[matches(., 'x')]"),
+            Err(XPathError::UnknownFunction(name)) if name == "matches"
+        ));
+        assert!(matches!(
+            XPath::parse("count()"),
+            Err(XPathError::Arity { given: 0, .. })
+        ));
+        assert!(matches!(
+            XPath::parse("//li[$price]"),
+            Err(XPathError::Syntax { .. })
+        ));
+
+        let html = Html::parse_document(PAGE);
+        let scalar = XPath::parse("count(//li)").unwrap();
+        assert_eq!(
+            scalar.select(&html).unwrap_err(),
+            XPathError::NotANodeSet("select")
+        );
+    }
+
+    #[test]
+    fn test_select_text_mixes_css_and_xpath() {
+        assert_eq!(xpath_expression("//a"), Some("//a"));
+        assert_eq!(xpath_expression("xpath: count(//a)"), Some("count(//a)"));
+        assert_eq!(xpath_expression(".item > a"), None);
+        assert!(!is_xpath("div.content"));
+
+        let html = Html::parse_document(PAGE);
+        let selectors = vec![
+            "h1".to_string(),
+            "//li[has-class('sale')]/a".to_string(),
+            "xpath:string((//li/a)[last()]/@href)".to_string(),
+            "p:::bad".to_string(),
+        ];
+        assert_eq!(
+            select_text(&html, &selectors).unwrap(),
+            vec!["Products", "Beta", "/c"]
+        );
+
+        let invalid = vec!["//li[".to_string()];
+        assert!(select_text(&html, &invalid).is_err());
+    }
+}
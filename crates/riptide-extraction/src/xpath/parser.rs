@@ -0,0 +1,863 @@
+//! XPath 1.0 tokenizer and recursive-descent parser
+
+use super::XPathError;
+
+/// Parsed XPath expression
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Compare(CompareOp, Box<Expr>, Box<Expr>),
+    Arithmetic(ArithmeticOp, Box<Expr>, Box<Expr>),
+    Negate(Box<Expr>),
+    Union(Box<Expr>, Box<Expr>),
+    /// Location path, optionally starting from the node-set of a filter expression
+    Path(Option<Box<Expr>>, LocationPath),
+    /// Primary expression followed by predicates
+    Filter(Box<Expr>, Vec<Expr>),
+    Literal(String),
+    Number(f64),
+    Function(Function, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ArithmeticOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LocationPath {
+    pub absolute: bool,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Step {
+    pub axis: Axis,
+    pub test: NodeTest,
+    pub predicates: Vec<Expr>,
+}
+
+impl Step {
+    fn descendant_or_self() -> Self {
+        Self {
+            axis: Axis::DescendantOrSelf,
+            test: NodeTest::Node,
+            predicates: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Axis {
+    Ancestor,
+    AncestorOrSelf,
+    Attribute,
+    Child,
+    Descendant,
+    DescendantOrSelf,
+    Following,
+    FollowingSibling,
+    Parent,
+    Preceding,
+    PrecedingSibling,
+    SelfNode,
+}
+
+impl Axis {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "ancestor" => Self::Ancestor,
+            "ancestor-or-self" => Self::AncestorOrSelf,
+            "attribute" => Self::Attribute,
+            "child" => Self::Child,
+            "descendant" => Self::Descendant,
+            "descendant-or-self" => Self::DescendantOrSelf,
+            "following" => Self::Following,
+            "following-sibling" => Self::FollowingSibling,
+            "parent" => Self::Parent,
+            "preceding" => Self::Preceding,
+            "preceding-sibling" => Self::PrecedingSibling,
+            "self" => Self::SelfNode,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum NodeTest {
+    /// `*`
+    Any,
+    /// `name` or `prefix:name`
+    Name {
+        prefix: Option<String>,
+        local: String,
+    },
+    /// `prefix:*`
+    Prefix(String),
+    Text,
+    Comment,
+    Node,
+    ProcessingInstruction(Option<String>),
+}
+
+/// Core XPath 1.0 function library plus `has-class`, the Scrapy/parsel extension
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Function {
+    Last,
+    Position,
+    Count,
+    Id,
+    LocalName,
+    NamespaceUri,
+    Name,
+    String,
+    Concat,
+    StartsWith,
+    Contains,
+    SubstringBefore,
+    SubstringAfter,
+    Substring,
+    StringLength,
+    NormalizeSpace,
+    Translate,
+    Boolean,
+    Not,
+    True,
+    False,
+    Lang,
+    Number,
+    Sum,
+    Floor,
+    Ceiling,
+    Round,
+    HasClass,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "last" => Self::Last,
+            "position" => Self::Position,
+            "count" => Self::Count,
+            "id" => Self::Id,
+            "local-name" => Self::LocalName,
+            "namespace-uri" => Self::NamespaceUri,
+            "name" => Self::Name,
+            "string" => Self::String,
+            "concat" => Self::Concat,
+            "starts-with" => Self::StartsWith,
+            "contains" => Self::Contains,
+            "substring-before" => Self::SubstringBefore,
+            "substring-after" => Self::SubstringAfter,
+            "substring" => Self::Substring,
+            "string-length" => Self::StringLength,
+            "normalize-space" => Self::NormalizeSpace,
+            "translate" => Self::Translate,
+            "boolean" => Self::Boolean,
+            "not" => Self::Not,
+            "true" => Self::True,
+            "false" => Self::False,
+            "lang" => Self::Lang,
+            "number" => Self::Number,
+            "sum" => Self::Sum,
+            "floor" => Self::Floor,
+            "ceiling" => Self::Ceiling,
+            "round" => Self::Round,
+            "has-class" => Self::HasClass,
+            _ => return None,
+        })
+    }
+
+    /// Minimum and maximum argument count (`None` for variadic)
+    fn arity(self) -> (usize, Option<usize>) {
+        match self {
+            Self::Last | Self::Position | Self::True | Self::False => (0, Some(0)),
+            Self::LocalName
+            | Self::NamespaceUri
+            | Self::Name
+            | Self::String
+            | Self::StringLength
+            | Self::NormalizeSpace
+            | Self::Number => (0, Some(1)),
+            Self::Count
+            | Self::Id
+            | Self::Boolean
+            | Self::Not
+            | Self::Lang
+            | Self::Sum
+            | Self::Floor
+            | Self::Ceiling
+            | Self::Round => (1, Some(1)),
+            Self::StartsWith | Self::Contains | Self::SubstringBefore | Self::SubstringAfter => {
+                (2, Some(2))
+            }
+            Self::Substring => (2, Some(3)),
+            Self::Translate => (3, Some(3)),
+            Self::Concat => (2, None),
+            Self::HasClass => (1, None),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Slash,
+    DoubleSlash,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    Dot,
+    DotDot,
+    At,
+    Comma,
+    DoubleColon,
+    Pipe,
+    Plus,
+    Minus,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Multiply,
+    And,
+    Or,
+    Div,
+    Mod,
+    Literal(String),
+    Number(f64),
+    /// Name test: `*`, `name`, `prefix:name` or `prefix:*`
+    NameTest(String),
+    NodeType(String),
+    FunctionName(String),
+    AxisName(String),
+}
+
+impl Token {
+    /// Whether a following `*` or name must be read as an operator (XPath 1.0 §3.7)
+    fn precedes_operator(&self) -> bool {
+        !matches!(
+            self,
+            Self::At
+                | Self::DoubleColon
+                | Self::LParen
+                | Self::LBracket
+                | Self::Comma
+                | Self::Slash
+                | Self::DoubleSlash
+                | Self::Pipe
+                | Self::Plus
+                | Self::Minus
+                | Self::Eq
+                | Self::Ne
+                | Self::Lt
+                | Self::Le
+                | Self::Gt
+                | Self::Ge
+                | Self::Multiply
+                | Self::And
+                | Self::Or
+                | Self::Div
+                | Self::Mod
+        )
+    }
+}
+
+fn syntax(position: usize, message: impl Into<String>) -> XPathError {
+    XPathError::Syntax {
+        position,
+        message: message.into(),
+    }
+}
+
+fn is_name_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '\u{b7}')
+}
+
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, XPathError> {
+    let chars: Vec<(usize, char)> = input.char_indices().collect();
+    let mut tokens: Vec<(Token, usize)> = Vec::new();
+    let mut i = 0;
+
+    let peek = |i: usize| chars.get(i).map(|&(_, c)| c);
+
+    while i < chars.len() {
+        let (position, c) = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let operator_context = tokens.last().is_some_and(|(t, _)| t.precedes_operator());
+
+        let token = match c {
+            '/' if peek(i + 1) == Some('/') => {
+                i += 2;
+                Token::DoubleSlash
+            }
+            '/' => {
+                i += 1;
+                Token::Slash
+            }
+            '(' => {
+                i += 1;
+                Token::LParen
+            }
+            ')' => {
+                i += 1;
+                Token::RParen
+            }
+            '[' => {
+                i += 1;
+                Token::LBracket
+            }
+            ']' => {
+                i += 1;
+                Token::RBracket
+            }
+            '@' => {
+                i += 1;
+                Token::At
+            }
+            ',' => {
+                i += 1;
+                Token::Comma
+            }
+            '|' => {
+                i += 1;
+                Token::Pipe
+            }
+            '+' => {
+                i += 1;
+                Token::Plus
+            }
+            '-' => {
+                i += 1;
+                Token::Minus
+            }
+            '=' => {
+                i += 1;
+                Token::Eq
+            }
+            '!' if peek(i + 1) == Some('=') => {
+                i += 2;
+                Token::Ne
+            }
+            '<' if peek(i + 1) == Some('=') => {
+                i += 2;
+                Token::Le
+            }
+            '<' => {
+                i += 1;
+                Token::Lt
+            }
+            '>' if peek(i + 1) == Some('=') => {
+                i += 2;
+                Token::Ge
+            }
+            '>' => {
+                i += 1;
+                Token::Gt
+            }
+            ':' if peek(i + 1) == Some(':') => {
+                i += 2;
+                Token::DoubleColon
+            }
+            '*' if operator_context => {
+                i += 1;
+                Token::Multiply
+            }
+            '*' => {
+                i += 1;
+                Token::NameTest("*".to_string())
+            }
+            '.' if peek(i + 1) == Some('.') => {
+                i += 2;
+                Token::DotDot
+            }
+            '.' if !peek(i + 1).is_some_and(|c| c.is_ascii_digit()) => {
+                i += 1;
+                Token::Dot
+            }
+            '"' | '\'' => {
+                let start = i + 1;
+                let end = (start..chars.len())
+                    .find(|&j| chars[j].1 == c)
+                    .ok_or_else(|| syntax(position, "unterminated string literal"))?;
+                i = end + 1;
+                Token::Literal(chars[start..end].iter().map(|&(_, c)| c).collect())
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                let start = i;
+                while peek(i).is_some_and(|c| c.is_ascii_digit()) {
+                    i += 1;
+                }
+                if peek(i) == Some('.') {
+                    i += 1;
+                    while peek(i).is_some_and(|c| c.is_ascii_digit()) {
+                        i += 1;
+                    }
+                }
+                let text: String = chars[start..i].iter().map(|&(_, c)| c).collect();
+                let value = text
+                    .parse()
+                    .map_err(|_| syntax(position, format!("invalid number '{}'", text)))?;
+                Token::Number(value)
+            }
+            '$' => return Err(syntax(position, "variable references are not supported")),
+            c if is_name_start(c) => {
+                let start = i;
+                while peek(i).is_some_and(is_name_char) {
+                    i += 1;
+                }
+                let mut name: String = chars[start..i].iter().map(|&(_, c)| c).collect();
+
+                if operator_context {
+                    match name.as_str() {
+                        "and" => Token::And,
+                        "or" => Token::Or,
+                        "div" => Token::Div,
+                        "mod" => Token::Mod,
+                        _ => {
+                            return Err(syntax(
+                                position,
+                                format!("expected an operator, found '{}'", name),
+                            ))
+                        }
+                    }
+                } else {
+                    // QName: `prefix:local` or `prefix:*`, but not the `::` of an axis
+                    if peek(i) == Some(':') && peek(i + 1) != Some(':') {
+                        match peek(i + 1) {
+                            Some('*') => {
+                                name.push_str(":*");
+                                i += 2;
+                            }
+                            Some(c) if is_name_start(c) => {
+                                name.push(':');
+                                i += 1;
+                                while peek(i).is_some_and(is_name_char) {
+                                    name.push(chars[i].1);
+                                    i += 1;
+                                }
+                            }
+                            _ => return Err(syntax(position, "incomplete qualified name")),
+                        }
+                    }
+
+                    let mut next = i;
+                    while peek(next).is_some_and(char::is_whitespace) {
+                        next += 1;
+                    }
+                    match (peek(next), peek(next + 1)) {
+                        (Some('('), _) => match name.as_str() {
+                            "comment" | "text" | "processing-instruction" | "node" => {
+                                Token::NodeType(name)
+                            }
+                            _ => Token::FunctionName(name),
+                        },
+                        (Some(':'), Some(':')) => Token::AxisName(name),
+                        _ => Token::NameTest(name),
+                    }
+                }
+            }
+            other => {
+                return Err(syntax(
+                    position,
+                    format!("unexpected character '{}'", other),
+                ))
+            }
+        };
+        tokens.push((token, position));
+    }
+
+    Ok(tokens)
+}
+
+/// Parse an XPath 1.0 expression
+pub(crate) fn parse(input: &str) -> Result<Expr, XPathError> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Err(syntax(0, "empty expression"));
+    }
+    let mut parser = Parser {
+        tokens,
+        index: 0,
+        end: input.len(),
+    };
+    let expr = parser.expr()?;
+    if let Some((token, position)) = parser.tokens.get(parser.index) {
+        return Err(syntax(*position, format!("unexpected {:?}", token)));
+    }
+    Ok(expr)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    index: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index).map(|(t, _)| t)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.index)
+            .map_or(self.end, |&(_, position)| position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).map(|(t, _)| t.clone());
+        self.index += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &Token, what: &str) -> Result<(), XPathError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(syntax(self.position(), format!("expected {}", what)))
+        }
+    }
+
+    fn expr(&mut self) -> Result<Expr, XPathError> {
+        let mut left = self.and_expr()?;
+        while self.eat(&Token::Or) {
+            left = Expr::Or(Box::new(left), Box::new(self.and_expr()?));
+        }
+        Ok(left)
+    }
+
+    fn and_expr(&mut self) -> Result<Expr, XPathError> {
+        let mut left = self.equality_expr()?;
+        while self.eat(&Token::And) {
+            left = Expr::And(Box::new(left), Box::new(self.equality_expr()?));
+        }
+        Ok(left)
+    }
+
+    fn equality_expr(&mut self) -> Result<Expr, XPathError> {
+        let mut left = self.relational_expr()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Eq) => CompareOp::Eq,
+                Some(Token::Ne) => CompareOp::Ne,
+                _ => return Ok(left),
+            };
+            self.index += 1;
+            left = Expr::Compare(op, Box::new(left), Box::new(self.relational_expr()?));
+        }
+    }
+
+    fn relational_expr(&mut self) -> Result<Expr, XPathError> {
+        let mut left = self.additive_expr()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Lt) => CompareOp::Lt,
+                Some(Token::Le) => CompareOp::Le,
+                Some(Token::Gt) => CompareOp::Gt,
+                Some(Token::Ge) => CompareOp::Ge,
+                _ => return Ok(left),
+            };
+            self.index += 1;
+            left = Expr::Compare(op, Box::new(left), Box::new(self.additive_expr()?));
+        }
+    }
+
+    fn additive_expr(&mut self) -> Result<Expr, XPathError> {
+        let mut left = self.multiplicative_expr()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => ArithmeticOp::Add,
+                Some(Token::Minus) => ArithmeticOp::Sub,
+                _ => return Ok(left),
+            };
+            self.index += 1;
+            left = Expr::Arithmetic(op, Box::new(left), Box::new(self.multiplicative_expr()?));
+        }
+    }
+
+    fn multiplicative_expr(&mut self) -> Result<Expr, XPathError> {
+        let mut left = self.unary_expr()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Multiply) => ArithmeticOp::Mul,
+                Some(Token::Div) => ArithmeticOp::Div,
+                Some(Token::Mod) => ArithmeticOp::Mod,
+                _ => return Ok(left),
+            };
+            self.index += 1;
+            left = Expr::Arithmetic(op, Box::new(left), Box::new(self.unary_expr()?));
+        }
+    }
+
+    fn unary_expr(&mut self) -> Result<Expr, XPathError> {
+        if self.eat(&Token::Minus) {
+            return Ok(Expr::Negate(Box::new(self.unary_expr()?)));
+        }
+        self.union_expr()
+    }
+
+    fn union_expr(&mut self) -> Result<Expr, XPathError> {
+        let mut left = self.path_expr()?;
+        while self.eat(&Token::Pipe) {
+            left = Expr::Union(Box::new(left), Box::new(self.path_expr()?));
+        }
+        Ok(left)
+    }
+
+    fn starts_step(&self) -> bool {
+        matches!(
+            self.peek(),
+            Some(
+                Token::NameTest(_)
+                    | Token::NodeType(_)
+                    | Token::AxisName(_)
+                    | Token::At
+                    | Token::Dot
+                    | Token::DotDot
+            )
+        )
+    }
+
+    fn path_expr(&mut self) -> Result<Expr, XPathError> {
+        match self.peek() {
+            Some(Token::Slash) => {
+                self.index += 1;
+                let steps = if self.starts_step() {
+                    self.relative_path()?
+                } else {
+                    Vec::new()
+                };
+                Ok(Expr::Path(
+                    None,
+                    LocationPath {
+                        absolute: true,
+                        steps,
+                    },
+                ))
+            }
+            Some(Token::DoubleSlash) => {
+                self.index += 1;
+                let mut steps = vec![Step::descendant_or_self()];
+                steps.extend(self.relative_path()?);
+                Ok(Expr::Path(
+                    None,
+                    LocationPath {
+                        absolute: true,
+                        steps,
+                    },
+                ))
+            }
+            _ if self.starts_step() => Ok(Expr::Path(
+                None,
+                LocationPath {
+                    absolute: false,
+                    steps: self.relative_path()?,
+                },
+            )),
+            _ => {
+                let primary = self.primary_expr()?;
+                let predicates = self.predicates()?;
+                let filter = if predicates.is_empty() {
+                    primary
+                } else {
+                    Expr::Filter(Box::new(primary), predicates)
+                };
+
+                let mut steps = Vec::new();
+                match self.peek() {
+                    Some(Token::Slash) => {
+                        self.index += 1;
+                    }
+                    Some(Token::DoubleSlash) => {
+                        self.index += 1;
+                        steps.push(Step::descendant_or_self());
+                    }
+                    _ => return Ok(filter),
+                }
+                steps.extend(self.relative_path()?);
+                Ok(Expr::Path(
+                    Some(Box::new(filter)),
+                    LocationPath {
+                        absolute: false,
+                        steps,
+                    },
+                ))
+            }
+        }
+    }
+
+    fn relative_path(&mut self) -> Result<Vec<Step>, XPathError> {
+        let mut steps = vec![self.step()?];
+        loop {
+            match self.peek() {
+                Some(Token::Slash) => {
+                    self.index += 1;
+                }
+                Some(Token::DoubleSlash) => {
+                    self.index += 1;
+                    steps.push(Step::descendant_or_self());
+                }
+                _ => return Ok(steps),
+            }
+            steps.push(self.step()?);
+        }
+    }
+
+    fn step(&mut self) -> Result<Step, XPathError> {
+        let position = self.position();
+        let axis = match self.next() {
+            Some(Token::Dot) => {
+                return Ok(Step {
+                    axis: Axis::SelfNode,
+                    test: NodeTest::Node,
+                    predicates: Vec::new(),
+                })
+            }
+            Some(Token::DotDot) => {
+                return Ok(Step {
+                    axis: Axis::Parent,
+                    test: NodeTest::Node,
+                    predicates: Vec::new(),
+                })
+            }
+            Some(Token::At) => Axis::Attribute,
+            Some(Token::AxisName(name)) => {
+                let axis = Axis::from_name(&name)
+                    .ok_or_else(|| syntax(position, format!("unsupported axis '{}'", name)))?;
+                self.expect(&Token::DoubleColon, "'::'")?;
+                axis
+            }
+            _ => {
+                self.index -= 1;
+                Axis::Child
+            }
+        };
+
+        let position = self.position();
+        let test = match self.next() {
+            Some(Token::NameTest(name)) => name_test(&name),
+            Some(Token::NodeType(kind)) => {
+                self.expect(&Token::LParen, "'('")?;
+                let test = match kind.as_str() {
+                    "text" => NodeTest::Text,
+                    "comment" => NodeTest::Comment,
+                    "node" => NodeTest::Node,
+                    _ => match self.peek() {
+                        Some(Token::Literal(target)) => {
+                            let target = target.clone();
+                            self.index += 1;
+                            NodeTest::ProcessingInstruction(Some(target))
+                        }
+                        _ => NodeTest::ProcessingInstruction(None),
+                    },
+                };
+                self.expect(&Token::RParen, "')'")?;
+                test
+            }
+            _ => return Err(syntax(position, "expected a node test")),
+        };
+
+        Ok(Step {
+            axis,
+            test,
+            predicates: self.predicates()?,
+        })
+    }
+
+    fn predicates(&mut self) -> Result<Vec<Expr>, XPathError> {
+        let mut predicates = Vec::new();
+        while self.eat(&Token::LBracket) {
+            predicates.push(self.expr()?);
+            self.expect(&Token::RBracket, "']'")?;
+        }
+        Ok(predicates)
+    }
+
+    fn primary_expr(&mut self) -> Result<Expr, XPathError> {
+        let position = self.position();
+        match self.next() {
+            Some(Token::LParen) => {
+                let expr = self.expr()?;
+                self.expect(&Token::RParen, "')'")?;
+                Ok(expr)
+            }
+            Some(Token::Literal(value)) => Ok(Expr::Literal(value)),
+            Some(Token::Number(value)) => Ok(Expr::Number(value)),
+            Some(Token::FunctionName(name)) => {
+                let function = Function::from_name(&name)
+                    .ok_or_else(|| XPathError::UnknownFunction(name.clone()))?;
+                self.expect(&Token::LParen, "'('")?;
+                let mut args = Vec::new();
+                if !self.eat(&Token::RParen) {
+                    loop {
+                        args.push(self.expr()?);
+                        if self.eat(&Token::RParen) {
+                            break;
+                        }
+                        self.expect(&Token::Comma, "',' or ')'")?;
+                    }
+                }
+                let (min, max) = function.arity();
+                if args.len() < min || max.is_some_and(|max| args.len() > max) {
+                    return Err(XPathError::Arity {
+                        function: name,
+                        given: args.len(),
+                    });
+                }
+                Ok(Expr::Function(function, args))
+            }
+            _ => Err(syntax(position, "expected an expression")),
+        }
+    }
+}
+
+fn name_test(name: &str) -> NodeTest {
+    if name == "*" {
+        return NodeTest::Any;
+    }
+    match name.split_once(':') {
+        Some((prefix, "*")) => NodeTest::Prefix(prefix.to_string()),
+        Some((prefix, local)) => NodeTest::Name {
+            prefix: Some(prefix.to_string()),
+            local: local.to_string(),
+        },
+        None => NodeTest::Name {
+            prefix: None,
+            local: name.to_string(),
+        },
+    }
+}
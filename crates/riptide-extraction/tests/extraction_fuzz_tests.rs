@@ -0,0 +1,257 @@
+//! Property-based fuzzing of the native HTML extraction path
+//!
+//! Generates malformed and hostile HTML (deep nesting, broken encodings,
+//! enormous attributes, tag soup) and checks every extractor:
+//!
+//! - never panics (an `Err` is fine),
+//! - finishes within `TIME_BUDGET`,
+//! - keeps its peak heap growth under `MEMORY_BASE + MEMORY_PER_BYTE * input`.
+//!
+//! Heap usage is measured with a counting global allocator, so measured runs
+//! are serialized. More cases: `PROPTEST_CASES=2000 cargo test -p
+//! riptide-extraction --test extraction_fuzz_tests --release`. The WASM
+//! extractor has the same harness in `wasm/riptide-extractor-wasm/tests`, and
+//! `fuzz/` holds coverage-guided `cargo fuzz` targets for both.
+
+use proptest::prelude::*;
+use riptide_extraction::{css_extraction, fallback_extract, regex_extraction, NativeHtmlParser};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+
+/// Wall-clock limit per extraction, generous for unoptimized builds
+const TIME_BUDGET: Duration = Duration::from_secs(20);
+
+/// Fixed heap allowance per extraction
+const MEMORY_BASE: usize = 64 * 1024 * 1024;
+
+/// Heap allowance per input byte
+const MEMORY_PER_BYTE: usize = 32;
+
+const URL: &str = "https://fuzz.example/page";
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static SERIAL: Mutex<()> = Mutex::new(());
+
+/// System allocator tracking live and peak heap bytes
+struct PeakAllocator;
+
+impl PeakAllocator {
+    fn grow(size: usize) {
+        let now = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(now, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                Self::grow(new_size - layout.size());
+            } else {
+                CURRENT.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+/// Run `extract` on a worker thread and check its time and memory budgets
+fn run_bounded(name: &str, html: String, extract: fn(&str)) {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let input_len = html.len();
+    let baseline = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+
+    let (done_tx, done_rx) = mpsc::channel();
+    let worker = std::thread::spawn(move || {
+        extract(&html);
+        drop(html);
+        let _ = done_tx.send(());
+    });
+    match done_rx.recv_timeout(TIME_BUDGET) {
+        Ok(()) => {}
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            panic!(
+                "{} did not finish within {:?} on a {} byte input",
+                name, TIME_BUDGET, input_len
+            )
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => {}
+    }
+    if let Err(payload) = worker.join() {
+        let message = payload
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| payload.downcast_ref::<&str>().copied())
+            .unwrap_or("non-string panic");
+        panic!(
+            "{} panicked on a {} byte input: {}",
+            name, input_len, message
+        );
+    }
+
+    let peak = PEAK.load(Ordering::SeqCst).saturating_sub(baseline);
+    let limit = MEMORY_BASE + MEMORY_PER_BYTE * input_len;
+    assert!(
+        peak <= limit,
+        "{} used {} bytes of heap on a {} byte input (limit {})",
+        name,
+        peak,
+        input_len,
+        limit
+    );
+}
+
+const TAGS: &[&str] = &[
+    "div", "span", "p", "a", "b", "i", "font", "table", "tr", "td", "ul", "li", "section",
+    "article", "main", "form", "select", "option", "svg", "math", "template", "noscript", "iframe",
+];
+
+/// Fragments that confuse tokenizers and tree builders
+#[rustfmt::skip]
+const SOUP: &[&str] = &[
+    "<", ">", "</", "/>", "<!--", "-->", "<!", "<?", "<![CDATA[", "]]>", "<script>", "</script>",
+    "<style>", "</style>", "<title>", "</title>", "<table>", "<td>", "<tr>", "<p>", "</p>",
+    "<a href=", "\"", "'", "=", "&", "&amp", "&#", "&#x", ";", "&nbsp;", "&#0;", "&#xD800;",
+    "&#x110000;", "\u{0}", "\u{feff}", "\u{202e}", "\r", "\n", " ", "text", "élan", "日本語", "🦀",
+];
+
+/// Thousands of nested elements, closed or left open
+fn deep_nesting() -> impl Strategy<Value = String> {
+    (
+        prop::collection::vec(prop::sample::select(TAGS), 1..4000),
+        any::<bool>(),
+    )
+        .prop_map(|(tags, close)| {
+            let mut html = String::from("<html><body>");
+            for tag in &tags {
+                html.push('<');
+                html.push_str(tag);
+                html.push('>');
+            }
+            html.push_str("deep text");
+            if close {
+                for tag in tags.iter().rev() {
+                    html.push_str("</");
+                    html.push_str(tag);
+                    html.push('>');
+                }
+            }
+            html
+        })
+}
+
+/// One huge attribute value plus many attributes on the same elements
+fn enormous_attributes() -> impl Strategy<Value = String> {
+    (
+        prop::sample::select(&["href", "src", "class", "style", "content", "srcset", "alt"][..]),
+        1usize..262_144,
+        prop::sample::select(&['a', '"', '\'', '<', '>', '&', ' ', '=', 'é', '🦀'][..]),
+        0usize..2000,
+    )
+        .prop_map(|(name, len, fill, count)| {
+            let value = fill.to_string().repeat(len);
+            let many: String = (0..count)
+                .map(|i| format!(" data-a{}=\"{}\"", i, i))
+                .collect();
+            format!(
+                "<html><head><title {many}>t</title>\
+                 <meta name=\"description\" {name}=\"{value}\" content=\"{value}\"></head>\
+                 <body><article {many}><a {name}=\"{value}\"{many}>link</a>\
+                 <img {name}=\"{value}\" src=\"/x.png\"><p>text</p></article></body></html>"
+            )
+        })
+}
+
+/// Arbitrary bytes decoded lossily, behind a charset the bytes do not match
+fn broken_encoding() -> impl Strategy<Value = String> {
+    (
+        prop::collection::vec(any::<u8>(), 0..16_384),
+        prop::sample::select(&["utf-8", "shift_jis", "utf-16", "iso-8859-1", "bogus"][..]),
+    )
+        .prop_map(|(bytes, charset)| {
+            format!(
+                "\u{feff}<html><head><meta charset=\"{}\"><title>{}</title></head>\
+                 <body><p>{}</p></body></html>",
+                charset,
+                String::from_utf8_lossy(&bytes[..bytes.len() / 4]),
+                String::from_utf8_lossy(&bytes)
+            )
+        })
+}
+
+/// Random concatenation of tokenizer-hostile fragments
+fn tag_soup() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop::sample::select(SOUP), 0..4000).prop_map(|parts| parts.concat())
+}
+
+fn hostile_html() -> impl Strategy<Value = String> {
+    prop_oneof![
+        deep_nesting(),
+        enormous_attributes(),
+        broken_encoding(),
+        tag_soup(),
+        any::<String>(),
+    ]
+}
+
+/// 48 cases per extractor unless `PROPTEST_CASES` is set
+fn config() -> ProptestConfig {
+    let cases = std::env::var("PROPTEST_CASES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(48);
+    ProptestConfig::with_cases(cases)
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn native_parser_survives_hostile_html(html in hostile_html()) {
+        run_bounded("native parser", html, |html| {
+            let _ = NativeHtmlParser::new().parse_headless_html(html, URL);
+        });
+    }
+
+    #[test]
+    fn css_extraction_survives_hostile_html(html in hostile_html()) {
+        run_bounded("css extraction", html, |html| {
+            let _ = futures::executor::block_on(css_extraction::extract_default(html, URL));
+        });
+    }
+
+    #[test]
+    fn regex_extraction_survives_hostile_html(html in hostile_html()) {
+        run_bounded("regex extraction", html, |html| {
+            let _ = futures::executor::block_on(regex_extraction::extract_default(html, URL));
+        });
+    }
+
+    #[test]
+    fn fallback_extraction_survives_hostile_html(html in hostile_html()) {
+        run_bounded("fallback extraction", html, |html| {
+            let _ = futures::executor::block_on(fallback_extract(html, URL));
+        });
+    }
+}
@@ -0,0 +1,338 @@
+//! Golden-file extraction regression corpus
+//!
+//! Every directory under `tests/corpus/` is a case:
+//!
+//! - `input.html`: the stored page
+//! - `case.json`: `{"url": ..., "tolerance": {...}}`, tolerance optional
+//! - `<strategy>.json`: the expected `ExtractedDoc` for each strategy, or
+//!   `{"error": ...}` when the strategy is expected to fail
+//!
+//! The runner extracts each case with every strategy and diffs the result
+//! against its golden under the case's tolerance rules. After an intended
+//! extraction change, re-record the goldens and review the diff:
+//!
+//! ```text
+//! RIPTIDE_BLESS=1 cargo test -p riptide-extraction --test golden_corpus_tests
+//! git diff crates/riptide-extraction/tests/corpus
+//! ```
+//!
+//! A strategy without a golden fails the run until it is blessed.
+
+use riptide_extraction::{css_extraction, fallback_extract, regex_extraction, NativeHtmlParser};
+use riptide_types::{ExtractedContent, ExtractedDoc};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Strategies every case is extracted with
+const STRATEGIES: [&str; 4] = ["native", "css", "regex", "fallback"];
+
+/// Fields that vary between runs and are never recorded
+const VOLATILE_FIELDS: [&str; 3] = ["parser_metadata", "provenance", "html"];
+
+/// Fields compared as unordered sets
+const SET_FIELDS: [&str; 3] = ["links", "media", "categories"];
+
+/// Fields compared by word overlap
+const TEXT_FIELDS: [&str; 2] = ["text", "markdown"];
+
+/// How far an extraction may drift from its golden
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Tolerance {
+    /// Minimum word-overlap similarity of text fields (1.0 = same words)
+    min_text_similarity: f64,
+    /// Allowed difference in `quality_score`
+    quality_score_delta: u64,
+    /// Allowed relative difference in `word_count`
+    word_count_ratio: f64,
+    /// Additional fields left out of the comparison
+    ignore_fields: Vec<String>,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self {
+            min_text_similarity: 0.97,
+            quality_score_delta: 5,
+            word_count_ratio: 0.02,
+            ignore_fields: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CaseConfig {
+    url: String,
+    #[serde(default)]
+    tolerance: Tolerance,
+}
+
+struct Case {
+    name: String,
+    dir: PathBuf,
+    html: String,
+    config: CaseConfig,
+}
+
+fn corpus_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus")
+}
+
+fn load_cases() -> Vec<Case> {
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(corpus_dir())
+        .expect("corpus directory is readable")
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+
+    dirs.into_iter()
+        .map(|dir| {
+            let name = dir.file_name().unwrap().to_string_lossy().into_owned();
+            let read = |file: &str| {
+                std::fs::read_to_string(dir.join(file))
+                    .unwrap_or_else(|e| panic!("{}/{}: {}", name, file, e))
+            };
+            let config = serde_json::from_str(&read("case.json"))
+                .unwrap_or_else(|e| panic!("{}/case.json: {}", name, e));
+            Case {
+                html: read("input.html"),
+                config,
+                name,
+                dir,
+            }
+        })
+        .collect()
+}
+
+fn doc_from_content(content: ExtractedContent) -> ExtractedDoc {
+    ExtractedDoc {
+        url: content.url,
+        title: Some(content.title),
+        text: content.content,
+        description: content.summary,
+        quality_score: Some((content.extraction_confidence.clamp(0.0, 1.0) * 100.0).round() as u8),
+        ..Default::default()
+    }
+}
+
+/// Extract `html` with `strategy` as golden-comparable JSON
+async fn extract(strategy: &str, html: &str, url: &str) -> Value {
+    let result = match strategy {
+        "native" => NativeHtmlParser::new()
+            .parse_headless_html(html, url)
+            .map_err(anyhow::Error::from),
+        "css" => css_extraction::extract_default(html, url)
+            .await
+            .map(doc_from_content),
+        "regex" => regex_extraction::extract_default(html, url)
+            .await
+            .map(doc_from_content),
+        "fallback" => fallback_extract(html, url).await.map(doc_from_content),
+        other => panic!("unknown strategy {}", other),
+    };
+
+    match result {
+        Ok(doc) => {
+            let mut value = serde_json::to_value(doc).expect("ExtractedDoc serializes");
+            if let Value::Object(fields) = &mut value {
+                for field in VOLATILE_FIELDS {
+                    fields.remove(field);
+                }
+            }
+            value
+        }
+        Err(e) => json!({ "error": e.to_string() }),
+    }
+}
+
+fn normalized_words(text: &str) -> Vec<String> {
+    text.split_whitespace().map(str::to_lowercase).collect()
+}
+
+/// Dice coefficient of the word multisets of `a` and `b`
+fn text_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (normalized_words(a), normalized_words(b));
+    if a == b {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for word in &a {
+        *counts.entry(word).or_default() += 1;
+    }
+    let mut common = 0;
+    for word in &b {
+        if let Some(count) = counts.get_mut(word.as_str()) {
+            if *count > 0 {
+                *count -= 1;
+                common += 1;
+            }
+        }
+    }
+    (2 * common) as f64 / (a.len() + b.len()) as f64
+}
+
+fn as_set(value: Option<&Value>) -> BTreeSet<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|items| items.iter().map(Value::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Differences between `actual` and `expected` beyond `tolerance`
+fn diff(actual: &Value, expected: &Value, tolerance: &Tolerance) -> Vec<String> {
+    let empty = Map::new();
+    let actual = actual.as_object().unwrap_or(&empty);
+    let expected = expected.as_object().unwrap_or(&empty);
+    let fields: BTreeSet<&String> = actual.keys().chain(expected.keys()).collect();
+
+    let mut differences = Vec::new();
+    for field in fields {
+        if VOLATILE_FIELDS.contains(&field.as_str()) || tolerance.ignore_fields.contains(field) {
+            continue;
+        }
+        let (got, want) = (actual.get(field), expected.get(field));
+        let problem = if TEXT_FIELDS.contains(&field.as_str()) {
+            let similarity = text_similarity(
+                got.and_then(Value::as_str).unwrap_or_default(),
+                want.and_then(Value::as_str).unwrap_or_default(),
+            );
+            (similarity < tolerance.min_text_similarity).then(|| {
+                format!(
+                    "similarity {:.3} below {:.3}",
+                    similarity, tolerance.min_text_similarity
+                )
+            })
+        } else if SET_FIELDS.contains(&field.as_str()) {
+            let (got, want) = (as_set(got), as_set(want));
+            (got != want).then(|| {
+                format!(
+                    "missing {:?}, unexpected {:?}",
+                    want.difference(&got).collect::<Vec<_>>(),
+                    got.difference(&want).collect::<Vec<_>>()
+                )
+            })
+        } else if field == "quality_score" {
+            let (got, want) = (got.and_then(Value::as_u64), want.and_then(Value::as_u64));
+            match (got, want) {
+                (Some(g), Some(w)) if g.abs_diff(w) <= tolerance.quality_score_delta => None,
+                (None, None) => None,
+                _ => Some(format!("{:?}, expected {:?}", got, want)),
+            }
+        } else if field == "word_count" || field == "reading_time" {
+            let (got, want) = (got.and_then(Value::as_u64), want.and_then(Value::as_u64));
+            match (got, want) {
+                (Some(g), Some(w)) => {
+                    let allowed = if field == "word_count" {
+                        (w as f64 * tolerance.word_count_ratio).ceil() as u64
+                    } else {
+                        1
+                    };
+                    (g.abs_diff(w) > allowed).then(|| format!("{}, expected {}", g, w))
+                }
+                (None, None) => None,
+                _ => Some(format!("{:?}, expected {:?}", got, want)),
+            }
+        } else {
+            (got != want).then(|| format!("{:?}, expected {:?}", got, want))
+        };
+        if let Some(problem) = problem {
+            differences.push(format!("{}: {}", field, problem));
+        }
+    }
+    differences
+}
+
+#[tokio::test]
+async fn test_golden_corpus() {
+    let bless = std::env::var_os("RIPTIDE_BLESS").is_some();
+    let cases = load_cases();
+    assert!(!cases.is_empty(), "corpus has no cases");
+
+    let mut failures = Vec::new();
+    for case in &cases {
+        for strategy in STRATEGIES {
+            let actual = extract(strategy, &case.html, &case.config.url).await;
+            let golden_path = case.dir.join(format!("{}.json", strategy));
+
+            if bless {
+                let mut json = serde_json::to_string_pretty(&actual).unwrap();
+                json.push('\n');
+                std::fs::write(&golden_path, json).unwrap();
+                continue;
+            }
+
+            let Ok(golden) = std::fs::read_to_string(&golden_path) else {
+                failures.push(format!("{}/{}: no golden", case.name, strategy));
+                continue;
+            };
+            let expected: Value = serde_json::from_str(&golden)
+                .unwrap_or_else(|e| panic!("{}: {}", golden_path.display(), e));
+            for difference in diff(&actual, &expected, &case.config.tolerance) {
+                failures.push(format!("{}/{}: {}", case.name, strategy, difference));
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "Extraction drifted from the goldens (re-bless with RIPTIDE_BLESS=1 if intended):\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn test_text_similarity_ignores_whitespace_and_case() {
+    assert_eq!(text_similarity("Hello   World\n", "hello world"), 1.0);
+    assert!(text_similarity("one two three four", "one two three five") < 0.8);
+    assert_eq!(text_similarity("", "anything"), 0.0);
+}
+
+#[test]
+fn test_diff_applies_tolerance_rules() {
+    let expected = json!({
+        "title": "Bike Lanes",
+        "text": "The council voted to build protected bike lanes on Main Street this year",
+        "links": ["https://a.example/", "https://b.example/"],
+        "quality_score": 80,
+        "word_count": 100,
+        "parser_metadata": { "parse_time_ms": 3 }
+    });
+    let within = json!({
+        "title": "Bike Lanes",
+        "text": "The council  voted to build protected bike lanes on Main Street this year",
+        "links": ["https://b.example/", "https://a.example/"],
+        "quality_score": 83,
+        "word_count": 102,
+        "parser_metadata": { "parse_time_ms": 9 }
+    });
+    assert!(diff(&within, &expected, &Tolerance::default()).is_empty());
+
+    let drifted = json!({
+        "title": "Bike lanes",
+        "text": "Subscribe to our newsletter",
+        "links": ["https://a.example/"],
+        "quality_score": 60,
+        "word_count": 100
+    });
+    let differences = diff(&drifted, &expected, &Tolerance::default());
+    let fields: Vec<&str> = differences
+        .iter()
+        .map(|d| d.split(':').next().unwrap())
+        .collect();
+    assert_eq!(fields, vec!["links", "quality_score", "text", "title"]);
+
+    let lenient = Tolerance {
+        ignore_fields: vec!["title".to_string(), "links".to_string()],
+        min_text_similarity: 0.0,
+        quality_score_delta: 20,
+        ..Default::default()
+    };
+    assert!(diff(&drifted, &expected, &lenient).is_empty());
+}
@@ -133,6 +133,7 @@ async fn test_wasm_resource_limits_enforced() -> Result<()> {
         enable_aot_cache: false,
         instance_pool_size: 1,
         max_idle_time: Duration::from_secs(10),
+        markdown_flavor: Default::default(),
     };
 
     let extractor = CmExtractor::with_config(wasm_path.to_str().unwrap(), config).await?;
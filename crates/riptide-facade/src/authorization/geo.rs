@@ -0,0 +1,131 @@
+//! Geographic crawl restrictions
+//!
+//! [`GeoRestriction`] resolves a URL's host through a GeoIP port and denies
+//! access when the server sits in a blocked country or autonomous system.
+//! Unlike [`AuthorizationPolicy`](super::AuthorizationPolicy) it is async,
+//! because resolving the host requires a DNS lookup.
+
+use crate::error::{RiptideError, RiptideResult};
+use riptide_fetch::geoip::locate_host;
+use riptide_types::ports::{GeoIpLookup, GeoLocation};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::warn;
+use url::Url;
+
+/// Deny crawling servers located in blocked countries or networks
+#[derive(Clone)]
+pub struct GeoRestriction {
+    lookup: Arc<dyn GeoIpLookup>,
+    blocked_countries: HashSet<String>,
+    blocked_asns: HashSet<u32>,
+}
+
+impl GeoRestriction {
+    /// Create a restriction with no blocked locations
+    pub fn new(lookup: Arc<dyn GeoIpLookup>) -> Self {
+        Self {
+            lookup,
+            blocked_countries: HashSet::new(),
+            blocked_asns: HashSet::new(),
+        }
+    }
+
+    /// Block servers in a country (ISO 3166-1 alpha-2 code, case-insensitive)
+    pub fn block_country(mut self, country_code: impl AsRef<str>) -> Self {
+        self.blocked_countries
+            .insert(country_code.as_ref().to_ascii_uppercase());
+        self
+    }
+
+    /// Block servers in an autonomous system
+    pub fn block_asn(mut self, asn: u32) -> Self {
+        self.blocked_asns.insert(asn);
+        self
+    }
+
+    /// Check a URL against the restriction
+    ///
+    /// Returns the server location when known. Hosts that cannot be located
+    /// are allowed: GeoIP coverage is incomplete and must not block crawls.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RiptideError::PermissionDenied` when the server location is blocked.
+    pub async fn check(&self, url: &str) -> RiptideResult<Option<GeoLocation>> {
+        let parsed = Url::parse(url)?;
+        let Some(host) = parsed.host_str() else {
+            return Ok(None);
+        };
+        let Some(location) = locate_host(self.lookup.as_ref(), host).await else {
+            return Ok(None);
+        };
+
+        if let Some(reason) = self.blocked_reason(&location) {
+            warn!(url = %url, reason = %reason, "Fetch denied by geo restriction");
+            return Err(RiptideError::PermissionDenied(format!(
+                "{} is hosted in a restricted location ({})",
+                host, reason
+            )));
+        }
+        Ok(Some(location))
+    }
+
+    fn blocked_reason(&self, location: &GeoLocation) -> Option<String> {
+        if let Some(country) = location
+            .country_code
+            .as_deref()
+            .filter(|c| self.blocked_countries.contains(&c.to_ascii_uppercase()))
+        {
+            return Some(format!("country {}", country));
+        }
+        location
+            .asn
+            .filter(|asn| self.blocked_asns.contains(asn))
+            .map(|asn| format!("AS{}", asn))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::ports::InMemoryGeoIp;
+
+    fn restriction() -> GeoRestriction {
+        let geoip = InMemoryGeoIp::new().with_entry(
+            "127.0.0.1".parse().unwrap(),
+            GeoLocation {
+                country_code: Some("DE".to_string()),
+                asn: Some(64500),
+                as_org: None,
+            },
+        );
+        GeoRestriction::new(Arc::new(geoip))
+    }
+
+    #[tokio::test]
+    async fn test_blocked_country_denied() {
+        let result = restriction()
+            .block_country("de")
+            .check("http://127.0.0.1/page")
+            .await;
+        assert!(matches!(result, Err(RiptideError::PermissionDenied(_))));
+    }
+
+    #[tokio::test]
+    async fn test_blocked_asn_denied() {
+        let result = restriction()
+            .block_asn(64500)
+            .check("http://127.0.0.1/")
+            .await;
+        assert!(matches!(result, Err(RiptideError::PermissionDenied(_))));
+    }
+
+    #[tokio::test]
+    async fn test_unblocked_and_unknown_allowed() {
+        let restriction = restriction().block_country("FR");
+        let location = restriction.check("http://127.0.0.1/").await.unwrap();
+        assert_eq!(location.unwrap().country_code.as_deref(), Some("DE"));
+        assert!(restriction.check("http://[::1]/").await.unwrap().is_none());
+    }
+}
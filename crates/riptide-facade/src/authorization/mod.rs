@@ -5,6 +5,7 @@
 //! - Tenant scoping (multi-tenancy isolation)
 //! - Role-based access control (RBAC)
 //! - Resource ownership policies
+//! - Geographic restrictions based on server GeoIP location
 //! - Fine-grained permission checks
 //!
 //! ## Architecture
@@ -34,8 +35,11 @@
 //! policy.authorize(&ctx, &resource)?;
 //! ```
 
+pub mod geo;
 pub mod policies;
 
+pub use geo::GeoRestriction;
+
 use riptide_types::error::Result as RiptideResult;
 use std::collections::HashSet;
 
@@ -0,0 +1,463 @@
+//! Declarative browser action scripts.
+//!
+//! An [`ActionScript`] describes an interaction flow (clicks, typing, waits
+//! on page conditions and loops) in YAML, so login and infinite-scroll flows
+//! can be written without Rust. Scripts are validated when loaded and run by
+//! [`BrowserFacade::run_script`](crate::facades::BrowserFacade::run_script).
+//!
+//! # Example
+//!
+//! ```yaml
+//! name: login-and-scroll
+//! steps:
+//!   - action: type
+//!     selector: "#email"
+//!     text: user@example.com
+//!   - action: click
+//!     selector: "button[type=submit]"
+//!   - action: wait_for
+//!     until: { element: ".dashboard" }
+//!     timeout_ms: 10000
+//!   - action: loop_until
+//!     until: page_height_stable
+//!     max_iterations: 30
+//!     steps:
+//!       - action: scroll_by
+//!         y: 2000
+//! ```
+
+use crate::error::{RiptideError, RiptideResult};
+use crate::facades::browser::BrowserAction;
+use serde::{Deserialize, Serialize};
+
+/// Most steps a script may contain, loop bodies included
+pub const MAX_SCRIPT_STEPS: usize = 200;
+
+/// Longest wait or wait timeout a step may use
+pub const MAX_WAIT_MS: u64 = 300_000;
+
+/// Most iterations a loop may run
+pub const MAX_LOOP_ITERATIONS: u32 = 500;
+
+/// Page condition waited on by `wait_for` and `loop_until` steps
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WaitCondition {
+    /// An element matching the selector exists
+    Element(String),
+    /// No element matches the selector
+    ElementGone(String),
+    /// At least `count` elements match the selector
+    MinCount { selector: String, count: usize },
+    /// The page URL contains the text
+    UrlContains(String),
+    /// A JavaScript expression is truthy
+    Script(String),
+    /// The page height did not change since the previous check
+    PageHeightStable,
+}
+
+impl WaitCondition {
+    /// JavaScript expression probing the condition
+    ///
+    /// Evaluates to a boolean, except for [`WaitCondition::PageHeightStable`]
+    /// which yields the current height for [`ConditionProbe`] to compare.
+    pub(crate) fn expression(&self) -> String {
+        match self {
+            Self::Element(selector) => {
+                format!("document.querySelector({}) !== null", js_string(selector))
+            }
+            Self::ElementGone(selector) => {
+                format!("document.querySelector({}) === null", js_string(selector))
+            }
+            Self::MinCount { selector, count } => format!(
+                "document.querySelectorAll({}).length >= {}",
+                js_string(selector),
+                count
+            ),
+            Self::UrlContains(text) => format!("location.href.includes({})", js_string(text)),
+            Self::Script(script) => format!("!!({})", script),
+            Self::PageHeightStable => "document.documentElement.scrollHeight".to_string(),
+        }
+    }
+
+    fn validate(&self, path: &str) -> RiptideResult<()> {
+        match self.problem() {
+            Some(problem) => Err(invalid(path, &problem)),
+            None => Ok(()),
+        }
+    }
+
+    /// Why the condition cannot be checked, `None` when it is valid
+    pub(crate) fn problem(&self) -> Option<String> {
+        match self {
+            Self::Element(selector) | Self::ElementGone(selector) => selector_problem(selector),
+            Self::MinCount { selector, count } => selector_problem(selector).or_else(|| {
+                (*count == 0).then(|| "min_count needs a count of at least 1".to_string())
+            }),
+            Self::UrlContains(text) if text.is_empty() => {
+                Some("url_contains needs a non-empty text".to_string())
+            }
+            Self::Script(script) if script.trim().is_empty() => {
+                Some("script condition is empty".to_string())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Tracks condition checks across polls
+///
+/// Height stability compares each check with the previous one, so a probe
+/// lives for one wait or loop.
+#[derive(Debug, Default)]
+pub(crate) struct ConditionProbe {
+    last_height: Option<f64>,
+}
+
+impl ConditionProbe {
+    /// Whether `condition` holds given the value of its expression
+    pub(crate) fn observe(&mut self, condition: &WaitCondition, value: &serde_json::Value) -> bool {
+        match condition {
+            WaitCondition::PageHeightStable => {
+                let height = value.as_f64();
+                let stable = height.is_some() && height == self.last_height;
+                self.last_height = height;
+                stable
+            }
+            _ => value.as_bool().unwrap_or(false),
+        }
+    }
+}
+
+/// One step of an action script
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ScriptStep {
+    /// Click an element
+    Click { selector: String },
+
+    /// Type text into an element
+    Type { selector: String, text: String },
+
+    /// Pause for a fixed duration
+    Wait { duration_ms: u64 },
+
+    /// Wait until a page condition holds
+    WaitFor {
+        until: WaitCondition,
+        #[serde(default = "default_timeout_ms")]
+        timeout_ms: u64,
+    },
+
+    /// Scroll an element into view
+    ScrollTo { selector: String },
+
+    /// Scroll by pixels
+    ScrollBy {
+        #[serde(default)]
+        x: i32,
+        #[serde(default)]
+        y: i32,
+    },
+
+    /// Submit a form
+    Submit { selector: String },
+
+    /// Focus an element
+    Focus { selector: String },
+
+    /// Repeat `steps` until a page condition holds
+    ///
+    /// The condition is checked before each iteration. Reaching
+    /// `max_iterations` ends the loop without an error, so infinite-scroll
+    /// pages stop after a bounded amount of content.
+    LoopUntil {
+        until: WaitCondition,
+        steps: Vec<ScriptStep>,
+        #[serde(default = "default_max_iterations")]
+        max_iterations: u32,
+        /// Pause after each iteration, letting new content load
+        #[serde(default = "default_loop_delay_ms")]
+        delay_ms: u64,
+    },
+}
+
+fn default_timeout_ms() -> u64 {
+    10_000
+}
+
+fn default_max_iterations() -> u32 {
+    20
+}
+
+fn default_loop_delay_ms() -> u64 {
+    500
+}
+
+impl ScriptStep {
+    /// Browser action performing this step, `None` for loops
+    pub fn to_action(&self) -> Option<BrowserAction> {
+        let action = match self {
+            Self::Click { selector } => BrowserAction::Click {
+                selector: selector.clone(),
+            },
+            Self::Type { selector, text } => BrowserAction::Type {
+                selector: selector.clone(),
+                text: text.clone(),
+            },
+            Self::Wait { duration_ms } => BrowserAction::Wait {
+                duration_ms: *duration_ms,
+            },
+            Self::WaitFor { until, timeout_ms } => BrowserAction::WaitUntil {
+                condition: until.clone(),
+                timeout_ms: *timeout_ms,
+            },
+            Self::ScrollTo { selector } => BrowserAction::ScrollTo {
+                selector: selector.clone(),
+            },
+            Self::ScrollBy { x, y } => BrowserAction::ScrollBy { x: *x, y: *y },
+            Self::Submit { selector } => BrowserAction::Submit {
+                selector: selector.clone(),
+            },
+            Self::Focus { selector } => BrowserAction::Focus {
+                selector: selector.clone(),
+            },
+            Self::LoopUntil { .. } => return None,
+        };
+        Some(action)
+    }
+
+    /// Number of steps, counting loop bodies
+    fn step_count(&self) -> usize {
+        match self {
+            Self::LoopUntil { steps, .. } => 1 + steps.len(),
+            _ => 1,
+        }
+    }
+
+    fn validate(&self, path: &str, in_loop: bool) -> RiptideResult<()> {
+        match self {
+            Self::Click { selector }
+            | Self::Type { selector, .. }
+            | Self::ScrollTo { selector }
+            | Self::Submit { selector }
+            | Self::Focus { selector } => validate_selector(path, selector),
+            Self::Wait { duration_ms } => validate_duration(path, "duration_ms", *duration_ms),
+            Self::WaitFor { until, timeout_ms } => {
+                until.validate(path)?;
+                validate_duration(path, "timeout_ms", *timeout_ms)
+            }
+            Self::ScrollBy { .. } => Ok(()),
+            Self::LoopUntil {
+                until,
+                steps,
+                max_iterations,
+                delay_ms,
+            } => {
+                if in_loop {
+                    return Err(invalid(path, "loops cannot be nested"));
+                }
+                if steps.is_empty() {
+                    return Err(invalid(path, "loop has no steps"));
+                }
+                if !(1..=MAX_LOOP_ITERATIONS).contains(max_iterations) {
+                    return Err(invalid(
+                        path,
+                        &format!("max_iterations must be 1-{}", MAX_LOOP_ITERATIONS),
+                    ));
+                }
+                until.validate(path)?;
+                validate_duration(path, "delay_ms", *delay_ms)?;
+                for (i, step) in steps.iter().enumerate() {
+                    step.validate(&format!("{}.steps[{}]", path, i), true)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A named, validated sequence of script steps
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ActionScript {
+    /// Script name, used in logs
+    #[serde(default)]
+    pub name: Option<String>,
+    pub steps: Vec<ScriptStep>,
+}
+
+impl ActionScript {
+    /// Parse and validate a script from YAML
+    pub fn from_yaml(yaml: &str) -> RiptideResult<Self> {
+        let script: Self = serde_yaml::from_str(yaml)
+            .map_err(|e| RiptideError::Validation(format!("Invalid action script: {}", e)))?;
+        script.validate()?;
+        Ok(script)
+    }
+
+    /// Check the script before running it
+    ///
+    /// Rejects empty scripts, invalid CSS selectors, nested loops and waits,
+    /// loops or scripts exceeding the limits in this module.
+    pub fn validate(&self) -> RiptideResult<()> {
+        if self.steps.is_empty() {
+            return Err(RiptideError::Validation(
+                "Action script has no steps".to_string(),
+            ));
+        }
+        let total: usize = self.steps.iter().map(ScriptStep::step_count).sum();
+        if total > MAX_SCRIPT_STEPS {
+            return Err(RiptideError::Validation(format!(
+                "Action script has {} steps, the limit is {}",
+                total, MAX_SCRIPT_STEPS
+            )));
+        }
+        for (i, step) in self.steps.iter().enumerate() {
+            step.validate(&format!("steps[{}]", i), false)?;
+        }
+        Ok(())
+    }
+}
+
+/// Quote `text` as a JavaScript string literal
+pub(crate) fn js_string(text: &str) -> String {
+    serde_json::Value::String(text.to_string()).to_string()
+}
+
+fn validate_selector(path: &str, selector: &str) -> RiptideResult<()> {
+    match selector_problem(selector) {
+        Some(problem) => Err(invalid(path, &problem)),
+        None => Ok(()),
+    }
+}
+
+/// Why `selector` is not a valid CSS selector, `None` when it is
+pub(crate) fn selector_problem(selector: &str) -> Option<String> {
+    scraper::Selector::parse(selector)
+        .is_err()
+        .then(|| format!("invalid CSS selector '{}'", selector))
+}
+
+fn validate_duration(path: &str, field: &str, ms: u64) -> RiptideResult<()> {
+    if ms > MAX_WAIT_MS {
+        return Err(invalid(
+            path,
+            &format!("{} must be at most {}", field, MAX_WAIT_MS),
+        ));
+    }
+    Ok(())
+}
+
+fn invalid(path: &str, message: &str) -> RiptideError {
+    RiptideError::Validation(format!("Action script {}: {}", path, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOGIN_SCRIPT: &str = r##"
+name: login
+steps:
+  - action: type
+    selector: "#email"
+    text: user@example.com
+  - action: click
+    selector: "button[type=submit]"
+  - action: wait_for
+    until: { element: ".dashboard" }
+  - action: loop_until
+    until: page_height_stable
+    max_iterations: 5
+    steps:
+      - action: scroll_by
+        y: 2000
+"##;
+
+    #[test]
+    fn test_parse_yaml_script() {
+        let script = ActionScript::from_yaml(LOGIN_SCRIPT).unwrap();
+        assert_eq!(script.name.as_deref(), Some("login"));
+        assert_eq!(script.steps.len(), 4);
+        assert_eq!(
+            script.steps[2].to_action(),
+            Some(BrowserAction::WaitUntil {
+                condition: WaitCondition::Element(".dashboard".to_string()),
+                timeout_ms: 10_000,
+            })
+        );
+        let ScriptStep::LoopUntil {
+            until,
+            steps,
+            delay_ms,
+            ..
+        } = &script.steps[3]
+        else {
+            panic!("Expected a loop step");
+        };
+        assert_eq!(until, &WaitCondition::PageHeightStable);
+        assert_eq!(*delay_ms, 500);
+        assert_eq!(
+            steps[0].to_action(),
+            Some(BrowserAction::ScrollBy { x: 0, y: 2000 })
+        );
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let cases = [
+            ("steps: []", "no steps"),
+            (
+                "steps:\n  - action: click\n    selector: \"##bad\"",
+                "invalid CSS selector",
+            ),
+            (
+                "steps:\n  - action: hover\n    selector: a",
+                "Invalid action script",
+            ),
+            (
+                "steps:\n  - action: wait\n    duration_ms: 999999999",
+                "duration_ms must be at most",
+            ),
+            (
+                "steps:\n  - action: loop_until\n    until: page_height_stable\n    steps:\n      - action: loop_until\n        until: page_height_stable\n        steps:\n          - action: scroll_by\n            y: 10",
+                "steps[0].steps[0]: loops cannot be nested",
+            ),
+            (
+                "steps:\n  - action: loop_until\n    until: { min_count: { selector: li, count: 0 } }\n    steps:\n      - action: scroll_by\n        y: 10",
+                "count of at least 1",
+            ),
+        ];
+        for (yaml, expected) in cases {
+            let err = ActionScript::from_yaml(yaml).unwrap_err().to_string();
+            assert!(err.contains(expected), "{}: {}", yaml, err);
+        }
+    }
+
+    #[test]
+    fn test_condition_expressions() {
+        assert_eq!(
+            WaitCondition::Element("a[href='x']".to_string()).expression(),
+            r#"document.querySelector("a[href='x']") !== null"#
+        );
+        assert_eq!(
+            WaitCondition::Script("window.done".to_string()).expression(),
+            "!!(window.done)"
+        );
+    }
+
+    #[test]
+    fn test_probe_page_height_stable() {
+        let mut probe = ConditionProbe::default();
+        let condition = WaitCondition::PageHeightStable;
+        assert!(!probe.observe(&condition, &serde_json::json!(1000)));
+        assert!(!probe.observe(&condition, &serde_json::json!(3000)));
+        assert!(probe.observe(&condition, &serde_json::json!(3000)));
+
+        let element = WaitCondition::Element("main".to_string());
+        assert!(probe.observe(&element, &serde_json::json!(true)));
+        assert!(!probe.observe(&element, &serde_json::Value::Null));
+    }
+}
@@ -0,0 +1,417 @@
+//! Named entity, keyword and topic enrichment for extracted documents
+//!
+//! [`AnnotationEnricher`] runs a [`TextAnnotator`] over a document's title
+//! and text and stores the result in [`ExtractedDoc::annotations`], where the
+//! Elasticsearch exporter indexes it for faceted search. The rule-based
+//! annotator from `riptide_extraction::nlp` needs no model; with the `llm`
+//! feature, [`LlmAnnotator`] asks an LLM provider instead and the rules
+//! serve as its fallback:
+//!
+//! ```rust,ignore
+//! use riptide_extraction::RuleBasedAnnotator;
+//! use riptide_facade::facades::{AnnotationConfig, AnnotationEnricher};
+//!
+//! let config = AnnotationConfig::from_env();
+//! let rules = Arc::new(RuleBasedAnnotator::new());
+//! let enricher = match config.create_annotator()? {
+//!     Some(llm) => AnnotationEnricher::new(llm).with_fallback(rules),
+//!     None => AnnotationEnricher::new(rules),
+//! }
+//! .with_max_chars(config.max_chars);
+//! enricher.enrich(&mut document).await?;
+//! ```
+
+use crate::error::RiptideResult;
+use riptide_types::ports::TextAnnotator;
+use riptide_types::ExtractedDoc;
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Annotation settings, from `RIPTIDE_ANNOTATION_*`
+#[derive(Clone, Serialize)]
+pub struct AnnotationConfig {
+    /// Annotate extracted documents at all
+    pub enabled: bool,
+    /// LLM provider type ("openai", "anthropic", "ollama", ...); `None`
+    /// annotates with the rule-based annotator only
+    pub provider: Option<String>,
+    pub model: String,
+    pub api_key: Option<String>,
+    /// Provider endpoint, for self-hosted and OpenAI-compatible providers
+    pub base_url: Option<String>,
+    /// Characters of text annotated per document; the rest is left out
+    pub max_chars: usize,
+}
+
+impl Default for AnnotationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: None,
+            model: "gpt-4o-mini".to_string(),
+            api_key: None,
+            base_url: None,
+            max_chars: 20_000,
+        }
+    }
+}
+
+impl std::fmt::Debug for AnnotationConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnnotationConfig")
+            .field("enabled", &self.enabled)
+            .field("provider", &self.provider)
+            .field("model", &self.model)
+            .field("api_key", &self.api_key.as_ref().map(|_| "***REDACTED***"))
+            .field("base_url", &self.base_url)
+            .field("max_chars", &self.max_chars)
+            .finish()
+    }
+}
+
+impl AnnotationConfig {
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let defaults = Self::default();
+        Self {
+            enabled: var("RIPTIDE_ANNOTATION_ENABLED")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.enabled),
+            provider: var("RIPTIDE_ANNOTATION_PROVIDER"),
+            model: var("RIPTIDE_ANNOTATION_MODEL").unwrap_or(defaults.model),
+            api_key: var("RIPTIDE_ANNOTATION_API_KEY"),
+            base_url: var("RIPTIDE_ANNOTATION_BASE_URL"),
+            max_chars: var("RIPTIDE_ANNOTATION_MAX_CHARS")
+                .and_then(|v| v.parse().ok())
+                .filter(|&n| n > 0)
+                .unwrap_or(defaults.max_chars),
+        }
+    }
+
+    /// Build the LLM annotator for the configured provider, `None` when
+    /// no provider is configured
+    #[cfg(feature = "llm")]
+    pub fn create_annotator(&self) -> RiptideResult<Option<Arc<dyn TextAnnotator>>> {
+        use riptide_intelligence::{create_provider_from_config, ProviderConfig};
+
+        let Some(provider_type) = &self.provider else {
+            return Ok(None);
+        };
+        let mut provider_config = ProviderConfig::new("annotation", provider_type.as_str());
+        if let Some(api_key) = &self.api_key {
+            provider_config = provider_config.with_config("api_key", api_key.as_str().into());
+        }
+        if let Some(base_url) = &self.base_url {
+            provider_config = provider_config.with_config("base_url", base_url.as_str().into());
+        }
+        let provider = create_provider_from_config(&provider_config)
+            .map_err(|e| crate::RiptideError::config(format!("Annotation provider: {}", e)))?;
+        Ok(Some(Arc::new(LlmAnnotator::new(
+            provider,
+            self.model.clone(),
+        ))))
+    }
+}
+
+/// Adds entities, keywords and topics to extracted documents
+#[derive(Clone)]
+pub struct AnnotationEnricher {
+    annotator: Arc<dyn TextAnnotator>,
+    fallback: Option<Arc<dyn TextAnnotator>>,
+    max_chars: usize,
+}
+
+impl AnnotationEnricher {
+    pub fn new(annotator: Arc<dyn TextAnnotator>) -> Self {
+        Self {
+            annotator,
+            fallback: None,
+            max_chars: AnnotationConfig::default().max_chars,
+        }
+    }
+
+    /// Annotate with `fallback` when the primary annotator fails
+    pub fn with_fallback(mut self, fallback: Arc<dyn TextAnnotator>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// Annotate at most `max_chars` characters per document
+    pub fn with_max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = max_chars.max(1);
+        self
+    }
+
+    /// Annotate `doc`
+    ///
+    /// Returns `false` without calling an annotator when the document has
+    /// no text or is already annotated.
+    pub async fn enrich(&self, doc: &mut ExtractedDoc) -> RiptideResult<bool> {
+        if doc.annotations.is_some() || doc.text.trim().is_empty() {
+            return Ok(false);
+        }
+
+        let text = match doc.text.char_indices().nth(self.max_chars) {
+            Some((end, _)) => &doc.text[..end],
+            None => doc.text.as_str(),
+        };
+        let title = doc.title.as_deref().filter(|t| !t.trim().is_empty());
+        let language = doc.language.as_deref();
+
+        let (mut annotations, annotator) =
+            match self.annotator.annotate(title, text, language).await {
+                Ok(annotations) => (annotations, &self.annotator),
+                Err(e) => {
+                    let Some(fallback) = &self.fallback else {
+                        return Err(e.into());
+                    };
+                    warn!(
+                        annotator = self.annotator.name(),
+                        fallback = fallback.name(),
+                        error = %e,
+                        "Annotation failed, using fallback"
+                    );
+                    (fallback.annotate(title, text, language).await?, fallback)
+                }
+            };
+        if annotations.annotator.is_empty() {
+            annotations.annotator = annotator.name().to_string();
+        }
+        doc.annotations = Some(annotations);
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "llm")]
+pub use llm_annotator::LlmAnnotator;
+
+#[cfg(feature = "llm")]
+mod llm_annotator {
+    use async_trait::async_trait;
+    use riptide_intelligence::{CompletionRequest, LlmProvider, Message};
+    use riptide_types::ports::TextAnnotator;
+    use riptide_types::{EntityKind, Keyword, NamedEntity, RiptideError, TextAnnotations};
+    use serde::Deserialize;
+    use std::sync::Arc;
+
+    const SYSTEM_PROMPT: &str = "You annotate web page text for search facets. \
+        Answer with a JSON object only: \
+        {\"entities\": [{\"text\": \"...\", \"type\": \"person|organization|location|date|money|other\"}], \
+        \"keywords\": [\"...\"], \"topics\": [\"...\"]}. \
+        List at most 25 entities by their full name, at most 15 lowercase keywords \
+        and key phrases, most relevant first, and at most 3 broad lowercase topics \
+        such as \"technology\" or \"sports\".";
+
+    /// Text annotator backed by an LLM provider
+    pub struct LlmAnnotator {
+        provider: Arc<dyn LlmProvider>,
+        model: String,
+        name: String,
+    }
+
+    impl LlmAnnotator {
+        /// Annotate with `model` of `provider`
+        pub fn new(provider: Arc<dyn LlmProvider>, model: impl Into<String>) -> Self {
+            let model = model.into();
+            Self {
+                provider,
+                name: format!("llm:{}", model),
+                model,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl TextAnnotator for LlmAnnotator {
+        async fn annotate(
+            &self,
+            title: Option<&str>,
+            text: &str,
+            _language: Option<&str>,
+        ) -> riptide_types::Result<TextAnnotations> {
+            let content = match title {
+                Some(title) => format!("{}\n\n{}", title, text),
+                None => text.to_string(),
+            };
+            let request = CompletionRequest::new(
+                self.model.clone(),
+                vec![Message::system(SYSTEM_PROMPT), Message::user(content)],
+            )
+            .with_max_tokens(1024)
+            .with_temperature(0.0);
+
+            let response = self
+                .provider
+                .complete(request)
+                .await
+                .map_err(|e| RiptideError::Custom(format!("LLM annotation failed: {}", e)))?;
+            let mut annotations = parse_response(&response.content, text)?;
+            annotations.annotator = self.name.clone();
+            Ok(annotations)
+        }
+
+        fn name(&self) -> &str {
+            &self.name
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct Response {
+        #[serde(default)]
+        entities: Vec<ResponseEntity>,
+        #[serde(default)]
+        keywords: Vec<String>,
+        #[serde(default)]
+        topics: Vec<String>,
+    }
+
+    #[derive(Deserialize)]
+    struct ResponseEntity {
+        text: String,
+        #[serde(rename = "type", default)]
+        kind: String,
+    }
+
+    /// Parse the model's JSON answer, counting entity mentions in `text`
+    /// and scoring keywords by rank
+    fn parse_response(content: &str, text: &str) -> riptide_types::Result<TextAnnotations> {
+        let json = content
+            .trim()
+            .trim_start_matches("```json")
+            .trim_start_matches("```")
+            .trim_end_matches("```")
+            .trim();
+        let response: Response = serde_json::from_str(json).map_err(|e| {
+            RiptideError::Custom(format!("LLM annotation returned invalid JSON: {}", e))
+        })?;
+
+        let mut entities: Vec<NamedEntity> = Vec::new();
+        for entity in response.entities {
+            let name = entity.text.trim();
+            if name.is_empty() || entities.iter().any(|e| e.text == name) {
+                continue;
+            }
+            entities.push(NamedEntity {
+                text: name.to_string(),
+                kind: EntityKind::from_label(&entity.kind),
+                mentions: text.matches(name).count().max(1) as u32,
+            });
+        }
+        entities.sort_by_key(|e| std::cmp::Reverse(e.mentions));
+
+        let terms: Vec<String> = response
+            .keywords
+            .iter()
+            .map(|k| k.trim().to_lowercase())
+            .filter(|k| !k.is_empty())
+            .collect();
+        let keywords = terms
+            .iter()
+            .enumerate()
+            .map(|(rank, term)| Keyword {
+                term: term.clone(),
+                score: ((1.0 - rank as f32 / terms.len() as f32) * 100.0).round() / 100.0,
+            })
+            .collect();
+
+        Ok(TextAnnotations {
+            entities,
+            keywords,
+            topics: response
+                .topics
+                .iter()
+                .map(|t| t.trim().to_lowercase())
+                .filter(|t| !t.is_empty())
+                .collect(),
+            annotator: String::new(),
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_parse_response_strips_fences_and_counts_mentions() {
+            let content = "```json\n{\"entities\": [{\"text\": \"Berlin\", \"type\": \"LOC\"}, \
+                           {\"text\": \"Anna Weber\", \"type\": \"person\"}], \
+                           \"keywords\": [\"Carbon Emissions\", \"forest fires\"], \
+                           \"topics\": [\"Environment\"]}\n```";
+            let text = "Anna Weber spoke in Berlin. Berlin was hot.";
+
+            let annotations = parse_response(content, text).unwrap();
+            assert_eq!(annotations.entities[0].text, "Berlin");
+            assert_eq!(annotations.entities[0].kind, EntityKind::Location);
+            assert_eq!(annotations.entities[0].mentions, 2);
+            assert_eq!(annotations.entities[1].kind, EntityKind::Person);
+            assert_eq!(annotations.keywords[0].term, "carbon emissions");
+            assert_eq!(annotations.keywords[0].score, 1.0);
+            assert_eq!(annotations.keywords[1].score, 0.5);
+            assert_eq!(annotations.topics, vec!["environment"]);
+
+            assert!(parse_response("not json", text).is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_extraction::RuleBasedAnnotator;
+    use riptide_types::ports::InMemoryTextAnnotator;
+    use riptide_types::{EntityKind, TextAnnotations};
+
+    fn doc(text: &str) -> ExtractedDoc {
+        ExtractedDoc {
+            url: "https://example.com/".to_string(),
+            title: Some("Climate report".to_string()),
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_enrich_annotates_once() {
+        let annotator = Arc::new(InMemoryTextAnnotator::new(TextAnnotations {
+            topics: vec!["environment".to_string()],
+            ..Default::default()
+        }));
+        let enricher = AnnotationEnricher::new(annotator.clone());
+
+        let mut document = doc("Emissions rose again.");
+        assert!(enricher.enrich(&mut document).await.unwrap());
+        assert!(!enricher.enrich(&mut document).await.unwrap());
+        let mut empty = doc("  ");
+        assert!(!enricher.enrich(&mut empty).await.unwrap());
+
+        let annotations = document.annotations.unwrap();
+        assert_eq!(annotations.topics, vec!["environment"]);
+        assert_eq!(annotations.annotator, "memory");
+        assert_eq!(annotator.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_enrich_falls_back_to_rules() {
+        let failing = Arc::new(InMemoryTextAnnotator::failing());
+        let text = "Anna Weber said the report was published in Berlin.";
+
+        let mut document = doc(text);
+        assert!(AnnotationEnricher::new(failing.clone())
+            .enrich(&mut document)
+            .await
+            .is_err());
+        assert!(document.annotations.is_none());
+
+        let enricher = AnnotationEnricher::new(failing.clone())
+            .with_fallback(Arc::new(RuleBasedAnnotator::new()));
+        assert!(enricher.enrich(&mut document).await.unwrap());
+
+        let annotations = document.annotations.unwrap();
+        assert_eq!(annotations.annotator, "rules");
+        assert!(annotations
+            .entities_of(EntityKind::Person)
+            .any(|name| name == "Anna Weber"));
+        assert_eq!(failing.calls(), 2);
+    }
+}
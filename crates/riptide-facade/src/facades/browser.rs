@@ -10,18 +10,28 @@
 //! - Cookie and storage management
 //! - Stealth features for anti-detection via unified HeadlessLauncher
 
+use crate::facades::action_script::{
+    js_string, ActionScript, ConditionProbe, ScriptStep, WaitCondition,
+};
+use crate::facades::login_flow::{
+    LoginCredentials, LoginFlow, LoginStep, TwoFactorChallenge, TwoFactorPrompt, REDACTED_VALUE,
+};
+use crate::facades::session_recorder::{
+    Recording, SessionRecorder, CONSOLE_DRAIN_SCRIPT, CONSOLE_HOOK_SCRIPT,
+};
 use crate::workflows::backpressure::BackpressureManager;
 use crate::{config::RiptideConfig, error::RiptideResult, RiptideError};
 use riptide_browser::launcher::{HeadlessLauncher, LaunchSession, LauncherConfig};
 use riptide_extraction::native_parser::{NativeHtmlParser, ParserConfig};
-use riptide_fetch::ReliableHttpClient;
+use riptide_fetch::{CookieJar, ReliableHttpClient, StoredCookie};
 use riptide_stealth::StealthPreset;
+use riptide_types::ports::AuthSession;
 use riptide_utils::circuit_breaker::{CircuitBreaker, Config as CircuitConfig, RealClock};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio_util::sync::CancellationToken;
-use tracing::warn;
+use tracing::{debug, warn};
 use url::Url;
 
 /// Browser facade providing simplified headless browser automation.
@@ -61,6 +71,7 @@ pub struct BrowserFacade {
     native_parser: Arc<NativeHtmlParser>,
     http_client: Arc<ReliableHttpClient>,
     backpressure: BackpressureManager,
+    recorder: Option<Arc<SessionRecorder>>,
 }
 
 /// A managed browser session with automatic resource cleanup.
@@ -71,6 +82,19 @@ pub struct BrowserFacade {
 pub struct BrowserSession<'a> {
     session: LaunchSession<'a>,
     _guard: crate::workflows::backpressure::BackpressureGuard,
+    recording: Option<Arc<Recording>>,
+}
+
+impl BrowserSession<'_> {
+    /// Browser session id, also the key of its recording
+    pub fn session_id(&self) -> &str {
+        &self.session.session_id
+    }
+
+    /// Recording in progress, when the facade has a session recorder
+    pub fn recording(&self) -> Option<&Arc<Recording>> {
+        self.recording.as_ref()
+    }
 }
 
 /// Options for taking screenshots.
@@ -163,7 +187,7 @@ impl ScreenshotOptions {
 ///     BrowserAction::Wait { duration_ms: 1000 },
 /// ];
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum BrowserAction {
     /// Click an element by CSS selector
     Click { selector: String },
@@ -177,6 +201,12 @@ pub enum BrowserAction {
     /// Wait for an element to appear
     WaitForElement { selector: String, timeout_ms: u64 },
 
+    /// Wait until a page condition holds
+    WaitUntil {
+        condition: WaitCondition,
+        timeout_ms: u64,
+    },
+
     /// Scroll to an element
     ScrollTo { selector: String },
 
@@ -203,6 +233,48 @@ pub struct Cookie {
     pub same_site: Option<String>,
 }
 
+impl From<&StoredCookie> for Cookie {
+    fn from(cookie: &StoredCookie) -> Self {
+        Self {
+            name: cookie.name.clone(),
+            value: cookie.value.clone(),
+            // A leading dot marks a domain cookie in CDP
+            domain: Some(if cookie.host_only {
+                cookie.domain.clone()
+            } else {
+                format!(".{}", cookie.domain)
+            }),
+            path: Some(cookie.path.clone()),
+            expires: cookie.expires.map(|expires| expires.timestamp()),
+            http_only: Some(cookie.http_only),
+            secure: Some(cookie.secure),
+            same_site: cookie.same_site.map(|s| format!("{:?}", s)),
+        }
+    }
+}
+
+impl Cookie {
+    /// Convert to a cookie jar entry; `None` without a domain
+    pub fn to_stored(&self) -> Option<StoredCookie> {
+        let domain = self.domain.as_deref()?;
+        Some(StoredCookie {
+            name: self.name.clone(),
+            value: self.value.clone(),
+            domain: domain.trim_start_matches('.').to_ascii_lowercase(),
+            path: self.path.clone().unwrap_or_else(|| "/".to_string()),
+            host_only: !domain.starts_with('.'),
+            secure: self.secure.unwrap_or(false),
+            http_only: self.http_only.unwrap_or(false),
+            same_site: self.same_site.as_deref().and_then(|s| s.parse().ok()),
+            // CDP reports session cookies with a negative expiry
+            expires: self
+                .expires
+                .filter(|&expires| expires > 0)
+                .and_then(|expires| chrono::DateTime::from_timestamp(expires, 0)),
+        })
+    }
+}
+
 impl BrowserFacade {
     /// Create a new browser facade with the given configuration.
     ///
@@ -257,10 +329,15 @@ impl BrowserFacade {
         // Initialize native parser for fallback
         let native_parser = NativeHtmlParser::with_config(ParserConfig {
             enable_markdown: false,
+            markdown_flavor: Default::default(),
+            enable_clean_html: false,
+            image_proxy: None,
+            boilerplate: None,
             extract_links: false,
             extract_media: false,
             detect_language: false,
             extract_categories: false,
+            extract_structured_data: true,
             max_content_length: 10_000_000,
             parse_timeout_ms: 5000,
             min_quality_score: 30,
@@ -282,9 +359,20 @@ impl BrowserFacade {
             native_parser: Arc::new(native_parser),
             http_client: Arc::new(http_client),
             backpressure,
+            recorder: None,
         })
     }
 
+    /// Record every session launched from this facade.
+    ///
+    /// Navigations, actions, console errors and periodic screenshots are
+    /// captured and stored as a replay bundle when the session is closed.
+    /// Load it with [`SessionRecorder::load`] using the session id.
+    pub fn with_recorder(mut self, recorder: Arc<SessionRecorder>) -> Self {
+        self.recorder = Some(recorder);
+        self
+    }
+
     /// Launch a new browser session.
     ///
     /// This retrieves a browser instance from the pool (or creates a new one)
@@ -337,9 +425,24 @@ impl BrowserFacade {
             .await
             .map_err(|e| RiptideError::config(format!("Failed to launch browser: {}", e)))?;
 
+        let recording = match &self.recorder {
+            Some(recorder) => {
+                if let Err(e) = session
+                    .page
+                    .evaluate_on_new_document(CONSOLE_HOOK_SCRIPT)
+                    .await
+                {
+                    warn!(error = %e, "Failed to install console error hook for recording");
+                }
+                Some(recorder.start(session.session_id.clone()))
+            }
+            None => None,
+        };
+
         Ok(BrowserSession {
             session,
             _guard: guard,
+            recording,
         })
     }
 
@@ -369,14 +472,55 @@ impl BrowserFacade {
         // Validate URL
         let _ = Url::parse(url)?;
 
-        session
+        let result = session
             .session
             .page
             .goto(url)
             .await
-            .map_err(|e| RiptideError::Fetch(format!("Navigation failed: {}", e)))?;
+            .map(|_| ())
+            .map_err(|e| RiptideError::Fetch(format!("Navigation failed: {}", e)));
 
-        Ok(())
+        if let Some(recording) = &session.recording {
+            recording.record_navigation(url, result.as_ref().err().map(ToString::to_string));
+            self.capture_recording(session, recording, result.is_err())
+                .await;
+        }
+
+        result
+    }
+
+    /// Collect console errors and a screenshot (when due) into a recording
+    ///
+    /// Failed steps always get a screenshot. Capture problems are logged and
+    /// never fail the step being recorded.
+    async fn capture_recording(
+        &self,
+        session: &BrowserSession<'_>,
+        recording: &Recording,
+        failed: bool,
+    ) {
+        match self.execute_script(session, CONSOLE_DRAIN_SCRIPT).await {
+            Ok(serde_json::Value::Array(errors)) => {
+                recording.record_console_errors(errors.into_iter().map(|e| {
+                    e.as_str()
+                        .map(str::to_string)
+                        .unwrap_or_else(|| e.to_string())
+                }))
+            }
+            Ok(_) => {}
+            Err(e) => warn!(error = %e, "Failed to collect console errors for recording"),
+        }
+
+        if failed || recording.screenshot_due() {
+            let screenshot = self.screenshot(session, ScreenshotOptions::default()).await;
+            let stored = match screenshot {
+                Ok(png) => recording.record_screenshot(&png).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = stored {
+                warn!(error = %e, "Failed to capture recording screenshot");
+            }
+        }
     }
 
     /// Take a screenshot of the current page.
@@ -683,73 +827,389 @@ impl BrowserFacade {
         &self,
         session: &BrowserSession<'_>,
         actions: &[BrowserAction],
+    ) -> RiptideResult<()> {
+        for action in actions {
+            self.perform_recorded(session, action, action).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Perform `action`, recording `recorded` in its place
+    async fn perform_recorded(
+        &self,
+        session: &BrowserSession<'_>,
+        action: &BrowserAction,
+        recorded: &BrowserAction,
+    ) -> RiptideResult<()> {
+        let result = self.perform_action(session, action).await;
+        if let Some(recording) = &session.recording {
+            recording.record_action(recorded, result.as_ref().err().map(ToString::to_string));
+            self.capture_recording(session, recording, result.is_err())
+                .await;
+        }
+        result
+    }
+
+    async fn perform_action(
+        &self,
+        session: &BrowserSession<'_>,
+        action: &BrowserAction,
     ) -> RiptideResult<()> {
         let page = &session.session.page;
 
-        for action in actions {
-            match action {
-                BrowserAction::Click { selector } => {
-                    page.find_element(selector)
-                        .await
-                        .map_err(|e| RiptideError::Fetch(format!("Element not found: {}", e)))?
-                        .click()
-                        .await
-                        .map_err(|e| RiptideError::Fetch(format!("Click failed: {}", e)))?;
+        match action {
+            BrowserAction::Click { selector } => {
+                page.find_element(selector)
+                    .await
+                    .map_err(|e| RiptideError::Fetch(format!("Element not found: {}", e)))?
+                    .click()
+                    .await
+                    .map_err(|e| RiptideError::Fetch(format!("Click failed: {}", e)))?;
+            }
+            BrowserAction::Type { selector, text } => {
+                page.find_element(selector)
+                    .await
+                    .map_err(|e| RiptideError::Fetch(format!("Element not found: {}", e)))?
+                    .type_str(text)
+                    .await
+                    .map_err(|e| RiptideError::Fetch(format!("Type failed: {}", e)))?;
+            }
+            BrowserAction::Wait { duration_ms } => {
+                tokio::time::sleep(tokio::time::Duration::from_millis(*duration_ms)).await;
+            }
+            BrowserAction::WaitForElement {
+                selector,
+                timeout_ms,
+            } => {
+                let timeout = tokio::time::Duration::from_millis(*timeout_ms);
+                tokio::time::timeout(timeout, async {
+                    loop {
+                        if page.find_element(selector).await.is_ok() {
+                            break;
+                        }
+                        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                    }
+                })
+                .await
+                .map_err(|_| RiptideError::Timeout)?;
+            }
+            BrowserAction::WaitUntil {
+                condition,
+                timeout_ms,
+            } => {
+                let timeout = tokio::time::Duration::from_millis(*timeout_ms);
+                tokio::time::timeout(timeout, async {
+                    let mut probe = ConditionProbe::default();
+                    while !self.condition_met(session, condition, &mut probe).await? {
+                        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+                    }
+                    Ok::<(), RiptideError>(())
+                })
+                .await
+                .map_err(|_| RiptideError::Timeout)??;
+            }
+            BrowserAction::ScrollTo { selector } => {
+                let script = format!(
+                    "document.querySelector({}).scrollIntoView({{behavior: 'smooth'}})",
+                    js_string(selector)
+                );
+                self.execute_script(session, &script).await?;
+            }
+            BrowserAction::ScrollBy { x, y } => {
+                let script = format!("window.scrollBy({}, {})", x, y);
+                self.execute_script(session, &script).await?;
+            }
+            BrowserAction::Submit { selector } => {
+                let script = format!("document.querySelector({}).submit()", js_string(selector));
+                self.execute_script(session, &script).await?;
+            }
+            BrowserAction::Focus { selector } => {
+                page.find_element(selector)
+                    .await
+                    .map_err(|e| RiptideError::Fetch(format!("Element not found: {}", e)))?
+                    .focus()
+                    .await
+                    .map_err(|e| RiptideError::Fetch(format!("Focus failed: {}", e)))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run a declarative action script.
+    ///
+    /// The script is validated before the first step runs. Steps are
+    /// performed like [`perform_actions`](Self::perform_actions), so they are
+    /// recorded when the facade has a session recorder.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error for invalid scripts, or the error of the
+    /// first failing step.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use riptide_facade::{ActionScript, BrowserFacade, RiptideConfig};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let facade = BrowserFacade::new(RiptideConfig::default()).await?;
+    /// # let session = facade.launch().await?;
+    /// let script = ActionScript::from_yaml(&std::fs::read_to_string("feed.yaml")?)?;
+    /// facade.navigate(&session, "https://example.com/feed").await?;
+    /// facade.run_script(&session, &script).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn run_script(
+        &self,
+        session: &BrowserSession<'_>,
+        script: &ActionScript,
+    ) -> RiptideResult<()> {
+        script.validate()?;
+
+        for step in &script.steps {
+            match step {
+                ScriptStep::LoopUntil {
+                    until,
+                    steps,
+                    max_iterations,
+                    delay_ms,
+                } => {
+                    let actions: Vec<BrowserAction> =
+                        steps.iter().filter_map(ScriptStep::to_action).collect();
+                    self.loop_until(session, until, &actions, *max_iterations, *delay_ms)
+                        .await?;
                 }
-                BrowserAction::Type { selector, text } => {
-                    page.find_element(selector)
-                        .await
-                        .map_err(|e| RiptideError::Fetch(format!("Element not found: {}", e)))?
-                        .type_str(text)
-                        .await
-                        .map_err(|e| RiptideError::Fetch(format!("Type failed: {}", e)))?;
+                step => {
+                    if let Some(action) = step.to_action() {
+                        self.perform_actions(session, &[action]).await?;
+                    }
                 }
-                BrowserAction::Wait { duration_ms } => {
-                    tokio::time::sleep(tokio::time::Duration::from_millis(*duration_ms)).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn loop_until(
+        &self,
+        session: &BrowserSession<'_>,
+        until: &WaitCondition,
+        actions: &[BrowserAction],
+        max_iterations: u32,
+        delay_ms: u64,
+    ) -> RiptideResult<()> {
+        let mut probe = ConditionProbe::default();
+        for iteration in 0..max_iterations {
+            if self.condition_met(session, until, &mut probe).await? {
+                debug!(iteration, "Loop condition met");
+                return Ok(());
+            }
+            self.perform_actions(session, actions).await?;
+            tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
+        }
+
+        debug!(max_iterations, "Loop stopped at max iterations");
+        Ok(())
+    }
+
+    async fn condition_met(
+        &self,
+        session: &BrowserSession<'_>,
+        condition: &WaitCondition,
+        probe: &mut ConditionProbe,
+    ) -> RiptideResult<bool> {
+        let value = self
+            .execute_script(session, &condition.expression())
+            .await?;
+        Ok(probe.observe(condition, &value))
+    }
+
+    /// Log in with a login flow and return the resulting session.
+    ///
+    /// The flow runs in a new browser session: it opens the login URL,
+    /// performs the steps and waits for the success condition, then the
+    /// session's cookies are returned as an [`AuthSession`] expiring on
+    /// redirects to the login URL. Values typed for credential placeholders
+    /// and two-factor codes are recorded as [`REDACTED_VALUE`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error for invalid flows, missing credentials and
+    /// two-factor prompts without a `two_factor` callback, a timeout when the
+    /// flow exceeds its `timeout_ms`, or the error of the first failing step.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use riptide_facade::{BrowserFacade, LoginCredentials, LoginFlow, RiptideConfig};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let facade = BrowserFacade::new(RiptideConfig::default()).await?;
+    /// let flow = LoginFlow::from_yaml(&std::fs::read_to_string("shop-login.yaml")?)?;
+    /// let credentials = LoginCredentials::new()
+    ///     .with("username", "ada@example.com")
+    ///     .with("password", std::env::var("SHOP_PASSWORD")?);
+    /// let session = facade.run_login_flow(&flow, &credentials, None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn run_login_flow(
+        &self,
+        flow: &LoginFlow,
+        credentials: &LoginCredentials,
+        two_factor: Option<&dyn TwoFactorPrompt>,
+    ) -> RiptideResult<AuthSession> {
+        flow.validate()?;
+        credentials.check(flow)?;
+
+        let session = self.launch().await?;
+        let timeout = Duration::from_millis(flow.timeout_ms);
+        let result = match tokio::time::timeout(
+            timeout,
+            self.perform_login(&session, flow, credentials, two_factor),
+        )
+        .await
+        {
+            Ok(Ok(())) => self.get_cookies(&session).await,
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(RiptideError::Timeout),
+        };
+        self.close(session).await?;
+
+        let cookies = result?;
+        debug!(flow = %flow.name, cookies = cookies.len(), "Login flow completed");
+        Ok(flow.auth_session(cookies.into_iter().map(|c| (c.name, c.value))))
+    }
+
+    async fn perform_login(
+        &self,
+        session: &BrowserSession<'_>,
+        flow: &LoginFlow,
+        credentials: &LoginCredentials,
+        two_factor: Option<&dyn TwoFactorPrompt>,
+    ) -> RiptideResult<()> {
+        self.navigate(session, &flow.login_url).await?;
+
+        for step in &flow.steps {
+            match step {
+                LoginStep::Navigate { url } => self.navigate(session, url).await?,
+                LoginStep::Fill { selector, value } => {
+                    let text = credentials.resolve(value)?;
+                    let recorded = if text == *value {
+                        text.clone()
+                    } else {
+                        REDACTED_VALUE.to_string()
+                    };
+                    self.type_redacted(session, selector, text, recorded)
+                        .await?;
                 }
-                BrowserAction::WaitForElement {
+                LoginStep::TwoFactor {
                     selector,
+                    submit,
+                    prompt,
                     timeout_ms,
                 } => {
-                    let timeout = tokio::time::Duration::from_millis(*timeout_ms);
-                    tokio::time::timeout(timeout, async {
-                        loop {
-                            if page.find_element(selector).await.is_ok() {
-                                break;
-                            }
-                            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                        }
-                    })
-                    .await
-                    .map_err(|_| RiptideError::Timeout)?;
+                    if !self
+                        .two_factor_requested(session, selector, &flow.success, *timeout_ms)
+                        .await?
+                    {
+                        debug!(flow = %flow.name, "No two-factor code requested");
+                        continue;
+                    }
+                    let two_factor = two_factor.ok_or_else(|| {
+                        RiptideError::Validation(format!(
+                            "Login flow '{}' asks for a two-factor code but no prompt is configured",
+                            flow.name
+                        ))
+                    })?;
+                    let url = self
+                        .execute_script(session, "window.location.href")
+                        .await?
+                        .as_str()
+                        .unwrap_or(&flow.login_url)
+                        .to_string();
+                    let code = two_factor
+                        .code(&TwoFactorChallenge {
+                            flow: flow.name.clone(),
+                            url,
+                            prompt: prompt.clone(),
+                        })
+                        .await?;
+                    self.type_redacted(session, selector, code, REDACTED_VALUE.to_string())
+                        .await?;
+                    if let Some(submit) = submit {
+                        self.perform_actions(
+                            session,
+                            &[BrowserAction::Click {
+                                selector: submit.clone(),
+                            }],
+                        )
+                        .await?;
+                    }
                 }
-                BrowserAction::ScrollTo { selector } => {
-                    let script = format!(
-                        "document.querySelector('{}').scrollIntoView({{behavior: 'smooth'}})",
-                        selector
-                    );
-                    self.execute_script(session, &script).await?;
-                }
-                BrowserAction::ScrollBy { x, y } => {
-                    let script = format!("window.scrollBy({}, {})", x, y);
-                    self.execute_script(session, &script).await?;
-                }
-                BrowserAction::Submit { selector } => {
-                    let script = format!("document.querySelector('{}').submit()", selector);
-                    self.execute_script(session, &script).await?;
-                }
-                BrowserAction::Focus { selector } => {
-                    page.find_element(selector)
-                        .await
-                        .map_err(|e| RiptideError::Fetch(format!("Element not found: {}", e)))?
-                        .focus()
-                        .await
-                        .map_err(|e| RiptideError::Fetch(format!("Focus failed: {}", e)))?;
+                step => {
+                    if let Some(action) = step.to_action() {
+                        self.perform_actions(session, &[action]).await?;
+                    }
                 }
             }
         }
 
-        Ok(())
+        // The flow's own timeout bounds the wait
+        self.perform_actions(
+            session,
+            &[BrowserAction::WaitUntil {
+                condition: flow.success.clone(),
+                timeout_ms: flow.timeout_ms,
+            }],
+        )
+        .await
+    }
+
+    /// Type `text` into `selector`, recording `recorded` instead
+    async fn type_redacted(
+        &self,
+        session: &BrowserSession<'_>,
+        selector: &str,
+        text: String,
+        recorded: String,
+    ) -> RiptideResult<()> {
+        let action = BrowserAction::Type {
+            selector: selector.to_string(),
+            text,
+        };
+        let recorded = BrowserAction::Type {
+            selector: selector.to_string(),
+            text: recorded,
+        };
+        self.perform_recorded(session, &action, &recorded).await
+    }
+
+    /// Wait for a two-factor field, returning `false` when the login
+    /// succeeds without one
+    async fn two_factor_requested(
+        &self,
+        session: &BrowserSession<'_>,
+        selector: &str,
+        success: &WaitCondition,
+        timeout_ms: u64,
+    ) -> RiptideResult<bool> {
+        let code_field = WaitCondition::Element(selector.to_string());
+        tokio::time::timeout(Duration::from_millis(timeout_ms), async {
+            let mut probe = ConditionProbe::default();
+            loop {
+                if self.condition_met(session, success, &mut probe).await? {
+                    return Ok(false);
+                }
+                if self.condition_met(session, &code_field, &mut probe).await? {
+                    return Ok(true);
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .map_err(|_| RiptideError::Timeout)?
     }
 
     /// Get all cookies for the current page.
@@ -803,7 +1263,9 @@ impl BrowserFacade {
         session: &BrowserSession<'_>,
         cookies: &[Cookie],
     ) -> RiptideResult<()> {
-        use chromiumoxide_cdp::cdp::browser_protocol::network::{CookieParam, SetCookiesParams};
+        use chromiumoxide_cdp::cdp::browser_protocol::network::{
+            CookieParam, CookieSameSite, SetCookiesParams, TimeSinceEpoch,
+        };
 
         let page = &session.session.page;
 
@@ -817,6 +1279,18 @@ impl BrowserFacade {
                 if let Some(path) = &c.path {
                     param.path = Some(path.clone());
                 }
+                param.secure = c.secure;
+                param.http_only = c.http_only;
+                param.same_site = match c.same_site.as_deref() {
+                    Some("Strict") => Some(CookieSameSite::Strict),
+                    Some("Lax") => Some(CookieSameSite::Lax),
+                    Some("None") => Some(CookieSameSite::None),
+                    _ => None,
+                };
+                param.expires = c
+                    .expires
+                    .filter(|&expires| expires > 0)
+                    .map(|expires| TimeSinceEpoch::new(expires as f64));
                 param
             })
             .collect();
@@ -828,6 +1302,52 @@ impl BrowserFacade {
         Ok(())
     }
 
+    /// Load every cookie of `jar` into the browser session.
+    ///
+    /// Lets a browser continue a login made by plain fetches sharing the
+    /// same jar, e.g. `PerHostFetchEngine::session_cookie_jar`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if cookie setting fails.
+    pub async fn load_cookie_jar(
+        &self,
+        session: &BrowserSession<'_>,
+        jar: &CookieJar,
+    ) -> RiptideResult<()> {
+        let cookies: Vec<Cookie> = jar.snapshot().iter().map(Cookie::from).collect();
+        if cookies.is_empty() {
+            return Ok(());
+        }
+        self.set_cookies(session, &cookies).await
+    }
+
+    /// Copy the cookies of the current page into `jar`.
+    ///
+    /// Fetches sharing the jar then see cookies set by the browser, such as
+    /// a session cookie obtained by logging in through a form.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of cookies copied.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if cookie retrieval fails.
+    pub async fn sync_cookie_jar(
+        &self,
+        session: &BrowserSession<'_>,
+        jar: &CookieJar,
+    ) -> RiptideResult<usize> {
+        let cookies = self.get_cookies(session).await?;
+        let mut synced = 0;
+        for cookie in cookies.iter().filter_map(Cookie::to_stored) {
+            jar.insert(cookie);
+            synced += 1;
+        }
+        Ok(synced)
+    }
+
     /// Get local storage data.
     ///
     /// # Arguments
@@ -1163,6 +1683,16 @@ impl BrowserFacade {
     /// Returns an error if closing fails (though the browser will still be
     /// returned to the pool via Drop).
     pub async fn close(&self, session: BrowserSession<'_>) -> RiptideResult<()> {
+        if let Some(recording) = &session.recording {
+            if let Err(e) = recording.finish().await {
+                warn!(
+                    session_id = %session.session_id(),
+                    error = %e,
+                    "Failed to store session recording"
+                );
+            }
+        }
+
         // The LaunchSession will automatically return the browser to the pool
         // when dropped, so we just need to drop it here
         drop(session);
@@ -1737,4 +2267,37 @@ mod tests {
         assert!(clear_script.contains("expires"));
         assert!(clear_script.contains("forEach"));
     }
+
+    #[test]
+    fn test_cookie_jar_conversion_round_trip() {
+        let jar = CookieJar::new();
+        let url: Url = "https://shop.example.com/".parse().unwrap();
+        jar.set_cookie(
+            "sid=abc; Domain=example.com; Secure; HttpOnly; SameSite=Strict; Max-Age=600",
+            &url,
+        );
+        let stored = jar.snapshot().remove(0);
+
+        let cookie = Cookie::from(&stored);
+        assert_eq!(cookie.domain.as_deref(), Some(".example.com"));
+        assert_eq!(cookie.same_site.as_deref(), Some("Strict"));
+        assert_eq!(
+            cookie.to_stored().unwrap(),
+            StoredCookie {
+                expires: stored
+                    .expires
+                    .map(|e| chrono::DateTime::from_timestamp(e.timestamp(), 0).unwrap()),
+                ..stored.clone()
+            }
+        );
+
+        let session_cookie = Cookie {
+            expires: Some(-1),
+            domain: Some("example.com".to_string()),
+            ..cookie
+        };
+        let converted = session_cookie.to_stored().unwrap();
+        assert!(converted.host_only);
+        assert!(converted.expires.is_none());
+    }
 }
@@ -2,13 +2,23 @@
 
 use crate::error::RiptideResult;
 use crate::RiptideError;
-use riptide_extraction::chunking::{create_strategy, ChunkingConfig, ChunkingMode};
+use riptide_extraction::chunking::{
+    create_strategy_with_embedder, semantic::SentenceEmbedder, ChunkingConfig, ChunkingMode,
+};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Instant;
 use tracing::info;
 
+/// Chunks content with the strategy named by `chunking_mode`
+///
+/// The `semantic` mode splits on topic shifts between sentence embeddings
+/// and needs an embedder ([`ChunkingFacade::with_embedder`]); without one
+/// it is rejected as invalid rather than silently chunked by topic.
 #[derive(Clone)]
-pub struct ChunkingFacade;
+pub struct ChunkingFacade {
+    embedder: Option<Arc<dyn SentenceEmbedder>>,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChunkRequest {
@@ -29,6 +39,10 @@ pub struct ChunkParameters {
     #[serde(default = "default_preserve_sentences")]
     pub preserve_sentences: bool,
     pub window_size: Option<usize>,
+    /// Semantic mode: split below this sentence-window similarity (default 0.5)
+    pub similarity_threshold: Option<f32>,
+    /// Semantic mode: sentences repeated from the previous chunk (default 1)
+    pub overlap_sentences: Option<usize>,
 }
 
 fn default_chunk_size() -> usize {
@@ -57,11 +71,19 @@ pub struct ChunkData {
     pub index: usize,
     pub content: String,
     pub length: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic_label: Option<String>,
 }
 
 impl ChunkingFacade {
     pub fn new() -> Self {
-        Self
+        Self { embedder: None }
+    }
+
+    /// Embed sentences with `embedder` in `semantic` mode
+    pub fn with_embedder(mut self, embedder: Arc<dyn SentenceEmbedder>) -> Self {
+        self.embedder = Some(embedder);
+        self
     }
 
     pub async fn chunk_content(&self, request: ChunkRequest) -> RiptideResult<ChunkResponse> {
@@ -93,7 +115,8 @@ impl ChunkingFacade {
 
         let chunking_mode =
             self.validate_and_create_chunking_mode(&request.chunking_mode, &params)?;
-        let strategy = create_strategy(chunking_mode, html_config);
+        let strategy =
+            create_strategy_with_embedder(chunking_mode, html_config, self.embedder.clone());
         let chunks = strategy
             .chunk(&request.content)
             .await
@@ -103,8 +126,9 @@ impl ChunkingFacade {
             .into_iter()
             .map(|chunk| ChunkData {
                 index: chunk.chunk_index,
-                content: chunk.content.clone(),
                 length: chunk.content.len(),
+                content: chunk.content,
+                topic_label: chunk.metadata.topic_label,
             })
             .collect();
 
@@ -135,7 +159,9 @@ impl ChunkingFacade {
             "fixed" => Ok(ChunkingMode::Fixed { size: params.chunk_size, by_tokens: true }),
             "sentence" => Ok(ChunkingMode::Sentence { max_sentences: params.chunk_size / 20 }),
             "html-aware" => Ok(ChunkingMode::HtmlAware { preserve_blocks: true, preserve_structure: true }),
-            invalid => Err(RiptideError::Validation(format!("Invalid chunking_mode '{}'. Supported: topic, sliding, fixed, sentence, html-aware", invalid))),
+            "semantic" if self.embedder.is_none() => Err(RiptideError::Validation("chunking_mode 'semantic' needs a sentence embedder, and none is configured; use 'topic' instead".to_string())),
+            "semantic" => Ok(ChunkingMode::Semantic { similarity_threshold: params.similarity_threshold.unwrap_or(0.5), overlap_sentences: params.overlap_sentences.unwrap_or(1), window_size: params.window_size.unwrap_or(2) }),
+            invalid => Err(RiptideError::Validation(format!("Invalid chunking_mode '{}'. Supported: topic, sliding, fixed, sentence, html-aware, semantic", invalid))),
         }
     }
 }
@@ -145,3 +171,29 @@ impl Default for ChunkingFacade {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_semantic_mode_requires_an_embedder() {
+        let request = ChunkRequest {
+            content: "One sentence. Another sentence.".to_string(),
+            chunking_mode: "semantic".to_string(),
+            parameters: Some(ChunkParameters {
+                chunk_size: 1000,
+                overlap_size: 200,
+                min_chunk_size: 100,
+                preserve_sentences: true,
+                ..Default::default()
+            }),
+        };
+        let result = ChunkingFacade::new().chunk_content(request).await;
+        assert!(
+            matches!(&result, Err(RiptideError::Validation(msg)) if msg.contains("semantic")),
+            "{:?}",
+            result.err()
+        );
+    }
+}
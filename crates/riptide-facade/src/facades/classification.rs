@@ -0,0 +1,879 @@
+//! Content-category and sentiment classification for extracted documents
+//!
+//! [`ClassificationEnricher`] runs a [`DocumentClassifier`] over a document's
+//! title and text, stores the categories and sentiment with their
+//! confidences in [`ExtractedDoc::classification`] and replaces the
+//! meta-keyword `categories` of the native parser with the classified
+//! labels. Categories come from a [`Taxonomy`], the built-in one or a JSON
+//! file. Two classifiers are available:
+//!
+//! - [`CentroidClassifier`] embeds each category's label, description and
+//!   examples once and picks the categories whose centroid is nearest to the
+//!   document embedding; any [`TextEmbedder`] works.
+//! - [`LlmClassifier`] (`llm` feature) asks an LLM provider to choose from
+//!   the taxonomy.
+//!
+//! ```rust,ignore
+//! use riptide_facade::facades::{ClassificationConfig, ClassificationEnricher};
+//!
+//! let config = ClassificationConfig::from_env();
+//! if let Some(classifier) = config.create_classifier()? {
+//!     let enricher = ClassificationEnricher::new(classifier).with_max_chars(config.max_chars);
+//!     enricher.enrich(&mut document).await?;
+//! }
+//! ```
+
+use crate::error::{RiptideError, RiptideResult};
+use async_trait::async_trait;
+use riptide_spider::TextEmbedder;
+use riptide_types::ports::DocumentClassifier;
+use riptide_types::{CategoryTag, DocumentClassification, ExtractedDoc, Sentiment, SentimentTag};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+/// A category documents can be tagged with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaxonomyCategory {
+    /// Label stored on documents, e.g. "technology"
+    pub label: String,
+    /// What belongs in the category, for the classifier
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Short example texts, sharpening the category's centroid
+    #[serde(default)]
+    pub examples: Vec<String>,
+}
+
+impl TaxonomyCategory {
+    pub fn new(label: impl Into<String>, description: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            description: Some(description.into()),
+            examples: Vec::new(),
+        }
+    }
+}
+
+/// Categories documents are classified into
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Taxonomy {
+    pub categories: Vec<TaxonomyCategory>,
+}
+
+impl Default for Taxonomy {
+    /// Broad news and web categories
+    fn default() -> Self {
+        let categories = [
+            ("technology", "software, hardware, the internet and gadgets"),
+            ("business", "companies, markets, industry and the economy"),
+            ("finance", "banking, investing, stocks and cryptocurrency"),
+            ("politics", "government, elections, policy and law"),
+            ("science", "research, physics, biology, chemistry and space"),
+            ("health", "medicine, disease, fitness and nutrition"),
+            ("sports", "games, athletes, teams, leagues and competitions"),
+            ("entertainment", "film, television, music, books and gaming"),
+            ("travel", "destinations, tourism, hotels, flights and trips"),
+            ("food", "recipes, cooking, restaurants and drinks"),
+            ("education", "schools, universities, teaching and courses"),
+            ("environment", "climate, nature, pollution and energy"),
+        ];
+        Self {
+            categories: categories
+                .into_iter()
+                .map(|(label, description)| TaxonomyCategory::new(label, description))
+                .collect(),
+        }
+    }
+}
+
+impl Taxonomy {
+    /// Read a taxonomy from a JSON file:
+    /// `{"categories": [{"label": "...", "description": "...", "examples": ["..."]}]}`
+    pub fn from_file(path: impl AsRef<Path>) -> RiptideResult<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            RiptideError::config(format!("Cannot read taxonomy {}: {}", path.display(), e))
+        })?;
+        let taxonomy: Self = serde_json::from_str(&content).map_err(|e| {
+            RiptideError::config(format!("Invalid taxonomy {}: {}", path.display(), e))
+        })?;
+        if taxonomy.categories.is_empty() {
+            return Err(RiptideError::config(format!(
+                "Taxonomy {} has no categories",
+                path.display()
+            )));
+        }
+        Ok(taxonomy)
+    }
+
+    /// The taxonomy's spelling of `label`, matched case-insensitively
+    pub fn resolve(&self, label: &str) -> Option<&str> {
+        let label = label.trim();
+        self.categories
+            .iter()
+            .find(|category| category.label.eq_ignore_ascii_case(label))
+            .map(|category| category.label.as_str())
+    }
+}
+
+/// How documents are classified
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClassificationMethod {
+    /// Ask the LLM to choose from the taxonomy
+    #[default]
+    Llm,
+    /// Nearest taxonomy centroid by embedding similarity
+    Centroid,
+}
+
+impl std::str::FromStr for ClassificationMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "llm" => Ok(Self::Llm),
+            "centroid" | "embedding" => Ok(Self::Centroid),
+            other => Err(format!("unknown classification method: {}", other)),
+        }
+    }
+}
+
+/// Classification settings, from `RIPTIDE_CLASSIFICATION_*`
+#[derive(Clone, Serialize)]
+pub struct ClassificationConfig {
+    /// Classify extracted documents at all
+    pub enabled: bool,
+    pub method: ClassificationMethod,
+    /// LLM provider type ("openai", "anthropic", "ollama", ...), used for
+    /// completions or embeddings depending on `method`
+    pub provider: Option<String>,
+    pub model: String,
+    pub api_key: Option<String>,
+    /// Provider endpoint, for self-hosted and OpenAI-compatible providers
+    pub base_url: Option<String>,
+    /// JSON taxonomy file; `None` uses the built-in taxonomy
+    pub taxonomy_file: Option<String>,
+    /// Categories kept per document
+    pub max_categories: usize,
+    /// Categories below this confidence are dropped
+    pub min_confidence: f32,
+    /// Characters of text classified per document; the rest is left out
+    pub max_chars: usize,
+}
+
+impl Default for ClassificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            method: ClassificationMethod::Llm,
+            provider: None,
+            model: "gpt-4o-mini".to_string(),
+            api_key: None,
+            base_url: None,
+            taxonomy_file: None,
+            max_categories: 3,
+            min_confidence: 0.2,
+            max_chars: 8_000,
+        }
+    }
+}
+
+impl std::fmt::Debug for ClassificationConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClassificationConfig")
+            .field("enabled", &self.enabled)
+            .field("method", &self.method)
+            .field("provider", &self.provider)
+            .field("model", &self.model)
+            .field("api_key", &self.api_key.as_ref().map(|_| "***REDACTED***"))
+            .field("base_url", &self.base_url)
+            .field("taxonomy_file", &self.taxonomy_file)
+            .field("max_categories", &self.max_categories)
+            .field("min_confidence", &self.min_confidence)
+            .field("max_chars", &self.max_chars)
+            .finish()
+    }
+}
+
+impl ClassificationConfig {
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let defaults = Self::default();
+        Self {
+            enabled: var("RIPTIDE_CLASSIFICATION_ENABLED")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.enabled),
+            method: var("RIPTIDE_CLASSIFICATION_METHOD")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.method),
+            provider: var("RIPTIDE_CLASSIFICATION_PROVIDER"),
+            model: var("RIPTIDE_CLASSIFICATION_MODEL").unwrap_or(defaults.model),
+            api_key: var("RIPTIDE_CLASSIFICATION_API_KEY"),
+            base_url: var("RIPTIDE_CLASSIFICATION_BASE_URL"),
+            taxonomy_file: var("RIPTIDE_CLASSIFICATION_TAXONOMY_FILE"),
+            max_categories: var("RIPTIDE_CLASSIFICATION_MAX_CATEGORIES")
+                .and_then(|v| v.parse().ok())
+                .filter(|&n| n > 0)
+                .unwrap_or(defaults.max_categories),
+            min_confidence: var("RIPTIDE_CLASSIFICATION_MIN_CONFIDENCE")
+                .and_then(|v| v.parse().ok())
+                .filter(|n: &f32| (0.0..=1.0).contains(n))
+                .unwrap_or(defaults.min_confidence),
+            max_chars: var("RIPTIDE_CLASSIFICATION_MAX_CHARS")
+                .and_then(|v| v.parse().ok())
+                .filter(|&n| n > 0)
+                .unwrap_or(defaults.max_chars),
+        }
+    }
+
+    /// The configured taxonomy file, or the built-in taxonomy
+    pub fn taxonomy(&self) -> RiptideResult<Taxonomy> {
+        match &self.taxonomy_file {
+            Some(path) => Taxonomy::from_file(path),
+            None => Ok(Taxonomy::default()),
+        }
+    }
+
+    /// Build the classifier for the configured method and provider, `None`
+    /// when no provider is configured
+    #[cfg(feature = "llm")]
+    pub fn create_classifier(&self) -> RiptideResult<Option<Arc<dyn DocumentClassifier>>> {
+        use riptide_intelligence::{create_provider_from_config, ProviderConfig};
+
+        let Some(provider_type) = &self.provider else {
+            return Ok(None);
+        };
+        let mut provider_config = ProviderConfig::new("classification", provider_type.as_str());
+        if let Some(api_key) = &self.api_key {
+            provider_config = provider_config.with_config("api_key", api_key.as_str().into());
+        }
+        if let Some(base_url) = &self.base_url {
+            provider_config = provider_config.with_config("base_url", base_url.as_str().into());
+        }
+        let provider = create_provider_from_config(&provider_config)
+            .map_err(|e| RiptideError::config(format!("Classification provider: {}", e)))?;
+
+        let taxonomy = self.taxonomy()?;
+        let classifier: Arc<dyn DocumentClassifier> = match self.method {
+            ClassificationMethod::Llm => Arc::new(
+                LlmClassifier::new(provider, self.model.clone(), taxonomy)
+                    .with_limits(self.max_categories, self.min_confidence),
+            ),
+            ClassificationMethod::Centroid => Arc::new(
+                CentroidClassifier::new(
+                    Arc::new(super::text_embedder::LlmEmbedder::new(provider)),
+                    taxonomy,
+                )
+                .with_limits(self.max_categories, self.min_confidence),
+            ),
+        };
+        Ok(Some(classifier))
+    }
+}
+
+/// Most confident first, at most `max`, none below `min_confidence`
+fn top_categories(
+    mut categories: Vec<CategoryTag>,
+    max: usize,
+    min_confidence: f32,
+) -> Vec<CategoryTag> {
+    categories.retain(|tag| tag.confidence >= min_confidence);
+    categories.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    categories.truncate(max);
+    categories
+}
+
+/// Phrases whose embeddings anchor each sentiment
+const SENTIMENT_ANCHORS: [(Sentiment, &str); 3] = [
+    (
+        Sentiment::Positive,
+        "great news, excellent, delighted, success, a wonderful and positive experience",
+    ),
+    (
+        Sentiment::Neutral,
+        "a factual, neutral report describing events, figures and details",
+    ),
+    (
+        Sentiment::Negative,
+        "bad news, terrible, disappointed, failure, a harmful and negative experience",
+    ),
+];
+
+/// Softmax temperature: cosine similarities of one text to several anchors
+/// differ by a few hundredths, so they are sharpened before normalizing
+const SOFTMAX_TEMPERATURE: f32 = 0.05;
+
+struct Centroids {
+    categories: Vec<Vec<f32>>,
+    sentiments: Vec<Vec<f32>>,
+}
+
+/// Classifies by cosine similarity to taxonomy and sentiment centroids
+///
+/// Centroids are embedded on first use and kept; every document then costs
+/// one embedding. Confidences are a softmax over the similarities, so they
+/// sum to one across the taxonomy.
+pub struct CentroidClassifier {
+    embedder: Arc<dyn TextEmbedder>,
+    taxonomy: Taxonomy,
+    max_categories: usize,
+    min_confidence: f32,
+    centroids: OnceCell<Centroids>,
+    name: String,
+}
+
+impl CentroidClassifier {
+    pub fn new(embedder: Arc<dyn TextEmbedder>, taxonomy: Taxonomy) -> Self {
+        let defaults = ClassificationConfig::default();
+        Self {
+            name: format!("centroid:{}", embedder.name()),
+            embedder,
+            taxonomy,
+            max_categories: defaults.max_categories,
+            min_confidence: defaults.min_confidence,
+            centroids: OnceCell::new(),
+        }
+    }
+
+    /// Keep at most `max_categories`, none below `min_confidence`
+    pub fn with_limits(mut self, max_categories: usize, min_confidence: f32) -> Self {
+        self.max_categories = max_categories.max(1);
+        self.min_confidence = min_confidence;
+        self
+    }
+
+    async fn embed(&self, texts: Vec<String>) -> riptide_types::Result<Vec<Vec<f32>>> {
+        let embeddings =
+            self.embedder.embed_batch(&texts).await.map_err(|e| {
+                riptide_types::RiptideError::Custom(format!("Embedding failed: {}", e))
+            })?;
+        if embeddings.len() != texts.len() {
+            return Err(riptide_types::RiptideError::Custom(format!(
+                "Embedder returned {} embeddings for {} texts",
+                embeddings.len(),
+                texts.len()
+            )));
+        }
+        Ok(embeddings)
+    }
+
+    /// Embed every category text and sentiment anchor in one batch and
+    /// average each category's embeddings
+    async fn build_centroids(&self) -> riptide_types::Result<Centroids> {
+        let mut texts = Vec::new();
+        let mut owners = Vec::new();
+        for (index, category) in self.taxonomy.categories.iter().enumerate() {
+            texts.push(match &category.description {
+                Some(description) => format!("{}: {}", category.label, description),
+                None => category.label.clone(),
+            });
+            owners.push(index);
+            for example in &category.examples {
+                texts.push(example.clone());
+                owners.push(index);
+            }
+        }
+        texts.extend(
+            SENTIMENT_ANCHORS
+                .iter()
+                .map(|(_, anchor)| anchor.to_string()),
+        );
+
+        let mut embeddings = self.embed(texts).await?;
+        let sentiments = embeddings.split_off(owners.len());
+        let mut categories: Vec<Vec<f32>> = vec![Vec::new(); self.taxonomy.categories.len()];
+        for (owner, embedding) in owners.into_iter().zip(embeddings) {
+            let centroid = &mut categories[owner];
+            if centroid.is_empty() {
+                centroid.resize(embedding.len(), 0.0);
+            }
+            let norm = norm(&embedding);
+            for (sum, value) in centroid.iter_mut().zip(&embedding) {
+                *sum += value / norm;
+            }
+        }
+        Ok(Centroids {
+            categories,
+            sentiments,
+        })
+    }
+}
+
+#[async_trait]
+impl DocumentClassifier for CentroidClassifier {
+    async fn classify(
+        &self,
+        title: Option<&str>,
+        text: &str,
+    ) -> riptide_types::Result<DocumentClassification> {
+        let centroids = self
+            .centroids
+            .get_or_try_init(|| self.build_centroids())
+            .await?;
+        let content = match title {
+            Some(title) => format!("{}\n\n{}", title, text),
+            None => text.to_string(),
+        };
+        let document = self.embed(vec![content]).await?.pop().unwrap_or_default();
+
+        let category_confidences = softmax(
+            centroids
+                .categories
+                .iter()
+                .map(|centroid| cosine_similarity(&document, centroid)),
+        );
+        let categories = self
+            .taxonomy
+            .categories
+            .iter()
+            .zip(category_confidences)
+            .map(|(category, confidence)| CategoryTag {
+                label: category.label.clone(),
+                confidence,
+            })
+            .collect();
+
+        let sentiment = softmax(
+            centroids
+                .sentiments
+                .iter()
+                .map(|anchor| cosine_similarity(&document, anchor)),
+        )
+        .into_iter()
+        .zip(SENTIMENT_ANCHORS.iter().map(|(sentiment, _)| *sentiment))
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(confidence, label)| SentimentTag { label, confidence });
+
+        Ok(DocumentClassification {
+            categories: top_categories(categories, self.max_categories, self.min_confidence),
+            sentiment,
+            classifier: self.name.clone(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+fn norm(vector: &[f32]) -> f32 {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        norm
+    } else {
+        1.0
+    }
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    dot / (norm(a) * norm(b))
+}
+
+/// Confidences from similarities, rounded to two decimals
+fn softmax(similarities: impl Iterator<Item = f32>) -> Vec<f32> {
+    let scaled: Vec<f32> = similarities.map(|s| s / SOFTMAX_TEMPERATURE).collect();
+    let max = scaled.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let exps: Vec<f32> = scaled.iter().map(|s| (s - max).exp()).collect();
+    let total: f32 = exps.iter().sum();
+    exps.iter()
+        .map(|e| (e / total * 100.0).round() / 100.0)
+        .collect()
+}
+
+/// Tags extracted documents with categories and sentiment
+#[derive(Clone)]
+pub struct ClassificationEnricher {
+    classifier: Arc<dyn DocumentClassifier>,
+    max_chars: usize,
+}
+
+impl ClassificationEnricher {
+    pub fn new(classifier: Arc<dyn DocumentClassifier>) -> Self {
+        Self {
+            classifier,
+            max_chars: ClassificationConfig::default().max_chars,
+        }
+    }
+
+    /// Classify at most `max_chars` characters per document
+    pub fn with_max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = max_chars.max(1);
+        self
+    }
+
+    /// Classify `doc`, replacing its `categories` with the classified labels
+    ///
+    /// Returns `false` without calling the classifier when the document has
+    /// no text or is already classified.
+    pub async fn enrich(&self, doc: &mut ExtractedDoc) -> RiptideResult<bool> {
+        if doc.classification.is_some() || doc.text.trim().is_empty() {
+            return Ok(false);
+        }
+
+        let text = match doc.text.char_indices().nth(self.max_chars) {
+            Some((end, _)) => &doc.text[..end],
+            None => doc.text.as_str(),
+        };
+        let title = doc.title.as_deref().filter(|t| !t.trim().is_empty());
+
+        let mut classification = self.classifier.classify(title, text).await?;
+        if classification.classifier.is_empty() {
+            classification.classifier = self.classifier.name().to_string();
+        }
+        doc.categories = classification
+            .categories
+            .iter()
+            .map(|tag| tag.label.clone())
+            .collect();
+        doc.classification = Some(classification);
+        Ok(true)
+    }
+}
+
+#[cfg(feature = "llm")]
+pub use llm_classifier::LlmClassifier;
+
+#[cfg(feature = "llm")]
+mod llm_classifier {
+    use super::{top_categories, Taxonomy};
+    use async_trait::async_trait;
+    use riptide_intelligence::{CompletionRequest, LlmProvider, Message};
+    use riptide_types::ports::DocumentClassifier;
+    use riptide_types::{
+        CategoryTag, DocumentClassification, RiptideError, Sentiment, SentimentTag,
+    };
+    use serde::Deserialize;
+    use std::sync::Arc;
+
+    /// Document classifier backed by an LLM provider
+    pub struct LlmClassifier {
+        provider: Arc<dyn LlmProvider>,
+        model: String,
+        taxonomy: Taxonomy,
+        max_categories: usize,
+        min_confidence: f32,
+        system_prompt: String,
+        name: String,
+    }
+
+    impl LlmClassifier {
+        /// Classify into `taxonomy` with `model` of `provider`
+        pub fn new(
+            provider: Arc<dyn LlmProvider>,
+            model: impl Into<String>,
+            taxonomy: Taxonomy,
+        ) -> Self {
+            let model = model.into();
+            let defaults = super::ClassificationConfig::default();
+            Self {
+                provider,
+                name: format!("llm:{}", model),
+                model,
+                system_prompt: system_prompt(&taxonomy),
+                taxonomy,
+                max_categories: defaults.max_categories,
+                min_confidence: defaults.min_confidence,
+            }
+        }
+
+        /// Keep at most `max_categories`, none below `min_confidence`
+        pub fn with_limits(mut self, max_categories: usize, min_confidence: f32) -> Self {
+            self.max_categories = max_categories.max(1);
+            self.min_confidence = min_confidence;
+            self
+        }
+    }
+
+    fn system_prompt(taxonomy: &Taxonomy) -> String {
+        let categories: Vec<String> = taxonomy
+            .categories
+            .iter()
+            .map(|category| match &category.description {
+                Some(description) => format!("- {}: {}", category.label, description),
+                None => format!("- {}", category.label),
+            })
+            .collect();
+        format!(
+            "You classify web page text. Choose the categories that fit from this list \
+             and no others:\n{}\n\
+             Answer with a JSON object only: \
+             {{\"categories\": [{{\"label\": \"...\", \"confidence\": 0.0}}], \
+             \"sentiment\": {{\"label\": \"positive|neutral|negative\", \"confidence\": 0.0}}}}. \
+             Confidences range from 0 to 1.",
+            categories.join("\n")
+        )
+    }
+
+    #[async_trait]
+    impl DocumentClassifier for LlmClassifier {
+        async fn classify(
+            &self,
+            title: Option<&str>,
+            text: &str,
+        ) -> riptide_types::Result<DocumentClassification> {
+            let content = match title {
+                Some(title) => format!("{}\n\n{}", title, text),
+                None => text.to_string(),
+            };
+            let request = CompletionRequest::new(
+                self.model.clone(),
+                vec![
+                    Message::system(self.system_prompt.clone()),
+                    Message::user(content),
+                ],
+            )
+            .with_max_tokens(256)
+            .with_temperature(0.0);
+
+            let response =
+                self.provider.complete(request).await.map_err(|e| {
+                    RiptideError::Custom(format!("LLM classification failed: {}", e))
+                })?;
+            let mut classification = parse_response(&response.content, &self.taxonomy)?;
+            classification.categories = top_categories(
+                classification.categories,
+                self.max_categories,
+                self.min_confidence,
+            );
+            classification.classifier = self.name.clone();
+            Ok(classification)
+        }
+
+        fn name(&self) -> &str {
+            &self.name
+        }
+    }
+
+    #[derive(Deserialize)]
+    struct Response {
+        #[serde(default)]
+        categories: Vec<ResponseTag>,
+        sentiment: Option<ResponseTag>,
+    }
+
+    #[derive(Deserialize)]
+    struct ResponseTag {
+        label: String,
+        #[serde(default)]
+        confidence: Option<f32>,
+    }
+
+    /// Parse the model's JSON answer, dropping labels outside `taxonomy`
+    fn parse_response(
+        content: &str,
+        taxonomy: &Taxonomy,
+    ) -> riptide_types::Result<DocumentClassification> {
+        let json = content
+            .trim()
+            .trim_start_matches("```json")
+            .trim_start_matches("```")
+            .trim_end_matches("```")
+            .trim();
+        let response: Response = serde_json::from_str(json).map_err(|e| {
+            RiptideError::Custom(format!("LLM classification returned invalid JSON: {}", e))
+        })?;
+        let confidence = |tag: &ResponseTag| tag.confidence.unwrap_or(1.0).clamp(0.0, 1.0);
+
+        let mut categories: Vec<CategoryTag> = Vec::new();
+        for tag in &response.categories {
+            let Some(label) = taxonomy.resolve(&tag.label) else {
+                continue;
+            };
+            if categories.iter().any(|c| c.label == label) {
+                continue;
+            }
+            categories.push(CategoryTag {
+                label: label.to_string(),
+                confidence: confidence(tag),
+            });
+        }
+        let sentiment = response.sentiment.and_then(|tag| {
+            Sentiment::from_label(&tag.label).map(|label| SentimentTag {
+                label,
+                confidence: confidence(&tag),
+            })
+        });
+
+        Ok(DocumentClassification {
+            categories,
+            sentiment,
+            classifier: String::new(),
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_parse_response_keeps_taxonomy_labels() {
+            let content =
+                "```json\n{\"categories\": [{\"label\": \"Sports\", \"confidence\": 0.9}, \
+                           {\"label\": \"cooking\", \"confidence\": 0.8}, \
+                           {\"label\": \"health\", \"confidence\": 1.7}], \
+                           \"sentiment\": {\"label\": \"NEG\", \"confidence\": 0.6}}\n```";
+
+            let classification = parse_response(content, &Taxonomy::default()).unwrap();
+            let labels: Vec<&str> = classification
+                .categories
+                .iter()
+                .map(|tag| tag.label.as_str())
+                .collect();
+            assert_eq!(labels, vec!["sports", "health"]);
+            assert_eq!(classification.categories[1].confidence, 1.0);
+            assert_eq!(
+                classification.sentiment,
+                Some(SentimentTag {
+                    label: Sentiment::Negative,
+                    confidence: 0.6,
+                })
+            );
+
+            assert!(parse_response("not json", &Taxonomy::default()).is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::ports::InMemoryDocumentClassifier;
+
+    /// Embeds text as counts of a few marker words
+    struct MarkerEmbedder;
+
+    #[async_trait]
+    impl TextEmbedder for MarkerEmbedder {
+        async fn embed_batch(&self, texts: &[String]) -> anyhow::Result<Vec<Vec<f32>>> {
+            const MARKERS: [&str; 5] = ["goal", "stock", "recipe", "great", "terrible"];
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let text = text.to_lowercase();
+                    let mut embedding: Vec<f32> = MARKERS
+                        .iter()
+                        .map(|marker| text.matches(marker).count() as f32)
+                        .collect();
+                    // Keeps texts without markers off the zero vector
+                    embedding.push(0.1);
+                    embedding
+                })
+                .collect())
+        }
+
+        fn name(&self) -> &str {
+            "markers"
+        }
+    }
+
+    fn taxonomy() -> Taxonomy {
+        Taxonomy {
+            categories: vec![
+                TaxonomyCategory {
+                    label: "sports".to_string(),
+                    description: Some("goal after goal".to_string()),
+                    examples: vec!["a late goal".to_string()],
+                },
+                TaxonomyCategory::new("finance", "stock prices"),
+                TaxonomyCategory::new("food", "a recipe"),
+            ],
+        }
+    }
+
+    fn doc(text: &str) -> ExtractedDoc {
+        ExtractedDoc {
+            url: "https://example.com/".to_string(),
+            title: Some("Match report".to_string()),
+            text: text.to_string(),
+            categories: vec!["news".to_string(), "homepage".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_centroid_classifier_picks_nearest_category() {
+        let classifier =
+            CentroidClassifier::new(Arc::new(MarkerEmbedder), taxonomy()).with_limits(2, 0.1);
+
+        let classification = classifier
+            .classify(None, "A great goal and another goal, a great night")
+            .await
+            .unwrap();
+        assert_eq!(classification.classifier, "centroid:markers");
+        assert_eq!(classification.categories.len(), 1);
+        assert_eq!(classification.categories[0].label, "sports");
+        assert!(classification.categories[0].confidence > 0.9);
+        assert_eq!(
+            classification.sentiment.map(|tag| tag.label),
+            Some(Sentiment::Positive)
+        );
+
+        let classification = classifier
+            .classify(Some("Terrible quarter"), "The stock fell")
+            .await
+            .unwrap();
+        assert_eq!(classification.categories[0].label, "finance");
+        assert_eq!(
+            classification.sentiment.map(|tag| tag.label),
+            Some(Sentiment::Negative)
+        );
+    }
+
+    #[test]
+    fn test_top_categories_and_taxonomy_labels() {
+        let tag = |label: &str, confidence| CategoryTag {
+            label: label.to_string(),
+            confidence,
+        };
+        let top = top_categories(
+            vec![tag("a", 0.1), tag("b", 0.5), tag("c", 0.3), tag("d", 0.4)],
+            2,
+            0.2,
+        );
+        assert_eq!(top, vec![tag("b", 0.5), tag("d", 0.4)]);
+
+        assert_eq!(Taxonomy::default().resolve(" Sports "), Some("sports"));
+        assert_eq!(Taxonomy::default().resolve("cooking"), None);
+    }
+
+    #[tokio::test]
+    async fn test_enrich_replaces_meta_categories() {
+        let classifier = Arc::new(InMemoryDocumentClassifier::new(DocumentClassification {
+            categories: vec![CategoryTag {
+                label: "sports".to_string(),
+                confidence: 0.8,
+            }],
+            sentiment: None,
+            classifier: String::new(),
+        }));
+        let enricher = ClassificationEnricher::new(classifier.clone());
+
+        let mut document = doc("The home side scored twice.");
+        assert!(enricher.enrich(&mut document).await.unwrap());
+        assert!(!enricher.enrich(&mut document).await.unwrap());
+        let mut empty = doc("  ");
+        assert!(!enricher.enrich(&mut empty).await.unwrap());
+        assert_eq!(empty.categories, vec!["news", "homepage"]);
+
+        assert_eq!(document.categories, vec!["sports"]);
+        assert_eq!(document.classification.unwrap().classifier, "memory");
+        assert_eq!(classifier.calls(), 1);
+
+        let mut document = doc("The home side scored twice.");
+        assert!(
+            ClassificationEnricher::new(Arc::new(InMemoryDocumentClassifier::failing()))
+                .enrich(&mut document)
+                .await
+                .is_err()
+        );
+        assert!(document.classification.is_none());
+        assert_eq!(document.categories, vec!["news", "homepage"]);
+    }
+}
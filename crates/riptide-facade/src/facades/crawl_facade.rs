@@ -16,6 +16,7 @@ use riptide_types::pipeline::{PipelineExecutor, StrategiesPipelineExecutor};
 // Import types from riptide-types
 use riptide_types::config::CrawlOptions;
 use riptide_types::pipeline::{PipelineResult, StrategiesPipelineResult};
+use riptide_types::ChangedSince;
 use std::sync::Arc;
 
 /// Crawl mode selector for the facade
@@ -187,6 +188,42 @@ impl CrawlFacade {
         self.pipeline_orchestrator.execute_batch(urls).await
     }
 
+    /// Batch crawl only the URLs whose content changed since `since`
+    ///
+    /// **Delegates to PipelineExecutor::execute_batch_changed_since (production code)**
+    ///
+    /// # Arguments
+    ///
+    /// * `urls` - List of URLs to crawl
+    /// * `since` - RFC 3339 timestamp or id of an earlier crawl
+    ///
+    /// # Returns
+    ///
+    /// Tuple of (results, statistics); unchanged URLs carry the
+    /// [`GATE_DECISION_UNCHANGED`](riptide_types::pipeline::GATE_DECISION_UNCHANGED) gate decision and an empty document
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `since` names an unknown or expired crawl id
+    pub async fn crawl_batch_changed_since(
+        &self,
+        urls: &[String],
+        since: &ChangedSince,
+    ) -> RiptideResult<(
+        Vec<Option<PipelineResult>>,
+        riptide_types::pipeline::PipelineStats,
+    )> {
+        self.pipeline_orchestrator
+            .execute_batch_changed_since(urls, since)
+            .await
+            .map_err(|e| match e {
+                riptide_types::RiptideError::ValidationError(message) => {
+                    RiptideError::Validation(message)
+                }
+                e => RiptideError::Other(e.into()),
+            })
+    }
+
     /// Get reference to the underlying standard pipeline executor
     ///
     /// Use this for advanced operations that need direct access to the
@@ -0,0 +1,213 @@
+//! DOM snapshot facade for captures pushed by the browser extension.
+//!
+//! Validates a [`DomSnapshot`] against size limits, renders its visible
+//! content to HTML for the extractors and resolves the resources manifest
+//! against the page URL.
+
+use crate::error::RiptideResult;
+use crate::RiptideError;
+use riptide_types::{DomSnapshot, SnapshotResource, DOM_SNAPSHOT_VERSION};
+use std::collections::HashSet;
+use url::Url;
+
+/// Size limits applied to incoming snapshots
+#[derive(Debug, Clone, Copy)]
+pub struct SnapshotLimits {
+    /// Maximum number of DOM nodes
+    pub max_nodes: usize,
+    /// Maximum tree depth; JSON bodies nest two levels per element, so
+    /// serde_json's recursion limit already stops trees past ~63 levels
+    pub max_depth: usize,
+    /// Maximum number of resources in the manifest
+    pub max_resources: usize,
+}
+
+impl Default for SnapshotLimits {
+    fn default() -> Self {
+        Self {
+            max_nodes: 200_000,
+            max_depth: 60,
+            max_resources: 5_000,
+        }
+    }
+}
+
+/// A snapshot ready for extraction
+#[derive(Debug, Clone)]
+pub struct PreparedSnapshot {
+    /// Page URL
+    pub url: String,
+    /// Visible content rendered as an HTML document
+    pub html: String,
+    /// Resources with absolute URLs, duplicates removed
+    pub resources: Vec<SnapshotResource>,
+    /// Number of nodes in the captured tree
+    pub node_count: usize,
+}
+
+#[derive(Clone, Default)]
+pub struct DomSnapshotFacade {
+    limits: SnapshotLimits,
+}
+
+impl DomSnapshotFacade {
+    pub fn new(limits: SnapshotLimits) -> Self {
+        Self { limits }
+    }
+
+    /// Validate a snapshot and render it for extraction
+    pub fn prepare(&self, snapshot: &DomSnapshot) -> RiptideResult<PreparedSnapshot> {
+        if snapshot.version == 0 || snapshot.version > DOM_SNAPSHOT_VERSION {
+            return Err(RiptideError::validation(format!(
+                "Unsupported snapshot version {} (supported: 1..={})",
+                snapshot.version, DOM_SNAPSHOT_VERSION
+            )));
+        }
+
+        let base = Url::parse(&snapshot.url)
+            .map_err(|e| RiptideError::validation(format!("Invalid snapshot URL: {}", e)))?;
+        if !matches!(base.scheme(), "http" | "https") || base.host_str().is_none() {
+            return Err(RiptideError::validation(
+                "Snapshot URL must be an http(s) URL with a host",
+            ));
+        }
+
+        // Depth first: it bounds the recursion of the node count and renderer
+        let depth = snapshot.root.depth();
+        if depth > self.limits.max_depth {
+            return Err(RiptideError::validation(format!(
+                "Snapshot tree is {} levels deep, over the limit of {}",
+                depth, self.limits.max_depth
+            )));
+        }
+        let node_count = snapshot.root.node_count();
+        if node_count > self.limits.max_nodes {
+            return Err(RiptideError::validation(format!(
+                "Snapshot has {} nodes, over the limit of {}",
+                node_count, self.limits.max_nodes
+            )));
+        }
+        if snapshot.resources.len() > self.limits.max_resources {
+            return Err(RiptideError::validation(format!(
+                "Snapshot lists {} resources, over the limit of {}",
+                snapshot.resources.len(),
+                self.limits.max_resources
+            )));
+        }
+
+        let mut seen = HashSet::new();
+        let resources = snapshot
+            .resources
+            .iter()
+            .filter_map(|resource| {
+                let url = base.join(&resource.url).ok()?;
+                if !matches!(url.scheme(), "http" | "https") {
+                    return None;
+                }
+                seen.insert(url.to_string()).then(|| SnapshotResource {
+                    url: url.to_string(),
+                    ..resource.clone()
+                })
+            })
+            .collect();
+
+        Ok(PreparedSnapshot {
+            url: base.to_string(),
+            html: snapshot.to_html(),
+            resources,
+            node_count,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::{DomNode, ResourceKind};
+
+    fn snapshot(root: DomNode, resources: Vec<SnapshotResource>) -> DomSnapshot {
+        DomSnapshot {
+            version: DOM_SNAPSHOT_VERSION,
+            url: "https://portal.example.com/app/report".to_string(),
+            captured_at: None,
+            title: None,
+            lang: None,
+            viewport: None,
+            root,
+            resources,
+        }
+    }
+
+    fn resource(url: &str) -> SnapshotResource {
+        SnapshotResource {
+            url: url.to_string(),
+            kind: ResourceKind::Image,
+            mime_type: None,
+            size_bytes: None,
+            status: None,
+        }
+    }
+
+    fn nested(depth: usize) -> DomNode {
+        (0..depth).fold(
+            DomNode::Text {
+                text: "x".to_string(),
+            },
+            |child, _| DomNode::Element {
+                tag: "div".to_string(),
+                attributes: Default::default(),
+                styles: None,
+                children: vec![child],
+            },
+        )
+    }
+
+    #[test]
+    fn test_prepare_resolves_and_dedupes_resources() {
+        let prepared = DomSnapshotFacade::default()
+            .prepare(&snapshot(
+                nested(2),
+                vec![
+                    resource("/img/chart.png"),
+                    resource("https://portal.example.com/img/chart.png"),
+                    resource("data:image/png;base64,AAAA"),
+                    resource("logo.svg"),
+                ],
+            ))
+            .unwrap();
+
+        let urls: Vec<_> = prepared.resources.iter().map(|r| r.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://portal.example.com/img/chart.png",
+                "https://portal.example.com/app/logo.svg"
+            ]
+        );
+        assert_eq!(prepared.node_count, 3);
+        assert!(prepared.html.contains("<div><div>x</div></div>"));
+    }
+
+    #[test]
+    fn test_prepare_enforces_limits() {
+        let facade = DomSnapshotFacade::new(SnapshotLimits {
+            max_nodes: 10,
+            max_depth: 4,
+            max_resources: 1,
+        });
+
+        assert!(facade.prepare(&snapshot(nested(5), vec![])).is_err());
+        assert!(facade.prepare(&snapshot(nested(3), vec![])).is_ok());
+        assert!(facade
+            .prepare(&snapshot(nested(1), vec![resource("a"), resource("b")]))
+            .is_err());
+
+        let mut unsupported = snapshot(nested(1), vec![]);
+        unsupported.version = DOM_SNAPSHOT_VERSION + 1;
+        assert!(facade.prepare(&unsupported).is_err());
+
+        let mut local = snapshot(nested(1), vec![]);
+        local.url = "file:///etc/passwd".to_string();
+        assert!(facade.prepare(&local).is_err());
+    }
+}
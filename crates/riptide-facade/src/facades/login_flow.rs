@@ -0,0 +1,1027 @@
+//! Recorded or declared multi-step login flows.
+//!
+//! A [`LoginFlow`] describes how to log in to a site: the login page, the
+//! steps to get through it (navigate, fill, click, submit, wait, answer a
+//! two-factor prompt) and the page condition that proves the login worked.
+//! Flows are written in YAML, or converted from a [`SessionRecording`] of a
+//! login done by hand with [`LoginFlow::from_recording`].
+//!
+//! [`BrowserFacade::run_login_flow`](crate::facades::BrowserFacade::run_login_flow)
+//! runs a flow in a fresh browser session and returns the cookies it ends up
+//! with as an [`AuthSession`]. [`LoginFlowSessionProvider`] plugs flows into
+//! crawls: it logs in the first time a crawl needs a domain, and logs in
+//! again when the crawler reports that the site rejected the session.
+//!
+//! Secrets stay out of flow files: `${name}` placeholders in `fill` values
+//! are resolved from [`LoginCredentials`] when the flow runs, and the values
+//! typed for them are redacted in session recordings. Two-factor codes come
+//! from a [`TwoFactorPrompt`] callback.
+//!
+//! # Example
+//!
+//! ```yaml
+//! name: shop
+//! login_url: https://shop.example.com/login
+//! steps:
+//!   - action: fill
+//!     selector: "#email"
+//!     value: "${username}"
+//!   - action: fill
+//!     selector: "#password"
+//!     value: "${password}"
+//!   - action: click
+//!     selector: "button[type=submit]"
+//!   # Skipped when the account page shows up without a code being asked
+//!   - action: two_factor
+//!     selector: "input[name=otp]"
+//!     submit: "#verify"
+//! success: { element: ".account-menu" }
+//! ```
+
+use crate::error::{RiptideError, RiptideResult};
+use crate::facades::action_script::{
+    selector_problem, WaitCondition, MAX_SCRIPT_STEPS, MAX_WAIT_MS,
+};
+use crate::facades::browser::{BrowserAction, BrowserFacade};
+use crate::facades::session_recorder::{is_secret_field, RecordedEventKind, SessionRecording};
+use async_trait::async_trait;
+use riptide_types::ports::{AuthSession, SessionProvider};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+use url::Url;
+
+/// Text recorded instead of credentials and two-factor codes
+pub const REDACTED_VALUE: &str = "[redacted]";
+
+/// Placeholder used for passwords when converting a recording
+const PASSWORD_PLACEHOLDER: &str = "${password}";
+
+/// Logins a provider attempts per flow before giving up
+const DEFAULT_MAX_LOGINS: u32 = 3;
+
+/// Renewals within this time of the last login reuse its session, so pages
+/// rejected together trigger one login
+const DEFAULT_RELOGIN_INTERVAL: Duration = Duration::from_secs(30);
+
+/// One step of a login flow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case", deny_unknown_fields)]
+pub enum LoginStep {
+    /// Open a URL
+    Navigate { url: String },
+
+    /// Type a value into a field; `${name}` placeholders are replaced with
+    /// credentials
+    Fill { selector: String, value: String },
+
+    /// Click an element
+    Click { selector: String },
+
+    /// Submit a form
+    Submit { selector: String },
+
+    /// Pause for a fixed duration
+    Wait { duration_ms: u64 },
+
+    /// Wait until a page condition holds
+    WaitFor {
+        until: WaitCondition,
+        #[serde(default = "default_step_timeout_ms")]
+        timeout_ms: u64,
+    },
+
+    /// Enter a two-factor code when the site asks for one
+    ///
+    /// Waits for `selector` to appear; when the flow's success condition
+    /// holds first, no code was needed and the step is skipped. Otherwise
+    /// the [`TwoFactorPrompt`] is asked for a code, which is typed into
+    /// `selector`, then `submit` is clicked.
+    TwoFactor {
+        selector: String,
+        #[serde(default)]
+        submit: Option<String>,
+        /// Hint shown by the prompt, e.g. "code sent by SMS"
+        #[serde(default)]
+        prompt: Option<String>,
+        #[serde(default = "default_step_timeout_ms")]
+        timeout_ms: u64,
+    },
+}
+
+fn default_step_timeout_ms() -> u64 {
+    10_000
+}
+
+fn default_flow_timeout_ms() -> u64 {
+    60_000
+}
+
+impl LoginStep {
+    /// Browser action performing this step, `None` for steps the facade
+    /// runs itself (navigation, fills and two-factor prompts)
+    pub fn to_action(&self) -> Option<BrowserAction> {
+        let action = match self {
+            Self::Click { selector } => BrowserAction::Click {
+                selector: selector.clone(),
+            },
+            Self::Submit { selector } => BrowserAction::Submit {
+                selector: selector.clone(),
+            },
+            Self::Wait { duration_ms } => BrowserAction::Wait {
+                duration_ms: *duration_ms,
+            },
+            Self::WaitFor { until, timeout_ms } => BrowserAction::WaitUntil {
+                condition: until.clone(),
+                timeout_ms: *timeout_ms,
+            },
+            Self::Navigate { .. } | Self::Fill { .. } | Self::TwoFactor { .. } => return None,
+        };
+        Some(action)
+    }
+
+    fn validate(&self, path: &str) -> RiptideResult<()> {
+        let problem = match self {
+            Self::Navigate { url } => http_url_problem(url),
+            Self::Fill { selector, value } => {
+                selector_problem(selector).or_else(|| placeholders(value).err())
+            }
+            Self::Click { selector } | Self::Submit { selector } => selector_problem(selector),
+            Self::Wait { duration_ms } => duration_problem("duration_ms", *duration_ms),
+            Self::WaitFor { until, timeout_ms } => until
+                .problem()
+                .or_else(|| duration_problem("timeout_ms", *timeout_ms)),
+            Self::TwoFactor {
+                selector,
+                submit,
+                timeout_ms,
+                ..
+            } => selector_problem(selector)
+                .or_else(|| submit.as_deref().and_then(selector_problem))
+                .or_else(|| duration_problem("timeout_ms", *timeout_ms)),
+        };
+        match problem {
+            Some(problem) => Err(invalid(path, &problem)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A named, validated login procedure for one site
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoginFlow {
+    /// Flow name, used in logs and two-factor prompts
+    pub name: String,
+    /// Page the flow starts on; crawls redirected here need a new login
+    pub login_url: String,
+    /// Domain the session is for, subdomains included; the login URL's host
+    /// when unset
+    #[serde(default)]
+    pub domain: Option<String>,
+    pub steps: Vec<LoginStep>,
+    /// Condition that holds once logged in
+    #[serde(with = "serde_yaml::with::singleton_map")]
+    pub success: WaitCondition,
+    /// Time allowed for the whole flow, two-factor prompts included
+    #[serde(default = "default_flow_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+impl LoginFlow {
+    /// Parse and validate a flow from YAML
+    pub fn from_yaml(yaml: &str) -> RiptideResult<Self> {
+        let flow: Self = serde_yaml::from_str(yaml)
+            .map_err(|e| RiptideError::Validation(format!("Invalid login flow: {}", e)))?;
+        flow.validate()?;
+        Ok(flow)
+    }
+
+    /// Build a flow from a recorded manual login
+    ///
+    /// The first navigation becomes the login URL and later ones `navigate`
+    /// steps; typed text becomes `fill` steps, clicks, submits and waits
+    /// carry over, and failed or other actions are dropped. Text typed into
+    /// password fields, or redacted in the recording, is replaced with the
+    /// `${password}` placeholder; review the other values before saving the
+    /// flow.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error when the recording has no navigation or
+    /// the resulting flow is invalid.
+    pub fn from_recording(
+        name: impl Into<String>,
+        recording: &SessionRecording,
+        success: WaitCondition,
+    ) -> RiptideResult<Self> {
+        let mut login_url = None;
+        let mut steps = Vec::new();
+
+        for event in &recording.events {
+            match &event.kind {
+                RecordedEventKind::Navigation { url, error: None } => match login_url {
+                    None => login_url = Some(url.clone()),
+                    Some(_) => steps.push(LoginStep::Navigate { url: url.clone() }),
+                },
+                RecordedEventKind::Action {
+                    action,
+                    error: None,
+                } => steps.extend(recorded_step(action)),
+                _ => {}
+            }
+        }
+
+        let login_url = login_url.ok_or_else(|| {
+            RiptideError::Validation(format!(
+                "Recording of session {} has no successful navigation to start a login flow from",
+                recording.session_id
+            ))
+        })?;
+        let flow = Self {
+            name: name.into(),
+            login_url,
+            domain: None,
+            steps,
+            success,
+            timeout_ms: default_flow_timeout_ms(),
+        };
+        flow.validate()?;
+        Ok(flow)
+    }
+
+    /// Domain the flow logs in to
+    pub fn domain(&self) -> Option<String> {
+        match &self.domain {
+            Some(domain) => Some(domain.to_ascii_lowercase()),
+            None => Url::parse(&self.login_url)
+                .ok()?
+                .host_str()
+                .map(str::to_ascii_lowercase),
+        }
+    }
+
+    /// Whether sessions from this flow authenticate requests to `host`
+    pub fn covers(&self, host: &str) -> bool {
+        let host = host.to_ascii_lowercase();
+        self.domain().is_some_and(|domain| {
+            host == domain
+                || host
+                    .strip_suffix(domain.as_str())
+                    .is_some_and(|sub| sub.ends_with('.'))
+        })
+    }
+
+    /// Credential names the flow's placeholders refer to
+    pub fn credential_names(&self) -> BTreeSet<String> {
+        self.steps
+            .iter()
+            .filter_map(|step| match step {
+                LoginStep::Fill { value, .. } => placeholders(value).ok(),
+                _ => None,
+            })
+            .flatten()
+            .collect()
+    }
+
+    /// Check the flow before running it
+    ///
+    /// Rejects flows without steps or a valid login URL, invalid selectors,
+    /// conditions and placeholders, and waits exceeding the action script
+    /// limits.
+    pub fn validate(&self) -> RiptideResult<()> {
+        if self.name.trim().is_empty() {
+            return Err(invalid("name", "must not be empty"));
+        }
+        if let Some(problem) = http_url_problem(&self.login_url) {
+            return Err(invalid("login_url", &problem));
+        }
+        if self.domain().is_none() {
+            return Err(invalid("domain", "login_url has no host"));
+        }
+        if self.steps.is_empty() {
+            return Err(invalid("steps", "flow has no steps"));
+        }
+        if self.steps.len() > MAX_SCRIPT_STEPS {
+            return Err(invalid(
+                "steps",
+                &format!(
+                    "flow has {} steps, the limit is {}",
+                    self.steps.len(),
+                    MAX_SCRIPT_STEPS
+                ),
+            ));
+        }
+        for (i, step) in self.steps.iter().enumerate() {
+            step.validate(&format!("steps[{}]", i))?;
+        }
+        if let Some(problem) = self.success.problem() {
+            return Err(invalid("success", &problem));
+        }
+        if self.timeout_ms == 0 || self.timeout_ms > MAX_WAIT_MS {
+            return Err(invalid("timeout_ms", &format!("must be 1-{}", MAX_WAIT_MS)));
+        }
+        Ok(())
+    }
+
+    /// Session carrying `cookies`, expiring when a crawl lands on the login page
+    pub fn auth_session<I, K, V>(&self, cookies: I) -> AuthSession
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        AuthSession::with_cookies(cookies).with_login_url(self.login_url.clone())
+    }
+}
+
+/// Step reproducing a recorded action, `None` for actions a login does not need
+fn recorded_step(action: &BrowserAction) -> Option<LoginStep> {
+    let step = match action {
+        BrowserAction::Type { selector, text } => {
+            let secret = text == REDACTED_VALUE || is_secret_field(selector);
+            LoginStep::Fill {
+                selector: selector.clone(),
+                value: if secret {
+                    PASSWORD_PLACEHOLDER.to_string()
+                } else {
+                    text.clone()
+                },
+            }
+        }
+        BrowserAction::Click { selector } => LoginStep::Click {
+            selector: selector.clone(),
+        },
+        BrowserAction::Submit { selector } => LoginStep::Submit {
+            selector: selector.clone(),
+        },
+        BrowserAction::Wait { duration_ms } => LoginStep::Wait {
+            duration_ms: *duration_ms,
+        },
+        BrowserAction::WaitForElement {
+            selector,
+            timeout_ms,
+        } => LoginStep::WaitFor {
+            until: WaitCondition::Element(selector.clone()),
+            timeout_ms: *timeout_ms,
+        },
+        BrowserAction::WaitUntil {
+            condition,
+            timeout_ms,
+        } => LoginStep::WaitFor {
+            until: condition.clone(),
+            timeout_ms: *timeout_ms,
+        },
+        BrowserAction::ScrollTo { .. }
+        | BrowserAction::ScrollBy { .. }
+        | BrowserAction::Focus { .. } => return None,
+    };
+    Some(step)
+}
+
+/// Values for the `${name}` placeholders of login flows
+///
+/// `Debug` output lists the names only.
+#[derive(Clone, Default)]
+pub struct LoginCredentials {
+    values: HashMap<String, String>,
+}
+
+impl std::fmt::Debug for LoginCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<_> = self.values.keys().collect();
+        names.sort();
+        f.debug_struct("LoginCredentials")
+            .field("names", &names)
+            .finish()
+    }
+}
+
+impl LoginCredentials {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the value of placeholder `name`
+    pub fn with(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.values.insert(name.into(), value.into());
+        self
+    }
+
+    /// Check that every placeholder of `flow` has a value
+    pub fn check(&self, flow: &LoginFlow) -> RiptideResult<()> {
+        let missing: Vec<_> = flow
+            .credential_names()
+            .into_iter()
+            .filter(|name| !self.values.contains_key(name))
+            .collect();
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(RiptideError::Validation(format!(
+                "Login flow '{}' needs credentials: {}",
+                flow.name,
+                missing.join(", ")
+            )))
+        }
+    }
+
+    /// `value` with its placeholders replaced
+    pub fn resolve(&self, value: &str) -> RiptideResult<String> {
+        let mut resolved = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(start) = rest.find("${") {
+            resolved.push_str(&rest[..start]);
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| RiptideError::Validation("Unterminated placeholder".to_string()))?;
+            let name = &rest[start + 2..start + end];
+            let credential = self.values.get(name).ok_or_else(|| {
+                RiptideError::Validation(format!("No credential for placeholder '{}'", name))
+            })?;
+            resolved.push_str(credential);
+            rest = &rest[start + end + 1..];
+        }
+        resolved.push_str(rest);
+        Ok(resolved)
+    }
+}
+
+/// Placeholder names in `value`, or why they are malformed
+fn placeholders(value: &str) -> Result<Vec<String>, String> {
+    let mut names = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("unterminated placeholder in '{}'", value))?;
+        let name = &rest[start + 2..start + end];
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("invalid placeholder '${{{}}}'", name));
+        }
+        names.push(name.to_string());
+        rest = &rest[start + end + 1..];
+    }
+    Ok(names)
+}
+
+/// Two-factor challenge shown to a [`TwoFactorPrompt`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TwoFactorChallenge {
+    /// Name of the flow asking
+    pub flow: String,
+    /// Page showing the code field
+    pub url: String,
+    /// Hint from the flow's `two_factor` step
+    pub prompt: Option<String>,
+}
+
+/// Source of two-factor codes: a terminal prompt, an authenticator, an
+/// SMS or email inbox
+#[async_trait]
+pub trait TwoFactorPrompt: Send + Sync {
+    /// Code to enter for `challenge`
+    async fn code(&self, challenge: &TwoFactorChallenge) -> RiptideResult<String>;
+}
+
+/// Runs login flows; implemented by [`BrowserFacade`]
+#[async_trait]
+pub(crate) trait LoginRunner: Send + Sync {
+    async fn log_in(
+        &self,
+        flow: &LoginFlow,
+        credentials: &LoginCredentials,
+        two_factor: Option<&dyn TwoFactorPrompt>,
+    ) -> RiptideResult<AuthSession>;
+}
+
+#[async_trait]
+impl LoginRunner for BrowserFacade {
+    async fn log_in(
+        &self,
+        flow: &LoginFlow,
+        credentials: &LoginCredentials,
+        two_factor: Option<&dyn TwoFactorPrompt>,
+    ) -> RiptideResult<AuthSession> {
+        self.run_login_flow(flow, credentials, two_factor).await
+    }
+}
+
+/// Session provider logging in with login flows
+///
+/// Each domain is served by the first flow covering it; domains without a
+/// flow are crawled anonymously. Sessions are minted on first use and
+/// minted again on renewal, at most `max_logins` times per flow. Renewals
+/// right after a login reuse its session, so pages rejected at the same
+/// time trigger a single login. Flows log in independently: a login waiting
+/// on a two-factor code holds up only requests to its own flow.
+pub struct LoginFlowSessionProvider {
+    runner: Arc<dyn LoginRunner>,
+    flows: Vec<LoginFlow>,
+    credentials: LoginCredentials,
+    two_factor: Option<Arc<dyn TwoFactorPrompt>>,
+    max_logins: u32,
+    relogin_interval: Duration,
+    logins: Mutex<HashMap<String, Arc<Mutex<FlowLogin>>>>,
+}
+
+/// Login state of one flow
+#[derive(Default)]
+struct FlowLogin {
+    session: Option<AuthSession>,
+    attempts: u32,
+    last_attempt: Option<Instant>,
+}
+
+impl LoginFlowSessionProvider {
+    /// Provider running flows in `browser` with `credentials`
+    pub fn new(browser: BrowserFacade, credentials: LoginCredentials) -> Self {
+        Self::with_runner(Arc::new(browser), credentials)
+    }
+
+    pub(crate) fn with_runner(runner: Arc<dyn LoginRunner>, credentials: LoginCredentials) -> Self {
+        Self {
+            runner,
+            flows: Vec::new(),
+            credentials,
+            two_factor: None,
+            max_logins: DEFAULT_MAX_LOGINS,
+            relogin_interval: DEFAULT_RELOGIN_INTERVAL,
+            logins: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Log in to the flow's domain with `flow`
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error when the flow is invalid or a credential
+    /// it needs is missing.
+    pub fn with_flow(mut self, flow: LoginFlow) -> RiptideResult<Self> {
+        flow.validate()?;
+        self.credentials.check(&flow)?;
+        self.flows.push(flow);
+        Ok(self)
+    }
+
+    /// Ask `prompt` for two-factor codes
+    pub fn with_two_factor(mut self, prompt: Arc<dyn TwoFactorPrompt>) -> Self {
+        self.two_factor = Some(prompt);
+        self
+    }
+
+    /// Logins attempted per flow before giving up, failed ones included
+    pub fn with_max_logins(mut self, max_logins: u32) -> Self {
+        self.max_logins = max_logins;
+        self
+    }
+
+    fn flow_for(&self, domain: &str) -> Option<&LoginFlow> {
+        self.flows.iter().find(|flow| flow.covers(domain))
+    }
+
+    /// Login state of `flow`, locked on its own so the map is only held
+    /// while looking it up
+    async fn flow_login(&self, flow: &LoginFlow) -> Arc<Mutex<FlowLogin>> {
+        self.logins
+            .lock()
+            .await
+            .entry(flow.name.clone())
+            .or_default()
+            .clone()
+    }
+
+    /// Run `flow` unless it used up its logins
+    async fn log_in(
+        &self,
+        flow: &LoginFlow,
+        login: &mut FlowLogin,
+    ) -> riptide_types::error::Result<Option<AuthSession>> {
+        if login.attempts >= self.max_logins {
+            warn!(flow = %flow.name, attempts = login.attempts, "Login flow gave up");
+            login.session = None;
+            return Ok(None);
+        }
+        login.attempts += 1;
+        login.last_attempt = Some(Instant::now());
+
+        match self
+            .runner
+            .log_in(flow, &self.credentials, self.two_factor.as_deref())
+            .await
+        {
+            Ok(session) => {
+                info!(flow = %flow.name, attempt = login.attempts, "Logged in with login flow");
+                login.session = Some(session.clone());
+                Ok(Some(session))
+            }
+            Err(e) => {
+                login.session = None;
+                Err(riptide_types::RiptideError::Custom(format!(
+                    "Login flow '{}' failed: {}",
+                    flow.name, e
+                )))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SessionProvider for LoginFlowSessionProvider {
+    async fn session(&self, domain: &str) -> riptide_types::error::Result<Option<AuthSession>> {
+        let Some(flow) = self.flow_for(domain) else {
+            return Ok(None);
+        };
+        let login = self.flow_login(flow).await;
+        let mut login = login.lock().await;
+        if let Some(session) = &login.session {
+            return Ok(Some(session.clone()));
+        }
+        if login.attempts >= self.max_logins {
+            return Err(riptide_types::RiptideError::Custom(format!(
+                "Login flow '{}' failed {} times",
+                flow.name, login.attempts
+            )));
+        }
+        self.log_in(flow, &mut login).await
+    }
+
+    async fn renew(&self, domain: &str) -> riptide_types::error::Result<Option<AuthSession>> {
+        let Some(flow) = self.flow_for(domain) else {
+            return Ok(None);
+        };
+        let login = self.flow_login(flow).await;
+        let mut login = login.lock().await;
+        let recent = login
+            .last_attempt
+            .is_some_and(|at| at.elapsed() < self.relogin_interval);
+        if let (true, Some(session)) = (recent, &login.session) {
+            return Ok(Some(session.clone()));
+        }
+        self.log_in(flow, &mut login).await
+    }
+}
+
+fn http_url_problem(value: &str) -> Option<String> {
+    match Url::parse(value) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => None,
+        Ok(url) => Some(format!(
+            "unsupported scheme '{}' in '{}'",
+            url.scheme(),
+            value
+        )),
+        Err(e) => Some(format!("invalid URL '{}': {}", value, e)),
+    }
+}
+
+fn duration_problem(field: &str, ms: u64) -> Option<String> {
+    (ms > MAX_WAIT_MS).then(|| format!("{} must be at most {}", field, MAX_WAIT_MS))
+}
+
+fn invalid(path: &str, message: &str) -> RiptideError {
+    RiptideError::Validation(format!("Login flow {}: {}", path, message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::facades::session_recorder::RecordedEvent;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    const SHOP_FLOW: &str = r##"
+name: shop
+login_url: https://shop.example.com/login
+steps:
+  - action: fill
+    selector: "#email"
+    value: "${username}"
+  - action: fill
+    selector: "#password"
+    value: "${password}"
+  - action: click
+    selector: "button[type=submit]"
+  - action: two_factor
+    selector: "input[name=otp]"
+    submit: "#verify"
+success: { element: ".account-menu" }
+"##;
+
+    fn credentials() -> LoginCredentials {
+        LoginCredentials::new()
+            .with("username", "ada@example.com")
+            .with("password", "hunter2")
+    }
+
+    #[test]
+    fn test_parse_yaml_flow() {
+        let flow = LoginFlow::from_yaml(SHOP_FLOW).unwrap();
+        assert_eq!(flow.domain().as_deref(), Some("shop.example.com"));
+        assert_eq!(flow.timeout_ms, 60_000);
+        assert_eq!(
+            flow.credential_names().into_iter().collect::<Vec<_>>(),
+            vec!["password", "username"]
+        );
+        assert_eq!(
+            flow.steps[2].to_action(),
+            Some(BrowserAction::Click {
+                selector: "button[type=submit]".to_string()
+            })
+        );
+        assert!(matches!(
+            &flow.steps[3],
+            LoginStep::TwoFactor {
+                timeout_ms: 10_000,
+                submit: Some(_),
+                ..
+            }
+        ));
+
+        assert!(flow.covers("shop.example.com"));
+        assert!(flow.covers("eu.shop.example.com"));
+        assert!(!flow.covers("myshop.example.com"));
+        assert!(!flow.covers("example.com"));
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let cases = [
+            ("login_url: ftp://example.com", "unsupported scheme"),
+            ("steps: []", "flow has no steps"),
+            (
+                "steps:\n  - action: fill\n    selector: \"#x\"\n    value: \"${pass word}\"",
+                "invalid placeholder",
+            ),
+            (
+                "steps:\n  - action: fill\n    selector: \"#x\"\n    value: \"${password\"",
+                "unterminated placeholder",
+            ),
+            (
+                "steps:\n  - action: two_factor\n    selector: \"##otp\"",
+                "steps[0]: invalid CSS selector",
+            ),
+            (
+                "steps:\n  - action: hover\n    selector: a",
+                "Invalid login flow",
+            ),
+            ("success: { url_contains: \"\" }", "Login flow success"),
+        ];
+        for (override_yaml, expected) in cases {
+            let mut flow: serde_yaml::Value = serde_yaml::from_str(SHOP_FLOW).unwrap();
+            let patch: serde_yaml::Mapping = serde_yaml::from_str(override_yaml).unwrap();
+            for (key, value) in patch {
+                flow[key.as_str().unwrap()] = value;
+            }
+            let yaml = serde_yaml::to_string(&flow).unwrap();
+            let err = LoginFlow::from_yaml(&yaml).unwrap_err().to_string();
+            assert!(err.contains(expected), "{}: {}", override_yaml, err);
+        }
+    }
+
+    #[test]
+    fn test_credentials() {
+        let credentials = credentials();
+        assert_eq!(
+            credentials.resolve("${username}:${password}!").unwrap(),
+            "ada@example.com:hunter2!"
+        );
+        assert_eq!(credentials.resolve("plain").unwrap(), "plain");
+        assert!(credentials.resolve("${token}").is_err());
+        assert!(!format!("{:?}", credentials).contains("hunter2"));
+
+        let flow = LoginFlow::from_yaml(SHOP_FLOW).unwrap();
+        assert!(credentials.check(&flow).is_ok());
+        let err = LoginCredentials::new()
+            .with("username", "ada")
+            .check(&flow)
+            .unwrap_err();
+        assert!(err.to_string().contains("needs credentials: password"));
+    }
+
+    #[test]
+    fn test_flow_from_recording() {
+        let event = |kind| RecordedEvent { offset_ms: 0, kind };
+        let recording = SessionRecording {
+            session_id: "s1".to_string(),
+            started_at: chrono::Utc::now(),
+            finished_at: None,
+            events: vec![
+                event(RecordedEventKind::Navigation {
+                    url: "https://app.example.com/signin".to_string(),
+                    error: None,
+                }),
+                event(RecordedEventKind::Action {
+                    action: BrowserAction::Type {
+                        selector: "#user".to_string(),
+                        text: "ada".to_string(),
+                    },
+                    error: None,
+                }),
+                event(RecordedEventKind::Action {
+                    action: BrowserAction::Type {
+                        selector: "input[type=password]".to_string(),
+                        text: "hunter2".to_string(),
+                    },
+                    error: None,
+                }),
+                event(RecordedEventKind::Action {
+                    action: BrowserAction::Click {
+                        selector: "#missing".to_string(),
+                    },
+                    error: Some("Element not found".to_string()),
+                }),
+                event(RecordedEventKind::Action {
+                    action: BrowserAction::Submit {
+                        selector: "form".to_string(),
+                    },
+                    error: None,
+                }),
+                event(RecordedEventKind::ConsoleError {
+                    message: "boom".to_string(),
+                }),
+            ],
+            error: None,
+        };
+
+        let flow = LoginFlow::from_recording(
+            "app",
+            &recording,
+            WaitCondition::UrlContains("/home".to_string()),
+        )
+        .unwrap();
+        assert_eq!(flow.login_url, "https://app.example.com/signin");
+        assert_eq!(
+            flow.steps,
+            vec![
+                LoginStep::Fill {
+                    selector: "#user".to_string(),
+                    value: "ada".to_string()
+                },
+                LoginStep::Fill {
+                    selector: "input[type=password]".to_string(),
+                    value: "${password}".to_string()
+                },
+                LoginStep::Submit {
+                    selector: "form".to_string()
+                },
+            ]
+        );
+
+        let empty = SessionRecording {
+            events: Vec::new(),
+            ..recording
+        };
+        assert!(LoginFlow::from_recording("app", &empty, flow.success.clone()).is_err());
+    }
+
+    /// Runner minting a new `sid` cookie per login
+    #[derive(Default)]
+    struct CountingRunner {
+        logins: AtomicU32,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl LoginRunner for CountingRunner {
+        async fn log_in(
+            &self,
+            flow: &LoginFlow,
+            _credentials: &LoginCredentials,
+            _two_factor: Option<&dyn TwoFactorPrompt>,
+        ) -> RiptideResult<AuthSession> {
+            let n = self.logins.fetch_add(1, Ordering::SeqCst) + 1;
+            if self.fail {
+                return Err(RiptideError::Timeout);
+            }
+            Ok(flow.auth_session([("sid", format!("s{}", n))]))
+        }
+    }
+
+    fn provider(runner: Arc<CountingRunner>) -> LoginFlowSessionProvider {
+        LoginFlowSessionProvider::with_runner(runner, credentials())
+            .with_flow(LoginFlow::from_yaml(SHOP_FLOW).unwrap())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_provider_logs_in_once_and_renews() {
+        let runner = Arc::new(CountingRunner::default());
+        let mut provider = provider(runner.clone());
+
+        assert!(provider
+            .session("other.example.com")
+            .await
+            .unwrap()
+            .is_none());
+
+        let session = provider.session("shop.example.com").await.unwrap().unwrap();
+        assert_eq!(session.cookie_header().as_deref(), Some("sid=s1"));
+        assert_eq!(
+            session.login_url.as_deref(),
+            Some("https://shop.example.com/login")
+        );
+        provider.session("eu.shop.example.com").await.unwrap();
+        assert_eq!(runner.logins.load(Ordering::SeqCst), 1);
+
+        // Renewals right after a login share it
+        let renewed = provider.renew("shop.example.com").await.unwrap().unwrap();
+        assert_eq!(renewed.cookie_header().as_deref(), Some("sid=s1"));
+
+        provider.relogin_interval = Duration::ZERO;
+        let renewed = provider.renew("shop.example.com").await.unwrap().unwrap();
+        assert_eq!(renewed.cookie_header().as_deref(), Some("sid=s2"));
+        provider.renew("shop.example.com").await.unwrap();
+
+        // Logins are capped per flow
+        assert!(provider.renew("shop.example.com").await.unwrap().is_none());
+        assert_eq!(runner.logins.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_provider_gives_up_after_failed_logins() {
+        let runner = Arc::new(CountingRunner {
+            fail: true,
+            ..Default::default()
+        });
+        let provider = provider(runner.clone()).with_max_logins(2);
+
+        for _ in 0..2 {
+            let err = provider.session("shop.example.com").await.unwrap_err();
+            assert!(err.to_string().contains("Login flow 'shop' failed"));
+        }
+        let err = provider.session("shop.example.com").await.unwrap_err();
+        assert!(err.to_string().contains("failed 2 times"));
+        assert_eq!(runner.logins.load(Ordering::SeqCst), 2);
+    }
+
+    /// Runner holding `shop` logins, as if waiting on a two-factor code,
+    /// until the gate opens
+    #[derive(Default)]
+    struct GatedRunner {
+        started: tokio::sync::Notify,
+        gate: tokio::sync::Notify,
+    }
+
+    #[async_trait]
+    impl LoginRunner for GatedRunner {
+        async fn log_in(
+            &self,
+            flow: &LoginFlow,
+            _credentials: &LoginCredentials,
+            _two_factor: Option<&dyn TwoFactorPrompt>,
+        ) -> RiptideResult<AuthSession> {
+            if flow.name == "shop" {
+                self.started.notify_one();
+                self.gate.notified().await;
+            }
+            Ok(flow.auth_session([("sid", flow.name.clone())]))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_waiting_login_holds_up_only_its_flow() {
+        let runner = Arc::new(GatedRunner::default());
+        let blog = LoginFlow {
+            name: "blog".to_string(),
+            login_url: "https://blog.example.com/login".to_string(),
+            ..LoginFlow::from_yaml(SHOP_FLOW).unwrap()
+        };
+        let provider = Arc::new(
+            LoginFlowSessionProvider::with_runner(runner.clone(), credentials())
+                .with_flow(LoginFlow::from_yaml(SHOP_FLOW).unwrap())
+                .unwrap()
+                .with_flow(blog)
+                .unwrap(),
+        );
+
+        let shop = tokio::spawn({
+            let provider = provider.clone();
+            async move { provider.session("shop.example.com").await }
+        });
+        runner.started.notified().await;
+
+        let session =
+            tokio::time::timeout(Duration::from_secs(5), provider.session("blog.example.com"))
+                .await
+                .expect("blog login waited on the shop login")
+                .unwrap()
+                .unwrap();
+        assert_eq!(session.cookie_header().as_deref(), Some("sid=blog"));
+
+        runner.gate.notify_one();
+        let session = shop.await.unwrap().unwrap().unwrap();
+        assert_eq!(session.cookie_header().as_deref(), Some("sid=shop"));
+    }
+
+    #[test]
+    fn test_provider_requires_credentials() {
+        let result = LoginFlowSessionProvider::with_runner(
+            Arc::new(CountingRunner::default()),
+            LoginCredentials::new(),
+        )
+        .with_flow(LoginFlow::from_yaml(SHOP_FLOW).unwrap());
+        assert!(result.is_err());
+    }
+}
@@ -3,9 +3,13 @@
 //! This module contains specialized facades that provide simplified
 //! interfaces for common web scraping tasks.
 
+pub mod action_script;
+pub mod annotation;
 pub mod browser;
 pub mod browser_metrics;
+pub mod classification;
 pub mod crawl_facade;
+pub mod dom_snapshot;
 pub mod engine;
 pub mod extraction;
 pub mod extraction_authz;
@@ -13,6 +17,7 @@ pub mod extraction_metrics;
 pub mod extractor;
 pub mod intelligence;
 pub mod llm;
+pub mod login_flow;
 pub mod pdf;
 pub mod pipeline;
 pub mod pipeline_metrics;
@@ -24,9 +29,15 @@ pub mod render_strategy;
 pub mod scraper;
 pub mod session;
 pub mod session_metrics;
+pub mod session_recorder;
 pub mod spider;
 pub mod table;
+#[cfg(feature = "llm")]
+pub mod text_embedder;
 pub mod trace;
+pub mod translation;
+#[cfg(feature = "llm")]
+pub mod url_scorer;
 #[cfg(feature = "workers")]
 pub mod workers;
 
@@ -49,10 +60,21 @@ pub use extraction_metrics::{ExtractionMetricsExt, MetricsExtractionFacade};
 pub use pipeline_metrics::MetricsPipelineFacade;
 pub use session_metrics::MetricsSessionFacade;
 
+pub use action_script::{ActionScript, ScriptStep, WaitCondition};
+#[cfg(feature = "llm")]
+pub use annotation::LlmAnnotator;
+pub use annotation::{AnnotationConfig, AnnotationEnricher};
 pub use browser::{
     BrowserAction, BrowserFacade, BrowserSession, Cookie, ImageFormat, ScreenshotOptions,
 };
+#[cfg(feature = "llm")]
+pub use classification::LlmClassifier;
+pub use classification::{
+    CentroidClassifier, ClassificationConfig, ClassificationEnricher, ClassificationMethod,
+    Taxonomy, TaxonomyCategory,
+};
 pub use crawl_facade::{CrawlFacade, CrawlMode, CrawlResult};
+pub use dom_snapshot::{DomSnapshotFacade, PreparedSnapshot, SnapshotLimits};
 pub use engine::{
     EngineCapability, EngineConfig, EngineFacade, EngineSelectionCriteria, EngineStats,
 };
@@ -62,6 +84,10 @@ pub use extractor::{
     ExtractedData, ExtractionFacade, FieldSpec, FieldType, HtmlExtractionOptions,
     PdfExtractionOptions, Schema,
 };
+pub use login_flow::{
+    LoginCredentials, LoginFlow, LoginFlowSessionProvider, LoginStep, TwoFactorChallenge,
+    TwoFactorPrompt,
+};
 // Re-export ExtractionMethod from types for convenience
 pub use llm::{
     LlmCapabilities, LlmFacade, LlmProvider, LlmRequest, LlmResponse,
@@ -88,16 +114,26 @@ pub use render_strategy::RenderStrategyFacade;
 pub use riptide_types::ExtractionMethod;
 pub use scraper::ScraperFacade;
 pub use session::{SessionConfig, SessionEvent, SessionFacade};
-pub use spider::{CrawlSummary, SpiderFacade, SpiderPreset};
+pub use session_recorder::{
+    RecordedEvent, RecordedEventKind, RecorderConfig, Recording, SessionRecorder, SessionRecording,
+};
+pub use spider::{CrawlOverrides, CrawlSummary, SpiderFacade, SpiderPreset};
 pub use table::{
     TableCacheService, TableExtractionOptions as FacadeTableExtractionOptions,
     TableExtractionRequest, TableFacade, TableFormat, TableMetadata as FacadeTableMetadata,
     TableSummary as FacadeTableSummary,
 };
+#[cfg(feature = "llm")]
+pub use text_embedder::LlmEmbedder;
 pub use trace::{
     CompleteTrace, SpanData, SpanEvent, TelemetryBackend, TraceData, TraceFacade, TraceMetadata,
     TraceQuery,
 };
+#[cfg(feature = "llm")]
+pub use translation::LlmTranslator;
+pub use translation::{TranslationConfig, TranslationEnricher};
+#[cfg(feature = "llm")]
+pub use url_scorer::LlmUrlScorer;
 #[cfg(feature = "workers")]
 pub use workers::{
     AuthorizationContext, JobFilter, JobResult, QueueStats, ScheduledJobRequest, SubmitJobRequest,
@@ -193,6 +193,20 @@ impl PdfFacade {
             description: None,
             html: None,
             parser_metadata: None,
+            provenance: None,
+            translation: None,
+            structured_data: Vec::new(),
+            annotations: None,
+            feed: None,
+            feed_links: Vec::new(),
+            classification: None,
+            clean_html: None,
+            media_assets: Vec::new(),
+            embedded_media: Vec::new(),
+            images: Vec::new(),
+            references: Vec::new(),
+            language_spans: Vec::new(),
+            quality_report: None,
         };
 
         // 5. Build statistics
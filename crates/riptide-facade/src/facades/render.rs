@@ -213,6 +213,7 @@ impl RenderFacade {
             render_time_ms: 100,
             actions_executed: Vec::new(),
             wait_conditions_met: Vec::new(),
+            frames: Vec::new(),
         };
 
         Ok(RenderResult {
@@ -1,7 +1,8 @@
 //! Basic web scraper facade implementation.
 
+use crate::authorization::GeoRestriction;
 use crate::{config::RiptideConfig, error::RiptideResult, RiptideError};
-use riptide_fetch::FetchEngine;
+use riptide_fetch::{FetchEngine, PerHostFetchEngine};
 use std::sync::Arc;
 use url::Url;
 
@@ -13,6 +14,8 @@ use url::Url;
 pub struct ScraperFacade {
     config: Arc<RiptideConfig>,
     client: Arc<FetchEngine>,
+    per_host: Option<Arc<PerHostFetchEngine>>,
+    geo_restriction: Option<GeoRestriction>,
 }
 
 impl ScraperFacade {
@@ -28,9 +31,32 @@ impl ScraperFacade {
         Ok(Self {
             config: Arc::new(config),
             client: Arc::new(client),
+            per_host: None,
+            geo_restriction: None,
         })
     }
 
+    /// Fetch through `engine`, with its per-host circuit breakers, rate
+    /// limits and metrics, instead of the facade's own client.
+    pub fn with_per_host_engine(mut self, engine: Arc<PerHostFetchEngine>) -> Self {
+        self.per_host = Some(engine);
+        self
+    }
+
+    /// Deny fetches to servers in blocked countries or networks.
+    pub fn with_geo_restriction(mut self, restriction: GeoRestriction) -> Self {
+        self.geo_restriction = Some(restriction);
+        self
+    }
+
+    /// Enforce the geo restriction, if one is configured.
+    async fn check_geo(&self, url: &str) -> RiptideResult<()> {
+        if let Some(restriction) = &self.geo_restriction {
+            restriction.check(url).await?;
+        }
+        Ok(())
+    }
+
     /// Fetch HTML content from a URL.
     ///
     /// # Arguments
@@ -45,6 +71,7 @@ impl ScraperFacade {
     ///
     /// Returns an error if:
     /// - The URL is invalid
+    /// - The server location is blocked by the geo restriction
     /// - The request fails
     /// - The response is not valid UTF-8
     ///
@@ -68,12 +95,14 @@ impl ScraperFacade {
 
         // Validate URL
         let _ = Url::parse(url_str)?;
+        self.check_geo(url_str).await?;
 
         // Fetch as text with timeout enforcement
-        self.client
-            .fetch_text(url_str)
-            .await
-            .map_err(|e| RiptideError::extraction(format!("Failed to fetch HTML: {}", e)))
+        let html = match &self.per_host {
+            Some(engine) => engine.fetch_text(url_str).await,
+            None => self.client.fetch_text(url_str).await,
+        };
+        html.map_err(|e| RiptideError::extraction(format!("Failed to fetch HTML: {}", e)))
     }
 
     /// Fetch raw bytes from a URL.
@@ -94,12 +123,14 @@ impl ScraperFacade {
 
         // Validate URL
         let _ = Url::parse(url_str)?;
+        self.check_geo(url_str).await?;
 
         // Fetch as bytes with timeout enforcement
-        self.client
-            .fetch_bytes(url_str)
-            .await
-            .map_err(|e| RiptideError::extraction(format!("Failed to fetch bytes: {}", e)))
+        let bytes = match &self.per_host {
+            Some(engine) => engine.fetch_bytes(url_str).await,
+            None => self.client.fetch_bytes(url_str).await,
+        };
+        bytes.map_err(|e| RiptideError::extraction(format!("Failed to fetch bytes: {}", e)))
     }
 
     /// Get the current configuration.
@@ -141,4 +172,34 @@ mod tests {
             assert!(result.is_err());
         });
     }
+
+    #[tokio::test]
+    async fn test_per_host_engine_records_fetches() {
+        use riptide_fetch::{CircuitBreakerConfig, RateLimitConfig, RetryConfig};
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<p>hi</p>"))
+            .mount(&server)
+            .await;
+        let engine = Arc::new(
+            PerHostFetchEngine::new(
+                RetryConfig::default(),
+                CircuitBreakerConfig::default(),
+                RateLimitConfig::default(),
+            )
+            .unwrap(),
+        );
+        let scraper = ScraperFacade::new(RiptideConfig::default())
+            .await
+            .unwrap()
+            .with_per_host_engine(engine.clone());
+
+        let html = scraper.fetch_html(server.uri()).await.unwrap();
+
+        assert_eq!(html, "<p>hi</p>");
+        assert_eq!(engine.get_all_metrics().await.total_success, 1);
+    }
 }
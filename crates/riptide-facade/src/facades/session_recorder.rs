@@ -0,0 +1,414 @@
+//! Session replay recording for browser automation debugging.
+//!
+//! A [`SessionRecorder`] captures what happened in a browser session
+//! (navigations, [`BrowserAction`]s with their errors, console errors and
+//! periodic screenshots) into a [`SessionRecording`] bundle stored through
+//! the [`BlobStorage`] port. Bundles are keyed by browser session id, so a
+//! failed authenticated flow can be replayed step by step afterwards.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use riptide_facade::facades::{BrowserFacade, SessionRecorder};
+//!
+//! let recorder = Arc::new(SessionRecorder::new(blob_storage.clone()));
+//! let facade = BrowserFacade::new(config).await?.with_recorder(recorder.clone());
+//!
+//! let session = facade.launch().await?;
+//! let session_id = session.session_id().to_string();
+//! facade.navigate(&session, "https://example.com/login").await?;
+//! facade.close(session).await?;
+//!
+//! let recording = recorder.load(&session_id).await?;
+//! ```
+
+use crate::error::RiptideResult;
+use crate::facades::browser::BrowserAction;
+use crate::facades::login_flow::REDACTED_VALUE;
+use chrono::{DateTime, Utc};
+use riptide_types::ports::BlobStorage;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Blob key prefix for recordings
+pub const RECORDING_PREFIX: &str = "recordings";
+
+/// Selector fragments of fields whose typed text is never recorded
+const SECRET_FIELD_HINTS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "otp",
+    "one-time-code",
+    "2fa",
+    "mfa",
+    "cvv",
+    "cvc",
+];
+
+/// Whether text typed into `selector` is a credential or one-time code
+pub(crate) fn is_secret_field(selector: &str) -> bool {
+    let selector = selector.to_ascii_lowercase();
+    SECRET_FIELD_HINTS
+        .iter()
+        .any(|hint| selector.contains(hint))
+}
+
+/// Script installed on every document to collect console errors
+pub(crate) const CONSOLE_HOOK_SCRIPT: &str = r#"(() => {
+  if (window.__riptideErrors) return;
+  window.__riptideErrors = [];
+  const push = (msg) => window.__riptideErrors.push(String(msg));
+  const original = console.error;
+  console.error = (...args) => { push(args.join(' ')); original.apply(console, args); };
+  window.addEventListener('error', (e) => push(e.message));
+  window.addEventListener('unhandledrejection', (e) => push('Unhandled rejection: ' + e.reason));
+})();"#;
+
+/// Script returning and clearing the console errors collected so far
+pub(crate) const CONSOLE_DRAIN_SCRIPT: &str =
+    "(() => { const errors = window.__riptideErrors || []; window.__riptideErrors = []; return errors; })()";
+
+/// Recording settings
+#[derive(Debug, Clone)]
+pub struct RecorderConfig {
+    /// Minimum time between screenshots
+    pub screenshot_interval: Duration,
+    /// Screenshots kept per session; later ones are skipped
+    pub max_screenshots: usize,
+}
+
+impl Default for RecorderConfig {
+    fn default() -> Self {
+        Self {
+            screenshot_interval: Duration::from_secs(5),
+            max_screenshots: 20,
+        }
+    }
+}
+
+/// Replayable record of one browser session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionRecording {
+    pub session_id: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Events in the order they happened
+    pub events: Vec<RecordedEvent>,
+    /// Last navigation or action error, if any step failed
+    pub error: Option<String>,
+}
+
+/// One step of a recorded session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Milliseconds since the session started
+    pub offset_ms: u64,
+    #[serde(flatten)]
+    pub kind: RecordedEventKind,
+}
+
+/// What happened at a recorded step
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecordedEventKind {
+    Navigation {
+        url: String,
+        error: Option<String>,
+    },
+    Action {
+        action: BrowserAction,
+        error: Option<String>,
+    },
+    ConsoleError {
+        message: String,
+    },
+    Screenshot {
+        key: String,
+        url: String,
+    },
+}
+
+/// Records browser sessions into blob storage
+pub struct SessionRecorder {
+    storage: Arc<dyn BlobStorage>,
+    config: RecorderConfig,
+}
+
+impl SessionRecorder {
+    /// Create a recorder with the default settings
+    pub fn new(storage: Arc<dyn BlobStorage>) -> Self {
+        Self {
+            storage,
+            config: RecorderConfig::default(),
+        }
+    }
+
+    /// Set the recording settings
+    pub fn with_config(mut self, config: RecorderConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Start recording the session `session_id`
+    pub fn start(&self, session_id: impl Into<String>) -> Arc<Recording> {
+        Arc::new(Recording {
+            storage: self.storage.clone(),
+            config: self.config.clone(),
+            started: Instant::now(),
+            state: Mutex::new(RecordingState {
+                recording: SessionRecording {
+                    session_id: session_id.into(),
+                    started_at: Utc::now(),
+                    finished_at: None,
+                    events: Vec::new(),
+                    error: None,
+                },
+                last_screenshot: None,
+                screenshots: 0,
+            }),
+        })
+    }
+
+    /// Stored recording of the session `session_id`
+    pub async fn load(&self, session_id: &str) -> RiptideResult<Option<SessionRecording>> {
+        let Some((data, _)) = self.storage.get(&bundle_key(session_id)).await? else {
+            return Ok(None);
+        };
+        let recording = serde_json::from_slice(&data)
+            .map_err(|e| anyhow::anyhow!("Invalid session recording: {}", e))?;
+        Ok(Some(recording))
+    }
+}
+
+/// Blob key of a session's recording bundle
+pub fn bundle_key(session_id: &str) -> String {
+    format!("{}/{}/recording.json", RECORDING_PREFIX, session_id)
+}
+
+/// Recording in progress for one browser session
+pub struct Recording {
+    storage: Arc<dyn BlobStorage>,
+    config: RecorderConfig,
+    started: Instant,
+    state: Mutex<RecordingState>,
+}
+
+struct RecordingState {
+    recording: SessionRecording,
+    last_screenshot: Option<Instant>,
+    screenshots: usize,
+}
+
+impl Recording {
+    /// Id of the recorded session
+    pub fn session_id(&self) -> String {
+        self.state().recording.session_id.clone()
+    }
+
+    /// Record a navigation and its outcome
+    pub fn record_navigation(&self, url: &str, error: Option<String>) {
+        self.record_failure(&error);
+        self.push(RecordedEventKind::Navigation {
+            url: url.to_string(),
+            error,
+        });
+    }
+
+    /// Record an action and its outcome
+    ///
+    /// Text typed into password, token and one-time-code fields is stored
+    /// as [`REDACTED_VALUE`].
+    pub fn record_action(&self, action: &BrowserAction, error: Option<String>) {
+        self.record_failure(&error);
+        let action = match action {
+            BrowserAction::Type { selector, .. } if is_secret_field(selector) => {
+                BrowserAction::Type {
+                    selector: selector.clone(),
+                    text: REDACTED_VALUE.to_string(),
+                }
+            }
+            action => action.clone(),
+        };
+        self.push(RecordedEventKind::Action { action, error });
+    }
+
+    /// Record console errors collected from the page
+    pub fn record_console_errors(&self, messages: impl IntoIterator<Item = String>) {
+        for message in messages {
+            self.push(RecordedEventKind::ConsoleError { message });
+        }
+    }
+
+    /// Whether the next screenshot is due
+    pub fn screenshot_due(&self) -> bool {
+        let state = self.state();
+        state.screenshots < self.config.max_screenshots
+            && state
+                .last_screenshot
+                .is_none_or(|at| at.elapsed() >= self.config.screenshot_interval)
+    }
+
+    /// Store a PNG screenshot and record it
+    pub async fn record_screenshot(&self, png: &[u8]) -> RiptideResult<()> {
+        let (session_id, index) = {
+            let mut state = self.state();
+            state.last_screenshot = Some(Instant::now());
+            state.screenshots += 1;
+            (state.recording.session_id.clone(), state.screenshots)
+        };
+        let key = format!(
+            "{}/{}/screenshot-{:03}.png",
+            RECORDING_PREFIX, session_id, index
+        );
+        let blob = self.storage.put(&key, png, "image/png").await?;
+        self.push(RecordedEventKind::Screenshot {
+            key: blob.key,
+            url: blob.url,
+        });
+        Ok(())
+    }
+
+    /// Snapshot of the recording so far
+    pub fn snapshot(&self) -> SessionRecording {
+        self.state().recording.clone()
+    }
+
+    /// Finish the recording and store the bundle
+    pub async fn finish(&self) -> RiptideResult<SessionRecording> {
+        let recording = {
+            let mut state = self.state();
+            state.recording.finished_at = Some(Utc::now());
+            state.recording.clone()
+        };
+        let data = serde_json::to_vec(&recording)
+            .map_err(|e| anyhow::anyhow!("Failed to serialize session recording: {}", e))?;
+        self.storage
+            .put(
+                &bundle_key(&recording.session_id),
+                &data,
+                "application/json",
+            )
+            .await?;
+        Ok(recording)
+    }
+
+    fn record_failure(&self, error: &Option<String>) {
+        if let Some(error) = error {
+            self.state().recording.error = Some(error.clone());
+        }
+    }
+
+    fn push(&self, kind: RecordedEventKind) {
+        let offset_ms = self.started.elapsed().as_millis() as u64;
+        self.state()
+            .recording
+            .events
+            .push(RecordedEvent { offset_ms, kind });
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, RecordingState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::ports::InMemoryBlobStorage;
+
+    fn recorder(config: RecorderConfig) -> (Arc<InMemoryBlobStorage>, SessionRecorder) {
+        let storage = Arc::new(InMemoryBlobStorage::new());
+        let recorder = SessionRecorder::new(storage.clone()).with_config(config);
+        (storage, recorder)
+    }
+
+    #[tokio::test]
+    async fn test_recording_round_trip() {
+        let (storage, recorder) = recorder(RecorderConfig::default());
+        let recording = recorder.start("session-1");
+
+        recording.record_navigation("https://example.com/login", None);
+        recording.record_action(
+            &BrowserAction::Type {
+                selector: "#email".to_string(),
+                text: "user@example.com".to_string(),
+            },
+            None,
+        );
+        recording.record_action(
+            &BrowserAction::Type {
+                selector: "input[name=Password]".to_string(),
+                text: "hunter2".to_string(),
+            },
+            None,
+        );
+        recording.record_action(
+            &BrowserAction::Click {
+                selector: "#submit".to_string(),
+            },
+            Some("Element not found".to_string()),
+        );
+        recording.record_console_errors(vec!["TypeError: x is undefined".to_string()]);
+        recording.record_screenshot(b"png").await.unwrap();
+        recording.finish().await.unwrap();
+
+        let loaded = recorder.load("session-1").await.unwrap().unwrap();
+        assert_eq!(loaded.events.len(), 6);
+        assert_eq!(loaded.error.as_deref(), Some("Element not found"));
+        assert!(loaded.finished_at.is_some());
+        assert!(matches!(
+            &loaded.events[1].kind,
+            RecordedEventKind::Action {
+                action: BrowserAction::Type { text, .. },
+                ..
+            } if text == "user@example.com"
+        ));
+        assert!(matches!(
+            &loaded.events[2].kind,
+            RecordedEventKind::Action {
+                action: BrowserAction::Type { text, .. },
+                ..
+            } if text == REDACTED_VALUE
+        ));
+        assert!(matches!(
+            &loaded.events[3].kind,
+            RecordedEventKind::Action { error: Some(_), .. }
+        ));
+        let RecordedEventKind::Screenshot { key, .. } = &loaded.events[5].kind else {
+            panic!("Expected a screenshot event");
+        };
+        assert_eq!(key, "recordings/session-1/screenshot-001.png");
+        assert!(storage.get(key).await.unwrap().is_some());
+
+        assert!(recorder.load("unknown").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_screenshot_schedule() {
+        let (_, recorder) = recorder(RecorderConfig {
+            screenshot_interval: Duration::from_secs(3600),
+            max_screenshots: 1,
+        });
+        let recording = recorder.start("session-2");
+
+        assert!(recording.screenshot_due());
+        recording.record_screenshot(b"png").await.unwrap();
+        assert!(!recording.screenshot_due());
+    }
+
+    #[test]
+    fn test_event_serialization() {
+        let event = RecordedEvent {
+            offset_ms: 42,
+            kind: RecordedEventKind::ConsoleError {
+                message: "boom".to_string(),
+            },
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "console_error");
+        assert_eq!(json["offset_ms"], 42);
+    }
+}
@@ -3,8 +3,18 @@
 //! Provides a high-level interface for the riptide-spider crawling engine
 //! with preset configurations and simplified API.
 
-use anyhow::Result;
-use riptide_spider::{config::SpiderPresets, CrawlState, PerformanceMetrics, Spider, SpiderConfig};
+use anyhow::{bail, Result};
+use riptide_config::CrawlManifest;
+use riptide_fetch::BandwidthLimiter;
+use riptide_reliability::ReliableHttpClient;
+use riptide_spider::{
+    config::SpiderPresets, scope::DepthRule, stream::DEFAULT_STREAM_BUFFER, ChangedSinceFilter,
+    ControlSnapshot, CrawlControl, CrawlRunState, CrawlState, CrawlStream, FrontierManager,
+    FrontierSnapshot, PerformanceMetrics, RobotsService, ScopeConfig, Spider, SpiderConfig,
+    UrlPattern,
+};
+use riptide_types::config::CrawlOptions;
+use riptide_types::ports::{CheckpointStore, SessionProvider, UrlPolicy};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use url::Url;
@@ -26,12 +36,127 @@ pub enum SpiderPreset {
     Authenticated,
 }
 
+/// Settings that apply to a single crawl.
+///
+/// Installed on the spider for the duration of one crawl and removed
+/// afterwards, so concurrent callers sharing a [`SpiderFacade`] do not see
+/// each other's sessions or policies.
+#[derive(Clone, Default)]
+pub struct CrawlOverrides {
+    session_provider: Option<Arc<dyn SessionProvider>>,
+    url_policy: Option<Arc<dyn UrlPolicy>>,
+    scope: Option<ScopeConfig>,
+    page_limit: Option<u64>,
+    changed_since: Option<ChangedSinceFilter>,
+    owner: Option<String>,
+}
+
+impl CrawlOverrides {
+    /// Overrides that change nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Authenticate fetches with sessions from `provider`.
+    pub fn with_session_provider(mut self, provider: Arc<dyn SessionProvider>) -> Self {
+        self.session_provider = Some(provider);
+        self
+    }
+
+    /// Check seeds and every discovered URL against `policy`.
+    pub fn with_url_policy(mut self, policy: Arc<dyn UrlPolicy>) -> Self {
+        self.url_policy = Some(policy);
+        self
+    }
+
+    /// Only crawl URLs within `scope`, instead of the spider's configured
+    /// scope.
+    pub fn with_scope(mut self, scope: ScopeConfig) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Stop the crawl after `max_pages` crawled pages.
+    pub fn with_page_limit(mut self, max_pages: u64) -> Self {
+        self.page_limit = Some(max_pages);
+        self
+    }
+
+    /// Only report pages that changed since `filter`'s cutoff.
+    pub fn with_changed_since(mut self, filter: ChangedSinceFilter) -> Self {
+        self.changed_since = Some(filter);
+        self
+    }
+
+    /// Record `owner` as the tenant the crawl runs for.
+    ///
+    /// Reported by [`SpiderFacade::control_snapshot`] while and after the
+    /// crawl runs.
+    pub fn with_owner(mut self, owner: impl Into<String>) -> Self {
+        self.owner = Some(owner.into());
+        self
+    }
+
+    /// Apply the scope, page cap and depth of `options`.
+    ///
+    /// `scope` patterns become regex scope rules and `spider_max_depth`
+    /// limits how far links are followed from the seeds.
+    pub fn with_crawl_options(mut self, options: &CrawlOptions) -> Self {
+        if options.scope.is_none() && options.spider_max_depth.is_none() {
+            return self;
+        }
+        let regexes = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|pattern| UrlPattern::Regex(pattern.clone()))
+                .collect()
+        };
+        let mut scope = ScopeConfig::default();
+        if let Some(crawl_scope) = &options.scope {
+            scope.include = regexes(&crawl_scope.include);
+            scope.exclude = regexes(&crawl_scope.exclude);
+            self.page_limit = crawl_scope.max_pages.map(|max| max as u64);
+        }
+        if let Some(max_depth) = options.spider_max_depth {
+            scope.depth_rules.push(DepthRule {
+                pattern: UrlPattern::Glob("**".to_string()),
+                max_depth: max_depth as u32,
+            });
+        }
+        self.with_scope(scope)
+    }
+
+    fn install(self, spider: &mut Spider) -> Result<()> {
+        spider.set_scope(self.scope.as_ref())?;
+        spider.set_page_limit(self.page_limit);
+        spider.set_session_provider(self.session_provider);
+        spider.set_url_policy(self.url_policy);
+        spider.set_changed_since(self.changed_since);
+        spider.set_owner(self.owner);
+        Ok(())
+    }
+
+    fn remove(spider: &mut Spider) {
+        spider.set_owner(None);
+        spider.set_changed_since(None);
+        spider.set_session_provider(None);
+        spider.set_url_policy(None);
+        spider.set_page_limit(None);
+        // The configured rules compiled when the spider was built
+        let _ = spider.set_scope(None);
+    }
+}
+
 /// High-level facade for web crawling operations.
 ///
 /// Wraps the riptide-spider engine with a simplified interface and preset configurations.
 #[derive(Clone)]
 pub struct SpiderFacade {
     spider: Arc<Mutex<Spider>>,
+    /// Frontier handle, readable while a crawl holds the spider lock
+    frontier: Arc<FrontierManager>,
+    /// Control handle, usable while a crawl holds the spider lock
+    control: Arc<CrawlControl>,
 }
 
 impl SpiderFacade {
@@ -66,6 +191,29 @@ impl SpiderFacade {
     /// # }
     /// ```
     pub async fn from_preset(preset: SpiderPreset, base_url: Url) -> Result<Self> {
+        let spider = Spider::new(Self::preset_config(preset, base_url)).await?;
+        Ok(Self::from_spider(spider))
+    }
+
+    /// Create a new spider from a preset that reads robots.txt through a
+    /// shared `robots` service, so files already fetched by other components
+    /// are not fetched again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if spider initialization fails.
+    pub async fn from_preset_with_robots(
+        preset: SpiderPreset,
+        base_url: Url,
+        robots: Arc<RobotsService>,
+    ) -> Result<Self> {
+        let spider = Spider::new(Self::preset_config(preset, base_url))
+            .await?
+            .with_robots_service(robots);
+        Ok(Self::from_spider(spider))
+    }
+
+    fn preset_config(preset: SpiderPreset, base_url: Url) -> SpiderConfig {
         let mut config = match preset {
             SpiderPreset::Development => SpiderPresets::development(),
             SpiderPreset::HighPerformance => SpiderPresets::high_performance(),
@@ -76,11 +224,7 @@ impl SpiderFacade {
         };
 
         config.base_url = base_url;
-        let spider = Spider::new(config).await?;
-
-        Ok(Self {
-            spider: Arc::new(Mutex::new(spider)),
-        })
+        config
     }
 
     /// Create a new spider from a custom configuration.
@@ -113,9 +257,49 @@ impl SpiderFacade {
     /// ```
     pub async fn from_config(config: SpiderConfig) -> Result<Self> {
         let spider = Spider::new(config).await?;
-        Ok(Self {
+        Ok(Self::from_spider(spider))
+    }
+
+    fn from_spider(spider: Spider) -> Self {
+        Self {
+            frontier: spider.frontier_manager().clone(),
+            control: spider.control().clone(),
             spider: Arc::new(Mutex::new(spider)),
-        })
+        }
+    }
+
+    /// Persist crawl state transitions to `store` under `key`
+    pub async fn with_control_store(mut self, store: Arc<dyn CheckpointStore>, key: &str) -> Self {
+        {
+            let mut spider = self.spider.lock().await;
+            spider.set_control_store(store, key);
+            self.control = spider.control().clone();
+        }
+        self
+    }
+
+    /// Send page requests through `client`, e.g. one resolving through a
+    /// shared DNS cache
+    pub async fn with_http_client(self, client: Arc<ReliableHttpClient>) -> Self {
+        self.spider.lock().await.set_http_client(client);
+        self
+    }
+
+    /// Read page bodies no faster than `limiter` allows
+    pub async fn with_bandwidth_limiter(self, limiter: Arc<BandwidthLimiter>) -> Self {
+        self.spider.lock().await.set_bandwidth_limiter(limiter);
+        self
+    }
+
+    /// Read back the crawl state persisted by a previous process
+    ///
+    /// A crawl that process was running is reported as stopped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the persisted state cannot be read.
+    pub async fn restore_control(&self) -> Result<()> {
+        self.control.restore().await
     }
 
     /// Start crawling from seed URLs.
@@ -153,11 +337,108 @@ impl SpiderFacade {
     /// # }
     /// ```
     pub async fn crawl(&self, seeds: Vec<Url>) -> Result<CrawlSummary> {
+        ensure_not_paused(&self.control)?;
         let spider = self.spider.lock().await;
         let result = spider.crawl(seeds).await?;
         Ok(CrawlSummary::from(result))
     }
 
+    /// Crawl from seed URLs with authenticated fetches.
+    ///
+    /// Requests carry the cookies and headers `provider` returns for their
+    /// host; rejected sessions are renewed once per page. The provider only
+    /// applies to this crawl.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the crawl operation fails.
+    pub async fn crawl_with_sessions(
+        &self,
+        seeds: Vec<Url>,
+        provider: Arc<dyn SessionProvider>,
+    ) -> Result<CrawlSummary> {
+        self.crawl_with(seeds, CrawlOverrides::new().with_session_provider(provider))
+            .await
+    }
+
+    /// Crawl from seed URLs with per-crawl overrides.
+    ///
+    /// `overrides` only apply to this crawl.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the crawl operation fails.
+    pub async fn crawl_with(
+        &self,
+        seeds: Vec<Url>,
+        overrides: CrawlOverrides,
+    ) -> Result<CrawlSummary> {
+        ensure_not_paused(&self.control)?;
+        let mut spider = self.spider.lock().await;
+        overrides.install(&mut spider)?;
+        let result = spider.crawl(seeds).await;
+        CrawlOverrides::remove(&mut spider);
+        Ok(CrawlSummary::from(result?))
+    }
+
+    /// Run the crawl a manifest declares.
+    ///
+    /// The filtered seeds are crawled and, with `max_depth`, links are
+    /// followed within the manifest's filters and page cap; without it only
+    /// the seeds are crawled. Extraction settings and outputs apply to the
+    /// pipeline and are not used by the spider. `overrides` add sessions or
+    /// a URL policy; the manifest's scope replaces theirs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest is invalid or the crawl fails.
+    pub async fn crawl_manifest(
+        &self,
+        manifest: &CrawlManifest,
+        overrides: CrawlOverrides,
+    ) -> Result<CrawlSummary> {
+        let report = manifest.validate();
+        if !report.valid {
+            bail!(
+                "Invalid manifest '{}': {}",
+                manifest.name,
+                report.errors.join("; ")
+            );
+        }
+        let seeds = manifest
+            .seed_urls()
+            .iter()
+            .map(|seed| Url::parse(seed))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut options = manifest.crawl_options();
+        options.spider_max_depth.get_or_insert(0);
+        options.scope = Some(manifest.filters.scope());
+        self.crawl_with(seeds, overrides.with_crawl_options(&options))
+            .await
+    }
+
+    /// Crawl from seed URLs, yielding pages as they are crawled.
+    ///
+    /// The crawl runs in the background and waits while the consumer is
+    /// behind; dropping the stream stops it. Use [`CrawlStream::finish`] for
+    /// the summary once the stream ends. `overrides` only apply to this
+    /// crawl.
+    pub fn crawl_stream(&self, seeds: Vec<Url>, overrides: CrawlOverrides) -> CrawlStream {
+        let (sink, results) = tokio::sync::mpsc::channel(DEFAULT_STREAM_BUFFER);
+        let spider = self.spider.clone();
+        let control = self.control.clone();
+        let crawl = tokio::spawn(async move {
+            ensure_not_paused(&control)?;
+            let mut spider = spider.lock().await;
+            overrides.install(&mut spider)?;
+            let result = spider.crawl_into(seeds, sink).await;
+            CrawlOverrides::remove(&mut spider);
+            result
+        });
+        CrawlStream::new(results, crawl)
+    }
+
     /// Get the current crawl state.
     ///
     /// # Returns
@@ -188,6 +469,9 @@ impl SpiderFacade {
 
     /// Get comprehensive status including state and metrics.
     ///
+    /// While a crawl holds the spider, the state is built from the frontier
+    /// and control handles and no metrics are returned.
+    ///
     /// # Returns
     ///
     /// Returns a tuple of (CrawlState, Option<PerformanceMetrics>).
@@ -196,17 +480,57 @@ impl SpiderFacade {
     ///
     /// This method should not fail under normal circumstances.
     pub async fn get_status(&self) -> Result<(CrawlState, Option<PerformanceMetrics>)> {
-        let spider = self.spider.lock().await;
+        let Ok(spider) = self.spider.try_lock() else {
+            let run_state = self.control.state();
+            let state = CrawlState {
+                active: run_state.is_active(),
+                frontier_size: self.frontier.size(),
+                run_state,
+                ..Default::default()
+            };
+            return Ok((state, None));
+        };
         let state = spider.get_crawl_state().await;
         let metrics = Some(spider.get_performance_metrics().await);
         Ok((state, metrics))
     }
 
-    /// Execute control action (stop or reset).
+    /// Current crawl state and its recent transitions.
+    pub fn control_snapshot(&self) -> ControlSnapshot {
+        self.control.snapshot()
+    }
+
+    /// Inspect the crawl frontier.
+    ///
+    /// Does not wait for a running crawl, so it can be used to diagnose a
+    /// crawl that appears stuck.
     ///
     /// # Arguments
     ///
-    /// * `action` - The control action: "stop" or "reset"
+    /// * `limit` - Maximum number of pending URLs to return
+    ///
+    /// # Returns
+    ///
+    /// Returns the next pending URLs in dequeue order, the per-domain depth
+    /// distribution of the queue, and why discovered URLs were rejected.
+    ///
+    /// # Errors
+    ///
+    /// This method should not fail under normal circumstances.
+    pub async fn inspect_frontier(&self, limit: usize) -> Result<FrontierSnapshot> {
+        Ok(self.frontier.snapshot(limit).await)
+    }
+
+    /// Execute control action (pause, resume, stop, cancel or reset).
+    ///
+    /// Pause, resume, stop and cancel apply to the running crawl and take
+    /// effect after the page being fetched; reset waits for the spider to be
+    /// idle.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The control action: "pause", "resume", "stop", "cancel"
+    ///   or "reset"
     ///
     /// # Returns
     ///
@@ -216,33 +540,75 @@ impl SpiderFacade {
     ///
     /// Returns an error if:
     /// - Invalid action is provided
+    /// - The action does not apply to the crawl's current state
     /// - Operation fails
     pub async fn control(&self, action: &str) -> Result<String> {
         match action {
+            "pause" => {
+                self.control.pause().await?;
+                Ok("Crawl pausing after the current page".to_string())
+            }
+            "resume" => {
+                self.control.resume().await?;
+                Ok("Crawl resumed".to_string())
+            }
             "stop" => {
                 self.stop().await?;
-                Ok("Spider stopped successfully".to_string())
+                Ok("Crawl stopping after the current page".to_string())
+            }
+            "cancel" => {
+                self.control.cancel().await?;
+                Ok("Crawl cancelling after the current page".to_string())
             }
             "reset" => {
+                if self.control.state().is_active() {
+                    anyhow::bail!("Cannot reset while a crawl is in progress; cancel it first");
+                }
                 self.reset().await?;
                 Ok("Spider reset successfully".to_string())
             }
             _ => Err(anyhow::anyhow!(
-                "Invalid action: '{}'. Must be 'stop' or 'reset'",
+                "Invalid action: '{}'. Must be 'pause', 'resume', 'stop', 'cancel' or 'reset'",
                 action
             )),
         }
     }
 
-    /// Stop the current crawl operation.
+    /// Stop the current crawl after the page being fetched, keeping its
+    /// frontier.
     ///
     /// # Errors
     ///
-    /// This method should not fail under normal circumstances.
+    /// Returns an error if no crawl is running or paused.
     pub async fn stop(&self) -> Result<()> {
-        let spider = self.spider.lock().await;
-        spider.stop().await;
-        Ok(())
+        self.control.stop().await
+    }
+
+    /// Checkpoint the crawl state to `store`, waiting up to `wait` for a
+    /// running crawl to release the spider.
+    ///
+    /// Callers that need the checkpoint promptly should [`stop`](Self::stop)
+    /// the crawl first; it keeps its frontier for the checkpoint.
+    ///
+    /// # Returns
+    ///
+    /// Returns `false` if the crawl was still running after `wait` and
+    /// nothing was written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the checkpoint cannot be written.
+    pub async fn checkpoint_within(
+        &self,
+        store: &dyn CheckpointStore,
+        checkpoint_id: &str,
+        wait: std::time::Duration,
+    ) -> Result<bool> {
+        let Ok(spider) = tokio::time::timeout(wait, self.spider.lock()).await else {
+            return Ok(false);
+        };
+        spider.checkpoint(store, checkpoint_id).await?;
+        Ok(true)
     }
 
     /// Reset the spider state and clear all caches.
@@ -256,6 +622,15 @@ impl SpiderFacade {
     }
 }
 
+/// Refuse to queue a crawl behind a paused one, which holds the spider
+/// until it is resumed, stopped, cancelled or paused for too long
+fn ensure_not_paused(control: &CrawlControl) -> Result<()> {
+    if control.state() == CrawlRunState::Paused {
+        bail!("A paused crawl holds the spider; resume, stop or cancel it first");
+    }
+    Ok(())
+}
+
 /// Summary of a completed crawl operation.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CrawlSummary {
@@ -263,6 +638,9 @@ pub struct CrawlSummary {
     pub pages_crawled: u64,
     /// Total number of pages that failed to crawl
     pub pages_failed: u64,
+    /// Crawled pages not reported as unchanged since `changed_since`
+    #[serde(default)]
+    pub pages_unchanged: u64,
     /// Duration of the crawl in seconds
     pub duration_secs: f64,
     /// Total bytes downloaded during the crawl
@@ -275,6 +653,17 @@ pub struct CrawlSummary {
     pub domains: Vec<String>,
     /// All URLs discovered during the crawl
     pub discovered_urls: Vec<String>,
+    /// Robots, crawl-delay, meta robots, and rate-limit decisions for audit
+    pub compliance: riptide_types::ComplianceReport,
+    /// Per-domain politeness state (concurrency, delays, backoff)
+    #[serde(default)]
+    pub politeness: std::collections::BTreeMap<String, riptide_spider::DomainPolitenessStats>,
+    /// Added, changed and removed URLs, for refresh crawls
+    #[serde(default)]
+    pub delta: Option<riptide_spider::DeltaReport>,
+    /// Budget consumption, and the budget that stopped the crawl, if any
+    #[serde(default)]
+    pub budget: riptide_spider::BudgetReport,
 }
 
 impl From<riptide_spider::SpiderResult> for CrawlSummary {
@@ -282,12 +671,17 @@ impl From<riptide_spider::SpiderResult> for CrawlSummary {
         Self {
             pages_crawled: result.pages_crawled,
             pages_failed: result.pages_failed,
+            pages_unchanged: result.pages_unchanged,
             duration_secs: result.duration.as_secs_f64(),
-            bytes_downloaded: 0, // SpiderResult doesn't track this in current implementation
+            bytes_downloaded: result.budget.bandwidth_used,
             errors_count: result.pages_failed as usize,
             stop_reason: result.stop_reason,
             domains: result.domains,
             discovered_urls: result.discovered_urls,
+            compliance: result.compliance,
+            politeness: result.politeness,
+            delta: result.delta,
+            budget: result.budget,
         }
     }
 }
@@ -333,6 +727,71 @@ mod tests {
         assert_eq!(metrics.pages_per_second, 0.0); // Should be zero initially
     }
 
+    #[tokio::test]
+    async fn test_spider_facade_control_actions() {
+        let base_url = Url::parse("https://example.com").unwrap();
+        let spider = SpiderFacade::from_preset(SpiderPreset::Development, base_url)
+            .await
+            .unwrap();
+
+        // Nothing is crawling, so only reset applies
+        assert!(spider.control("pause").await.is_err());
+        assert!(spider.control("cancel").await.is_err());
+        assert!(spider.control("launch").await.is_err());
+        assert!(spider.control("reset").await.is_ok());
+
+        let (state, metrics) = spider.get_status().await.unwrap();
+        assert_eq!(state.run_state, riptide_spider::CrawlRunState::Idle);
+        assert!(metrics.is_some());
+        assert!(spider.control_snapshot().transitions.is_empty());
+    }
+
+    #[test]
+    fn test_crawl_options_become_scope_rules() {
+        let options = CrawlOptions {
+            spider_max_depth: Some(2),
+            scope: Some(riptide_types::config::CrawlScope {
+                include: vec!["/docs/".to_string()],
+                exclude: vec![],
+                max_pages: Some(5),
+            }),
+            ..CrawlOptions::default()
+        };
+        let overrides = CrawlOverrides::new().with_crawl_options(&options);
+
+        let scope = overrides.scope.unwrap();
+        assert_eq!(scope.include, vec![UrlPattern::Regex("/docs/".to_string())]);
+        assert_eq!(scope.depth_rules[0].max_depth, 2);
+        assert_eq!(overrides.page_limit, Some(5));
+
+        let overrides = CrawlOverrides::new().with_crawl_options(&CrawlOptions::default());
+        assert!(overrides.scope.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_crawl_manifest_rejects_invalid_manifest() {
+        let base_url = Url::parse("https://example.com").unwrap();
+        let spider = SpiderFacade::from_preset(SpiderPreset::Development, base_url)
+            .await
+            .unwrap();
+        let manifest = CrawlManifest::from_yaml(
+            r#"
+version: 1
+name: docs
+seeds: ["https://example.com/"]
+filters:
+  include: ["("]
+"#,
+        )
+        .unwrap();
+
+        let err = spider
+            .crawl_manifest(&manifest, CrawlOverrides::new())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().starts_with("Invalid manifest 'docs'"));
+    }
+
     #[tokio::test]
     async fn test_spider_facade_reset() {
         let base_url = Url::parse("https://example.com").unwrap();
@@ -0,0 +1,135 @@
+//! LLM-backed text embeddings for query-aware crawls
+//!
+//! [`LlmEmbedder`] adapts an LLM provider's `embed()` to the spider's
+//! [`TextEmbedder`] port, so page relevance can be scored by embedding
+//! similarity to the crawl query:
+//!
+//! ```rust,ignore
+//! use riptide_facade::facades::LlmEmbedder;
+//! use riptide_spider::SpiderBuilder;
+//!
+//! config.query_aware.embedding_stop_threshold = Some(0.25);
+//! let spider = SpiderBuilder::new()
+//!     .with_config(config)
+//!     .with_text_embedder(Arc::new(LlmEmbedder::new(provider)))
+//!     .build()
+//!     .into_spider()
+//!     .await?;
+//! ```
+//!
+//! It also implements the chunker's [`SentenceEmbedder`] port, for
+//! `semantic` chunking through
+//! [`ChunkingFacade::with_embedder`](super::ChunkingFacade::with_embedder).
+
+use async_trait::async_trait;
+use futures::future::try_join_all;
+use riptide_extraction::chunking::semantic::SentenceEmbedder;
+use riptide_intelligence::LlmProvider;
+use riptide_spider::TextEmbedder;
+use std::sync::Arc;
+
+/// Text embedder backed by an LLM provider
+///
+/// The provider port embeds one text per call, so a batch is sent as
+/// concurrent calls; the spider's `embedding_batch_size` bounds how many
+/// run at once.
+pub struct LlmEmbedder {
+    provider: Arc<dyn LlmProvider>,
+}
+
+impl LlmEmbedder {
+    /// Embed texts with `provider`
+    pub fn new(provider: Arc<dyn LlmProvider>) -> Self {
+        Self { provider }
+    }
+}
+
+#[async_trait]
+impl TextEmbedder for LlmEmbedder {
+    async fn embed_batch(&self, texts: &[String]) -> anyhow::Result<Vec<Vec<f32>>> {
+        try_join_all(texts.iter().map(|text| self.provider.embed(text)))
+            .await
+            .map_err(|e| anyhow::anyhow!("LLM embedding failed: {}", e))
+    }
+
+    fn name(&self) -> &str {
+        self.provider.name()
+    }
+}
+
+#[async_trait]
+impl SentenceEmbedder for LlmEmbedder {
+    async fn embed_batch(&self, texts: &[String]) -> anyhow::Result<Vec<Vec<f32>>> {
+        TextEmbedder::embed_batch(self, texts).await
+    }
+
+    fn name(&self) -> &str {
+        self.provider.name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_intelligence::{
+        CompletionRequest, CompletionResponse, Cost, IntelligenceError, LlmCapabilities,
+    };
+    use std::collections::HashMap;
+
+    /// Provider embedding text as `[length]`, failing on "fail"
+    struct LengthProvider;
+
+    #[async_trait]
+    impl LlmProvider for LengthProvider {
+        async fn complete(
+            &self,
+            _request: CompletionRequest,
+        ) -> riptide_intelligence::Result<CompletionResponse> {
+            Err(IntelligenceError::Provider("unsupported".to_string()))
+        }
+
+        async fn embed(&self, text: &str) -> riptide_intelligence::Result<Vec<f32>> {
+            if text == "fail" {
+                return Err(IntelligenceError::Provider("unavailable".to_string()));
+            }
+            Ok(vec![text.len() as f32])
+        }
+
+        fn capabilities(&self) -> LlmCapabilities {
+            LlmCapabilities {
+                provider_name: "length".to_string(),
+                models: Vec::new(),
+                supports_embeddings: true,
+                supports_streaming: false,
+                supports_functions: false,
+                max_context_length: 4096,
+                rate_limits: HashMap::new(),
+            }
+        }
+
+        fn estimate_cost(&self, _tokens: usize) -> Cost {
+            Cost::zero("USD")
+        }
+
+        fn name(&self) -> &str {
+            "length"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_embeds_batch_in_order() {
+        let embedder = LlmEmbedder::new(Arc::new(LengthProvider));
+        let texts = vec!["a".to_string(), "abc".to_string(), "ab".to_string()];
+
+        let vectors = TextEmbedder::embed_batch(&embedder, &texts).await.unwrap();
+        assert_eq!(vectors, vec![vec![1.0], vec![3.0], vec![2.0]]);
+        assert_eq!(TextEmbedder::name(&embedder), "length");
+    }
+
+    #[tokio::test]
+    async fn test_provider_failure_fails_batch() {
+        let embedder = LlmEmbedder::new(Arc::new(LengthProvider));
+        let texts = vec!["ok".to_string(), "fail".to_string()];
+        assert!(TextEmbedder::embed_batch(&embedder, &texts).await.is_err());
+    }
+}
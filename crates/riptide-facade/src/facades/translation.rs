@@ -0,0 +1,383 @@
+//! Translation enrichment for extracted documents
+//!
+//! [`TranslationEnricher`] translates a document's title and text into a
+//! target language through a [`Translator`] and stores the result next to
+//! the original in [`ExtractedDoc::translation`]. Documents already in the
+//! target language are left alone. Long texts are translated in
+//! paragraph-aligned chunks, up to a configurable number of characters.
+//!
+//! With the `llm` feature, [`LlmTranslator`] adapts an LLM provider to the
+//! translator port, and [`TranslationConfig::create_translator`] builds one
+//! from `RIPTIDE_TRANSLATION_*` settings:
+//!
+//! ```rust,ignore
+//! use riptide_facade::facades::{TranslationConfig, TranslationEnricher};
+//!
+//! let config = TranslationConfig::from_env();
+//! if let Some(translator) = config.create_translator()? {
+//!     let enricher = TranslationEnricher::new(translator)
+//!         .with_limits(config.max_chars, config.chunk_chars);
+//!     enricher.enrich(&mut document, "en").await?;
+//! }
+//! ```
+
+use crate::error::RiptideResult;
+use riptide_types::ports::Translator;
+use riptide_types::{DocumentTranslation, ExtractedDoc};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Translation settings, from `RIPTIDE_TRANSLATE_TO` and `RIPTIDE_TRANSLATION_*`
+#[derive(Clone, Serialize)]
+pub struct TranslationConfig {
+    /// Language documents are translated to when a request does not name one;
+    /// `None` translates only on request
+    pub target_language: Option<String>,
+    /// LLM provider type ("openai", "anthropic", "ollama", ...); `None` disables translation
+    pub provider: Option<String>,
+    pub model: String,
+    pub api_key: Option<String>,
+    /// Provider endpoint, for self-hosted and OpenAI-compatible providers
+    pub base_url: Option<String>,
+    /// Characters of text translated per document; the rest is left out
+    pub max_chars: usize,
+    /// Characters sent per translation call
+    pub chunk_chars: usize,
+}
+
+impl Default for TranslationConfig {
+    fn default() -> Self {
+        Self {
+            target_language: None,
+            provider: None,
+            model: "gpt-4o-mini".to_string(),
+            api_key: None,
+            base_url: None,
+            max_chars: 20_000,
+            chunk_chars: 4_000,
+        }
+    }
+}
+
+impl std::fmt::Debug for TranslationConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TranslationConfig")
+            .field("target_language", &self.target_language)
+            .field("provider", &self.provider)
+            .field("model", &self.model)
+            .field("api_key", &self.api_key.as_ref().map(|_| "***REDACTED***"))
+            .field("base_url", &self.base_url)
+            .field("max_chars", &self.max_chars)
+            .field("chunk_chars", &self.chunk_chars)
+            .finish()
+    }
+}
+
+impl TranslationConfig {
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let defaults = Self::default();
+        Self {
+            target_language: var("RIPTIDE_TRANSLATE_TO"),
+            provider: var("RIPTIDE_TRANSLATION_PROVIDER"),
+            model: var("RIPTIDE_TRANSLATION_MODEL").unwrap_or(defaults.model),
+            api_key: var("RIPTIDE_TRANSLATION_API_KEY"),
+            base_url: var("RIPTIDE_TRANSLATION_BASE_URL"),
+            max_chars: var("RIPTIDE_TRANSLATION_MAX_CHARS")
+                .and_then(|v| v.parse().ok())
+                .filter(|&n| n > 0)
+                .unwrap_or(defaults.max_chars),
+            chunk_chars: var("RIPTIDE_TRANSLATION_CHUNK_CHARS")
+                .and_then(|v| v.parse().ok())
+                .filter(|&n| n > 0)
+                .unwrap_or(defaults.chunk_chars),
+        }
+    }
+
+    /// Build the LLM translator for the configured provider, `None` when
+    /// no provider is configured
+    #[cfg(feature = "llm")]
+    pub fn create_translator(&self) -> RiptideResult<Option<Arc<dyn Translator>>> {
+        use riptide_intelligence::{create_provider_from_config, ProviderConfig};
+
+        let Some(provider_type) = &self.provider else {
+            return Ok(None);
+        };
+        let mut provider_config = ProviderConfig::new("translation", provider_type.as_str());
+        if let Some(api_key) = &self.api_key {
+            provider_config = provider_config.with_config("api_key", api_key.as_str().into());
+        }
+        if let Some(base_url) = &self.base_url {
+            provider_config = provider_config.with_config("base_url", base_url.as_str().into());
+        }
+        let provider = create_provider_from_config(&provider_config)
+            .map_err(|e| crate::RiptideError::config(format!("Translation provider: {}", e)))?;
+        Ok(Some(Arc::new(LlmTranslator::new(
+            provider,
+            self.model.clone(),
+        ))))
+    }
+}
+
+/// Adds translations to extracted documents
+#[derive(Clone)]
+pub struct TranslationEnricher {
+    translator: Arc<dyn Translator>,
+    max_chars: usize,
+    chunk_chars: usize,
+}
+
+impl TranslationEnricher {
+    pub fn new(translator: Arc<dyn Translator>) -> Self {
+        let defaults = TranslationConfig::default();
+        Self {
+            translator,
+            max_chars: defaults.max_chars,
+            chunk_chars: defaults.chunk_chars,
+        }
+    }
+
+    /// Translate at most `max_chars` characters per document, `chunk_chars` per call
+    pub fn with_limits(mut self, max_chars: usize, chunk_chars: usize) -> Self {
+        self.max_chars = max_chars.max(1);
+        self.chunk_chars = chunk_chars.max(1);
+        self
+    }
+
+    /// Translate `doc` into `target_language`
+    ///
+    /// Returns `false` without calling the translator when the document has
+    /// no text, is already in the target language, or already carries a
+    /// translation into it.
+    pub async fn enrich(
+        &self,
+        doc: &mut ExtractedDoc,
+        target_language: &str,
+    ) -> RiptideResult<bool> {
+        let target_language = target_language.trim();
+        if target_language.is_empty() || doc.text.trim().is_empty() {
+            return Ok(false);
+        }
+        let already_there = doc
+            .language
+            .iter()
+            .chain(doc.translation.as_ref().map(|t| &t.language))
+            .any(|language| DocumentTranslation::same_language(language, target_language));
+        if already_there {
+            return Ok(false);
+        }
+
+        let source_language = doc.language.as_deref();
+        let (mut text, truncated) = truncate_chars(&doc.text, self.max_chars);
+        if truncated {
+            // Do not hand the translator half a word
+            if let Some(end) = text.rfind(char::is_whitespace).filter(|&end| end > 0) {
+                text = &text[..end];
+            }
+        }
+        let mut translated = Vec::new();
+        for chunk in split_chunks(text, self.chunk_chars) {
+            translated.push(
+                self.translator
+                    .translate(chunk, source_language, target_language)
+                    .await?,
+            );
+        }
+        let title = match doc.title.as_deref().filter(|t| !t.trim().is_empty()) {
+            Some(title) => Some(
+                self.translator
+                    .translate(title, source_language, target_language)
+                    .await?,
+            ),
+            None => None,
+        };
+
+        doc.translation = Some(DocumentTranslation {
+            language: target_language.to_string(),
+            source_language: doc.language.clone(),
+            title,
+            text: translated.join("\n\n"),
+            truncated,
+            translator: self.translator.name().to_string(),
+            translated_at: chrono::Utc::now(),
+        });
+        Ok(true)
+    }
+}
+
+/// First `max_chars` characters of `text`, and whether anything was cut
+fn truncate_chars(text: &str, max_chars: usize) -> (&str, bool) {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => (&text[..end], true),
+        None => (text, false),
+    }
+}
+
+/// Split `text` into chunks of at most `max_chars` characters, breaking
+/// between paragraphs where possible and between words otherwise
+fn split_chunks(text: &str, max_chars: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        let (head, cut) = truncate_chars(rest, max_chars);
+        if !cut {
+            chunks.push(rest);
+            break;
+        }
+        let end = head
+            .rfind("\n\n")
+            .or_else(|| head.rfind(char::is_whitespace))
+            .filter(|&end| end > 0)
+            .unwrap_or(head.len());
+        chunks.push(rest[..end].trim_end());
+        rest = rest[end..].trim_start();
+    }
+    chunks
+}
+
+#[cfg(feature = "llm")]
+pub use llm_translator::LlmTranslator;
+
+#[cfg(feature = "llm")]
+mod llm_translator {
+    use async_trait::async_trait;
+    use riptide_intelligence::{CompletionRequest, LlmProvider, Message};
+    use riptide_types::ports::Translator;
+    use riptide_types::RiptideError;
+    use std::sync::Arc;
+
+    /// Translator backed by an LLM provider
+    pub struct LlmTranslator {
+        provider: Arc<dyn LlmProvider>,
+        model: String,
+        name: String,
+    }
+
+    impl LlmTranslator {
+        /// Translate with `model` of `provider`
+        pub fn new(provider: Arc<dyn LlmProvider>, model: impl Into<String>) -> Self {
+            let model = model.into();
+            Self {
+                provider,
+                name: format!("llm:{}", model),
+                model,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Translator for LlmTranslator {
+        async fn translate(
+            &self,
+            text: &str,
+            source_language: Option<&str>,
+            target_language: &str,
+        ) -> riptide_types::Result<String> {
+            let source = source_language
+                .map(|language| format!("from language \"{}\" ", language))
+                .unwrap_or_default();
+            let request = CompletionRequest::new(
+                self.model.clone(),
+                vec![
+                    Message::system(format!(
+                        "You translate web page text {}into language \"{}\". \
+                         Keep paragraph breaks, names and numbers. \
+                         Answer with the translation only.",
+                        source, target_language
+                    )),
+                    Message::user(text),
+                ],
+            )
+            // Translations run about as long as the source; leave headroom
+            .with_max_tokens((text.chars().count() as u32 / 2).max(256))
+            .with_temperature(0.0);
+
+            let response = self
+                .provider
+                .complete(request)
+                .await
+                .map_err(|e| RiptideError::Custom(format!("LLM translation failed: {}", e)))?;
+            Ok(response.content.trim().to_string())
+        }
+
+        fn name(&self) -> &str {
+            &self.name
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::ports::InMemoryTranslator;
+
+    fn doc(text: &str, language: Option<&str>) -> ExtractedDoc {
+        ExtractedDoc {
+            url: "https://example.de/".to_string(),
+            title: Some("Hallo".to_string()),
+            text: text.to_string(),
+            language: language.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_enrich_translates_title_and_text() {
+        let translator =
+            Arc::new(InMemoryTranslator::new().with_translation("Hallo", "en", "Hello"));
+        let enricher = TranslationEnricher::new(translator.clone());
+
+        let mut document = doc("Guten Tag.\n\nWie geht es?", Some("de"));
+        assert!(enricher.enrich(&mut document, "en").await.unwrap());
+
+        let translation = document.translation.unwrap();
+        assert_eq!(translation.language, "en");
+        assert_eq!(translation.source_language.as_deref(), Some("de"));
+        assert_eq!(translation.title.as_deref(), Some("Hello"));
+        assert_eq!(translation.text, "[en] Guten Tag.\n\nWie geht es?");
+        assert!(!translation.truncated);
+        assert_eq!(translation.translator, "memory");
+        assert_eq!(document.text, "Guten Tag.\n\nWie geht es?");
+        assert_eq!(translator.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_enrich_skips_documents_in_target_language() {
+        let translator = Arc::new(InMemoryTranslator::new());
+        let enricher = TranslationEnricher::new(translator.clone());
+
+        let mut english = doc("Hello there", Some("en-US"));
+        assert!(!enricher.enrich(&mut english, "en").await.unwrap());
+        let mut empty = doc("  ", Some("de"));
+        assert!(!enricher.enrich(&mut empty, "en").await.unwrap());
+
+        let mut german = doc("Guten Tag", Some("de"));
+        assert!(enricher.enrich(&mut german, "en").await.unwrap());
+        assert!(!enricher.enrich(&mut german, "en-GB").await.unwrap());
+        assert_eq!(translator.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_enrich_chunks_and_truncates_long_text() {
+        let translator = Arc::new(InMemoryTranslator::new());
+        let enricher = TranslationEnricher::new(translator.clone()).with_limits(30, 12);
+
+        let mut document = doc("eins zwei\n\ndrei vier fünf sechs sieben acht", None);
+        document.title = None;
+        assert!(enricher.enrich(&mut document, "en").await.unwrap());
+
+        let translation = document.translation.unwrap();
+        assert!(translation.truncated);
+        assert_eq!(
+            translation.text,
+            "[en] eins zwei\n\n[en] drei vier\n\n[en] fünf"
+        );
+    }
+
+    #[test]
+    fn test_split_chunks_prefers_paragraphs() {
+        assert_eq!(split_chunks("aaa bbb\n\nccc", 9), vec!["aaa bbb", "ccc"]);
+        assert_eq!(split_chunks("abcdefgh", 3), vec!["abc", "def", "gh"]);
+        assert_eq!(split_chunks("short", 10), vec!["short"]);
+    }
+}
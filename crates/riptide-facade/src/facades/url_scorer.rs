@@ -0,0 +1,244 @@
+//! LLM-backed URL scoring for best-first crawls
+//!
+//! [`LlmUrlScorer`] asks an LLM provider how likely a discovered link is to
+//! lead to content about a crawl topic, using the URL, its anchor text and
+//! the relevance of the linking page. Register it when building a spider:
+//!
+//! ```rust,ignore
+//! use riptide_facade::facades::LlmUrlScorer;
+//! use riptide_spider::SpiderBuilder;
+//!
+//! let scorer = LlmUrlScorer::new(provider, "gpt-4o-mini", "pricing and plans");
+//! let spider = SpiderBuilder::new()
+//!     .with_config(config)
+//!     .with_url_scorer(Arc::new(scorer))
+//!     .build()
+//!     .into_spider()
+//!     .await?;
+//! ```
+
+use async_trait::async_trait;
+use riptide_intelligence::{CompletionRequest, LlmProvider, Message};
+use riptide_spider::strategy::{DefaultScoring, UrlScorer, UrlScoringContext};
+use riptide_spider::ScoringConfig;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Highest score the LLM scorer produces; the top third crawls at high priority
+const MAX_SCORE: f64 = 3.0;
+
+/// Best-first URL scorer backed by an LLM provider
+///
+/// Each call costs one completion, so the scorer suits focused crawls with
+/// small frontiers. Provider failures and unparseable answers fall back to
+/// [`DefaultScoring`] (or the scorer set with [`LlmUrlScorer::with_fallback`]).
+pub struct LlmUrlScorer {
+    provider: Arc<dyn LlmProvider>,
+    model: String,
+    topic: String,
+    fallback: Arc<dyn UrlScorer>,
+}
+
+impl LlmUrlScorer {
+    /// Score links by their relevance to `topic` using `model`
+    pub fn new(
+        provider: Arc<dyn LlmProvider>,
+        model: impl Into<String>,
+        topic: impl Into<String>,
+    ) -> Self {
+        Self {
+            provider,
+            model: model.into(),
+            topic: topic.into(),
+            fallback: Arc::new(DefaultScoring::new(ScoringConfig::default())),
+        }
+    }
+
+    /// Scorer used when the provider fails or answers with no rating
+    pub fn with_fallback(mut self, fallback: Arc<dyn UrlScorer>) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    fn prompt(&self, ctx: &UrlScoringContext<'_>) -> String {
+        let mut prompt = format!(
+            "A web crawler is looking for pages about: {}\n\
+             Rate from 0 to 10 how likely this link leads to such a page.\n\
+             Answer with the number only.\n\nURL: {}\nDepth: {}\n",
+            self.topic, ctx.url, ctx.depth
+        );
+        if let Some(anchor) = ctx.anchor_text {
+            prompt.push_str(&format!("Link text: {}\n", anchor));
+        }
+        if let Some(relevance) = ctx.parent_relevance {
+            prompt.push_str(&format!(
+                "Linking page relevance: {:.1}/10\n",
+                (relevance / MAX_SCORE * 10.0).clamp(0.0, 10.0)
+            ));
+        }
+        prompt
+    }
+}
+
+/// First number in an LLM answer, as a 0-10 rating
+fn parse_rating(answer: &str) -> Option<f64> {
+    answer
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .find_map(|token| token.trim_matches('.').parse::<f64>().ok())
+        .map(|rating| rating.clamp(0.0, 10.0))
+}
+
+#[async_trait]
+impl UrlScorer for LlmUrlScorer {
+    async fn score(&self, ctx: &UrlScoringContext<'_>) -> f64 {
+        let request = CompletionRequest::new(
+            self.model.clone(),
+            vec![
+                Message::system("You rate links for a focused web crawler."),
+                Message::user(self.prompt(ctx)),
+            ],
+        )
+        .with_max_tokens(4)
+        .with_temperature(0.0);
+
+        match self.provider.complete(request).await {
+            Ok(response) => match parse_rating(&response.content) {
+                Some(rating) => rating / 10.0 * MAX_SCORE,
+                None => {
+                    warn!(url = %ctx.url, answer = %response.content, "Unparseable LLM URL rating");
+                    self.fallback.score(ctx).await
+                }
+            },
+            Err(e) => {
+                warn!(url = %ctx.url, error = %e, "LLM URL scoring failed");
+                self.fallback.score(ctx).await
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        "llm"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_intelligence::{
+        CompletionResponse, Cost, IntelligenceError, LlmCapabilities, Usage,
+    };
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use url::Url;
+
+    /// Provider answering with a fixed string and recording prompts
+    struct FixedProvider {
+        answer: Option<&'static str>,
+        prompts: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl LlmProvider for FixedProvider {
+        async fn complete(
+            &self,
+            request: CompletionRequest,
+        ) -> riptide_intelligence::Result<CompletionResponse> {
+            let prompt = request.messages.last().map(|m| m.content.clone());
+            self.prompts.lock().unwrap().extend(prompt);
+            let answer = self
+                .answer
+                .ok_or_else(|| IntelligenceError::Provider("unavailable".to_string()))?;
+            Ok(CompletionResponse::new(
+                request.id,
+                answer,
+                request.model,
+                Usage {
+                    prompt_tokens: 0,
+                    completion_tokens: 0,
+                    total_tokens: 0,
+                },
+            ))
+        }
+
+        async fn embed(&self, _text: &str) -> riptide_intelligence::Result<Vec<f32>> {
+            Ok(Vec::new())
+        }
+
+        fn capabilities(&self) -> LlmCapabilities {
+            LlmCapabilities {
+                provider_name: "fixed".to_string(),
+                models: Vec::new(),
+                supports_embeddings: false,
+                supports_streaming: false,
+                supports_functions: false,
+                max_context_length: 4096,
+                rate_limits: HashMap::new(),
+            }
+        }
+
+        fn estimate_cost(&self, _tokens: usize) -> Cost {
+            Cost::zero("USD")
+        }
+
+        fn name(&self) -> &str {
+            "fixed"
+        }
+    }
+
+    fn scorer(answer: Option<&'static str>) -> (Arc<FixedProvider>, LlmUrlScorer) {
+        let provider = Arc::new(FixedProvider {
+            answer,
+            prompts: Mutex::new(Vec::new()),
+        });
+        let scorer = LlmUrlScorer::new(provider.clone(), "test-model", "pricing");
+        (provider, scorer)
+    }
+
+    #[test]
+    fn test_parse_rating() {
+        assert_eq!(parse_rating("8"), Some(8.0));
+        assert_eq!(parse_rating("Rating: 7.5."), Some(7.5));
+        assert_eq!(parse_rating("42"), Some(10.0));
+        assert_eq!(parse_rating("no idea"), None);
+    }
+
+    #[tokio::test]
+    async fn test_llm_rating_maps_to_score() {
+        let (provider, scorer) = scorer(Some("10"));
+        let url = Url::parse("https://example.com/plans").unwrap();
+        let metadata = HashMap::new();
+        let ctx = UrlScoringContext {
+            url: &url,
+            anchor_text: Some("See plans"),
+            depth: 1,
+            parent_relevance: Some(1.5),
+            metadata: &metadata,
+        };
+
+        assert!((scorer.score(&ctx).await - MAX_SCORE).abs() < f64::EPSILON);
+        let prompts = provider.prompts.lock().unwrap();
+        assert!(prompts[0].contains("Link text: See plans"));
+        assert!(prompts[0].contains("Linking page relevance: 5.0/10"));
+    }
+
+    #[tokio::test]
+    async fn test_provider_failure_uses_fallback() {
+        let url = Url::parse("https://example.com/").unwrap();
+        let metadata = HashMap::new();
+        let ctx = UrlScoringContext {
+            url: &url,
+            anchor_text: None,
+            depth: 0,
+            parent_relevance: None,
+            metadata: &metadata,
+        };
+        let expected = DefaultScoring::new(ScoringConfig::default())
+            .score(&ctx)
+            .await;
+
+        for answer in [None, Some("unsure")] {
+            let (_, scorer) = scorer(answer);
+            assert!((scorer.score(&ctx).await - expected).abs() < f64::EPSILON);
+        }
+    }
+}
@@ -110,25 +110,32 @@
 //!
 //! ## Testing
 //!
-//! Use in-memory port implementations for fast, deterministic tests:
+//! Every port has an in-memory implementation in `riptide_types::ports`
+//! (also re-exported by `riptide_test_utils::fakes`) for fast, deterministic
+//! tests without Redis or Chrome:
 //!
 //! ```rust,ignore
-//! use riptide_types::ports::{InMemoryCache, FakeClock, DeterministicEntropy};
+//! use riptide_types::ports::{FakeBrowserDriver, FakeClock, InMemoryCache, RecordingEventBus};
 //!
 //! #[tokio::test]
 //! async fn test_extraction_with_cache() {
-//!     let cache = Arc::new(InMemoryCache::new());
-//!     let clock = Arc::new(FakeClock::at_epoch());
+//!     let browser = Arc::new(
+//!         FakeBrowserDriver::new().with_page("https://example.com", "<title>Expected Title</title>"),
+//!     );
+//!     let events = Arc::new(RecordingEventBus::new());
 //!
 //!     let facade = ExtractionFacade {
-//!         cache,
-//!         clock,
+//!         browser,
+//!         events: events.clone(),
+//!         cache: Arc::new(InMemoryCache::new()),
+//!         clock: Arc::new(FakeClock::at_epoch()),
 //!         // ... other test doubles
 //!     };
 //!
 //!     // Deterministic, fast test with no real infrastructure
 //!     let result = facade.extract("https://example.com").await?;
 //!     assert_eq!(result.title, "Expected Title");
+//!     assert_eq!(events.published_of_type("extraction.completed").len(), 1);
 //! }
 //! ```
 
@@ -155,9 +162,10 @@ pub use config::RiptideConfig;
 pub use dto::{Document, Event, Product, StructuredData, ToDto};
 pub use error::{RiptideError, RiptideResult};
 pub use facades::{
-    BrowserAction, BrowserFacade, BrowserSession, Cookie, CrawlFacade, CrawlMode, CrawlResult,
-    CrawlSummary, ImageFormat, PipelineFacade, ScraperFacade, ScreenshotOptions, SpiderFacade,
-    SpiderPreset,
+    ActionScript, BrowserAction, BrowserFacade, BrowserSession, Cookie, CrawlFacade, CrawlMode,
+    CrawlOverrides, CrawlResult, CrawlSummary, ImageFormat, LoginCredentials, LoginFlow,
+    LoginFlowSessionProvider, PipelineFacade, ScraperFacade, ScreenshotOptions, SpiderFacade,
+    SpiderPreset, TwoFactorPrompt,
 };
 pub use traits::{
     Chainable, Content, ExtractChain, ExtractOpts, ExtractionStrategy, Extractor, Spider,
@@ -53,6 +53,8 @@ pub struct BusinessMetrics {
     pub gate_phase_duration: Histogram,
     pub wasm_phase_duration: Histogram,
     pub render_phase_duration: Histogram,
+    pub cache_write_phase_duration: Histogram,
+    pub event_emit_phase_duration: Histogram,
     pub pipeline_phase_gate_analysis_ms: Histogram,
     pub pipeline_phase_extraction_ms: Histogram,
 
@@ -333,6 +335,24 @@ impl BusinessMetrics {
             .buckets(vec![0.1, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0, 60.0]),
         )?;
 
+        let cache_write_phase_duration = Histogram::with_opts(
+            HistogramOpts::new(
+                "riptide_business_cache_write_phase_duration_seconds",
+                "Cache write phase duration",
+            )
+            .const_label("service", "riptide-business")
+            .buckets(vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5]),
+        )?;
+
+        let event_emit_phase_duration = Histogram::with_opts(
+            HistogramOpts::new(
+                "riptide_business_event_emit_phase_duration_seconds",
+                "Event emission time per pipeline execution",
+            )
+            .const_label("service", "riptide-business")
+            .buckets(vec![0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5]),
+        )?;
+
         let pipeline_phase_gate_analysis_ms = Histogram::with_opts(
             HistogramOpts::new(
                 "riptide_business_pipeline_phase_gate_analysis_milliseconds",
@@ -632,6 +652,8 @@ impl BusinessMetrics {
         registry.register(Box::new(gate_phase_duration.clone()))?;
         registry.register(Box::new(wasm_phase_duration.clone()))?;
         registry.register(Box::new(render_phase_duration.clone()))?;
+        registry.register(Box::new(cache_write_phase_duration.clone()))?;
+        registry.register(Box::new(event_emit_phase_duration.clone()))?;
         registry.register(Box::new(pipeline_phase_gate_analysis_ms.clone()))?;
         registry.register(Box::new(pipeline_phase_extraction_ms.clone()))?;
         registry.register(Box::new(pdf_total_processed.clone()))?;
@@ -693,6 +715,8 @@ impl BusinessMetrics {
             gate_phase_duration,
             wasm_phase_duration,
             render_phase_duration,
+            cache_write_phase_duration,
+            event_emit_phase_duration,
             pipeline_phase_gate_analysis_ms,
             pipeline_phase_extraction_ms,
             pdf_total_processed,
@@ -0,0 +1,121 @@
+//! HTTP client decorator that records every exchange to a web archive
+//!
+//! Wraps any [`HttpClient`] so callers that only see the port (spider,
+//! extraction fetches) produce WARC output without code changes. Archive
+//! failures are logged and never fail the underlying request.
+
+use async_trait::async_trait;
+use riptide_types::error::Result;
+use riptide_types::ports::{ArchivedExchange, HttpClient, HttpRequest, HttpResponse, WebArchive};
+use std::sync::Arc;
+use tracing::warn;
+
+/// [`HttpClient`] decorator writing request/response records to a [`WebArchive`]
+pub struct ArchivingHttpClient {
+    inner: Arc<dyn HttpClient>,
+    archive: Arc<dyn WebArchive>,
+}
+
+impl ArchivingHttpClient {
+    /// Wrap `inner`, archiving each successful exchange to `archive`
+    pub fn new(inner: Arc<dyn HttpClient>, archive: Arc<dyn WebArchive>) -> Self {
+        Self { inner, archive }
+    }
+
+    async fn record(&self, request: HttpRequest, response: &HttpResponse) {
+        let exchange = ArchivedExchange::new(request, response.clone());
+        if let Err(e) = self.archive.write_exchange(&exchange).await {
+            warn!(url = %exchange.target_uri, error = %e, "Failed to write WARC records");
+        }
+    }
+}
+
+#[async_trait]
+impl HttpClient for ArchivingHttpClient {
+    async fn get(&self, url: &str) -> Result<HttpResponse> {
+        let response = self.inner.get(url).await?;
+        self.record(HttpRequest::new("GET", url), &response).await;
+        Ok(response)
+    }
+
+    async fn post(&self, url: &str, body: &[u8]) -> Result<HttpResponse> {
+        let response = self.inner.post(url, body).await?;
+        self.record(
+            HttpRequest::new("POST", url).with_body(body.to_vec()),
+            &response,
+        )
+        .await;
+        Ok(response)
+    }
+
+    async fn request(&self, req: HttpRequest) -> Result<HttpResponse> {
+        let response = self.inner.request(req.clone()).await?;
+        self.record(req, &response).await;
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::warc::{WarcFileArchive, WarcWriterConfig};
+    use riptide_types::error::RiptideError;
+    use std::collections::HashMap;
+
+    struct StaticClient;
+
+    #[async_trait]
+    impl HttpClient for StaticClient {
+        async fn get(&self, url: &str) -> Result<HttpResponse> {
+            self.request(HttpRequest::new("GET", url)).await
+        }
+
+        async fn post(&self, url: &str, body: &[u8]) -> Result<HttpResponse> {
+            self.request(HttpRequest::new("POST", url).with_body(body.to_vec()))
+                .await
+        }
+
+        async fn request(&self, req: HttpRequest) -> Result<HttpResponse> {
+            if req.url.contains("unreachable") {
+                return Err(RiptideError::Network(format!("Cannot reach {}", req.url)));
+            }
+            Ok(HttpResponse::new(
+                200,
+                HashMap::new(),
+                b"<p>ok</p>".to_vec(),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_every_successful_fetch_is_archived() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = Arc::new(WarcFileArchive::new(WarcWriterConfig {
+            output_dir: dir.path().to_path_buf(),
+            gzip: false,
+            ..Default::default()
+        }));
+        let client = ArchivingHttpClient::new(Arc::new(StaticClient), archive.clone());
+
+        client.get("https://example.com/a").await.unwrap();
+        client.post("https://example.com/b", b"q=1").await.unwrap();
+        assert!(client.get("https://unreachable.test/").await.is_err());
+
+        let files = archive.finish().await.unwrap();
+        let text = std::fs::read_to_string(&files[0]).unwrap();
+        let targets: Vec<_> = text
+            .lines()
+            .filter_map(|l| l.strip_prefix("WARC-Target-URI: "))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                "https://example.com/a",
+                "https://example.com/a",
+                "https://example.com/b",
+                "https://example.com/b"
+            ]
+        );
+        assert!(text.contains("q=1"));
+    }
+}
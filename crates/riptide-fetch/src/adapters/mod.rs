@@ -6,8 +6,11 @@
 //! # Available Adapters
 //!
 //! - `reqwest_http_client`: Production HTTP client using reqwest with connection pooling
+//! - `archiving_http_client`: Decorator recording exchanges to a web archive (WARC)
 
+pub mod archiving_http_client;
 pub mod reqwest_http_client;
 
 // Re-export adapters
+pub use archiving_http_client::ArchivingHttpClient;
 pub use reqwest_http_client::ReqwestHttpClient;
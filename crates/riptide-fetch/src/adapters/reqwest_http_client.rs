@@ -7,28 +7,45 @@
 //! (riptide-reliability depends on riptide-fetch). For reliability features, use
 //! HttpClientService from riptide-reliability directly in higher-level modules.
 
+use crate::bandwidth::{self, BandwidthLimiter};
+use crate::body_stream::{BodyLimits, BodyStream, BodyStreamError};
+use crate::content_encoding::{self, CompressionMetrics, WireBytes, DEFAULT_MAX_DECODED_BYTES};
+use crate::dns::CachingResolver;
+use crate::middleware::{FetchMiddleware, MiddlewareChain};
 use async_trait::async_trait;
 use riptide_types::error::{Result, RiptideError};
 use riptide_types::ports::http::{HttpClient, HttpRequest, HttpResponse};
+use riptide_types::usage::UsageScope;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Reqwest-based HTTP client adapter
 pub struct ReqwestHttpClient {
     client: reqwest::Client,
+    body_limits: Option<BodyLimits>,
+    compression: Arc<CompressionMetrics>,
+    middleware: MiddlewareChain,
+    bandwidth: Option<Arc<BandwidthLimiter>>,
 }
 
 impl ReqwestHttpClient {
     /// Creates a new HTTP client with default configuration
     pub fn new() -> Result<Self> {
-        let client = reqwest::Client::builder()
+        let client = content_encoding::configure(reqwest::Client::builder())
             .pool_max_idle_per_host(10)
             .pool_idle_timeout(Duration::from_secs(90))
             .timeout(Duration::from_secs(30))
             .build()
             .map_err(|e| RiptideError::Network(format!("Failed to create HTTP client: {}", e)))?;
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            body_limits: None,
+            compression: Arc::default(),
+            middleware: MiddlewareChain::default(),
+            bandwidth: None,
+        })
     }
 
     /// Creates a new HTTP client with custom configuration
@@ -37,19 +54,97 @@ impl ReqwestHttpClient {
         max_idle_per_host: usize,
         idle_timeout: Duration,
     ) -> Result<Self> {
-        let client = reqwest::Client::builder()
+        let client = content_encoding::configure(reqwest::Client::builder())
             .pool_max_idle_per_host(max_idle_per_host)
             .pool_idle_timeout(idle_timeout)
             .timeout(timeout)
             .build()
             .map_err(|e| RiptideError::Network(format!("Failed to create HTTP client: {}", e)))?;
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            body_limits: None,
+            compression: Arc::default(),
+            middleware: MiddlewareChain::default(),
+            bandwidth: None,
+        })
+    }
+
+    /// Creates a new HTTP client resolving hosts through `resolver`
+    pub fn with_dns_resolver(resolver: Arc<CachingResolver>) -> Result<Self> {
+        let client = content_encoding::configure(reqwest::Client::builder())
+            .pool_max_idle_per_host(10)
+            .pool_idle_timeout(Duration::from_secs(90))
+            .timeout(Duration::from_secs(30))
+            .dns_resolver(resolver)
+            .build()
+            .map_err(|e| RiptideError::Network(format!("Failed to create HTTP client: {}", e)))?;
+
+        Ok(Self {
+            client,
+            body_limits: None,
+            compression: Arc::default(),
+            middleware: MiddlewareChain::default(),
+            bandwidth: None,
+        })
+    }
+
+    /// Streams bodies under `limits` instead of buffering them unchecked
+    ///
+    /// Oversized bodies and disallowed content types fail with
+    /// `RiptideError::ValidationError` as soon as they are detected.
+    pub fn with_body_limits(mut self, limits: BodyLimits) -> Self {
+        self.body_limits = Some(limits);
+        self
+    }
+
+    /// Record compression counters in `metrics`, e.g. the fetch engine's
+    pub fn with_compression_metrics(mut self, metrics: Arc<CompressionMetrics>) -> Self {
+        self.compression = metrics;
+        self
+    }
+
+    /// Run `middleware` around every request, after those already registered
+    pub fn with_middleware(mut self, middleware: Arc<dyn FetchMiddleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Read bodies no faster than `limiter` allows
+    pub fn with_bandwidth_limiter(mut self, limiter: Arc<BandwidthLimiter>) -> Self {
+        self.bandwidth = Some(limiter);
+        self
+    }
+
+    /// Sends a request through the middleware chain
+    async fn execute(&self, request: reqwest::Request) -> Result<reqwest::Response> {
+        self.middleware
+            .send(request, 0, |request| async move {
+                self.client
+                    .execute(request)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Request execution failed: {}", e))
+            })
+            .await
+            .map_err(|e| RiptideError::Network(e.to_string()))
     }
 
     /// Converts reqwest::Response to HttpResponse (anti-corruption layer)
-    async fn convert_response(resp: reqwest::Response) -> Result<HttpResponse> {
+    ///
+    /// The body's wire size is charged to the current [`UsageScope`].
+    async fn convert_response(&self, resp: reqwest::Response) -> Result<HttpResponse> {
+        // Body limits below the default are enforced by `BodyStream`
+        let max_decoded_bytes = self
+            .body_limits
+            .as_ref()
+            .map_or(DEFAULT_MAX_DECODED_BYTES, |limits| {
+                limits.max_content_length.max(DEFAULT_MAX_DECODED_BYTES)
+            });
+        let resp = content_encoding::decode_response(resp, max_decoded_bytes, &self.compression)
+            .map_err(|e| RiptideError::Network(e.to_string()))?;
+
         let status = resp.status().as_u16();
+        let wire = WireBytes::of(&resp);
 
         // Convert headers
         let mut headers = HashMap::new();
@@ -60,15 +155,38 @@ impl ReqwestHttpClient {
         }
 
         // Read body
-        let body = resp
-            .bytes()
-            .await
-            .map_err(|e| RiptideError::Network(format!("Failed to read response body: {}", e)))?
-            .to_vec();
+        let body = match &self.body_limits {
+            Some(limits) => self
+                .read_limited(resp, limits.clone())
+                .await
+                .map_err(|e| match e {
+                    BodyStreamError::Transport(_) => RiptideError::Network(e.to_string()),
+                    _ => RiptideError::ValidationError(e.to_string()),
+                })?,
+            None => match &self.bandwidth {
+                Some(limiter) => bandwidth::read_throttled(resp, limiter).await,
+                None => resp.bytes().await.map(|body| body.to_vec()),
+            }
+            .map_err(|e| RiptideError::Network(format!("Failed to read response body: {}", e)))?,
+        };
+        UsageScope::record_bandwidth(WireBytes::wire_size(wire.as_ref(), body.len()));
 
         Ok(HttpResponse::new(status, headers, body))
     }
 
+    /// Reads a body through [`BodyStream`], stopping at the first violation
+    async fn read_limited(
+        &self,
+        resp: reqwest::Response,
+        limits: BodyLimits,
+    ) -> std::result::Result<Vec<u8>, BodyStreamError> {
+        let mut stream = BodyStream::new(resp, limits).await?;
+        if let Some(limiter) = &self.bandwidth {
+            stream = stream.with_bandwidth_limiter(limiter.clone());
+        }
+        stream.collect().await
+    }
+
     /// Converts HttpRequest to reqwest::Request (anti-corruption layer)
     fn build_request(&self, req: HttpRequest) -> Result<reqwest::Request> {
         let method = reqwest::Method::from_bytes(req.method.as_bytes())
@@ -106,38 +224,33 @@ impl Default for ReqwestHttpClient {
 #[async_trait]
 impl HttpClient for ReqwestHttpClient {
     async fn get(&self, url: &str) -> Result<HttpResponse> {
-        let resp = self
+        let request = self
             .client
             .get(url)
-            .send()
-            .await
+            .build()
             .map_err(|e| RiptideError::Network(format!("GET request failed: {}", e)))?;
+        let resp = self.execute(request).await?;
 
-        Self::convert_response(resp).await
+        self.convert_response(resp).await
     }
 
     async fn post(&self, url: &str, body: &[u8]) -> Result<HttpResponse> {
-        let resp = self
+        let request = self
             .client
             .post(url)
             .body(body.to_vec())
-            .send()
-            .await
+            .build()
             .map_err(|e| RiptideError::Network(format!("POST request failed: {}", e)))?;
+        let resp = self.execute(request).await?;
 
-        Self::convert_response(resp).await
+        self.convert_response(resp).await
     }
 
     async fn request(&self, req: HttpRequest) -> Result<HttpResponse> {
         let request = self.build_request(req)?;
+        let resp = self.execute(request).await?;
 
-        let resp = self
-            .client
-            .execute(request)
-            .await
-            .map_err(|e| RiptideError::Network(format!("Request execution failed: {}", e)))?;
-
-        Self::convert_response(resp).await
+        self.convert_response(resp).await
     }
 }
 
@@ -180,6 +293,114 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_body_limits_reject_oversized_and_binary_bodies() {
+        use wiremock::matchers::path;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(path("/page"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw("<html></html>", "text/html"))
+            .mount(&server)
+            .await;
+        Mock::given(path("/video"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(vec![0u8; 64], "video/mp4"))
+            .mount(&server)
+            .await;
+        Mock::given(path("/huge"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(vec![b'a'; 8192], "text/plain"))
+            .mount(&server)
+            .await;
+
+        let client = ReqwestHttpClient::new()
+            .unwrap()
+            .with_body_limits(BodyLimits::default().with_max_content_length(4096));
+
+        let page = client.get(&format!("{}/page", server.uri())).await.unwrap();
+        assert_eq!(page.body, b"<html></html>");
+        for blocked in ["video", "huge"] {
+            let err = client
+                .get(&format!("{}/{}", server.uri(), blocked))
+                .await
+                .unwrap_err();
+            assert!(matches!(err, RiptideError::ValidationError(_)), "{}", err);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_charges_wire_bytes_to_usage_scope() {
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+        use wiremock::matchers::path;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let html = "<p>repeated</p>".repeat(1000);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(html.as_bytes()).unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        let server = MockServer::start().await;
+        Mock::given(path("/gzip"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-encoding", "gzip")
+                    .set_body_raw(gzipped.clone(), "text/html"),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(path("/plain"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw("plain", "text/plain"))
+            .mount(&server)
+            .await;
+
+        let client = ReqwestHttpClient::new().unwrap();
+        let usage = Arc::new(UsageScope::default());
+        let page = usage
+            .clone()
+            .scope(async {
+                client
+                    .get(&format!("{}/plain", server.uri()))
+                    .await
+                    .unwrap();
+                client.get(&format!("{}/gzip", server.uri())).await.unwrap()
+            })
+            .await;
+
+        assert_eq!(page.body, html.as_bytes());
+        assert_eq!(usage.bandwidth_bytes(), gzipped.len() as u64 + 5);
+    }
+
+    #[tokio::test]
+    async fn test_body_limits_accept_feeds() {
+        use wiremock::matchers::path;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let feeds = [
+            ("rss", "application/rss+xml", "<rss version=\"2.0\"></rss>"),
+            ("atom", "application/atom+xml", "<feed></feed>"),
+            ("json", "application/feed+json", "{\"version\":\"1.1\"}"),
+            ("rdf", "application/rdf+xml", "<rdf:RDF></rdf:RDF>"),
+        ];
+        for (name, content_type, body) in feeds {
+            Mock::given(path(format!("/{}", name)))
+                .respond_with(ResponseTemplate::new(200).set_body_raw(body, content_type))
+                .mount(&server)
+                .await;
+        }
+
+        let client = ReqwestHttpClient::new()
+            .unwrap()
+            .with_body_limits(BodyLimits::default());
+        for (name, _, body) in feeds {
+            let feed = client
+                .get(&format!("{}/{}", server.uri(), name))
+                .await
+                .unwrap();
+            assert_eq!(feed.body, body.as_bytes(), "{}", name);
+        }
+    }
+
     // Integration tests (require network)
     #[tokio::test]
     #[ignore = "Requires network access"]
@@ -0,0 +1,400 @@
+//! Download bandwidth limiting
+//!
+//! A [`BandwidthLimiter`] caps how fast response bodies are read, across all
+//! downloads and per host, so crawls on shared infrastructure leave room for
+//! other traffic. Each cap is a token bucket refilled at the configured
+//! bytes per second and holding up to `burst_secs` worth of tokens.
+//!
+//! Readers charge a chunk after receiving it and then sleep off any debt, so
+//! a chunk larger than the bucket still goes through and the average rate
+//! holds. Concurrent readers queue behind each other's debt. Throttling
+//! reads also slows the sender through TCP flow control, so the cap applies
+//! on the wire rather than only to what the crawler processes.
+//!
+//! Limits can be replaced at runtime with [`BandwidthLimiter::set_config`];
+//! per-host buckets restart from full on a change.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, RwLock};
+use std::time::{Duration, Instant};
+
+/// Host buckets kept before idle ones are dropped
+const MAX_DOMAIN_BUCKETS: usize = 10_000;
+
+/// Bandwidth caps in bytes per second; 0 means no cap
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BandwidthConfig {
+    /// Cap on all downloads together
+    pub global_bytes_per_sec: u64,
+    /// Cap applied to each host separately
+    pub per_domain_bytes_per_sec: u64,
+    /// Caps replacing `per_domain_bytes_per_sec` for a domain and its
+    /// subdomains, which then share one bucket; 0 exempts the domain
+    pub domain_overrides: BTreeMap<String, u64>,
+    /// Seconds of traffic a bucket saves up for bursts
+    pub burst_secs: f64,
+}
+
+impl Default for BandwidthConfig {
+    fn default() -> Self {
+        Self {
+            global_bytes_per_sec: 0,
+            per_domain_bytes_per_sec: 0,
+            domain_overrides: BTreeMap::new(),
+            burst_secs: 1.0,
+        }
+    }
+}
+
+impl BandwidthConfig {
+    /// Read caps from `RIPTIDE_BANDWIDTH_GLOBAL_BPS`,
+    /// `RIPTIDE_BANDWIDTH_PER_DOMAIN_BPS` and `RIPTIDE_BANDWIDTH_BURST_SECS`
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        let read = |name: &str| std::env::var(name).ok().and_then(|v| v.trim().parse().ok());
+        if let Some(bps) = read("RIPTIDE_BANDWIDTH_GLOBAL_BPS") {
+            config.global_bytes_per_sec = bps;
+        }
+        if let Some(bps) = read("RIPTIDE_BANDWIDTH_PER_DOMAIN_BPS") {
+            config.per_domain_bytes_per_sec = bps;
+        }
+        if let Some(secs) = std::env::var("RIPTIDE_BANDWIDTH_BURST_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+        {
+            config.burst_secs = secs;
+        }
+        config
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.burst_secs.is_finite() || self.burst_secs <= 0.0 {
+            return Err(format!(
+                "burst_secs must be a positive number, got {}",
+                self.burst_secs
+            ));
+        }
+        if let Some(domain) = self.domain_overrides.keys().find(|d| d.trim().is_empty()) {
+            return Err(format!("Invalid domain override {:?}", domain));
+        }
+        Ok(())
+    }
+
+    /// Whether any cap is set
+    pub fn is_limited(&self) -> bool {
+        self.global_bytes_per_sec > 0
+            || self.per_domain_bytes_per_sec > 0
+            || self.domain_overrides.values().any(|&bps| bps > 0)
+    }
+
+    /// Bucket key and cap for `host`
+    fn domain_limit(&self, host: &str) -> (String, u64) {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        let matched = self.domain_overrides.iter().find(|(domain, _)| {
+            host == domain.as_str()
+                || host
+                    .strip_suffix(domain.as_str())
+                    .is_some_and(|sub| sub.ends_with('.'))
+        });
+        match matched {
+            Some((domain, &bps)) => (domain.clone(), bps),
+            None => (host, self.per_domain_bytes_per_sec),
+        }
+    }
+}
+
+/// Token bucket that may go into debt
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(bytes_per_sec: u64, burst_secs: f64, now: Instant) -> Self {
+        let rate = bytes_per_sec as f64;
+        let capacity = (rate * burst_secs).max(1.0);
+        Self {
+            rate,
+            capacity,
+            tokens: capacity,
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.updated = now;
+    }
+
+    /// Take `bytes` and return how long to wait until the debt is paid
+    fn reserve(&mut self, bytes: u64, now: Instant) -> Duration {
+        self.refill(now);
+        self.tokens -= bytes as f64;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+
+    fn is_full(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens >= self.capacity
+    }
+}
+
+/// Counters of a [`BandwidthLimiter`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BandwidthStats {
+    /// Body bytes charged
+    pub bytes: u64,
+    /// Reads that had to wait
+    pub throttled_reads: u64,
+    /// Total time spent waiting, in milliseconds
+    pub throttled_ms: u64,
+    /// Hosts with a live bucket
+    pub tracked_domains: usize,
+}
+
+/// Global and per-host download rate limiter
+#[derive(Debug)]
+pub struct BandwidthLimiter {
+    config: RwLock<BandwidthConfig>,
+    global: Mutex<Option<TokenBucket>>,
+    domains: Mutex<HashMap<String, TokenBucket>>,
+    bytes: AtomicU64,
+    throttled_reads: AtomicU64,
+    throttled_ms: AtomicU64,
+}
+
+impl BandwidthLimiter {
+    pub fn new(config: BandwidthConfig) -> Self {
+        let limiter = Self {
+            config: RwLock::new(BandwidthConfig::default()),
+            global: Mutex::new(None),
+            domains: Mutex::new(HashMap::new()),
+            bytes: AtomicU64::new(0),
+            throttled_reads: AtomicU64::new(0),
+            throttled_ms: AtomicU64::new(0),
+        };
+        limiter.set_config(config);
+        limiter
+    }
+
+    /// Current limits
+    pub fn config(&self) -> BandwidthConfig {
+        self.config
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    /// Replace the limits; reads already waiting keep their delay
+    pub fn set_config(&self, mut config: BandwidthConfig) {
+        config.domain_overrides = config
+            .domain_overrides
+            .into_iter()
+            .map(|(domain, bps)| (domain.trim_end_matches('.').to_ascii_lowercase(), bps))
+            .collect();
+        // Held while the buckets are reset so no read sees old buckets with new limits
+        let mut current = self
+            .config
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+        *lock(&self.global) = (config.global_bytes_per_sec > 0)
+            .then(|| TokenBucket::new(config.global_bytes_per_sec, config.burst_secs, now));
+        lock(&self.domains).clear();
+        *current = config;
+    }
+
+    /// Charge `bytes` read from `host`, returning how long to wait
+    pub fn reserve(&self, host: &str, bytes: u64) -> Duration {
+        self.bytes.fetch_add(bytes, Ordering::Relaxed);
+        let now = Instant::now();
+        let config = self
+            .config
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let global_wait = lock(&self.global)
+            .as_mut()
+            .map_or(Duration::ZERO, |bucket| bucket.reserve(bytes, now));
+
+        let (key, bps) = config.domain_limit(host);
+        let domain_wait = if bps > 0 {
+            let mut domains = lock(&self.domains);
+            if domains.len() >= MAX_DOMAIN_BUCKETS && !domains.contains_key(&key) {
+                domains.retain(|_, bucket| !bucket.is_full(now));
+            }
+            domains
+                .entry(key)
+                .or_insert_with(|| TokenBucket::new(bps, config.burst_secs, now))
+                .reserve(bytes, now)
+        } else {
+            Duration::ZERO
+        };
+
+        global_wait.max(domain_wait)
+    }
+
+    /// Charge `bytes` read from `host` and wait until they fit the limits
+    pub async fn acquire(&self, host: &str, bytes: u64) {
+        let wait = self.reserve(host, bytes);
+        if !wait.is_zero() {
+            self.throttled_reads.fetch_add(1, Ordering::Relaxed);
+            self.throttled_ms
+                .fetch_add(wait.as_millis() as u64, Ordering::Relaxed);
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    pub fn stats(&self) -> BandwidthStats {
+        BandwidthStats {
+            bytes: self.bytes.load(Ordering::Relaxed),
+            throttled_reads: self.throttled_reads.load(Ordering::Relaxed),
+            throttled_ms: self.throttled_ms.load(Ordering::Relaxed),
+            tracked_domains: lock(&self.domains).len(),
+        }
+    }
+}
+
+impl Default for BandwidthLimiter {
+    fn default() -> Self {
+        Self::new(BandwidthConfig::default())
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Read a whole response body at the limiter's pace
+pub async fn read_throttled(
+    mut response: reqwest::Response,
+    limiter: &BandwidthLimiter,
+) -> reqwest::Result<Vec<u8>> {
+    let host = response.url().host_str().unwrap_or_default().to_string();
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        limiter.acquire(&host, chunk.len() as u64).await;
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_allows_burst_then_charges_debt() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(1000, 1.0, now);
+
+        assert_eq!(bucket.reserve(1000, now), Duration::ZERO);
+        assert_eq!(bucket.reserve(500, now), Duration::from_millis(500));
+        // Another reader queues behind the existing debt
+        assert_eq!(bucket.reserve(500, now), Duration::from_secs(1));
+        assert_eq!(
+            bucket.reserve(0, now + Duration::from_secs(1)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_unlimited_config_never_waits() {
+        let limiter = BandwidthLimiter::default();
+        assert_eq!(limiter.reserve("example.com", u64::MAX / 2), Duration::ZERO);
+        assert_eq!(limiter.stats().tracked_domains, 0);
+    }
+
+    #[test]
+    fn test_domains_are_limited_separately() {
+        let limiter = BandwidthLimiter::new(BandwidthConfig {
+            per_domain_bytes_per_sec: 1000,
+            ..Default::default()
+        });
+
+        assert_eq!(limiter.reserve("a.example", 1000), Duration::ZERO);
+        assert_eq!(limiter.reserve("b.example", 1000), Duration::ZERO);
+        assert!(limiter.reserve("a.example", 1000) > Duration::from_millis(900));
+        assert_eq!(limiter.stats().tracked_domains, 2);
+    }
+
+    #[test]
+    fn test_global_limit_spans_domains() {
+        let limiter = BandwidthLimiter::new(BandwidthConfig {
+            global_bytes_per_sec: 1000,
+            ..Default::default()
+        });
+
+        assert_eq!(limiter.reserve("a.example", 1000), Duration::ZERO);
+        assert!(limiter.reserve("b.example", 1000) > Duration::from_millis(900));
+    }
+
+    #[test]
+    fn test_overrides_share_a_bucket_across_subdomains() {
+        let config = BandwidthConfig {
+            per_domain_bytes_per_sec: 1000,
+            domain_overrides: BTreeMap::from([("cdn.example".to_string(), 5000)]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.domain_limit("img.CDN.example."),
+            ("cdn.example".to_string(), 5000)
+        );
+        assert_eq!(
+            config.domain_limit("notcdn.example"),
+            ("notcdn.example".to_string(), 1000)
+        );
+    }
+
+    #[test]
+    fn test_set_config_applies_new_limits() {
+        let limiter = BandwidthLimiter::new(BandwidthConfig {
+            global_bytes_per_sec: 1000,
+            ..Default::default()
+        });
+        limiter.reserve("a.example", 1000);
+        assert!(!limiter.reserve("a.example", 1000).is_zero());
+
+        limiter.set_config(BandwidthConfig::default());
+        assert!(limiter.reserve("a.example", 1_000_000).is_zero());
+        assert_eq!(limiter.stats().bytes, 1_002_000);
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_burst() {
+        let config = BandwidthConfig {
+            burst_secs: 0.0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        assert!(BandwidthConfig::default().validate().is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_acquire_sleeps_off_debt() {
+        let limiter = BandwidthLimiter::new(BandwidthConfig {
+            global_bytes_per_sec: 1000,
+            ..Default::default()
+        });
+        let start = tokio::time::Instant::now();
+        limiter.acquire("a.example", 1000).await;
+        limiter.acquire("a.example", 2000).await;
+
+        assert!(start.elapsed() >= Duration::from_secs(2));
+        assert_eq!(limiter.stats().throttled_reads, 1);
+    }
+}
@@ -0,0 +1,461 @@
+//! Streaming response bodies with size and content-type limits
+//!
+//! [`BodyStream`] hands a response body out chunk by chunk instead of
+//! buffering it, so a crawl that runs into a multi-gigabyte file gives up
+//! after `max_content_length` bytes rather than after exhausting memory.
+//! Before the caller sees any data, the first `sniff_bytes` are read and the
+//! content type (declared, corrected by magic numbers) is checked against the
+//! allow-list. Dropping the stream closes the connection, so any error is an
+//! early abort.
+//!
+//! Limits count decoded bytes, which also bounds gzip/brotli bombs.
+
+use crate::bandwidth::BandwidthLimiter;
+use bytes::{Bytes, BytesMut};
+use futures::Stream;
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use reqwest::{Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Media types fetched for extraction by default
+pub const DEFAULT_ALLOWED_CONTENT_TYPES: &[&str] = &[
+    "text/html",
+    "application/xhtml+xml",
+    "application/xml",
+    "text/xml",
+    "text/plain",
+    "application/pdf",
+    "application/json",
+    "application/ld+json",
+    "application/rss+xml",
+    "application/atom+xml",
+    "application/feed+json",
+    "application/rdf+xml",
+];
+
+/// Limits applied while a body streams in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BodyLimits {
+    /// Largest accepted body in bytes
+    pub max_content_length: u64,
+    /// Bytes read before the content type is checked
+    pub sniff_bytes: usize,
+    /// Accepted media types (`type/subtype` or `type/*`), empty accepts all
+    pub allowed_content_types: Vec<String>,
+}
+
+impl Default for BodyLimits {
+    fn default() -> Self {
+        Self {
+            max_content_length: 20 * 1024 * 1024,
+            sniff_bytes: 1024,
+            allowed_content_types: DEFAULT_ALLOWED_CONTENT_TYPES
+                .iter()
+                .map(|t| t.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl BodyLimits {
+    /// Set the largest accepted body
+    pub fn with_max_content_length(mut self, max_content_length: u64) -> Self {
+        self.max_content_length = max_content_length;
+        self
+    }
+
+    /// Replace the content-type allow-list
+    pub fn with_allowed_content_types<I, S>(mut self, content_types: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_content_types = content_types.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Whether `content_type` matches the allow-list
+    pub fn allows(&self, content_type: &str) -> bool {
+        if self.allowed_content_types.is_empty() {
+            return true;
+        }
+        let actual = essence(content_type);
+        self.allowed_content_types.iter().any(|allowed| {
+            let allowed = essence(allowed);
+            match allowed.strip_suffix("/*") {
+                Some("*") => true,
+                Some(top) => actual
+                    .split_once('/')
+                    .is_some_and(|(actual_top, _)| actual_top == top),
+                None => actual == allowed,
+            }
+        })
+    }
+}
+
+/// Why a body stream was aborted
+#[derive(Debug, Error)]
+pub enum BodyStreamError {
+    #[error("Declared Content-Length {declared} exceeds the {limit} byte limit")]
+    DeclaredTooLarge { declared: u64, limit: u64 },
+
+    #[error("Body exceeded the {limit} byte limit after {received} bytes")]
+    TooLarge { limit: u64, received: u64 },
+
+    #[error("Content type {content_type} is not allowed")]
+    DisallowedContentType { content_type: String },
+
+    #[error("Failed to read response body: {0}")]
+    Transport(#[from] reqwest::Error),
+}
+
+/// Response body delivered in chunks under [`BodyLimits`]
+#[derive(Debug)]
+pub struct BodyStream {
+    response: Response,
+    status: StatusCode,
+    headers: HeaderMap,
+    content_type: String,
+    max_content_length: u64,
+    /// Sniffed bytes not handed out yet
+    prefix: Option<Bytes>,
+    received: u64,
+    finished: bool,
+    bandwidth: Option<Arc<BandwidthLimiter>>,
+}
+
+impl BodyStream {
+    /// Start streaming `response`
+    ///
+    /// Fails before reading anything when the declared `Content-Length` is
+    /// over the limit, and after the sniffed prefix when the content type is
+    /// not allowed. Empty bodies skip the content-type check.
+    pub async fn new(mut response: Response, limits: BodyLimits) -> Result<Self, BodyStreamError> {
+        let limit = limits.max_content_length;
+        if let Some(declared) = response.content_length().filter(|&len| len > limit) {
+            return Err(BodyStreamError::DeclaredTooLarge { declared, limit });
+        }
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let mut prefix = BytesMut::new();
+        let mut finished = false;
+        while prefix.len() < limits.sniff_bytes {
+            let Some(chunk) = response.chunk().await? else {
+                finished = true;
+                break;
+            };
+            prefix.extend_from_slice(&chunk);
+            let received = prefix.len() as u64;
+            if received > limit {
+                return Err(BodyStreamError::TooLarge { limit, received });
+            }
+        }
+
+        let declared = headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok());
+        let content_type = sniff_content_type(declared, &prefix);
+        if !prefix.is_empty() && !limits.allows(&content_type) {
+            return Err(BodyStreamError::DisallowedContentType { content_type });
+        }
+
+        Ok(Self {
+            response,
+            status,
+            headers,
+            content_type,
+            max_content_length: limit,
+            received: prefix.len() as u64,
+            prefix: Some(prefix.freeze()),
+            finished,
+            bandwidth: None,
+        })
+    }
+
+    /// Hand chunks out no faster than `limiter` allows
+    ///
+    /// The sniffed prefix is charged when it is handed out.
+    pub fn with_bandwidth_limiter(mut self, limiter: Arc<BandwidthLimiter>) -> Self {
+        self.bandwidth = Some(limiter);
+        self
+    }
+
+    /// Response status
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Response headers
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Content type after sniffing (essence only, lowercase)
+    pub fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    /// Bytes read from the connection so far
+    pub fn received(&self) -> u64 {
+        self.received
+    }
+
+    /// Next body chunk, `None` at the end of the body
+    pub async fn next_chunk(&mut self) -> Result<Option<Bytes>, BodyStreamError> {
+        let chunk = self.read_chunk().await?;
+        if let (Some(limiter), Some(chunk)) = (&self.bandwidth, &chunk) {
+            let host = self.response.url().host_str().unwrap_or_default();
+            limiter.acquire(host, chunk.len() as u64).await;
+        }
+        Ok(chunk)
+    }
+
+    async fn read_chunk(&mut self) -> Result<Option<Bytes>, BodyStreamError> {
+        if let Some(prefix) = self.prefix.take().filter(|p| !p.is_empty()) {
+            return Ok(Some(prefix));
+        }
+        if self.finished {
+            return Ok(None);
+        }
+        let Some(chunk) = self.response.chunk().await? else {
+            self.finished = true;
+            return Ok(None);
+        };
+        self.received += chunk.len() as u64;
+        if self.received > self.max_content_length {
+            self.finished = true;
+            return Err(BodyStreamError::TooLarge {
+                limit: self.max_content_length,
+                received: self.received,
+            });
+        }
+        Ok(Some(chunk))
+    }
+
+    /// Read the rest of the body into memory
+    pub async fn collect(mut self) -> Result<Vec<u8>, BodyStreamError> {
+        let mut body = Vec::new();
+        while let Some(chunk) = self.next_chunk().await? {
+            body.extend_from_slice(&chunk);
+        }
+        Ok(body)
+    }
+
+    /// The remaining body as a [`Stream`] of chunks
+    pub fn into_stream(self) -> impl Stream<Item = Result<Bytes, BodyStreamError>> {
+        futures::stream::try_unfold(self, |mut stream| async move {
+            Ok(stream.next_chunk().await?.map(|chunk| (chunk, stream)))
+        })
+    }
+}
+
+/// Media type of a body from its declared `Content-Type` and first bytes
+///
+/// Binary signatures (PDF, archives, images, video, executables) win over the
+/// declared type, so a download mislabelled as `text/html` is still caught.
+/// Markup is only recognized when nothing more specific than
+/// `application/octet-stream` was declared.
+pub fn sniff_content_type(declared: Option<&str>, prefix: &[u8]) -> String {
+    if let Some(binary) = binary_signature(prefix) {
+        return binary.to_string();
+    }
+    match declared.map(essence) {
+        Some(essence) if !essence.is_empty() && essence != "application/octet-stream" => essence,
+        _ => markup_signature(prefix)
+            .unwrap_or("application/octet-stream")
+            .to_string(),
+    }
+}
+
+/// Lowercase `type/subtype` without parameters
+fn essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn binary_signature(prefix: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1f\x8b", "application/gzip"),
+        (b"7z\xbc\xaf\x27\x1c", "application/x-7z-compressed"),
+        (b"Rar!\x1a\x07", "application/vnd.rar"),
+        (b"\x7fELF", "application/x-executable"),
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"\x1a\x45\xdf\xa3", "video/webm"),
+        (b"OggS", "audio/ogg"),
+        (b"fLaC", "audio/flac"),
+    ];
+    if let Some((_, media_type)) = SIGNATURES.iter().find(|(sig, _)| prefix.starts_with(sig)) {
+        return Some(media_type);
+    }
+    match (prefix.get(..4), prefix.get(4..8), prefix.get(8..12)) {
+        (Some(b"RIFF"), _, Some(b"WEBP")) => Some("image/webp"),
+        (Some(b"RIFF"), _, Some(b"WAVE")) => Some("audio/wav"),
+        (Some(b"RIFF"), _, Some(b"AVI ")) => Some("video/x-msvideo"),
+        (_, Some(b"ftyp"), _) => Some("video/mp4"),
+        _ => None,
+    }
+}
+
+fn markup_signature(prefix: &[u8]) -> Option<&'static str> {
+    let text = prefix.strip_prefix(b"\xef\xbb\xbf").unwrap_or(prefix);
+    let start = text.iter().position(|b| !b.is_ascii_whitespace())?;
+    let head: Vec<u8> = text[start..]
+        .iter()
+        .take(14)
+        .map(u8::to_ascii_lowercase)
+        .collect();
+    const HTML: &[&[u8]] = &[
+        b"<!doctype html",
+        b"<html",
+        b"<head",
+        b"<body",
+        b"<!--",
+        b"<script",
+        b"<title",
+        b"<div",
+        b"<p>",
+    ];
+    if HTML.iter().any(|sig| head.starts_with(sig)) {
+        Some("text/html")
+    } else if head.starts_with(b"<?xml") {
+        Some("application/xml")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::path;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn serve(body: Vec<u8>, content_type: &str) -> (MockServer, Response) {
+        let server = MockServer::start().await;
+        Mock::given(path("/body"))
+            .respond_with(ResponseTemplate::new(200).set_body_raw(body, content_type))
+            .mount(&server)
+            .await;
+        let response = reqwest::get(format!("{}/body", server.uri()))
+            .await
+            .unwrap();
+        (server, response)
+    }
+
+    #[test]
+    fn test_sniff_content_type() {
+        assert_eq!(
+            sniff_content_type(Some("text/html; charset=UTF-8"), b"<p>hi"),
+            "text/html"
+        );
+        assert_eq!(
+            sniff_content_type(Some("text/html"), b"%PDF-1.7\n"),
+            "application/pdf"
+        );
+        assert_eq!(
+            sniff_content_type(None, b"\xef\xbb\xbf  <!DOCTYPE HTML>"),
+            "text/html"
+        );
+        assert_eq!(
+            sniff_content_type(Some("application/octet-stream"), b"<?xml version"),
+            "application/xml"
+        );
+        assert_eq!(sniff_content_type(None, b"\0\0\0\x18ftypmp42"), "video/mp4");
+        assert_eq!(
+            sniff_content_type(None, b"plain"),
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn test_allows_wildcards_and_parameters() {
+        let limits = BodyLimits::default();
+        assert!(limits.allows("Text/HTML; charset=utf-8"));
+        assert!(!limits.allows("video/mp4"));
+
+        let images = limits.clone().with_allowed_content_types(["image/*"]);
+        assert!(images.allows("image/png"));
+        assert!(!images.allows("text/html"));
+
+        assert!(limits
+            .with_allowed_content_types(Vec::<String>::new())
+            .allows("video/mp4"));
+    }
+
+    #[tokio::test]
+    async fn test_streams_allowed_body_in_full() {
+        let html = format!("<html><body>{}</body></html>", "x".repeat(100_000));
+        let (_server, response) = serve(html.clone().into_bytes(), "text/html").await;
+
+        let stream = BodyStream::new(response, BodyLimits::default())
+            .await
+            .unwrap();
+        assert_eq!(stream.status(), StatusCode::OK);
+        assert_eq!(stream.content_type(), "text/html");
+        assert_eq!(stream.collect().await.unwrap(), html.into_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_declared_oversized_body() {
+        let (_server, response) = serve(vec![b'a'; 4096], "text/plain").await;
+        let limits = BodyLimits::default().with_max_content_length(1024);
+
+        let err = BodyStream::new(response, limits).await.unwrap_err();
+        assert!(matches!(
+            err,
+            BodyStreamError::DeclaredTooLarge {
+                declared: 4096,
+                limit: 1024
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_aborts_mid_stream_past_limit() {
+        let (_server, response) = serve(vec![b'a'; 256 * 1024], "text/html").await;
+        let mut stream = BodyStream::new(response, BodyLimits::default())
+            .await
+            .unwrap();
+        // As if the server had omitted Content-Length
+        stream.max_content_length = 64 * 1024;
+
+        let mut result = Ok(None);
+        for _ in 0..1024 {
+            result = stream.next_chunk().await;
+            if !matches!(result, Ok(Some(_))) {
+                break;
+            }
+        }
+        assert!(matches!(
+            result,
+            Err(BodyStreamError::TooLarge { limit: 65536, .. })
+        ));
+        assert!(matches!(stream.next_chunk().await, Ok(None)));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_sniffed_binary_served_as_html() {
+        let mut zip = b"PK\x03\x04".to_vec();
+        zip.extend(std::iter::repeat_n(0u8, 8192));
+        let (_server, response) = serve(zip, "text/html").await;
+
+        let err = BodyStream::new(response, BodyLimits::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            BodyStreamError::DisallowedContentType { ref content_type } if content_type == "application/zip"
+        ));
+    }
+}
@@ -0,0 +1,475 @@
+//! Content-Encoding negotiation and decoding
+//!
+//! Fetch clients are built with reqwest's own decoders switched off (see
+//! [`configure`]) so the wire size of every body stays observable.
+//! [`decode_response`] wraps a compressed body in a streaming gzip, deflate,
+//! brotli or zstd decoder, fails the body once the decoded size passes
+//! `max_decoded_bytes` (decompression bombs), and reports compressed and
+//! decoded sizes to [`CompressionMetrics`] when the body has been read. The
+//! compressed size of a decoded body is also available from the response
+//! itself as [`WireBytes`].
+
+use anyhow::Result;
+use async_compression::tokio::bufread::{BrotliDecoder, GzipDecoder, ZlibDecoder, ZstdDecoder};
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH};
+use reqwest::{ClientBuilder, Response, ResponseBuilderExt, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncRead;
+use tokio_util::io::{ReaderStream, StreamReader};
+
+/// `Accept-Encoding` sent with every request
+pub const ACCEPTED_ENCODINGS: &str = "gzip, deflate, br, zstd";
+
+/// Default limit on a decoded body, in bytes
+pub const DEFAULT_MAX_DECODED_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Content codings decoded by [`decode_response`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContentEncoding {
+    Gzip,
+    Deflate,
+    Brotli,
+    Zstd,
+}
+
+impl ContentEncoding {
+    /// Parse a `Content-Encoding` value, `None` for identity, stacked or
+    /// unknown codings
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(Self::Gzip),
+            "deflate" => Some(Self::Deflate),
+            "br" => Some(Self::Brotli),
+            "zstd" => Some(Self::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Token as it appears in `Content-Encoding`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Gzip => "gzip",
+            Self::Deflate => "deflate",
+            Self::Brotli => "br",
+            Self::Zstd => "zstd",
+        }
+    }
+}
+
+/// Advertise [`ACCEPTED_ENCODINGS`] and leave decoding to [`decode_response`]
+pub fn configure(builder: ClientBuilder) -> ClientBuilder {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        ACCEPT_ENCODING,
+        HeaderValue::from_static(ACCEPTED_ENCODINGS),
+    );
+    builder
+        .no_gzip()
+        .no_brotli()
+        .no_deflate()
+        .no_zstd()
+        .default_headers(headers)
+}
+
+/// Counters for one content coding
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EncodingStats {
+    /// Bodies decoded to the end
+    pub responses: u64,
+    /// Bytes received on the wire
+    pub compressed_bytes: u64,
+    /// Bytes after decoding
+    pub decoded_bytes: u64,
+    /// `decoded_bytes / compressed_bytes`
+    pub compression_ratio: f64,
+}
+
+/// Snapshot of [`CompressionMetrics`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CompressionStats {
+    /// Counters per content coding (`gzip`, `deflate`, `br`, `zstd`)
+    pub encodings: BTreeMap<String, EncodingStats>,
+    /// Responses received without a content coding
+    pub identity_responses: u64,
+    /// Bodies aborted for passing the decoded size limit
+    pub oversized_bodies: u64,
+    /// Decoded over compressed bytes across all codings
+    pub compression_ratio: f64,
+}
+
+/// Compression counters shared by the clients of a fetch engine
+#[derive(Debug, Default)]
+pub struct CompressionMetrics {
+    inner: Mutex<CompressionStats>,
+}
+
+impl CompressionMetrics {
+    /// Record a fully decoded body
+    pub fn record(&self, encoding: ContentEncoding, compressed_bytes: u64, decoded_bytes: u64) {
+        if let Ok(mut stats) = self.inner.lock() {
+            let entry = stats
+                .encodings
+                .entry(encoding.as_str().to_string())
+                .or_default();
+            entry.responses += 1;
+            entry.compressed_bytes += compressed_bytes;
+            entry.decoded_bytes += decoded_bytes;
+        }
+    }
+
+    fn record_identity(&self) {
+        if let Ok(mut stats) = self.inner.lock() {
+            stats.identity_responses += 1;
+        }
+    }
+
+    fn record_oversized(&self) {
+        if let Ok(mut stats) = self.inner.lock() {
+            stats.oversized_bodies += 1;
+        }
+    }
+
+    /// Current counters with ratios filled in
+    pub fn snapshot(&self) -> CompressionStats {
+        let mut stats = self
+            .inner
+            .lock()
+            .map(|stats| stats.clone())
+            .unwrap_or_default();
+        let (mut compressed, mut decoded) = (0, 0);
+        for entry in stats.encodings.values_mut() {
+            entry.compression_ratio = ratio(entry.decoded_bytes, entry.compressed_bytes);
+            compressed += entry.compressed_bytes;
+            decoded += entry.decoded_bytes;
+        }
+        stats.compression_ratio = ratio(decoded, compressed);
+        stats
+    }
+}
+
+/// Bytes of an encoded body received on the wire so far
+///
+/// [`decode_response`] attaches one to every response it decodes; take it
+/// with [`WireBytes::of`] before reading the body and read it afterwards.
+/// Responses without one arrived unencoded, so their body length is their
+/// wire size.
+#[derive(Debug, Clone, Default)]
+pub struct WireBytes(Arc<AtomicU64>);
+
+impl WireBytes {
+    /// Counter of a decoded response, `None` for unencoded responses
+    pub fn of(response: &Response) -> Option<Self> {
+        response.extensions().get::<Self>().cloned()
+    }
+
+    /// Wire bytes of a body whose decoded length is `body_len`
+    pub fn wire_size(wire: Option<&Self>, body_len: usize) -> u64 {
+        wire.map_or(body_len as u64, Self::get)
+    }
+
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+fn ratio(decoded: u64, compressed: u64) -> f64 {
+    if compressed == 0 {
+        0.0
+    } else {
+        decoded as f64 / compressed as f64
+    }
+}
+
+/// Replace an encoded body with its streaming decoding
+///
+/// Responses without a supported coding, and responses that cannot carry a
+/// body (HEAD, 1xx, 204, 304, `Content-Length: 0`), are returned unchanged.
+/// The decoded response drops `Content-Encoding` and `Content-Length` and
+/// carries the [`WireBytes`] counter of its body.
+pub fn decode_response(
+    response: Response,
+    max_decoded_bytes: u64,
+    metrics: &Arc<CompressionMetrics>,
+) -> Result<Response> {
+    let coding = response
+        .headers()
+        .get(CONTENT_ENCODING)
+        .map(|value| value.to_str().unwrap_or_default().trim().to_string());
+    let encoding = match coding.as_deref() {
+        None | Some("") | Some("identity") => {
+            metrics.record_identity();
+            return Ok(response);
+        }
+        Some(coding) => match ContentEncoding::parse(coding) {
+            Some(encoding) => encoding,
+            None => return Ok(response),
+        },
+    };
+    if has_empty_body(&response) {
+        return Ok(response);
+    }
+
+    let wire = WireBytes::default();
+    let mut builder = http::Response::builder()
+        .status(response.status())
+        .version(response.version())
+        .url(response.url().clone())
+        .extension(wire.clone());
+    for (name, value) in response.headers() {
+        if name != CONTENT_ENCODING && name != CONTENT_LENGTH {
+            builder = builder.header(name, value);
+        }
+    }
+    let body = decoded_stream(
+        response.bytes_stream(),
+        encoding,
+        max_decoded_bytes,
+        wire,
+        metrics.clone(),
+    );
+    let decoded = builder
+        .body(reqwest::Body::wrap_stream(body))
+        .map_err(|e| anyhow::anyhow!("Failed to rebuild decoded response: {}", e))?;
+    Ok(Response::from(decoded))
+}
+
+/// Whether the response has no body to decode
+///
+/// A HEAD response has an exact body size of 0 whatever `Content-Length`
+/// says, so the size hint covers it along with `Content-Length: 0`.
+fn has_empty_body(response: &Response) -> bool {
+    let status = response.status();
+    status.is_informational()
+        || status == StatusCode::NO_CONTENT
+        || status == StatusCode::NOT_MODIFIED
+        || response.content_length() == Some(0)
+}
+
+fn decoded_stream(
+    raw: impl Stream<Item = reqwest::Result<Bytes>> + Send + 'static,
+    encoding: ContentEncoding,
+    max_decoded_bytes: u64,
+    wire: WireBytes,
+    metrics: Arc<CompressionMetrics>,
+) -> impl Stream<Item = io::Result<Bytes>> + Send + 'static {
+    let compressed = wire.0;
+    let counter = compressed.clone();
+    let reader = StreamReader::new(raw.map(move |chunk| {
+        let chunk = chunk.map_err(io::Error::other)?;
+        counter.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+        Ok::<_, io::Error>(chunk)
+    }));
+    let decoder: Pin<Box<dyn AsyncRead + Send>> = match encoding {
+        ContentEncoding::Gzip => Box::pin(GzipDecoder::new(reader)),
+        ContentEncoding::Deflate => Box::pin(ZlibDecoder::new(reader)),
+        ContentEncoding::Brotli => Box::pin(BrotliDecoder::new(reader)),
+        ContentEncoding::Zstd => Box::pin(ZstdDecoder::new(reader)),
+    };
+
+    futures::stream::unfold(Some((ReaderStream::new(decoder), 0u64)), move |state| {
+        let compressed = compressed.clone();
+        let metrics = metrics.clone();
+        async move {
+            let (mut chunks, decoded) = state?;
+            match chunks.next().await {
+                Some(Ok(chunk)) => {
+                    let decoded = decoded + chunk.len() as u64;
+                    if decoded > max_decoded_bytes {
+                        metrics.record_oversized();
+                        let error = io::Error::other(format!(
+                            "Decoded {} body exceeds {} bytes",
+                            encoding.as_str(),
+                            max_decoded_bytes
+                        ));
+                        return Some((Err(error), None));
+                    }
+                    Some((Ok(chunk), Some((chunks, decoded))))
+                }
+                Some(Err(e)) => Some((Err(e), None)),
+                None => {
+                    metrics.record(encoding, compressed.load(Ordering::Relaxed), decoded);
+                    None
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_compression::tokio::bufread::{BrotliEncoder, GzipEncoder, ZlibEncoder, ZstdEncoder};
+    use tokio::io::AsyncReadExt;
+    use wiremock::matchers::path;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn encode(encoding: ContentEncoding, data: &[u8]) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        match encoding {
+            ContentEncoding::Gzip => GzipEncoder::new(data).read_to_end(&mut encoded).await,
+            ContentEncoding::Deflate => ZlibEncoder::new(data).read_to_end(&mut encoded).await,
+            ContentEncoding::Brotli => BrotliEncoder::new(data).read_to_end(&mut encoded).await,
+            ContentEncoding::Zstd => ZstdEncoder::new(data).read_to_end(&mut encoded).await,
+        }
+        .unwrap();
+        encoded
+    }
+
+    async fn serve(encoding: ContentEncoding, body: Vec<u8>) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(path("/page"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-encoding", encoding.as_str())
+                    .set_body_raw(body, "text/html"),
+            )
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[test]
+    fn test_parse_content_encoding() {
+        assert_eq!(
+            ContentEncoding::parse(" GZIP "),
+            Some(ContentEncoding::Gzip)
+        );
+        assert_eq!(
+            ContentEncoding::parse("x-gzip"),
+            Some(ContentEncoding::Gzip)
+        );
+        assert_eq!(ContentEncoding::parse("br"), Some(ContentEncoding::Brotli));
+        assert_eq!(ContentEncoding::parse("zstd"), Some(ContentEncoding::Zstd));
+        assert_eq!(ContentEncoding::parse("gzip, br"), None);
+        assert_eq!(ContentEncoding::parse("compress"), None);
+    }
+
+    #[tokio::test]
+    async fn test_decodes_every_supported_encoding_and_records_ratio() {
+        let html = "<html><body>".to_string() + &"<p>compressible</p>".repeat(2000);
+        let client = configure(reqwest::Client::builder()).build().unwrap();
+        let metrics = Arc::new(CompressionMetrics::default());
+
+        for encoding in [
+            ContentEncoding::Gzip,
+            ContentEncoding::Deflate,
+            ContentEncoding::Brotli,
+            ContentEncoding::Zstd,
+        ] {
+            let encoded = encode(encoding, html.as_bytes()).await;
+            let server = serve(encoding, encoded.clone()).await;
+            let response = client
+                .get(format!("{}/page", server.uri()))
+                .send()
+                .await
+                .unwrap();
+            let response = decode_response(response, DEFAULT_MAX_DECODED_BYTES, &metrics).unwrap();
+
+            assert!(response.headers().get(CONTENT_ENCODING).is_none());
+            let wire = WireBytes::of(&response).unwrap();
+            assert_eq!(response.text().await.unwrap(), html, "{:?}", encoding);
+            assert_eq!(wire.get(), encoded.len() as u64);
+
+            let stats = metrics.snapshot().encodings[encoding.as_str()].clone();
+            assert_eq!(stats.responses, 1);
+            assert_eq!(stats.compressed_bytes, encoded.len() as u64);
+            assert_eq!(stats.decoded_bytes, html.len() as u64);
+            assert!(stats.compression_ratio > 10.0, "{:?}", stats);
+        }
+        assert!(metrics.snapshot().compression_ratio > 10.0);
+    }
+
+    #[tokio::test]
+    async fn test_aborts_decompression_bomb() {
+        let bomb = encode(ContentEncoding::Gzip, &vec![0u8; 8 * 1024 * 1024]).await;
+        let server = serve(ContentEncoding::Gzip, bomb).await;
+        let client = configure(reqwest::Client::builder()).build().unwrap();
+        let metrics = Arc::new(CompressionMetrics::default());
+
+        let response = client
+            .get(format!("{}/page", server.uri()))
+            .send()
+            .await
+            .unwrap();
+        let response = decode_response(response, 1024 * 1024, &metrics).unwrap();
+
+        assert!(response.bytes().await.is_err());
+        let stats = metrics.snapshot();
+        assert_eq!(stats.oversized_bodies, 1);
+        assert!(stats.encodings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_leaves_empty_encoded_bodies_undecoded() {
+        let server = MockServer::start().await;
+        let gzipped = encode(ContentEncoding::Gzip, b"<html></html>").await;
+        for status in [200, 204, 304] {
+            Mock::given(path(format!("/{}", status)))
+                .respond_with(
+                    ResponseTemplate::new(status)
+                        .insert_header("content-encoding", "gzip")
+                        .set_body_raw(gzipped.clone(), "text/html"),
+                )
+                .mount(&server)
+                .await;
+        }
+        Mock::given(path("/empty"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-encoding", "gzip")
+                    .set_body_raw(Vec::new(), "text/html"),
+            )
+            .mount(&server)
+            .await;
+        let client = configure(reqwest::Client::builder()).build().unwrap();
+        let metrics = Arc::new(CompressionMetrics::default());
+
+        let head = client.head(format!("{}/200", server.uri()));
+        let mut requests = vec![head];
+        for route in ["204", "304", "empty"] {
+            requests.push(client.get(format!("{}/{}", server.uri(), route)));
+        }
+        for request in requests {
+            let response = request.send().await.unwrap();
+            let response = decode_response(response, DEFAULT_MAX_DECODED_BYTES, &metrics).unwrap();
+            assert!(response.headers().get(CONTENT_ENCODING).is_some());
+            assert!(response.bytes().await.unwrap().is_empty());
+        }
+        assert!(metrics.snapshot().encodings.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_passes_identity_responses_through() {
+        let server = MockServer::start().await;
+        Mock::given(path("/plain"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("plain"))
+            .mount(&server)
+            .await;
+        let client = configure(reqwest::Client::builder()).build().unwrap();
+        let metrics = Arc::new(CompressionMetrics::default());
+
+        let response = client
+            .get(format!("{}/plain", server.uri()))
+            .send()
+            .await
+            .unwrap();
+        let response = decode_response(response, DEFAULT_MAX_DECODED_BYTES, &metrics).unwrap();
+
+        assert!(WireBytes::of(&response).is_none());
+        assert_eq!(response.text().await.unwrap(), "plain");
+        assert_eq!(metrics.snapshot().identity_responses, 1);
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(
+            requests[0].headers.get("accept-encoding").unwrap(),
+            ACCEPTED_ENCODINGS
+        );
+    }
+}
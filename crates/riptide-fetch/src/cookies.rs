@@ -0,0 +1,699 @@
+//! Cookie jar shared by fetches and browser sessions
+//!
+//! [`CookieJar`] stores cookies following RFC 6265: host-only and domain
+//! cookies, path matching, `Secure`, `HttpOnly`, `SameSite`, `Expires` and
+//! `Max-Age`. It implements reqwest's `CookieStore`, so clients built on it
+//! send and store cookies on every request.
+//!
+//! - **Sessions**: [`SessionCookieStore`] lets clients shared by several
+//!   tenants keep their cookies apart. Each request uses the jar of the
+//!   [`CookieJar::scope`] it runs in; [`PerHostFetchEngine::fetch_in_session`]
+//!   picks that jar from [`SessionCookieJars`] by session or tenant id
+//! - **Persistence**: [`CookieJar::save_to_session`] snapshots the jar into a
+//!   session's metadata through the [`SessionStorage`] port, and
+//!   [`CookieJar::restore_from_session`] loads it back
+//! - **Browser sessions**: [`CookieJar::snapshot`] and [`CookieJar::insert`]
+//!   exchange [`StoredCookie`]s with a headless browser, so a login made by
+//!   either side is seen by the other
+//!
+//! [`PerHostFetchEngine::fetch_in_session`]: crate::PerHostFetchEngine::fetch_in_session
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use riptide_fetch::{CookieJar, PerHostFetchEngine};
+//! use std::sync::Arc;
+//!
+//! # async fn example(engine: PerHostFetchEngine) -> anyhow::Result<()> {
+//! engine
+//!     .fetch_in_session("tenant-a", "https://example.com/login")
+//!     .await?;
+//! // Cookies set by the response are sent with the next request of the
+//! // same session only
+//! let jar = engine.session_cookie_jar("tenant-a");
+//! let header = jar.cookie_header(&"https://example.com/account".parse()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use reqwest::header::HeaderValue;
+use riptide_types::ports::SessionStorage;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::time::Instant;
+use url::Url;
+
+/// Session metadata key holding the serialized jar
+pub const SESSION_COOKIES_KEY: &str = "riptide.cookies";
+
+/// Sessions [`SessionCookieJars`] keeps by default
+pub const DEFAULT_MAX_SESSION_JARS: usize = 10_000;
+
+/// Second-level labels under which country TLDs register domains, as in
+/// `example.co.uk`
+const COUNTRY_SECOND_LEVEL_LABELS: &[&str] = &["ac", "co", "com", "edu", "gov", "net", "org"];
+
+tokio::task_local! {
+    static CURRENT: Arc<CookieJar>;
+}
+
+/// `SameSite` cookie attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+impl FromStr for SameSite {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "strict" => Ok(Self::Strict),
+            "lax" => Ok(Self::Lax),
+            "none" => Ok(Self::None),
+            other => anyhow::bail!("Unknown SameSite value: {}", other),
+        }
+    }
+}
+
+/// A cookie held by a [`CookieJar`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredCookie {
+    pub name: String,
+    pub value: String,
+    /// Lowercase domain without a leading dot
+    pub domain: String,
+    pub path: String,
+    /// Only sent to `domain` itself, not its subdomains
+    pub host_only: bool,
+    pub secure: bool,
+    pub http_only: bool,
+    /// Unset is treated as `Lax`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub same_site: Option<SameSite>,
+    /// `None` for session cookies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<DateTime<Utc>>,
+}
+
+impl StoredCookie {
+    /// Parse a `Set-Cookie` header received from `url`
+    ///
+    /// Returns `None` for malformed cookies and cookies `url` may not set:
+    /// a `Domain` the host does not belong to or that is a public suffix
+    /// other than the host itself, `Secure` over plain HTTP, or
+    /// `SameSite=None` without `Secure`.
+    pub fn parse(set_cookie: &str, url: &Url) -> Option<Self> {
+        let host = url.host_str()?.to_ascii_lowercase();
+        let mut parts = set_cookie.split(';');
+        let (name, value) = parts.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = Self {
+            name: name.to_string(),
+            value: value.trim().trim_matches('"').to_string(),
+            domain: host.clone(),
+            path: default_path(url),
+            host_only: true,
+            secure: false,
+            http_only: false,
+            same_site: None,
+            expires: None,
+        };
+        let mut max_age = None;
+
+        for attribute in parts {
+            let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            let value = value.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "domain" => {
+                    let domain = value.trim_start_matches('.').to_ascii_lowercase();
+                    if !domain.is_empty() {
+                        if !domain_matches(&host, &domain) {
+                            return None;
+                        }
+                        // A public suffix may only name the host itself, and
+                        // then the cookie stays host-only
+                        if is_public_suffix(&domain) {
+                            if domain != host {
+                                return None;
+                            }
+                        } else {
+                            cookie.host_only = false;
+                            cookie.domain = domain;
+                        }
+                    }
+                }
+                "path" if value.starts_with('/') => cookie.path = value.to_string(),
+                "secure" => cookie.secure = true,
+                "httponly" => cookie.http_only = true,
+                "samesite" => cookie.same_site = value.parse().ok(),
+                "expires" => {
+                    if let Ok(expires) = DateTime::parse_from_rfc2822(&value.replace('-', " ")) {
+                        cookie.expires = Some(expires.with_timezone(&Utc));
+                    }
+                }
+                "max-age" => max_age = value.parse::<i64>().ok(),
+                _ => {}
+            }
+        }
+
+        // Max-Age takes precedence over Expires
+        if let Some(seconds) = max_age {
+            cookie.expires = Some(Utc::now() + ChronoDuration::seconds(seconds.max(0)));
+        }
+        if cookie.secure && url.scheme() != "https" {
+            return None;
+        }
+        if cookie.same_site == Some(SameSite::None) && !cookie.secure {
+            return None;
+        }
+        Some(cookie)
+    }
+
+    /// Whether the cookie has expired at `now`
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    /// Whether the cookie is sent with a request to `url`
+    pub fn matches(&self, url: &Url) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        let host = host.to_ascii_lowercase();
+        let domain_ok = if self.host_only {
+            host == self.domain
+        } else {
+            domain_matches(&host, &self.domain)
+        };
+        domain_ok
+            && path_matches(url.path(), &self.path)
+            && (!self.secure || url.scheme() == "https")
+    }
+}
+
+/// Domain, path and name identifying a cookie
+type CookieKey = (String, String, String);
+
+/// Thread-safe cookie store keyed by domain, path and name
+#[derive(Debug, Default)]
+pub struct CookieJar {
+    cookies: RwLock<HashMap<CookieKey, StoredCookie>>,
+}
+
+impl CookieJar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Jar holding `cookies`
+    pub fn from_cookies(cookies: impl IntoIterator<Item = StoredCookie>) -> Self {
+        let jar = Self::new();
+        for cookie in cookies {
+            jar.insert(cookie);
+        }
+        jar
+    }
+
+    /// Store a `Set-Cookie` header received from `url`
+    ///
+    /// Returns false when the header was rejected.
+    pub fn set_cookie(&self, set_cookie: &str, url: &Url) -> bool {
+        match StoredCookie::parse(set_cookie, url) {
+            Some(cookie) => {
+                self.insert(cookie);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Add or replace a cookie; an expired cookie removes the stored one
+    ///
+    /// Domain cookies for a public suffix are ignored.
+    pub fn insert(&self, cookie: StoredCookie) {
+        if !cookie.host_only && is_public_suffix(&cookie.domain) {
+            return;
+        }
+        let key = (
+            cookie.domain.clone(),
+            cookie.path.clone(),
+            cookie.name.clone(),
+        );
+        let mut cookies = self.write();
+        if cookie.is_expired(Utc::now()) {
+            cookies.remove(&key);
+        } else {
+            cookies.insert(key, cookie);
+        }
+    }
+
+    /// Cookies sent with a request to `url`, longest path first, then by name
+    ///
+    /// With `same_site` false the request is treated as a cross-site
+    /// subrequest, and only `SameSite=None` cookies are included.
+    pub fn cookies_for(&self, url: &Url, same_site: bool) -> Vec<StoredCookie> {
+        let now = Utc::now();
+        let mut matching: Vec<StoredCookie> = self
+            .read()
+            .values()
+            .filter(|cookie| !cookie.is_expired(now) && cookie.matches(url))
+            .filter(|cookie| same_site || cookie.same_site == Some(SameSite::None))
+            .cloned()
+            .collect();
+        matching.sort_by(|a, b| {
+            b.path
+                .len()
+                .cmp(&a.path.len())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        matching
+    }
+
+    /// `Cookie` header for a same-site request to `url`
+    pub fn cookie_header(&self, url: &Url) -> Option<String> {
+        let cookies = self.cookies_for(url, true);
+        if cookies.is_empty() {
+            return None;
+        }
+        Some(
+            cookies
+                .iter()
+                .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+                .collect::<Vec<_>>()
+                .join("; "),
+        )
+    }
+
+    /// Every unexpired cookie
+    pub fn snapshot(&self) -> Vec<StoredCookie> {
+        let now = Utc::now();
+        self.read()
+            .values()
+            .filter(|cookie| !cookie.is_expired(now))
+            .cloned()
+            .collect()
+    }
+
+    /// Drop expired cookies, returning how many were removed
+    pub fn remove_expired(&self) -> usize {
+        let now = Utc::now();
+        let mut cookies = self.write();
+        let before = cookies.len();
+        cookies.retain(|_, cookie| !cookie.is_expired(now));
+        before - cookies.len()
+    }
+
+    pub fn clear(&self) {
+        self.write().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Write the jar into the metadata of session `session_id`
+    pub async fn save_to_session(
+        &self,
+        storage: &dyn SessionStorage,
+        session_id: &str,
+    ) -> Result<()> {
+        let mut session = storage
+            .get_session(session_id)
+            .await?
+            .with_context(|| format!("Session {} not found", session_id))?;
+        session.metadata.insert(
+            SESSION_COOKIES_KEY.to_string(),
+            serde_json::to_string(&self.snapshot())?,
+        );
+        storage.save_session(&session).await?;
+        Ok(())
+    }
+
+    /// Load the cookies saved in session `session_id` into the jar
+    ///
+    /// Returns the number of cookies restored; 0 when the session does not
+    /// exist or holds no cookies.
+    pub async fn restore_from_session(
+        &self,
+        storage: &dyn SessionStorage,
+        session_id: &str,
+    ) -> Result<usize> {
+        let Some(session) = storage.get_session(session_id).await? else {
+            return Ok(0);
+        };
+        let Some(saved) = session.metadata.get(SESSION_COOKIES_KEY) else {
+            return Ok(0);
+        };
+        let cookies: Vec<StoredCookie> =
+            serde_json::from_str(saved).context("Invalid cookies in session metadata")?;
+        let restored = cookies.len();
+        for cookie in cookies {
+            self.insert(cookie);
+        }
+        Ok(restored)
+    }
+
+    /// Run `future` with the requests it sends through a
+    /// [`SessionCookieStore`] client using this jar
+    pub async fn scope<F: Future>(self: Arc<Self>, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+
+    /// The jar of the current scope, if any
+    pub fn current() -> Option<Arc<CookieJar>> {
+        CURRENT.try_with(Arc::clone).ok()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, HashMap<CookieKey, StoredCookie>> {
+        self.cookies
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, HashMap<CookieKey, StoredCookie>> {
+        self.cookies
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl reqwest::cookie::CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        for header in cookie_headers {
+            if let Ok(set_cookie) = header.to_str() {
+                self.set_cookie(set_cookie, url);
+            }
+        }
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        self.cookie_header(url)
+            .and_then(|header| HeaderValue::from_str(&header).ok())
+    }
+}
+
+/// Cookie store using the jar of the current [`CookieJar::scope`]
+///
+/// Requests sent outside a scope use the fallback jar, or send and keep no
+/// cookies without one, so a client shared by several tenants never mixes
+/// their cookies. The scope follows the task, so requests count only when
+/// they are sent from the task that entered it.
+#[derive(Debug, Default)]
+pub struct SessionCookieStore {
+    fallback: Option<Arc<CookieJar>>,
+}
+
+impl SessionCookieStore {
+    /// Store falling back to `fallback` outside a scope
+    pub fn new(fallback: Option<Arc<CookieJar>>) -> Self {
+        Self { fallback }
+    }
+
+    fn jar(&self) -> Option<Arc<CookieJar>> {
+        CookieJar::current().or_else(|| self.fallback.clone())
+    }
+}
+
+impl reqwest::cookie::CookieStore for SessionCookieStore {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        if let Some(jar) = self.jar() {
+            jar.set_cookies(cookie_headers, url);
+        }
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        self.jar()?.cookies(url)
+    }
+}
+
+/// Cookie jars keyed by session or tenant id
+///
+/// Holds at most `max_sessions` jars, dropping the one used least recently
+/// to make room.
+#[derive(Debug)]
+pub struct SessionCookieJars {
+    jars: Mutex<HashMap<String, (Arc<CookieJar>, Instant)>>,
+    max_sessions: usize,
+}
+
+impl Default for SessionCookieJars {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_SESSION_JARS)
+    }
+}
+
+impl SessionCookieJars {
+    pub fn new(max_sessions: usize) -> Self {
+        Self {
+            jars: Mutex::new(HashMap::new()),
+            max_sessions: max_sessions.max(1),
+        }
+    }
+
+    /// Jar of `session`, created empty on first use
+    pub fn jar(&self, session: &str) -> Arc<CookieJar> {
+        let now = Instant::now();
+        let mut jars = self.lock();
+        if let Some((jar, last_used)) = jars.get_mut(session) {
+            *last_used = now;
+            return jar.clone();
+        }
+        if jars.len() >= self.max_sessions {
+            if let Some(oldest) = jars
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(session, _)| session.clone())
+            {
+                jars.remove(&oldest);
+            }
+        }
+        let jar = Arc::new(CookieJar::new());
+        jars.insert(session.to_string(), (jar.clone(), now));
+        jar
+    }
+
+    /// Drop the jar of `session`, returning it if there was one
+    pub fn remove(&self, session: &str) -> Option<Arc<CookieJar>> {
+        self.lock().remove(session).map(|(jar, _)| jar)
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, (Arc<CookieJar>, Instant)>> {
+        self.jars
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Whether `domain` is a public suffix, under which any site can register
+///
+/// Uses a small heuristic instead of the public suffix list: single labels
+/// such as `com`, and common second-level labels of country TLDs such as
+/// `co.uk`. IP addresses are never public suffixes.
+fn is_public_suffix(domain: &str) -> bool {
+    if domain.parse::<IpAddr>().is_ok() {
+        return false;
+    }
+    let labels: Vec<&str> = domain.trim_end_matches('.').split('.').collect();
+    match labels.as_slice() {
+        [_] => true,
+        [second, tld] => tld.len() == 2 && COUNTRY_SECOND_LEVEL_LABELS.contains(second),
+        _ => false,
+    }
+}
+
+/// RFC 6265 domain matching; IP addresses only match themselves
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || (host.ends_with(domain)
+            && host[..host.len() - domain.len()].ends_with('.')
+            && host.parse::<IpAddr>().is_err())
+}
+
+/// RFC 6265 path matching
+fn path_matches(request_path: &str, cookie_path: &str) -> bool {
+    request_path == cookie_path
+        || (request_path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || request_path[cookie_path.len()..].starts_with('/')))
+}
+
+/// Directory of the request path, used when `Path` is not set
+fn default_path(url: &Url) -> String {
+    match url.path().rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(index) => url.path()[..index].to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::ports::{InMemorySessionStorage, Session};
+    use std::time::{Duration, SystemTime};
+
+    fn url(raw: &str) -> Url {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_attributes_and_rejections() {
+        let origin = url("https://shop.example.com/account/login");
+        let cookie = StoredCookie::parse(
+            "sid=abc; Domain=.Example.com; Secure; HttpOnly; SameSite=Strict; Max-Age=3600",
+            &origin,
+        )
+        .unwrap();
+        assert_eq!(cookie.domain, "example.com");
+        assert!(!cookie.host_only);
+        assert_eq!(cookie.path, "/account");
+        assert!(cookie.secure && cookie.http_only);
+        assert_eq!(cookie.same_site, Some(SameSite::Strict));
+        assert!(cookie.expires.unwrap() > Utc::now());
+
+        let expires =
+            StoredCookie::parse("a=1; Expires=Wed, 21-Oct-2015 07:28:00 GMT", &origin).unwrap();
+        assert!(expires.is_expired(Utc::now()));
+
+        assert!(StoredCookie::parse("a=1; Domain=other.com", &origin).is_none());
+        assert!(StoredCookie::parse("a=1; Domain=com", &origin).is_none());
+        let uk = url("https://shop.example.co.uk/");
+        assert!(StoredCookie::parse("a=1; Domain=co.uk", &uk).is_none());
+        assert!(
+            !StoredCookie::parse("a=1; Domain=example.co.uk", &uk)
+                .unwrap()
+                .host_only
+        );
+        let own = StoredCookie::parse("a=1; Domain=localhost", &url("http://localhost/")).unwrap();
+        assert!(own.host_only);
+        assert!(StoredCookie::parse("a=1; Secure", &url("http://example.com/")).is_none());
+        assert!(StoredCookie::parse("a=1; SameSite=None", &origin).is_none());
+        assert!(StoredCookie::parse("novalue", &origin).is_none());
+    }
+
+    #[test]
+    fn test_matching_by_domain_path_scheme_and_same_site() {
+        let jar = CookieJar::new();
+        let origin = url("https://example.com/app/page");
+        assert!(jar.set_cookie("host=1", &origin));
+        assert!(jar.set_cookie("wide=2; Domain=example.com; Path=/", &origin));
+        assert!(jar.set_cookie("tls=3; Secure; Path=/", &origin));
+        assert!(jar.set_cookie("cross=4; Secure; SameSite=None; Path=/", &origin));
+
+        let header = jar
+            .cookie_header(&url("https://example.com/app/x"))
+            .unwrap();
+        assert!(header.starts_with("host=1; "));
+        assert_eq!(header.split("; ").count(), 4);
+        let names = |target: &str, same_site: bool| {
+            let mut names: Vec<String> = jar
+                .cookies_for(&url(target), same_site)
+                .into_iter()
+                .map(|cookie| cookie.name)
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names("https://www.example.com/", true), ["wide"]);
+        assert_eq!(names("http://example.com/app/x", true), ["host", "wide"]);
+        assert_eq!(
+            names("https://example.com/application", true),
+            ["cross", "tls", "wide"]
+        );
+        assert_eq!(names("https://example.com/app/x", false), ["cross"]);
+
+        assert!(jar.set_cookie("host=gone; Max-Age=0", &origin));
+        assert_eq!(
+            names("https://example.com/app/x", true),
+            ["cross", "tls", "wide"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_session_store_uses_scoped_jar() {
+        use reqwest::cookie::CookieStore;
+
+        let origin = url("https://example.com/");
+        let header = HeaderValue::from_static("sid=abc");
+        let jars = SessionCookieJars::new(2);
+        let store = SessionCookieStore::default();
+
+        store.set_cookies(&mut std::iter::once(&header), &origin);
+        assert!(store.cookies(&origin).is_none());
+
+        jars.jar("a")
+            .scope(async { store.set_cookies(&mut std::iter::once(&header), &origin) })
+            .await;
+        assert_eq!(jars.jar("a").len(), 1);
+        assert!(jars.jar("b").is_empty());
+        let sent = jars.jar("b").scope(async { store.cookies(&origin) }).await;
+        assert!(sent.is_none());
+
+        // "a" was used least recently
+        jars.jar("b");
+        jars.jar("c");
+        assert_eq!(jars.len(), 2);
+        assert!(jars.jar("a").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_session_snapshot_round_trip() {
+        let storage = InMemorySessionStorage::new();
+        storage
+            .save_session(&Session {
+                id: "s1".to_string(),
+                user_id: "user".to_string(),
+                tenant_id: "tenant".to_string(),
+                created_at: SystemTime::now(),
+                expires_at: SystemTime::now() + Duration::from_secs(3600),
+                metadata: HashMap::new(),
+            })
+            .await
+            .unwrap();
+
+        let jar = CookieJar::new();
+        let origin = url("https://example.com/");
+        jar.set_cookie("sid=abc; HttpOnly", &origin);
+        jar.set_cookie("pref=dark; Max-Age=600", &origin);
+        jar.save_to_session(&storage, "s1").await.unwrap();
+        assert!(jar.save_to_session(&storage, "missing").await.is_err());
+
+        let restored = CookieJar::new();
+        assert_eq!(
+            restored.restore_from_session(&storage, "s1").await.unwrap(),
+            2
+        );
+        assert_eq!(restored.cookie_header(&origin), jar.cookie_header(&origin));
+        assert_eq!(
+            restored
+                .restore_from_session(&storage, "missing")
+                .await
+                .unwrap(),
+            0
+        );
+    }
+}
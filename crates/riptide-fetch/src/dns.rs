@@ -0,0 +1,448 @@
+//! In-process DNS cache with TTL respect and per-host overrides
+//!
+//! [`CachingResolver`] plugs into reqwest as its DNS resolver so large crawls
+//! resolve each host once per TTL instead of once per connection:
+//!
+//! - **TTL respect**: answers are cached for their record TTL, clamped to the
+//!   configured bounds; failed lookups are cached briefly as well
+//! - **Dual stack**: IPv6 and IPv4 addresses are returned interleaved, so the
+//!   connector races both families (happy eyeballs, RFC 8305)
+//! - **Host overrides**: configured hosts resolve to fixed addresses without
+//!   touching DNS, e.g. to point a production host name at staging
+//!
+//! ```rust,ignore
+//! use riptide_config::DnsConfig;
+//! use riptide_fetch::dns::CachingResolver;
+//!
+//! let config = DnsConfig::default()
+//!     .with_host_override("example.com", vec!["10.0.0.5".parse()?]);
+//! let resolver = Arc::new(CachingResolver::new(config)?);
+//! let client = reqwest::Client::builder().dns_resolver(resolver).build()?;
+//! ```
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use hickory_resolver::config::LookupIpStrategy;
+use hickory_resolver::TokioAsyncResolver;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use riptide_config::DnsConfig;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::debug;
+
+/// Source of DNS answers behind the cache
+#[async_trait]
+pub trait DnsBackend: Send + Sync {
+    /// Resolve `host`, returning its addresses and how long they may be cached
+    async fn lookup(&self, host: &str) -> io::Result<(Vec<IpAddr>, Duration)>;
+}
+
+/// [`DnsBackend`] querying the system's nameservers with hickory
+pub struct HickoryBackend {
+    resolver: TokioAsyncResolver,
+}
+
+impl HickoryBackend {
+    /// Use the nameservers and options from the system configuration
+    /// (`/etc/resolv.conf` on Unix)
+    pub fn from_system_conf() -> io::Result<Self> {
+        let (config, mut options) = hickory_resolver::system_conf::read_system_conf()
+            .map_err(|e| io::Error::other(format!("Failed to read DNS configuration: {}", e)))?;
+        // Caching happens in CachingResolver; both families feed happy eyeballs
+        options.cache_size = 0;
+        options.ip_strategy = LookupIpStrategy::Ipv4AndIpv6;
+        Ok(Self {
+            resolver: TokioAsyncResolver::tokio(config, options),
+        })
+    }
+}
+
+#[async_trait]
+impl DnsBackend for HickoryBackend {
+    async fn lookup(&self, host: &str) -> io::Result<(Vec<IpAddr>, Duration)> {
+        let lookup = self
+            .resolver
+            .lookup_ip(host)
+            .await
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        let ttl = lookup
+            .valid_until()
+            .saturating_duration_since(std::time::Instant::now());
+        Ok((lookup.iter().collect(), ttl))
+    }
+}
+
+/// DNS cache counters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DnsCacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    /// Lookups answered from a cached failure
+    pub negative_hits: u64,
+    /// Lookups answered by a host override
+    pub overrides: u64,
+    pub failures: u64,
+}
+
+struct CacheEntry {
+    answer: Result<Arc<[IpAddr]>, String>,
+    expires_at: Instant,
+}
+
+#[derive(Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    negative_hits: AtomicU64,
+    overrides: AtomicU64,
+    failures: AtomicU64,
+}
+
+struct Inner {
+    config: DnsConfig,
+    backend: Arc<dyn DnsBackend>,
+    cache: DashMap<String, CacheEntry>,
+    counters: Counters,
+}
+
+/// Caching DNS resolver usable as reqwest's `dns_resolver`
+#[derive(Clone)]
+pub struct CachingResolver {
+    inner: Arc<Inner>,
+}
+
+impl std::fmt::Debug for CachingResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachingResolver")
+            .field("config", &self.inner.config)
+            .field("entries", &self.inner.cache.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl CachingResolver {
+    /// Resolver querying the system's nameservers
+    pub fn new(config: DnsConfig) -> io::Result<Self> {
+        Ok(Self::with_backend(
+            config,
+            Arc::new(HickoryBackend::from_system_conf()?),
+        ))
+    }
+
+    /// Resolver querying `backend`
+    pub fn with_backend(config: DnsConfig, backend: Arc<dyn DnsBackend>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                config,
+                backend,
+                cache: DashMap::new(),
+                counters: Counters::default(),
+            }),
+        }
+    }
+
+    /// Addresses of `host`, in connection order
+    pub async fn resolve_host(&self, host: &str) -> io::Result<Vec<IpAddr>> {
+        let inner = &self.inner;
+        let counters = &inner.counters;
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+
+        if let Some(addresses) = inner.config.host_override(&host) {
+            counters.overrides.fetch_add(1, Ordering::Relaxed);
+            return Ok(self.order(addresses));
+        }
+
+        if inner.config.cache_enabled {
+            if let Some(answer) = self.cached(&host) {
+                return answer.map(|addresses| self.order(&addresses));
+            }
+        }
+
+        counters.misses.fetch_add(1, Ordering::Relaxed);
+        let result = inner
+            .backend
+            .lookup(&host)
+            .await
+            .and_then(|(addresses, ttl)| {
+                if addresses.is_empty() {
+                    Err(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("No addresses for {}", host),
+                    ))
+                } else {
+                    Ok((addresses, ttl))
+                }
+            });
+
+        match result {
+            Ok((addresses, ttl)) => {
+                let config = &inner.config;
+                let ttl = ttl.clamp(
+                    Duration::from_secs(config.min_ttl_secs),
+                    Duration::from_secs(config.max_ttl_secs),
+                );
+                debug!(
+                    host = %host,
+                    addresses = addresses.len(),
+                    ttl_secs = ttl.as_secs(),
+                    "Resolved host"
+                );
+                self.store(&host, Ok(addresses.clone().into()), ttl);
+                Ok(self.order(&addresses))
+            }
+            Err(e) => {
+                counters.failures.fetch_add(1, Ordering::Relaxed);
+                let ttl = Duration::from_secs(inner.config.negative_ttl_secs);
+                self.store(&host, Err(e.to_string()), ttl);
+                Err(e)
+            }
+        }
+    }
+
+    /// Forget the cached answer for `host`
+    pub fn invalidate(&self, host: &str) {
+        self.inner
+            .cache
+            .remove(&host.trim_end_matches('.').to_ascii_lowercase());
+    }
+
+    /// Forget every cached answer
+    pub fn clear(&self) {
+        self.inner.cache.clear();
+    }
+
+    pub fn stats(&self) -> DnsCacheStats {
+        let counters = &self.inner.counters;
+        DnsCacheStats {
+            entries: self.inner.cache.len(),
+            hits: counters.hits.load(Ordering::Relaxed),
+            misses: counters.misses.load(Ordering::Relaxed),
+            negative_hits: counters.negative_hits.load(Ordering::Relaxed),
+            overrides: counters.overrides.load(Ordering::Relaxed),
+            failures: counters.failures.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Unexpired cached answer for `host`
+    fn cached(&self, host: &str) -> Option<io::Result<Arc<[IpAddr]>>> {
+        let counters = &self.inner.counters;
+        let entry = self.inner.cache.get(host)?;
+        if entry.expires_at <= Instant::now() {
+            drop(entry);
+            self.inner.cache.remove(host);
+            return None;
+        }
+        Some(match &entry.answer {
+            Ok(addresses) => {
+                counters.hits.fetch_add(1, Ordering::Relaxed);
+                Ok(addresses.clone())
+            }
+            Err(message) => {
+                counters.negative_hits.fetch_add(1, Ordering::Relaxed);
+                Err(io::Error::other(message.clone()))
+            }
+        })
+    }
+
+    fn store(&self, host: &str, answer: Result<Arc<[IpAddr]>, String>, ttl: Duration) {
+        let config = &self.inner.config;
+        if !config.cache_enabled || ttl.is_zero() {
+            return;
+        }
+        let cache = &self.inner.cache;
+        let now = Instant::now();
+        if cache.len() >= config.max_entries && !cache.contains_key(host) {
+            cache.retain(|_, entry| entry.expires_at > now);
+            if cache.len() >= config.max_entries {
+                let soonest = cache
+                    .iter()
+                    .min_by_key(|entry| entry.expires_at)
+                    .map(|entry| entry.key().clone());
+                if let Some(soonest) = soonest {
+                    cache.remove(&soonest);
+                }
+            }
+        }
+        cache.insert(
+            host.to_string(),
+            CacheEntry {
+                answer,
+                expires_at: now + ttl,
+            },
+        );
+    }
+
+    fn order(&self, addresses: &[IpAddr]) -> Vec<IpAddr> {
+        order_addresses(addresses, self.inner.config.dual_stack)
+    }
+}
+
+/// Order addresses for connecting
+///
+/// Dual stack interleaves the families starting with IPv6, so the connector
+/// falls back to IPv4 quickly when IPv6 is broken. Otherwise IPv4 addresses
+/// are used alone when there are any.
+pub fn order_addresses(addresses: &[IpAddr], dual_stack: bool) -> Vec<IpAddr> {
+    let (v6, v4): (Vec<IpAddr>, Vec<IpAddr>) = addresses.iter().partition(|ip| ip.is_ipv6());
+    if !dual_stack {
+        return if v4.is_empty() { v6 } else { v4 };
+    }
+
+    let mut ordered = Vec::with_capacity(addresses.len());
+    let (mut v6, mut v4) = (v6.into_iter(), v4.into_iter());
+    loop {
+        match (v6.next(), v4.next()) {
+            (None, None) => break,
+            (a, b) => ordered.extend(a.into_iter().chain(b)),
+        }
+    }
+    ordered
+}
+
+impl Resolve for CachingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolver = self.clone();
+        Box::pin(async move {
+            let addresses = resolver.resolve_host(name.as_str()).await?;
+            // The connector fills in the port
+            let addrs: Addrs = Box::new(
+                addresses
+                    .into_iter()
+                    .map(|ip| SocketAddr::new(ip, 0))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            );
+            Ok(addrs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    struct StaticBackend {
+        answers: Vec<IpAddr>,
+        ttl: Duration,
+        lookups: AtomicUsize,
+    }
+
+    impl StaticBackend {
+        fn new(answers: &[&str], ttl: Duration) -> Arc<Self> {
+            Arc::new(Self {
+                answers: answers.iter().map(|a| a.parse().unwrap()).collect(),
+                ttl,
+                lookups: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl DnsBackend for StaticBackend {
+        async fn lookup(&self, host: &str) -> io::Result<(Vec<IpAddr>, Duration)> {
+            self.lookups.fetch_add(1, Ordering::SeqCst);
+            if host.ends_with(".invalid") {
+                return Err(io::Error::new(io::ErrorKind::NotFound, "NXDOMAIN"));
+            }
+            Ok((self.answers.clone(), self.ttl))
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_answers_cached_for_clamped_ttl() {
+        let backend = StaticBackend::new(&["192.0.2.1"], Duration::from_secs(3600));
+        let resolver = CachingResolver::with_backend(
+            DnsConfig {
+                max_ttl_secs: 60,
+                ..Default::default()
+            },
+            backend.clone(),
+        );
+
+        resolver.resolve_host("example.com").await.unwrap();
+        resolver.resolve_host("EXAMPLE.com.").await.unwrap();
+        assert_eq!(backend.lookups.load(Ordering::SeqCst), 1);
+
+        // A one-hour TTL is capped at the configured maximum
+        tokio::time::advance(Duration::from_secs(61)).await;
+        resolver.resolve_host("example.com").await.unwrap();
+        assert_eq!(backend.lookups.load(Ordering::SeqCst), 2);
+
+        let stats = resolver.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 2, 1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_failures_cached_briefly() {
+        let backend = StaticBackend::new(&["192.0.2.1"], Duration::from_secs(60));
+        let resolver = CachingResolver::with_backend(DnsConfig::default(), backend.clone());
+
+        assert!(resolver.resolve_host("missing.invalid").await.is_err());
+        assert!(resolver.resolve_host("missing.invalid").await.is_err());
+        assert_eq!(backend.lookups.load(Ordering::SeqCst), 1);
+        assert_eq!(resolver.stats().negative_hits, 1);
+
+        tokio::time::advance(Duration::from_secs(11)).await;
+        assert!(resolver.resolve_host("missing.invalid").await.is_err());
+        assert_eq!(backend.lookups.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_host_override_bypasses_dns() {
+        let backend = StaticBackend::new(&["192.0.2.1"], Duration::from_secs(60));
+        let staging: IpAddr = "10.0.0.5".parse().unwrap();
+        let resolver = CachingResolver::with_backend(
+            DnsConfig::default().with_host_override("example.com", vec![staging]),
+            backend.clone(),
+        );
+
+        assert_eq!(
+            resolver.resolve_host("example.com").await.unwrap(),
+            vec![staging]
+        );
+        assert_eq!(backend.lookups.load(Ordering::SeqCst), 0);
+        assert_eq!(resolver.stats().overrides, 1);
+    }
+
+    #[test]
+    fn test_dual_stack_ordering() {
+        let addresses: Vec<IpAddr> = ["192.0.2.1", "192.0.2.2", "2001:db8::1"]
+            .iter()
+            .map(|a| a.parse().unwrap())
+            .collect();
+
+        let ordered = order_addresses(&addresses, true);
+        assert_eq!(
+            ordered,
+            vec![addresses[2], addresses[0], addresses[1]],
+            "IPv6 first, then alternating families"
+        );
+        assert_eq!(
+            order_addresses(&addresses, false),
+            vec![addresses[0], addresses[1]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_capacity_evicts_soonest_expiring() {
+        let backend = StaticBackend::new(&["192.0.2.1"], Duration::from_secs(60));
+        let resolver = CachingResolver::with_backend(
+            DnsConfig {
+                max_entries: 2,
+                ..Default::default()
+            },
+            backend,
+        );
+        for host in ["a.test", "b.test", "c.test"] {
+            resolver.resolve_host(host).await.unwrap();
+        }
+        assert_eq!(resolver.stats().entries, 2);
+    }
+}
@@ -1,25 +1,76 @@
+use crate::bandwidth::{self, BandwidthLimiter};
+use crate::body_stream::{BodyLimits, BodyStream};
+use crate::content_encoding::{
+    self, CompressionMetrics, CompressionStats, DEFAULT_MAX_DECODED_BYTES,
+};
+use crate::cookies::{CookieJar, SessionCookieJars, SessionCookieStore};
+use crate::dns::{CachingResolver, DnsCacheStats};
+use crate::geoip::locate_host;
+use crate::middleware::{FetchMiddleware, MiddlewareChain};
+use crate::proxy_pool::{ProxyPool, ProxyPoolStats};
+use crate::resumable::{self, ResumableDownload, ResumeConfig};
 use crate::robots::{RobotsConfig, RobotsManager};
+use crate::robots_service::{RobotsService, RobotsServiceStats};
+use crate::tls::DomainTlsConfig;
 use crate::{telemetry_info, telemetry_span};
 use riptide_utils::circuit_breaker::{self as circuit, CircuitBreaker, Config as CircuitConfig};
+use riptide_utils::retry::{server_retry_delay, Throttled};
 // Removed unused error imports
 use anyhow::Result;
-use reqwest::{Client, Response};
+use chrono::Utc;
+use reqwest::header::HeaderMap;
+use reqwest::{Client, ClientBuilder, Response};
+use riptide_types::ContentFingerprint;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::{debug, error, info, instrument};
 
 // Re-export types from riptide-types to maintain backward compatibility
+use riptide_types::ports::{GeoIpLookup, GeoLocation};
 pub use riptide_types::{CircuitBreakerConfig, RetryConfig};
 pub use riptide_utils::circuit_breaker::State as CircuitState;
 
+/// Builder of a client's base settings, reused for its TLS profile clients
+#[derive(Clone)]
+struct ClientFactory(Arc<dyn Fn() -> ClientBuilder + Send + Sync>);
+
+impl ClientFactory {
+    fn new(factory: impl Fn() -> ClientBuilder + Send + Sync + 'static) -> Self {
+        Self(Arc::new(factory))
+    }
+
+    fn builder(&self) -> ClientBuilder {
+        (self.0)()
+    }
+
+    fn build(&self) -> Result<Client> {
+        self.builder()
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to create HTTP client: {}", e))
+    }
+}
+
+impl std::fmt::Debug for ClientFactory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ClientFactory")
+    }
+}
+
 /// Enhanced HTTP client with reliability patterns and robots.txt compliance
 #[derive(Debug)]
 pub struct ReliableHttpClient {
     client: Client,
+    factory: ClientFactory,
+    /// Client of each TLS profile, first match wins
+    tls_clients: Vec<(DomainTlsConfig, Client)>,
     retry_config: RetryConfig,
     circuit_breaker: Arc<CircuitBreaker>,
     robots_manager: Option<Arc<RobotsManager>>,
+    compression: Arc<CompressionMetrics>,
+    max_decoded_bytes: u64,
+    middleware: MiddlewareChain,
+    proxy_pool: Option<Arc<ProxyPool>>,
 }
 
 impl ReliableHttpClient {
@@ -27,14 +78,13 @@ impl ReliableHttpClient {
         retry_config: RetryConfig,
         circuit_breaker_config: CircuitBreakerConfig,
     ) -> Result<Self> {
-        let client = Client::builder()
-            .user_agent("RipTide/1.0")
-            .gzip(true)
-            .brotli(true)
-            .connect_timeout(Duration::from_secs(3))
-            .timeout(Duration::from_secs(20)) // Increased to 20s for total timeout
-            .build()
-            .map_err(|e| anyhow::anyhow!("Failed to create HTTP client: {}", e))?;
+        let factory = ClientFactory::new(|| {
+            content_encoding::configure(Client::builder())
+                .user_agent("RipTide/1.0")
+                .connect_timeout(Duration::from_secs(3))
+                .timeout(Duration::from_secs(20)) // Increased to 20s for total timeout
+        });
+        let client = factory.build()?;
 
         let cb_config = CircuitConfig {
             failure_threshold: circuit_breaker_config.failure_threshold,
@@ -44,9 +94,15 @@ impl ReliableHttpClient {
 
         Ok(Self {
             client,
+            factory,
+            tls_clients: Vec::new(),
             retry_config,
             circuit_breaker: CircuitBreaker::new(cb_config, Arc::new(circuit::RealClock)),
             robots_manager: None,
+            compression: Arc::default(),
+            max_decoded_bytes: DEFAULT_MAX_DECODED_BYTES,
+            middleware: MiddlewareChain::default(),
+            proxy_pool: None,
         })
     }
 
@@ -56,14 +112,85 @@ impl ReliableHttpClient {
         circuit_breaker_config: CircuitBreakerConfig,
         robots_config: RobotsConfig,
     ) -> Result<Self> {
-        let client = Client::builder()
-            .user_agent(&robots_config.user_agent)
-            .gzip(true)
-            .brotli(true)
-            .connect_timeout(Duration::from_secs(3))
-            .timeout(Duration::from_secs(20))
-            .build()
-            .map_err(|e| anyhow::anyhow!("Failed to create HTTP client: {}", e))?;
+        let robots_manager = RobotsManager::new(robots_config)?;
+        Self::new_with_robots_manager(retry_config, circuit_breaker_config, robots_manager)
+    }
+
+    /// Create a new client with robots.txt compliance, reading robots.txt
+    /// through a `service` shared with other components
+    pub fn new_with_robots_service(
+        retry_config: RetryConfig,
+        circuit_breaker_config: CircuitBreakerConfig,
+        robots_config: RobotsConfig,
+        service: Arc<RobotsService>,
+    ) -> Result<Self> {
+        let robots_manager = RobotsManager::with_service(robots_config, service);
+        Self::new_with_robots_manager(retry_config, circuit_breaker_config, robots_manager)
+    }
+
+    fn new_with_robots_manager(
+        retry_config: RetryConfig,
+        circuit_breaker_config: CircuitBreakerConfig,
+        robots_manager: RobotsManager,
+    ) -> Result<Self> {
+        let user_agent = robots_manager.get_config().user_agent.clone();
+        let factory = ClientFactory::new(move || {
+            content_encoding::configure(Client::builder())
+                .user_agent(&user_agent)
+                .connect_timeout(Duration::from_secs(3))
+                .timeout(Duration::from_secs(20))
+        });
+        let client = factory.build()?;
+
+        let cb_config = CircuitConfig {
+            failure_threshold: circuit_breaker_config.failure_threshold,
+            open_cooldown_ms: circuit_breaker_config.open_cooldown_ms,
+            half_open_max_in_flight: circuit_breaker_config.half_open_max_in_flight,
+        };
+
+        Ok(Self {
+            client,
+            factory,
+            tls_clients: Vec::new(),
+            retry_config,
+            circuit_breaker: CircuitBreaker::new(cb_config, Arc::new(circuit::RealClock)),
+            robots_manager: Some(Arc::new(robots_manager)),
+            compression: Arc::default(),
+            max_decoded_bytes: DEFAULT_MAX_DECODED_BYTES,
+            middleware: MiddlewareChain::default(),
+            proxy_pool: None,
+        })
+    }
+
+    /// Create a new client that sends and stores cookies in `cookie_jar`
+    pub fn new_with_cookie_jar(
+        retry_config: RetryConfig,
+        circuit_breaker_config: CircuitBreakerConfig,
+        cookie_jar: Arc<CookieJar>,
+    ) -> Result<Self> {
+        Self::new_with_transport(retry_config, circuit_breaker_config, cookie_jar, None)
+    }
+
+    /// Create a new client with a cookie store and, optionally, a caching DNS
+    /// resolver shared with other clients
+    pub fn new_with_transport(
+        retry_config: RetryConfig,
+        circuit_breaker_config: CircuitBreakerConfig,
+        cookie_store: Arc<impl reqwest::cookie::CookieStore + 'static>,
+        dns_resolver: Option<Arc<CachingResolver>>,
+    ) -> Result<Self> {
+        let factory = ClientFactory::new(move || {
+            let builder = content_encoding::configure(Client::builder())
+                .user_agent("RipTide/1.0")
+                .cookie_provider(cookie_store.clone())
+                .connect_timeout(Duration::from_secs(3))
+                .timeout(Duration::from_secs(20));
+            match &dns_resolver {
+                Some(resolver) => builder.dns_resolver(resolver.clone()),
+                None => builder,
+            }
+        });
+        let client = factory.build()?;
 
         let cb_config = CircuitConfig {
             failure_threshold: circuit_breaker_config.failure_threshold,
@@ -73,9 +200,15 @@ impl ReliableHttpClient {
 
         Ok(Self {
             client,
+            factory,
+            tls_clients: Vec::new(),
             retry_config,
             circuit_breaker: CircuitBreaker::new(cb_config, Arc::new(circuit::RealClock)),
-            robots_manager: Some(Arc::new(RobotsManager::new(robots_config)?)),
+            robots_manager: None,
+            compression: Arc::default(),
+            max_decoded_bytes: DEFAULT_MAX_DECODED_BYTES,
+            middleware: MiddlewareChain::default(),
+            proxy_pool: None,
         })
     }
 
@@ -93,6 +226,81 @@ impl ReliableHttpClient {
         self
     }
 
+    /// Record compression counters in `metrics`, e.g. one shared by several clients
+    pub fn with_compression_metrics(mut self, metrics: Arc<CompressionMetrics>) -> Self {
+        self.compression = metrics;
+        self
+    }
+
+    /// Fail bodies that decode to more than `max_decoded_bytes`
+    pub fn with_max_decoded_bytes(mut self, max_decoded_bytes: u64) -> Self {
+        self.max_decoded_bytes = max_decoded_bytes;
+        self
+    }
+
+    /// Run `middleware` around every request, after those already registered
+    pub fn with_middleware(mut self, middleware: Arc<dyn FetchMiddleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Run `chain` around every request instead of the registered middlewares
+    pub fn with_middleware_chain(mut self, chain: MiddlewareChain) -> Self {
+        self.middleware = chain;
+        self
+    }
+
+    /// Present per-domain client certificates and trust per-domain CA
+    /// bundles
+    ///
+    /// Each profile gets a client with this client's settings, used for the
+    /// hosts it lists. Requests sent through a proxy pool use the pool's
+    /// clients instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a profile's files cannot be read or its client built
+    pub fn with_tls_profiles(mut self, profiles: &[DomainTlsConfig]) -> Result<Self> {
+        self.tls_clients = profiles
+            .iter()
+            .map(|tls| {
+                let client = tls.apply(self.factory.builder())?.build().map_err(|e| {
+                    anyhow::anyhow!(
+                        "Failed to create HTTP client for {}: {}",
+                        tls.domains.join(", "),
+                        e
+                    )
+                })?;
+                Ok((tls.clone(), client))
+            })
+            .collect::<Result<_>>()?;
+        Ok(self)
+    }
+
+    /// Client for `url`: the one of the first TLS profile matching its host,
+    /// else the default client
+    fn client_for(&self, url: &url::Url) -> &Client {
+        let host = url.host_str().unwrap_or_default();
+        self.tls_clients
+            .iter()
+            .find(|(tls, _)| tls.matches(host))
+            .map_or(&self.client, |(_, client)| client)
+    }
+
+    /// Send every attempt through a proxy from `pool`
+    ///
+    /// Retries and the circuit breaker still apply; each attempt picks its
+    /// proxy anew, so a retry may leave through another one.
+    pub fn with_proxy_pool(mut self, pool: Arc<ProxyPool>) -> Self {
+        self.proxy_pool = Some(pool);
+        self
+    }
+
+    /// Compression counters of this client
+    pub fn compression_stats(&self) -> CompressionStats {
+        self.compression.snapshot()
+    }
+
     /// Perform HTTP POST with retry logic, circuit breaker protection, and optional robots.txt compliance
     #[instrument(skip(self, body), fields(url = %url))]
     pub async fn post_with_retry<T: serde::Serialize>(
@@ -120,24 +328,25 @@ impl ReliableHttpClient {
         let mut last_error = None;
 
         for attempt in 0..self.retry_config.max_attempts {
-            // Use circuit breaker for the request
-            match circuit::guarded_call(&self.circuit_breaker, || async {
-                self.client
-                    .post(url)
-                    .json(body)
-                    .send()
-                    .await
-                    .map_err(|e| anyhow::anyhow!(e))
-            })
-            .await
-            {
+            let mut server_delay = None;
+            let request = self.client.post(url).json(body).build()?;
+            // Use circuit breaker for requests that go on the wire
+            match self.send(request, attempt).await {
                 Ok(response) => {
+                    let status = response.status();
+                    if is_throttling(status) {
+                        server_delay = server_retry_delay(response.headers());
+                    }
                     match response.error_for_status() {
                         Ok(success_response) => {
                             if attempt > 0 {
                                 debug!(url = %url, attempt = attempt + 1, "Request succeeded after retry");
                             }
-                            return Ok(success_response);
+                            return content_encoding::decode_response(
+                                success_response,
+                                self.max_decoded_bytes,
+                                &self.compression,
+                            );
                         }
                         Err(status_error) => {
                             // Don't retry 4xx client errors (except 408, 429)
@@ -149,7 +358,7 @@ impl ReliableHttpClient {
                                     return Err(status_error.into());
                                 }
                             }
-                            last_error = Some(status_error.into());
+                            last_error = Some(status_failure(status_error, status, server_delay));
                         }
                     }
                 }
@@ -166,7 +375,10 @@ impl ReliableHttpClient {
 
             // Don't sleep after the last attempt
             if attempt < self.retry_config.max_attempts - 1 {
-                let delay = self.calculate_delay(attempt);
+                let Some(delay) = self.next_delay(attempt, server_delay) else {
+                    debug!(url = %url, retry_after = ?server_delay, "Server delay exceeds max_delay, not retrying");
+                    break;
+                };
                 debug!(url = %url, attempt = attempt + 1, delay_ms = delay.as_millis(), "Retrying request");
                 tokio::time::sleep(delay).await;
             }
@@ -176,8 +388,20 @@ impl ReliableHttpClient {
     }
 
     /// Perform HTTP GET with retry logic, circuit breaker protection, and robots.txt compliance
-    #[instrument(skip(self), fields(url = %url))]
     pub async fn get_with_retry(&self, url: &str) -> Result<Response> {
+        self.get_with_headers(url, &[]).await
+    }
+
+    /// Perform HTTP GET with extra request headers, retry logic, circuit breaker protection, and robots.txt compliance
+    ///
+    /// A `304 Not Modified` answer to conditional headers is returned as a
+    /// response, not an error.
+    #[instrument(skip(self, headers), fields(url = %url))]
+    pub async fn get_with_headers(
+        &self,
+        url: &str,
+        headers: &[(&str, String)],
+    ) -> Result<Response> {
         let _span = telemetry_span!(
             "http_fetch_with_retry",
             url = %url,
@@ -198,23 +422,30 @@ impl ReliableHttpClient {
         let mut last_error = None;
 
         for attempt in 0..self.retry_config.max_attempts {
-            // Use circuit breaker for the request
-            match circuit::guarded_call(&self.circuit_breaker, || async {
-                self.client
-                    .get(url)
-                    .send()
-                    .await
-                    .map_err(|e| anyhow::anyhow!(e))
-            })
-            .await
-            {
+            let mut server_delay = None;
+            let request = headers
+                .iter()
+                .fold(self.client.get(url), |request, (name, value)| {
+                    request.header(*name, value)
+                })
+                .build()?;
+            // Use circuit breaker for requests that go on the wire
+            match self.send(request, attempt).await {
                 Ok(response) => {
+                    let status = response.status();
+                    if is_throttling(status) {
+                        server_delay = server_retry_delay(response.headers());
+                    }
                     match response.error_for_status() {
                         Ok(success_response) => {
                             if attempt > 0 {
                                 debug!(url = %url, attempt = attempt + 1, "Request succeeded after retry");
                             }
-                            return Ok(success_response);
+                            return content_encoding::decode_response(
+                                success_response,
+                                self.max_decoded_bytes,
+                                &self.compression,
+                            );
                         }
                         Err(status_error) => {
                             // Don't retry 4xx client errors (except 408, 429)
@@ -226,7 +457,7 @@ impl ReliableHttpClient {
                                     return Err(status_error.into());
                                 }
                             }
-                            last_error = Some(status_error.into());
+                            last_error = Some(status_failure(status_error, status, server_delay));
                         }
                     }
                 }
@@ -243,7 +474,10 @@ impl ReliableHttpClient {
 
             // Don't sleep after the last attempt
             if attempt < self.retry_config.max_attempts - 1 {
-                let delay = self.calculate_delay(attempt);
+                let Some(delay) = self.next_delay(attempt, server_delay) else {
+                    debug!(url = %url, retry_after = ?server_delay, "Server delay exceeds max_delay, not retrying");
+                    break;
+                };
                 debug!(url = %url, attempt = attempt + 1, delay_ms = delay.as_millis(), "Retrying request");
                 tokio::time::sleep(delay).await;
             }
@@ -252,6 +486,37 @@ impl ReliableHttpClient {
         Err(last_error.unwrap_or_else(|| anyhow::anyhow!("All retry attempts failed")))
     }
 
+    /// Send one attempt through the middleware chain
+    async fn send(&self, request: reqwest::Request, attempt: u32) -> Result<Response> {
+        self.middleware
+            .send(request, attempt, |request| {
+                circuit::guarded_call(&self.circuit_breaker, move || async move {
+                    match &self.proxy_pool {
+                        Some(pool) => pool.execute(request).await,
+                        None => self
+                            .client_for(request.url())
+                            .execute(request)
+                            .await
+                            .map_err(|e| anyhow::anyhow!(e)),
+                    }
+                })
+            })
+            .await
+    }
+
+    /// Delay before the next attempt, honouring a server-dictated wait
+    ///
+    /// `None` when the server asked for longer than `max_delay`: retrying
+    /// sooner would only be throttled again.
+    fn next_delay(&self, attempt: u32, server_delay: Option<Duration>) -> Option<Duration> {
+        let backoff = self.calculate_delay(attempt);
+        match server_delay {
+            Some(wait) if wait > self.retry_config.max_delay => None,
+            Some(wait) => Some(wait.max(backoff)),
+            None => Some(backoff),
+        }
+    }
+
     fn calculate_delay(&self, attempt: u32) -> Duration {
         let delay = self.retry_config.initial_delay.as_millis() as f64
             * self.retry_config.backoff_multiplier.powi(attempt as i32);
@@ -283,12 +548,96 @@ impl ReliableHttpClient {
     pub fn is_robots_enabled(&self) -> bool {
         self.robots_manager.is_some()
     }
+
+    /// robots.txt fetch counters, when robots.txt compliance is enabled
+    pub fn robots_stats(&self) -> Option<RobotsServiceStats> {
+        self.robots_manager
+            .as_ref()
+            .map(|manager| manager.service().stats())
+    }
+}
+
+/// Outcome of [`FetchEngine::fetch_conditional`]
+#[derive(Debug, Clone)]
+pub enum ConditionalFetch {
+    /// The server confirmed the stored validators with `304 Not Modified`
+    NotModified(ContentFingerprint),
+    /// The document was downloaded
+    Fetched {
+        /// Response body
+        body: Vec<u8>,
+        /// Fingerprint of the downloaded body
+        fingerprint: ContentFingerprint,
+        /// Whether the content hash differs from the previous fingerprint
+        /// (always `true` without one)
+        changed: bool,
+    },
+}
+
+impl ConditionalFetch {
+    /// Fingerprint after the fetch
+    pub fn fingerprint(&self) -> &ContentFingerprint {
+        match self {
+            Self::NotModified(fingerprint) | Self::Fetched { fingerprint, .. } => fingerprint,
+        }
+    }
+
+    /// Whether the content changed since the previous fingerprint
+    pub fn is_changed(&self) -> bool {
+        matches!(self, Self::Fetched { changed: true, .. })
+    }
+
+    /// `NotModified` when `response` is a `304` to a request sent with
+    /// `previous`'s [`conditional_headers`](ContentFingerprint::conditional_headers)
+    pub fn not_modified(
+        response: &Response,
+        previous: Option<&ContentFingerprint>,
+    ) -> Option<Self> {
+        let previous =
+            previous.filter(|_| response.status() == reqwest::StatusCode::NOT_MODIFIED)?;
+        let (etag, last_modified) = validators(response.headers());
+        Some(Self::NotModified(ContentFingerprint {
+            etag: etag.or_else(|| previous.etag.clone()),
+            last_modified: last_modified.or_else(|| previous.last_modified.clone()),
+            checked_at: Utc::now(),
+            ..previous.clone()
+        }))
+    }
+
+    /// `Fetched` for a downloaded `body` served with `headers`
+    pub fn downloaded(
+        headers: &HeaderMap,
+        body: Vec<u8>,
+        previous: Option<&ContentFingerprint>,
+    ) -> Self {
+        let (etag, last_modified) = validators(headers);
+        let fingerprint =
+            ContentFingerprint::observe(previous, &body, etag, last_modified, Utc::now());
+        let changed = !previous.is_some_and(|p| p.content_hash == fingerprint.content_hash);
+        Self::Fetched {
+            body,
+            fingerprint,
+            changed,
+        }
+    }
+}
+
+/// `ETag` and `Last-Modified` of a response
+fn validators(headers: &HeaderMap) -> (Option<String>, Option<String>) {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    (header("etag"), header("last-modified"))
 }
 
 /// FetchEngine provides a high-level interface for fetching content with full integration
 #[derive(Debug)]
 pub struct FetchEngine {
     client: ReliableHttpClient,
+    bandwidth: Option<Arc<BandwidthLimiter>>,
 }
 
 impl FetchEngine {
@@ -297,7 +646,10 @@ impl FetchEngine {
         let client =
             ReliableHttpClient::new(RetryConfig::default(), CircuitBreakerConfig::default())?;
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            bandwidth: None,
+        })
     }
 
     /// Create a fetch engine with custom configuration
@@ -306,7 +658,10 @@ impl FetchEngine {
         circuit_breaker_config: CircuitBreakerConfig,
     ) -> Result<Self> {
         let client = ReliableHttpClient::new(retry_config, circuit_breaker_config)?;
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            bandwidth: None,
+        })
     }
 
     /// Create a fetch engine with robots.txt compliance
@@ -320,7 +675,75 @@ impl FetchEngine {
             circuit_breaker_config,
             robots_config,
         )?;
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            bandwidth: None,
+        })
+    }
+
+    /// Create a fetch engine with robots.txt compliance, reading robots.txt
+    /// through a `service` shared with other components
+    pub fn with_robots_service(
+        retry_config: RetryConfig,
+        circuit_breaker_config: CircuitBreakerConfig,
+        robots_config: RobotsConfig,
+        service: Arc<RobotsService>,
+    ) -> Result<Self> {
+        let client = ReliableHttpClient::new_with_robots_service(
+            retry_config,
+            circuit_breaker_config,
+            robots_config,
+            service,
+        )?;
+        Ok(Self {
+            client,
+            bandwidth: None,
+        })
+    }
+
+    /// Create a fetch engine that keeps cookies in `cookie_jar`
+    pub fn with_cookie_jar(
+        retry_config: RetryConfig,
+        circuit_breaker_config: CircuitBreakerConfig,
+        cookie_jar: Arc<CookieJar>,
+    ) -> Result<Self> {
+        let client = ReliableHttpClient::new_with_cookie_jar(
+            retry_config,
+            circuit_breaker_config,
+            cookie_jar,
+        )?;
+        Ok(Self {
+            client,
+            bandwidth: None,
+        })
+    }
+
+    /// Record compression counters in `metrics`, e.g. one shared with other clients
+    pub fn with_compression_metrics(mut self, metrics: Arc<CompressionMetrics>) -> Self {
+        self.client = self.client.with_compression_metrics(metrics);
+        self
+    }
+
+    /// Run `middleware` around every request, after those already registered
+    pub fn with_middleware(mut self, middleware: Arc<dyn FetchMiddleware>) -> Self {
+        self.client = self.client.with_middleware(middleware);
+        self
+    }
+
+    /// Present per-domain client certificates and trust per-domain CA
+    /// bundles, see [`ReliableHttpClient::with_tls_profiles`]
+    pub fn with_tls_profiles(mut self, profiles: &[DomainTlsConfig]) -> Result<Self> {
+        self.client = self.client.with_tls_profiles(profiles)?;
+        Ok(self)
+    }
+
+    /// Read bodies no faster than `limiter` allows
+    ///
+    /// Applies to the bodies the engine reads itself; [`Self::fetch`] hands
+    /// out the response unread.
+    pub fn with_bandwidth_limiter(mut self, limiter: Arc<BandwidthLimiter>) -> Self {
+        self.bandwidth = Some(limiter);
+        self
     }
 
     /// Fetch content from a URL with full retry and circuit breaker protection
@@ -331,15 +754,82 @@ impl FetchEngine {
     /// Fetch content and return as text
     pub async fn fetch_text(&self, url: &str) -> Result<String> {
         let response = self.client.get_with_retry(url).await?;
-        let text = response.text().await.map_err(|e| anyhow::anyhow!(e))?;
-        Ok(text)
+        match &self.bandwidth {
+            Some(limiter) => {
+                let body = bandwidth::read_throttled(response, limiter)
+                    .await
+                    .map_err(|e| anyhow::anyhow!(e))?;
+                Ok(String::from_utf8_lossy(&body).into_owned())
+            }
+            None => response.text().await.map_err(|e| anyhow::anyhow!(e)),
+        }
     }
 
     /// Fetch content and return as bytes
     pub async fn fetch_bytes(&self, url: &str) -> Result<Vec<u8>> {
         let response = self.client.get_with_retry(url).await?;
-        let bytes = response.bytes().await.map_err(|e| anyhow::anyhow!(e))?;
-        Ok(bytes.to_vec())
+        self.read_body(response).await
+    }
+
+    /// Fetch content as a chunk stream under `limits`
+    ///
+    /// Oversized or disallowed bodies fail here or mid-stream instead of
+    /// being buffered; dropping the stream aborts the download.
+    pub async fn fetch_stream(&self, url: &str, limits: BodyLimits) -> Result<BodyStream> {
+        let response = self.client.get_with_retry(url).await?;
+        let stream = BodyStream::new(response, limits).await?;
+        Ok(match &self.bandwidth {
+            Some(limiter) => stream.with_bandwidth_limiter(limiter.clone()),
+            None => stream,
+        })
+    }
+
+    /// Fetch a whole body under `limits`, resuming dropped transfers with
+    /// range requests and verifying its checksum
+    ///
+    /// See [`crate::resumable`] for when a transfer can be resumed.
+    pub async fn fetch_resumable(
+        &self,
+        url: &str,
+        limits: BodyLimits,
+        config: &ResumeConfig,
+    ) -> Result<ResumableDownload> {
+        Ok(resumable::download(&self.client, url, limits, config).await?)
+    }
+
+    /// Revalidate a URL against its stored fingerprint
+    ///
+    /// Sends `If-None-Match` / `If-Modified-Since` from `previous`. A `304`
+    /// refreshes the fingerprint without downloading the body; otherwise the
+    /// body is fetched and compared by content hash, so servers that ignore
+    /// validators are still detected as unchanged.
+    pub async fn fetch_conditional(
+        &self,
+        url: &str,
+        previous: Option<&ContentFingerprint>,
+    ) -> Result<ConditionalFetch> {
+        let headers = previous
+            .map(|p| p.conditional_headers())
+            .unwrap_or_default();
+        let response = self.client.get_with_headers(url, &headers).await?;
+        if let Some(not_modified) = ConditionalFetch::not_modified(&response, previous) {
+            debug!(url = %url, "Conditional fetch: not modified");
+            return Ok(not_modified);
+        }
+
+        let headers = response.headers().clone();
+        let body = self.read_body(response).await?;
+        Ok(ConditionalFetch::downloaded(&headers, body, previous))
+    }
+
+    /// Read a whole body, e.g. of a [`Self::fetch`] response, throttled when
+    /// a bandwidth limiter is set
+    pub async fn read_body(&self, response: Response) -> Result<Vec<u8>> {
+        let body = match &self.bandwidth {
+            Some(limiter) => bandwidth::read_throttled(response, limiter).await,
+            None => response.bytes().await.map(|body| body.to_vec()),
+        };
+        body.map_err(|e| anyhow::anyhow!(e))
     }
 
     /// Get circuit breaker status
@@ -360,6 +850,10 @@ impl FetchEngine {
             total_requests: 0,
             total_success: 0,
             total_failures: 0,
+            proxy_pool: None,
+            dns: None,
+            compression: Some(self.client.compression_stats()),
+            robots: self.client.robots_stats(),
         }
     }
 }
@@ -425,6 +919,9 @@ pub struct HostMetrics {
     pub success_count: u64,
     pub failure_count: u64,
     pub total_duration_ms: u64,
+    /// Server country/ASN, when a GeoIP lookup is configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<GeoLocation>,
 }
 
 /// Aggregated metrics response for all hosts
@@ -434,6 +931,18 @@ pub struct FetchMetricsResponse {
     pub total_requests: u64,
     pub total_success: u64,
     pub total_failures: u64,
+    /// Proxy pool counters, when requests go through a proxy pool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy_pool: Option<ProxyPoolStats>,
+    /// DNS cache counters, when hosts are resolved through a caching resolver
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dns: Option<DnsCacheStats>,
+    /// Content-Encoding counters and compression ratios
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression: Option<CompressionStats>,
+    /// robots.txt fetch counters, when robots.txt compliance is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub robots: Option<RobotsServiceStats>,
 }
 
 /// Per-host metrics response with calculated averages
@@ -444,10 +953,12 @@ pub struct HostMetricsResponse {
     pub failure_count: u64,
     pub avg_duration_ms: f64,
     pub circuit_state: String,
+    /// Server country/ASN, when a GeoIP lookup is configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub location: Option<GeoLocation>,
 }
 
 /// Enhanced FetchEngine with per-host circuit breakers, rate limiting, and metrics
-#[derive(Debug)]
 pub struct PerHostFetchEngine {
     /// Per-host HTTP clients with individual circuit breakers
     clients: Arc<std::sync::RwLock<std::collections::HashMap<String, Arc<ReliableHttpClient>>>>,
@@ -466,6 +977,56 @@ pub struct PerHostFetchEngine {
 
     /// Default rate limit configuration for new rate limiters
     rate_limit_config: RateLimitConfig,
+
+    /// Optional GeoIP lookup used to annotate per-host metrics
+    geoip: Option<Arc<dyn GeoIpLookup>>,
+
+    /// Optional proxy pool requests are sent through
+    proxy_pool: Option<Arc<ProxyPool>>,
+
+    /// Jar for fetches made outside a session; without one they keep no
+    /// cookies
+    cookie_jar: Option<Arc<CookieJar>>,
+
+    /// Jars of [`PerHostFetchEngine::fetch_in_session`] sessions
+    session_jars: Arc<SessionCookieJars>,
+
+    /// Optional DNS cache shared by every per-host client
+    dns_resolver: Option<Arc<CachingResolver>>,
+
+    /// Compression counters shared by every per-host client
+    compression: Arc<CompressionMetrics>,
+
+    /// Middlewares every per-host client runs around its requests
+    middleware: MiddlewareChain,
+
+    /// Client certificates and CA bundles; a host's client uses the first
+    /// profile matching it
+    tls_profiles: Vec<DomainTlsConfig>,
+
+    /// Optional download caps bodies are read under
+    bandwidth: Option<Arc<BandwidthLimiter>>,
+}
+
+impl std::fmt::Debug for PerHostFetchEngine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PerHostFetchEngine")
+            .field("retry_config", &self.retry_config)
+            .field("circuit_config", &self.circuit_config)
+            .field("rate_limit_config", &self.rate_limit_config)
+            .field("geoip", &self.geoip.is_some())
+            .field(
+                "proxy_pool",
+                &self.proxy_pool.as_ref().map(|pool| pool.len()),
+            )
+            .field("cookies", &self.cookie_jar.as_ref().map(|jar| jar.len()))
+            .field("cookie_sessions", &self.session_jars.len())
+            .field("dns_cache", &self.dns_resolver.is_some())
+            .field("middleware", &self.middleware)
+            .field("tls_profiles", &self.tls_profiles.len())
+            .field("bandwidth", &self.bandwidth.is_some())
+            .finish_non_exhaustive()
+    }
 }
 
 impl PerHostFetchEngine {
@@ -482,9 +1043,113 @@ impl PerHostFetchEngine {
             retry_config,
             circuit_config,
             rate_limit_config,
+            geoip: None,
+            proxy_pool: None,
+            cookie_jar: None,
+            session_jars: Arc::default(),
+            dns_resolver: None,
+            compression: Arc::default(),
+            middleware: MiddlewareChain::default(),
+            tls_profiles: Vec::new(),
+            bandwidth: None,
         })
     }
 
+    /// Annotate per-host metrics with the server's country and ASN
+    pub fn with_geoip(mut self, geoip: Arc<dyn GeoIpLookup>) -> Self {
+        self.geoip = Some(geoip);
+        self
+    }
+
+    /// Send requests through a proxy from `pool`
+    ///
+    /// Per-host rate limits, retries, circuit breakers and metrics still
+    /// apply; failing proxies are also ejected by the pool.
+    pub fn with_proxy_pool(mut self, pool: Arc<ProxyPool>) -> Self {
+        self.proxy_pool = Some(pool);
+        self
+    }
+
+    /// Keep cookies of fetches made outside a session in `cookie_jar`, e.g.
+    /// one shared with a browser session or restored from session storage
+    ///
+    /// Only for engines serving a single tenant: every such fetch shares it.
+    pub fn with_cookie_jar(mut self, cookie_jar: Arc<CookieJar>) -> Self {
+        self.cookie_jar = Some(cookie_jar);
+        self
+    }
+
+    /// Keep the jars of up to `max_sessions` sessions
+    pub fn with_max_cookie_sessions(mut self, max_sessions: usize) -> Self {
+        self.session_jars = Arc::new(SessionCookieJars::new(max_sessions));
+        self
+    }
+
+    /// Resolve hosts through `resolver`, caching answers across per-host
+    /// clients and applying its host overrides
+    pub fn with_dns_resolver(mut self, resolver: Arc<CachingResolver>) -> Self {
+        self.dns_resolver = Some(resolver);
+        self
+    }
+
+    /// Record compression counters in `metrics` instead of a private set
+    pub fn with_compression_metrics(mut self, metrics: Arc<CompressionMetrics>) -> Self {
+        self.compression = metrics;
+        self
+    }
+
+    /// Run `middleware` around every request of every host, after those
+    /// already registered
+    pub fn with_middleware(mut self, middleware: Arc<dyn FetchMiddleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Present per-domain client certificates and trust per-domain CA
+    /// bundles
+    ///
+    /// A profile's files are read when the first client of a host it matches
+    /// is created, so errors surface on that host's first fetch.
+    pub fn with_tls_profiles(mut self, profiles: Vec<DomainTlsConfig>) -> Self {
+        self.tls_profiles = profiles;
+        self
+    }
+
+    /// Read bodies no faster than `limiter` allows
+    ///
+    /// Applies to [`Self::fetch_text`], [`Self::fetch_bytes`] and
+    /// [`Self::read_body`]; [`Self::fetch`] hands out the response unread.
+    pub fn with_bandwidth_limiter(mut self, limiter: Arc<BandwidthLimiter>) -> Self {
+        self.bandwidth = Some(limiter);
+        self
+    }
+
+    /// Jar used by fetches made outside a session, if any
+    pub fn cookie_jar(&self) -> Option<Arc<CookieJar>> {
+        self.cookie_jar.clone()
+    }
+
+    /// Jar of `session`, e.g. to share it with a browser session or save it
+    /// to session storage
+    pub fn session_cookie_jar(&self, session: &str) -> Arc<CookieJar> {
+        self.session_jars.jar(session)
+    }
+
+    /// Drop the cookies of `session`, returning whether it had a jar
+    pub fn end_session(&self, session: &str) -> bool {
+        self.session_jars.remove(session).is_some()
+    }
+
+    /// Fetch `url` sending and keeping cookies in the jar of `session`
+    ///
+    /// `session` is a session or tenant id; fetches of other sessions never
+    /// see its cookies.
+    pub async fn fetch_in_session(&self, session: &str, url: &str) -> Result<Response> {
+        self.session_cookie_jar(session)
+            .scope(self.fetch(url))
+            .await
+    }
+
     /// Fetch content from a URL with per-host circuit breakers and rate limiting
     #[instrument(skip(self), fields(url = %url))]
     pub async fn fetch(&self, url: &str) -> Result<Response> {
@@ -506,6 +1171,7 @@ impl PerHostFetchEngine {
         // 5. Track metrics
         let duration = start.elapsed();
         self.record_metrics(&host, duration, result.is_ok());
+        self.record_location(&host).await;
 
         // 6. Log response
         self.log_response(url, &result, duration);
@@ -513,6 +1179,31 @@ impl PerHostFetchEngine {
         result
     }
 
+    /// Fetch content and return as text
+    pub async fn fetch_text(&self, url: &str) -> Result<String> {
+        let response = self.fetch(url).await?;
+        match &self.bandwidth {
+            Some(_) => Ok(String::from_utf8_lossy(&self.read_body(response).await?).into_owned()),
+            None => response.text().await.map_err(|e| anyhow::anyhow!(e)),
+        }
+    }
+
+    /// Fetch content and return as bytes
+    pub async fn fetch_bytes(&self, url: &str) -> Result<Vec<u8>> {
+        let response = self.fetch(url).await?;
+        self.read_body(response).await
+    }
+
+    /// Read a whole body, e.g. of a [`Self::fetch`] response, throttled when
+    /// a bandwidth limiter is set
+    pub async fn read_body(&self, response: Response) -> Result<Vec<u8>> {
+        let body = match &self.bandwidth {
+            Some(limiter) => bandwidth::read_throttled(response, limiter).await,
+            None => response.bytes().await.map(|body| body.to_vec()),
+        };
+        body.map_err(|e| anyhow::anyhow!(e))
+    }
+
     /// Get or create a per-host HTTP client
     async fn get_or_create_client(&self, host: &str) -> Result<Arc<ReliableHttpClient>> {
         // Check if client already exists (read lock)
@@ -538,10 +1229,21 @@ impl PerHostFetchEngine {
         }
 
         // Create new client with per-host circuit breaker
-        let client = Arc::new(ReliableHttpClient::new(
+        let mut client = ReliableHttpClient::new_with_transport(
             self.retry_config.clone(),
             self.circuit_config.clone(),
-        )?);
+            Arc::new(SessionCookieStore::new(self.cookie_jar.clone())),
+            self.dns_resolver.clone(),
+        )?
+        .with_compression_metrics(self.compression.clone())
+        .with_middleware_chain(self.middleware.clone());
+        if let Some(tls) = self.tls_profiles.iter().find(|tls| tls.matches(host)) {
+            client = client.with_tls_profiles(std::slice::from_ref(tls))?;
+        }
+        if let Some(pool) = &self.proxy_pool {
+            client = client.with_proxy_pool(pool.clone());
+        }
+        let client = Arc::new(client);
 
         clients.insert(host.to_string(), client.clone());
         info!(host = %host, "Created new per-host HTTP client");
@@ -649,6 +1351,29 @@ impl PerHostFetchEngine {
         }
     }
 
+    /// Resolve and store the host location once per host
+    async fn record_location(&self, host: &str) {
+        let Some(geoip) = &self.geoip else {
+            return;
+        };
+        let known = self
+            .metrics
+            .read()
+            .ok()
+            .and_then(|m| m.get(host).map(|h| h.location.is_some()))
+            .unwrap_or(false);
+        if known {
+            return;
+        }
+
+        let Some(location) = locate_host(geoip.as_ref(), host).await else {
+            return;
+        };
+        if let Ok(mut metrics_map) = self.metrics.write() {
+            metrics_map.entry(host.to_string()).or_default().location = Some(location);
+        }
+    }
+
     /// Get metrics for a specific host
     pub fn get_host_metrics(&self, host: &str) -> Option<HostMetrics> {
         let metrics = self.metrics.read().ok()?;
@@ -668,6 +1393,10 @@ impl PerHostFetchEngine {
                         total_requests: 0,
                         total_success: 0,
                         total_failures: 0,
+                        proxy_pool: None,
+                        dns: None,
+                        compression: None,
+                        robots: None,
                     };
                 }
             };
@@ -680,6 +1409,10 @@ impl PerHostFetchEngine {
                         total_requests: 0,
                         total_success: 0,
                         total_failures: 0,
+                        proxy_pool: None,
+                        dns: None,
+                        compression: None,
+                        robots: None,
                     };
                 }
             };
@@ -716,6 +1449,7 @@ impl PerHostFetchEngine {
                     failure_count: host_metrics.failure_count,
                     avg_duration_ms,
                     circuit_state,
+                    location: host_metrics.location.clone(),
                 },
             );
         }
@@ -725,10 +1459,40 @@ impl PerHostFetchEngine {
             total_requests,
             total_success,
             total_failures,
+            proxy_pool: self.proxy_pool.as_ref().map(|pool| pool.stats()),
+            dns: self.dns_resolver.as_ref().map(|resolver| resolver.stats()),
+            compression: Some(self.compression.snapshot()),
+            robots: None,
         }
     }
 }
 
+/// 429 and 503 responses carry server-dictated delays
+fn is_throttling(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status == reqwest::StatusCode::SERVICE_UNAVAILABLE
+}
+
+/// Error for a failed response
+///
+/// Throttled responses get a [`Throttled`] context with the server's delay;
+/// the `reqwest::Error` underneath stays reachable via `downcast_ref`.
+fn status_failure(
+    error: reqwest::Error,
+    status: reqwest::StatusCode,
+    retry_after: Option<Duration>,
+) -> anyhow::Error {
+    let error = anyhow::Error::from(error);
+    if is_throttling(status) {
+        error.context(Throttled {
+            status: status.as_u16(),
+            retry_after,
+        })
+    } else {
+        error
+    }
+}
+
 /// Check if an error is retryable
 #[allow(dead_code)]
 fn is_retryable_error(error: &reqwest::Error) -> bool {
@@ -741,6 +1505,8 @@ pub fn http_client() -> Result<Client> {
         .user_agent("RipTide/1.0")
         .gzip(true)
         .brotli(true)
+        .deflate(true)
+        .zstd(true)
         .connect_timeout(Duration::from_secs(3))
         .timeout(Duration::from_secs(20)) // Updated to 20s
         .build()
@@ -824,6 +1590,25 @@ mod tests {
         assert!(client.get_robots_manager().is_some());
     }
 
+    #[tokio::test]
+    async fn test_shared_robots_service_metrics() {
+        use crate::robots_service::RobotsServiceConfig;
+
+        let service = Arc::new(RobotsService::new(RobotsServiceConfig::default()).unwrap());
+        let engine = FetchEngine::with_robots_service(
+            RetryConfig::default(),
+            CircuitBreakerConfig::default(),
+            RobotsConfig::default(),
+            service.clone(),
+        )
+        .expect("Failed to create fetch engine for test");
+
+        let manager = engine.client.get_robots_manager().unwrap();
+        assert!(Arc::ptr_eq(manager.service(), &service));
+        let robots = engine.get_all_metrics().await.robots.unwrap();
+        assert_eq!(robots.fetches, 0);
+    }
+
     #[tokio::test]
     async fn test_circuit_breaker_transitions() {
         // Using TestClock for deterministic testing
@@ -1213,4 +1998,534 @@ mod tests {
             delay_with_jitter
         );
     }
+
+    #[tokio::test]
+    async fn test_fetch_conditional() {
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/page"))
+            .and(header("If-None-Match", "\"v1\""))
+            .respond_with(ResponseTemplate::new(304))
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/page"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("ETag", "\"v1\"")
+                    .set_body_string("hello"),
+            )
+            .mount(&server)
+            .await;
+
+        let engine = FetchEngine::new().expect("Failed to create engine");
+        let url = format!("{}/page", server.uri());
+
+        let first = engine.fetch_conditional(&url, None).await.unwrap();
+        assert!(first.is_changed());
+        assert_eq!(first.fingerprint().etag.as_deref(), Some("\"v1\""));
+
+        let second = engine
+            .fetch_conditional(&url, Some(first.fingerprint()))
+            .await
+            .unwrap();
+        assert!(matches!(second, ConditionalFetch::NotModified(_)));
+        assert!(!second.is_changed());
+        assert_eq!(
+            second.fingerprint().content_hash,
+            first.fingerprint().content_hash
+        );
+
+        // Same body without validators is detected as unchanged by hash
+        let mut stale = first.fingerprint().clone();
+        stale.etag = None;
+        let third = engine.fetch_conditional(&url, Some(&stale)).await.unwrap();
+        assert!(matches!(
+            third,
+            ConditionalFetch::Fetched { changed: false, .. }
+        ));
+    }
+
+    fn retry_after_client(max_attempts: u32) -> ReliableHttpClient {
+        ReliableHttpClient::new(
+            RetryConfig {
+                max_attempts,
+                initial_delay: Duration::from_millis(10),
+                max_delay: Duration::from_secs(5),
+                backoff_multiplier: 2.0,
+                jitter: false,
+            },
+            CircuitBreakerConfig::default(),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_retry_waits_for_retry_after() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "1"))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+
+        let started = std::time::Instant::now();
+        let response = retry_after_client(2)
+            .get_with_retry(&server.uri())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+        assert!(started.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_proxy_pool_requests_are_retried() {
+        use crate::proxy_pool::ProxyPoolConfig;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // A plain HTTP server also answers absolute-form proxy requests
+        let proxy = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&proxy)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&proxy)
+            .await;
+        let pool = Arc::new(ProxyPool::new([proxy.uri()], ProxyPoolConfig::default()).unwrap());
+
+        let response = retry_after_client(2)
+            .with_proxy_pool(pool.clone())
+            .get_with_retry("http://origin.test/page")
+            .await
+            .unwrap();
+
+        assert_eq!(response.text().await.unwrap(), "ok");
+        assert_eq!(pool.stats().proxies[0].requests, 2);
+        let requests = proxy.received_requests().await.unwrap();
+        assert!(requests
+            .iter()
+            .all(|request| request.url.host_str() == Some("origin.test")));
+    }
+
+    #[tokio::test]
+    async fn test_session_cookies_are_isolated() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/login"))
+            .respond_with(ResponseTemplate::new(200).insert_header("Set-Cookie", "sid=a; Path=/"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        let engine = PerHostFetchEngine::new(
+            RetryConfig::default(),
+            CircuitBreakerConfig::default(),
+            RateLimitConfig::default(),
+        )
+        .unwrap();
+        let page = format!("{}/page", server.uri());
+
+        engine
+            .fetch_in_session("a", &format!("{}/login", server.uri()))
+            .await
+            .unwrap();
+        engine.fetch_in_session("b", &page).await.unwrap();
+        engine.fetch(&page).await.unwrap();
+        engine.fetch_in_session("a", &page).await.unwrap();
+
+        let cookies: Vec<Option<String>> = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .skip(1)
+            .map(|request| {
+                request
+                    .headers
+                    .get("cookie")
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string)
+            })
+            .collect();
+        assert_eq!(cookies, [None, None, Some("sid=a".to_string())]);
+        assert!(engine.end_session("a"));
+        assert!(engine.session_cookie_jar("a").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_long_server_delay_is_surfaced_without_retrying() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(503).insert_header("X-RateLimit-Reset", "120"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let err = retry_after_client(3)
+            .get_with_retry(&server.uri())
+            .await
+            .unwrap_err();
+
+        let throttled = err.downcast_ref::<Throttled>().unwrap();
+        assert_eq!(throttled.status, 503);
+        assert_eq!(throttled.retry_after, Some(Duration::from_secs(120)));
+        // The status error stays reachable for existing callers
+        assert!(err.downcast_ref::<reqwest::Error>().is_some());
+    }
+
+    /// Rewrites `/old` to `/new`, tags each attempt and records response statuses
+    #[derive(Default)]
+    struct RecordingMiddleware {
+        seen: std::sync::Mutex<Vec<(u32, u16, String)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl FetchMiddleware for RecordingMiddleware {
+        async fn on_request(&self, request: &mut reqwest::Request) -> Result<()> {
+            if request.url().path() == "/old" {
+                request.url_mut().set_path("/new");
+            }
+            request
+                .headers_mut()
+                .insert("x-signature", "signed".parse().unwrap());
+            Ok(())
+        }
+
+        async fn on_response(
+            &self,
+            exchange: &crate::middleware::Exchange,
+            response: &mut Response,
+        ) -> Result<()> {
+            self.seen.lock().unwrap().push((
+                exchange.attempt,
+                response.status().as_u16(),
+                exchange.url.path().to_string(),
+            ));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_runs_on_every_attempt() {
+        use crate::middleware::StaticHeaders;
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/new"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/new"))
+            .and(header("x-signature", "signed"))
+            .and(header("x-api-key", "secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+
+        let recorder = Arc::new(RecordingMiddleware::default());
+        let client = retry_after_client(2)
+            .with_middleware(recorder.clone())
+            .with_middleware(Arc::new(
+                StaticHeaders::new([("x-api-key", "secret")]).unwrap(),
+            ));
+
+        let response = client
+            .get_with_retry(&format!("{}/old", server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "ok");
+        assert_eq!(
+            *recorder.seen.lock().unwrap(),
+            vec![(0, 503, "/new".to_string()), (1, 200, "/new".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_per_host_engine_runs_middleware() {
+        use crate::middleware::StaticHeaders;
+        use wiremock::matchers::{header, method};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(header("x-api-key", "secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+
+        let recorder = Arc::new(RecordingMiddleware::default());
+        let engine = PerHostFetchEngine::new(
+            RetryConfig::default(),
+            CircuitBreakerConfig::default(),
+            RateLimitConfig::default(),
+        )
+        .unwrap()
+        .with_middleware(recorder.clone())
+        .with_middleware(Arc::new(
+            StaticHeaders::new([("x-api-key", "secret")]).unwrap(),
+        ));
+
+        let response = engine
+            .fetch(&format!("{}/page", server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "ok");
+        assert_eq!(
+            *recorder.seen.lock().unwrap(),
+            vec![(0, 200, "/page".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_per_host_engine_reads_bodies_under_bandwidth_limiter() {
+        use crate::bandwidth::BandwidthConfig;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("a".repeat(2048)))
+            .mount(&server)
+            .await;
+
+        let limiter = Arc::new(BandwidthLimiter::new(BandwidthConfig {
+            per_domain_bytes_per_sec: 1024 * 1024,
+            ..Default::default()
+        }));
+        let engine = PerHostFetchEngine::new(
+            RetryConfig::default(),
+            CircuitBreakerConfig::default(),
+            RateLimitConfig::default(),
+        )
+        .unwrap()
+        .with_bandwidth_limiter(limiter.clone());
+
+        let text = engine.fetch_text(&server.uri()).await.unwrap();
+        assert_eq!(text.len(), 2048);
+        let response = engine.fetch(&server.uri()).await.unwrap();
+        assert_eq!(engine.read_body(response).await.unwrap().len(), 2048);
+
+        let stats = limiter.stats();
+        assert_eq!(stats.bytes, 4096);
+        assert_eq!(stats.tracked_domains, 1);
+    }
+
+    #[tokio::test]
+    async fn test_tls_profile_redirects_stay_on_profile_hosts() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        let port = server.address().port();
+        let redirect = |to: String| ResponseTemplate::new(302).insert_header("location", to);
+        Mock::given(method("GET"))
+            .and(path("/inside"))
+            .respond_with(redirect(format!("http://127.0.0.1:{}/landing", port)))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/outside"))
+            .respond_with(redirect(format!("http://localhost:{}/landing", port)))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/landing"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("landed"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let profile = DomainTlsConfig {
+            domains: vec!["127.0.0.1".to_string()],
+            ..Default::default()
+        };
+        let engine = FetchEngine::new()
+            .unwrap()
+            .with_tls_profiles(&[profile])
+            .unwrap();
+
+        let inside = engine
+            .fetch(&format!("http://127.0.0.1:{}/inside", port))
+            .await
+            .unwrap();
+        assert_eq!(inside.text().await.unwrap(), "landed");
+
+        let outside = engine
+            .fetch(&format!("http://127.0.0.1:{}/outside", port))
+            .await
+            .unwrap();
+        assert_eq!(outside.status(), 302);
+        assert_eq!(
+            outside.headers()["location"],
+            format!("http://localhost:{}/landing", port).as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_middleware_error_fails_without_sending() {
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        struct Reject;
+
+        #[async_trait::async_trait]
+        impl FetchMiddleware for Reject {
+            fn name(&self) -> &str {
+                "Reject"
+            }
+
+            async fn on_request(&self, _request: &mut reqwest::Request) -> Result<()> {
+                Err(anyhow::anyhow!("no credentials"))
+            }
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(wiremock::matchers::any())
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let client = retry_after_client(3).with_middleware(Arc::new(Reject));
+        let err = client.get_with_retry(&server.uri()).await.unwrap_err();
+
+        let err = err
+            .downcast_ref::<crate::middleware::MiddlewareError>()
+            .unwrap();
+        assert_eq!(err.middleware, "Reject");
+        assert_eq!(client.get_circuit_breaker_failure_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_middleware_short_circuits_request() {
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        /// Answers every request from memory
+        struct Canned;
+
+        #[async_trait::async_trait]
+        impl FetchMiddleware for Canned {
+            async fn respond(&self, _request: &reqwest::Request) -> Result<Option<Response>> {
+                let response = http::Response::builder().status(200).body("canned")?;
+                Ok(Some(Response::from(response)))
+            }
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(wiremock::matchers::any())
+            .respond_with(ResponseTemplate::new(200))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let outer = Arc::new(RecordingMiddleware::default());
+        let inner = Arc::new(RecordingMiddleware::default());
+        let client = retry_after_client(2)
+            .with_middleware(outer.clone())
+            .with_middleware(Arc::new(Canned))
+            .with_middleware(inner.clone());
+
+        let response = client
+            .get_with_retry(&format!("{}/old", server.uri()))
+            .await
+            .unwrap();
+        assert_eq!(response.text().await.unwrap(), "canned");
+        assert_eq!(
+            *outer.seen.lock().unwrap(),
+            vec![(0, 200, "/new".to_string())]
+        );
+        assert!(inner.seen.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_middleware_replays_after_refreshing_credentials() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use wiremock::matchers::header;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        /// Sends the current token version and refreshes it on a 401
+        #[derive(Default)]
+        struct RefreshingAuth {
+            version: AtomicU32,
+        }
+
+        #[async_trait::async_trait]
+        impl FetchMiddleware for RefreshingAuth {
+            async fn on_request(&self, request: &mut reqwest::Request) -> Result<()> {
+                let token = format!("Bearer v{}", self.version.load(Ordering::SeqCst));
+                request
+                    .headers_mut()
+                    .insert("authorization", token.parse()?);
+                Ok(())
+            }
+
+            async fn replay(
+                &self,
+                _exchange: &crate::middleware::Exchange,
+                response: &Response,
+            ) -> Result<bool> {
+                if response.status() != reqwest::StatusCode::UNAUTHORIZED {
+                    return Ok(false);
+                }
+                self.version.fetch_add(1, Ordering::SeqCst);
+                Ok(true)
+            }
+        }
+
+        let server = MockServer::start().await;
+        Mock::given(header("authorization", "Bearer v1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(wiremock::matchers::any())
+            .respond_with(ResponseTemplate::new(401))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let auth = Arc::new(RefreshingAuth::default());
+        let client = retry_after_client(1).with_middleware(auth.clone());
+        let response = client.get_with_retry(&server.uri()).await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "ok");
+
+        // One replay per request: a second refresh does not help
+        auth.version.store(5, Ordering::SeqCst);
+        let err = client.get_with_retry(&server.uri()).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<reqwest::Error>()
+                .and_then(reqwest::Error::status),
+            Some(reqwest::StatusCode::UNAUTHORIZED)
+        );
+    }
 }
@@ -0,0 +1,133 @@
+//! GeoIP enrichment of fetched hosts
+//!
+//! Resolves a host to its IP addresses and looks the first one up through a
+//! [`GeoIpLookup`] port. With the `geoip` feature enabled, [`MaxMindGeoIp`]
+//! provides an adapter over MaxMind-format (GeoLite2/GeoIP2) Country and ASN
+//! databases.
+
+use riptide_types::ports::{GeoIpLookup, GeoLocation};
+use std::net::IpAddr;
+use tracing::debug;
+
+/// Resolve `host` and look up the location of its first address
+///
+/// DNS failures and database misses yield `None`: GeoIP data is advisory
+/// and must never fail a fetch on its own.
+pub async fn locate_host(lookup: &dyn GeoIpLookup, host: &str) -> Option<GeoLocation> {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let ip = match host.parse::<IpAddr>() {
+        Ok(ip) => ip,
+        Err(_) => tokio::net::lookup_host((host, 0))
+            .await
+            .ok()?
+            .next()?
+            .ip(),
+    };
+
+    match lookup.lookup(ip) {
+        Ok(location) => location.filter(|loc| !loc.is_empty()),
+        Err(e) => {
+            debug!(host = %host, ip = %ip, error = %e, "GeoIP lookup failed");
+            None
+        }
+    }
+}
+
+#[cfg(feature = "geoip")]
+pub use maxmind::MaxMindGeoIp;
+
+#[cfg(feature = "geoip")]
+mod maxmind {
+    use maxminddb::{geoip2, MaxMindDBError, Reader};
+    use riptide_types::error::{Result, RiptideError};
+    use riptide_types::ports::{GeoIpLookup, GeoLocation};
+    use std::net::IpAddr;
+    use std::path::Path;
+
+    /// GeoIP adapter over MaxMind-format Country and ASN databases
+    pub struct MaxMindGeoIp {
+        country: Option<Reader<Vec<u8>>>,
+        asn: Option<Reader<Vec<u8>>>,
+    }
+
+    impl MaxMindGeoIp {
+        /// Open a Country database and, optionally, an ASN database
+        pub fn open(country_db: impl AsRef<Path>, asn_db: Option<&Path>) -> Result<Self> {
+            let country = Reader::open_readfile(country_db.as_ref()).map_err(open_error)?;
+            let asn = asn_db
+                .map(|path| Reader::open_readfile(path).map_err(open_error))
+                .transpose()?;
+            Ok(Self {
+                country: Some(country),
+                asn,
+            })
+        }
+
+        /// Open only an ASN database
+        pub fn open_asn(asn_db: impl AsRef<Path>) -> Result<Self> {
+            Ok(Self {
+                country: None,
+                asn: Some(Reader::open_readfile(asn_db.as_ref()).map_err(open_error)?),
+            })
+        }
+    }
+
+    impl GeoIpLookup for MaxMindGeoIp {
+        fn lookup(&self, ip: IpAddr) -> Result<Option<GeoLocation>> {
+            let mut location = GeoLocation::default();
+
+            if let Some(reader) = &self.country {
+                if let Some(record) = found(reader.lookup::<geoip2::Country>(ip))? {
+                    location.country_code = record
+                        .country
+                        .and_then(|c| c.iso_code)
+                        .map(str::to_string);
+                }
+            }
+
+            if let Some(reader) = &self.asn {
+                if let Some(record) = found(reader.lookup::<geoip2::Asn>(ip))? {
+                    location.asn = record.autonomous_system_number;
+                    location.as_org = record.autonomous_system_organization.map(str::to_string);
+                }
+            }
+
+            Ok((!location.is_empty()).then_some(location))
+        }
+    }
+
+    /// Treat "address not found" as a miss rather than an error
+    fn found<T>(result: std::result::Result<T, MaxMindDBError>) -> Result<Option<T>> {
+        match result {
+            Ok(record) => Ok(Some(record)),
+            Err(MaxMindDBError::AddressNotFoundError(_)) => Ok(None),
+            Err(e) => Err(RiptideError::custom(format!("GeoIP lookup failed: {}", e))),
+        }
+    }
+
+    fn open_error(e: MaxMindDBError) -> RiptideError {
+        RiptideError::Configuration(format!("Failed to open GeoIP database: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::ports::InMemoryGeoIp;
+
+    #[tokio::test]
+    async fn test_locate_ip_literal_host() {
+        let geoip = InMemoryGeoIp::new().with_entry(
+            "127.0.0.1".parse().unwrap(),
+            GeoLocation {
+                country_code: Some("ZZ".to_string()),
+                asn: None,
+                as_org: None,
+            },
+        );
+
+        let location = locate_host(&geoip, "127.0.0.1").await.unwrap();
+        assert_eq!(location.country_code.as_deref(), Some("ZZ"));
+        assert!(locate_host(&geoip, "[::1]").await.is_none());
+    }
+}
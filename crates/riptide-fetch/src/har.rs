@@ -0,0 +1,630 @@
+//! HAR recording of fetch sessions
+//!
+//! [`HarRecorder`] is a [`FetchMiddleware`] that logs every request a client
+//! sends and the response it gets back as an HTTP Archive (HAR 1.2) entry.
+//! Attach one recorder to every client of a crawl session with
+//! `with_middleware` ([`crate::FetchEngine`], [`crate::PerHostFetchEngine`],
+//! [`crate::ReliableHttpClient`] and the spider's client built from it),
+//! then write [`HarRecorder::har`] to a `.har` file to inspect slow fetches
+//! in browser devtools or feed them to other HAR tooling.
+//!
+//! `timings.wait` is the time to the response headers and
+//! `timings.receive` the time from there until the body was read to the
+//! end; the entry is updated once the body is consumed, and keeps
+//! `receive` at 0 and the `Content-Length` body size (`-1` without one)
+//! when it never is. Requests failing before a response (DNS, connect,
+//! timeouts) are recorded with status 0 and the failure in a custom
+//! `_error`, which also notes a body read that failed midway. Each retry
+//! attempt is its own entry, tagged with a custom `_attempt`.
+//!
+//! Credentials are masked: `Authorization`, `Cookie`, `Set-Cookie` and
+//! headers named like secrets (`X-Api-Key`, `X-Auth-Token`) unless
+//! [`HarRecorder::with_sensitive_headers`] is set, and URL userinfo always.
+//! Query strings are recorded as sent.
+
+use crate::middleware::{Exchange, FetchMiddleware};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use reqwest::header::{HeaderMap, HeaderName, CONTENT_ENCODING, CONTENT_TYPE, LOCATION};
+use reqwest::{Body, Response, ResponseBuilderExt, Version};
+use riptide_config::redaction::{is_sensitive_key, redact_text, redact_url, REDACTED};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Entries kept by a recorder unless configured otherwise
+pub const DEFAULT_MAX_HAR_ENTRIES: usize = 10_000;
+
+/// Headers always treated as credentials
+const CREDENTIAL_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+];
+
+/// HAR document root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Har {
+    pub log: HarLog,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HarLog {
+    pub version: String,
+    pub creator: HarCreator,
+    pub entries: Vec<HarEntry>,
+    /// Entries dropped after the recorder reached its limit
+    #[serde(rename = "_droppedEntries", default)]
+    pub dropped_entries: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HarCreator {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    pub started_date_time: DateTime<Utc>,
+    /// Total time in milliseconds
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    pub cache: serde_json::Value,
+    pub timings: HarTimings,
+    #[serde(rename = "serverIPAddress", skip_serializing_if = "Option::is_none")]
+    pub server_ip_address: Option<String>,
+    /// Zero-based retry attempt
+    #[serde(rename = "_attempt")]
+    pub attempt: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    pub http_version: String,
+    pub cookies: Vec<HarNameValue>,
+    pub headers: Vec<HarNameValue>,
+    pub query_string: Vec<HarNameValue>,
+    pub headers_size: i64,
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: u16,
+    pub status_text: String,
+    pub http_version: String,
+    pub cookies: Vec<HarNameValue>,
+    pub headers: Vec<HarNameValue>,
+    pub content: HarContent,
+    #[serde(rename = "redirectURL")]
+    pub redirect_url: String,
+    pub headers_size: i64,
+    pub body_size: i64,
+    /// Why the request or the body read failed
+    #[serde(rename = "_error", default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    pub size: i64,
+    pub mime_type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HarNameValue {
+    pub name: String,
+    pub value: String,
+}
+
+/// Phase timings in milliseconds; `-1` marks phases that were not measured
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HarTimings {
+    pub blocked: f64,
+    pub dns: f64,
+    pub connect: f64,
+    pub send: f64,
+    pub wait: f64,
+    pub receive: f64,
+}
+
+/// Recorded entries, keyed by an id the body reader updates them by
+type Entries = Arc<Mutex<Vec<(u64, HarEntry)>>>;
+
+/// Middleware recording every exchange as a HAR entry
+#[derive(Debug)]
+pub struct HarRecorder {
+    entries: Entries,
+    next_id: AtomicU64,
+    max_entries: usize,
+    dropped: AtomicU64,
+    include_sensitive_headers: bool,
+}
+
+impl Default for HarRecorder {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_HAR_ENTRIES)
+    }
+}
+
+impl HarRecorder {
+    /// Recorder keeping at most `max_entries`; later exchanges are counted
+    /// in `_droppedEntries` instead
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: Arc::default(),
+            next_id: AtomicU64::new(0),
+            max_entries,
+            dropped: AtomicU64::new(0),
+            include_sensitive_headers: false,
+        }
+    }
+
+    /// Record credential headers unmasked
+    pub fn with_sensitive_headers(mut self, include: bool) -> Self {
+        self.include_sensitive_headers = include;
+        self
+    }
+
+    /// Number of recorded entries
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Drop all recorded entries
+    pub fn clear(&self) {
+        self.lock().clear();
+        self.dropped.store(0, Ordering::Relaxed);
+    }
+
+    /// HAR document of the entries recorded so far, ordered by start time
+    pub fn har(&self) -> Har {
+        let mut entries: Vec<HarEntry> =
+            self.lock().iter().map(|(_, entry)| entry.clone()).collect();
+        entries.sort_by_key(|entry| entry.started_date_time);
+        Har {
+            log: HarLog {
+                version: "1.2".to_string(),
+                creator: HarCreator {
+                    name: "RipTide".to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                },
+                entries,
+                dropped_entries: self.dropped.load(Ordering::Relaxed),
+            },
+        }
+    }
+
+    /// Write the HAR document to `path` as JSON
+    pub fn write_to(&self, path: impl AsRef<Path>) -> Result<()> {
+        let json = serde_json::to_vec_pretty(&self.har())?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<(u64, HarEntry)>> {
+        lock(&self.entries)
+    }
+
+    /// Store `entry`, returning its id unless the recorder is full
+    fn push(&self, entry: HarEntry) -> Option<u64> {
+        let mut entries = self.lock();
+        if entries.len() >= self.max_entries {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        entries.push((id, entry));
+        Some(id)
+    }
+
+    fn headers(&self, headers: &HeaderMap) -> Vec<HarNameValue> {
+        headers
+            .iter()
+            .map(|(name, value)| {
+                let name = name.as_str();
+                let sensitive = CREDENTIAL_HEADERS.contains(&name) || is_sensitive_key(name);
+                let value = if sensitive && !self.include_sensitive_headers {
+                    REDACTED.to_string()
+                } else {
+                    String::from_utf8_lossy(value.as_bytes()).into_owned()
+                };
+                HarNameValue {
+                    name: name.to_string(),
+                    value,
+                }
+            })
+            .collect()
+    }
+
+    fn request(&self, exchange: &Exchange, http_version: String) -> HarRequest {
+        HarRequest {
+            method: exchange.method.to_string(),
+            url: redact_url(exchange.url.as_str()),
+            http_version,
+            cookies: Vec::new(),
+            headers: self.headers(&exchange.headers),
+            query_string: exchange
+                .url
+                .query_pairs()
+                .map(|(name, value)| HarNameValue {
+                    name: name.into_owned(),
+                    value: value.into_owned(),
+                })
+                .collect(),
+            headers_size: -1,
+            body_size: exchange.body_size.map(|len| len as i64).unwrap_or(0),
+        }
+    }
+
+    fn entry(&self, exchange: &Exchange, response: &Response) -> HarEntry {
+        let elapsed = exchange.elapsed();
+        let wait_ms = millis(elapsed);
+        let http_version = http_version(response.version());
+        let body_size = response
+            .content_length()
+            .map(|len| len as i64)
+            .unwrap_or(-1);
+        let header = |name: HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_string()
+        };
+
+        HarEntry {
+            started_date_time: started(elapsed),
+            time: wait_ms,
+            request: self.request(exchange, http_version.clone()),
+            response: HarResponse {
+                status: response.status().as_u16(),
+                status_text: response
+                    .status()
+                    .canonical_reason()
+                    .unwrap_or_default()
+                    .to_string(),
+                http_version,
+                cookies: Vec::new(),
+                headers: self.headers(response.headers()),
+                content: HarContent {
+                    size: body_size,
+                    mime_type: header(CONTENT_TYPE),
+                },
+                redirect_url: header(LOCATION),
+                headers_size: -1,
+                body_size,
+                error: None,
+            },
+            cache: serde_json::json!({}),
+            timings: HarTimings {
+                blocked: -1.0,
+                dns: -1.0,
+                connect: -1.0,
+                send: 0.0,
+                wait: wait_ms,
+                receive: 0.0,
+            },
+            server_ip_address: response.remote_addr().map(|addr| addr.ip().to_string()),
+            attempt: exchange.attempt,
+        }
+    }
+
+    fn error_entry(&self, exchange: &Exchange, error: &anyhow::Error) -> HarEntry {
+        let elapsed = exchange.elapsed();
+        let wait_ms = millis(elapsed);
+        HarEntry {
+            started_date_time: started(elapsed),
+            time: wait_ms,
+            request: self.request(exchange, http_version(Version::HTTP_11)),
+            response: HarResponse {
+                status: 0,
+                status_text: String::new(),
+                http_version: String::new(),
+                cookies: Vec::new(),
+                headers: Vec::new(),
+                content: HarContent {
+                    size: 0,
+                    mime_type: String::new(),
+                },
+                redirect_url: String::new(),
+                headers_size: -1,
+                body_size: -1,
+                error: Some(redact_text(&format!("{:#}", error))),
+            },
+            cache: serde_json::json!({}),
+            timings: HarTimings {
+                blocked: -1.0,
+                dns: -1.0,
+                connect: -1.0,
+                send: 0.0,
+                wait: wait_ms,
+                receive: 0.0,
+            },
+            server_ip_address: None,
+            attempt: exchange.attempt,
+        }
+    }
+
+    /// Swap the body of `response` for one updating entry `id` with the
+    /// receive time and size once it has been read
+    fn time_body(&self, id: u64, response: &mut Response) -> Result<()> {
+        let placeholder = Response::from(http::Response::new(Body::from(Vec::<u8>::new())));
+        let mut original = std::mem::replace(response, placeholder);
+        let extensions = std::mem::take(original.extensions_mut());
+        let headers = original.headers().clone();
+        let builder = http::Response::builder()
+            .status(original.status())
+            .version(original.version())
+            .url(original.url().clone());
+
+        let timer = BodyTimer {
+            entries: self.entries.clone(),
+            id,
+            started: Instant::now(),
+            bytes: 0,
+            encoded: headers.contains_key(CONTENT_ENCODING),
+        };
+        let body = futures::stream::unfold(
+            Some((original.bytes_stream().boxed(), timer)),
+            |state| async move {
+                let (mut stream, mut timer) = state?;
+                match stream.next().await {
+                    Some(Ok(chunk)) => {
+                        timer.bytes += chunk.len() as u64;
+                        Some((Ok(chunk), Some((stream, timer))))
+                    }
+                    Some(Err(error)) => {
+                        timer.finish(Some(redact_text(&error.to_string())));
+                        Some((Err(error), None))
+                    }
+                    None => {
+                        timer.finish(None);
+                        None
+                    }
+                }
+            },
+        );
+
+        let mut rebuilt = builder.body(Body::wrap_stream(body))?;
+        *rebuilt.headers_mut() = headers;
+        rebuilt.extensions_mut().extend(extensions);
+        *response = Response::from(rebuilt);
+        Ok(())
+    }
+}
+
+/// Completes an entry when its response body has been read
+struct BodyTimer {
+    entries: Entries,
+    id: u64,
+    started: Instant,
+    bytes: u64,
+    /// Whether the wire size differs from the decoded content size
+    encoded: bool,
+}
+
+impl BodyTimer {
+    fn finish(&self, error: Option<String>) {
+        let receive_ms = millis(self.started.elapsed());
+        let mut entries = lock(&self.entries);
+        let Some((_, entry)) = entries.iter_mut().find(|(id, _)| *id == self.id) else {
+            return;
+        };
+        entry.timings.receive = receive_ms;
+        entry.time = entry.timings.wait + receive_ms;
+        entry.response.body_size = self.bytes as i64;
+        if !self.encoded {
+            entry.response.content.size = self.bytes as i64;
+        }
+        entry.response.error = error;
+    }
+}
+
+#[async_trait]
+impl FetchMiddleware for HarRecorder {
+    fn name(&self) -> &str {
+        "HarRecorder"
+    }
+
+    async fn on_response(&self, exchange: &Exchange, response: &mut Response) -> Result<()> {
+        let entry = self.entry(exchange, response);
+        match self.push(entry) {
+            Some(id) => self.time_body(id, response),
+            None => Ok(()),
+        }
+    }
+
+    async fn on_error(&self, exchange: &Exchange, error: &anyhow::Error) {
+        self.push(self.error_entry(exchange, error));
+    }
+}
+
+fn lock(entries: &Entries) -> MutexGuard<'_, Vec<(u64, HarEntry)>> {
+    entries
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Wall-clock time `elapsed` ago
+fn started(elapsed: Duration) -> DateTime<Utc> {
+    Utc::now() - chrono::Duration::from_std(elapsed).unwrap_or_else(|_| chrono::Duration::zero())
+}
+
+fn http_version(version: Version) -> String {
+    match version {
+        Version::HTTP_09 => "HTTP/0.9",
+        Version::HTTP_10 => "HTTP/1.0",
+        Version::HTTP_2 => "HTTP/2.0",
+        Version::HTTP_3 => "HTTP/3.0",
+        _ => "HTTP/1.1",
+    }
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::MiddlewareChain;
+    use std::sync::Arc;
+    use wiremock::matchers::method;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn exchange(
+        recorder: Arc<HarRecorder>,
+        client: &reqwest::Client,
+        request: reqwest::RequestBuilder,
+    ) -> Result<Response> {
+        let mut chain = MiddlewareChain::default();
+        chain.push(recorder);
+        chain
+            .send(request.build().unwrap(), 0, |request| async move {
+                Ok(client.execute(request).await?)
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_records_entries_with_masked_credentials() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(
+                ResponseTemplate::new(201)
+                    .insert_header("content-type", "application/json")
+                    .insert_header("set-cookie", "session=abc")
+                    .set_body_raw("{}", "application/json"),
+            )
+            .mount(&server)
+            .await;
+
+        let recorder = Arc::new(HarRecorder::default());
+        let client = reqwest::Client::new();
+        let response = exchange(
+            recorder.clone(),
+            &client,
+            client
+                .post(format!("{}/items?page=2", server.uri()))
+                .header("authorization", "Bearer secret-token")
+                .header("x-api-key", "secret-key")
+                .header("accept", "application/json")
+                .body("{\"a\":1}"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.text().await.unwrap(), "{}");
+
+        let har = recorder.har();
+        assert_eq!(har.log.version, "1.2");
+        let entry = &har.log.entries[0];
+        assert_eq!(entry.request.method, "POST");
+        assert_eq!(entry.request.body_size, 7);
+        assert_eq!(entry.request.query_string[0].name, "page");
+        assert_eq!(entry.response.status, 201);
+        assert_eq!(entry.response.status_text, "Created");
+        assert_eq!(entry.response.content.mime_type, "application/json");
+        assert_eq!(entry.response.body_size, 2);
+        assert_eq!(entry.response.content.size, 2);
+        assert_eq!(entry.time, entry.timings.wait + entry.timings.receive);
+        assert!(entry.response.error.is_none());
+
+        let json = serde_json::to_string(&har).unwrap();
+        assert!(!json.contains("secret"), "{}", json);
+        assert!(!json.contains("session=abc"), "{}", json);
+        assert!(json.contains("\"accept\""));
+        assert!(json.contains("\"startedDateTime\""));
+        assert!(json.contains("\"redirectURL\""));
+    }
+
+    #[tokio::test]
+    async fn test_caps_entries() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let recorder = Arc::new(HarRecorder::new(2));
+        let client = reqwest::Client::new();
+        for _ in 0..3 {
+            exchange(recorder.clone(), &client, client.get(server.uri()))
+                .await
+                .unwrap();
+        }
+
+        let har = recorder.har();
+        assert_eq!(har.log.entries.len(), 2);
+        assert_eq!(har.log.dropped_entries, 1);
+
+        recorder.clear();
+        assert!(recorder.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_records_failed_requests() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://user:secret@{}/down", listener.local_addr().unwrap());
+        drop(listener);
+
+        let recorder = Arc::new(HarRecorder::default());
+        let client = reqwest::Client::new();
+        assert!(exchange(recorder.clone(), &client, client.get(&url))
+            .await
+            .is_err());
+
+        let har = recorder.har();
+        let entry = &har.log.entries[0];
+        assert_eq!(entry.response.status, 0);
+        assert_eq!(entry.request.method, "GET");
+        assert!(entry.response.error.is_some());
+        assert!(entry.timings.wait >= 0.0);
+
+        let json = serde_json::to_string(&har).unwrap();
+        assert!(json.contains("\"_error\""));
+        assert!(!json.contains("secret"), "{}", json);
+    }
+
+    #[tokio::test]
+    async fn test_measures_body_receive_time() {
+        let server = MockServer::start().await;
+        let body = "x".repeat(64 * 1024);
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body.clone()))
+            .mount(&server)
+            .await;
+
+        let recorder = Arc::new(HarRecorder::default());
+        let client = reqwest::Client::new();
+        let response = exchange(recorder.clone(), &client, client.get(server.uri()))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(response.bytes().await.unwrap().len(), body.len());
+
+        let entry = &recorder.har().log.entries[0];
+        assert!(entry.timings.receive >= 20.0, "{:?}", entry.timings);
+        assert_eq!(entry.response.body_size, body.len() as i64);
+        assert_eq!(entry.time, entry.timings.wait + entry.timings.receive);
+    }
+}
@@ -4,11 +4,28 @@
 //! This crate provides low-level HTTP fetching capabilities with:
 //!
 //! - **Async HTTP client**: Built on reqwest with connection pooling
-//! - **Retry logic**: Exponential backoff with configurable retries
+//! - **Retry logic**: Exponential backoff with configurable retries, honouring `Retry-After` and `X-RateLimit-Reset`
 //! - **Response caching**: Intelligent HTTP caching
+//! - **Body streaming**: Chunked downloads with size limits and content-type sniffing
+//! - **Resumable downloads**: Range-request resumption of dropped transfers with SHA-256 verification
+//! - **Content encoding**: gzip, deflate, brotli and zstd decoding with a decoded-size guard and compression ratios
 //! - **Rate limiting**: Request throttling and delay management
+//! - **Bandwidth limiting**: Token-bucket caps on download bytes/sec, globally and per domain
+//! - **robots.txt**: RFC 9309 rules with wildcards, per-agent groups, `Host` and `Crawl-delay`
+//! - **Robots service**: One fetch per origin shared by spider, fetch and facades, cached by response headers and optionally in Redis
+//! - **Conditional requests**: ETag/Last-Modified revalidation with content-hash fallback
+//! - **Middleware**: Per-request hooks to add headers, sign requests, rewrite URLs or record metrics
 //! - **Error handling**: Comprehensive HTTP error types
 //! - **Metrics**: Request/response monitoring
+//! - **Web archiving**: WARC 1.1 output for fetched and rendered pages
+//! - **Cookies**: RFC 6265 cookie jar, persisted through session storage and shared with browser sessions
+//! - **Proxy pool**: Health-checked HTTP/SOCKS5 proxy rotation, per request or sticky per domain
+//! - **DNS cache**: TTL-respecting resolver cache, dual-stack ordering and per-host overrides
+//! - **GeoIP enrichment**: Country/ASN of fetched hosts (MaxMind adapter behind `geoip`)
+//! - **Client TLS**: Per-domain client certificates (mTLS) and CA bundles
+//! - **URL rewriting**: Per-domain regex/template rules redirecting fetches to mirrors, canonical hosts or APIs
+//! - **HAR recording**: Requests, response headers and timings of a fetch session as HAR 1.2
+//! - **HTTP cassettes**: Record/replay of responses for offline integration tests (behind `cassette`)
 //!
 //! ## Architecture
 //!
@@ -20,16 +37,54 @@
 
 // Core modules
 pub mod adapters;
+pub mod bandwidth;
+pub mod body_stream;
+#[cfg(feature = "cassette")]
+pub mod cassette;
+pub mod content_encoding;
+pub mod cookies;
+pub mod dns;
 pub mod fetch;
+pub mod geoip;
+pub mod har;
+pub mod middleware;
+pub mod proxy_pool;
+pub mod resumable;
 pub mod robots;
+pub mod robots_rules;
+pub mod robots_service;
 pub mod telemetry;
+pub mod tls;
+pub mod url_rewrite;
+pub mod warc;
 
 // Re-export circuit breaker from riptide-utils
 pub use riptide_utils::circuit_breaker::{
     CircuitBreaker, Config as CircuitConfig, RealClock, State as CircuitState,
 };
+pub use riptide_utils::retry::Throttled;
 
 // Re-export main types
-pub use adapters::ReqwestHttpClient;
+pub use adapters::{ArchivingHttpClient, ReqwestHttpClient};
+pub use bandwidth::{BandwidthConfig, BandwidthLimiter, BandwidthStats};
+pub use body_stream::{BodyLimits, BodyStream, BodyStreamError};
+#[cfg(feature = "cassette")]
+pub use cassette::{Cassette, CassetteHttpClient, CassetteMode};
+pub use content_encoding::{CompressionMetrics, CompressionStats, ContentEncoding, WireBytes};
+pub use cookies::{
+    CookieJar, SameSite, SessionCookieJars, SessionCookieStore, StoredCookie,
+    DEFAULT_MAX_SESSION_JARS,
+};
+pub use dns::{CachingResolver, DnsBackend, DnsCacheStats};
 pub use fetch::*;
+pub use har::{Har, HarEntry, HarRecorder};
+pub use middleware::{Exchange, FetchMiddleware, MiddlewareChain, MiddlewareError, StaticHeaders};
+pub use proxy_pool::{ProxyPool, ProxyPoolConfig, ProxyPoolStats, ProxyRotation, ProxyStats};
+pub use resumable::{ResumableDownload, ResumeConfig, ResumeError};
 pub use robots::{RobotsConfig, RobotsManager};
+pub use robots_rules::{RobotsGroup, RobotsRules};
+pub use robots_service::{
+    RobotsFile, RobotsService, RobotsServiceConfig, RobotsServiceStats, RobotsStatus,
+};
+pub use tls::{ClientCertificate, DomainTlsConfig};
+pub use url_rewrite::{UrlRewrite, UrlRewriteRule, UrlRewriter};
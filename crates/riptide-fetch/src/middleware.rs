@@ -0,0 +1,352 @@
+//! Per-request fetch middleware
+//!
+//! A [`FetchMiddleware`] sees every request a client sends, just before it
+//! goes on the wire, and every response, before retry and decoding logic
+//! looks at it. Middlewares can add headers, sign requests (AWS SigV4,
+//! HMAC), rewrite URLs or record metrics without changes to this crate.
+//!
+//! Middlewares run in registration order on the way out and in reverse
+//! order on the way back, so the first registered middleware sees the
+//! final response last. Hooks run once per attempt: a retried request is
+//! rebuilt and passes through the chain again, so time-based signatures
+//! stay fresh. A hook returning an error fails the fetch without a retry
+//! and without counting against the circuit breaker.
+//!
+//! Like a tower layer, a middleware can also:
+//!
+//! - **short-circuit** a request by answering it from
+//!   [`FetchMiddleware::respond`]: nothing is sent, later middlewares do not
+//!   see the request, and the response goes back through the earlier ones
+//! - **replay** a request from [`FetchMiddleware::replay`], e.g. after
+//!   refreshing credentials a `401` showed had expired. The request passes
+//!   through the whole chain again, once per [`MiddlewareChain::send`]
+//!
+//! Clients built on [`MiddlewareChain::send`] support both and report
+//! requests that failed before a response to [`FetchMiddleware::on_error`];
+//! code calling [`MiddlewareChain::before_request`],
+//! [`MiddlewareChain::after_response`] and [`MiddlewareChain::after_error`]
+//! itself only gets those hooks.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Request, Response};
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::debug;
+use url::Url;
+
+/// Times [`MiddlewareChain::send`] sends a request again on a middleware's
+/// request
+const MAX_REPLAYS: u32 = 1;
+
+/// Hooks around each request a fetch client sends
+#[async_trait]
+pub trait FetchMiddleware: Send + Sync {
+    /// Name used in logs and errors
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Inspect or modify a request before it is sent
+    ///
+    /// URL, method, headers and (buffered) body can all be changed through
+    /// `request`'s `*_mut` accessors.
+    async fn on_request(&self, _request: &mut Request) -> Result<()> {
+        Ok(())
+    }
+
+    /// Inspect or modify a response before the client handles it
+    async fn on_response(&self, _exchange: &Exchange, _response: &mut Response) -> Result<()> {
+        Ok(())
+    }
+
+    /// Observe a request that failed before a response arrived (DNS,
+    /// connect, timeouts)
+    ///
+    /// Runs in reverse registration order; the fetch fails with the send
+    /// error either way.
+    async fn on_error(&self, _exchange: &Exchange, _error: &anyhow::Error) {}
+
+    /// Answer a request without sending it, after [`Self::on_request`] ran
+    ///
+    /// Build responses with `reqwest::Response::from(http::Response)`.
+    async fn respond(&self, _request: &Request) -> Result<Option<Response>> {
+        Ok(None)
+    }
+
+    /// Whether to send the request again, after every [`Self::on_response`]
+    /// hook saw `response`
+    ///
+    /// Requests with streamed bodies are never replayed.
+    async fn replay(&self, _exchange: &Exchange, _response: &Response) -> Result<bool> {
+        Ok(false)
+    }
+}
+
+/// The request a response belongs to, as it was sent
+#[derive(Debug, Clone)]
+pub struct Exchange {
+    pub method: Method,
+    /// URL after every middleware ran
+    pub url: Url,
+    /// Request headers as sent
+    pub headers: HeaderMap,
+    /// Size of a buffered request body; `None` for streamed or no bodies
+    pub body_size: Option<u64>,
+    /// Zero-based attempt number
+    pub attempt: u32,
+    started: Instant,
+}
+
+impl Exchange {
+    fn new(request: &Request, attempt: u32) -> Self {
+        Self {
+            method: request.method().clone(),
+            url: request.url().clone(),
+            headers: request.headers().clone(),
+            body_size: request
+                .body()
+                .and_then(|body| body.as_bytes())
+                .map(|bytes| bytes.len() as u64),
+            attempt,
+            started: Instant::now(),
+        }
+    }
+
+    /// Time since the request was sent; in the response hooks, the time to
+    /// the response headers
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+/// Failure raised by a middleware hook
+#[derive(Debug, thiserror::Error)]
+#[error("Fetch middleware {middleware} failed: {source}")]
+pub struct MiddlewareError {
+    pub middleware: String,
+    #[source]
+    pub source: anyhow::Error,
+}
+
+/// Ordered set of middlewares applied by a client
+#[derive(Clone, Default)]
+pub struct MiddlewareChain {
+    middlewares: Vec<Arc<dyn FetchMiddleware>>,
+}
+
+impl fmt::Debug for MiddlewareChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.middlewares.iter().map(|m| m.name()))
+            .finish()
+    }
+}
+
+impl MiddlewareChain {
+    /// Append a middleware; it runs after those already registered
+    pub fn push(&mut self, middleware: Arc<dyn FetchMiddleware>) {
+        self.middlewares.push(middleware);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.middlewares.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.middlewares.len()
+    }
+
+    /// Run the request hooks, returning the exchange to pass to [`Self::after_response`]
+    pub async fn before_request(
+        &self,
+        request: &mut Request,
+        attempt: u32,
+    ) -> Result<Exchange, MiddlewareError> {
+        for middleware in &self.middlewares {
+            middleware
+                .on_request(request)
+                .await
+                .map_err(failed(middleware))?;
+        }
+        Ok(Exchange::new(request, attempt))
+    }
+
+    /// Run the response hooks in reverse registration order
+    pub async fn after_response(
+        &self,
+        exchange: &Exchange,
+        response: &mut Response,
+    ) -> Result<(), MiddlewareError> {
+        Self::response_hooks(&self.middlewares, exchange, response).await
+    }
+
+    /// Run the error hooks in reverse registration order
+    pub async fn after_error(&self, exchange: &Exchange, error: &anyhow::Error) {
+        for middleware in self.middlewares.iter().rev() {
+            middleware.on_error(exchange, error).await;
+        }
+    }
+
+    /// Send `request` through the chain, with `send` putting it on the wire
+    ///
+    /// Runs the request hooks, lets a middleware answer the request
+    /// instead of `send`, runs the response hooks of the middlewares the
+    /// request went through (or their error hooks when `send` fails), and
+    /// replays the request when one of them asks.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of `send`, or a [`MiddlewareError`] when a hook
+    /// fails.
+    pub async fn send<F, Fut>(&self, request: Request, attempt: u32, send: F) -> Result<Response>
+    where
+        F: Fn(Request) -> Fut,
+        Fut: Future<Output = Result<Response>>,
+    {
+        let mut request = request;
+        let mut replays = 0;
+        loop {
+            let original = if replays < MAX_REPLAYS {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            // Middlewares the request passed through, and the response if
+            // the last of them answered it
+            let mut passed = self.middlewares.len();
+            let mut answer = None;
+            for (index, middleware) in self.middlewares.iter().enumerate() {
+                middleware
+                    .on_request(&mut request)
+                    .await
+                    .map_err(failed(middleware))?;
+                if let Some(response) = middleware
+                    .respond(&request)
+                    .await
+                    .map_err(failed(middleware))?
+                {
+                    debug!(
+                        middleware = middleware.name(),
+                        url = %request.url(),
+                        "Middleware answered request"
+                    );
+                    passed = index + 1;
+                    answer = Some(response);
+                    break;
+                }
+            }
+
+            let exchange = Exchange::new(&request, attempt);
+            let mut response = match answer {
+                Some(response) => response,
+                None => match send(request).await {
+                    Ok(response) => response,
+                    Err(error) => {
+                        self.after_error(&exchange, &error).await;
+                        return Err(error);
+                    }
+                },
+            };
+            let passed = &self.middlewares[..passed];
+            Self::response_hooks(passed, &exchange, &mut response).await?;
+
+            let Some(original) = original else {
+                return Ok(response);
+            };
+            let mut replay = None;
+            for middleware in passed {
+                if middleware
+                    .replay(&exchange, &response)
+                    .await
+                    .map_err(failed(middleware))?
+                {
+                    replay = Some(middleware.name());
+                    break;
+                }
+            }
+            match replay {
+                Some(name) => {
+                    debug!(
+                        middleware = name,
+                        url = %exchange.url,
+                        status = %response.status(),
+                        "Replaying request"
+                    );
+                    request = original;
+                    replays += 1;
+                }
+                None => return Ok(response),
+            }
+        }
+    }
+
+    async fn response_hooks(
+        middlewares: &[Arc<dyn FetchMiddleware>],
+        exchange: &Exchange,
+        response: &mut Response,
+    ) -> Result<(), MiddlewareError> {
+        for middleware in middlewares.iter().rev() {
+            middleware
+                .on_response(exchange, response)
+                .await
+                .map_err(failed(middleware))?;
+        }
+        Ok(())
+    }
+}
+
+fn failed(
+    middleware: &Arc<dyn FetchMiddleware>,
+) -> impl FnOnce(anyhow::Error) -> MiddlewareError + '_ {
+    move |source| MiddlewareError {
+        middleware: middleware.name().to_string(),
+        source,
+    }
+}
+
+/// Middleware adding fixed headers to every request
+///
+/// Headers the request already carries are left alone.
+#[derive(Debug, Clone)]
+pub struct StaticHeaders {
+    headers: reqwest::header::HeaderMap,
+}
+
+impl StaticHeaders {
+    pub fn new<I, K, V>(headers: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut map = reqwest::header::HeaderMap::new();
+        for (name, value) in headers {
+            map.insert(
+                reqwest::header::HeaderName::from_bytes(name.as_ref().as_bytes())?,
+                reqwest::header::HeaderValue::from_str(value.as_ref())?,
+            );
+        }
+        Ok(Self { headers: map })
+    }
+}
+
+#[async_trait]
+impl FetchMiddleware for StaticHeaders {
+    fn name(&self) -> &str {
+        "StaticHeaders"
+    }
+
+    async fn on_request(&self, request: &mut Request) -> Result<()> {
+        for (name, value) in &self.headers {
+            if !request.headers().contains_key(name) {
+                request.headers_mut().insert(name.clone(), value.clone());
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,485 @@
+//! Resumable downloads over HTTP range requests
+//!
+//! [`download`] reads a body through [`BodyStream`] and, when the connection
+//! drops part-way, asks for the rest with `Range: bytes=N-` instead of
+//! starting over. A body is only resumed when that is safe:
+//!
+//! - the server advertised `Accept-Ranges: bytes` and a `Content-Length`
+//! - the body had no content coding (a decoded body has no byte offsets that
+//!   match the wire)
+//! - there is a strong `ETag` or a `Last-Modified` to send as `If-Range`, so
+//!   a server whose resource changed in between sends the new body in full
+//!
+//! The assembled body is checked against the declared length, against a
+//! SHA-256 `Repr-Digest`/`Content-Digest`/`Digest` header when the server
+//! sends one, and against [`ResumeConfig::expected_sha256`] when given.
+
+use crate::body_stream::{BodyLimits, BodyStream, BodyStreamError};
+use crate::fetch::ReliableHttpClient;
+use base64::Engine;
+use reqwest::header::{HeaderMap, ACCEPT_RANGES, CONTENT_RANGE, ETAG, LAST_MODIFIED};
+use reqwest::{Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tracing::{debug, warn};
+
+/// Resumption settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResumeConfig {
+    /// Range requests made after dropped connections before giving up
+    pub max_resumes: u32,
+    /// Hex SHA-256 the assembled body must match
+    pub expected_sha256: Option<String>,
+}
+
+impl Default for ResumeConfig {
+    fn default() -> Self {
+        Self {
+            max_resumes: 3,
+            expected_sha256: None,
+        }
+    }
+}
+
+/// A body read to the end, possibly over several connections
+#[derive(Debug, Clone)]
+pub struct ResumableDownload {
+    pub status: StatusCode,
+    /// Headers of the response the body started with
+    pub headers: HeaderMap,
+    /// Content type after sniffing
+    pub content_type: String,
+    pub body: Vec<u8>,
+    /// Hex SHA-256 of `body`
+    pub sha256: String,
+    /// Range requests it took to complete the body
+    pub resumes: u32,
+    /// Whether `sha256` was checked against a server digest or an expected value
+    pub checksum_verified: bool,
+}
+
+/// Why a resumable download failed
+#[derive(Debug, Error)]
+pub enum ResumeError {
+    #[error(transparent)]
+    Body(#[from] BodyStreamError),
+
+    #[error("Range request failed: {0}")]
+    Request(anyhow::Error),
+
+    #[error("Unexpected range response: {0}")]
+    InvalidRange(String),
+
+    #[error("Body has {received} bytes, expected {expected}")]
+    LengthMismatch { expected: u64, received: u64 },
+
+    #[error("Body SHA-256 {actual} does not match {expected}")]
+    ChecksumMismatch { expected: String, actual: String },
+}
+
+/// What a range request has to match to continue a body
+#[derive(Debug, Clone)]
+struct Resumption {
+    validator: String,
+    total: u64,
+}
+
+impl Resumption {
+    /// Resumption data of a full response, `None` when it cannot be resumed
+    fn from_response(response: &Response) -> Option<Self> {
+        let headers = response.headers();
+        let ranges = headers.get(ACCEPT_RANGES)?.to_str().ok()?;
+        if !ranges.eq_ignore_ascii_case("bytes") {
+            return None;
+        }
+        // decode_response drops Content-Length, so a coded body has none
+        let total = response.content_length()?;
+        let etag = headers
+            .get(ETAG)
+            .and_then(|v| v.to_str().ok())
+            .filter(|tag| !tag.starts_with("W/"));
+        let validator = etag.or_else(|| headers.get(LAST_MODIFIED)?.to_str().ok())?;
+        Some(Self {
+            validator: validator.to_string(),
+            total,
+        })
+    }
+}
+
+/// Parse `Content-Range: bytes start-end/total`
+fn parse_content_range(value: &str) -> Option<(u64, u64, u64)> {
+    let (range, total) = value.trim().strip_prefix("bytes ")?.split_once('/')?;
+    let (start, end) = range.split_once('-')?;
+    Some((start.parse().ok()?, end.parse().ok()?, total.parse().ok()?))
+}
+
+/// SHA-256 the server declared for the body, from `Repr-Digest`,
+/// `Content-Digest` (RFC 9530) or `Digest` (RFC 3230)
+pub fn declared_sha256(headers: &HeaderMap) -> Option<Vec<u8>> {
+    ["repr-digest", "content-digest", "digest"]
+        .iter()
+        .filter_map(|name| headers.get(*name)?.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|entry| {
+            let (algorithm, value) = entry.trim().split_once('=')?;
+            if !algorithm.eq_ignore_ascii_case("sha-256") {
+                return None;
+            }
+            base64::engine::general_purpose::STANDARD
+                .decode(value.trim().trim_matches(':'))
+                .ok()
+        })
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Download `url` to the end, resuming with range requests after dropped connections
+pub async fn download(
+    client: &ReliableHttpClient,
+    url: &str,
+    limits: BodyLimits,
+    config: &ResumeConfig,
+) -> Result<ResumableDownload, ResumeError> {
+    let response = client
+        .get_with_retry(url)
+        .await
+        .map_err(ResumeError::Request)?;
+    let mut resumption = Resumption::from_response(&response);
+    let mut stream = BodyStream::new(response, limits.clone()).await?;
+    let status = stream.status();
+    let mut headers = stream.headers().clone();
+    let content_type = stream.content_type().to_string();
+
+    let mut body = Vec::new();
+    let mut resumes = 0;
+    loop {
+        match stream.next_chunk().await {
+            Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+            Ok(None) => break,
+            Err(BodyStreamError::Transport(e)) => {
+                let Some(resume) = resumption.as_ref().filter(|_| resumes < config.max_resumes)
+                else {
+                    return Err(BodyStreamError::Transport(e).into());
+                };
+                resumes += 1;
+                warn!(
+                    url = %url,
+                    received = body.len(),
+                    total = resume.total,
+                    error = %e,
+                    "Body transfer interrupted, resuming with a range request"
+                );
+                let offset = body.len() as u64;
+                let response = client
+                    .get_with_headers(
+                        url,
+                        &[
+                            ("range", format!("bytes={}-", offset)),
+                            ("if-range", resume.validator.clone()),
+                            ("accept-encoding", "identity".to_string()),
+                        ],
+                    )
+                    .await
+                    .map_err(ResumeError::Request)?;
+
+                if response.status() == StatusCode::PARTIAL_CONTENT {
+                    check_continuation(&response, offset, resume.total)?;
+                    let remaining = BodyLimits {
+                        max_content_length: limits.max_content_length.saturating_sub(offset),
+                        sniff_bytes: 0,
+                        allowed_content_types: Vec::new(),
+                    };
+                    stream = BodyStream::new(response, remaining).await?;
+                } else {
+                    // If-Range did not match: the resource changed, start over
+                    debug!(url = %url, status = %response.status(), "Range not honoured, restarting body");
+                    resumption = Resumption::from_response(&response);
+                    stream = BodyStream::new(response, limits.clone()).await?;
+                    headers = stream.headers().clone();
+                    body.clear();
+                }
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    if let Some(resume) = &resumption {
+        if body.len() as u64 != resume.total {
+            return Err(ResumeError::LengthMismatch {
+                expected: resume.total,
+                received: body.len() as u64,
+            });
+        }
+    }
+
+    let digest = Sha256::digest(&body);
+    let sha256 = hex(&digest);
+    let mut checksum_verified = false;
+    if let Some(declared) = declared_sha256(&headers) {
+        if declared != digest.as_slice() {
+            return Err(ResumeError::ChecksumMismatch {
+                expected: hex(&declared),
+                actual: sha256,
+            });
+        }
+        checksum_verified = true;
+    }
+    if let Some(expected) = &config.expected_sha256 {
+        if !expected.eq_ignore_ascii_case(&sha256) {
+            return Err(ResumeError::ChecksumMismatch {
+                expected: expected.to_lowercase(),
+                actual: sha256,
+            });
+        }
+        checksum_verified = true;
+    }
+
+    Ok(ResumableDownload {
+        status,
+        headers,
+        content_type,
+        body,
+        sha256,
+        resumes,
+        checksum_verified,
+    })
+}
+
+/// A `206` must continue exactly where the body stopped, uncoded
+fn check_continuation(response: &Response, offset: u64, total: u64) -> Result<(), ResumeError> {
+    let content_range = response
+        .headers()
+        .get(CONTENT_RANGE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    match parse_content_range(content_range) {
+        Some((start, end, full)) if start == offset && full == total && end + 1 == total => {}
+        _ => {
+            return Err(ResumeError::InvalidRange(format!(
+                "expected bytes {}-{}/{}, got '{}'",
+                offset,
+                total.saturating_sub(1),
+                total,
+                content_range
+            )))
+        }
+    }
+    if response.content_length() != Some(total - offset) {
+        return Err(ResumeError::InvalidRange(
+            "range response is content-coded or has no length".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::{CircuitBreakerConfig, RetryConfig};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn client() -> ReliableHttpClient {
+        ReliableHttpClient::new(
+            RetryConfig {
+                max_attempts: 1,
+                ..Default::default()
+            },
+            CircuitBreakerConfig::default(),
+        )
+        .unwrap()
+    }
+
+    fn sha256_b64(body: &[u8]) -> String {
+        base64::engine::general_purpose::STANDARD.encode(Sha256::digest(body))
+    }
+
+    /// Serve `body`, cutting every connection off after `cut_after` body bytes
+    ///
+    /// Range requests are answered with `206` from the requested offset. Each
+    /// entry of the returned log is the `Range` header of one request.
+    async fn flaky_server(
+        body: Vec<u8>,
+        cut_after: usize,
+        extra_headers: String,
+    ) -> (String, Arc<std::sync::Mutex<Vec<Option<String>>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let requests = log.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    return;
+                };
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let n = socket.read(&mut buf).await.unwrap_or(0);
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let request = String::from_utf8_lossy(&request).to_lowercase();
+                let range = request
+                    .lines()
+                    .find_map(|l| l.strip_prefix("range: bytes="))
+                    .map(|r| r.trim_end_matches('-').to_string());
+                requests.lock().unwrap().push(range.clone());
+
+                let offset: usize = range.as_deref().map_or(0, |r| r.parse().unwrap());
+                let rest = &body[offset..];
+                let head = if range.is_some() {
+                    format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes {}-{}/{}\r\n",
+                        offset,
+                        body.len() - 1,
+                        body.len()
+                    )
+                } else {
+                    format!("HTTP/1.1 200 OK\r\n{}", extra_headers)
+                };
+                let head = format!(
+                    "{}Content-Type: text/html\r\nAccept-Ranges: bytes\r\nETag: \"v1\"\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    head,
+                    rest.len()
+                );
+                let _ = socket.write_all(head.as_bytes()).await;
+                let _ = socket.write_all(&rest[..rest.len().min(cut_after)]).await;
+                let _ = socket.shutdown().await;
+            }
+        });
+        (format!("http://{}/file", addr), log)
+    }
+
+    fn page(len: usize) -> Vec<u8> {
+        let mut body = b"<html><body>".to_vec();
+        body.extend((0..len).map(|i| b'a' + (i % 26) as u8));
+        body
+    }
+
+    #[test]
+    fn test_parse_content_range_and_digest() {
+        assert_eq!(
+            parse_content_range("bytes 100-199/200"),
+            Some((100, 199, 200))
+        );
+        assert_eq!(parse_content_range("bytes */200"), None);
+
+        let body = b"hello";
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "repr-digest",
+            format!("sha-512=:AAAA:, sha-256=:{}:", sha256_b64(body))
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(
+            declared_sha256(&headers).unwrap(),
+            Sha256::digest(body).as_slice()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resumes_interrupted_body() {
+        let body = page(200_000);
+        let digest = format!("Repr-Digest: sha-256=:{}:\r\n", sha256_b64(&body));
+        let (url, log) = flaky_server(body.clone(), 70_000, digest).await;
+
+        let download = download(
+            &client(),
+            &url,
+            BodyLimits::default(),
+            &ResumeConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(download.body, body);
+        assert_eq!(download.resumes, 2);
+        assert!(download.checksum_verified);
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![None, Some("70000".to_string()), Some("140000".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_resumes() {
+        let (url, _log) = flaky_server(page(200_000), 50_000, String::new()).await;
+        let config = ResumeConfig {
+            max_resumes: 1,
+            ..Default::default()
+        };
+
+        let err = download(&client(), &url, BodyLimits::default(), &config)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ResumeError::Body(BodyStreamError::Transport(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_checksum_mismatch() {
+        let body = page(10_000);
+        let (url, _log) = flaky_server(body.clone(), usize::MAX, String::new()).await;
+
+        let config = ResumeConfig {
+            expected_sha256: Some(hex(&Sha256::digest(b"something else"))),
+            ..Default::default()
+        };
+        let err = download(&client(), &url, BodyLimits::default(), &config)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ResumeError::ChecksumMismatch { .. }));
+
+        let config = ResumeConfig {
+            expected_sha256: Some(hex(&Sha256::digest(&body)).to_uppercase()),
+            ..Default::default()
+        };
+        let download = download(&client(), &url, BodyLimits::default(), &config)
+            .await
+            .unwrap();
+        assert_eq!(download.resumes, 0);
+        assert!(download.checksum_verified);
+    }
+
+    #[tokio::test]
+    async fn test_unresumable_body_fails_on_interruption() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                // No Accept-Ranges: the body cannot be resumed
+                let head = "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: 100000\r\nConnection: close\r\n\r\n";
+                let _ = socket.write_all(head.as_bytes()).await;
+                let _ = socket.write_all(&page(20_000)).await;
+            }
+        });
+
+        let err = download(
+            &client(),
+            &format!("http://{}/file", addr),
+            BodyLimits::default(),
+            &ResumeConfig::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, ResumeError::Body(_)));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+}
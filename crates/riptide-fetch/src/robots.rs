@@ -1,8 +1,8 @@
+use crate::robots_rules::RobotsGroup;
+use crate::robots_service::{RobotsFile, RobotsService, RobotsServiceConfig, RobotsStatus};
 use anyhow::{Context, Result};
 use dashmap::DashMap;
 use rand::Rng;
-use reqwest::Client;
-use robotstxt::DefaultMatcher;
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -34,6 +34,30 @@ pub struct RobotsConfig {
     pub development_mode: bool,
     /// Timeout for fetching robots.txt files
     pub fetch_timeout: Duration,
+    /// TTL in seconds for a robots.txt that could not be fetched (5xx, 429,
+    /// network error); doubles with each consecutive failure
+    #[serde(default = "default_error_cache_ttl")]
+    pub error_cache_ttl: u64,
+    /// Upper bound in seconds on the retry backoff for unreachable robots.txt
+    #[serde(default = "default_max_error_backoff")]
+    pub max_error_backoff: u64,
+    /// Disallow the whole host while its robots.txt is unreachable and no
+    /// earlier copy is cached (RFC 9309); when false the host is crawled as
+    /// if it had no robots.txt
+    #[serde(default = "default_disallow_on_error")]
+    pub disallow_on_error: bool,
+}
+
+fn default_error_cache_ttl() -> u64 {
+    60
+}
+
+fn default_max_error_backoff() -> u64 {
+    3600
+}
+
+fn default_disallow_on_error() -> bool {
+    true
 }
 
 impl Default for RobotsConfig {
@@ -49,6 +73,9 @@ impl Default for RobotsConfig {
             jitter_factor: 0.2, // ±20%
             development_mode: false,
             fetch_timeout: Duration::from_secs(10),
+            error_cache_ttl: default_error_cache_ttl(),
+            max_error_backoff: default_max_error_backoff(),
+            disallow_on_error: default_disallow_on_error(),
         }
     }
 }
@@ -56,10 +83,14 @@ impl Default for RobotsConfig {
 /// Cached robots.txt entry with TTL
 #[derive(Debug, Clone)]
 struct CachedRobots {
-    robots_content: String,
+    /// Rules of the group selected for our user agent
+    group: RobotsGroup,
+    /// Crawl delay of our group, clamped to the configured maximum
     crawl_delay: Option<f64>,
     cached_at: Instant,
     ttl: Duration,
+    /// Consecutive failed fetches, driving the retry backoff
+    failures: u32,
 }
 
 impl CachedRobots {
@@ -126,29 +157,43 @@ impl TokenBucket {
 }
 
 /// Per-host rate limiting and robots.txt management
+///
+/// robots.txt files come from a [`RobotsService`], which managers of
+/// different components can share so each file is fetched once.
 #[derive(Debug)]
 pub struct RobotsManager {
     config: RobotsConfig,
     robots_cache: DashMap<String, CachedRobots>,
     rate_limiters: DashMap<String, Arc<TokenBucket>>,
-    http_client: Client,
+    service: Arc<RobotsService>,
 }
 
 impl RobotsManager {
+    /// Manager with its own robots service built from `config`
     pub fn new(config: RobotsConfig) -> Result<Self> {
-        let http_client = Client::builder()
-            .user_agent(&config.user_agent)
-            .timeout(config.fetch_timeout)
-            .gzip(true)
-            .build()
-            .context("Failed to create HTTP client for robots manager")?;
-
-        Ok(Self {
+        let service = RobotsService::new(RobotsServiceConfig {
+            user_agent: config.user_agent.clone(),
+            fetch_timeout: config.fetch_timeout,
+            default_ttl: Duration::from_secs(config.cache_ttl),
+            error_ttl: Duration::from_secs(config.error_cache_ttl),
+            ..Default::default()
+        })?;
+        Ok(Self::with_service(config, Arc::new(service)))
+    }
+
+    /// Manager reading robots.txt through a shared `service`
+    pub fn with_service(config: RobotsConfig, service: Arc<RobotsService>) -> Self {
+        Self {
             config,
             robots_cache: DashMap::new(),
             rate_limiters: DashMap::new(),
-            http_client,
-        })
+            service,
+        }
+    }
+
+    /// Robots service robots.txt files are read through
+    pub fn service(&self) -> &Arc<RobotsService> {
+        &self.service
     }
 
     /// Check if a URL is allowed to be crawled according to robots.txt
@@ -166,22 +211,17 @@ impl RobotsManager {
         }
 
         let parsed_url = Url::parse(url).context("Failed to parse URL")?;
-        let host = parsed_url
-            .host_str()
-            .context("URL has no host")?
-            .to_string();
 
         // Get or fetch robots.txt for this host
-        let robots = self.get_robots_for_host(&host).await?;
-
-        // Check if the path is allowed using DefaultMatcher
-        let path = parsed_url.path();
-        let mut matcher = DefaultMatcher::default();
-        let allowed = matcher.one_agent_allowed_by_robots(
-            &robots.robots_content,
-            &self.config.user_agent,
-            url,
-        );
+        let robots = self.get_robots(&parsed_url).await?;
+
+        // Rules match against the path including the query string
+        let mut path = parsed_url.path().to_string();
+        if let Some(query) = parsed_url.query() {
+            path.push('?');
+            path.push_str(query);
+        }
+        let allowed = robots.group.is_allowed(&path);
 
         debug!(url = %url, path = %path, allowed = allowed, "Robots.txt check completed");
         Ok(allowed)
@@ -201,7 +241,7 @@ impl RobotsManager {
         // Try to consume a token
         while !rate_limiter.try_consume(1).await {
             // Calculate delay based on current rate
-            let delay = self.calculate_delay(&host).await;
+            let delay = self.calculate_delay(&parsed_url).await;
             debug!(host = %host, delay_ms = delay.as_millis(), "Rate limiting: waiting");
             tokio::time::sleep(delay).await;
         }
@@ -209,45 +249,37 @@ impl RobotsManager {
         Ok(())
     }
 
-    /// Get robots.txt for a host (cached or fetch fresh)
-    async fn get_robots_for_host(&self, host: &str) -> Result<CachedRobots> {
+    /// Get robots.txt for the origin of a URL (cached or fetch fresh)
+    ///
+    /// robots.txt applies per scheme, host and port, so entries are cached
+    /// by origin; crawl delays still throttle the host as a whole.
+    async fn get_robots(&self, url: &Url) -> Result<CachedRobots> {
+        let host = url.host_str().context("URL has no host")?;
+        let origin = RobotsService::origin(url)?;
+
         // Check cache first
-        if let Some(cached) = self.robots_cache.get(host) {
+        let previous = self.robots_cache.get(&origin).map(|cached| cached.clone());
+        if let Some(cached) = &previous {
             if !cached.is_expired() {
-                debug!(host = %host, "Using cached robots.txt");
+                debug!(origin = %origin, "Using cached robots.txt");
                 return Ok(cached.clone());
             }
-            debug!(host = %host, "Cached robots.txt expired");
+            debug!(origin = %origin, "Cached robots.txt expired");
         }
 
-        // Fetch fresh robots.txt
-        let robots_url = format!("https://{}/robots.txt", host);
-        debug!(host = %host, robots_url = %robots_url, "Fetching robots.txt");
-
-        let robots_content = match self.fetch_robots_txt(&robots_url).await {
-            Ok(content) => content,
-            Err(e) => {
-                warn!(host = %host, error = %e, "Failed to fetch robots.txt, allowing all");
-                // If we can't fetch robots.txt, be permissive but conservative
-                String::new()
+        // The service fetches the file unless it holds a fresh copy
+        let file = self.service.get(url).await?;
+        let cached_robots = match file.status {
+            RobotsStatus::Found | RobotsStatus::Missing => self.file_entry(&file),
+            RobotsStatus::Unavailable => {
+                let reason = file.error.as_deref().unwrap_or("unavailable");
+                self.unavailable_entry(&origin, reason, previous)
             }
         };
-
-        // Store robots.txt content for later parsing
-
-        // Extract crawl delay for our user agent
-        let crawl_delay = self.extract_crawl_delay(&robots_content);
-
-        let cached_robots = CachedRobots {
-            robots_content,
-            crawl_delay,
-            cached_at: Instant::now(),
-            ttl: Duration::from_secs(self.config.cache_ttl),
-        };
+        let crawl_delay = cached_robots.crawl_delay;
 
         // Update cache
-        self.robots_cache
-            .insert(host.to_string(), cached_robots.clone());
+        self.robots_cache.insert(origin, cached_robots.clone());
 
         // Update rate limiter if we have a new crawl delay
         if let Some(delay) = crawl_delay {
@@ -258,49 +290,82 @@ impl RobotsManager {
         Ok(cached_robots)
     }
 
-    /// Fetch robots.txt content from URL
-    async fn fetch_robots_txt(&self, robots_url: &str) -> Result<String> {
-        let response = self
-            .http_client
-            .get(robots_url)
-            .send()
-            .await
-            .context("Failed to fetch robots.txt")?;
-
-        if response.status().is_success() {
-            let content = response
-                .text()
-                .await
-                .context("Failed to read robots.txt content")?;
-            Ok(content)
-        } else {
-            // If robots.txt doesn't exist or returns error, return empty (permissive)
-            debug!(robots_url = %robots_url, status = %response.status(), "robots.txt not found or error");
-            Ok(String::new())
-        }
+    /// Cache entry for a fetched (or missing) robots.txt, fresh for as long
+    /// as the service keeps the file
+    fn file_entry(&self, file: &RobotsFile) -> CachedRobots {
+        let rules = &file.rules;
+        let mut entry = self.cache_entry(rules.group_for(&self.config.user_agent));
+        entry.ttl = file.ttl();
+        entry
     }
 
-    /// Extract crawl delay from robots.txt content
-    fn extract_crawl_delay(&self, robots_content: &str) -> Option<f64> {
-        // Simple parsing for crawl-delay directive
-        for line in robots_content.lines() {
-            let line = line.trim().to_lowercase();
-            if line.starts_with("crawl-delay:") {
-                if let Some(delay_str) = line.split(':').nth(1) {
-                    if let Ok(delay) = delay_str.trim().parse::<f64>() {
-                        // Clamp to reasonable bounds
-                        let clamped_delay = delay.max(0.1_f64).min(self.config.max_crawl_delay);
-                        debug!(
-                            original_delay = delay,
-                            clamped_delay = clamped_delay,
-                            "Parsed crawl delay"
-                        );
-                        return Some(clamped_delay);
-                    }
-                }
-            }
+    /// Cache entry for a successfully fetched (or missing) robots.txt
+    fn cache_entry(&self, group: RobotsGroup) -> CachedRobots {
+        CachedRobots {
+            crawl_delay: self.extract_crawl_delay(&group),
+            group,
+            cached_at: Instant::now(),
+            ttl: Duration::from_secs(self.config.cache_ttl),
+            failures: 0,
         }
-        None
+    }
+
+    /// Cache entry for a robots.txt that could not be fetched
+    ///
+    /// The last known rules stay in force; without any, the host is
+    /// disallowed or allowed according to `disallow_on_error`. The entry
+    /// expires with exponential backoff so the file is retried.
+    fn unavailable_entry(
+        &self,
+        origin: &str,
+        reason: &str,
+        previous: Option<CachedRobots>,
+    ) -> CachedRobots {
+        let failures = previous.as_ref().map_or(0, |p| p.failures) + 1;
+        let ttl = self.error_backoff(failures);
+        warn!(
+            origin = %origin,
+            reason = %reason,
+            failures,
+            retry_in_secs = ttl.as_secs(),
+            "robots.txt unavailable"
+        );
+
+        let mut entry = previous.unwrap_or_else(|| {
+            let group = if self.config.disallow_on_error {
+                RobotsGroup::disallow_all()
+            } else {
+                RobotsGroup::default()
+            };
+            self.cache_entry(group)
+        });
+        entry.cached_at = Instant::now();
+        entry.ttl = ttl;
+        entry.failures = failures;
+        entry
+    }
+
+    /// Retry backoff after `failures` consecutive failed fetches
+    fn error_backoff(&self, failures: u32) -> Duration {
+        let factor = 1u64 << failures.saturating_sub(1).min(16);
+        let secs = self
+            .config
+            .error_cache_ttl
+            .saturating_mul(factor)
+            .min(self.config.max_error_backoff);
+        Duration::from_secs(secs)
+    }
+
+    /// Crawl delay of a group, clamped to reasonable bounds
+    fn extract_crawl_delay(&self, group: &RobotsGroup) -> Option<f64> {
+        let delay = group.crawl_delay()?;
+        let clamped_delay = delay.max(0.1_f64).min(self.config.max_crawl_delay);
+        debug!(
+            original_delay = delay,
+            clamped_delay = clamped_delay,
+            "Parsed crawl delay"
+        );
+        Some(clamped_delay)
     }
 
     /// Get or create rate limiter for host
@@ -331,13 +396,9 @@ impl RobotsManager {
     }
 
     /// Calculate delay with jitter
-    async fn calculate_delay(&self, host: &str) -> Duration {
-        let base_delay = if let Some(cached) = self.robots_cache.get(host) {
-            if let Some(crawl_delay) = cached.crawl_delay {
-                Duration::from_secs_f64(crawl_delay)
-            } else {
-                Duration::from_secs_f64(1.0 / self.config.default_rps)
-            }
+    async fn calculate_delay(&self, url: &Url) -> Duration {
+        let base_delay = if let Some(crawl_delay) = self.crawl_delay(url) {
+            Duration::from_secs_f64(crawl_delay)
         } else {
             Duration::from_secs_f64(1.0 / self.config.default_rps)
         };
@@ -364,6 +425,14 @@ impl RobotsManager {
         Ok(true)
     }
 
+    /// Crawl delay from the cached robots.txt of the origin of `url`, in seconds
+    pub fn crawl_delay(&self, url: &Url) -> Option<f64> {
+        let origin = RobotsService::origin(url).ok()?;
+        self.robots_cache
+            .get(&origin)
+            .and_then(|cached| cached.crawl_delay)
+    }
+
     /// Get current configuration
     pub fn get_config(&self) -> &RobotsConfig {
         &self.config
@@ -390,6 +459,7 @@ impl RobotsManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::robots_rules::RobotsRules;
     use tokio::time::{sleep, Duration};
 
     #[test]
@@ -404,10 +474,11 @@ mod tests {
     #[test]
     fn test_cached_robots_expiry() {
         let cached = CachedRobots {
-            robots_content: String::new(),
+            group: RobotsGroup::default(),
             crawl_delay: None,
             cached_at: Instant::now() - Duration::from_secs(3700), // Past TTL
             ttl: Duration::from_secs(3600),
+            failures: 0,
         };
         assert!(cached.is_expired());
     }
@@ -454,7 +525,8 @@ Crawl-delay: 2.5
 Disallow: /admin
 "#;
 
-        let delay = manager.extract_crawl_delay(robots_content);
+        let group = RobotsRules::parse(robots_content).group_for("RipTide/1.0");
+        let delay = manager.extract_crawl_delay(&group);
         assert_eq!(delay, Some(2.5));
     }
 
@@ -466,11 +538,87 @@ Disallow: /admin
         };
         let manager = RobotsManager::new(config).expect("Failed to create manager for test");
 
-        let robots_content = "Crawl-delay: 100"; // Very high delay
-        let delay = manager.extract_crawl_delay(robots_content);
+        let robots_content = "User-agent: *\nCrawl-delay: 100"; // Very high delay
+        let group = RobotsRules::parse(robots_content).group_for("RipTide/1.0");
+        let delay = manager.extract_crawl_delay(&group);
         assert_eq!(delay, Some(5.0)); // Should be clamped to max
     }
 
+    #[test]
+    fn test_error_backoff_doubles_up_to_cap() {
+        let config = RobotsConfig {
+            error_cache_ttl: 60,
+            max_error_backoff: 300,
+            ..Default::default()
+        };
+        let manager = RobotsManager::new(config).expect("Failed to create manager for test");
+
+        assert_eq!(manager.error_backoff(1), Duration::from_secs(60));
+        assert_eq!(manager.error_backoff(2), Duration::from_secs(120));
+        assert_eq!(manager.error_backoff(3), Duration::from_secs(240));
+        assert_eq!(manager.error_backoff(4), Duration::from_secs(300));
+        assert_eq!(manager.error_backoff(100), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn test_unavailable_robots_keeps_last_known_rules() {
+        let manager =
+            RobotsManager::new(RobotsConfig::default()).expect("Failed to create manager for test");
+
+        // Nothing cached: the host is disallowed until robots.txt can be read
+        let first = manager.unavailable_entry("https://example.com", "503", None);
+        assert!(!first.group.is_allowed("/page"));
+        assert_eq!(first.failures, 1);
+
+        let rules = RobotsRules::parse("User-agent: *\nDisallow: /admin");
+        let known = manager.cache_entry(rules.group_for(&manager.get_config().user_agent));
+        let retried = manager.unavailable_entry("https://example.com", "503", Some(known));
+        assert!(retried.group.is_allowed("/page"));
+        assert!(!retried.group.is_allowed("/admin"));
+        assert_eq!(retried.ttl, Duration::from_secs(60));
+
+        let lenient = RobotsManager::new(RobotsConfig {
+            disallow_on_error: false,
+            ..Default::default()
+        })
+        .expect("Failed to create manager for test");
+        assert!(lenient
+            .unavailable_entry("https://example.com", "503", None)
+            .group
+            .is_allowed("/page"));
+    }
+
+    #[tokio::test]
+    async fn test_rules_are_cached_per_origin() {
+        use wiremock::matchers::path;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let strict = MockServer::start().await;
+        Mock::given(path("/robots.txt"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("User-agent: *\nCrawl-delay: 3\nDisallow: /\n"),
+            )
+            .mount(&strict)
+            .await;
+        let open = MockServer::start().await;
+        Mock::given(path("/robots.txt"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&open)
+            .await;
+
+        // Same host, different ports
+        let manager =
+            RobotsManager::new(RobotsConfig::default()).expect("Failed to create manager for test");
+        let strict_page = Url::parse(&format!("{}/page", strict.uri())).expect("Valid URL");
+        let open_page = Url::parse(&format!("{}/page", open.uri())).expect("Valid URL");
+
+        assert!(!manager.is_allowed(strict_page.as_str()).await.unwrap());
+        assert!(manager.is_allowed(open_page.as_str()).await.unwrap());
+        assert_eq!(manager.crawl_delay(&strict_page), Some(3.0));
+        assert_eq!(manager.crawl_delay(&open_page), None);
+    }
+
     #[test]
     fn test_url_parsing() {
         let url = "https://example.com/path/to/resource";
@@ -0,0 +1,305 @@
+//! robots.txt parsing and matching
+//!
+//! Implements RFC 9309 with the extensions Google and Yandex crawlers honor:
+//!
+//! - **Group selection**: the group whose `User-agent` is the longest match
+//!   for our product token applies, falling back to `*`; groups naming the
+//!   same agent are merged
+//! - **Wildcards**: `*` matches any sequence of characters and a trailing `$`
+//!   anchors the pattern to the end of the path
+//! - **Precedence**: the longest matching rule wins, `Allow` wins ties
+//! - **Crawl-delay**: per group
+//! - **Host**: the preferred mirror of the site, file-wide
+//! - **Sitemap**: sitemap URLs, file-wide
+
+/// A single `Allow` or `Disallow` line
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    allow: bool,
+    pattern: String,
+}
+
+impl Rule {
+    fn matches(&self, path: &str) -> bool {
+        pattern_matches(&self.pattern, path)
+    }
+}
+
+/// Rules that apply to one crawler
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RobotsGroup {
+    user_agents: Vec<String>,
+    rules: Vec<Rule>,
+    crawl_delay: Option<f64>,
+}
+
+impl RobotsGroup {
+    /// A group that disallows every path
+    pub fn disallow_all() -> Self {
+        Self {
+            rules: vec![Rule {
+                allow: false,
+                pattern: "/".to_string(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    /// Whether `path` (path and query of a URL) may be crawled
+    pub fn is_allowed(&self, path: &str) -> bool {
+        if path == "/robots.txt" {
+            return true;
+        }
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(path))
+            .max_by_key(|rule| (rule.pattern.len(), rule.allow))
+            .is_none_or(|rule| rule.allow)
+    }
+
+    /// `Crawl-delay` of the group, in seconds
+    pub fn crawl_delay(&self) -> Option<f64> {
+        self.crawl_delay
+    }
+
+    fn merge(&mut self, other: &RobotsGroup) {
+        self.rules.extend(other.rules.iter().cloned());
+        self.crawl_delay = self.crawl_delay.or(other.crawl_delay);
+    }
+}
+
+/// Parsed robots.txt file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RobotsRules {
+    groups: Vec<RobotsGroup>,
+    host: Option<String>,
+    sitemaps: Vec<String>,
+}
+
+impl RobotsRules {
+    /// Parse robots.txt content, ignoring lines it does not understand
+    pub fn parse(content: &str) -> Self {
+        let mut rules = Self::default();
+        let mut current: Option<RobotsGroup> = None;
+        // A User-agent line following rules starts a new group
+        let mut in_rules = false;
+
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim();
+
+            match key.trim().to_ascii_lowercase().as_str() {
+                "user-agent" => {
+                    if in_rules {
+                        rules.groups.extend(current.take());
+                        in_rules = false;
+                    }
+                    current
+                        .get_or_insert_with(RobotsGroup::default)
+                        .user_agents
+                        .push(value.to_ascii_lowercase());
+                }
+                directive @ ("allow" | "disallow") => {
+                    in_rules = true;
+                    if let Some(group) = current.as_mut().filter(|_| !value.is_empty()) {
+                        group.rules.push(Rule {
+                            allow: directive == "allow",
+                            pattern: value.to_string(),
+                        });
+                    }
+                }
+                "crawl-delay" => {
+                    in_rules = true;
+                    if let (Some(group), Ok(delay)) = (current.as_mut(), value.parse::<f64>()) {
+                        if delay.is_finite() && delay >= 0.0 {
+                            group.crawl_delay.get_or_insert(delay);
+                        }
+                    }
+                }
+                "host" if rules.host.is_none() && !value.is_empty() => {
+                    rules.host = Some(value.to_ascii_lowercase());
+                }
+                "sitemap" => {
+                    let is_http = value.starts_with("http://") || value.starts_with("https://");
+                    if is_http && !rules.sitemaps.iter().any(|s| s == value) {
+                        rules.sitemaps.push(value.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+        rules.groups.extend(current);
+        rules
+    }
+
+    /// Rules for `user_agent`, merged across every group that names it
+    ///
+    /// Groups are matched on the product token of the user agent
+    /// (`RipTide` for `RipTide/1.0`). With no matching group everything is
+    /// allowed.
+    pub fn group_for(&self, user_agent: &str) -> RobotsGroup {
+        let product = user_agent
+            .split(|c: char| c == '/' || c.is_whitespace())
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        let specificity = |group: &RobotsGroup| {
+            group
+                .user_agents
+                .iter()
+                .filter_map(|agent| match agent.as_str() {
+                    "*" => Some(0),
+                    agent if !agent.is_empty() && product.starts_with(agent) => Some(agent.len()),
+                    _ => None,
+                })
+                .max()
+        };
+
+        let Some(best) = self.groups.iter().filter_map(specificity).max() else {
+            return RobotsGroup::default();
+        };
+        let mut selected = RobotsGroup::default();
+        for group in self
+            .groups
+            .iter()
+            .filter(|group| specificity(group) == Some(best))
+        {
+            selected.merge(group);
+        }
+        selected
+    }
+
+    /// `Host` directive naming the preferred mirror of the site
+    pub fn host(&self) -> Option<&str> {
+        self.host.as_deref()
+    }
+
+    /// Absolute `Sitemap` URLs, in file order
+    pub fn sitemaps(&self) -> &[String] {
+        &self.sitemaps
+    }
+}
+
+/// Match a robots.txt path pattern with `*` wildcards and an optional `$`
+/// end anchor against `path`
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = parts.next().and_then(|first| path.strip_prefix(first)) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return !anchored || rest.is_empty();
+    };
+    // Matching each literal at its leftmost position leaves the longest
+    // possible remainder for the parts after it
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    if anchored {
+        rest.ends_with(last)
+    } else {
+        rest.contains(last)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_wildcards() {
+        assert!(pattern_matches("/fish", "/fish.html"));
+        assert!(pattern_matches("/fish*", "/fishheads/yummy.html"));
+        assert!(pattern_matches("/*.php", "/folder/filename.php?parameters"));
+        assert!(!pattern_matches("/*.php$", "/filename.php?parameters"));
+        assert!(pattern_matches("/*.php$", "/folder/filename.php"));
+        assert!(pattern_matches(
+            "/fish*.php",
+            "/fishheads/catfish.php?parameters"
+        ));
+        assert!(!pattern_matches("/fish*.php", "/Fish.PHP"));
+        assert!(pattern_matches("/a*b*c", "/aXbYbZc"));
+        assert!(pattern_matches("/$", "/"));
+        assert!(!pattern_matches("/$", "/page"));
+        assert!(pattern_matches("*", "/anything"));
+    }
+
+    #[test]
+    fn test_longest_match_wins_and_allow_breaks_ties() {
+        let rules = RobotsRules::parse(
+            "User-agent: *\n\
+             Disallow: /private\n\
+             Allow: /private/public\n\
+             Allow: /page\n\
+             Disallow: /*.htm\n\
+             Allow: /tie\n\
+             Disallow: /tie\n",
+        );
+        let group = rules.group_for("RipTide/1.0");
+        assert!(!group.is_allowed("/private/data"));
+        assert!(group.is_allowed("/private/public/data"));
+        assert!(!group.is_allowed("/page.htm"));
+        assert!(group.is_allowed("/page"));
+        assert!(group.is_allowed("/tie"));
+        assert!(group.is_allowed("/robots.txt"));
+    }
+
+    #[test]
+    fn test_group_selection_by_product_token() {
+        let rules = RobotsRules::parse(
+            "User-agent: *\n\
+             Disallow: /\n\
+             \n\
+             User-agent: riptide\n\
+             User-agent: otherbot\n\
+             Disallow: /admin\n\
+             Crawl-delay: 2\n\
+             \n\
+             User-agent: RipTide\n\
+             Disallow: /tmp\n",
+        );
+
+        let ours = rules.group_for("RipTide/1.0 (+https://example.com/bot)");
+        assert!(ours.is_allowed("/articles"));
+        assert!(!ours.is_allowed("/admin/users"));
+        assert!(!ours.is_allowed("/tmp/x"));
+        assert_eq!(ours.crawl_delay(), Some(2.0));
+
+        let other = rules.group_for("SomeBot/2.0");
+        assert!(!other.is_allowed("/articles"));
+        assert_eq!(other.crawl_delay(), None);
+    }
+
+    #[test]
+    fn test_host_and_unknown_directives() {
+        let rules = RobotsRules::parse(
+            "# comment\n\
+             Disallow: /ignored-outside-group\n\
+             Host: WWW.Example.com\n\
+             Sitemap: https://example.com/sitemap.xml\n\
+             User-agent: *\n\
+             Disallow:\n\
+             Crawl-delay: soon\n",
+        );
+        assert_eq!(rules.host(), Some("www.example.com"));
+        assert_eq!(rules.sitemaps(), ["https://example.com/sitemap.xml"]);
+        let group = rules.group_for("RipTide");
+        assert!(group.is_allowed("/ignored-outside-group"));
+        assert_eq!(group.crawl_delay(), None);
+        assert!(RobotsRules::parse("").group_for("RipTide").is_allowed("/"));
+        assert!(!RobotsGroup::disallow_all().is_allowed("/"));
+    }
+}
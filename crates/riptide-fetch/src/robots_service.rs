@@ -0,0 +1,672 @@
+//! robots.txt fetching and caching shared across components
+//!
+//! A [`RobotsService`] fetches and parses each origin's robots.txt once for
+//! everything that asks: spider crawls, fetch clients, sitemap discovery and
+//! facades built on them. Parsed files are kept in memory and, with a shared
+//! [`CacheStorage`] such as Redis, reused by other instances too.
+//!
+//! Freshness follows the response's `Cache-Control` or `Expires` header,
+//! clamped so a file is never trusted for longer than the 24 hours RFC 9309
+//! allows. Concurrent lookups for one origin wait for a single fetch.
+//! Expired files are evicted whenever a file is fetched, and the memory
+//! cache holds at most `max_entries` origins. Only the first
+//! `max_body_bytes` of a file are read, 500 KiB by default as RFC 9309
+//! permits.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use reqwest::header::{HeaderMap, CACHE_CONTROL, DATE, EXPIRES};
+use reqwest::{Client, StatusCode};
+use riptide_types::conditional::parse_http_date;
+use riptide_types::ports::CacheStorage;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::robots_rules::RobotsRules;
+
+/// Configuration of a [`RobotsService`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RobotsServiceConfig {
+    /// User agent robots.txt is requested with
+    pub user_agent: String,
+    /// Timeout for fetching a robots.txt file
+    pub fetch_timeout: Duration,
+    /// Freshness of a file whose response has no caching headers
+    pub default_ttl: Duration,
+    /// Lower bound on header-derived freshness, so `no-cache` files are not
+    /// refetched for every URL
+    pub min_ttl: Duration,
+    /// Upper bound on freshness
+    pub max_ttl: Duration,
+    /// How long an unreachable robots.txt (5xx, 429, network error) is
+    /// remembered before it is fetched again
+    pub error_ttl: Duration,
+    /// Prefix of the keys files are stored under in the shared cache
+    pub key_prefix: String,
+    /// Origins held in memory at most; the files expiring soonest are
+    /// dropped beyond it
+    pub max_entries: usize,
+    /// Bytes of a robots.txt read at most; rules past it are ignored
+    pub max_body_bytes: usize,
+}
+
+impl Default for RobotsServiceConfig {
+    fn default() -> Self {
+        Self {
+            user_agent: "RipTide/1.0".to_string(),
+            fetch_timeout: Duration::from_secs(10),
+            default_ttl: Duration::from_secs(3600),
+            min_ttl: Duration::from_secs(60),
+            max_ttl: Duration::from_secs(24 * 3600),
+            error_ttl: Duration::from_secs(60),
+            key_prefix: "riptide:robots:".to_string(),
+            max_entries: 10_000,
+            max_body_bytes: 500 * 1024,
+        }
+    }
+}
+
+/// What fetching an origin's robots.txt returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RobotsStatus {
+    /// The file was served
+    Found,
+    /// 4xx other than 429: no restrictions apply
+    Missing,
+    /// 5xx, 429 or a network error: the site's rules are unknown
+    Unavailable,
+}
+
+/// An origin's robots.txt as last fetched
+#[derive(Debug, Clone)]
+pub struct RobotsFile {
+    pub status: RobotsStatus,
+    /// Parsed file; empty unless `status` is `Found`
+    pub rules: Arc<RobotsRules>,
+    /// Status code or network error of an unavailable file
+    pub error: Option<String>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl RobotsFile {
+    /// Whether the file may still be used without refetching
+    pub fn is_fresh(&self) -> bool {
+        Utc::now() < self.expires_at
+    }
+
+    /// Remaining freshness, zero once expired
+    pub fn ttl(&self) -> Duration {
+        (self.expires_at - Utc::now()).to_std().unwrap_or_default()
+    }
+}
+
+/// A [`RobotsFile`] as stored in the shared cache; the body is re-parsed on
+/// load since [`RobotsRules`] are cheap to build
+#[derive(Serialize, Deserialize)]
+struct SharedRobotsFile {
+    status: RobotsStatus,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    error: Option<String>,
+    expires_at: DateTime<Utc>,
+}
+
+/// robots.txt lookup and fetch counters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RobotsServiceStats {
+    /// Origins held in memory
+    pub entries: usize,
+    /// Lookups answered from memory
+    pub memory_hits: u64,
+    /// Lookups answered from the shared cache
+    pub shared_hits: u64,
+    /// robots.txt requests sent
+    pub fetches: u64,
+    pub found: u64,
+    pub missing: u64,
+    pub unavailable: u64,
+    /// Body bytes of fetched files
+    pub bytes_fetched: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    memory_hits: AtomicU64,
+    shared_hits: AtomicU64,
+    fetches: AtomicU64,
+    found: AtomicU64,
+    missing: AtomicU64,
+    unavailable: AtomicU64,
+    bytes_fetched: AtomicU64,
+}
+
+/// Fetches, parses and caches robots.txt per origin for every component
+/// holding a handle to it
+pub struct RobotsService {
+    config: RobotsServiceConfig,
+    client: Client,
+    files: DashMap<String, RobotsFile>,
+    /// Per-origin locks so concurrent misses share one fetch
+    inflight: DashMap<String, Arc<Mutex<()>>>,
+    shared: Option<Arc<dyn CacheStorage>>,
+    counters: Counters,
+}
+
+impl std::fmt::Debug for RobotsService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RobotsService")
+            .field("config", &self.config)
+            .field("entries", &self.files.len())
+            .field("shared_cache", &self.shared.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl RobotsService {
+    pub fn new(config: RobotsServiceConfig) -> Result<Self> {
+        let client = Client::builder()
+            .user_agent(&config.user_agent)
+            .timeout(config.fetch_timeout)
+            .gzip(true)
+            .build()
+            .context("Failed to create HTTP client for robots service")?;
+
+        Ok(Self {
+            config,
+            client,
+            files: DashMap::new(),
+            inflight: DashMap::new(),
+            shared: None,
+            counters: Counters::default(),
+        })
+    }
+
+    /// Also keep files in `cache`, e.g. Redis shared by several instances
+    pub fn with_shared_cache(mut self, cache: Arc<dyn CacheStorage>) -> Self {
+        self.shared = Some(cache);
+        self
+    }
+
+    pub fn config(&self) -> &RobotsServiceConfig {
+        &self.config
+    }
+
+    /// robots.txt of the origin of `url`, fetched only when no fresh copy
+    /// is cached
+    ///
+    /// Fails only for URLs without a host; an unreachable file is returned
+    /// with [`RobotsStatus::Unavailable`].
+    pub async fn get(&self, url: &Url) -> Result<RobotsFile> {
+        let origin = Self::origin(url)?;
+        if let Some(file) = self.fresh(&origin) {
+            return Ok(file);
+        }
+
+        let lock = self.inflight.entry(origin.clone()).or_default().clone();
+        let _guard = lock.lock().await;
+        // Another lookup may have fetched the file while we waited
+        if let Some(file) = self.fresh(&origin) {
+            return Ok(file);
+        }
+
+        let file = match self.load_shared(&origin).await {
+            Some(file) => {
+                self.counters.shared_hits.fetch_add(1, Ordering::Relaxed);
+                debug!(origin = %origin, "Using robots.txt from shared cache");
+                file
+            }
+            None => {
+                let (file, body) = self.fetch(&origin).await;
+                self.store_shared(&origin, &file, body).await;
+                file
+            }
+        };
+
+        self.files.insert(origin.clone(), file.clone());
+        self.inflight.remove(&origin);
+        self.evict();
+        Ok(file)
+    }
+
+    /// Cached robots.txt of the origin of `url`, fresh or expired but not
+    /// yet evicted, without fetching
+    pub fn cached(&self, url: &Url) -> Option<RobotsFile> {
+        let origin = Self::origin(url).ok()?;
+        self.files.get(&origin).map(|file| file.clone())
+    }
+
+    /// Drop every file held in memory; the shared cache is left alone
+    pub fn clear(&self) {
+        self.files.clear();
+    }
+
+    pub fn stats(&self) -> RobotsServiceStats {
+        let counters = &self.counters;
+        RobotsServiceStats {
+            entries: self.files.len(),
+            memory_hits: counters.memory_hits.load(Ordering::Relaxed),
+            shared_hits: counters.shared_hits.load(Ordering::Relaxed),
+            fetches: counters.fetches.load(Ordering::Relaxed),
+            found: counters.found.load(Ordering::Relaxed),
+            missing: counters.missing.load(Ordering::Relaxed),
+            unavailable: counters.unavailable.load(Ordering::Relaxed),
+            bytes_fetched: counters.bytes_fetched.load(Ordering::Relaxed),
+        }
+    }
+
+    /// `scheme://host[:port]`, the scope a robots.txt applies to
+    pub(crate) fn origin(url: &Url) -> Result<String> {
+        let host = url.host_str().context("URL has no host")?;
+        Ok(match url.port() {
+            Some(port) => format!("{}://{}:{}", url.scheme(), host, port),
+            None => format!("{}://{}", url.scheme(), host),
+        })
+    }
+
+    /// Drop expired files, then the files expiring soonest while more than
+    /// `max_entries` origins are held
+    fn evict(&self) {
+        self.files.retain(|_, file| file.is_fresh());
+        let excess = self.files.len().saturating_sub(self.config.max_entries);
+        if excess == 0 {
+            return;
+        }
+        let mut expiries: Vec<(DateTime<Utc>, String)> = self
+            .files
+            .iter()
+            .map(|entry| (entry.expires_at, entry.key().clone()))
+            .collect();
+        expiries.sort_unstable();
+        for (_, origin) in expiries.into_iter().take(excess) {
+            self.files.remove(&origin);
+        }
+    }
+
+    fn fresh(&self, origin: &str) -> Option<RobotsFile> {
+        let file = self.files.get(origin).filter(|file| file.is_fresh())?;
+        self.counters.memory_hits.fetch_add(1, Ordering::Relaxed);
+        Some(file.clone())
+    }
+
+    /// Fetch the file, returning it with the body to share
+    async fn fetch(&self, origin: &str) -> (RobotsFile, String) {
+        let robots_url = format!("{}/robots.txt", origin);
+        debug!(robots_url = %robots_url, "Fetching robots.txt");
+        self.counters.fetches.fetch_add(1, Ordering::Relaxed);
+
+        let response = match self.client.get(&robots_url).send().await {
+            Ok(response) => response,
+            Err(e) => return (self.unavailable(origin, format!("{:#}", e)), String::new()),
+        };
+
+        let status = response.status();
+        if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            return (self.unavailable(origin, status.to_string()), String::new());
+        }
+
+        let ttl = self.ttl_from_headers(response.headers());
+        if !status.is_success() {
+            // A missing robots.txt places no restrictions on crawling
+            debug!(robots_url = %robots_url, status = %status, "robots.txt not found");
+            self.counters.missing.fetch_add(1, Ordering::Relaxed);
+            return (
+                self.file(RobotsStatus::Missing, "", None, ttl),
+                String::new(),
+            );
+        }
+
+        match self.read_body(response).await {
+            Ok(body) => {
+                self.counters.found.fetch_add(1, Ordering::Relaxed);
+                self.counters
+                    .bytes_fetched
+                    .fetch_add(body.len() as u64, Ordering::Relaxed);
+                let file = self.file(RobotsStatus::Found, &body, None, ttl);
+                (file, body)
+            }
+            Err(e) => (self.unavailable(origin, format!("{:#}", e)), String::new()),
+        }
+    }
+
+    /// Body of `response` up to `max_body_bytes`; a line cut off by the cap
+    /// is dropped so it cannot be parsed as a shorter rule
+    async fn read_body(&self, mut response: reqwest::Response) -> reqwest::Result<String> {
+        let limit = self.config.max_body_bytes;
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            let room = limit - body.len();
+            if chunk.len() > room {
+                body.extend_from_slice(&chunk[..room]);
+                let end = body.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+                body.truncate(end);
+                debug!(url = %response.url(), limit, "robots.txt truncated");
+                break;
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+
+    fn unavailable(&self, origin: &str, reason: String) -> RobotsFile {
+        warn!(origin = %origin, reason = %reason, "robots.txt unavailable");
+        self.counters.unavailable.fetch_add(1, Ordering::Relaxed);
+        self.file(
+            RobotsStatus::Unavailable,
+            "",
+            Some(reason),
+            self.config.error_ttl,
+        )
+    }
+
+    fn file(
+        &self,
+        status: RobotsStatus,
+        body: &str,
+        error: Option<String>,
+        ttl: Duration,
+    ) -> RobotsFile {
+        let rules = match status {
+            RobotsStatus::Found => RobotsRules::parse(body),
+            _ => RobotsRules::default(),
+        };
+        RobotsFile {
+            status,
+            rules: Arc::new(rules),
+            error,
+            expires_at: Utc::now()
+                + chrono::Duration::from_std(ttl).unwrap_or(chrono::Duration::zero()),
+        }
+    }
+
+    /// Freshness from the response headers, clamped to the configured bounds
+    fn ttl_from_headers(&self, headers: &HeaderMap) -> Duration {
+        header_ttl(headers)
+            .unwrap_or(self.config.default_ttl)
+            .clamp(
+                self.config.min_ttl,
+                self.config.max_ttl.max(self.config.min_ttl),
+            )
+    }
+
+    async fn load_shared(&self, origin: &str) -> Option<RobotsFile> {
+        let cache = self.shared.as_ref()?;
+        let key = format!("{}{}", self.config.key_prefix, origin);
+        let bytes = match cache.get(&key).await {
+            Ok(bytes) => bytes?,
+            Err(e) => {
+                debug!(origin = %origin, error = %e, "Shared robots cache unavailable");
+                return None;
+            }
+        };
+        let shared: SharedRobotsFile = serde_json::from_slice(&bytes).ok()?;
+        let file = RobotsFile {
+            rules: Arc::new(match shared.status {
+                RobotsStatus::Found => RobotsRules::parse(&shared.body),
+                _ => RobotsRules::default(),
+            }),
+            status: shared.status,
+            error: shared.error,
+            expires_at: shared.expires_at,
+        };
+        file.is_fresh().then_some(file)
+    }
+
+    async fn store_shared(&self, origin: &str, file: &RobotsFile, body: String) {
+        let Some(cache) = &self.shared else {
+            return;
+        };
+        let key = format!("{}{}", self.config.key_prefix, origin);
+        let shared = SharedRobotsFile {
+            status: file.status,
+            body,
+            error: file.error.clone(),
+            expires_at: file.expires_at,
+        };
+        let Ok(bytes) = serde_json::to_vec(&shared) else {
+            return;
+        };
+        if let Err(e) = cache.set(&key, &bytes, Some(file.ttl())).await {
+            debug!(origin = %origin, error = %e, "Failed to share robots.txt");
+        }
+    }
+}
+
+/// Freshness lifetime a response declares: `s-maxage` or `max-age`, zero
+/// for `no-store`/`no-cache`, else `Expires` relative to `Date`
+fn header_ttl(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name| headers.get(name).and_then(|v| v.to_str().ok());
+
+    if let Some(cache_control) = header(CACHE_CONTROL) {
+        let mut max_age = None;
+        for directive in cache_control.split(',') {
+            let directive = directive.trim().to_ascii_lowercase();
+            match directive.split_once('=') {
+                Some(("s-maxage", secs)) => {
+                    if let Ok(secs) = secs.trim_matches('"').parse() {
+                        return Some(Duration::from_secs(secs));
+                    }
+                }
+                Some(("max-age", secs)) => max_age = secs.trim_matches('"').parse().ok(),
+                None if directive == "no-store" || directive == "no-cache" => {
+                    return Some(Duration::ZERO)
+                }
+                _ => {}
+            }
+        }
+        if let Some(secs) = max_age {
+            return Some(Duration::from_secs(secs));
+        }
+    }
+
+    let expires = parse_http_date(header(EXPIRES)?)?;
+    let date = header(DATE)
+        .and_then(parse_http_date)
+        .unwrap_or_else(Utc::now);
+    Some((expires - date).to_std().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use riptide_types::ports::InMemoryCache;
+    use wiremock::matchers::path;
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_header_ttl() {
+        let ttl = |pairs| header_ttl(&headers(pairs));
+        assert_eq!(
+            ttl(&[("cache-control", "public, max-age=600")]),
+            Some(Duration::from_secs(600))
+        );
+        assert_eq!(
+            ttl(&[("cache-control", "max-age=600, s-maxage=120")]),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(ttl(&[("cache-control", "no-cache")]), Some(Duration::ZERO));
+        assert_eq!(
+            ttl(&[
+                ("date", "Mon, 01 Jan 2024 00:00:00 GMT"),
+                ("expires", "Mon, 01 Jan 2024 02:00:00 GMT"),
+            ]),
+            Some(Duration::from_secs(7200))
+        );
+        assert_eq!(ttl(&[]), None);
+    }
+
+    #[test]
+    fn test_ttl_is_clamped() {
+        let service = RobotsService::new(RobotsServiceConfig::default()).unwrap();
+        assert_eq!(
+            service.ttl_from_headers(&headers(&[("cache-control", "no-store")])),
+            Duration::from_secs(60)
+        );
+        assert_eq!(
+            service.ttl_from_headers(&headers(&[("cache-control", "max-age=31536000")])),
+            Duration::from_secs(24 * 3600)
+        );
+        assert_eq!(
+            service.ttl_from_headers(&HeaderMap::new()),
+            Duration::from_secs(3600)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_one_fetch_per_origin_and_shared_cache() {
+        let server = MockServer::start().await;
+        Mock::given(path("/robots.txt"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("cache-control", "max-age=900")
+                    .set_body_string("User-agent: *\nDisallow: /private\n"),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let cache: Arc<dyn CacheStorage> = Arc::new(InMemoryCache::new());
+        let service = Arc::new(
+            RobotsService::new(RobotsServiceConfig::default())
+                .unwrap()
+                .with_shared_cache(cache.clone()),
+        );
+        let page = Url::parse(&format!("{}/private/page", server.uri())).unwrap();
+
+        let lookups = (0..4).map(|_| {
+            let service = service.clone();
+            let page = page.clone();
+            tokio::spawn(async move { service.get(&page).await.unwrap() })
+        });
+        for lookup in lookups {
+            let file = lookup.await.unwrap();
+            assert_eq!(file.status, RobotsStatus::Found);
+            assert!(!file.rules.group_for("RipTide").is_allowed("/private/page"));
+            assert!(file.ttl() > Duration::from_secs(800));
+        }
+        let stats = service.stats();
+        assert_eq!((stats.fetches, stats.memory_hits, stats.entries), (1, 3, 1));
+
+        // Another instance finds the file in the shared cache
+        let other = RobotsService::new(RobotsServiceConfig::default())
+            .unwrap()
+            .with_shared_cache(cache);
+        let file = other.get(&page).await.unwrap();
+        assert_eq!(file.status, RobotsStatus::Found);
+        assert!(!file.rules.group_for("RipTide").is_allowed("/private/x"));
+        assert_eq!((other.stats().fetches, other.stats().shared_hits), (0, 1));
+    }
+
+    #[tokio::test]
+    async fn test_missing_and_unavailable() {
+        let server = MockServer::start().await;
+        let service = RobotsService::new(RobotsServiceConfig::default()).unwrap();
+        let url = Url::parse(&server.uri()).unwrap();
+
+        Mock::given(path("/robots.txt"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        let file = service.get(&url).await.unwrap();
+        assert_eq!(file.status, RobotsStatus::Unavailable);
+        assert_eq!(file.error.as_deref(), Some("503 Service Unavailable"));
+        assert!(file.ttl() <= Duration::from_secs(60));
+
+        service.clear();
+        Mock::given(path("/robots.txt"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        let file = service.get(&url).await.unwrap();
+        assert_eq!(file.status, RobotsStatus::Missing);
+        assert!(file.rules.group_for("RipTide").is_allowed("/anything"));
+
+        let stats = service.stats();
+        assert_eq!((stats.fetches, stats.unavailable, stats.missing), (2, 1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_body_is_capped_at_a_line() {
+        let server = MockServer::start().await;
+        Mock::given(path("/robots.txt"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_string("User-agent: *\nDisallow: /a\nDisallow: /private\n"),
+            )
+            .mount(&server)
+            .await;
+        let service = RobotsService::new(RobotsServiceConfig {
+            max_body_bytes: 32,
+            ..Default::default()
+        })
+        .unwrap();
+
+        let file = service
+            .get(&Url::parse(&server.uri()).unwrap())
+            .await
+            .unwrap();
+        let group = file.rules.group_for("RipTide");
+        assert!(!group.is_allowed("/a"));
+        // The cut `Disallow: /` line is dropped rather than blocking everything
+        assert!(group.is_allowed("/private"));
+        assert!(group.is_allowed("/b"));
+        assert_eq!(service.stats().bytes_fetched, 27);
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_is_bounded() {
+        let service = RobotsService::new(RobotsServiceConfig {
+            max_entries: 2,
+            ..Default::default()
+        })
+        .unwrap();
+        let mut servers = Vec::new();
+        for max_age in [300, 600, 900] {
+            let server = MockServer::start().await;
+            Mock::given(path("/robots.txt"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .insert_header("cache-control", format!("max-age={}", max_age).as_str()),
+                )
+                .mount(&server)
+                .await;
+            let url = Url::parse(&server.uri()).unwrap();
+            service.get(&url).await.unwrap();
+            servers.push((server, url));
+        }
+
+        assert_eq!(service.stats().entries, 2);
+        // The file expiring soonest made room
+        assert!(service.cached(&servers[0].1).is_none());
+        assert!(service.cached(&servers[2].1).is_some());
+
+        // Expired files go at the next fetch
+        service.files.alter(
+            &RobotsService::origin(&servers[1].1).unwrap(),
+            |_, mut file| {
+                file.expires_at = Utc::now() - chrono::Duration::seconds(1);
+                file
+            },
+        );
+        service.get(&servers[0].1).await.unwrap();
+        assert!(service.cached(&servers[1].1).is_none());
+        assert_eq!(service.stats().entries, 2);
+    }
+}
@@ -0,0 +1,245 @@
+//! Per-domain TLS settings: client certificates (mTLS) and custom root CAs
+//!
+//! Enterprise portals and partner APIs often sit behind a private CA and
+//! require a client certificate. A [`DomainTlsConfig`] scopes such settings
+//! to the hosts that need them, so the certificate is never presented to
+//! other sites. [`ReliableHttpClient`](crate::ReliableHttpClient) and
+//! `riptide_reliability::HttpClientService` build one client per profile and
+//! pick it by request host; the first matching profile wins. A profile's
+//! client only follows redirects to the profile's hosts and hands other
+//! redirects back to the caller.
+//!
+//! Certificates and keys are read from PEM files when the client is built.
+
+use anyhow::{anyhow, Context, Result};
+use reqwest::redirect::Policy;
+use reqwest::{Certificate, ClientBuilder, Identity};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Redirects a profile's client follows before failing, as reqwest's default
+const MAX_REDIRECTS: usize = 10;
+
+/// Client certificate presented during the TLS handshake
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientCertificate {
+    /// PEM certificate chain, leaf first
+    pub cert_path: PathBuf,
+    /// PEM private key (PKCS#8, PKCS#1 or SEC1)
+    pub key_path: PathBuf,
+}
+
+/// TLS settings for a set of hosts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DomainTlsConfig {
+    /// Hosts the profile applies to: `portal.corp.example` matches that host
+    /// only, `*.corp.example` matches its subdomains
+    pub domains: Vec<String>,
+    /// Client certificate for mutual TLS
+    pub client_certificate: Option<ClientCertificate>,
+    /// PEM bundle of extra root CAs to trust
+    pub ca_bundle: Option<PathBuf>,
+    /// Keep trusting the built-in public roots next to `ca_bundle`
+    pub include_system_roots: bool,
+}
+
+impl Default for DomainTlsConfig {
+    fn default() -> Self {
+        Self {
+            domains: Vec::new(),
+            client_certificate: None,
+            ca_bundle: None,
+            include_system_roots: true,
+        }
+    }
+}
+
+impl DomainTlsConfig {
+    /// Whether the profile applies to `host`
+    pub fn matches(&self, host: &str) -> bool {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        self.domains.iter().any(|pattern| {
+            let pattern = pattern.trim_end_matches('.').to_ascii_lowercase();
+            match pattern.strip_prefix("*.") {
+                Some(parent) => host
+                    .strip_suffix(parent)
+                    .is_some_and(|sub| sub.len() > 1 && sub.ends_with('.')),
+                None => host == pattern,
+            }
+        })
+    }
+
+    /// Read a JSON array of profiles, as in `RIPTIDE_TLS_PROFILES_FILE`
+    pub fn load(path: impl AsRef<Path>) -> Result<Vec<Self>> {
+        let path = path.as_ref();
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read TLS profiles {}", path.display()))?;
+        serde_json::from_str(&raw)
+            .with_context(|| format!("Invalid TLS profiles {}", path.display()))
+    }
+
+    /// Index of the first of `profiles` matching the host of `url`
+    pub fn find(profiles: &[Self], url: &url::Url) -> Option<usize> {
+        let host = url.host_str()?;
+        profiles.iter().position(|tls| tls.matches(host))
+    }
+
+    /// Redirect policy following redirects within the profile's hosts only
+    ///
+    /// A redirect elsewhere is returned to the caller as the `3xx` response
+    /// rather than followed with the profile's certificate.
+    pub fn redirect_policy(&self) -> Policy {
+        let profile = self.clone();
+        Policy::custom(move |attempt| {
+            if attempt.previous().len() >= MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if attempt.url().host_str().is_some_and(|h| profile.matches(h)) {
+                attempt.follow()
+            } else {
+                attempt.stop()
+            }
+        })
+    }
+
+    /// Add the profile's roots, identity and redirect policy to a client
+    /// builder
+    pub fn apply(&self, builder: ClientBuilder) -> Result<ClientBuilder> {
+        if self.domains.is_empty() {
+            return Err(anyhow!("TLS profile has no domains"));
+        }
+        if self.ca_bundle.is_none() && !self.include_system_roots {
+            return Err(anyhow!(
+                "TLS profile for {} trusts no roots: set ca_bundle or include_system_roots",
+                self.domains.join(", ")
+            ));
+        }
+
+        let mut builder = builder
+            .tls_built_in_root_certs(self.include_system_roots)
+            .redirect(self.redirect_policy());
+        if let Some(path) = &self.ca_bundle {
+            let pem = std::fs::read(path)
+                .with_context(|| format!("Failed to read CA bundle {}", path.display()))?;
+            let roots = Certificate::from_pem_bundle(&pem)
+                .with_context(|| format!("Invalid CA bundle {}", path.display()))?;
+            if roots.is_empty() {
+                return Err(anyhow!("CA bundle {} has no certificates", path.display()));
+            }
+            for root in roots {
+                builder = builder.add_root_certificate(root);
+            }
+        }
+        if let Some(cert) = &self.client_certificate {
+            let mut pem = std::fs::read(&cert.cert_path).with_context(|| {
+                format!(
+                    "Failed to read client certificate {}",
+                    cert.cert_path.display()
+                )
+            })?;
+            pem.push(b'\n');
+            pem.extend(std::fs::read(&cert.key_path).with_context(|| {
+                format!("Failed to read client key {}", cert.key_path.display())
+            })?);
+            let identity = Identity::from_pem(&pem).with_context(|| {
+                format!(
+                    "Invalid client certificate {} / key {}",
+                    cert.cert_path.display(),
+                    cert.key_path.display()
+                )
+            })?;
+            builder = builder.identity(identity);
+        }
+        Ok(builder)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn profile(domains: &[&str]) -> DomainTlsConfig {
+        DomainTlsConfig {
+            domains: domains.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_domain_matching() {
+        let tls = profile(&["portal.corp.example", "*.partner.example"]);
+
+        assert!(tls.matches("portal.corp.example"));
+        assert!(tls.matches("Portal.Corp.Example."));
+        assert!(!tls.matches("other.corp.example"));
+        assert!(tls.matches("api.partner.example"));
+        assert!(tls.matches("a.b.partner.example"));
+        assert!(!tls.matches("partner.example"));
+        assert!(!tls.matches("evilpartner.example"));
+    }
+
+    #[test]
+    fn test_load_reads_profile_array() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"[{{"domains": ["*.partner.example"], "ca_bundle": "/etc/riptide/ca.pem"}}]"#
+        )
+        .unwrap();
+
+        let profiles = DomainTlsConfig::load(file.path()).unwrap();
+        assert_eq!(profiles.len(), 1);
+        assert!(profiles[0].include_system_roots);
+        assert_eq!(
+            profiles[0].ca_bundle.as_deref(),
+            Some(std::path::Path::new("/etc/riptide/ca.pem"))
+        );
+
+        let url = url::Url::parse("https://api.partner.example/v1").unwrap();
+        assert_eq!(DomainTlsConfig::find(&profiles, &url), Some(0));
+        let url = url::Url::parse("https://example.com/").unwrap();
+        assert_eq!(DomainTlsConfig::find(&profiles, &url), None);
+
+        writeln!(file, "not json").unwrap();
+        assert!(DomainTlsConfig::load(file.path()).is_err());
+    }
+
+    #[test]
+    fn test_apply_rejects_incomplete_profiles() {
+        assert!(profile(&[]).apply(ClientBuilder::new()).is_err());
+
+        let no_roots = DomainTlsConfig {
+            include_system_roots: false,
+            ..profile(&["portal.corp.example"])
+        };
+        assert!(no_roots.apply(ClientBuilder::new()).is_err());
+    }
+
+    #[test]
+    fn test_apply_reports_unreadable_files() {
+        let mut bundle = tempfile::NamedTempFile::new().unwrap();
+        writeln!(bundle, "not a certificate").unwrap();
+
+        let tls = DomainTlsConfig {
+            ca_bundle: Some(bundle.path().to_path_buf()),
+            ..profile(&["portal.corp.example"])
+        };
+        let err = tls.apply(ClientBuilder::new()).unwrap_err();
+        assert!(err.to_string().contains("has no certificates"), "{}", err);
+
+        let tls = DomainTlsConfig {
+            client_certificate: Some(ClientCertificate {
+                cert_path: "/nonexistent/client.pem".into(),
+                key_path: "/nonexistent/client.key".into(),
+            }),
+            ..profile(&["portal.corp.example"])
+        };
+        let err = tls.apply(ClientBuilder::new()).unwrap_err();
+        assert!(
+            err.to_string().contains("/nonexistent/client.pem"),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,373 @@
+//! Rule-based URL rewriting
+//!
+//! A [`UrlRewriter`] maps the URL a caller asked for to the URL that is
+//! actually fetched: a cache-friendly mirror, the canonical host, or the API
+//! equivalent of an HTML page. Rules are tried in order and the first one
+//! that matches decides the URL; rewritten URLs are not rewritten again, and
+//! a rule whose template is `{url}` exempts URLs from the rules after it.
+//!
+//! A rule applies to the hosts in `domains` (all hosts when empty): an entry
+//! matches that host exactly, and `*.example.com` matches its subdomains. A
+//! rule with a `pattern` must also match the full URL. The `replacement`
+//! template is expanded with:
+//!
+//! - `$1`, `${name}`: capture groups of `pattern` (`$$` for a literal `$`)
+//! - `{scheme}`, `{host}`, `{port}`, `{path}`, `{query}`, `{url}`: parts of
+//!   the original URL; `{query}` includes the leading `?` when there is one
+//!
+//! A rewrite that does not produce an absolute `http(s)` URL is dropped and
+//! the original URL is fetched.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_fetch::url_rewrite::{UrlRewriteRule, UrlRewriter};
+//!
+//! let rewriter = UrlRewriter::new(vec![
+//!     UrlRewriteRule::new("wiki-api", "https://{host}/api/rest_v1/page/html/$1")
+//!         .with_domains(["en.wikipedia.org"])
+//!         .with_pattern(r"^https://[^/]+/wiki/([^?#]+)"),
+//!     UrlRewriteRule::new("mirror", "https://mirror.example.net{path}{query}")
+//!         .with_domains(["*.example.com"]),
+//! ])
+//! .unwrap();
+//!
+//! let rewrite = rewriter.rewrite("https://en.wikipedia.org/wiki/Rust").unwrap();
+//! assert_eq!(rewrite.fetched_url, "https://en.wikipedia.org/api/rest_v1/page/html/Rust");
+//! assert_eq!(rewrite.rule, "wiki-api");
+//!
+//! let rewrite = rewriter.rewrite("https://www.example.com/a?b=1").unwrap();
+//! assert_eq!(rewrite.fetched_url, "https://mirror.example.net/a?b=1");
+//! assert!(rewriter.rewrite("https://example.com/a").is_none());
+//! ```
+
+use crate::middleware::FetchMiddleware;
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use regex::{Captures, Regex};
+use reqwest::Request;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use url::Url;
+
+pub use riptide_types::extracted::UrlRewrite;
+
+/// One rewrite rule, as written in a rules file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UrlRewriteRule {
+    /// Name recorded with each rewrite the rule makes
+    pub name: String,
+    /// Hosts the rule applies to; empty for every host
+    #[serde(default)]
+    pub domains: Vec<String>,
+    /// Regex the full URL must match
+    #[serde(default)]
+    pub pattern: Option<String>,
+    /// Template of the rewritten URL
+    pub replacement: String,
+}
+
+impl UrlRewriteRule {
+    pub fn new(name: impl Into<String>, replacement: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            domains: Vec::new(),
+            pattern: None,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Limit the rule to `domains`
+    pub fn with_domains<I, S>(mut self, domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.domains = domains.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Require the full URL to match `pattern`
+    pub fn with_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.pattern = Some(pattern.into());
+        self
+    }
+}
+
+#[derive(Debug)]
+struct CompiledRule {
+    name: String,
+    domains: Vec<String>,
+    pattern: Option<Regex>,
+    replacement: String,
+}
+
+impl CompiledRule {
+    fn applies_to(&self, host: &str) -> bool {
+        self.domains.is_empty()
+            || self
+                .domains
+                .iter()
+                .any(|domain| match domain.strip_prefix("*.") {
+                    Some(parent) => host
+                        .strip_suffix(parent)
+                        .is_some_and(|sub| sub.ends_with('.')),
+                    None => host == domain,
+                })
+    }
+
+    fn apply(&self, url: &Url) -> Option<String> {
+        let host = url.host_str()?.to_ascii_lowercase();
+        if !self.applies_to(&host) {
+            return None;
+        }
+        let captures = match &self.pattern {
+            Some(pattern) => Some(pattern.captures(url.as_str())?),
+            None => None,
+        };
+        Some(expand(&self.replacement, url, captures.as_ref()))
+    }
+}
+
+/// Ordered set of URL rewrite rules
+#[derive(Debug, Default)]
+pub struct UrlRewriter {
+    rules: Vec<CompiledRule>,
+}
+
+impl UrlRewriter {
+    /// Compile `rules`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the rule when a pattern is not a valid regex.
+    pub fn new(rules: Vec<UrlRewriteRule>) -> Result<Self> {
+        let rules = rules
+            .into_iter()
+            .map(|rule| {
+                let pattern = rule
+                    .pattern
+                    .as_deref()
+                    .map(Regex::new)
+                    .transpose()
+                    .with_context(|| {
+                        format!("Invalid pattern in URL rewrite rule {}", rule.name)
+                    })?;
+                Ok(CompiledRule {
+                    domains: rule
+                        .domains
+                        .iter()
+                        .map(|d| d.trim().trim_end_matches('.').to_ascii_lowercase())
+                        .collect(),
+                    name: rule.name,
+                    pattern,
+                    replacement: rule.replacement,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Parse and compile rules from a JSON array
+    ///
+    /// # Errors
+    ///
+    /// Returns an error when the JSON is not an array of rules or a rule
+    /// does not compile.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let rules: Vec<UrlRewriteRule> =
+            serde_json::from_str(json).map_err(|e| anyhow!("Invalid URL rewrite rules: {}", e))?;
+        Self::new(rules)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Rewrite `url` with the first matching rule
+    ///
+    /// Returns `None` when `url` is not an absolute URL, no rule matches, or
+    /// the matching rule leaves it unchanged or produces an invalid URL.
+    pub fn rewrite(&self, url: &str) -> Option<UrlRewrite> {
+        if self.rules.is_empty() {
+            return None;
+        }
+        let parsed = Url::parse(url).ok()?;
+        let (rule, rewritten) = self
+            .rules
+            .iter()
+            .find_map(|rule| rule.apply(&parsed).map(|rewritten| (rule, rewritten)))?;
+        match Url::parse(&rewritten) {
+            Ok(target) if matches!(target.scheme(), "http" | "https") => {
+                (target.as_str() != parsed.as_str()).then(|| UrlRewrite {
+                    original_url: url.to_string(),
+                    fetched_url: target.into(),
+                    rule: rule.name.clone(),
+                })
+            }
+            _ => {
+                debug!(
+                    url = %url,
+                    rule = %rule.name,
+                    rewritten = %rewritten,
+                    "Dropping invalid URL rewrite"
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Rewrites the URL of every request a client sends
+///
+/// Rewrites made this way are not visible to the caller; fetch through
+/// [`UrlRewriter::rewrite`] where they must be recorded.
+#[async_trait]
+impl FetchMiddleware for UrlRewriter {
+    fn name(&self) -> &str {
+        "url_rewrite"
+    }
+
+    async fn on_request(&self, request: &mut Request) -> Result<()> {
+        if let Some(rewrite) = self.rewrite(request.url().as_str()) {
+            *request.url_mut() = Url::parse(&rewrite.fetched_url)?;
+        }
+        Ok(())
+    }
+}
+
+/// Expand a replacement template in one pass, so text substituted from the
+/// URL is never expanded again
+fn expand(template: &str, url: &Url, captures: Option<&Captures<'_>>) -> String {
+    let capture = |name: &str| -> String {
+        let group = match name.parse::<usize>() {
+            Ok(index) => captures.and_then(|c| c.get(index)),
+            Err(_) => captures.and_then(|c| c.name(name)),
+        };
+        group.map_or_else(String::new, |m| m.as_str().to_string())
+    };
+
+    let mut out = String::with_capacity(template.len() + url.as_str().len());
+    let mut rest = template;
+    while let Some(pos) = rest.find(['$', '{']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if let Some(after) = tail.strip_prefix("$$") {
+            out.push('$');
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix("${") {
+            match after.find('}') {
+                Some(end) => {
+                    out.push_str(&capture(&after[..end]));
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    out.push_str(tail);
+                    rest = "";
+                }
+            }
+        } else if let Some(after) = tail.strip_prefix('$') {
+            let digits = after.len() - after.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            if digits == 0 {
+                out.push('$');
+            } else {
+                out.push_str(&capture(&after[..digits]));
+            }
+            rest = &after[digits..];
+        } else {
+            let after = &tail[1..];
+            let value = after
+                .find('}')
+                .and_then(|end| placeholder(&after[..end], url).map(|value| (end, value)));
+            match value {
+                Some((end, value)) => {
+                    out.push_str(&value);
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    out.push('{');
+                    rest = after;
+                }
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn placeholder(name: &str, url: &Url) -> Option<String> {
+    Some(match name {
+        "scheme" => url.scheme().to_string(),
+        "host" => url.host_str().unwrap_or_default().to_string(),
+        "port" => url.port().map(|p| p.to_string()).unwrap_or_default(),
+        "path" => url.path().to_string(),
+        "query" => url.query().map(|q| format!("?{}", q)).unwrap_or_default(),
+        "url" => url.as_str().to_string(),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_matching_rule_rewrites() {
+        let rewriter = UrlRewriter::new(vec![
+            UrlRewriteRule::new("canonical", "https://example.com{path}{query}")
+                .with_domains(["www.example.com", "m.example.com"]),
+            UrlRewriteRule::new("cdn", "https://cache.example.net/${file}")
+                .with_pattern(r"^https?://static\.[^/]+/(?P<file>.+)$"),
+            UrlRewriteRule::new("unused", "https://never.example.org/"),
+        ])
+        .unwrap();
+
+        let rewrite = rewriter.rewrite("https://m.example.com/news?id=3").unwrap();
+        assert_eq!(rewrite.fetched_url, "https://example.com/news?id=3");
+        assert_eq!(rewrite.original_url, "https://m.example.com/news?id=3");
+        assert_eq!(rewrite.rule, "canonical");
+
+        let rewrite = rewriter
+            .rewrite("http://static.example.com/img/a.png")
+            .unwrap();
+        assert_eq!(rewrite.fetched_url, "https://cache.example.net/img/a.png");
+
+        // Rules without domains or patterns match every URL
+        assert_eq!(
+            rewriter.rewrite("https://other.org/").unwrap().rule,
+            "unused"
+        );
+    }
+
+    #[test]
+    fn test_domains_and_unchanged_or_invalid_rewrites() {
+        let rewriter = UrlRewriter::new(vec![
+            UrlRewriteRule::new("bad", "mirror/{path}").with_domains(["bad.example.com"]),
+            UrlRewriteRule::new("noop", "{url}").with_domains(["*.example.com"]),
+            UrlRewriteRule::new("cost", "https://example.com/$$5{nope}$x"),
+        ])
+        .unwrap();
+
+        assert!(rewriter.rewrite("https://a.example.com/").is_none());
+        assert!(rewriter.rewrite("https://bad.example.com/").is_none());
+        assert_eq!(
+            rewriter
+                .rewrite("https://example.com/x")
+                .unwrap()
+                .fetched_url,
+            "https://example.com/$5%7Bnope%7D$x"
+        );
+        assert!(rewriter.rewrite("not a url").is_none());
+
+        assert!(
+            UrlRewriter::from_json(r#"[{"name": "x", "replacement": "/", "pattern": "("}]"#)
+                .unwrap_err()
+                .to_string()
+                .contains("rule x")
+        );
+    }
+}
@@ -0,0 +1,24 @@
+//! WARC 1.1 web archive writer
+//!
+//! Produces standards-compliant archive files from fetched responses and
+//! rendered pages, readable by replay tools such as pywb:
+//!
+//! - **record**: WARC record model, HTTP message reconstruction, digests
+//! - **writer**: Rotating file writer and the `WebArchive` port adapter
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use riptide_fetch::warc::{WarcFileArchive, WarcWriterConfig};
+//! use riptide_types::ports::WebArchive;
+//!
+//! let archive = WarcFileArchive::new(WarcWriterConfig::default());
+//! archive.write_exchange(&exchange).await?;
+//! let files = archive.finish().await?;
+//! ```
+
+pub mod record;
+pub mod writer;
+
+pub use record::{sha1_digest, WarcRecord, WarcRecordType, WARC_VERSION};
+pub use writer::{WarcFileArchive, WarcWriter, WarcWriterConfig};
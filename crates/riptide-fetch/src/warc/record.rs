@@ -0,0 +1,353 @@
+//! WARC 1.1 record model and serialization
+//!
+//! Builds individual records (`warcinfo`, `request`, `response`, `resource`,
+//! `metadata`) with the mandatory named fields plus block and payload digests.
+//! HTTP messages are reconstructed from the [`HttpRequest`]/[`HttpResponse`]
+//! port types, since the underlying client does not expose raw wire bytes.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use riptide_types::ports::{HttpRequest, HttpResponse};
+use sha1::{Digest, Sha1};
+use url::Url;
+use uuid::Uuid;
+
+/// WARC format version written in every record header
+pub const WARC_VERSION: &str = "WARC/1.1";
+
+/// Header prefix used to preserve transfer headers invalidated by decoding
+pub const ORIGINAL_HEADER_PREFIX: &str = "X-Archive-Orig-";
+
+/// Headers describing the wire encoding of a body the client has already decoded
+const TRANSFER_HEADERS: &[&str] = &["content-encoding", "transfer-encoding", "content-length"];
+
+/// WARC record types produced by the writer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WarcRecordType {
+    Warcinfo,
+    Request,
+    Response,
+    Resource,
+    Metadata,
+}
+
+impl WarcRecordType {
+    /// Value of the `WARC-Type` field
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WarcRecordType::Warcinfo => "warcinfo",
+            WarcRecordType::Request => "request",
+            WarcRecordType::Response => "response",
+            WarcRecordType::Resource => "resource",
+            WarcRecordType::Metadata => "metadata",
+        }
+    }
+}
+
+/// A single WARC record: named fields followed by a content block
+#[derive(Debug, Clone)]
+pub struct WarcRecord {
+    pub record_type: WarcRecordType,
+    /// Value of `WARC-Record-ID`, including angle brackets
+    pub record_id: String,
+    /// Named fields in write order, excluding the mandatory ones
+    pub headers: Vec<(String, String)>,
+    pub content_type: String,
+    pub block: Vec<u8>,
+    pub date: DateTime<Utc>,
+}
+
+impl WarcRecord {
+    /// Create a record with a fresh ID and block digest
+    pub fn new(
+        record_type: WarcRecordType,
+        content_type: impl Into<String>,
+        block: Vec<u8>,
+        date: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            record_type,
+            record_id: new_record_id(),
+            headers: Vec::new(),
+            content_type: content_type.into(),
+            block,
+            date,
+        }
+    }
+
+    /// Append a named field
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Look up a named field (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// `warcinfo` record describing the file and the software that wrote it
+    pub fn warcinfo(filename: &str, date: DateTime<Utc>) -> Self {
+        let fields = [
+            ("software", format!("riptide/{}", env!("CARGO_PKG_VERSION"))),
+            ("format", "WARC File Format 1.1".to_string()),
+            (
+                "conformsTo",
+                "https://iipc.github.io/warc-specifications/specifications/warc-format/warc-1.1/"
+                    .to_string(),
+            ),
+        ];
+        Self::new(
+            WarcRecordType::Warcinfo,
+            "application/warc-fields",
+            warc_fields_block(fields.iter().map(|(k, v)| (*k, v.as_str()))),
+            date,
+        )
+        .with_header("WARC-Filename", filename)
+    }
+
+    /// `response` record holding the reconstructed HTTP response
+    pub fn response(target_uri: &str, response: &HttpResponse, date: DateTime<Utc>) -> Self {
+        Self::new(
+            WarcRecordType::Response,
+            "application/http;msgtype=response",
+            http_response_block(response),
+            date,
+        )
+        .with_header("WARC-Target-URI", target_uri)
+        .with_header("WARC-Payload-Digest", sha1_digest(&response.body))
+    }
+
+    /// `request` record holding the reconstructed HTTP request
+    pub fn request(target_uri: &str, request: &HttpRequest, date: DateTime<Utc>) -> Self {
+        Self::new(
+            WarcRecordType::Request,
+            "application/http;msgtype=request",
+            http_request_block(request),
+            date,
+        )
+        .with_header("WARC-Target-URI", target_uri)
+    }
+
+    /// `resource` record holding content not obtained over HTTP, e.g. a rendered DOM
+    pub fn resource(
+        target_uri: &str,
+        content_type: &str,
+        body: &[u8],
+        date: DateTime<Utc>,
+    ) -> Self {
+        Self::new(WarcRecordType::Resource, content_type, body.to_vec(), date)
+            .with_header("WARC-Target-URI", target_uri)
+            .with_header("WARC-Payload-Digest", sha1_digest(body))
+    }
+
+    /// `metadata` record with `name: value` fields
+    pub fn metadata(target_uri: &str, fields: &[(String, String)], date: DateTime<Utc>) -> Self {
+        Self::new(
+            WarcRecordType::Metadata,
+            "application/warc-fields",
+            warc_fields_block(fields.iter().map(|(k, v)| (k.as_str(), v.as_str()))),
+            date,
+        )
+        .with_header("WARC-Target-URI", target_uri)
+    }
+
+    /// Serialize the record, including the trailing `CRLF CRLF`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut head = format!(
+            "{}\r\nWARC-Type: {}\r\nWARC-Record-ID: {}\r\nWARC-Date: {}\r\n",
+            WARC_VERSION,
+            self.record_type.as_str(),
+            self.record_id,
+            self.date.to_rfc3339_opts(SecondsFormat::Secs, true),
+        );
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, sanitize_field(value)));
+        }
+        head.push_str(&format!(
+            "WARC-Block-Digest: {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\r\n",
+            sha1_digest(&self.block),
+            self.content_type,
+            self.block.len()
+        ));
+
+        let mut bytes = Vec::with_capacity(head.len() + self.block.len() + 4);
+        bytes.extend_from_slice(head.as_bytes());
+        bytes.extend_from_slice(&self.block);
+        bytes.extend_from_slice(b"\r\n\r\n");
+        bytes
+    }
+}
+
+/// New `<urn:uuid:...>` record identifier
+pub fn new_record_id() -> String {
+    format!("<urn:uuid:{}>", Uuid::new_v4())
+}
+
+/// `sha1:<BASE32>` digest as used by `WARC-Block-Digest` and `WARC-Payload-Digest`
+pub fn sha1_digest(data: &[u8]) -> String {
+    format!("sha1:{}", base32_encode(&Sha1::digest(data)))
+}
+
+/// Reconstruct an HTTP/1.1 response message from the port type
+///
+/// The client hands us a decoded body, so `Content-Encoding`,
+/// `Transfer-Encoding`, and `Content-Length` are preserved under
+/// [`ORIGINAL_HEADER_PREFIX`] and a correct `Content-Length` is written instead.
+/// Replay tools would otherwise try to decode the body a second time.
+pub fn http_response_block(response: &HttpResponse) -> Vec<u8> {
+    let reason = http::StatusCode::from_u16(response.status)
+        .ok()
+        .and_then(|s| s.canonical_reason())
+        .unwrap_or("");
+    let mut head = format!("HTTP/1.1 {} {}\r\n", response.status, reason);
+
+    let mut headers: Vec<_> = response.headers.iter().collect();
+    headers.sort();
+    for (name, value) in headers {
+        if TRANSFER_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+            head.push_str(&format!(
+                "{}{}: {}\r\n",
+                ORIGINAL_HEADER_PREFIX,
+                name,
+                sanitize_field(value)
+            ));
+        } else {
+            head.push_str(&format!("{}: {}\r\n", name, sanitize_field(value)));
+        }
+    }
+    head.push_str(&format!("Content-Length: {}\r\n\r\n", response.body.len()));
+
+    let mut block = head.into_bytes();
+    block.extend_from_slice(&response.body);
+    block
+}
+
+/// Reconstruct an HTTP/1.1 request message from the port type
+pub fn http_request_block(request: &HttpRequest) -> Vec<u8> {
+    let parsed = Url::parse(&request.url).ok();
+    let target = parsed
+        .as_ref()
+        .map(|u| match u.query() {
+            Some(query) => format!("{}?{}", u.path(), query),
+            None => u.path().to_string(),
+        })
+        .unwrap_or_else(|| request.url.clone());
+    let mut head = format!("{} {} HTTP/1.1\r\n", request.method, target);
+
+    let has_host = request
+        .headers
+        .keys()
+        .any(|k| k.eq_ignore_ascii_case("host"));
+    if !has_host {
+        if let Some(host) = parsed.as_ref().and_then(|u| u.host_str()) {
+            match parsed.as_ref().and_then(|u| u.port()) {
+                Some(port) => head.push_str(&format!("Host: {}:{}\r\n", host, port)),
+                None => head.push_str(&format!("Host: {}\r\n", host)),
+            }
+        }
+    }
+
+    let mut headers: Vec<_> = request.headers.iter().collect();
+    headers.sort();
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, sanitize_field(value)));
+    }
+    head.push_str("\r\n");
+
+    let mut block = head.into_bytes();
+    if let Some(body) = &request.body {
+        block.extend_from_slice(body);
+    }
+    block
+}
+
+fn warc_fields_block<'a>(fields: impl Iterator<Item = (&'a str, &'a str)>) -> Vec<u8> {
+    fields
+        .map(|(name, value)| format!("{}: {}\r\n", name, sanitize_field(value)))
+        .collect::<String>()
+        .into_bytes()
+}
+
+/// Strip line breaks so a value can never terminate the header block early
+fn sanitize_field(value: &str) -> String {
+    value.replace(['\r', '\n'], " ")
+}
+
+/// RFC 4648 base32 without padding (SHA-1 digests are 20 bytes, a multiple of 5)
+fn base32_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+    let mut out = String::with_capacity(data.len().div_ceil(5) * 8);
+    let mut buffer: u64 = 0;
+    let mut bits = 0;
+    for &byte in data {
+        buffer = (buffer << 8) | u64::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_sha1_digest_format() {
+        // Well-known digest of the empty string
+        assert_eq!(sha1_digest(b""), "sha1:3I42H3S6NNFQ2MSVX7XZKYAYSCX5QBYJ");
+    }
+
+    #[test]
+    fn test_response_record_serialization() {
+        let mut headers = HashMap::new();
+        headers.insert("Content-Type".to_string(), "text/html".to_string());
+        headers.insert("Content-Encoding".to_string(), "gzip".to_string());
+        let response = HttpResponse::new(200, headers, b"<html></html>".to_vec());
+
+        let record = WarcRecord::response("https://example.com/", &response, Utc::now());
+        let text = String::from_utf8(record.to_bytes()).unwrap();
+
+        assert!(text.starts_with("WARC/1.1\r\nWARC-Type: response\r\n"));
+        assert!(text.contains("WARC-Target-URI: https://example.com/\r\n"));
+        assert!(text.contains("Content-Type: application/http;msgtype=response\r\n"));
+        assert!(text.contains("HTTP/1.1 200 OK\r\n"));
+        assert!(text.contains("X-Archive-Orig-Content-Encoding: gzip\r\n"));
+        assert!(text.contains("Content-Length: 13\r\n\r\n<html></html>"));
+        assert!(text.ends_with("<html></html>\r\n\r\n"));
+        assert_eq!(
+            record.header("WARC-Payload-Digest"),
+            Some(sha1_digest(b"<html></html>").as_str())
+        );
+    }
+
+    #[test]
+    fn test_request_block_includes_host_and_query() {
+        let request = HttpRequest::new("GET", "https://example.com:8443/a/b?x=1")
+            .with_header("User-Agent", "riptide");
+        let block = String::from_utf8(http_request_block(&request)).unwrap();
+
+        assert!(block.starts_with("GET /a/b?x=1 HTTP/1.1\r\nHost: example.com:8443\r\n"));
+        assert!(block.ends_with("User-Agent: riptide\r\n\r\n"));
+    }
+
+    #[test]
+    fn test_header_values_cannot_inject_lines() {
+        let record = WarcRecord::metadata(
+            "https://example.com/",
+            &[("note".to_string(), "a\r\nWARC-Type: response".to_string())],
+            Utc::now(),
+        );
+        let block = String::from_utf8(record.block).unwrap();
+        assert_eq!(block, "note: a  WARC-Type: response\r\n");
+    }
+}
@@ -0,0 +1,309 @@
+//! Rotating WARC file writer and the [`WebArchive`] adapter built on it
+
+use super::record::WarcRecord;
+use async_trait::async_trait;
+use chrono::Utc;
+use flate2::{write::GzEncoder, Compression};
+use riptide_types::error::{Result, RiptideError};
+use riptide_types::ports::{ArchivedExchange, WebArchive};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// WARC writer configuration
+#[derive(Debug, Clone)]
+pub struct WarcWriterConfig {
+    /// Directory archive files are created in
+    pub output_dir: PathBuf,
+    /// File name prefix, e.g. `riptide` → `riptide-20250101120000000-00000-ab12cd34.warc.gz`
+    pub prefix: String,
+    /// Start a new file once the current one reaches this size in bytes
+    pub max_file_size: u64,
+    /// Compress each record as a separate gzip member (`.warc.gz`)
+    pub gzip: bool,
+}
+
+impl Default for WarcWriterConfig {
+    fn default() -> Self {
+        Self {
+            output_dir: PathBuf::from("data/warc"),
+            prefix: "riptide".to_string(),
+            max_file_size: 1024 * 1024 * 1024, // 1GB, the customary WARC file size
+            gzip: true,
+        }
+    }
+}
+
+struct OpenFile {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    size: u64,
+}
+
+/// Synchronous WARC writer with size-based file rotation
+///
+/// Every new file starts with a `warcinfo` record. A record is never split
+/// across files, so a single record larger than `max_file_size` gets a file
+/// of its own.
+pub struct WarcWriter {
+    config: WarcWriterConfig,
+    current: Option<OpenFile>,
+    serial: u32,
+    files: Vec<PathBuf>,
+}
+
+impl WarcWriter {
+    /// Create a writer; the output directory is created on first write
+    pub fn new(config: WarcWriterConfig) -> Self {
+        Self {
+            config,
+            current: None,
+            serial: 0,
+            files: Vec::new(),
+        }
+    }
+
+    /// Append a record, rotating to a new file first if it would overflow
+    pub fn write_record(&mut self, record: &WarcRecord) -> io::Result<()> {
+        let bytes = self.encode(record)?;
+
+        let needs_rotation = match &self.current {
+            Some(file) => {
+                file.size > 0 && file.size + bytes.len() as u64 > self.config.max_file_size
+            }
+            None => true,
+        };
+        if needs_rotation {
+            self.open_next_file()?;
+        }
+
+        self.append(&bytes)
+    }
+
+    /// Flush and close the current file, returning every file written so far
+    pub fn finish(&mut self) -> io::Result<Vec<PathBuf>> {
+        if let Some(mut file) = self.current.take() {
+            file.writer.flush()?;
+        }
+        Ok(self.files.clone())
+    }
+
+    fn open_next_file(&mut self) -> io::Result<()> {
+        if let Some(mut previous) = self.current.take() {
+            previous.writer.flush()?;
+        }
+        std::fs::create_dir_all(&self.config.output_dir)?;
+
+        let extension = if self.config.gzip { "warc.gz" } else { "warc" };
+        let id = Uuid::new_v4().simple().to_string();
+        let filename = format!(
+            "{}-{}-{:05}-{}.{}",
+            self.config.prefix,
+            Utc::now().format("%Y%m%d%H%M%S%3f"),
+            self.serial,
+            &id[..8],
+            extension
+        );
+        self.serial += 1;
+
+        let path = self.config.output_dir.join(&filename);
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        self.current = Some(OpenFile {
+            path: path.clone(),
+            writer: BufWriter::new(file),
+            size: 0,
+        });
+        self.files.push(path);
+
+        let info = self.encode(&WarcRecord::warcinfo(&filename, Utc::now()))?;
+        self.append(&info)
+    }
+
+    fn append(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let file = self
+            .current
+            .as_mut()
+            .ok_or_else(|| io::Error::other("no open WARC file"))?;
+        file.writer.write_all(bytes)?;
+        file.size += bytes.len() as u64;
+        Ok(())
+    }
+
+    fn encode(&self, record: &WarcRecord) -> io::Result<Vec<u8>> {
+        let raw = record.to_bytes();
+        if !self.config.gzip {
+            return Ok(raw);
+        }
+        // One gzip member per record keeps the file seekable by offset for replay indexes
+        let mut encoder = GzEncoder::new(Vec::with_capacity(raw.len() / 2), Compression::default());
+        encoder.write_all(&raw)?;
+        encoder.finish()
+    }
+
+    /// Path of the file currently being written
+    pub fn current_path(&self) -> Option<&PathBuf> {
+        self.current.as_ref().map(|f| &f.path)
+    }
+}
+
+/// [`WebArchive`] adapter writing WARC 1.1 files to the local filesystem
+///
+/// File I/O runs on the blocking thread pool; records from concurrent fetches
+/// are serialized through a mutex.
+#[derive(Clone)]
+pub struct WarcFileArchive {
+    writer: Arc<Mutex<WarcWriter>>,
+}
+
+impl WarcFileArchive {
+    /// Create an archive writing to `config.output_dir`
+    pub fn new(config: WarcWriterConfig) -> Self {
+        Self {
+            writer: Arc::new(Mutex::new(WarcWriter::new(config))),
+        }
+    }
+
+    async fn write_records(&self, records: Vec<WarcRecord>) -> Result<()> {
+        let writer = Arc::clone(&self.writer);
+        tokio::task::spawn_blocking(move || {
+            let mut writer = writer
+                .lock()
+                .map_err(|_| RiptideError::Storage("WARC writer lock poisoned".to_string()))?;
+            for record in &records {
+                writer.write_record(record)?;
+            }
+            Ok(())
+        })
+        .await
+        .map_err(|e| RiptideError::Storage(format!("WARC write task failed: {}", e)))?
+    }
+}
+
+#[async_trait]
+impl WebArchive for WarcFileArchive {
+    async fn write_exchange(&self, exchange: &ArchivedExchange) -> Result<()> {
+        let mut response = WarcRecord::response(
+            &exchange.target_uri,
+            &exchange.response,
+            exchange.fetched_at,
+        );
+        let mut request =
+            WarcRecord::request(&exchange.target_uri, &exchange.request, exchange.fetched_at)
+                .with_header("WARC-Concurrent-To", response.record_id.clone());
+        if let Some(ip) = &exchange.ip_address {
+            response = response.with_header("WARC-IP-Address", ip.clone());
+            request = request.with_header("WARC-IP-Address", ip.clone());
+        }
+        self.write_records(vec![response, request]).await
+    }
+
+    async fn write_resource(
+        &self,
+        target_uri: &str,
+        content_type: &str,
+        body: &[u8],
+    ) -> Result<()> {
+        self.write_records(vec![WarcRecord::resource(
+            target_uri,
+            content_type,
+            body,
+            Utc::now(),
+        )])
+        .await
+    }
+
+    async fn write_metadata(&self, target_uri: &str, fields: &[(String, String)]) -> Result<()> {
+        self.write_records(vec![WarcRecord::metadata(target_uri, fields, Utc::now())])
+            .await
+    }
+
+    async fn finish(&self) -> Result<Vec<String>> {
+        let writer = Arc::clone(&self.writer);
+        let files = tokio::task::spawn_blocking(move || {
+            writer
+                .lock()
+                .map_err(|_| RiptideError::Storage("WARC writer lock poisoned".to_string()))?
+                .finish()
+                .map_err(RiptideError::from)
+        })
+        .await
+        .map_err(|e| RiptideError::Storage(format!("WARC finish task failed: {}", e)))??;
+
+        Ok(files
+            .into_iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::MultiGzDecoder;
+    use riptide_types::ports::{HttpRequest, HttpResponse};
+    use std::collections::HashMap;
+    use std::io::Read;
+
+    fn config(dir: &tempfile::TempDir, gzip: bool, max_file_size: u64) -> WarcWriterConfig {
+        WarcWriterConfig {
+            output_dir: dir.path().to_path_buf(),
+            prefix: "test".to_string(),
+            max_file_size,
+            gzip,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exchange_written_as_response_and_request() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = WarcFileArchive::new(config(&dir, true, u64::MAX));
+
+        let exchange = ArchivedExchange::new(
+            HttpRequest::new("GET", "https://example.com/"),
+            HttpResponse::new(200, HashMap::new(), b"hello".to_vec()),
+        );
+        archive.write_exchange(&exchange).await.unwrap();
+        let files = archive.finish().await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with(".warc.gz"));
+
+        let mut text = String::new();
+        MultiGzDecoder::new(File::open(&files[0]).unwrap())
+            .read_to_string(&mut text)
+            .unwrap();
+        let types: Vec<_> = text
+            .lines()
+            .filter_map(|l| l.strip_prefix("WARC-Type: "))
+            .collect();
+        assert_eq!(types, vec!["warcinfo", "response", "request"]);
+        assert!(text.contains("WARC-Concurrent-To: <urn:uuid:"));
+    }
+
+    #[test]
+    fn test_rotation_by_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = WarcWriter::new(config(&dir, false, 2048));
+
+        for _ in 0..4 {
+            let record = WarcRecord::resource(
+                "https://example.com/",
+                "text/html",
+                &[b'x'; 1024],
+                Utc::now(),
+            );
+            writer.write_record(&record).unwrap();
+        }
+        let files = writer.finish().unwrap();
+
+        assert!(files.len() > 1);
+        for file in &files {
+            let content = std::fs::read_to_string(file).unwrap();
+            assert!(content.starts_with("WARC/1.1\r\nWARC-Type: warcinfo\r\n"));
+        }
+    }
+}
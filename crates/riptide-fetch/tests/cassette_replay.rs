@@ -0,0 +1,60 @@
+//! Replays the committed `news_site` cassette through both cassette entry points
+
+use riptide_fetch::{Cassette, CassetteHttpClient, CassetteMode, ReqwestHttpClient};
+use riptide_types::ports::HttpClient;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+fn news_site() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/cassettes/news_site.json")
+}
+
+#[tokio::test]
+async fn test_http_client_replays_committed_cassette() {
+    let client = CassetteHttpClient::with_mode(
+        news_site(),
+        Arc::new(ReqwestHttpClient::new().unwrap()),
+        CassetteMode::Replay,
+    )
+    .unwrap();
+    assert!(!client.is_recording());
+
+    let index = client.get("https://news.example.com/").await.unwrap();
+    assert_eq!(index.status, 200);
+    assert!(String::from_utf8_lossy(&index.body).contains("Headlines"));
+    // Masked credentials are kept out of replays
+    assert!(index.header("set-cookie").is_none());
+
+    let article = client
+        .get("https://news.example.com/articles/1")
+        .await
+        .unwrap();
+    assert!(String::from_utf8_lossy(&article.body).contains("The new edition is out."));
+
+    assert!(client
+        .get("https://news.example.com/unrecorded")
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_mounted_cassette_serves_a_crawl() {
+    let server = wiremock::MockServer::start().await;
+    Cassette::load(news_site()).unwrap().mount(&server).await;
+    let client = ReqwestHttpClient::new().unwrap();
+
+    let index = client.get(&format!("{}/", server.uri())).await.unwrap();
+    let html = String::from_utf8_lossy(&index.body).into_owned();
+    let article_url = format!("{}/articles/1", server.uri());
+    assert!(html.contains(&article_url));
+    assert!(!html.contains("news.example.com"));
+    assert!(index.header("set-cookie").is_none());
+
+    let article = client.get(&article_url).await.unwrap();
+    assert!(String::from_utf8_lossy(&article.body).contains("Rust 2024 ships"));
+
+    // The recorded redirect points back at the mock server
+    let moved = client.get(&format!("{}/old", server.uri())).await.unwrap();
+    assert_eq!(moved.status, 200);
+    assert!(String::from_utf8_lossy(&moved.body).contains("The new edition is out."));
+}
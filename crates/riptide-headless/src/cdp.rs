@@ -1,8 +1,13 @@
 // P3-T4.4: Import from riptide-browser (consolidation)
+use crate::dynamic::{
+    AutoScrollConfig, AutoScrollStop, FrameDisposition, FrameProvenance, GrowthTracker,
+    IframeConfig, ScrollSnapshot,
+};
 use crate::models::*;
 use axum::{extract::State, http::StatusCode, Json};
 use chromiumoxide::Page;
 use riptide_browser::launcher::HeadlessLauncher;
+use riptide_config::CommonValidator;
 use riptide_stealth::StealthPreset;
 use std::{
     sync::Arc,
@@ -34,8 +39,29 @@ pub async fn render(
         "Starting headless render request"
     );
 
-    // Hard timeout cap of 3 seconds as per requirements
-    let render_timeout = Duration::from_secs(3);
+    if let Err(error) = validate_actions(&req) {
+        warn!(request_id = %request_id, error = %error, "Rejected render request");
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(RenderErrorResp {
+                error,
+                request_id: Some(request_id),
+                duration_ms: 0,
+            }),
+        ));
+    }
+
+    // Hard timeout cap of 3 seconds as per requirements, extended by any
+    // auto-scroll and iframe budgets so each is bounded by its own limit
+    let frames_budget = Duration::from_millis(
+        req.iframes
+            .as_ref()
+            .map(IframeConfig::time_budget_ms)
+            .unwrap_or(0),
+    );
+    let render_timeout = Duration::from_secs(3)
+        .saturating_add(auto_scroll_budget(&req))
+        .saturating_add(frames_budget);
 
     match timeout(
         render_timeout,
@@ -160,6 +186,26 @@ async fn exec_actions(page: &Page, actions: &[PageAction]) -> anyhow::Result<()>
                     sleep(Duration::from_millis(*delay_ms)).await;
                 }
             }
+            PageAction::AutoScroll {
+                max_cycles,
+                wait_ms,
+                stable_cycles,
+                max_duration_ms,
+                max_height_px,
+                item_selector,
+                max_items,
+            } => {
+                let config = AutoScrollConfig {
+                    max_cycles: *max_cycles,
+                    wait_ms: *wait_ms,
+                    stable_cycles: *stable_cycles,
+                    max_duration_ms: *max_duration_ms,
+                    max_height_px: *max_height_px,
+                    item_selector: item_selector.clone(),
+                    max_items: *max_items,
+                };
+                auto_scroll(page, &config).await?;
+            }
             PageAction::Js { code } => {
                 // Add timeout for JavaScript execution (2s default)
                 timeout(Duration::from_millis(2000), page.evaluate(code.as_str()))
@@ -214,6 +260,216 @@ async fn exec_actions(page: &Page, actions: &[PageAction]) -> anyhow::Result<()>
     Ok(())
 }
 
+/// Reject page actions whose waits exceed the service limits
+fn validate_actions(req: &RenderReq) -> Result<(), String> {
+    for action in req.actions.iter().flatten() {
+        if let PageAction::AutoScroll {
+            wait_ms,
+            max_duration_ms,
+            ..
+        } = action
+        {
+            let config = AutoScrollConfig {
+                wait_ms: *wait_ms,
+                max_duration_ms: *max_duration_ms,
+                ..Default::default()
+            };
+            config.validate()?;
+        }
+    }
+    Ok(())
+}
+
+/// Total auto-scroll time budget requested by a render request
+fn auto_scroll_budget(req: &RenderReq) -> Duration {
+    let budget_ms = req
+        .actions
+        .iter()
+        .flatten()
+        .map(|action| match action {
+            PageAction::AutoScroll {
+                max_duration_ms, ..
+            } => *max_duration_ms,
+            _ => 0,
+        })
+        .fold(0u64, u64::saturating_add);
+    Duration::from_millis(budget_ms)
+}
+
+/// Measure page height and item count
+async fn scroll_snapshot(
+    page: &Page,
+    item_selector: Option<&str>,
+) -> anyhow::Result<ScrollSnapshot> {
+    let selector = serde_json::to_string(&item_selector)?;
+    let js = format!(
+        "(() => {{ const s = {}; return [document.body ? document.body.scrollHeight : 0, s ? document.querySelectorAll(s).length : 0]; }})()",
+        selector
+    );
+    let result = timeout(Duration::from_millis(500), page.evaluate(js.as_str()))
+        .await
+        .map_err(|_| anyhow::anyhow!("Page size measurement timed out"))??;
+    let [height, items]: [u64; 2] = result.into_value()?;
+    Ok(ScrollSnapshot { height, items })
+}
+
+/// Scroll-and-wait until infinite scroll / lazy-loaded content stops growing
+async fn auto_scroll(page: &Page, config: &AutoScrollConfig) -> anyhow::Result<AutoScrollStop> {
+    let item_selector = config.item_selector.as_deref();
+    let deadline = Instant::now()
+        .checked_add(Duration::from_millis(config.max_duration_ms))
+        .unwrap_or_else(Instant::now);
+    let initial = scroll_snapshot(page, item_selector).await?;
+    let mut tracker = GrowthTracker::new(config);
+    tracker.start(initial);
+
+    let stop = loop {
+        if Instant::now() >= deadline {
+            break AutoScrollStop::TimeBudget;
+        }
+        timeout(
+            Duration::from_millis(500),
+            page.evaluate("window.scrollTo(0, document.body.scrollHeight);"),
+        )
+        .await
+        .map_err(|_| anyhow::anyhow!("Auto-scroll step timed out"))??;
+
+        // Never sleep past the deadline
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        sleep(Duration::from_millis(config.wait_ms).min(remaining)).await;
+
+        let snapshot = scroll_snapshot(page, item_selector).await?;
+        if let Some(stop) = tracker.observe(snapshot) {
+            break stop;
+        }
+    };
+
+    debug!(
+        cycles = tracker.cycles(),
+        expanded = tracker.expanded(initial),
+        stop = stop.as_str(),
+        "Auto-scroll finished"
+    );
+    Ok(stop)
+}
+
+/// Iframe found by the in-page frame scan
+#[derive(serde::Deserialize)]
+struct FrameProbe {
+    path: String,
+    src: String,
+    depth: u32,
+    same_origin: bool,
+}
+
+/// Walk the frame tree, inlining readable (same-origin) frames into their
+/// parent when `inline` is set. Frames are visited depth-first so nested
+/// frames are inlined before their parent.
+const FRAME_SCAN_JS: &str = r#"(inline, maxFrames, maxDepth) => {
+    const out = [];
+    const walk = (doc, prefix, depth) => {
+        Array.from(doc.querySelectorAll('iframe')).forEach((frame, i) => {
+            if (out.length >= maxFrames) return;
+            const path = prefix === '' ? String(i) : prefix + '.' + i;
+            const src = frame.src || (frame.hasAttribute('srcdoc') ? 'about:srcdoc' : 'about:blank');
+            let inner = null;
+            try { inner = frame.contentDocument; } catch (e) { inner = null; }
+            out.push({ path, src, depth, same_origin: !!(inner && inner.documentElement) });
+            if (!inner || !inner.documentElement) return;
+            if (depth < maxDepth) walk(inner, path, depth + 1);
+            if (inline) {
+                const div = doc.createElement('div');
+                div.setAttribute('data-riptide-frame', path);
+                div.setAttribute('data-riptide-frame-src', src);
+                div.innerHTML = (inner.body || inner.documentElement).innerHTML;
+                frame.replaceWith(div);
+            }
+        });
+    };
+    walk(document, '', 1);
+    return out;
+}"#;
+
+/// Inline same-origin iframes and render cross-origin ones as sub-documents
+async fn process_frames(
+    state: &AppState,
+    page: &Page,
+    config: &IframeConfig,
+    stealth_preset: &StealthPreset,
+    request_id: &str,
+) -> anyhow::Result<Vec<FrameProvenance>> {
+    let js = format!(
+        "({})({}, {}, {})",
+        FRAME_SCAN_JS, config.inline_same_origin, config.max_frames, config.max_depth
+    );
+    let result = timeout(
+        Duration::from_millis(riptide_browser::models::FRAME_SCAN_TIMEOUT_MS),
+        page.evaluate(js.as_str()),
+    )
+    .await
+    .map_err(|_| anyhow::anyhow!("Frame scan timed out"))??;
+    let probes: Vec<FrameProbe> = result.into_value()?;
+
+    let mut frames = Vec::with_capacity(probes.len());
+    for probe in probes {
+        let disposition = config.disposition(probe.same_origin, &probe.src);
+        let mut frame = FrameProvenance {
+            path: probe.path,
+            src: probe.src,
+            depth: probe.depth,
+            same_origin: probe.same_origin,
+            disposition,
+            html: None,
+            error: None,
+        };
+        if disposition == FrameDisposition::SubDocument {
+            match render_sub_document(state, &frame.src, config, stealth_preset).await {
+                Ok(html) => frame.html = Some(html),
+                Err(e) => frame.error = Some(e.to_string()),
+            }
+        }
+        frames.push(frame);
+    }
+
+    debug!(
+        request_id = %request_id,
+        frames = frames.len(),
+        inlined = frames
+            .iter()
+            .filter(|f| f.disposition == FrameDisposition::Inlined)
+            .count(),
+        "Frame processing finished"
+    );
+    Ok(frames)
+}
+
+/// Render a cross-origin frame on its own pooled page
+///
+/// Frame URLs come from the rendered page, so they are checked against
+/// private and local addresses, after DNS resolution, before navigating.
+async fn render_sub_document(
+    state: &AppState,
+    src: &str,
+    config: &IframeConfig,
+    stealth_preset: &StealthPreset,
+) -> anyhow::Result<String> {
+    timeout(Duration::from_millis(config.frame_timeout_ms), async {
+        let url = src.to_string();
+        tokio::task::spawn_blocking(move || {
+            CommonValidator::new_default().validate_url_resolved(&url)
+        })
+        .await??;
+        let session = state
+            .launcher
+            .launch_page(src, Some(stealth_preset.clone()))
+            .await?;
+        let html = session.page().content().await?;
+        Ok::<_, anyhow::Error>(html)
+    })
+    .await
+    .map_err(|_| anyhow::anyhow!("Frame render timed out: {}", src))?
+}
+
 /// Internal render implementation using HeadlessLauncher with browser pooling
 async fn render_internal(
     state: AppState,
@@ -233,7 +489,9 @@ async fn render_internal(
     // Launch page using the pooled launcher (this reuses browsers from the pool!)
     let session = timeout(
         Duration::from_millis(2000), // 2s for checkout + navigation
-        state.launcher.launch_page(&req.url, Some(stealth_preset)),
+        state
+            .launcher
+            .launch_page(&req.url, Some(stealth_preset.clone())),
     )
     .await
     .map_err(|_| RenderErrorResp {
@@ -281,6 +539,21 @@ async fn render_internal(
         }
     }
 
+    // Inline same-origin iframes before the DOM is serialized
+    let mut frames = Vec::new();
+    if let Some(iframes) = &req.iframes {
+        match process_frames(&state, page, iframes, &stealth_preset, &request_id).await {
+            Ok(found) => frames = found,
+            Err(e) => {
+                warn!(
+                    request_id = %request_id,
+                    error = %e,
+                    "Frame processing failed, proceeding without frame content"
+                );
+            }
+        }
+    }
+
     // Extract content
     let (html, final_url) = extract_page_content(page, &req.url, &request_id).await?;
 
@@ -319,6 +592,7 @@ async fn render_internal(
         screenshot_b64: artifacts_out.screenshot_b64.clone(), // For backward compatibility
         session_id: req.session_id.clone(),                   // Echo back session ID for now
         artifacts: artifacts_out,
+        frames,
     })
 }
 
@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::time::Duration;
 
+pub use riptide_browser::models::{FrameDisposition, FrameProvenance, IframeConfig};
+
 /// Dynamic content handling configuration for crawling complex web applications
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DynamicConfig {
@@ -11,6 +13,14 @@ pub struct DynamicConfig {
     /// Scroll configuration for infinite scroll pages
     pub scroll: Option<ScrollConfig>,
 
+    /// Scroll-and-wait expansion of infinite scroll and lazy-loaded content
+    #[serde(default)]
+    pub auto_scroll: Option<AutoScrollConfig>,
+
+    /// Same-origin inlining and cross-origin sub-document rendering of iframes
+    #[serde(default)]
+    pub iframes: Option<IframeConfig>,
+
     /// Actions to perform on the page
     pub actions: Vec<PageAction>,
 
@@ -29,6 +39,8 @@ impl Default for DynamicConfig {
         Self {
             wait_for: None,
             scroll: None,
+            auto_scroll: None,
+            iframes: None,
             actions: Vec::new(),
             capture_artifacts: false,
             timeout: Duration::from_secs(30),
@@ -116,6 +128,180 @@ pub enum ScrollMode {
     Custom(String),
 }
 
+/// Automatic expansion of infinite scroll and lazy-loaded content
+///
+/// Each cycle scrolls to the bottom of the page, waits for new content and
+/// measures the page height (and item count when `item_selector` is set).
+/// Expansion stops once the content has not grown for `stable_cycles`
+/// cycles, or when any budget is hit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoScrollConfig {
+    /// Maximum number of scroll-and-wait cycles
+    pub max_cycles: u32,
+
+    /// Time to wait for new content after each scroll
+    pub wait_ms: u64,
+
+    /// Cycles without growth after which the content counts as stable
+    pub stable_cycles: u32,
+
+    /// Time budget for the whole expansion
+    pub max_duration_ms: u64,
+
+    /// Stop once the page is at least this tall
+    pub max_height_px: Option<u64>,
+
+    /// Selector matching feed items, counted alongside the page height
+    pub item_selector: Option<String>,
+
+    /// Stop once at least this many items are loaded
+    pub max_items: Option<u64>,
+}
+
+impl Default for AutoScrollConfig {
+    fn default() -> Self {
+        Self {
+            max_cycles: 10,
+            wait_ms: 800,
+            stable_cycles: 2,
+            max_duration_ms: 10_000,
+            max_height_px: None,
+            item_selector: None,
+            max_items: None,
+        }
+    }
+}
+
+/// Longest wait allowed after each auto-scroll cycle
+pub const MAX_AUTO_SCROLL_WAIT_MS: u64 = 10_000;
+
+/// Longest time budget allowed for a whole auto-scroll expansion
+pub const MAX_AUTO_SCROLL_DURATION_MS: u64 = 60_000;
+
+impl AutoScrollConfig {
+    /// Reject waits and time budgets above the service limits
+    pub fn validate(&self) -> Result<(), String> {
+        if self.wait_ms > MAX_AUTO_SCROLL_WAIT_MS {
+            return Err(format!(
+                "auto_scroll.wait_ms must be at most {}",
+                MAX_AUTO_SCROLL_WAIT_MS
+            ));
+        }
+        if self.max_duration_ms > MAX_AUTO_SCROLL_DURATION_MS {
+            return Err(format!(
+                "auto_scroll.max_duration_ms must be at most {}",
+                MAX_AUTO_SCROLL_DURATION_MS
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Page size measured after a scroll cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ScrollSnapshot {
+    /// `document.body.scrollHeight` in pixels
+    pub height: u64,
+
+    /// Number of elements matching the item selector
+    pub items: u64,
+}
+
+/// Why auto-scrolling stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoScrollStop {
+    /// Content stopped growing
+    Stable,
+    /// `max_cycles` reached
+    MaxCycles,
+    /// `max_height_px` reached
+    HeightBudget,
+    /// `max_items` reached
+    ItemBudget,
+    /// `max_duration_ms` elapsed
+    TimeBudget,
+}
+
+impl AutoScrollStop {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AutoScrollStop::Stable => "stable",
+            AutoScrollStop::MaxCycles => "max_cycles",
+            AutoScrollStop::HeightBudget => "height_budget",
+            AutoScrollStop::ItemBudget => "item_budget",
+            AutoScrollStop::TimeBudget => "time_budget",
+        }
+    }
+}
+
+/// Growth detection across auto-scroll cycles
+#[derive(Debug, Clone)]
+pub struct GrowthTracker {
+    config: AutoScrollConfig,
+    last: Option<ScrollSnapshot>,
+    cycles: u32,
+    unchanged: u32,
+}
+
+impl GrowthTracker {
+    pub fn new(config: &AutoScrollConfig) -> Self {
+        Self {
+            config: config.clone(),
+            last: None,
+            cycles: 0,
+            unchanged: 0,
+        }
+    }
+
+    /// Record the page size before the first scroll
+    pub fn start(&mut self, snapshot: ScrollSnapshot) {
+        self.last = Some(snapshot);
+    }
+
+    /// Record the page size after a cycle, returning why to stop if expansion is done
+    pub fn observe(&mut self, snapshot: ScrollSnapshot) -> Option<AutoScrollStop> {
+        self.cycles += 1;
+        let grew = self
+            .last
+            .is_none_or(|last| snapshot.height > last.height || snapshot.items > last.items);
+        self.unchanged = if grew { 0 } else { self.unchanged + 1 };
+        self.last = Some(snapshot);
+
+        if self
+            .config
+            .max_height_px
+            .is_some_and(|max| snapshot.height >= max)
+        {
+            Some(AutoScrollStop::HeightBudget)
+        } else if self
+            .config
+            .max_items
+            .is_some_and(|max| snapshot.items >= max)
+        {
+            Some(AutoScrollStop::ItemBudget)
+        } else if self.unchanged >= self.config.stable_cycles.max(1) {
+            Some(AutoScrollStop::Stable)
+        } else if self.cycles >= self.config.max_cycles {
+            Some(AutoScrollStop::MaxCycles)
+        } else {
+            None
+        }
+    }
+
+    /// Cycles observed so far
+    pub fn cycles(&self) -> u32 {
+        self.cycles
+    }
+
+    /// Whether any cycle grew the content, i.e. the page expands on scroll
+    pub fn expanded(&self, initial: ScrollSnapshot) -> bool {
+        self.last
+            .is_some_and(|last| last.height > initial.height || last.items > initial.items)
+    }
+}
+
 /// Actions to perform on the page
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PageAction {
@@ -319,6 +505,10 @@ pub struct DynamicRenderResult {
 
     /// Wait conditions that were satisfied
     pub wait_conditions_met: Vec<String>,
+
+    /// Provenance of every iframe handled during rendering
+    #[serde(default)]
+    pub frames: Vec<FrameProvenance>,
 }
 
 /// Dynamic content handler trait for different rendering backends
@@ -468,6 +658,118 @@ mod tests {
         assert!(matches!(config.mode, ScrollMode::Stepped));
     }
 
+    #[test]
+    fn test_growth_tracker_stops_when_stable() {
+        let mut tracker = GrowthTracker::new(&AutoScrollConfig::default());
+        let initial = ScrollSnapshot {
+            height: 1000,
+            items: 0,
+        };
+        tracker.start(initial);
+
+        let grown = ScrollSnapshot {
+            height: 2000,
+            items: 0,
+        };
+        assert_eq!(tracker.observe(grown), None);
+        assert_eq!(tracker.observe(grown), None);
+        assert_eq!(tracker.observe(grown), Some(AutoScrollStop::Stable));
+        assert_eq!(tracker.cycles(), 3);
+        assert!(tracker.expanded(initial));
+    }
+
+    #[test]
+    fn test_growth_tracker_budgets() {
+        let config = AutoScrollConfig {
+            max_cycles: 3,
+            max_items: Some(50),
+            item_selector: Some("article".to_string()),
+            ..Default::default()
+        };
+        let mut tracker = GrowthTracker::new(&config);
+        for height in [2000, 3000] {
+            let snapshot = ScrollSnapshot { height, items: 10 };
+            assert_eq!(tracker.observe(snapshot), None);
+        }
+        let snapshot = ScrollSnapshot {
+            height: 4000,
+            items: 20,
+        };
+        assert_eq!(tracker.observe(snapshot), Some(AutoScrollStop::MaxCycles));
+
+        let mut tracker = GrowthTracker::new(&config);
+        let snapshot = ScrollSnapshot {
+            height: 2000,
+            items: 60,
+        };
+        assert_eq!(tracker.observe(snapshot), Some(AutoScrollStop::ItemBudget));
+    }
+
+    #[test]
+    fn test_auto_scroll_config_partial_json() {
+        let config: AutoScrollConfig = serde_json::from_str(r#"{"max_cycles": 4}"#).unwrap();
+        assert_eq!(config.max_cycles, 4);
+        assert_eq!(config.stable_cycles, 2);
+        assert!(config.item_selector.is_none());
+    }
+
+    #[test]
+    fn test_auto_scroll_config_limits() {
+        assert!(AutoScrollConfig::default().validate().is_ok());
+        let config = AutoScrollConfig {
+            max_duration_ms: MAX_AUTO_SCROLL_DURATION_MS + 1,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        let config = AutoScrollConfig {
+            wait_ms: u64::MAX,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_iframe_disposition() {
+        let config = IframeConfig::default();
+        assert_eq!(
+            config.disposition(true, "https://example.com/embed"),
+            FrameDisposition::Inlined
+        );
+        assert_eq!(
+            config.disposition(false, "https://widgets.example.net/"),
+            FrameDisposition::Skipped
+        );
+
+        let config = IframeConfig {
+            inline_same_origin: false,
+            render_cross_origin: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.disposition(true, "about:srcdoc"),
+            FrameDisposition::Skipped
+        );
+        assert_eq!(
+            config.disposition(false, "https://widgets.example.net/"),
+            FrameDisposition::SubDocument
+        );
+        assert_eq!(
+            config.disposition(false, "javascript:void(0)"),
+            FrameDisposition::Skipped
+        );
+    }
+
+    #[test]
+    fn test_iframe_time_budget() {
+        let config = IframeConfig::default();
+        assert_eq!(config.time_budget_ms(), 1000);
+
+        let config: IframeConfig =
+            serde_json::from_str(r#"{"render_cross_origin": true, "max_frames": 3}"#).unwrap();
+        assert!(config.inline_same_origin);
+        assert_eq!(config.time_budget_ms(), 1000 + 3 * 2000);
+    }
+
     #[test]
     fn test_viewport_config_default() {
         let config = ViewportConfig::default();
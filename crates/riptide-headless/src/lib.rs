@@ -74,7 +74,10 @@ pub mod cdp;
 pub mod dynamic;
 
 // Re-export dynamic types for convenience
-pub use dynamic::{DynamicConfig, PageAction, ScrollConfig, ViewportConfig, WaitCondition};
+pub use dynamic::{
+    AutoScrollConfig, DynamicConfig, FrameDisposition, FrameProvenance, IframeConfig, PageAction,
+    ScrollConfig, ViewportConfig, WaitCondition,
+};
 
 // Backward compatibility: Module re-exports for existing code
 pub mod pool {
@@ -35,6 +35,7 @@ async fn test_render_request_minimal() {
         timeouts: None,
         artifacts: None,
         stealth_config: None,
+        iframes: None,
     };
 
     assert_eq!(req.url, "https://example.com");
@@ -53,6 +54,7 @@ async fn test_render_request_with_wait_for() {
         timeouts: None,
         artifacts: None,
         stealth_config: None,
+        iframes: None,
     };
 
     assert_eq!(req.url, "https://example.com");
@@ -87,6 +89,7 @@ async fn test_render_request_with_actions() {
         timeouts: None,
         artifacts: None,
         stealth_config: None,
+        iframes: None,
     };
 
     assert!(req.actions.is_some());
@@ -109,6 +112,7 @@ async fn test_render_request_with_artifacts() {
         timeouts: None,
         artifacts: Some(artifacts),
         stealth_config: None,
+        iframes: None,
     };
 
     assert!(req.artifacts.is_some());
@@ -135,6 +139,7 @@ async fn test_render_request_with_stealth_config() {
         timeouts: None,
         artifacts: None,
         stealth_config: Some(stealth_config),
+        iframes: None,
     };
 
     assert!(req.stealth_config.is_some());
@@ -266,6 +271,7 @@ async fn test_multiple_concurrent_requests() {
             timeouts: None,
             artifacts: None,
             stealth_config: None,
+            iframes: None,
         },
         RenderReq {
             url: "https://example.org".to_string(),
@@ -276,6 +282,7 @@ async fn test_multiple_concurrent_requests() {
             timeouts: None,
             artifacts: None,
             stealth_config: None,
+            iframes: None,
         },
     ];
 
@@ -309,6 +316,7 @@ async fn test_render_request_serialization() {
             mhtml: false,
         }),
         stealth_config: None,
+        iframes: None,
     };
 
     // Test that RenderReq can be serialized/deserialized
@@ -3,8 +3,8 @@
 //! Tests for dynamic configuration, wait conditions, scroll behavior, and page actions
 
 use riptide_headless::dynamic::{
-    DynamicCapabilities, DynamicConfig, DynamicError, PageAction, ScrollConfig, ScrollMode,
-    ViewportConfig, WaitCondition,
+    AutoScrollConfig, DynamicCapabilities, DynamicConfig, DynamicError, PageAction, ScrollConfig,
+    ScrollMode, ViewportConfig, WaitCondition,
 };
 use std::collections::HashMap;
 use std::time::Duration;
@@ -26,6 +26,8 @@ fn test_dynamic_config_with_custom_values() {
     let config = DynamicConfig {
         wait_for: Some(WaitCondition::Timeout(Duration::from_secs(5))),
         scroll: Some(ScrollConfig::default()),
+        auto_scroll: None,
+        iframes: None,
         actions: vec![PageAction::Click {
             selector: "button".to_string(),
             wait_after: None,
@@ -444,6 +446,11 @@ fn test_complex_dynamic_config() {
             after_scroll_js: Some("loadMoreContent()".to_string()),
             stop_condition: Some("noMoreContent()".to_string()),
         }),
+        auto_scroll: Some(AutoScrollConfig {
+            max_cycles: 5,
+            item_selector: Some("article".to_string()),
+            ..Default::default()
+        }),
         actions: vec![
             PageAction::Wait(WaitCondition::Selector {
                 selector: ".page-loaded".to_string(),
@@ -471,6 +478,7 @@ fn test_complex_dynamic_config() {
 
     assert!(config.wait_for.is_some());
     assert!(config.scroll.is_some());
+    assert_eq!(config.auto_scroll.as_ref().unwrap().max_cycles, 5);
     assert_eq!(config.actions.len(), 3);
     assert!(config.capture_artifacts);
     assert_eq!(config.timeout, Duration::from_secs(90));
@@ -89,6 +89,7 @@ async fn test_render_request_validation() {
         timeouts: None,
         artifacts: None,
         stealth_config: None,
+        iframes: None,
     };
 
     assert_eq!(req_empty_url.url, "");
@@ -103,6 +104,7 @@ async fn test_render_request_validation() {
         timeouts: None,
         artifacts: None,
         stealth_config: None,
+        iframes: None,
     };
 
     assert_eq!(req_invalid_url.url, "not-a-valid-url");
@@ -117,6 +119,7 @@ async fn test_render_request_validation() {
         timeouts: None,
         artifacts: None,
         stealth_config: None,
+        iframes: None,
     };
 
     assert_eq!(req_many_scrolls.scroll_steps, Some(1000));
@@ -285,6 +288,7 @@ async fn test_invalid_stealth_configuration() {
             timeouts: None,
             artifacts: None,
             stealth_config: Some(stealth_config),
+            iframes: None,
         };
 
         assert!(req.stealth_config.is_some());
@@ -387,6 +391,7 @@ async fn test_session_id_persistence() {
         timeouts: None,
         artifacts: None,
         stealth_config: None,
+        iframes: None,
     };
 
     assert_eq!(req.session_id, Some("persistent-session-123".to_string()));
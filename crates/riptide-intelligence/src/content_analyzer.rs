@@ -126,6 +126,8 @@ impl ContentAnalyzer {
         DynamicConfig {
             wait_for,
             scroll,
+            auto_scroll: None,
+            iframes: None,
             actions: Vec::new(),
             capture_artifacts: false,
             timeout: Duration::from_secs(3),
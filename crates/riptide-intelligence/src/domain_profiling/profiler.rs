@@ -9,11 +9,14 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use riptide_extraction::boilerplate::{BoilerplateClassifier, BoilerplateTweaks};
+
 use super::analyzer::SiteBaseline;
 use super::DOMAIN_REGISTRY_DIR;
 
 // Phase 10.4: Import Engine type and trait for cache functionality
 use riptide_reliability::engine_selection::{Engine, EngineCacheable};
+use riptide_reliability::gate::ContentVertical;
 
 /// Domain profile containing configuration and baseline information
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -40,6 +43,16 @@ pub struct DomainProfile {
     /// Expiration timestamp for cached engine (TTL: 7 days)
     #[serde(default)]
     pub engine_cache_expires_at: Option<DateTime<Utc>>,
+
+    /// Boilerplate classifier adjustments for this site, for
+    /// `ParserConfig::boilerplate`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub boilerplate: Option<BoilerplateTweaks>,
+
+    /// Kind of site, selecting its gate thresholds; when unset it is
+    /// inferred from the metadata tags
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vertical: Option<ContentVertical>,
 }
 
 /// Domain-specific extraction configuration
@@ -143,6 +156,8 @@ impl DomainProfile {
             preferred_engine: None,
             last_success_confidence: None,
             engine_cache_expires_at: None,
+            boilerplate: None,
+            vertical: None,
         }
     }
 
@@ -192,6 +207,44 @@ impl DomainProfile {
         self.updated_at = Utc::now();
     }
 
+    /// Set the boilerplate classifier adjustments for this site
+    pub fn set_boilerplate_tweaks(&mut self, tweaks: BoilerplateTweaks) {
+        self.boilerplate = Some(tweaks);
+        self.updated_at = Utc::now();
+    }
+
+    /// Retrain this site's boilerplate classifier on a page whose article
+    /// text is known, keeping its selectors and offset
+    ///
+    /// Returns the share of the page's blocks the new weights classify
+    /// correctly.
+    pub fn train_boilerplate(&mut self, html: &str, content_text: &str) -> f64 {
+        let mut classifier =
+            BoilerplateClassifier::with_tweaks(self.boilerplate.clone().unwrap_or_default());
+        let accuracy = classifier.learn_page(html, content_text);
+        self.set_boilerplate_tweaks(classifier.tweaks().clone());
+        accuracy
+    }
+
+    /// Set the kind of site, overriding what its tags suggest
+    pub fn set_vertical(&mut self, vertical: ContentVertical) {
+        self.vertical = Some(vertical);
+        self.updated_at = Utc::now();
+    }
+
+    /// Kind of site: the configured vertical, else the first tag naming
+    /// one, else [`ContentVertical::General`]
+    pub fn content_vertical(&self) -> ContentVertical {
+        self.vertical
+            .or_else(|| {
+                self.metadata
+                    .tags
+                    .iter()
+                    .find_map(|tag| ContentVertical::from_tag(tag))
+            })
+            .unwrap_or(ContentVertical::General)
+    }
+
     /// Update metadata
     pub fn update_metadata(&mut self, update_fn: impl FnOnce(&mut DomainMetadata)) {
         update_fn(&mut self.metadata);
@@ -681,4 +734,46 @@ mod tests {
         invalid_config.config.rate_limit = -1.0;
         assert!(ProfileManager::validate(&invalid_config).is_err());
     }
+
+    #[test]
+    fn test_boilerplate_training_persists() {
+        // The site's article lives in an <aside>, which reads as chrome by default
+        let html = r#"<html><body>
+            <div class="content"><p>Latest: markets open higher as investors weigh the
+               quarterly earnings season and the outlook for interest rates this year.</p></div>
+            <aside><p>The bridge reopened on Monday after two years of repairs, bringing
+               relief to commuters who had faced long detours through the old town.</p></aside>
+        </body></html>"#;
+        let article = "The bridge reopened on Monday after two years of repairs, bringing \
+            relief to commuters who had faced long detours through the old town.";
+
+        let mut profile = DomainProfile::new("example.com".to_string());
+        assert_eq!(profile.train_boilerplate(html, article), 1.0);
+        let tweaks = profile.boilerplate.clone().unwrap();
+        assert!(tweaks.weights.is_some());
+
+        let json = serde_json::to_string(&profile).unwrap();
+        let loaded: DomainProfile = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.boilerplate, Some(tweaks));
+
+        // Profiles saved before boilerplate tweaks existed still load
+        let mut legacy: serde_json::Value = serde_json::from_str(&json).unwrap();
+        legacy.as_object_mut().unwrap().remove("boilerplate");
+        let legacy: DomainProfile = serde_json::from_value(legacy).unwrap();
+        assert!(legacy.boilerplate.is_none());
+    }
+
+    #[test]
+    fn test_content_vertical_from_tags_and_override() {
+        let mut profile = DomainProfile::new("shop.example.com".to_string());
+        assert_eq!(profile.content_vertical(), ContentVertical::General);
+
+        profile.update_metadata(|m| m.tags = vec!["featured".to_string(), "Shop".to_string()]);
+        assert_eq!(profile.content_vertical(), ContentVertical::Ecommerce);
+
+        profile.set_vertical(ContentVertical::Forum);
+        assert_eq!(profile.content_vertical(), ContentVertical::Forum);
+        let json = serde_json::to_string(&profile).unwrap();
+        assert!(json.contains(r#""vertical":"forum""#));
+    }
 }
@@ -3,6 +3,7 @@
 //! This crate provides a vendor-agnostic abstraction layer for Large Language Models (LLMs)
 //! with built-in safety guarantees including timeouts, circuit breakers, and fallback chains.
 
+use riptide_types::{ErrorCode, HasErrorCode};
 use std::sync::Arc;
 use thiserror::Error;
 
@@ -33,6 +34,7 @@ pub mod runtime_switch;
 pub mod smart_retry;
 pub mod tenant_isolation;
 pub mod timeout;
+pub mod usage;
 
 #[cfg(feature = "mock")]
 pub mod mock_provider;
@@ -96,6 +98,7 @@ pub use smart_retry::{RetryConfig, Retryable, SmartRetry, SmartRetryStrategy};
 pub use table_analyzer::TableAnalyzer;
 pub use tenant_isolation::{RequestPermit, TenantIsolationManager, TenantState, TenantStatus};
 pub use timeout::{with_custom_timeout, with_timeout, TimeoutWrapper};
+pub use usage::UsageReportingProvider;
 
 #[cfg(feature = "mock")]
 pub use mock_provider::MockLlmProvider;
@@ -128,6 +131,21 @@ pub enum IntelligenceError {
     Network(String),
 }
 
+impl HasErrorCode for IntelligenceError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            IntelligenceError::Provider(_)
+            | IntelligenceError::AllProvidersFailed
+            | IntelligenceError::Network(_) => ErrorCode::LlmFailed,
+            IntelligenceError::Timeout { .. } => ErrorCode::Timeout,
+            IntelligenceError::CircuitOpen { .. } => ErrorCode::CircuitOpen,
+            IntelligenceError::Configuration(_) => ErrorCode::ConfigInvalid,
+            IntelligenceError::RateLimit { .. } => ErrorCode::RateLimited,
+            IntelligenceError::InvalidRequest(_) => ErrorCode::InvalidRequest,
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, IntelligenceError>;
 
 /// Intelligence client that combines all safety features
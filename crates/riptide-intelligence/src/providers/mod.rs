@@ -26,10 +26,20 @@ pub use openai::OpenAIProvider;
 
 use std::sync::Arc;
 
-use crate::{registry::ProviderConfig, IntelligenceError, LlmProvider, Result};
+use crate::{
+    registry::ProviderConfig, usage::UsageReportingProvider, IntelligenceError, LlmProvider, Result,
+};
 
 /// Factory function to create providers from configuration
+///
+/// Providers charge their completion tokens to the current
+/// [`UsageScope`](riptide_types::usage::UsageScope).
 pub fn create_provider_from_config(config: &ProviderConfig) -> Result<Arc<dyn LlmProvider>> {
+    let provider = create_builtin_provider(config)?;
+    Ok(Arc::new(UsageReportingProvider::new(provider)))
+}
+
+fn create_builtin_provider(config: &ProviderConfig) -> Result<Arc<dyn LlmProvider>> {
     match config.provider_type.as_str() {
         "openai" => {
             let api_key = get_config_string(config, "api_key")?;
@@ -0,0 +1,80 @@
+//! Token usage reporting for LLM providers
+
+use async_trait::async_trait;
+use riptide_types::usage::UsageScope;
+use std::sync::Arc;
+
+use crate::{CompletionRequest, CompletionResponse, Cost, LlmCapabilities, LlmProvider, Result};
+
+/// Wrapper charging the tokens of every completion to the current
+/// [`UsageScope`], so crawl metering sees what enrichment spent
+pub struct UsageReportingProvider {
+    inner: Arc<dyn LlmProvider>,
+}
+
+impl UsageReportingProvider {
+    pub fn new(provider: Arc<dyn LlmProvider>) -> Self {
+        Self { inner: provider }
+    }
+
+    /// Get the wrapped provider
+    pub fn inner(&self) -> &Arc<dyn LlmProvider> {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl LlmProvider for UsageReportingProvider {
+    async fn complete(&self, request: CompletionRequest) -> Result<CompletionResponse> {
+        let response = self.inner.complete(request).await?;
+        UsageScope::record_llm_tokens(u64::from(response.usage.total_tokens));
+        Ok(response)
+    }
+
+    async fn embed(&self, text: &str) -> Result<Vec<f32>> {
+        self.inner.embed(text).await
+    }
+
+    fn capabilities(&self) -> LlmCapabilities {
+        self.inner.capabilities()
+    }
+
+    fn estimate_cost(&self, tokens: usize) -> Cost {
+        self.inner.estimate_cost(tokens)
+    }
+
+    async fn health_check(&self) -> Result<()> {
+        self.inner.health_check().await
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    async fn is_available(&self) -> bool {
+        self.inner.is_available().await
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use super::*;
+    use crate::mock_provider::MockLlmProvider;
+    use crate::provider::Message;
+
+    #[tokio::test]
+    async fn test_completion_tokens_are_charged_to_scope() {
+        let provider = UsageReportingProvider::new(Arc::new(MockLlmProvider::new()));
+        let request = CompletionRequest::new("mock-gpt-3.5", vec![Message::user("Hello there")]);
+
+        let usage = Arc::new(UsageScope::default());
+        let response = usage
+            .clone()
+            .scope(provider.complete(request))
+            .await
+            .unwrap();
+
+        assert!(response.usage.total_tokens > 0);
+        assert_eq!(usage.llm_tokens(), u64::from(response.usage.total_tokens));
+    }
+}
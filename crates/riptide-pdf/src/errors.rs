@@ -1,3 +1,4 @@
+use riptide_types::{ErrorCode, HasErrorCode};
 use serde::{Deserialize, Serialize};
 
 /// Errors that can occur during PDF processing
@@ -80,6 +81,22 @@ impl From<anyhow::Error> for PdfError {
     }
 }
 
+impl HasErrorCode for PdfError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            PdfError::InvalidPdf { .. }
+            | PdfError::CorruptedPdf { .. }
+            | PdfError::UnsupportedVersion { .. } => ErrorCode::PdfInvalid,
+            PdfError::EncryptedPdf => ErrorCode::PdfEncrypted,
+            PdfError::FileTooLarge { .. } => ErrorCode::PayloadTooLarge,
+            PdfError::Timeout { .. } => ErrorCode::Timeout,
+            PdfError::MemoryLimit { .. } => ErrorCode::ResourceExhausted,
+            PdfError::ProcessingError { .. } => ErrorCode::ExtractionFailed,
+            PdfError::IoError { .. } => ErrorCode::StorageFailed,
+        }
+    }
+}
+
 /// Result type for PDF operations
 pub type PdfResult<T> = Result<T, PdfError>;
 
@@ -133,6 +150,18 @@ mod tests {
         let _error_trait: &dyn std::error::Error = &error;
     }
 
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(PdfError::EncryptedPdf.error_code(), ErrorCode::PdfEncrypted);
+        assert_eq!(
+            PdfError::CorruptedPdf {
+                message: "bad xref".to_string()
+            }
+            .error_code(),
+            ErrorCode::PdfInvalid
+        );
+    }
+
     #[test]
     fn test_pdf_result_type() {
         let success: PdfResult<String> = Ok("Success".to_string());
@@ -5,6 +5,8 @@
 use super::metrics::PdfMetricsCollector;
 use super::types::ExtractedDoc;
 use super::*;
+use riptide_types::language::{detect_language_spans, dominant_language};
+use riptide_types::references::parse_reference_section;
 use riptide_types::ParserMetadata;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -164,6 +166,10 @@ impl PdfPipelineIntegration {
         // Safe conversion: usize to u32 with saturation (word counts rarely exceed u32::MAX)
         let word_count = u32::try_from(text_content.split_whitespace().count()).unwrap_or(u32::MAX);
         let reading_time = Some(utils::estimate_reading_time(word_count));
+        // Papers end with a references section; its DOIs and arXiv IDs are
+        // what research crawls follow
+        let references = parse_reference_section(&text_content);
+        let language_spans = detect_language_spans(&text_content);
 
         ExtractedDoc {
             url: url.unwrap_or("pdf://document").to_string(),
@@ -174,7 +180,7 @@ impl PdfPipelineIntegration {
             text: text_content,
             links: Vec::new(), // PDFs typically don't have external links in our extraction
             media,
-            language: None, // Could be detected from text content
+            language: dominant_language(&language_spans),
             reading_time,
             quality_score: Some(if result.success { 85 } else { 30 }),
             word_count: Some(word_count),
@@ -186,6 +192,20 @@ impl PdfPipelineIntegration {
                 extraction_path: None,
                 primary_error: None,
             }),
+            provenance: None,
+            translation: None,
+            structured_data: Vec::new(),
+            annotations: None,
+            feed: None,
+            feed_links: Vec::new(),
+            classification: None,
+            clean_html: None,
+            media_assets: Vec::new(),
+            embedded_media: Vec::new(),
+            images: Vec::new(),
+            references,
+            language_spans,
+            quality_report: None,
             categories: vec!["document".to_string(), "pdf".to_string()],
             site_name: metadata_obj.producer.clone(),
             description: metadata_obj.subject.clone(),
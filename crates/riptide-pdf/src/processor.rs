@@ -717,6 +717,8 @@ impl PdfiumProcessor {
             // Calculate reading time
             let word_count = text.split_whitespace().count() as u32;
             let reading_time = Some(utils::estimate_reading_time(word_count));
+            let references = riptide_types::references::parse_reference_section(&text);
+            let language_spans = riptide_types::language::detect_language_spans(&text);
 
             Ok(super::types::ExtractedDoc {
                 url: "pdf://document".to_string(),
@@ -727,7 +729,7 @@ impl PdfiumProcessor {
                 text: text.clone(),
                 links,
                 media,
-                language: None,
+                language: riptide_types::language::dominant_language(&language_spans),
                 reading_time,
                 quality_score: Some(85),
                 word_count: Some(word_count),
@@ -739,6 +741,20 @@ impl PdfiumProcessor {
                     extraction_path: None,
                     primary_error: None,
                 }),
+                provenance: None,
+                translation: None,
+                structured_data: Vec::new(),
+                annotations: None,
+                feed: None,
+                feed_links: Vec::new(),
+                classification: None,
+                clean_html: None,
+                media_assets: Vec::new(),
+                embedded_media: Vec::new(),
+                images: Vec::new(),
+                references,
+                language_spans,
+                quality_report: None,
                 categories: vec!["document".to_string(), "pdf".to_string()],
                 site_name: metadata.get("producer").cloned(),
                 description: metadata.get("subject").cloned(),
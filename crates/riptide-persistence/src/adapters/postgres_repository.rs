@@ -87,6 +87,9 @@ impl<T> PostgresRepository<T> {
     }
 
     /// Build WHERE clause from filter fields
+    ///
+    /// Fields are compared as JSONB, so filter values match by JSON type
+    /// (the number `2` does not match the string `"2"`).
     fn build_where_clause(&self, filter: &RepositoryFilter) -> (String, Vec<serde_json::Value>) {
         if filter.fields.is_empty() {
             return (String::new(), Vec::new());
@@ -97,7 +100,7 @@ impl<T> PostgresRepository<T> {
         let mut param_count = 1;
 
         for (field, value) in &filter.fields {
-            conditions.push(format!("data->>'{}' = ${}", field, param_count));
+            conditions.push(format!("data->'{}' = ${}", field, param_count));
             values.push(value.clone());
             param_count += 1;
         }
@@ -106,6 +109,8 @@ impl<T> PostgresRepository<T> {
     }
 
     /// Build ORDER BY clause from filter sort
+    ///
+    /// Sorts on the JSONB value so numbers order numerically.
     fn build_order_clause(&self, filter: &RepositoryFilter) -> String {
         if filter.sort.is_empty() {
             return String::from("ORDER BY created_at DESC");
@@ -116,7 +121,7 @@ impl<T> PostgresRepository<T> {
             .iter()
             .map(|(field, ascending)| {
                 let direction = if *ascending { "ASC" } else { "DESC" };
-                format!("data->>'{}' {}", field, direction)
+                format!("data->'{}' {}", field, direction)
             })
             .collect();
 
@@ -336,8 +341,8 @@ mod tests {
         let (clause, values) = repo.build_where_clause(&filter);
 
         assert!(clause.contains("WHERE"));
-        assert!(clause.contains("data->>'status'"));
-        assert!(clause.contains("data->>'type'"));
+        assert!(clause.contains("data->'status' = $"));
+        assert!(clause.contains("data->'type' = $"));
         assert_eq!(values.len(), 2);
     }
 
@@ -11,6 +11,8 @@ use crate::{
 };
 use chrono::{DateTime, Utc};
 use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use riptide_types::error::{Result as RiptideResult, RiptideError};
+use riptide_types::ports::checkpoint::{validate_checkpoint_id, CheckpointStore};
 use riptide_types::ports::session::{Session, SessionStorage};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -677,6 +679,11 @@ impl StateManager {
         }
     }
 
+    /// Checkpoint storage, also usable as a [`CheckpointStore`] by crawlers
+    pub fn checkpoint_manager(&self) -> Arc<CheckpointManager> {
+        Arc::clone(&self.checkpoint_manager)
+    }
+
     /// Create system checkpoint
     pub async fn create_checkpoint(
         &self,
@@ -941,6 +948,12 @@ impl ConfigurationManager {
     }
 }
 
+/// Subdirectory holding [`CheckpointStore`] entries
+///
+/// Crawl checkpoints are overwritten in place under a fixed id, so they live
+/// outside the rotated system checkpoints and never evict each other.
+const STORE_DIR: &str = "store";
+
 /// Checkpoint manager for persistent storage
 pub struct CheckpointManager {
     config: StateConfig,
@@ -949,8 +962,16 @@ pub struct CheckpointManager {
 
 impl CheckpointManager {
     async fn new(config: StateConfig) -> PersistenceResult<Self> {
-        let checkpoints_dir = PathBuf::from("./data/checkpoints");
-        fs::create_dir_all(&checkpoints_dir).await?;
+        Self::open("./data/checkpoints", config).await
+    }
+
+    /// Checkpoint manager keeping its files under `dir`
+    ///
+    /// [`CheckpointStore`] entries go to a `store` subdirectory, which is not
+    /// subject to `max_checkpoints` rotation.
+    pub async fn open(dir: impl Into<PathBuf>, config: StateConfig) -> PersistenceResult<Self> {
+        let checkpoints_dir = dir.into();
+        fs::create_dir_all(checkpoints_dir.join(STORE_DIR)).await?;
 
         Ok(Self {
             config,
@@ -958,9 +979,31 @@ impl CheckpointManager {
         })
     }
 
+    fn checkpoint_path(&self, checkpoint_id: &str) -> PathBuf {
+        self.checkpoints_dir.join(format!("{}.ckpt", checkpoint_id))
+    }
+
+    fn store_path(&self, checkpoint_id: &str) -> PathBuf {
+        self.checkpoints_dir
+            .join(STORE_DIR)
+            .join(format!("{}.ckpt", checkpoint_id))
+    }
+
+    /// Write `data` to a temporary file next to `path`, then rename it into
+    /// place so readers never see a partially written checkpoint
+    async fn write_atomic(path: &Path, data: &[u8]) -> PersistenceResult<()> {
+        let temp_path = path.with_extension(format!("{}.tmp", Uuid::new_v4()));
+        fs::write(&temp_path, data).await?;
+        if let Err(e) = fs::rename(&temp_path, path).await {
+            let _ = fs::remove_file(&temp_path).await;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
     async fn store_checkpoint(&self, checkpoint_id: &str, data: &[u8]) -> PersistenceResult<()> {
-        let file_path = self.checkpoints_dir.join(format!("{}.ckpt", checkpoint_id));
-        fs::write(&file_path, data).await?;
+        let file_path = self.checkpoint_path(checkpoint_id);
+        Self::write_atomic(&file_path, data).await?;
 
         // Clean up old checkpoints
         self.cleanup_old_checkpoints().await?;
@@ -976,7 +1019,7 @@ impl CheckpointManager {
     }
 
     async fn load_checkpoint(&self, checkpoint_id: &str) -> PersistenceResult<Vec<u8>> {
-        let file_path = self.checkpoints_dir.join(format!("{}.ckpt", checkpoint_id));
+        let file_path = self.checkpoint_path(checkpoint_id);
         let data = fs::read(&file_path).await?;
 
         debug!(
@@ -1021,6 +1064,34 @@ impl CheckpointManager {
     }
 }
 
+#[async_trait::async_trait]
+impl CheckpointStore for CheckpointManager {
+    async fn save(&self, checkpoint_id: &str, data: &[u8]) -> RiptideResult<()> {
+        validate_checkpoint_id(checkpoint_id)?;
+        Self::write_atomic(&self.store_path(checkpoint_id), data)
+            .await
+            .map_err(|e| RiptideError::Storage(e.to_string()))
+    }
+
+    async fn load(&self, checkpoint_id: &str) -> RiptideResult<Option<Vec<u8>>> {
+        validate_checkpoint_id(checkpoint_id)?;
+        match fs::read(self.store_path(checkpoint_id)).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(&self, checkpoint_id: &str) -> RiptideResult<()> {
+        validate_checkpoint_id(checkpoint_id)?;
+        match fs::remove_file(self.store_path(checkpoint_id)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
 /// Hot reload watcher for configuration files
 pub struct HotReloadWatcher {
     _watcher: RecommendedWatcher,
@@ -5,6 +5,13 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "wasm-pool")]
 use wasmtime::ResourceLimiter;
 
+/// Fuel one extraction may consume by default, as for riptide-extraction's
+/// WASM extractor
+pub const DEFAULT_FUEL_LIMIT: u64 = 1_000_000;
+
+/// Interval at which the pool advances the engine epoch
+pub const EPOCH_TICK_MS: u64 = 10;
+
 /// Configuration for the pool and extractor component
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExtractorConfig {
@@ -15,7 +22,11 @@ pub struct ExtractorConfig {
     pub extraction_timeout: Option<u64>,
     pub max_pool_size: usize,
     pub initial_pool_size: usize,
+    /// CPU time one extraction may run before it is interrupted, enforced
+    /// with wasmtime epoch interruption
     pub epoch_timeout_ms: u64,
+    /// Fuel one extraction may consume; `None` disables fuel metering
+    pub fuel_limit: Option<u64>,
     pub health_check_interval: u64,
     pub memory_limit: Option<usize>,
     pub circuit_breaker_timeout: u64,
@@ -35,6 +46,7 @@ impl Default for ExtractorConfig {
             max_pool_size: 8,
             initial_pool_size: 2,
             epoch_timeout_ms: 60000,
+            fuel_limit: Some(DEFAULT_FUEL_LIMIT),
             health_check_interval: 30000,
             memory_limit: Some(512 * 1024 * 1024), // 512MB
             circuit_breaker_timeout: 5000,
@@ -45,7 +57,7 @@ impl Default for ExtractorConfig {
 }
 
 impl ExtractorConfig {
-    /// Load configuration from environment variables (12 fields)
+    /// Load configuration from environment variables (14 fields)
     pub fn from_env() -> Self {
         let mut config = Self::default();
 
@@ -87,6 +99,12 @@ impl ExtractorConfig {
                 config.epoch_timeout_ms = val;
             }
         }
+        if let Ok(val) = std::env::var("POOL_FUEL_LIMIT") {
+            if let Ok(val) = val.parse::<u64>() {
+                // 0 disables fuel metering
+                config.fuel_limit = (val > 0).then_some(val);
+            }
+        }
         if let Ok(val) = std::env::var("POOL_HEALTH_CHECK_INTERVAL_MS") {
             if let Ok(val) = val.parse() {
                 config.health_check_interval = val;
@@ -131,8 +149,30 @@ impl ExtractorConfig {
         if self.circuit_breaker_failure_threshold == 0 {
             return Err("circuit_breaker_failure_threshold must be greater than 0".to_string());
         }
+        if self.epoch_timeout_ms == 0 {
+            return Err("epoch_timeout_ms must be greater than 0".to_string());
+        }
+        if self.fuel_limit == Some(0) {
+            return Err("fuel_limit must be greater than 0".to_string());
+        }
         Ok(())
     }
+
+    /// Epoch ticks an extraction may run for, given [`EPOCH_TICK_MS`]
+    pub fn epoch_deadline_ticks(&self) -> u64 {
+        self.epoch_timeout_ms.div_ceil(EPOCH_TICK_MS).max(1)
+    }
+
+    /// Wasmtime configuration for engines used with this pool: component
+    /// model, epoch interruption, and fuel when `fuel_limit` is set
+    #[cfg(feature = "wasm-pool")]
+    pub fn wasmtime_config(&self) -> wasmtime::Config {
+        let mut config = wasmtime::Config::new();
+        config.wasm_component_model(true);
+        config.epoch_interruption(true);
+        config.consume_fuel(self.fuel_limit.is_some());
+        config
+    }
 }
 
 /// Performance metrics for extraction and pool operations
@@ -153,7 +193,16 @@ pub struct PerformanceMetrics {
     pub wasm_peak_memory_pages: u64,
     pub wasm_grow_failed_total: u64,
     pub failed_extractions: u64,
+    /// Extractions interrupted for exceeding `epoch_timeout_ms`
     pub epoch_timeouts: u64,
+    /// Extractions stopped for running out of fuel
+    pub fuel_exhaustions: u64,
+    /// Fuel consumed by all extractions
+    pub fuel_consumed: u64,
+    /// Extractor components switched in without a restart
+    pub component_reloads: u64,
+    /// Reloads rejected at compilation, validation or the probe extraction
+    pub component_reload_failures: u64,
     /// P2-2: WIT validation metrics
     pub wit_validations_total: u64,
     pub wit_validations_passed: u64,
@@ -47,6 +47,9 @@ pub struct PoolHealthStatus {
     pub fallback_rate_percent: f64,
     /// Memory usage statistics
     pub memory_stats: MemoryHealthStats,
+    /// Fuel and CPU time limits and how often extractions hit them
+    #[serde(default)]
+    pub execution_limits: ExecutionLimitStats,
     /// Last health check timestamp
     #[serde(skip)]
     pub last_check: Option<Instant>,
@@ -67,6 +70,21 @@ pub struct MemoryHealthStats {
     pub memory_pressure: MemoryPressureLevel,
 }
 
+/// Execution limits of extractions and trips against them
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExecutionLimitStats {
+    /// Fuel one extraction may consume, if fuel is metered
+    pub fuel_limit: Option<u64>,
+    /// CPU time one extraction may run, in milliseconds
+    pub cpu_time_limit_ms: u64,
+    /// Extractions stopped for running out of fuel
+    pub fuel_exhaustions: u64,
+    /// Extractions interrupted for exceeding the CPU time limit
+    pub cpu_timeouts: u64,
+    /// Fuel consumed by all extractions
+    pub fuel_consumed: u64,
+}
+
 /// Overall health levels
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum HealthLevel {
@@ -264,6 +282,13 @@ impl PoolHealthMonitor {
                 grow_failures: metrics.wasm_grow_failed_total,
                 memory_pressure,
             },
+            execution_limits: ExecutionLimitStats {
+                fuel_limit: self.config.fuel_limit,
+                cpu_time_limit_ms: self.config.epoch_timeout_ms,
+                fuel_exhaustions: metrics.fuel_exhaustions,
+                cpu_timeouts: metrics.epoch_timeouts,
+                fuel_consumed: metrics.fuel_consumed,
+            },
             last_check: Some(Instant::now()),
             trend,
         };
@@ -488,6 +513,7 @@ impl PoolHealthMonitor {
                 "success_rate_percent": latest.success_rate_percent,
                 "fallback_rate_percent": latest.fallback_rate_percent,
                 "memory_pressure": latest.memory_stats.memory_pressure,
+                "execution_limits": latest.execution_limits,
                 "trend": latest.trend,
                 "total_extractions": latest.total_extractions
             }))
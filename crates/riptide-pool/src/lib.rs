@@ -10,6 +10,8 @@
 //! - **Health Monitoring**: Continuous health checks and validation
 //! - **Circuit Breaker**: Fault tolerance and resilience patterns
 //! - **Memory Management**: Advanced memory allocation and cleanup
+//! - **Execution Limits**: Per-extraction WASM fuel and CPU time limits
+//! - **Hot Reload**: Swap the extractor component without a restart
 //! - **Event Integration**: Pub/sub messaging for pool operations
 //!
 //! ## Usage
@@ -19,8 +21,9 @@
 //! use wasmtime::Engine;
 //!
 //! # async fn example() -> anyhow::Result<()> {
-//! let engine = Engine::default();
 //! let config = ExtractorConfig::default();
+//! // Fuel and CPU time limits need an engine built from the pool config
+//! let engine = Engine::new(&config.wasmtime_config())?;
 //! let pool = AdvancedInstancePool::new(config, engine, "path/to/component.wasm").await?;
 //! # Ok(())
 //! # }
@@ -35,15 +38,20 @@ pub mod memory_manager;
 pub mod models;
 pub mod native_pool;
 pub mod pool;
+#[cfg(feature = "wasm-pool")]
+pub mod reload;
 
 // Re-export main public API
-pub use config::{ExtractorConfig, PerformanceMetrics, WasmResourceTracker};
+pub use config::{
+    ExtractorConfig, PerformanceMetrics, WasmResourceTracker, DEFAULT_FUEL_LIMIT, EPOCH_TICK_MS,
+};
 #[cfg(feature = "wasm-pool")]
 pub use events_integration::{EventAwareInstancePool, PoolEventEmitter};
 #[cfg(feature = "wasm-pool")]
 pub use health_monitor::PoolHealthMonitor;
 pub use health_monitor::{
-    HealthLevel, HealthTrend, MemoryHealthStats, MemoryPressureLevel, PoolHealthStatus,
+    ExecutionLimitStats, HealthLevel, HealthTrend, MemoryHealthStats, MemoryPressureLevel,
+    PoolHealthStatus,
 };
 #[cfg(feature = "wasm-pool")]
 pub use memory_manager::{
@@ -52,10 +60,12 @@ pub use memory_manager::{
 // Re-export PoolMetrics from riptide-events
 pub use models::CircuitBreakerState;
 #[cfg(feature = "wasm-pool")]
-pub use models::PooledInstance;
+pub use models::{LoadedComponent, PooledInstance};
 pub use native_pool::{
     NativeExtractorPool, NativeExtractorType, NativePoolConfig, NativePoolMetrics,
 };
 #[cfg(feature = "wasm-pool")]
 pub use pool::{create_event_aware_pool, get_instances_per_worker, AdvancedInstancePool};
+#[cfg(feature = "wasm-pool")]
+pub use reload::ComponentReloadReport;
 pub use riptide_events::types::PoolMetrics;
@@ -27,6 +27,8 @@ pub struct PooledInstance {
     pub failure_count: u64,
     pub memory_usage_bytes: u64,
     pub resource_tracker: WasmResourceTracker,
+    /// Generation of the component this instance was created from
+    pub generation: u64,
 }
 
 #[cfg(feature = "wasm-pool")]
@@ -51,9 +53,16 @@ impl PooledInstance {
             failure_count: 0,
             memory_usage_bytes: 0,
             resource_tracker: WasmResourceTracker::default(),
+            generation: 0,
         }
     }
 
+    /// Mark the instance as created from component `generation`
+    pub fn with_generation(mut self, generation: u64) -> Self {
+        self.generation = generation;
+        self
+    }
+
     /// Check if instance is healthy and reusable
     pub fn is_healthy(&self, config: &ExtractorConfig) -> bool {
         self.use_count < 1000
@@ -97,10 +106,22 @@ impl std::fmt::Debug for PooledInstance {
             .field("failure_count", &self.failure_count)
             .field("memory_usage_bytes", &self.memory_usage_bytes)
             .field("resource_tracker", &self.resource_tracker)
+            .field("generation", &self.generation)
             .finish()
     }
 }
 
+/// Extractor component the pool creates instances from
+#[cfg(feature = "wasm-pool")]
+#[derive(Clone)]
+pub struct LoadedComponent {
+    pub component: Arc<Component>,
+    /// 0 for the component the pool started with, incremented on each reload
+    pub generation: u64,
+    /// Artifact path, or a label for uploaded bytes
+    pub source: String,
+}
+
 /// Circuit breaker states for WASM error handling
 #[derive(Clone, Debug)]
 pub enum CircuitBreakerState {
@@ -409,6 +409,20 @@ impl NativeExtractorPool {
             reading_time: None,
             quality_score: None,
             parser_metadata: None,
+            provenance: None,
+            translation: None,
+            structured_data: Vec::new(),
+            annotations: None,
+            feed: None,
+            feed_links: Vec::new(),
+            classification: None,
+            clean_html: None,
+            media_assets: Vec::new(),
+            embedded_media: Vec::new(),
+            images: Vec::new(),
+            references: Vec::new(),
+            language_spans: Vec::new(),
+            quality_report: None,
             word_count: None,
             categories: Vec::new(),
             site_name: None,
@@ -1,11 +1,11 @@
 #[cfg(feature = "wasm-pool")]
-use wasmtime::{component::*, Engine};
+use wasmtime::{component::*, Engine, Trap};
 
 #[cfg(feature = "wasm-pool")]
-use super::config::{ExtractorConfig, PerformanceMetrics, WasmResourceTracker};
+use super::config::{ExtractorConfig, PerformanceMetrics, WasmResourceTracker, EPOCH_TICK_MS};
 
 #[cfg(feature = "wasm-pool")]
-use super::models::{CircuitBreakerState, PooledInstance};
+use super::models::{CircuitBreakerState, LoadedComponent, PooledInstance};
 
 #[cfg(feature = "wasm-pool")]
 use riptide_events::{Event, EventBus, EventEmitter, PoolEvent, PoolMetrics, PoolOperation};
@@ -16,6 +16,18 @@ use riptide_types::config::ExtractionMode;
 #[cfg(feature = "wasm-pool")]
 use riptide_types::extracted::{ExtractedDoc, ParserMetadata};
 
+#[cfg(feature = "wasm-pool")]
+use riptide_types::ports::{ContentExtractor, ExtractionResult};
+
+#[cfg(feature = "wasm-pool")]
+use riptide_types::{ExtractedContent, RiptideError};
+
+#[cfg(feature = "wasm-pool")]
+use riptide_extraction::validation::{ValidationReport, WitValidator};
+
+#[cfg(feature = "wasm-pool")]
+use riptide_extraction::wasm_fuel::FuelMeter;
+
 #[cfg(feature = "wasm-pool")]
 use std::collections::VecDeque;
 
@@ -23,10 +35,10 @@ use std::collections::VecDeque;
 use std::env;
 
 #[cfg(feature = "wasm-pool")]
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 
 #[cfg(feature = "wasm-pool")]
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, RwLock};
 
 #[cfg(feature = "wasm-pool")]
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -38,7 +50,7 @@ use std::time::{Duration, Instant};
 use tokio::sync::Semaphore;
 
 #[cfg(feature = "wasm-pool")]
-use tokio::time::{sleep, timeout};
+use tokio::time::timeout;
 
 #[cfg(feature = "wasm-pool")]
 use uuid::Uuid;
@@ -59,14 +71,24 @@ wasmtime::component::bindgen!({
 });
 
 /// Advanced instance pool with semaphore-based concurrency control
+///
+/// Each extraction runs with at most `fuel_limit` fuel, also drawn from the
+/// caller's [`FuelMeter`] scope, and is interrupted after `epoch_timeout_ms`
+/// of CPU time. The engine must be created from
+/// [`ExtractorConfig::wasmtime_config`] for these limits to apply.
+/// Extractions that hit either limit fail with
+/// [`RiptideError::ResourceExhausted`] instead of falling back to native
+/// extraction, which would likely stall on the same page.
 #[cfg(feature = "wasm-pool")]
 pub struct AdvancedInstancePool {
     /// Pool configuration
     pub(super) config: ExtractorConfig,
     /// Shared engine for all instances
     pub(super) engine: Arc<Engine>,
-    /// Shared component for all instances
-    pub(super) component: Arc<Component>,
+    /// Component new instances are created from, swapped on reload
+    pub(super) component: Arc<RwLock<LoadedComponent>>,
+    /// Serializes component reloads
+    pub(super) reload_lock: Arc<Mutex<()>>,
     /// Shared linker for all instances
     pub(super) linker: Arc<Linker<WasmResourceTracker>>,
     /// Available instances queue
@@ -77,9 +99,6 @@ pub struct AdvancedInstancePool {
     pub(super) metrics: Arc<Mutex<PerformanceMetrics>>,
     /// Circuit breaker state
     pub(super) circuit_state: Arc<Mutex<CircuitBreakerState>>,
-    /// Component path for creation
-    #[allow(dead_code)]
-    pub(super) component_path: String,
     /// Pool unique identifier
     pub(super) pool_id: String,
     /// Optional event bus for event emission
@@ -110,19 +129,34 @@ impl AdvancedInstancePool {
         let initial_metrics = Arc::new(Mutex::new(PerformanceMetrics::default()));
 
         // P2-2: WIT validation before instantiation
-        // Note: WIT validation is disabled to avoid circular dependency with riptide-core.
-        // Validation can be added at a higher level if needed.
         if config.enable_wit_validation {
-            warn!("WIT validation is enabled in config but not available in riptide-pool to avoid circular dependency");
+            validate_component(&component, &initial_metrics).await?;
+        }
+
+        if config.fuel_limit.is_some() {
+            wasmtime::Store::new(&engine, ()).set_fuel(0).map_err(|_| {
+                anyhow!(
+                    "fuel_limit requires an engine with fuel consumption enabled; \
+                     create it from ExtractorConfig::wasmtime_config"
+                )
+            })?;
         }
 
         let linker: wasmtime::component::Linker<WasmResourceTracker> =
             wasmtime::component::Linker::new(&engine);
 
+        let engine = Arc::new(engine);
+        spawn_epoch_ticker(Arc::downgrade(&engine))?;
+
         let pool = Self {
             config: config.clone(),
-            engine: Arc::new(engine),
-            component: Arc::new(component),
+            engine,
+            component: Arc::new(RwLock::new(LoadedComponent {
+                component: Arc::new(component),
+                generation: 0,
+                source: component_path.to_string(),
+            })),
+            reload_lock: Arc::new(Mutex::new(())),
             linker: Arc::new(linker),
             available_instances: Arc::new(Mutex::new(VecDeque::with_capacity(
                 config.max_pool_size,
@@ -134,7 +168,6 @@ impl AdvancedInstancePool {
                 success_count: 0,
                 last_failure: None,
             })),
-            component_path: component_path.to_string(),
             pool_id: Uuid::new_v4().to_string(),
             event_bus: None,
             pending_acquisitions: Arc::new(AtomicUsize::new(0)),
@@ -206,6 +239,16 @@ impl AdvancedInstancePool {
         url: &str,
         mode: ExtractionMode,
     ) -> Result<ExtractedDoc> {
+        // The component's tl parser only understands CSS, so XPath selectors run here
+        if let ExtractionMode::Custom(selectors) = &mode {
+            if selectors
+                .iter()
+                .any(|selector| riptide_extraction::xpath::is_xpath(selector))
+            {
+                return Self::extract_custom_selectors(html, url, selectors);
+            }
+        }
+
         // Check circuit breaker
         if self.is_circuit_open().await {
             return self.fallback_extract(html, url, mode).await;
@@ -321,6 +364,10 @@ impl AdvancedInstancePool {
 
         match extraction_result {
             Ok(doc) => Ok(doc),
+            Err(e) if is_resource_exhausted(&e) => {
+                warn!(url = %url, error = %e, "WASM extraction stopped at execution limit");
+                Err(e)
+            }
             Err(e) => {
                 // Fallback to native extraction if WASM fails
                 tracing::warn!(
@@ -372,6 +419,11 @@ impl AdvancedInstancePool {
         }; // Lock dropped here
 
         if let Some(instance) = maybe_instance {
+            if instance.generation != self.component_generation().await {
+                // Left over from before a reload; dropped like an unhealthy one
+                debug!(instance_id = %instance.id, "Discarding instance of replaced component");
+                return self.create_instance().await;
+            }
             if instance.is_healthy(&self.config) {
                 return Ok(instance);
             }
@@ -408,12 +460,14 @@ impl AdvancedInstancePool {
     pub async fn create_instance(&self) -> Result<PooledInstance> {
         debug!("Creating new WASM instance");
 
+        let loaded = self.component.read().await.clone();
         let instance = PooledInstance::new(
             self.engine.clone(),
-            self.component.clone(),
+            loaded.component,
             self.linker.clone(),
             self.config.memory_limit_pages.unwrap_or(256) as usize,
-        );
+        )
+        .with_generation(loaded.generation);
 
         debug!(instance_id = %instance.id, "New WASM instance created");
 
@@ -445,17 +499,21 @@ impl AdvancedInstancePool {
         // Create fresh store to prevent state leaks
         let mut store = instance.create_fresh_store();
 
-        // Set epoch deadline for timeout handling
-        store.set_epoch_deadline(self.config.epoch_timeout_ms);
+        // Interrupt the extraction after epoch_timeout_ms of CPU time
+        store.set_epoch_deadline(self.config.epoch_deadline_ticks());
 
-        // Spawn epoch advancement task
-        let engine_weak = Arc::downgrade(&instance.engine);
-        tokio::spawn(async move {
-            sleep(Duration::from_millis(30000)).await; // 30 second timeout
-            if let Some(engine) = engine_weak.upgrade() {
-                engine.increment_epoch();
+        let fuel = self.config.fuel_limit.map(FuelMeter::grant);
+        if let Some(fuel) = fuel {
+            if fuel == 0 {
+                self.record_fuel_exhaustion().await;
+                return Err(RiptideError::ResourceExhausted {
+                    resource: "wasm_fuel".to_string(),
+                    limit: 0,
+                }
+                .into());
             }
-        });
+            store.set_fuel(fuel)?;
+        }
 
         // Instantiate component with fresh bindings
         let bindings = Extractor::instantiate(&mut store, &instance.component, &*instance.linker)
@@ -469,6 +527,12 @@ impl AdvancedInstancePool {
             .interface0
             .call_extract(&mut store, html, url, &wit_mode);
 
+        if let Some(fuel) = fuel {
+            let consumed = fuel.saturating_sub(store.get_fuel().unwrap_or(0));
+            FuelMeter::charge(consumed);
+            self.metrics.lock().await.fuel_consumed += consumed;
+        }
+
         match result {
             Ok(Ok(content)) => {
                 // Success - convert to internal format
@@ -492,6 +556,20 @@ impl AdvancedInstancePool {
                         extraction_path: None,
                         primary_error: None,
                     }),
+                    provenance: None,
+                    translation: None,
+                    structured_data: Vec::new(),
+                    annotations: None,
+                    feed: None,
+                    feed_links: Vec::new(),
+                    classification: None,
+                    clean_html: None,
+                    media_assets: Vec::new(),
+                    embedded_media: Vec::new(),
+                    images: Vec::new(),
+                    references: Vec::new(),
+                    language_spans: Vec::new(),
+                    quality_report: None,
                     word_count: content.word_count,
                     categories: content.categories,
                     site_name: content.site_name,
@@ -502,14 +580,34 @@ impl AdvancedInstancePool {
             Ok(Err(extraction_error)) => {
                 Err(anyhow!("WASM extraction error: {:?}", extraction_error))
             }
-            Err(e) => Err(anyhow!("Component call failed: {}", e)),
+            Err(e) => match e.downcast_ref::<Trap>() {
+                Some(Trap::OutOfFuel) => {
+                    self.record_fuel_exhaustion().await;
+                    Err(RiptideError::ResourceExhausted {
+                        resource: "wasm_fuel".to_string(),
+                        limit: fuel.unwrap_or_default(),
+                    }
+                    .into())
+                }
+                Some(Trap::Interrupt) => {
+                    self.record_epoch_timeout().await;
+                    Err(RiptideError::ResourceExhausted {
+                        resource: "cpu_time_ms".to_string(),
+                        limit: self.config.epoch_timeout_ms,
+                    }
+                    .into())
+                }
+                _ => Err(anyhow!("Component call failed: {}", e)),
+            },
         }
     }
 
     /// Return instance to pool
     pub async fn return_instance(&self, instance: PooledInstance) {
         let instance_id = instance.id.clone();
-        let is_healthy = instance.is_healthy(&self.config);
+        // Instances of a replaced component are drained as they come back
+        let is_healthy = instance.is_healthy(&self.config)
+            && instance.generation == self.component_generation().await;
 
         if is_healthy {
             // Add healthy instance back to pool
@@ -559,6 +657,34 @@ impl AdvancedInstancePool {
         }
     }
 
+    /// Custom-mode extraction on the host for selector lists containing XPath
+    fn extract_custom_selectors(
+        html: &str,
+        url: &str,
+        selectors: &[String],
+    ) -> Result<ExtractedDoc> {
+        use scraper::{Html, Selector};
+        let document = Html::parse_document(html);
+
+        let text = riptide_extraction::xpath::select_text(&document, selectors)
+            .map_err(|e| anyhow!("Custom selector extraction failed: {}", e))?
+            .join("\n\n");
+        let title = Selector::parse("title").ok().and_then(|selector| {
+            document
+                .select(&selector)
+                .next()
+                .map(|element| element.text().collect::<String>().trim().to_string())
+                .filter(|title| !title.is_empty())
+        });
+
+        Ok(ExtractedDoc {
+            url: url.to_string(),
+            title,
+            text,
+            ..Default::default()
+        })
+    }
+
     /// Fallback to native extraction
     async fn fallback_extract(
         &self,
@@ -931,12 +1057,17 @@ impl AdvancedInstancePool {
     }
 
     /// Record epoch timeout
-    #[allow(dead_code)]
     async fn record_epoch_timeout(&self) {
         let mut metrics = self.metrics.lock().await;
         metrics.epoch_timeouts += 1;
     }
 
+    /// Record an extraction stopped for running out of fuel
+    async fn record_fuel_exhaustion(&self) {
+        let mut metrics = self.metrics.lock().await;
+        metrics.fuel_exhaustions += 1;
+    }
+
     /// Update semaphore wait time metric
     async fn update_semaphore_wait_time(&self, wait_time: Duration) {
         let mut metrics = self.metrics.lock().await;
@@ -971,6 +1102,17 @@ impl AdvancedInstancePool {
         &self.pool_id
     }
 
+    /// Generation of the component serving extractions: 0 for the one the
+    /// pool started with, incremented on each reload
+    pub async fn component_generation(&self) -> u64 {
+        self.component.read().await.generation
+    }
+
+    /// Artifact path or upload label of the component serving extractions
+    pub async fn component_source(&self) -> String {
+        self.component.read().await.source.clone()
+    }
+
     /// Create pool metrics for event emission
     pub async fn get_pool_metrics_for_events(&self) -> PoolMetrics {
         let (available, active, total) = self.get_pool_status().await;
@@ -1014,6 +1156,52 @@ impl AdvancedInstancePool {
     }
 }
 
+/// Advance the engine epoch every [`EPOCH_TICK_MS`] until the engine is
+/// dropped, so extractions past their epoch deadline are interrupted
+#[cfg(feature = "wasm-pool")]
+fn spawn_epoch_ticker(engine: Weak<Engine>) -> Result<()> {
+    std::thread::Builder::new()
+        .name("riptide-pool-epoch".to_string())
+        .spawn(move || {
+            while let Some(strong) = engine.upgrade() {
+                strong.increment_epoch();
+                drop(strong);
+                std::thread::sleep(Duration::from_millis(EPOCH_TICK_MS));
+            }
+        })?;
+    Ok(())
+}
+
+/// Check `component` with the [`WitValidator`], recording the outcome in
+/// the pool's WIT validation metrics
+#[cfg(feature = "wasm-pool")]
+pub(super) async fn validate_component(
+    component: &Component,
+    metrics: &Mutex<PerformanceMetrics>,
+) -> Result<ValidationReport> {
+    let report = WitValidator::strict().validate_or_error(component);
+
+    let mut metrics = metrics.lock().await;
+    metrics.wit_validations_total += 1;
+    match &report {
+        Ok(report) => {
+            metrics.wit_validations_passed += 1;
+            metrics.wit_validation_warnings += report.warnings.len() as u64;
+        }
+        Err(_) => metrics.wit_validations_failed += 1,
+    }
+    report
+}
+
+/// Whether `error` is a fuel or CPU time trip
+#[cfg(feature = "wasm-pool")]
+fn is_resource_exhausted(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<RiptideError>(),
+        Some(RiptideError::ResourceExhausted { .. })
+    )
+}
+
 /// Configuration for RIPTIDE_WASM_INSTANCES_PER_WORKER environment variable
 #[cfg(feature = "wasm-pool")]
 pub fn get_instances_per_worker() -> usize {
@@ -1055,6 +1243,49 @@ impl EventEmitter for AdvancedInstancePool {
     }
 }
 
+/// Serve the pool through the `ContentExtractor` port, in article mode
+#[async_trait]
+#[cfg(feature = "wasm-pool")]
+impl ContentExtractor for AdvancedInstancePool {
+    async fn extract(
+        &self,
+        html: &str,
+        url: &str,
+    ) -> riptide_types::error::Result<ExtractionResult> {
+        let doc = AdvancedInstancePool::extract(self, html, url, ExtractionMode::Article)
+            .await
+            .map_err(|e| match e.downcast::<RiptideError>() {
+                Ok(exhausted @ RiptideError::ResourceExhausted { .. }) => exhausted,
+                Ok(e) => RiptideError::Extraction(format!("Extraction failed: {}", e)),
+                Err(e) => RiptideError::Extraction(format!("Extraction failed: {}", e)),
+            })?;
+        let content: ExtractedContent = doc.into();
+
+        let mut metadata = std::collections::HashMap::new();
+        metadata.insert("title".to_string(), content.title);
+        if let Some(summary) = content.summary {
+            metadata.insert("summary".to_string(), summary);
+        }
+        metadata.insert("strategy".to_string(), "wasm".to_string());
+        metadata.insert("url".to_string(), content.url);
+
+        Ok(ExtractionResult {
+            text: content.content,
+            metadata,
+            quality_score: content.extraction_confidence,
+        })
+    }
+
+    fn extractor_type(&self) -> &str {
+        "wasm"
+    }
+
+    async fn is_available(&self) -> bool {
+        // An open circuit falls back to native extraction
+        true
+    }
+}
+
 /// Factory function to create an event-aware instance pool
 #[cfg(feature = "wasm-pool")]
 pub async fn create_event_aware_pool(
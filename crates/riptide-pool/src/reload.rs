@@ -0,0 +1,270 @@
+//! Hot reload of the extractor component
+//!
+//! [`AdvancedInstancePool::reload_component`] replaces the component the pool
+//! creates instances from without restarting the process:
+//!
+//! 1. the new artifact is compiled on a blocking thread
+//! 2. it is checked with the WIT validator (when `enable_wit_validation` is
+//!    set) and by running a probe extraction under the pool's fuel and CPU
+//!    time limits
+//! 3. `initial_pool_size` instances of it are created
+//! 4. the component and the idle instances are swapped in one step
+//!
+//! Idle instances of the old component are dropped at the switch. Instances
+//! in flight finish their extraction on the old component and are dropped
+//! when returned. If any step fails, the pool keeps serving the old
+//! component.
+//!
+//! [`AdvancedInstancePool::watch_component`] polls an artifact path and
+//! reloads when the file changes.
+
+use super::config::WasmResourceTracker;
+use super::models::{LoadedComponent, PooledInstance};
+use super::pool::exports::riptide::extractor::extract::ExtractionMode as WitExtractionMode;
+use super::pool::{validate_component, AdvancedInstancePool, Extractor};
+use anyhow::{anyhow, Result};
+use riptide_events::{EventBus, PoolEvent, PoolOperation};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use tokio::task::JoinHandle;
+use tracing::{info, warn};
+use wasmtime::component::Component;
+
+/// Page the new component must extract without trapping before the switch
+const PROBE_HTML: &str = "<html><head><title>Reload probe</title></head>\
+    <body><article><p>Extractor component reload probe.</p></article></body></html>";
+const PROBE_URL: &str = "https://example.com/reload-probe";
+
+/// Outcome of a component reload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentReloadReport {
+    /// Generation now serving extractions
+    pub generation: u64,
+    /// Artifact path or upload label
+    pub source: String,
+    /// Instances of the new component created before the switch
+    pub warmed_instances: usize,
+    /// Idle instances of the old component dropped at the switch
+    pub drained_instances: usize,
+    /// Extractions still running on the old component
+    pub in_flight_extractions: usize,
+    /// Non-fatal WIT validation findings
+    pub validation_warnings: Vec<String>,
+    pub duration_ms: u64,
+}
+
+impl AdvancedInstancePool {
+    /// Reload the extractor component from the artifact at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving the current component in place, when the
+    /// artifact cannot be compiled, fails validation or traps on the probe
+    /// extraction.
+    pub async fn reload_component(&self, path: impl AsRef<Path>) -> Result<ComponentReloadReport> {
+        let path = path.as_ref().to_path_buf();
+        let source = path.display().to_string();
+        let engine = self.engine.clone();
+        let compiled =
+            tokio::task::spawn_blocking(move || Component::from_file(&engine, &path)).await?;
+        self.switch_component(compiled, source).await
+    }
+
+    /// Reload the extractor component from uploaded component `bytes`,
+    /// reported under `source`
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::reload_component`].
+    pub async fn reload_component_bytes(
+        &self,
+        bytes: Vec<u8>,
+        source: &str,
+    ) -> Result<ComponentReloadReport> {
+        let engine = self.engine.clone();
+        let compiled = tokio::task::spawn_blocking(move || Component::new(&engine, &bytes)).await?;
+        self.switch_component(compiled, source.to_string()).await
+    }
+
+    /// Reload the component whenever the artifact at `path` changes,
+    /// checking its modification time every `interval`
+    ///
+    /// Replace the artifact by renaming a complete file over it: a reload of
+    /// a partly written file fails and is retried on the next change. The
+    /// task ends when the pool is dropped.
+    pub fn watch_component(
+        self: &Arc<Self>,
+        path: impl Into<PathBuf>,
+        interval: Duration,
+    ) -> JoinHandle<()> {
+        let pool = Arc::downgrade(self);
+        let path = path.into();
+        tokio::spawn(async move {
+            let mut last_modified = modified(&path);
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(pool) = pool.upgrade() else {
+                    break;
+                };
+                let current = modified(&path);
+                if current.is_none() || current == last_modified {
+                    continue;
+                }
+                last_modified = current;
+                if let Err(e) = pool.reload_component(&path).await {
+                    warn!(path = %path.display(), error = %e, "Extractor component reload failed");
+                }
+            }
+        })
+    }
+
+    async fn switch_component(
+        &self,
+        compiled: wasmtime::Result<Component>,
+        source: String,
+    ) -> Result<ComponentReloadReport> {
+        let started = Instant::now();
+        let _reload = self.reload_lock.lock().await;
+
+        let result = match compiled {
+            Ok(component) => self.install_component(component, &source, started).await,
+            Err(e) => Err(anyhow!("Failed to compile component {}: {}", source, e)),
+        };
+
+        let mut metrics = self.metrics.lock().await;
+        match &result {
+            Ok(report) => {
+                metrics.component_reloads += 1;
+                metrics.pool_size = report.warmed_instances;
+                info!(
+                    generation = report.generation,
+                    source = %source,
+                    warmed = report.warmed_instances,
+                    drained = report.drained_instances,
+                    in_flight = report.in_flight_extractions,
+                    "Extractor component reloaded"
+                );
+            }
+            Err(e) => {
+                metrics.component_reload_failures += 1;
+                warn!(source = %source, error = %e, "Keeping current extractor component");
+            }
+        }
+        result
+    }
+
+    async fn install_component(
+        &self,
+        component: Component,
+        source: &str,
+        started: Instant,
+    ) -> Result<ComponentReloadReport> {
+        let validation_warnings = if self.config.enable_wit_validation {
+            validate_component(&component, &self.metrics)
+                .await?
+                .warnings
+        } else {
+            Vec::new()
+        };
+
+        let component = Arc::new(component);
+        self.probe_component(&component)?;
+
+        // Only reloads change the generation and they hold the reload lock
+        let generation = self.component_generation().await + 1;
+        let warmed: VecDeque<PooledInstance> = (0..self.config.initial_pool_size)
+            .map(|_| {
+                PooledInstance::new(
+                    self.engine.clone(),
+                    component.clone(),
+                    self.linker.clone(),
+                    self.config.memory_limit_pages.unwrap_or(256) as usize,
+                )
+                .with_generation(generation)
+            })
+            .collect();
+        let warmed_instances = warmed.len();
+
+        let drained_instances = {
+            let mut loaded = self.component.write().await;
+            let mut instances = self.available_instances.lock().await;
+            *loaded = LoadedComponent {
+                component,
+                generation,
+                source: source.to_string(),
+            };
+            std::mem::replace(&mut *instances, warmed).len()
+        };
+
+        let report = ComponentReloadReport {
+            generation,
+            source: source.to_string(),
+            warmed_instances,
+            drained_instances,
+            in_flight_extractions: self
+                .config
+                .max_pool_size
+                .saturating_sub(self.semaphore.available_permits()),
+            validation_warnings,
+            duration_ms: started.elapsed().as_millis() as u64,
+        };
+        if let Some(event_bus) = &self.event_bus {
+            emit_reloaded(event_bus, &self.pool_id, &report).await;
+        }
+        Ok(report)
+    }
+
+    /// Instantiate `component` against the extractor world and run the probe
+    /// extraction under the pool's limits
+    fn probe_component(&self, component: &Arc<Component>) -> Result<()> {
+        let mut probe = PooledInstance::new(
+            self.engine.clone(),
+            component.clone(),
+            self.linker.clone(),
+            self.config.memory_limit_pages.unwrap_or(256) as usize,
+        );
+        let mut store: wasmtime::Store<WasmResourceTracker> = probe.create_fresh_store();
+        store.set_epoch_deadline(self.config.epoch_deadline_ticks());
+        if let Some(fuel) = self.config.fuel_limit {
+            store.set_fuel(fuel)?;
+        }
+
+        let bindings = Extractor::instantiate(&mut store, component, &*self.linker)
+            .map_err(|e| anyhow!("Component does not implement the extractor world: {}", e))?;
+        bindings
+            .interface0
+            .call_extract(
+                &mut store,
+                PROBE_HTML,
+                PROBE_URL,
+                &WitExtractionMode::Article,
+            )
+            .map_err(|e| anyhow!("Probe extraction trapped: {}", e))?;
+        Ok(())
+    }
+}
+
+async fn emit_reloaded(event_bus: &EventBus, pool_id: &str, report: &ComponentReloadReport) {
+    let mut event = PoolEvent::new(
+        PoolOperation::ComponentReloaded,
+        pool_id.to_string(),
+        "instance_pool",
+    );
+    event.add_metadata("generation", &report.generation.to_string());
+    event.add_metadata("source", &report.source);
+    event.add_metadata("warmed_instances", &report.warmed_instances.to_string());
+    event.add_metadata("drained_instances", &report.drained_instances.to_string());
+
+    if let Err(e) = event_bus.emit(event).await {
+        warn!(error = %e, pool_id = %pool_id, "Failed to emit component reloaded event");
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
@@ -244,6 +244,42 @@ fn test_config_validation_failures() {
     );
 }
 
+#[test]
+fn test_fuel_limit_settings() {
+    with_env_vars(vec![("POOL_FUEL_LIMIT", "5000000")], || {
+        let config = ExtractorConfig::from_env();
+        assert_eq!(config.fuel_limit, Some(5_000_000));
+    });
+
+    // 0 disables fuel metering
+    with_env_vars(vec![("POOL_FUEL_LIMIT", "0")], || {
+        let config = ExtractorConfig::from_env();
+        assert_eq!(config.fuel_limit, None);
+        assert!(config.validate().is_ok());
+    });
+}
+
+#[test]
+fn test_execution_limit_validation() {
+    let config = ExtractorConfig {
+        epoch_timeout_ms: 25,
+        ..Default::default()
+    };
+    assert_eq!(config.epoch_deadline_ticks(), 3);
+
+    let no_fuel = ExtractorConfig {
+        fuel_limit: Some(0),
+        ..Default::default()
+    };
+    assert!(no_fuel.validate().is_err());
+
+    let no_cpu_time = ExtractorConfig {
+        epoch_timeout_ms: 0,
+        ..Default::default()
+    };
+    assert!(no_cpu_time.validate().is_err());
+}
+
 #[test]
 fn test_partial_env_var_override() {
     with_env_vars(
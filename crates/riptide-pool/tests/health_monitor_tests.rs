@@ -55,6 +55,13 @@ async fn test_health_status_serialization() {
             grow_failures: 0,
             memory_pressure: MemoryPressureLevel::Low,
         },
+        execution_limits: ExecutionLimitStats {
+            fuel_limit: Some(riptide_pool::DEFAULT_FUEL_LIMIT),
+            cpu_time_limit_ms: 60_000,
+            fuel_exhaustions: 2,
+            cpu_timeouts: 1,
+            fuel_consumed: 42_000_000,
+        },
         last_check: Some(std::time::Instant::now()),
         trend: HealthTrend::Stable,
     };
@@ -65,6 +72,11 @@ async fn test_health_status_serialization() {
     assert_eq!(status.max_instances, 8);
     assert!((status.utilization_percent - 37.5).abs() < 0.1);
     assert!(status.success_rate_percent > 90.0);
+
+    let json = serde_json::to_value(&status).unwrap();
+    assert_eq!(json["execution_limits"]["fuel_limit"], 1_000_000);
+    assert_eq!(json["execution_limits"]["fuel_exhaustions"], 2);
+    assert_eq!(json["execution_limits"]["cpu_timeouts"], 1);
 }
 
 #[tokio::test]
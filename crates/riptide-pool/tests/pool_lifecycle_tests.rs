@@ -18,6 +18,7 @@ fn create_test_config(max_pool: usize, initial: usize) -> ExtractorConfig {
         max_pool_size: max_pool,
         initial_pool_size: initial,
         epoch_timeout_ms: 10000,
+        fuel_limit: Some(DEFAULT_FUEL_LIMIT),
         health_check_interval: 5000,
         memory_limit: Some(128 * 1024 * 1024),
         circuit_breaker_timeout: 5000,
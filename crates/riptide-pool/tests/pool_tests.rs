@@ -27,6 +27,7 @@ fn create_test_config() -> ExtractorConfig {
         max_pool_size: 4,
         initial_pool_size: 2,
         epoch_timeout_ms: 10000,
+        fuel_limit: Some(DEFAULT_FUEL_LIMIT),
         health_check_interval: 30000,
         memory_limit: Some(128 * 1024 * 1024), // 128MB
         circuit_breaker_timeout: 30000,        // 30 seconds in milliseconds
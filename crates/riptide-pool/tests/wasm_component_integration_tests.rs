@@ -35,14 +35,19 @@ use std::time::Duration;
 // Test Utilities and Helpers
 // ============================================================================
 
-/// Check if WASM component exists
-fn wasm_component_exists() -> bool {
+/// Path of the built WASM component, if any
+fn wasm_component_path() -> Option<&'static str> {
     let paths = [
         "/workspaces/eventmesh/target/wasm32-wasip2/release/riptide_extractor_wasm.wasm",
         "./target/wasm32-wasip2/release/riptide_extractor_wasm.wasm",
     ];
 
-    paths.iter().any(|p| std::path::Path::new(p).exists())
+    paths.into_iter().find(|p| std::path::Path::new(p).exists())
+}
+
+/// Check if WASM component exists
+fn wasm_component_exists() -> bool {
+    wasm_component_path().is_some()
 }
 
 // ============================================================================
@@ -265,6 +270,42 @@ fn test_wasm_component_availability() -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
+#[tokio::test]
+async fn test_component_hot_reload() -> Result<()> {
+    use riptide_pool::{AdvancedInstancePool, ExtractorConfig};
+
+    let Some(path) = wasm_component_path() else {
+        println!("⚠️  WASM component not found - skipping hot reload test");
+        return Ok(());
+    };
+
+    let config = ExtractorConfig {
+        initial_pool_size: 2,
+        ..Default::default()
+    };
+    let engine = wasmtime::Engine::new(&config.wasmtime_config())?;
+    let pool = AdvancedInstancePool::new(config, engine, path).await?;
+    assert_eq!(pool.component_generation().await, 0);
+
+    // A broken upload is rejected and the current component keeps serving
+    assert!(pool
+        .reload_component_bytes(b"not a component".to_vec(), "upload")
+        .await
+        .is_err());
+    assert_eq!(pool.component_generation().await, 0);
+    assert_eq!(pool.component_source().await, path);
+
+    let report = pool.reload_component(path).await?;
+    assert_eq!(report.generation, 1);
+    assert_eq!(report.warmed_instances, 2);
+    assert_eq!(report.drained_instances, 2);
+
+    let metrics = pool.get_metrics().await;
+    assert_eq!(metrics.component_reloads, 1);
+    assert_eq!(metrics.component_reload_failures, 1);
+    Ok(())
+}
+
 // ============================================================================
 // Category 5: Integration Test Summary
 // ============================================================================
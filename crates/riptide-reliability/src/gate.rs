@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Features extracted from HTML content used for deciding the optimal extraction strategy.
 ///
@@ -54,6 +55,119 @@ pub enum Decision {
     Headless,
 }
 
+/// Kind of site a page belongs to, as recorded in its domain profile
+///
+/// How much static HTML can be trusted differs by vertical: news articles
+/// are server-rendered far more often than product pages, so each vertical
+/// can get its own gate thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContentVertical {
+    News,
+    Ecommerce,
+    Docs,
+    Forum,
+    /// Anything not profiled as one of the above
+    General,
+}
+
+impl ContentVertical {
+    /// Every vertical, in report order
+    pub const ALL: [ContentVertical; 5] = [
+        Self::News,
+        Self::Ecommerce,
+        Self::Docs,
+        Self::Forum,
+        Self::General,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::News => "news",
+            Self::Ecommerce => "ecommerce",
+            Self::Docs => "docs",
+            Self::Forum => "forum",
+            Self::General => "general",
+        }
+    }
+
+    /// Vertical named by a profile tag or configuration key, accepting
+    /// common synonyms ("shop", "documentation", "community", ...)
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag
+            .trim()
+            .to_ascii_lowercase()
+            .replace(['-', '_', ' '], "")
+            .as_str()
+        {
+            "news" | "media" | "magazine" | "blog" => Some(Self::News),
+            "ecommerce" | "shop" | "store" | "retail" => Some(Self::Ecommerce),
+            "docs" | "documentation" | "reference" | "wiki" => Some(Self::Docs),
+            "forum" | "forums" | "community" | "discussion" => Some(Self::Forum),
+            "general" => Some(Self::General),
+            _ => None,
+        }
+    }
+}
+
+/// High and low gate thresholds, see [`decide`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GateThresholds {
+    /// Scores at or above this use fast extraction
+    pub hi: f32,
+    /// Scores at or below this go to headless rendering
+    pub lo: f32,
+}
+
+impl Default for GateThresholds {
+    fn default() -> Self {
+        Self { hi: 0.7, lo: 0.3 }
+    }
+}
+
+impl GateThresholds {
+    /// Parse `"hi,lo"`, e.g. `"0.6,0.25"`; both must lie in 0.0-1.0 with
+    /// `lo <= hi`
+    pub fn parse(value: &str) -> Option<Self> {
+        let (hi, lo) = value.split_once(',')?;
+        let hi: f32 = hi.trim().parse().ok()?;
+        let lo: f32 = lo.trim().parse().ok()?;
+        ((0.0..=1.0).contains(&lo) && (lo..=1.0).contains(&hi)).then_some(Self { hi, lo })
+    }
+}
+
+/// Gate thresholds per content vertical, falling back to a default pair
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VerticalThresholds {
+    /// Thresholds of verticals without their own
+    pub default: GateThresholds,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub verticals: HashMap<ContentVertical, GateThresholds>,
+}
+
+impl VerticalThresholds {
+    pub fn new(default: GateThresholds) -> Self {
+        Self {
+            default,
+            verticals: HashMap::new(),
+        }
+    }
+
+    /// Use `thresholds` for pages of `vertical`
+    pub fn with_vertical(mut self, vertical: ContentVertical, thresholds: GateThresholds) -> Self {
+        self.verticals.insert(vertical, thresholds);
+        self
+    }
+
+    /// Thresholds applying to pages of `vertical`
+    pub fn for_vertical(&self, vertical: ContentVertical) -> GateThresholds {
+        self.verticals
+            .get(&vertical)
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
 /// Calculates a quality score for the HTML content based on various features.
 ///
 /// The score represents how likely it is that fast extraction will work well.
@@ -284,6 +398,47 @@ mod tests {
         assert_eq!(decide(&features, 0.7, 0.3), Decision::Headless);
     }
 
+    #[test]
+    fn test_vertical_thresholds() {
+        let features = GateFeatures {
+            html_bytes: 10000,
+            visible_text_chars: 3000,
+            p_count: 8,
+            article_count: 0,
+            h1h2_count: 2,
+            script_bytes: 2000,
+            has_og: true,
+            has_jsonld_article: false,
+            spa_markers: 0,
+            domain_prior: 0.5,
+        };
+        let thresholds = VerticalThresholds::default().with_vertical(
+            ContentVertical::News,
+            GateThresholds::parse("0.4,0.2").unwrap(),
+        );
+
+        let news = thresholds.for_vertical(ContentVertical::News);
+        assert_eq!(decide(&features, news.hi, news.lo), Decision::Raw);
+        let general = thresholds.for_vertical(ContentVertical::Ecommerce);
+        assert_eq!(general, GateThresholds::default());
+        assert_eq!(
+            decide(&features, general.hi, general.lo),
+            Decision::ProbesFirst
+        );
+
+        assert_eq!(GateThresholds::parse("0.2,0.4"), None);
+        assert_eq!(GateThresholds::parse("1.5,0.4"), None);
+        assert_eq!(
+            ContentVertical::from_tag("E-Commerce"),
+            Some(ContentVertical::Ecommerce)
+        );
+        assert_eq!(
+            ContentVertical::from_tag("documentation"),
+            Some(ContentVertical::Docs)
+        );
+        assert_eq!(ContentVertical::from_tag("travel"), None);
+    }
+
     #[test]
     fn test_should_use_headless_pdf_urls() {
         // PDF file extensions should skip headless
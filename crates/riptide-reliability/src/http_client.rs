@@ -2,9 +2,10 @@
 //!
 //! This module consolidates HTTP client functionality across the codebase with:
 //! - Circuit breaker protection for fault tolerance
-//! - Retry logic with exponential backoff
+//! - Retry logic with exponential backoff, honouring `Retry-After` on 429/503
 //! - Connection pooling and timeout management
 //! - Robots.txt compliance (optional)
+//! - Fetch middleware (`riptide_fetch::FetchMiddleware`) around every attempt
 //! - Preset configurations for common use cases
 //!
 //! # Architecture
@@ -36,12 +37,16 @@
 use anyhow::{Context, Result};
 use reqwest::{Client, ClientBuilder, Method, Response as ReqwestResponse, StatusCode};
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::{debug, error, info, warn};
 
+use crate::tls::DomainTlsConfig;
+use riptide_fetch::content_encoding::{self, CompressionMetrics, DEFAULT_MAX_DECODED_BYTES};
+use riptide_fetch::{CachingResolver, FetchMiddleware, MiddlewareChain};
 use riptide_utils::circuit_breaker::{self as circuit, CircuitBreaker, Config as CircuitConfig};
-use riptide_utils::retry::RetryPolicy;
+use riptide_utils::retry::{server_retry_delay, RetryPolicy, Throttled};
 
 /// Circuit breaker presets for different use cases
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -74,6 +79,7 @@ impl CircuitBreakerPreset {
                 circuit_cooldown_ms: 60_000,   // 1 min cooldown
                 max_retries: 2,                // Fewer retries (expensive)
                 initial_backoff_ms: 500,
+                ..HttpConfig::default()
             },
             Self::PdfProcessing => HttpConfig {
                 timeout_ms: 120_000,        // 2 min for PDF downloads
@@ -85,6 +91,7 @@ impl CircuitBreakerPreset {
                 circuit_cooldown_ms: 120_000, // 2 min cooldown
                 max_retries: 1,               // Minimal retries (very expensive)
                 initial_backoff_ms: 1000,
+                ..HttpConfig::default()
             },
             Self::SearchIndexing => HttpConfig {
                 timeout_ms: 30_000,         // 30s for indexing operations
@@ -96,6 +103,7 @@ impl CircuitBreakerPreset {
                 circuit_cooldown_ms: 30_000, // 30s cooldown
                 max_retries: 5,              // Aggressive retries
                 initial_backoff_ms: 200,
+                ..HttpConfig::default()
             },
             Self::ExternalApi => HttpConfig {
                 timeout_ms: 30_000,
@@ -107,6 +115,7 @@ impl CircuitBreakerPreset {
                 circuit_cooldown_ms: 30_000,
                 max_retries: 3,
                 initial_backoff_ms: 100,
+                ..HttpConfig::default()
             },
             Self::InternalService => HttpConfig {
                 timeout_ms: 10_000,        // 10s for internal calls
@@ -118,6 +127,7 @@ impl CircuitBreakerPreset {
                 circuit_cooldown_ms: 15_000,  // 15s cooldown
                 max_retries: 5,               // Aggressive retries
                 initial_backoff_ms: 50,
+                ..HttpConfig::default()
             },
             Self::WebScraping => HttpConfig {
                 timeout_ms: 45_000,         // 45s for scraping
@@ -129,6 +139,7 @@ impl CircuitBreakerPreset {
                 circuit_cooldown_ms: 45_000, // 45s cooldown
                 max_retries: 4,
                 initial_backoff_ms: 250,
+                ..HttpConfig::default()
             },
         }
     }
@@ -155,6 +166,9 @@ pub struct HttpConfig {
     pub max_retries: usize,
     /// Initial retry backoff in milliseconds
     pub initial_backoff_ms: u64,
+    /// Per-domain client certificates and CA bundles, first match wins
+    #[serde(default)]
+    pub tls: Vec<DomainTlsConfig>,
 }
 
 impl Default for HttpConfig {
@@ -169,6 +183,7 @@ impl Default for HttpConfig {
             circuit_cooldown_ms: 30000,
             max_retries: 3,
             initial_backoff_ms: 100,
+            tls: Vec::new(),
         }
     }
 }
@@ -236,9 +251,14 @@ impl FetchOptions {
 #[derive(Debug)]
 pub struct HttpClientService {
     client: Client,
+    /// One client per `config.tls` profile, in the same order
+    tls_clients: Vec<Client>,
     circuit_breaker: Arc<CircuitBreaker>,
     retry_policy: RetryPolicy,
     config: HttpConfig,
+    middleware: MiddlewareChain,
+    dns_resolver: Option<Arc<CachingResolver>>,
+    compression: Arc<CompressionMetrics>,
 }
 
 impl HttpClientService {
@@ -257,17 +277,7 @@ impl HttpClientService {
             config.timeout_ms
         );
 
-        let client = ClientBuilder::new()
-            .timeout(Duration::from_millis(config.timeout_ms))
-            .connect_timeout(Duration::from_millis(config.connect_timeout_ms))
-            .pool_idle_timeout(Duration::from_secs(config.pool_idle_timeout_secs))
-            .pool_max_idle_per_host(config.pool_max_idle_per_host)
-            .user_agent(&config.user_agent)
-            .gzip(true)
-            .brotli(true)
-            .use_rustls_tls()
-            .build()
-            .context("Failed to build HTTP client")?;
+        let (client, tls_clients) = Self::build_clients(&config, None)?;
 
         let circuit_config = CircuitConfig {
             failure_threshold: config.circuit_failure_threshold,
@@ -288,12 +298,100 @@ impl HttpClientService {
 
         Ok(Self {
             client,
+            tls_clients,
             circuit_breaker,
             retry_policy,
             config,
+            middleware: MiddlewareChain::default(),
+            dns_resolver: None,
+            compression: Arc::default(),
         })
     }
 
+    /// Resolve hosts through `resolver`, e.g. a DNS cache shared with other
+    /// clients
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the clients cannot be rebuilt
+    pub fn with_dns_resolver(mut self, resolver: Arc<CachingResolver>) -> Result<Self> {
+        self.dns_resolver = Some(resolver);
+        (self.client, self.tls_clients) =
+            Self::build_clients(&self.config, self.dns_resolver.as_ref())?;
+        Ok(self)
+    }
+
+    /// Use per-domain client certificates and CA bundles, replacing
+    /// `config.tls`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a profile's files cannot be read or its client built
+    pub fn with_tls_profiles(mut self, profiles: Vec<DomainTlsConfig>) -> Result<Self> {
+        self.config.tls = profiles;
+        (self.client, self.tls_clients) =
+            Self::build_clients(&self.config, self.dns_resolver.as_ref())?;
+        Ok(self)
+    }
+
+    /// Run `middleware` around every request, after those already registered
+    ///
+    /// Each retry passes through the chain again. A hook error fails the
+    /// attempt like a network error.
+    pub fn with_middleware(mut self, middleware: Arc<dyn FetchMiddleware>) -> Self {
+        self.middleware.push(middleware);
+        self
+    }
+
+    /// Default client and one client per TLS profile
+    fn build_clients(
+        config: &HttpConfig,
+        dns_resolver: Option<&Arc<CachingResolver>>,
+    ) -> Result<(Client, Vec<Client>)> {
+        let client = Self::client_builder(config, dns_resolver)
+            .build()
+            .context("Failed to build HTTP client")?;
+
+        let tls_clients = config
+            .tls
+            .iter()
+            .map(|tls| {
+                tls.apply(Self::client_builder(config, dns_resolver))?
+                    .build()
+                    .with_context(|| {
+                        format!("Failed to build HTTP client for {}", tls.domains.join(", "))
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok((client, tls_clients))
+    }
+
+    fn client_builder(
+        config: &HttpConfig,
+        dns_resolver: Option<&Arc<CachingResolver>>,
+    ) -> ClientBuilder {
+        let builder = content_encoding::configure(ClientBuilder::new())
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .connect_timeout(Duration::from_millis(config.connect_timeout_ms))
+            .pool_idle_timeout(Duration::from_secs(config.pool_idle_timeout_secs))
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            .user_agent(&config.user_agent)
+            .use_rustls_tls();
+        match dns_resolver {
+            Some(resolver) => builder.dns_resolver(resolver.clone()),
+            None => builder,
+        }
+    }
+
+    /// Client for `url`: the one of the first TLS profile matching its host,
+    /// else the default client
+    fn client_for(&self, url: &str) -> &Client {
+        url::Url::parse(url)
+            .ok()
+            .and_then(|url| DomainTlsConfig::find(&self.config.tls, &url))
+            .map_or(&self.client, |index| &self.tls_clients[index])
+    }
+
     /// Create a default HTTP client service
     pub fn new_default() -> Result<Self> {
         Self::new(HttpConfig::default())
@@ -368,6 +466,7 @@ impl HttpClientService {
         let timeout = options
             .timeout
             .unwrap_or(Duration::from_millis(self.config.timeout_ms));
+        let client = self.client_for(url);
 
         // Create custom retry policy if options override defaults
         let retry_policy = if options.max_retries.is_some() {
@@ -384,68 +483,91 @@ impl HttpClientService {
             "Initiating HTTP request with reliability patterns"
         );
 
-        // Execute with retry logic
+        // Execute with retry logic, waiting as long as throttling servers ask
+        let attempts = AtomicU32::new(0);
         retry_policy
-            .execute(|| async {
-                // Check circuit breaker (unless bypassed)
-                if !options.bypass_circuit_breaker {
-                    match self.circuit_breaker.try_acquire() {
-                        Ok(_permit) => {
-                            debug!("Circuit breaker: request permitted");
-                        }
-                        Err(msg) => {
-                            error!("Circuit breaker: {}", msg);
-                            return Err(anyhow::anyhow!("Circuit breaker open: {}", msg));
-                        }
-                    }
-                }
-
-                // Build request
-                let mut request_builder = self.client.request(method.clone(), url).timeout(timeout);
-
-                // Add custom headers
-                for (key, value) in &options.headers {
-                    request_builder = request_builder.header(key, value);
-                }
-
-                // Add body if present
-                if let Some(ref body_data) = body {
-                    request_builder = request_builder.body(body_data.clone());
-                }
-
-                // Send request
-                let response = request_builder
-                    .send()
-                    .await
-                    .context("HTTP request failed")?;
-
-                // Check status code
-                let status = response.status();
-                if status.is_success() || status.is_redirection() {
-                    // Success - record in circuit breaker
+            .execute_with_retry_after(
+                || async {
+                    // Check circuit breaker (unless bypassed)
                     if !options.bypass_circuit_breaker {
-                        self.circuit_breaker.on_success();
+                        match self.circuit_breaker.try_acquire() {
+                            Ok(_permit) => {
+                                debug!("Circuit breaker: request permitted");
+                            }
+                            Err(msg) => {
+                                error!("Circuit breaker: {}", msg);
+                                return Err(anyhow::anyhow!("Circuit breaker open: {}", msg));
+                            }
+                        }
                     }
-                    Ok(response)
-                } else {
-                    // Failure - record in circuit breaker
-                    if !options.bypass_circuit_breaker {
-                        self.circuit_breaker.on_failure();
+
+                    // Build request
+                    let mut request_builder = client.request(method.clone(), url).timeout(timeout);
+
+                    // Add custom headers
+                    for (key, value) in &options.headers {
+                        request_builder = request_builder.header(key, value);
                     }
 
-                    // Don't retry client errors (4xx) except specific cases
-                    if status.is_client_error()
-                        && status != StatusCode::REQUEST_TIMEOUT
-                        && status != StatusCode::TOO_MANY_REQUESTS
-                    {
-                        warn!(status = %status, "Client error - not retrying");
-                        return Err(anyhow::anyhow!("Client error: {}", status));
+                    // Add body if present
+                    if let Some(ref body_data) = body {
+                        request_builder = request_builder.body(body_data.clone());
                     }
 
-                    // Retry server errors (5xx) and retryable client errors
-                    Err(anyhow::anyhow!("HTTP error: {}", status))
-                }
-            })
+                    // Send request through the middleware chain
+                    let attempt = attempts.fetch_add(1, Ordering::Relaxed);
+                    let response = self
+                        .middleware
+                        .send(request_builder.build()?, attempt, |request| async move {
+                            client.execute(request).await.context("HTTP request failed")
+                        })
+                        .await?;
+                    // Decode here rather than in reqwest, so the body carries
+                    // its wire size and decoded size limit
+                    let response = content_encoding::decode_response(
+                        response,
+                        DEFAULT_MAX_DECODED_BYTES,
+                        &self.compression,
+                    )?;
+
+                    // Check status code
+                    let status = response.status();
+                    if status.is_success() || status.is_redirection() {
+                        // Success - record in circuit breaker
+                        if !options.bypass_circuit_breaker {
+                            self.circuit_breaker.on_success();
+                        }
+                        Ok(response)
+                    } else {
+                        // Failure - record in circuit breaker
+                        if !options.bypass_circuit_breaker {
+                            self.circuit_breaker.on_failure();
+                        }
+
+                        // Don't retry client errors (4xx) except specific cases
+                        if status.is_client_error()
+                            && status != StatusCode::REQUEST_TIMEOUT
+                            && status != StatusCode::TOO_MANY_REQUESTS
+                        {
+                            warn!(status = %status, "Client error - not retrying");
+                            return Err(anyhow::anyhow!("Client error: {}", status));
+                        }
+
+                        if status == StatusCode::TOO_MANY_REQUESTS
+                            || status == StatusCode::SERVICE_UNAVAILABLE
+                        {
+                            return Err(anyhow::Error::new(Throttled {
+                                status: status.as_u16(),
+                                retry_after: server_retry_delay(response.headers()),
+                            }));
+                        }
+
+                        // Retry server errors (5xx) and retryable client errors
+                        Err(anyhow::anyhow!("HTTP error: {}", status))
+                    }
+                },
+                |err| err.downcast_ref::<Throttled>().and_then(|t| t.retry_after),
+            )
             .await
     }
 
@@ -454,6 +576,11 @@ impl HttpClientService {
         self.circuit_breaker.state()
     }
 
+    /// Compression counters of the bodies this service decoded
+    pub fn compression_stats(&self) -> content_encoding::CompressionStats {
+        self.compression.snapshot()
+    }
+
     /// Get HTTP client configuration
     pub fn config(&self) -> &HttpConfig {
         &self.config
@@ -515,12 +642,38 @@ mod tests {
             circuit_cooldown_ms: 60000,
             max_retries: 5,
             initial_backoff_ms: 200,
+            tls: Vec::new(),
         };
 
         let result = HttpClientService::new(config);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_tls_profile_selected_by_host() {
+        let config = HttpConfig {
+            tls: vec![DomainTlsConfig {
+                domains: vec!["*.corp.example".to_string()],
+                ..Default::default()
+            }],
+            ..HttpConfig::default()
+        };
+        let service = HttpClientService::new(config).unwrap();
+
+        assert!(std::ptr::eq(
+            service.client_for("https://portal.corp.example/login"),
+            &service.tls_clients[0]
+        ));
+        assert!(std::ptr::eq(
+            service.client_for("https://example.com/"),
+            &service.client
+        ));
+        assert!(std::ptr::eq(
+            service.client_for("not a url"),
+            &service.client
+        ));
+    }
+
     #[test]
     fn test_circuit_breaker_presets() {
         // Test all presets can be converted to config
@@ -538,6 +691,70 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_dns_resolver_applies_host_overrides() {
+        use riptide_config::DnsConfig;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .mount(&server)
+            .await;
+        let resolver = CachingResolver::new(
+            DnsConfig::default().with_host_override("origin.test", vec![server.address().ip()]),
+        )
+        .unwrap();
+        let client = ReliableHttpClient::with_preset(CircuitBreakerPreset::InternalService)
+            .unwrap()
+            .with_dns_resolver(Arc::new(resolver))
+            .unwrap();
+
+        let url = format!("http://origin.test:{}/", server.address().port());
+        let response = client.get(&url).await.unwrap();
+
+        assert_eq!(response.text().await.unwrap(), "ok");
+    }
+
+    #[tokio::test]
+    async fn test_decodes_bodies_and_keeps_wire_size() {
+        use flate2::{write::GzEncoder, Compression};
+        use riptide_fetch::WireBytes;
+        use std::io::Write;
+        use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
+
+        let html = "<p>repeated</p>".repeat(1000);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(html.as_bytes()).unwrap();
+        let gzipped = encoder.finish().unwrap();
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("content-encoding", "gzip")
+                    .set_body_raw(gzipped.clone(), "text/html"),
+            )
+            .mount(&server)
+            .await;
+        let service = HttpClientService::new_default().unwrap();
+
+        let response = service
+            .get(&server.uri(), FetchOptions::default())
+            .await
+            .unwrap();
+        let wire = WireBytes::of(&response);
+        let body = response.text().await.unwrap();
+
+        assert_eq!(body, html);
+        assert_eq!(
+            WireBytes::wire_size(wire.as_ref(), body.len()),
+            gzipped.len() as u64
+        );
+        assert_eq!(service.compression_stats().encodings["gzip"].responses, 1);
+    }
+
     #[test]
     fn test_reliable_http_client_creation() {
         let result = ReliableHttpClient::with_preset(CircuitBreakerPreset::ExternalApi);
@@ -602,6 +819,25 @@ impl ReliableHttpClient {
         })
     }
 
+    /// Run `middleware` around every request, after those already registered
+    pub fn with_middleware(mut self, middleware: Arc<dyn FetchMiddleware>) -> Self {
+        self.service = self.service.with_middleware(middleware);
+        self
+    }
+
+    /// Resolve hosts through `resolver`, e.g. a DNS cache shared with other
+    /// clients
+    pub fn with_dns_resolver(mut self, resolver: Arc<CachingResolver>) -> Result<Self> {
+        self.service = self.service.with_dns_resolver(resolver)?;
+        Ok(self)
+    }
+
+    /// Use per-domain client certificates and CA bundles
+    pub fn with_tls_profiles(mut self, profiles: Vec<DomainTlsConfig>) -> Result<Self> {
+        self.service = self.service.with_tls_profiles(profiles)?;
+        Ok(self)
+    }
+
     /// Get the preset used by this client
     pub fn preset(&self) -> CircuitBreakerPreset {
         self.preset
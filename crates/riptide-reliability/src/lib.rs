@@ -151,6 +151,7 @@ pub mod http_client;
 #[cfg(feature = "reliability-patterns")]
 pub mod reliability;
 pub mod timeout;
+pub use riptide_fetch::tls;
 
 // Re-export canonical circuit breaker from riptide-utils (shared infrastructure)
 // ARCHITECTURE: Circuit breaker lives in riptide-utils to avoid circular dependencies
@@ -184,7 +185,10 @@ pub use engine_selection::{
     analyze_content, calculate_content_ratio, decide_engine, decide_engine_with_flags,
     ContentAnalysis, Engine, EngineCacheable, EngineSelectionFlags,
 };
-pub use gate::{decide, score, should_use_headless, Decision, GateFeatures};
+pub use gate::{
+    decide, score, should_use_headless, ContentVertical, Decision, GateFeatures, GateThresholds,
+    VerticalThresholds,
+};
 pub use http_client::{
     CircuitBreakerPreset, FetchOptions, HttpClientService, HttpConfig, ReliableHttpClient,
 };
@@ -197,3 +201,4 @@ pub use reliability::{
 pub use timeout::{
     get_global_timeout_manager, AdaptiveTimeoutManager, TimeoutConfig, TimeoutProfile, TimeoutStats,
 };
+pub use tls::{ClientCertificate, DomainTlsConfig};
@@ -674,6 +674,20 @@ impl riptide_types::ports::ReliableContentExtractor for ReliableExtractor {
                     description: None,
                     html: None,
                     parser_metadata: None,
+                    provenance: None,
+                    translation: None,
+                    structured_data: Vec::new(),
+                    annotations: None,
+                    feed: None,
+                    feed_links: Vec::new(),
+                    classification: None,
+                    clean_html: None,
+                    media_assets: Vec::new(),
+                    embedded_media: Vec::new(),
+                    images: Vec::new(),
+                    references: Vec::new(),
+                    language_spans: Vec::new(),
+                    quality_report: None,
                 })
             }
         }
@@ -747,6 +761,20 @@ mod tests {
                 description: Some("Test description".to_string()),
                 html: None,
                 parser_metadata: None,
+                provenance: None,
+                translation: None,
+                structured_data: Vec::new(),
+                annotations: None,
+                feed: None,
+                feed_links: Vec::new(),
+                classification: None,
+                clean_html: None,
+                media_assets: Vec::new(),
+                embedded_media: Vec::new(),
+                images: Vec::new(),
+                references: Vec::new(),
+                language_spans: Vec::new(),
+                quality_report: None,
             })
         }
     }
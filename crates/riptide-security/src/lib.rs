@@ -9,16 +9,23 @@ use tracing::{debug, warn};
 pub mod api_keys;
 pub mod audit;
 pub mod budget;
+pub mod metering;
 pub mod middleware;
 pub mod pii;
+pub mod policy;
 pub mod types;
 
 // Re-export commonly used types and structs
 pub use api_keys::{ApiKey, ApiKeyManager};
 pub use audit::{AuditConfig, AuditLogEntry, AuditLogger};
 pub use budget::{BudgetHealthStatus, BudgetManager};
+pub use metering::{
+    CostBreakdown, CostModel, Meter, MeteredUsage, MeteringConfig, TenantUsageReport,
+    UsageAttribution,
+};
 pub use middleware::{RequestSecurityContext, SecurityHealthStatus, SecurityMiddleware};
 pub use pii::{PiiRedactionMiddleware, PiiRedactor};
+pub use policy::{CrawlPolicy, HintPolicy, PolicyConfig, PolicyEngine, PolicyRequest, PolicyRule};
 pub use types::*;
 
 /// Security middleware configuration
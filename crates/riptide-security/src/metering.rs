@@ -0,0 +1,490 @@
+//! Crawl cost metering and attribution
+//!
+//! Records the resources crawl work consumes (bandwidth, headless render
+//! time, LLM tokens and stored bytes) against a tenant, and within the
+//! tenant against the crawl and the domain that caused it. A [`CostModel`]
+//! turns the aggregated usage into money for chargeback.
+//!
+//! Tenants, and each tenant's crawls and domains, are tracked up to the
+//! limits of [`MeteringConfig`]; usage past a limit is folded into
+//! [`OTHER_KEY`] so memory stays bounded.
+
+use crate::types::TenantId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+/// Key that tenants, crawls and domains beyond their limits are folded into
+pub const OTHER_KEY: &str = "(other)";
+
+const BYTES_PER_GB: f64 = 1_000_000_000.0;
+
+/// Resources consumed by crawl work
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct MeteredUsage {
+    /// Bytes downloaded from origins
+    pub bandwidth_bytes: u64,
+    /// Seconds spent rendering pages in a headless browser
+    pub render_seconds: f64,
+    /// Prompt and completion tokens sent to LLM providers
+    pub llm_tokens: u64,
+    /// Bytes written to caches and artifact storage
+    pub storage_bytes: u64,
+}
+
+impl MeteredUsage {
+    /// Add `other` to this usage
+    pub fn add(&mut self, other: &MeteredUsage) {
+        self.bandwidth_bytes = self.bandwidth_bytes.saturating_add(other.bandwidth_bytes);
+        self.render_seconds += other.render_seconds;
+        self.llm_tokens = self.llm_tokens.saturating_add(other.llm_tokens);
+        self.storage_bytes = self.storage_bytes.saturating_add(other.storage_bytes);
+    }
+
+    /// Whether nothing was consumed
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Prices applied to metered usage, in USD
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CostModel {
+    pub bandwidth_per_gb: f64,
+    pub render_per_second: f64,
+    pub llm_per_1k_tokens: f64,
+    pub storage_per_gb: f64,
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        Self {
+            bandwidth_per_gb: 0.09,
+            render_per_second: 0.0001,
+            llm_per_1k_tokens: 0.002,
+            storage_per_gb: 0.023,
+        }
+    }
+}
+
+impl CostModel {
+    /// Price `usage` under this model
+    pub fn cost(&self, usage: &MeteredUsage) -> CostBreakdown {
+        let bandwidth_usd = usage.bandwidth_bytes as f64 / BYTES_PER_GB * self.bandwidth_per_gb;
+        let render_usd = usage.render_seconds * self.render_per_second;
+        let llm_usd = usage.llm_tokens as f64 / 1000.0 * self.llm_per_1k_tokens;
+        let storage_usd = usage.storage_bytes as f64 / BYTES_PER_GB * self.storage_per_gb;
+        CostBreakdown {
+            bandwidth_usd,
+            render_usd,
+            llm_usd,
+            storage_usd,
+            total_usd: bandwidth_usd + render_usd + llm_usd + storage_usd,
+        }
+    }
+}
+
+/// Cost of metered usage, per resource
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CostBreakdown {
+    pub bandwidth_usd: f64,
+    pub render_usd: f64,
+    pub llm_usd: f64,
+    pub storage_usd: f64,
+    pub total_usd: f64,
+}
+
+/// Who a piece of usage is charged to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageAttribution {
+    pub tenant_id: TenantId,
+    /// Crawl the work belonged to, when it has an id
+    pub crawl_id: Option<String>,
+    /// Domain the work was done for
+    pub domain: Option<String>,
+}
+
+impl UsageAttribution {
+    pub fn new(tenant_id: TenantId) -> Self {
+        Self {
+            tenant_id,
+            crawl_id: None,
+            domain: None,
+        }
+    }
+
+    pub fn with_crawl(mut self, crawl_id: impl Into<String>) -> Self {
+        self.crawl_id = Some(crawl_id.into());
+        self
+    }
+
+    pub fn with_domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+}
+
+/// Metering configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MeteringConfig {
+    pub cost_model: CostModel,
+    /// Tenants tracked individually; later tenants go to [`OTHER_KEY`]
+    pub max_tenants: usize,
+    /// Crawls tracked individually per tenant; later crawls go to [`OTHER_KEY`]
+    pub max_crawls_per_tenant: usize,
+    /// Domains tracked individually per tenant; later domains go to [`OTHER_KEY`]
+    pub max_domains_per_tenant: usize,
+}
+
+impl Default for MeteringConfig {
+    fn default() -> Self {
+        Self {
+            cost_model: CostModel::default(),
+            max_tenants: 10_000,
+            max_crawls_per_tenant: 1_000,
+            max_domains_per_tenant: 10_000,
+        }
+    }
+}
+
+/// Usage and cost of one crawl or domain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributedUsage {
+    /// Crawl id or domain
+    pub key: String,
+    pub usage: MeteredUsage,
+    pub cost: CostBreakdown,
+}
+
+/// Usage of a tenant since metering started, broken down by crawl and domain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantUsageReport {
+    pub tenant_id: TenantId,
+    pub since: DateTime<Utc>,
+    pub last_recorded: DateTime<Utc>,
+    pub usage: MeteredUsage,
+    pub cost: CostBreakdown,
+    /// Most expensive crawls first
+    pub by_crawl: Vec<AttributedUsage>,
+    /// Most expensive domains first
+    pub by_domain: Vec<AttributedUsage>,
+}
+
+#[derive(Debug)]
+struct TenantLedger {
+    since: DateTime<Utc>,
+    last_recorded: DateTime<Utc>,
+    total: MeteredUsage,
+    crawls: HashMap<String, MeteredUsage>,
+    domains: HashMap<String, MeteredUsage>,
+}
+
+impl TenantLedger {
+    fn new() -> Self {
+        let now = Utc::now();
+        Self {
+            since: now,
+            last_recorded: now,
+            total: MeteredUsage::default(),
+            crawls: HashMap::new(),
+            domains: HashMap::new(),
+        }
+    }
+}
+
+/// Add `usage` under `key`, folding new keys into [`OTHER_KEY`] past `limit`
+fn charge(
+    entries: &mut HashMap<String, MeteredUsage>,
+    key: &str,
+    usage: &MeteredUsage,
+    limit: usize,
+) {
+    let key = if entries.contains_key(key) || entries.len() < limit {
+        key
+    } else {
+        OTHER_KEY
+    };
+    entries.entry(key.to_string()).or_default().add(usage);
+}
+
+/// Aggregates metered usage per tenant, crawl and domain
+pub struct Meter {
+    config: MeteringConfig,
+    tenants: RwLock<HashMap<TenantId, TenantLedger>>,
+}
+
+impl Default for Meter {
+    fn default() -> Self {
+        Self::new(MeteringConfig::default())
+    }
+}
+
+impl Meter {
+    pub fn new(config: MeteringConfig) -> Self {
+        Self {
+            config,
+            tenants: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Prices applied to usage reports
+    pub fn cost_model(&self) -> &CostModel {
+        &self.config.cost_model
+    }
+
+    /// Charge `usage` to `attribution`
+    pub async fn record(&self, attribution: &UsageAttribution, usage: MeteredUsage) {
+        if usage.is_empty() {
+            return;
+        }
+        let mut tenants = self.tenants.write().await;
+        let tenant_id = if tenants.contains_key(&attribution.tenant_id)
+            || tenants.len() < self.config.max_tenants
+        {
+            attribution.tenant_id.clone()
+        } else {
+            TenantId::from(OTHER_KEY)
+        };
+        let ledger = tenants.entry(tenant_id).or_insert_with(TenantLedger::new);
+        ledger.last_recorded = Utc::now();
+        ledger.total.add(&usage);
+        if let Some(crawl_id) = &attribution.crawl_id {
+            charge(
+                &mut ledger.crawls,
+                crawl_id,
+                &usage,
+                self.config.max_crawls_per_tenant,
+            );
+        }
+        if let Some(domain) = &attribution.domain {
+            charge(
+                &mut ledger.domains,
+                domain,
+                &usage,
+                self.config.max_domains_per_tenant,
+            );
+        }
+    }
+
+    /// Usage report of a tenant, `None` if nothing was recorded for it
+    ///
+    /// `limit` caps the crawl and domain breakdowns to their most expensive
+    /// entries.
+    pub async fn tenant_usage(
+        &self,
+        tenant_id: &TenantId,
+        limit: Option<usize>,
+    ) -> Option<TenantUsageReport> {
+        let tenants = self.tenants.read().await;
+        let ledger = tenants.get(tenant_id)?;
+        Some(TenantUsageReport {
+            tenant_id: tenant_id.clone(),
+            since: ledger.since,
+            last_recorded: ledger.last_recorded,
+            usage: ledger.total,
+            cost: self.config.cost_model.cost(&ledger.total),
+            by_crawl: self.breakdown(&ledger.crawls, limit),
+            by_domain: self.breakdown(&ledger.domains, limit),
+        })
+    }
+
+    /// Tenants with recorded usage
+    pub async fn tenants(&self) -> Vec<TenantId> {
+        let mut tenants: Vec<_> = self.tenants.read().await.keys().cloned().collect();
+        tenants.sort_by(|a, b| a.0.cmp(&b.0));
+        tenants
+    }
+
+    fn breakdown(
+        &self,
+        entries: &HashMap<String, MeteredUsage>,
+        limit: Option<usize>,
+    ) -> Vec<AttributedUsage> {
+        let mut breakdown: Vec<_> = entries
+            .iter()
+            .map(|(key, usage)| AttributedUsage {
+                key: key.clone(),
+                usage: *usage,
+                cost: self.config.cost_model.cost(usage),
+            })
+            .collect();
+        breakdown.sort_by(|a, b| {
+            b.cost
+                .total_usd
+                .total_cmp(&a.cost.total_usd)
+                .then_with(|| a.key.cmp(&b.key))
+        });
+        if let Some(limit) = limit {
+            breakdown.truncate(limit);
+        }
+        breakdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bandwidth(bytes: u64) -> MeteredUsage {
+        MeteredUsage {
+            bandwidth_bytes: bytes,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_cost_model() {
+        let model = CostModel {
+            bandwidth_per_gb: 0.1,
+            render_per_second: 0.01,
+            llm_per_1k_tokens: 0.5,
+            storage_per_gb: 0.02,
+        };
+        let cost = model.cost(&MeteredUsage {
+            bandwidth_bytes: 2_000_000_000,
+            render_seconds: 30.0,
+            llm_tokens: 4_000,
+            storage_bytes: 500_000_000,
+        });
+        assert!((cost.bandwidth_usd - 0.2).abs() < 1e-9);
+        assert!((cost.render_usd - 0.3).abs() < 1e-9);
+        assert!((cost.llm_usd - 2.0).abs() < 1e-9);
+        assert!((cost.storage_usd - 0.01).abs() < 1e-9);
+        assert!((cost.total_usd - 2.51).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_usage_is_attributed_per_tenant_crawl_and_domain() {
+        let meter = Meter::default();
+        let tenant = TenantId::from("acme");
+        let crawl = UsageAttribution::new(tenant.clone()).with_crawl("c1");
+
+        meter
+            .record(&crawl.clone().with_domain("a.example"), bandwidth(300))
+            .await;
+        meter
+            .record(&crawl.clone().with_domain("b.example"), bandwidth(100))
+            .await;
+        meter
+            .record(
+                &UsageAttribution::new(tenant.clone())
+                    .with_crawl("c2")
+                    .with_domain("a.example"),
+                MeteredUsage {
+                    render_seconds: 2.0,
+                    ..Default::default()
+                },
+            )
+            .await;
+        meter
+            .record(&UsageAttribution::new("other".into()), bandwidth(1))
+            .await;
+
+        let report = meter.tenant_usage(&tenant, None).await.unwrap();
+        assert_eq!(report.usage.bandwidth_bytes, 400);
+        assert_eq!(report.usage.render_seconds, 2.0);
+
+        let crawls: Vec<_> = report.by_crawl.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(
+            crawls,
+            ["c2", "c1"],
+            "render time costs more than 400 bytes"
+        );
+        assert_eq!(report.by_crawl[1].usage.bandwidth_bytes, 400);
+
+        let a = &report.by_domain[0];
+        assert_eq!(a.key, "a.example");
+        assert_eq!(a.usage.bandwidth_bytes, 300);
+        assert_eq!(a.usage.render_seconds, 2.0);
+
+        assert_eq!(
+            meter
+                .tenant_usage(&tenant, Some(1))
+                .await
+                .unwrap()
+                .by_domain
+                .len(),
+            1
+        );
+        assert!(meter.tenant_usage(&"nobody".into(), None).await.is_none());
+        assert_eq!(
+            meter.tenants().await,
+            [TenantId::from("acme"), TenantId::from("other")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_breakdowns_fold_into_other_past_limit() {
+        let meter = Meter::new(MeteringConfig {
+            max_domains_per_tenant: 2,
+            ..Default::default()
+        });
+        let tenant = TenantId::from("acme");
+        for domain in [
+            "a.example",
+            "b.example",
+            "c.example",
+            "d.example",
+            "a.example",
+        ] {
+            meter
+                .record(
+                    &UsageAttribution::new(tenant.clone()).with_domain(domain),
+                    bandwidth(10),
+                )
+                .await;
+        }
+
+        let report = meter.tenant_usage(&tenant, None).await.unwrap();
+        let domains: HashMap<_, _> = report
+            .by_domain
+            .iter()
+            .map(|d| (d.key.as_str(), d.usage.bandwidth_bytes))
+            .collect();
+        assert_eq!(
+            domains,
+            HashMap::from([("a.example", 20), ("b.example", 10), (OTHER_KEY, 20)])
+        );
+        assert_eq!(report.usage.bandwidth_bytes, 50);
+    }
+
+    #[tokio::test]
+    async fn test_tenants_fold_into_other_past_limit() {
+        let meter = Meter::new(MeteringConfig {
+            max_tenants: 2,
+            ..Default::default()
+        });
+        for tenant in ["acme", "globex", "initech", "umbrella", "acme"] {
+            meter
+                .record(&UsageAttribution::new(tenant.into()), bandwidth(10))
+                .await;
+        }
+
+        assert_eq!(
+            meter.tenants().await,
+            [
+                TenantId::from(OTHER_KEY),
+                TenantId::from("acme"),
+                TenantId::from("globex")
+            ]
+        );
+        let other = meter.tenant_usage(&OTHER_KEY.into(), None).await.unwrap();
+        assert_eq!(other.usage.bandwidth_bytes, 20);
+        assert!(meter.tenant_usage(&"initech".into(), None).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_empty_usage_is_not_recorded() {
+        let meter = Meter::default();
+        meter
+            .record(
+                &UsageAttribution::new("acme".into()),
+                MeteredUsage::default(),
+            )
+            .await;
+        assert!(meter.tenants().await.is_empty());
+    }
+}
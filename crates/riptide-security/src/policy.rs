@@ -0,0 +1,611 @@
+//! Per-URL Legal and Compliance Policy Engine for RipTide
+//!
+//! Consulted before any fetch to decide whether a URL may be crawled for a
+//! tenant. Policies combine domain blocklists, URL pattern blocklists,
+//! jurisdiction rules (based on the server's GeoIP country) and
+//! content-category exclusions. Policies also limit the per-request
+//! [`PipelineHints`] a tenant's clients may send. Denials are returned as
+//! typed [`SecurityError::PolicyDenied`] errors and recorded in the audit log.
+
+use crate::audit::{AuditDetails, AuditLogEntry, AuditLogger, AuditOutcome};
+use crate::types::*;
+use anyhow::{anyhow, Result};
+use chrono::Utc;
+use regex::Regex;
+use riptide_types::PipelineHints;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+use url::Url;
+use uuid::Uuid;
+
+/// Policy rule that caused a denial
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyRule {
+    DomainBlocklist,
+    UrlPattern,
+    Jurisdiction,
+    ContentCategory,
+    PipelineHint,
+}
+
+impl fmt::Display for PolicyRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            PolicyRule::DomainBlocklist => "domain_blocklist",
+            PolicyRule::UrlPattern => "url_pattern",
+            PolicyRule::Jurisdiction => "jurisdiction",
+            PolicyRule::ContentCategory => "content_category",
+            PolicyRule::PipelineHint => "pipeline_hint",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Crawl policy for a single tenant
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrawlPolicy {
+    /// Blocked domains; subdomains are blocked too
+    pub blocked_domains: Vec<String>,
+    /// Regular expressions matched against the full URL
+    pub blocked_url_patterns: Vec<String>,
+    /// ISO 3166-1 alpha-2 codes of server countries that must not be crawled
+    pub blocked_countries: Vec<String>,
+    /// When set, only servers in these countries may be crawled; servers
+    /// whose country cannot be resolved are denied
+    pub allowed_countries: Option<Vec<String>>,
+    /// Content categories that must not be crawled
+    pub excluded_categories: Vec<String>,
+    /// Pipeline hints requests may carry
+    pub pipeline_hints: HintPolicy,
+}
+
+/// Limits on the per-request pipeline hints of a tenant
+///
+/// Everything is allowed by default, so existing policies keep working.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HintPolicy {
+    /// Whether requests may force headless rendering
+    pub allow_force_headless: bool,
+    /// Whether requests may skip the cache
+    pub allow_skip_cache: bool,
+    /// Extraction schemas requests may prefer; any when unset
+    pub allowed_extraction_schemas: Option<Vec<String>>,
+    /// Stealth presets requests may ask for; any when unset
+    pub allowed_stealth_presets: Option<Vec<String>>,
+    /// Highest per-request budget a request may set, in USD
+    pub max_budget_usd: Option<f64>,
+}
+
+impl Default for HintPolicy {
+    fn default() -> Self {
+        Self {
+            allow_force_headless: true,
+            allow_skip_cache: true,
+            allowed_extraction_schemas: None,
+            allowed_stealth_presets: None,
+            max_budget_usd: None,
+        }
+    }
+}
+
+impl HintPolicy {
+    /// Why `hints` are not allowed, if they are not
+    fn violation(&self, hints: &PipelineHints) -> Option<String> {
+        let listed = |allowed: &Option<Vec<String>>, value: &str| {
+            allowed
+                .as_ref()
+                .is_none_or(|allowed| allowed.iter().any(|a| a.eq_ignore_ascii_case(value)))
+        };
+
+        if hints.force_headless && !self.allow_force_headless {
+            return Some("force_headless is not allowed".to_string());
+        }
+        if hints.skip_cache && !self.allow_skip_cache {
+            return Some("skip_cache is not allowed".to_string());
+        }
+        if let Some(schema) = hints
+            .extraction_schema
+            .as_deref()
+            .filter(|schema| !listed(&self.allowed_extraction_schemas, schema))
+        {
+            return Some(format!("extraction schema {} is not allowed", schema));
+        }
+        if let Some(preset) = hints
+            .stealth_preset
+            .as_deref()
+            .filter(|preset| !listed(&self.allowed_stealth_presets, preset))
+        {
+            return Some(format!("stealth preset {} is not allowed", preset));
+        }
+        match (hints.max_budget_usd, self.max_budget_usd) {
+            (Some(budget), Some(limit)) if budget > limit => Some(format!(
+                "budget ${:.2} exceeds the tenant limit of ${:.2}",
+                budget, limit
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Policy configuration for all tenants
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolicyConfig {
+    /// Policy applied to tenants without their own entry
+    pub default_policy: CrawlPolicy,
+    /// Tenant-specific policies, replacing the default policy
+    pub tenants: HashMap<String, CrawlPolicy>,
+    /// Content category membership by domain, e.g. `"gambling": ["casino.example"]`
+    pub category_domains: HashMap<String, Vec<String>>,
+}
+
+impl PolicyConfig {
+    /// Load a policy configuration from a JSON file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read policy file {}: {}", path.display(), e))?;
+        serde_json::from_str(&contents)
+            .map_err(|e| anyhow!("Failed to parse policy file {}: {}", path.display(), e))
+    }
+}
+
+/// Facts about a URL the engine cannot derive itself
+#[derive(Debug, Clone, Default)]
+pub struct PolicyRequest<'a> {
+    /// URL about to be fetched
+    pub url: &'a str,
+    /// Server country from GeoIP, when known
+    pub server_country: Option<&'a str>,
+}
+
+/// Compiled tenant policy
+#[derive(Debug)]
+struct CompiledPolicy {
+    policy: CrawlPolicy,
+    patterns: Vec<Regex>,
+}
+
+impl CompiledPolicy {
+    fn compile(policy: CrawlPolicy) -> Result<Self> {
+        let patterns = policy
+            .blocked_url_patterns
+            .iter()
+            .map(|p| Regex::new(p).map_err(|e| anyhow!("Invalid URL pattern '{}': {}", p, e)))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { policy, patterns })
+    }
+}
+
+/// Policy Engine - decides whether a URL may be fetched for a tenant
+pub struct PolicyEngine {
+    default_policy: Arc<CompiledPolicy>,
+    tenants: RwLock<HashMap<TenantId, Arc<CompiledPolicy>>>,
+    category_domains: HashMap<String, Vec<String>>,
+    audit_logger: Option<Arc<AuditLogger>>,
+}
+
+impl PolicyEngine {
+    /// Create a new policy engine
+    pub fn new(config: PolicyConfig, audit_logger: Option<Arc<AuditLogger>>) -> Result<Self> {
+        let tenants = config
+            .tenants
+            .into_iter()
+            .map(|(tenant, policy)| {
+                Ok((TenantId(tenant), Arc::new(CompiledPolicy::compile(policy)?)))
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        Ok(Self {
+            default_policy: Arc::new(CompiledPolicy::compile(config.default_policy)?),
+            tenants: RwLock::new(tenants),
+            category_domains: config.category_domains,
+            audit_logger,
+        })
+    }
+
+    /// Create an engine that allows every URL
+    pub fn permissive() -> Self {
+        Self {
+            default_policy: Arc::new(CompiledPolicy {
+                policy: CrawlPolicy::default(),
+                patterns: Vec::new(),
+            }),
+            tenants: RwLock::new(HashMap::new()),
+            category_domains: HashMap::new(),
+            audit_logger: None,
+        }
+    }
+
+    /// Replace the policy for a tenant
+    pub async fn set_tenant_policy(&self, tenant_id: TenantId, policy: CrawlPolicy) -> Result<()> {
+        let compiled = Arc::new(CompiledPolicy::compile(policy)?);
+        self.tenants.write().await.insert(tenant_id, compiled);
+        Ok(())
+    }
+
+    /// Whether evaluating a URL for this tenant requires the server country
+    pub async fn needs_jurisdiction(&self, tenant_id: &TenantId) -> bool {
+        let policy = self.policy_for(tenant_id).await;
+        !policy.policy.blocked_countries.is_empty() || policy.policy.allowed_countries.is_some()
+    }
+
+    /// Check a URL against the tenant's policy, auditing denials
+    pub async fn check(
+        &self,
+        tenant_id: &TenantId,
+        request: &PolicyRequest<'_>,
+    ) -> SecurityResult<()> {
+        let policy = self.policy_for(tenant_id).await;
+        let Err(err) = self.evaluate(&policy, request) else {
+            debug!(tenant_id = %tenant_id, url = %request.url, "URL allowed by policy");
+            return Ok(());
+        };
+
+        warn!(tenant_id = %tenant_id, url = %request.url, error = %err, "URL denied by policy");
+        if let Some(logger) = &self.audit_logger {
+            if let Err(e) = logger
+                .log_event(self.create_denial_entry(tenant_id, request, &err))
+                .await
+            {
+                warn!(error = %e, "Failed to audit policy denial");
+            }
+        }
+        Err(err)
+    }
+
+    /// Check the pipeline hints of a request for `url` against the tenant's
+    /// policy, auditing denials
+    pub async fn check_hints(
+        &self,
+        tenant_id: &TenantId,
+        url: &str,
+        hints: &PipelineHints,
+    ) -> SecurityResult<()> {
+        let policy = self.policy_for(tenant_id).await;
+        let Some(reason) = policy.policy.pipeline_hints.violation(hints) else {
+            return Ok(());
+        };
+
+        let err = SecurityError::PolicyDenied {
+            url: url.to_string(),
+            rule: PolicyRule::PipelineHint,
+            reason,
+        };
+        warn!(tenant_id = %tenant_id, url = %url, error = %err, "Pipeline hints denied by policy");
+        if let Some(logger) = &self.audit_logger {
+            let request = PolicyRequest {
+                url,
+                server_country: None,
+            };
+            if let Err(e) = logger
+                .log_event(self.create_denial_entry(tenant_id, &request, &err))
+                .await
+            {
+                warn!(error = %e, "Failed to audit policy denial");
+            }
+        }
+        Err(err)
+    }
+
+    async fn policy_for(&self, tenant_id: &TenantId) -> Arc<CompiledPolicy> {
+        self.tenants
+            .read()
+            .await
+            .get(tenant_id)
+            .cloned()
+            .unwrap_or_else(|| self.default_policy.clone())
+    }
+
+    fn evaluate(
+        &self,
+        compiled: &CompiledPolicy,
+        request: &PolicyRequest<'_>,
+    ) -> SecurityResult<()> {
+        let policy = &compiled.policy;
+        let deny = |rule, reason: String| SecurityError::PolicyDenied {
+            url: request.url.to_string(),
+            rule,
+            reason,
+        };
+
+        let host = Url::parse(request.url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_ascii_lowercase()))
+            .ok_or_else(|| deny(PolicyRule::UrlPattern, "URL has no host".to_string()))?;
+
+        if let Some(domain) = policy
+            .blocked_domains
+            .iter()
+            .find(|domain| host_matches(&host, domain))
+        {
+            return Err(deny(
+                PolicyRule::DomainBlocklist,
+                format!("domain {} is blocked", domain),
+            ));
+        }
+
+        if let Some(pattern) = compiled.patterns.iter().find(|p| p.is_match(request.url)) {
+            return Err(deny(
+                PolicyRule::UrlPattern,
+                format!("URL matches blocked pattern {}", pattern.as_str()),
+            ));
+        }
+
+        match request.server_country {
+            None if policy.allowed_countries.is_some() => {
+                return Err(deny(
+                    PolicyRule::Jurisdiction,
+                    "server country is unknown".to_string(),
+                ));
+            }
+            None => {}
+            Some(country) => {
+                let blocked = policy
+                    .blocked_countries
+                    .iter()
+                    .any(|c| c.eq_ignore_ascii_case(country));
+                let not_allowed = policy.allowed_countries.as_ref().is_some_and(|allowed| {
+                    !allowed.iter().any(|c| c.eq_ignore_ascii_case(country))
+                });
+                if blocked || not_allowed {
+                    return Err(deny(
+                        PolicyRule::Jurisdiction,
+                        format!("server is located in restricted jurisdiction {}", country),
+                    ));
+                }
+            }
+        }
+
+        if let Some(category) = policy.excluded_categories.iter().find(|category| {
+            self.category_domains
+                .get(category.as_str())
+                .is_some_and(|domains| domains.iter().any(|d| host_matches(&host, d)))
+        }) {
+            return Err(deny(
+                PolicyRule::ContentCategory,
+                format!("content category {} is excluded", category),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Create an audit entry for a policy denial
+    fn create_denial_entry(
+        &self,
+        tenant_id: &TenantId,
+        request: &PolicyRequest<'_>,
+        err: &SecurityError,
+    ) -> AuditLogEntry {
+        let mut metadata = HashMap::new();
+        if let SecurityError::PolicyDenied { rule, .. } = err {
+            metadata.insert("rule".to_string(), Value::String(rule.to_string()));
+        }
+        if let Some(country) = request.server_country {
+            metadata.insert(
+                "server_country".to_string(),
+                Value::String(country.to_string()),
+            );
+        }
+
+        AuditLogEntry {
+            id: Uuid::new_v4().to_string(),
+            timestamp: Utc::now(),
+            event_type: SecurityEventType::PolicyDenied,
+            severity: SecuritySeverity::Medium,
+            tenant_id: Some(tenant_id.clone()),
+            user_id: None,
+            api_key_id: None,
+            request_id: None,
+            source_ip: None,
+            user_agent: None,
+            resource: Some(request.url.to_string()),
+            action: "fetch".to_string(),
+            outcome: AuditOutcome::Failure,
+            details: AuditDetails {
+                description: "Fetch denied by crawl policy".to_string(),
+                error_message: Some(err.to_string()),
+                request_payload: None,
+                response_payload: None,
+                duration_ms: None,
+                bytes_processed: None,
+                cost_usd: None,
+                tokens_used: None,
+                model_name: None,
+                rate_limit_info: None,
+                pii_redacted: false,
+                pii_detections: None,
+            },
+            metadata,
+        }
+    }
+}
+
+/// Whether `host` is `domain` or one of its subdomains
+fn host_matches(host: &str, domain: &str) -> bool {
+    let domain = domain.trim_start_matches('.').to_ascii_lowercase();
+    host == domain || host.ends_with(&format!(".{}", domain))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine() -> PolicyEngine {
+        let config = PolicyConfig {
+            default_policy: CrawlPolicy {
+                blocked_domains: vec!["blocked.example".to_string()],
+                blocked_url_patterns: vec![r"/private/".to_string()],
+                blocked_countries: vec!["KP".to_string()],
+                excluded_categories: vec!["gambling".to_string()],
+                ..Default::default()
+            },
+            tenants: HashMap::from([(
+                "eu-only".to_string(),
+                CrawlPolicy {
+                    allowed_countries: Some(vec!["DE".to_string(), "FR".to_string()]),
+                    ..Default::default()
+                },
+            )]),
+            category_domains: HashMap::from([(
+                "gambling".to_string(),
+                vec!["casino.example".to_string()],
+            )]),
+        };
+        PolicyEngine::new(config, None).unwrap()
+    }
+
+    async fn denied_rule(
+        engine: &PolicyEngine,
+        tenant: &str,
+        url: &str,
+        country: Option<&str>,
+    ) -> Option<PolicyRule> {
+        let request = PolicyRequest {
+            url,
+            server_country: country,
+        };
+        match engine.check(&TenantId::from(tenant), &request).await {
+            Ok(()) => None,
+            Err(SecurityError::PolicyDenied { rule, .. }) => Some(rule),
+            Err(e) => panic!("unexpected error: {}", e),
+        }
+    }
+
+    async fn denied_hint(
+        engine: &PolicyEngine,
+        tenant: &str,
+        hints: &PipelineHints,
+    ) -> Option<PolicyRule> {
+        match engine
+            .check_hints(&TenantId::from(tenant), "https://ok.example/", hints)
+            .await
+        {
+            Ok(()) => None,
+            Err(SecurityError::PolicyDenied { rule, .. }) => Some(rule),
+            Err(e) => panic!("unexpected error: {}", e),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_policy_rules() {
+        let engine = engine();
+        assert_eq!(
+            denied_rule(&engine, "t1", "https://www.blocked.example/", None).await,
+            Some(PolicyRule::DomainBlocklist)
+        );
+        assert_eq!(
+            denied_rule(&engine, "t1", "https://ok.example/private/x", None).await,
+            Some(PolicyRule::UrlPattern)
+        );
+        assert_eq!(
+            denied_rule(&engine, "t1", "https://ok.example/", Some("kp")).await,
+            Some(PolicyRule::Jurisdiction)
+        );
+        assert_eq!(
+            denied_rule(&engine, "t1", "https://casino.example/", None).await,
+            Some(PolicyRule::ContentCategory)
+        );
+        assert_eq!(
+            denied_rule(&engine, "t1", "https://ok.example/", Some("DE")).await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tenant_policy_replaces_default() {
+        let engine = engine();
+        assert_eq!(
+            denied_rule(&engine, "eu-only", "https://blocked.example/", Some("FR")).await,
+            None
+        );
+        assert_eq!(
+            denied_rule(&engine, "eu-only", "https://ok.example/", Some("US")).await,
+            Some(PolicyRule::Jurisdiction)
+        );
+        // Without a resolved country an allow-list fails closed
+        assert_eq!(
+            denied_rule(&engine, "eu-only", "https://ok.example/", None).await,
+            Some(PolicyRule::Jurisdiction)
+        );
+        assert!(engine.needs_jurisdiction(&TenantId::from("eu-only")).await);
+        assert!(
+            !PolicyEngine::permissive()
+                .needs_jurisdiction(&TenantId::from("any"))
+                .await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_hints_limited_by_tenant() {
+        let engine = engine();
+        let strict = CrawlPolicy {
+            pipeline_hints: HintPolicy {
+                allow_force_headless: false,
+                allowed_stealth_presets: Some(vec!["low".to_string(), "medium".to_string()]),
+                max_budget_usd: Some(0.5),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        engine
+            .set_tenant_policy(TenantId::from("strict"), strict)
+            .await
+            .unwrap();
+
+        let headless = PipelineHints {
+            force_headless: true,
+            ..Default::default()
+        };
+        let preset = |name: &str| PipelineHints {
+            stealth_preset: Some(name.to_string()),
+            skip_cache: true,
+            ..Default::default()
+        };
+        let budget = |usd| PipelineHints {
+            max_budget_usd: Some(usd),
+            ..Default::default()
+        };
+
+        assert_eq!(denied_hint(&engine, "t1", &headless).await, None);
+        assert_eq!(
+            denied_hint(&engine, "strict", &headless).await,
+            Some(PolicyRule::PipelineHint)
+        );
+        assert_eq!(
+            denied_hint(&engine, "strict", &preset("Medium")).await,
+            None
+        );
+        assert_eq!(
+            denied_hint(&engine, "strict", &preset("high")).await,
+            Some(PolicyRule::PipelineHint)
+        );
+        assert_eq!(denied_hint(&engine, "strict", &budget(0.25)).await, None);
+        assert_eq!(
+            denied_hint(&engine, "strict", &budget(2.0)).await,
+            Some(PolicyRule::PipelineHint)
+        );
+    }
+
+    #[test]
+    fn test_invalid_pattern_rejected() {
+        let config = PolicyConfig {
+            default_policy: CrawlPolicy {
+                blocked_url_patterns: vec!["(".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(PolicyEngine::new(config, None).is_err());
+    }
+}
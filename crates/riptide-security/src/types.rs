@@ -95,6 +95,7 @@ pub enum SecurityEventType {
     SecurityViolation,
     AuditLogAccess,
     ConfigurationChange,
+    PolicyDenied,
     SystemStartup,
     SystemShutdown,
 }
@@ -115,6 +116,7 @@ impl fmt::Display for SecurityEventType {
             SecurityEventType::SecurityViolation => "SECURITY_VIOLATION",
             SecurityEventType::AuditLogAccess => "AUDIT_LOG_ACCESS",
             SecurityEventType::ConfigurationChange => "CONFIGURATION_CHANGE",
+            SecurityEventType::PolicyDenied => "POLICY_DENIED",
             SecurityEventType::SystemStartup => "SYSTEM_STARTUP",
             SecurityEventType::SystemShutdown => "SYSTEM_SHUTDOWN",
         };
@@ -289,6 +291,13 @@ pub enum SecurityError {
     #[error("Network error: {0}")]
     NetworkError(String),
 
+    #[error("Policy denied {url} ({rule}): {reason}")]
+    PolicyDenied {
+        url: String,
+        rule: crate::policy::PolicyRule,
+        reason: String,
+    },
+
     #[error("Unknown security error: {0}")]
     Unknown(String),
 }
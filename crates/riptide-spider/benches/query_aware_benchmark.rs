@@ -34,6 +34,7 @@ impl QueryAwareBenchmark {
             relevance_window_size: 10,
             bm25_k1: 1.2,
             bm25_b: 0.75,
+            ..Default::default()
         };
 
         // Generate realistic test data
@@ -112,7 +112,7 @@ impl SiteType {
 }
 
 /// Content analysis metrics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContentMetrics {
     /// Unique text characters in the content
     pub unique_text_chars: usize,
@@ -142,6 +142,77 @@ impl ContentMetrics {
     }
 }
 
+/// Name of the built-in signal that fires on consecutive empty pages
+pub const EMPTY_PAGES_SIGNAL: &str = "empty_pages";
+/// Name of the built-in signal that fires on sustained low content gain
+pub const CONTENT_GAIN_SIGNAL: &str = "content_gain";
+/// Name of the built-in signal that fires on low average content quality
+pub const CONTENT_QUALITY_SIGNAL: &str = "content_quality";
+
+/// Crawl state a [`StopSignal`] is evaluated against
+#[derive(Debug, Clone, Copy)]
+pub struct StopContext {
+    /// Total pages analyzed
+    pub pages_analyzed: usize,
+    /// Average unique characters gained per page over the window
+    pub current_gain_average: f64,
+    /// Content gain threshold in effect
+    pub threshold: f64,
+    /// Site type detected
+    pub detected_site_type: SiteType,
+}
+
+/// Outcome of evaluating one stop signal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignalReport {
+    /// Signal name
+    pub name: String,
+    /// Whether the signal asks to stop the crawl
+    pub triggered: bool,
+    /// Human-readable explanation of the outcome
+    pub detail: String,
+}
+
+impl SignalReport {
+    /// Report a signal asking to stop
+    pub fn triggered(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            triggered: true,
+            detail: detail.into(),
+        }
+    }
+
+    /// Report a signal that does not ask to stop
+    pub fn quiet(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            triggered: false,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// A user-provided condition for ending a crawl
+///
+/// Signals see every analyzed page through [`StopSignal::observe`] and are
+/// evaluated together with the built-in signals on each stop check, once
+/// `min_pages_before_stop` pages have been analyzed. Any triggered signal
+/// stops the crawl.
+pub trait StopSignal: Send + Sync {
+    /// Name reported in [`StopDecision::signals`]
+    fn name(&self) -> &str;
+
+    /// Record an analyzed crawl result
+    fn observe(&self, _result: &CrawlResult, _metrics: &ContentMetrics) {}
+
+    /// Decide whether the crawl should stop
+    fn evaluate(&self, context: &StopContext) -> SignalReport;
+
+    /// Clear accumulated state when the engine is reset
+    fn reset(&self) {}
+}
+
 /// Adaptive stop decision information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StopDecision {
@@ -159,15 +230,42 @@ pub struct StopDecision {
     pub pages_analyzed: usize,
     /// Site type detected
     pub detected_site_type: SiteType,
+    /// Outcome of every signal evaluated for this decision
+    #[serde(default)]
+    pub signals: Vec<SignalReport>,
+}
+
+impl StopDecision {
+    /// Signals that asked to stop the crawl
+    pub fn triggered_signals(&self) -> impl Iterator<Item = &SignalReport> {
+        self.signals.iter().filter(|signal| signal.triggered)
+    }
 }
 
 /// Site analysis data including URL and content metrics
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct SiteAnalysisData {
     url: String,
     metrics: ContentMetrics,
 }
 
+/// Adaptive stop state as stored in crawl checkpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdaptiveStopState {
+    /// Content gain window
+    pub content_window: ContentWindow,
+    /// Recent quality scores
+    pub quality_scores: Vec<f64>,
+    /// Consecutive low-gain count
+    pub consecutive_low_gain: usize,
+    /// Total pages analyzed
+    pub pages_analyzed: usize,
+    /// Site type detected
+    pub detected_site_type: SiteType,
+    /// Recent samples used for site type detection
+    site_analysis_samples: Vec<SiteAnalysisData>,
+}
+
 /// Adaptive stopping engine
 pub struct AdaptiveStopEngine {
     config: AdaptiveStopConfig,
@@ -188,6 +286,9 @@ pub struct AdaptiveStopEngine {
 
     // Performance tracking
     analysis_times: Arc<RwLock<VecDeque<Duration>>>,
+
+    // User-provided stop signals
+    signals: Arc<RwLock<Vec<Arc<dyn StopSignal>>>>,
 }
 
 impl AdaptiveStopEngine {
@@ -200,23 +301,32 @@ impl AdaptiveStopEngine {
             detected_site_type: Arc::new(RwLock::new(SiteType::Unknown)),
             site_analysis_samples: Arc::new(RwLock::new(Vec::new())),
             analysis_times: Arc::new(RwLock::new(VecDeque::new())),
+            signals: Arc::new(RwLock::new(Vec::new())),
             config,
         }
     }
 
+    /// Register a custom stop signal, evaluated alongside the built-in ones
+    pub async fn add_signal(&self, signal: Arc<dyn StopSignal>) {
+        info!(signal = signal.name(), "Registered adaptive stop signal");
+        self.signals.write().await.push(signal);
+    }
+
     /// Analyze a crawl result and update internal state
     pub async fn analyze_result(&self, result: &CrawlResult) -> Result<ContentMetrics> {
         let start_time = Instant::now();
 
         if !result.success {
             // Don't analyze failed results
-            return Ok(ContentMetrics {
+            let metrics = ContentMetrics {
                 unique_text_chars: 0,
                 content_size: 0,
                 link_count: 0,
                 quality_score: 0.0,
                 analysis_time: start_time.elapsed(),
-            });
+            };
+            self.observe_signals(result, &metrics).await;
+            return Ok(metrics);
         }
 
         // Calculate basic metrics
@@ -288,6 +398,8 @@ impl AdaptiveStopEngine {
             }
         }
 
+        self.observe_signals(result, &metrics).await;
+
         debug!(
             unique_chars = unique_text_chars,
             content_size = content_size,
@@ -300,9 +412,20 @@ impl AdaptiveStopEngine {
         Ok(metrics)
     }
 
+    async fn observe_signals(&self, result: &CrawlResult, metrics: &ContentMetrics) {
+        for signal in self.signals.read().await.iter() {
+            signal.observe(result, metrics);
+        }
+    }
+
     /// Check if crawling should stop based on adaptive criteria
+    ///
+    /// Every signal, built-in and custom, is evaluated and reported in
+    /// [`StopDecision::signals`]; the reason is taken from the first one that
+    /// triggered.
     pub async fn should_stop(&self) -> Result<StopDecision> {
         let pages_analyzed = *self.pages_analyzed.read().await;
+        let detected_site_type = *self.detected_site_type.read().await;
 
         // Don't stop if we haven't analyzed enough pages
         if pages_analyzed < self.config.min_pages_before_stop {
@@ -316,163 +439,190 @@ impl AdaptiveStopEngine {
                 threshold_used: self.config.min_gain_threshold,
                 consecutive_low_gain: 0,
                 pages_analyzed,
-                detected_site_type: *self.detected_site_type.read().await,
-            });
-        }
-
-        let detected_site_type = *self.detected_site_type.read().await;
-
-        // CRITICAL FIX 1: Check for consecutive empty content pages
-        let samples = self.site_analysis_samples.read().await;
-        if samples.len() >= 3 {
-            let recent_samples = &samples[samples.len().saturating_sub(3)..];
-            let empty_count = recent_samples
-                .iter()
-                .filter(|s| s.metrics.unique_text_chars == 0 && s.metrics.content_size == 0)
-                .count();
-
-            if empty_count >= 3 {
-                info!(
-                    empty_pages = empty_count,
-                    total_pages = pages_analyzed,
-                    "Stopping: detected consecutive empty pages"
-                );
-                return Ok(StopDecision {
-                    should_stop: true,
-                    reason: format!(
-                        "Detected {} consecutive empty pages - no content being extracted",
-                        empty_count
-                    ),
-                    current_gain_average: 0.0,
-                    threshold_used: self.config.min_gain_threshold,
-                    consecutive_low_gain: empty_count,
-                    pages_analyzed,
-                    detected_site_type,
-                });
-            }
-        }
-
-        // Get current gain average
-        let window = self.content_window.read().await;
-        if !window.has_sufficient_data() {
-            return Ok(StopDecision {
-                should_stop: false,
-                reason: "Insufficient data in content window".to_string(),
-                current_gain_average: f64::INFINITY,
-                threshold_used: self.config.min_gain_threshold,
-                consecutive_low_gain: 0,
-                pages_analyzed,
                 detected_site_type,
+                signals: Vec::new(),
             });
         }
 
-        let current_gain_average = window.average_gain();
+        let mut signals = vec![self.empty_pages_signal().await];
 
-        // Calculate adaptive threshold
-        let threshold = if self.config.enable_adaptive_threshold {
-            self.calculate_adaptive_threshold().await
+        // Get current gain average
+        let (sufficient_data, window_gain) = {
+            let window = self.content_window.read().await;
+            (window.has_sufficient_data(), window.average_gain())
+        };
+        let (current_gain_average, threshold) = if !sufficient_data {
+            (f64::INFINITY, self.config.min_gain_threshold)
+        } else if self.config.enable_adaptive_threshold {
+            (window_gain, self.calculate_adaptive_threshold().await)
         } else {
-            self.config.min_gain_threshold
+            (window_gain, self.config.min_gain_threshold)
         };
 
-        let consecutive_low_gain = *self.consecutive_low_gain.read().await;
-
-        // Check if gain is below threshold
-        if current_gain_average < threshold {
-            let mut consecutive = self.consecutive_low_gain.write().await;
-            *consecutive += 1;
+        let (gain_signal, consecutive_low_gain) = if sufficient_data {
+            self.content_gain_signal(current_gain_average, threshold)
+                .await
+        } else {
+            let report =
+                SignalReport::quiet(CONTENT_GAIN_SIGNAL, "Insufficient data in content window");
+            (report, *self.consecutive_low_gain.read().await)
+        };
+        signals.push(gain_signal);
+        if self.config.enable_quality_scoring {
+            signals.push(self.content_quality_signal().await);
+        }
 
-            debug!(
-                gain = current_gain_average,
-                threshold = threshold,
-                consecutive = *consecutive,
-                patience = self.config.patience,
-                "Low gain detected"
-            );
+        let context = StopContext {
+            pages_analyzed,
+            current_gain_average,
+            threshold,
+            detected_site_type,
+        };
+        for signal in self.signals.read().await.iter() {
+            let mut report = signal.evaluate(&context);
+            report.name = signal.name().to_string();
+            signals.push(report);
+        }
 
-            // Check if we've been patient enough
-            if *consecutive >= self.config.patience {
+        let triggered = signals.iter().find(|signal| signal.triggered);
+        let should_stop = triggered.is_some();
+        let reason = match triggered {
+            Some(signal) => {
                 info!(
+                    signal = %signal.name,
+                    reason = %signal.detail,
+                    pages = pages_analyzed,
+                    "Stopping: adaptive stop signal triggered"
+                );
+                signal.detail.clone()
+            }
+            None if !sufficient_data => "Insufficient data in content window".to_string(),
+            None => {
+                debug!(
                     gain = current_gain_average,
                     threshold = threshold,
-                    consecutive = *consecutive,
+                    consecutive_low = consecutive_low_gain,
                     pages = pages_analyzed,
-                    "Stopping: low content gain exceeded patience"
+                    "Continuing crawl"
                 );
-                return Ok(StopDecision {
-                    should_stop: true,
-                    reason: format!(
-                        "Low content gain for {} consecutive iterations (gain: {:.2} < threshold: {:.2})",
-                        *consecutive, current_gain_average, threshold
-                    ),
-                    current_gain_average,
-                    threshold_used: threshold,
-                    consecutive_low_gain: *consecutive,
-                    pages_analyzed,
-                    detected_site_type,
-                });
+                "Continue crawling".to_string()
             }
+        };
+
+        Ok(StopDecision {
+            should_stop,
+            reason,
+            current_gain_average,
+            threshold_used: threshold,
+            consecutive_low_gain,
+            pages_analyzed,
+            detected_site_type,
+            signals,
+        })
+    }
+
+    /// Built-in signal: the last pages yielded no content at all
+    async fn empty_pages_signal(&self) -> SignalReport {
+        let samples = self.site_analysis_samples.read().await;
+        let recent_samples = &samples[samples.len().saturating_sub(3)..];
+        let empty_count = recent_samples
+            .iter()
+            .filter(|s| s.metrics.unique_text_chars == 0 && s.metrics.content_size == 0)
+            .count();
+
+        if empty_count >= 3 {
+            SignalReport::triggered(
+                EMPTY_PAGES_SIGNAL,
+                format!(
+                    "Detected {} consecutive empty pages - no content being extracted",
+                    empty_count
+                ),
+            )
         } else {
+            SignalReport::quiet(
+                EMPTY_PAGES_SIGNAL,
+                format!("{} of the last 3 pages were empty", empty_count),
+            )
+        }
+    }
+
+    /// Built-in signal: content gain stayed below the threshold for longer
+    /// than the configured patience
+    ///
+    /// Returns the report and the updated consecutive low-gain count.
+    async fn content_gain_signal(&self, gain: f64, threshold: f64) -> (SignalReport, usize) {
+        let mut consecutive = self.consecutive_low_gain.write().await;
+        if gain >= threshold {
             // Reset consecutive counter on good gain
-            *self.consecutive_low_gain.write().await = 0;
+            *consecutive = 0;
+            let detail = format!("Content gain {:.2} >= threshold {:.2}", gain, threshold);
+            return (SignalReport::quiet(CONTENT_GAIN_SIGNAL, detail), 0);
         }
 
-        // CRITICAL FIX 2: Improved quality threshold checking
-        if self.config.enable_quality_scoring {
-            let quality_scores = self.quality_scores.read().await;
-            if quality_scores.len() >= 3 {
-                // Safe conversion: quality_scores.len() is small (VecDeque bounded)
-                #[allow(clippy::cast_precision_loss)]
-                let avg_quality: f64 =
-                    quality_scores.iter().sum::<f64>() / quality_scores.len() as f64;
+        *consecutive += 1;
+        debug!(
+            gain = gain,
+            threshold = threshold,
+            consecutive = *consecutive,
+            patience = self.config.patience,
+            "Low gain detected"
+        );
 
-                debug!(
-                    avg_quality = avg_quality,
-                    threshold = self.config.quality_threshold,
-                    samples = quality_scores.len(),
-                    "Quality check"
-                );
+        let report = if *consecutive >= self.config.patience {
+            SignalReport::triggered(
+                CONTENT_GAIN_SIGNAL,
+                format!(
+                    "Low content gain for {} consecutive iterations (gain: {:.2} < threshold: {:.2})",
+                    *consecutive, gain, threshold
+                ),
+            )
+        } else {
+            SignalReport::quiet(
+                CONTENT_GAIN_SIGNAL,
+                format!(
+                    "Low content gain for {} of {} allowed iterations",
+                    *consecutive, self.config.patience
+                ),
+            )
+        };
+        (report, *consecutive)
+    }
 
-                if avg_quality < self.config.quality_threshold {
-                    info!(
-                        avg_quality = avg_quality,
-                        threshold = self.config.quality_threshold,
-                        pages = pages_analyzed,
-                        "Stopping: low average content quality"
-                    );
-                    return Ok(StopDecision {
-                        should_stop: true,
-                        reason: format!(
-                            "Low content quality average: {:.3} < {:.3}",
-                            avg_quality, self.config.quality_threshold
-                        ),
-                        current_gain_average,
-                        threshold_used: threshold,
-                        consecutive_low_gain,
-                        pages_analyzed,
-                        detected_site_type,
-                    });
-                }
-            }
+    /// Built-in signal: average content quality fell below the threshold
+    async fn content_quality_signal(&self) -> SignalReport {
+        let quality_scores = self.quality_scores.read().await;
+        if quality_scores.len() < 3 {
+            return SignalReport::quiet(CONTENT_QUALITY_SIGNAL, "Not enough quality samples");
         }
 
+        // Safe conversion: quality_scores.len() is small (VecDeque bounded)
+        #[allow(clippy::cast_precision_loss)]
+        let avg_quality: f64 = quality_scores.iter().sum::<f64>() / quality_scores.len() as f64;
+
         debug!(
-            gain = current_gain_average,
-            threshold = threshold,
-            consecutive_low = consecutive_low_gain,
-            pages = pages_analyzed,
-            "Continuing crawl"
+            avg_quality = avg_quality,
+            threshold = self.config.quality_threshold,
+            samples = quality_scores.len(),
+            "Quality check"
         );
 
-        Ok(StopDecision {
-            should_stop: false,
-            reason: "Continue crawling".to_string(),
-            current_gain_average,
-            threshold_used: threshold,
-            consecutive_low_gain,
-            pages_analyzed,
-            detected_site_type,
-        })
+        if avg_quality < self.config.quality_threshold {
+            SignalReport::triggered(
+                CONTENT_QUALITY_SIGNAL,
+                format!(
+                    "Low content quality average: {:.3} < {:.3}",
+                    avg_quality, self.config.quality_threshold
+                ),
+            )
+        } else {
+            SignalReport::quiet(
+                CONTENT_QUALITY_SIGNAL,
+                format!(
+                    "Content quality average {:.3} >= {:.3}",
+                    avg_quality, self.config.quality_threshold
+                ),
+            )
+        }
     }
 
     /// Calculate quality score for content
@@ -799,10 +949,47 @@ impl AdaptiveStopEngine {
         *self.detected_site_type.write().await = SiteType::Unknown;
         self.site_analysis_samples.write().await.clear();
         self.analysis_times.write().await.clear();
+        for signal in self.signals.read().await.iter() {
+            signal.reset();
+        }
 
         info!("Adaptive stop engine reset");
     }
 
+    /// Export the stop condition state, for crawl checkpoints
+    pub async fn export_state(&self) -> AdaptiveStopState {
+        AdaptiveStopState {
+            content_window: self.content_window.read().await.clone(),
+            quality_scores: self.quality_scores.read().await.iter().copied().collect(),
+            consecutive_low_gain: *self.consecutive_low_gain.read().await,
+            pages_analyzed: *self.pages_analyzed.read().await,
+            detected_site_type: *self.detected_site_type.read().await,
+            site_analysis_samples: self.site_analysis_samples.read().await.clone(),
+        }
+    }
+
+    /// Replace the stop condition state with a previously exported one
+    ///
+    /// Fails if the window size changed since the export.
+    pub async fn restore_state(&self, state: AdaptiveStopState) -> Result<()> {
+        if state.content_window.window_size != self.config.window_size {
+            anyhow::bail!(
+                "Adaptive stop window size changed from {} to {}",
+                state.content_window.window_size,
+                self.config.window_size
+            );
+        }
+
+        *self.content_window.write().await = state.content_window;
+        *self.quality_scores.write().await = state.quality_scores.into();
+        *self.consecutive_low_gain.write().await = state.consecutive_low_gain;
+        *self.pages_analyzed.write().await = state.pages_analyzed;
+        *self.detected_site_type.write().await = state.detected_site_type;
+        *self.site_analysis_samples.write().await = state.site_analysis_samples;
+        self.analysis_times.write().await.clear();
+        Ok(())
+    }
+
     /// Get configuration
     pub fn get_config(&self) -> &AdaptiveStopConfig {
         &self.config
@@ -1051,4 +1238,98 @@ mod tests {
             hints.default_multiplier
         );
     }
+
+    /// Stops once a given number of pages has been observed
+    struct PageCountSignal {
+        limit: usize,
+        seen: std::sync::atomic::AtomicUsize,
+    }
+
+    impl StopSignal for PageCountSignal {
+        fn name(&self) -> &str {
+            "page_count"
+        }
+
+        fn observe(&self, _result: &CrawlResult, _metrics: &ContentMetrics) {
+            self.seen.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+
+        fn evaluate(&self, _context: &StopContext) -> SignalReport {
+            let seen = self.seen.load(std::sync::atomic::Ordering::Relaxed);
+            let detail = format!("{} of {} pages", seen, self.limit);
+            if seen >= self.limit {
+                SignalReport::triggered(self.name(), detail)
+            } else {
+                SignalReport::quiet(self.name(), detail)
+            }
+        }
+
+        fn reset(&self) {
+            self.seen.store(0, std::sync::atomic::Ordering::Relaxed);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_stop_signal_is_reported() {
+        let config = AdaptiveStopConfig {
+            min_pages_before_stop: 2,
+            enable_quality_scoring: false,
+            ..Default::default()
+        };
+        let engine = AdaptiveStopEngine::new(config);
+        engine
+            .add_signal(Arc::new(PageCountSignal {
+                limit: 3,
+                seen: Default::default(),
+            }))
+            .await;
+
+        let text = "Plenty of varied content on every page. ".repeat(50);
+        let analyze = |i: usize| {
+            let url = Url::from_str(&format!("https://example.com/p{}", i)).expect("Valid URL");
+            let mut result = CrawlResult::success(CrawlRequest::new(url));
+            result.text_content = Some(format!("{} {}", i, text));
+            result.content_size = 4096;
+            result
+        };
+
+        for i in 0..2 {
+            engine
+                .analyze_result(&analyze(i))
+                .await
+                .expect("Analysis should work");
+        }
+        let decision = engine.should_stop().await.expect("Decision should work");
+        assert!(!decision.should_stop, "{}", decision.reason);
+        let names: Vec<&str> = decision.signals.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![EMPTY_PAGES_SIGNAL, CONTENT_GAIN_SIGNAL, "page_count"]
+        );
+
+        engine
+            .analyze_result(&analyze(2))
+            .await
+            .expect("Analysis should work");
+        let decision = engine.should_stop().await.expect("Decision should work");
+        assert!(decision.should_stop);
+        assert_eq!(decision.reason, "3 of 3 pages");
+        let triggered: Vec<&str> = decision
+            .triggered_signals()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert_eq!(triggered, vec!["page_count"]);
+
+        engine.reset().await;
+        engine
+            .analyze_result(&analyze(3))
+            .await
+            .expect("Analysis should work");
+        engine
+            .analyze_result(&analyze(4))
+            .await
+            .expect("Analysis should work");
+        let decision = engine.should_stop().await.expect("Decision should work");
+        assert!(!decision.should_stop, "{}", decision.reason);
+    }
 }
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -17,6 +17,9 @@ pub struct BudgetConfig {
     pub per_host: PerHostBudgetLimits,
     /// Per-session budget limits
     pub per_session: Option<PerSessionBudgetLimits>,
+    /// Maximum pages per domain; a domain also covers its subdomains
+    #[serde(default)]
+    pub per_domain_max_pages: HashMap<String, u64>,
     /// Enforcement strategy
     pub enforcement: EnforcementStrategy,
     /// Budget monitoring interval
@@ -33,6 +36,7 @@ impl Default for BudgetConfig {
             global: GlobalBudgetLimits::default(),
             per_host: PerHostBudgetLimits::default(),
             per_session: None,
+            per_domain_max_pages: HashMap::new(),
             enforcement: EnforcementStrategy::Strict,
             monitoring_interval: Duration::from_secs(60),
             enable_warnings: true,
@@ -194,8 +198,38 @@ impl BudgetUsage {
     }
 }
 
+/// Budget counters as stored in crawl checkpoints
+///
+/// Elapsed time is stored instead of `Instant`s so duration limits keep
+/// counting from where the checkpointed crawl stopped.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BudgetCounters {
+    /// Pages crawled so far
+    pub pages_crawled: u64,
+    /// Bandwidth used so far
+    pub bandwidth_used: u64,
+    /// Deepest depth reached
+    pub current_depth: u32,
+    /// Crawl time elapsed so far
+    pub elapsed: Duration,
+    /// Per-host counters
+    pub hosts: HashMap<String, HostBudgetCounters>,
+}
+
+/// Per-host budget counters as stored in crawl checkpoints
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HostBudgetCounters {
+    /// Pages crawled on this host
+    pub pages_crawled: u64,
+    /// Bandwidth used on this host
+    pub bandwidth_used: u64,
+    /// Deepest depth reached on this host
+    pub current_depth: u32,
+}
+
 /// Types of budget limits
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum BudgetLimitType {
     Pages,
     Duration,
@@ -205,6 +239,19 @@ pub enum BudgetLimitType {
     Depth,
 }
 
+impl BudgetLimitType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BudgetLimitType::Pages => "pages",
+            BudgetLimitType::Duration => "duration",
+            BudgetLimitType::Bandwidth => "bandwidth",
+            BudgetLimitType::Memory => "memory",
+            BudgetLimitType::Concurrent => "concurrent",
+            BudgetLimitType::Depth => "depth",
+        }
+    }
+}
+
 /// Per-host budget tracking
 #[derive(Debug, Clone)]
 pub struct HostBudget {
@@ -277,14 +324,38 @@ impl HostBudget {
 }
 
 /// Budget violation information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BudgetViolation {
     pub limit_type: BudgetLimitType,
     pub current_value: u64,
     pub limit_value: u64,
+    /// Host or domain of a per-host or per-domain limit
     pub host: Option<String>,
 }
 
+impl std::fmt::Display for BudgetViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} budget", self.limit_type.as_str())?;
+        if let Some(host) = &self.host {
+            write!(f, " for {}", host)?;
+        }
+        write!(f, " ({}/{})", self.current_value, self.limit_value)
+    }
+}
+
+/// Budget consumption of a crawl, and the budget that stopped it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BudgetReport {
+    /// Pages crawled
+    pub pages_crawled: u64,
+    /// Bytes downloaded
+    pub bandwidth_used: u64,
+    /// Budget whose exhaustion stopped the crawl
+    pub tripped: Option<BudgetViolation>,
+    /// Domains whose per-domain page budget was used up
+    pub exhausted_domains: Vec<String>,
+}
+
 impl BudgetViolation {
     pub fn utilization_percentage(&self) -> f64 {
         if self.limit_value == 0 {
@@ -322,6 +393,9 @@ pub struct BudgetManager {
     bandwidth_used: AtomicU64,
     concurrent_requests: AtomicUsize,
 
+    // Per-domain exhaustion tracking
+    exhausted_domains: Arc<RwLock<BTreeSet<String>>>,
+
     // Monitoring
     last_monitoring: Arc<RwLock<Instant>>,
     warnings_issued: Arc<RwLock<Vec<BudgetWarning>>>,
@@ -341,6 +415,7 @@ impl BudgetManager {
             pages_crawled: AtomicU64::new(0),
             bandwidth_used: AtomicU64::new(0),
             concurrent_requests: AtomicUsize::new(0),
+            exhausted_domains: Arc::new(RwLock::new(BTreeSet::new())),
             last_monitoring: Arc::new(RwLock::new(Instant::now())),
             warnings_issued: Arc::new(RwLock::new(Vec::new())),
         }
@@ -516,49 +591,113 @@ impl BudgetManager {
             }
         }
 
+        Ok(self.global_exhaustion(&global))
+    }
+
+    /// First exhausted crawl-wide budget: pages, duration or bandwidth
+    fn global_exhaustion(&self, global: &BudgetUsage) -> Option<BudgetViolation> {
         if let Some(max_pages) = self.config.global.max_pages {
             if global.pages_crawled >= max_pages {
-                return Ok(Some(BudgetViolation {
+                return Some(BudgetViolation {
                     limit_type: BudgetLimitType::Pages,
                     current_value: global.pages_crawled,
                     limit_value: max_pages,
                     host: None,
-                }));
+                });
             }
         }
 
         if let Some(max_duration) = self.config.global.max_duration {
             if global.duration() >= max_duration {
-                return Ok(Some(BudgetViolation {
+                return Some(BudgetViolation {
                     limit_type: BudgetLimitType::Duration,
                     current_value: global.duration().as_secs(),
                     limit_value: max_duration.as_secs(),
                     host: None,
-                }));
+                });
             }
         }
 
         if let Some(max_bandwidth) = self.config.global.max_bandwidth {
             if global.bandwidth_used >= max_bandwidth {
-                return Ok(Some(BudgetViolation {
+                return Some(BudgetViolation {
                     limit_type: BudgetLimitType::Bandwidth,
                     current_value: global.bandwidth_used,
                     limit_value: max_bandwidth,
                     host: None,
-                }));
+                });
             }
         }
 
-        Ok(None)
+        None
     }
 
     /// Check host-specific budget limits
     async fn check_host_limits(&self, host: &str, _depth: u32) -> Result<Option<BudgetViolation>> {
         let host_budgets = self.host_budgets.read().await;
-        if let Some(host_budget) = host_budgets.get(host) {
-            return Ok(host_budget.is_limit_exceeded());
+        if let Some(violation) = host_budgets.get(host).and_then(|b| b.is_limit_exceeded()) {
+            return Ok(Some(violation));
+        }
+
+        // Per-domain page budgets, counted across the domain's hosts
+        let Some((domain, &max_pages)) = self
+            .config
+            .per_domain_max_pages
+            .iter()
+            .filter(|(domain, _)| host_in_domain(host, domain))
+            .max_by_key(|(domain, _)| domain.len())
+        else {
+            return Ok(None);
+        };
+        let pages: u64 = host_budgets
+            .values()
+            .filter(|b| host_in_domain(&b.host, domain))
+            .map(|b| b.usage.pages_crawled)
+            .sum();
+        if pages < max_pages {
+            return Ok(None);
+        }
+        drop(host_budgets);
+
+        if self.exhausted_domains.write().await.insert(domain.clone()) {
+            info!(domain = %domain, pages = pages, "Domain page budget exhausted");
+        }
+        Ok(Some(BudgetViolation {
+            limit_type: BudgetLimitType::Pages,
+            current_value: pages,
+            limit_value: max_pages,
+            host: Some(domain.clone()),
+        }))
+    }
+
+    /// Crawl-wide budget that is used up, if the crawl should hard-stop
+    ///
+    /// Pages, duration and bandwidth budgets stop the crawl unless
+    /// enforcement is [`EnforcementStrategy::Soft`]. Per-host and per-domain
+    /// budgets only reject URLs of their host or domain.
+    pub async fn exhausted_budget(&self) -> Option<BudgetViolation> {
+        if matches!(self.config.enforcement, EnforcementStrategy::Soft) {
+            return None;
+        }
+        let global = self.get_global_usage().await;
+        self.global_exhaustion(&global)
+    }
+
+    /// Budget consumption so far, with the budget that tripped, if any
+    pub async fn report(&self) -> BudgetReport {
+        let global = self.get_global_usage().await;
+        BudgetReport {
+            pages_crawled: global.pages_crawled,
+            bandwidth_used: global.bandwidth_used,
+            tripped: self.exhausted_budget().await,
+            exhausted_domains: self
+                .exhausted_domains
+                .read()
+                .await
+                .iter()
+                .cloned()
+                .collect(),
         }
-        Ok(None)
     }
 
     /// Perform periodic monitoring and issue warnings
@@ -658,6 +797,74 @@ impl BudgetManager {
         host_budgets.get(host).map(|hb| hb.usage.clone())
     }
 
+    /// Export the budget counters, for crawl checkpoints
+    pub async fn export_counters(&self) -> BudgetCounters {
+        let global = self.get_global_usage().await;
+        let hosts = self
+            .host_budgets
+            .read()
+            .await
+            .iter()
+            .map(|(host, budget)| {
+                let counters = HostBudgetCounters {
+                    pages_crawled: budget.usage.pages_crawled,
+                    bandwidth_used: budget.usage.bandwidth_used,
+                    current_depth: budget.usage.current_depth,
+                };
+                (host.clone(), counters)
+            })
+            .collect();
+
+        BudgetCounters {
+            pages_crawled: global.pages_crawled,
+            bandwidth_used: global.bandwidth_used,
+            current_depth: global.current_depth,
+            elapsed: global.duration(),
+            hosts,
+        }
+    }
+
+    /// Replace the budget counters with previously exported ones
+    ///
+    /// In-flight request counts are reset, since no request survives a restart.
+    pub async fn restore_counters(&self, counters: BudgetCounters) {
+        let now = Instant::now();
+
+        self.pages_crawled
+            .store(counters.pages_crawled, Ordering::Relaxed);
+        self.bandwidth_used
+            .store(counters.bandwidth_used, Ordering::Relaxed);
+        self.concurrent_requests.store(0, Ordering::Relaxed);
+
+        *self.global_usage.write().await = BudgetUsage {
+            current_depth: counters.current_depth,
+            pages_crawled: counters.pages_crawled,
+            start_time: Some(now.checked_sub(counters.elapsed).unwrap_or(now)),
+            bandwidth_used: counters.bandwidth_used,
+            ..Default::default()
+        };
+        self.exhausted_domains.write().await.clear();
+
+        let mut host_budgets = self.host_budgets.write().await;
+        host_budgets.clear();
+        for (host, host_counters) in counters.hosts {
+            let mut budget = HostBudget::new(host.clone(), self.config.per_host.clone());
+            budget.usage = BudgetUsage {
+                current_depth: host_counters.current_depth,
+                pages_crawled: host_counters.pages_crawled,
+                bandwidth_used: host_counters.bandwidth_used,
+                ..Default::default()
+            };
+            host_budgets.insert(host, budget);
+        }
+    }
+
+    /// Clear all counters and warnings, for a new crawl
+    pub async fn reset(&self) {
+        self.restore_counters(BudgetCounters::default()).await;
+        self.clear_warnings().await;
+    }
+
     /// Get all warnings issued
     pub async fn get_warnings(&self) -> Vec<BudgetWarning> {
         self.warnings_issued.read().await.clone()
@@ -716,6 +923,12 @@ impl BudgetManager {
     }
 }
 
+/// Whether `host` is `domain` or one of its subdomains
+fn host_in_domain(host: &str, domain: &str) -> bool {
+    host.strip_suffix(domain)
+        .is_some_and(|rest| rest.is_empty() || rest.ends_with('.'))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -843,6 +1056,97 @@ mod tests {
             .expect("Should work"));
     }
 
+    #[tokio::test]
+    async fn test_domain_page_budget() {
+        let mut config = BudgetConfig::default();
+        config
+            .per_domain_max_pages
+            .insert("example.com".to_string(), 2);
+        config.enforcement = EnforcementStrategy::Strict;
+
+        let budget_manager = BudgetManager::new(config);
+        for page in ["https://example.com/a", "https://blog.example.com/b"] {
+            let url = Url::from_str(page).expect("Valid URL");
+            budget_manager
+                .start_request(&url, 0)
+                .await
+                .expect("Should work");
+            budget_manager
+                .complete_request(&url, 100, true)
+                .await
+                .expect("Should work");
+        }
+
+        let url = Url::from_str("https://www.example.com/c").expect("Valid URL");
+        assert!(!budget_manager
+            .can_make_request(&url, 0)
+            .await
+            .expect("Should work"));
+        let url = Url::from_str("https://notexample.com/").expect("Valid URL");
+        assert!(budget_manager
+            .can_make_request(&url, 0)
+            .await
+            .expect("Should work"));
+
+        let report = budget_manager.report().await;
+        assert_eq!(report.exhausted_domains, vec!["example.com".to_string()]);
+        assert!(report.tripped.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_bandwidth_budget_trips_until_reset() {
+        let mut config = BudgetConfig::default();
+        config.global.max_bandwidth = Some(1000);
+        let budget_manager = BudgetManager::new(config);
+        let url = Url::from_str("https://example.com/page").expect("Valid URL");
+
+        assert!(budget_manager.exhausted_budget().await.is_none());
+        budget_manager
+            .start_request(&url, 0)
+            .await
+            .expect("Should work");
+        budget_manager
+            .complete_request(&url, 1500, true)
+            .await
+            .expect("Should work");
+        let violation = budget_manager.exhausted_budget().await.expect("Tripped");
+        assert_eq!(violation.limit_type, BudgetLimitType::Bandwidth);
+        assert_eq!(violation.to_string(), "bandwidth budget (1500/1000)");
+
+        budget_manager.reset().await;
+        assert!(budget_manager.exhausted_budget().await.is_none());
+        let report = budget_manager.report().await;
+        assert_eq!(report.bandwidth_used, 0);
+        assert!(budget_manager.get_host_usage("example.com").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_restore_counters() {
+        let mut config = BudgetConfig::default();
+        config.global.max_pages = Some(1);
+        let url = Url::from_str("https://example.com/page").expect("Valid URL");
+
+        let budget_manager = BudgetManager::new(config.clone());
+        budget_manager
+            .start_request(&url, 0)
+            .await
+            .expect("Should work");
+        budget_manager
+            .complete_request(&url, 512, true)
+            .await
+            .expect("Should work");
+        let counters = budget_manager.export_counters().await;
+        assert_eq!(counters.hosts["example.com"].pages_crawled, 1);
+
+        let restored = BudgetManager::new(config);
+        restored.restore_counters(counters).await;
+        assert_eq!(restored.get_global_usage().await.bandwidth_used, 512);
+        assert!(!restored
+            .can_make_request(&url, 0)
+            .await
+            .expect("Should work"));
+    }
+
     #[tokio::test]
     async fn test_concurrent_request_limit() {
         let mut config = BudgetConfig::default();
@@ -34,7 +34,11 @@
 //! ```
 
 use crate::config::SpiderConfig;
+use crate::core::Spider;
 use crate::extractor::{BasicExtractor, ContentExtractor};
+use crate::query_aware::TextEmbedder;
+use crate::strategy::UrlScorer;
+use riptide_types::ports::SessionProvider;
 use std::sync::Arc;
 
 /// SpiderBuilder - Construct Spider instances with extractor plugins
@@ -65,6 +69,9 @@ pub struct SpiderBuilder {
     respect_robots: Option<bool>,
     max_depth: Option<u32>,
     max_pages: Option<u32>,
+    url_scorer: Option<Arc<dyn UrlScorer>>,
+    session_provider: Option<Arc<dyn SessionProvider>>,
+    text_embedder: Option<Arc<dyn TextEmbedder>>,
 }
 
 impl SpiderBuilder {
@@ -136,6 +143,42 @@ impl SpiderBuilder {
         self
     }
 
+    /// Register a custom URL scorer for the best-first strategy
+    ///
+    /// # Arguments
+    /// * `scorer` - Any UrlScorer implementation, e.g. an LLM-backed scorer
+    ///
+    /// # Default
+    /// `None` - Best-first crawls use `DefaultScoring`
+    pub fn with_url_scorer(mut self, scorer: Arc<dyn UrlScorer>) -> Self {
+        self.url_scorer = Some(scorer);
+        self
+    }
+
+    /// Score query-aware relevance with text embeddings
+    ///
+    /// # Arguments
+    /// * `embedder` - Embeds page text and the target query, e.g. over an LLM provider
+    ///
+    /// # Default
+    /// `None` - Query-aware relevance uses BM25 and URL signals only
+    pub fn with_text_embedder(mut self, embedder: Arc<dyn TextEmbedder>) -> Self {
+        self.text_embedder = Some(embedder);
+        self
+    }
+
+    /// Authenticate fetches with sessions from a session provider
+    ///
+    /// # Arguments
+    /// * `provider` - Supplies cookies/headers per host and renews rejected sessions
+    ///
+    /// # Default
+    /// `None` - Pages are fetched anonymously
+    pub fn with_session_provider(mut self, provider: Arc<dyn SessionProvider>) -> Self {
+        self.session_provider = Some(provider);
+        self
+    }
+
     /// Build a spider instance
     ///
     /// Creates a Spider with the configured settings. If no extractor is set,
@@ -168,6 +211,9 @@ impl SpiderBuilder {
             config,
             extractor: Arc::new(extractor),
             respect_robots: self.respect_robots.unwrap_or(true),
+            url_scorer: self.url_scorer,
+            session_provider: self.session_provider,
+            text_embedder: self.text_embedder,
         }
     }
 
@@ -182,6 +228,9 @@ impl SpiderBuilder {
             config,
             extractor: Arc::new(extractor),
             respect_robots: self.respect_robots.unwrap_or(true),
+            url_scorer: self.url_scorer,
+            session_provider: self.session_provider,
+            text_embedder: self.text_embedder,
         }
     }
 }
@@ -199,6 +248,26 @@ pub struct BuiltSpider {
     pub config: SpiderConfig,
     pub extractor: Arc<Box<dyn ContentExtractor>>,
     pub respect_robots: bool,
+    pub url_scorer: Option<Arc<dyn UrlScorer>>,
+    pub session_provider: Option<Arc<dyn SessionProvider>>,
+    pub text_embedder: Option<Arc<dyn TextEmbedder>>,
+}
+
+impl BuiltSpider {
+    /// Create the crawling engine with this configuration and plugged-in components
+    pub async fn into_spider(self) -> anyhow::Result<Spider> {
+        let mut config = self.config;
+        config.respect_robots = self.respect_robots;
+        let mut spider = Spider::new(config).await?;
+        if let Some(scorer) = self.url_scorer {
+            spider = spider.with_url_scorer(scorer);
+        }
+        if let Some(embedder) = self.text_embedder {
+            spider = spider.with_text_embedder(embedder);
+        }
+        spider.set_session_provider(self.session_provider);
+        Ok(spider)
+    }
 }
 
 #[cfg(test)]
@@ -239,4 +308,31 @@ mod tests {
         assert_eq!(built.extractor.strategy_name(), "basic");
         assert!(built.respect_robots);
     }
+
+    #[test]
+    fn test_builder_url_scorer() {
+        use crate::strategy::DefaultScoring;
+        use crate::types::ScoringConfig;
+
+        let built = SpiderBuilder::new().build();
+        assert!(built.url_scorer.is_none());
+
+        let built = SpiderBuilder::new()
+            .with_url_scorer(Arc::new(DefaultScoring::new(ScoringConfig::default())))
+            .build_raw();
+        let scorer = built.url_scorer.expect("Scorer should be carried over");
+        assert_eq!(scorer.name(), "default");
+    }
+
+    #[test]
+    fn test_builder_session_provider() {
+        use riptide_types::ports::InMemorySessionProvider;
+
+        assert!(SpiderBuilder::new().build().session_provider.is_none());
+
+        let built = SpiderBuilder::new()
+            .with_session_provider(Arc::new(InMemorySessionProvider::new()))
+            .build();
+        assert!(built.session_provider.is_some());
+    }
 }
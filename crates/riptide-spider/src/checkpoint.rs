@@ -0,0 +1,83 @@
+//! Crawl checkpoints for resumable crawls
+//!
+//! A [`SpiderCheckpoint`] captures everything a [`Spider`](crate::Spider)
+//! needs to continue a crawl after the process dies: pending frontier
+//! requests, the visited URL set, budget counters and adaptive stop state.
+//! Checkpoints are JSON-encoded and written through the
+//! [`CheckpointStore`] port, so any store compatible with the persistence
+//! layer's `CheckpointManager` can hold them.
+
+use crate::adaptive_stop::AdaptiveStopState;
+use crate::budget::BudgetCounters;
+use crate::types::CrawlRequest;
+use crate::url_utils::VisitedUrls;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use riptide_types::ports::CheckpointStore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use url::Url;
+
+/// Current checkpoint format version
+pub const CHECKPOINT_VERSION: u32 = 1;
+
+/// Serialized state of an in-progress crawl
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpiderCheckpoint {
+    /// Checkpoint format version
+    pub version: u32,
+    /// When the checkpoint was taken
+    pub created_at: DateTime<Utc>,
+    /// Pages crawled before the checkpoint
+    pub pages_crawled: u64,
+    /// Pages failed before the checkpoint
+    pub pages_failed: u64,
+    /// Domains being crawled
+    pub active_domains: HashSet<String>,
+    /// Pending requests in dequeue order
+    pub frontier: Vec<CrawlRequest>,
+    /// URLs already seen
+    pub visited: VisitedUrls,
+    /// Budget counters
+    pub budget: BudgetCounters,
+    /// Adaptive stop state
+    pub adaptive_stop: AdaptiveStopState,
+    /// Seed URLs the crawl scope is relative to
+    #[serde(default)]
+    pub seeds: Vec<Url>,
+}
+
+impl SpiderCheckpoint {
+    /// Encode the checkpoint for storage
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).context("Failed to serialize spider checkpoint")
+    }
+
+    /// Decode a stored checkpoint
+    ///
+    /// Fails on checkpoints written by a different format version.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let checkpoint: Self =
+            serde_json::from_slice(data).context("Failed to deserialize spider checkpoint")?;
+        if checkpoint.version != CHECKPOINT_VERSION {
+            anyhow::bail!(
+                "Unsupported spider checkpoint version {} (expected {})",
+                checkpoint.version,
+                CHECKPOINT_VERSION
+            );
+        }
+        Ok(checkpoint)
+    }
+}
+
+/// Periodic checkpointing of a running crawl
+#[derive(Clone)]
+pub struct CheckpointSettings {
+    /// Store checkpoints are written to
+    pub store: Arc<dyn CheckpointStore>,
+    /// Id the checkpoint is stored under (overwritten on every write)
+    pub checkpoint_id: String,
+    /// Write a checkpoint every this many processed pages
+    pub interval_pages: u64,
+}
@@ -0,0 +1,164 @@
+//! Compliance reporter for spider crawls
+//!
+//! Collects robots.txt skips, honored crawl delays, meta robots exclusions,
+//! rate-limit responses, and sitemap usage while a crawl runs, and produces a
+//! [`ComplianceReport`] attached to the [`SpiderResult`](crate::SpiderResult).
+
+use chrono::Utc;
+use regex::Regex;
+use riptide_types::compliance::{
+    ComplianceReport, CrawlDelayRecord, MetaRobotsExclusion, RateLimitEvent, RobotsSkip,
+    SitemapRecord,
+};
+use std::sync::{Mutex, OnceLock};
+
+/// Thread-safe collector for a single crawl's compliance events
+#[derive(Debug, Default)]
+pub struct ComplianceReporter {
+    report: Mutex<ComplianceReport>,
+}
+
+impl ComplianceReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a fresh report for a new crawl
+    pub fn reset(&self) {
+        *self.lock() = ComplianceReport::new();
+    }
+
+    /// Record a URL skipped because robots.txt disallows it
+    pub fn record_robots_disallowed(&self, url: &str) {
+        self.lock().robots_disallowed.push(RobotsSkip {
+            url: url.to_string(),
+            at: Utc::now(),
+        });
+    }
+
+    /// Record a request made while honoring a host's crawl delay
+    pub fn record_crawl_delay(&self, host: &str, delay_secs: f64) {
+        let mut report = self.lock();
+        match report.crawl_delays.iter_mut().find(|r| r.host == host) {
+            Some(record) => {
+                record.delay_secs = delay_secs;
+                record.requests += 1;
+            }
+            None => report.crawl_delays.push(CrawlDelayRecord {
+                host: host.to_string(),
+                delay_secs,
+                requests: 1,
+            }),
+        }
+    }
+
+    /// Record a page excluded by meta robots directives
+    pub fn record_meta_robots(&self, url: &str, directives: Vec<String>) {
+        self.lock()
+            .meta_robots_exclusions
+            .push(MetaRobotsExclusion {
+                url: url.to_string(),
+                directives,
+                at: Utc::now(),
+            });
+    }
+
+    /// Record a 429/503 response
+    pub fn record_rate_limited(&self, url: &str, status: u16, retry_after: Option<String>) {
+        self.lock().rate_limited.push(RateLimitEvent {
+            url: url.to_string(),
+            status,
+            retry_after,
+            at: Utc::now(),
+        });
+    }
+
+    /// Record sitemap discovery for a host
+    pub fn record_sitemap(&self, host: &str, urls_discovered: usize) {
+        self.lock().sitemaps.push(SitemapRecord {
+            host: host.to_string(),
+            urls_discovered,
+        });
+    }
+
+    /// Finalize and return the report for the current crawl
+    pub fn finish(&self) -> ComplianceReport {
+        let mut report = self.lock().clone();
+        report.finished_at = Some(Utc::now());
+        report
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ComplianceReport> {
+        // A panic while recording leaves the report usable; keep collecting
+        self.report.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Exclusion directives (`noindex`, `nofollow`, `none`) from meta robots tags
+pub fn meta_robots_exclusions(html: &str) -> Vec<String> {
+    static META_TAG: OnceLock<Regex> = OnceLock::new();
+    static ATTR: OnceLock<Regex> = OnceLock::new();
+    let meta_tag = META_TAG.get_or_init(|| Regex::new(r"(?is)<meta\s[^>]*>").expect("valid regex"));
+    let attr = ATTR.get_or_init(|| {
+        Regex::new(r#"(?is)\b(name|content)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).expect("valid regex")
+    });
+
+    let mut directives = Vec::new();
+    for tag in meta_tag.find_iter(html) {
+        let mut name = None;
+        let mut content = None;
+        for caps in attr.captures_iter(tag.as_str()) {
+            let value = caps.get(2).or_else(|| caps.get(3)).map(|m| m.as_str());
+            match caps[1].to_ascii_lowercase().as_str() {
+                "name" => name = value,
+                _ => content = value,
+            }
+        }
+        if !name.is_some_and(|n| n.eq_ignore_ascii_case("robots")) {
+            continue;
+        }
+        for directive in content.unwrap_or_default().split(',') {
+            let directive = directive.trim().to_ascii_lowercase();
+            let excludes = matches!(directive.as_str(), "noindex" | "nofollow" | "none");
+            if excludes && !directives.contains(&directive) {
+                directives.push(directive);
+            }
+        }
+    }
+    directives
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meta_robots_exclusions() {
+        let html = r#"<html><head><meta name="robots" content="NOINDEX, follow"></head></html>"#;
+        assert_eq!(meta_robots_exclusions(html), vec!["noindex"]);
+
+        let html = r#"<meta content='noindex,nofollow' name='robots'>"#;
+        assert_eq!(meta_robots_exclusions(html), vec!["noindex", "nofollow"]);
+
+        assert!(meta_robots_exclusions("<meta name=\"description\" content=\"x\">").is_empty());
+    }
+
+    #[test]
+    fn test_reporter_aggregates_crawl_delays() {
+        let reporter = ComplianceReporter::new();
+        reporter.record_crawl_delay("example.com", 2.0);
+        reporter.record_crawl_delay("example.com", 2.0);
+        reporter.record_robots_disallowed("https://example.com/private");
+        reporter.record_rate_limited("https://example.com/a", 429, Some("30".to_string()));
+
+        let report = reporter.finish();
+        assert_eq!(report.crawl_delays.len(), 1);
+        assert_eq!(report.crawl_delays[0].requests, 2);
+        assert_eq!(report.robots_disallowed.len(), 1);
+        assert_eq!(report.rate_limited[0].status, 429);
+        assert!(report.finished_at.is_some());
+
+        reporter.reset();
+        assert!(reporter.finish().robots_disallowed.is_empty());
+    }
+}
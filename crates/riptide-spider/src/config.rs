@@ -1,8 +1,10 @@
 use crate::{
     adaptive_stop::AdaptiveStopConfig,
     budget::BudgetConfig,
+    content_dedup::ContentDedupConfig,
     frontier::FrontierConfig,
     query_aware::QueryAwareConfig,
+    scope::{ScopeConfig, UrlScope},
     session::SessionConfig,
     strategy::AdaptiveCriteria as StrategyAdaptiveCriteria,
     types::{SitemapConfig, StrategyConfig},
@@ -74,6 +76,12 @@ pub struct SpiderConfig {
     pub performance: PerformanceConfig,
     /// Query-aware crawling configuration
     pub query_aware: QueryAwareConfig,
+    /// URL scoping rules applied before URLs enter the frontier
+    #[serde(default)]
+    pub scope: ScopeConfig,
+    /// Near-duplicate and soft-404 detection for crawled pages
+    #[serde(default)]
+    pub content_dedup: ContentDedupConfig,
 }
 
 impl Default for SpiderConfig {
@@ -104,6 +112,8 @@ impl Default for SpiderConfig {
             url_processing: UrlProcessingConfig::default(),
             performance: PerformanceConfig::default(),
             query_aware: QueryAwareConfig::default(),
+            scope: ScopeConfig::default(),
+            content_dedup: ContentDedupConfig::default(),
         }
     }
 }
@@ -294,6 +304,18 @@ impl SpiderConfig {
         self
     }
 
+    /// Set URL scoping rules
+    pub fn with_scope(mut self, scope: ScopeConfig) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Set near-duplicate and soft-404 detection
+    pub fn with_content_dedup(mut self, content_dedup: ContentDedupConfig) -> Self {
+        self.content_dedup = content_dedup;
+        self
+    }
+
     /// Validate configuration
     ///
     /// Performs comprehensive validation of all configuration values including:
@@ -499,6 +521,16 @@ impl SpiderConfig {
             return Err("Adaptive stop min_gain_threshold cannot be negative".to_string());
         }
 
+        // === Scope Configuration Validation ===
+        if let Err(e) = UrlScope::new(&self.scope) {
+            return Err(format!("Scope {:#}", e));
+        }
+
+        // === Content Dedup Configuration Validation ===
+        if let Err(e) = self.content_dedup.validate() {
+            return Err(format!("Content dedup {}", e));
+        }
+
         Ok(())
     }
 
@@ -810,5 +842,21 @@ mod tests {
         config = SpiderConfig::default();
         config.max_redirects = 25;
         assert!(config.validate().is_err());
+
+        // Reset and test invalid scope pattern
+        config = SpiderConfig::default().with_scope(ScopeConfig {
+            include: vec![crate::scope::UrlPattern::Regex("[unclosed".to_string())],
+            ..Default::default()
+        });
+        assert!(config
+            .validate()
+            .is_err_and(|e| e.contains("Invalid regex pattern")));
+
+        // Reset and test zero shingle size
+        config = SpiderConfig::default().with_content_dedup(ContentDedupConfig {
+            shingle_size: 0,
+            ..Default::default()
+        });
+        assert!(config.validate().is_err());
     }
 }
@@ -0,0 +1,451 @@
+//! Near-duplicate and soft-404 detection
+//!
+//! Pages are fingerprinted with a 64-bit SimHash over word shingles of
+//! their text. Two pages whose fingerprints differ in at most
+//! `max_hamming_distance` bits are near-duplicates; the later page is
+//! flagged as a duplicate of the first one seen.
+//!
+//! Soft-404s are `200` responses served from a "not found" template. A
+//! short page whose title or headings contain a not-found phrase as whole
+//! words, or consist of just "404", is a soft-404, and so is any later page
+//! that is a near-duplicate of one.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use riptide_spider::content_dedup::{ContentDedupConfig, ContentDeduplicator};
+//! use url::Url;
+//!
+//! let dedup = ContentDeduplicator::new(ContentDedupConfig {
+//!     enabled: true,
+//!     ..Default::default()
+//! });
+//!
+//! let html = "<html><title>Page not found</title><body>Sorry!</body></html>";
+//! let url = Url::parse("https://example.com/missing").unwrap();
+//! let check = dedup.check(&url, html, "Page not found Sorry!");
+//! assert!(check.soft_404);
+//! ```
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use url::Url;
+
+/// Phrases marking a "not found" page title or heading
+pub const DEFAULT_SOFT_404_PHRASES: &[&str] = &[
+    "not found",
+    "error 404",
+    "404 error",
+    "404 page",
+    "page doesn't exist",
+    "page does not exist",
+    "page cannot be found",
+    "page could not be found",
+    "no longer available",
+    "nothing was found",
+    "page you requested",
+];
+
+/// SimHash fingerprints are split into this many bands for lookup. A
+/// near-duplicate within [`MAX_HAMMING_DISTANCE`] bits shares at least
+/// one whole band with the original.
+const BANDS: u32 = 4;
+
+/// Largest supported `max_hamming_distance`
+pub const MAX_HAMMING_DISTANCE: u32 = BANDS - 1;
+
+/// Content fingerprinting settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContentDedupConfig {
+    /// Fingerprint crawled pages
+    pub enabled: bool,
+    /// Words per shingle (must be > 0)
+    pub shingle_size: usize,
+    /// Fingerprints differing in at most this many bits are near-duplicates
+    /// (must be ≤ 3)
+    pub max_hamming_distance: u32,
+    /// Flag soft-404 pages
+    pub detect_soft_404: bool,
+    /// Phrases marking a not-found title or heading, matched
+    /// case-insensitively against whole words
+    pub soft_404_phrases: Vec<String>,
+    /// Pages with more words are never flagged as soft-404 by phrase alone
+    pub soft_404_max_words: usize,
+    /// Do not follow links found on near-duplicate pages
+    pub prune_duplicates: bool,
+    /// Do not follow links found on soft-404 pages (off by default, since
+    /// detection is heuristic)
+    pub prune_soft_404: bool,
+    /// Fingerprints remembered per crawl; later pages are still checked
+    /// but no longer stored
+    pub max_fingerprints: usize,
+}
+
+impl Default for ContentDedupConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            shingle_size: 3,
+            max_hamming_distance: 3,
+            detect_soft_404: true,
+            soft_404_phrases: DEFAULT_SOFT_404_PHRASES
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+            soft_404_max_words: 300,
+            prune_duplicates: false,
+            prune_soft_404: false,
+            max_fingerprints: 100_000,
+        }
+    }
+}
+
+impl ContentDedupConfig {
+    /// Check the settings are usable
+    pub fn validate(&self) -> Result<(), String> {
+        if self.shingle_size == 0 {
+            return Err("shingle_size must be greater than 0".to_string());
+        }
+        if self.max_hamming_distance > MAX_HAMMING_DISTANCE {
+            return Err(format!(
+                "max_hamming_distance must be at most {}",
+                MAX_HAMMING_DISTANCE
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Fingerprinting outcome for one page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentCheck {
+    /// SimHash of the page text
+    pub simhash: u64,
+    /// First page seen with near-identical content
+    pub duplicate_of: Option<Url>,
+    /// Whether the page is a "not found" page served with a success status
+    pub soft_404: bool,
+}
+
+impl ContentCheck {
+    /// Whether the page is a near-duplicate of an earlier page
+    pub fn is_duplicate(&self) -> bool {
+        self.duplicate_of.is_some()
+    }
+}
+
+/// Near-duplicates and soft-404s found during a crawl
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentDedupReport {
+    /// Pages fingerprinted
+    pub pages_checked: u64,
+    /// `(url, duplicate_of)` pairs, in crawl order
+    pub near_duplicates: Vec<(String, String)>,
+    /// Soft-404 URLs, in crawl order
+    pub soft_404s: Vec<String>,
+}
+
+#[derive(Default)]
+struct DedupState {
+    fingerprints: Vec<(u64, Url)>,
+    /// Band value → indexes into `fingerprints`
+    bands: HashMap<(u32, u16), Vec<usize>>,
+    /// Fingerprints of pages flagged as soft-404
+    soft_404_templates: Vec<u64>,
+    report: ContentDedupReport,
+}
+
+/// Fingerprints crawled pages to flag near-duplicates and soft-404s
+pub struct ContentDeduplicator {
+    config: ContentDedupConfig,
+    phrases: Vec<String>,
+    state: Mutex<DedupState>,
+}
+
+impl ContentDeduplicator {
+    pub fn new(config: ContentDedupConfig) -> Self {
+        let phrases = config
+            .soft_404_phrases
+            .iter()
+            .map(|p| normalize_words(p))
+            .filter(|p| !p.is_empty())
+            .collect();
+        Self {
+            config,
+            phrases,
+            state: Mutex::new(DedupState::default()),
+        }
+    }
+
+    pub fn config(&self) -> &ContentDedupConfig {
+        &self.config
+    }
+
+    /// Fingerprint a successfully fetched page and record it
+    pub fn check(&self, url: &Url, html: &str, text: &str) -> ContentCheck {
+        let hash = simhash(text, self.config.shingle_size);
+        let mut state = self.state();
+        state.report.pages_checked += 1;
+
+        let duplicate_of = self.find_near_duplicate(&state, hash);
+        let soft_404 = self.config.detect_soft_404
+            && (state
+                .soft_404_templates
+                .iter()
+                .any(|t| hamming_distance(*t, hash) <= self.config.max_hamming_distance)
+                || self.has_not_found_markers(html, text));
+
+        if let Some(original) = &duplicate_of {
+            state
+                .report
+                .near_duplicates
+                .push((url.to_string(), original.to_string()));
+        } else if state.fingerprints.len() < self.config.max_fingerprints {
+            let index = state.fingerprints.len();
+            state.fingerprints.push((hash, url.clone()));
+            for band in 0..BANDS {
+                state
+                    .bands
+                    .entry((band, band_value(hash, band)))
+                    .or_default()
+                    .push(index);
+            }
+        }
+        if soft_404 {
+            state.report.soft_404s.push(url.to_string());
+            if !state.soft_404_templates.contains(&hash) {
+                state.soft_404_templates.push(hash);
+            }
+        }
+
+        ContentCheck {
+            simhash: hash,
+            duplicate_of,
+            soft_404,
+        }
+    }
+
+    /// Whether links found on a page with this check should be skipped
+    pub fn should_prune(&self, check: &ContentCheck) -> bool {
+        (self.config.prune_duplicates && check.is_duplicate())
+            || (self.config.prune_soft_404 && check.soft_404)
+    }
+
+    /// Forget all fingerprints, for a new crawl
+    pub fn reset(&self) {
+        *self.state() = DedupState::default();
+    }
+
+    /// Take the report of the current crawl
+    pub fn finish(&self) -> ContentDedupReport {
+        std::mem::take(&mut self.state().report)
+    }
+
+    fn find_near_duplicate(&self, state: &DedupState, hash: u64) -> Option<Url> {
+        (0..BANDS)
+            .filter_map(|band| state.bands.get(&(band, band_value(hash, band))))
+            .flatten()
+            .map(|&index| &state.fingerprints[index])
+            .filter(|(other, _)| hamming_distance(*other, hash) <= self.config.max_hamming_distance)
+            .min_by_key(|(other, _)| hamming_distance(*other, hash))
+            .map(|(_, url)| url.clone())
+    }
+
+    fn has_not_found_markers(&self, html: &str, text: &str) -> bool {
+        if text.split_whitespace().count() > self.config.soft_404_max_words {
+            return false;
+        }
+        headings(html).any(|heading| {
+            let heading = normalize_words(heading);
+            // A bare status code is a not-found marker on its own, but a 404
+            // elsewhere in a heading ("Top 404 fixes") is not
+            let padded = format!(" {} ", heading);
+            heading == "404"
+                || self
+                    .phrases
+                    .iter()
+                    .any(|p| padded.contains(format!(" {} ", p).as_str()))
+        })
+    }
+
+    fn state(&self) -> MutexGuard<'_, DedupState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 64-bit SimHash of `text` over shingles of `shingle_size` words
+pub fn simhash(text: &str, shingle_size: usize) -> u64 {
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect();
+    if words.is_empty() {
+        return 0;
+    }
+
+    let mut weights = [0i64; 64];
+    for shingle in words.windows(shingle_size.clamp(1, words.len())) {
+        let hash = fnv1a(shingle);
+        for (bit, weight) in weights.iter_mut().enumerate() {
+            if hash & (1 << bit) != 0 {
+                *weight += 1;
+            } else {
+                *weight -= 1;
+            }
+        }
+    }
+
+    weights
+        .iter()
+        .enumerate()
+        .filter(|(_, w)| **w > 0)
+        .fold(0, |hash, (bit, _)| hash | (1 << bit))
+}
+
+/// Number of differing bits between two fingerprints
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// FNV-1a hash of a shingle; stable across runs and platforms
+fn fnv1a(words: &[String]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for (i, word) in words.iter().enumerate() {
+        let separator = (i > 0).then_some(b' ');
+        for byte in separator.into_iter().chain(word.bytes()) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+fn band_value(hash: u64, band: u32) -> u16 {
+    (hash >> (band * 16)) as u16
+}
+
+/// Lowercase alphanumeric words of `text`, joined by single spaces
+fn normalize_words(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Text of the `<title>` and `<h1>`/`<h2>` elements
+fn headings(html: &str) -> impl Iterator<Item = &str> {
+    static HEADING: OnceLock<Option<Regex>> = OnceLock::new();
+    HEADING
+        .get_or_init(|| Regex::new(r"(?is)<(title|h1|h2)[^>]*>(.*?)</(?:title|h1|h2)>").ok())
+        .iter()
+        .flat_map(move |re| re.captures_iter(html))
+        .filter_map(|cap| cap.get(2).map(|m| m.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARTICLE: &str = "The quick brown fox jumps over the lazy dog while the farmer \
+        watches from the porch and drinks his morning coffee before heading out to the \
+        fields where the corn has grown tall after weeks of summer rain. Later that evening \
+        the family gathers around the long wooden table to share stories about the harvest, \
+        the weather and the neighbours who moved away last spring, while the children play \
+        outside until the sun finally sets behind the hills";
+
+    fn dedup(config: ContentDedupConfig) -> ContentDeduplicator {
+        ContentDeduplicator::new(ContentDedupConfig {
+            enabled: true,
+            ..config
+        })
+    }
+
+    fn url(path: &str) -> Url {
+        Url::parse("https://example.com")
+            .unwrap()
+            .join(path)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_simhash_similarity() {
+        let near = format!("{} today", ARTICLE);
+        let other = "Quarterly revenue grew by twelve percent as the company expanded \
+            into new markets across Europe and Asia despite supply chain pressure";
+
+        assert_eq!(simhash(ARTICLE, 3), simhash(ARTICLE, 3));
+        assert!(hamming_distance(simhash(ARTICLE, 3), simhash(&near, 3)) <= 3);
+        assert!(hamming_distance(simhash(ARTICLE, 3), simhash(other, 3)) > 3);
+        assert_eq!(simhash("", 3), 0);
+    }
+
+    #[test]
+    fn test_near_duplicates_flagged() {
+        let dedup = dedup(ContentDedupConfig::default());
+        let first = dedup.check(&url("/a"), "", ARTICLE);
+        assert!(!first.is_duplicate());
+
+        let second = dedup.check(&url("/a?utm_source=feed"), "", ARTICLE);
+        assert_eq!(second.duplicate_of, Some(url("/a")));
+        assert!(!dedup.should_prune(&second));
+
+        let report = dedup.finish();
+        assert_eq!(report.pages_checked, 2);
+        assert_eq!(report.near_duplicates.len(), 1);
+    }
+
+    #[test]
+    fn test_soft_404_detection() {
+        let dedup = dedup(ContentDedupConfig {
+            prune_soft_404: true,
+            ..Default::default()
+        });
+        let html = "<html><head><title>Oops! Page Not Found</title></head></html>";
+        let text = "Oops! Page Not Found. The page you are looking for was moved, \
+            removed or renamed. Try the search box or go back to the home page.";
+
+        let check = dedup.check(&url("/missing"), html, text);
+        assert!(check.soft_404);
+        assert!(dedup.should_prune(&check));
+
+        // Same template without the telltale title
+        let check = dedup.check(&url("/gone"), "<title>Example</title>", text);
+        assert!(check.soft_404);
+
+        let check = dedup.check(&url("/about"), "<title>About us</title>", ARTICLE);
+        assert!(!check.soft_404);
+        assert_eq!(dedup.finish().soft_404s.len(), 2);
+    }
+
+    #[test]
+    fn test_soft_404_heading_patterns() {
+        let dedup = dedup(ContentDedupConfig::default());
+        let short = "Nothing to see";
+
+        let check = dedup.check(&url("/a"), "<h1>404</h1>", short);
+        assert!(check.soft_404);
+        assert!(!dedup.should_prune(&check));
+        let html = "<title>Error 404 - Example</title>";
+        let check = dedup.check(&url("/b"), html, "Please try again later");
+        assert!(check.soft_404);
+
+        dedup.reset();
+        let check = dedup.check(&url("/c"), "<h1>Top 404 fixes for your site</h1>", short);
+        assert!(!check.soft_404);
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(ContentDedupConfig::default().validate().is_ok());
+        let config = ContentDedupConfig {
+            max_hamming_distance: 8,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,483 @@
+//! Pause, resume, stop and cancellation of a running crawl
+//!
+//! A [`CrawlControl`] is shared between a [`Spider`](crate::Spider) and
+//! whoever controls it, so a crawl can be steered without waiting for the
+//! crawl loop to give up the spider. Requests are honoured between pages:
+//!
+//! - pausing lets the page being fetched finish, then takes nothing more
+//!   from the frontier until the crawl is resumed; a crawl paused for
+//!   longer than its maximum pause is stopped, since it keeps the spider
+//!   busy while paused
+//! - stopping ends the crawl and keeps the frontier, so it can still be
+//!   checkpointed and resumed
+//! - cancelling ends the crawl and clears the frontier and the crawl's
+//!   checkpoint
+//!
+//! Each crawl run gets an id, so a request meant for one crawl cannot steer
+//! the next. Every state change is recorded with its time. With a store attached the
+//! state and its history are written through the [`CheckpointStore`] port
+//! on each change and can be read back after a restart.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use riptide_types::ports::CheckpointStore;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+/// State changes kept in the history
+pub const MAX_TRANSITIONS: usize = 100;
+
+/// How long a crawl may stay paused before it is stopped
+pub const DEFAULT_MAX_PAUSE: Duration = Duration::from_secs(30 * 60);
+
+/// Lifecycle state of the crawl a spider runs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrawlRunState {
+    /// No crawl has run yet
+    #[default]
+    Idle,
+    /// Crawling
+    Running,
+    /// Holding the frontier until resumed
+    Paused,
+    /// Stop requested; the page being fetched finishes first
+    Stopping,
+    /// Cancel requested; the page being fetched finishes first
+    Cancelling,
+    /// Stopped on request, frontier kept
+    Stopped,
+    /// Cancelled on request, frontier cleared
+    Cancelled,
+    /// Finished on its own (frontier exhausted, budget or adaptive stop)
+    Completed,
+}
+
+impl CrawlRunState {
+    /// Whether a crawl is in progress
+    pub fn is_active(self) -> bool {
+        matches!(
+            self,
+            Self::Running | Self::Paused | Self::Stopping | Self::Cancelling
+        )
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Running => "running",
+            Self::Paused => "paused",
+            Self::Stopping => "stopping",
+            Self::Cancelling => "cancelling",
+            Self::Stopped => "stopped",
+            Self::Cancelled => "cancelled",
+            Self::Completed => "completed",
+        }
+    }
+}
+
+impl fmt::Display for CrawlRunState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One state change of a crawl
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ControlTransition {
+    pub from: CrawlRunState,
+    pub to: CrawlRunState,
+    pub at: DateTime<Utc>,
+    /// Why the state changed, for changes not requested by a client
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Current state and recent history, as persisted
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ControlSnapshot {
+    pub state: CrawlRunState,
+    /// Id of the current or last crawl run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crawl_id: Option<String>,
+    /// Tenant or client the current or last crawl ran for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Most recent last, at most [`MAX_TRANSITIONS`]
+    pub transitions: Vec<ControlTransition>,
+}
+
+/// Shared handle steering a spider's crawl
+pub struct CrawlControl {
+    state: watch::Sender<CrawlRunState>,
+    transitions: Mutex<VecDeque<ControlTransition>>,
+    crawl_id: Mutex<Option<String>>,
+    owner: Mutex<Option<String>>,
+    store: Option<(Arc<dyn CheckpointStore>, String)>,
+    max_pause: Duration,
+}
+
+impl Default for CrawlControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CrawlControl {
+    pub fn new() -> Self {
+        Self {
+            state: watch::Sender::new(CrawlRunState::Idle),
+            transitions: Mutex::new(VecDeque::new()),
+            crawl_id: Mutex::new(None),
+            owner: Mutex::new(None),
+            store: None,
+            max_pause: DEFAULT_MAX_PAUSE,
+        }
+    }
+
+    /// Persist the state to `store` under `key` on every change
+    pub fn with_store(mut self, store: Arc<dyn CheckpointStore>, key: impl Into<String>) -> Self {
+        self.store = Some((store, key.into()));
+        self
+    }
+
+    /// Stop crawls paused for longer than `max_pause`
+    pub fn with_max_pause(mut self, max_pause: Duration) -> Self {
+        self.max_pause = max_pause;
+        self
+    }
+
+    /// Current state
+    pub fn state(&self) -> CrawlRunState {
+        *self.state.borrow()
+    }
+
+    /// Id of the current or last crawl run
+    pub fn crawl_id(&self) -> Option<String> {
+        lock(&self.crawl_id).clone()
+    }
+
+    /// Tenant or client the current or last crawl ran for
+    pub fn owner(&self) -> Option<String> {
+        lock(&self.owner).clone()
+    }
+
+    /// Current state and recent history
+    pub fn snapshot(&self) -> ControlSnapshot {
+        ControlSnapshot {
+            state: self.state(),
+            crawl_id: self.crawl_id(),
+            owner: self.owner(),
+            transitions: lock(&self.transitions).iter().cloned().collect(),
+        }
+    }
+
+    /// Read back the persisted state and history
+    ///
+    /// A crawl that was in progress when the state was written did not
+    /// survive the restart and is reported as stopped.
+    pub async fn restore(&self) -> Result<()> {
+        let Some((store, key)) = &self.store else {
+            return Ok(());
+        };
+        let Some(data) = store
+            .load(key)
+            .await
+            .context("Failed to load crawl control state")?
+        else {
+            return Ok(());
+        };
+        let snapshot: ControlSnapshot =
+            serde_json::from_slice(&data).context("Failed to parse crawl control state")?;
+
+        *lock(&self.transitions) = snapshot.transitions.into_iter().collect();
+        *lock(&self.crawl_id) = snapshot.crawl_id;
+        *lock(&self.owner) = snapshot.owner;
+        self.state.send_replace(snapshot.state);
+        if snapshot.state.is_active() {
+            self.transition(
+                |_| true,
+                CrawlRunState::Stopped,
+                Some("Interrupted by restart"),
+            )
+            .await;
+        }
+        Ok(())
+    }
+
+    /// Hold the frontier after the page being fetched
+    ///
+    /// # Errors
+    ///
+    /// Returns an error unless the crawl is running.
+    pub async fn pause(&self) -> Result<()> {
+        self.request(|s| s == CrawlRunState::Running, CrawlRunState::Paused)
+            .await
+    }
+
+    /// Continue a paused crawl
+    ///
+    /// # Errors
+    ///
+    /// Returns an error unless the crawl is paused.
+    pub async fn resume(&self) -> Result<()> {
+        self.request(|s| s == CrawlRunState::Paused, CrawlRunState::Running)
+            .await
+    }
+
+    /// End the crawl after the page being fetched, keeping the frontier
+    ///
+    /// # Errors
+    ///
+    /// Returns an error unless the crawl is running or paused.
+    pub async fn stop(&self) -> Result<()> {
+        self.request(
+            |s| matches!(s, CrawlRunState::Running | CrawlRunState::Paused),
+            CrawlRunState::Stopping,
+        )
+        .await
+    }
+
+    /// End the crawl after the page being fetched and discard its frontier
+    /// and checkpoint
+    ///
+    /// # Errors
+    ///
+    /// Returns an error unless a crawl is in progress.
+    pub async fn cancel(&self) -> Result<()> {
+        self.request(
+            |s| s.is_active() && s != CrawlRunState::Cancelling,
+            CrawlRunState::Cancelling,
+        )
+        .await
+    }
+
+    /// Mark a new crawl run for `owner` as started
+    pub(crate) async fn begin(&self, owner: Option<String>) {
+        *lock(&self.crawl_id) = Some(uuid::Uuid::new_v4().to_string());
+        *lock(&self.owner) = owner;
+        self.transition(|_| true, CrawlRunState::Running, None)
+            .await;
+    }
+
+    /// Mark the crawl as ended, with the state a stop or cancel request
+    /// leads to or [`CrawlRunState::Completed`]
+    pub(crate) async fn finish(&self) -> CrawlRunState {
+        let end = match self.state() {
+            CrawlRunState::Stopping => CrawlRunState::Stopped,
+            CrawlRunState::Cancelling => CrawlRunState::Cancelled,
+            _ => CrawlRunState::Completed,
+        };
+        self.transition(|_| true, end, None).await;
+        end
+    }
+
+    /// Forget the state and history, as after a spider reset
+    pub(crate) async fn reset(&self) {
+        lock(&self.transitions).clear();
+        *lock(&self.crawl_id) = None;
+        *lock(&self.owner) = None;
+        self.state.send_replace(CrawlRunState::Idle);
+        self.persist().await;
+    }
+
+    /// Wait while the crawl is paused; returns at once otherwise
+    ///
+    /// A crawl still paused after the maximum pause is moved to stopping.
+    pub(crate) async fn wait_while_paused(&self) {
+        let mut receiver = self.state.subscribe();
+        let resumed = receiver.wait_for(|state| *state != CrawlRunState::Paused);
+        // The sender lives as long as `self`, so waiting cannot fail
+        if tokio::time::timeout(self.max_pause, resumed).await.is_ok() {
+            return;
+        }
+        let reason = format!("Paused for longer than {:?}", self.max_pause);
+        self.transition(
+            |s| s == CrawlRunState::Paused,
+            CrawlRunState::Stopping,
+            Some(&reason),
+        )
+        .await;
+    }
+
+    /// Whether a stop or cancel was requested
+    pub(crate) fn end_requested(&self) -> bool {
+        matches!(
+            self.state(),
+            CrawlRunState::Stopping | CrawlRunState::Cancelling
+        )
+    }
+
+    async fn request(
+        &self,
+        allowed: impl Fn(CrawlRunState) -> bool,
+        to: CrawlRunState,
+    ) -> Result<()> {
+        if self.transition(allowed, to, None).await {
+            return Ok(());
+        }
+        anyhow::bail!("Cannot move a {} crawl to {}", self.state(), to)
+    }
+
+    /// Move to `to` if the current state passes `allowed`
+    async fn transition(
+        &self,
+        allowed: impl Fn(CrawlRunState) -> bool,
+        to: CrawlRunState,
+        reason: Option<&str>,
+    ) -> bool {
+        let mut from = to;
+        let changed = self.state.send_if_modified(|state| {
+            if *state == to || !allowed(*state) {
+                return false;
+            }
+            from = std::mem::replace(state, to);
+            true
+        });
+        if !changed {
+            return false;
+        }
+
+        info!(from = %from, to = %to, "Crawl state changed");
+        {
+            let mut transitions = lock(&self.transitions);
+            if transitions.len() == MAX_TRANSITIONS {
+                transitions.pop_front();
+            }
+            transitions.push_back(ControlTransition {
+                from,
+                to,
+                at: Utc::now(),
+                reason: reason.map(str::to_string),
+            });
+        }
+        self.persist().await;
+        true
+    }
+
+    async fn persist(&self) {
+        let Some((store, key)) = &self.store else {
+            return;
+        };
+        let data = match serde_json::to_vec(&self.snapshot()) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!(error = %e, "Failed to serialize crawl control state");
+                return;
+            }
+        };
+        if let Err(e) = store.save(key, &data).await {
+            warn!(error = %e, "Failed to persist crawl control state");
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::ports::InMemoryCheckpointStore;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_transitions() {
+        let control = CrawlControl::new();
+        assert!(control.pause().await.is_err());
+
+        control.begin(None).await;
+        let crawl_id = control.crawl_id().unwrap();
+        control.pause().await.unwrap();
+        assert!(control.pause().await.is_err());
+        control.resume().await.unwrap();
+        control.cancel().await.unwrap();
+        assert!(control.end_requested());
+        assert_eq!(control.finish().await, CrawlRunState::Cancelled);
+        assert!(control.resume().await.is_err());
+
+        let snapshot = control.snapshot();
+        assert_eq!(snapshot.state, CrawlRunState::Cancelled);
+        assert_eq!(snapshot.crawl_id.as_deref(), Some(crawl_id.as_str()));
+        let states: Vec<_> = snapshot.transitions.iter().map(|t| t.to).collect();
+        assert_eq!(
+            states,
+            vec![
+                CrawlRunState::Running,
+                CrawlRunState::Paused,
+                CrawlRunState::Running,
+                CrawlRunState::Cancelling,
+                CrawlRunState::Cancelled,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_while_paused() {
+        let control = Arc::new(CrawlControl::new());
+        control.begin(None).await;
+        control.pause().await.unwrap();
+
+        let waiter = tokio::spawn({
+            let control = control.clone();
+            async move { control.wait_while_paused().await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiter.is_finished());
+
+        control.stop().await.unwrap();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_long_pause_stops_the_crawl() {
+        let control = CrawlControl::new().with_max_pause(Duration::from_millis(20));
+        control.begin(None).await;
+        control.pause().await.unwrap();
+
+        tokio::time::timeout(Duration::from_secs(1), control.wait_while_paused())
+            .await
+            .unwrap();
+        assert!(control.end_requested());
+        assert_eq!(control.finish().await, CrawlRunState::Stopped);
+        let transitions = control.snapshot().transitions;
+        assert_eq!(
+            transitions[2].reason.as_deref(),
+            Some("Paused for longer than 20ms")
+        );
+
+        // The next run gets a new id
+        let first = control.crawl_id();
+        control.begin(None).await;
+        assert_ne!(control.crawl_id(), first);
+    }
+
+    #[tokio::test]
+    async fn test_persisted_state_survives_restart() {
+        let store: Arc<dyn CheckpointStore> = Arc::new(InMemoryCheckpointStore::new());
+        let control = CrawlControl::new().with_store(store.clone(), "spider-control");
+        control.begin(Some("tenant-a".to_string())).await;
+        control.pause().await.unwrap();
+
+        let restarted = CrawlControl::new().with_store(store, "spider-control");
+        restarted.restore().await.unwrap();
+        let snapshot = restarted.snapshot();
+        assert_eq!(snapshot.state, CrawlRunState::Stopped);
+        assert_eq!(snapshot.owner.as_deref(), Some("tenant-a"));
+        assert_eq!(snapshot.crawl_id, control.crawl_id());
+        assert_eq!(snapshot.transitions.len(), 3);
+        assert_eq!(
+            snapshot.transitions[2].reason.as_deref(),
+            Some("Interrupted by restart")
+        );
+    }
+}
@@ -15,29 +15,83 @@ fn extract_links_basic(content: &str, base_url: &Url) -> Result<Vec<Url>> {
 
     Ok(links)
 }
+
+/// Visible text of each link, keyed by resolved URL (first link wins)
+fn extract_anchor_texts(content: &str, base_url: &Url) -> Result<HashMap<Url, String>> {
+    let anchor_regex =
+        regex::Regex::new(r#"(?is)<a\s[^>]*?href\s*=\s*["']([^"']+)["'][^>]*>(.*?)</a>"#)?;
+    let tag_regex = regex::Regex::new(r"(?s)<[^>]*>")?;
+    let mut anchors = HashMap::new();
+
+    for cap in anchor_regex.captures_iter(content) {
+        let (Some(href), Some(inner)) = (cap.get(1), cap.get(2)) else {
+            continue;
+        };
+        let Ok(url) = base_url.join(href.as_str()) else {
+            continue;
+        };
+        let text = tag_regex.replace_all(inner.as_str(), " ");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !text.is_empty() {
+            anchors.entry(url).or_insert(text);
+        }
+    }
+
+    Ok(anchors)
+}
+
+/// Request headers carrying a crawl session's cookies and extra headers
+fn session_headers(session: &AuthSession) -> Vec<(String, String)> {
+    let mut headers: Vec<_> = session
+        .headers
+        .iter()
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect();
+    if let Some(cookies) = session.cookie_header() {
+        headers.push(("Cookie".to_string(), cookies));
+    }
+    headers
+}
+use crate::checkpoint::{CheckpointSettings, SpiderCheckpoint, CHECKPOINT_VERSION};
+use crate::compliance::{meta_robots_exclusions, ComplianceReporter};
+use crate::control::{CrawlControl, CrawlRunState};
 use crate::memory_manager::MemoryManager;
 use crate::{
-    adaptive_stop::{AdaptiveStopEngine, StopDecision},
-    budget::BudgetManager,
+    adaptive_stop::{AdaptiveStopEngine, StopDecision, StopSignal},
+    budget::{BudgetManager, BudgetReport},
     config::SpiderConfig,
+    content_dedup::{ContentDedupReport, ContentDeduplicator},
     frontier::FrontierManager,
-    query_aware::{QueryAwareScorer, QueryAwareStats},
+    politeness::DomainPolitenessStats,
+    query_aware::{QueryAwareScorer, QueryAwareStats, TextEmbedder},
+    refresh::{ChangedSinceFilter, DeltaReport, PageRecord, RefreshStore, RefreshTracker},
+    results::{CrawlEdge, CrawlGraph, EnrichedCrawlResult},
+    scope::{ScopeConfig, UrlScope},
     session::SessionManager,
     sitemap::SitemapParser,
-    strategy::StrategyEngine,
-    types::{CrawlRequest, CrawlResult, Priority},
+    strategy::{StrategyEngine, UrlScorer, ANCHOR_TEXT_KEY, PARENT_RELEVANCE_KEY},
+    stream::{CrawlStream, DEFAULT_STREAM_BUFFER},
+    types::{CrawlRequest, CrawlResult, FrontierSnapshot, Priority, RejectionReason},
     url_utils::UrlUtils,
 };
 use anyhow::{Context, Result};
+use riptide_fetch::bandwidth::{self, BandwidthLimiter};
 use riptide_fetch::robots::RobotsManager;
-use riptide_fetch::FetchEngine;
+use riptide_fetch::robots_service::RobotsService;
+use riptide_fetch::{ConditionalFetch, FetchEngine, WireBytes};
+use riptide_reliability::{CircuitBreakerPreset, FetchOptions, ReliableHttpClient};
+use riptide_types::compliance::ComplianceReport;
+use riptide_types::ports::{AuthSession, CheckpointStore, SessionProvider, UrlPolicy};
+use riptide_types::ContentFingerprint;
+use riptide_utils::retry::{server_retry_delay, Throttled};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{RwLock, Semaphore};
+use tokio::sync::{mpsc, RwLock, Semaphore};
 use tokio::time::sleep;
-use tracing::{debug, error, info, instrument};
+use tracing::{debug, error, info, instrument, warn};
 use url::Url;
 
 /// Serde module for Duration serialization
@@ -61,6 +115,83 @@ mod duration_serde {
     }
 }
 
+/// Result of revalidating a page in refresh mode
+enum RefreshFetch {
+    /// Page is unchanged; follow its stored links
+    Unchanged(Vec<Url>),
+    /// Page is new or changed
+    Fetched {
+        content: String,
+        fingerprint: ContentFingerprint,
+    },
+}
+
+/// State of a single crawl, from its start to its [`SpiderResult`]
+///
+/// Kept off the [`Spider`] so one crawl never resets or reports into the
+/// compliance, refresh delta, link graph or seeds of another.
+struct CrawlRun {
+    compliance: ComplianceReporter,
+    // Scope rules with this crawl's seeds
+    scope: UrlScope,
+    refresh: Option<RefreshTracker>,
+    link_graph: Option<RwLock<CrawlGraph>>,
+    content_dedup: Option<ContentDeduplicator>,
+}
+
+impl CrawlRun {
+    /// Finish the refresh delta of the crawl, if refreshing
+    async fn finish_refresh(&self) -> Option<DeltaReport> {
+        let refresh = self.refresh.as_ref()?;
+        match refresh.finish().await {
+            Ok(delta) => Some(delta),
+            Err(e) => {
+                warn!(error = %e, "Failed to finish refresh crawl");
+                None
+            }
+        }
+    }
+
+    /// Add the links of a crawled page to the link graph, if recording
+    async fn record_links(&self, result: &CrawlResult) {
+        let Some(graph) = &self.link_graph else {
+            return;
+        };
+        let mut graph = graph.write().await;
+        for url in &result.extracted_urls {
+            graph.add_edge(CrawlEdge {
+                from: result.request.url.to_string(),
+                to: url.to_string(),
+                anchor_text: result.anchor_texts.get(url).cloned(),
+                depth: result.request.depth,
+            });
+        }
+    }
+
+    /// Whether links of a near-duplicate or soft-404 page should not be followed
+    fn prune_expansion(&self, result: &CrawlResult) -> bool {
+        let (Some(dedup), Some(check)) = (&self.content_dedup, &result.content_check) else {
+            return false;
+        };
+        let prune = dedup.should_prune(check);
+        if prune {
+            debug!(
+                url = %result.request.url,
+                duplicate_of = ?check.duplicate_of.as_ref().map(Url::as_str),
+                soft_404 = check.soft_404,
+                "Not following links of flagged page"
+            );
+        }
+        prune
+    }
+
+    /// Take the link graph of the crawl, if recording
+    async fn finish_link_graph(&self) -> Option<CrawlGraph> {
+        let graph = self.link_graph.as_ref()?;
+        Some(std::mem::take(&mut *graph.write().await))
+    }
+}
+
 /// Main Spider engine for deep crawling
 pub struct Spider {
     config: SpiderConfig,
@@ -71,6 +202,7 @@ pub struct Spider {
     budget_manager: Arc<BudgetManager>,
     adaptive_stop_engine: Arc<AdaptiveStopEngine>,
     url_utils: Arc<RwLock<UrlUtils>>,
+    scope: UrlScope,
 
     // Session and authentication
     session_manager: Arc<SessionManager>,
@@ -82,6 +214,12 @@ pub struct Spider {
     memory_manager: Option<Arc<MemoryManager>>,
     fetch_engine: Option<Arc<FetchEngine>>,
 
+    // Client every page request is sent through
+    http_client: Arc<ReliableHttpClient>,
+
+    // Download caps page bodies are read under
+    bandwidth: Option<Arc<BandwidthLimiter>>,
+
     // Concurrency control
     global_semaphore: Arc<Semaphore>,
     host_semaphores: Arc<RwLock<HashMap<String, Arc<Semaphore>>>>,
@@ -92,6 +230,33 @@ pub struct Spider {
 
     // Query-aware functionality
     query_aware_scorer: Arc<RwLock<Option<QueryAwareScorer>>>,
+
+    // Periodic crawl checkpoints
+    checkpointing: Option<CheckpointSettings>,
+
+    // Change detection against a previous crawl
+    refresh: Option<RefreshStore>,
+
+    // Cookies and headers for crawling behind logins
+    session_provider: Option<Arc<dyn SessionProvider>>,
+
+    // Admission checks for seeds and discovered URLs
+    url_policy: Option<Arc<dyn UrlPolicy>>,
+
+    // Whether crawls record their link graph
+    record_link_graph: bool,
+
+    // Pause, resume, stop and cancel requests
+    control: Arc<CrawlControl>,
+
+    // Page cap of the next crawls, on top of the budget's
+    page_limit: Option<u64>,
+
+    // Holds back pages unchanged since a differential crawl's cutoff
+    changed_since: Option<ChangedSinceFilter>,
+
+    // Tenant or client the next crawls run for
+    owner: Option<String>,
 }
 
 /// Current crawl state
@@ -112,6 +277,12 @@ pub struct CrawlState {
     pub last_stop_decision: Option<StopDecision>,
     /// Domains being crawled
     pub active_domains: std::collections::HashSet<String>,
+    /// Seed URLs of the crawl, kept for checkpoints
+    #[serde(default)]
+    pub seeds: Vec<Url>,
+    /// Lifecycle state: running, paused, stopped, cancelled, ...
+    #[serde(default)]
+    pub run_state: CrawlRunState,
 }
 
 /// Performance metrics
@@ -138,6 +309,8 @@ pub struct SpiderResult {
     pub pages_crawled: u64,
     /// Total pages failed
     pub pages_failed: u64,
+    /// Crawled pages held back as unchanged since `changed_since`
+    pub pages_unchanged: u64,
     /// Crawl duration
     pub duration: Duration,
     /// Reason for stopping
@@ -148,6 +321,18 @@ pub struct SpiderResult {
     pub domains: Vec<String>,
     /// URLs discovered during crawl (in order of discovery, capped at max_pages)
     pub discovered_urls: Vec<String>,
+    /// Robots, crawl-delay, meta robots, and rate-limit decisions for audit
+    pub compliance: ComplianceReport,
+    /// Per-domain politeness state (concurrency, delays, backoff)
+    pub politeness: BTreeMap<String, DomainPolitenessStats>,
+    /// Added, changed and removed URLs, for refresh crawls
+    pub delta: Option<DeltaReport>,
+    /// Links between pages, when link graph recording is enabled
+    pub link_graph: Option<CrawlGraph>,
+    /// Near-duplicates and soft-404s, when content fingerprinting is enabled
+    pub content_dedup: Option<ContentDedupReport>,
+    /// Budget consumption, and the budget that stopped the crawl, if any
+    pub budget: BudgetReport,
 }
 
 impl Spider {
@@ -170,13 +355,20 @@ impl Spider {
         let url_utils = Arc::new(RwLock::new(UrlUtils::new(
             config.url_processing.clone().into(),
         )));
+        let scope = UrlScope::new(&config.scope)?;
 
         // Initialize session and sitemap components
         let session_manager = Arc::new(SessionManager::new(config.session.clone()));
         let sitemap_parser = Arc::new(RwLock::new(SitemapParser::new(config.sitemap.clone())));
 
         // Initialize robots manager
-        let robots_manager = Arc::new(RobotsManager::new(config.robots.clone())?); // Initialize concurrency control
+        let robots_manager = Arc::new(RobotsManager::new(config.robots.clone())?);
+        let http_client = Arc::new(
+            ReliableHttpClient::with_preset(CircuitBreakerPreset::WebScraping)
+                .context("Failed to create reliable HTTP client")?,
+        );
+
+        // Initialize concurrency control
         let global_semaphore = Arc::new(Semaphore::new(config.performance.max_concurrent_global));
         let host_semaphores = Arc::new(RwLock::new(HashMap::new()));
 
@@ -202,17 +394,29 @@ impl Spider {
             budget_manager,
             adaptive_stop_engine,
             url_utils,
+            scope,
             session_manager,
             sitemap_parser,
             robots_manager,
             circuit_breaker: None,
             memory_manager: None,
             fetch_engine: None,
+            http_client,
+            bandwidth: None,
             global_semaphore,
             host_semaphores,
             crawl_state,
             performance_metrics,
             query_aware_scorer,
+            checkpointing: None,
+            refresh: None,
+            session_provider: None,
+            url_policy: None,
+            record_link_graph: false,
+            control: Arc::new(CrawlControl::new()),
+            page_limit: None,
+            changed_since: None,
+            owner: None,
         })
     }
 
@@ -232,10 +436,220 @@ impl Spider {
         self
     }
 
+    /// Send page requests through `client` instead of a default
+    /// [`CircuitBreakerPreset::WebScraping`] client, e.g. one resolving
+    /// through a shared DNS cache
+    pub fn with_http_client(mut self, client: Arc<ReliableHttpClient>) -> Self {
+        self.set_http_client(client);
+        self
+    }
+
+    /// Replace the client page requests are sent through
+    pub fn set_http_client(&mut self, client: Arc<ReliableHttpClient>) {
+        self.http_client = client;
+    }
+
+    /// Read page bodies no faster than `limiter` allows, e.g. one shared with
+    /// the other fetch clients
+    pub fn with_bandwidth_limiter(mut self, limiter: Arc<BandwidthLimiter>) -> Self {
+        self.set_bandwidth_limiter(limiter);
+        self
+    }
+
+    /// Replace the limiter page bodies are read under
+    pub fn set_bandwidth_limiter(&mut self, limiter: Arc<BandwidthLimiter>) {
+        self.bandwidth = Some(limiter);
+    }
+
+    /// Read robots.txt through a `service` shared with other components, for
+    /// both robots checks and `Sitemap:` discovery
+    pub fn with_robots_service(mut self, service: Arc<RobotsService>) -> Self {
+        self.robots_manager = Arc::new(RobotsManager::with_service(
+            self.config.robots.clone(),
+            service.clone(),
+        ));
+        self.sitemap_parser = Arc::new(RwLock::new(
+            SitemapParser::new(self.config.sitemap.clone()).with_robots_service(service),
+        ));
+        self
+    }
+
+    /// Score best-first URLs with a custom scorer
+    ///
+    /// Replaces the default path/depth heuristic whenever the crawl runs the
+    /// best-first strategy; other strategies ignore the scorer.
+    pub fn with_url_scorer(mut self, scorer: Arc<dyn UrlScorer>) -> Self {
+        self.strategy_engine = Arc::new(RwLock::new(
+            StrategyEngine::new(self.config.strategy.to_crawling_strategy())
+                .with_url_scorer(scorer),
+        ));
+        self
+    }
+
+    /// Blend embedding similarity into query-aware relevance
+    ///
+    /// Each crawled page is embedded and compared with the target query; the
+    /// similarity weighs into the priority of its links and, with
+    /// `embedding_stop_threshold` set, can stop the crawl early. Has no effect
+    /// unless query foraging is enabled with a target query.
+    pub fn with_text_embedder(mut self, embedder: Arc<dyn TextEmbedder>) -> Self {
+        let config = &self.config.query_aware;
+        let scorer = config
+            .query_foraging
+            .then(|| QueryAwareScorer::new(config.clone()).with_embedder(embedder));
+        self.query_aware_scorer = Arc::new(RwLock::new(scorer));
+        self
+    }
+
+    /// Authenticate fetches with sessions from `provider`
+    ///
+    /// Each request carries the cookies and headers the provider returns for
+    /// its host. A `401` or a redirect to the login page renews the session
+    /// and retries the page once. Refresh-mode revalidation stays anonymous.
+    pub fn with_session_provider(mut self, provider: Arc<dyn SessionProvider>) -> Self {
+        self.session_provider = Some(provider);
+        self
+    }
+
+    /// Check seeds, sitemap entries and discovered links against `policy`
+    ///
+    /// Denied URLs never enter the frontier and are counted as
+    /// [`RejectionReason::PolicyDenied`].
+    pub fn with_url_policy(mut self, policy: Arc<dyn UrlPolicy>) -> Self {
+        self.url_policy = Some(policy);
+        self
+    }
+
+    /// Record the link graph of each crawl in [`SpiderResult::link_graph`]
+    ///
+    /// Every link found on a crawled page becomes an edge, including links
+    /// that were not followed. A resumed crawl only records links found after
+    /// the checkpoint.
+    pub fn with_link_graph(mut self) -> Self {
+        self.record_link_graph = true;
+        self
+    }
+
+    /// Replace the session provider used by subsequent crawls
+    pub fn set_session_provider(&mut self, provider: Option<Arc<dyn SessionProvider>>) {
+        self.session_provider = provider;
+    }
+
+    /// Replace the URL policy used by subsequent crawls
+    pub fn set_url_policy(&mut self, policy: Option<Arc<dyn UrlPolicy>>) {
+        self.url_policy = policy;
+    }
+
+    /// Replace the scope rules used by subsequent crawls
+    ///
+    /// `None` restores the rules of the spider's configuration. Fails on
+    /// invalid patterns, leaving the current rules in place.
+    pub fn set_scope(&mut self, scope: Option<&ScopeConfig>) -> Result<()> {
+        self.scope = UrlScope::new(scope.unwrap_or(&self.config.scope))?;
+        Ok(())
+    }
+
+    /// Stop subsequent crawls after `limit` crawled pages
+    ///
+    /// Applies on top of the budget's page limit; `None` leaves only the
+    /// budget.
+    pub fn set_page_limit(&mut self, limit: Option<u64>) {
+        self.page_limit = limit;
+    }
+
+    /// Only report pages of subsequent crawls that changed since `filter`'s cutoff
+    ///
+    /// Unchanged pages are counted in [`SpiderResult::pages_unchanged`] and
+    /// their links are still followed. Refresh crawls report changes in
+    /// their [`DeltaReport`] instead and ignore the filter.
+    pub fn set_changed_since(&mut self, filter: Option<ChangedSinceFilter>) {
+        self.changed_since = filter;
+    }
+
+    /// Record `owner` as the tenant or client subsequent crawls run for
+    ///
+    /// Reported in the [`CrawlControl`] snapshot so callers can check who
+    /// may inspect or steer the crawl.
+    pub fn set_owner(&mut self, owner: Option<String>) {
+        self.owner = owner;
+    }
+
+    /// Write a checkpoint to `store` every `interval_pages` processed pages
+    ///
+    /// The checkpoint is overwritten in place under `checkpoint_id`; pass the
+    /// same store and id to [`Spider::resume`] after a restart.
+    pub fn with_checkpointing(
+        mut self,
+        store: Arc<dyn CheckpointStore>,
+        checkpoint_id: impl Into<String>,
+        interval_pages: u64,
+    ) -> Self {
+        self.checkpointing = Some(CheckpointSettings {
+            store,
+            checkpoint_id: checkpoint_id.into(),
+            interval_pages: interval_pages.max(1),
+        });
+        self
+    }
+
+    /// Persist the crawl's lifecycle state to `store` under `key`
+    ///
+    /// Replaces the [`Spider::control`] handle, so take it afterwards; call
+    /// [`CrawlControl::restore`] on it to read the state back after a
+    /// restart.
+    pub fn set_control_store(&mut self, store: Arc<dyn CheckpointStore>, key: &str) {
+        self.control = Arc::new(CrawlControl::new().with_store(store, key));
+    }
+
+    /// Crawl in refresh mode against the results stored in `store`
+    ///
+    /// Pages are revalidated with their stored ETag/Last-Modified and content
+    /// hash; unchanged pages are not re-processed and their stored links are
+    /// followed instead. Every crawl updates the store and reports a
+    /// [`DeltaReport`] in [`SpiderResult::delta`]. The first crawl against an
+    /// empty store reports every page as added.
+    pub fn with_refresh(mut self, store: RefreshStore) -> Self {
+        self.refresh = Some(store);
+        self
+    }
+
     /// Start crawling from seed URLs
     #[instrument(skip(self), fields(seeds = seeds.len()))]
     pub async fn crawl(&self, seeds: Vec<Url>) -> Result<SpiderResult> {
+        self.start_crawl(seeds, None).await
+    }
+
+    /// Crawl from seed URLs, sending each crawled page to `sink` as it completes
+    ///
+    /// A full channel holds the crawl back until the receiver catches up; a
+    /// closed channel stops the crawl with the reason "Result stream closed".
+    #[instrument(skip(self, sink), fields(seeds = seeds.len()))]
+    pub async fn crawl_into(
+        &self,
+        seeds: Vec<Url>,
+        sink: mpsc::Sender<EnrichedCrawlResult>,
+    ) -> Result<SpiderResult> {
+        self.start_crawl(seeds, Some(sink)).await
+    }
+
+    /// Crawl from seed URLs in the background, streaming pages as they are crawled
+    ///
+    /// See [`CrawlStream`] for backpressure and cancellation.
+    pub fn crawl_stream(self: &Arc<Self>, seeds: Vec<Url>) -> CrawlStream {
+        let (sink, results) = mpsc::channel(DEFAULT_STREAM_BUFFER);
+        let spider = Arc::clone(self);
+        let crawl = tokio::spawn(async move { spider.crawl_into(seeds, sink).await });
+        CrawlStream::new(results, crawl)
+    }
+
+    async fn start_crawl(
+        &self,
+        seeds: Vec<Url>,
+        sink: Option<mpsc::Sender<EnrichedCrawlResult>>,
+    ) -> Result<SpiderResult> {
         info!("Starting crawl with {} seed URLs", seeds.len());
+        self.control.begin(self.owner.clone()).await;
+        let run = self.begin_run(&seeds, false).await?;
 
         // Initialize crawl state
         {
@@ -248,33 +662,207 @@ impl Spider {
                 .iter()
                 .filter_map(|url| url.host_str().map(|h| h.to_string()))
                 .collect();
+            state.seeds = seeds.clone();
         }
 
         // Discover and add sitemap URLs
         for seed in &seeds {
             if let Ok(sitemap_urls) = self.discover_sitemap_urls(seed).await {
+                run.compliance
+                    .record_sitemap(seed.host_str().unwrap_or("unknown"), sitemap_urls.len());
                 info!(
                     "Discovered {} URLs from sitemaps for {}",
                     sitemap_urls.len(),
                     seed.host_str().unwrap_or("unknown")
                 );
+                for request in sitemap_urls {
+                    if let Some(violation) = run.scope.check(&request.url, request.depth) {
+                        debug!(
+                            url = %request.url,
+                            rule = violation.as_str(),
+                            "Sitemap URL out of scope"
+                        );
+                        self.frontier_manager
+                            .record_rejection(&request, RejectionReason::OutOfScope)
+                            .await;
+                        continue;
+                    }
+                    if self.policy_denies(&request.url).await {
+                        self.frontier_manager
+                            .record_rejection(&request, RejectionReason::PolicyDenied)
+                            .await;
+                        continue;
+                    }
+                    self.frontier_manager.add_request(request).await?;
+                }
             }
         }
 
         // Add seed URLs to frontier
         for seed in seeds {
             let request = CrawlRequest::new(seed).with_priority(Priority::High);
+            if self.policy_denies(&request.url).await {
+                self.frontier_manager
+                    .record_rejection(&request, RejectionReason::PolicyDenied)
+                    .await;
+                continue;
+            }
             self.frontier_manager.add_request(request).await?;
         }
 
-        // Start main crawl loop
-        let result = self.crawl_loop().await?;
+        self.run_crawl(&run, sink.as_ref()).await
+    }
+
+    /// Set up the per-crawl state of a new or resumed crawl
+    async fn begin_run(&self, seeds: &[Url], resumed: bool) -> Result<CrawlRun> {
+        // Resumed crawls get their counters back from the checkpoint
+        if !resumed {
+            self.budget_manager.reset().await;
+        }
+        let mut scope = self.scope.clone();
+        scope.set_seeds(seeds);
+        let refresh = match &self.refresh {
+            Some(store) => {
+                let tracker = RefreshTracker::new(store.clone());
+                tracker.begin(resumed).await?;
+                Some(tracker)
+            }
+            None => None,
+        };
+        let content_dedup = self
+            .config
+            .content_dedup
+            .enabled
+            .then(|| ContentDeduplicator::new(self.config.content_dedup.clone()));
+
+        Ok(CrawlRun {
+            compliance: ComplianceReporter::new(),
+            scope,
+            refresh,
+            link_graph: self
+                .record_link_graph
+                .then(|| RwLock::new(CrawlGraph::new())),
+            content_dedup,
+        })
+    }
+
+    /// Resume a crawl from the checkpoint stored under `checkpoint_id`
+    ///
+    /// Restores the frontier, visited URLs, budget counters and adaptive
+    /// stop state, then continues crawling where the checkpointed crawl
+    /// stopped. The returned page counts include pages crawled before the
+    /// checkpoint.
+    #[instrument(skip(self, store))]
+    pub async fn resume(
+        &self,
+        store: &dyn CheckpointStore,
+        checkpoint_id: &str,
+    ) -> Result<SpiderResult> {
+        let data = store
+            .load(checkpoint_id)
+            .await
+            .context("Failed to load spider checkpoint")?
+            .ok_or_else(|| anyhow::anyhow!("No spider checkpoint found: {}", checkpoint_id))?;
+        let checkpoint = SpiderCheckpoint::from_bytes(&data)?;
+
+        info!(
+            pages_crawled = checkpoint.pages_crawled,
+            pending = checkpoint.frontier.len(),
+            checkpoint_created_at = %checkpoint.created_at,
+            "Resuming crawl from checkpoint"
+        );
+        self.control.begin(self.owner.clone()).await;
+        let run = self.begin_run(&checkpoint.seeds, true).await?;
+        self.restore_checkpoint(checkpoint).await?;
+        {
+            let mut state = self.crawl_state.write().await;
+            state.active = true;
+            state.start_time = Some(Instant::now());
+        }
+
+        self.run_crawl(&run, None).await
+    }
+
+    /// Write a checkpoint of the current crawl to `store`
+    ///
+    /// Called while a crawl is running, the request being processed at that
+    /// moment is not captured; [`Spider::with_checkpointing`] writes
+    /// checkpoints between requests instead.
+    pub async fn checkpoint(&self, store: &dyn CheckpointStore, checkpoint_id: &str) -> Result<()> {
+        let checkpoint = self.export_checkpoint().await;
+        store
+            .save(checkpoint_id, &checkpoint.to_bytes()?)
+            .await
+            .context("Failed to store spider checkpoint")?;
+
+        debug!(
+            checkpoint_id = %checkpoint_id,
+            pending = checkpoint.frontier.len(),
+            "Spider checkpoint written"
+        );
+        Ok(())
+    }
+
+    /// Capture the current crawl state
+    pub async fn export_checkpoint(&self) -> SpiderCheckpoint {
+        let state = self.crawl_state.read().await.clone();
+        let visited = self.url_utils.read().await.export_visited().await;
+
+        SpiderCheckpoint {
+            version: CHECKPOINT_VERSION,
+            created_at: chrono::Utc::now(),
+            pages_crawled: state.pages_crawled,
+            pages_failed: state.pages_failed,
+            active_domains: state.active_domains,
+            frontier: self.frontier_manager.pending_requests().await,
+            visited,
+            budget: self.budget_manager.export_counters().await,
+            adaptive_stop: self.adaptive_stop_engine.export_state().await,
+            seeds: state.seeds,
+        }
+    }
+
+    /// Replace the crawl state with a checkpoint
+    pub async fn restore_checkpoint(&self, checkpoint: SpiderCheckpoint) -> Result<()> {
+        self.url_utils
+            .read()
+            .await
+            .restore_visited(checkpoint.visited)
+            .await?;
+        self.budget_manager
+            .restore_counters(checkpoint.budget)
+            .await;
+        self.adaptive_stop_engine
+            .restore_state(checkpoint.adaptive_stop)
+            .await?;
+        self.frontier_manager.restore(checkpoint.frontier).await?;
+
+        let mut state = self.crawl_state.write().await;
+        state.pages_crawled = checkpoint.pages_crawled;
+        state.pages_failed = checkpoint.pages_failed;
+        state.active_domains = checkpoint.active_domains;
+        state.seeds = checkpoint.seeds;
+        state.frontier_size = self.frontier_manager.size();
+        Ok(())
+    }
+
+    /// Run the crawl loop over the current frontier
+    async fn run_crawl(
+        &self,
+        run: &CrawlRun,
+        sink: Option<&mpsc::Sender<EnrichedCrawlResult>>,
+    ) -> Result<SpiderResult> {
+        let result = self.crawl_loop(run, sink).await;
 
         // Clean up
         {
             let mut state = self.crawl_state.write().await;
             state.active = false;
         }
+        if self.control.finish().await == CrawlRunState::Cancelled {
+            self.discard_cancelled_crawl().await;
+        }
+        let result = result?;
 
         info!(
             "Crawl completed: {} pages crawled, {} failed",
@@ -283,21 +871,82 @@ impl Spider {
         Ok(result)
     }
 
+    /// Drop the frontier and checkpoint of a cancelled crawl
+    async fn discard_cancelled_crawl(&self) {
+        self.frontier_manager.clear().await;
+        self.crawl_state.write().await.frontier_size = 0;
+        if let Some(checkpointing) = &self.checkpointing {
+            if let Err(e) = checkpointing
+                .store
+                .delete(&checkpointing.checkpoint_id)
+                .await
+            {
+                warn!(error = %e, "Failed to delete checkpoint of cancelled crawl");
+            }
+        }
+        info!("Cancelled crawl cleaned up");
+    }
+
+    /// Hold the crawl while it is paused
+    ///
+    /// The page fetched when the pause was requested has been processed, so
+    /// the checkpoint written here covers everything crawled so far.
+    async fn wait_while_paused(&self) {
+        if self.control.state() != CrawlRunState::Paused {
+            return;
+        }
+        if let Some(checkpointing) = &self.checkpointing {
+            if let Err(e) = self
+                .checkpoint(checkpointing.store.as_ref(), &checkpointing.checkpoint_id)
+                .await
+            {
+                warn!(error = %e, "Failed to checkpoint paused crawl");
+            }
+        }
+        info!("Crawl paused");
+        self.control.wait_while_paused().await;
+        info!(state = %self.control.state(), "Crawl no longer paused");
+    }
+
     /// Main crawl loop
-    async fn crawl_loop(&self) -> Result<SpiderResult> {
+    async fn crawl_loop(
+        &self,
+        run: &CrawlRun,
+        sink: Option<&mpsc::Sender<EnrichedCrawlResult>>,
+    ) -> Result<SpiderResult> {
         let start_time = Instant::now();
-        let mut pages_crawled = 0u64;
-        let mut pages_failed = 0u64;
+        // Non-zero when resuming from a checkpoint
+        let (mut pages_crawled, mut pages_failed) = {
+            let state = self.crawl_state.read().await;
+            (state.pages_crawled, state.pages_failed)
+        };
+        let mut pages_unchanged = 0;
         let mut last_metrics_update = Instant::now();
         let mut discovered_urls: Vec<String> = Vec::new();
         let max_urls_to_collect = self.config.budget.global.max_pages.unwrap_or(10000) as usize;
+        let mut stream_closed = false;
 
         loop {
+            self.wait_while_paused().await;
+
             // Check if we should stop crawling
-            if let Some(stop_reason) = self.should_stop_crawling().await? {
+            let stop_reason = if stream_closed {
+                Some("Result stream closed".to_string())
+            } else if self.control.end_requested() {
+                Some(match self.control.state() {
+                    CrawlRunState::Cancelling => "Cancelled by request".to_string(),
+                    _ => "Stopped by request".to_string(),
+                })
+            } else if let Some(limit) = self.page_limit.filter(|&limit| pages_crawled >= limit) {
+                Some(format!("Page limit reached: {}", limit))
+            } else {
+                self.should_stop_crawling().await?
+            };
+            if let Some(stop_reason) = stop_reason {
                 return Ok(SpiderResult {
                     pages_crawled,
                     pages_failed,
+                    pages_unchanged,
                     duration: start_time.elapsed(),
                     stop_reason,
                     performance: self.performance_metrics.read().await.clone(),
@@ -310,11 +959,17 @@ impl Spider {
                         .cloned()
                         .collect(),
                     discovered_urls: discovered_urls.clone(),
+                    compliance: run.compliance.finish(),
+                    politeness: self.frontier_manager.politeness_stats(),
+                    delta: run.finish_refresh().await,
+                    link_graph: run.finish_link_graph().await,
+                    content_dedup: run.content_dedup.as_ref().map(|d| d.finish()),
+                    budget: self.budget_manager.report().await,
                 });
             }
 
-            // Get next request from frontier
-            let request = match self.frontier_manager.next_request().await? {
+            // Get next request from frontier, respecting per-host politeness
+            let request = match self.frontier_manager.next_ready_request().await? {
                 Some(req) => req,
                 None => {
                     // No more requests in frontier
@@ -322,6 +977,7 @@ impl Spider {
                         return Ok(SpiderResult {
                             pages_crawled,
                             pages_failed,
+                            pages_unchanged,
                             duration: start_time.elapsed(),
                             stop_reason: "Frontier exhausted".to_string(),
                             performance: self.performance_metrics.read().await.clone(),
@@ -334,49 +990,83 @@ impl Spider {
                                 .cloned()
                                 .collect(),
                             discovered_urls: discovered_urls.clone(),
+                            compliance: run.compliance.finish(),
+                            politeness: self.frontier_manager.politeness_stats(),
+                            delta: run.finish_refresh().await,
+                            link_graph: run.finish_link_graph().await,
+                            content_dedup: run.content_dedup.as_ref().map(|d| d.finish()),
+                            budget: self.budget_manager.report().await,
                         });
                     }
 
-                    // Wait a bit and try again
+                    // Pending hosts are held back; wait a bit and try again
                     sleep(Duration::from_millis(100)).await;
                     continue;
                 }
             };
 
             // Process the request
-            match self.process_request(request).await {
-                Ok(result) => {
+            let dispatched = request.clone();
+            match self.process_request(run, request).await {
+                Ok(mut result) => {
                     if result.success {
                         pages_crawled += 1;
+                        if result.unchanged {
+                            pages_unchanged += 1;
+                        }
 
                         // Collect the current URL if within capacity
-                        if discovered_urls.len() < max_urls_to_collect {
+                        if !result.unchanged && discovered_urls.len() < max_urls_to_collect {
                             discovered_urls.push(result.request.url.to_string());
                         }
 
+                        let page_similarity = self.page_embedding_similarity(&result).await;
+
+                        run.record_links(&result).await;
+
                         // Add extracted URLs to frontier - clone before moving to avoid partial move
-                        let extracted_urls = result.extracted_urls.clone();
+                        let extracted_urls = if run.prune_expansion(&result) {
+                            Vec::new()
+                        } else {
+                            result.extracted_urls.clone()
+                        };
                         for extracted_url in extracted_urls {
-                            let child_request = CrawlRequest::new(extracted_url)
+                            let mut child_request = CrawlRequest::new(extracted_url)
                                 .with_depth(result.request.depth + 1)
                                 .with_parent(result.request.url.clone());
+                            if let Some(anchor) = result.anchor_texts.get(&child_request.url) {
+                                child_request = child_request
+                                    .with_metadata(ANCHOR_TEXT_KEY.to_string(), anchor.clone());
+                            }
+                            if let Some(score) = result.request.score {
+                                child_request = child_request.with_metadata(
+                                    PARENT_RELEVANCE_KEY.to_string(),
+                                    score.to_string(),
+                                );
+                            }
 
                             // Check if URL should be crawled
-                            if self.should_crawl_url_internal(&child_request).await? {
-                                // Calculate priority based on strategy
-                                let mut priority = self
-                                    .strategy_engine
-                                    .read()
-                                    .await
-                                    .calculate_priority(&child_request)
+                            if let Some(reason) = self.rejection_reason(run, &child_request).await?
+                            {
+                                self.frontier_manager
+                                    .record_rejection(&child_request, reason)
                                     .await;
+                            } else {
+                                // Score for best-first, then calculate priority based on strategy
+                                let mut priority = {
+                                    let strategy_engine = self.strategy_engine.read().await;
+                                    child_request.score =
+                                        strategy_engine.score_request(&child_request).await;
+                                    strategy_engine.calculate_priority(&child_request).await
+                                };
 
                                 // Apply query-aware scoring if enabled
                                 if let Some(scorer) = self.query_aware_scorer.write().await.as_mut()
                                 {
-                                    let relevance_score = scorer.score_request(
+                                    let relevance_score = scorer.score_request_with_similarity(
                                         &child_request,
                                         result.text_content.as_deref(),
+                                        page_similarity,
                                     );
                                     // Blend strategy priority with relevance score using helper function
                                     priority =
@@ -402,6 +1092,16 @@ impl Spider {
                             .await
                             .record_crawl_result(true)
                             .await;
+
+                        // Hand the page to the result stream; a full channel holds the crawl back
+                        if let Some(sink) = sink.filter(|_| !result.unchanged) {
+                            if let Some(item) = EnrichedCrawlResult::take_from(&mut result) {
+                                if sink.send(item).await.is_err() {
+                                    debug!("Result stream closed, stopping crawl");
+                                    stream_closed = true;
+                                }
+                            }
+                        }
                     } else {
                         pages_failed += 1;
                         self.strategy_engine
@@ -419,6 +1119,9 @@ impl Spider {
                 Err(e) => {
                     pages_failed += 1;
                     error!("Request processing failed: {}", e);
+                    self.frontier_manager
+                        .record_result(&dispatched, false, Some(e.to_string()))
+                        .await;
                 }
             }
 
@@ -436,12 +1139,23 @@ impl Spider {
                 state.pages_failed = pages_failed;
                 state.frontier_size = self.frontier_manager.size();
             }
+
+            if let Some(checkpointing) = &self.checkpointing {
+                if (pages_crawled + pages_failed) % checkpointing.interval_pages == 0 {
+                    if let Err(e) = self
+                        .checkpoint(checkpointing.store.as_ref(), &checkpointing.checkpoint_id)
+                        .await
+                    {
+                        warn!(error = %e, "Failed to write crawl checkpoint");
+                    }
+                }
+            }
         }
     }
 
     /// Process a single crawl request
-    #[instrument(skip(self), fields(url = %request.url))]
-    async fn process_request(&self, request: CrawlRequest) -> Result<CrawlResult> {
+    #[instrument(skip(self, run), fields(url = %request.url))]
+    async fn process_request(&self, run: &CrawlRun, request: CrawlRequest) -> Result<CrawlResult> {
         let start_time = Instant::now();
 
         debug!(
@@ -488,6 +1202,8 @@ impl Spider {
             .can_crawl_with_wait(request.url.as_str())
             .await?
         {
+            run.compliance
+                .record_robots_disallowed(request.url.as_str());
             self.budget_manager
                 .complete_request(&request.url, 0, false)
                 .await?;
@@ -496,6 +1212,12 @@ impl Spider {
                 "Blocked by robots.txt".to_string(),
             ));
         }
+        if let Some(delay) = self.robots_manager.crawl_delay(&request.url) {
+            run.compliance.record_crawl_delay(&host, delay);
+            if let Ok(delay) = Duration::try_from_secs_f64(delay) {
+                self.frontier_manager.set_crawl_delay(&host, delay);
+            }
+        }
 
         // Check circuit breaker if available
         if let Some(_circuit_breaker) = &self.circuit_breaker {
@@ -510,25 +1232,102 @@ impl Spider {
             None
         };
 
-        // Perform the actual fetch
-        let fetch_result = if let Some(fetch_engine) = &self.fetch_engine {
+        // Perform the actual fetch; refresh crawls revalidate against the stored page
+        let mut refreshed = None;
+        let fetch_result = if let Some(refresh) = &run.refresh {
+            match self.refresh_fetch(run, refresh, &request).await {
+                Ok(RefreshFetch::Unchanged(links)) => {
+                    self.budget_manager
+                        .complete_request(&request.url, 0, true)
+                        .await?;
+                    let mut result = CrawlResult::success(request);
+                    result.extracted_urls = links;
+                    result.processing_time = start_time.elapsed();
+                    return Ok(result);
+                }
+                Ok(RefreshFetch::Fetched {
+                    content,
+                    fingerprint,
+                }) => {
+                    refreshed = Some(fingerprint);
+                    let size = content.len();
+                    Ok((content, size))
+                }
+                Err(e) => Err(e),
+            }
+        } else if let Some(fetch_engine) = &self.fetch_engine {
             // Use integrated fetch engine
-            self.fetch_with_engine(fetch_engine, &request).await
+            self.fetch_with_engine(run, fetch_engine, &request).await
         } else {
-            // Use basic fetch (ReliableHttpClient is created internally)
-            self.basic_fetch(&request, None).await
+            // Use basic fetch through the spider's HTTP client
+            self.basic_fetch(run, &request, None).await
         };
 
         let (success, content_size, error) = match fetch_result {
             Ok((content, size)) => {
+                // Honor meta robots exclusions when respecting robots
+                let directives = if self.config.respect_robots {
+                    meta_robots_exclusions(&content)
+                } else {
+                    Vec::new()
+                };
+                let excluded = |d: &str| directives.iter().any(|x| x == d || x == "none");
+                let nofollow = excluded("nofollow");
+                let noindex = excluded("noindex");
+                if !directives.is_empty() {
+                    run.compliance
+                        .record_meta_robots(request.url.as_str(), directives.clone());
+                }
+
                 // Extract URLs and analyze content
-                let extracted_urls = self.extract_urls(&content, &request.url).await?;
-                let text_content = self.extract_text_content(&content).await;
+                let (extracted_urls, anchor_texts) = if nofollow {
+                    (Vec::new(), HashMap::new())
+                } else {
+                    (
+                        self.extract_urls(&content, &request.url).await?,
+                        extract_anchor_texts(&content, &request.url)?,
+                    )
+                };
+                let text_content = if noindex {
+                    None
+                } else {
+                    self.extract_text_content(&content).await
+                };
+
+                if let (Some(refresh), Some(fingerprint)) = (&run.refresh, refreshed.take()) {
+                    if let Err(e) = refresh
+                        .record_fetched(&request.url, fingerprint, &extracted_urls)
+                        .await
+                    {
+                        warn!(url = %request.url, error = %e, "Failed to store refresh record");
+                    }
+                }
+
+                let content_check = run.content_dedup.as_ref().map(|dedup| {
+                    let text = match &text_content {
+                        Some(text) => Cow::Borrowed(text.as_str()),
+                        None => {
+                            Cow::Owned(self.simple_text_extraction(&content).unwrap_or_default())
+                        }
+                    };
+                    dedup.check(&request.url, &content, &text)
+                });
+
+                let unchanged = match &self.changed_since {
+                    Some(filter) if run.refresh.is_none() => {
+                        filter.unchanged(&request.url, content.as_bytes()).await
+                    }
+                    _ => false,
+                };
 
                 let mut result = CrawlResult::success(request.clone());
-                result.content_size = size;
+                result.content_size = content.len();
+                result.unchanged = unchanged;
+                result.html = Some(content);
+                result.content_check = content_check;
                 result.text_content = text_content;
                 result.extracted_urls = extracted_urls;
+                result.anchor_texts = anchor_texts;
                 result.processing_time = start_time.elapsed();
 
                 self.budget_manager
@@ -562,44 +1361,270 @@ impl Spider {
     /// Basic fetch implementation using ReliableHttpClient
     async fn basic_fetch(
         &self,
+        run: &CrawlRun,
         request: &CrawlRequest,
         _client: Option<()>, // Unused - kept for API compatibility
     ) -> Result<(String, usize)> {
-        use riptide_reliability::{CircuitBreakerPreset, ReliableHttpClient};
-        use std::sync::Arc;
-
-        // Create client with WebScraping preset
-        let http_client = Arc::new(
-            ReliableHttpClient::with_preset(CircuitBreakerPreset::WebScraping)
-                .context("Failed to create reliable HTTP client")?,
-        );
-
-        let response = http_client
-            .get(request.url.as_str())
-            .await
-            .context("Failed to send HTTP request")?;
-
+        let response = self.send_request(run, request, Vec::new()).await?;
         if !response.status().is_success() {
             return Err(anyhow::anyhow!("HTTP error: {}", response.status()));
         }
 
-        let content = response
-            .text()
-            .await
-            .context("Failed to read response body")?;
-        let size = content.len();
+        // Bandwidth counts what crossed the wire, not the decoded page
+        let wire = WireBytes::of(&response);
+        let content = match &self.bandwidth {
+            Some(limiter) => bandwidth::read_throttled(response, limiter)
+                .await
+                .map(|body| String::from_utf8_lossy(&body).into_owned()),
+            None => response.text().await,
+        }
+        .context("Failed to read response body")?;
+        let size = WireBytes::wire_size(wire.as_ref(), content.len()) as usize;
 
         Ok((content, size))
     }
 
+    /// Send a GET for `request` with the crawl session's headers and `headers`
+    ///
+    /// A session rejected with `401` or a login redirect is renewed once.
+    /// `429` and `503` responses are fed into the politeness scheduler;
+    /// callers decide what other statuses mean.
+    async fn send_request(
+        &self,
+        run: &CrawlRun,
+        request: &CrawlRequest,
+        headers: Vec<(String, String)>,
+    ) -> Result<reqwest::Response> {
+        let provider = self.session_provider.as_ref().zip(request.host());
+        let mut session = match provider {
+            Some((provider, host)) => provider
+                .session(host)
+                .await
+                .context("Failed to load crawl session")?,
+            None => None,
+        };
+
+        // Renew a rejected session once, then give up on the page
+        let mut renewed = false;
+        let response = loop {
+            let mut options = headers
+                .iter()
+                .cloned()
+                .fold(FetchOptions::default(), |options, (k, v)| {
+                    options.add_header(k, v)
+                });
+            if let Some(session) = &session {
+                options = session_headers(session)
+                    .into_iter()
+                    .fold(options, |options, (k, v)| options.add_header(k, v));
+            }
+            let response = match self
+                .http_client
+                .get_with_options(request.url.as_str(), options)
+                .await
+            {
+                Ok(response) => response,
+                Err(e) => {
+                    // Throttling that outlasted the client's retries
+                    if let Some(throttled) = e.downcast_ref::<Throttled>() {
+                        self.record_throttled(run, request, throttled);
+                    }
+                    return Err(e.context("Failed to send HTTP request"));
+                }
+            };
+
+            let rejected = session.as_ref().is_some_and(|session| {
+                response.status() == reqwest::StatusCode::UNAUTHORIZED
+                    || session.is_login_redirect(request.url.as_str(), response.url().as_str())
+            });
+            if !rejected {
+                break response;
+            }
+            let Some((provider, host)) = provider.filter(|_| !renewed) else {
+                return Err(anyhow::anyhow!(
+                    "Session rejected for {} (HTTP {}, landed on {})",
+                    request.url,
+                    response.status(),
+                    response.url()
+                ));
+            };
+            debug!(url = %request.url, "Session rejected, renewing");
+            renewed = true;
+            session = provider
+                .renew(host)
+                .await
+                .context("Failed to renew crawl session")?;
+            if session.is_none() {
+                return Err(anyhow::anyhow!(
+                    "Session rejected for {} and could not be renewed",
+                    request.url
+                ));
+            }
+        };
+
+        let status = response.status().as_u16();
+        if status == 429 || status == 503 {
+            let throttled = Throttled {
+                status,
+                retry_after: server_retry_delay(response.headers()),
+            };
+            self.record_throttled(run, request, &throttled);
+        }
+
+        Ok(response)
+    }
+
+    /// Feed a 429/503 into the politeness scheduler and compliance report
+    fn record_throttled(&self, run: &CrawlRun, request: &CrawlRequest, throttled: &Throttled) {
+        if let Some(host) = request.host() {
+            self.frontier_manager
+                .record_throttled(host, throttled.status, throttled.retry_after);
+        }
+        run.compliance.record_rate_limited(
+            request.url.as_str(),
+            throttled.status,
+            throttled.retry_after.map(|d| d.as_secs().to_string()),
+        );
+    }
+
+    /// Revalidate a page against its stored record in refresh mode
+    ///
+    /// Unchanged pages (`304` or identical content hash) yield their stored
+    /// links; new and changed pages yield their body for processing. Failed
+    /// checks are recorded with the tracker, 404/410 as removed pages. The
+    /// request goes through [`send_request`](Self::send_request), so it
+    /// carries the crawl's session like any other page.
+    async fn refresh_fetch(
+        &self,
+        run: &CrawlRun,
+        refresh: &RefreshTracker,
+        request: &CrawlRequest,
+    ) -> Result<RefreshFetch> {
+        let previous = match refresh.previous(&request.url).await {
+            Ok(previous) => previous,
+            Err(e) => {
+                refresh.record_failed(&request.url);
+                return Err(e);
+            }
+        };
+        let fingerprint = previous.as_ref().map(|p| &p.fingerprint);
+        let validators = fingerprint
+            .map(ContentFingerprint::conditional_headers)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect();
+
+        let response = match self.send_request(run, request, validators).await {
+            Ok(response) => response,
+            Err(e) => {
+                refresh.record_failed(&request.url);
+                return Err(e);
+            }
+        };
+        let fetched =
+            if let Some(not_modified) = ConditionalFetch::not_modified(&response, fingerprint) {
+                not_modified
+            } else if response.status().is_success() {
+                let headers = response.headers().clone();
+                let body = match &self.bandwidth {
+                    Some(limiter) => bandwidth::read_throttled(response, limiter).await,
+                    None => response.bytes().await.map(|body| body.to_vec()),
+                };
+                match body {
+                    Ok(body) => ConditionalFetch::downloaded(&headers, body, fingerprint),
+                    Err(e) => {
+                        refresh.record_failed(&request.url);
+                        return Err(anyhow::Error::new(e).context("Failed to read response body"));
+                    }
+                }
+            } else {
+                match response.status().as_u16() {
+                    404 | 410 => refresh.record_gone(&request.url),
+                    _ => refresh.record_failed(&request.url),
+                }
+                return Err(anyhow::anyhow!("HTTP error: {}", response.status()));
+            };
+
+        match (fetched, previous) {
+            (ConditionalFetch::NotModified(fingerprint), Some(previous))
+            | (
+                ConditionalFetch::Fetched {
+                    fingerprint,
+                    changed: false,
+                    ..
+                },
+                Some(previous),
+            ) => {
+                let links = previous
+                    .links
+                    .iter()
+                    .filter_map(|link| Url::parse(link).ok())
+                    .collect();
+                let record = PageRecord {
+                    fingerprint,
+                    links: previous.links,
+                };
+                if let Err(e) = refresh.record_unchanged(&request.url, record).await {
+                    warn!(url = %request.url, error = %e, "Failed to store refresh record");
+                }
+                debug!(url = %request.url, "Page unchanged since last crawl");
+                Ok(RefreshFetch::Unchanged(links))
+            }
+            (
+                ConditionalFetch::Fetched {
+                    body, fingerprint, ..
+                },
+                _,
+            ) => Ok(RefreshFetch::Fetched {
+                content: String::from_utf8_lossy(&body).into_owned(),
+                fingerprint,
+            }),
+            (ConditionalFetch::NotModified(_), None) => {
+                refresh.record_failed(&request.url);
+                Err(anyhow::anyhow!(
+                    "Unexpected 304 Not Modified without stored validators"
+                ))
+            }
+        }
+    }
+
     /// Fetch using integrated fetch engine
+    ///
+    /// The engine applies its own retries, circuit breaker, TLS profiles and
+    /// bandwidth limiter. Crawls with a session provider keep using the
+    /// spider's client, which sends and renews the session.
     async fn fetch_with_engine(
         &self,
-        _fetch_engine: &Arc<FetchEngine>,
+        run: &CrawlRun,
+        fetch_engine: &Arc<FetchEngine>,
         request: &CrawlRequest,
     ) -> Result<(String, usize)> {
-        // Placeholder - integrate with actual fetch engine
-        self.basic_fetch(request, None).await
+        if self.session_provider.is_some() {
+            return self.basic_fetch(run, request, None).await;
+        }
+
+        let response = match fetch_engine.fetch(request.url.as_str()).await {
+            Ok(response) => response,
+            Err(e) => {
+                if let Some(throttled) = e.downcast_ref::<Throttled>() {
+                    self.record_throttled(run, request, throttled);
+                }
+                return Err(e.context("Failed to send HTTP request"));
+            }
+        };
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("HTTP error: {}", response.status()));
+        }
+
+        let wire = WireBytes::of(&response);
+        let body = fetch_engine
+            .read_body(response)
+            .await
+            .context("Failed to read response body")?;
+        let size = WireBytes::wire_size(wire.as_ref(), body.len()) as usize;
+
+        Ok((String::from_utf8_lossy(&body).into_owned(), size))
     }
 
     /// Extract URLs from content using riptide-extraction DOM parser
@@ -646,20 +1671,33 @@ impl Spider {
     /// Check if a URL should be crawled
     #[cfg(test)]
     pub async fn should_crawl_url(&self, request: &CrawlRequest) -> Result<bool> {
-        self.should_crawl_url_internal(request).await
+        let run = self.begin_run(&[], false).await?;
+        Ok(self.rejection_reason(&run, request).await?.is_none())
     }
 
-    /// Check if a URL should be crawled (internal)
-    async fn should_crawl_url_internal(&self, request: &CrawlRequest) -> Result<bool> {
-        // Check URL validity
-        if !self
-            .url_utils
-            .read()
-            .await
-            .is_valid_for_crawling(&request.url)
-            .await?
+    /// Why a URL should not be crawled, or `None` if it should
+    async fn rejection_reason(
+        &self,
+        run: &CrawlRun,
+        request: &CrawlRequest,
+    ) -> Result<Option<RejectionReason>> {
+        // Check URL validity (exclusions first, they are cheaper)
         {
-            return Ok(false);
+            let url_utils = self.url_utils.read().await;
+            if url_utils.should_exclude_url(&request.url) {
+                return Ok(Some(RejectionReason::Excluded));
+            }
+            if let Some(violation) = run.scope.check(&request.url, request.depth) {
+                debug!(url = %request.url, rule = violation.as_str(), "URL out of scope");
+                return Ok(Some(RejectionReason::OutOfScope));
+            }
+            if url_utils.is_duplicate_and_mark(&request.url).await? {
+                return Ok(Some(RejectionReason::Duplicate));
+            }
+        }
+
+        if self.policy_denies(&request.url).await {
+            return Ok(Some(RejectionReason::PolicyDenied));
         }
 
         // Check budget constraints
@@ -668,14 +1706,59 @@ impl Spider {
             .can_make_request(&request.url, request.depth)
             .await?
         {
-            return Ok(false);
+            return Ok(Some(RejectionReason::BudgetExceeded));
+        }
+
+        Ok(None)
+    }
+
+    /// Whether the attached URL policy denies `url`
+    async fn policy_denies(&self, url: &Url) -> bool {
+        let Some(policy) = &self.url_policy else {
+            return false;
+        };
+        match policy.denial(url.as_str()).await {
+            Some(reason) => {
+                debug!(url = %url, reason = %reason, "URL denied by crawl policy");
+                true
+            }
+            None => false,
         }
+    }
 
-        Ok(true)
+    /// Embedding similarity of a crawled page to the query, if an embedder is attached
+    async fn page_embedding_similarity(&self, result: &CrawlResult) -> Option<f64> {
+        let content = result.text_content.as_deref()?;
+        let analyzer = self
+            .query_aware_scorer
+            .read()
+            .await
+            .as_ref()?
+            .embedding_analyzer()?
+            .clone();
+
+        match analyzer.score(content).await {
+            Ok(similarity) => {
+                if let Some(scorer) = self.query_aware_scorer.write().await.as_mut() {
+                    scorer.record_embedding_similarity(similarity);
+                }
+                Some(similarity)
+            }
+            Err(e) => {
+                warn!(url = %result.request.url, error = %e, "Embedding relevance scoring failed");
+                None
+            }
+        }
     }
 
     /// Check if crawling should stop
     async fn should_stop_crawling(&self) -> Result<Option<String>> {
+        // Hard-stop when a crawl-wide budget is used up
+        if let Some(violation) = self.budget_manager.exhausted_budget().await {
+            info!(budget = %violation, "Budget exhausted, stopping crawl");
+            return Ok(Some(format!("Budget exhausted: {}", violation)));
+        }
+
         // Check query-aware early stopping
         if let Some(scorer) = self.query_aware_scorer.read().await.as_ref() {
             let (should_stop, reason) = scorer.should_stop_early();
@@ -686,8 +1769,11 @@ impl Spider {
 
         // Check adaptive stop conditions
         let stop_decision = self.adaptive_stop_engine.should_stop().await?;
-        if stop_decision.should_stop {
-            return Ok(Some(stop_decision.reason));
+        let should_stop = stop_decision.should_stop;
+        let reason = stop_decision.reason.clone();
+        self.crawl_state.write().await.last_stop_decision = Some(stop_decision);
+        if should_stop {
+            return Ok(Some(reason));
         }
 
         // Check memory pressure if memory manager is available
@@ -769,7 +1855,17 @@ impl Spider {
 
     /// Get current crawl state
     pub async fn get_crawl_state(&self) -> CrawlState {
-        self.crawl_state.read().await.clone()
+        let mut state = self.crawl_state.read().await.clone();
+        state.run_state = self.control.state();
+        state
+    }
+
+    /// Handle pausing, resuming, stopping or cancelling the crawl
+    ///
+    /// Usable while a crawl is running, unlike the spider itself when it is
+    /// shared behind a lock.
+    pub fn control(&self) -> &Arc<CrawlControl> {
+        &self.control
     }
 
     /// Get current performance metrics
@@ -782,11 +1878,24 @@ impl Spider {
         self.frontier_manager.get_metrics().await
     }
 
+    /// Inspect the frontier queue (see [`FrontierManager::snapshot`])
+    pub async fn inspect_frontier(&self, limit: usize) -> FrontierSnapshot {
+        self.frontier_manager.snapshot(limit).await
+    }
+
     /// Get adaptive stop statistics
     pub async fn get_adaptive_stop_stats(&self) -> crate::adaptive_stop::AdaptiveStopStats {
         self.adaptive_stop_engine.get_stats().await
     }
 
+    /// Register a custom signal with the adaptive stop engine
+    ///
+    /// Its report is included in every subsequent stop decision, available
+    /// as [`CrawlState::last_stop_decision`].
+    pub async fn add_stop_signal(&self, signal: Arc<dyn StopSignal>) {
+        self.adaptive_stop_engine.add_signal(signal).await;
+    }
+
     /// Get reference to session manager for testing
     #[cfg(test)]
     pub fn session_manager(&self) -> &Arc<SessionManager> {
@@ -799,8 +1908,9 @@ impl Spider {
         &self.adaptive_stop_engine
     }
 
-    /// Get reference to frontier manager for testing
-    #[cfg(test)]
+    /// Get reference to the frontier manager
+    ///
+    /// Holders can inspect the frontier while a crawl is running.
     pub fn frontier_manager(&self) -> &Arc<FrontierManager> {
         &self.frontier_manager
     }
@@ -833,6 +1943,7 @@ impl Spider {
                 avg_recent_relevance: 0.0,
                 corpus_size: 0,
                 recent_scores: Vec::new(),
+                avg_recent_similarity: None,
             },
         }
     }
@@ -865,11 +1976,12 @@ impl Spider {
         }
     }
 
-    /// Stop the current crawl
+    /// Stop the current crawl after the page being fetched
     pub async fn stop(&self) {
-        let mut state = self.crawl_state.write().await;
-        state.active = false;
-        info!("Crawl stop requested");
+        match self.control.stop().await {
+            Ok(()) => info!("Crawl stop requested"),
+            Err(e) => debug!(error = %e, "No crawl to stop"),
+        }
     }
 
     /// Clear all state and reset spider
@@ -880,6 +1992,9 @@ impl Spider {
         // Reset adaptive stop engine
         self.adaptive_stop_engine.reset().await;
 
+        // Reset budget counters
+        self.budget_manager.reset().await;
+
         // Clear URL utils
         self.url_utils.write().await.clear().await;
 
@@ -900,6 +2015,8 @@ impl Spider {
             *metrics = PerformanceMetrics::default();
         }
 
+        self.control.reset().await;
+
         info!("Spider reset completed");
         Ok(())
     }
@@ -937,6 +2054,7 @@ impl SpiderEngine for Spider {
             },
             domains: result.domains,
             discovered_urls: result.discovered_urls,
+            compliance: Some(result.compliance),
         })
     }
 
@@ -1017,8 +2135,38 @@ impl From<crate::config::UrlProcessingConfig> for crate::url_utils::UrlUtilsConf
 mod tests {
     use super::*;
     use crate::config::SpiderPresets;
+    use crate::scope::UrlPattern;
     use std::str::FromStr;
 
+    #[test]
+    fn test_extract_anchor_texts() {
+        let base = Url::from_str("https://example.com/docs/").expect("Valid URL");
+        let html = r#"<a class="nav" href="pricing">See <b>our</b>
+            pricing</a> <a href="/img"><img src="x.png"></a> <a href="pricing">Again</a>"#;
+
+        let anchors = extract_anchor_texts(html, &base).expect("Valid regex");
+        let pricing = Url::from_str("https://example.com/docs/pricing").expect("Valid URL");
+        assert_eq!(
+            anchors.get(&pricing).map(String::as_str),
+            Some("See our pricing")
+        );
+        assert_eq!(anchors.len(), 1);
+    }
+
+    #[test]
+    fn test_session_headers() {
+        let session = AuthSession::with_cookies([("sid", "abc")])
+            .with_header("Authorization", "Bearer token");
+        assert_eq!(
+            session_headers(&session),
+            vec![
+                ("Authorization".to_string(), "Bearer token".to_string()),
+                ("Cookie".to_string(), "sid=abc".to_string()),
+            ]
+        );
+        assert!(session_headers(&AuthSession::default()).is_empty());
+    }
+
     #[tokio::test]
     async fn test_spider_creation() {
         let config = SpiderPresets::development();
@@ -1039,6 +2187,37 @@ mod tests {
         assert_eq!(metrics.pages_per_second, 0.0);
     }
 
+    #[tokio::test]
+    async fn test_link_graph_recording() {
+        let page = Url::from_str("https://example.com/").expect("Valid URL");
+        let about = Url::from_str("https://example.com/about").expect("Valid URL");
+        let mut result = CrawlResult::success(CrawlRequest::new(page).with_depth(2));
+        result.extracted_urls = vec![about.clone()];
+        result.anchor_texts.insert(about, "About".to_string());
+
+        let spider = Spider::new(SpiderPresets::development())
+            .await
+            .expect("Spider should be created");
+        let run = spider.begin_run(&[], false).await.expect("Run starts");
+        run.record_links(&result).await;
+        assert!(run.finish_link_graph().await.is_none());
+
+        let spider = spider.with_link_graph();
+        let run = spider.begin_run(&[], false).await.expect("Run starts");
+        let other = spider.begin_run(&[], false).await.expect("Run starts");
+        run.record_links(&result).await;
+        let graph = run.finish_link_graph().await.expect("Graph is recorded");
+        assert_eq!(graph.len(), 1);
+        assert_eq!(graph.edges()[0].anchor_text.as_deref(), Some("About"));
+        assert_eq!(graph.edges()[0].depth, 2);
+        // Concurrent crawls keep their own graph
+        assert!(other
+            .finish_link_graph()
+            .await
+            .expect("Graph is recorded")
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn test_spider_reset() {
         let config = SpiderPresets::development();
@@ -1066,6 +2245,65 @@ mod tests {
         // Result depends on URL utils configuration
     }
 
+    #[tokio::test]
+    async fn test_url_policy_rejects_denied_links() {
+        struct BlockHost(&'static str);
+
+        #[async_trait::async_trait]
+        impl UrlPolicy for BlockHost {
+            async fn denial(&self, url: &str) -> Option<String> {
+                url.contains(self.0).then(|| "blocked domain".to_string())
+            }
+        }
+
+        let spider = Spider::new(SpiderPresets::development())
+            .await
+            .expect("Spider should be created")
+            .with_url_policy(Arc::new(BlockHost("blocked.example")));
+
+        let denied =
+            CrawlRequest::new(Url::from_str("https://blocked.example/page").expect("Valid URL"));
+        assert_eq!(
+            spider.rejection_reason(&denied).await.expect("Check works"),
+            Some(RejectionReason::PolicyDenied)
+        );
+        let allowed =
+            CrawlRequest::new(Url::from_str("https://example.com/page").expect("Valid URL"));
+        assert_ne!(
+            spider
+                .rejection_reason(&allowed)
+                .await
+                .expect("Check works"),
+            Some(RejectionReason::PolicyDenied)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scope_override_replaces_configured_rules() {
+        let mut spider = Spider::new(SpiderPresets::development())
+            .await
+            .expect("Spider should be created");
+        let blog =
+            CrawlRequest::new(Url::from_str("https://example.com/blog/1").expect("Valid URL"));
+
+        spider
+            .set_scope(Some(&ScopeConfig {
+                include: vec![UrlPattern::Regex("/docs/".to_string())],
+                ..Default::default()
+            }))
+            .expect("Valid scope");
+        assert_eq!(
+            spider.rejection_reason(&blog).await.expect("Check works"),
+            Some(RejectionReason::OutOfScope)
+        );
+
+        spider.set_scope(None).expect("Configured scope");
+        assert_ne!(
+            spider.rejection_reason(&blog).await.expect("Check works"),
+            Some(RejectionReason::OutOfScope)
+        );
+    }
+
     #[tokio::test]
     async fn test_host_semaphore_creation() {
         let config = SpiderPresets::development();
@@ -1080,4 +2318,54 @@ mod tests {
         let sem3 = spider.get_host_semaphore("other.com").await;
         assert!(!Arc::ptr_eq(&sem1, &sem3));
     }
+
+    #[tokio::test]
+    async fn test_checkpoint_restore() {
+        let store = riptide_types::ports::InMemoryCheckpointStore::new();
+        let spider = Spider::new(SpiderPresets::development())
+            .await
+            .expect("Spider should be created");
+
+        let seen = Url::from_str("https://example.com/seen").expect("Valid URL");
+        let pending = Url::from_str("https://example.com/pending").expect("Valid URL");
+        spider
+            .url_utils()
+            .read()
+            .await
+            .is_duplicate_and_mark(&seen)
+            .await
+            .expect("Dedup should work");
+        spider
+            .frontier_manager()
+            .add_request(CrawlRequest::new(pending.clone()))
+            .await
+            .expect("Add should work");
+        spider
+            .checkpoint(&store, "crawl-1")
+            .await
+            .expect("Checkpoint should be written");
+
+        let resumed = Spider::new(SpiderPresets::development())
+            .await
+            .expect("Spider should be created");
+        let checkpoint =
+            SpiderCheckpoint::from_bytes(&store.load("crawl-1").await.unwrap().unwrap())
+                .expect("Checkpoint should decode");
+        resumed
+            .restore_checkpoint(checkpoint)
+            .await
+            .expect("Restore should work");
+
+        let requests = resumed.frontier_manager().pending_requests().await;
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].url, pending);
+        assert!(resumed
+            .url_utils()
+            .read()
+            .await
+            .is_duplicate_and_mark(&seen)
+            .await
+            .unwrap());
+        assert!(resumed.resume(&store, "missing").await.is_err());
+    }
 }
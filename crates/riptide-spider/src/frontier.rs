@@ -1,8 +1,12 @@
-use crate::types::{CrawlRequest, FrontierMetrics, HostState, Priority};
+use crate::politeness::{DomainPolitenessStats, PolitenessConfig, PolitenessScheduler};
+use crate::types::{
+    CrawlRequest, FrontierMetrics, FrontierSnapshot, HostState, PendingUrl, Priority, RejectedUrl,
+    RejectionReason,
+};
 use anyhow::Result;
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
-use std::collections::{BinaryHeap, VecDeque};
+use std::collections::{BTreeMap, BinaryHeap, VecDeque};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -41,6 +45,9 @@ pub struct FrontierConfig {
     pub cleanup_interval: Duration,
     /// Maximum age for requests before cleanup
     pub max_request_age: Duration,
+    /// Per-host concurrency caps, crawl delays and adaptive backoff
+    #[serde(default)]
+    pub politeness: PolitenessConfig,
 }
 
 impl Default for FrontierConfig {
@@ -55,6 +62,7 @@ impl Default for FrontierConfig {
             max_host_diversity: 0.3, // 30% from single host max
             cleanup_interval: Duration::from_secs(300), // 5 minutes
             max_request_age: Duration::from_secs(3600), // 1 hour
+            politeness: PolitenessConfig::default(),
         }
     }
 }
@@ -130,6 +138,26 @@ impl HostQueue {
     }
 }
 
+/// Number of recent rejections kept for inspection
+const MAX_RECENT_REJECTIONS: usize = 100;
+
+/// Rejection counts and the most recent rejected URLs
+#[derive(Debug, Default)]
+struct RejectionLog {
+    counts: BTreeMap<RejectionReason, u64>,
+    recent: VecDeque<RejectedUrl>,
+}
+
+impl RejectionLog {
+    fn record(&mut self, rejected: RejectedUrl) {
+        *self.counts.entry(rejected.reason).or_insert(0) += 1;
+        if self.recent.len() >= MAX_RECENT_REJECTIONS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(rejected);
+    }
+}
+
 /// Disk-backed queue for spillover (simplified implementation)
 #[derive(Debug)]
 struct DiskBackedQueue {
@@ -189,6 +217,12 @@ pub struct FrontierManager {
 
     // Cleanup tracking
     last_cleanup: Arc<Mutex<Instant>>,
+
+    // Rejected URLs, for frontier inspection
+    rejections: Mutex<RejectionLog>,
+
+    // Per-domain politeness
+    politeness: PolitenessScheduler,
 }
 
 impl FrontierManager {
@@ -203,6 +237,8 @@ impl FrontierManager {
             None
         };
 
+        let politeness = PolitenessScheduler::new(config.politeness.clone());
+
         Ok(Self {
             config,
             high_priority: Arc::new(Mutex::new(VecDeque::new())),
@@ -216,6 +252,8 @@ impl FrontierManager {
             requests_added: AtomicU64::new(0),
             requests_processed: AtomicU64::new(0),
             last_cleanup: Arc::new(Mutex::new(Instant::now())),
+            rejections: Mutex::new(RejectionLog::default()),
+            politeness,
         })
     }
 
@@ -237,6 +275,8 @@ impl FrontierManager {
                 return Ok(());
             } else {
                 warn!("Frontier at memory limit and no disk spillover configured");
+                self.record_rejection(&request, RejectionReason::MemoryLimit)
+                    .await;
                 return Err(anyhow::anyhow!("Frontier memory limit exceeded"));
             }
         }
@@ -253,6 +293,9 @@ impl FrontierManager {
                             max_allowed = self.config.max_requests_per_host,
                             "Host request limit reached, dropping request"
                         );
+                        drop(queue);
+                        self.record_rejection(&request, RejectionReason::HostLimit)
+                            .await;
                         return Ok(());
                     }
                 }
@@ -356,6 +399,73 @@ impl FrontierManager {
         Ok(None)
     }
 
+    /// Get the next request whose host the politeness scheduler allows
+    ///
+    /// Follows the same queue order as [`next_request`](Self::next_request)
+    /// but skips requests for hosts that are at their concurrency cap or
+    /// still waiting out a crawl delay or backoff. Returns `None` while every
+    /// pending host is held back; the request counts as in flight until
+    /// [`record_result`](Self::record_result) is called for it.
+    pub async fn next_ready_request(&self) -> Result<Option<CrawlRequest>> {
+        if !self.politeness.is_enabled() {
+            return self.next_request().await;
+        }
+
+        let request = match self.pop_ready_scored().await {
+            Some(request) => Some(request),
+            None => {
+                let mut found = None;
+                for queue in [
+                    &self.high_priority,
+                    &self.medium_priority,
+                    &self.low_priority,
+                ] {
+                    if let Some(request) = self.pop_ready(&mut *queue.lock().await) {
+                        found = Some(request);
+                        break;
+                    }
+                }
+                found
+            }
+        };
+
+        if request.is_some() {
+            self.total_size.fetch_sub(1, Ordering::Relaxed);
+            self.requests_processed.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(request)
+    }
+
+    /// Pop the best scored request whose host is ready
+    async fn pop_ready_scored(&self) -> Option<CrawlRequest> {
+        let mut queue = self.best_first_queue.lock().await;
+        let mut deferred = Vec::new();
+        let mut found = None;
+        while let Some(candidate) = queue.pop() {
+            if self.dispatch(&candidate.request) {
+                found = Some(candidate.request);
+                break;
+            }
+            deferred.push(candidate);
+        }
+        queue.extend(deferred);
+        found
+    }
+
+    /// Remove the first request in `queue` whose host is ready
+    fn pop_ready(&self, queue: &mut VecDeque<CrawlRequest>) -> Option<CrawlRequest> {
+        let position = queue.iter().position(|request| self.dispatch(request))?;
+        queue.remove(position)
+    }
+
+    /// Ask the politeness scheduler to dispatch a request
+    fn dispatch(&self, request: &CrawlRequest) -> bool {
+        match request.host() {
+            Some(host) => self.politeness.try_dispatch(host),
+            None => true,
+        }
+    }
+
     /// Get the next request for a specific host
     pub async fn next_request_for_host(&self, host: &str) -> Result<Option<CrawlRequest>> {
         if let Some(host_queue_ref) = self.host_queues.get(host) {
@@ -380,6 +490,7 @@ impl FrontierManager {
         error: Option<String>,
     ) {
         if let Some(host) = request.host() {
+            self.politeness.finish(host, success);
             if let Some(host_queue_ref) = self.host_queues.get(host) {
                 let mut host_queue = host_queue_ref.lock().await;
                 if success {
@@ -393,6 +504,21 @@ impl FrontierManager {
         }
     }
 
+    /// Slow a host down after a 429 or 503 response
+    pub fn record_throttled(&self, host: &str, status: u16, retry_after: Option<Duration>) {
+        self.politeness.record_throttled(host, status, retry_after);
+    }
+
+    /// Apply a host's robots.txt crawl delay to dispatching
+    pub fn set_crawl_delay(&self, host: &str, delay: Duration) {
+        self.politeness.set_crawl_delay(host, delay);
+    }
+
+    /// Per-domain politeness state
+    pub fn politeness_stats(&self) -> BTreeMap<String, DomainPolitenessStats> {
+        self.politeness.stats()
+    }
+
     /// Get current frontier size
     pub fn size(&self) -> usize {
         self.total_size.load(Ordering::Relaxed)
@@ -414,6 +540,139 @@ impl FrontierManager {
         true
     }
 
+    /// Record a URL that was not added to the frontier
+    pub async fn record_rejection(&self, request: &CrawlRequest, reason: RejectionReason) {
+        debug!(url = %request.url, reason = ?reason, "URL rejected from frontier");
+        self.rejections.lock().await.record(RejectedUrl {
+            url: request.url.to_string(),
+            depth: request.depth,
+            reason,
+        });
+    }
+
+    /// Inspect the pending queue without modifying it
+    ///
+    /// Returns up to `limit` of the next requests in dequeue order (scored
+    /// requests first, then by priority), the pending depth distribution per
+    /// domain, and rejection statistics.
+    pub async fn snapshot(&self, limit: usize) -> FrontierSnapshot {
+        // Same lock order as `update_metrics`
+        let high = self.high_priority.lock().await;
+        let best_first = self.best_first_queue.lock().await;
+        let medium = self.medium_priority.lock().await;
+        let low = self.low_priority.lock().await;
+
+        let mut scored: Vec<&PriorityRequest> = best_first.iter().collect();
+        scored.sort_by(|a, b| b.cmp(a));
+        let pending = scored
+            .into_iter()
+            .map(|p| &p.request)
+            .chain(high.iter())
+            .chain(medium.iter())
+            .chain(low.iter());
+
+        let mut snapshot = FrontierSnapshot {
+            total_pending: self.size(),
+            ..FrontierSnapshot::default()
+        };
+        for request in pending {
+            if snapshot.top_pending.len() < limit {
+                snapshot.top_pending.push(PendingUrl::from(request));
+            }
+            *snapshot
+                .depth_by_domain
+                .entry(request.host().unwrap_or("unknown").to_string())
+                .or_default()
+                .entry(request.depth)
+                .or_insert(0) += 1;
+        }
+
+        let rejections = self.rejections.lock().await;
+        snapshot.rejections = rejections.counts.clone();
+        snapshot.recent_rejections = rejections.recent.iter().cloned().collect();
+        snapshot
+    }
+
+    /// Copy all pending requests in dequeue order, for crawl checkpoints
+    pub async fn pending_requests(&self) -> Vec<CrawlRequest> {
+        // Same lock order as `update_metrics`
+        let high = self.high_priority.lock().await;
+        let best_first = self.best_first_queue.lock().await;
+        let medium = self.medium_priority.lock().await;
+        let low = self.low_priority.lock().await;
+
+        let mut scored: Vec<&PriorityRequest> = best_first.iter().collect();
+        scored.sort_by(|a, b| b.cmp(a));
+        scored
+            .into_iter()
+            .map(|p| &p.request)
+            .chain(high.iter())
+            .chain(medium.iter())
+            .chain(low.iter())
+            .cloned()
+            .collect()
+    }
+
+    /// Replace the frontier contents with previously exported requests
+    ///
+    /// The new queues are built aside and swapped in while holding every
+    /// queue lock, so a concurrent reader never sees a half-restored
+    /// frontier. Memory and per-host limits are not applied: the requests
+    /// were admitted once already and a checkpoint must not lose any.
+    pub async fn restore(&self, requests: Vec<CrawlRequest>) -> Result<()> {
+        let count = requests.len();
+        let mut high = VecDeque::new();
+        let mut medium = VecDeque::new();
+        let mut low = VecDeque::new();
+        let mut best_first = BinaryHeap::new();
+        let mut hosts: BTreeMap<String, HostQueue> = BTreeMap::new();
+
+        for request in requests {
+            if self.config.enable_host_balancing {
+                if let Some(host) = request.host() {
+                    hosts
+                        .entry(host.to_string())
+                        .or_insert_with(|| HostQueue::new(host.to_string()))
+                        .push_request(request.clone());
+                }
+            }
+            if let Some(score) = request.score {
+                best_first.push(PriorityRequest { request, score });
+            } else {
+                match request.priority {
+                    Priority::Critical | Priority::High => high.push_back(request),
+                    Priority::Medium => medium.push_back(request),
+                    Priority::Low => low.push_back(request),
+                }
+            }
+        }
+
+        {
+            // Same lock order as `update_metrics`
+            let mut high_queue = self.high_priority.lock().await;
+            let mut best_first_queue = self.best_first_queue.lock().await;
+            let mut medium_queue = self.medium_priority.lock().await;
+            let mut low_queue = self.low_priority.lock().await;
+
+            *high_queue = high;
+            *best_first_queue = best_first;
+            *medium_queue = medium;
+            *low_queue = low;
+
+            self.host_queues.clear();
+            for (host, queue) in hosts {
+                self.host_queues.insert(host, Arc::new(Mutex::new(queue)));
+            }
+            self.politeness.clear();
+            self.total_size.store(count, Ordering::Relaxed);
+            *self.rejections.lock().await = RejectionLog::default();
+        }
+
+        self.update_metrics().await;
+        info!(requests = count, "Restored frontier");
+        Ok(())
+    }
+
     /// Get frontier metrics
     pub async fn get_metrics(&self) -> FrontierMetrics {
         self.metrics.read().await.clone()
@@ -508,7 +767,9 @@ impl FrontierManager {
         self.low_priority.lock().await.clear();
         self.best_first_queue.lock().await.clear();
         self.host_queues.clear();
+        self.politeness.clear();
         self.total_size.store(0, Ordering::Relaxed);
+        *self.rejections.lock().await = RejectionLog::default();
 
         info!("Cleared frontier");
     }
@@ -663,4 +924,125 @@ mod tests {
         assert_eq!(host_state.error_count, 1);
         assert_eq!(host_state.success_rate(), 0.5);
     }
+
+    #[tokio::test]
+    async fn test_snapshot_and_rejections() {
+        let config = FrontierConfig {
+            max_requests_per_host: 2,
+            ..FrontierConfig::default()
+        };
+        let frontier = FrontierManager::new(config).expect("Failed to create frontier");
+
+        let low = Url::from_str("https://example.com/low").expect("Valid URL");
+        let scored = Url::from_str("https://example.com/scored").expect("Valid URL");
+        let dropped = Url::from_str("https://example.com/dropped").expect("Valid URL");
+        frontier
+            .add_request(CrawlRequest::new(low.clone()).with_priority(Priority::Low))
+            .await
+            .expect("Failed to add low priority");
+        frontier
+            .add_request(
+                CrawlRequest::new(scored.clone())
+                    .with_depth(2)
+                    .with_score(0.9),
+            )
+            .await
+            .expect("Failed to add scored");
+        frontier
+            .add_request(CrawlRequest::new(dropped))
+            .await
+            .expect("Host limit drops silently");
+
+        let snapshot = frontier.snapshot(10).await;
+        assert_eq!(snapshot.total_pending, 2);
+        assert_eq!(snapshot.top_pending[0].url, scored.as_str());
+        assert_eq!(snapshot.top_pending[1].url, low.as_str());
+        assert_eq!(snapshot.depth_by_domain["example.com"][&2], 1);
+        assert_eq!(snapshot.rejections[&RejectionReason::HostLimit], 1);
+        assert_eq!(snapshot.recent_rejections.len(), 1);
+
+        assert_eq!(frontier.snapshot(1).await.top_pending.len(), 1);
+        assert_eq!(frontier.size(), 2, "snapshot must not dequeue");
+    }
+
+    #[tokio::test]
+    async fn test_restore_keeps_every_request() {
+        let config = FrontierConfig {
+            max_requests_per_host: 1,
+            ..FrontierConfig::default()
+        };
+        let frontier = FrontierManager::new(config).expect("Failed to create frontier");
+        frontier
+            .add_request(CrawlRequest::new(
+                Url::from_str("https://old.example/").expect("Valid URL"),
+            ))
+            .await
+            .expect("Failed to add request");
+
+        let requests: Vec<CrawlRequest> = (0..3)
+            .map(|i| {
+                CrawlRequest::new(
+                    Url::from_str(&format!("https://example.com/{i}")).expect("Valid URL"),
+                )
+            })
+            .collect();
+        frontier
+            .restore(requests.clone())
+            .await
+            .expect("Failed to restore");
+
+        assert_eq!(frontier.size(), 3, "restore must not apply host limits");
+        let pending: Vec<Url> = frontier
+            .pending_requests()
+            .await
+            .into_iter()
+            .map(|r| r.url)
+            .collect();
+        let expected: Vec<Url> = requests.into_iter().map(|r| r.url).collect();
+        assert_eq!(pending, expected);
+    }
+
+    #[tokio::test]
+    async fn test_next_ready_request_respects_host_politeness() {
+        let config = FrontierConfig {
+            politeness: PolitenessConfig {
+                max_concurrent_per_host: 1,
+                ..PolitenessConfig::default()
+            },
+            ..FrontierConfig::default()
+        };
+        let frontier = FrontierManager::new(config).expect("Failed to create frontier");
+
+        let a1 = Url::from_str("https://a.example/1").expect("Valid URL");
+        let a2 = Url::from_str("https://a.example/2").expect("Valid URL");
+        let b1 = Url::from_str("https://b.example/1").expect("Valid URL");
+        for (url, priority) in [
+            (&a1, Priority::High),
+            (&a2, Priority::High),
+            (&b1, Priority::Low),
+        ] {
+            frontier
+                .add_request(CrawlRequest::new(url.clone()).with_priority(priority))
+                .await
+                .expect("Failed to add request");
+        }
+
+        // a.example is at its cap after the first dispatch, so b.example jumps ahead
+        let first = frontier.next_ready_request().await.unwrap().unwrap();
+        assert_eq!(first.url, a1);
+        let second = frontier.next_ready_request().await.unwrap().unwrap();
+        assert_eq!(second.url, b1);
+        assert!(frontier.next_ready_request().await.unwrap().is_none());
+        assert_eq!(frontier.size(), 1);
+
+        frontier.record_result(&first, true, None).await;
+        let third = frontier.next_ready_request().await.unwrap().unwrap();
+        assert_eq!(third.url, a2);
+
+        frontier.record_throttled("b.example", 429, Some(Duration::from_secs(30)));
+        let stats = frontier.politeness_stats();
+        assert_eq!(stats["a.example"].requests_dispatched, 2);
+        assert_eq!(stats["a.example"].deferrals, 2);
+        assert_eq!(stats["b.example"].throttled_responses, 1);
+    }
 }
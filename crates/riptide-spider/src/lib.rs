@@ -8,6 +8,11 @@
 //! - **Adaptive stopping**: Content-based crawl termination
 //! - **Budget controls**: Time, depth, and page count limits
 //! - **Rate limiting**: Respectful crawling with configurable delays
+//! - **Politeness**: Per-domain concurrency caps, robots.txt crawl delays and
+//!   adaptive backoff on 429/503
+//! - **Refresh crawls**: Conditional re-crawls reporting added/changed/removed URLs
+//! - **Content fingerprinting**: SimHash near-duplicate and soft-404 detection
+//! - **Result streaming**: Pages yielded as they are crawled, with backpressure
 //! - **Session persistence**: Support for authenticated crawling
 //! - **Query-aware crawling**: Relevance-based URL prioritization
 //!
@@ -22,19 +27,27 @@
 pub mod adaptive_stop;
 pub mod budget;
 pub mod builder;
+pub mod checkpoint;
+pub mod compliance;
 pub mod config;
+pub mod content_dedup;
+pub mod control;
 pub mod core;
 pub mod extractor;
 pub mod frontier;
 pub mod memory_manager;
+pub mod politeness;
 pub mod query_aware;
 pub mod query_aware_benchmark;
 pub mod query_aware_tests;
+pub mod refresh;
 pub mod results;
+pub mod scope;
 // NOTE: robots module removed - now re-exported from riptide-fetch (Sprint 0.4.1)
 pub mod session;
 pub mod sitemap;
 pub mod strategy;
+pub mod stream;
 pub mod types;
 pub mod url_utils;
 pub mod wasm_validation;
@@ -44,23 +57,36 @@ pub use riptide_utils::circuit_breaker::CircuitBreaker;
 
 // Re-export robots from riptide-fetch (Sprint 0.4.1 - deduplication)
 pub use riptide_fetch::robots::{RobotsConfig, RobotsManager};
+pub use riptide_fetch::robots_service::RobotsService;
 
 // Re-export main types
-pub use adaptive_stop::AdaptiveStopEngine;
-pub use budget::BudgetManager;
+pub use adaptive_stop::{AdaptiveStopEngine, SignalReport, StopContext, StopDecision, StopSignal};
+pub use budget::{BudgetManager, BudgetReport, BudgetViolation};
 pub use builder::{BuiltSpider, SpiderBuilder};
+pub use checkpoint::{CheckpointSettings, SpiderCheckpoint};
+pub use compliance::ComplianceReporter;
 pub use config::SpiderConfig;
+pub use content_dedup::{
+    ContentCheck, ContentDedupConfig, ContentDedupReport, ContentDeduplicator,
+};
+pub use control::{ControlSnapshot, ControlTransition, CrawlControl, CrawlRunState};
 pub use core::{CrawlState, PerformanceMetrics, Spider, SpiderResult};
 pub use extractor::{BasicExtractor, ContentExtractor, NoOpExtractor};
 pub use frontier::FrontierManager;
+pub use politeness::{DomainPolitenessStats, PolitenessConfig, PolitenessScheduler};
 pub use query_aware::{
-    BM25Scorer, ContentSimilarityAnalyzer, DomainDiversityAnalyzer, QueryAwareConfig,
-    QueryAwareScorer, QueryAwareStats, UrlSignalAnalyzer,
+    BM25Scorer, ContentSimilarityAnalyzer, DomainDiversityAnalyzer, EmbeddingSimilarityAnalyzer,
+    QueryAwareConfig, QueryAwareScorer, QueryAwareStats, TextEmbedder, UrlSignalAnalyzer,
+};
+pub use refresh::{ChangedSinceFilter, DeltaReport, PageRecord, RefreshStore, RefreshTracker};
+pub use results::{
+    enrich, enrich_with_dedup, CrawlEdge, CrawlGraph, EnrichedCrawlResult, RawCrawlResult,
 };
-pub use results::{enrich, EnrichedCrawlResult, RawCrawlResult};
+pub use scope::{ScopeConfig, SubdomainPolicy, UrlPattern, UrlScope};
 pub use session::SessionManager;
-pub use sitemap::SitemapParser;
-pub use strategy::{CrawlingStrategy, StrategyEngine};
+pub use sitemap::{SitemapDocument, SitemapEntry, SitemapParser};
+pub use strategy::{CrawlingStrategy, StrategyEngine, UrlScorer, UrlScoringContext};
+pub use stream::CrawlStream;
 pub use types::*;
 
 #[cfg(test)]
@@ -29,7 +29,7 @@ pub use core::{CrawlState, PerformanceMetrics, Spider, SpiderResult};
 pub use frontier::FrontierManager;
 pub use query_aware::{QueryAwareConfig, QueryAwareScorer, QueryAwareStats};
 pub use session::SessionManager;
-pub use sitemap::SitemapParser;
+pub use sitemap::{SitemapDocument, SitemapEntry, SitemapParser};
 pub use strategy::{CrawlingStrategy, StrategyEngine};
 pub use types::*;
 
@@ -0,0 +1,347 @@
+//! Per-domain politeness scheduling
+//!
+//! The [`PolitenessScheduler`] decides when the
+//! [`FrontierManager`](crate::FrontierManager) may hand out another request
+//! for a host. Each host gets its own concurrency cap and a minimum gap
+//! between dispatches, the largest of:
+//!
+//! - the configured `min_delay`,
+//! - the `Crawl-delay` from the host's robots.txt (capped at `max_crawl_delay`),
+//! - an adaptive backoff that grows on 429/503 responses (honoring
+//!   `Retry-After`) and decays again on successful responses.
+//!
+//! Per-domain state is reported as [`DomainPolitenessStats`] in the
+//! [`SpiderResult`](crate::SpiderResult).
+
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+/// Configuration for per-domain politeness
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PolitenessConfig {
+    /// Enforce politeness when dispatching requests
+    pub enabled: bool,
+    /// Maximum concurrent in-flight requests per host
+    pub max_concurrent_per_host: u32,
+    /// Minimum delay between dispatches to the same host
+    pub min_delay: Duration,
+    /// Honor `Crawl-delay` directives from robots.txt
+    pub respect_crawl_delay: bool,
+    /// Upper bound for robots.txt crawl delays
+    pub max_crawl_delay: Duration,
+    /// Backoff applied on the first 429/503 from a host
+    pub initial_backoff: Duration,
+    /// Factor the backoff grows by on every further 429/503
+    pub backoff_multiplier: f64,
+    /// Upper bound for the adaptive backoff
+    pub max_backoff: Duration,
+    /// Factor the backoff shrinks by on every successful response
+    pub recovery_factor: f64,
+}
+
+impl Default for PolitenessConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_concurrent_per_host: 2,
+            min_delay: Duration::ZERO,
+            respect_crawl_delay: true,
+            max_crawl_delay: Duration::from_secs(60),
+            initial_backoff: Duration::from_secs(1),
+            backoff_multiplier: 2.0,
+            max_backoff: Duration::from_secs(300),
+            recovery_factor: 0.5,
+        }
+    }
+}
+
+/// Politeness state of one host, as reported in crawl results
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DomainPolitenessStats {
+    /// Requests currently in flight
+    pub in_flight: u32,
+    /// Concurrency cap for the host
+    pub max_concurrent: u32,
+    /// Crawl delay from robots.txt, after capping
+    pub crawl_delay_ms: Option<u64>,
+    /// Current adaptive backoff
+    pub backoff_ms: u64,
+    /// Gap currently enforced between dispatches
+    pub effective_delay_ms: u64,
+    /// Requests dispatched to the host
+    pub requests_dispatched: u64,
+    /// Times a request was held back by the scheduler
+    pub deferrals: u64,
+    /// 429/503 responses received
+    pub throttled_responses: u64,
+    /// Status of the most recent throttling response
+    pub last_throttle_status: Option<u16>,
+}
+
+/// Mutable scheduling state of one host
+#[derive(Debug, Default)]
+struct DomainState {
+    in_flight: u32,
+    crawl_delay: Option<Duration>,
+    backoff: Duration,
+    next_allowed: Option<Instant>,
+    requests_dispatched: u64,
+    deferrals: u64,
+    throttled_responses: u64,
+    last_throttle_status: Option<u16>,
+}
+
+/// Per-host concurrency and delay scheduler
+#[derive(Debug, Default)]
+pub struct PolitenessScheduler {
+    config: PolitenessConfig,
+    domains: DashMap<String, DomainState>,
+}
+
+impl PolitenessScheduler {
+    pub fn new(config: PolitenessConfig) -> Self {
+        Self {
+            config,
+            domains: DashMap::new(),
+        }
+    }
+
+    /// Whether politeness is enforced
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Dispatch a request to `host` if the host is ready
+    ///
+    /// Returns `false`, and counts a deferral, when the host is at its
+    /// concurrency cap or its delay has not elapsed. On success the request
+    /// is counted as in flight until [`finish`](Self::finish) is called.
+    pub fn try_dispatch(&self, host: &str) -> bool {
+        let now = Instant::now();
+        let mut state = self.domains.entry(host.to_string()).or_default();
+        let waiting = state.next_allowed.is_some_and(|at| at > now);
+        if waiting || state.in_flight >= self.config.max_concurrent_per_host {
+            state.deferrals = state.deferrals.saturating_add(1);
+            return false;
+        }
+
+        state.in_flight = state.in_flight.saturating_add(1);
+        state.requests_dispatched = state.requests_dispatched.saturating_add(1);
+        let delay = self.effective_delay(&state);
+        state.next_allowed = (!delay.is_zero()).then(|| now + delay);
+        true
+    }
+
+    /// Time until `host` can take another request, zero if it is ready now
+    pub fn ready_in(&self, host: &str) -> Duration {
+        self.domains
+            .get(host)
+            .and_then(|state| state.next_allowed)
+            .map_or(Duration::ZERO, |at| {
+                at.saturating_duration_since(Instant::now())
+            })
+    }
+
+    /// Record the robots.txt crawl delay of `host`
+    pub fn set_crawl_delay(&self, host: &str, delay: Duration) {
+        if !self.config.respect_crawl_delay {
+            return;
+        }
+        let delay = delay.min(self.config.max_crawl_delay);
+        let mut state = self.domains.entry(host.to_string()).or_default();
+        if state.crawl_delay != Some(delay) {
+            debug!(host = %host, delay_ms = delay.as_millis() as u64, "Applying robots.txt crawl delay");
+            state.crawl_delay = Some(delay);
+        }
+    }
+
+    /// Release the in-flight slot of a finished request
+    ///
+    /// Successful responses shrink any adaptive backoff.
+    pub fn finish(&self, host: &str, success: bool) {
+        let Some(mut state) = self.domains.get_mut(host) else {
+            return;
+        };
+        state.in_flight = state.in_flight.saturating_sub(1);
+        if success && !state.backoff.is_zero() {
+            let reduced = state
+                .backoff
+                .mul_f64(self.config.recovery_factor.clamp(0.0, 1.0));
+            state.backoff = if reduced <= self.config.initial_backoff / 2 {
+                Duration::ZERO
+            } else {
+                reduced
+            };
+        }
+    }
+
+    /// Slow down after a 429 or 503 from `host`
+    ///
+    /// Grows the host's backoff and holds further dispatches for at least
+    /// the backoff or `retry_after`, whichever is longer.
+    pub fn record_throttled(&self, host: &str, status: u16, retry_after: Option<Duration>) {
+        let mut state = self.domains.entry(host.to_string()).or_default();
+        let grown = if state.backoff.is_zero() {
+            self.config.initial_backoff
+        } else {
+            state
+                .backoff
+                .mul_f64(self.config.backoff_multiplier.max(1.0))
+        };
+        state.backoff = grown.min(self.config.max_backoff);
+        state.throttled_responses = state.throttled_responses.saturating_add(1);
+        state.last_throttle_status = Some(status);
+
+        let hold = retry_after
+            .map_or(state.backoff, |retry| retry.max(state.backoff))
+            .min(self.config.max_backoff);
+        let until = Instant::now() + hold;
+        state.next_allowed = Some(state.next_allowed.map_or(until, |at| at.max(until)));
+        info!(
+            host = %host,
+            status,
+            backoff_ms = state.backoff.as_millis() as u64,
+            "Host throttled, slowing down"
+        );
+    }
+
+    /// Snapshot the politeness state of every host seen
+    pub fn stats(&self) -> BTreeMap<String, DomainPolitenessStats> {
+        self.domains
+            .iter()
+            .map(|entry| {
+                let state = entry.value();
+                let stats = DomainPolitenessStats {
+                    in_flight: state.in_flight,
+                    max_concurrent: self.config.max_concurrent_per_host,
+                    crawl_delay_ms: state.crawl_delay.map(|d| d.as_millis() as u64),
+                    backoff_ms: state.backoff.as_millis() as u64,
+                    effective_delay_ms: self.effective_delay(state).as_millis() as u64,
+                    requests_dispatched: state.requests_dispatched,
+                    deferrals: state.deferrals,
+                    throttled_responses: state.throttled_responses,
+                    last_throttle_status: state.last_throttle_status,
+                };
+                (entry.key().clone(), stats)
+            })
+            .collect()
+    }
+
+    /// Forget all per-host state
+    pub fn clear(&self) {
+        self.domains.clear();
+    }
+
+    fn effective_delay(&self, state: &DomainState) -> Duration {
+        self.config
+            .min_delay
+            .max(state.crawl_delay.unwrap_or(Duration::ZERO))
+            .max(state.backoff)
+    }
+}
+
+pub use riptide_utils::retry::parse_retry_after;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheduler(config: PolitenessConfig) -> PolitenessScheduler {
+        PolitenessScheduler::new(config)
+    }
+
+    #[test]
+    fn test_concurrency_cap() {
+        let politeness = scheduler(PolitenessConfig {
+            max_concurrent_per_host: 2,
+            ..PolitenessConfig::default()
+        });
+
+        assert!(politeness.try_dispatch("a.com"));
+        assert!(politeness.try_dispatch("a.com"));
+        assert!(!politeness.try_dispatch("a.com"));
+        assert!(politeness.try_dispatch("b.com"), "caps are per host");
+
+        politeness.finish("a.com", true);
+        assert!(politeness.try_dispatch("a.com"));
+
+        let stats = politeness.stats();
+        assert_eq!(stats["a.com"].in_flight, 2);
+        assert_eq!(stats["a.com"].requests_dispatched, 3);
+        assert_eq!(stats["a.com"].deferrals, 1);
+    }
+
+    #[test]
+    fn test_crawl_delay_spaces_dispatches() {
+        let politeness = scheduler(PolitenessConfig {
+            max_crawl_delay: Duration::from_secs(5),
+            ..PolitenessConfig::default()
+        });
+        politeness.set_crawl_delay("a.com", Duration::from_secs(30));
+
+        assert!(politeness.try_dispatch("a.com"));
+        politeness.finish("a.com", true);
+        assert!(!politeness.try_dispatch("a.com"));
+        assert!(politeness.ready_in("a.com") > Duration::from_secs(4));
+        assert_eq!(politeness.stats()["a.com"].crawl_delay_ms, Some(5000));
+    }
+
+    #[test]
+    fn test_crawl_delay_ignored_when_disabled() {
+        let politeness = scheduler(PolitenessConfig {
+            respect_crawl_delay: false,
+            ..PolitenessConfig::default()
+        });
+        politeness.set_crawl_delay("a.com", Duration::from_secs(30));
+
+        assert!(politeness.try_dispatch("a.com"));
+        politeness.finish("a.com", true);
+        assert!(politeness.try_dispatch("a.com"));
+    }
+
+    #[test]
+    fn test_throttling_backs_off_and_recovers() {
+        let politeness = scheduler(PolitenessConfig {
+            initial_backoff: Duration::from_millis(100),
+            ..PolitenessConfig::default()
+        });
+
+        assert!(politeness.try_dispatch("a.com"));
+        politeness.record_throttled("a.com", 429, None);
+        politeness.finish("a.com", false);
+        assert!(!politeness.try_dispatch("a.com"));
+
+        politeness.record_throttled("a.com", 503, Some(Duration::from_secs(10)));
+        let stats = politeness.stats();
+        assert_eq!(stats["a.com"].backoff_ms, 200);
+        assert_eq!(stats["a.com"].throttled_responses, 2);
+        assert_eq!(stats["a.com"].last_throttle_status, Some(503));
+        assert!(politeness.ready_in("a.com") > Duration::from_secs(9));
+
+        // Successes shrink 200ms -> 100ms -> 50ms, which resets the backoff
+        politeness.domains.get_mut("a.com").unwrap().next_allowed = None;
+        assert!(politeness.try_dispatch("a.com"));
+        politeness.finish("a.com", true);
+        assert_eq!(politeness.stats()["a.com"].backoff_ms, 100);
+        politeness.domains.get_mut("a.com").unwrap().next_allowed = None;
+        assert!(politeness.try_dispatch("a.com"));
+        politeness.finish("a.com", true);
+        assert_eq!(politeness.stats()["a.com"].backoff_ms, 0);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after(" 5 "), Some(Duration::from_secs(5)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+        assert_eq!(parse_retry_after("soon"), None);
+
+        let future = (chrono::Utc::now() + chrono::Duration::seconds(60)).to_rfc2822();
+        let parsed = parse_retry_after(&future).unwrap();
+        assert!(parsed > Duration::from_secs(55));
+    }
+}
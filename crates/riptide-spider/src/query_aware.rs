@@ -1,8 +1,14 @@
 use crate::types::{CrawlRequest, CrawlResult};
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
 use url::Url;
 
+/// Upper bound on chunks embedded per page
+const MAX_CHUNKS_PER_PAGE: usize = 8;
+
 /// Configuration for query-aware spider functionality
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryAwareConfig {
@@ -25,6 +31,34 @@ pub struct QueryAwareConfig {
     /// BM25 parameters
     pub bm25_k1: f64,
     pub bm25_b: f64,
+    /// Share of the relevance score taken by embedding similarity (ε)
+    ///
+    /// Only applies once a [`TextEmbedder`] is attached; the other
+    /// components are scaled by `1 - ε`.
+    #[serde(default = "default_embedding_weight")]
+    pub embedding_weight: f64,
+    /// Maximum texts per embedding request
+    #[serde(default = "default_embedding_batch_size")]
+    pub embedding_batch_size: usize,
+    /// Characters per embedded chunk of page text
+    #[serde(default = "default_embedding_chunk_chars")]
+    pub embedding_chunk_chars: usize,
+    /// Stop early when the average embedding similarity over the relevance
+    /// window falls below this value (disabled when `None`)
+    #[serde(default)]
+    pub embedding_stop_threshold: Option<f64>,
+}
+
+fn default_embedding_weight() -> f64 {
+    0.4
+}
+
+fn default_embedding_batch_size() -> usize {
+    16
+}
+
+fn default_embedding_chunk_chars() -> usize {
+    2000
 }
 
 impl Default for QueryAwareConfig {
@@ -40,6 +74,10 @@ impl Default for QueryAwareConfig {
             relevance_window_size: 10,
             bm25_k1: 1.2,
             bm25_b: 0.75,
+            embedding_weight: default_embedding_weight(),
+            embedding_batch_size: default_embedding_batch_size(),
+            embedding_chunk_chars: default_embedding_chunk_chars(),
+            embedding_stop_threshold: None,
         }
     }
 }
@@ -332,6 +370,109 @@ impl ContentSimilarityAnalyzer {
     }
 }
 
+/// Text embedding backend for semantic relevance scoring
+///
+/// `riptide-facade` implements this over an LLM provider's `embed()`; the
+/// spider itself carries no model dependency.
+#[async_trait]
+pub trait TextEmbedder: Send + Sync {
+    /// Embed `texts`, returning one vector per text in input order
+    async fn embed_batch(&self, texts: &[String]) -> anyhow::Result<Vec<Vec<f32>>>;
+
+    /// Embedder name for logging
+    fn name(&self) -> &str;
+}
+
+/// Embedding similarity analyzer for semantic query relevance
+///
+/// Pages are split into chunks and scored by their best-matching chunk, so
+/// a relevant section is not diluted by boilerplate. Chunks are embedded in
+/// batches of `batch_size`; the query is embedded once.
+#[derive(Clone)]
+pub struct EmbeddingSimilarityAnalyzer {
+    embedder: Arc<dyn TextEmbedder>,
+    query: String,
+    query_vector: Arc<OnceCell<Vec<f32>>>,
+    batch_size: usize,
+    chunk_chars: usize,
+}
+
+impl std::fmt::Debug for EmbeddingSimilarityAnalyzer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EmbeddingSimilarityAnalyzer")
+            .field("embedder", &self.embedder.name())
+            .field("query", &self.query)
+            .field("batch_size", &self.batch_size)
+            .field("chunk_chars", &self.chunk_chars)
+            .finish()
+    }
+}
+
+impl EmbeddingSimilarityAnalyzer {
+    pub fn new(
+        embedder: Arc<dyn TextEmbedder>,
+        query: &str,
+        batch_size: usize,
+        chunk_chars: usize,
+    ) -> Self {
+        Self {
+            embedder,
+            query: query.to_string(),
+            query_vector: Arc::new(OnceCell::new()),
+            batch_size: batch_size.max(1),
+            chunk_chars: chunk_chars.max(1),
+        }
+    }
+
+    /// Similarity of one page's text to the query, in `[0, 1]`
+    pub async fn score(&self, content: &str) -> anyhow::Result<f64> {
+        Ok(self.score_batch(&[content]).await?[0])
+    }
+
+    /// Similarity of several pages to the query, in `[0, 1]`
+    ///
+    /// Chunks of all pages share embedding requests, `batch_size` at a time.
+    pub async fn score_batch(&self, contents: &[&str]) -> anyhow::Result<Vec<f64>> {
+        let query_vector = self
+            .query_vector
+            .get_or_try_init(|| async {
+                let mut vectors = self.embedder.embed_batch(&[self.query.clone()]).await?;
+                vectors
+                    .pop()
+                    .ok_or_else(|| anyhow::anyhow!("Embedder returned no query vector"))
+            })
+            .await?;
+
+        let chunks: Vec<(usize, String)> = contents
+            .iter()
+            .enumerate()
+            .flat_map(|(page, content)| {
+                chunk_text(content, self.chunk_chars)
+                    .into_iter()
+                    .map(move |chunk| (page, chunk))
+            })
+            .collect();
+
+        let mut similarities = vec![0.0; contents.len()];
+        for batch in chunks.chunks(self.batch_size) {
+            let texts: Vec<String> = batch.iter().map(|(_, chunk)| chunk.clone()).collect();
+            let vectors = self.embedder.embed_batch(&texts).await?;
+            if vectors.len() != texts.len() {
+                anyhow::bail!(
+                    "Embedder returned {} vectors for {} texts",
+                    vectors.len(),
+                    texts.len()
+                );
+            }
+            for ((page, _), vector) in batch.iter().zip(&vectors) {
+                let similarity = cosine_similarity(query_vector, vector);
+                similarities[*page] = f64::max(similarities[*page], similarity);
+            }
+        }
+        Ok(similarities)
+    }
+}
+
 /// Main query-aware scoring engine
 #[derive(Debug)]
 pub struct QueryAwareScorer {
@@ -340,7 +481,9 @@ pub struct QueryAwareScorer {
     pub(crate) url_analyzer: UrlSignalAnalyzer,
     pub(crate) domain_analyzer: DomainDiversityAnalyzer,
     pub(crate) content_analyzer: ContentSimilarityAnalyzer,
+    pub(crate) embedding_analyzer: Option<EmbeddingSimilarityAnalyzer>,
     pub(crate) recent_scores: Vec<f64>,
+    pub(crate) recent_similarities: Vec<f64>,
 }
 
 impl QueryAwareScorer {
@@ -360,7 +503,37 @@ impl QueryAwareScorer {
             url_analyzer,
             domain_analyzer,
             content_analyzer,
+            embedding_analyzer: None,
             recent_scores: Vec::new(),
+            recent_similarities: Vec::new(),
+        }
+    }
+
+    /// Add embedding similarity to the relevance score
+    ///
+    /// Requires a target query; without one the scorer is returned unchanged.
+    pub fn with_embedder(mut self, embedder: Arc<dyn TextEmbedder>) -> Self {
+        if let Some(query) = self.config.target_query.as_deref() {
+            self.embedding_analyzer = Some(EmbeddingSimilarityAnalyzer::new(
+                embedder,
+                query,
+                self.config.embedding_batch_size,
+                self.config.embedding_chunk_chars,
+            ));
+        }
+        self
+    }
+
+    /// Embedding analyzer, if an embedder is attached
+    pub fn embedding_analyzer(&self) -> Option<&EmbeddingSimilarityAnalyzer> {
+        self.embedding_analyzer.as_ref()
+    }
+
+    /// Record a crawled page's embedding similarity for early stopping
+    pub fn record_embedding_similarity(&mut self, similarity: f64) {
+        self.recent_similarities.push(similarity);
+        if self.recent_similarities.len() > self.config.relevance_window_size {
+            self.recent_similarities.remove(0);
         }
     }
 
@@ -379,6 +552,16 @@ impl QueryAwareScorer {
 
     /// Calculate comprehensive relevance score for a crawl request
     pub fn score_request(&mut self, request: &CrawlRequest, content: Option<&str>) -> f64 {
+        self.score_request_with_similarity(request, content, None)
+    }
+
+    /// Calculate the relevance score, blending in the linking page's embedding similarity
+    pub fn score_request_with_similarity(
+        &mut self,
+        request: &CrawlRequest,
+        content: Option<&str>,
+        similarity: Option<f64>,
+    ) -> f64 {
         if !self.config.query_foraging {
             return 1.0; // Return neutral score when feature is disabled
         }
@@ -411,6 +594,12 @@ impl QueryAwareScorer {
             total_score += self.config.content_similarity_weight * similarity_score;
         }
 
+        // Embedding similarity (ε component)
+        if let Some(similarity) = similarity {
+            let weight = self.config.embedding_weight.clamp(0.0, 1.0);
+            total_score = total_score * (1.0 - weight) + weight * similarity;
+        }
+
         // Store recent score for trend analysis
         self.recent_scores.push(total_score);
         if self.recent_scores.len() > self.config.relevance_window_size {
@@ -435,10 +624,24 @@ impl QueryAwareScorer {
                 "Low relevance detected: average score {:.3} below threshold {:.3}",
                 avg_score, self.config.min_relevance_threshold
             );
-            (true, reason)
-        } else {
-            (false, String::new())
+            return (true, reason);
+        }
+
+        if let Some(threshold) = self.config.embedding_stop_threshold {
+            if self.recent_similarities.len() >= self.config.relevance_window_size {
+                let avg_similarity = self.recent_similarities.iter().sum::<f64>()
+                    / self.recent_similarities.len() as f64;
+                if avg_similarity < threshold {
+                    let reason = format!(
+                        "Low semantic relevance: average similarity {:.3} below threshold {:.3}",
+                        avg_similarity, threshold
+                    );
+                    return (true, reason);
+                }
+            }
         }
+
+        (false, String::new())
     }
 
     /// Get mutable access to recent scores (for testing/benchmarking)
@@ -463,6 +666,9 @@ impl QueryAwareScorer {
             avg_recent_relevance: avg_recent_score,
             corpus_size: self.bm25_scorer.total_docs,
             recent_scores: self.recent_scores.clone(),
+            avg_recent_similarity: (!self.recent_similarities.is_empty()).then(|| {
+                self.recent_similarities.iter().sum::<f64>() / self.recent_similarities.len() as f64
+            }),
         }
     }
 
@@ -481,6 +687,49 @@ pub struct QueryAwareStats {
     pub avg_recent_relevance: f64,
     pub corpus_size: usize,
     pub recent_scores: Vec<f64>,
+    /// Average embedding similarity of recent pages, when embeddings are used
+    #[serde(default)]
+    pub avg_recent_similarity: Option<f64>,
+}
+
+/// Cosine similarity of two vectors, clamped to `[0, 1]`
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f64 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f64, 0.0f64, 0.0f64);
+    for (x, y) in a.iter().zip(b) {
+        let (x, y) = (f64::from(*x), f64::from(*y));
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    (dot / (norm_a.sqrt() * norm_b.sqrt())).clamp(0.0, 1.0)
+}
+
+/// Split text into whitespace-aligned chunks of about `chunk_chars` characters
+fn chunk_text(text: &str, chunk_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.len() + word.len() + 1 > chunk_chars {
+            chunks.push(std::mem::take(&mut current));
+            if chunks.len() == MAX_CHUNKS_PER_PAGE {
+                return chunks;
+            }
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
 }
 
 /// Simple tokenization function
@@ -673,4 +922,113 @@ mod tests {
         // Weights should sum to 1.0 for proper normalization
         assert!((total_weight - 1.0).abs() < 0.001);
     }
+
+    /// Embeds texts on two axes, "rust" and "cooking", recording batch sizes
+    #[derive(Default)]
+    struct KeywordEmbedder {
+        batches: std::sync::Mutex<Vec<usize>>,
+    }
+
+    #[async_trait]
+    impl TextEmbedder for KeywordEmbedder {
+        async fn embed_batch(&self, texts: &[String]) -> anyhow::Result<Vec<Vec<f32>>> {
+            self.batches.lock().unwrap().push(texts.len());
+            Ok(texts
+                .iter()
+                .map(|text| {
+                    let text = text.to_lowercase();
+                    let axis = |word: &str| if text.contains(word) { 1.0 } else { 0.0 };
+                    vec![axis("rust"), axis("cooking"), 0.05]
+                })
+                .collect())
+        }
+
+        fn name(&self) -> &str {
+            "keyword"
+        }
+    }
+
+    fn embedding_config() -> QueryAwareConfig {
+        QueryAwareConfig {
+            query_foraging: true,
+            target_query: Some("rust".to_string()),
+            relevance_window_size: 2,
+            min_relevance_threshold: 0.0,
+            embedding_batch_size: 2,
+            embedding_chunk_chars: 20,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_chunk_text() {
+        assert!(chunk_text("   ", 10).is_empty());
+        assert_eq!(
+            chunk_text("alpha beta gamma delta", 11),
+            vec!["alpha beta", "gamma delta"]
+        );
+        let long = "word ".repeat(100);
+        assert_eq!(chunk_text(&long, 5).len(), MAX_CHUNKS_PER_PAGE);
+    }
+
+    #[tokio::test]
+    async fn test_embedding_similarity_batches_chunks() {
+        let embedder = Arc::new(KeywordEmbedder::default());
+        let analyzer = EmbeddingSimilarityAnalyzer::new(embedder.clone(), "rust", 2, 20);
+
+        let scores = analyzer
+            .score_batch(&[
+                "intro text here, then a section on rust ownership",
+                "a page about cooking pasta",
+                "",
+            ])
+            .await
+            .unwrap();
+        assert!(scores[0] > 0.9);
+        assert!(scores[1] < 0.1);
+        assert_eq!(scores[2], 0.0);
+
+        // Query embedded once, then chunks in batches of at most two
+        let batches = embedder.batches.lock().unwrap().clone();
+        assert_eq!(batches[0], 1);
+        assert!(batches[1..].iter().all(|&size| size <= 2));
+
+        analyzer.score("rust").await.unwrap();
+        assert_eq!(embedder.batches.lock().unwrap().len(), batches.len() + 1);
+    }
+
+    #[tokio::test]
+    async fn test_embedding_similarity_blends_and_stops() {
+        let mut config = embedding_config();
+        config.embedding_stop_threshold = Some(0.5);
+        let mut scorer =
+            QueryAwareScorer::new(config).with_embedder(Arc::new(KeywordEmbedder::default()));
+        let request = CrawlRequest::new(Url::from_str("https://example.com/a").unwrap());
+
+        let analyzer = scorer.embedding_analyzer().unwrap().clone();
+        let relevant = analyzer.score("rust borrow checker").await.unwrap();
+        let baseline = scorer.score_request(&request, None);
+        let blended = scorer.score_request_with_similarity(&request, None, Some(relevant));
+        assert!(blended > baseline);
+
+        for page in ["cooking pasta", "cooking rice"] {
+            let similarity = analyzer.score(page).await.unwrap();
+            scorer.record_embedding_similarity(similarity);
+        }
+        let (stop, reason) = scorer.should_stop_early();
+        assert!(stop);
+        assert!(reason.contains("semantic"));
+        assert!(scorer.get_stats().avg_recent_similarity.unwrap() < 0.5);
+    }
+
+    #[test]
+    fn test_embedder_requires_query() {
+        let config = QueryAwareConfig {
+            target_query: None,
+            ..embedding_config()
+        };
+        let scorer =
+            QueryAwareScorer::new(config).with_embedder(Arc::new(KeywordEmbedder::default()));
+        assert!(scorer.embedding_analyzer().is_none());
+    }
 }
@@ -0,0 +1,532 @@
+//! Incremental re-crawls with change detection
+//!
+//! In refresh mode a [`Spider`](crate::Spider) revalidates every page against
+//! the fingerprint stored by the previous crawl instead of downloading it
+//! again: validators go out as `If-None-Match` / `If-Modified-Since`, and
+//! bodies that do come back are compared by content hash. Unchanged pages
+//! are not re-processed; their stored outgoing links keep the crawl going.
+//!
+//! Page records and the URL index of the last crawl live in a
+//! [`RefreshStore`] on top of the [`CacheStorage`] port, so any cache
+//! backend (e.g. riptide-cache's Redis storage) can hold them. Fingerprints
+//! go to the shared [`FingerprintStore`], where the API's differential
+//! crawls and cache revalidation read and update the same entries. Each
+//! refresh produces a [`DeltaReport`] of added, changed and removed URLs.
+//!
+//! Outside refresh mode, a [`ChangedSinceFilter`] holds back pages whose
+//! fingerprint shows no change since a given instant, the spider's side of
+//! the API's `changed_since` crawls.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use riptide_types::ports::CacheStorage;
+use riptide_types::{ContentFingerprint, FingerprintStore};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tracing::{debug, info, warn};
+use url::Url;
+
+/// Stored state of a page from the last crawl
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageRecord {
+    /// Content hash and HTTP validators
+    pub fingerprint: ContentFingerprint,
+    /// Outgoing links, re-queued when the page is unchanged
+    pub links: Vec<String>,
+}
+
+/// Crawl results store for refresh crawls
+///
+/// Links and the index are namespaced by `scope`, so several sites or crawl
+/// configurations can share one cache; fingerprints are shared by URL.
+#[derive(Clone)]
+pub struct RefreshStore {
+    cache: Arc<dyn CacheStorage>,
+    fingerprints: FingerprintStore,
+    scope: String,
+    ttl: Option<Duration>,
+}
+
+impl RefreshStore {
+    /// Create a store for the crawl identified by `scope`
+    pub fn new(cache: Arc<dyn CacheStorage>, scope: impl Into<String>) -> Self {
+        Self {
+            fingerprints: FingerprintStore::new(cache.clone()),
+            cache,
+            scope: scope.into(),
+            ttl: None,
+        }
+    }
+
+    /// Expire stored records after `ttl` (records are kept forever by default)
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.fingerprints = self.fingerprints.with_ttl(ttl);
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Load the stored record of a page
+    ///
+    /// Pages this scope has no links for have no record, even when another
+    /// crawl fingerprinted them: without links, an unchanged page would end
+    /// the crawl there.
+    pub async fn page(&self, url: &str) -> Result<Option<PageRecord>> {
+        let Some(bytes) = self
+            .cache
+            .get(&self.links_key(url))
+            .await
+            .context("Failed to load page links")?
+        else {
+            return Ok(None);
+        };
+        let Some(fingerprint) = self
+            .fingerprints
+            .get(url)
+            .await
+            .context("Failed to load page fingerprint")?
+        else {
+            return Ok(None);
+        };
+        let links = serde_json::from_slice(&bytes).context("Failed to deserialize page links")?;
+        Ok(Some(PageRecord { fingerprint, links }))
+    }
+
+    /// Store the record of a page
+    pub async fn save_page(&self, url: &str, record: &PageRecord) -> Result<()> {
+        self.fingerprints
+            .save(url, &record.fingerprint)
+            .await
+            .context("Failed to store page fingerprint")?;
+        let bytes = serde_json::to_vec(&record.links).context("Failed to serialize page links")?;
+        self.cache
+            .set(&self.links_key(url), &bytes, self.ttl)
+            .await
+            .context("Failed to store page links")?;
+        Ok(())
+    }
+
+    /// Load the URLs of the last crawl
+    pub async fn index(&self) -> Result<BTreeSet<String>> {
+        let bytes = self
+            .cache
+            .get(&self.index_key())
+            .await
+            .context("Failed to load refresh index")?;
+        match bytes {
+            Some(b) => serde_json::from_slice(&b).context("Failed to deserialize refresh index"),
+            None => Ok(BTreeSet::new()),
+        }
+    }
+
+    /// Replace the URLs of the last crawl
+    pub async fn save_index(&self, urls: &BTreeSet<String>) -> Result<()> {
+        let bytes = serde_json::to_vec(urls).context("Failed to serialize refresh index")?;
+        self.cache
+            .set(&self.index_key(), &bytes, self.ttl)
+            .await
+            .context("Failed to store refresh index")?;
+        Ok(())
+    }
+
+    fn links_key(&self, url: &str) -> String {
+        format!(
+            "riptide:v1:refresh:{}:links:{}",
+            self.scope,
+            ContentFingerprint::hash_content(url.as_bytes())
+        )
+    }
+
+    fn index_key(&self) -> String {
+        format!("riptide:v1:refresh:{}:index", self.scope)
+    }
+}
+
+/// Changes found by a refresh crawl, compared with the previous crawl
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeltaReport {
+    /// When the previous crawl's index was loaded
+    pub started_at: Option<DateTime<Utc>>,
+    /// URLs not seen by the previous crawl
+    pub added: Vec<String>,
+    /// URLs whose content changed
+    pub changed: Vec<String>,
+    /// URLs confirmed unchanged by `304` or an identical content hash
+    pub unchanged: Vec<String>,
+    /// URLs of the previous crawl that are gone (404/410) or no longer reached
+    pub removed: Vec<String>,
+    /// URLs that could not be checked; they stay in the index
+    pub failed: Vec<String>,
+}
+
+impl DeltaReport {
+    /// Whether the refresh found any added, changed or removed URL
+    pub fn has_changes(&self) -> bool {
+        !(self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty())
+    }
+}
+
+#[derive(Debug, Default)]
+struct RefreshProgress {
+    started_at: Option<DateTime<Utc>>,
+    previous: BTreeSet<String>,
+    report_removed: bool,
+    added: BTreeSet<String>,
+    changed: BTreeSet<String>,
+    unchanged: BTreeSet<String>,
+    gone: BTreeSet<String>,
+    failed: BTreeSet<String>,
+}
+
+/// Tracks a running refresh crawl against its [`RefreshStore`]
+pub struct RefreshTracker {
+    store: RefreshStore,
+    progress: Mutex<RefreshProgress>,
+}
+
+impl RefreshTracker {
+    pub fn new(store: RefreshStore) -> Self {
+        Self {
+            store,
+            progress: Mutex::new(RefreshProgress::default()),
+        }
+    }
+
+    /// Load the previous crawl's index and start a new delta
+    ///
+    /// Resumed crawls do not know which pages were checked before the
+    /// checkpoint, so they report no removals and keep the previous index.
+    pub async fn begin(&self, resumed: bool) -> Result<()> {
+        let previous = self.store.index().await?;
+        info!(
+            previous_urls = previous.len(),
+            resumed, "Starting refresh crawl"
+        );
+        *self.lock() = RefreshProgress {
+            started_at: Some(Utc::now()),
+            previous,
+            report_removed: !resumed,
+            ..RefreshProgress::default()
+        };
+        Ok(())
+    }
+
+    /// Stored record of a page, if the previous crawl saw it
+    pub async fn previous(&self, url: &Url) -> Result<Option<PageRecord>> {
+        self.store.page(url.as_str()).await
+    }
+
+    /// Record a page confirmed unchanged, by `304` or an identical content hash
+    pub async fn record_unchanged(&self, url: &Url, record: PageRecord) -> Result<()> {
+        self.store.save_page(url.as_str(), &record).await?;
+        self.lock().unchanged.insert(url.to_string());
+        Ok(())
+    }
+
+    /// Record a new or changed page with its fingerprint and extracted links
+    ///
+    /// The page counts as changed when the previous crawl indexed it, even
+    /// if its stored record has since expired.
+    pub async fn record_fetched(
+        &self,
+        url: &Url,
+        fingerprint: ContentFingerprint,
+        links: &[Url],
+    ) -> Result<()> {
+        let record = PageRecord {
+            fingerprint,
+            links: links.iter().map(Url::to_string).collect(),
+        };
+        self.store.save_page(url.as_str(), &record).await?;
+
+        let url = url.to_string();
+        let mut progress = self.lock();
+        if progress.previous.contains(&url) {
+            progress.changed.insert(url);
+        } else {
+            progress.added.insert(url);
+        }
+        Ok(())
+    }
+
+    /// Record a page the server reports as gone (404/410)
+    pub fn record_gone(&self, url: &Url) {
+        self.lock().gone.insert(url.to_string());
+    }
+
+    /// Record a page that could not be checked
+    pub fn record_failed(&self, url: &Url) {
+        self.lock().failed.insert(url.to_string());
+    }
+
+    /// Build the delta report and store the new index
+    pub async fn finish(&self) -> Result<DeltaReport> {
+        let (report, index) = {
+            let progress = self.lock();
+            let seen: BTreeSet<String> = progress
+                .added
+                .iter()
+                .chain(&progress.changed)
+                .chain(&progress.unchanged)
+                .cloned()
+                .collect();
+
+            let mut index: BTreeSet<String> = seen.clone();
+            // Unchecked pages stay indexed so the next refresh reports them correctly
+            index.extend(
+                progress
+                    .failed
+                    .iter()
+                    .filter(|url| progress.previous.contains(*url))
+                    .cloned(),
+            );
+
+            let removed: Vec<String> = if progress.report_removed {
+                progress
+                    .previous
+                    .iter()
+                    .filter(|url| !index.contains(*url))
+                    .cloned()
+                    .collect()
+            } else {
+                index.extend(
+                    progress
+                        .previous
+                        .iter()
+                        .filter(|url| !progress.gone.contains(*url))
+                        .cloned(),
+                );
+                progress
+                    .gone
+                    .iter()
+                    .filter(|url| progress.previous.contains(*url))
+                    .cloned()
+                    .collect()
+            };
+
+            let report = DeltaReport {
+                started_at: progress.started_at,
+                added: progress.added.iter().cloned().collect(),
+                changed: progress.changed.iter().cloned().collect(),
+                unchanged: progress.unchanged.iter().cloned().collect(),
+                removed,
+                failed: progress.failed.iter().cloned().collect(),
+            };
+            (report, index)
+        };
+
+        self.store.save_index(&index).await?;
+        debug!(
+            added = report.added.len(),
+            changed = report.changed.len(),
+            removed = report.removed.len(),
+            "Refresh crawl finished"
+        );
+        Ok(report)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, RefreshProgress> {
+        self.progress.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Holds back pages whose content has not changed since `since`
+///
+/// Pages are still fetched, so their links keep the crawl going, but a page
+/// whose body matches its stored fingerprint and which last changed before
+/// `since` is not returned. Store failures count as changed.
+#[derive(Clone)]
+pub struct ChangedSinceFilter {
+    since: DateTime<Utc>,
+    fingerprints: FingerprintStore,
+}
+
+impl ChangedSinceFilter {
+    pub fn new(since: DateTime<Utc>, fingerprints: FingerprintStore) -> Self {
+        Self {
+            since,
+            fingerprints,
+        }
+    }
+
+    /// Whether `body` is unchanged since the cutoff
+    ///
+    /// New or changed content is fingerprinted, so later differential crawls
+    /// compare against this fetch.
+    pub async fn unchanged(&self, url: &Url, body: &[u8]) -> bool {
+        let previous = self
+            .fingerprints
+            .get(url.as_str())
+            .await
+            .map_err(|e| warn!(url = %url, error = %e, "Failed to load content fingerprint"))
+            .ok()
+            .flatten();
+        let content_hash = ContentFingerprint::hash_content(body);
+        let same_content = previous
+            .as_ref()
+            .is_some_and(|previous| previous.content_hash == content_hash);
+        if same_content {
+            return previous.is_some_and(|previous| !previous.changed_since(self.since));
+        }
+
+        let fingerprint =
+            ContentFingerprint::observe(previous.as_ref(), body, None, None, Utc::now());
+        if let Err(e) = self.fingerprints.save(url.as_str(), &fingerprint).await {
+            warn!(url = %url, error = %e, "Failed to store content fingerprint");
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::ports::InMemoryCache;
+
+    fn fingerprint(body: &[u8]) -> ContentFingerprint {
+        ContentFingerprint::observe(None, body, None, None, Utc::now())
+    }
+
+    fn url(path: &str) -> Url {
+        Url::parse(&format!("https://example.com{}", path)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_delta_between_crawls() {
+        let store = RefreshStore::new(Arc::new(InMemoryCache::new()), "site");
+        let tracker = RefreshTracker::new(store.clone());
+
+        // First crawl: everything is new
+        tracker.begin(false).await.unwrap();
+        for path in ["/", "/a", "/b"] {
+            tracker
+                .record_fetched(&url(path), fingerprint(path.as_bytes()), &[])
+                .await
+                .unwrap();
+        }
+        let first = tracker.finish().await.unwrap();
+        assert_eq!(first.added.len(), 3);
+        assert!(first.removed.is_empty());
+
+        // Second crawl: "/" unchanged, "/a" changed, "/b" gone, "/c" new
+        tracker.begin(false).await.unwrap();
+        let root = tracker.previous(&url("/")).await.unwrap().unwrap();
+        tracker.record_unchanged(&url("/"), root).await.unwrap();
+        tracker
+            .record_fetched(&url("/a"), fingerprint(b"new"), &[])
+            .await
+            .unwrap();
+        tracker.record_gone(&url("/b"));
+        tracker
+            .record_fetched(&url("/c"), fingerprint(b"c"), &[url("/")])
+            .await
+            .unwrap();
+        let second = tracker.finish().await.unwrap();
+
+        assert_eq!(second.added, vec![url("/c").to_string()]);
+        assert_eq!(second.changed, vec![url("/a").to_string()]);
+        assert_eq!(second.unchanged, vec![url("/").to_string()]);
+        assert_eq!(second.removed, vec![url("/b").to_string()]);
+        assert!(second.has_changes());
+
+        let index = store.index().await.unwrap();
+        assert!(!index.contains(url("/b").as_str()));
+        assert_eq!(
+            store.page(url("/c").as_str()).await.unwrap().unwrap().links,
+            vec![url("/").to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fingerprints_are_shared_across_scopes() {
+        let cache: Arc<dyn CacheStorage> = Arc::new(InMemoryCache::new());
+        let docs = RefreshStore::new(cache.clone(), "docs");
+        let blog = RefreshStore::new(cache.clone(), "blog");
+        let page = url("/");
+
+        docs.save_page(
+            page.as_str(),
+            &PageRecord {
+                fingerprint: fingerprint(b"v1"),
+                links: vec![url("/a").to_string()],
+            },
+        )
+        .await
+        .unwrap();
+        assert!(blog.page(page.as_str()).await.unwrap().is_none());
+
+        blog.save_page(
+            page.as_str(),
+            &PageRecord {
+                fingerprint: fingerprint(b"v2"),
+                links: vec![],
+            },
+        )
+        .await
+        .unwrap();
+        let record = docs.page(page.as_str()).await.unwrap().unwrap();
+        assert_eq!(
+            record.fingerprint.content_hash,
+            fingerprint(b"v2").content_hash
+        );
+        assert_eq!(record.links, vec![url("/a").to_string()]);
+        assert_eq!(
+            FingerprintStore::new(cache)
+                .get(page.as_str())
+                .await
+                .unwrap(),
+            Some(record.fingerprint)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_and_resumed_refresh_keep_index() {
+        let store = RefreshStore::new(Arc::new(InMemoryCache::new()), "site");
+        let tracker = RefreshTracker::new(store.clone());
+        tracker.begin(false).await.unwrap();
+        for path in ["/a", "/b"] {
+            tracker
+                .record_fetched(&url(path), fingerprint(b"x"), &[])
+                .await
+                .unwrap();
+        }
+        tracker.finish().await.unwrap();
+
+        // A failed check is not a removal
+        tracker.begin(false).await.unwrap();
+        tracker.record_failed(&url("/a"));
+        let report = tracker.finish().await.unwrap();
+        assert_eq!(report.failed, vec![url("/a").to_string()]);
+        assert_eq!(report.removed, vec![url("/b").to_string()]);
+
+        // A resumed refresh only reports pages that are known to be gone
+        tracker.begin(true).await.unwrap();
+        let report = tracker.finish().await.unwrap();
+        assert!(report.removed.is_empty());
+        assert!(store.index().await.unwrap().contains(url("/a").as_str()));
+    }
+
+    #[tokio::test]
+    async fn test_changed_since_filter() {
+        let fingerprints = FingerprintStore::new(Arc::new(InMemoryCache::new()));
+        let before = Utc::now() - chrono::Duration::hours(1);
+        let page = url("/");
+
+        // First sight of a page counts as changed and is fingerprinted
+        let filter = ChangedSinceFilter::new(before, fingerprints.clone());
+        assert!(!filter.unchanged(&page, b"v1").await);
+        assert!(!filter.unchanged(&page, b"v1").await);
+
+        let after = Utc::now();
+        let filter = ChangedSinceFilter::new(after, fingerprints.clone());
+        assert!(filter.unchanged(&page, b"v1").await);
+        assert!(!filter.unchanged(&page, b"v2").await);
+        assert!(fingerprints
+            .get(page.as_str())
+            .await
+            .unwrap()
+            .unwrap()
+            .changed_since(after));
+    }
+}
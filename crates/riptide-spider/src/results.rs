@@ -6,6 +6,12 @@
 //! - **RawCrawlResult**: HTTP response data without processing
 //! - **EnrichedCrawlResult**: Processed result with extracted links and text
 //!
+//! With a [`ContentDeduplicator`], enrichment also flags near-duplicate
+//! pages and soft-404s (see [`enrich_with_dedup`]).
+//!
+//! Across a crawl, links between pages can be collected into a
+//! **CrawlGraph** and exported as GraphML, DOT or JSONL for link analysis.
+//!
 //! This separation enables:
 //! - Deferred content processing for performance
 //! - Pluggable extraction strategies via ContentExtractor trait
@@ -42,8 +48,13 @@
 //! assert!(enriched.text_content.is_some());
 //! ```
 
+use crate::content_dedup::{ContentCheck, ContentDeduplicator};
 use crate::extractor::ContentExtractor;
-use http::{HeaderMap, StatusCode};
+use crate::types::CrawlResult;
+use http::{header::CONTENT_TYPE, HeaderMap, HeaderValue, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::Write;
 use url::Url;
 
 /// Raw crawl result containing unprocessed HTTP response data.
@@ -92,8 +103,10 @@ pub struct RawCrawlResult {
 /// ## Fields
 ///
 /// - `raw`: The original raw crawl result
+/// - `depth`: Crawl depth of the page (0 outside a crawl)
 /// - `extracted_urls`: Links found in the HTML content
 /// - `text_content`: Extracted plain text (if extraction succeeded)
+/// - `content_check`: Near-duplicate and soft-404 flags (if fingerprinted)
 ///
 /// ## Design Notes
 ///
@@ -110,11 +123,52 @@ pub struct EnrichedCrawlResult {
     /// The original raw crawl result
     pub raw: RawCrawlResult,
 
+    /// Crawl depth from the seed URLs, 0 when enriched outside a crawl
+    pub depth: u32,
+
     /// URLs extracted from the HTML content by the ContentExtractor
     pub extracted_urls: Vec<Url>,
 
     /// Plain text content extracted from the HTML (if available)
     pub text_content: Option<String>,
+
+    /// Near-duplicate and soft-404 flags, set by [`enrich_with_dedup`]
+    pub content_check: Option<ContentCheck>,
+}
+
+impl EnrichedCrawlResult {
+    /// Build a streamed result from a crawled page, taking its response body.
+    ///
+    /// Returns `None` for pages without a body (failures and unchanged
+    /// pages of refresh crawls).
+    pub(crate) fn take_from(result: &mut CrawlResult) -> Option<Self> {
+        let html = result.html.take()?;
+        let mut headers = HeaderMap::new();
+        if let Some(content_type) = result
+            .content_type
+            .as_deref()
+            .and_then(|v| HeaderValue::from_str(v).ok())
+        {
+            headers.insert(CONTENT_TYPE, content_type);
+        }
+        let status = result
+            .status_code
+            .and_then(|code| StatusCode::from_u16(code).ok())
+            .unwrap_or(StatusCode::OK);
+
+        Some(Self {
+            raw: RawCrawlResult {
+                url: result.request.url.clone(),
+                html,
+                status,
+                headers,
+            },
+            depth: result.request.depth,
+            extracted_urls: result.extracted_urls.clone(),
+            text_content: result.text_content.clone(),
+            content_check: result.content_check.clone(),
+        })
+    }
 }
 
 /// Enrich a raw crawl result using a content extractor.
@@ -176,11 +230,216 @@ pub fn enrich(raw: RawCrawlResult, extractor: &dyn ContentExtractor) -> Enriched
 
     EnrichedCrawlResult {
         raw,
+        depth: 0,
         extracted_urls,
         text_content,
+        content_check: None,
     }
 }
 
+/// Enrich a raw crawl result and fingerprint its content.
+///
+/// Like [`enrich`], but successful responses are also checked by `dedup`,
+/// which flags near-duplicates of earlier pages and soft-404s in
+/// `content_check`. When the deduplicator prunes the page, its extracted
+/// URLs are dropped so they are not expanded.
+pub fn enrich_with_dedup(
+    raw: RawCrawlResult,
+    extractor: &dyn ContentExtractor,
+    dedup: &ContentDeduplicator,
+) -> EnrichedCrawlResult {
+    let mut enriched = enrich(raw, extractor);
+    if enriched.raw.status.is_success() {
+        let check = dedup.check(
+            &enriched.raw.url,
+            &enriched.raw.html,
+            enriched.text_content.as_deref().unwrap_or_default(),
+        );
+        if dedup.should_prune(&check) {
+            enriched.extracted_urls.clear();
+        }
+        enriched.content_check = Some(check);
+    }
+    enriched
+}
+
+/// A link from a crawled page, as recorded in a [`CrawlGraph`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrawlEdge {
+    /// URL of the page containing the link
+    pub from: String,
+    /// URL the link points to, whether or not it was crawled
+    pub to: String,
+    /// Visible text of the link, if any
+    pub anchor_text: Option<String>,
+    /// Crawl depth of the linking page
+    pub depth: u32,
+}
+
+/// Directed link graph of a crawl
+///
+/// Holds one edge per distinct (from, to) pair, in discovery order; the
+/// first anchor text seen for a pair is kept. Export with
+/// [`to_graphml`](Self::to_graphml), [`to_dot`](Self::to_dot) or
+/// [`to_jsonl`](Self::to_jsonl) for PageRank or SEO tooling.
+///
+/// ## Example
+///
+/// ```rust
+/// use riptide_spider::results::{CrawlEdge, CrawlGraph};
+///
+/// let mut graph = CrawlGraph::new();
+/// graph.add_edge(CrawlEdge {
+///     from: "https://example.com/".to_string(),
+///     to: "https://example.com/about".to_string(),
+///     anchor_text: Some("About us".to_string()),
+///     depth: 0,
+/// });
+///
+/// assert_eq!(graph.nodes().len(), 2);
+/// assert!(graph.to_dot().contains("About us"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CrawlGraph {
+    edges: Vec<CrawlEdge>,
+    seen: HashSet<(String, String)>,
+}
+
+impl CrawlGraph {
+    /// Create an empty graph
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an edge, returning `false` if the link was already recorded
+    pub fn add_edge(&mut self, edge: CrawlEdge) -> bool {
+        if !self.seen.insert((edge.from.clone(), edge.to.clone())) {
+            return false;
+        }
+        self.edges.push(edge);
+        true
+    }
+
+    /// Edges in discovery order
+    pub fn edges(&self) -> &[CrawlEdge] {
+        &self.edges
+    }
+
+    /// All URLs appearing in the graph, sorted
+    pub fn nodes(&self) -> Vec<&str> {
+        self.edges
+            .iter()
+            .flat_map(|edge| [edge.from.as_str(), edge.to.as_str()])
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Number of edges
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Whether no links were recorded
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Export as JSON Lines, one [`CrawlEdge`] object per line
+    pub fn to_jsonl(&self) -> String {
+        let mut out = String::new();
+        for edge in &self.edges {
+            if let Ok(line) = serde_json::to_string(edge) {
+                out.push_str(&line);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// Export as a Graphviz DOT digraph
+    ///
+    /// Nodes are labelled by URL; edges carry the anchor text as label and
+    /// the depth as a `depth` attribute.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph crawl {\n");
+        for node in self.nodes() {
+            let _ = writeln!(out, "  \"{}\";", escape_dot(node));
+        }
+        for edge in &self.edges {
+            let _ = write!(
+                out,
+                "  \"{}\" -> \"{}\" [depth={}",
+                escape_dot(&edge.from),
+                escape_dot(&edge.to),
+                edge.depth
+            );
+            if let Some(anchor) = &edge.anchor_text {
+                let _ = write!(out, ", label=\"{}\"", escape_dot(anchor));
+            }
+            out.push_str("];\n");
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Export as GraphML
+    ///
+    /// Nodes get ids `n0`, `n1`, ... (sorted by URL) with the URL in the
+    /// `url` attribute; edges carry `anchor_text` and `depth` attributes.
+    pub fn to_graphml(&self) -> String {
+        let nodes = self.nodes();
+        let index = |url: &str| nodes.binary_search(&url).unwrap_or_default();
+
+        let mut out = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"url\" for=\"node\" attr.name=\"url\" attr.type=\"string\"/>\n",
+            "  <key id=\"anchor_text\" for=\"edge\" attr.name=\"anchor_text\" attr.type=\"string\"/>\n",
+            "  <key id=\"depth\" for=\"edge\" attr.name=\"depth\" attr.type=\"int\"/>\n",
+            "  <graph id=\"crawl\" edgedefault=\"directed\">\n",
+        ));
+        for (i, node) in nodes.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    <node id=\"n{}\"><data key=\"url\">{}</data></node>",
+                i,
+                escape_xml(node)
+            );
+        }
+        for edge in &self.edges {
+            let _ = write!(
+                out,
+                "    <edge source=\"n{}\" target=\"n{}\">",
+                index(&edge.from),
+                index(&edge.to)
+            );
+            if let Some(anchor) = &edge.anchor_text {
+                let _ = write!(
+                    out,
+                    "<data key=\"anchor_text\">{}</data>",
+                    escape_xml(anchor)
+                );
+            }
+            let _ = writeln!(out, "<data key=\"depth\">{}</data></edge>", edge.depth);
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -320,4 +579,117 @@ mod tests {
         let debug_str = format!("{:?}", enriched);
         assert!(debug_str.contains("EnrichedCrawlResult"));
     }
+
+    #[test]
+    fn test_enrich_with_dedup_prunes_soft_404() {
+        use crate::content_dedup::ContentDedupConfig;
+
+        let dedup = ContentDeduplicator::new(ContentDedupConfig {
+            enabled: true,
+            prune_soft_404: true,
+            ..Default::default()
+        });
+        let html = r#"<html><head><title>Page Not Found</title></head>
+            <body><a href="/home">Home</a></body></html>"#;
+
+        let raw = create_raw_result("https://example.com/missing", html);
+        let enriched = enrich_with_dedup(raw, &BasicExtractor, &dedup);
+        let check = enriched.content_check.unwrap();
+        assert!(check.soft_404);
+        assert!(enriched.extracted_urls.is_empty());
+
+        let raw = create_raw_result("https://example.com/missing", html);
+        assert!(enrich(raw, &BasicExtractor).content_check.is_none());
+    }
+
+    fn sample_graph() -> CrawlGraph {
+        let mut graph = CrawlGraph::new();
+        let edge = |from: &str, to: &str, anchor: Option<&str>, depth| CrawlEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            anchor_text: anchor.map(str::to_string),
+            depth,
+        };
+        assert!(graph.add_edge(edge(
+            "https://example.com/",
+            "https://example.com/a?x=1&y=2",
+            Some("Say \"hi\" <now>"),
+            0,
+        )));
+        assert!(graph.add_edge(edge(
+            "https://example.com/a?x=1&y=2",
+            "https://example.com/",
+            None,
+            1,
+        )));
+        assert!(!graph.add_edge(edge(
+            "https://example.com/",
+            "https://example.com/a?x=1&y=2",
+            Some("Duplicate"),
+            0,
+        )));
+        graph
+    }
+
+    #[test]
+    fn test_crawl_graph_deduplicates_edges() {
+        let graph = sample_graph();
+        assert_eq!(graph.len(), 2);
+        assert_eq!(
+            graph.edges()[0].anchor_text.as_deref(),
+            Some("Say \"hi\" <now>")
+        );
+        assert_eq!(
+            graph.nodes(),
+            vec!["https://example.com/", "https://example.com/a?x=1&y=2"]
+        );
+    }
+
+    #[test]
+    fn test_crawl_graph_exports() {
+        let graph = sample_graph();
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph crawl {"));
+        assert!(dot.contains(
+            r#""https://example.com/" -> "https://example.com/a?x=1&y=2" [depth=0, label="Say \"hi\" <now>"];"#
+        ));
+
+        let graphml = graph.to_graphml();
+        assert!(graphml.contains(r#"<data key="url">https://example.com/a?x=1&amp;y=2</data>"#));
+        assert!(graphml.contains(
+            r#"<edge source="n0" target="n1"><data key="anchor_text">Say &quot;hi&quot; &lt;now&gt;</data><data key="depth">0</data></edge>"#
+        ));
+        assert!(
+            graphml.contains(r#"<edge source="n1" target="n0"><data key="depth">1</data></edge>"#)
+        );
+
+        let lines: Vec<CrawlEdge> = graph
+            .to_jsonl()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines, graph.edges());
+    }
+
+    #[test]
+    fn test_enriched_from_crawl_result() {
+        let request = crate::types::CrawlRequest::new(Url::parse("https://example.com/a").unwrap())
+            .with_depth(2);
+        let mut result = CrawlResult::success(request.clone());
+        result.content_type = Some("text/html".to_string());
+        result.html = Some("<p>Hello</p>".to_string());
+        result.text_content = Some("Hello".to_string());
+
+        let enriched = EnrichedCrawlResult::take_from(&mut result).unwrap();
+        assert_eq!(enriched.raw.url.as_str(), "https://example.com/a");
+        assert_eq!(enriched.raw.html, "<p>Hello</p>");
+        assert_eq!(enriched.raw.headers[CONTENT_TYPE], "text/html");
+        assert_eq!(enriched.depth, 2);
+        assert_eq!(enriched.text_content.as_deref(), Some("Hello"));
+        assert!(result.html.is_none());
+
+        let mut failed = CrawlResult::failure(request, "HTTP error: 500".to_string());
+        assert!(EnrichedCrawlResult::take_from(&mut failed).is_none());
+    }
 }
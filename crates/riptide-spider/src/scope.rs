@@ -0,0 +1,498 @@
+//! URL scoping rules
+//!
+//! Scope rules decide which discovered URLs may enter the frontier at all,
+//! before deduplication and budgets are checked. They cover include/exclude
+//! patterns (glob or regex), path prefixes, query parameter limits,
+//! subdomain policy relative to the seeds, staying under the seed path, and
+//! per-pattern depth limits.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use riptide_spider::scope::{ScopeConfig, SubdomainPolicy, UrlPattern, UrlScope};
+//! use url::Url;
+//!
+//! let config = ScopeConfig {
+//!     exclude: vec![UrlPattern::Glob("/**/print/**".to_string())],
+//!     max_query_params: Some(2),
+//!     subdomain_policy: SubdomainPolicy::SameRegistrableDomain,
+//!     stay_under_seed_path: true,
+//!     ..Default::default()
+//! };
+//! let mut scope = UrlScope::new(&config).unwrap();
+//! scope.set_seeds(&[Url::parse("https://example.com/docs/").unwrap()]);
+//!
+//! let url = Url::parse("https://example.com/docs/guide").unwrap();
+//! assert!(scope.check(&url, 1).is_none());
+//! let url = Url::parse("https://example.com/blog/").unwrap();
+//! assert!(scope.check(&url, 1).is_some());
+//! ```
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use url::{Host, Position, Url};
+
+/// Second-level labels under two-letter country TLDs that are not registrable
+/// on their own (`example.co.uk`, `example.com.au`)
+const COUNTRY_SECOND_LEVEL_LABELS: &[&str] = &["ac", "co", "com", "edu", "gov", "net", "org"];
+
+/// A URL pattern
+///
+/// Glob patterns support `*` (any characters except `/`), `**` (any
+/// characters) and `?` (one character except `/`). Globs starting with `/`
+/// match the path and query; other globs match the whole URL. Regex
+/// patterns match anywhere in the whole URL.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UrlPattern {
+    Glob(String),
+    Regex(String),
+}
+
+/// Which hosts are in scope relative to the seed URLs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubdomainPolicy {
+    /// Only the exact host of a seed
+    SameHost,
+    /// Any subdomain of a seed's registrable domain (`blog.example.com` for
+    /// an `example.com` seed)
+    SameRegistrableDomain,
+    /// Any host
+    #[default]
+    Any,
+}
+
+/// Maximum crawl depth for URLs matching a pattern
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepthRule {
+    pub pattern: UrlPattern,
+    pub max_depth: u32,
+}
+
+/// URL scoping rules, evaluated before URLs enter the frontier
+///
+/// The defaults put every URL in scope.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScopeConfig {
+    /// When non-empty, URLs must match at least one of these patterns
+    pub include: Vec<UrlPattern>,
+    /// URLs matching any of these patterns are out of scope; wins over `include`
+    pub exclude: Vec<UrlPattern>,
+    /// When non-empty, URL paths must start with one of these prefixes
+    pub path_prefixes: Vec<String>,
+    /// Maximum number of query parameters
+    pub max_query_params: Option<usize>,
+    /// Hosts in scope relative to the seeds
+    pub subdomain_policy: SubdomainPolicy,
+    /// Only crawl the seed hosts, below the directory of the seed URL
+    pub stay_under_seed_path: bool,
+    /// Depth limits for matching URLs; the first matching rule applies
+    pub depth_rules: Vec<DepthRule>,
+}
+
+/// The scope rule a URL failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScopeViolation {
+    Excluded,
+    NotIncluded,
+    PathPrefix,
+    QueryParams,
+    Host,
+    SeedPath,
+    Depth,
+}
+
+impl ScopeViolation {
+    /// Rule name for logs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Excluded => "exclude",
+            Self::NotIncluded => "include",
+            Self::PathPrefix => "path_prefixes",
+            Self::QueryParams => "max_query_params",
+            Self::Host => "subdomain_policy",
+            Self::SeedPath => "stay_under_seed_path",
+            Self::Depth => "depth_rules",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CompiledPattern {
+    regex: Regex,
+    path_only: bool,
+}
+
+impl CompiledPattern {
+    fn new(pattern: &UrlPattern) -> Result<Self> {
+        let compiled = match pattern {
+            UrlPattern::Glob(glob) => Self {
+                regex: Regex::new(&glob_to_regex(glob))
+                    .with_context(|| format!("Invalid glob pattern '{}'", glob))?,
+                path_only: glob.starts_with('/'),
+            },
+            UrlPattern::Regex(regex) => Self {
+                regex: Regex::new(regex)
+                    .with_context(|| format!("Invalid regex pattern '{}'", regex))?,
+                path_only: false,
+            },
+        };
+        Ok(compiled)
+    }
+
+    fn is_match(&self, url: &Url) -> bool {
+        if self.path_only {
+            self.regex
+                .is_match(&url[Position::BeforePath..Position::AfterQuery])
+        } else {
+            self.regex.is_match(url.as_str())
+        }
+    }
+}
+
+/// Compiled scope rules for one crawl
+#[derive(Debug, Clone)]
+pub struct UrlScope {
+    include: Vec<CompiledPattern>,
+    exclude: Vec<CompiledPattern>,
+    path_prefixes: Vec<String>,
+    max_query_params: Option<usize>,
+    subdomain_policy: SubdomainPolicy,
+    stay_under_seed_path: bool,
+    depth_rules: Vec<(CompiledPattern, u32)>,
+    seeds: Vec<Url>,
+}
+
+impl UrlScope {
+    /// Compile the rules of `config`
+    ///
+    /// Fails on invalid glob or regex patterns.
+    pub fn new(config: &ScopeConfig) -> Result<Self> {
+        let compile = |patterns: &[UrlPattern]| {
+            patterns
+                .iter()
+                .map(CompiledPattern::new)
+                .collect::<Result<Vec<_>>>()
+        };
+
+        Ok(Self {
+            include: compile(&config.include)?,
+            exclude: compile(&config.exclude)?,
+            path_prefixes: config.path_prefixes.clone(),
+            max_query_params: config.max_query_params,
+            subdomain_policy: config.subdomain_policy,
+            stay_under_seed_path: config.stay_under_seed_path,
+            depth_rules: config
+                .depth_rules
+                .iter()
+                .map(|rule| Ok((CompiledPattern::new(&rule.pattern)?, rule.max_depth)))
+                .collect::<Result<Vec<_>>>()?,
+            seeds: Vec::new(),
+        })
+    }
+
+    /// Set the seed URLs that host and seed-path rules are relative to
+    ///
+    /// Without seeds, those rules put every URL in scope.
+    pub fn set_seeds(&mut self, seeds: &[Url]) {
+        self.seeds = seeds.to_vec();
+    }
+
+    /// Seed URLs of the current crawl
+    pub fn seeds(&self) -> &[Url] {
+        &self.seeds
+    }
+
+    /// First rule `url` at `depth` violates, `None` when it is in scope
+    pub fn check(&self, url: &Url, depth: u32) -> Option<ScopeViolation> {
+        if self.exclude.iter().any(|pattern| pattern.is_match(url)) {
+            return Some(ScopeViolation::Excluded);
+        }
+        if !self.include.is_empty() && !self.include.iter().any(|pattern| pattern.is_match(url)) {
+            return Some(ScopeViolation::NotIncluded);
+        }
+        if !self.path_prefixes.is_empty()
+            && !self
+                .path_prefixes
+                .iter()
+                .any(|prefix| url.path().starts_with(prefix.as_str()))
+        {
+            return Some(ScopeViolation::PathPrefix);
+        }
+        if self
+            .max_query_params
+            .is_some_and(|max| url.query_pairs().count() > max)
+        {
+            return Some(ScopeViolation::QueryParams);
+        }
+        if !self.seeds.is_empty() {
+            if !self.seeds.iter().any(|seed| self.host_in_scope(seed, url)) {
+                return Some(ScopeViolation::Host);
+            }
+            if self.stay_under_seed_path
+                && !self.seeds.iter().any(|seed| under_seed_path(seed, url))
+            {
+                return Some(ScopeViolation::SeedPath);
+            }
+        }
+        if let Some((_, max_depth)) = self
+            .depth_rules
+            .iter()
+            .find(|(pattern, _)| pattern.is_match(url))
+        {
+            if depth > *max_depth {
+                return Some(ScopeViolation::Depth);
+            }
+        }
+        None
+    }
+
+    fn host_in_scope(&self, seed: &Url, url: &Url) -> bool {
+        match self.subdomain_policy {
+            SubdomainPolicy::Any => true,
+            SubdomainPolicy::SameHost => seed.host_str() == url.host_str(),
+            SubdomainPolicy::SameRegistrableDomain => {
+                match (registrable_domain(seed), registrable_domain(url)) {
+                    (Some(seed_domain), Some(domain)) => seed_domain == domain,
+                    _ => false,
+                }
+            }
+        }
+    }
+}
+
+/// Whether `url` is on the seed's host, below the seed's directory
+///
+/// A seed ending in `/` or in an extension-less segment (`/docs`) scopes to
+/// that directory; a seed file (`/docs/index.html`) scopes to its parent.
+fn under_seed_path(seed: &Url, url: &Url) -> bool {
+    if seed.host_str() != url.host_str()
+        || seed.port_or_known_default() != url.port_or_known_default()
+    {
+        return false;
+    }
+    let seed_path = seed.path();
+    let directory = match seed_path.rsplit_once('/') {
+        _ if seed_path.ends_with('/') => seed_path.to_string(),
+        Some((parent, last)) if last.contains('.') => format!("{}/", parent),
+        _ => format!("{}/", seed_path),
+    };
+    let path = url.path();
+    path.starts_with(&directory) || path == directory.trim_end_matches('/')
+}
+
+/// Registrable domain of the URL's host, e.g. `example.co.uk` for
+/// `www.example.co.uk`
+///
+/// Uses a small heuristic instead of the public suffix list: the last two
+/// labels, or three under common second-level labels of country TLDs. IP
+/// hosts are their own registrable domain.
+fn registrable_domain(url: &Url) -> Option<String> {
+    match url.host()? {
+        Host::Domain(domain) => {
+            let labels: Vec<&str> = domain.trim_end_matches('.').split('.').collect();
+            let n = labels.len();
+            let keep = if n >= 3
+                && labels[n - 1].len() == 2
+                && COUNTRY_SECOND_LEVEL_LABELS.contains(&labels[n - 2])
+            {
+                3
+            } else {
+                2
+            };
+            Some(labels[n.saturating_sub(keep)..].join("."))
+        }
+        host => Some(host.to_string()),
+    }
+}
+
+/// Translate a glob into an anchored regex
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    fn scope(config: ScopeConfig, seeds: &[&str]) -> UrlScope {
+        let mut scope = UrlScope::new(&config).unwrap();
+        scope.set_seeds(&seeds.iter().map(|s| url(s)).collect::<Vec<_>>());
+        scope
+    }
+
+    #[test]
+    fn test_default_scope_allows_everything() {
+        let scope = scope(ScopeConfig::default(), &["https://example.com/"]);
+        assert!(scope
+            .check(&url("https://other.org/a?b=1&c=2"), 50)
+            .is_none());
+    }
+
+    #[test]
+    fn test_patterns() {
+        let scope = scope(
+            ScopeConfig {
+                include: vec![
+                    UrlPattern::Glob("/blog/**".to_string()),
+                    UrlPattern::Regex(r"/products/\d+$".to_string()),
+                ],
+                exclude: vec![UrlPattern::Glob("https://*/blog/drafts/*".to_string())],
+                ..Default::default()
+            },
+            &[],
+        );
+        assert!(scope
+            .check(&url("https://a.com/blog/2024/post"), 1)
+            .is_none());
+        assert!(scope.check(&url("https://a.com/products/42"), 1).is_none());
+        assert_eq!(
+            scope.check(&url("https://a.com/about"), 1),
+            Some(ScopeViolation::NotIncluded)
+        );
+        assert_eq!(
+            scope.check(&url("https://a.com/blog/drafts/x"), 1),
+            Some(ScopeViolation::Excluded)
+        );
+        // `*` does not cross path segments
+        assert!(scope
+            .check(&url("https://a.com/blog/drafts/x/y"), 1)
+            .is_none());
+    }
+
+    #[test]
+    fn test_path_prefix_and_query_params() {
+        let scope = scope(
+            ScopeConfig {
+                path_prefixes: vec!["/docs".to_string(), "/api/".to_string()],
+                max_query_params: Some(1),
+                ..Default::default()
+            },
+            &[],
+        );
+        assert!(scope
+            .check(&url("https://a.com/docs/intro?v=2"), 1)
+            .is_none());
+        assert_eq!(
+            scope.check(&url("https://a.com/blog"), 1),
+            Some(ScopeViolation::PathPrefix)
+        );
+        assert_eq!(
+            scope.check(&url("https://a.com/api/x?a=1&b=2"), 1),
+            Some(ScopeViolation::QueryParams)
+        );
+    }
+
+    #[test]
+    fn test_subdomain_policies() {
+        let seeds = &["https://www.example.co.uk/"];
+        let same_host = scope(
+            ScopeConfig {
+                subdomain_policy: SubdomainPolicy::SameHost,
+                ..Default::default()
+            },
+            seeds,
+        );
+        assert!(same_host
+            .check(&url("https://www.example.co.uk/a"), 1)
+            .is_none());
+        assert_eq!(
+            same_host.check(&url("https://shop.example.co.uk/a"), 1),
+            Some(ScopeViolation::Host)
+        );
+
+        let same_domain = scope(
+            ScopeConfig {
+                subdomain_policy: SubdomainPolicy::SameRegistrableDomain,
+                ..Default::default()
+            },
+            seeds,
+        );
+        assert!(same_domain
+            .check(&url("https://shop.example.co.uk/a"), 1)
+            .is_none());
+        assert_eq!(
+            same_domain.check(&url("https://other.co.uk/a"), 1),
+            Some(ScopeViolation::Host)
+        );
+    }
+
+    #[test]
+    fn test_stay_under_seed_path() {
+        let scope = scope(
+            ScopeConfig {
+                stay_under_seed_path: true,
+                ..Default::default()
+            },
+            &["https://a.com/docs/index.html", "https://b.com/guide"],
+        );
+        assert!(scope.check(&url("https://a.com/docs/setup"), 1).is_none());
+        assert!(scope.check(&url("https://b.com/guide"), 1).is_none());
+        assert!(scope.check(&url("https://b.com/guide/part-2"), 1).is_none());
+        assert_eq!(
+            scope.check(&url("https://b.com/guidelines"), 1),
+            Some(ScopeViolation::SeedPath)
+        );
+        assert_eq!(
+            scope.check(&url("https://c.com/docs/setup"), 1),
+            Some(ScopeViolation::SeedPath)
+        );
+    }
+
+    #[test]
+    fn test_depth_rules() {
+        let scope = scope(
+            ScopeConfig {
+                depth_rules: vec![
+                    DepthRule {
+                        pattern: UrlPattern::Glob("/tag/**".to_string()),
+                        max_depth: 1,
+                    },
+                    DepthRule {
+                        pattern: UrlPattern::Glob("/**".to_string()),
+                        max_depth: 4,
+                    },
+                ],
+                ..Default::default()
+            },
+            &[],
+        );
+        assert!(scope.check(&url("https://a.com/tag/rust"), 1).is_none());
+        assert_eq!(
+            scope.check(&url("https://a.com/tag/rust"), 2),
+            Some(ScopeViolation::Depth)
+        );
+        assert!(scope.check(&url("https://a.com/post/1"), 4).is_none());
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let config = ScopeConfig {
+            exclude: vec![UrlPattern::Regex("(unclosed".to_string())],
+            ..Default::default()
+        };
+        assert!(UrlScope::new(&config).is_err());
+    }
+}
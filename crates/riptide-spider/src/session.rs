@@ -1,5 +1,6 @@
 use anyhow::{Context, Result};
 use reqwest::cookie::Jar;
+use riptide_config::redaction::Redacted;
 use riptide_reliability::http_client::{CircuitBreakerPreset, ReliableHttpClient};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -104,22 +105,12 @@ pub struct LoginConfig {
 
 impl std::fmt::Debug for LoginConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        // Redact password to show only first 4 chars
-        let redacted_password = {
-            let chars: Vec<char> = self.password.chars().collect();
-            if chars.len() <= 4 {
-                format!("{}...", self.password)
-            } else {
-                format!("{}...", chars[..4].iter().collect::<String>())
-            }
-        };
-
         f.debug_struct("LoginConfig")
             .field("login_url", &self.login_url)
             .field("username_field", &self.username_field)
             .field("password_field", &self.password_field)
             .field("username", &self.username)
-            .field("password", &redacted_password)
+            .field("password", &Redacted(&self.password))
             .field("additional_fields", &self.additional_fields)
             .field("success_indicators", &self.success_indicators)
             .field("failure_indicators", &self.failure_indicators)
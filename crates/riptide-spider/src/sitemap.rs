@@ -1,14 +1,28 @@
+//! Sitemap discovery and parsing
+//!
+//! [`SitemapParser`] follows sitemap indexes recursively (up to
+//! `max_index_depth` levels), transparently decompresses gzipped sitemaps
+//! (`.xml.gz`) and caps the total number of URLs discovered per site at
+//! `max_urls`, so news sites with nested, compressed indexes work as seeds.
+
 use crate::types::{CrawlRequest, Priority, SitemapConfig};
 use anyhow::{Context, Result};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use flate2::read::MultiGzDecoder;
+use riptide_fetch::robots_service::RobotsService;
+use riptide_fetch::{BodyLimits, BodyStream};
 use riptide_reliability::http_client::{CircuitBreakerPreset, ReliableHttpClient};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
+use std::io::Read;
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 use url::Url;
 use xml::reader::{EventReader, XmlEvent};
 
+/// Largest uncompressed sitemap accepted (the sitemap protocol limit)
+const MAX_SITEMAP_BYTES: u64 = 50 * 1024 * 1024;
+
 /// Entry from a sitemap
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SitemapEntry {
@@ -18,12 +32,57 @@ pub struct SitemapEntry {
     pub priority: Option<f64>,
 }
 
+/// Contents of a single sitemap file
+#[derive(Debug, Clone, Default)]
+pub struct SitemapDocument {
+    /// URL entries of a `<urlset>`
+    pub entries: Vec<SitemapEntry>,
+    /// Child sitemap locations of a `<sitemapindex>`
+    pub sitemaps: Vec<String>,
+}
+
 /// Sitemap parser for discovering URLs
 #[derive(Debug)]
 pub struct SitemapParser {
     config: SitemapConfig,
     client: Arc<ReliableHttpClient>,
     cache: HashSet<String>,
+    /// Shared robots.txt source for `Sitemap:` directives
+    robots_service: Option<Arc<RobotsService>>,
+}
+
+/// State shared while traversing the sitemaps of one site
+struct Traversal {
+    visited: HashSet<String>,
+    seen_urls: HashSet<String>,
+    entries: Vec<SitemapEntry>,
+    limit: usize,
+}
+
+impl Traversal {
+    fn new(limit: Option<usize>) -> Self {
+        Self {
+            visited: HashSet::new(),
+            seen_urls: HashSet::new(),
+            entries: Vec::new(),
+            limit: limit.unwrap_or(usize::MAX),
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.entries.len() >= self.limit
+    }
+
+    fn extend(&mut self, entries: Vec<SitemapEntry>) {
+        for entry in entries {
+            if self.is_full() {
+                break;
+            }
+            if self.seen_urls.insert(entry.url.to_string()) {
+                self.entries.push(entry);
+            }
+        }
+    }
 }
 
 impl SitemapParser {
@@ -43,87 +102,190 @@ impl SitemapParser {
             config,
             client,
             cache: HashSet::new(),
+            robots_service: None,
         }
     }
 
+    /// Read `Sitemap:` directives from robots.txt files fetched by `service`
+    /// instead of fetching robots.txt again
+    pub fn with_robots_service(mut self, service: Arc<RobotsService>) -> Self {
+        self.robots_service = Some(service);
+        self
+    }
+
     /// Parse sitemap from URL and return entries
+    ///
+    /// Sitemap indexes are followed recursively. Failures of the sitemap
+    /// itself are returned; failures of child sitemaps are logged and skipped.
     pub async fn parse_sitemap(&self, sitemap_url: &str) -> Result<Vec<SitemapEntry>> {
-        debug!("Parsing sitemap: {}", sitemap_url);
-
-        let response = self
-            .client
-            .get(sitemap_url)
-            .await
-            .context("Failed to fetch sitemap")?;
-
-        let content = response
-            .text()
-            .await
-            .context("Failed to read sitemap content")?;
-
-        self.parse_sitemap_content(&content).await
+        let mut traversal = Traversal::new(self.config.max_urls);
+        self.traverse(sitemap_url, &mut traversal).await?;
+        Ok(traversal.entries)
     }
 
     /// Parse sitemap content from string
     pub async fn parse_sitemap_content(&self, content: &str) -> Result<Vec<SitemapEntry>> {
-        let mut entries = Vec::new();
-        let parser = EventReader::from_str(content);
+        let mut entries = self.parse_document(content)?.entries;
+        if let Some(max_urls) = self.config.max_urls {
+            entries.truncate(max_urls);
+        }
+
+        info!("Parsed {} entries from sitemap", entries.len());
+        Ok(entries)
+    }
+
+    /// Parse a sitemap or sitemap index document
+    ///
+    /// Entries below `min_priority` are dropped. `<loc>` elements of
+    /// extensions (images, videos, news) are ignored.
+    pub fn parse_document(&self, content: &str) -> Result<SitemapDocument> {
+        let mut document = SitemapDocument::default();
+        let mut path: Vec<String> = Vec::new();
+        let mut text = String::new();
 
         let mut current_url: Option<String> = None;
         let mut current_lastmod: Option<DateTime<Utc>> = None;
         let mut current_changefreq: Option<String> = None;
         let mut current_priority: Option<f64> = None;
 
-        for event in parser {
-            match event {
-                Ok(XmlEvent::StartElement { name, .. }) => {
-                    if name.local_name.as_str() == "url" {
+        for event in EventReader::from_str(content) {
+            match event.context("Malformed sitemap XML")? {
+                XmlEvent::StartElement { name, .. } => {
+                    if name.local_name == "url" {
                         // Reset current entry
                         current_url = None;
                         current_lastmod = None;
                         current_changefreq = None;
                         current_priority = None;
                     }
+                    path.push(name.local_name);
+                    text.clear();
                 }
-                Ok(XmlEvent::Characters(data)) => {
-                    // This is a simplified parser - in reality we'd need to track element context
-                    if data.starts_with("http") {
-                        current_url = Some(data);
-                    }
+                XmlEvent::Characters(data) | XmlEvent::CData(data) => {
+                    text.push_str(&data);
                 }
-                Ok(XmlEvent::EndElement { name }) => {
-                    if name.local_name == "url" {
-                        if let Some(url_str) = &current_url {
-                            if let Ok(url) = Url::parse(url_str) {
-                                // Apply filters
-                                if let Some(max_urls) = self.config.max_urls {
-                                    if entries.len() >= max_urls {
-                                        break;
-                                    }
-                                }
-
-                                if let Some(priority) = current_priority {
-                                    if priority < self.config.min_priority {
-                                        continue;
-                                    }
-                                }
-
-                                entries.push(SitemapEntry {
+                XmlEvent::EndElement { name } => {
+                    let parent = path.len().checked_sub(2).map(|i| path[i].as_str());
+                    let value = text.trim();
+                    match (parent, name.local_name.as_str()) {
+                        (Some("url"), "loc") => current_url = Some(value.to_string()),
+                        (Some("url"), "lastmod") => current_lastmod = parse_lastmod(value),
+                        (Some("url"), "changefreq") if !value.is_empty() => {
+                            current_changefreq = Some(value.to_ascii_lowercase());
+                        }
+                        (Some("url"), "priority") => {
+                            current_priority = value
+                                .parse::<f64>()
+                                .ok()
+                                .filter(|p| p.is_finite())
+                                .map(|p| p.clamp(0.0, 1.0));
+                        }
+                        (Some("sitemap"), "loc") if is_http_url(value) => {
+                            document.sitemaps.push(value.to_string());
+                        }
+                        (_, "url") => {
+                            let url = current_url.take().and_then(|u| Url::parse(&u).ok());
+                            let below_threshold = current_priority
+                                .is_some_and(|priority| priority < self.config.min_priority);
+                            if let Some(url) = url.filter(|_| !below_threshold) {
+                                document.entries.push(SitemapEntry {
                                     url,
                                     last_modified: current_lastmod,
-                                    change_frequency: current_changefreq.clone(),
+                                    change_frequency: current_changefreq.take(),
                                     priority: current_priority,
                                 });
                             }
                         }
+                        _ => {}
                     }
+                    path.pop();
+                    text.clear();
                 }
                 _ => {}
             }
         }
 
-        info!("Parsed {} entries from sitemap", entries.len());
-        Ok(entries)
+        Ok(document)
+    }
+
+    /// Fetch a sitemap, decompressing gzipped sitemaps
+    async fn fetch_document(&self, sitemap_url: &str) -> Result<SitemapDocument> {
+        debug!("Parsing sitemap: {}", sitemap_url);
+
+        let response = self
+            .client
+            .get(sitemap_url)
+            .await
+            .context("Failed to fetch sitemap")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Sitemap returned HTTP {}", response.status());
+        }
+
+        // Plain sitemaps are held to the same cap as decompressed ones
+        let limits = BodyLimits::default()
+            .with_max_content_length(MAX_SITEMAP_BYTES)
+            .with_allowed_content_types(Vec::<String>::new());
+        let body = BodyStream::new(response, limits)
+            .await
+            .context("Failed to read sitemap content")?
+            .collect()
+            .await
+            .context("Failed to read sitemap content")?;
+        let content = decode_sitemap(&body)?;
+
+        self.parse_document(&content)
+    }
+
+    /// Collect the entries of a sitemap and, breadth first, of the sitemaps
+    /// it indexes
+    async fn traverse(&self, sitemap_url: &str, traversal: &mut Traversal) -> Result<()> {
+        let mut pending = VecDeque::from([(sitemap_url.to_string(), 0usize)]);
+
+        while let Some((url, depth)) = pending.pop_front() {
+            if traversal.is_full() {
+                debug!(limit = traversal.limit, "Sitemap URL limit reached");
+                break;
+            }
+            if !traversal.visited.insert(url.clone()) {
+                continue;
+            }
+
+            let document = match self.fetch_document(&url).await {
+                Ok(document) => document,
+                Err(e) if depth == 0 => return Err(e),
+                Err(e) => {
+                    warn!("Failed to parse sitemap {}: {}", url, e);
+                    continue;
+                }
+            };
+
+            debug!(
+                sitemap = %url,
+                entries = document.entries.len(),
+                children = document.sitemaps.len(),
+                "Parsed sitemap"
+            );
+            traversal.extend(document.entries);
+
+            if document.sitemaps.is_empty() {
+                continue;
+            }
+            if !self.config.follow_sitemap_index {
+                debug!("Not following sitemap index {}", url);
+            } else if depth >= self.config.max_index_depth {
+                warn!(
+                    "Sitemap index {} exceeds max depth {}, skipping {} child sitemap(s)",
+                    url,
+                    self.config.max_index_depth,
+                    document.sitemaps.len()
+                );
+            } else {
+                pending.extend(document.sitemaps.into_iter().map(|s| (s, depth + 1)));
+            }
+        }
+
+        Ok(())
     }
 
     /// Discover sitemaps for a domain
@@ -176,6 +338,15 @@ impl SitemapParser {
     /// Parse robots.txt for sitemap directives
     /// Returns sitemap URLs found in robots.txt per RFC 9309
     async fn parse_robots_txt_sitemaps(&self, base_url: &Url) -> Result<Vec<String>> {
+        if let Some(service) = &self.robots_service {
+            let file = service.get(base_url).await?;
+            info!(
+                "Found {} sitemap(s) in robots.txt",
+                file.rules.sitemaps().len()
+            );
+            return Ok(file.rules.sitemaps().to_vec());
+        }
+
         let robots_url = format!("{}/robots.txt", base_url.origin().ascii_serialization());
         debug!("Checking robots.txt at: {}", robots_url);
 
@@ -234,27 +405,19 @@ impl SitemapParser {
     /// Parse all discovered sitemaps and return unique URLs
     pub async fn parse_all_sitemaps(&self, base_url: &Url) -> Result<Vec<SitemapEntry>> {
         let sitemap_urls = self.discover_sitemaps(base_url).await?;
-        let mut all_entries = Vec::new();
-        let mut seen_urls = HashSet::new();
+        let mut traversal = Traversal::new(self.config.max_urls);
 
         for sitemap_url in sitemap_urls {
-            match self.parse_sitemap(&sitemap_url).await {
-                Ok(entries) => {
-                    for entry in entries {
-                        let url_str = entry.url.to_string();
-                        if seen_urls.insert(url_str) {
-                            all_entries.push(entry);
-                        }
-                    }
-                }
-                Err(e) => {
-                    warn!("Failed to parse sitemap {}: {}", sitemap_url, e);
-                }
+            if let Err(e) = self.traverse(&sitemap_url, &mut traversal).await {
+                warn!("Failed to parse sitemap {}: {}", sitemap_url, e);
             }
         }
 
-        info!("Discovered {} unique URLs from sitemaps", all_entries.len());
-        Ok(all_entries)
+        info!(
+            "Discovered {} unique URLs from sitemaps",
+            traversal.entries.len()
+        );
+        Ok(traversal.entries)
     }
 
     /// Discover sitemaps and parse them, returning all entries
@@ -262,7 +425,7 @@ impl SitemapParser {
         debug!("Discovering and parsing sitemaps for: {}", base_url);
 
         let sitemap_urls = self.discover_sitemaps(base_url).await?;
-        let mut all_entries = Vec::new();
+        let mut traversal = Traversal::new(self.config.max_urls);
 
         for sitemap_url in sitemap_urls {
             // Check cache to avoid re-parsing the same sitemap
@@ -271,14 +434,8 @@ impl SitemapParser {
                 continue;
             }
 
-            match self.parse_sitemap(&sitemap_url).await {
-                Ok(entries) => {
-                    debug!(
-                        "Parsed {} entries from sitemap: {}",
-                        entries.len(),
-                        sitemap_url
-                    );
-                    all_entries.extend(entries);
+            match self.traverse(&sitemap_url, &mut traversal).await {
+                Ok(()) => {
                     // Add to cache after successful parsing
                     self.cache.insert(sitemap_url);
                 }
@@ -288,18 +445,11 @@ impl SitemapParser {
             }
         }
 
-        // Remove duplicates based on URL
-        let mut seen_urls = HashSet::new();
-        all_entries.retain(|entry| {
-            let url_str = entry.url.to_string();
-            seen_urls.insert(url_str)
-        });
-
         info!(
             "Discovered and parsed {} unique URLs from sitemaps",
-            all_entries.len()
+            traversal.entries.len()
         );
-        Ok(all_entries)
+        Ok(traversal.entries)
     }
 
     /// Convert sitemap entries to crawl requests with appropriate priority and metadata
@@ -356,3 +506,150 @@ impl SitemapParser {
         self.cache.clear();
     }
 }
+
+/// Decode a sitemap body, decompressing it when gzipped
+///
+/// Gzip is detected from the magic bytes rather than the URL, since servers
+/// may or may not decode `.xml.gz` files themselves.
+fn decode_sitemap(body: &[u8]) -> Result<String> {
+    let mut content = String::new();
+    if body.starts_with(&[0x1f, 0x8b]) {
+        MultiGzDecoder::new(body)
+            .take(MAX_SITEMAP_BYTES + 1)
+            .read_to_string(&mut content)
+            .context("Failed to decompress gzipped sitemap")?;
+    } else {
+        content = String::from_utf8_lossy(body).into_owned();
+    }
+
+    if content.len() as u64 > MAX_SITEMAP_BYTES {
+        anyhow::bail!("Sitemap exceeds {} bytes uncompressed", MAX_SITEMAP_BYTES);
+    }
+    Ok(content)
+}
+
+/// Parse a W3C datetime `<lastmod>` value (full timestamp or date only)
+fn parse_lastmod(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Some(ts.with_timezone(&Utc));
+    }
+    // W3C datetime allows omitting seconds
+    if let Ok(ts) = DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M%:z") {
+        return Some(ts.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|ts| ts.and_utc())
+}
+
+fn is_http_url(value: &str) -> bool {
+    Url::parse(value).is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+
+    fn parser(config: SitemapConfig) -> SitemapParser {
+        SitemapParser::new(config)
+    }
+
+    const URLSET: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9"
+        xmlns:image="http://www.google.com/schemas/sitemap-image/1.1">
+  <url>
+    <loc>https://example.com/a</loc>
+    <lastmod>2024-03-01</lastmod>
+    <changefreq>Daily</changefreq>
+    <priority>0.9</priority>
+    <image:image><image:loc>https://cdn.example.com/a.jpg</image:loc></image:image>
+  </url>
+  <url>
+    <loc><![CDATA[https://example.com/b]]></loc>
+    <lastmod>2024-03-02T10:30:00+02:00</lastmod>
+    <priority>0.2</priority>
+  </url>
+</urlset>"#;
+
+    #[test]
+    fn test_parse_urlset_fields() {
+        let document = parser(SitemapConfig::default())
+            .parse_document(URLSET)
+            .unwrap();
+        assert!(document.sitemaps.is_empty());
+        assert_eq!(document.entries.len(), 2);
+
+        let a = &document.entries[0];
+        assert_eq!(a.url.as_str(), "https://example.com/a");
+        assert_eq!(a.change_frequency.as_deref(), Some("daily"));
+        assert_eq!(a.priority, Some(0.9));
+        assert_eq!(
+            a.last_modified.unwrap().to_rfc3339(),
+            "2024-03-01T00:00:00+00:00"
+        );
+
+        let b = &document.entries[1];
+        assert_eq!(b.url.as_str(), "https://example.com/b");
+        assert_eq!(
+            b.last_modified.unwrap().to_rfc3339(),
+            "2024-03-02T08:30:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_sitemap_index() {
+        let index = r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+  <sitemap><loc>https://example.com/news-1.xml.gz</loc><lastmod>2024-03-01</lastmod></sitemap>
+  <sitemap><loc>https://example.com/news-2.xml</loc></sitemap>
+  <sitemap><loc>ftp://example.com/ignored.xml</loc></sitemap>
+</sitemapindex>"#;
+        let document = parser(SitemapConfig::default())
+            .parse_document(index)
+            .unwrap();
+        assert!(document.entries.is_empty());
+        assert_eq!(
+            document.sitemaps,
+            vec![
+                "https://example.com/news-1.xml.gz",
+                "https://example.com/news-2.xml"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_min_priority_filter() {
+        let config = SitemapConfig {
+            min_priority: 0.5,
+            ..Default::default()
+        };
+        let document = parser(config).parse_document(URLSET).unwrap();
+        assert_eq!(document.entries.len(), 1);
+        assert_eq!(document.entries[0].url.as_str(), "https://example.com/a");
+    }
+
+    #[test]
+    fn test_decode_gzipped_sitemap() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(URLSET.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        assert_eq!(decode_sitemap(&compressed).unwrap(), URLSET);
+        assert_eq!(decode_sitemap(URLSET.as_bytes()).unwrap(), URLSET);
+    }
+
+    #[test]
+    fn test_traversal_limit_and_dedup() {
+        let mut traversal = Traversal::new(Some(2));
+        let entries = parser(SitemapConfig::default())
+            .parse_document(URLSET)
+            .unwrap()
+            .entries;
+        traversal.extend(entries.clone());
+        traversal.extend(entries);
+        assert_eq!(traversal.entries.len(), 2);
+        assert!(traversal.is_full());
+    }
+}
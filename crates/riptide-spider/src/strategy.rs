@@ -1,5 +1,6 @@
 use crate::types::{CrawlRequest, Priority, ScoringConfig};
 use anyhow::Result;
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -128,6 +129,58 @@ impl StrategyContext {
     }
 }
 
+/// Request metadata key holding the text of the link a URL was found through
+pub const ANCHOR_TEXT_KEY: &str = "anchor_text";
+
+/// Request metadata key holding the best-first score of the linking page
+pub const PARENT_RELEVANCE_KEY: &str = "parent_relevance";
+
+/// What a [`UrlScorer`] knows about a discovered URL
+#[derive(Debug, Clone, Copy)]
+pub struct UrlScoringContext<'a> {
+    pub url: &'a Url,
+    /// Text of the link the URL was discovered through
+    pub anchor_text: Option<&'a str>,
+    /// Depth from the seed URL
+    pub depth: u32,
+    /// Best-first score of the linking page (`None` for seeds)
+    pub parent_relevance: Option<f64>,
+    /// Remaining request metadata
+    pub metadata: &'a HashMap<String, String>,
+}
+
+impl<'a> UrlScoringContext<'a> {
+    /// Scoring inputs recorded on a request
+    pub fn from_request(request: &'a CrawlRequest) -> Self {
+        Self {
+            url: &request.url,
+            anchor_text: request.metadata.get(ANCHOR_TEXT_KEY).map(String::as_str),
+            depth: request.depth,
+            parent_relevance: request
+                .metadata
+                .get(PARENT_RELEVANCE_KEY)
+                .and_then(|s| s.parse().ok()),
+            metadata: &request.metadata,
+        }
+    }
+}
+
+/// Pluggable URL scorer for the best-first strategy
+///
+/// Scores use the [`DefaultScoring`] scale: requests scoring above 2.0 are
+/// crawled at high priority, above 1.0 at medium, anything lower at low
+/// priority. Within the frontier, higher scores are always crawled first.
+/// Scorers may be slow (e.g. backed by an LLM); they run once per
+/// discovered URL that passes the crawl filters.
+#[async_trait]
+pub trait UrlScorer: Send + Sync {
+    /// Score a discovered URL
+    async fn score(&self, ctx: &UrlScoringContext<'_>) -> f64;
+
+    /// Scorer name for logs
+    fn name(&self) -> &str;
+}
+
 /// URL scoring function for best-first strategy
 pub trait ScoringFunction: Send + Sync {
     fn score_url(&self, url: &Url, depth: u32, metadata: &HashMap<String, String>) -> f64;
@@ -188,11 +241,25 @@ impl ScoringFunction for DefaultScoring {
     }
 }
 
+#[async_trait]
+impl UrlScorer for DefaultScoring {
+    async fn score(&self, ctx: &UrlScoringContext<'_>) -> f64 {
+        self.score_url(ctx.url, ctx.depth, ctx.metadata)
+    }
+
+    fn name(&self) -> &str {
+        "default"
+    }
+}
+
 /// Strategy engine that manages crawling strategy execution
 pub struct StrategyEngine {
     current_strategy: CrawlingStrategy,
     context: Arc<RwLock<StrategyContext>>,
-    scoring_function: Option<Arc<dyn ScoringFunction>>,
+    /// Scorer of the current strategy (best-first only)
+    url_scorer: Option<Arc<dyn UrlScorer>>,
+    /// User-registered scorer replacing [`DefaultScoring`]
+    custom_scorer: Option<Arc<dyn UrlScorer>>,
 }
 
 impl StrategyEngine {
@@ -202,21 +269,34 @@ impl StrategyEngine {
             ..Default::default()
         };
 
-        let scoring_function = match &strategy {
-            CrawlingStrategy::BestFirst { scoring_config } => {
-                Some(Arc::new(DefaultScoring::new(scoring_config.clone()))
-                    as Arc<dyn ScoringFunction>)
-            }
-            _ => None,
-        };
-
         Self {
+            url_scorer: scorer_for(&strategy, None),
             current_strategy: strategy,
             context: Arc::new(RwLock::new(context)),
-            scoring_function,
+            custom_scorer: None,
         }
     }
 
+    /// Score best-first URLs with `scorer` instead of [`DefaultScoring`]
+    ///
+    /// The scorer stays registered across adaptive strategy switches and is
+    /// used whenever the current strategy is best-first.
+    pub fn with_url_scorer(mut self, scorer: Arc<dyn UrlScorer>) -> Self {
+        self.url_scorer = scorer_for(&self.current_strategy, Some(&scorer));
+        self.custom_scorer = Some(scorer);
+        self
+    }
+
+    /// Best-first score of a request, `None` when the current strategy does not score URLs
+    pub async fn score_request(&self, request: &CrawlRequest) -> Option<f64> {
+        let scorer = self.url_scorer.as_ref()?;
+        let score = scorer
+            .score(&UrlScoringContext::from_request(request))
+            .await;
+        debug!(url = %request.url, scorer = scorer.name(), score, "Scored URL");
+        Some(score)
+    }
+
     /// Process requests according to the current strategy
     pub async fn process_requests(
         &mut self,
@@ -244,13 +324,11 @@ impl StrategyEngine {
                 }
                 CrawlingStrategy::BestFirst { scoring_config: _ } => {
                     // Best-First: calculate scores and sort by score
-                    if let Some(scoring_fn) = &self.scoring_function {
+                    if let Some(scorer) = &self.url_scorer {
                         for request in &mut requests {
-                            let score = scoring_fn.score_url(
-                                &request.url,
-                                request.depth,
-                                &request.metadata,
-                            );
+                            let score = scorer
+                                .score(&UrlScoringContext::from_request(request))
+                                .await;
                             request.score = Some(score);
                         }
                     }
@@ -327,13 +405,7 @@ impl StrategyEngine {
         let new_name = strategy_name(&new_strategy);
 
         // Update scoring function if needed
-        self.scoring_function = match &new_strategy {
-            CrawlingStrategy::BestFirst { scoring_config } => {
-                Some(Arc::new(DefaultScoring::new(scoring_config.clone()))
-                    as Arc<dyn ScoringFunction>)
-            }
-            _ => None,
-        };
+        self.url_scorer = scorer_for(&new_strategy, self.custom_scorer.as_ref());
 
         // Update context
         {
@@ -416,6 +488,21 @@ impl StrategyEngine {
     }
 }
 
+/// URL scorer for a strategy: the custom scorer if registered, else the default
+fn scorer_for(
+    strategy: &CrawlingStrategy,
+    custom: Option<&Arc<dyn UrlScorer>>,
+) -> Option<Arc<dyn UrlScorer>> {
+    match strategy {
+        CrawlingStrategy::BestFirst { scoring_config } => {
+            Some(custom.cloned().unwrap_or_else(|| {
+                Arc::new(DefaultScoring::new(scoring_config.clone())) as Arc<dyn UrlScorer>
+            }))
+        }
+        _ => None,
+    }
+}
+
 /// Get a human-readable name for a strategy
 fn strategy_name(strategy: &CrawlingStrategy) -> String {
     match strategy {
@@ -573,4 +660,67 @@ mod tests {
         assert_eq!(criteria.min_success_rate, 0.7);
         assert_eq!(criteria.min_pages_for_switch, 100);
     }
+
+    struct AnchorScorer;
+
+    #[async_trait]
+    impl UrlScorer for AnchorScorer {
+        async fn score(&self, ctx: &UrlScoringContext<'_>) -> f64 {
+            let anchor = match ctx.anchor_text {
+                Some(text) if text.contains("pricing") => 2.0,
+                _ => 0.0,
+            };
+            anchor + ctx.parent_relevance.unwrap_or(0.0) / 2.0
+        }
+
+        fn name(&self) -> &str {
+            "anchor"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_url_scorer() {
+        let mut strategy_engine =
+            StrategyEngine::new(best_first_strategy()).with_url_scorer(Arc::new(AnchorScorer));
+
+        let request = |path: &str, anchor: &str, parent_relevance: &str| {
+            CrawlRequest::new(Url::from_str(path).expect("Valid URL"))
+                .with_metadata(ANCHOR_TEXT_KEY.to_string(), anchor.to_string())
+                .with_metadata(
+                    PARENT_RELEVANCE_KEY.to_string(),
+                    parent_relevance.to_string(),
+                )
+        };
+        let requests = vec![
+            request("https://example.com/about", "About us", "1.0"),
+            request("https://example.com/p", "See pricing", "0.0"),
+            request("https://example.com/blog", "Blog", "3.0"),
+        ];
+
+        let scored = strategy_engine
+            .score_request(&requests[1])
+            .await
+            .expect("Best-first scores requests");
+        assert!((scored - 2.0).abs() < f64::EPSILON);
+
+        let processed = strategy_engine
+            .process_requests(requests)
+            .await
+            .expect("Processing should succeed");
+        assert_eq!(processed[0].url.path(), "/p");
+        assert_eq!(processed[1].url.path(), "/blog");
+        assert_eq!(processed[2].url.path(), "/about");
+        assert_eq!(
+            strategy_engine.calculate_priority(&processed[2]).await,
+            Priority::Low
+        );
+    }
+
+    #[tokio::test]
+    async fn test_non_scoring_strategy_ignores_scorer() {
+        let strategy_engine =
+            StrategyEngine::new(breadth_first_strategy()).with_url_scorer(Arc::new(AnchorScorer));
+        let request = CrawlRequest::new(Url::from_str("https://example.com/").expect("Valid URL"));
+        assert!(strategy_engine.score_request(&request).await.is_none());
+    }
 }
@@ -0,0 +1,106 @@
+//! Streaming crawl results.
+//!
+//! [`CrawlStream`] yields each successfully crawled page as an
+//! [`EnrichedCrawlResult`] while the crawl is still running, so long crawls
+//! can be forwarded to clients (NDJSON, SSE) instead of being collected
+//! until the end.
+//!
+//! Results pass through a bounded channel: when the consumer falls behind,
+//! the crawl waits before fetching the next page. Dropping the stream stops
+//! the crawl after the page in flight.
+
+use crate::core::SpiderResult;
+use crate::results::EnrichedCrawlResult;
+use anyhow::Result;
+use futures::Stream;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Pages buffered between the crawl and a slow consumer
+pub const DEFAULT_STREAM_BUFFER: usize = 32;
+
+/// Results of a running crawl, yielded as pages are crawled
+///
+/// The stream ends when the crawl stops; [`CrawlStream::finish`] then
+/// returns the crawl summary.
+pub struct CrawlStream {
+    results: mpsc::Receiver<EnrichedCrawlResult>,
+    crawl: JoinHandle<Result<SpiderResult>>,
+}
+
+impl CrawlStream {
+    /// Wrap a crawl task feeding `results`
+    pub fn new(
+        results: mpsc::Receiver<EnrichedCrawlResult>,
+        crawl: JoinHandle<Result<SpiderResult>>,
+    ) -> Self {
+        Self { results, crawl }
+    }
+
+    /// Wait for the crawl to complete and return its summary
+    ///
+    /// Results not yet consumed are discarded; the crawl itself runs to
+    /// completion.
+    pub async fn finish(mut self) -> Result<SpiderResult> {
+        while self.results.recv().await.is_some() {}
+        self.crawl
+            .await
+            .map_err(|e| anyhow::anyhow!("Crawl task failed: {}", e))?
+    }
+}
+
+impl Stream for CrawlStream {
+    type Item = EnrichedCrawlResult;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().results.poll_recv(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::RawCrawlResult;
+    use futures::StreamExt;
+    use http::{HeaderMap, StatusCode};
+    use url::Url;
+
+    fn page(url: &str) -> EnrichedCrawlResult {
+        EnrichedCrawlResult {
+            raw: RawCrawlResult {
+                url: Url::parse(url).unwrap(),
+                html: String::new(),
+                status: StatusCode::OK,
+                headers: HeaderMap::new(),
+            },
+            depth: 0,
+            extracted_urls: Vec::new(),
+            text_content: None,
+            content_check: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_crawl_stream_yields_results_then_finishes() {
+        let (tx, rx) = mpsc::channel(1);
+        let crawl = tokio::spawn(async move {
+            for url in ["https://example.com/", "https://example.com/a"] {
+                tx.send(page(url)).await?;
+            }
+            Err(anyhow::anyhow!("stopped"))
+        });
+        let mut stream = CrawlStream::new(rx, crawl);
+
+        let urls: Vec<String> = stream
+            .by_ref()
+            .map(|r| r.raw.url.to_string())
+            .collect()
+            .await;
+        assert_eq!(urls, vec!["https://example.com/", "https://example.com/a"]);
+
+        let err = stream.finish().await.unwrap_err();
+        assert_eq!(err.to_string(), "stopped");
+    }
+}
@@ -1,6 +1,7 @@
+use crate::content_dedup::ContentCheck;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::time::{Duration, Instant, SystemTime};
 use url::Url;
 
@@ -112,12 +113,21 @@ pub struct CrawlResult {
     pub text_content: Option<String>,
     /// Extracted URLs from the page
     pub extracted_urls: Vec<Url>,
+    /// Anchor text of extracted URLs whose links had any
+    pub anchor_texts: HashMap<Url, String>,
     /// Processing time
     pub processing_time: Duration,
     /// Error message if unsuccessful
     pub error: Option<String>,
     /// Custom metadata from processing
     pub metadata: HashMap<String, String>,
+    /// Near-duplicate and soft-404 flags, when content fingerprinting is enabled
+    pub content_check: Option<ContentCheck>,
+    /// Response body of a successful fetch, handed to the result stream
+    pub html: Option<String>,
+    /// Content unchanged since the crawl's `changed_since`; links are
+    /// followed but the page is not reported
+    pub unchanged: bool,
 }
 
 impl CrawlResult {
@@ -130,9 +140,13 @@ impl CrawlResult {
             content_size: 0,
             text_content: None,
             extracted_urls: Vec::new(),
+            anchor_texts: HashMap::new(),
             processing_time: Duration::from_millis(0),
             error: None,
             metadata: HashMap::new(),
+            content_check: None,
+            html: None,
+            unchanged: false,
         }
     }
 
@@ -145,9 +159,13 @@ impl CrawlResult {
             content_size: 0,
             text_content: None,
             extracted_urls: Vec::new(),
+            anchor_texts: HashMap::new(),
             processing_time: Duration::from_millis(0),
             error: Some(error),
             metadata: HashMap::new(),
+            content_check: None,
+            html: None,
+            unchanged: false,
         }
     }
 
@@ -319,6 +337,72 @@ impl FrontierMetrics {
     }
 }
 
+/// Why a discovered URL was not added to the frontier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectionReason {
+    /// Matched an exclusion pattern or file extension filter
+    Excluded,
+    /// Outside the crawl scope rules (`SpiderConfig::scope`)
+    OutOfScope,
+    /// Already seen during this crawl
+    Duplicate,
+    /// Crawl budget (depth, pages, per-host limits) exhausted
+    BudgetExceeded,
+    /// Host already has the maximum number of queued requests
+    HostLimit,
+    /// Frontier at its memory limit without disk spillover
+    MemoryLimit,
+    /// Denied by the crawl's URL policy, e.g. a tenant crawl policy
+    PolicyDenied,
+}
+
+/// A request waiting in the frontier
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingUrl {
+    pub url: String,
+    pub priority: Priority,
+    /// Best-first score, when the request was scored
+    pub score: Option<f64>,
+    pub depth: u32,
+    pub parent: Option<String>,
+}
+
+impl From<&CrawlRequest> for PendingUrl {
+    fn from(request: &CrawlRequest) -> Self {
+        Self {
+            url: request.url.to_string(),
+            priority: request.priority,
+            score: request.score,
+            depth: request.depth,
+            parent: request.parent.as_ref().map(|p| p.to_string()),
+        }
+    }
+}
+
+/// A URL that was not added to the frontier
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedUrl {
+    pub url: String,
+    pub depth: u32,
+    pub reason: RejectionReason,
+}
+
+/// Point-in-time view of the frontier queue for inspection
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FrontierSnapshot {
+    /// Total requests waiting in the frontier
+    pub total_pending: usize,
+    /// Next requests to be dequeued, in dequeue order
+    pub top_pending: Vec<PendingUrl>,
+    /// Pending request count per domain, keyed by depth
+    pub depth_by_domain: BTreeMap<String, BTreeMap<u32, usize>>,
+    /// Rejection counts since the frontier was created or cleared
+    pub rejections: BTreeMap<RejectionReason, u64>,
+    /// Most recent rejections, oldest first
+    pub recent_rejections: Vec<RejectedUrl>,
+}
+
 /// Configuration for URL scoring in best-first strategy
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScoringConfig {
@@ -363,7 +447,7 @@ impl Default for ScoringConfig {
 }
 
 /// Adaptive stop analysis window
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContentWindow {
     /// Sliding window of unique text character counts
     pub char_counts: Vec<usize>,
@@ -498,7 +582,9 @@ pub struct SitemapConfig {
     pub custom_sitemaps: Vec<String>,
     /// Follow sitemap index files
     pub follow_sitemap_index: bool,
-    /// Maximum URLs to extract from sitemaps
+    /// Maximum nesting depth of sitemap indexes to follow
+    pub max_index_depth: usize,
+    /// Maximum URLs to extract from all sitemaps of a site
     pub max_urls: Option<usize>,
     /// Respect lastmod dates in sitemaps
     pub respect_lastmod: bool,
@@ -516,6 +602,7 @@ impl Default for SitemapConfig {
             enable_discovery: true,
             custom_sitemaps: Vec::new(),
             follow_sitemap_index: true,
+            max_index_depth: 3,
             max_urls: Some(10000),
             respect_lastmod: false,
             min_priority: 0.0,
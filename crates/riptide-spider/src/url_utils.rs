@@ -132,6 +132,30 @@ impl BloomFilter {
         hasher.finish() as usize
     }
 
+    /// Bits packed eight per byte, least significant bit first
+    fn packed_bits(&self) -> Vec<u8> {
+        self.bits
+            .chunks(8)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0u8, |byte, (i, &bit)| byte | (u8::from(bit) << i))
+            })
+            .collect()
+    }
+
+    fn restore_packed_bits(&mut self, packed: &[u8], insertions: u64) -> Result<()> {
+        if packed.len() != self.bits.len().div_ceil(8) {
+            anyhow::bail!("Bloom filter size does not match the current configuration");
+        }
+        for (i, bit) in self.bits.iter_mut().enumerate() {
+            *bit = packed[i / 8] & (1 << (i % 8)) != 0;
+        }
+        self.insertions.store(insertions, Ordering::Relaxed);
+        Ok(())
+    }
+
     fn estimated_fpr(&self) -> f64 {
         let insertions = self.insertions.load(Ordering::Relaxed) as f64;
         if insertions == 0.0 {
@@ -429,6 +453,46 @@ impl UrlUtils {
         self.excluded_count.store(0, Ordering::Relaxed);
     }
 
+    /// Export the URLs seen so far, for crawl checkpoints
+    pub async fn export_visited(&self) -> VisitedUrls {
+        let exact_urls = self
+            .exact_urls
+            .as_ref()
+            .map(|urls| urls.iter().map(|entry| entry.key().clone()).collect())
+            .unwrap_or_default();
+        let bloom_guard = self.bloom_filter.lock().await;
+
+        VisitedUrls {
+            exact_urls,
+            bloom_bits: bloom_guard.as_ref().map(BloomFilter::packed_bits),
+            bloom_insertions: bloom_guard
+                .as_ref()
+                .map(|bloom| bloom.insertions.load(Ordering::Relaxed))
+                .unwrap_or(0),
+        }
+    }
+
+    /// Replace the seen URLs with a previously exported set
+    ///
+    /// Fails if the bloom filter capacity or false positive rate changed
+    /// since the export, as the stored bits would no longer be meaningful.
+    pub async fn restore_visited(&self, visited: VisitedUrls) -> Result<()> {
+        self.clear().await;
+
+        if let Some(ref exact_urls) = self.exact_urls {
+            for url in visited.exact_urls {
+                exact_urls.insert(url, ());
+            }
+        }
+
+        if let Some(bits) = visited.bloom_bits {
+            if let Some(ref mut bloom) = *self.bloom_filter.lock().await {
+                bloom.restore_packed_bits(&bits, visited.bloom_insertions)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Get configuration
     pub fn get_config(&self) -> &UrlUtilsConfig {
         &self.config
@@ -440,6 +504,17 @@ impl UrlUtils {
     }
 }
 
+/// URLs seen by [`UrlUtils`], as stored in crawl checkpoints
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VisitedUrls {
+    /// Exactly tracked normalized URLs
+    pub exact_urls: Vec<String>,
+    /// Bloom filter bits packed eight per byte, if the filter is enabled
+    pub bloom_bits: Option<Vec<u8>>,
+    /// Number of bloom filter insertions
+    pub bloom_insertions: u64,
+}
+
 /// Statistics about URL processing
 #[derive(Debug, Clone)]
 pub struct UrlUtilsStats {
@@ -579,4 +654,19 @@ mod tests {
         assert!(stats.excluded_count > 0); // CSS file should be excluded
         assert!(stats.duplicates_found > 0); // Duplicate should be detected
     }
+
+    #[tokio::test]
+    async fn test_export_and_restore_visited() {
+        let url_utils = UrlUtils::new(UrlUtilsConfig::default());
+        let seen = Url::from_str("https://example.com/seen").expect("Valid URL");
+        let unseen = Url::from_str("https://example.com/unseen").expect("Valid URL");
+        assert!(!url_utils.is_duplicate_and_mark(&seen).await.unwrap());
+
+        let visited = url_utils.export_visited().await;
+        let restored = UrlUtils::new(UrlUtilsConfig::default());
+        restored.restore_visited(visited).await.unwrap();
+
+        assert!(restored.is_duplicate_and_mark(&seen).await.unwrap());
+        assert!(!restored.is_duplicate_and_mark(&unseen).await.unwrap());
+    }
 }
@@ -406,7 +406,7 @@ pub enum ProxyRotation {
 }
 
 /// Proxy authentication
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ProxyAuth {
     /// Username
     pub username: String,
@@ -415,6 +415,16 @@ pub struct ProxyAuth {
     pub password: String,
 }
 
+impl std::fmt::Debug for ProxyAuth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Same mask as riptide_config::redaction::REDACTED (stealth stays dependency-free)
+        f.debug_struct("ProxyAuth")
+            .field("username", &self.username)
+            .field("password", &format_args!("***REDACTED***"))
+            .finish()
+    }
+}
+
 /// Timing configuration for request pacing
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TimingConfig {
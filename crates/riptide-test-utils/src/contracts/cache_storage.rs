@@ -7,13 +7,13 @@
 //! # Usage
 //!
 //! ```rust,ignore
-//! use riptide_types::ports::CacheStorage;
+//! use riptide_test_utils::contracts::cache_storage;
 //!
 //! #[tokio::test]
 //! async fn test_my_implementation() {
 //!     let cache = MyCache::new();
-//!     cache_storage_contract::test_basic_operations(&cache).await.unwrap();
-//!     cache_storage_contract::test_ttl_expiration(&cache).await.unwrap();
+//!     cache_storage::test_basic_operations(&cache).await.unwrap();
+//!     cache_storage::test_ttl_expiration(&cache).await.unwrap();
 //!     // ... run all contract tests
 //! }
 //! ```
@@ -0,0 +1,221 @@
+//! Contract tests for IdempotencyStore trait
+//!
+//! These tests pin down the lock semantics every idempotency backend must
+//! share: one holder per key, idempotent release, TTL expiry, and results
+//! that are only cached while the lock is held.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use riptide_test_utils::contracts::idempotency;
+//!
+//! #[tokio::test]
+//! async fn test_my_idempotency_store() {
+//!     let store = MyIdempotencyStore::new();
+//!     idempotency::run_all_tests(&store).await.unwrap();
+//! }
+//! ```
+
+use riptide_types::error::Result as RiptideResult;
+use riptide_types::ports::IdempotencyStore;
+use std::time::Duration;
+
+const LOCK_TTL: Duration = Duration::from_secs(60);
+
+/// Test acquire/release lifecycle
+///
+/// Validates:
+/// - A free key can be acquired and then exists
+/// - A held key cannot be acquired a second time
+/// - A released key no longer exists and can be acquired again
+pub async fn test_acquire_release<S: IdempotencyStore>(store: &S) -> RiptideResult<()> {
+    let key = "contract_idem_lifecycle";
+
+    assert!(!store.exists(key).await?, "Key should not exist initially");
+
+    let token = store.try_acquire(key, LOCK_TTL).await?;
+    assert!(store.exists(key).await?, "Key should exist while held");
+
+    assert!(
+        store.try_acquire(key, LOCK_TTL).await.is_err(),
+        "Held key must not be acquired twice"
+    );
+
+    store.release(token).await?;
+    assert!(
+        !store.exists(key).await?,
+        "Key should not exist after release"
+    );
+
+    let token = store.try_acquire(key, LOCK_TTL).await?;
+    store.release(token).await?;
+
+    Ok(())
+}
+
+/// Test that release is idempotent
+///
+/// Validates:
+/// - Releasing the same token twice is not an error
+/// - Keys are independent of each other
+pub async fn test_release_idempotent<S: IdempotencyStore>(store: &S) -> RiptideResult<()> {
+    let first = store
+        .try_acquire("contract_idem_release_a", LOCK_TTL)
+        .await?;
+    let second = store
+        .try_acquire("contract_idem_release_b", LOCK_TTL)
+        .await?;
+
+    store.release(first.clone()).await?;
+    store.release(first).await?;
+
+    assert!(
+        store.exists("contract_idem_release_b").await?,
+        "Releasing one key must not release another"
+    );
+    store.release(second).await?;
+
+    Ok(())
+}
+
+/// Test TTL reporting
+///
+/// Validates:
+/// - A held key reports a remaining TTL no longer than requested
+/// - A missing key reports no TTL
+pub async fn test_ttl<S: IdempotencyStore>(store: &S) -> RiptideResult<()> {
+    let key = "contract_idem_ttl";
+    let token = store.try_acquire(key, LOCK_TTL).await?;
+
+    let remaining = store.ttl(key).await?;
+    assert!(
+        matches!(remaining, Some(d) if !d.is_zero() && d <= LOCK_TTL),
+        "Held key should report 0 < ttl <= {:?}, got {:?}",
+        LOCK_TTL,
+        remaining
+    );
+
+    store.release(token).await?;
+    assert_eq!(
+        store.ttl(key).await?,
+        None,
+        "Missing key should report no TTL"
+    );
+
+    Ok(())
+}
+
+/// Test lock expiration
+///
+/// Validates:
+/// - A lock disappears once its TTL has passed
+/// - An expired key can be acquired again
+pub async fn test_expiration<S: IdempotencyStore>(store: &S) -> RiptideResult<()> {
+    let key = "contract_idem_expiry";
+    store.try_acquire(key, Duration::from_secs(1)).await?;
+
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+
+    assert!(!store.exists(key).await?, "Lock should expire after TTL");
+    let token = store.try_acquire(key, LOCK_TTL).await?;
+    store.release(token).await?;
+
+    Ok(())
+}
+
+/// Test result caching
+///
+/// Validates:
+/// - A result stored under a held key can be read back
+/// - Unknown keys have no result
+/// - Storing a result for a key that was never acquired does not make it
+///   readable (backends may report this as an error or ignore it)
+pub async fn test_result_caching<S: IdempotencyStore>(store: &S) -> RiptideResult<()> {
+    let key = "contract_idem_result";
+    let token = store.try_acquire(key, LOCK_TTL).await?;
+
+    store
+        .store_result(key, b"cached response", LOCK_TTL)
+        .await?;
+    assert_eq!(
+        store.get_result(key).await?,
+        Some(b"cached response".to_vec()),
+        "Stored result should be readable while the lock is held"
+    );
+    store.release(token).await?;
+
+    assert_eq!(
+        store.get_result("contract_idem_result_unknown").await?,
+        None,
+        "Unknown key should have no result"
+    );
+
+    let orphan = "contract_idem_result_orphan";
+    let _ = store.store_result(orphan, b"orphan", LOCK_TTL).await;
+    assert_eq!(
+        store.get_result(orphan).await?,
+        None,
+        "Result must not be stored without a held lock"
+    );
+
+    Ok(())
+}
+
+/// Test concurrent acquisition
+///
+/// Validates:
+/// - Exactly one of several simultaneous acquisitions of a key succeeds
+pub async fn test_concurrent_acquire<S: IdempotencyStore>(store: &S) -> RiptideResult<()> {
+    let key = "contract_idem_race";
+
+    let attempts = tokio::join!(
+        store.try_acquire(key, LOCK_TTL),
+        store.try_acquire(key, LOCK_TTL),
+        store.try_acquire(key, LOCK_TTL),
+        store.try_acquire(key, LOCK_TTL),
+    );
+    let mut winners: Vec<_> = [attempts.0, attempts.1, attempts.2, attempts.3]
+        .into_iter()
+        .filter_map(Result::ok)
+        .collect();
+
+    assert_eq!(winners.len(), 1, "Exactly one acquisition should succeed");
+    store.release(winners.remove(0)).await?;
+
+    Ok(())
+}
+
+/// Test cleanup of expired keys
+///
+/// Validates:
+/// - cleanup_expired succeeds and leaves held keys alone
+pub async fn test_cleanup_keeps_held_keys<S: IdempotencyStore>(store: &S) -> RiptideResult<()> {
+    let key = "contract_idem_cleanup";
+    let token = store.try_acquire(key, LOCK_TTL).await?;
+
+    store.cleanup_expired().await?;
+    assert!(
+        store.exists(key).await?,
+        "Cleanup must not remove held keys"
+    );
+
+    store.release(token).await?;
+
+    Ok(())
+}
+
+/// Run all contract tests
+///
+/// This is a convenience function that runs all contract tests in sequence.
+/// Use individual test functions for more granular control.
+pub async fn run_all_tests<S: IdempotencyStore>(store: &S) -> RiptideResult<()> {
+    test_acquire_release(store).await?;
+    test_release_idempotent(store).await?;
+    test_ttl(store).await?;
+    test_expiration(store).await?;
+    test_result_caching(store).await?;
+    test_concurrent_acquire(store).await?;
+    test_cleanup_keeps_held_keys(store).await?;
+
+    Ok(())
+}
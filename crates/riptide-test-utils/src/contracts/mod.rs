@@ -0,0 +1,25 @@
+//! Reusable contract suites for port implementations
+//!
+//! Each module holds generic async test functions parameterized by a port
+//! trait, plus a `run_all_tests()` that runs them in sequence. Every
+//! implementation of a port — in-memory fakes as well as the Redis and
+//! PostgreSQL adapters — runs the same suite, so a fake that drifts from the
+//! real adapter's behavior fails the same test the adapter passes.
+//!
+//! ```rust,ignore
+//! use riptide_test_utils::contracts::cache_storage;
+//!
+//! #[tokio::test]
+//! async fn redis_storage_honours_cache_contract() {
+//!     let cache = RedisStorage::new(&redis_url).await.unwrap();
+//!     cache_storage::run_all_tests(&cache).await.unwrap();
+//! }
+//! ```
+//!
+//! The suites use fixed keys and IDs, so run them against a fresh instance
+//! (or, for repositories, a table that the suite may share with other data).
+
+pub mod cache_storage;
+pub mod idempotency;
+pub mod repository;
+pub mod session_storage;
@@ -0,0 +1,259 @@
+//! Contract tests for Repository trait
+//!
+//! These tests run against a `Repository<ContractEntity>` and validate CRUD,
+//! upsert, field filters, sorting and pagination. Filter values and sort
+//! keys are compared as JSON values, so numbers filter and sort numerically.
+//!
+//! Each test writes entities under its own `group` and only queries that
+//! group, so the suite can share a table with other data.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use riptide_test_utils::contracts::repository::{self, ContractEntity};
+//!
+//! #[tokio::test]
+//! async fn test_my_repository() {
+//!     let repo: MyRepository<ContractEntity> = MyRepository::new();
+//!     repository::run_all_tests(&repo).await.unwrap();
+//! }
+//! ```
+
+use riptide_types::error::Result as RiptideResult;
+use riptide_types::ports::{Repository, RepositoryFilter};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Entity stored by the repository contract
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractEntity {
+    pub id: String,
+    pub group: String,
+    pub rank: i64,
+}
+
+impl ContractEntity {
+    pub fn new(id: impl Into<String>, group: impl Into<String>, rank: i64) -> Self {
+        Self {
+            id: id.into(),
+            group: group.into(),
+            rank,
+        }
+    }
+}
+
+fn in_group(group: &str) -> RepositoryFilter {
+    RepositoryFilter::new().with_field("group", json!(group))
+}
+
+fn ids(entities: &[ContractEntity]) -> Vec<&str> {
+    entities.iter().map(|e| e.id.as_str()).collect()
+}
+
+async fn delete_all<R: Repository<ContractEntity>>(
+    repo: &R,
+    entities: &[ContractEntity],
+) -> RiptideResult<()> {
+    for entity in entities {
+        repo.delete(&entity.id).await?;
+    }
+    Ok(())
+}
+
+/// Test basic CRUD operations
+///
+/// Validates:
+/// - Missing entities return None and do not exist
+/// - Saved entities round-trip unchanged
+/// - Deleted entities are gone, and deleting again is not an error
+pub async fn test_crud_operations<R: Repository<ContractEntity>>(repo: &R) -> RiptideResult<()> {
+    let entity = ContractEntity::new("contract-crud-1", "contract-crud", 1);
+
+    assert!(
+        repo.find_by_id(&entity.id).await?.is_none(),
+        "Entity should not exist initially"
+    );
+    assert!(!repo.exists(&entity.id).await?);
+
+    repo.save(&entity).await?;
+    assert_eq!(
+        repo.find_by_id(&entity.id).await?,
+        Some(entity.clone()),
+        "Saved entity should round-trip"
+    );
+    assert!(repo.exists(&entity.id).await?, "Saved entity should exist");
+
+    repo.delete(&entity.id).await?;
+    assert!(
+        repo.find_by_id(&entity.id).await?.is_none(),
+        "Deleted entity should be gone"
+    );
+    assert!(!repo.exists(&entity.id).await?);
+
+    repo.delete(&entity.id).await?;
+
+    Ok(())
+}
+
+/// Test that save is an upsert
+///
+/// Validates:
+/// - Saving an existing ID replaces the entity instead of duplicating it
+pub async fn test_save_upserts<R: Repository<ContractEntity>>(repo: &R) -> RiptideResult<()> {
+    let group = "contract-upsert";
+    let original = ContractEntity::new("contract-upsert-1", group, 1);
+    let updated = ContractEntity::new("contract-upsert-1", group, 2);
+
+    repo.save(&original).await?;
+    repo.save(&updated).await?;
+
+    assert_eq!(repo.find_by_id(&original.id).await?, Some(updated.clone()));
+    assert_eq!(
+        repo.count(in_group(group)).await?,
+        1,
+        "Upsert must not create a second entity"
+    );
+
+    delete_all(repo, &[updated]).await
+}
+
+/// Test field equality filters
+///
+/// Validates:
+/// - find_all and count only return entities matching every field filter
+/// - Numeric fields match numerically
+/// - A filter matching nothing returns an empty result
+pub async fn test_field_filters<R: Repository<ContractEntity>>(repo: &R) -> RiptideResult<()> {
+    let group = "contract-filter";
+    let entities = vec![
+        ContractEntity::new("contract-filter-1", group, 1),
+        ContractEntity::new("contract-filter-2", group, 2),
+        ContractEntity::new("contract-filter-3", group, 2),
+        ContractEntity::new("contract-filter-4", "contract-filter-other", 2),
+    ];
+    for entity in &entities {
+        repo.save(entity).await?;
+    }
+
+    let mut found = repo.find_all(in_group(group)).await?;
+    found.sort_by(|a, b| a.id.cmp(&b.id));
+    assert_eq!(
+        ids(&found),
+        [
+            "contract-filter-1",
+            "contract-filter-2",
+            "contract-filter-3"
+        ],
+        "Group filter should match exactly the group"
+    );
+    assert_eq!(repo.count(in_group(group)).await?, 3);
+
+    let rank_two = in_group(group).with_field("rank", json!(2));
+    let mut found = repo.find_all(rank_two.clone()).await?;
+    found.sort_by(|a, b| a.id.cmp(&b.id));
+    assert_eq!(
+        ids(&found),
+        ["contract-filter-2", "contract-filter-3"],
+        "Field filters should combine with AND"
+    );
+    assert_eq!(repo.count(rank_two).await?, 2);
+
+    let nothing = in_group(group).with_field("rank", json!(99));
+    assert!(repo.find_all(nothing.clone()).await?.is_empty());
+    assert_eq!(repo.count(nothing).await?, 0);
+
+    delete_all(repo, &entities).await
+}
+
+/// Test sorting
+///
+/// Validates:
+/// - Ascending and descending sorts order by field value
+/// - Numbers sort numerically (2 before 10)
+pub async fn test_sorting<R: Repository<ContractEntity>>(repo: &R) -> RiptideResult<()> {
+    let group = "contract-sort";
+    let entities = vec![
+        ContractEntity::new("contract-sort-10", group, 10),
+        ContractEntity::new("contract-sort-2", group, 2),
+        ContractEntity::new("contract-sort-1", group, 1),
+    ];
+    for entity in &entities {
+        repo.save(entity).await?;
+    }
+
+    let ascending = repo
+        .find_all(in_group(group).with_sort("rank", true))
+        .await?;
+    assert_eq!(
+        ids(&ascending),
+        ["contract-sort-1", "contract-sort-2", "contract-sort-10"],
+        "Ascending sort should order numerically"
+    );
+
+    let descending = repo
+        .find_all(in_group(group).with_sort("rank", false))
+        .await?;
+    assert_eq!(
+        ids(&descending),
+        ["contract-sort-10", "contract-sort-2", "contract-sort-1"],
+        "Descending sort should reverse the order"
+    );
+
+    delete_all(repo, &entities).await
+}
+
+/// Test pagination
+///
+/// Validates:
+/// - offset and limit select a window of the sorted results
+/// - An offset past the end returns nothing
+/// - count ignores offset and limit
+pub async fn test_pagination<R: Repository<ContractEntity>>(repo: &R) -> RiptideResult<()> {
+    let group = "contract-page";
+    let entities: Vec<_> = (1..=5)
+        .map(|rank| ContractEntity::new(format!("contract-page-{}", rank), group, rank))
+        .collect();
+    for entity in &entities {
+        repo.save(entity).await?;
+    }
+
+    let sorted = in_group(group).with_sort("rank", true);
+
+    let first = repo.find_all(sorted.clone().with_limit(2)).await?;
+    assert_eq!(ids(&first), ["contract-page-1", "contract-page-2"]);
+
+    let middle = repo
+        .find_all(sorted.clone().with_offset(2).with_limit(2))
+        .await?;
+    assert_eq!(ids(&middle), ["contract-page-3", "contract-page-4"]);
+
+    let tail = repo.find_all(sorted.clone().with_offset(4)).await?;
+    assert_eq!(ids(&tail), ["contract-page-5"]);
+
+    assert!(repo
+        .find_all(sorted.clone().with_offset(10))
+        .await?
+        .is_empty());
+
+    assert_eq!(
+        repo.count(sorted.with_offset(3).with_limit(1)).await?,
+        5,
+        "count should ignore offset and limit"
+    );
+
+    delete_all(repo, &entities).await
+}
+
+/// Run all contract tests
+///
+/// This is a convenience function that runs all contract tests in sequence.
+/// Use individual test functions for more granular control.
+pub async fn run_all_tests<R: Repository<ContractEntity>>(repo: &R) -> RiptideResult<()> {
+    test_crud_operations(repo).await?;
+    test_save_upserts(repo).await?;
+    test_field_filters(repo).await?;
+    test_sorting(repo).await?;
+    test_pagination(repo).await?;
+
+    Ok(())
+}
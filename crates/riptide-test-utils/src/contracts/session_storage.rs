@@ -7,13 +7,13 @@
 //! # Usage
 //!
 //! ```rust,ignore
-//! use riptide_types::ports::SessionStorage;
+//! use riptide_test_utils::contracts::session_storage;
 //!
 //! #[tokio::test]
 //! async fn test_my_session_storage() {
 //!     let storage = MySessionStorage::new();
-//!     session_storage_contract::test_crud_operations(&storage).await.unwrap();
-//!     session_storage_contract::test_multi_tenancy(&storage).await.unwrap();
+//!     session_storage::test_crud_operations(&storage).await.unwrap();
+//!     session_storage::test_multi_tenancy(&storage).await.unwrap();
 //!     // ... run all contract tests
 //! }
 //! ```
@@ -0,0 +1,123 @@
+//! In-memory test doubles for every port
+//!
+//! One import for facade tests that should run without Redis, Chrome or
+//! other infrastructure. The doubles live next to their ports in
+//! `riptide_types::ports` and are re-exported here, together with
+//! [`FakeBlobStorage`], which adds failure injection to the in-memory blob
+//! store.
+//!
+//! ```rust,ignore
+//! use riptide_test_utils::fakes::*;
+//!
+//! let browser = FakeBrowserDriver::new().with_page("https://example.com", "<h1>Hi</h1>");
+//! let events = RecordingEventBus::new();
+//! // ... run the facade, then assert on events.published()
+//! ```
+
+use async_trait::async_trait;
+use riptide_types::error::{Result, RiptideError};
+use riptide_types::ports::{BlobStorage, StoredBlob};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+pub use riptide_types::ports::{
+    DeterministicEntropy, FakeBrowserDriver, FakeClock, FakeMessageQueue, FakeSearchEngine,
+    InMemoryBlobStorage, InMemoryCache, InMemoryCheckpointStore, InMemoryFeatureFlags,
+    InMemoryGeoIp, InMemoryIdempotencyStore, InMemorySessionProvider, InMemorySessionStorage,
+    RecordingEventBus,
+};
+
+/// Blob storage that can be made to fail
+///
+/// Behaves like [`InMemoryBlobStorage`] until [`fail_with`](Self::fail_with)
+/// is called; every operation then fails with that message until
+/// [`recover`](Self::recover).
+#[derive(Clone, Default)]
+pub struct FakeBlobStorage {
+    inner: InMemoryBlobStorage,
+    failure: Arc<Mutex<Option<String>>>,
+    operations: Arc<Mutex<Vec<String>>>,
+}
+
+impl FakeBlobStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fail every following operation with `message`
+    pub fn fail_with(&self, message: impl Into<String>) {
+        *lock(&self.failure) = Some(message.into());
+    }
+
+    /// Stop failing
+    pub fn recover(&self) {
+        *lock(&self.failure) = None;
+    }
+
+    /// Operations attempted so far, as `put:<key>`, `get:<key>` or
+    /// `delete:<key>`
+    pub fn operations(&self) -> Vec<String> {
+        lock(&self.operations).clone()
+    }
+
+    /// Stored blobs
+    pub fn inner(&self) -> &InMemoryBlobStorage {
+        &self.inner
+    }
+
+    fn attempt(&self, operation: &str, key: &str) -> Result<()> {
+        lock(&self.operations).push(format!("{}:{}", operation, key));
+        match lock(&self.failure).as_ref() {
+            Some(message) => Err(RiptideError::Storage(message.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl BlobStorage for FakeBlobStorage {
+    async fn put(&self, key: &str, data: &[u8], content_type: &str) -> Result<StoredBlob> {
+        self.attempt("put", key)?;
+        self.inner.put(key, data, content_type).await
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<(Vec<u8>, String)>> {
+        self.attempt("get", key)?;
+        self.inner.get(key).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.attempt("delete", key)?;
+        self.inner.delete(key).await
+    }
+
+    fn url_for(&self, key: &str) -> String {
+        self.inner.url_for(key)
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_blob_failure_injection() {
+        let blobs = FakeBlobStorage::new();
+        blobs.put("a.png", b"png", "image/png").await.unwrap();
+
+        blobs.fail_with("bucket unavailable");
+        assert!(blobs.get("a.png").await.is_err());
+        blobs.recover();
+        assert!(blobs.get("a.png").await.unwrap().is_some());
+
+        assert_eq!(
+            blobs.operations(),
+            vec!["put:a.png", "get:a.png", "get:a.png"]
+        );
+    }
+}
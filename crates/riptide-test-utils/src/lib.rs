@@ -4,8 +4,11 @@
 //! used across the EventMesh test suite.
 
 pub mod assertions;
+pub mod contracts;
 pub mod factories;
+pub mod fakes;
 pub mod fixtures;
+pub mod simulation;
 
 // TODO: Add mock_server module when needed
 // #[cfg(feature = "http-mock")]
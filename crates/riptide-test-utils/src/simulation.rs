@@ -0,0 +1,199 @@
+//! Deterministic simulation runtime
+//!
+//! Runs code under test on a tokio runtime whose clock is paused: sleeps,
+//! timeouts and intervals complete as soon as every task is idle, by jumping
+//! virtual time forward instead of waiting. A 30-second retry backoff takes
+//! microseconds, and the outcome does not depend on machine load.
+//!
+//! [`SimClock`] reads the same virtual time, so components that take an
+//! injected clock (the `riptide_types::ports::Clock` port, e.g. session
+//! facades, or the circuit breaker `Clock` in `riptide_utils`) see time move
+//! with the sleeps. [`Simulation::entropy`] is seeded, so "random" IDs and
+//! jitter repeat from run to run.
+//!
+//! Virtual time also jumps while tasks wait on real sockets, firing their
+//! timeouts early: simulate against in-process fakes
+//! (`riptide_test_utils::fakes`), not live servers.
+//!
+//! ```rust,ignore
+//! use riptide_test_utils::simulation::simulate;
+//! use riptide_utils::circuit_breaker::{CircuitBreaker, Config};
+//!
+//! #[test]
+//! fn breaker_recovers_after_cooldown() {
+//!     simulate(7, |sim| async move {
+//!         let breaker = CircuitBreaker::new(Config::default(), sim.clock());
+//!         // ... trip it, then:
+//!         sim.advance(Duration::from_secs(30)).await;
+//!         assert!(breaker.try_acquire().is_ok());
+//!     });
+//! }
+//! ```
+
+use chrono::{DateTime, Utc};
+use riptide_types::ports::{Clock, DeterministicEntropy};
+use riptide_utils::circuit_breaker;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// Clock following tokio's (virtual) time
+#[derive(Debug, Clone)]
+pub struct SimClock {
+    origin: tokio::time::Instant,
+    start: DateTime<Utc>,
+}
+
+impl SimClock {
+    /// Clock reading `start` now and moving with tokio time from here
+    pub fn starting_at(start: DateTime<Utc>) -> Self {
+        Self {
+            origin: tokio::time::Instant::now(),
+            start,
+        }
+    }
+
+    /// Virtual time elapsed since the clock was created
+    pub fn elapsed(&self) -> Duration {
+        self.origin.elapsed()
+    }
+}
+
+impl Clock for SimClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::from(self.now_utc())
+    }
+
+    fn now_utc(&self) -> DateTime<Utc> {
+        self.start + chrono::Duration::from_std(self.elapsed()).unwrap_or(chrono::Duration::MAX)
+    }
+}
+
+impl circuit_breaker::Clock for SimClock {
+    fn now_ms(&self) -> u64 {
+        u64::try_from(self.now_utc().timestamp_millis()).unwrap_or(0)
+    }
+}
+
+/// Virtual clock and seeded entropy shared by one simulated test
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    seed: u64,
+    clock: Arc<SimClock>,
+    entropy: Arc<DeterministicEntropy>,
+}
+
+impl Simulation {
+    /// Start a simulation at the Unix epoch
+    ///
+    /// Must run on a runtime with paused time, i.e. inside [`simulate`] or
+    /// a `#[tokio::test(start_paused = true)]` test.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            clock: Arc::new(SimClock::starting_at(DateTime::UNIX_EPOCH)),
+            entropy: Arc::new(DeterministicEntropy::new(seed)),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Clock to inject into the components under test
+    pub fn clock(&self) -> Arc<SimClock> {
+        self.clock.clone()
+    }
+
+    /// Seeded entropy to inject into the components under test
+    pub fn entropy(&self) -> Arc<DeterministicEntropy> {
+        self.entropy.clone()
+    }
+
+    /// Move virtual time forward by `duration`, firing due timers
+    pub async fn advance(&self, duration: Duration) {
+        tokio::time::advance(duration).await;
+    }
+
+    /// Virtual time elapsed since the simulation started
+    pub fn elapsed(&self) -> Duration {
+        self.clock.elapsed()
+    }
+}
+
+/// Run `test` on a fresh single-threaded runtime with paused time
+pub fn simulate<F, Fut>(seed: u64, test: F) -> Fut::Output
+where
+    F: FnOnce(Simulation) -> Fut,
+    Fut: Future,
+{
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .start_paused(true)
+        .build()
+        .expect("Failed to build simulation runtime");
+    runtime.block_on(async move { test(Simulation::new(seed)).await })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::ports::Entropy;
+    use riptide_utils::circuit_breaker::{CircuitBreaker, Config, State};
+
+    #[test]
+    fn test_backoff_and_timeouts_run_in_virtual_time() {
+        let wall = std::time::Instant::now();
+        simulate(1, |sim| async move {
+            let mut delay = Duration::from_secs(30);
+            for _ in 0..3 {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+            assert_eq!(sim.elapsed(), Duration::from_secs(210));
+            assert_eq!(
+                sim.clock().now_utc(),
+                DateTime::UNIX_EPOCH + chrono::Duration::seconds(210)
+            );
+
+            let hung = tokio::time::timeout(
+                Duration::from_secs(5),
+                tokio::time::sleep(Duration::from_secs(60)),
+            )
+            .await;
+            assert!(hung.is_err());
+        });
+        assert!(wall.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_circuit_breaker_cooldown_follows_virtual_time() {
+        simulate(1, |sim| async move {
+            let breaker = CircuitBreaker::new(
+                Config {
+                    failure_threshold: 2,
+                    open_cooldown_ms: 30_000,
+                    half_open_max_in_flight: 1,
+                },
+                sim.clock(),
+            );
+            breaker.on_failure();
+            breaker.on_failure();
+            assert_eq!(breaker.state(), State::Open);
+            assert!(breaker.try_acquire().is_err());
+
+            sim.advance(Duration::from_secs(29)).await;
+            assert!(breaker.try_acquire().is_err());
+            sim.advance(Duration::from_secs(1)).await;
+            assert!(breaker.try_acquire().is_ok());
+            assert_eq!(breaker.state(), State::HalfOpen);
+        });
+    }
+
+    #[test]
+    fn test_entropy_repeats_for_a_seed() {
+        let ids = |seed| simulate(seed, |sim| async move { sim.entropy().random_id() });
+        assert_eq!(ids(42), ids(42));
+        assert_ne!(ids(42), ids(43));
+    }
+}
@@ -0,0 +1,30 @@
+//! Port contracts run against the in-memory fakes
+//!
+//! The Redis and PostgreSQL adapters run the same suites in their own crates
+//! (`riptide-cache/tests/redis_contract_tests.rs`,
+//! `riptide-persistence/tests/postgres_contract_tests.rs`), so a fake that
+//! drifts from its adapter fails here.
+
+use riptide_test_utils::contracts::{cache_storage, idempotency, session_storage};
+use riptide_test_utils::fakes::{InMemoryCache, InMemoryIdempotencyStore, InMemorySessionStorage};
+
+#[tokio::test]
+async fn in_memory_cache_honours_cache_storage_contract() {
+    cache_storage::run_all_tests(&InMemoryCache::new())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn in_memory_session_storage_honours_session_storage_contract() {
+    session_storage::run_all_tests(&InMemorySessionStorage::without_cleanup())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn in_memory_idempotency_store_honours_idempotency_contract() {
+    idempotency::run_all_tests(&InMemoryIdempotencyStore::new())
+        .await
+        .unwrap();
+}
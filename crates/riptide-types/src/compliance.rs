@@ -0,0 +1,98 @@
+//! Crawl compliance reporting types
+//!
+//! A [`ComplianceReport`] records, for a single crawl, every decision made to
+//! respect site policies: URLs skipped because of robots.txt, crawl delays
+//! honored, meta robots exclusions, rate-limit responses, and sitemaps used
+//! for discovery. The report is machine-readable so it can be attached to
+//! crawl results and archived for legal or audit review.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Per-crawl compliance report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComplianceReport {
+    /// When the crawl started
+    pub started_at: DateTime<Utc>,
+    /// When the report was finalized
+    pub finished_at: Option<DateTime<Utc>>,
+    /// URLs skipped because robots.txt disallowed them
+    pub robots_disallowed: Vec<RobotsSkip>,
+    /// Crawl-delay directives honored, one entry per host
+    pub crawl_delays: Vec<CrawlDelayRecord>,
+    /// Pages excluded by `<meta name="robots">` directives
+    pub meta_robots_exclusions: Vec<MetaRobotsExclusion>,
+    /// Responses signalling the crawler to slow down (429/503)
+    pub rate_limited: Vec<RateLimitEvent>,
+    /// Sitemaps consulted for URL discovery
+    pub sitemaps: Vec<SitemapRecord>,
+}
+
+/// URL skipped because of robots.txt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RobotsSkip {
+    pub url: String,
+    pub at: DateTime<Utc>,
+}
+
+/// Crawl delay honored for a host
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrawlDelayRecord {
+    pub host: String,
+    /// Delay from the host's robots.txt, in seconds
+    pub delay_secs: f64,
+    /// Number of requests made while honoring the delay
+    pub requests: u64,
+}
+
+/// Page excluded by meta robots directives
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetaRobotsExclusion {
+    pub url: String,
+    /// Directives applied, e.g. `noindex`, `nofollow`
+    pub directives: Vec<String>,
+    pub at: DateTime<Utc>,
+}
+
+/// Rate-limit response received from a server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RateLimitEvent {
+    pub url: String,
+    pub status: u16,
+    /// Value of the `Retry-After` header, if present
+    pub retry_after: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+/// Sitemap consulted during discovery
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SitemapRecord {
+    pub host: String,
+    pub urls_discovered: usize,
+}
+
+impl ComplianceReport {
+    /// Create an empty report for a crawl starting now
+    pub fn new() -> Self {
+        Self {
+            started_at: Utc::now(),
+            finished_at: None,
+            robots_disallowed: Vec::new(),
+            crawl_delays: Vec::new(),
+            meta_robots_exclusions: Vec::new(),
+            rate_limited: Vec::new(),
+            sitemaps: Vec::new(),
+        }
+    }
+
+    /// Total number of URLs skipped or excluded for compliance reasons
+    pub fn excluded_count(&self) -> usize {
+        self.robots_disallowed.len() + self.meta_robots_exclusions.len()
+    }
+}
+
+impl Default for ComplianceReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
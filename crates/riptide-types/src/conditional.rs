@@ -204,6 +204,93 @@ pub fn validate_cache(
     CacheValidation::Unknown
 }
 
+/// Reference point of a differential crawl
+///
+/// Accepts either an RFC 3339 timestamp or the id of an earlier crawl.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangedSince {
+    /// Documents changed after this instant
+    Timestamp(DateTime<Utc>),
+    /// Documents changed after the given crawl started
+    CrawlId(String),
+}
+
+impl ChangedSince {
+    /// Parse a `changed_since` value; anything that is not a timestamp is a crawl id
+    pub fn parse(value: &str) -> Self {
+        let value = value.trim();
+        match DateTime::parse_from_rfc3339(value) {
+            Ok(ts) => Self::Timestamp(ts.with_timezone(&Utc)),
+            Err(_) => Self::CrawlId(value.to_string()),
+        }
+    }
+}
+
+/// Stored content fingerprint of a URL, used for differential crawling
+///
+/// Records the content hash and HTTP validators from the last fetch, and
+/// when the content last changed, so a later crawl can send a conditional
+/// request and skip documents that have not changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentFingerprint {
+    /// SHA-256 of the response body
+    pub content_hash: String,
+    /// ETag validator from the last response
+    pub etag: Option<String>,
+    /// Last-Modified validator from the last response
+    pub last_modified: Option<String>,
+    /// When the content hash last changed
+    pub changed_at: DateTime<Utc>,
+    /// When the URL was last fetched or revalidated
+    pub checked_at: DateTime<Utc>,
+}
+
+impl ContentFingerprint {
+    /// Hash a response body
+    pub fn hash_content(body: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(body))
+    }
+
+    /// Fingerprint a freshly fetched body, keeping `changed_at` when the content is unchanged
+    pub fn observe(
+        previous: Option<&Self>,
+        body: &[u8],
+        etag: Option<String>,
+        last_modified: Option<String>,
+        fetched_at: DateTime<Utc>,
+    ) -> Self {
+        let content_hash = Self::hash_content(body);
+        let changed_at = match previous {
+            Some(prev) if prev.content_hash == content_hash => prev.changed_at,
+            _ => fetched_at,
+        };
+        Self {
+            content_hash,
+            etag,
+            last_modified,
+            changed_at,
+            checked_at: fetched_at,
+        }
+    }
+
+    /// Whether the content changed after `since`
+    pub fn changed_since(&self, since: DateTime<Utc>) -> bool {
+        self.changed_at > since
+    }
+
+    /// Conditional request headers revalidating this fingerprint
+    pub fn conditional_headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = Vec::new();
+        if let Some(etag) = &self.etag {
+            headers.push(("If-None-Match", etag.clone()));
+        }
+        if let Some(last_modified) = &self.last_modified {
+            headers.push(("If-Modified-Since", last_modified.clone()));
+        }
+        headers
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -296,4 +383,37 @@ mod tests {
         let result = validate_cache(None, cached_time, None, newer_time);
         assert!(matches!(result, CacheValidation::Stale));
     }
+
+    #[test]
+    fn test_changed_since_parse() {
+        assert!(matches!(
+            ChangedSince::parse("2024-05-01T00:00:00Z"),
+            ChangedSince::Timestamp(_)
+        ));
+        assert_eq!(
+            ChangedSince::parse("crawl-42"),
+            ChangedSince::CrawlId("crawl-42".to_string())
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_tracks_content_changes() {
+        let t0 = Utc::now() - chrono::Duration::hours(2);
+        let t1 = t0 + chrono::Duration::hours(1);
+
+        let first = ContentFingerprint::observe(None, b"v1", Some("\"a\"".into()), None, t0);
+        assert_eq!(first.changed_at, t0);
+        assert_eq!(
+            first.conditional_headers(),
+            vec![("If-None-Match", "\"a\"".to_string())]
+        );
+
+        let same = ContentFingerprint::observe(Some(&first), b"v1", None, None, t1);
+        assert_eq!(same.changed_at, t0);
+        assert_eq!(same.checked_at, t1);
+        assert!(!same.changed_since(t0));
+
+        let changed = ContentFingerprint::observe(Some(&same), b"v2", None, None, t1);
+        assert!(changed.changed_since(t0));
+    }
 }
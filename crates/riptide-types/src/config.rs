@@ -8,9 +8,39 @@ pub enum ExtractionMode {
     Article,
     Full,
     Metadata,
+    /// Text matched by CSS selectors or XPath expressions, in selector order
     Custom(Vec<String>),
 }
 
+/// How extraction handles inline MathML formulas and SVG graphics
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmbeddedMarkup {
+    /// Keep only their text content: formula tokens run together and SVG
+    /// is left out of markdown
+    #[default]
+    Text,
+    /// MathML as LaTeX between `$` (or `$$` for display formulas); SVG as
+    /// its alt description
+    Latex,
+    /// MathML and SVG markup kept verbatim
+    Raw,
+    /// Alt descriptions: `alttext` or `aria-label` of formulas, `aria-label`,
+    /// `<title>` or `<desc>` of SVG
+    Alt,
+}
+
+impl EmbeddedMarkup {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Text => "text",
+            Self::Latex => "latex",
+            Self::Raw => "raw",
+            Self::Alt => "alt",
+        }
+    }
+}
+
 /// Rendering mode for content processing
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub enum RenderMode {
@@ -23,6 +53,8 @@ pub enum RenderMode {
     Adaptive,
     /// PDF processing mode
     Pdf,
+    /// RSS, Atom or JSON Feed parsing, even without a feed content type
+    Feed,
     /// HTML output mode
     Html,
     /// Markdown output mode
@@ -43,12 +75,14 @@ pub enum OutputFormat {
     Text,
     /// Markdown format
     Markdown,
+    /// Sanitized reader-mode HTML of the main content, alongside text and markdown
+    CleanHtml,
 }
 
 /// Content chunking configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChunkingConfig {
-    /// Chunking mode: "topic", "sliding", "fixed", "sentence", "html-aware"
+    /// Chunking mode: "topic", "sliding", "fixed", "sentence", "html-aware", "semantic"
     pub chunking_mode: String,
     /// Maximum chunk size in tokens
     pub chunk_size: usize,
@@ -120,11 +154,33 @@ pub struct CrawlOptions {
     pub use_spider: Option<bool>,
     pub spider_max_depth: Option<usize>,
     pub spider_strategy: Option<String>,
+    /// URL filters and page cap of a spider crawl; discovered URLs outside
+    /// them are not crawled
+    pub scope: Option<CrawlScope>,
     // Content chunking configuration
     pub chunking_config: Option<ChunkingConfig>,
     /// Skip content extraction and return raw HTML only
     /// When true, only HTML field is populated, text/links/metadata are empty
     pub skip_extraction: Option<bool>,
+    /// Write fetched and rendered pages to WARC 1.1 archive files
+    /// When true, the response lists the archive files in `warc_files`
+    pub archive_warc: Option<bool>,
+    /// Keep fetched PDFs as `Pdf` artifacts in blob storage
+    /// PDFs larger than the media pipeline's `max_pdf_bytes` are not stored
+    pub store_pdf: Option<bool>,
+    /// Only return documents changed since this RFC 3339 timestamp or crawl id
+    /// Unchanged URLs are reported as skipped, without a document
+    pub changed_since: Option<String>,
+    /// Translate extracted text into this language (e.g. "en")
+    /// Overrides `RIPTIDE_TRANSLATE_TO`; needs a configured translator
+    pub translate_to: Option<String>,
+    /// Per-request pipeline tuning, checked against the tenant's policy
+    pub pipeline_hints: Option<PipelineHints>,
+    /// Resource limits of this crawl; the response reports consumption and
+    /// the limit that ended the crawl
+    pub budget: Option<CrawlBudget>,
+    /// Handling of inline MathML and SVG in extracted text
+    pub embedded_markup: Option<EmbeddedMarkup>,
 }
 
 impl Default for CrawlOptions {
@@ -141,8 +197,90 @@ impl Default for CrawlOptions {
             use_spider: None,
             spider_max_depth: None,
             spider_strategy: None,
+            scope: None,
             chunking_config: None,
             skip_extraction: None,
+            archive_warc: None,
+            store_pdf: None,
+            changed_since: None,
+            translate_to: None,
+            pipeline_hints: None,
+            budget: None,
+            embedded_markup: None,
         }
     }
 }
+
+impl CrawlOptions {
+    /// Whether pages must be neither read from nor written to the cache
+    pub fn bypass_cache(&self) -> bool {
+        self.cache_mode == "bypass" || self.pipeline_hints.as_ref().is_some_and(|h| h.skip_cache)
+    }
+
+    /// Whether pages must be rendered headless whatever the gate decides
+    pub fn force_headless(&self) -> bool {
+        self.pipeline_hints
+            .as_ref()
+            .is_some_and(|h| h.force_headless)
+    }
+}
+
+/// Per-request overrides of pipeline behavior
+///
+/// Lets a client tune a single request without changing global
+/// configuration. Every hint set here must be allowed by the tenant's crawl
+/// policy, otherwise the request is rejected.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PipelineHints {
+    /// Render pages headless instead of following the gate decision
+    pub force_headless: bool,
+    /// Neither read nor write the cache, as with `cache_mode: "bypass"`
+    pub skip_cache: bool,
+    /// Extractor to prefer: a registered extractor such as "native" for
+    /// crawls, an extraction strategy such as "css" for `/extract`
+    pub extraction_schema: Option<String>,
+    /// Stealth preset of headless renders: "none", "low", "medium" or "high"
+    pub stealth_preset: Option<String>,
+    /// Spending ceiling the client sets for paid services such as LLMs, in
+    /// USD; rejected when above the tenant's per-request limit
+    pub max_budget_usd: Option<f64>,
+}
+
+impl PipelineHints {
+    /// Whether no hint is set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// URLs a spider crawl may visit
+///
+/// Patterns are regular expressions matched anywhere in the URL. A URL is in
+/// scope when it matches one of `include` (or `include` is empty) and none
+/// of `exclude`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrawlScope {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    /// Pages crawled before the crawl stops
+    pub max_pages: Option<usize>,
+}
+
+/// Resource limits of one crawl
+///
+/// Unset limits are not enforced. Once a limit is reached, URLs not yet
+/// started are skipped; the wall time limit also cuts off URLs in flight.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrawlBudget {
+    /// Wall-clock time of the whole crawl, in seconds
+    pub max_wall_time_secs: Option<f64>,
+    /// Headless rendering time summed over all pages, in seconds
+    pub max_render_secs: Option<f64>,
+    /// Bytes received on the wire over all pages, before decompression
+    pub max_bytes: Option<u64>,
+    /// Fuel consumed by WASM extraction over all pages
+    pub max_wasm_fuel: Option<u64>,
+}
@@ -0,0 +1,339 @@
+//! Differences between two extractions of a page
+//!
+//! [`ExtractionDiff::between`] compares two [`ExtractedDoc`]s of the same
+//! page and reports:
+//!
+//! - text blocks (lines of `text`) added, removed or modified, aligned on
+//!   the longest run of unchanged blocks so an inserted paragraph does not
+//!   mark everything after it as changed
+//! - links added and removed
+//! - changed metadata: title, byline, publication date, language,
+//!   description, site name and categories
+//!
+//! Blocks are compared with whitespace collapsed. Pages with more than
+//! [`MAX_ALIGNED_BLOCKS`] blocks between their common start and end are not
+//! aligned: those blocks are reported as removed and added.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_types::diff::{BlockChangeKind, ExtractionDiff};
+//! use riptide_types::ExtractedDoc;
+//!
+//! let old = ExtractedDoc {
+//!     title: Some("Opening hours".to_string()),
+//!     text: "Open daily.\nMonday 9-17\nClosed on holidays.".to_string(),
+//!     ..Default::default()
+//! };
+//! let new = ExtractedDoc {
+//!     title: Some("Opening hours".to_string()),
+//!     text: "Open daily.\nMonday 10-18\nClosed on holidays.".to_string(),
+//!     ..Default::default()
+//! };
+//!
+//! let diff = ExtractionDiff::between(&old, &new);
+//! assert_eq!(diff.text_changes.len(), 1);
+//! assert_eq!(diff.text_changes[0].kind, BlockChangeKind::Modified);
+//! assert_eq!(diff.text_changes[0].new_text.as_deref(), Some("Monday 10-18"));
+//! assert!(diff.metadata_changes.is_empty());
+//! ```
+
+use crate::extracted::ExtractedDoc;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Most changed blocks, on either side, aligned on unchanged ones
+pub const MAX_ALIGNED_BLOCKS: usize = 1_000;
+
+/// How a text block changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// Text block that differs between the two documents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextBlockChange {
+    pub kind: BlockChangeKind,
+    /// Position among the old document's blocks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_index: Option<usize>,
+    /// Position among the new document's blocks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_index: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_text: Option<String>,
+}
+
+/// Metadata field that differs between the two documents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetadataChange {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// Structured difference between two extractions of a page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractionDiff {
+    /// URL of the newer document
+    pub url: String,
+    pub computed_at: DateTime<Utc>,
+    /// In document order
+    pub text_changes: Vec<TextBlockChange>,
+    pub links_added: Vec<String>,
+    pub links_removed: Vec<String>,
+    pub metadata_changes: Vec<MetadataChange>,
+}
+
+impl ExtractionDiff {
+    /// Difference from `old` to `new`
+    pub fn between(old: &ExtractedDoc, new: &ExtractedDoc) -> Self {
+        Self {
+            url: new.url.clone(),
+            computed_at: Utc::now(),
+            text_changes: diff_blocks(&text_blocks(&old.text), &text_blocks(&new.text)),
+            links_added: missing_from(&new.links, &old.links),
+            links_removed: missing_from(&old.links, &new.links),
+            metadata_changes: metadata_changes(old, new),
+        }
+    }
+
+    /// Whether the documents are the same in text, links and metadata
+    pub fn is_empty(&self) -> bool {
+        self.text_changes.is_empty()
+            && self.links_added.is_empty()
+            && self.links_removed.is_empty()
+            && self.metadata_changes.is_empty()
+    }
+
+    /// Number of text changes of `kind`
+    pub fn count(&self, kind: BlockChangeKind) -> usize {
+        self.text_changes.iter().filter(|c| c.kind == kind).count()
+    }
+}
+
+/// Copy of `doc` with only the fields diffs compare, for storing between
+/// extractions
+pub fn snapshot(doc: &ExtractedDoc) -> ExtractedDoc {
+    ExtractedDoc {
+        url: doc.url.clone(),
+        title: doc.title.clone(),
+        text: doc.text.clone(),
+        links: doc.links.clone(),
+        byline: doc.byline.clone(),
+        published_iso: doc.published_iso.clone(),
+        language: doc.language.clone(),
+        description: doc.description.clone(),
+        site_name: doc.site_name.clone(),
+        categories: doc.categories.clone(),
+        ..Default::default()
+    }
+}
+
+/// Non-empty lines of `text` with whitespace collapsed
+fn text_blocks(text: &str) -> Vec<String> {
+    text.lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+fn diff_blocks(old: &[String], new: &[String]) -> Vec<TextBlockChange> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    // Pairs of unchanged (old, new) positions within the changed middle
+    let unchanged = if old_mid.len() <= MAX_ALIGNED_BLOCKS && new_mid.len() <= MAX_ALIGNED_BLOCKS {
+        longest_common_blocks(old_mid, new_mid)
+    } else {
+        Vec::new()
+    };
+
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for (next_i, next_j) in unchanged
+        .into_iter()
+        .chain(std::iter::once((old_mid.len(), new_mid.len())))
+    {
+        let removed = (i..next_i).map(|k| (prefix + k, &old_mid[k]));
+        let mut added = (j..next_j).map(|k| (prefix + k, &new_mid[k]));
+        for (old_index, old_text) in removed {
+            match added.next() {
+                Some((new_index, new_text)) => changes.push(TextBlockChange {
+                    kind: BlockChangeKind::Modified,
+                    old_index: Some(old_index),
+                    new_index: Some(new_index),
+                    old_text: Some(old_text.clone()),
+                    new_text: Some(new_text.clone()),
+                }),
+                None => changes.push(TextBlockChange {
+                    kind: BlockChangeKind::Removed,
+                    old_index: Some(old_index),
+                    new_index: None,
+                    old_text: Some(old_text.clone()),
+                    new_text: None,
+                }),
+            }
+        }
+        for (new_index, new_text) in added {
+            changes.push(TextBlockChange {
+                kind: BlockChangeKind::Added,
+                old_index: None,
+                new_index: Some(new_index),
+                old_text: None,
+                new_text: Some(new_text.clone()),
+            });
+        }
+        i = next_i + 1;
+        j = next_j + 1;
+    }
+    changes
+}
+
+/// Positions of a longest common subsequence of `old` and `new`, in order
+fn longest_common_blocks(old: &[String], new: &[String]) -> Vec<(usize, usize)> {
+    let width = new.len() + 1;
+    // lengths[i * width + j]: longest common run of old[i..] and new[j..]
+    let mut lengths = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// Entries of `links` not in `other`, once each, in order
+fn missing_from(links: &[String], other: &[String]) -> Vec<String> {
+    let other: HashSet<&str> = other.iter().map(String::as_str).collect();
+    let mut seen = HashSet::new();
+    links
+        .iter()
+        .filter(|link| !other.contains(link.as_str()) && seen.insert(link.as_str()))
+        .cloned()
+        .collect()
+}
+
+fn metadata_changes(old: &ExtractedDoc, new: &ExtractedDoc) -> Vec<MetadataChange> {
+    let categories =
+        |doc: &ExtractedDoc| (!doc.categories.is_empty()).then(|| doc.categories.join(", "));
+    let fields = [
+        ("title", old.title.clone(), new.title.clone()),
+        ("byline", old.byline.clone(), new.byline.clone()),
+        (
+            "published_iso",
+            old.published_iso.clone(),
+            new.published_iso.clone(),
+        ),
+        ("language", old.language.clone(), new.language.clone()),
+        (
+            "description",
+            old.description.clone(),
+            new.description.clone(),
+        ),
+        ("site_name", old.site_name.clone(), new.site_name.clone()),
+        ("categories", categories(old), categories(new)),
+    ];
+    fields
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(field, old, new)| MetadataChange {
+            field: field.to_string(),
+            old,
+            new,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(text: &str, links: &[&str]) -> ExtractedDoc {
+        ExtractedDoc {
+            url: "https://example.com/page".to_string(),
+            text: text.to_string(),
+            links: links.iter().map(|l| l.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_inserted_block_keeps_following_blocks_aligned() {
+        let old = doc("Intro\nFirst point\nSecond point\nOutro", &["/a", "/b"]);
+        let mut new = doc(
+            "Intro\nNew   point\nFirst point\nSecond point, revised\nOutro",
+            &["/b", "/c", "/c"],
+        );
+        new.title = Some("Updated".to_string());
+
+        let diff = ExtractionDiff::between(&old, &new);
+        assert_eq!(
+            diff.text_changes,
+            vec![
+                TextBlockChange {
+                    kind: BlockChangeKind::Added,
+                    old_index: None,
+                    new_index: Some(1),
+                    old_text: None,
+                    new_text: Some("New point".to_string()),
+                },
+                TextBlockChange {
+                    kind: BlockChangeKind::Modified,
+                    old_index: Some(2),
+                    new_index: Some(3),
+                    old_text: Some("Second point".to_string()),
+                    new_text: Some("Second point, revised".to_string()),
+                },
+            ]
+        );
+        assert_eq!(diff.links_added, vec!["/c"]);
+        assert_eq!(diff.links_removed, vec!["/a"]);
+        assert_eq!(diff.metadata_changes.len(), 1);
+        assert_eq!(diff.metadata_changes[0].field, "title");
+        assert_eq!(diff.metadata_changes[0].old, None);
+    }
+
+    #[test]
+    fn test_removed_blocks_and_identical_documents() {
+        let old = doc("A\nB\nC\nD", &[]);
+        let new = doc("A\nD", &[]);
+        let diff = ExtractionDiff::between(&old, &new);
+        assert_eq!(diff.count(BlockChangeKind::Removed), 2);
+        assert_eq!(diff.text_changes[0].old_index, Some(1));
+
+        assert!(ExtractionDiff::between(&old, &snapshot(&old)).is_empty());
+    }
+}
@@ -0,0 +1,386 @@
+//! DOM snapshot capture format
+//!
+//! A [`DomSnapshot`] is what a browser extension sends when it captures a
+//! page the user is looking at, typically one behind a login the crawler
+//! cannot reproduce. It carries the serialized DOM tree, a small subset of
+//! computed styles per element (enough to tell visible content from hidden
+//! chrome), and a manifest of the resources the page loaded.
+//!
+//! [`DomSnapshot::to_html`] turns the tree back into an HTML document that
+//! the regular extractors understand. Hidden elements, scripts and other
+//! non-content nodes are dropped while rendering.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Snapshot format version produced by the current extension
+pub const DOM_SNAPSHOT_VERSION: u32 = 1;
+
+/// Elements whose content never reaches the rendered document
+const SKIPPED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "iframe", "object", "embed", "canvas",
+];
+
+/// Elements without a closing tag
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "hr", "img", "input", "link", "meta", "source", "track", "wbr",
+];
+
+/// A captured page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DomSnapshot {
+    /// Format version, see [`DOM_SNAPSHOT_VERSION`]
+    #[serde(default = "default_version")]
+    pub version: u32,
+    /// Page URL after redirects (`location.href`)
+    pub url: String,
+    /// When the capture was taken
+    #[serde(default)]
+    pub captured_at: Option<DateTime<Utc>>,
+    /// Document title
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Document language (`<html lang>`)
+    #[serde(default)]
+    pub lang: Option<String>,
+    /// Viewport size at capture time
+    #[serde(default)]
+    pub viewport: Option<Viewport>,
+    /// The `<html>` element, or `<body>` when the extension captures only the body
+    pub root: DomNode,
+    /// Resources the page loaded
+    #[serde(default)]
+    pub resources: Vec<SnapshotResource>,
+}
+
+fn default_version() -> u32 {
+    DOM_SNAPSHOT_VERSION
+}
+
+/// Viewport size in CSS pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Viewport {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// A node of the serialized DOM tree
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DomNode {
+    /// An element with its attributes, computed styles and children
+    Element {
+        /// Lowercase tag name
+        tag: String,
+        #[serde(default)]
+        attributes: BTreeMap<String, String>,
+        /// Computed styles, when the extension captured them
+        #[serde(default)]
+        styles: Option<ComputedStyle>,
+        #[serde(default)]
+        children: Vec<DomNode>,
+    },
+    /// A text node
+    Text { text: String },
+}
+
+impl DomNode {
+    /// Number of nodes in this subtree, including this one
+    pub fn node_count(&self) -> usize {
+        match self {
+            DomNode::Element { children, .. } => {
+                1 + children.iter().map(DomNode::node_count).sum::<usize>()
+            }
+            DomNode::Text { .. } => 1,
+        }
+    }
+
+    /// Depth of this subtree; a leaf has depth 1
+    pub fn depth(&self) -> usize {
+        match self {
+            DomNode::Element { children, .. } => {
+                1 + children.iter().map(DomNode::depth).max().unwrap_or(0)
+            }
+            DomNode::Text { .. } => 1,
+        }
+    }
+}
+
+/// Subset of `getComputedStyle()` kept per element
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ComputedStyle {
+    pub display: Option<String>,
+    pub visibility: Option<String>,
+    pub opacity: Option<f32>,
+    pub position: Option<String>,
+    pub font_size: Option<String>,
+    pub font_weight: Option<String>,
+    /// Rendered box width in CSS pixels
+    pub width: Option<f32>,
+    /// Rendered box height in CSS pixels
+    pub height: Option<f32>,
+}
+
+impl ComputedStyle {
+    /// Whether the element was invisible to the user when captured
+    pub fn is_hidden(&self) -> bool {
+        self.display.as_deref() == Some("none")
+            || matches!(self.visibility.as_deref(), Some("hidden" | "collapse"))
+            || self.opacity.is_some_and(|o| o <= 0.0)
+    }
+}
+
+/// Kind of a loaded resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceKind {
+    Document,
+    Stylesheet,
+    Script,
+    Image,
+    Font,
+    Media,
+    Xhr,
+    Other,
+}
+
+/// Entry of the resources manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotResource {
+    /// Resource URL as loaded by the page
+    pub url: String,
+    pub kind: ResourceKind,
+    #[serde(default)]
+    pub mime_type: Option<String>,
+    /// Transfer size in bytes, when the browser reported it
+    #[serde(default)]
+    pub size_bytes: Option<u64>,
+    /// HTTP status, when the browser reported it
+    #[serde(default)]
+    pub status: Option<u16>,
+}
+
+impl DomSnapshot {
+    /// Render the visible part of the snapshot as an HTML document
+    pub fn to_html(&self) -> String {
+        let mut body = String::new();
+        let mut has_head = false;
+        render_node(&self.root, &mut body, &mut has_head, false);
+
+        if has_head {
+            return format!("<!DOCTYPE html>{}", body);
+        }
+
+        // Without a captured <head>, rebuild the document around the content
+        let content: &[DomNode] = match &self.root {
+            DomNode::Element { tag, children, .. } if tag.eq_ignore_ascii_case("html") => {
+                body.clear();
+                for child in children {
+                    render_node(child, &mut body, &mut has_head, false);
+                }
+                children
+            }
+            root => std::slice::from_ref(root),
+        };
+
+        let mut html = String::from("<!DOCTYPE html><html");
+        if let Some(lang) = &self.lang {
+            html.push_str(&format!(" lang=\"{}\"", escape(lang, true)));
+        }
+        html.push_str("><head><meta charset=\"utf-8\">");
+        if let Some(title) = &self.title {
+            html.push_str(&format!("<title>{}</title>", escape(title, false)));
+        }
+        html.push_str("</head>");
+        let wrap_body = !content.iter().any(
+            |node| matches!(node, DomNode::Element { tag, .. } if tag.eq_ignore_ascii_case("body")),
+        );
+        if wrap_body {
+            html.push_str("<body>");
+        }
+        html.push_str(&body);
+        if wrap_body {
+            html.push_str("</body>");
+        }
+        html.push_str("</html>");
+        html
+    }
+}
+
+/// Render `node`; `in_head` disables the visibility check, since browsers
+/// report `display: none` for `<head>` and everything in it
+fn render_node(node: &DomNode, out: &mut String, has_head: &mut bool, in_head: bool) {
+    match node {
+        DomNode::Text { text } => out.push_str(&escape(text, false)),
+        DomNode::Element {
+            tag,
+            attributes,
+            styles,
+            children,
+        } => {
+            let tag = tag.to_ascii_lowercase();
+            let in_head = in_head || tag == "head";
+            if SKIPPED_TAGS.contains(&tag.as_str())
+                || (!in_head && styles.as_ref().is_some_and(ComputedStyle::is_hidden))
+                || !tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            {
+                return;
+            }
+            if tag == "head" {
+                *has_head = true;
+            }
+
+            out.push('<');
+            out.push_str(&tag);
+            for (name, value) in attributes {
+                let name = name.to_ascii_lowercase();
+                // Event handlers never carry content and must not survive
+                if name.starts_with("on")
+                    || !name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':'))
+                {
+                    continue;
+                }
+                out.push_str(&format!(" {}=\"{}\"", name, escape(value, true)));
+            }
+            out.push('>');
+            if VOID_TAGS.contains(&tag.as_str()) {
+                return;
+            }
+            for child in children {
+                render_node(child, out, has_head, in_head);
+            }
+            out.push_str(&format!("</{}>", tag));
+        }
+    }
+}
+
+fn escape(text: &str, attribute: bool) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' if attribute => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(tag: &str, children: Vec<DomNode>) -> DomNode {
+        DomNode::Element {
+            tag: tag.to_string(),
+            attributes: BTreeMap::new(),
+            styles: None,
+            children,
+        }
+    }
+
+    fn text(text: &str) -> DomNode {
+        DomNode::Text {
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_to_html_drops_hidden_and_script_nodes() {
+        let hidden = DomNode::Element {
+            tag: "div".to_string(),
+            attributes: BTreeMap::new(),
+            styles: Some(ComputedStyle {
+                display: Some("none".to_string()),
+                ..Default::default()
+            }),
+            children: vec![text("cookie banner")],
+        };
+        let snapshot = DomSnapshot {
+            version: DOM_SNAPSHOT_VERSION,
+            url: "https://portal.example.com/report".to_string(),
+            captured_at: None,
+            title: Some("Q3 <Report>".to_string()),
+            lang: Some("en".to_string()),
+            viewport: None,
+            root: element(
+                "body",
+                vec![
+                    element("h1", vec![text("Revenue & costs")]),
+                    hidden,
+                    element("script", vec![text("track()")]),
+                ],
+            ),
+            resources: Vec::new(),
+        };
+
+        let html = snapshot.to_html();
+        assert!(html.starts_with("<!DOCTYPE html><html lang=\"en\">"));
+        assert!(html.contains("<title>Q3 &lt;Report&gt;</title>"));
+        assert!(html.contains("<body><h1>Revenue &amp; costs</h1></body>"));
+        assert!(!html.contains("cookie banner"));
+        assert!(!html.contains("track()"));
+    }
+
+    #[test]
+    fn test_to_html_adds_head_to_headless_html_root() {
+        let snapshot = DomSnapshot {
+            version: DOM_SNAPSHOT_VERSION,
+            url: "https://portal.example.com/".to_string(),
+            captured_at: None,
+            title: Some("Home".to_string()),
+            lang: None,
+            viewport: None,
+            root: element("html", vec![element("body", vec![text("hi")])]),
+            resources: Vec::new(),
+        };
+
+        assert_eq!(
+            snapshot.to_html(),
+            "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Home</title></head>\
+             <body>hi</body></html>"
+        );
+    }
+
+    #[test]
+    fn test_to_html_strips_event_handlers() {
+        let mut attributes = BTreeMap::new();
+        attributes.insert("href".to_string(), "/a?x=1&y=\"2\"".to_string());
+        attributes.insert("onclick".to_string(), "steal()".to_string());
+        let node = DomNode::Element {
+            tag: "a".to_string(),
+            attributes,
+            styles: None,
+            children: vec![text("link")],
+        };
+
+        let mut out = String::new();
+        render_node(&node, &mut out, &mut false, false);
+        assert_eq!(out, "<a href=\"/a?x=1&amp;y=&quot;2&quot;\">link</a>");
+    }
+
+    #[test]
+    fn test_snapshot_deserializes_tagged_nodes() {
+        let snapshot: DomSnapshot = serde_json::from_value(serde_json::json!({
+            "url": "https://portal.example.com/",
+            "root": {
+                "type": "element",
+                "tag": "body",
+                "children": [{"type": "text", "text": "hi"}]
+            },
+            "resources": [{"url": "https://cdn.example.com/a.css", "kind": "stylesheet"}]
+        }))
+        .unwrap();
+
+        assert_eq!(snapshot.version, DOM_SNAPSHOT_VERSION);
+        assert_eq!(snapshot.root.node_count(), 2);
+        assert_eq!(snapshot.root.depth(), 2);
+        assert_eq!(snapshot.resources[0].kind, ResourceKind::Stylesheet);
+    }
+}
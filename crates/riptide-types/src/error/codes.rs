@@ -0,0 +1,318 @@
+//! Machine-readable error codes
+//!
+//! Every error surfaced to clients (API responses, CLI exit codes, job
+//! results) carries an [`ErrorCode`] such as `RIPTIDE_FETCH_TIMEOUT`, so
+//! clients can branch on a stable identifier instead of parsing messages.
+//! Codes are grouped into [`ErrorCategory`]s, which the CLI maps to exit
+//! codes.
+//!
+//! Error types opt in by implementing [`HasErrorCode`]. Codes are part of the
+//! public API: new codes may be added, existing codes are never renamed.
+
+use crate::error::{RiptideError, StrategyError, StreamingError};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Broad class of an error code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The request itself is invalid; retrying unchanged will fail again
+    Input,
+    /// Authentication, authorization, crawl policy or robots.txt denied access
+    Access,
+    /// A rate limit was hit; retry after backing off
+    RateLimit,
+    /// The target site could not be fetched or rendered
+    Target,
+    /// Content was fetched but could not be processed
+    Processing,
+    /// A dependency (cache, storage, browser pool, LLM provider) is unavailable
+    Unavailable,
+    /// The server is misconfigured or the feature is not enabled
+    Configuration,
+    /// Unexpected internal failure
+    Internal,
+}
+
+macro_rules! error_codes {
+    ($( $(#[$doc:meta])* $variant:ident => $code:literal, $category:ident, $retryable:literal; )*) => {
+        /// Stable, machine-readable error code
+        ///
+        /// Serialized as its `RIPTIDE_*` string.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum ErrorCode {
+            $( $(#[$doc])* $variant, )*
+        }
+
+        impl ErrorCode {
+            /// All error codes, in documentation order
+            pub const ALL: &'static [ErrorCode] = &[$(ErrorCode::$variant),*];
+
+            /// The `RIPTIDE_*` string for this code
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    $(ErrorCode::$variant => $code,)*
+                }
+            }
+
+            /// Category this code belongs to
+            pub const fn category(self) -> ErrorCategory {
+                match self {
+                    $(ErrorCode::$variant => ErrorCategory::$category,)*
+                }
+            }
+
+            /// Whether retrying the same request may succeed
+            pub const fn is_retryable(self) -> bool {
+                match self {
+                    $(ErrorCode::$variant => $retryable,)*
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    /// Malformed or invalid request data
+    InvalidRequest => "RIPTIDE_INVALID_REQUEST", Input, false;
+    /// URL could not be parsed or uses an unsupported scheme
+    InvalidUrl => "RIPTIDE_INVALID_URL", Input, false;
+    /// Request body or processed document exceeds the size limit
+    PayloadTooLarge => "RIPTIDE_PAYLOAD_TOO_LARGE", Input, false;
+    /// Request content type is not supported
+    UnsupportedMediaType => "RIPTIDE_UNSUPPORTED_MEDIA_TYPE", Input, false;
+    /// Requested resource (job, session, crawl) does not exist
+    NotFound => "RIPTIDE_NOT_FOUND", Input, false;
+    /// Resource already exists
+    AlreadyExists => "RIPTIDE_ALREADY_EXISTS", Input, false;
+    /// Missing or invalid credentials
+    Unauthorized => "RIPTIDE_UNAUTHORIZED", Access, false;
+    /// Caller is not allowed to perform the operation
+    PermissionDenied => "RIPTIDE_PERMISSION_DENIED", Access, false;
+    /// URL rejected by the tenant's crawl policy
+    PolicyDenied => "RIPTIDE_POLICY_DENIED", Access, false;
+    /// URL disallowed by the site's robots.txt
+    RobotsDisallowed => "RIPTIDE_ROBOTS_DISALLOWED", Access, false;
+    /// Rate limit exceeded
+    RateLimited => "RIPTIDE_RATE_LIMITED", RateLimit, true;
+    /// Target could not be fetched (DNS, connection, HTTP error)
+    FetchFailed => "RIPTIDE_FETCH_FAILED", Target, true;
+    /// Fetching the target timed out
+    FetchTimeout => "RIPTIDE_FETCH_TIMEOUT", Target, true;
+    /// Headless browser navigation or page operation failed
+    BrowserFailed => "RIPTIDE_BROWSER_FAILED", Target, true;
+    /// Content extraction failed
+    ExtractionFailed => "RIPTIDE_EXTRACTION_FAILED", Processing, false;
+    /// Content gate declined to route or process the page
+    GateBlocked => "RIPTIDE_GATE_BLOCKED", Processing, false;
+    /// Content could not be parsed
+    ParseFailed => "RIPTIDE_PARSE_FAILED", Processing, false;
+    /// Document is not a valid or supported PDF
+    PdfInvalid => "RIPTIDE_PDF_INVALID", Processing, false;
+    /// PDF is encrypted
+    PdfEncrypted => "RIPTIDE_PDF_ENCRYPTED", Processing, false;
+    /// LLM provider request failed
+    LlmFailed => "RIPTIDE_LLM_FAILED", Processing, true;
+    /// Streaming connection or protocol failure
+    StreamFailed => "RIPTIDE_STREAM_FAILED", Processing, true;
+    /// Operation was cancelled
+    Cancelled => "RIPTIDE_CANCELLED", Processing, false;
+    /// Operation timed out
+    Timeout => "RIPTIDE_TIMEOUT", Unavailable, true;
+    /// Circuit breaker is open for the dependency
+    CircuitOpen => "RIPTIDE_CIRCUIT_OPEN", Unavailable, true;
+    /// Browser pool could not provide a browser
+    BrowserUnavailable => "RIPTIDE_BROWSER_UNAVAILABLE", Unavailable, true;
+    /// Cache backend unavailable
+    CacheUnavailable => "RIPTIDE_CACHE_UNAVAILABLE", Unavailable, true;
+    /// Storage or database operation failed
+    StorageFailed => "RIPTIDE_STORAGE_FAILED", Unavailable, true;
+    /// Required dependency or service unavailable
+    DependencyUnavailable => "RIPTIDE_DEPENDENCY_UNAVAILABLE", Unavailable, true;
+    /// Memory, buffer or pool capacity exhausted
+    ResourceExhausted => "RIPTIDE_RESOURCE_EXHAUSTED", Unavailable, true;
+    /// Invalid server or strategy configuration
+    ConfigInvalid => "RIPTIDE_CONFIG_INVALID", Configuration, false;
+    /// Feature not enabled in this build or deployment
+    FeatureDisabled => "RIPTIDE_FEATURE_DISABLED", Configuration, false;
+    /// Operation not implemented
+    NotImplemented => "RIPTIDE_NOT_IMPLEMENTED", Configuration, false;
+    /// Unexpected internal error
+    Internal => "RIPTIDE_INTERNAL", Internal, false;
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ErrorCode {
+    type Err = RiptideError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ErrorCode::ALL
+            .iter()
+            .copied()
+            .find(|code| code.as_str() == s)
+            .ok_or_else(|| RiptideError::Parse(format!("Unknown error code: {}", s)))
+    }
+}
+
+impl Serialize for ErrorCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ErrorCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Errors that map to an [`ErrorCode`]
+pub trait HasErrorCode {
+    /// Machine-readable code for this error
+    fn error_code(&self) -> ErrorCode;
+}
+
+impl HasErrorCode for RiptideError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            RiptideError::BrowserInitialization(_) => ErrorCode::BrowserUnavailable,
+            RiptideError::BrowserOperation(_) | RiptideError::Navigation(_) => {
+                ErrorCode::BrowserFailed
+            }
+            RiptideError::Extraction(_) => ErrorCode::ExtractionFailed,
+            RiptideError::Configuration(_) => ErrorCode::ConfigInvalid,
+            RiptideError::Network(_) => ErrorCode::FetchFailed,
+            RiptideError::Timeout(_) => ErrorCode::Timeout,
+            RiptideError::Parse(_) | RiptideError::Json(_) => ErrorCode::ParseFailed,
+            RiptideError::InvalidUrl(_) => ErrorCode::InvalidUrl,
+            RiptideError::Io(_) | RiptideError::Storage(_) | RiptideError::DatabaseError(_) => {
+                ErrorCode::StorageFailed
+            }
+            RiptideError::Cache(_) | RiptideError::CacheError(_) => ErrorCode::CacheUnavailable,
+            RiptideError::Pool(_) => ErrorCode::ResourceExhausted,
+            RiptideError::ValidationError(_) => ErrorCode::InvalidRequest,
+            RiptideError::SerializationError(_) => ErrorCode::Internal,
+            RiptideError::NotFound(_) => ErrorCode::NotFound,
+            RiptideError::AlreadyExists(_) => ErrorCode::AlreadyExists,
+            RiptideError::PermissionDenied(_) => ErrorCode::PermissionDenied,
+            RiptideError::RateLimitExceeded { .. } => ErrorCode::RateLimited,
+            RiptideError::CircuitBreakerOpen(_) => ErrorCode::CircuitOpen,
+            RiptideError::ResourceExhausted { .. } => ErrorCode::ResourceExhausted,
+            RiptideError::NotImplemented(_) => ErrorCode::NotImplemented,
+            RiptideError::Custom(_) => ErrorCode::Internal,
+            RiptideError::Other(err) => err.error_code(),
+        }
+    }
+}
+
+impl HasErrorCode for StreamingError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            StreamingError::ConnectionFailed { .. }
+            | StreamingError::ProcessingFailed { .. }
+            | StreamingError::InvalidState { .. }
+            | StreamingError::ProtocolError { .. } => ErrorCode::StreamFailed,
+            StreamingError::BufferOverflow { .. } => ErrorCode::ResourceExhausted,
+            StreamingError::Timeout { .. } => ErrorCode::Timeout,
+            StreamingError::Cancelled { .. } => ErrorCode::Cancelled,
+            StreamingError::SerializationError { .. } => ErrorCode::Internal,
+            StreamingError::ConfigError { .. } => ErrorCode::ConfigInvalid,
+        }
+    }
+}
+
+impl HasErrorCode for StrategyError {
+    fn error_code(&self) -> ErrorCode {
+        match self {
+            StrategyError::CssSelectorFailed { .. }
+            | StrategyError::WasmExecutionFailed { .. }
+            | StrategyError::JsonLdNotFound { .. }
+            | StrategyError::StrategyFailed { .. } => ErrorCode::ExtractionFailed,
+            StrategyError::LlmTimeout { .. } => ErrorCode::LlmFailed,
+            StrategyError::LlmCircuitBreakerOpen { .. } => ErrorCode::CircuitOpen,
+            StrategyError::BrowserNavigationFailed { .. } => ErrorCode::BrowserFailed,
+            StrategyError::RegexPatternInvalid { .. } => ErrorCode::ConfigInvalid,
+            StrategyError::IcsParsingFailed { .. } => ErrorCode::ParseFailed,
+        }
+    }
+}
+
+/// Finds the first error in the chain with a known code
+///
+/// Only error types defined in this crate are recognised; crates with their
+/// own error types should downcast those first.
+impl HasErrorCode for anyhow::Error {
+    fn error_code(&self) -> ErrorCode {
+        self.chain()
+            .find_map(|err| {
+                if let Some(err) = err.downcast_ref::<RiptideError>() {
+                    Some(err.error_code())
+                } else if let Some(err) = err.downcast_ref::<StreamingError>() {
+                    Some(err.error_code())
+                } else {
+                    err.downcast_ref::<StrategyError>()
+                        .map(HasErrorCode::error_code)
+                }
+            })
+            .unwrap_or(ErrorCode::Internal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_codes_are_unique_and_prefixed() {
+        let codes: HashSet<&str> = ErrorCode::ALL.iter().map(|c| c.as_str()).collect();
+        assert_eq!(codes.len(), ErrorCode::ALL.len());
+        assert!(codes.iter().all(|c| c.starts_with("RIPTIDE_")));
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let json = serde_json::to_string(&ErrorCode::FetchTimeout).unwrap();
+        assert_eq!(json, "\"RIPTIDE_FETCH_TIMEOUT\"");
+        let code: ErrorCode = serde_json::from_str(&json).unwrap();
+        assert_eq!(code, ErrorCode::FetchTimeout);
+        assert!(serde_json::from_str::<ErrorCode>("\"FETCH_TIMEOUT\"").is_err());
+    }
+
+    #[test]
+    fn test_riptide_error_codes() {
+        assert_eq!(
+            RiptideError::Network("reset".to_string()).error_code(),
+            ErrorCode::FetchFailed
+        );
+        assert_eq!(
+            RiptideError::Timeout(1000).error_code().category(),
+            ErrorCategory::Unavailable
+        );
+        assert!(RiptideError::Timeout(1000).error_code().is_retryable());
+    }
+
+    #[test]
+    fn test_anyhow_chain_lookup() {
+        let err = anyhow::Error::new(RiptideError::NotFound("job".to_string()))
+            .context("Failed to load job");
+        assert_eq!(err.error_code(), ErrorCode::NotFound);
+        assert_eq!(anyhow::anyhow!("boom").error_code(), ErrorCode::Internal);
+
+        let trip: anyhow::Error = RiptideError::ResourceExhausted {
+            resource: "wasm_fuel".to_string(),
+            limit: 1_000_000,
+        }
+        .into();
+        assert_eq!(trip.error_code(), ErrorCode::ResourceExhausted);
+        assert_eq!(trip.to_string(), "wasm_fuel limit of 1000000 exhausted");
+    }
+}
@@ -4,12 +4,15 @@
 //! - `RiptideError`: General framework errors
 //! - `StrategyError`: Strategy-specific extraction errors with rich context
 //! - `StreamingError`: Streaming-specific errors (Sprint 4.3)
+//! - `ErrorCode`: Machine-readable error codes shared by all of the above
 
+pub mod codes;
 pub mod riptide_error;
 pub mod strategy_error;
 pub mod streaming;
 
 // Re-export for convenience
+pub use codes::{ErrorCategory, ErrorCode, HasErrorCode};
 pub use riptide_error::{Result, RiptideError};
 pub use strategy_error::StrategyError;
 pub use streaming::{ErrorSeverity, StreamingError};
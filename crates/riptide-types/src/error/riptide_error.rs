@@ -106,6 +106,15 @@ pub enum RiptideError {
     #[error("Circuit breaker is open: {0}")]
     CircuitBreakerOpen(String),
 
+    /// An execution limit was reached, such as WASM fuel or CPU time
+    #[error("{resource} limit of {limit} exhausted")]
+    ResourceExhausted {
+        /// Limited resource, e.g. "wasm_fuel" or "cpu_time_ms"
+        resource: String,
+        /// Configured limit, in the resource's unit
+        limit: u64,
+    },
+
     /// Operation not implemented
     #[error("Operation not implemented: {0}")]
     NotImplemented(String),
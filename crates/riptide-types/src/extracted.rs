@@ -1,6 +1,15 @@
 //! Extracted content types and quality metrics
 
+use crate::feed::{DocumentFeed, FeedLink};
+use crate::language::LanguageSpan;
+use crate::media::{EmbeddedMedia, ImageMetadata};
+use crate::ports::{ExperimentTag, GeoLocation};
+use crate::quality::ExtractionQualityReport;
+use crate::references::Reference;
+use crate::structured_data::StructuredDataItem;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 
 /// Basic extracted document for core orchestration
@@ -14,6 +23,9 @@ pub struct BasicExtractedDoc {
     pub byline: Option<String>,
     pub published_iso: Option<String>,
     pub markdown: Option<String>,
+    /// Sanitized reader-mode HTML of the main content, for re-rendering
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clean_html: Option<String>,
     pub media: Vec<String>,
     pub language: Option<String>,
     pub reading_time: Option<u32>,
@@ -25,6 +37,48 @@ pub struct BasicExtractedDoc {
     pub html: Option<String>,
     /// Parser metadata for observability (optional)
     pub parser_metadata: Option<ParserMetadata>,
+    /// Which engine and version produced this document (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<ExtractionProvenance>,
+    /// Translation of `title` and `text`, whose language stays in `language`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub translation: Option<DocumentTranslation>,
+    /// schema.org entities from JSON-LD, Microdata and RDFa markup
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub structured_data: Vec<StructuredDataItem>,
+    /// Named entities, keywords and topics found in `text`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<TextAnnotations>,
+    /// Entries, when this document is an RSS, Atom or JSON Feed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub feed: Option<DocumentFeed>,
+    /// Feeds the page advertises with `<link rel="alternate">`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub feed_links: Vec<FeedLink>,
+    /// Taxonomy categories and sentiment, with per-tag confidence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classification: Option<DocumentClassification>,
+    /// Images from `media` downloaded, measured and perceptually hashed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub media_assets: Vec<MediaAsset>,
+    /// Video and audio the page embeds, with duration, thumbnail, stream
+    /// manifests and captions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub embedded_media: Vec<EmbeddedMedia>,
+    /// Images with alt text, caption and responsive sources; content type
+    /// and size are filled in when images are probed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<ImageMetadata>,
+    /// Bibliography entries, footnotes and DOI/arXiv citation links
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<Reference>,
+    /// Runs of `text` in one language, for pages mixing languages that
+    /// `language` alone cannot describe
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub language_spans: Vec<LanguageSpan>,
+    /// Signals and weights behind `quality_score`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quality_report: Option<ExtractionQualityReport>,
 }
 
 /// Alias for ExtractedDoc to maintain compatibility
@@ -181,3 +235,399 @@ pub struct ParserMetadata {
     /// Error message if primary parser failed (when fallback occurred)
     pub primary_error: Option<String>,
 }
+
+/// Provenance record for an extracted document
+///
+/// Persisted alongside the document so consumers can tell which engine and
+/// version produced it, and re-extract selectively after an upgrade.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractionProvenance {
+    /// Version of the extraction engine that produced the document
+    pub extractor_version: String,
+    /// SHA-256 of the WASM component, when WASM extraction was used
+    pub component_hash: Option<String>,
+    /// Extraction strategy: "wasm", "native", "pdf", "raw", "reprocess"
+    pub strategy: String,
+    /// Gate decision that routed the page: "raw", "probes_first", "headless", "pdf"
+    pub gate_decision: Option<String>,
+    /// When the source content was fetched
+    pub fetched_at: DateTime<Utc>,
+    /// SHA-256 over the source HTTP response headers (see [`Self::hash_headers`])
+    pub source_headers_hash: Option<String>,
+    /// Country and ASN of the server the source was fetched from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_location: Option<GeoLocation>,
+    /// A/B experiment and arm the document was produced under
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experiment: Option<ExperimentTag>,
+    /// Rewrite rule that redirected the fetch to another URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url_rewrite: Option<UrlRewrite>,
+}
+
+/// URL rewrite applied before a fetch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UrlRewrite {
+    /// URL the crawl asked for
+    pub original_url: String,
+    /// URL that was actually fetched
+    pub fetched_url: String,
+    /// Name of the rule that rewrote it
+    pub rule: String,
+}
+
+impl ExtractionProvenance {
+    /// Create a provenance record fetched now
+    pub fn new(extractor_version: impl Into<String>, strategy: impl Into<String>) -> Self {
+        Self {
+            extractor_version: extractor_version.into(),
+            component_hash: None,
+            strategy: strategy.into(),
+            gate_decision: None,
+            fetched_at: Utc::now(),
+            source_headers_hash: None,
+            server_location: None,
+            experiment: None,
+            url_rewrite: None,
+        }
+    }
+
+    /// Set the WASM component hash
+    pub fn with_component_hash(mut self, hash: Option<String>) -> Self {
+        self.component_hash = hash;
+        self
+    }
+
+    /// Set the gate decision
+    pub fn with_gate_decision(mut self, decision: impl Into<String>) -> Self {
+        self.gate_decision = Some(decision.into());
+        self
+    }
+
+    /// Set the fetch timestamp
+    pub fn with_fetched_at(mut self, fetched_at: DateTime<Utc>) -> Self {
+        self.fetched_at = fetched_at;
+        self
+    }
+
+    /// Record the hash of the source response headers
+    pub fn with_source_headers(mut self, headers: &HashMap<String, String>) -> Self {
+        self.source_headers_hash = Some(Self::hash_headers(headers));
+        self
+    }
+
+    /// Record the GeoIP location of the source server
+    pub fn with_server_location(mut self, location: Option<GeoLocation>) -> Self {
+        self.server_location = location;
+        self
+    }
+
+    /// Tag the document with the experiment it was produced under
+    pub fn with_experiment(mut self, experiment: Option<ExperimentTag>) -> Self {
+        self.experiment = experiment;
+        self
+    }
+
+    /// Record the rewrite that redirected the fetch
+    pub fn with_url_rewrite(mut self, rewrite: Option<UrlRewrite>) -> Self {
+        self.url_rewrite = rewrite;
+        self
+    }
+
+    /// Hash response headers independent of order and name casing
+    pub fn hash_headers(headers: &HashMap<String, String>) -> String {
+        let mut entries: Vec<(String, &str)> = headers
+            .iter()
+            .map(|(name, value)| (name.to_ascii_lowercase(), value.trim()))
+            .collect();
+        entries.sort();
+
+        let mut hasher = Sha256::new();
+        for (name, value) in entries {
+            hasher.update(name.as_bytes());
+            hasher.update(b":");
+            hasher.update(value.as_bytes());
+            hasher.update(b"\n");
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// SHA-256 of a WASM component binary
+    pub fn hash_component(bytes: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(bytes))
+    }
+
+    /// Whether the document was produced by a different engine than the current one
+    ///
+    /// A component hash mismatch only counts when both sides recorded one.
+    pub fn is_stale(&self, extractor_version: &str, component_hash: Option<&str>) -> bool {
+        if self.extractor_version != extractor_version {
+            return true;
+        }
+        match (self.component_hash.as_deref(), component_hash) {
+            (Some(recorded), Some(current)) => recorded != current,
+            _ => false,
+        }
+    }
+}
+
+/// Machine translation of an extracted document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentTranslation {
+    /// Target language tag (BCP 47, e.g. "en" or "pt-BR")
+    pub language: String,
+    /// Language the text was translated from, when known
+    pub source_language: Option<String>,
+    pub title: Option<String>,
+    pub text: String,
+    /// Whether `text` covers only the beginning of the document
+    #[serde(default)]
+    pub truncated: bool,
+    /// Translator that produced the text, e.g. "llm:gpt-4o-mini"
+    pub translator: String,
+    pub translated_at: DateTime<Utc>,
+}
+
+impl DocumentTranslation {
+    /// Whether two language tags name the same language, ignoring region
+    /// and script subtags ("en-GB" matches "en")
+    pub fn same_language(a: &str, b: &str) -> bool {
+        let primary = |tag: &str| {
+            tag.trim()
+                .split(['-', '_'])
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase()
+        };
+        !primary(a).is_empty() && primary(a) == primary(b)
+    }
+}
+
+/// Named entities, keywords and topics of an extracted document, for
+/// faceted search
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TextAnnotations {
+    /// Entities, most mentioned first
+    #[serde(default)]
+    pub entities: Vec<NamedEntity>,
+    /// Keywords and key phrases, most relevant first
+    #[serde(default)]
+    pub keywords: Vec<Keyword>,
+    /// Broad subjects, e.g. "technology" or "sports"
+    #[serde(default)]
+    pub topics: Vec<String>,
+    /// Annotator that produced them: "rules" or e.g. "llm:gpt-4o-mini"
+    pub annotator: String,
+}
+
+impl TextAnnotations {
+    /// Entity names of one kind, for facet fields
+    pub fn entities_of(&self, kind: EntityKind) -> impl Iterator<Item = &str> {
+        self.entities
+            .iter()
+            .filter(move |entity| entity.kind == kind)
+            .map(|entity| entity.text.as_str())
+    }
+}
+
+/// An entity mentioned in a document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamedEntity {
+    /// Canonical surface form, e.g. the full name of a person
+    pub text: String,
+    pub kind: EntityKind,
+    /// Number of mentions, counting short forms such as a surname
+    pub mentions: u32,
+}
+
+/// Category of a [`NamedEntity`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+    Person,
+    Organization,
+    Location,
+    Date,
+    Money,
+    Other,
+}
+
+impl EntityKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Person => "person",
+            Self::Organization => "organization",
+            Self::Location => "location",
+            Self::Date => "date",
+            Self::Money => "money",
+            Self::Other => "other",
+        }
+    }
+
+    /// Lenient parse of the labels NER tools and LLMs use ("PER", "ORG",
+    /// "GPE", "place", ...), falling back to [`EntityKind::Other`]
+    pub fn from_label(label: &str) -> Self {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "person" | "per" | "people" => Self::Person,
+            "organization" | "organisation" | "org" | "company" => Self::Organization,
+            "location" | "loc" | "gpe" | "place" | "country" | "city" => Self::Location,
+            "date" | "time" => Self::Date,
+            "money" | "price" | "amount" => Self::Money,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// A keyword or key phrase with its relevance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keyword {
+    /// Lowercased term, e.g. "machine learning"
+    pub term: String,
+    /// Relevance from 0.0 to 1.0, relative to the most relevant keyword
+    pub score: f32,
+}
+
+/// Content categories and sentiment assigned by a classifier
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DocumentClassification {
+    /// Categories from the configured taxonomy, most confident first
+    #[serde(default)]
+    pub categories: Vec<CategoryTag>,
+    pub sentiment: Option<SentimentTag>,
+    /// Classifier that produced them, e.g. "llm:gpt-4o-mini"
+    pub classifier: String,
+}
+
+/// A taxonomy category with the classifier's confidence in it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CategoryTag {
+    pub label: String,
+    /// From 0.0 to 1.0
+    pub confidence: f32,
+}
+
+/// Overall tone of a document with the classifier's confidence in it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SentimentTag {
+    pub label: Sentiment,
+    /// From 0.0 to 1.0
+    pub confidence: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sentiment {
+    Positive,
+    Neutral,
+    Negative,
+}
+
+impl Sentiment {
+    pub const ALL: [Sentiment; 3] = [Self::Positive, Self::Neutral, Self::Negative];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Positive => "positive",
+            Self::Neutral => "neutral",
+            Self::Negative => "negative",
+        }
+    }
+
+    /// Lenient parse of sentiment labels ("POS", "negative", "mixed", ...);
+    /// `None` for anything unrecognized
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "positive" | "pos" => Some(Self::Positive),
+            "neutral" | "neu" | "mixed" => Some(Self::Neutral),
+            "negative" | "neg" => Some(Self::Negative),
+            _ => None,
+        }
+    }
+}
+
+/// An image from `ExtractedDoc::media` fetched by the media pipeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MediaAsset {
+    /// Absolute source URL
+    pub url: String,
+    pub content_type: String,
+    pub size_bytes: u64,
+    pub width: u32,
+    pub height: u32,
+    /// 64-bit difference hash (dHash) as 16 hex digits; similar images
+    /// differ in few bits
+    pub phash: String,
+    /// Durable copy in blob storage, when storing is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stored_url: Option<String>,
+    /// First image seen with the same or a near-identical hash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicate_of: Option<MediaDuplicate>,
+}
+
+/// Earlier occurrence of a duplicated image, possibly on another page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaDuplicate {
+    /// Image URL
+    pub url: String,
+    /// Page the image was found on
+    pub page_url: String,
+    /// Differing hash bits, 0 for an identical hash
+    pub distance: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_hash_ignores_order_and_case() {
+        let mut a = HashMap::new();
+        a.insert("Content-Type".to_string(), "text/html".to_string());
+        a.insert("ETag".to_string(), "\"abc\"".to_string());
+        let mut b = HashMap::new();
+        b.insert("etag".to_string(), "\"abc\"".to_string());
+        b.insert("content-type".to_string(), "text/html ".to_string());
+
+        assert_eq!(
+            ExtractionProvenance::hash_headers(&a),
+            ExtractionProvenance::hash_headers(&b)
+        );
+    }
+
+    #[test]
+    fn test_is_stale() {
+        let provenance = ExtractionProvenance::new("0.9.0", "wasm")
+            .with_component_hash(Some("aaa".to_string()))
+            .with_gate_decision("raw");
+
+        assert!(!provenance.is_stale("0.9.0", Some("aaa")));
+        assert!(!provenance.is_stale("0.9.0", None));
+        assert!(provenance.is_stale("0.9.0", Some("bbb")));
+        assert!(provenance.is_stale("0.10.0", Some("aaa")));
+    }
+
+    #[test]
+    fn test_provenance_optional_in_serialized_doc() {
+        let doc: BasicExtractedDoc = serde_json::from_str(
+            r#"{"url":"https://example.com","title":null,"text":"","quality_score":null,
+                "links":[],"byline":null,"published_iso":null,"markdown":null,"media":[],
+                "language":null,"reading_time":null,"word_count":null,"categories":[],
+                "site_name":null,"description":null,"html":null,"parser_metadata":null}"#,
+        )
+        .unwrap();
+        assert!(doc.provenance.is_none());
+        assert!(doc.translation.is_none());
+        let json = serde_json::to_string(&doc).unwrap();
+        assert!(!json.contains("provenance"));
+        assert!(!json.contains("translation"));
+    }
+
+    #[test]
+    fn test_same_language_ignores_subtags() {
+        assert!(DocumentTranslation::same_language("en-GB", "en"));
+        assert!(DocumentTranslation::same_language("pt_BR", "PT"));
+        assert!(!DocumentTranslation::same_language("de", "en"));
+        assert!(!DocumentTranslation::same_language("", ""));
+    }
+}
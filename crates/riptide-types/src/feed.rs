@@ -0,0 +1,89 @@
+//! Syndication feeds: RSS 2.0, Atom and JSON Feed
+//!
+//! A fetched feed becomes one [`ExtractedDoc`] for the feed itself, whose
+//! [`DocumentFeed`] lists an [`ExtractedDoc`] per entry. HTML pages keep the
+//! feeds they advertise as [`FeedLink`]s, so crawls can switch to them.
+
+use crate::extracted::ExtractedDoc;
+use serde::{Deserialize, Serialize};
+
+/// Syndication format of a feed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedFormat {
+    /// RSS 2.0 (and the compatible 0.9x versions)
+    Rss,
+    Atom,
+    /// JSON Feed 1.x
+    JsonFeed,
+}
+
+impl FeedFormat {
+    /// Format announced by a `Content-Type` or `<link type>` value
+    ///
+    /// Generic XML and JSON types are not enough to tell a feed apart; the
+    /// body has to be sniffed for those.
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let essence = media_type.split(';').next()?.trim().to_ascii_lowercase();
+        match essence.as_str() {
+            "application/rss+xml" | "application/rdf+xml" => Some(Self::Rss),
+            "application/atom+xml" => Some(Self::Atom),
+            "application/feed+json" | "application/json+feed" => Some(Self::JsonFeed),
+            _ => None,
+        }
+    }
+
+    /// Registered media type of the format
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            Self::Rss => "application/rss+xml",
+            Self::Atom => "application/atom+xml",
+            Self::JsonFeed => "application/feed+json",
+        }
+    }
+}
+
+/// Entries of a parsed feed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentFeed {
+    pub format: FeedFormat,
+    /// One document per entry, in feed order, with the entry's link as
+    /// `url` and its summary as `description`
+    #[serde(default)]
+    pub entries: Vec<ExtractedDoc>,
+}
+
+/// Feed advertised by a page with `<link rel="alternate">`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedLink {
+    /// Absolute feed URL
+    pub url: String,
+    pub format: FeedFormat,
+    pub title: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_media_type() {
+        assert_eq!(
+            FeedFormat::from_media_type("application/rss+xml; charset=utf-8"),
+            Some(FeedFormat::Rss)
+        );
+        assert_eq!(
+            FeedFormat::from_media_type("Application/Atom+XML"),
+            Some(FeedFormat::Atom)
+        );
+        assert_eq!(
+            FeedFormat::from_media_type("application/feed+json"),
+            Some(FeedFormat::JsonFeed)
+        );
+        assert_eq!(FeedFormat::from_media_type("text/xml"), None);
+        assert_eq!(
+            serde_json::to_value(FeedFormat::JsonFeed).unwrap(),
+            "json_feed"
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Shared store of content fingerprints
+//!
+//! One [`ContentFingerprint`] is kept per URL on top of the [`CacheStorage`]
+//! port. Every component that revalidates pages reads and writes the same
+//! entry: differential API crawls (`changed_since`), spider refresh crawls
+//! and the revalidation of expired cached documents. A page fetched by any
+//! of them gives the others its current validators and content hash.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_types::ports::InMemoryCache;
+//! use riptide_types::{ContentFingerprint, FingerprintStore};
+//! use std::sync::Arc;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> riptide_types::error::Result<()> {
+//! let store = FingerprintStore::new(Arc::new(InMemoryCache::new()));
+//! let fingerprint = ContentFingerprint::observe(None, b"<html>", None, None, chrono::Utc::now());
+//! store.save("https://example.com/", &fingerprint).await?;
+//! assert_eq!(store.get("https://example.com/").await?, Some(fingerprint));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::conditional::ContentFingerprint;
+use crate::error::Result as RiptideResult;
+use crate::ports::CacheStorage;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Content fingerprints by URL
+#[derive(Clone)]
+pub struct FingerprintStore {
+    cache: Arc<dyn CacheStorage>,
+    ttl: Option<Duration>,
+}
+
+impl FingerprintStore {
+    /// Create a store over `cache`, keeping fingerprints forever
+    pub fn new(cache: Arc<dyn CacheStorage>) -> Self {
+        Self { cache, ttl: None }
+    }
+
+    /// Expire fingerprints `ttl` after they were last written
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Load the fingerprint of a URL
+    pub async fn get(&self, url: &str) -> RiptideResult<Option<ContentFingerprint>> {
+        self.cache
+            .get(&Self::key(url))
+            .await?
+            .map(|bytes| serde_json::from_slice(&bytes))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Store the fingerprint of a URL
+    pub async fn save(&self, url: &str, fingerprint: &ContentFingerprint) -> RiptideResult<()> {
+        let bytes = serde_json::to_vec(fingerprint)?;
+        self.cache.set(&Self::key(url), &bytes, self.ttl).await
+    }
+
+    fn key(url: &str) -> String {
+        format!(
+            "riptide:v1:fingerprint:{}",
+            ContentFingerprint::hash_content(url.as_bytes())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::InMemoryCache;
+    use chrono::Utc;
+
+    #[tokio::test]
+    async fn test_fingerprints_are_shared_by_url() {
+        let cache: Arc<dyn CacheStorage> = Arc::new(InMemoryCache::new());
+        let writer = FingerprintStore::new(cache.clone()).with_ttl(Duration::from_secs(60));
+        let reader = FingerprintStore::new(cache);
+
+        let fingerprint = ContentFingerprint::observe(
+            None,
+            b"body",
+            Some("\"v1\"".to_string()),
+            None,
+            Utc::now(),
+        );
+        writer
+            .save("https://example.com/a", &fingerprint)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            reader.get("https://example.com/a").await.unwrap(),
+            Some(fingerprint)
+        );
+        assert_eq!(reader.get("https://example.com/b").await.unwrap(), None);
+    }
+}
@@ -4,6 +4,7 @@
 //! and riptide-facade (orchestration layer). Living in riptide-types ensures
 //! one-way dependency: api → facade → types (no cycles).
 
+use crate::config::PipelineHints;
 use serde::{Deserialize, Serialize};
 
 // ============================================================================
@@ -21,6 +22,9 @@ pub struct ExtractRequest {
     /// Extraction options
     #[serde(default)]
     pub options: ExtractOptions,
+    /// Per-request pipeline tuning, checked against the tenant's policy
+    #[serde(default)]
+    pub pipeline_hints: Option<PipelineHints>,
 }
 
 /// Extraction options
@@ -148,7 +152,7 @@ pub enum ResultMode {
     Urls,
     /// Return full page objects with content
     Pages,
-    /// Stream results as NDJSON (not yet implemented)
+    /// Stream pages as NDJSON (or SSE) while the crawl runs
     Stream,
     /// Store results for async retrieval (not yet implemented)
     Store,
@@ -0,0 +1,398 @@
+//! Language detection over extracted text
+//!
+//! Markup (`<html lang>`, `Content-Language`) names one language per page,
+//! which misdescribes mixed pages such as an English site frame around a
+//! Japanese article. [`detect_language_spans`] detects the language of each
+//! text block (line) and merges neighbouring blocks of one language into
+//! [`LanguageSpan`]s, each with the detector's confidence.
+//!
+//! The detector needs no model, so it also runs on PDF text:
+//!
+//! - scripts used by one main language (Hangul, Greek, Thai, Hebrew, ...)
+//!   are told apart by code point; Han text is Japanese when it contains
+//!   kana, Cyrillic and Arabic text by a few letters unique to Ukrainian and
+//!   Persian
+//! - Latin-script text is scored against stopword lists for English,
+//!   German, French, Spanish, Italian, Portuguese and Dutch, and is left
+//!   undetected in other languages
+//!
+//! Blocks too short to tell, such as menu labels, join the span before them.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_types::language::detect_language_spans;
+//!
+//! let text = "Sign in to read the full story and the rest of our archive.\n\n\
+//!             東京の桜は今年、例年より早く満開になりました。週末には多くの人が公園を訪れています。";
+//!
+//! let spans = detect_language_spans(text);
+//! assert_eq!(spans.len(), 2);
+//! assert_eq!(spans[0].language, "en");
+//! assert_eq!(spans[1].language, "ja");
+//! assert!(text[spans[1].start..spans[1].end].starts_with("東京"));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Letters a block needs before its language is guessed
+const MIN_LETTERS: usize = 12;
+
+/// Stopword matches Latin-script text needs before its language is guessed
+const MIN_STOPWORDS: usize = 2;
+
+/// Share of words that are stopwords in ordinary prose; text with fewer is
+/// guessed with less confidence
+const TYPICAL_STOPWORD_SHARE: f32 = 0.25;
+
+/// Frequent short words of the Latin-script languages told apart
+const STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "of", "to", "is", "in", "that", "it", "was", "for", "with", "are",
+            "this", "you", "have", "on", "not", "be", "by", "from", "at", "which", "our",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "und", "das", "ist", "nicht", "mit", "ein", "eine", "zu", "den", "von",
+            "auch", "sich", "auf", "für", "ich", "dem", "des", "wir", "sind", "wird",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "et", "des", "est", "une", "un", "du", "dans", "que", "pour", "pas",
+            "qui", "sur", "au", "avec", "sont", "ce", "nous", "aux", "été",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "la", "los", "las", "y", "que", "es", "en", "una", "un", "por", "con", "para",
+            "del", "se", "no", "su", "al", "lo", "como", "está", "pero",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il", "di", "che", "e", "la", "per", "una", "sono", "non", "con", "del", "della",
+            "gli", "le", "un", "è", "nel", "anche", "questo", "alla", "più",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "o", "a", "os", "as", "de", "que", "não", "em", "um", "uma", "para", "com", "do", "da",
+            "é", "se", "mais", "por", "são", "ao", "foi",
+        ],
+    ),
+    (
+        "nl",
+        &[
+            "de", "het", "een", "en", "van", "is", "dat", "niet", "op", "te", "zijn", "met",
+            "voor", "ook", "er", "aan", "wordt", "hij", "naar", "bij",
+        ],
+    ),
+];
+
+/// Language of a piece of text with the detector's confidence in it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LanguageGuess {
+    /// ISO 639-1 code, e.g. "en" or "ja"
+    pub language: &'static str,
+    /// From 0.0 to 1.0
+    pub confidence: f32,
+}
+
+/// Run of a document's `text` detected as one language
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LanguageSpan {
+    /// ISO 639-1 code, e.g. "en" or "ja"
+    pub language: String,
+    /// Byte offset in `text` where the run starts
+    pub start: usize,
+    /// Byte offset in `text` where the run ends, exclusive
+    pub end: usize,
+    /// Detector confidence, from 0.0 to 1.0
+    pub confidence: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Script {
+    Latin,
+    Kana,
+    Han,
+    Hangul,
+    Cyrillic,
+    Greek,
+    Arabic,
+    Hebrew,
+    Thai,
+    Devanagari,
+    Other,
+}
+
+impl Script {
+    const COUNT: usize = 11;
+
+    /// Script of a letter; `None` for anything else
+    fn of(c: char) -> Option<Self> {
+        if !c.is_alphabetic() {
+            return None;
+        }
+        Some(match u32::from(c) {
+            0x0000..=0x024F | 0x1E00..=0x1EFF => Self::Latin,
+            0x3040..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => Self::Kana,
+            0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => Self::Han,
+            0x1100..=0x11FF | 0x3130..=0x318F | 0xAC00..=0xD7AF => Self::Hangul,
+            0x0400..=0x04FF => Self::Cyrillic,
+            0x0370..=0x03FF => Self::Greek,
+            0x0600..=0x06FF | 0x0750..=0x077F => Self::Arabic,
+            0x0590..=0x05FF => Self::Hebrew,
+            0x0E00..=0x0E7F => Self::Thai,
+            0x0900..=0x097F => Self::Devanagari,
+            _ => Self::Other,
+        })
+    }
+}
+
+/// Guess the language of `text`
+///
+/// Returns `None` for text too short to tell or in a language the detector
+/// does not know.
+// Safe conversion: letter counts stay far below 2^24
+#[allow(clippy::cast_precision_loss)]
+pub fn detect_language(text: &str) -> Option<LanguageGuess> {
+    let mut counts = [0usize; Script::COUNT];
+    for script in text.chars().filter_map(Script::of) {
+        counts[script as usize] += 1;
+    }
+    let count = |script: Script| counts[script as usize];
+    let letters: usize = counts.iter().sum();
+    if letters < MIN_LETTERS {
+        return None;
+    }
+
+    // Kana and Han are counted together, as Japanese mixes both
+    let cjk = count(Script::Kana) + count(Script::Han);
+    let (script, n) = [
+        Script::Latin,
+        Script::Hangul,
+        Script::Cyrillic,
+        Script::Greek,
+        Script::Arabic,
+        Script::Hebrew,
+        Script::Thai,
+        Script::Devanagari,
+        Script::Other,
+    ]
+    .into_iter()
+    .map(|script| (script, count(script)))
+    .chain([(Script::Han, cjk)])
+    .max_by_key(|&(_, n)| n)?;
+    let script_share = n as f32 / letters as f32;
+    let has_any = |chars: &[char]| text.chars().any(|c| chars.contains(&c));
+
+    let language = match script {
+        Script::Latin => return latin_language(text, script_share),
+        Script::Han if count(Script::Kana) * 10 >= cjk => "ja",
+        Script::Han => "zh",
+        Script::Hangul => "ko",
+        Script::Cyrillic if has_any(&['і', 'ї', 'є', 'ґ', 'І', 'Ї', 'Є', 'Ґ']) => "uk",
+        Script::Cyrillic => "ru",
+        Script::Greek => "el",
+        Script::Arabic if has_any(&['پ', 'چ', 'ژ', 'گ']) => "fa",
+        Script::Arabic => "ar",
+        Script::Hebrew => "he",
+        Script::Thai => "th",
+        Script::Devanagari => "hi",
+        Script::Kana | Script::Other => return None,
+    };
+    Some(LanguageGuess {
+        language,
+        confidence: script_share,
+    })
+}
+
+/// Split `text` into runs of one language
+///
+/// Each line is a block. Offsets are byte offsets into `text`; a span's
+/// confidence is the mean of its blocks', weighted by block length.
+// Safe conversion: block lengths stay far below 2^24
+#[allow(clippy::cast_precision_loss)]
+pub fn detect_language_spans(text: &str) -> Vec<LanguageSpan> {
+    let mut spans: Vec<LanguageSpan> = Vec::new();
+    // Length-weighted confidence sum and detected length of each span
+    let mut weights: Vec<(f32, usize)> = Vec::new();
+    // Start of undetected blocks before the first span
+    let mut leading_start = None;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let block_start = offset + (line.len() - line.trim_start().len());
+        offset += line.len();
+        let block = line.trim();
+        if block.is_empty() {
+            continue;
+        }
+        let block_end = block_start + block.len();
+
+        let Some(guess) = detect_language(block) else {
+            match spans.last_mut() {
+                Some(span) => span.end = block_end,
+                None => {
+                    leading_start.get_or_insert(block_start);
+                }
+            }
+            continue;
+        };
+        match (spans.last_mut(), weights.last_mut()) {
+            (Some(span), Some(weight)) if span.language == guess.language => {
+                span.end = block_end;
+                weight.0 += guess.confidence * block.len() as f32;
+                weight.1 += block.len();
+            }
+            _ => {
+                spans.push(LanguageSpan {
+                    language: guess.language.to_string(),
+                    start: leading_start.take().unwrap_or(block_start),
+                    end: block_end,
+                    confidence: 0.0,
+                });
+                weights.push((guess.confidence * block.len() as f32, block.len()));
+            }
+        }
+    }
+
+    for (span, (sum, len)) in spans.iter_mut().zip(weights) {
+        span.confidence = sum / len as f32;
+    }
+    spans
+}
+
+/// Language covering most of the text of `spans`
+pub fn dominant_language(spans: &[LanguageSpan]) -> Option<String> {
+    let mut coverage: HashMap<&str, usize> = HashMap::new();
+    for span in spans {
+        *coverage.entry(span.language.as_str()).or_default() += span.end - span.start;
+    }
+    coverage
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+        .map(|(language, _)| language.to_string())
+}
+
+/// Score Latin-script `text` against the stopword lists
+// Safe conversion: word counts stay far below 2^24
+#[allow(clippy::cast_precision_loss)]
+fn latin_language(text: &str, script_share: f32) -> Option<LanguageGuess> {
+    let mut hits = [0usize; STOPWORDS.len()];
+    let mut words = 0usize;
+    let mut stopwords = 0usize;
+    for word in text.split(|c: char| !c.is_alphabetic()) {
+        if word.is_empty() {
+            continue;
+        }
+        words += 1;
+        let word = word.to_lowercase();
+        let mut matched = false;
+        for (hit, (_, list)) in hits.iter_mut().zip(STOPWORDS) {
+            if list.contains(&word.as_str()) {
+                *hit += 1;
+                matched = true;
+            }
+        }
+        stopwords += usize::from(matched);
+    }
+
+    // First listed language wins ties
+    let (best, best_hits) = hits
+        .iter()
+        .copied()
+        .enumerate()
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))?;
+    if best_hits < MIN_STOPWORDS {
+        return None;
+    }
+    let total: usize = hits.iter().sum();
+    let coverage = (stopwords as f32 / words as f32 / TYPICAL_STOPWORD_SHARE).min(1.0);
+    Some(LanguageGuess {
+        language: STOPWORDS[best].0,
+        confidence: best_hits as f32 / total as f32 * coverage * script_share,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_language() {
+        let cases = [
+            (
+                "The committee said that the report was published on Monday.",
+                "en",
+            ),
+            (
+                "Die Regierung hat mit den Ländern eine neue Regelung für das Jahr vereinbart.",
+                "de",
+            ),
+            (
+                "Le gouvernement a présenté les mesures dans une conférence de presse.",
+                "fr",
+            ),
+            (
+                "El ministro dijo que la reforma de las pensiones está lista para el debate.",
+                "es",
+            ),
+            (
+                "Het kabinet heeft de plannen voor het nieuwe jaar niet bekendgemaakt.",
+                "nl",
+            ),
+            ("東京の桜は今年、例年より早く満開になりました。", "ja"),
+            ("北京今年的春天来得比往年更早一些。", "zh"),
+            ("서울의 벚꽃은 올해 예년보다 일찍 피었습니다.", "ko"),
+            ("Правительство представило новый закон о бюджете.", "ru"),
+            ("Уряд представив новий закон про бюджет країни.", "uk"),
+        ];
+        for (text, language) in cases {
+            let guess = detect_language(text).unwrap();
+            assert_eq!(guess.language, language, "{}", text);
+            assert!(
+                guess.confidence > 0.3 && guess.confidence <= 1.0,
+                "{}",
+                text
+            );
+        }
+
+        assert!(detect_language("Home").is_none());
+        assert!(detect_language("Lorem ipsum dolor sit amet consectetur").is_none());
+    }
+
+    #[test]
+    fn test_spans_of_mixed_page() {
+        let text = "Home\nNews\nSign in to read the full story and the rest of our archive.\n\n\
+                    東京の桜は今年、例年より早く満開になりました。\n\
+                    ログイン\n\
+                    週末には多くの人が公園を訪れています。\n\n\
+                    Copyright 2026. All of the content on this site is protected.";
+
+        let spans = detect_language_spans(text);
+        let languages: Vec<_> = spans.iter().map(|s| s.language.as_str()).collect();
+        assert_eq!(languages, ["en", "ja", "en"]);
+        // Leading menu labels join the first span, the short label the
+        // Japanese one
+        assert_eq!(spans[0].start, 0);
+        assert!(text[spans[1].start..spans[1].end].contains("ログイン"));
+        assert!(text[spans[1].start..spans[1].end].ends_with("訪れています。"));
+        assert_eq!(spans[2].end, text.len());
+        assert!(spans.iter().all(|s| s.confidence > 0.5));
+
+        assert_eq!(dominant_language(&spans).as_deref(), Some("ja"));
+        assert!(detect_language_spans("").is_empty());
+    }
+}
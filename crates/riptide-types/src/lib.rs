@@ -38,44 +38,85 @@
 //! - `Clock`, `Entropy`, `CacheStorage`: Infrastructure abstractions
 
 // Public modules
+pub mod compliance;
 pub mod component;
 pub mod conditional;
 pub mod config;
+pub mod diff;
+pub mod dom_snapshot;
 pub mod error;
 pub mod extracted;
 pub mod extraction_method; // Facade-level extraction methods
 pub mod extractors;
+pub mod feed;
+pub mod fingerprints;
 pub mod http_types;
+pub mod language;
+pub mod media;
 pub mod pipeline;
 pub mod ports; // Port interfaces for hexagonal architecture
+pub mod quality;
+pub mod references;
+pub mod region;
 pub mod reliability; // Reliability configuration types (circuit breaker, retry)
 pub mod secrets;
+pub mod structured_data;
 pub mod traits;
 pub mod types;
+pub mod usage;
 
 // Re-export commonly used types at the crate root
+pub use compliance::{
+    ComplianceReport, CrawlDelayRecord, MetaRobotsExclusion, RateLimitEvent, RobotsSkip,
+    SitemapRecord,
+};
 pub use component::{ComponentId, ComponentMeta};
 pub use conditional::{
     format_http_date, generate_etag, generate_weak_etag, parse_http_date, validate_cache,
-    CacheValidation, ConditionalRequest, ConditionalResponse,
+    CacheValidation, ChangedSince, ConditionalRequest, ConditionalResponse, ContentFingerprint,
+};
+pub use config::{
+    ChunkingConfig, CrawlBudget, CrawlScope, EmbeddedMarkup, ExtractionMode, OutputFormat,
+    PipelineHints, RenderMode, TopicChunkingConfig,
+};
+pub use diff::{BlockChangeKind, ExtractionDiff, MetadataChange, TextBlockChange};
+pub use dom_snapshot::{
+    ComputedStyle, DomNode, DomSnapshot, ResourceKind, SnapshotResource, Viewport,
+    DOM_SNAPSHOT_VERSION,
 };
-pub use config::{ChunkingConfig, ExtractionMode, OutputFormat, RenderMode, TopicChunkingConfig};
-pub use error::{Result, RiptideError, StrategyError};
+pub use error::{ErrorCategory, ErrorCode, HasErrorCode, Result, RiptideError, StrategyError};
 pub use extracted::{
-    BasicExtractedDoc, ComponentInfo, ContentChunk, ExtractedContent, ExtractedDoc,
-    ExtractionQuality, ExtractionStats, HealthStatus, ParserMetadata,
+    BasicExtractedDoc, CategoryTag, ComponentInfo, ContentChunk, DocumentClassification,
+    DocumentTranslation, EntityKind, ExtractedContent, ExtractedDoc, ExtractionProvenance,
+    ExtractionQuality, ExtractionStats, HealthStatus, Keyword, MediaAsset, MediaDuplicate,
+    NamedEntity, ParserMetadata, Sentiment, SentimentTag, TextAnnotations, UrlRewrite,
 };
 pub use extraction_method::ExtractionMethod;
+pub use feed::{DocumentFeed, FeedFormat, FeedLink};
+pub use fingerprints::FingerprintStore;
 pub use http_types::{
     ContentMetadata, CrawledPage, ExtractOptions, ExtractRequest, ExtractResponse,
     ParserMetadataHttp, ResultMode, SearchQuery, SearchResponse, SearchResult, SpiderResultPages,
     SpiderResultStats, SpiderResultUrls,
 };
+pub use language::{LanguageGuess, LanguageSpan};
+pub use media::{
+    CaptionTrack, EmbeddedMedia, ImageMetadata, ImageSource, MediaKind, MediaMetadataSource,
+    StreamFormat, StreamManifest,
+};
 pub use pipeline::{
-    CombinedPipelineExecutor, GateDecisionStats, PipelineExecutor, PipelineResult,
-    PipelineRetryConfig, PipelineStats, StrategiesPipelineExecutor, StrategiesPipelineResult,
+    CacheStatus, CombinedPipelineExecutor, GateDecisionStats, PhaseTimings, PipelineExecutor,
+    PipelineResult, PipelineRetryConfig, PipelineStats, StrategiesPipelineExecutor,
+    StrategiesPipelineResult,
 };
+pub use quality::{ExtractionQualityReport, QualitySignal, QualitySignalKind};
+pub use references::{Reference, ReferenceKind};
+pub use region::{RegionAffinity, RegionPeer, RegionRouter, RoutingHint, DEFAULT_REGION};
 pub use reliability::{CircuitBreakerConfig, RetryConfig};
+pub use structured_data::{
+    SchemaArticle, SchemaEntity, SchemaEvent, SchemaJobPosting, SchemaOffer, SchemaProduct,
+    SchemaRating, SchemaRecipe, SchemaSalary, StructuredDataItem, StructuredDataSource,
+};
 pub use traits::{Browser, Extractor, Scraper};
 pub use types::{
     BrowserConfig, ExtractionConfig, ExtractionRequest, ExtractionResult, ScrapedContent,
@@ -84,12 +125,13 @@ pub use types::{
 
 // Re-export port traits for dependency injection
 pub use ports::{
-    BrowserDriver, BrowserSession, CacheStorage, Clock, DeterministicEntropy, DomainEvent, Entropy,
-    EventBus, EventHandler, FakeClock, IdempotencyStore, IdempotencyToken, InMemoryCache,
-    PdfMetadata, PdfProcessor, Pool, PoolError, PoolHealth, PoolStats, PooledResource, Repository,
-    RepositoryFilter, ScriptResult, SearchDocument, SearchEngine, SearchQuery as PortSearchQuery,
-    SearchResult as PortSearchResult, Session, SessionFilter, SessionStorage, SubscriptionId,
-    SystemClock, SystemEntropy, Transaction, TransactionManager,
+    BrowserDriver, BrowserSession, CacheStorage, Clock, DeterministicEntropy, DocumentClassifier,
+    DomainEvent, EmailAttachment, EmailMessage, EmailSender, Entropy, EventBus, EventHandler,
+    FakeClock, IdempotencyStore, IdempotencyToken, InMemoryCache, PdfMetadata, PdfProcessor, Pool,
+    PoolError, PoolHealth, PoolStats, PooledResource, Repository, RepositoryFilter, ScriptResult,
+    SearchDocument, SearchEngine, SearchQuery as PortSearchQuery, SearchResult as PortSearchResult,
+    Session, SessionFilter, SessionStorage, SubscriptionId, SystemClock, SystemEntropy,
+    TextAnnotator, Transaction, TransactionManager, Translator,
 };
 
 // Re-export third-party types for convenience
@@ -0,0 +1,339 @@
+//! Images, video and audio embedded in a page
+//!
+//! [`EmbeddedMedia`] describes a player, stream or file from the page's
+//! markup alone: Open Graph and Twitter card tags, schema.org
+//! `VideoObject`/`AudioObject` entities and `<video>`/`<audio>` elements.
+//! The media itself is never fetched.
+//!
+//! [`ImageMetadata`] describes an `<img>` with its lazy-loaded and
+//! responsive sources, alt text and caption; its content type and size are
+//! only known when the image was probed.
+
+use serde::{Deserialize, Serialize};
+
+/// Whether an embedded item is a video or an audio track
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaKind {
+    Video,
+    Audio,
+}
+
+/// Markup an [`EmbeddedMedia`] was described by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaMetadataSource {
+    /// `og:video` / `og:audio` meta tags
+    OpenGraph,
+    /// `twitter:player` meta tags
+    TwitterCard,
+    /// schema.org `VideoObject` / `AudioObject` in JSON-LD, Microdata or RDFa
+    SchemaOrg,
+    /// `<video>`, `<audio>` or a known player `<iframe>`
+    Element,
+    /// Stream manifest URL found in an inline script
+    Script,
+}
+
+/// Adaptive streaming format of a manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamFormat {
+    /// HTTP Live Streaming playlist (`.m3u8`)
+    Hls,
+    /// MPEG-DASH manifest (`.mpd`)
+    Dash,
+}
+
+impl StreamFormat {
+    /// Format announced by a `type` attribute or `og:video:type` value
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let essence = media_type.split(';').next()?.trim().to_ascii_lowercase();
+        match essence.as_str() {
+            "application/x-mpegurl" | "application/vnd.apple.mpegurl" | "audio/mpegurl" => {
+                Some(Self::Hls)
+            }
+            "application/dash+xml" => Some(Self::Dash),
+            _ => None,
+        }
+    }
+
+    /// Format implied by a URL's path extension
+    pub fn from_url(url: &str) -> Option<Self> {
+        let path = url.split(['?', '#']).next()?.to_ascii_lowercase();
+        if path.ends_with(".m3u8") {
+            Some(Self::Hls)
+        } else if path.ends_with(".mpd") {
+            Some(Self::Dash)
+        } else {
+            None
+        }
+    }
+}
+
+/// Adaptive stream manifest of an embedded item
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamManifest {
+    /// Absolute manifest URL
+    pub url: String,
+    pub format: StreamFormat,
+}
+
+/// Captions or subtitles track, e.g. from `<track>`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaptionTrack {
+    /// Absolute URL of the WebVTT/SRT file
+    pub url: String,
+    /// BCP 47 language tag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// `captions` or `subtitles`
+    pub kind: String,
+}
+
+/// A video or audio item embedded in a page
+///
+/// Descriptions of the same item from several sources are merged into one,
+/// so the Open Graph duration and the `<track>` captions of one player end up
+/// side by side.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmbeddedMedia {
+    pub kind: MediaKind,
+    /// Markup the item was found in, first source first
+    pub sources: Vec<MediaMetadataSource>,
+    /// Absolute URL of the media file itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Absolute URL of an embeddable player page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embed_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<f64>,
+    /// Absolute URL of a poster or thumbnail image
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// Upload or publication date as given, usually ISO 8601
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub manifests: Vec<StreamManifest>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub captions: Vec<CaptionTrack>,
+}
+
+impl EmbeddedMedia {
+    pub fn new(kind: MediaKind, source: MediaMetadataSource) -> Self {
+        Self {
+            kind,
+            sources: vec![source],
+            url: None,
+            embed_url: None,
+            mime_type: None,
+            title: None,
+            description: None,
+            duration_secs: None,
+            thumbnail_url: None,
+            width: None,
+            height: None,
+            upload_date: None,
+            manifests: Vec::new(),
+            captions: Vec::new(),
+        }
+    }
+
+    /// Add a manifest unless its URL is already listed
+    pub fn add_manifest(&mut self, manifest: StreamManifest) {
+        if !self.manifests.iter().any(|m| m.url == manifest.url) {
+            self.manifests.push(manifest);
+        }
+    }
+
+    /// Whether `other` describes the same item: both share a media, player
+    /// or manifest URL
+    pub fn same_item(&self, other: &EmbeddedMedia) -> bool {
+        let urls = |media: &EmbeddedMedia| -> Vec<String> {
+            media
+                .url
+                .iter()
+                .chain(media.embed_url.iter())
+                .cloned()
+                .chain(media.manifests.iter().map(|m| m.url.clone()))
+                .collect()
+        };
+        let ours = urls(self);
+        urls(other).iter().any(|url| ours.contains(url))
+    }
+
+    /// Fill fields missing here from `other`, keeping values already set
+    pub fn merge(&mut self, other: EmbeddedMedia) {
+        for source in other.sources {
+            if !self.sources.contains(&source) {
+                self.sources.push(source);
+            }
+        }
+        self.url = self.url.take().or(other.url);
+        self.embed_url = self.embed_url.take().or(other.embed_url);
+        self.mime_type = self.mime_type.take().or(other.mime_type);
+        self.title = self.title.take().or(other.title);
+        self.description = self.description.take().or(other.description);
+        self.duration_secs = self.duration_secs.or(other.duration_secs);
+        self.thumbnail_url = self.thumbnail_url.take().or(other.thumbnail_url);
+        self.width = self.width.or(other.width);
+        self.height = self.height.or(other.height);
+        self.upload_date = self.upload_date.take().or(other.upload_date);
+        for manifest in other.manifests {
+            self.add_manifest(manifest);
+        }
+        for track in other.captions {
+            if !self.captions.iter().any(|c| c.url == track.url) {
+                self.captions.push(track);
+            }
+        }
+    }
+}
+
+/// Responsive candidate of an image, from `srcset` or a `<picture>`
+/// `<source>`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageSource {
+    /// Absolute candidate URL
+    pub url: String,
+    /// Width descriptor ("480w")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    /// Pixel density descriptor ("2x")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub density: Option<f32>,
+    /// Media query of the `<source>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media: Option<String>,
+    /// `type` of the `<source>`, e.g. "image/avif"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// An image shown in a page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageMetadata {
+    /// Absolute URL of the image, with lazy-load placeholders resolved
+    pub url: String,
+    /// `srcset` and `<picture>` candidates, in markup order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<ImageSource>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// `<figcaption>` of the enclosing `<figure>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
+    /// Declared width in CSS pixels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    /// Declared height in CSS pixels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// The URL came from a lazy-loading attribute such as `data-src`
+    #[serde(default)]
+    pub lazy: bool,
+    /// `Content-Type` reported for the image, when probed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Size reported for the image, when probed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size_bytes: Option<u64>,
+}
+
+impl ImageMetadata {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            sources: Vec::new(),
+            alt: None,
+            title: None,
+            caption: None,
+            width: None,
+            height: None,
+            lazy: false,
+            content_type: None,
+            size_bytes: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_format_detection() {
+        assert_eq!(
+            StreamFormat::from_media_type("application/vnd.apple.mpegURL"),
+            Some(StreamFormat::Hls)
+        );
+        assert_eq!(
+            StreamFormat::from_media_type("application/dash+xml; charset=utf-8"),
+            Some(StreamFormat::Dash)
+        );
+        assert_eq!(StreamFormat::from_media_type("video/mp4"), None);
+        assert_eq!(
+            StreamFormat::from_url("https://cdn.example.com/live/master.M3U8?token=1"),
+            Some(StreamFormat::Hls)
+        );
+        assert_eq!(
+            StreamFormat::from_url("https://cdn.example.com/vod/stream.mpd#t=10"),
+            Some(StreamFormat::Dash)
+        );
+        assert_eq!(
+            StreamFormat::from_url("https://cdn.example.com/clip.mp4"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_merge_keeps_existing_and_fills_missing() {
+        let mut og = EmbeddedMedia::new(MediaKind::Video, MediaMetadataSource::OpenGraph);
+        og.url = Some("https://cdn.example.com/clip.mp4".to_string());
+        og.title = Some("From Open Graph".to_string());
+
+        let mut element = EmbeddedMedia::new(MediaKind::Video, MediaMetadataSource::Element);
+        element.url = Some("https://cdn.example.com/clip.mp4".to_string());
+        element.title = Some("From element".to_string());
+        element.thumbnail_url = Some("https://cdn.example.com/poster.jpg".to_string());
+        element.captions.push(CaptionTrack {
+            url: "https://cdn.example.com/en.vtt".to_string(),
+            language: Some("en".to_string()),
+            label: None,
+            kind: "captions".to_string(),
+        });
+
+        assert!(og.same_item(&element));
+        og.merge(element);
+
+        assert_eq!(
+            og.sources,
+            vec![MediaMetadataSource::OpenGraph, MediaMetadataSource::Element]
+        );
+        assert_eq!(og.title.as_deref(), Some("From Open Graph"));
+        assert_eq!(
+            og.thumbnail_url.as_deref(),
+            Some("https://cdn.example.com/poster.jpg")
+        );
+        assert_eq!(og.captions.len(), 1);
+
+        let other = EmbeddedMedia::new(MediaKind::Video, MediaMetadataSource::Element);
+        assert!(!og.same_item(&other));
+    }
+}
@@ -15,8 +15,9 @@ pub use traits::{CombinedPipelineExecutor, PipelineExecutor, StrategiesPipelineE
 
 // Re-export result types
 pub use results::{
-    DualPathResult, EnhancementResult, FastPathResult, GateDecisionStats, PipelineResult,
-    PipelineRetryConfig, PipelineStats, StrategiesPipelineResult,
+    ArtifactKind, CacheStatus, DualPathResult, EnhancementResult, FastPathResult,
+    GateDecisionStats, PhaseTimings, PipelineArtifact, PipelineResult, PipelineRetryConfig,
+    PipelineStats, StrategiesPipelineResult, GATE_DECISION_UNCHANGED,
 };
 
 // Re-export facade domain types
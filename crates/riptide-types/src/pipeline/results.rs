@@ -8,6 +8,9 @@
 use crate::ExtractedDoc;
 use serde::{Deserialize, Serialize};
 
+/// Gate decision reported for URLs a differential crawl found unchanged
+pub const GATE_DECISION_UNCHANGED: &str = "unchanged";
+
 /// Pipeline execution result containing the extracted document and metadata.
 ///
 /// This is the primary output type from pipeline orchestration, containing
@@ -20,6 +23,11 @@ pub struct PipelineResult {
     /// Whether the content was served from cache
     pub from_cache: bool,
 
+    /// How the cache was used; `Revalidated` when a stale entry was
+    /// confirmed by the origin with `304 Not Modified`
+    #[serde(default)]
+    pub cache_status: CacheStatus,
+
     /// The decision made by the gate (Raw, ProbesFirst, Headless, pdf, cached)
     pub gate_decision: String,
 
@@ -34,6 +42,131 @@ pub struct PipelineResult {
 
     /// HTTP status code from the original fetch
     pub http_status: u16,
+
+    /// Binary artifacts (screenshots, thumbnails, source PDFs) stored via the
+    /// `BlobStorage` port; cached results carry none
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<PipelineArtifact>,
+
+    /// Per-phase timing breakdown
+    #[serde(default)]
+    pub timings: PhaseTimings,
+}
+
+/// Cache outcome of a pipeline execution
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheStatus {
+    /// Fetched and extracted from the origin
+    #[default]
+    Miss,
+    /// Served from a fresh cache entry without contacting the origin
+    Hit,
+    /// Served from a stale cache entry after a conditional request
+    /// (`If-None-Match` / `If-Modified-Since`) returned `304 Not Modified`
+    Revalidated,
+}
+
+/// Time spent in each pipeline phase, in milliseconds, and what the fetch
+/// and enrichment phases consumed
+///
+/// Phases the request never reached (e.g. everything after a cache hit) are
+/// `None`, so a slow request can be attributed to a phase from the payload alone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PhaseTimings {
+    /// HTTP fetch, including conditional revalidation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fetch_ms: Option<u64>,
+
+    /// Gate analysis and decision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gate_ms: Option<u64>,
+
+    /// Headless rendering
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub render_ms: Option<u64>,
+
+    /// Content extraction (HTML or PDF)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extract_ms: Option<u64>,
+
+    /// Writing the extracted document to the cache
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write_ms: Option<u64>,
+
+    /// Event bus emission, summed over all events of the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_emit_ms: Option<u64>,
+
+    /// Bytes the fetches of the request received on the wire (before
+    /// decompression); pages rendered in the headless browser are not counted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fetch_bytes: Option<u64>,
+
+    /// LLM prompt and completion tokens spent on enrichment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llm_tokens: Option<u64>,
+}
+
+impl PhaseTimings {
+    /// Add `duration` to the event emission total
+    pub fn add_event_emit(&mut self, duration: std::time::Duration) {
+        let ms = duration.as_millis() as u64;
+        self.event_emit_ms = Some(self.event_emit_ms.unwrap_or(0) + ms);
+    }
+
+    /// Sum of all recorded phases
+    pub fn total_ms(&self) -> u64 {
+        [
+            self.fetch_ms,
+            self.gate_ms,
+            self.render_ms,
+            self.extract_ms,
+            self.cache_write_ms,
+            self.event_emit_ms,
+        ]
+        .into_iter()
+        .flatten()
+        .sum()
+    }
+}
+
+/// Kind of artifact produced by the media pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    /// Full-size screenshot in its original format
+    Screenshot,
+    /// Full-size screenshot re-encoded as WebP
+    ScreenshotWebp,
+    /// Downscaled preview image
+    Thumbnail,
+    /// Source PDF document as fetched
+    Pdf,
+}
+
+/// Durable reference to a stored artifact
+///
+/// Replaces inline base64 payloads in responses; clients fetch the bytes from `url`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineArtifact {
+    /// What the artifact is
+    pub kind: ArtifactKind,
+
+    /// Durable URL the artifact can be downloaded from
+    pub url: String,
+
+    /// MIME type (e.g. `image/png`, `image/webp`)
+    pub content_type: String,
+
+    /// Image width in pixels (0 for documents)
+    pub width: u32,
+
+    /// Image height in pixels (0 for documents)
+    pub height: u32,
+
+    /// Size in bytes
+    pub size_bytes: u64,
 }
 
 /// Pipeline execution statistics for monitoring and optimization.
@@ -241,6 +374,20 @@ mod tests {
                 published_iso: None,
                 markdown: None,
                 parser_metadata: None,
+                provenance: None,
+                translation: None,
+                structured_data: Vec::new(),
+                annotations: None,
+                feed: None,
+                feed_links: Vec::new(),
+                classification: None,
+                clean_html: None,
+                media_assets: Vec::new(),
+                embedded_media: Vec::new(),
+                images: Vec::new(),
+                references: Vec::new(),
+                language_spans: Vec::new(),
+                quality_report: None,
                 media: vec![],
                 language: None,
                 reading_time: None,
@@ -251,16 +398,59 @@ mod tests {
                 html: None,
             },
             from_cache: false,
+            cache_status: CacheStatus::Miss,
             gate_decision: "raw".to_string(),
             quality_score: 0.85,
             processing_time_ms: 150,
             cache_key: "test:key".to_string(),
             http_status: 200,
+            artifacts: Vec::new(),
+            timings: PhaseTimings {
+                fetch_ms: Some(120),
+                extract_ms: Some(30),
+                ..Default::default()
+            },
         };
 
         let json = serde_json::to_string(&result).unwrap();
         let deserialized: PipelineResult = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized.gate_decision, "raw");
+        assert_eq!(deserialized.timings, result.timings);
+        assert!(!json.contains("render_ms"));
+    }
+
+    #[test]
+    fn test_cache_status_serialization() {
+        assert_eq!(
+            serde_json::to_value(CacheStatus::Revalidated).unwrap(),
+            "revalidated"
+        );
+
+        // Results recorded before the field existed deserialize as misses
+        let json = serde_json::json!({
+            "document": ExtractedDoc::default(),
+            "from_cache": false,
+            "gate_decision": "raw",
+            "quality_score": 0.5,
+            "processing_time_ms": 10,
+            "cache_key": "test:key",
+            "http_status": 200,
+        });
+        let result: PipelineResult = serde_json::from_value(json).unwrap();
+        assert_eq!(result.cache_status, CacheStatus::Miss);
+    }
+
+    #[test]
+    fn test_phase_timings_accumulate_event_emit() {
+        let mut timings = PhaseTimings {
+            fetch_ms: Some(40),
+            ..Default::default()
+        };
+        timings.add_event_emit(std::time::Duration::from_millis(3));
+        timings.add_event_emit(std::time::Duration::from_millis(2));
+
+        assert_eq!(timings.event_emit_ms, Some(5));
+        assert_eq!(timings.total_ms(), 45);
     }
 
     #[test]
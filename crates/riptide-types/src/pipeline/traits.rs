@@ -20,6 +20,7 @@
 //! 2. **No Implementation**: This module contains ONLY trait definitions, NO business logic
 //! 3. **Stable Contracts**: These interfaces should remain stable across refactorings
 
+use crate::conditional::ChangedSince;
 use crate::error::Result as RiptideResult;
 use async_trait::async_trait;
 
@@ -73,6 +74,22 @@ pub trait PipelineExecutor: Send + Sync {
     /// - Vector of optional results (Some = success, None = failure)
     /// - Aggregate statistics for the batch
     async fn execute_batch(&self, urls: &[String]) -> (Vec<Option<PipelineResult>>, PipelineStats);
+
+    /// Execute pipeline for multiple URLs, skipping unchanged content
+    ///
+    /// URLs whose content has not changed since the timestamp or crawl id in
+    /// `since` are revalidated against their stored fingerprints and
+    /// reported with the [`GATE_DECISION_UNCHANGED`](super::GATE_DECISION_UNCHANGED)
+    /// gate decision and an empty document.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if `since` names an unknown or expired crawl id.
+    async fn execute_batch_changed_since(
+        &self,
+        urls: &[String],
+        since: &ChangedSince,
+    ) -> RiptideResult<(Vec<Option<PipelineResult>>, PipelineStats)>;
 }
 
 /// Trait for strategies-enhanced pipeline execution
@@ -145,6 +162,20 @@ mod tests {
                     published_iso: None,
                     markdown: None,
                     parser_metadata: None,
+                    provenance: None,
+                    translation: None,
+                    structured_data: Vec::new(),
+                    annotations: None,
+                    feed: None,
+                    feed_links: Vec::new(),
+                    classification: None,
+                    clean_html: None,
+                    media_assets: Vec::new(),
+                    embedded_media: Vec::new(),
+                    images: Vec::new(),
+                    references: Vec::new(),
+                    language_spans: Vec::new(),
+                    quality_report: None,
                     media: vec![],
                     language: None,
                     reading_time: None,
@@ -155,11 +186,14 @@ mod tests {
                     html: None,
                 },
                 from_cache: false,
+                cache_status: crate::pipeline::CacheStatus::Miss,
                 gate_decision: "Raw".to_string(),
                 quality_score: 0.9,
                 processing_time_ms: 100,
                 cache_key: "test-key".to_string(),
                 http_status: 200,
+                artifacts: Vec::new(),
+                timings: Default::default(),
             })
         }
 
@@ -179,6 +213,14 @@ mod tests {
             };
             (results, stats)
         }
+
+        async fn execute_batch_changed_since(
+            &self,
+            urls: &[String],
+            _since: &ChangedSince,
+        ) -> RiptideResult<(Vec<Option<PipelineResult>>, PipelineStats)> {
+            Ok(self.execute_batch(urls).await)
+        }
     }
 
     #[tokio::test]
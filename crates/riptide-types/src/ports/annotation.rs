@@ -0,0 +1,45 @@
+//! Text annotation port
+//!
+//! Finds named entities, keywords and topics in extracted text. The
+//! rule-based annotator lives in `riptide-extraction` (`RuleBasedAnnotator`),
+//! the LLM-backed one in `riptide-facade` (`LlmAnnotator`);
+//! [`InMemoryTextAnnotator`](crate::ports::InMemoryTextAnnotator) returns
+//! canned annotations for tests.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use riptide_types::ports::TextAnnotator;
+//!
+//! async fn people(annotator: &dyn TextAnnotator, text: &str) -> riptide_types::Result<Vec<String>> {
+//!     let annotations = annotator.annotate(None, text, Some("en")).await?;
+//!     Ok(annotations
+//!         .entities_of(riptide_types::EntityKind::Person)
+//!         .map(str::to_string)
+//!         .collect())
+//! }
+//! ```
+
+use async_trait::async_trait;
+
+use crate::error::Result;
+use crate::extracted::TextAnnotations;
+
+/// Text annotation port trait
+#[async_trait]
+pub trait TextAnnotator: Send + Sync {
+    /// Annotate `text`
+    ///
+    /// `title` weighs in on keywords and topics; `language` is a BCP 47 hint.
+    /// Implementations may leave `TextAnnotations::annotator` empty, callers
+    /// record [`name`](Self::name) there.
+    async fn annotate(
+        &self,
+        title: Option<&str>,
+        text: &str,
+        language: Option<&str>,
+    ) -> Result<TextAnnotations>;
+
+    /// Identifier recorded on annotated documents, e.g. `"rules"`
+    fn name(&self) -> &str;
+}
@@ -0,0 +1,60 @@
+//! Web archive port definition
+//!
+//! This module defines the abstract interface for persisting fetched and
+//! rendered content as web archive records (e.g. WARC files). Adapters decide
+//! the on-disk format; the pipeline only hands over HTTP exchanges, rendered
+//! resources, and per-page metadata.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::error::Result;
+use crate::ports::http::{HttpRequest, HttpResponse};
+
+/// A single HTTP request/response pair captured during a fetch
+#[derive(Debug, Clone)]
+pub struct ArchivedExchange {
+    /// URI the request was issued for
+    pub target_uri: String,
+    /// Request as sent on the wire
+    pub request: HttpRequest,
+    /// Response as received
+    pub response: HttpResponse,
+    /// Time the response was received
+    pub fetched_at: DateTime<Utc>,
+    /// Remote IP address, when known
+    pub ip_address: Option<String>,
+}
+
+impl ArchivedExchange {
+    /// Creates an exchange record timestamped now
+    pub fn new(request: HttpRequest, response: HttpResponse) -> Self {
+        Self {
+            target_uri: request.url.clone(),
+            request,
+            response,
+            fetched_at: Utc::now(),
+            ip_address: None,
+        }
+    }
+}
+
+/// Web archive port interface
+///
+/// Implementations must be safe to share across concurrent fetches; records
+/// from one crawl may be written in any order.
+#[async_trait]
+pub trait WebArchive: Send + Sync {
+    /// Writes a request/response record pair
+    async fn write_exchange(&self, exchange: &ArchivedExchange) -> Result<()>;
+
+    /// Writes a resource record, e.g. the DOM produced by a headless render
+    async fn write_resource(&self, target_uri: &str, content_type: &str, body: &[u8])
+        -> Result<()>;
+
+    /// Writes a metadata record describing a capture
+    async fn write_metadata(&self, target_uri: &str, fields: &[(String, String)]) -> Result<()>;
+
+    /// Flushes pending records and returns the paths of all archive files written
+    async fn finish(&self) -> Result<Vec<String>>;
+}
@@ -0,0 +1,90 @@
+//! Blob storage port for binary artifacts
+//!
+//! Defines a backend-agnostic interface for storing binary objects such as
+//! screenshots and thumbnails. Adapters (local filesystem, S3-compatible
+//! stores) map keys to objects and expose a durable URL for each one, so
+//! API responses can reference artifacts instead of embedding them.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use riptide_types::ports::BlobStorage;
+//!
+//! async fn store(storage: &dyn BlobStorage, png: &[u8]) -> Result<String> {
+//!     let blob = storage.put("screenshots/abc/full.png", png, "image/png").await?;
+//!     Ok(blob.url)
+//! }
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, RiptideError};
+
+/// Reference to a stored blob
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredBlob {
+    /// Storage key, e.g. `screenshots/3f2a.../thumbnail.webp`
+    pub key: String,
+    /// Durable URL the blob can be retrieved from
+    pub url: String,
+    /// MIME type recorded at upload
+    pub content_type: String,
+    /// Size in bytes
+    pub size_bytes: u64,
+}
+
+/// Blob storage port trait
+///
+/// Keys are `/`-separated relative paths. Implementations must reject keys
+/// that are absolute or contain `..` segments (see [`validate_blob_key`]).
+#[async_trait]
+pub trait BlobStorage: Send + Sync {
+    /// Store a blob, replacing any existing blob with the same key
+    async fn put(&self, key: &str, data: &[u8], content_type: &str) -> Result<StoredBlob>;
+
+    /// Retrieve a blob and its content type
+    ///
+    /// # Returns
+    /// * `Ok(Some((data, content_type)))` - Blob exists
+    /// * `Ok(None)` - No blob stored under this key
+    async fn get(&self, key: &str) -> Result<Option<(Vec<u8>, String)>>;
+
+    /// Delete a blob (no-op if it does not exist)
+    async fn delete(&self, key: &str) -> Result<()>;
+
+    /// Durable URL for a key
+    fn url_for(&self, key: &str) -> String;
+}
+
+/// Reject keys that could escape the storage root
+pub fn validate_blob_key(key: &str) -> Result<()> {
+    let invalid = key.is_empty()
+        || key.starts_with('/')
+        || key.contains('\\')
+        || key
+            .split('/')
+            .any(|seg| seg.is_empty() || seg == "." || seg == "..");
+    if invalid {
+        return Err(RiptideError::ValidationError(format!(
+            "Invalid blob key: {}",
+            key
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_blob_key() {
+        assert!(validate_blob_key("screenshots/abc/full.png").is_ok());
+        assert!(validate_blob_key("").is_err());
+        assert!(validate_blob_key("/etc/passwd").is_err());
+        assert!(validate_blob_key("a/../../b").is_err());
+        assert!(validate_blob_key("a//b").is_err());
+        assert!(validate_blob_key("a\\b").is_err());
+    }
+}
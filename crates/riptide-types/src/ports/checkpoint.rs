@@ -0,0 +1,76 @@
+//! Checkpoint storage port for resumable work
+//!
+//! Long-running jobs (e.g. spider crawls) serialize their state into an
+//! opaque byte payload and store it under a checkpoint id, so a restarted
+//! process can pick up where the previous one stopped. The port mirrors the
+//! store/load contract of the persistence layer's `CheckpointManager`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use riptide_types::ports::CheckpointStore;
+//!
+//! async fn save(store: &dyn CheckpointStore, state: &[u8]) -> Result<()> {
+//!     store.save("crawl-42", state).await?;
+//!     let restored = store.load("crawl-42").await?;
+//!     Ok(())
+//! }
+//! ```
+
+use async_trait::async_trait;
+
+use crate::error::{Result, RiptideError};
+
+/// Checkpoint storage port trait
+///
+/// Ids are used as file or key names by adapters and must pass
+/// [`validate_checkpoint_id`].
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    /// Store a checkpoint, replacing any existing checkpoint with the same id
+    async fn save(&self, checkpoint_id: &str, data: &[u8]) -> Result<()>;
+
+    /// Load a checkpoint
+    ///
+    /// # Returns
+    /// * `Ok(Some(data))` - Checkpoint exists
+    /// * `Ok(None)` - No checkpoint stored under this id
+    async fn load(&self, checkpoint_id: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Delete a checkpoint (no-op if it does not exist)
+    async fn delete(&self, checkpoint_id: &str) -> Result<()>;
+}
+
+/// Reject ids that are empty or could escape the storage root
+///
+/// Only ASCII alphanumerics, `-`, `_` and `.` are allowed, and the id may
+/// not start with `.`.
+pub fn validate_checkpoint_id(checkpoint_id: &str) -> Result<()> {
+    let valid = !checkpoint_id.is_empty()
+        && !checkpoint_id.starts_with('.')
+        && checkpoint_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !valid {
+        return Err(RiptideError::ValidationError(format!(
+            "Invalid checkpoint id: {}",
+            checkpoint_id
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_checkpoint_id() {
+        assert!(validate_checkpoint_id("crawl-42").is_ok());
+        assert!(validate_checkpoint_id("spider_2024.01").is_ok());
+        assert!(validate_checkpoint_id("").is_err());
+        assert!(validate_checkpoint_id("..").is_err());
+        assert!(validate_checkpoint_id("../etc/passwd").is_err());
+        assert!(validate_checkpoint_id("a/b").is_err());
+    }
+}
@@ -0,0 +1,41 @@
+//! Document classification port
+//!
+//! Tags documents with categories from a taxonomy and an overall sentiment,
+//! each with a confidence. The LLM-backed and embedding-centroid
+//! classifiers live in `riptide-facade` (`LlmClassifier`,
+//! `CentroidClassifier`);
+//! [`InMemoryDocumentClassifier`](crate::ports::InMemoryDocumentClassifier)
+//! returns a canned classification for tests.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use riptide_types::ports::DocumentClassifier;
+//!
+//! async fn top_category(
+//!     classifier: &dyn DocumentClassifier,
+//!     text: &str,
+//! ) -> riptide_types::Result<Option<String>> {
+//!     let classification = classifier.classify(None, text).await?;
+//!     Ok(classification.categories.first().map(|tag| tag.label.clone()))
+//! }
+//! ```
+
+use async_trait::async_trait;
+
+use crate::error::Result;
+use crate::extracted::DocumentClassification;
+
+/// Document classification port trait
+#[async_trait]
+pub trait DocumentClassifier: Send + Sync {
+    /// Classify `text`
+    ///
+    /// Categories come most confident first. Implementations may leave
+    /// `DocumentClassification::classifier` empty, callers record
+    /// [`name`](Self::name) there.
+    async fn classify(&self, title: Option<&str>, text: &str) -> Result<DocumentClassification>;
+
+    /// Identifier recorded on classified documents, e.g. `"llm:gpt-4o-mini"`
+    fn name(&self) -> &str;
+}
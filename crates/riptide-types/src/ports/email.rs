@@ -0,0 +1,96 @@
+//! Outbound email port
+//!
+//! Delivers notification and report emails, optionally with attachments.
+//! The SMTP adapter lives in `riptide-workers` (behind `email-reports`);
+//! [`InMemoryEmailSender`](crate::ports::InMemoryEmailSender) records
+//! messages for tests.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use riptide_types::ports::{EmailMessage, EmailSender};
+//!
+//! async fn notify(sender: &dyn EmailSender) -> riptide_types::Result<()> {
+//!     let message = EmailMessage::new(["ops@example.com"], "Crawl finished", "42 pages");
+//!     sender.send(&message).await
+//! }
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, RiptideError};
+
+/// File attached to an email
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmailAttachment {
+    /// File name shown to the recipient
+    pub filename: String,
+    /// MIME type, e.g. `text/html`
+    pub content_type: String,
+    #[serde(with = "serde_bytes")]
+    pub data: Vec<u8>,
+}
+
+/// An email to deliver
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EmailMessage {
+    /// Recipient addresses
+    pub to: Vec<String>,
+    pub subject: String,
+    /// Plain-text body
+    pub text_body: String,
+    /// Optional HTML alternative of the body
+    pub html_body: Option<String>,
+    pub attachments: Vec<EmailAttachment>,
+}
+
+impl EmailMessage {
+    /// Plain-text message without attachments
+    pub fn new<I, S>(to: I, subject: impl Into<String>, text_body: impl Into<String>) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            to: to.into_iter().map(Into::into).collect(),
+            subject: subject.into(),
+            text_body: text_body.into(),
+            html_body: None,
+            attachments: Vec::new(),
+        }
+    }
+
+    /// Add an attachment
+    pub fn with_attachment(mut self, attachment: EmailAttachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    /// Check that the message has recipients and a subject
+    ///
+    /// Address syntax is left to the adapter.
+    pub fn validate(&self) -> Result<()> {
+        if self.to.iter().all(|to| to.trim().is_empty()) {
+            return Err(RiptideError::ValidationError(
+                "Email has no recipients".to_string(),
+            ));
+        }
+        if self.subject.contains(['\r', '\n']) {
+            return Err(RiptideError::ValidationError(
+                "Email subject must be a single line".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Email delivery port trait
+#[async_trait]
+pub trait EmailSender: Send + Sync {
+    /// Deliver a message to all of its recipients
+    ///
+    /// Returns once the message was accepted for delivery (e.g. by the SMTP
+    /// relay), not when it reached the mailboxes.
+    async fn send(&self, message: &EmailMessage) -> Result<()>;
+}
@@ -0,0 +1,239 @@
+//! Feature flag port
+//!
+//! Feature flags gate optional behaviour at runtime and carry the A/B
+//! experiments that route a share of production traffic to an alternative
+//! extraction strategy or gate threshold. Adapters may wrap a flag service
+//! or a static configuration file; assignment is deterministic per URL, so
+//! every instance agrees on which arm a page belongs to.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use riptide_types::ports::FeatureFlags;
+//!
+//! async fn gate_threshold(flags: &dyn FeatureFlags, url: &str, default: f32) -> f32 {
+//!     match flags.assign(url).await {
+//!         Ok(Some(assignment)) => assignment.gate_hi_threshold().unwrap_or(default),
+//!         _ => default,
+//!     }
+//! }
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::Result;
+
+/// Experiment arm a request was assigned to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExperimentArm {
+    /// Production behaviour
+    Control,
+    /// The experiment's variant
+    Treatment,
+}
+
+impl ExperimentArm {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExperimentArm::Control => "control",
+            ExperimentArm::Treatment => "treatment",
+        }
+    }
+}
+
+/// Behaviour overrides applied to the treatment arm
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExperimentVariant {
+    /// Name of the alternative extractor, e.g. `native`
+    pub extractor: Option<String>,
+    /// Gate score above which pages are extracted without rendering
+    pub gate_hi_threshold: Option<f32>,
+    /// Gate score below which pages go straight to headless rendering
+    pub gate_lo_threshold: Option<f32>,
+}
+
+/// A/B experiment over a share of traffic to some domains
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Experiment {
+    /// Identifier tagged on every result produced under the experiment
+    pub id: String,
+    /// Domains in the experiment, subdomains included (empty = all domains)
+    #[serde(default)]
+    pub domains: Vec<String>,
+    /// Percentage of matching URLs assigned to the treatment arm (0-100)
+    pub traffic_percent: u8,
+    /// Overrides for the treatment arm
+    #[serde(default)]
+    pub variant: ExperimentVariant,
+}
+
+impl Experiment {
+    /// Whether URLs on `host` take part in the experiment
+    pub fn matches_host(&self, host: &str) -> bool {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        self.domains.is_empty()
+            || self.domains.iter().any(|domain| {
+                let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+                host == domain || host.ends_with(&format!(".{}", domain))
+            })
+    }
+
+    /// Arm of a URL, stable across calls and instances
+    pub fn arm(&self, url: &str) -> ExperimentArm {
+        if Self::bucket(&self.id, url) < u64::from(self.traffic_percent.min(100)) {
+            ExperimentArm::Treatment
+        } else {
+            ExperimentArm::Control
+        }
+    }
+
+    /// Bucket 0-99 of a URL within an experiment
+    fn bucket(experiment_id: &str, url: &str) -> u64 {
+        let digest = Sha256::new()
+            .chain_update(experiment_id.as_bytes())
+            .chain_update(b"\n")
+            .chain_update(url.as_bytes())
+            .finalize();
+        let mut prefix = [0u8; 8];
+        prefix.copy_from_slice(&digest[..8]);
+        u64::from_be_bytes(prefix) % 100
+    }
+}
+
+/// Experiment id and arm recorded on a result
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ExperimentTag {
+    pub experiment_id: String,
+    pub arm: ExperimentArm,
+}
+
+/// Assignment of a URL to an experiment arm
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExperimentAssignment {
+    pub experiment_id: String,
+    pub arm: ExperimentArm,
+    /// Overrides to apply; empty for the control arm
+    pub variant: ExperimentVariant,
+}
+
+impl ExperimentAssignment {
+    /// Tag for results produced under this assignment
+    pub fn tag(&self) -> ExperimentTag {
+        ExperimentTag {
+            experiment_id: self.experiment_id.clone(),
+            arm: self.arm,
+        }
+    }
+
+    /// Alternative extractor for this request
+    pub fn extractor(&self) -> Option<&str> {
+        self.variant.extractor.as_deref()
+    }
+
+    /// Overridden high gate threshold for this request
+    pub fn gate_hi_threshold(&self) -> Option<f32> {
+        self.variant.gate_hi_threshold
+    }
+
+    /// Overridden low gate threshold for this request
+    pub fn gate_lo_threshold(&self) -> Option<f32> {
+        self.variant.gate_lo_threshold
+    }
+}
+
+/// Feature flag port trait
+#[async_trait]
+pub trait FeatureFlags: Send + Sync {
+    /// Whether a boolean flag is on
+    async fn is_enabled(&self, flag: &str) -> Result<bool>;
+
+    /// Experiments currently running, in priority order
+    async fn experiments(&self) -> Result<Vec<Experiment>>;
+
+    /// Assign a URL to the first running experiment covering its host
+    ///
+    /// URLs that match an experiment but fall outside its traffic share are
+    /// assigned to the control arm, so both arms are measured on the same
+    /// domains. Returns `None` when no experiment covers the URL.
+    async fn assign(&self, url: &str) -> Result<Option<ExperimentAssignment>> {
+        let Some(host) = url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+        else {
+            return Ok(None);
+        };
+
+        let experiment = self
+            .experiments()
+            .await?
+            .into_iter()
+            .find(|e| e.matches_host(&host));
+        Ok(experiment.map(|experiment| {
+            let arm = experiment.arm(url);
+            ExperimentAssignment {
+                experiment_id: experiment.id,
+                arm,
+                variant: match arm {
+                    ExperimentArm::Treatment => experiment.variant,
+                    ExperimentArm::Control => ExperimentVariant::default(),
+                },
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn experiment(traffic_percent: u8) -> Experiment {
+        Experiment {
+            id: "gate-0.6".to_string(),
+            domains: vec!["example.com".to_string()],
+            traffic_percent,
+            variant: ExperimentVariant {
+                gate_hi_threshold: Some(0.6),
+                ..ExperimentVariant::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_matches_host() {
+        let experiment = experiment(50);
+        assert!(experiment.matches_host("example.com"));
+        assert!(experiment.matches_host("News.Example.com"));
+        assert!(!experiment.matches_host("notexample.com"));
+        assert!(Experiment {
+            domains: Vec::new(),
+            ..experiment
+        }
+        .matches_host("anything.org"));
+    }
+
+    #[test]
+    fn test_arm_is_stable_and_follows_traffic_share() {
+        let urls: Vec<String> = (0..1000)
+            .map(|i| format!("https://example.com/page/{}", i))
+            .collect();
+
+        assert!(urls
+            .iter()
+            .all(|u| experiment(0).arm(u) == ExperimentArm::Control));
+        assert!(urls
+            .iter()
+            .all(|u| experiment(100).arm(u) == ExperimentArm::Treatment));
+
+        let half = experiment(50);
+        let treated = urls
+            .iter()
+            .filter(|u| half.arm(u) == ExperimentArm::Treatment)
+            .count();
+        assert!((400..600).contains(&treated), "treated {}", treated);
+        assert!(urls.iter().all(|u| half.arm(u) == half.arm(u)));
+    }
+}
@@ -0,0 +1,53 @@
+//! GeoIP lookup port
+//!
+//! Resolves the country and autonomous system (ASN) of a server IP address.
+//! Adapters wrap a MaxMind-format database; the lookup result is attached to
+//! fetch metrics and extraction provenance, and drives jurisdiction rules
+//! such as "never crawl servers located in country X".
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use riptide_types::ports::GeoIpLookup;
+//!
+//! fn country(geoip: &dyn GeoIpLookup, ip: std::net::IpAddr) -> Option<String> {
+//!     geoip.lookup(ip).ok().flatten().and_then(|loc| loc.country_code)
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+use crate::error::Result;
+
+/// Geographic and network location of a server
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeoLocation {
+    /// ISO 3166-1 alpha-2 country code, e.g. `DE`
+    pub country_code: Option<String>,
+    /// Autonomous system number
+    pub asn: Option<u32>,
+    /// Autonomous system organization, e.g. `Hetzner Online GmbH`
+    pub as_org: Option<String>,
+}
+
+impl GeoLocation {
+    /// Whether the lookup produced no information
+    pub fn is_empty(&self) -> bool {
+        self.country_code.is_none() && self.asn.is_none() && self.as_org.is_none()
+    }
+}
+
+/// GeoIP lookup port trait
+///
+/// Lookups are synchronous: MaxMind databases are read into memory when
+/// opened and answer in microseconds, so there is no benefit to an async
+/// interface.
+pub trait GeoIpLookup: Send + Sync {
+    /// Look up the location of an IP address
+    ///
+    /// # Returns
+    /// * `Ok(Some(location))` - Address found in the database
+    /// * `Ok(None)` - Address not present (private ranges, unallocated space)
+    fn lookup(&self, ip: IpAddr) -> Result<Option<GeoLocation>>;
+}
@@ -0,0 +1,80 @@
+//! In-memory text annotator for testing and development
+//!
+//! Returns the annotations given to [`InMemoryTextAnnotator::new`] for every
+//! text, or an error when built with [`InMemoryTextAnnotator::failing`], so
+//! tests can exercise fallbacks without a model.
+
+use crate::error::{Result, RiptideError};
+use crate::extracted::TextAnnotations;
+use crate::ports::annotation::TextAnnotator;
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Annotator answering with fixed annotations
+#[derive(Clone, Default)]
+pub struct InMemoryTextAnnotator {
+    annotations: Option<TextAnnotations>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl InMemoryTextAnnotator {
+    /// Annotate every text with `annotations`
+    pub fn new(annotations: TextAnnotations) -> Self {
+        Self {
+            annotations: Some(annotations),
+            calls: Arc::default(),
+        }
+    }
+
+    /// Fail every call
+    pub fn failing() -> Self {
+        Self::default()
+    }
+
+    /// Number of `annotate` calls so far
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl TextAnnotator for InMemoryTextAnnotator {
+    async fn annotate(
+        &self,
+        _title: Option<&str>,
+        _text: &str,
+        _language: Option<&str>,
+    ) -> Result<TextAnnotations> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.annotations
+            .clone()
+            .ok_or_else(|| RiptideError::Custom("annotator unavailable".to_string()))
+    }
+
+    fn name(&self) -> &str {
+        "memory"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_returns_canned_annotations_or_fails() {
+        let annotations = TextAnnotations {
+            topics: vec!["sports".to_string()],
+            ..Default::default()
+        };
+        let annotator = InMemoryTextAnnotator::new(annotations.clone());
+        assert_eq!(
+            annotator.annotate(None, "text", None).await.unwrap(),
+            annotations
+        );
+
+        let failing = InMemoryTextAnnotator::failing();
+        assert!(failing.annotate(None, "text", None).await.is_err());
+        assert_eq!(annotator.calls() + failing.calls(), 2);
+    }
+}
@@ -0,0 +1,108 @@
+//! In-memory blob storage for testing and development
+//!
+//! Stores blobs in a `RwLock<HashMap>`; URLs are built from a configurable
+//! base so tests can assert on the URLs returned to clients.
+
+use crate::error::Result;
+use crate::ports::blob::{validate_blob_key, BlobStorage, StoredBlob};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Blob bytes and content type, by key
+type Blobs = HashMap<String, (Vec<u8>, String)>;
+
+/// Thread-safe in-memory blob storage
+#[derive(Clone)]
+pub struct InMemoryBlobStorage {
+    base_url: String,
+    store: Arc<RwLock<Blobs>>,
+}
+
+impl InMemoryBlobStorage {
+    /// Create storage whose URLs are `memory://blobs/<key>`
+    pub fn new() -> Self {
+        Self::with_base_url("memory://blobs")
+    }
+
+    /// Create storage whose URLs are `<base_url>/<key>`
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            store: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Number of stored blobs
+    pub async fn len(&self) -> usize {
+        self.store.read().await.len()
+    }
+
+    /// Check if storage is empty
+    pub async fn is_empty(&self) -> bool {
+        self.store.read().await.is_empty()
+    }
+}
+
+impl Default for InMemoryBlobStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl BlobStorage for InMemoryBlobStorage {
+    async fn put(&self, key: &str, data: &[u8], content_type: &str) -> Result<StoredBlob> {
+        validate_blob_key(key)?;
+        self.store
+            .write()
+            .await
+            .insert(key.to_string(), (data.to_vec(), content_type.to_string()));
+        Ok(StoredBlob {
+            key: key.to_string(),
+            url: self.url_for(key),
+            content_type: content_type.to_string(),
+            size_bytes: data.len() as u64,
+        })
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<(Vec<u8>, String)>> {
+        validate_blob_key(key)?;
+        Ok(self.store.read().await.get(key).cloned())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        validate_blob_key(key)?;
+        self.store.write().await.remove(key);
+        Ok(())
+    }
+
+    fn url_for(&self, key: &str) -> String {
+        format!("{}/{}", self.base_url, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_put_get_delete() {
+        let storage = InMemoryBlobStorage::with_base_url("https://cdn.example.com/");
+        let blob = storage
+            .put("shots/a.png", b"png-bytes", "image/png")
+            .await
+            .unwrap();
+        assert_eq!(blob.url, "https://cdn.example.com/shots/a.png");
+        assert_eq!(blob.size_bytes, 9);
+
+        let (data, content_type) = storage.get("shots/a.png").await.unwrap().unwrap();
+        assert_eq!(data, b"png-bytes");
+        assert_eq!(content_type, "image/png");
+
+        storage.delete("shots/a.png").await.unwrap();
+        assert!(storage.get("shots/a.png").await.unwrap().is_none());
+        assert!(storage.put("../escape", b"", "text/plain").await.is_err());
+    }
+}
@@ -0,0 +1,176 @@
+//! Scripted browser driver for testing
+//!
+//! [`FakeBrowserDriver`] serves pages registered up front instead of
+//! launching Chrome: navigating to a scripted URL opens a session on its
+//! HTML, scripts return registered values, and every call is recorded so
+//! tests can assert on what the code under test did.
+
+use crate::error::{Result, RiptideError};
+use crate::ports::features::{BrowserDriver, BrowserSession, ScriptResult};
+use async_trait::async_trait;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Script answered with the HTML of the session's page
+const OUTER_HTML_SCRIPT: &str = "document.documentElement.outerHTML";
+
+/// PNG signature returned as screenshot data
+pub const FAKE_SCREENSHOT: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+#[derive(Default)]
+struct BrowserState {
+    pages: HashMap<String, String>,
+    scripts: HashMap<String, serde_json::Value>,
+    failing_urls: HashSet<String>,
+    open_sessions: HashMap<String, String>,
+    navigations: Vec<String>,
+    executed_scripts: Vec<String>,
+}
+
+/// Browser driver serving scripted pages
+#[derive(Clone, Default)]
+pub struct FakeBrowserDriver {
+    state: Arc<Mutex<BrowserState>>,
+    next_session: Arc<AtomicUsize>,
+}
+
+impl FakeBrowserDriver {
+    /// Driver without any page; every navigation fails until one is added
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve `html` at `url`
+    pub fn with_page(self, url: impl Into<String>, html: impl Into<String>) -> Self {
+        self.lock().pages.insert(url.into(), html.into());
+        self
+    }
+
+    /// Answer `script` with `value` on every page
+    pub fn with_script_result(self, script: impl Into<String>, value: serde_json::Value) -> Self {
+        self.lock().scripts.insert(script.into(), value);
+        self
+    }
+
+    /// Fail navigations to `url`, even when it has a page
+    pub fn with_failing_url(self, url: impl Into<String>) -> Self {
+        self.lock().failing_urls.insert(url.into());
+        self
+    }
+
+    /// URLs navigated to, in order, including failed navigations
+    pub fn navigations(&self) -> Vec<String> {
+        self.lock().navigations.clone()
+    }
+
+    /// Scripts executed, in order
+    pub fn executed_scripts(&self) -> Vec<String> {
+        self.lock().executed_scripts.clone()
+    }
+
+    /// Number of sessions opened and not closed
+    pub fn open_sessions(&self) -> usize {
+        self.lock().open_sessions.len()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BrowserState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn page_of(&self, session: &BrowserSession) -> Result<String> {
+        let state = self.lock();
+        let url = state.open_sessions.get(&session.id).ok_or_else(|| {
+            RiptideError::BrowserOperation(format!("Session {} is closed", session.id))
+        })?;
+        Ok(state.pages.get(url).cloned().unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl BrowserDriver for FakeBrowserDriver {
+    async fn navigate(&self, url: &str) -> Result<BrowserSession> {
+        let mut state = self.lock();
+        state.navigations.push(url.to_string());
+        if state.failing_urls.contains(url) || !state.pages.contains_key(url) {
+            return Err(RiptideError::Navigation(format!(
+                "No scripted page for {}",
+                url
+            )));
+        }
+
+        let id = format!(
+            "fake-session-{}",
+            self.next_session.fetch_add(1, Ordering::Relaxed)
+        );
+        state.open_sessions.insert(id.clone(), url.to_string());
+        Ok(BrowserSession::new(id, url).with_metadata("browser", "fake"))
+    }
+
+    async fn execute_script(&self, session: &BrowserSession, script: &str) -> Result<ScriptResult> {
+        let html = self.page_of(session)?;
+        let mut state = self.lock();
+        state.executed_scripts.push(script.to_string());
+        let value = if script == OUTER_HTML_SCRIPT {
+            serde_json::Value::String(html)
+        } else {
+            state
+                .scripts
+                .get(script)
+                .cloned()
+                .unwrap_or(serde_json::Value::Null)
+        };
+        Ok(ScriptResult {
+            value,
+            success: true,
+            error: None,
+        })
+    }
+
+    async fn screenshot(&self, session: &BrowserSession) -> Result<Vec<u8>> {
+        self.page_of(session)?;
+        Ok(FAKE_SCREENSHOT.to_vec())
+    }
+
+    async fn close(&self, session: BrowserSession) -> Result<()> {
+        self.lock().open_sessions.remove(&session.id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_scripted_pages_and_recording() {
+        let driver = FakeBrowserDriver::new()
+            .with_page("https://example.com", "<html><h1>Hi</h1></html>")
+            .with_script_result("document.title", serde_json::json!("Example"));
+
+        let session = driver.navigate("https://example.com").await.unwrap();
+        assert_eq!(
+            driver.get_html(&session).await.unwrap(),
+            "<html><h1>Hi</h1></html>"
+        );
+        let title = driver
+            .execute_script(&session, "document.title")
+            .await
+            .unwrap();
+        assert_eq!(title.value, "Example");
+        assert_eq!(driver.screenshot(&session).await.unwrap(), FAKE_SCREENSHOT);
+        assert_eq!(driver.open_sessions(), 1);
+
+        assert!(driver.navigate("https://unknown.test").await.is_err());
+        assert_eq!(
+            driver.navigations(),
+            vec!["https://example.com", "https://unknown.test"]
+        );
+
+        driver.close(session.clone()).await.unwrap();
+        assert_eq!(driver.open_sessions(), 0);
+        assert!(driver.screenshot(&session).await.is_err());
+    }
+}
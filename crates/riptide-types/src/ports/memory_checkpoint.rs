@@ -0,0 +1,72 @@
+//! In-memory checkpoint storage for testing and development
+
+use crate::error::Result;
+use crate::ports::checkpoint::{validate_checkpoint_id, CheckpointStore};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Thread-safe in-memory checkpoint storage
+#[derive(Clone, Default)]
+pub struct InMemoryCheckpointStore {
+    store: Arc<RwLock<HashMap<String, Vec<u8>>>>,
+}
+
+impl InMemoryCheckpointStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of stored checkpoints
+    pub async fn len(&self) -> usize {
+        self.store.read().await.len()
+    }
+
+    /// Check if storage is empty
+    pub async fn is_empty(&self) -> bool {
+        self.store.read().await.is_empty()
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for InMemoryCheckpointStore {
+    async fn save(&self, checkpoint_id: &str, data: &[u8]) -> Result<()> {
+        validate_checkpoint_id(checkpoint_id)?;
+        self.store
+            .write()
+            .await
+            .insert(checkpoint_id.to_string(), data.to_vec());
+        Ok(())
+    }
+
+    async fn load(&self, checkpoint_id: &str) -> Result<Option<Vec<u8>>> {
+        validate_checkpoint_id(checkpoint_id)?;
+        Ok(self.store.read().await.get(checkpoint_id).cloned())
+    }
+
+    async fn delete(&self, checkpoint_id: &str) -> Result<()> {
+        validate_checkpoint_id(checkpoint_id)?;
+        self.store.write().await.remove(checkpoint_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_save_load_delete() {
+        let store = InMemoryCheckpointStore::new();
+        assert!(store.load("crawl").await.unwrap().is_none());
+
+        store.save("crawl", b"state").await.unwrap();
+        assert_eq!(store.load("crawl").await.unwrap().unwrap(), b"state");
+
+        store.delete("crawl").await.unwrap();
+        assert!(store.is_empty().await);
+        assert!(store.save("../crawl", b"state").await.is_err());
+    }
+}
@@ -0,0 +1,79 @@
+//! In-memory document classifier for testing and development
+//!
+//! Returns the classification given to [`InMemoryDocumentClassifier::new`]
+//! for every text, or an error when built with
+//! [`InMemoryDocumentClassifier::failing`].
+
+use crate::error::{Result, RiptideError};
+use crate::extracted::DocumentClassification;
+use crate::ports::classification::DocumentClassifier;
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Classifier answering with a fixed classification
+#[derive(Clone, Default)]
+pub struct InMemoryDocumentClassifier {
+    classification: Option<DocumentClassification>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl InMemoryDocumentClassifier {
+    /// Classify every text as `classification`
+    pub fn new(classification: DocumentClassification) -> Self {
+        Self {
+            classification: Some(classification),
+            calls: Arc::default(),
+        }
+    }
+
+    /// Fail every call
+    pub fn failing() -> Self {
+        Self::default()
+    }
+
+    /// Number of `classify` calls so far
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl DocumentClassifier for InMemoryDocumentClassifier {
+    async fn classify(&self, _title: Option<&str>, _text: &str) -> Result<DocumentClassification> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.classification
+            .clone()
+            .ok_or_else(|| RiptideError::Custom("classifier unavailable".to_string()))
+    }
+
+    fn name(&self) -> &str {
+        "memory"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extracted::CategoryTag;
+
+    #[tokio::test]
+    async fn test_returns_canned_classification_or_fails() {
+        let classification = DocumentClassification {
+            categories: vec![CategoryTag {
+                label: "sports".to_string(),
+                confidence: 0.9,
+            }],
+            ..Default::default()
+        };
+        let classifier = InMemoryDocumentClassifier::new(classification.clone());
+        assert_eq!(
+            classifier.classify(None, "text").await.unwrap(),
+            classification
+        );
+
+        let failing = InMemoryDocumentClassifier::failing();
+        assert!(failing.classify(None, "text").await.is_err());
+        assert_eq!(classifier.calls() + failing.calls(), 2);
+    }
+}
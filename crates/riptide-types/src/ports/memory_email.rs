@@ -0,0 +1,62 @@
+//! In-memory email sender for testing and development
+//!
+//! Records every message instead of delivering it, so tests can assert on
+//! recipients, subjects and attachments.
+
+use crate::error::Result;
+use crate::ports::email::{EmailMessage, EmailSender};
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Email sender keeping sent messages in memory
+#[derive(Clone, Default)]
+pub struct InMemoryEmailSender {
+    sent: Arc<RwLock<Vec<EmailMessage>>>,
+}
+
+impl InMemoryEmailSender {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Messages sent so far, oldest first
+    pub async fn sent(&self) -> Vec<EmailMessage> {
+        self.sent.read().await.clone()
+    }
+}
+
+#[async_trait]
+impl EmailSender for InMemoryEmailSender {
+    async fn send(&self, message: &EmailMessage) -> Result<()> {
+        message.validate()?;
+        self.sent.write().await.push(message.clone());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::email::EmailAttachment;
+
+    #[tokio::test]
+    async fn test_records_valid_messages() {
+        let sender = InMemoryEmailSender::new();
+        let message = EmailMessage::new(["ops@example.com"], "Weekly report", "See attached")
+            .with_attachment(EmailAttachment {
+                filename: "report.html".to_string(),
+                content_type: "text/html".to_string(),
+                data: b"<html></html>".to_vec(),
+            });
+
+        sender.send(&message).await.unwrap();
+        assert_eq!(sender.sent().await, vec![message]);
+
+        let no_recipients = EmailMessage::new(Vec::<String>::new(), "Report", "");
+        assert!(sender.send(&no_recipients).await.is_err());
+        let header_injection = EmailMessage::new(["ops@example.com"], "Hi\r\nBcc: x@y", "");
+        assert!(sender.send(&header_injection).await.is_err());
+        assert_eq!(sender.sent().await.len(), 1);
+    }
+}
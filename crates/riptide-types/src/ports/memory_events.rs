@@ -0,0 +1,179 @@
+//! Recording event bus for testing
+//!
+//! [`RecordingEventBus`] keeps every published event and delivers it to the
+//! subscribed handlers before `publish` returns, so tests can assert on
+//! events and on handler side effects without waiting. Handler failures are
+//! recorded instead of failing the publisher, as with a real broker.
+
+use crate::error::Result;
+use crate::ports::events::{DomainEvent, EventBus, EventHandler, SubscriptionId};
+use async_trait::async_trait;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+#[derive(Default)]
+struct BusState {
+    published: Vec<DomainEvent>,
+    handlers: Vec<(SubscriptionId, Arc<dyn EventHandler>)>,
+    handler_errors: Vec<(String, String)>,
+    next_subscription: usize,
+}
+
+/// Event bus recording published events and delivering them synchronously
+#[derive(Clone, Default)]
+pub struct RecordingEventBus {
+    state: Arc<Mutex<BusState>>,
+}
+
+impl RecordingEventBus {
+    /// Create a bus without events or subscribers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Events published so far, in order
+    pub fn published(&self) -> Vec<DomainEvent> {
+        self.lock().published.clone()
+    }
+
+    /// Events of `event_type` published so far, in order
+    pub fn published_of_type(&self, event_type: &str) -> Vec<DomainEvent> {
+        self.lock()
+            .published
+            .iter()
+            .filter(|event| event.event_type == event_type)
+            .cloned()
+            .collect()
+    }
+
+    /// Event ID and error of every failed delivery
+    pub fn handler_errors(&self) -> Vec<(String, String)> {
+        self.lock().handler_errors.clone()
+    }
+
+    /// Number of active subscriptions
+    pub fn subscriber_count(&self) -> usize {
+        self.lock().handlers.len()
+    }
+
+    /// Forget published events and handler errors, keeping subscriptions
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.published.clear();
+        state.handler_errors.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BusState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl EventBus for RecordingEventBus {
+    async fn publish(&self, event: DomainEvent) -> Result<()> {
+        // Handlers run without the lock so they may publish in turn
+        let handlers: Vec<Arc<dyn EventHandler>> = {
+            let mut state = self.lock();
+            state.published.push(event.clone());
+            state
+                .handlers
+                .iter()
+                .map(|(_, handler)| handler.clone())
+                .collect()
+        };
+
+        for handler in handlers {
+            let wanted = handler
+                .event_types()
+                .is_none_or(|types| types.contains(&event.event_type));
+            if !wanted {
+                continue;
+            }
+            if let Err(e) = handler.handle(&event).await {
+                self.lock()
+                    .handler_errors
+                    .push((event.id.clone(), e.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    async fn subscribe(&self, handler: Arc<dyn EventHandler>) -> Result<SubscriptionId> {
+        let mut state = self.lock();
+        state.next_subscription += 1;
+        let id = format!("recording-sub-{}", state.next_subscription);
+        state.handlers.push((id.clone(), handler));
+        Ok(id)
+    }
+
+    async fn unsubscribe(&self, subscription_id: &str) -> Result<()> {
+        self.lock().handlers.retain(|(id, _)| id != subscription_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::RiptideError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Counter {
+        handled: AtomicUsize,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl EventHandler for Counter {
+        async fn handle(&self, _event: &DomainEvent) -> Result<()> {
+            self.handled.fetch_add(1, Ordering::SeqCst);
+            if self.fail {
+                return Err(RiptideError::Custom("handler failed".to_string()));
+            }
+            Ok(())
+        }
+
+        fn event_types(&self) -> Option<Vec<String>> {
+            Some(vec!["crawl.completed".to_string()])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_records_and_delivers_events() {
+        let bus = RecordingEventBus::new();
+        let counter = Arc::new(Counter {
+            handled: AtomicUsize::new(0),
+            fail: false,
+        });
+        let failing = Arc::new(Counter {
+            handled: AtomicUsize::new(0),
+            fail: true,
+        });
+        let subscription = bus.subscribe(counter.clone()).await.unwrap();
+        bus.subscribe(failing).await.unwrap();
+
+        let event = DomainEvent::new("crawl.completed", "job-1", serde_json::json!({}));
+        bus.publish(event.clone()).await.unwrap();
+        bus.publish(DomainEvent::new(
+            "crawl.started",
+            "job-2",
+            serde_json::json!({}),
+        ))
+        .await
+        .unwrap();
+
+        assert_eq!(bus.published().len(), 2);
+        assert_eq!(bus.published_of_type("crawl.completed")[0].id, event.id);
+        assert_eq!(counter.handled.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            bus.handler_errors(),
+            vec![(event.id.clone(), "handler failed".to_string())]
+        );
+
+        bus.unsubscribe(&subscription).await.unwrap();
+        assert_eq!(bus.subscriber_count(), 1);
+        bus.clear();
+        assert!(bus.published().is_empty());
+    }
+}
@@ -0,0 +1,102 @@
+//! In-memory feature flags for testing and static configuration
+//!
+//! Holds a fixed set of enabled flags and running experiments. The API
+//! loads it from the experiments file; tests build it directly.
+
+use crate::error::Result;
+use crate::ports::feature_flags::{Experiment, FeatureFlags};
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Thread-safe in-memory flag and experiment store
+#[derive(Clone, Default)]
+pub struct InMemoryFeatureFlags {
+    flags: Arc<RwLock<HashSet<String>>>,
+    experiments: Arc<RwLock<Vec<Experiment>>>,
+}
+
+impl InMemoryFeatureFlags {
+    /// Create a store with no flags and no experiments
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a store running the given experiments
+    pub fn with_experiments(experiments: Vec<Experiment>) -> Self {
+        Self {
+            flags: Arc::default(),
+            experiments: Arc::new(RwLock::new(experiments)),
+        }
+    }
+
+    /// Turn a flag on or off
+    pub async fn set_flag(&self, flag: impl Into<String>, enabled: bool) {
+        let flag = flag.into();
+        let mut flags = self.flags.write().await;
+        if enabled {
+            flags.insert(flag);
+        } else {
+            flags.remove(&flag);
+        }
+    }
+
+    /// Replace the running experiments
+    pub async fn set_experiments(&self, experiments: Vec<Experiment>) {
+        *self.experiments.write().await = experiments;
+    }
+}
+
+#[async_trait]
+impl FeatureFlags for InMemoryFeatureFlags {
+    async fn is_enabled(&self, flag: &str) -> Result<bool> {
+        Ok(self.flags.read().await.contains(flag))
+    }
+
+    async fn experiments(&self) -> Result<Vec<Experiment>> {
+        Ok(self.experiments.read().await.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ports::feature_flags::{ExperimentArm, ExperimentVariant};
+
+    #[tokio::test]
+    async fn test_flags_and_assignment() {
+        let flags = InMemoryFeatureFlags::with_experiments(vec![Experiment {
+            id: "native-extractor".to_string(),
+            domains: vec!["example.com".to_string()],
+            traffic_percent: 100,
+            variant: ExperimentVariant {
+                extractor: Some("native".to_string()),
+                ..ExperimentVariant::default()
+            },
+        }]);
+
+        assert!(!flags.is_enabled("experiments").await.unwrap());
+        flags.set_flag("experiments", true).await;
+        assert!(flags.is_enabled("experiments").await.unwrap());
+
+        let assignment = flags
+            .assign("https://www.example.com/a")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(assignment.arm, ExperimentArm::Treatment);
+        assert_eq!(assignment.extractor(), Some("native"));
+        assert_eq!(assignment.tag().experiment_id, "native-extractor");
+
+        assert!(flags.assign("https://other.org/").await.unwrap().is_none());
+        assert!(flags.assign("not a url").await.unwrap().is_none());
+
+        flags.set_experiments(Vec::new()).await;
+        assert!(flags
+            .assign("https://www.example.com/a")
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,59 @@
+//! In-memory GeoIP lookup for testing and development
+//!
+//! Answers from a fixed table of addresses, so tests can exercise
+//! jurisdiction rules without shipping a MaxMind database.
+
+use crate::error::Result;
+use crate::ports::geoip::{GeoIpLookup, GeoLocation};
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+/// Static address-to-location table
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryGeoIp {
+    entries: HashMap<IpAddr, GeoLocation>,
+}
+
+impl InMemoryGeoIp {
+    /// Create an empty table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the location of an address
+    pub fn with_entry(mut self, ip: IpAddr, location: GeoLocation) -> Self {
+        self.entries.insert(ip, location);
+        self
+    }
+}
+
+impl GeoIpLookup for InMemoryGeoIp {
+    fn lookup(&self, ip: IpAddr) -> Result<Option<GeoLocation>> {
+        Ok(self.entries.get(&ip).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        let ip: IpAddr = "203.0.113.7".parse().unwrap();
+        let geoip = InMemoryGeoIp::new().with_entry(
+            ip,
+            GeoLocation {
+                country_code: Some("DE".to_string()),
+                asn: Some(24940),
+                as_org: None,
+            },
+        );
+
+        let location = geoip.lookup(ip).unwrap().unwrap();
+        assert_eq!(location.country_code.as_deref(), Some("DE"));
+        assert!(geoip
+            .lookup("198.51.100.1".parse().unwrap())
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,185 @@
+//! In-memory message queue for testing and development
+//!
+//! Leases expire on the wall clock; an expired lease puts the message back
+//! at the front of its queue on the next [`receive`](MessageQueue::receive).
+//! Acknowledged messages are kept so tests can assert on what was processed.
+
+use crate::error::{Result, RiptideError};
+use crate::ports::queue::{MessageQueue, QueuedMessage};
+use async_trait::async_trait;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime};
+
+#[derive(Default)]
+struct QueueState {
+    ready: VecDeque<QueuedMessage>,
+    leased: HashMap<String, (QueuedMessage, Instant)>,
+    acked: Vec<QueuedMessage>,
+}
+
+impl QueueState {
+    fn requeue_expired(&mut self, now: Instant) {
+        let expired: Vec<String> = self
+            .leased
+            .iter()
+            .filter(|(_, (_, deadline))| *deadline <= now)
+            .map(|(id, _)| id.clone())
+            .collect();
+        for id in expired {
+            if let Some((message, _)) = self.leased.remove(&id) {
+                self.ready.push_front(message);
+            }
+        }
+    }
+}
+
+/// Thread-safe in-memory message queue
+#[derive(Clone, Default)]
+pub struct FakeMessageQueue {
+    queues: Arc<Mutex<HashMap<String, QueueState>>>,
+}
+
+impl FakeMessageQueue {
+    /// Create a queue without messages
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Messages of `queue` currently leased
+    pub fn in_flight(&self, queue: &str) -> usize {
+        self.lock().get(queue).map_or(0, |state| state.leased.len())
+    }
+
+    /// Messages of `queue` acknowledged so far, in order
+    pub fn acked(&self, queue: &str) -> Vec<QueuedMessage> {
+        self.lock()
+            .get(queue)
+            .map(|state| state.acked.clone())
+            .unwrap_or_default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, QueueState>> {
+        self.queues
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// End the lease of a message, acknowledging or requeueing it
+    fn settle(&self, queue: &str, message_id: &str, requeue: bool) -> Result<()> {
+        let not_found =
+            || RiptideError::NotFound(format!("No leased message {} in {}", message_id, queue));
+        let mut queues = self.lock();
+        let state = queues.get_mut(queue).ok_or_else(not_found)?;
+        let (message, _) = state.leased.remove(message_id).ok_or_else(not_found)?;
+        if requeue {
+            state.ready.push_front(message);
+        } else {
+            state.acked.push(message);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MessageQueue for FakeMessageQueue {
+    async fn enqueue(&self, queue: &str, payload: &[u8]) -> Result<String> {
+        let id = uuid::Uuid::new_v4().to_string();
+        self.lock()
+            .entry(queue.to_string())
+            .or_default()
+            .ready
+            .push_back(QueuedMessage {
+                id: id.clone(),
+                payload: payload.to_vec(),
+                attempts: 0,
+                enqueued_at: SystemTime::now(),
+            });
+        Ok(id)
+    }
+
+    async fn receive(
+        &self,
+        queue: &str,
+        visibility_timeout: Duration,
+    ) -> Result<Option<QueuedMessage>> {
+        let now = Instant::now();
+        let mut queues = self.lock();
+        let Some(state) = queues.get_mut(queue) else {
+            return Ok(None);
+        };
+        state.requeue_expired(now);
+        let Some(mut message) = state.ready.pop_front() else {
+            return Ok(None);
+        };
+        message.attempts += 1;
+        state.leased.insert(
+            message.id.clone(),
+            (message.clone(), now + visibility_timeout),
+        );
+        Ok(Some(message))
+    }
+
+    async fn ack(&self, queue: &str, message_id: &str) -> Result<()> {
+        self.settle(queue, message_id, false)
+    }
+
+    async fn nack(&self, queue: &str, message_id: &str) -> Result<()> {
+        self.settle(queue, message_id, true)
+    }
+
+    async fn depth(&self, queue: &str) -> Result<usize> {
+        let mut queues = self.lock();
+        Ok(queues.get_mut(queue).map_or(0, |state| {
+            state.requeue_expired(Instant::now());
+            state.ready.len()
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_lease_ack_nack_and_redelivery() {
+        let queue = FakeMessageQueue::new();
+        queue.enqueue("jobs", b"first").await.unwrap();
+        queue.enqueue("jobs", b"second").await.unwrap();
+        assert_eq!(queue.depth("jobs").await.unwrap(), 2);
+
+        let first = queue
+            .receive("jobs", Duration::from_secs(60))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.payload, b"first");
+        assert_eq!(queue.in_flight("jobs"), 1);
+        queue.nack("jobs", &first.id).await.unwrap();
+
+        let again = queue
+            .receive("jobs", Duration::ZERO)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((again.id.as_str(), again.attempts), (first.id.as_str(), 2));
+
+        // The zero lease has already expired, so the message comes back
+        let third = queue
+            .receive("jobs", Duration::from_secs(60))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((third.id.as_str(), third.attempts), (first.id.as_str(), 3));
+        queue.ack("jobs", &third.id).await.unwrap();
+        assert!(queue.ack("jobs", &third.id).await.is_err());
+
+        assert_eq!(queue.acked("jobs").len(), 1);
+        assert_eq!(queue.depth("jobs").await.unwrap(), 1);
+        assert!(queue
+            .receive("empty", Duration::ZERO)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,177 @@
+//! In-memory search engine for testing and development
+//!
+//! [`FakeSearchEngine`] scores documents by how often the query terms occur
+//! in their title (weighted double) and content, or in the requested fields
+//! only. Filters must equal the document's field value. Ties are broken by
+//! document ID so results are deterministic.
+
+use crate::error::Result;
+use crate::ports::features::{SearchDocument, SearchEngine, SearchQuery, SearchResult};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Length of result snippets, in characters
+const SNIPPET_CHARS: usize = 160;
+
+/// Thread-safe in-memory search index
+#[derive(Clone, Default)]
+pub struct FakeSearchEngine {
+    documents: Arc<RwLock<BTreeMap<String, SearchDocument>>>,
+}
+
+impl FakeSearchEngine {
+    /// Create an empty index
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of indexed documents
+    pub async fn len(&self) -> usize {
+        self.documents.read().await.len()
+    }
+
+    /// Check if the index is empty
+    pub async fn is_empty(&self) -> bool {
+        self.documents.read().await.is_empty()
+    }
+
+    /// Indexed document with `id`
+    pub async fn document(&self, id: &str) -> Option<SearchDocument> {
+        self.documents.read().await.get(id).cloned()
+    }
+}
+
+fn occurrences(text: &str, terms: &[String]) -> usize {
+    let text = text.to_lowercase();
+    terms
+        .iter()
+        .map(|term| text.matches(term.as_str()).count())
+        .sum()
+}
+
+fn score(document: &SearchDocument, query: &SearchQuery, terms: &[String]) -> usize {
+    if query.fields.is_empty() {
+        return 2 * occurrences(&document.title, terms) + occurrences(&document.content, terms);
+    }
+    query
+        .fields
+        .iter()
+        .map(|field| match field.as_str() {
+            "title" => occurrences(&document.title, terms),
+            "content" => occurrences(&document.content, terms),
+            other => document
+                .fields
+                .get(other)
+                .map(|value| match value {
+                    serde_json::Value::String(s) => occurrences(s, terms),
+                    value => occurrences(&value.to_string(), terms),
+                })
+                .unwrap_or(0),
+        })
+        .sum()
+}
+
+#[async_trait]
+impl SearchEngine for FakeSearchEngine {
+    async fn index(&self, document: SearchDocument) -> Result<()> {
+        self.documents
+            .write()
+            .await
+            .insert(document.id.clone(), document);
+        Ok(())
+    }
+
+    async fn search(&self, query: SearchQuery) -> Result<Vec<SearchResult>> {
+        let terms: Vec<String> = query
+            .query
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect();
+        let documents = self.documents.read().await;
+
+        let mut scored: Vec<(usize, &SearchDocument)> = documents
+            .values()
+            .filter(|document| {
+                query
+                    .filters
+                    .iter()
+                    .all(|(key, value)| document.fields.get(key) == Some(value))
+            })
+            .map(|document| (score(document, &query, &terms), document))
+            .filter(|(score, _)| terms.is_empty() || *score > 0)
+            .collect();
+        // Stable sort keeps ID order among equal scores
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+        Ok(scored
+            .into_iter()
+            .skip(query.offset.unwrap_or(0))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .map(|(score, document)| SearchResult {
+                id: document.id.clone(),
+                score: score as f64,
+                title: document.title.clone(),
+                snippet: document.content.chars().take(SNIPPET_CHARS).collect(),
+                fields: document.fields.clone(),
+            })
+            .collect())
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        self.documents.write().await.remove(id);
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<()> {
+        self.documents.write().await.clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_ranking_filters_and_paging() {
+        let engine = FakeSearchEngine::new();
+        engine
+            .index_batch(vec![
+                SearchDocument::new("a", "Rust crawler", "A crawler written in Rust")
+                    .with_field("lang", serde_json::json!("en")),
+                SearchDocument::new("b", "Crawling", "Notes on rust and crawling")
+                    .with_field("lang", serde_json::json!("en")),
+                SearchDocument::new("c", "Rust", "Rouille")
+                    .with_field("lang", serde_json::json!("fr")),
+            ])
+            .await
+            .unwrap();
+
+        let ids =
+            |results: Vec<SearchResult>| results.into_iter().map(|r| r.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(engine.search(SearchQuery::new("rust")).await.unwrap()),
+            vec!["a", "c", "b"]
+        );
+        assert_eq!(
+            ids(engine
+                .search(SearchQuery::new("rust").with_filter("lang", serde_json::json!("en")))
+                .await
+                .unwrap()),
+            vec!["a", "b"]
+        );
+        assert_eq!(
+            ids(engine
+                .search(SearchQuery::new("rust").with_field("content").with_limit(1))
+                .await
+                .unwrap()),
+            vec!["a"]
+        );
+
+        engine.delete("a").await.unwrap();
+        engine.clear().await.unwrap();
+        assert!(engine.is_empty().await);
+    }
+}
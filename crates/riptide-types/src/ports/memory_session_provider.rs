@@ -0,0 +1,82 @@
+//! In-memory session provider for testing and static credentials
+//!
+//! Holds one session per domain. Renewal hands out whatever session is
+//! stored at that point, so callers can swap in fresh credentials with
+//! [`InMemorySessionProvider::set_session`] while a crawl is running.
+
+use crate::error::Result;
+use crate::ports::session_provider::{AuthSession, SessionProvider};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Thread-safe in-memory session provider
+#[derive(Clone, Default)]
+pub struct InMemorySessionProvider {
+    sessions: Arc<RwLock<HashMap<String, AuthSession>>>,
+    renewals: Arc<AtomicUsize>,
+}
+
+impl InMemorySessionProvider {
+    /// Create a provider without sessions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the session for `domain`
+    pub async fn set_session(&self, domain: impl Into<String>, session: AuthSession) {
+        self.sessions
+            .write()
+            .await
+            .insert(domain.into().to_ascii_lowercase(), session);
+    }
+
+    /// Number of renewals requested so far
+    pub fn renewals(&self) -> usize {
+        self.renewals.load(Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl SessionProvider for InMemorySessionProvider {
+    async fn session(&self, domain: &str) -> Result<Option<AuthSession>> {
+        Ok(self
+            .sessions
+            .read()
+            .await
+            .get(&domain.to_ascii_lowercase())
+            .cloned())
+    }
+
+    async fn renew(&self, domain: &str) -> Result<Option<AuthSession>> {
+        self.renewals.fetch_add(1, Ordering::Relaxed);
+        self.session(domain).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sessions_per_domain() {
+        let provider = InMemorySessionProvider::new();
+        assert!(provider.session("example.com").await.unwrap().is_none());
+
+        provider
+            .set_session("Example.com", AuthSession::with_cookies([("sid", "1")]))
+            .await;
+        let session = provider.session("example.com").await.unwrap().unwrap();
+        assert_eq!(session.cookie_header().as_deref(), Some("sid=1"));
+        assert!(provider.session("other.org").await.unwrap().is_none());
+
+        provider
+            .set_session("example.com", AuthSession::with_cookies([("sid", "2")]))
+            .await;
+        let renewed = provider.renew("example.com").await.unwrap().unwrap();
+        assert_eq!(renewed.cookie_header().as_deref(), Some("sid=2"));
+        assert_eq!(provider.renewals(), 1);
+    }
+}
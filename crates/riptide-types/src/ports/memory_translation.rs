@@ -0,0 +1,87 @@
+//! In-memory translator for testing and development
+//!
+//! Returns translations registered with
+//! [`InMemoryTranslator::with_translation`] and marks any other text with
+//! its target language, e.g. `"[fr] Hello"`, so tests can tell translated
+//! text apart without a model.
+
+use crate::error::Result;
+use crate::ports::translation::Translator;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// Translator answering from a fixed dictionary
+#[derive(Clone, Default)]
+pub struct InMemoryTranslator {
+    translations: HashMap<(String, String), String>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl InMemoryTranslator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Translate `text` into `target_language` as `translation`
+    pub fn with_translation(
+        mut self,
+        text: impl Into<String>,
+        target_language: impl Into<String>,
+        translation: impl Into<String>,
+    ) -> Self {
+        self.translations
+            .insert((text.into(), target_language.into()), translation.into());
+        self
+    }
+
+    /// Number of `translate` calls so far
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::Relaxed)
+    }
+}
+
+#[async_trait]
+impl Translator for InMemoryTranslator {
+    async fn translate(
+        &self,
+        text: &str,
+        _source_language: Option<&str>,
+        target_language: &str,
+    ) -> Result<String> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        Ok(self
+            .translations
+            .get(&(text.to_string(), target_language.to_string()))
+            .cloned()
+            .unwrap_or_else(|| format!("[{}] {}", target_language, text)))
+    }
+
+    fn name(&self) -> &str {
+        "memory"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_returns_registered_or_marked_text() {
+        let translator = InMemoryTranslator::new().with_translation("Hallo", "en", "Hello");
+
+        assert_eq!(
+            translator
+                .translate("Hallo", Some("de"), "en")
+                .await
+                .unwrap(),
+            "Hello"
+        );
+        assert_eq!(
+            translator.translate("Welt", None, "en").await.unwrap(),
+            "[en] Welt"
+        );
+        assert_eq!(translator.calls(), 2);
+    }
+}
@@ -26,10 +26,27 @@
 //!
 //! ## Features
 //! - **features**: Browser automation, PDF processing, search engine
+//! - **feature_flags**: Runtime flags and A/B experiment assignment
 //!
 //! ## Infrastructure
 //! - **infrastructure**: Clock, entropy, and cache abstractions
 //! - **cache**: Cache storage (from Phase 0)
+//! - **archive**: Web archive (WARC) record sink
+//! - **blob**: Binary artifact storage (screenshots, thumbnails)
+//! - **email**: Outbound email delivery (reports, notifications)
+//! - **checkpoint**: Checkpoint storage for resumable jobs
+//! - **session_provider**: Cookies and headers for authenticated crawling
+//! - **url_policy**: Admission checks for URLs a crawl discovers
+//! - **queue**: Message queue with at-least-once delivery
+//! - **translation**: Machine translation of extracted text
+//! - **annotation**: Named entities, keywords and topics of extracted text
+//! - **classification**: Taxonomy categories and sentiment of documents
+//!
+//! ## Test Doubles
+//! Every port has an in-memory implementation (`memory_*` modules), so
+//! facades can be tested without Redis, Chrome or a search backend, e.g.
+//! [`FakeBrowserDriver`] with scripted pages, [`FakeSearchEngine`],
+//! [`FakeMessageQueue`] and the [`RecordingEventBus`].
 //!
 //! # Example
 //!
@@ -55,9 +72,11 @@ pub mod memory_cache;
 
 // Phase 1 ports
 pub mod events;
+pub mod feature_flags;
 pub mod features;
 pub mod idempotency;
 pub mod infrastructure;
+pub mod memory_feature_flags;
 pub mod memory_idempotency;
 pub mod memory_session;
 pub mod repository;
@@ -66,6 +85,8 @@ pub mod session;
 // Phase 2 ports
 pub mod circuit_breaker;
 pub mod extractor;
+pub mod geoip;
+pub mod memory_geoip;
 
 // Sprint 1.5 ports
 pub mod health;
@@ -89,47 +110,117 @@ pub mod worker;
 // Coordination port (Redis-optional refactoring)
 pub mod coordination;
 
+// Web archive port
+pub mod archive;
+
+// Blob storage port
+pub mod blob;
+pub mod memory_blob;
+
+// Outbound email port
+pub mod email;
+pub mod memory_email;
+
+// Checkpoint storage port
+pub mod checkpoint;
+pub mod memory_checkpoint;
+
+// Message queue port
+pub mod memory_queue;
+pub mod queue;
+
+// Test doubles for feature and event ports
+pub mod memory_browser;
+pub mod memory_events;
+pub mod memory_search;
+
+// Session provider port (authenticated crawling)
+pub mod memory_session_provider;
+pub mod session_provider;
+
+// URL policy port (crawl admission checks)
+pub mod url_policy;
+
+// Machine translation port
+pub mod memory_translation;
+pub mod translation;
+
+// Text annotation port
+pub mod annotation;
+pub mod memory_annotation;
+
+// Document classification port
+pub mod classification;
+pub mod memory_classification;
+
 // Spider port
 #[cfg(feature = "spider")]
 pub mod spider;
 
 // Re-export all ports for convenience
+pub use annotation::TextAnnotator;
+pub use archive::{ArchivedExchange, WebArchive};
+pub use blob::{validate_blob_key, BlobStorage, StoredBlob};
 pub use cache::{CacheStats, CacheStorage};
+pub use checkpoint::{validate_checkpoint_id, CheckpointStore};
 pub use circuit_breaker::{
     with_circuit_breaker, CircuitBreaker, CircuitBreakerConfig, CircuitBreakerPermit,
     CircuitBreakerStats, CircuitState,
 };
+pub use classification::DocumentClassifier;
 pub use coordination::{
     CoordinationResult, DistributedCoordination, Subscriber, SubscriberMessage,
 };
+pub use email::{EmailAttachment, EmailMessage, EmailSender};
 pub use events::{DomainEvent, EventBus, EventHandler, SubscriptionId};
 pub use extractor::{
     ContentExtractor, ExtractionResult, ReliabilityStats, ReliableContentExtractor,
 };
+pub use feature_flags::{
+    Experiment, ExperimentArm, ExperimentAssignment, ExperimentTag, ExperimentVariant, FeatureFlags,
+};
 pub use features::{
     BrowserDriver, BrowserSession, PdfMetadata, PdfProcessor, ScriptResult, SearchDocument,
     SearchEngine, SearchQuery, SearchResult,
 };
+pub use geoip::{GeoIpLookup, GeoLocation};
 pub use health::{HealthCheck, HealthRegistry, HealthStatus};
 pub use http::{HttpClient, HttpRequest, HttpResponse};
 pub use idempotency::{IdempotencyStore, IdempotencyToken};
 pub use infrastructure::{
     Clock, DeterministicEntropy, Entropy, FakeClock, SystemClock, SystemEntropy,
 };
+pub use memory_annotation::InMemoryTextAnnotator;
+pub use memory_blob::InMemoryBlobStorage;
+pub use memory_browser::FakeBrowserDriver;
 pub use memory_cache::InMemoryCache;
+pub use memory_checkpoint::InMemoryCheckpointStore;
+pub use memory_classification::InMemoryDocumentClassifier;
+pub use memory_email::InMemoryEmailSender;
+pub use memory_events::RecordingEventBus;
+pub use memory_feature_flags::InMemoryFeatureFlags;
+pub use memory_geoip::InMemoryGeoIp;
 pub use memory_idempotency::InMemoryIdempotencyStore;
+pub use memory_queue::FakeMessageQueue;
+pub use memory_search::FakeSearchEngine;
 pub use memory_session::InMemorySessionStorage;
+pub use memory_session_provider::InMemorySessionProvider;
+pub use memory_translation::InMemoryTranslator;
 pub use metrics::{BusinessMetrics, MetricsCollector, MetricsRegistry};
 pub use pool::{Pool, PoolError, PoolHealth, PoolStats, PooledResource};
+pub use queue::{MessageQueue, QueuedMessage};
 pub use rate_limit::{HostStats, PerHostRateLimiter, RateLimitStats, RateLimiter};
 pub use repository::{Repository, RepositoryFilter, Transaction, TransactionManager};
 pub use session::{Session, SessionFilter, SessionStorage};
+pub use session_provider::{AuthSession, SessionProvider};
 pub use streaming::{
     DeepSearchMetadata, DeepSearchResultData, ProcessedResult, StreamCompletionSummary,
     StreamConfig, StreamErrorData, StreamEvent, StreamLifecycle, StreamMetadata, StreamMetrics,
     StreamProcessor, StreamProgress, StreamResult, StreamResultData, StreamState, StreamSummary,
     StreamingTransport,
 };
+pub use translation::Translator;
+pub use url_policy::UrlPolicy;
 
 #[cfg(feature = "spider")]
 pub use spider::{CrawlResults, CrawlState, PerformanceMetrics, SpiderEngine};
@@ -0,0 +1,67 @@
+//! Message queue port for distributing work
+//!
+//! Delivery is at-least-once: a received message is leased for a visibility
+//! timeout and delivered again unless it is acknowledged before the lease
+//! expires. Consumers must therefore be idempotent.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use riptide_types::ports::MessageQueue;
+//! use std::time::Duration;
+//!
+//! async fn example(queue: &dyn MessageQueue) -> Result<()> {
+//!     queue.enqueue("crawl", b"https://example.com").await?;
+//!
+//!     if let Some(message) = queue.receive("crawl", Duration::from_secs(30)).await? {
+//!         match process(&message.payload).await {
+//!             Ok(()) => queue.ack("crawl", &message.id).await?,
+//!             Err(_) => queue.nack("crawl", &message.id).await?,
+//!         }
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::error::Result as RiptideResult;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+
+/// A message received from a queue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueuedMessage {
+    /// Identifier used to acknowledge the message
+    pub id: String,
+    /// Message body
+    pub payload: Vec<u8>,
+    /// Deliveries so far, including this one
+    pub attempts: u32,
+    /// When the message was enqueued
+    pub enqueued_at: SystemTime,
+}
+
+/// Backend-agnostic work queue
+#[async_trait]
+pub trait MessageQueue: Send + Sync {
+    /// Append a message to `queue`, returning its ID
+    async fn enqueue(&self, queue: &str, payload: &[u8]) -> RiptideResult<String>;
+
+    /// Lease the oldest available message of `queue` for `visibility_timeout`
+    ///
+    /// Returns `None` when no message is available.
+    async fn receive(
+        &self,
+        queue: &str,
+        visibility_timeout: Duration,
+    ) -> RiptideResult<Option<QueuedMessage>>;
+
+    /// Remove a received message for good
+    async fn ack(&self, queue: &str, message_id: &str) -> RiptideResult<()>;
+
+    /// Make a received message available again immediately
+    async fn nack(&self, queue: &str, message_id: &str) -> RiptideResult<()>;
+
+    /// Number of messages waiting to be received, excluding leased ones
+    async fn depth(&self, queue: &str) -> RiptideResult<usize>;
+}
@@ -0,0 +1,143 @@
+//! Session provider port for authenticated crawling
+//!
+//! A session provider hands a crawler the cookies and headers to send to a
+//! domain, so spiders can crawl pages behind a login. When a response shows
+//! the session is no longer accepted (`401 Unauthorized` or a redirect to the
+//! login page), the crawler asks the provider to renew it and retries once.
+//! Adapters may read browser sessions stored by the API, replay a login
+//! flow, or serve static credentials.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use riptide_types::ports::SessionProvider;
+//!
+//! async fn credentials(provider: &dyn SessionProvider, host: &str) -> Option<String> {
+//!     let session = provider.session(host).await.ok()??;
+//!     session.cookie_header()
+//! }
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::error::Result;
+
+/// Path fragments that mark a login page when no login URL is configured
+const LOGIN_PATH_MARKERS: &[&str] = &["login", "signin", "sign-in", "sign_in", "logon"];
+
+/// Cookies and headers that authenticate requests to one domain
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthSession {
+    /// Cookies sent with every request, by name
+    #[serde(default)]
+    pub cookies: BTreeMap<String, String>,
+    /// Extra request headers, e.g. `Authorization`
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// Login page URL; responses redirected here mean the session expired
+    #[serde(default)]
+    pub login_url: Option<String>,
+}
+
+impl AuthSession {
+    /// Session sending the given cookies
+    pub fn with_cookies<I, K, V>(cookies: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        Self {
+            cookies: cookies
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Add a request header
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Set the login page URL
+    pub fn with_login_url(mut self, login_url: impl Into<String>) -> Self {
+        self.login_url = Some(login_url.into());
+        self
+    }
+
+    /// `Cookie` header value, `None` without cookies
+    pub fn cookie_header(&self) -> Option<String> {
+        if self.cookies.is_empty() {
+            return None;
+        }
+        Some(
+            self.cookies
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<_>>()
+                .join("; "),
+        )
+    }
+
+    /// Whether a request for `requested` that ended at `final_url` was sent to the login page
+    ///
+    /// With a login URL configured, the final URL must start with it;
+    /// otherwise any redirect to a path containing a login marker counts.
+    pub fn is_login_redirect(&self, requested: &str, final_url: &str) -> bool {
+        if requested == final_url {
+            return false;
+        }
+        if let Some(login_url) = &self.login_url {
+            return final_url.starts_with(login_url.as_str());
+        }
+        url::Url::parse(final_url).is_ok_and(|url| {
+            let path = url.path().to_ascii_lowercase();
+            LOGIN_PATH_MARKERS
+                .iter()
+                .any(|marker| path.contains(marker))
+        })
+    }
+}
+
+/// Session provider port trait
+#[async_trait]
+pub trait SessionProvider: Send + Sync {
+    /// Session to use for requests to `domain`, `None` to crawl it anonymously
+    async fn session(&self, domain: &str) -> Result<Option<AuthSession>>;
+
+    /// Replace a session the server rejected
+    ///
+    /// Returns `None` when no fresh session can be obtained; the crawler
+    /// then records the page as failed instead of retrying.
+    async fn renew(&self, domain: &str) -> Result<Option<AuthSession>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cookie_header() {
+        assert_eq!(AuthSession::default().cookie_header(), None);
+        let session = AuthSession::with_cookies([("sid", "abc"), ("csrf", "x")]);
+        assert_eq!(session.cookie_header().as_deref(), Some("csrf=x; sid=abc"));
+    }
+
+    #[test]
+    fn test_login_redirect_detection() {
+        let page = "https://example.com/account";
+        let session = AuthSession::default();
+        assert!(!session.is_login_redirect(page, page));
+        assert!(session.is_login_redirect(page, "https://example.com/users/sign-in?next=%2F"));
+        assert!(!session.is_login_redirect(page, "https://example.com/account/"));
+
+        let session = session.with_login_url("https://sso.example.com/auth");
+        assert!(session.is_login_redirect(page, "https://sso.example.com/auth?client=1"));
+        assert!(!session.is_login_redirect(page, "https://example.com/login"));
+    }
+}
@@ -132,6 +132,10 @@ pub struct CrawlResults {
 
     /// URLs discovered during the crawl (may be capped based on config)
     pub discovered_urls: Vec<String>,
+
+    /// Robots, crawl-delay, and rate-limit decisions made during the crawl
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compliance: Option<crate::compliance::ComplianceReport>,
 }
 
 /// Performance metrics for crawl operations
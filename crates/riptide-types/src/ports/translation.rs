@@ -0,0 +1,39 @@
+//! Machine translation port
+//!
+//! Translates extracted text into another language. The LLM-backed adapter
+//! lives in `riptide-facade` (`LlmTranslator`);
+//! [`InMemoryTranslator`](crate::ports::InMemoryTranslator) returns canned
+//! translations for tests.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use riptide_types::ports::Translator;
+//!
+//! async fn to_english(translator: &dyn Translator, text: &str) -> riptide_types::Result<String> {
+//!     translator.translate(text, Some("de"), "en").await
+//! }
+//! ```
+
+use async_trait::async_trait;
+
+use crate::error::Result;
+
+/// Translation port trait
+#[async_trait]
+pub trait Translator: Send + Sync {
+    /// Translate `text` into `target_language`
+    ///
+    /// Language tags are BCP 47 (`"en"`, `"pt-BR"`). `source_language` is a
+    /// hint; translators detect the language themselves when it is `None`.
+    /// Paragraph breaks should be preserved.
+    async fn translate(
+        &self,
+        text: &str,
+        source_language: Option<&str>,
+        target_language: &str,
+    ) -> Result<String>;
+
+    /// Identifier recorded on translated documents, e.g. `"llm:gpt-4o-mini"`
+    fn name(&self) -> &str;
+}
@@ -0,0 +1,25 @@
+//! URL policy port for crawl admission checks
+//!
+//! A crawler consults its URL policy before a URL enters the frontier, so
+//! rules such as blocked domains or server jurisdictions apply to every
+//! page a crawl discovers, not only to the seeds it was started with.
+//! Adapters typically evaluate a tenant's crawl policy.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use riptide_types::ports::UrlPolicy;
+//!
+//! async fn admit(policy: &dyn UrlPolicy, url: &str) -> bool {
+//!     policy.denial(url).await.is_none()
+//! }
+//! ```
+
+use async_trait::async_trait;
+
+/// URL policy port trait
+#[async_trait]
+pub trait UrlPolicy: Send + Sync {
+    /// Why `url` must not be fetched, or `None` when it is allowed
+    async fn denial(&self, url: &str) -> Option<String>;
+}
@@ -0,0 +1,195 @@
+//! Explainable extraction quality
+//!
+//! An [`ExtractionQualityReport`] breaks a document's quality score into
+//! the signals it is computed from. Each signal measures one property of
+//! the page (a length, a ratio, a count), normalizes it to a 0.0-1.0
+//! score and contributes `score * weight` points; the weights add up to
+//! 100, and the points to the document's score.
+//!
+//! [`ExtractionQualityReport::shortfalls`] lists the signals that cost the
+//! most points, which is usually enough to tell why a page scored low.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_types::quality::{ExtractionQualityReport, QualitySignal, QualitySignalKind};
+//!
+//! let report = ExtractionQualityReport::from_signals(vec![
+//!     QualitySignal::new(QualitySignalKind::ContentLength, 400.0, 0.2, 60.0, "400 characters"),
+//!     QualitySignal::new(QualitySignalKind::LinkDensity, 0.1, 0.8, 40.0, "10% of text is links"),
+//! ]);
+//! assert_eq!(report.score, 44);
+//! assert_eq!(report.shortfalls()[0].kind, QualitySignalKind::ContentLength);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Version of the scoring model reports are produced with
+pub const QUALITY_MODEL_VERSION: u8 = 2;
+
+/// Property of a page that counts towards its quality score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QualitySignalKind {
+    /// The page has a title
+    Title,
+    /// Share of the title's words found at the start of the text
+    TitleMatch,
+    /// Characters of extracted text
+    ContentLength,
+    /// Paragraphs of extracted text
+    Paragraphs,
+    /// Extracted text relative to the size of the HTML
+    TextDensity,
+    /// Share of the page's text inside links (lower is better)
+    LinkDensity,
+    /// Headings, lists and links in the extracted content
+    Structure,
+    /// schema.org structured data on the page
+    Schema,
+    /// Ad slots relative to paragraphs (lower is better)
+    AdDensity,
+}
+
+impl QualitySignalKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Title => "title",
+            Self::TitleMatch => "title_match",
+            Self::ContentLength => "content_length",
+            Self::Paragraphs => "paragraphs",
+            Self::TextDensity => "text_density",
+            Self::LinkDensity => "link_density",
+            Self::Structure => "structure",
+            Self::Schema => "schema",
+            Self::AdDensity => "ad_density",
+        }
+    }
+}
+
+/// One measured signal and the points it contributes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QualitySignal {
+    pub kind: QualitySignalKind,
+    /// Raw measurement: a count, a length or a ratio
+    pub value: f64,
+    /// Measurement normalized to 0.0 (poor) - 1.0 (good)
+    pub score: f64,
+    /// Points the signal contributes at a score of 1.0
+    pub weight: f64,
+    /// `score * weight`
+    pub points: f64,
+    /// The measurement in words
+    pub explanation: String,
+}
+
+impl QualitySignal {
+    pub fn new(
+        kind: QualitySignalKind,
+        value: f64,
+        score: f64,
+        weight: f64,
+        explanation: impl Into<String>,
+    ) -> Self {
+        let score = score.clamp(0.0, 1.0);
+        Self {
+            kind,
+            value,
+            score,
+            weight,
+            points: score * weight,
+            explanation: explanation.into(),
+        }
+    }
+
+    /// Points lost to a score below 1.0
+    pub fn shortfall(&self) -> f64 {
+        self.weight - self.points
+    }
+}
+
+/// Quality score of a document with the signals behind it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExtractionQualityReport {
+    /// Scoring model version, [`QUALITY_MODEL_VERSION`] for new reports
+    pub version: u8,
+    /// Sum of the signals' points, 0-100
+    pub score: u8,
+    pub signals: Vec<QualitySignal>,
+}
+
+impl ExtractionQualityReport {
+    /// Report scoring the sum of the points of `signals`
+    pub fn from_signals(signals: Vec<QualitySignal>) -> Self {
+        let points: f64 = signals.iter().map(|s| s.points).sum();
+        Self {
+            version: QUALITY_MODEL_VERSION,
+            score: points.round().clamp(0.0, 100.0) as u8,
+            signals,
+        }
+    }
+
+    pub fn signal(&self, kind: QualitySignalKind) -> Option<&QualitySignal> {
+        self.signals.iter().find(|s| s.kind == kind)
+    }
+
+    /// Signals that lost points, those that lost the most first
+    pub fn shortfalls(&self) -> Vec<&QualitySignal> {
+        let mut shortfalls: Vec<&QualitySignal> = self
+            .signals
+            .iter()
+            .filter(|s| s.shortfall() > 0.5)
+            .collect();
+        shortfalls.sort_by(|a, b| b.shortfall().total_cmp(&a.shortfall()));
+        shortfalls
+    }
+
+    /// The `limit` largest shortfalls as `kind (explanation, -points)`
+    pub fn explain(&self, limit: usize) -> String {
+        self.shortfalls()
+            .into_iter()
+            .take(limit)
+            .map(|s| {
+                format!(
+                    "{} ({}, -{:.0})",
+                    s.kind.as_str(),
+                    s.explanation,
+                    s.shortfall()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_sums_points_and_orders_shortfalls() {
+        let report = ExtractionQualityReport::from_signals(vec![
+            QualitySignal::new(QualitySignalKind::Title, 1.0, 1.0, 10.0, "present"),
+            QualitySignal::new(QualitySignalKind::Paragraphs, 1.0, 0.2, 15.0, "1 paragraph"),
+            QualitySignal::new(QualitySignalKind::AdDensity, 0.6, 1.4, 5.0, "6 ad slots"),
+            QualitySignal::new(QualitySignalKind::Schema, 0.0, 0.0, 5.0, "none"),
+        ]);
+
+        assert_eq!(report.version, QUALITY_MODEL_VERSION);
+        // Scores are clamped to 1.0
+        assert_eq!(
+            report.signal(QualitySignalKind::AdDensity).unwrap().points,
+            5.0
+        );
+        assert_eq!(report.score, 18);
+        assert_eq!(
+            report
+                .shortfalls()
+                .iter()
+                .map(|s| s.kind)
+                .collect::<Vec<_>>(),
+            vec![QualitySignalKind::Paragraphs, QualitySignalKind::Schema]
+        );
+        assert_eq!(report.explain(1), "paragraphs (1 paragraph, -12)");
+    }
+}
@@ -0,0 +1,538 @@
+//! Bibliographic references cited by a document
+//!
+//! [`Reference`] is one bibliography entry, footnote or outbound citation
+//! link of an academic page or PDF, with the DOI, arXiv ID and year found in
+//! it, so research crawls can follow what a paper cites. Identifiers are
+//! normalised: DOIs are lowercased without a resolver prefix and arXiv IDs
+//! lose their `arXiv:` prefix and version suffix.
+//!
+//! The identifier helpers and [`parse_reference_section`] work on plain
+//! text, which is all PDF extraction provides; HTML pages are handled by the
+//! extraction crate.
+
+use serde::{Deserialize, Serialize};
+
+/// Upper bound on references taken from one document
+pub const MAX_REFERENCES: usize = 2_000;
+
+/// Headings that open a reference list, compared lowercased
+const SECTION_HEADINGS: &[&str] = &[
+    "references",
+    "reference list",
+    "bibliography",
+    "works cited",
+    "literature cited",
+    "cited literature",
+];
+
+/// Headings that end a reference list in plain text
+const SECTION_ENDS: &[&str] = &["appendix", "acknowledgments", "acknowledgements"];
+
+/// Where in the document a reference was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    /// Entry of a references or bibliography section
+    Bibliography,
+    /// Footnote or endnote
+    Footnote,
+    /// Link in the body to a DOI or arXiv resolver
+    Link,
+}
+
+/// A work cited by a document
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reference {
+    pub kind: ReferenceKind,
+    /// Marker the document cites it by, e.g. "12" for "[12]"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// The reference as written, whitespace collapsed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// Absolute URL the reference links to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// DOI without resolver prefix, lowercased ("10.1038/nature14539")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doi: Option<String>,
+    /// arXiv identifier without version ("1706.03762", "hep-th/9901001")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arxiv_id: Option<String>,
+    /// Publication year
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub year: Option<u16>,
+}
+
+impl Reference {
+    pub fn new(kind: ReferenceKind) -> Self {
+        Self {
+            kind,
+            label: None,
+            text: None,
+            url: None,
+            doi: None,
+            arxiv_id: None,
+            year: None,
+        }
+    }
+
+    /// Reference from the text of a bibliography entry or footnote, with
+    /// the identifiers, link and year it mentions
+    pub fn from_text(kind: ReferenceKind, text: &str) -> Self {
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let mut reference = Self::new(kind);
+        reference.doi = find_doi(&text);
+        reference.arxiv_id = find_arxiv_id(&text);
+        reference.url = text
+            .split(' ')
+            .find(|word| word.starts_with("http://") || word.starts_with("https://"))
+            .map(|word| trim_trailing_punctuation(word).to_string());
+        reference.year = find_year(&text);
+        reference.text = (!text.is_empty()).then_some(text);
+        reference
+    }
+
+    /// Citation link to `url` when it points at a DOI or arXiv resolver
+    pub fn from_link(url: &str, text: Option<&str>) -> Option<Self> {
+        let doi = doi_from_url(url);
+        let arxiv_id = arxiv_id_from_url(url);
+        if doi.is_none() && arxiv_id.is_none() {
+            return None;
+        }
+        let mut reference = Self::new(ReferenceKind::Link);
+        reference.url = Some(url.to_string());
+        reference.doi = doi;
+        reference.arxiv_id = arxiv_id;
+        reference.text = text
+            .map(|text| text.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|text| !text.is_empty());
+        Some(reference)
+    }
+
+    /// Whether `other` cites the same work: both share a DOI, arXiv ID or URL
+    pub fn same_work(&self, other: &Reference) -> bool {
+        let shared = |a: &Option<String>, b: &Option<String>| a.is_some() && a == b;
+        shared(&self.doi, &other.doi)
+            || shared(&self.arxiv_id, &other.arxiv_id)
+            || shared(&self.url, &other.url)
+    }
+
+    /// Fill fields missing here from `other`, keeping values already set
+    pub fn merge(&mut self, other: Reference) {
+        self.label = self.label.take().or(other.label);
+        self.text = self.text.take().or(other.text);
+        self.url = self.url.take().or(other.url);
+        self.doi = self.doi.take().or(other.doi);
+        self.arxiv_id = self.arxiv_id.take().or(other.arxiv_id);
+        self.year = self.year.or(other.year);
+    }
+}
+
+/// Add `reference` to `references`, merging it into an earlier reference to
+/// the same work
+pub fn push_reference(references: &mut Vec<Reference>, reference: Reference) {
+    if let Some(existing) = references.iter_mut().find(|r| r.same_work(&reference)) {
+        existing.merge(reference);
+    } else if references.len() < MAX_REFERENCES {
+        references.push(reference);
+    }
+}
+
+/// First DOI ("10.<registrant>/<suffix>") in `text`
+pub fn find_doi(text: &str) -> Option<String> {
+    let mut from = 0;
+    while let Some(offset) = text[from..].find("10.") {
+        let start = from + offset;
+        from = start + 3;
+        let boundary = text[..start]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric() && c != '.');
+        if !boundary {
+            continue;
+        }
+
+        let rest = &text[start + 3..];
+        let registrant_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let registrant = &rest[..registrant_len];
+        if !(4..=20).contains(&registrant.len())
+            || !registrant.starts_with(|c: char| c.is_ascii_digit())
+        {
+            continue;
+        }
+        let Some(suffix) = rest[registrant_len..].strip_prefix('/') else {
+            continue;
+        };
+        let suffix_len = suffix
+            .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '<' | '>'))
+            .unwrap_or(suffix.len());
+        let suffix = trim_trailing_punctuation(&suffix[..suffix_len]);
+        if !suffix.is_empty() {
+            return Some(format!("10.{}/{}", registrant, suffix).to_lowercase());
+        }
+    }
+    None
+}
+
+/// First arXiv identifier in `text`, written as `arXiv:<id>` or as an
+/// arxiv.org abstract or PDF link
+pub fn find_arxiv_id(text: &str) -> Option<String> {
+    let lower = text.to_ascii_lowercase();
+    ["arxiv:", "arxiv.org/abs/", "arxiv.org/pdf/"]
+        .iter()
+        .flat_map(|marker| {
+            lower
+                .match_indices(marker)
+                .map(move |(start, _)| start + marker.len())
+        })
+        .filter_map(|start| parse_arxiv_id(text[start..].trim_start()))
+        .next()
+}
+
+/// DOI of a resolver link such as `https://doi.org/10.1000/xyz`
+pub fn doi_from_url(url: &str) -> Option<String> {
+    let lower = url.to_ascii_lowercase();
+    let path = &url[lower.find("doi.org/")? + "doi.org/".len()..];
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    find_doi(&percent_decode(path))
+}
+
+/// arXiv identifier of an `arxiv.org/abs/...` or `arxiv.org/pdf/...` link
+pub fn arxiv_id_from_url(url: &str) -> Option<String> {
+    let lower = url.to_ascii_lowercase();
+    if !lower.contains("arxiv.org/abs/") && !lower.contains("arxiv.org/pdf/") {
+        return None;
+    }
+    find_arxiv_id(url.split(['?', '#']).next().unwrap_or_default())
+}
+
+/// Parse the references section of plain document text, e.g. from a PDF
+///
+/// The section starts at the last line reading "References",
+/// "Bibliography" or a similar heading and ends at an appendix or the end
+/// of the text. Entries start at "[n]" or "n." markers numbered in
+/// sequence; without markers they are separated by blank lines, or else
+/// one per line.
+pub fn parse_reference_section(text: &str) -> Vec<Reference> {
+    let lines: Vec<&str> = text.lines().map(str::trim).collect();
+    let Some(heading) = lines.iter().rposition(|line| is_reference_heading(line)) else {
+        return Vec::new();
+    };
+    let section: Vec<&str> = lines[heading + 1..]
+        .iter()
+        .take_while(|line| !is_heading(line, SECTION_ENDS))
+        .copied()
+        .collect();
+
+    let marked = section
+        .iter()
+        .filter(|line| entry_marker(line).is_some())
+        .count();
+    let blank_separated = section.iter().any(|line| line.is_empty());
+
+    let mut entries: Vec<(Option<String>, String)> = Vec::new();
+    for line in section {
+        if marked >= 2 {
+            // A year opening a continuation line is not the next marker
+            let next = entries
+                .last()
+                .and_then(|(label, _)| label.as_deref()?.parse::<u32>().ok())
+                .map(|n| (n + 1).to_string());
+            if let Some((label, rest)) = entry_marker(line)
+                .filter(|(label, _)| next.is_none() || next.as_ref() == Some(label))
+            {
+                entries.push((Some(label), rest.to_string()));
+                continue;
+            }
+            if let Some((_, entry)) = entries.last_mut() {
+                join_line(entry, line);
+            }
+        } else if line.is_empty() {
+            if entries.last().is_some_and(|(_, entry)| !entry.is_empty()) {
+                entries.push((None, String::new()));
+            }
+        } else if blank_separated {
+            match entries.last_mut() {
+                Some((_, entry)) => join_line(entry, line),
+                None => entries.push((None, line.to_string())),
+            }
+        } else {
+            entries.push((None, line.to_string()));
+        }
+    }
+
+    let mut references = Vec::new();
+    for (label, entry) in entries {
+        if entry.trim().len() < 10 {
+            continue;
+        }
+        let mut reference = Reference::from_text(ReferenceKind::Bibliography, &entry);
+        reference.label = label;
+        push_reference(&mut references, reference);
+    }
+    references
+}
+
+/// Whether `heading` titles a reference list ("References", "7.
+/// Bibliography", "Works cited:")
+pub fn is_reference_heading(heading: &str) -> bool {
+    is_heading(heading, SECTION_HEADINGS)
+}
+
+/// Whether `line` is one of `headings`, allowing section numbers ("7." or
+/// "VII") and a trailing colon
+fn is_heading(line: &str, headings: &[&str]) -> bool {
+    let line = line.trim_end_matches(':').to_lowercase();
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let title = match words.first() {
+        Some(first)
+            if words.len() > 1
+                && first
+                    .trim_end_matches('.')
+                    .chars()
+                    .all(|c| c.is_ascii_digit() || matches!(c, 'i' | 'v' | 'x' | 'l' | '.')) =>
+        {
+            words[1..].join(" ")
+        }
+        _ => words.join(" "),
+    };
+    headings.iter().any(|heading| {
+        title == *heading || (*heading == "appendix" && title.starts_with("appendix"))
+    })
+}
+
+/// `(label, rest)` of a line starting with "[12]" or "12."
+fn entry_marker(line: &str) -> Option<(String, &str)> {
+    let (label, rest) = match line.strip_prefix('[') {
+        Some(rest) => {
+            let (label, rest) = rest.split_once(']')?;
+            (label, rest)
+        }
+        None => {
+            let (label, rest) = line.split_once(". ")?;
+            (label, rest)
+        }
+    };
+    let label = label.trim();
+    let valid = (1..=4).contains(&label.len()) && label.chars().all(|c| c.is_ascii_digit());
+    (valid && !rest.trim().is_empty()).then(|| (label.to_string(), rest.trim()))
+}
+
+/// Append a continuation line, rejoining words hyphenated across lines
+fn join_line(entry: &mut String, line: &str) {
+    if line.is_empty() {
+        return;
+    }
+    if entry.ends_with('-') && line.starts_with(|c: char| c.is_lowercase()) {
+        entry.pop();
+    } else if !entry.is_empty() {
+        entry.push(' ');
+    }
+    entry.push_str(line);
+}
+
+/// First standalone year between 1800 and 2099, preferring one in
+/// parentheses; identifiers and links are skipped so their digits are not
+/// mistaken for years
+fn find_year(text: &str) -> Option<u16> {
+    let year = |word: &str| -> Option<u16> {
+        let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+        // "2019a" disambiguates several works of one author and year
+        let word = word
+            .strip_suffix(|c: char| c.is_ascii_lowercase())
+            .unwrap_or(word);
+        let year: u16 = word.parse().ok().filter(|_| word.len() == 4)?;
+        (1800..=2099).contains(&year).then_some(year)
+    };
+    let words: Vec<&str> = text
+        .split_whitespace()
+        .filter(|word| !word.contains('/') && !word.to_ascii_lowercase().contains("arxiv"))
+        .collect();
+    words
+        .iter()
+        .filter(|word| word.starts_with('('))
+        .find_map(|word| year(word))
+        .or_else(|| words.iter().find_map(|word| year(word)))
+}
+
+/// arXiv identifier at the start of `text`: new-style "YYMM.NNNNN" or
+/// old-style "archive/YYMMNNN", version suffix dropped
+fn parse_arxiv_id(text: &str) -> Option<String> {
+    let end = text
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '/' | '-')))
+        .unwrap_or(text.len());
+    let candidate = text[..end].trim_end_matches('.');
+    let candidate = candidate.strip_suffix(".pdf").unwrap_or(candidate);
+
+    let id = match candidate.rfind('v') {
+        Some(i)
+            if i + 1 < candidate.len()
+                && candidate[i + 1..].chars().all(|c| c.is_ascii_digit()) =>
+        {
+            &candidate[..i]
+        }
+        _ => candidate,
+    };
+
+    let digits = |s: &str, range: std::ops::RangeInclusive<usize>| {
+        range.contains(&s.len()) && s.chars().all(|c| c.is_ascii_digit())
+    };
+    let valid = match id.split_once('/') {
+        Some((archive, number)) => {
+            !archive.is_empty()
+                && archive
+                    .chars()
+                    .all(|c| c.is_ascii_alphabetic() || matches!(c, '-' | '.'))
+                && digits(number, 7..=7)
+        }
+        None => match id.split_once('.') {
+            Some((month, number)) => digits(month, 4..=4) && digits(number, 4..=5),
+            None => false,
+        },
+    };
+    valid.then(|| id.to_string())
+}
+
+fn trim_trailing_punctuation(value: &str) -> &str {
+    let mut value = value.trim_end_matches(['.', ',', ';', ':']);
+    // Keep brackets that are part of the identifier, e.g. "10.1002/(SICI)..."
+    for (open, close) in [('(', ')'), ('[', ']')] {
+        while value.ends_with(close) && value.matches(close).count() > value.matches(open).count() {
+            value = value[..value.len() - 1].trim_end_matches(['.', ',', ';', ':']);
+        }
+    }
+    value
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            if let Some(byte) = value
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifiers_in_text_and_links() {
+        assert_eq!(
+            find_doi("LeCun et al. Nature 521, 436 (2015). doi:10.1038/NATURE14539."),
+            Some("10.1038/nature14539".to_string())
+        );
+        assert_eq!(
+            find_doi(
+                "see (https://doi.org/10.1002/(SICI)1097-4571(199806)49:8<693::AID>3.0.CO;2-0)"
+            ),
+            Some("10.1002/(sici)1097-4571(199806)49:8".to_string())
+        );
+        assert_eq!(find_doi("version 10.5 of the tool"), None);
+        assert_eq!(
+            find_arxiv_id("Vaswani et al. arXiv: 1706.03762v5 [cs.CL]"),
+            Some("1706.03762".to_string())
+        );
+        assert_eq!(
+            find_arxiv_id("https://arxiv.org/abs/hep-th/9901001v2"),
+            Some("hep-th/9901001".to_string())
+        );
+        assert_eq!(find_arxiv_id("1706.03762 without a marker"), None);
+        assert_eq!(
+            doi_from_url("https://doi.org/10.1145%2F3292500.3330701?via=x"),
+            Some("10.1145/3292500.3330701".to_string())
+        );
+        assert_eq!(
+            arxiv_id_from_url("https://arxiv.org/pdf/2101.00001v1.pdf"),
+            Some("2101.00001".to_string())
+        );
+        assert_eq!(
+            arxiv_id_from_url("https://example.com/abs/2101.00001"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_reference_from_text_and_merge() {
+        let reference = Reference::from_text(
+            ReferenceKind::Bibliography,
+            "Smith, J. (2019a). A study.\n  Journal 4, 1905.01234. https://example.org/paper.",
+        );
+        assert_eq!(reference.year, Some(2019));
+        assert_eq!(reference.url.as_deref(), Some("https://example.org/paper"));
+        assert_eq!(
+            reference.text.as_deref(),
+            Some("Smith, J. (2019a). A study. Journal 4, 1905.01234. https://example.org/paper.")
+        );
+
+        let mut entry = Reference::from_text(
+            ReferenceKind::Bibliography,
+            "Devlin et al. BERT. arXiv:1810.04805, 2018.",
+        );
+        let link =
+            Reference::from_link("https://arxiv.org/abs/1810.04805", Some(" BERT ")).unwrap();
+        assert!(entry.same_work(&link));
+        entry.merge(link);
+        assert_eq!(entry.kind, ReferenceKind::Bibliography);
+        assert_eq!(entry.year, Some(2018));
+        assert_eq!(
+            entry.url.as_deref(),
+            Some("https://arxiv.org/abs/1810.04805")
+        );
+
+        assert!(Reference::from_link("https://example.com/paper", None).is_none());
+    }
+
+    #[test]
+    fn test_parse_numbered_reference_section() {
+        let text = "1. Introduction\nWe build on [1] and [2].\n\n\
+            6. References\n\
+            [1] A. Author. Deep learn-\ning. Nature,\n\
+            2015. doi:10.1038/nature14539\n\
+            [2] B. Author. Attention. arXiv:1706.03762, 2017.\n\
+            [3] ok\n\
+            Appendix A\n\
+            [4] Not a reference at all, just appendix text 2020.";
+        let references = parse_reference_section(text);
+        assert_eq!(references.len(), 2);
+        assert_eq!(references[0].label.as_deref(), Some("1"));
+        assert_eq!(
+            references[0].text.as_deref(),
+            Some("A. Author. Deep learning. Nature, 2015. doi:10.1038/nature14539")
+        );
+        assert_eq!(references[0].doi.as_deref(), Some("10.1038/nature14539"));
+        assert_eq!(references[1].arxiv_id.as_deref(), Some("1706.03762"));
+        assert_eq!(references[1].year, Some(2017));
+    }
+
+    #[test]
+    fn test_parse_unnumbered_reference_section() {
+        let text = "Body text.\nBibliography:\n\
+            Doe, J. 2001. First work. Press.\n\n\
+            Roe, R. 2003. Second work\nspanning two lines. Press.";
+        let references = parse_reference_section(text);
+        assert_eq!(references.len(), 2);
+        assert_eq!(
+            references[1].text.as_deref(),
+            Some("Roe, R. 2003. Second work spanning two lines. Press.")
+        );
+        assert!(references[0].label.is_none());
+        assert!(parse_reference_section("No such section here.").is_empty());
+    }
+}
@@ -0,0 +1,188 @@
+//! Multi-region deployment awareness
+//!
+//! A riptide-api deployment may run in several regions. Each instance knows
+//! its own region label and, optionally, the peer regions it can forward
+//! work to. [`RegionRouter`] turns a target URL into a [`RoutingHint`] that
+//! tells callers which region is closest to the target host, so renders can
+//! be routed there and performance can be reported per region.
+//!
+//! Host placement comes from the server's GeoIP location: affinity rules map
+//! country codes (e.g. `DE` → `eu-central`) to regions. Callers locate the
+//! target host through the [`GeoIpLookup`](crate::ports::GeoIpLookup) port
+//! and pass the result to [`RegionRouter::route`].
+
+use crate::ports::GeoLocation;
+use serde::{Deserialize, Serialize};
+
+/// Region label used when no region has been configured
+pub const DEFAULT_REGION: &str = "default";
+
+/// A peer region reachable from this deployment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegionPeer {
+    /// Region label, e.g. `eu-central`
+    pub name: String,
+    /// Base URL of the riptide-api instance serving that region
+    pub endpoint: String,
+}
+
+/// Maps servers located in `country` to `region`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegionAffinity {
+    /// ISO 3166-1 alpha-2 country code, e.g. `DE`
+    pub country: String,
+    /// Region label closest to servers in that country
+    pub region: String,
+}
+
+/// Routing hint for a single target URL
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoutingHint {
+    /// Region serving this request
+    pub local_region: String,
+    /// Region closest to the target host
+    pub preferred_region: String,
+    /// Endpoint of the preferred region when it is a known peer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preferred_endpoint: Option<String>,
+}
+
+impl RoutingHint {
+    /// Whether the request is already being served by the preferred region
+    pub fn is_local(&self) -> bool {
+        self.local_region == self.preferred_region
+    }
+}
+
+/// Resolves routing hints from region affinity rules
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegionRouter {
+    /// Region label of this instance
+    pub local_region: Option<String>,
+    /// Peer regions this instance can forward work to
+    #[serde(default)]
+    pub peers: Vec<RegionPeer>,
+    /// Country affinity rules
+    #[serde(default)]
+    pub affinity: Vec<RegionAffinity>,
+}
+
+impl RegionRouter {
+    /// Create a router for the given local region
+    pub fn new(local_region: impl Into<String>) -> Self {
+        Self {
+            local_region: Some(local_region.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Add a peer region
+    pub fn with_peer(mut self, name: impl Into<String>, endpoint: impl Into<String>) -> Self {
+        self.peers.push(RegionPeer {
+            name: name.into(),
+            endpoint: endpoint.into(),
+        });
+        self
+    }
+
+    /// Add a country affinity rule
+    pub fn with_affinity(mut self, country: impl Into<String>, region: impl Into<String>) -> Self {
+        self.affinity.push(RegionAffinity {
+            country: country.into().to_ascii_uppercase(),
+            region: region.into(),
+        });
+        self
+    }
+
+    /// Region label of this instance, falling back to [`DEFAULT_REGION`]
+    pub fn local_region(&self) -> &str {
+        self.local_region.as_deref().unwrap_or(DEFAULT_REGION)
+    }
+
+    /// Region closest to servers in `country_code` according to the affinity rules
+    pub fn region_for_country(&self, country_code: &str) -> Option<&str> {
+        self.affinity
+            .iter()
+            .find(|rule| rule.country.eq_ignore_ascii_case(country_code))
+            .map(|rule| rule.region.as_str())
+    }
+
+    /// Build a routing hint for a target server at `location`
+    ///
+    /// Servers that could not be located, or whose country has no rule, are
+    /// routed locally.
+    pub fn route(&self, location: Option<&GeoLocation>) -> RoutingHint {
+        let local = self.local_region().to_string();
+        let preferred = location
+            .and_then(|location| location.country_code.as_deref())
+            .and_then(|country| self.region_for_country(country))
+            .map_or_else(|| local.clone(), str::to_string);
+
+        let preferred_endpoint = if preferred == local {
+            None
+        } else {
+            self.peers
+                .iter()
+                .find(|peer| peer.name == preferred)
+                .map(|peer| peer.endpoint.clone())
+        };
+
+        RoutingHint {
+            local_region: local,
+            preferred_region: preferred,
+            preferred_endpoint,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn router() -> RegionRouter {
+        RegionRouter::new("us-east")
+            .with_peer("eu-central", "https://eu.riptide.internal")
+            .with_affinity("de", "eu-central")
+            .with_affinity("GB", "eu-west")
+    }
+
+    fn located_in(country: &str) -> GeoLocation {
+        GeoLocation {
+            country_code: Some(country.to_string()),
+            ..GeoLocation::default()
+        }
+    }
+
+    #[test]
+    fn test_route_to_peer_region() {
+        let hint = router().route(Some(&located_in("DE")));
+        assert_eq!(hint.preferred_region, "eu-central");
+        assert_eq!(
+            hint.preferred_endpoint.as_deref(),
+            Some("https://eu.riptide.internal")
+        );
+        assert!(!hint.is_local());
+    }
+
+    #[test]
+    fn test_country_rules_ignore_case() {
+        let router = router();
+        assert_eq!(router.region_for_country("gb"), Some("eu-west"));
+        assert_eq!(router.region_for_country("DE"), Some("eu-central"));
+        assert_eq!(router.region_for_country("US"), None);
+    }
+
+    #[test]
+    fn test_unmatched_host_routes_locally() {
+        let hint = router().route(Some(&located_in("US")));
+        assert!(hint.is_local());
+        assert_eq!(hint.preferred_endpoint, None);
+
+        let hint = router().route(Some(&GeoLocation::default()));
+        assert!(hint.is_local());
+
+        let hint = RegionRouter::default().route(None);
+        assert_eq!(hint.local_region, DEFAULT_REGION);
+        assert!(hint.is_local());
+    }
+}
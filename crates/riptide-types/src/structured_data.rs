@@ -0,0 +1,199 @@
+//! Typed schema.org entities found in a page's structured data
+//!
+//! Pages describe themselves with JSON-LD scripts, Microdata attributes or
+//! RDFa attributes. Extractors normalize all three syntaxes into the types
+//! below, keeping only the schema.org types RipTide understands.
+
+use serde::{Deserialize, Serialize};
+
+/// Markup syntax a structured-data item was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StructuredDataSource {
+    /// `<script type="application/ld+json">`
+    JsonLd,
+    /// `itemscope` / `itemtype` / `itemprop` attributes
+    Microdata,
+    /// `vocab` / `typeof` / `property` attributes
+    Rdfa,
+}
+
+/// One schema.org entity found in a page
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredDataItem {
+    pub source: StructuredDataSource,
+    /// schema.org type as declared, e.g. "NewsArticle" for an [`SchemaEntity::Article`]
+    pub schema_type: String,
+    #[serde(flatten)]
+    pub entity: SchemaEntity,
+}
+
+/// schema.org entity, tagged by its normalized kind
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SchemaEntity {
+    Article(SchemaArticle),
+    Product(SchemaProduct),
+    Event(SchemaEvent),
+    Recipe(SchemaRecipe),
+    JobPosting(SchemaJobPosting),
+}
+
+impl SchemaEntity {
+    /// Normalized kind name, matching the serialized `kind` tag
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Article(_) => "article",
+            Self::Product(_) => "product",
+            Self::Event(_) => "event",
+            Self::Recipe(_) => "recipe",
+            Self::JobPosting(_) => "job_posting",
+        }
+    }
+}
+
+/// schema.org `Article` and its subtypes (`NewsArticle`, `BlogPosting`, ...)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaArticle {
+    pub headline: Option<String>,
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    pub publisher: Option<String>,
+    pub date_published: Option<String>,
+    pub date_modified: Option<String>,
+    pub section: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<String>,
+    pub url: Option<String>,
+}
+
+/// schema.org `Product`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaProduct {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub brand: Option<String>,
+    pub sku: Option<String>,
+    /// First of `gtin`, `gtin13`, `gtin12`, `gtin8`, `gtin14`
+    pub gtin: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub offers: Vec<SchemaOffer>,
+    pub rating: Option<SchemaRating>,
+    pub url: Option<String>,
+}
+
+/// schema.org `Event` and its subtypes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaEvent {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    /// Place name, falling back to its address
+    pub location: Option<String>,
+    pub organizer: Option<String>,
+    /// Status without the schema.org prefix, e.g. "EventScheduled"
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub offers: Vec<SchemaOffer>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<String>,
+    pub url: Option<String>,
+}
+
+/// schema.org `Recipe`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaRecipe {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    /// ISO 8601 durations, e.g. "PT15M"
+    pub prep_time: Option<String>,
+    pub cook_time: Option<String>,
+    pub total_time: Option<String>,
+    pub recipe_yield: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ingredients: Vec<String>,
+    /// Instruction steps in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub instructions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<String>,
+    pub rating: Option<SchemaRating>,
+}
+
+/// schema.org `JobPosting`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaJobPosting {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub hiring_organization: Option<String>,
+    pub date_posted: Option<String>,
+    pub valid_through: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub employment_types: Vec<String>,
+    /// Locality, region and country of the job location
+    pub location: Option<String>,
+    /// Whether `jobLocationType` is "TELECOMMUTE"
+    #[serde(default)]
+    pub remote: bool,
+    pub salary: Option<SchemaSalary>,
+}
+
+/// schema.org `Offer`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaOffer {
+    /// Price as written, since pages mix "19.99" and "19,99"
+    pub price: Option<String>,
+    pub currency: Option<String>,
+    /// Availability without the schema.org prefix, e.g. "InStock"
+    pub availability: Option<String>,
+    pub url: Option<String>,
+}
+
+/// schema.org `AggregateRating`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaRating {
+    pub value: Option<f64>,
+    pub best: Option<f64>,
+    pub count: Option<u64>,
+}
+
+/// schema.org `MonetaryAmount` of a job's base salary
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchemaSalary {
+    pub currency: Option<String>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Pay period, e.g. "YEAR" or "HOUR"
+    pub unit: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_serializes_flat_with_kind_tag() {
+        let item = StructuredDataItem {
+            source: StructuredDataSource::JsonLd,
+            schema_type: "NewsArticle".to_string(),
+            entity: SchemaEntity::Article(SchemaArticle {
+                headline: Some("Hello".to_string()),
+                ..Default::default()
+            }),
+        };
+
+        let json = serde_json::to_value(&item).unwrap();
+        assert_eq!(json["source"], "json_ld");
+        assert_eq!(json["kind"], "article");
+        assert_eq!(json["headline"], "Hello");
+
+        let back: StructuredDataItem = serde_json::from_value(json).unwrap();
+        assert_eq!(back, item);
+    }
+}
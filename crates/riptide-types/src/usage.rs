@@ -0,0 +1,104 @@
+//! Resource usage of a unit of work
+//!
+//! Work inside [`UsageScope::scope`] charges the bytes its fetches received
+//! on the wire and the LLM tokens its provider calls consumed to the scope,
+//! so a caller can meter one pipeline run without threading counters through
+//! every layer. Fetch adapters call [`UsageScope::record_bandwidth`] and LLM
+//! providers [`UsageScope::record_llm_tokens`]; outside a scope both are
+//! no-ops. The scope follows the task, so work spawned onto other tasks is
+//! not counted.
+//!
+//! # Example
+//!
+//! ```rust
+//! use riptide_types::usage::UsageScope;
+//! use std::sync::Arc;
+//!
+//! # async fn example() {
+//! let usage = Arc::new(UsageScope::default());
+//! usage.clone().scope(async {
+//!     UsageScope::record_bandwidth(1024);
+//! }).await;
+//! assert_eq!(usage.bandwidth_bytes(), 1024);
+//! # }
+//! ```
+
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+tokio::task_local! {
+    static CURRENT: Arc<UsageScope>;
+}
+
+/// Bandwidth and LLM tokens consumed by a unit of work
+#[derive(Debug, Default)]
+pub struct UsageScope {
+    bandwidth_bytes: AtomicU64,
+    llm_tokens: AtomicU64,
+}
+
+impl UsageScope {
+    /// Run `future` with the usage it records charged to this scope
+    pub async fn scope<F: Future>(self: Arc<Self>, future: F) -> F::Output {
+        CURRENT.scope(self, future).await
+    }
+
+    /// The current task's scope, if any
+    pub fn current() -> Option<Arc<UsageScope>> {
+        CURRENT.try_with(Arc::clone).ok()
+    }
+
+    /// Bytes received on the wire so far
+    pub fn bandwidth_bytes(&self) -> u64 {
+        self.bandwidth_bytes.load(Ordering::Relaxed)
+    }
+
+    /// LLM prompt and completion tokens so far
+    pub fn llm_tokens(&self) -> u64 {
+        self.llm_tokens.load(Ordering::Relaxed)
+    }
+
+    /// Charge `bytes` received on the wire to the current scope
+    pub fn record_bandwidth(bytes: u64) {
+        let _ = CURRENT.try_with(|usage| usage.bandwidth_bytes.fetch_add(bytes, Ordering::Relaxed));
+    }
+
+    /// Charge `tokens` an LLM call consumed to the current scope
+    pub fn record_llm_tokens(tokens: u64) {
+        let _ = CURRENT.try_with(|usage| usage.llm_tokens.fetch_add(tokens, Ordering::Relaxed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_usage_is_charged_to_the_enclosing_scope() {
+        let outer = Arc::new(UsageScope::default());
+        let inner = Arc::new(UsageScope::default());
+
+        outer
+            .clone()
+            .scope(async {
+                UsageScope::record_bandwidth(100);
+                inner
+                    .clone()
+                    .scope(async {
+                        UsageScope::record_bandwidth(10);
+                        UsageScope::record_llm_tokens(7);
+                    })
+                    .await;
+                UsageScope::record_llm_tokens(1);
+            })
+            .await;
+        UsageScope::record_bandwidth(1_000);
+
+        assert_eq!(outer.bandwidth_bytes(), 100);
+        assert_eq!(outer.llm_tokens(), 1);
+        assert_eq!(inner.bandwidth_bytes(), 10);
+        assert_eq!(inner.llm_tokens(), 7);
+        assert!(UsageScope::current().is_none());
+    }
+}
@@ -20,7 +20,7 @@
 //!
 //! ```rust,ignore
 //! use riptide_types::ports::CacheStorage;
-//! use riptide_types::tests::contracts::cache_storage_contract;
+//! use riptide_test_utils::contracts::cache_storage as cache_storage_contract;
 //!
 //! struct MyCache { /* ... */ }
 //!
@@ -46,7 +46,7 @@
 //!
 //! ```rust,ignore
 //! use riptide_types::ports::SessionStorage;
-//! use riptide_types::tests::contracts::session_storage_contract;
+//! use riptide_test_utils::contracts::session_storage as session_storage_contract;
 //!
 //! struct MySessionStorage { /* ... */ }
 //!
@@ -106,12 +106,12 @@
 //! # Adding New Contract Tests
 //!
 //! When adding a new port trait:
-//! 1. Create a new module in `crates/riptide-types/tests/contracts/`
+//! 1. Create a new module in `crates/riptide-test-utils/src/contracts/`
 //! 2. Define test functions covering all trait methods
 //! 3. Include edge cases, error handling, and concurrency tests
 //! 4. Add a `run_all_tests()` convenience function
 //! 5. Include self-tests with a simple in-memory implementation
-//! 6. Export the module from this `mod.rs`
+//! 6. Export the module from `riptide_test_utils::contracts`
 //! 7. Document usage patterns
 //!
 //! # Best Practices
@@ -150,9 +150,12 @@
 //! cargo test -p riptide-types -- --nocapture
 //! ```
 
-pub mod cache_storage_contract;
 pub mod coordination_contract;
-pub mod session_storage_contract;
+
+// The storage contracts live in riptide-test-utils so adapter crates can run them
+pub use riptide_test_utils::contracts::{
+    cache_storage as cache_storage_contract, session_storage as session_storage_contract,
+};
 
 // Re-export for convenience
 #[allow(unused_imports)]
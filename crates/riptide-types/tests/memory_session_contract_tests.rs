@@ -4,12 +4,9 @@
 //! against the InMemorySessionStorage implementation to ensure
 //! it adheres to all expected behaviors.
 
+use riptide_test_utils::contracts::session_storage as session_storage_contract;
 use riptide_types::ports::InMemorySessionStorage;
 
-// Include the contract test module
-#[path = "contracts/session_storage_contract.rs"]
-mod session_storage_contract;
-
 /// Create a fresh InMemorySessionStorage for each test
 fn create_storage() -> InMemorySessionStorage {
     // Use without_cleanup to avoid background task interference in tests
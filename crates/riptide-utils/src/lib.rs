@@ -3,7 +3,7 @@
 //! This crate provides common utilities used across the Riptide EventMesh platform:
 //!
 //! - **HTTP**: HTTP client factory with connection pooling
-//! - **Retry**: Retry policies with exponential backoff
+//! - **Retry**: Retry policies with exponential backoff and `Retry-After` support
 //! - **Time**: Time utilities and timestamp conversions
 //! - **Error**: Common error types and result aliases
 //!
@@ -34,7 +34,7 @@ pub use circuit_breaker::{
 pub use error::{Error, Result};
 pub use health_registry::{InMemoryHealthRegistry, SimpleHealthCheck};
 pub use http::{HttpClientFactory, HttpConfig};
-pub use retry::RetryPolicy;
+pub use retry::{server_retry_delay, RetryPolicy, Throttled};
 
 #[cfg(test)]
 mod tests {
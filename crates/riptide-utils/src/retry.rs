@@ -1,9 +1,91 @@
 //! Retry policy with exponential backoff
-
+//!
+//! Also understands server-dictated delays: [`server_retry_delay`] reads
+//! `Retry-After` and the `X-RateLimit-Reset` family from a throttled
+//! response, and [`RetryPolicy::execute_with_retry_after`] waits at least
+//! that long before retrying. Clients report a throttled response that
+//! exhausted its retries as a [`Throttled`] error so schedulers can hold the
+//! host for the delay.
+
+use reqwest::header::HeaderMap;
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{debug, warn};
 
+/// Reset headers read after `Retry-After`, in order
+const RATE_LIMIT_RESET_HEADERS: &[&str] =
+    &["ratelimit-reset", "x-ratelimit-reset", "x-rate-limit-reset"];
+
+/// Reset values above this are Unix timestamps rather than delta-seconds
+const EPOCH_THRESHOLD_SECS: u64 = 1_000_000_000;
+
+/// A throttled response (429 or 503) that was not retried successfully
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Throttled {
+    /// Response status code
+    pub status: u16,
+    /// Delay the server asked for, if it sent one
+    pub retry_after: Option<Duration>,
+}
+
+impl std::fmt::Display for Throttled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HTTP {} throttled", self.status)?;
+        if let Some(delay) = self.retry_after {
+            write!(f, ", server asked to retry after {:?}", delay)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Throttled {}
+
+/// Parse a `Retry-After` header value (delta-seconds or HTTP-date)
+///
+/// Dates in the past yield `None`.
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    (at.with_timezone(&chrono::Utc) - chrono::Utc::now())
+        .to_std()
+        .ok()
+}
+
+/// Parse a rate-limit reset value (delta-seconds or Unix timestamp)
+///
+/// Timestamps in the past yield a zero delay.
+pub fn parse_rate_limit_reset(value: &str) -> Option<Duration> {
+    let secs = value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|s| s.is_finite() && *s >= 0.0)?;
+    if secs as u64 > EPOCH_THRESHOLD_SECS {
+        let now = chrono::Utc::now().timestamp_millis() as f64 / 1000.0;
+        return Duration::try_from_secs_f64((secs - now).max(0.0)).ok();
+    }
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/// Delay a throttled response asks for
+///
+/// Reads `Retry-After` first, then `RateLimit-Reset`, `X-RateLimit-Reset`
+/// and `X-Rate-Limit-Reset`. Only meaningful on 429 and 503 responses: reset
+/// headers on successful responses describe the next window, not a wait.
+pub fn server_retry_delay(headers: &HeaderMap) -> Option<Duration> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+    header("retry-after")
+        .and_then(parse_retry_after)
+        .or_else(|| {
+            RATE_LIMIT_RESET_HEADERS
+                .iter()
+                .find_map(|name| header(name).and_then(parse_rate_limit_reset))
+        })
+}
+
 /// Retry policy with exponential backoff configuration
 #[derive(Debug, Clone)]
 pub struct RetryPolicy {
@@ -56,6 +138,21 @@ impl RetryPolicy {
         Duration::from_millis(backoff_ms)
     }
 
+    /// Delay before retrying after failed attempt `attempt` (0-based)
+    ///
+    /// A server-dictated delay wins over a shorter backoff. Returns `None`
+    /// when the server asks for longer than `max_backoff_ms`: an earlier
+    /// retry would only be throttled again, so the caller should give up and
+    /// pass the delay on.
+    pub fn retry_delay(&self, attempt: usize, server_delay: Option<Duration>) -> Option<Duration> {
+        let backoff = self.backoff_duration(attempt);
+        match server_delay {
+            Some(wait) if wait > Duration::from_millis(self.max_backoff_ms) => None,
+            Some(wait) => Some(wait.max(backoff)),
+            None => Some(backoff),
+        }
+    }
+
     /// Executes an async operation with retry logic
     ///
     /// # Arguments
@@ -65,11 +162,34 @@ impl RetryPolicy {
     /// # Errors
     ///
     /// Returns the last error if all retry attempts fail
-    pub async fn execute<F, Fut, T, E>(&self, mut operation: F) -> Result<T, E>
+    pub async fn execute<F, Fut, T, E>(&self, operation: F) -> Result<T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+    {
+        self.execute_with_retry_after(operation, |_| None).await
+    }
+
+    /// Executes an async operation with retry logic, honouring server delays
+    ///
+    /// `retry_after` extracts the delay a failed attempt's server asked for
+    /// (see [`retry_delay`](Self::retry_delay)); retries stop early when it
+    /// exceeds `max_backoff_ms`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last error if all retry attempts fail
+    pub async fn execute_with_retry_after<F, Fut, T, E, R>(
+        &self,
+        mut operation: F,
+        retry_after: R,
+    ) -> Result<T, E>
     where
         F: FnMut() -> Fut,
         Fut: std::future::Future<Output = Result<T, E>>,
         E: std::fmt::Display,
+        R: Fn(&E) -> Option<Duration>,
     {
         let mut attempt = 0;
 
@@ -92,7 +212,14 @@ impl RetryPolicy {
                         return Err(err);
                     }
 
-                    let backoff = self.backoff_duration(attempt - 1);
+                    let server_delay = retry_after(&err);
+                    let Some(backoff) = self.retry_delay(attempt - 1, server_delay) else {
+                        warn!(
+                            "Operation failed (attempt {}/{}): {}. Server asked to retry after {:?}, giving up",
+                            attempt, self.max_attempts, err, server_delay
+                        );
+                        return Err(err);
+                    };
                     warn!(
                         "Operation failed (attempt {}/{}): {}. Retrying in {:?}",
                         attempt, self.max_attempts, err, backoff
@@ -160,6 +287,88 @@ mod tests {
         assert_eq!(duration.as_millis(), 1000);
     }
 
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
+        assert_eq!(parse_retry_after("soon"), None);
+
+        let future = (chrono::Utc::now() + chrono::Duration::seconds(60)).to_rfc2822();
+        assert!(parse_retry_after(&future).unwrap() > Duration::from_secs(55));
+    }
+
+    #[test]
+    fn test_parse_rate_limit_reset() {
+        assert_eq!(parse_rate_limit_reset("30"), Some(Duration::from_secs(30)));
+        assert_eq!(
+            parse_rate_limit_reset("1.5"),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(parse_rate_limit_reset("1000000001"), Some(Duration::ZERO));
+        assert_eq!(parse_rate_limit_reset("-1"), None);
+        assert_eq!(parse_rate_limit_reset("1e300"), None);
+
+        let reset = (chrono::Utc::now().timestamp() + 60).to_string();
+        let delay = parse_rate_limit_reset(&reset).unwrap();
+        assert!(delay > Duration::from_secs(55) && delay <= Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_server_retry_delay_prefers_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(server_retry_delay(&headers), None);
+
+        headers.insert("x-ratelimit-reset", "20".parse().unwrap());
+        assert_eq!(server_retry_delay(&headers), Some(Duration::from_secs(20)));
+
+        headers.insert("retry-after", "5".parse().unwrap());
+        assert_eq!(server_retry_delay(&headers), Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_retry_delay_honours_server() {
+        let policy = RetryPolicy::new(3, 100, 1000, 2.0);
+
+        assert_eq!(
+            policy.retry_delay(0, None),
+            Some(Duration::from_millis(100))
+        );
+        // Server delay wins over a shorter backoff, backoff over a shorter delay
+        assert_eq!(
+            policy.retry_delay(0, Some(Duration::from_millis(500))),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            policy.retry_delay(2, Some(Duration::from_millis(10))),
+            Some(Duration::from_millis(400))
+        );
+        // Longer than the policy allows: give up
+        assert_eq!(policy.retry_delay(0, Some(Duration::from_secs(5))), None);
+    }
+
+    #[tokio::test]
+    async fn test_execute_gives_up_when_server_delay_too_long() {
+        let policy = RetryPolicy::new(5, 10, 100, 2.0);
+        let counter = Arc::new(AtomicUsize::new(0));
+        let counter_clone = counter.clone();
+
+        let result = policy
+            .execute_with_retry_after(
+                || async {
+                    counter_clone.fetch_add(1, Ordering::SeqCst);
+                    Err::<i32, _>(Throttled {
+                        status: 429,
+                        retry_after: Some(Duration::from_secs(60)),
+                    })
+                },
+                |err| err.retry_after,
+            )
+            .await;
+
+        assert_eq!(result.unwrap_err().status, 429);
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
     #[tokio::test]
     async fn test_execute_success_first_attempt() {
         let policy = RetryPolicy::default();
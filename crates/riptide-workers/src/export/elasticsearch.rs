@@ -0,0 +1,456 @@
+//! Elasticsearch / OpenSearch export sink
+//!
+//! Documents are written with the `_bulk` API. Each document's `_id` is the
+//! SHA-256 of its URL and is written with an `index` action, so exporting a
+//! page again replaces its previous version instead of adding a copy. The
+//! index is created with the configured mapping the first time the sink
+//! writes to it; an existing index is left untouched.
+
+use super::{ExportDocument, ExportSink, ExportStats};
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use riptide_types::ContentFingerprint;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// Credentials sent with every request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ElasticsearchAuth {
+    Basic {
+        username: String,
+        password: String,
+    },
+    /// Base64 `id:key` API key, sent as `Authorization: ApiKey ...`
+    ApiKey(String),
+}
+
+/// Elasticsearch sink configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ElasticsearchSinkConfig {
+    /// Cluster URL, e.g. `http://localhost:9200`
+    pub endpoint: String,
+    /// Target index
+    pub index: String,
+    /// Ingest pipeline applied to every document
+    pub pipeline: Option<String>,
+    /// Mappings used when the sink creates the index; `None` uses [`default_mapping`]
+    pub mapping: Option<Value>,
+    pub auth: Option<ElasticsearchAuth>,
+    /// Documents per `_bulk` request
+    pub batch_size: usize,
+    /// Timeout of a single request in seconds
+    pub timeout_secs: u64,
+}
+
+impl Default for ElasticsearchSinkConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: "http://localhost:9200".to_string(),
+            index: "riptide-documents".to_string(),
+            pipeline: None,
+            mapping: None,
+            auth: None,
+            batch_size: 500,
+            timeout_secs: 30,
+        }
+    }
+}
+
+impl ElasticsearchSinkConfig {
+    /// Sink configuration from `RIPTIDE_EXPORT_ES_*`, `None` when no URL is set
+    ///
+    /// Reads `RIPTIDE_EXPORT_ES_URL`, `_INDEX`, `_PIPELINE`, `_API_KEY` or
+    /// `_USERNAME`/`_PASSWORD`, `_BATCH_SIZE` and `_MAPPING_FILE`
+    /// (a JSON file with the index mappings).
+    pub fn from_env() -> Result<Option<Self>> {
+        let var = |name: &str| std::env::var(format!("RIPTIDE_EXPORT_ES_{}", name)).ok();
+        let Some(endpoint) = var("URL") else {
+            return Ok(None);
+        };
+        let defaults = Self::default();
+
+        let auth = match (var("API_KEY"), var("USERNAME")) {
+            (Some(key), _) => Some(ElasticsearchAuth::ApiKey(key)),
+            (None, Some(username)) => Some(ElasticsearchAuth::Basic {
+                username,
+                password: var("PASSWORD").unwrap_or_default(),
+            }),
+            (None, None) => None,
+        };
+        let mapping = var("MAPPING_FILE")
+            .map(|path| -> Result<Value> {
+                let raw = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read index mapping {}", path))?;
+                serde_json::from_str(&raw)
+                    .with_context(|| format!("Invalid index mapping {}", path))
+            })
+            .transpose()?;
+
+        Ok(Some(Self {
+            endpoint,
+            index: var("INDEX").unwrap_or(defaults.index),
+            pipeline: var("PIPELINE"),
+            mapping,
+            auth,
+            batch_size: var("BATCH_SIZE")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.batch_size),
+            timeout_secs: defaults.timeout_secs,
+        }))
+    }
+}
+
+/// Mappings for exported documents
+///
+/// Text fields are analysed for search, identifiers and tags are keywords.
+pub fn default_mapping() -> Value {
+    json!({
+        "properties": {
+            "url": { "type": "keyword" },
+            "content_hash": { "type": "keyword" },
+            "exported_at": { "type": "date" },
+            "title": { "type": "text", "fields": { "raw": { "type": "keyword", "ignore_above": 512 } } },
+            "text": { "type": "text" },
+            "markdown": { "type": "text", "index": false },
+            "description": { "type": "text" },
+            "byline": { "type": "text" },
+            "site_name": { "type": "keyword" },
+            "language": { "type": "keyword" },
+            "categories": { "type": "keyword" },
+            "links": { "type": "keyword", "index": false },
+            "media": { "type": "keyword", "index": false },
+            "published_iso": { "type": "date", "ignore_malformed": true },
+            "quality_score": { "type": "byte" },
+            "word_count": { "type": "integer" },
+            "reading_time": { "type": "integer" },
+            "html": { "type": "text", "index": false },
+            "tables": { "type": "object", "enabled": false },
+            "parser_metadata": { "type": "object", "enabled": false },
+            "provenance": { "type": "object", "enabled": false },
+            "feed": { "type": "object", "enabled": false },
+            "feed_links": { "type": "object", "enabled": false },
+            "annotations": {
+                "properties": {
+                    // Nested so facets can filter on an entity's kind and name together
+                    "entities": {
+                        "type": "nested",
+                        "properties": {
+                            "text": { "type": "keyword", "ignore_above": 256 },
+                            "kind": { "type": "keyword" },
+                            "mentions": { "type": "integer" }
+                        }
+                    },
+                    "keywords": {
+                        "properties": {
+                            "term": { "type": "keyword", "ignore_above": 256 },
+                            "score": { "type": "float" }
+                        }
+                    },
+                    "topics": { "type": "keyword" },
+                    "annotator": { "type": "keyword" }
+                }
+            },
+            "classification": {
+                "properties": {
+                    "categories": {
+                        "properties": {
+                            "label": { "type": "keyword" },
+                            "confidence": { "type": "float" }
+                        }
+                    },
+                    "sentiment": {
+                        "properties": {
+                            "label": { "type": "keyword" },
+                            "confidence": { "type": "float" }
+                        }
+                    },
+                    "classifier": { "type": "keyword" }
+                }
+            }
+        }
+    })
+}
+
+/// Bulk-indexes documents into Elasticsearch or OpenSearch
+pub struct ElasticsearchSink {
+    config: ElasticsearchSinkConfig,
+    client: reqwest::Client,
+    /// Set once the index is known to exist
+    index_ready: AtomicBool,
+}
+
+impl ElasticsearchSink {
+    pub fn new(config: ElasticsearchSinkConfig) -> Result<Self> {
+        if config.batch_size == 0 {
+            return Err(anyhow!("Elasticsearch batch_size must be at least 1"));
+        }
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .context("Failed to build Elasticsearch HTTP client")?;
+        Ok(Self {
+            config,
+            client,
+            index_ready: AtomicBool::new(false),
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.config.endpoint.trim_end_matches('/'), path)
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let request = self.client.request(method, self.url(path));
+        match &self.config.auth {
+            Some(ElasticsearchAuth::Basic { username, password }) => {
+                request.basic_auth(username, Some(password))
+            }
+            Some(ElasticsearchAuth::ApiKey(key)) => {
+                request.header("Authorization", format!("ApiKey {}", key))
+            }
+            None => request,
+        }
+    }
+
+    /// Create the index with the configured mapping unless it exists
+    async fn ensure_index(&self) -> Result<()> {
+        if self.index_ready.load(Ordering::Acquire) {
+            return Ok(());
+        }
+        let index = &self.config.index;
+        let exists = self
+            .request(reqwest::Method::HEAD, index)
+            .send()
+            .await
+            .context("Failed to reach Elasticsearch")?;
+        if exists.status() == reqwest::StatusCode::NOT_FOUND {
+            let mappings = self.config.mapping.clone().unwrap_or_else(default_mapping);
+            let response = self
+                .request(reqwest::Method::PUT, index)
+                .json(&json!({ "mappings": mappings }))
+                .send()
+                .await
+                .context("Failed to create Elasticsearch index")?;
+            let status = response.status();
+            let body: Value = response.json().await.unwrap_or(Value::Null);
+            // Another worker may have created it in the meantime
+            let already_exists =
+                body["error"]["type"].as_str() == Some("resource_already_exists_exception");
+            if !status.is_success() && !already_exists {
+                return Err(anyhow!(
+                    "Failed to create index {}: HTTP {} {}",
+                    index,
+                    status,
+                    body["error"]["reason"].as_str().unwrap_or_default()
+                ));
+            }
+            info!(index = %index, "Created Elasticsearch export index");
+        } else if !exists.status().is_success() {
+            return Err(anyhow!(
+                "Failed to check index {}: HTTP {}",
+                index,
+                exists.status()
+            ));
+        }
+        self.index_ready.store(true, Ordering::Release);
+        Ok(())
+    }
+
+    /// NDJSON body of a `_bulk` request
+    pub fn bulk_body(&self, documents: &[ExportDocument]) -> Result<String> {
+        let mut body = String::new();
+        for doc in documents {
+            let action =
+                json!({ "index": { "_index": self.config.index, "_id": document_id(doc) } });
+            body.push_str(&action.to_string());
+            body.push('\n');
+            body.push_str(&serde_json::to_string(doc)?);
+            body.push('\n');
+        }
+        Ok(body)
+    }
+
+    async fn bulk(&self, documents: &[ExportDocument]) -> Result<ExportStats> {
+        let mut request = self
+            .request(reqwest::Method::POST, "_bulk")
+            .header("Content-Type", "application/x-ndjson")
+            .body(self.bulk_body(documents)?);
+        if let Some(pipeline) = &self.config.pipeline {
+            request = request.query(&[("pipeline", pipeline)]);
+        }
+        let response = request
+            .send()
+            .await
+            .context("Failed to send bulk request")?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Bulk request failed: HTTP {} {}", status, body));
+        }
+        let body: Value = response
+            .json()
+            .await
+            .context("Invalid bulk response body")?;
+        Ok(parse_bulk_response(&body))
+    }
+}
+
+/// Index id of a document, stable across exports of its URL
+///
+/// URLs can exceed the 512-byte id limit, so their SHA-256 is used.
+pub fn document_id(doc: &ExportDocument) -> String {
+    ContentFingerprint::hash_content(doc.document.url.as_bytes())
+}
+
+/// Per-item outcomes of a `_bulk` response
+pub fn parse_bulk_response(body: &Value) -> ExportStats {
+    let mut stats = ExportStats::default();
+    let items = body["items"].as_array().map(Vec::as_slice).unwrap_or(&[]);
+    for item in items {
+        let Some(result) = item.as_object().and_then(|ops| ops.values().next()) else {
+            continue;
+        };
+        match result["status"].as_u64() {
+            Some(200..=299) => stats.exported += 1,
+            Some(409) => stats.duplicates += 1,
+            status => {
+                let error = &result["error"];
+                stats.record_failure(format!(
+                    "{} {}: {}",
+                    status.unwrap_or_default(),
+                    error["type"].as_str().unwrap_or("error"),
+                    error["reason"].as_str().unwrap_or("no reason given")
+                ));
+            }
+        }
+    }
+    stats
+}
+
+#[async_trait]
+impl ExportSink for ElasticsearchSink {
+    fn name(&self) -> &str {
+        "elasticsearch"
+    }
+
+    async fn export(&self, documents: &[ExportDocument]) -> Result<ExportStats> {
+        self.ensure_index().await?;
+        let mut stats = ExportStats::default();
+        for batch in documents.chunks(self.config.batch_size) {
+            let batch_stats = self.bulk(batch).await?;
+            debug!(
+                index = %self.config.index,
+                exported = batch_stats.exported,
+                duplicates = batch_stats.duplicates,
+                failed = batch_stats.failed,
+                "Bulk request completed"
+            );
+            stats.merge(batch_stats);
+        }
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use riptide_types::ExtractedDoc;
+
+    fn export_doc(url: &str, text: &str) -> ExportDocument {
+        ExportDocument::new(ExtractedDoc {
+            url: url.to_string(),
+            text: text.to_string(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_bulk_body_uses_url_ids() {
+        let sink = ElasticsearchSink::new(ElasticsearchSinkConfig::default()).unwrap();
+        let doc = export_doc("https://a.example/", "hello");
+        let body = sink.bulk_body(std::slice::from_ref(&doc)).unwrap();
+
+        let lines: Vec<Value> = body
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["index"]["_id"], document_id(&doc).as_str());
+        assert_eq!(lines[0]["index"]["_index"], "riptide-documents");
+        assert_eq!(lines[1]["url"], "https://a.example/");
+        assert!(body.ends_with('\n'));
+
+        // A changed page keeps its id, so it replaces the indexed version
+        let changed = export_doc("https://a.example/", "hello again");
+        assert_ne!(changed.content_hash, doc.content_hash);
+        assert_eq!(document_id(&changed), document_id(&doc));
+        assert_ne!(
+            document_id(&export_doc("https://a.example/other", "hello")),
+            document_id(&doc)
+        );
+    }
+
+    #[test]
+    fn test_annotations_match_facet_mapping() {
+        use riptide_types::{EntityKind, NamedEntity, TextAnnotations};
+
+        let mut doc = export_doc("https://a.example/", "Anna Weber in Berlin");
+        doc.document.annotations = Some(TextAnnotations {
+            entities: vec![NamedEntity {
+                text: "Berlin".to_string(),
+                kind: EntityKind::Location,
+                mentions: 1,
+            }],
+            topics: vec!["travel".to_string()],
+            annotator: "rules".to_string(),
+            ..Default::default()
+        });
+        let json = serde_json::to_value(&doc).unwrap();
+        assert_eq!(json["annotations"]["entities"][0]["kind"], "location");
+        assert_eq!(json["annotations"]["topics"][0], "travel");
+
+        let mapping = default_mapping();
+        let annotations = &mapping["properties"]["annotations"]["properties"];
+        assert_eq!(annotations["entities"]["type"], "nested");
+        assert_eq!(
+            annotations["entities"]["properties"]["kind"]["type"],
+            "keyword"
+        );
+        assert_eq!(annotations["topics"]["type"], "keyword");
+    }
+
+    #[test]
+    fn test_parse_bulk_response() {
+        let stats = parse_bulk_response(&json!({
+            "errors": true,
+            "items": [
+                { "create": { "_id": "a", "status": 201 } },
+                { "create": { "_id": "b", "status": 409, "error": { "type": "version_conflict_engine_exception" } } },
+                { "create": { "_id": "c", "status": 400, "error": { "type": "mapper_parsing_exception", "reason": "failed to parse field [published_iso]" } } },
+                { "index": { "_id": "d", "status": 200 } }
+            ]
+        }));
+
+        assert_eq!(stats.exported, 2);
+        assert_eq!(stats.duplicates, 1);
+        assert_eq!(stats.failed, 1);
+        assert_eq!(
+            stats.errors,
+            ["400 mapper_parsing_exception: failed to parse field [published_iso]"]
+        );
+    }
+
+    #[test]
+    fn test_rejects_empty_batches() {
+        assert!(ElasticsearchSink::new(ElasticsearchSinkConfig {
+            batch_size: 0,
+            ..Default::default()
+        })
+        .is_err());
+    }
+}
@@ -0,0 +1,235 @@
+//! Export of extracted documents to external stores
+//!
+//! Crawl jobs leave their results in the cache; an export job hands them to
+//! one or more [`ExportSink`]s so search and analytics systems can consume
+//! them. Enqueue a `Custom` job named [`EXPORT_JOB`] whose payload is an
+//! [`ExportJob`] once the crawls it covers have completed.
+//!
+//! Documents carry the SHA-256 of their text: the same content reached
+//! through several URLs of one job is exported once, and sinks keyed by URL
+//! use it to tell changed pages from unchanged ones.
+//!
+//! Available sinks:
+//! - [`ElasticsearchSink`]: bulk indexing into Elasticsearch or OpenSearch
+//! - `PostgresSink`: upserts into SQL tables (behind the `postgres-export` feature)
+
+pub mod elasticsearch;
+pub mod postgres;
+
+pub use elasticsearch::{ElasticsearchAuth, ElasticsearchSink, ElasticsearchSinkConfig};
+#[cfg(feature = "postgres-export")]
+pub use postgres::PostgresSink;
+pub use postgres::{PostgresSinkConfig, PostgresStatements};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use riptide_types::{ContentFingerprint, ExtractedDoc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Custom job name handled by `ExportProcessor`
+pub const EXPORT_JOB: &str = "export";
+
+/// Payload of an export job
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportJob {
+    /// URLs whose cached crawl results are exported
+    pub urls: Vec<String>,
+    /// Documents exported as given, in addition to `urls`
+    pub documents: Vec<ExtractedDoc>,
+    /// Names of the sinks to write to; empty writes to every configured sink
+    pub sinks: Vec<String>,
+}
+
+/// An extracted document ready to be written to a sink
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportDocument {
+    /// SHA-256 of the document text, the deduplication key
+    pub content_hash: String,
+    pub exported_at: DateTime<Utc>,
+    /// Tables found in the page HTML, filled only for sinks that store them
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tables: Vec<ExportTable>,
+    #[serde(flatten)]
+    pub document: ExtractedDoc,
+}
+
+impl ExportDocument {
+    pub fn new(document: ExtractedDoc) -> Self {
+        Self {
+            content_hash: ContentFingerprint::hash_content(document.text.as_bytes()),
+            exported_at: Utc::now(),
+            tables: Vec::new(),
+            document,
+        }
+    }
+}
+
+/// A table extracted from a document's page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportTable {
+    pub caption: Option<String>,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl From<riptide_extraction::processor::TableData> for ExportTable {
+    fn from(table: riptide_extraction::processor::TableData) -> Self {
+        Self {
+            caption: table.caption,
+            headers: table.headers,
+            rows: table.rows,
+        }
+    }
+}
+
+/// Wrap documents for export, dropping repeated content
+///
+/// Returns the unique documents in input order and how many were dropped.
+pub fn dedup_documents(documents: Vec<ExtractedDoc>) -> (Vec<ExportDocument>, usize) {
+    let mut seen = HashSet::new();
+    let mut duplicates = 0;
+    let unique = documents
+        .into_iter()
+        .map(ExportDocument::new)
+        .filter(|doc| {
+            let first = seen.insert(doc.content_hash.clone());
+            if !first {
+                duplicates += 1;
+            }
+            first
+        })
+        .collect();
+    (unique, duplicates)
+}
+
+/// What a sink did with a batch of documents
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportStats {
+    /// Documents written
+    pub exported: usize,
+    /// Documents skipped because the sink already held their content
+    pub duplicates: usize,
+    /// Documents the sink rejected
+    pub failed: usize,
+    /// First rejection reasons, for diagnosis
+    pub errors: Vec<String>,
+}
+
+/// Rejection reasons kept per export
+const MAX_EXPORT_ERRORS: usize = 10;
+
+impl ExportStats {
+    /// Record a rejected document
+    pub fn record_failure(&mut self, reason: impl Into<String>) {
+        self.failed += 1;
+        if self.errors.len() < MAX_EXPORT_ERRORS {
+            self.errors.push(reason.into());
+        }
+    }
+
+    /// Add the counts of another batch
+    pub fn merge(&mut self, other: ExportStats) {
+        self.exported += other.exported;
+        self.duplicates += other.duplicates;
+        self.failed += other.failed;
+        for error in other.errors {
+            if self.errors.len() >= MAX_EXPORT_ERRORS {
+                break;
+            }
+            self.errors.push(error);
+        }
+    }
+}
+
+/// Destination for exported documents
+#[async_trait]
+pub trait ExportSink: Send + Sync {
+    /// Name used to select the sink in [`ExportJob::sinks`]
+    fn name(&self) -> &str;
+
+    /// Whether documents should carry their page tables
+    ///
+    /// Tables are extracted from the cached raw HTML, which costs a parse
+    /// per page, so only sinks that store them ask for it.
+    fn wants_tables(&self) -> bool {
+        false
+    }
+
+    /// Write documents, reporting per-document outcomes
+    ///
+    /// An `Err` means the sink could not be reached at all; rejected
+    /// documents are counted in [`ExportStats::failed`] instead.
+    async fn export(&self, documents: &[ExportDocument]) -> Result<ExportStats>;
+}
+
+/// Outcome of an export job
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportResult {
+    /// Documents handed to the sinks after deduplication
+    pub documents: usize,
+    /// Documents dropped because another document in the job had the same content
+    pub duplicates_in_job: usize,
+    /// URLs with no successful cached crawl result
+    pub missing_urls: Vec<String>,
+    /// Per-sink outcome, keyed by sink name
+    pub sinks: std::collections::BTreeMap<String, ExportStats>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(url: &str, text: &str) -> ExtractedDoc {
+        ExtractedDoc {
+            url: url.to_string(),
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_dedup_documents_on_content_hash() {
+        let (unique, duplicates) = dedup_documents(vec![
+            doc("https://a.example/1", "same body"),
+            doc("https://a.example/2", "other body"),
+            doc("https://a.example/1?ref=x", "same body"),
+        ]);
+
+        assert_eq!(duplicates, 1);
+        let urls: Vec<_> = unique.iter().map(|d| d.document.url.as_str()).collect();
+        assert_eq!(urls, ["https://a.example/1", "https://a.example/2"]);
+        assert_eq!(
+            unique[0].content_hash,
+            ContentFingerprint::hash_content(b"same body")
+        );
+    }
+
+    #[test]
+    fn test_export_document_flattens_fields() {
+        let value =
+            serde_json::to_value(ExportDocument::new(doc("https://a.example/", "hi"))).unwrap();
+        assert_eq!(value["url"], "https://a.example/");
+        assert_eq!(value["text"], "hi");
+        assert!(value["content_hash"].is_string());
+        assert!(value["exported_at"].is_string());
+    }
+
+    #[test]
+    fn test_stats_merge_caps_errors() {
+        let mut total = ExportStats::default();
+        for i in 0..15 {
+            let mut batch = ExportStats {
+                exported: 1,
+                ..Default::default()
+            };
+            batch.record_failure(format!("error {}", i));
+            total.merge(batch);
+        }
+        assert_eq!(total.exported, 15);
+        assert_eq!(total.failed, 15);
+        assert_eq!(total.errors.len(), MAX_EXPORT_ERRORS);
+    }
+}
@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use riptide_types::ErrorCode;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -73,6 +74,12 @@ pub enum JobType {
         url: Option<String>,
         options: Option<PdfExtractionOptions>,
     },
+    /// Re-run extraction over cached raw HTML without refetching
+    Reprocess {
+        urls: Vec<String>,
+        /// Extraction mode passed to the extractor (defaults to "default")
+        mode: Option<String>,
+    },
     /// Scheduled maintenance task
     Maintenance {
         task_type: String,
@@ -307,6 +314,9 @@ pub struct JobResult {
     pub data: Option<serde_json::Value>,
     /// Error message if failed
     pub error: Option<String>,
+    /// Machine-readable error code if failed
+    #[serde(default)]
+    pub error_code: Option<ErrorCode>,
     /// Processing time in milliseconds
     pub processing_time_ms: u64,
     /// Worker that processed the job
@@ -328,6 +338,7 @@ impl JobResult {
             success: true,
             data,
             error: None,
+            error_code: None,
             processing_time_ms,
             worker_id,
             completed_at: Utc::now(),
@@ -346,11 +357,18 @@ impl JobResult {
             success: false,
             data: None,
             error: Some(error),
+            error_code: Some(ErrorCode::Internal),
             processing_time_ms,
             worker_id,
             completed_at: Utc::now(),
         }
     }
+
+    /// Set the error code of a failed result
+    pub fn with_error_code(mut self, code: ErrorCode) -> Self {
+        self.error_code = Some(code);
+        self
+    }
 }
 
 #[cfg(test)]
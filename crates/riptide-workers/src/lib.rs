@@ -1,22 +1,44 @@
+pub mod export;
 pub mod job;
 pub mod metrics;
 pub mod processors;
 pub mod queue;
+pub mod reports;
+pub mod retention;
 pub mod scheduler;
 pub mod service;
 pub mod state;
 pub mod worker;
 
+#[cfg(feature = "postgres-export")]
+pub use export::PostgresSink;
+pub use export::{
+    ElasticsearchAuth, ElasticsearchSink, ElasticsearchSinkConfig, ExportDocument, ExportJob,
+    ExportResult, ExportSink, ExportStats, ExportTable, PostgresSinkConfig, EXPORT_JOB,
+};
 pub use job::{Job, JobPriority, JobResult, JobStatus, JobType, PdfExtractionOptions, RetryConfig};
 pub use metrics::{WorkerMetrics, WorkerMetricsSnapshot};
+#[cfg(feature = "email-reports")]
+pub use processors::ReportProcessor;
 pub use processors::{
-    BatchCrawlProcessor, BatchCrawlResponse, CrawlResult, CustomJobProcessor, CustomJobResult,
-    MaintenanceProcessor, MaintenanceResult, PdfExtractionResult, PdfExtractionStats, PdfProcessor,
-    SingleCrawlProcessor,
+    store_raw_html_snapshot, BatchCrawlProcessor, BatchCrawlResponse, CrawlResult,
+    CustomJobProcessor, CustomJobResult, ExportProcessor, MaintenanceProcessor, MaintenanceResult,
+    PdfExtractionResult, PdfExtractionStats, PdfProcessor, RawHtmlRetentionProcessor,
+    RawHtmlRetentionResult, RawHtmlSnapshot, ReprocessProcessor, SingleCrawlProcessor,
+    SnapshotResult, DEFAULT_RESULT_TTL_SECS,
 };
 pub use queue::{JobQueue, QueueConfig, QueueStats};
+#[cfg(feature = "email-reports")]
+pub use reports::SmtpEmailSender;
+pub use reports::{
+    ReportAttachmentFormat, ReportJob, ReportResult, ReportSummary, ReportsConfig, SmtpConfig,
+    SmtpTls, TenantReportConfig, REPORT_JOB,
+};
+pub use retention::{RawHtmlRetentionPolicy, RetentionDecision, RAW_HTML_RETENTION_JOB};
 pub use scheduler::{JobScheduler, ScheduledJob, SchedulerConfig, SchedulerStats};
-pub use service::{WorkerService, WorkerServiceConfig, WorkerServiceHealth};
+pub use service::{
+    result_ttl_from_env, WorkerDrainReport, WorkerService, WorkerServiceConfig, WorkerServiceHealth,
+};
 pub use state::{
     JobState, StateTransitionError, StateTransitionGuard, TransitionMetrics, WorkerState,
 };
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use clap::Parser;
 use riptide_workers::{WorkerService, WorkerServiceConfig};
+use std::time::Duration;
 use tracing_subscriber::EnvFilter;
 
 #[derive(Parser)]
@@ -27,6 +28,10 @@ struct Args {
 
     #[arg(long, default_value = "true")]
     enable_scheduler: bool,
+
+    /// Seconds in-flight jobs get to finish on shutdown
+    #[arg(long, default_value = "30")]
+    drain_timeout_secs: u64,
 }
 
 #[tokio::main]
@@ -47,6 +52,7 @@ async fn main() -> Result<()> {
         max_concurrency = args.max_concurrency,
         wasm_path = %args.wasm_path,
         enable_scheduler = args.enable_scheduler,
+        drain_timeout_secs = args.drain_timeout_secs,
         "Starting RipTide Worker Service"
     );
 
@@ -67,13 +73,7 @@ async fn main() -> Result<()> {
     tracing::info!("Worker service initialized successfully");
 
     // Set up graceful shutdown
-    let shutdown_signal = async {
-        if let Err(e) = tokio::signal::ctrl_c().await {
-            tracing::error!("Failed to listen for Ctrl+C: {}", e);
-            return;
-        }
-        tracing::info!("Received shutdown signal, initiating graceful shutdown");
-    };
+    let drain_budget = Duration::from_secs(args.drain_timeout_secs);
 
     // Start worker service
     tokio::select! {
@@ -83,11 +83,20 @@ async fn main() -> Result<()> {
                 return Err(e);
             }
         }
-        _ = shutdown_signal => {
+        _ = shutdown_signal() => {
             tracing::info!("Shutdown signal received");
         }
     }
 
+    // Let in-flight jobs finish before stopping
+    let report = worker_service.drain(drain_budget).await;
+    tracing::info!(
+        jobs_in_flight_at_start = report.jobs_in_flight_at_start,
+        jobs_unfinished = report.jobs_unfinished,
+        elapsed_ms = report.elapsed_ms,
+        "Worker drain finished"
+    );
+
     // Stop worker service
     tracing::info!("Stopping worker service");
     worker_service.stop().await?;
@@ -95,3 +104,34 @@ async fn main() -> Result<()> {
     tracing::info!("RipTide Worker Service shutdown complete");
     Ok(())
 }
+
+/// Resolves on SIGTERM or Ctrl+C
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal_handler) => {
+                signal_handler.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to install SIGTERM handler: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("Received Ctrl+C, initiating graceful shutdown"),
+        _ = terminate => tracing::info!("Received SIGTERM, initiating graceful shutdown"),
+    }
+}
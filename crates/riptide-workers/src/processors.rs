@@ -1,11 +1,143 @@
+use crate::export::{
+    dedup_documents, ExportDocument, ExportJob, ExportResult, ExportSink, EXPORT_JOB,
+};
 use crate::job::{Job, JobType, PdfExtractionOptions};
+use crate::retention::{
+    RawHtmlRetentionPolicy, RetentionDecision, MAX_RAW_HTML_RETENTION_DAYS, RAW_HTML_RETENTION_JOB,
+};
 use crate::worker::JobProcessor;
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use riptide_types::{config::CrawlOptions, ExtractedDoc};
+use riptide_cache::redis::{CacheEntry, CacheMetadata};
+use riptide_types::ports::CacheStorage;
+use riptide_types::{config::CrawlOptions, ContentFingerprint, ExtractedDoc, ExtractionProvenance};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tracing::{debug, error, info};
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+/// Upper bound on raw HTML snapshot lifetime; the retention job prunes earlier
+const RAW_HTML_TTL_SECS: u64 = MAX_RAW_HTML_RETENTION_DAYS as u64 * 24 * 3600;
+
+/// Default lifetime of cached crawl results
+pub const DEFAULT_RESULT_TTL_SECS: u64 = 3600;
+
+/// Cache key for the raw HTML snapshot of a URL
+///
+/// SHA-256 of the URL, so keys stay valid across builds.
+pub fn raw_html_cache_key(url: &str) -> String {
+    format!(
+        "raw_html_{}",
+        ContentFingerprint::hash_content(url.as_bytes())
+    )
+}
+
+/// Store `snapshot` in `cache`, where the reprocess and raw HTML retention
+/// jobs find it
+///
+/// For crawls outside the workers, such as the API pipeline, sharing the
+/// workers' Redis.
+pub async fn store_raw_html_snapshot(
+    cache: &dyn CacheStorage,
+    snapshot: &RawHtmlSnapshot,
+) -> Result<()> {
+    set_entry(
+        cache,
+        &raw_html_cache_key(&snapshot.url),
+        snapshot,
+        RAW_HTML_TTL_SECS,
+    )
+    .await
+}
+
+/// Read a value `CacheManager` stored under `key`
+async fn get_entry<T: serde::de::DeserializeOwned>(
+    cache: &dyn CacheStorage,
+    key: &str,
+) -> Result<Option<T>> {
+    let Some(bytes) = cache.get(key).await? else {
+        return Ok(None);
+    };
+    let entry: CacheEntry<T> = serde_json::from_slice(&bytes)?;
+    let age = chrono::Utc::now().signed_duration_since(entry.created_at);
+    if age.num_seconds() > entry.ttl as i64 {
+        return Ok(None);
+    }
+    Ok(Some(entry.data))
+}
+
+/// Latest cached crawl result of `url`
+///
+/// Follows the URL's raw HTML snapshot to the key its result was cached
+/// under, by a crawl job or the API pipeline, whatever the options of that
+/// crawl. Results without a snapshot are looked up under the key of a crawl
+/// job without options.
+pub(crate) async fn cached_result(
+    cache: &dyn CacheStorage,
+    url: &str,
+) -> Result<Option<CrawlResult>> {
+    let snapshot = get_entry::<RawHtmlSnapshot>(cache, &raw_html_cache_key(url)).await?;
+    let Some((status, target)) = snapshot.and_then(|s| Some((s.status, s.result?))) else {
+        return get_entry(cache, &BatchCrawlProcessor::cache_key(url, &None)).await;
+    };
+    match target {
+        SnapshotResult::CrawlJob { key, .. } => get_entry(cache, &key).await,
+        SnapshotResult::Document { key, .. } => {
+            let Some(bytes) = cache.get(&key).await? else {
+                return Ok(None);
+            };
+            // The pipeline wraps documents with their revalidation state;
+            // reprocessed ones are stored bare
+            #[derive(serde::Deserialize)]
+            struct Wrapped {
+                document: ExtractedDoc,
+            }
+            let document = match serde_json::from_slice::<Wrapped>(&bytes) {
+                Ok(wrapped) => wrapped.document,
+                Err(_) => serde_json::from_slice::<ExtractedDoc>(&bytes)?,
+            };
+            Ok(Some(CrawlResult {
+                url: url.to_string(),
+                status,
+                from_cache: true,
+                processing_time_ms: 0,
+                document: Some(document),
+                error: None,
+            }))
+        }
+    }
+}
+
+/// Store `value` under `key` in the entry format of `CacheManager::set_simple`
+async fn set_entry<T: serde::Serialize>(
+    cache: &dyn CacheStorage,
+    key: &str,
+    value: &T,
+    ttl_secs: u64,
+) -> Result<()> {
+    let entry = CacheEntry {
+        data: value,
+        etag: None,
+        last_modified: None,
+        created_at: chrono::Utc::now(),
+        ttl: ttl_secs,
+        content_size: serde_json::to_vec(value)?.len(),
+        metadata: CacheMetadata {
+            extractor_version: "legacy".to_string(),
+            options_hash: "none".to_string(),
+            url_hash: "unknown".to_string(),
+            content_type: None,
+        },
+    };
+    cache
+        .set(
+            key,
+            &serde_json::to_vec(&entry)?,
+            Some(Duration::from_secs(ttl_secs)),
+        )
+        .await?;
+    Ok(())
+}
 
 /// Batch crawl processor for handling multiple URL crawling
 pub struct BatchCrawlProcessor {
@@ -19,6 +151,8 @@ pub struct BatchCrawlProcessor {
     max_batch_size: usize,
     /// Maximum concurrent requests within a batch
     max_concurrency: usize,
+    /// Lifetime of cached crawl results
+    result_ttl_secs: u64,
 }
 
 impl BatchCrawlProcessor {
@@ -36,9 +170,16 @@ impl BatchCrawlProcessor {
             cache,
             max_batch_size,
             max_concurrency,
+            result_ttl_secs: DEFAULT_RESULT_TTL_SECS,
         }
     }
 
+    /// Keep cached crawl results for `ttl` instead of [`DEFAULT_RESULT_TTL_SECS`]
+    pub fn with_result_ttl(mut self, ttl: Duration) -> Self {
+        self.result_ttl_secs = ttl.as_secs();
+        self
+    }
+
     /// Process a single URL with caching support
     async fn process_single_url(
         &self,
@@ -48,7 +189,7 @@ impl BatchCrawlProcessor {
         let start_time = std::time::Instant::now();
 
         // Check cache first
-        let cache_key = self.generate_cache_key(url, options);
+        let cache_key = Self::cache_key(url, options);
         let mut cache = self.cache.lock().await;
 
         // Try to get cached result
@@ -89,9 +230,37 @@ impl BatchCrawlProcessor {
             .await
             .context("Failed to get response text")?;
 
+        let fetched_at = chrono::Utc::now();
+
         // Extract content using WASM
         let extraction_result = self.extractor.extract(content.as_bytes(), url, "default");
 
+        // Keep the raw HTML so it can be re-extracted later without refetching.
+        // Outcome fields let the retention job keep failures and low-quality pages.
+        let snapshot = RawHtmlSnapshot {
+            url: url.to_string(),
+            status,
+            html: content,
+            fetched_at,
+            quality_score: extraction_result
+                .as_ref()
+                .ok()
+                .and_then(|doc| doc.quality_score),
+            extraction_failed: extraction_result.is_err(),
+            result: Some(SnapshotResult::CrawlJob {
+                key: cache_key.clone(),
+                ttl_secs: self.result_ttl_secs,
+            }),
+        };
+        let mut cache = self.cache.lock().await;
+        if let Err(e) = cache
+            .set_simple(&raw_html_cache_key(url), &snapshot, RAW_HTML_TTL_SECS)
+            .await
+        {
+            debug!(error = %e, url = %url, "Failed to cache raw HTML snapshot");
+        }
+        drop(cache);
+
         let processing_time = start_time.elapsed().as_millis() as u64;
 
         match extraction_result {
@@ -107,8 +276,10 @@ impl BatchCrawlProcessor {
 
                 // Cache the successful result
                 let mut cache = self.cache.lock().await;
-                if let Err(e) = cache.set_simple(&cache_key, &result, 3600).await {
-                    // 1 hour TTL
+                if let Err(e) = cache
+                    .set_simple(&cache_key, &result, self.result_ttl_secs)
+                    .await
+                {
                     debug!(error = %e, cache_key = %cache_key, "Failed to cache result");
                 }
                 drop(cache);
@@ -127,20 +298,20 @@ impl BatchCrawlProcessor {
     }
 
     /// Generate cache key for URL and options
-    fn generate_cache_key(&self, url: &str, options: &Option<CrawlOptions>) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let mut hasher = DefaultHasher::new();
-        url.hash(&mut hasher);
-
+    ///
+    /// SHA-256 of the URL and the options that change the result, so keys
+    /// stay valid across builds.
+    fn cache_key(url: &str, options: &Option<CrawlOptions>) -> String {
+        let mut input = url.to_string();
         if let Some(opts) = options {
             // Include relevant options in cache key
-            std::mem::discriminant(&opts.output_format).hash(&mut hasher);
-            opts.cache_mode.hash(&mut hasher);
+            input.push_str(&format!("\n{:?}\n{}", opts.output_format, opts.cache_mode));
         }
 
-        format!("batch_crawl_{:x}", hasher.finish())
+        format!(
+            "batch_crawl_{}",
+            ContentFingerprint::hash_content(input.as_bytes())
+        )
     }
 }
 
@@ -277,6 +448,8 @@ pub struct SingleCrawlProcessor {
     extractor: Arc<dyn riptide_reliability::WasmExtractor>,
     /// Cache manager for storing results
     cache: Arc<tokio::sync::Mutex<riptide_cache::redis::CacheManager>>,
+    /// Lifetime of cached crawl results
+    result_ttl: Duration,
 }
 
 impl SingleCrawlProcessor {
@@ -289,8 +462,15 @@ impl SingleCrawlProcessor {
             http_client,
             extractor,
             cache,
+            result_ttl: Duration::from_secs(DEFAULT_RESULT_TTL_SECS),
         }
     }
+
+    /// Keep cached crawl results for `ttl` instead of [`DEFAULT_RESULT_TTL_SECS`]
+    pub fn with_result_ttl(mut self, ttl: Duration) -> Self {
+        self.result_ttl = ttl;
+        self
+    }
 }
 
 #[async_trait]
@@ -306,7 +486,8 @@ impl JobProcessor for SingleCrawlProcessor {
                     self.cache.clone(),
                     1, // Single URL
                     1, // Single concurrency
-                );
+                )
+                .with_result_ttl(self.result_ttl);
 
                 let result = batch_processor.process_single_url(url, options).await?;
 
@@ -336,6 +517,627 @@ impl JobProcessor for SingleCrawlProcessor {
     }
 }
 
+/// Re-extraction processor replaying cached raw HTML
+///
+/// Runs snapshots stored by crawl jobs and the API pipeline through the
+/// current extractor, so extraction improvements can be applied without
+/// re-crawling. Refreshed documents replace the cached results the
+/// snapshots were extracted into, under the keys they were cached with.
+pub struct ReprocessProcessor {
+    /// Extractor applied to the cached HTML
+    extractor: Arc<dyn riptide_reliability::WasmExtractor>,
+    /// Cache holding raw HTML snapshots and crawl results
+    cache: Arc<dyn CacheStorage>,
+    /// Maximum number of URLs per job
+    max_batch_size: usize,
+}
+
+impl ReprocessProcessor {
+    pub fn new(
+        extractor: Arc<dyn riptide_reliability::WasmExtractor>,
+        cache: Arc<dyn CacheStorage>,
+        max_batch_size: usize,
+    ) -> Self {
+        Self {
+            extractor,
+            cache,
+            max_batch_size,
+        }
+    }
+
+    /// Re-extract a single URL from its cached snapshot
+    async fn reprocess_url(&self, url: &str, mode: &str) -> CrawlResult {
+        let start_time = std::time::Instant::now();
+
+        let snapshot_key = raw_html_cache_key(url);
+        let snapshot = match get_entry::<RawHtmlSnapshot>(self.cache.as_ref(), &snapshot_key).await
+        {
+            Ok(Some(snapshot)) => snapshot,
+            Ok(None) => {
+                return CrawlResult::failed(url, 0, "No cached HTML for URL".to_string());
+            }
+            Err(e) => {
+                return CrawlResult::failed(url, 0, format!("Failed to read cached HTML: {}", e));
+            }
+        };
+
+        let mut document = match self.extractor.extract(snapshot.html.as_bytes(), url, mode) {
+            Ok(document) => document,
+            Err(e) => return CrawlResult::failed(url, snapshot.status, e.to_string()),
+        };
+        document.provenance = Some(
+            ExtractionProvenance::new(riptide_extraction::EXTRACTOR_VERSION, "reprocess")
+                .with_fetched_at(snapshot.fetched_at),
+        );
+
+        let result = CrawlResult {
+            url: url.to_string(),
+            status: snapshot.status,
+            from_cache: true,
+            processing_time_ms: start_time.elapsed().as_millis() as u64,
+            document: Some(document),
+            error: None,
+        };
+
+        // Replace the cached result so later crawls serve the new extraction.
+        // Snapshots stored before their result was recorded have none.
+        if let Some(target) = &snapshot.result {
+            if let Err(e) = self.replace_result(target, &result).await {
+                debug!(error = %e, url = %url, "Failed to cache reprocessed result");
+            }
+        }
+
+        result
+    }
+
+    /// Store `result` where and how the snapshot's original result was cached
+    async fn replace_result(&self, target: &SnapshotResult, result: &CrawlResult) -> Result<()> {
+        match target {
+            SnapshotResult::CrawlJob { key, ttl_secs } => {
+                set_entry(self.cache.as_ref(), key, result, *ttl_secs).await
+            }
+            SnapshotResult::Document { key, ttl_secs } => {
+                let Some(document) = &result.document else {
+                    return Ok(());
+                };
+                self.cache
+                    .set(
+                        key,
+                        &serde_json::to_vec(document)?,
+                        Some(Duration::from_secs(*ttl_secs)),
+                    )
+                    .await?;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl JobProcessor for ReprocessProcessor {
+    async fn process_job(&self, job: &Job) -> Result<serde_json::Value> {
+        match &job.job_type {
+            JobType::Reprocess { urls, mode } => {
+                info!(job_id = %job.id, url_count = urls.len(), "Processing reprocess job");
+
+                if urls.len() > self.max_batch_size {
+                    return Err(anyhow::anyhow!(
+                        "Batch size {} exceeds maximum allowed size {}",
+                        urls.len(),
+                        self.max_batch_size
+                    ));
+                }
+
+                let mode = mode.as_deref().unwrap_or("default");
+                let mut results = Vec::with_capacity(urls.len());
+                for url in urls {
+                    let result = self.reprocess_url(url, mode).await;
+                    if let Some(error) = &result.error {
+                        warn!(job_id = %job.id, url = %url, error = %error, "Reprocess failed");
+                    }
+                    results.push(result);
+                }
+
+                let successful = results.iter().filter(|r| r.error.is_none()).count();
+                let response = BatchCrawlResponse {
+                    total_urls: urls.len(),
+                    successful,
+                    failed: urls.len() - successful,
+                    from_cache: successful,
+                    results,
+                };
+
+                info!(
+                    job_id = %job.id,
+                    total = response.total_urls,
+                    successful = response.successful,
+                    failed = response.failed,
+                    "Reprocess job completed"
+                );
+
+                Ok(serde_json::to_value(response)?)
+            }
+            _ => Err(anyhow::anyhow!(
+                "Unsupported job type for ReprocessProcessor"
+            )),
+        }
+    }
+
+    fn supported_job_types(&self) -> Vec<String> {
+        vec!["Reprocess".to_string()]
+    }
+
+    fn processor_name(&self) -> String {
+        "ReprocessProcessor".to_string()
+    }
+}
+
+/// Raw HTML retention processor
+///
+/// Handles `Custom` jobs named `raw_html_retention`, whose payload is a
+/// [`RawHtmlRetentionPolicy`] (missing fields use defaults). Schedule it with
+/// cron to keep snapshot storage bounded.
+pub struct RawHtmlRetentionProcessor {
+    /// Cache holding raw HTML snapshots
+    cache: Arc<tokio::sync::Mutex<riptide_cache::redis::CacheManager>>,
+}
+
+impl RawHtmlRetentionProcessor {
+    pub fn new(cache: Arc<tokio::sync::Mutex<riptide_cache::redis::CacheManager>>) -> Self {
+        Self { cache }
+    }
+
+    /// Apply a retention policy to every stored snapshot
+    ///
+    /// Keys are walked one SCAN page at a time, and the cache lock is taken
+    /// per snapshot, so the sweep holds neither the whole keyspace in memory
+    /// nor crawl and reprocess jobs sharing the cache. SCAN can return a key
+    /// twice; `scanned` and `kept` may count it twice.
+    async fn apply(&self, policy: &RawHtmlRetentionPolicy) -> Result<RawHtmlRetentionResult> {
+        let now = chrono::Utc::now();
+        let mut result = RawHtmlRetentionResult::default();
+
+        let mut cursor = 0;
+        loop {
+            let (next, keys) = self
+                .cache
+                .lock()
+                .await
+                .scan_page(cursor, "raw_html_*")
+                .await?;
+            for key in keys {
+                result.scanned += 1;
+                let mut cache = self.cache.lock().await;
+                let snapshot = match cache.get_simple::<RawHtmlSnapshot>(&key).await {
+                    Ok(Some(snapshot)) => snapshot,
+                    // Expired or deleted between scan and read
+                    Ok(None) => continue,
+                    Err(e) => {
+                        debug!(error = %e, key = %key, "Failed to read raw HTML snapshot");
+                        result.errors += 1;
+                        continue;
+                    }
+                };
+
+                match policy.decide(&snapshot, now) {
+                    RetentionDecision::Keep => result.kept += 1,
+                    RetentionDecision::Delete => match cache.delete(&key).await {
+                        Ok(()) => result.deleted += 1,
+                        Err(e) => {
+                            debug!(error = %e, key = %key, "Failed to delete raw HTML snapshot");
+                            result.errors += 1;
+                        }
+                    },
+                }
+            }
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl JobProcessor for RawHtmlRetentionProcessor {
+    async fn process_job(&self, job: &Job) -> Result<serde_json::Value> {
+        match &job.job_type {
+            JobType::Custom { job_name, payload } if job_name == RAW_HTML_RETENTION_JOB => {
+                let policy: RawHtmlRetentionPolicy = if payload.is_null() {
+                    RawHtmlRetentionPolicy::default()
+                } else {
+                    serde_json::from_value(payload.clone())
+                        .context("Invalid raw HTML retention policy")?
+                };
+                policy.validate()?;
+
+                info!(job_id = %job.id, ?policy, "Processing raw HTML retention job");
+                let result = self.apply(&policy).await?;
+                info!(
+                    job_id = %job.id,
+                    scanned = result.scanned,
+                    kept = result.kept,
+                    deleted = result.deleted,
+                    errors = result.errors,
+                    "Raw HTML retention job completed"
+                );
+
+                Ok(serde_json::to_value(result)?)
+            }
+            _ => Err(anyhow::anyhow!(
+                "Unsupported job type for RawHtmlRetentionProcessor"
+            )),
+        }
+    }
+
+    fn supported_job_types(&self) -> Vec<String> {
+        vec![RAW_HTML_RETENTION_JOB.to_string()]
+    }
+
+    fn processor_name(&self) -> String {
+        "RawHtmlRetentionProcessor".to_string()
+    }
+}
+
+/// Export processor writing crawl results to external stores
+///
+/// Handles `Custom` jobs named `export`, whose payload is an [`ExportJob`].
+/// Cached results of the listed URLs and any inline documents are
+/// deduplicated on content and written to the selected sinks.
+pub struct ExportProcessor {
+    /// Cache holding crawl results and raw HTML snapshots
+    cache: Arc<dyn CacheStorage>,
+    /// Configured sinks
+    sinks: Vec<Arc<dyn ExportSink>>,
+}
+
+impl ExportProcessor {
+    pub fn new(cache: Arc<dyn CacheStorage>, sinks: Vec<Arc<dyn ExportSink>>) -> Self {
+        Self { cache, sinks }
+    }
+
+    /// Sinks named in the job, or all of them
+    fn select_sinks(&self, names: &[String]) -> Result<Vec<&Arc<dyn ExportSink>>> {
+        if names.is_empty() {
+            return Ok(self.sinks.iter().collect());
+        }
+        names
+            .iter()
+            .map(|name| {
+                self.sinks
+                    .iter()
+                    .find(|sink| sink.name() == name)
+                    .ok_or_else(|| anyhow::anyhow!("Export sink '{}' is not configured", name))
+            })
+            .collect()
+    }
+
+    /// Extract the tables of each document from its HTML or cached snapshot
+    async fn attach_tables(&self, documents: &mut [ExportDocument]) {
+        for doc in documents {
+            let html = match &doc.document.html {
+                Some(html) => html.clone(),
+                None => {
+                    let key = raw_html_cache_key(&doc.document.url);
+                    match get_entry::<RawHtmlSnapshot>(self.cache.as_ref(), &key).await {
+                        Ok(Some(snapshot)) => snapshot.html,
+                        _ => continue,
+                    }
+                }
+            };
+            match riptide_extraction::dom_utils::extract_tables(
+                &html,
+                riptide_extraction::TableExtractionMode::All,
+            )
+            .await
+            {
+                Ok(tables) => doc.tables = tables.into_iter().map(Into::into).collect(),
+                Err(e) => debug!(
+                    error = %e,
+                    url = %doc.document.url,
+                    "Failed to extract tables for export"
+                ),
+            }
+        }
+    }
+
+    async fn export(&self, job: ExportJob) -> Result<ExportResult> {
+        let sinks = self.select_sinks(&job.sinks)?;
+        let mut result = ExportResult::default();
+
+        let mut documents = job.documents;
+        for url in job.urls {
+            match cached_result(self.cache.as_ref(), &url).await {
+                Ok(Some(CrawlResult {
+                    document: Some(document),
+                    ..
+                })) => documents.push(document),
+                Ok(_) => result.missing_urls.push(url),
+                Err(e) => {
+                    debug!(error = %e, url = %url, "Failed to read cached crawl result");
+                    result.missing_urls.push(url);
+                }
+            }
+        }
+
+        let (mut documents, duplicates) = dedup_documents(documents);
+        result.documents = documents.len();
+        result.duplicates_in_job = duplicates;
+        if documents.is_empty() {
+            return Ok(result);
+        }
+        if sinks.iter().any(|sink| sink.wants_tables()) {
+            self.attach_tables(&mut documents).await;
+        }
+
+        for sink in sinks {
+            let stats = sink
+                .export(&documents)
+                .await
+                .with_context(|| format!("Export to {} failed", sink.name()))?;
+            result.sinks.insert(sink.name().to_string(), stats);
+        }
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl JobProcessor for ExportProcessor {
+    async fn process_job(&self, job: &Job) -> Result<serde_json::Value> {
+        match &job.job_type {
+            JobType::Custom { job_name, payload } if job_name == EXPORT_JOB => {
+                let export: ExportJob =
+                    serde_json::from_value(payload.clone()).context("Invalid export job")?;
+
+                info!(
+                    job_id = %job.id,
+                    urls = export.urls.len(),
+                    documents = export.documents.len(),
+                    "Processing export job"
+                );
+                let result = self.export(export).await?;
+                for (sink, stats) in &result.sinks {
+                    info!(
+                        job_id = %job.id,
+                        sink = %sink,
+                        exported = stats.exported,
+                        duplicates = stats.duplicates,
+                        failed = stats.failed,
+                        "Export job completed"
+                    );
+                    if let Some(error) = stats.errors.first() {
+                        warn!(job_id = %job.id, sink = %sink, error = %error, "Documents rejected by sink");
+                    }
+                }
+
+                Ok(serde_json::to_value(result)?)
+            }
+            _ => Err(anyhow::anyhow!("Unsupported job type for ExportProcessor")),
+        }
+    }
+
+    fn supported_job_types(&self) -> Vec<String> {
+        vec![EXPORT_JOB.to_string()]
+    }
+
+    fn processor_name(&self) -> String {
+        "ExportProcessor".to_string()
+    }
+}
+
+/// Report processor emailing crawl reports
+///
+/// Handles `Custom` jobs named `crawl_report`, whose payload is a
+/// [`ReportJob`](crate::reports::ReportJob). The cached results of the
+/// tenant's crawls completed since its previous report, or of the listed
+/// URLs, are rendered into a riptide-streaming report that is sent to the
+/// tenant's recipients.
+#[cfg(feature = "email-reports")]
+pub struct ReportProcessor {
+    /// Cache scanned for raw HTML snapshots of completed crawls
+    cache: Arc<tokio::sync::Mutex<riptide_cache::redis::CacheManager>>,
+    /// Cache holding crawl results and raw HTML snapshots
+    storage: Arc<dyn CacheStorage>,
+    sender: Arc<dyn riptide_types::ports::EmailSender>,
+    config: crate::reports::ReportsConfig,
+}
+
+#[cfg(feature = "email-reports")]
+impl ReportProcessor {
+    pub fn new(
+        cache: Arc<tokio::sync::Mutex<riptide_cache::redis::CacheManager>>,
+        storage: Arc<dyn CacheStorage>,
+        sender: Arc<dyn riptide_types::ports::EmailSender>,
+        config: crate::reports::ReportsConfig,
+    ) -> Self {
+        Self {
+            cache,
+            storage,
+            sender,
+            config,
+        }
+    }
+
+    /// When the tenant's last report went out
+    async fn last_report(&self, tenant: &str) -> Result<Option<chrono::DateTime<chrono::Utc>>> {
+        let key = crate::reports::last_report_cache_key(tenant);
+        let Some(bytes) = self.storage.get(&key).await? else {
+            return Ok(None);
+        };
+        let sent = chrono::DateTime::parse_from_rfc3339(std::str::from_utf8(&bytes)?)?;
+        Ok(Some(sent.with_timezone(&chrono::Utc)))
+    }
+
+    /// URLs of the tenant's crawls completed after `since`, most recent
+    /// first, from their raw HTML snapshots
+    async fn completed_crawls(
+        &self,
+        tenant: &crate::reports::TenantReportConfig,
+        since: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<Vec<String>> {
+        let mut crawls = Vec::new();
+        let mut cursor = 0;
+        loop {
+            let (next, keys) = self
+                .cache
+                .lock()
+                .await
+                .scan_page(cursor, "raw_html_*")
+                .await?;
+            for key in keys {
+                let snapshot = match get_entry::<RawHtmlSnapshot>(self.storage.as_ref(), &key).await
+                {
+                    Ok(Some(snapshot)) => snapshot,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        debug!(error = %e, key = %key, "Failed to read raw HTML snapshot");
+                        continue;
+                    }
+                };
+                if tenant.covers(&snapshot.url) && since.is_none_or(|t| snapshot.fetched_at > t) {
+                    crawls.push((snapshot.fetched_at, snapshot.url));
+                }
+            }
+            if next == 0 {
+                break;
+            }
+            cursor = next;
+        }
+
+        crawls.sort_by(|a, b| b.0.cmp(&a.0));
+        if crawls.len() > crate::reports::MAX_REPORT_PAGES {
+            warn!(
+                completed = crawls.len(),
+                reported = crate::reports::MAX_REPORT_PAGES,
+                "Report covers only the most recent completed crawls"
+            );
+            crawls.truncate(crate::reports::MAX_REPORT_PAGES);
+        }
+        Ok(crawls.into_iter().map(|(_, url)| url).collect())
+    }
+
+    async fn report(&self, job: crate::reports::ReportJob) -> Result<crate::reports::ReportResult> {
+        use crate::reports::{render_report, ReportResult, ReportSummary};
+        use riptide_types::ports::{EmailAttachment, EmailMessage};
+
+        let tenant = self.config.tenant(&job.tenant).ok_or_else(|| {
+            anyhow::anyhow!("No report configuration for tenant '{}'", job.tenant)
+        })?;
+        let generated_at = chrono::Utc::now();
+        // Only reports over the window since the previous one move it
+        let advances_window = job.urls.is_empty() && job.since.is_none();
+        let urls = if job.urls.is_empty() {
+            let since = match job.since {
+                Some(since) => Some(since),
+                None => self.last_report(&job.tenant).await?,
+            };
+            self.completed_crawls(tenant, since).await?
+        } else {
+            job.urls
+        };
+
+        let mut results = Vec::new();
+        let mut missing_urls = Vec::new();
+        for url in &urls {
+            match cached_result(self.storage.as_ref(), url).await {
+                Ok(Some(result)) if result.document.is_some() => results.push(result),
+                Ok(_) => missing_urls.push(url.clone()),
+                Err(e) => {
+                    debug!(error = %e, url = %url, "Failed to read cached crawl result");
+                    missing_urls.push(url.clone());
+                }
+            }
+        }
+
+        let title = job.title.as_deref().unwrap_or(&tenant.title);
+        let summary = ReportSummary::new(&job.tenant, title, urls.len(), &results);
+        let report = render_report(tenant, &summary, &results).await?;
+        let filename = format!(
+            "riptide-report-{}-{}.{}",
+            job.tenant,
+            summary.generated_at.format("%Y%m%d"),
+            tenant.format.extension()
+        );
+        let attachment_bytes = report.len();
+
+        let message = EmailMessage::new(
+            tenant.recipients.iter().cloned(),
+            summary.render(&tenant.subject).replace(['\r', '\n'], " "),
+            summary.render(&tenant.body),
+        )
+        .with_attachment(EmailAttachment {
+            filename: filename.clone(),
+            content_type: tenant.format.content_type().to_string(),
+            data: report,
+        });
+        self.sender
+            .send(&message)
+            .await
+            .context("Failed to send report email")?;
+        if advances_window {
+            self.storage
+                .set(
+                    &crate::reports::last_report_cache_key(&job.tenant),
+                    generated_at.to_rfc3339().as_bytes(),
+                    None,
+                )
+                .await
+                .context("Failed to record report time")?;
+        }
+
+        Ok(ReportResult {
+            tenant: job.tenant,
+            recipients: tenant.recipients.len(),
+            reported_urls: results.len(),
+            missing_urls,
+            attachment: filename,
+            attachment_bytes,
+        })
+    }
+}
+
+#[cfg(feature = "email-reports")]
+#[async_trait]
+impl JobProcessor for ReportProcessor {
+    async fn process_job(&self, job: &Job) -> Result<serde_json::Value> {
+        match &job.job_type {
+            JobType::Custom { job_name, payload } if job_name == crate::reports::REPORT_JOB => {
+                let report: crate::reports::ReportJob =
+                    serde_json::from_value(payload.clone()).context("Invalid report job")?;
+
+                info!(
+                    job_id = %job.id,
+                    tenant = %report.tenant,
+                    urls = report.urls.len(),
+                    "Processing report job"
+                );
+                let result = self.report(report).await?;
+                info!(
+                    job_id = %job.id,
+                    tenant = %result.tenant,
+                    recipients = result.recipients,
+                    reported_urls = result.reported_urls,
+                    missing_urls = result.missing_urls.len(),
+                    "Report email sent"
+                );
+
+                Ok(serde_json::to_value(result)?)
+            }
+            _ => Err(anyhow::anyhow!("Unsupported job type for ReportProcessor")),
+        }
+    }
+
+    fn supported_job_types(&self) -> Vec<String> {
+        vec![crate::reports::REPORT_JOB.to_string()]
+    }
+
+    fn processor_name(&self) -> String {
+        "ReportProcessor".to_string()
+    }
+}
+
 /// Maintenance task processor
 pub struct MaintenanceProcessor;
 
@@ -481,6 +1283,66 @@ pub struct CrawlResult {
     pub error: Option<String>,
 }
 
+impl CrawlResult {
+    fn failed(url: &str, status: u16, error: String) -> Self {
+        Self {
+            url: url.to_string(),
+            status,
+            from_cache: false,
+            processing_time_ms: 0,
+            document: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Raw HTML snapshot cached by crawl jobs for later re-extraction
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RawHtmlSnapshot {
+    pub url: String,
+    pub status: u16,
+    pub html: String,
+    pub fetched_at: chrono::DateTime<chrono::Utc>,
+    /// Quality score of the extraction performed at fetch time
+    #[serde(default)]
+    pub quality_score: Option<u8>,
+    /// Whether extraction failed at fetch time
+    #[serde(default)]
+    pub extraction_failed: bool,
+    /// Cached result extracted from this snapshot
+    #[serde(default)]
+    pub result: Option<SnapshotResult>,
+}
+
+/// Cached result extracted from a raw HTML snapshot, replaced when the
+/// snapshot is reprocessed
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "format", rename_all = "snake_case")]
+pub enum SnapshotResult {
+    /// [`CrawlResult`] cached by a crawl job under `key`
+    CrawlJob {
+        key: String,
+        /// Snapshots stored before the TTL was recorded used the default
+        #[serde(default = "default_result_ttl_secs")]
+        ttl_secs: u64,
+    },
+    /// Bare [`ExtractedDoc`] cached by the API pipeline under `key`
+    Document { key: String, ttl_secs: u64 },
+}
+
+fn default_result_ttl_secs() -> u64 {
+    DEFAULT_RESULT_TTL_SECS
+}
+
+/// Result structure for raw HTML retention runs
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct RawHtmlRetentionResult {
+    pub scanned: usize,
+    pub kept: usize,
+    pub deleted: usize,
+    pub errors: usize,
+}
+
 /// Result structure for maintenance tasks
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct MaintenanceResult {
@@ -844,6 +1706,207 @@ mod tests {
         assert_eq!(processor.supported_job_types(), vec!["BatchCrawl"]);
     }
 
+    #[test]
+    fn test_reprocess_processor_name() {
+        let processor = ReprocessProcessor::new(
+            Arc::new(MockWasmExtractor),
+            Arc::new(riptide_types::InMemoryCache::new()),
+            100,
+        );
+        assert_eq!(processor.processor_name(), "ReprocessProcessor");
+        assert_eq!(processor.supported_job_types(), vec!["Reprocess"]);
+    }
+
+    fn snapshot(url: &str, result: SnapshotResult) -> RawHtmlSnapshot {
+        RawHtmlSnapshot {
+            url: url.to_string(),
+            status: 200,
+            html: "<html><body><p>Stored</p></body></html>".to_string(),
+            fetched_at: chrono::Utc::now(),
+            quality_score: Some(40),
+            extraction_failed: false,
+            result: Some(result),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reprocess_replaces_cached_results() {
+        let cache: Arc<dyn CacheStorage> = Arc::new(riptide_types::InMemoryCache::new());
+        let options = Some(CrawlOptions {
+            cache_mode: "enabled".to_string(),
+            ..Default::default()
+        });
+        let job_url = "https://example.com/job";
+        let job_key = BatchCrawlProcessor::cache_key(job_url, &options);
+        let api_url = "https://example.com/api";
+        let api_key = "riptide:v1:read_through:test";
+
+        store_raw_html_snapshot(
+            cache.as_ref(),
+            &snapshot(
+                job_url,
+                SnapshotResult::CrawlJob {
+                    key: job_key.clone(),
+                    ttl_secs: 60,
+                },
+            ),
+        )
+        .await
+        .unwrap();
+        store_raw_html_snapshot(
+            cache.as_ref(),
+            &snapshot(
+                api_url,
+                SnapshotResult::Document {
+                    key: api_key.to_string(),
+                    ttl_secs: 60,
+                },
+            ),
+        )
+        .await
+        .unwrap();
+
+        let processor = ReprocessProcessor::new(Arc::new(MockWasmExtractor), cache.clone(), 10);
+        let job = Job::new(JobType::Reprocess {
+            urls: vec![
+                job_url.to_string(),
+                api_url.to_string(),
+                "https://example.com/never-crawled".to_string(),
+            ],
+            mode: None,
+        });
+        let response: BatchCrawlResponse =
+            serde_json::from_value(processor.process_job(&job).await.unwrap()).unwrap();
+        assert_eq!(response.successful, 2);
+        assert_eq!(response.failed, 1);
+
+        // The crawl job's result is replaced under its options' key
+        let cached: CrawlResult = get_entry(cache.as_ref(), &job_key).await.unwrap().unwrap();
+        let document = cached.document.unwrap();
+        assert_eq!(document.title.as_deref(), Some("Mock Title"));
+        assert_eq!(document.provenance.unwrap().strategy, "reprocess");
+        assert!(cache
+            .get(&BatchCrawlProcessor::cache_key(job_url, &None))
+            .await
+            .unwrap()
+            .is_none());
+
+        // The pipeline's document is replaced as a bare document
+        let bytes = cache.get(api_key).await.unwrap().unwrap();
+        let document: ExtractedDoc = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(document.url, api_url);
+    }
+
+    /// Sink keeping the URLs it was given
+    #[derive(Default)]
+    struct RecordingSink {
+        urls: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl ExportSink for RecordingSink {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn export(&self, documents: &[ExportDocument]) -> Result<crate::export::ExportStats> {
+            let mut urls = self.urls.lock().unwrap();
+            urls.extend(documents.iter().map(|d| d.document.url.clone()));
+            Ok(crate::export::ExportStats {
+                exported: documents.len(),
+                ..Default::default()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_reads_results_where_they_were_cached() {
+        let cache: Arc<dyn CacheStorage> = Arc::new(riptide_types::InMemoryCache::new());
+        let doc = |url: &str| ExtractedDoc {
+            url: url.to_string(),
+            text: format!("Text of {}", url),
+            ..Default::default()
+        };
+
+        // A crawl job with options, found through its snapshot
+        let job_url = "https://example.com/job";
+        let job_key = BatchCrawlProcessor::cache_key(
+            job_url,
+            &Some(CrawlOptions {
+                cache_mode: "enabled".to_string(),
+                ..Default::default()
+            }),
+        );
+        let job_result = CrawlResult {
+            url: job_url.to_string(),
+            status: 200,
+            from_cache: false,
+            processing_time_ms: 5,
+            document: Some(doc(job_url)),
+            error: None,
+        };
+        set_entry(cache.as_ref(), &job_key, &job_result, 60)
+            .await
+            .unwrap();
+        let job_target = SnapshotResult::CrawlJob {
+            key: job_key,
+            ttl_secs: 60,
+        };
+        store_raw_html_snapshot(cache.as_ref(), &snapshot(job_url, job_target))
+            .await
+            .unwrap();
+
+        // An API crawl, cached with its revalidation state
+        let api_url = "https://example.com/api";
+        let api_key = "riptide:v1:read_through:0123abcd";
+        let entry = serde_json::json!({
+            "document": doc(api_url),
+            "content_hash": null,
+            "stored_at": chrono::Utc::now(),
+        });
+        cache
+            .set(api_key, &serde_json::to_vec(&entry).unwrap(), None)
+            .await
+            .unwrap();
+        let api_target = SnapshotResult::Document {
+            key: api_key.to_string(),
+            ttl_secs: 60,
+        };
+        store_raw_html_snapshot(cache.as_ref(), &snapshot(api_url, api_target))
+            .await
+            .unwrap();
+
+        let sink = Arc::new(RecordingSink::default());
+        let processor = ExportProcessor::new(cache, vec![sink.clone()]);
+        let result = processor
+            .export(ExportJob {
+                urls: vec![
+                    job_url.to_string(),
+                    api_url.to_string(),
+                    "https://example.com/never-crawled".to_string(),
+                ],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(*sink.urls.lock().unwrap(), [job_url, api_url]);
+        assert_eq!(result.documents, 2);
+        assert_eq!(result.missing_urls, ["https://example.com/never-crawled"]);
+    }
+
+    #[test]
+    fn test_raw_html_cache_key_stable() {
+        assert_eq!(
+            raw_html_cache_key("https://example.com/"),
+            raw_html_cache_key("https://example.com/")
+        );
+        assert_ne!(
+            raw_html_cache_key("https://example.com/a"),
+            raw_html_cache_key("https://example.com/b")
+        );
+    }
+
     #[test]
     fn test_maintenance_processor_job_types() {
         let processor = MaintenanceProcessor;
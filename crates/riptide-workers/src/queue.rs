@@ -572,6 +572,9 @@ impl JobQueue {
                             crate::job::JobType::PdfExtraction { .. } => {
                                 type_filter.eq_ignore_ascii_case("pdf_extraction")
                             }
+                            crate::job::JobType::Reprocess { .. } => {
+                                type_filter.eq_ignore_ascii_case("reprocess")
+                            }
                             crate::job::JobType::Maintenance { task_type, .. } => {
                                 type_filter.eq_ignore_ascii_case("maintenance")
                                     || type_filter
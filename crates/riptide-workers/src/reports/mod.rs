@@ -0,0 +1,456 @@
+//! Scheduled crawl report emails
+//!
+//! A `Custom` job named [`REPORT_JOB`] renders the riptide-streaming report
+//! over the crawls of its tenant's sites completed since the tenant's
+//! previous report, or over the URLs it lists, and emails it, attached, to
+//! the recipients configured for its tenant. Tenants with a `schedule` get
+//! the job registered with the scheduler on startup, so reports go out on
+//! their cron cadence without anyone enqueueing them; schedules of tenants
+//! removed from the file are dropped.
+//!
+//! Tenants are read from the JSON file named by `RIPTIDE_REPORTS_FILE`:
+//!
+//! ```json
+//! {
+//!   "tenants": {
+//!     "acme": {
+//!       "recipients": ["seo@acme.example"],
+//!       "schedule": "0 0 7 * * Mon",
+//!       "urls": ["https://acme.example/", "https://docs.acme.example/"],
+//!       "subject": "Weekly crawl report for {tenant}: {successful}/{pages} pages"
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! Rendering and delivery need the `email-reports` feature; mail goes out
+//! through [`SmtpEmailSender`] configured from `RIPTIDE_SMTP_*`.
+
+pub mod smtp;
+
+#[cfg(feature = "email-reports")]
+pub use smtp::SmtpEmailSender;
+pub use smtp::{SmtpConfig, SmtpTls};
+
+use crate::job::JobType;
+use crate::processors::CrawlResult;
+use crate::scheduler::ScheduledJob;
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Custom job name handled by `ReportProcessor`
+pub const REPORT_JOB: &str = "crawl_report";
+
+/// Most completed crawls one report covers; the most recent are kept
+pub const MAX_REPORT_PAGES: usize = 1_000;
+
+/// Payload of a report job
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportJob {
+    /// Tenant whose recipients and templates are used
+    pub tenant: String,
+    /// URLs whose cached crawl results are reported; empty reports the
+    /// tenant's completed crawls
+    pub urls: Vec<String>,
+    /// Report crawls completed after this time instead of since the
+    /// tenant's previous report
+    pub since: Option<DateTime<Utc>>,
+    /// Report title, overriding the tenant's
+    pub title: Option<String>,
+}
+
+/// Format of the attached report
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportAttachmentFormat {
+    /// Self-contained HTML report with charts
+    #[default]
+    Html,
+    /// The report data as JSON
+    Json,
+}
+
+impl ReportAttachmentFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Html => "html",
+            Self::Json => "json",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Html => "text/html; charset=utf-8",
+            Self::Json => "application/json",
+        }
+    }
+}
+
+/// Report settings of one tenant
+///
+/// `subject` and `body` are templates; see [`ReportSummary::render`] for
+/// the placeholders they may use.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TenantReportConfig {
+    pub recipients: Vec<String>,
+    /// Six-field cron expression; tenants without one only get reports on demand
+    pub schedule: Option<String>,
+    /// URL prefixes of the tenant's sites; crawls under them are reported
+    pub urls: Vec<String>,
+    pub title: String,
+    pub subject: String,
+    pub body: String,
+    pub format: ReportAttachmentFormat,
+    pub include_charts: bool,
+}
+
+impl Default for TenantReportConfig {
+    fn default() -> Self {
+        Self {
+            recipients: Vec::new(),
+            schedule: None,
+            urls: Vec::new(),
+            title: "RipTide Crawl Report".to_string(),
+            subject: "{title} for {tenant}: {successful}/{pages} pages".to_string(),
+            body: "{title} for {tenant}, generated {generated_at}.\n\n\
+                   Pages: {pages}\n\
+                   Reported: {successful}\n\
+                   Missing from cache: {missing}\n\
+                   Words: {words}\n\n\
+                   The full report is attached."
+                .to_string(),
+            format: ReportAttachmentFormat::Html,
+            include_charts: true,
+        }
+    }
+}
+
+impl TenantReportConfig {
+    /// Whether a crawl of `url` belongs to the tenant's sites
+    pub fn covers(&self, url: &str) -> bool {
+        self.urls
+            .iter()
+            .any(|prefix| url.starts_with(prefix.as_str()))
+    }
+}
+
+/// Per-tenant report delivery
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportsConfig {
+    pub tenants: HashMap<String, TenantReportConfig>,
+    /// SMTP relay; not part of the tenants file
+    #[serde(skip)]
+    pub smtp: SmtpConfig,
+}
+
+impl ReportsConfig {
+    /// Read `RIPTIDE_REPORTS_FILE` and `RIPTIDE_SMTP_*`; `None` when no
+    /// tenants file is configured
+    pub fn from_env() -> Result<Option<Self>> {
+        let Some(path) = std::env::var("RIPTIDE_REPORTS_FILE")
+            .ok()
+            .filter(|p| !p.is_empty())
+        else {
+            return Ok(None);
+        };
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read reports file {}", path))?;
+        let mut config: Self =
+            serde_json::from_str(&raw).with_context(|| format!("Invalid reports file {}", path))?;
+        config.smtp = SmtpConfig::from_env()?;
+        config.validate()?;
+        Ok(Some(config))
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        for (tenant, config) in &self.tenants {
+            if config.recipients.is_empty() {
+                return Err(anyhow!("Report tenant '{}' has no recipients", tenant));
+            }
+            if let Some(schedule) = &config.schedule {
+                schedule
+                    .parse::<cron::Schedule>()
+                    .with_context(|| format!("Invalid schedule for report tenant '{}'", tenant))?;
+                if config.urls.is_empty() {
+                    return Err(anyhow!(
+                        "Report tenant '{}' has a schedule but no urls",
+                        tenant
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn tenant(&self, tenant: &str) -> Option<&TenantReportConfig> {
+        self.tenants.get(tenant)
+    }
+
+    /// Scheduled jobs for the tenants with a `schedule`, named `report:<tenant>`
+    pub fn scheduled_jobs(&self) -> Result<Vec<ScheduledJob>> {
+        self.tenants
+            .iter()
+            .filter_map(|(tenant, config)| Some((tenant, config.schedule.clone()?)))
+            .map(|(tenant, schedule)| {
+                let payload = ReportJob {
+                    tenant: tenant.clone(),
+                    ..Default::default()
+                };
+                ScheduledJob::new(
+                    report_schedule_name(tenant),
+                    schedule,
+                    JobType::Custom {
+                        job_name: REPORT_JOB.to_string(),
+                        payload: serde_json::to_value(payload)?,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+/// Name of the scheduled job sending a tenant's reports
+pub fn report_schedule_name(tenant: &str) -> String {
+    format!("report:{}", tenant)
+}
+
+/// Whether `job` is a tenant's report schedule
+pub fn is_report_schedule(job: &ScheduledJob) -> bool {
+    job.name.starts_with("report:")
+        && matches!(
+            &job.job_template,
+            JobType::Custom { job_name, .. } if job_name == REPORT_JOB
+        )
+}
+
+/// Cache key of the time a tenant's last report went out
+pub fn last_report_cache_key(tenant: &str) -> String {
+    format!("report_last_sent:{}", tenant)
+}
+
+/// Figures substituted into the subject and body templates
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReportSummary {
+    pub tenant: String,
+    pub title: String,
+    /// Pages in the report
+    pub pages: usize,
+    /// URLs with a cached document
+    pub successful: usize,
+    /// URLs without a cached document
+    pub missing: usize,
+    pub words: usize,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl ReportSummary {
+    pub fn new(tenant: &str, title: &str, pages: usize, results: &[CrawlResult]) -> Self {
+        Self {
+            tenant: tenant.to_string(),
+            title: title.to_string(),
+            pages,
+            successful: results.len(),
+            missing: pages.saturating_sub(results.len()),
+            words: results
+                .iter()
+                .filter_map(|r| r.document.as_ref())
+                .map(|doc| doc.text.split_whitespace().count())
+                .sum(),
+            generated_at: Utc::now(),
+        }
+    }
+
+    /// Substitute `{tenant}`, `{title}`, `{pages}`, `{successful}`,
+    /// `{missing}`, `{words}`, `{date}` and `{generated_at}`; unknown
+    /// placeholders are left as written
+    pub fn render(&self, template: &str) -> String {
+        let values = [
+            ("tenant", self.tenant.clone()),
+            ("title", self.title.clone()),
+            ("pages", self.pages.to_string()),
+            ("successful", self.successful.to_string()),
+            ("missing", self.missing.to_string()),
+            ("words", self.words.to_string()),
+            ("date", self.generated_at.format("%Y-%m-%d").to_string()),
+            (
+                "generated_at",
+                self.generated_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+            ),
+        ];
+
+        let mut out = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find('{') {
+            out.push_str(&rest[..start]);
+            let tail = &rest[start..];
+            let value = tail.find('}').and_then(|end| {
+                let name = &tail[1..end];
+                values
+                    .iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, value)| (value, end))
+            });
+            match value {
+                Some((value, end)) => {
+                    out.push_str(value);
+                    rest = &tail[end + 1..];
+                }
+                None => {
+                    out.push('{');
+                    rest = &tail[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// Outcome of a report job
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportResult {
+    pub tenant: String,
+    pub recipients: usize,
+    pub reported_urls: usize,
+    /// URLs without a cached crawl result
+    pub missing_urls: Vec<String>,
+    pub attachment: String,
+    pub attachment_bytes: usize,
+}
+
+/// Render the riptide-streaming report over `results`
+#[cfg(feature = "email-reports")]
+pub async fn render_report(
+    config: &TenantReportConfig,
+    summary: &ReportSummary,
+    results: &[CrawlResult],
+) -> Result<Vec<u8>> {
+    use riptide_streaming::{ReportConfig, ReportGenerator};
+
+    let extractions = results
+        .iter()
+        .filter_map(|result| {
+            let doc = result.document.as_ref()?;
+            let mut metadata = HashMap::new();
+            metadata.insert("status".to_string(), serde_json::json!(result.status));
+            if let Some(quality) = doc.quality_score {
+                metadata.insert("quality_score".to_string(), serde_json::json!(quality));
+            }
+            Some(riptide_streaming::ExtractionResult {
+                id: result.url.clone(),
+                url: result.url.clone(),
+                title: doc.title.clone(),
+                content: doc.text.clone(),
+                metadata,
+                timestamp: summary.generated_at,
+                extraction_time_ms: result.processing_time_ms,
+                word_count: doc.text.split_whitespace().count(),
+                links: doc.links.clone(),
+                images: doc.media.clone(),
+            })
+        })
+        .collect();
+
+    let generator = ReportGenerator::with_config(ReportConfig {
+        title: summary.title.clone(),
+        include_charts: config.include_charts,
+        ..ReportConfig::default()
+    });
+    let report_id = format!("{}-{}", summary.tenant, summary.generated_at.timestamp());
+    let data = generator
+        .prepare_report_data(&report_id, extractions)
+        .await
+        .context("Failed to prepare report data")?;
+    match config.format {
+        ReportAttachmentFormat::Html => generator
+            .generate_html_report(&data)
+            .await
+            .context("Failed to render HTML report"),
+        ReportAttachmentFormat::Json => Ok(serde_json::to_vec_pretty(&data)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary() -> ReportSummary {
+        ReportSummary {
+            tenant: "acme".to_string(),
+            title: "Weekly".to_string(),
+            pages: 3,
+            successful: 2,
+            missing: 1,
+            words: 420,
+            generated_at: DateTime::parse_from_rfc3339("2026-03-02T07:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        }
+    }
+
+    #[test]
+    fn test_render_substitutes_placeholders() {
+        let summary = summary();
+        assert_eq!(
+            summary.render("{title} for {tenant} on {date}: {successful}/{pages} ({words} words)"),
+            "Weekly for acme on 2026-03-02: 2/3 (420 words)"
+        );
+        assert_eq!(
+            summary.render("{unknown} {missing} { {pages"),
+            "{unknown} 1 { {pages"
+        );
+    }
+
+    #[test]
+    fn test_tenants_file_defaults_and_schedules() {
+        let config: ReportsConfig = serde_json::from_value(serde_json::json!({
+            "tenants": {
+                "acme": {
+                    "recipients": ["seo@acme.example"],
+                    "schedule": "0 0 7 * * Mon",
+                    "urls": ["https://acme.example/"]
+                },
+                "globex": {"recipients": ["ops@globex.example"], "format": "json"}
+            }
+        }))
+        .unwrap();
+        config.validate().unwrap();
+
+        let acme = config.tenant("acme").unwrap();
+        assert_eq!(acme.format, ReportAttachmentFormat::Html);
+        assert_eq!(acme.title, "RipTide Crawl Report");
+
+        let jobs = config.scheduled_jobs().unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].name, report_schedule_name("acme"));
+        assert!(is_report_schedule(&jobs[0]));
+        assert!(acme.covers("https://acme.example/pricing"));
+        assert!(!acme.covers("https://globex.example/"));
+    }
+
+    #[test]
+    fn test_validate_rejects_incomplete_tenants() {
+        let mut config = ReportsConfig::default();
+        config
+            .tenants
+            .insert("acme".to_string(), TenantReportConfig::default());
+        assert!(config.validate().is_err());
+
+        let tenant = config.tenants.get_mut("acme").unwrap();
+        tenant.recipients.push("seo@acme.example".to_string());
+        tenant.schedule = Some("0 0 7 * * Mon".to_string());
+        assert!(config.validate().is_err());
+
+        let tenant = config.tenants.get_mut("acme").unwrap();
+        tenant.urls.push("https://acme.example/".to_string());
+        assert!(config.validate().is_ok());
+
+        config.tenants.get_mut("acme").unwrap().schedule = Some("every monday".to_string());
+        assert!(config.validate().is_err());
+    }
+}
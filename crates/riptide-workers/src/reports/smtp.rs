@@ -0,0 +1,218 @@
+//! SMTP adapter for the [`EmailSender`](riptide_types::ports::EmailSender) port
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+
+/// Transport security towards the SMTP relay
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpTls {
+    /// Plain connection upgraded with STARTTLS (port 587)
+    #[default]
+    Starttls,
+    /// Implicit TLS (port 465)
+    Tls,
+    /// No encryption; only for local relays
+    None,
+}
+
+/// SMTP relay settings, from `RIPTIDE_SMTP_*`
+#[derive(Clone, PartialEq, Eq)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub tls: SmtpTls,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Sender address, e.g. `RipTide Reports <reports@example.com>`
+    pub from: String,
+    pub timeout_secs: u64,
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_string(),
+            port: 587,
+            tls: SmtpTls::Starttls,
+            username: None,
+            password: None,
+            from: "RipTide Reports <reports@localhost>".to_string(),
+            timeout_secs: 30,
+        }
+    }
+}
+
+impl std::fmt::Debug for SmtpConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SmtpConfig")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("tls", &self.tls)
+            .field("username", &self.username)
+            .field(
+                "password",
+                &self.password.as_ref().map(|_| "***REDACTED***"),
+            )
+            .field("from", &self.from)
+            .field("timeout_secs", &self.timeout_secs)
+            .finish()
+    }
+}
+
+impl SmtpConfig {
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| {
+            std::env::var(format!("RIPTIDE_SMTP_{}", name))
+                .ok()
+                .filter(|v| !v.is_empty())
+        };
+        let defaults = Self::default();
+        let tls = match var("TLS").as_deref() {
+            None => defaults.tls,
+            Some("starttls") => SmtpTls::Starttls,
+            Some("tls") => SmtpTls::Tls,
+            Some("none") => SmtpTls::None,
+            Some(other) => {
+                return Err(anyhow!(
+                    "Invalid RIPTIDE_SMTP_TLS '{}' (expected starttls, tls or none)",
+                    other
+                ))
+            }
+        };
+        let default_port = match tls {
+            SmtpTls::Tls => 465,
+            SmtpTls::Starttls => 587,
+            SmtpTls::None => 25,
+        };
+        Ok(Self {
+            host: var("HOST").unwrap_or(defaults.host),
+            port: var("PORT")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default_port),
+            tls,
+            username: var("USERNAME"),
+            password: var("PASSWORD"),
+            from: var("FROM").unwrap_or(defaults.from),
+            timeout_secs: var("TIMEOUT_SECS")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(defaults.timeout_secs),
+        })
+    }
+}
+
+#[cfg(feature = "email-reports")]
+pub use sender::SmtpEmailSender;
+
+#[cfg(feature = "email-reports")]
+mod sender {
+    use super::{SmtpConfig, SmtpTls};
+    use anyhow::{Context, Result};
+    use async_trait::async_trait;
+    use lettre::message::header::ContentType;
+    use lettre::message::{Attachment, Mailbox, MultiPart, SinglePart};
+    use lettre::transport::smtp::authentication::Credentials;
+    use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+    use riptide_types::ports::{EmailMessage, EmailSender};
+    use riptide_types::RiptideError;
+    use std::time::Duration;
+
+    /// [`EmailSender`] delivering through an SMTP relay
+    pub struct SmtpEmailSender {
+        transport: AsyncSmtpTransport<Tokio1Executor>,
+        from: Mailbox,
+    }
+
+    impl SmtpEmailSender {
+        pub fn new(config: &SmtpConfig) -> Result<Self> {
+            let builder = match config.tls {
+                SmtpTls::Starttls => {
+                    AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)?
+                }
+                SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)?,
+                SmtpTls::None => {
+                    AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)
+                }
+            };
+            let mut builder = builder
+                .port(config.port)
+                .timeout(Some(Duration::from_secs(config.timeout_secs)));
+            if let (Some(username), Some(password)) = (&config.username, &config.password) {
+                builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+            }
+
+            Ok(Self {
+                transport: builder.build(),
+                from: config
+                    .from
+                    .parse()
+                    .with_context(|| format!("Invalid RIPTIDE_SMTP_FROM '{}'", config.from))?,
+            })
+        }
+
+        fn build(&self, message: &EmailMessage) -> riptide_types::Result<Message> {
+            let mut builder = Message::builder()
+                .from(self.from.clone())
+                .subject(message.subject.clone());
+            for to in message.to.iter().filter(|to| !to.trim().is_empty()) {
+                let mailbox: Mailbox = to.parse().map_err(|e| {
+                    RiptideError::ValidationError(format!("Invalid recipient '{}': {}", to, e))
+                })?;
+                builder = builder.to(mailbox);
+            }
+
+            let text = SinglePart::plain(message.text_body.clone());
+            let mut body = match &message.html_body {
+                Some(html) => MultiPart::mixed().multipart(
+                    MultiPart::alternative()
+                        .singlepart(text)
+                        .singlepart(SinglePart::html(html.clone())),
+                ),
+                None => MultiPart::mixed().singlepart(text),
+            };
+            for attachment in &message.attachments {
+                let content_type = ContentType::parse(&attachment.content_type)
+                    .or_else(|_| ContentType::parse("application/octet-stream"))
+                    .map_err(|e| RiptideError::ValidationError(e.to_string()))?;
+                body = body.singlepart(
+                    Attachment::new(attachment.filename.clone())
+                        .body(attachment.data.clone(), content_type),
+                );
+            }
+
+            builder
+                .multipart(body)
+                .map_err(|e| RiptideError::ValidationError(format!("Invalid email: {}", e)))
+        }
+    }
+
+    #[async_trait]
+    impl EmailSender for SmtpEmailSender {
+        async fn send(&self, message: &EmailMessage) -> riptide_types::Result<()> {
+            message.validate()?;
+            let email = self.build(message)?;
+            self.transport
+                .send(email)
+                .await
+                .map_err(|e| RiptideError::Network(format!("SMTP delivery failed: {}", e)))?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_redacts_password() {
+        let config = SmtpConfig {
+            username: Some("reports".to_string()),
+            password: Some("hunter2".to_string()),
+            ..Default::default()
+        };
+        let debug = format!("{:?}", config);
+        assert!(debug.contains("reports"));
+        assert!(!debug.contains("hunter2"));
+    }
+}
@@ -0,0 +1,200 @@
+//! Raw HTML retention policy
+//!
+//! Crawl jobs keep the raw HTML of each page so it can be re-extracted later
+//! (see `ReprocessProcessor`). Keeping every body forever is expensive, so the
+//! retention job applies a tiered policy:
+//!
+//! 1. **Full window**: every snapshot younger than `full_retention_days` is kept
+//! 2. **Sampled window**: older snapshots are kept only if they failed
+//!    extraction, scored below `low_quality_threshold`, or fall in the
+//!    `sample_rate` sample
+//! 3. **Expiry**: snapshots older than `max_retention_days` are deleted
+//!
+//! Sampling is deterministic per URL, so repeated runs keep the same pages.
+
+use crate::processors::RawHtmlSnapshot;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Custom job name handled by `RawHtmlRetentionProcessor`
+pub const RAW_HTML_RETENTION_JOB: &str = "raw_html_retention";
+
+/// Hard upper bound on snapshot lifetime, also used as the snapshot TTL
+pub const MAX_RAW_HTML_RETENTION_DAYS: u32 = 90;
+
+/// Tiered retention policy for raw HTML snapshots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RawHtmlRetentionPolicy {
+    /// Keep every snapshot for this many days
+    pub full_retention_days: u32,
+    /// Fraction of pages (0.0 - 1.0) kept after the full window
+    pub sample_rate: f64,
+    /// Keep pages whose extraction failed
+    pub keep_failures: bool,
+    /// Keep pages whose quality score is below this value
+    pub low_quality_threshold: Option<u8>,
+    /// Delete every snapshot older than this many days
+    pub max_retention_days: u32,
+}
+
+impl Default for RawHtmlRetentionPolicy {
+    fn default() -> Self {
+        Self {
+            full_retention_days: 7,
+            sample_rate: 0.1,
+            keep_failures: true,
+            low_quality_threshold: Some(40),
+            max_retention_days: MAX_RAW_HTML_RETENTION_DAYS,
+        }
+    }
+}
+
+/// Outcome of applying the policy to one snapshot
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetentionDecision {
+    Keep,
+    Delete,
+}
+
+impl RawHtmlRetentionPolicy {
+    /// Check that the policy is internally consistent
+    pub fn validate(&self) -> Result<()> {
+        if !(0.0..=1.0).contains(&self.sample_rate) {
+            return Err(anyhow!(
+                "sample_rate must be between 0.0 and 1.0, got {}",
+                self.sample_rate
+            ));
+        }
+        if self.full_retention_days > self.max_retention_days {
+            return Err(anyhow!(
+                "full_retention_days ({}) exceeds max_retention_days ({})",
+                self.full_retention_days,
+                self.max_retention_days
+            ));
+        }
+        if self.max_retention_days > MAX_RAW_HTML_RETENTION_DAYS {
+            return Err(anyhow!(
+                "max_retention_days cannot exceed {} (snapshot TTL)",
+                MAX_RAW_HTML_RETENTION_DAYS
+            ));
+        }
+        Ok(())
+    }
+
+    /// Decide whether a snapshot should be kept at `now`
+    pub fn decide(&self, snapshot: &RawHtmlSnapshot, now: DateTime<Utc>) -> RetentionDecision {
+        let age = now - snapshot.fetched_at;
+        if age <= Duration::days(i64::from(self.full_retention_days)) {
+            return RetentionDecision::Keep;
+        }
+        if age > Duration::days(i64::from(self.max_retention_days)) {
+            return RetentionDecision::Delete;
+        }
+
+        let failed = self.keep_failures && snapshot.extraction_failed;
+        let low_quality = matches!(
+            (self.low_quality_threshold, snapshot.quality_score),
+            (Some(threshold), Some(score)) if score < threshold
+        );
+        if failed || low_quality || self.in_sample(&snapshot.url) {
+            RetentionDecision::Keep
+        } else {
+            RetentionDecision::Delete
+        }
+    }
+
+    /// Deterministic per-URL sampling
+    fn in_sample(&self, url: &str) -> bool {
+        let mut hasher = DefaultHasher::new();
+        url.hash(&mut hasher);
+        let bucket = hasher.finish() % 10_000;
+        (bucket as f64) < self.sample_rate * 10_000.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(url: &str, age_days: i64) -> RawHtmlSnapshot {
+        RawHtmlSnapshot {
+            url: url.to_string(),
+            status: 200,
+            html: "<html></html>".to_string(),
+            fetched_at: Utc::now() - Duration::days(age_days),
+            quality_score: Some(80),
+            extraction_failed: false,
+            result: None,
+        }
+    }
+
+    #[test]
+    fn test_full_window_and_expiry() {
+        let policy = RawHtmlRetentionPolicy::default();
+        let now = Utc::now();
+
+        assert_eq!(
+            policy.decide(&snapshot("https://example.com/", 1), now),
+            RetentionDecision::Keep
+        );
+        assert_eq!(
+            policy.decide(&snapshot("https://example.com/", 120), now),
+            RetentionDecision::Delete
+        );
+    }
+
+    #[test]
+    fn test_sampled_window_keeps_failures_and_low_quality() {
+        let policy = RawHtmlRetentionPolicy {
+            sample_rate: 0.0,
+            ..Default::default()
+        };
+        let now = Utc::now();
+
+        let plain = snapshot("https://example.com/plain", 30);
+        assert_eq!(policy.decide(&plain, now), RetentionDecision::Delete);
+
+        let mut failed = snapshot("https://example.com/failed", 30);
+        failed.extraction_failed = true;
+        assert_eq!(policy.decide(&failed, now), RetentionDecision::Keep);
+
+        let mut low = snapshot("https://example.com/low", 30);
+        low.quality_score = Some(10);
+        assert_eq!(policy.decide(&low, now), RetentionDecision::Keep);
+    }
+
+    #[test]
+    fn test_sample_rate_bounds() {
+        let now = Utc::now();
+        let keep_all = RawHtmlRetentionPolicy {
+            sample_rate: 1.0,
+            ..Default::default()
+        };
+        let kept = (0..100)
+            .filter(|i| {
+                keep_all.decide(&snapshot(&format!("https://example.com/{}", i), 30), now)
+                    == RetentionDecision::Keep
+            })
+            .count();
+        assert_eq!(kept, 100);
+
+        let invalid = RawHtmlRetentionPolicy {
+            sample_rate: 1.5,
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_policy_deserializes_with_defaults() {
+        let policy: RawHtmlRetentionPolicy =
+            serde_json::from_value(serde_json::json!({ "sample_rate": 0.25 })).unwrap();
+        assert_eq!(policy.sample_rate, 0.25);
+        assert_eq!(policy.full_retention_days, 7);
+        assert!(policy.validate().is_ok());
+    }
+}
@@ -1,15 +1,20 @@
+use crate::export::{ElasticsearchSink, ElasticsearchSinkConfig, ExportSink, PostgresSinkConfig};
 use crate::job::{Job, JobType};
 use crate::metrics::WorkerMetrics;
 use crate::processors::{
-    BatchCrawlProcessor, CustomJobProcessor, MaintenanceProcessor, SingleCrawlProcessor,
+    BatchCrawlProcessor, CustomJobProcessor, ExportProcessor, MaintenanceProcessor,
+    RawHtmlRetentionProcessor, ReprocessProcessor, SingleCrawlProcessor, DEFAULT_RESULT_TTL_SECS,
 };
 use crate::queue::{JobQueue, QueueConfig};
+use crate::reports::ReportsConfig;
 use crate::scheduler::{JobScheduler, ScheduledJob, SchedulerConfig};
 use crate::worker::{WorkerConfig, WorkerPool};
 use anyhow::{Context, Result};
 // use riptide_reliability::WasmExtractor;
 use riptide_cache::redis::CacheManager;
+use riptide_cache::RedisStorage;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 use tracing::{error, info, warn};
 use uuid::Uuid;
@@ -31,10 +36,18 @@ pub struct WorkerServiceConfig {
     pub max_batch_size: usize,
     /// Maximum concurrent requests within a batch
     pub max_concurrency: usize,
+    /// Lifetime of cached crawl results in seconds, from `WORKER_RESULT_TTL_SECS`
+    pub result_ttl_secs: u64,
     /// WASM extractor path
     pub wasm_path: String,
     /// Enable job scheduling
     pub enable_scheduler: bool,
+    /// Elasticsearch/OpenSearch export sink, from `RIPTIDE_EXPORT_ES_*`
+    pub elasticsearch_export: Option<ElasticsearchSinkConfig>,
+    /// PostgreSQL export sink, from `RIPTIDE_EXPORT_PG_*`
+    pub postgres_export: Option<PostgresSinkConfig>,
+    /// Scheduled report emails, from `RIPTIDE_REPORTS_FILE` and `RIPTIDE_SMTP_*`
+    pub email_reports: Option<ReportsConfig>,
 }
 
 impl Default for WorkerServiceConfig {
@@ -51,14 +64,33 @@ impl Default for WorkerServiceConfig {
             scheduler_config: SchedulerConfig::default(),
             max_batch_size: 50,
             max_concurrency: 10,
+            result_ttl_secs: result_ttl_from_env(),
             wasm_path: std::env::var("WASM_EXTRACTOR_PATH").unwrap_or_else(|_| {
                 "./target/wasm32-wasip2/release/riptide_extractor_wasm.wasm".to_string()
             }),
             enable_scheduler: true,
+            elasticsearch_export: ElasticsearchSinkConfig::from_env().unwrap_or_else(|e| {
+                warn!(error = %e, "Ignoring invalid Elasticsearch export configuration");
+                None
+            }),
+            postgres_export: PostgresSinkConfig::from_env(),
+            email_reports: ReportsConfig::from_env().unwrap_or_else(|e| {
+                warn!(error = %e, "Ignoring invalid report email configuration");
+                None
+            }),
         }
     }
 }
 
+/// `WORKER_RESULT_TTL_SECS`, or [`DEFAULT_RESULT_TTL_SECS`]
+pub fn result_ttl_from_env() -> u64 {
+    std::env::var("WORKER_RESULT_TTL_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .filter(|&secs| secs > 0)
+        .unwrap_or(DEFAULT_RESULT_TTL_SECS)
+}
+
 /// Main worker service that orchestrates job processing
 pub struct WorkerService {
     /// Service configuration
@@ -73,6 +105,8 @@ pub struct WorkerService {
     metrics: Arc<WorkerMetrics>,
     /// Service running state
     running: Arc<std::sync::atomic::AtomicBool>,
+    /// Set once a drain starts; new jobs are rejected from then on
+    draining: Arc<std::sync::atomic::AtomicBool>,
 }
 
 impl WorkerService {
@@ -103,6 +137,11 @@ impl WorkerService {
             None
         };
 
+        // Report emails go out on their tenants' cadence
+        if let Some(scheduler) = &scheduler {
+            Self::register_report_schedules(scheduler, config.email_reports.as_ref()).await?;
+        }
+
         // Initialize job processors
         info!("Initializing job processors");
         let processors = Self::create_job_processors_static(&config).await?;
@@ -124,6 +163,7 @@ impl WorkerService {
             scheduler,
             metrics,
             running: Arc::new(std::sync::atomic::AtomicBool::new(false)),
+            draining: Arc::new(std::sync::atomic::AtomicBool::new(false)),
         };
 
         // Auto-start the service
@@ -205,12 +245,72 @@ impl WorkerService {
         Ok(())
     }
 
+    /// Stop taking jobs and wait up to `budget` for in-flight jobs to finish
+    ///
+    /// The scheduler stops immediately and [`submit_job`](Self::submit_job)
+    /// fails from the moment the drain starts. Jobs still running when the
+    /// budget runs out are left to the queue's retry handling.
+    pub async fn drain(&self, budget: Duration) -> WorkerDrainReport {
+        let started = Instant::now();
+        self.draining
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+        let jobs_in_flight_at_start = self.jobs_in_flight();
+        info!(
+            jobs_in_flight = jobs_in_flight_at_start,
+            budget_ms = budget.as_millis() as u64,
+            "Draining worker service"
+        );
+
+        if let Some(scheduler) = &self.scheduler {
+            scheduler.stop().await;
+        }
+        let jobs_unfinished = match &self.worker_pool {
+            Some(worker_pool) => worker_pool.drain(budget).await,
+            None => 0,
+        };
+        self.running
+            .store(false, std::sync::atomic::Ordering::Relaxed);
+
+        let report = WorkerDrainReport {
+            jobs_in_flight_at_start,
+            jobs_unfinished,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+        };
+        if jobs_unfinished > 0 {
+            warn!(
+                jobs_unfinished,
+                elapsed_ms = report.elapsed_ms,
+                "Worker drain budget exhausted with jobs still running"
+            );
+        } else {
+            info!(elapsed_ms = report.elapsed_ms, "Worker service drained");
+        }
+        report
+    }
+
+    /// Whether a drain has started
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Number of jobs currently being processed by this service's workers
+    pub fn jobs_in_flight(&self) -> usize {
+        self.worker_pool
+            .as_ref()
+            .map_or(0, |pool| pool.jobs_in_flight())
+    }
+
     /// Submit a job to the queue
     pub async fn submit_job(&self, job: Job) -> Result<Uuid> {
+        if self.is_draining() {
+            anyhow::bail!("Worker service is draining and not accepting new jobs");
+        }
+
         let job_type_name = match &job.job_type {
             JobType::BatchCrawl { .. } => "BatchCrawl",
             JobType::SingleCrawl { .. } => "SingleCrawl",
             JobType::PdfExtraction { .. } => "PdfExtraction",
+            JobType::Reprocess { .. } => "Reprocess",
             JobType::Maintenance { .. } => "Maintenance",
             JobType::Custom { job_name, .. } => job_name,
         };
@@ -389,32 +489,124 @@ impl WorkerService {
             .await
             .context("Failed to initialize cache manager")?;
         let cache = Arc::new(tokio::sync::Mutex::new(cache_manager));
+        // Same Redis through the storage port, for processors sharing
+        // snapshots with the API pipeline
+        let storage = Arc::new(
+            RedisStorage::new(&config.redis_url)
+                .await
+                .context("Failed to initialize cache storage")?,
+        );
 
-        let processors: Vec<Arc<dyn crate::worker::JobProcessor>> = vec![
+        let mut processors: Vec<Arc<dyn crate::worker::JobProcessor>> = vec![
             // Batch crawl processor
-            Arc::new(BatchCrawlProcessor::new(
-                http_client.clone(),
-                extractor.clone(),
-                cache.clone(),
-                config.max_batch_size,
-                config.max_concurrency,
-            )),
+            Arc::new(
+                BatchCrawlProcessor::new(
+                    http_client.clone(),
+                    extractor.clone(),
+                    cache.clone(),
+                    config.max_batch_size,
+                    config.max_concurrency,
+                )
+                .with_result_ttl(Duration::from_secs(config.result_ttl_secs)),
+            ),
             // Single crawl processor
-            Arc::new(SingleCrawlProcessor::new(
-                http_client.clone(),
+            Arc::new(
+                SingleCrawlProcessor::new(http_client.clone(), extractor.clone(), cache.clone())
+                    .with_result_ttl(Duration::from_secs(config.result_ttl_secs)),
+            ),
+            // Re-extraction over cached HTML
+            Arc::new(ReprocessProcessor::new(
                 extractor.clone(),
-                cache.clone(),
+                storage.clone(),
+                config.max_batch_size,
             )),
+            // Raw HTML retention (custom job "raw_html_retention")
+            Arc::new(RawHtmlRetentionProcessor::new(cache.clone())),
             // Maintenance processor
             Arc::new(MaintenanceProcessor),
             // Custom job processor
             Arc::new(CustomJobProcessor),
         ];
 
+        // Export to external stores (custom job "export")
+        let mut sinks: Vec<Arc<dyn ExportSink>> = Vec::new();
+        if let Some(es_config) = &config.elasticsearch_export {
+            info!(endpoint = %es_config.endpoint, index = %es_config.index, "Elasticsearch export enabled");
+            sinks.push(Arc::new(ElasticsearchSink::new(es_config.clone())?));
+        }
+        if let Some(pg_config) = &config.postgres_export {
+            #[cfg(feature = "postgres-export")]
+            {
+                info!(table = %pg_config.documents_table, "PostgreSQL export enabled");
+                sinks.push(Arc::new(crate::export::PostgresSink::new(
+                    pg_config.clone(),
+                )?));
+            }
+            #[cfg(not(feature = "postgres-export"))]
+            warn!(
+                table = %pg_config.documents_table,
+                "PostgreSQL export configured but the 'postgres-export' feature is disabled"
+            );
+        }
+        if !sinks.is_empty() {
+            processors.push(Arc::new(ExportProcessor::new(storage.clone(), sinks)));
+        }
+
+        // Report emails (custom job "crawl_report")
+        if let Some(reports) = &config.email_reports {
+            #[cfg(feature = "email-reports")]
+            {
+                info!(
+                    tenants = reports.tenants.len(),
+                    smtp_host = %reports.smtp.host,
+                    "Report emails enabled"
+                );
+                let sender = Arc::new(crate::reports::SmtpEmailSender::new(&reports.smtp)?);
+                processors.push(Arc::new(crate::processors::ReportProcessor::new(
+                    cache.clone(),
+                    storage.clone(),
+                    sender,
+                    reports.clone(),
+                )));
+            }
+            #[cfg(not(feature = "email-reports"))]
+            warn!(
+                tenants = reports.tenants.len(),
+                "Report emails configured but the 'email-reports' feature is disabled"
+            );
+        }
+
         info!("Initialized {} job processors", processors.len());
         Ok(processors)
     }
 
+    /// Add a scheduled job per tenant with a report `schedule`, replacing
+    /// the ones persisted by an earlier start so config changes take effect
+    /// and tenants removed from the file stop getting reports
+    async fn register_report_schedules(
+        scheduler: &JobScheduler,
+        reports: Option<&ReportsConfig>,
+    ) -> Result<()> {
+        let scheduled_jobs = match reports {
+            Some(reports) if cfg!(feature = "email-reports") => reports.scheduled_jobs()?,
+            _ => Vec::new(),
+        };
+        for existing in scheduler.list_scheduled_jobs() {
+            if crate::reports::is_report_schedule(&existing) {
+                scheduler.remove_scheduled_job(existing.id).await?;
+            }
+        }
+        for scheduled_job in scheduled_jobs {
+            info!(
+                name = %scheduled_job.name,
+                cron_expression = %scheduled_job.cron_expression,
+                "Scheduling report emails"
+            );
+            scheduler.add_scheduled_job(scheduled_job).await?;
+        }
+        Ok(())
+    }
+
     /// Create job processors (instance method for backward compatibility)
     #[allow(dead_code)]
     async fn create_job_processors(&self) -> Result<Vec<Arc<dyn crate::worker::JobProcessor>>> {
@@ -535,6 +727,17 @@ pub struct WorkerServiceHealth {
     pub metrics_snapshot: crate::metrics::WorkerMetricsSnapshot,
 }
 
+/// Outcome of draining the worker service
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct WorkerDrainReport {
+    /// Jobs being processed when the drain started
+    pub jobs_in_flight_at_start: usize,
+    /// Jobs still running when the budget ran out
+    pub jobs_unfinished: usize,
+    /// Time spent draining in milliseconds
+    pub elapsed_ms: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
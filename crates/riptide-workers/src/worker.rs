@@ -6,6 +6,8 @@ use async_trait::async_trait;
 use chrono::Utc;
 use dashmap::DashMap;
 use futures::future::join_all;
+use riptide_pdf::PdfError;
+use riptide_types::{ErrorCode, HasErrorCode};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -14,6 +16,9 @@ use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+/// How often a draining pool checks whether in-flight jobs have finished
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Trait for processing different types of jobs
 #[async_trait]
 pub trait JobProcessor: Send + Sync {
@@ -302,6 +307,7 @@ impl Worker {
                 JobType::BatchCrawl { .. } => "BatchCrawl",
                 JobType::SingleCrawl { .. } => "SingleCrawl",
                 JobType::PdfExtraction { .. } => "PdfExtraction",
+                JobType::Reprocess { .. } => "Reprocess",
                 JobType::Maintenance { .. } => "Maintenance",
                 JobType::Custom { job_name, .. } => job_name.as_str(),
             };
@@ -354,6 +360,7 @@ impl Worker {
             JobType::BatchCrawl { .. } => "BatchCrawl",
             JobType::SingleCrawl { .. } => "SingleCrawl",
             JobType::PdfExtraction { .. } => "PdfExtraction",
+            JobType::Reprocess { .. } => "Reprocess",
             JobType::Maintenance { .. } => "Maintenance",
             JobType::Custom { job_name, .. } => job_name,
         };
@@ -391,12 +398,10 @@ impl Worker {
             }
             Ok(Err(e)) => {
                 let processing_time = job.processing_time_ms().unwrap_or(0);
-                Ok(JobResult::failure(
-                    job.id,
-                    self.id.clone(),
-                    e.to_string(),
-                    processing_time,
-                ))
+                Ok(
+                    JobResult::failure(job.id, self.id.clone(), e.to_string(), processing_time)
+                        .with_error_code(job_error_code(&e)),
+                )
             }
             Err(_) => {
                 let error = format!(
@@ -406,12 +411,10 @@ impl Worker {
                 let processing_time = job
                     .processing_time_ms()
                     .unwrap_or(self.config.job_timeout_secs * 1000);
-                Ok(JobResult::failure(
-                    job.id,
-                    self.id.clone(),
-                    error,
-                    processing_time,
-                ))
+                Ok(
+                    JobResult::failure(job.id, self.id.clone(), error, processing_time)
+                        .with_error_code(ErrorCode::Timeout),
+                )
             }
         }
     }
@@ -439,6 +442,14 @@ impl Worker {
     }
 }
 
+/// Error code recorded on a failed job result
+fn job_error_code(error: &anyhow::Error) -> ErrorCode {
+    error
+        .chain()
+        .find_map(|err| err.downcast_ref::<PdfError>())
+        .map_or_else(|| error.error_code(), HasErrorCode::error_code)
+}
+
 /// Snapshot of worker statistics
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct WorkerStatsSnapshot {
@@ -544,6 +555,46 @@ impl WorkerPool {
         self.workers.clear();
     }
 
+    /// Number of jobs the workers are currently processing
+    pub fn jobs_in_flight(&self) -> usize {
+        self.workers
+            .iter()
+            .filter(|worker_ref| worker_ref.value().stats.current_job.read().is_some())
+            .count()
+    }
+
+    /// Stop workers from taking new jobs and wait up to `budget` for the
+    /// jobs they are processing to finish
+    ///
+    /// Returns the number of jobs still running when the budget ran out.
+    pub async fn drain(&self, budget: Duration) -> usize {
+        info!(
+            jobs_in_flight = self.jobs_in_flight(),
+            budget_ms = budget.as_millis() as u64,
+            "Draining worker pool"
+        );
+        self.running.store(false, Ordering::Relaxed);
+
+        // Workers finish their current job before checking the flag again
+        for worker_ref in self.workers.iter() {
+            worker_ref.value().stop().await;
+        }
+
+        let deadline = Instant::now() + budget;
+        let mut remaining = self.jobs_in_flight();
+        while remaining > 0 {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                break;
+            }
+            sleep(left.min(DRAIN_POLL_INTERVAL)).await;
+            remaining = self.jobs_in_flight();
+        }
+
+        self.workers.clear();
+        remaining
+    }
+
     /// Get pool statistics
     pub fn get_pool_stats(&self) -> WorkerPoolStats {
         let mut worker_stats = Vec::new();
@@ -600,4 +651,13 @@ mod tests {
         assert_eq!(stats.jobs_processed.load(Ordering::Relaxed), 0);
         assert_eq!(stats.jobs_failed.load(Ordering::Relaxed), 0);
     }
+
+    #[test]
+    fn test_job_error_code() {
+        let pdf = anyhow::Error::new(PdfError::EncryptedPdf).context("PDF job failed");
+        assert_eq!(job_error_code(&pdf), PdfError::EncryptedPdf.error_code());
+
+        let other = anyhow::anyhow!("something broke");
+        assert_eq!(job_error_code(&other), ErrorCode::Internal);
+    }
 }
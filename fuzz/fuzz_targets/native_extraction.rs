@@ -0,0 +1,23 @@
+//! Coverage-guided fuzzing of the native extractors
+//!
+//! ```text
+//! cargo +nightly fuzz run native_extraction -- -timeout=10 -rss_limit_mb=512 -max_len=4194304
+//! ```
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use riptide_extraction::{css_extraction, fallback_extract, regex_extraction, NativeHtmlParser};
+
+const URL: &str = "https://fuzz.example/page";
+
+fuzz_target!(|data: &[u8]| {
+    let html = String::from_utf8_lossy(data);
+
+    let _ = NativeHtmlParser::new().parse_headless_html(&html, URL);
+    futures::executor::block_on(async {
+        let _ = css_extraction::extract_default(&html, URL).await;
+        let _ = regex_extraction::extract_default(&html, URL).await;
+        let _ = fallback_extract(&html, URL).await;
+    });
+});
@@ -0,0 +1,40 @@
+//! Coverage-guided fuzzing of the WASM extractor's guest code, run natively
+//!
+//! The first byte selects the extraction mode; for custom mode the input is
+//! `selector\0selector\0...\x01html`.
+//!
+//! ```text
+//! cargo +nightly fuzz run wasm_extraction -- -timeout=10 -rss_limit_mb=512 -max_len=4194304
+//! ```
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use riptide_extractor_wasm::{Component, ExtractionMode};
+
+const URL: &str = "https://fuzz.example/page";
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&selector, rest)) = data.split_first() else {
+        return;
+    };
+    let (mode, html) = match selector % 4 {
+        0 => (ExtractionMode::Article, rest),
+        1 => (ExtractionMode::Full, rest),
+        2 => (ExtractionMode::Metadata, rest),
+        _ => {
+            let split = rest.iter().position(|&b| b == 1).unwrap_or(rest.len());
+            let selectors = rest[..split]
+                .split(|&b| b == 0)
+                .map(|s| String::from_utf8_lossy(s).into_owned())
+                .collect();
+            (
+                ExtractionMode::Custom(selectors),
+                rest.get(split + 1..).unwrap_or_default(),
+            )
+        }
+    };
+
+    let html = String::from_utf8_lossy(html).into_owned();
+    let _ = Component::new().extract(html, URL.to_string(), mode);
+});
@@ -10,6 +10,9 @@
 //!
 //! Duration: 6 days
 //! Dependencies: Phase 4 load testing (10k+ sessions), Phase 5 integration complete
+//!
+//! Timeout tests run with paused tokio time, so their sleeps advance a
+//! virtual clock instead of waiting (see `riptide_test_utils::simulation`).
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -20,7 +23,7 @@ mod network_failure_injection {
     use super::*;
 
     /// Test HTTP timeout scenarios with varying timeout durations
-    #[tokio::test]
+    #[tokio::test(start_paused = true)]
     async fn test_http_timeout_injection() {
         // Simulate various timeout scenarios
         let timeout_scenarios = vec![
@@ -380,7 +383,7 @@ mod browser_pool_chaos_tests {
     }
 
     /// Test browser hang detection and timeout
-    #[tokio::test]
+    #[tokio::test(start_paused = true)]
     async fn test_browser_hang_detection() {
         let hang_timeout = Duration::from_secs(5);
 
@@ -462,7 +465,7 @@ mod extraction_pipeline_chaos_tests {
     }
 
     /// Test extraction pipeline timeout handling
-    #[tokio::test]
+    #[tokio::test(start_paused = true)]
     async fn test_extraction_pipeline_timeout() {
         let pipeline_timeout = Duration::from_secs(5);
 
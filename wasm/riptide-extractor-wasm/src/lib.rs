@@ -13,6 +13,9 @@ mod utf8_utils;
 // Extraction module with comprehensive link, media, language, and category extraction
 mod extraction;
 
+// HTML to Markdown rendering of the extracted content region
+mod markdown;
+
 // Generate bindings from enhanced WIT file
 wit_bindgen::generate!({
     world: "extractor",
@@ -92,6 +95,17 @@ impl Component {
         self.extract_with_stats_internal(html, url, mode)
     }
 
+    /// Render the content region selected by `mode` as Markdown
+    pub fn to_markdown(
+        &self,
+        html: String,
+        url: String,
+        mode: ExtractionMode,
+        flavor: MarkdownFlavor,
+    ) -> Result<String, ExtractionError> {
+        self.to_markdown_internal(html, url, mode, flavor)
+    }
+
     /// Validate HTML content without full extraction
     pub fn validate_html(&self, html: String) -> Result<bool, ExtractionError> {
         self.validate_html_internal(html)
@@ -143,6 +157,24 @@ impl Component {
         perform_enhanced_extraction(&html, &url, &mode)
     }
 
+    /// Internal Markdown rendering function
+    fn to_markdown_internal(
+        &self,
+        html: String,
+        url: String,
+        mode: ExtractionMode,
+        flavor: MarkdownFlavor,
+    ) -> Result<String, ExtractionError> {
+        validate_extraction_input(&html, &url)?;
+        validate_content_size(html.len())?;
+        validate_extraction_mode(&mode)?;
+
+        let document = tl::parse(&html, tl::ParserOptions::default()).map_err(|_| {
+            ExtractionError::ParseError("Failed to parse HTML document".to_string())
+        })?;
+        Ok(render_markdown(&document, &url, &mode, flavor))
+    }
+
     /// Extract content with detailed performance statistics
     fn extract_with_stats_internal(
         &self,
@@ -212,6 +244,7 @@ impl Component {
                 "language-detection".to_string(),
                 "category-extraction".to_string(),
                 "url-resolution".to_string(),
+                "markdown-conversion".to_string(),
             ],
             supported_modes: get_supported_modes(),
             build_timestamp: Some(get_build_timestamp().to_string()),
@@ -268,6 +301,17 @@ impl Guest for Component {
         component.extract_with_stats_internal(html, url, mode)
     }
 
+    /// Render the content region selected by `mode` as Markdown
+    fn to_markdown(
+        html: String,
+        url: String,
+        mode: ExtractionMode,
+        flavor: MarkdownFlavor,
+    ) -> Result<String, ExtractionError> {
+        let component = Component::new();
+        component.to_markdown_internal(html, url, mode, flavor)
+    }
+
     /// Validate HTML content without full extraction
     fn validate_html(html: String) -> Result<bool, ExtractionError> {
         let component = Component::new();
@@ -356,7 +400,7 @@ fn perform_extraction_with_scraper(
         title,
         byline,
         published_iso: published,
-        markdown: String::new(), // Markdown conversion can be added later if needed
+        markdown: render_markdown(&document, url, mode, MarkdownFlavor::Gfm),
         text,
         links: vec![],  // Will be populated by enhanced extraction
         media: vec![],  // Will be populated by enhanced extraction
@@ -475,28 +519,26 @@ fn extract_meta_content(
     None
 }
 
-/// Extract article content using common article selectors
-fn extract_article_content(document: &tl::VDom, parser: &tl::Parser) -> String {
-    // Try common article content selectors
-    let selectors = [
-        "article",
-        "main",
-        "[role='main']",
-        ".article-content",
-        ".post-content",
-        ".entry-content",
-        "#content",
-    ];
-
-    for selector_str in &selectors {
+/// Common article content selectors, in order of preference
+const ARTICLE_SELECTORS: [&str; 7] = [
+    "article",
+    "main",
+    "[role='main']",
+    ".article-content",
+    ".post-content",
+    ".entry-content",
+    "#content",
+];
+
+/// First article container holding more than 200 characters of text
+fn find_article_root(document: &tl::VDom, parser: &tl::Parser) -> Option<tl::NodeHandle> {
+    for selector_str in &ARTICLE_SELECTORS {
         if let Some(nodes) = document.query_selector(selector_str) {
             if let Some(node_handle) = nodes.into_iter().next() {
                 if let Some(node) = node_handle.get(parser) {
                     if let Some(tag) = node.as_tag() {
-                        let text = tag.inner_text(parser);
-                        let trimmed = text.trim();
-                        if trimmed.len() > 200 {
-                            return trimmed.to_string();
+                        if tag.inner_text(parser).trim().len() > 200 {
+                            return Some(node_handle);
                         }
                     }
                 }
@@ -504,24 +546,60 @@ fn extract_article_content(document: &tl::VDom, parser: &tl::Parser) -> String {
         }
     }
 
-    // Fallback to body
-    extract_full_content(document, parser)
+    None
+}
+
+/// The `<body>` element
+fn find_body(document: &tl::VDom) -> Option<tl::NodeHandle> {
+    document
+        .query_selector("body")
+        .and_then(|mut nodes| nodes.next())
+}
+
+/// Text of the node behind `handle`
+fn node_text(handle: tl::NodeHandle, parser: &tl::Parser) -> Option<String> {
+    let tag = handle.get(parser)?.as_tag()?;
+    Some(tag.inner_text(parser).trim().to_string())
+}
+
+/// Extract article content using common article selectors
+fn extract_article_content(document: &tl::VDom, parser: &tl::Parser) -> String {
+    match find_article_root(document, parser).and_then(|root| node_text(root, parser)) {
+        Some(text) => text,
+        // Fallback to body
+        None => extract_full_content(document, parser),
+    }
 }
 
 /// Extract full page content
 fn extract_full_content(document: &tl::VDom, parser: &tl::Parser) -> String {
-    if let Some(nodes) = document.query_selector("body") {
-        if let Some(node_handle) = nodes.into_iter().next() {
-            if let Some(node) = node_handle.get(parser) {
-                if let Some(tag) = node.as_tag() {
-                    let text = tag.inner_text(parser);
-                    return text.trim().to_string();
-                }
-            }
-        }
-    }
+    find_body(document)
+        .and_then(|body| node_text(body, parser))
+        .unwrap_or_default()
+}
 
-    String::new()
+/// Render the content region `mode` extracts text from as Markdown
+fn render_markdown(
+    document: &tl::VDom,
+    url: &str,
+    mode: &ExtractionMode,
+    flavor: MarkdownFlavor,
+) -> String {
+    let parser = document.parser();
+    let roots: Vec<tl::NodeHandle> = match mode {
+        ExtractionMode::Article => find_article_root(document, parser)
+            .or_else(|| find_body(document))
+            .into_iter()
+            .collect(),
+        ExtractionMode::Full => find_body(document).into_iter().collect(),
+        ExtractionMode::Metadata => Vec::new(),
+        ExtractionMode::Custom(selectors) => selectors
+            .iter()
+            .filter_map(|selector| document.query_selector(selector))
+            .flatten()
+            .collect(),
+    };
+    markdown::MarkdownRenderer::new(parser, flavor, url).render(&roots)
 }
 
 /// Extract content using custom CSS selectors
@@ -0,0 +1,821 @@
+//! HTML to Markdown rendering on the `tl` DOM
+//!
+//! Port of the host's `riptide_extraction::markdown` converter, following the
+//! same rules so the WASM and native extraction paths agree: headings,
+//! paragraphs, emphasis, links and images, nested lists, block quotes, fenced
+//! code blocks and tables. GFM adds pipe tables, strikethrough and task lists;
+//! CommonMark keeps tables as raw HTML blocks, rebuilt from the rows with the
+//! cell text escaped.
+
+use crate::utf8_utils::get_attr_string;
+use crate::MarkdownFlavor;
+use std::borrow::Cow;
+use url::Url;
+
+/// Elements dropped together with their content
+const SKIPPED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "head", "iframe", "object", "embed", "svg",
+    "canvas", "nav", "button", "select", "textarea",
+];
+
+/// Elements rendered as blocks of their own
+const BLOCK_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "body",
+    "center",
+    "dd",
+    "details",
+    "dialog",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hgroup",
+    "hr",
+    "html",
+    "li",
+    "main",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "summary",
+    "table",
+    "ul",
+];
+
+/// Stands in for `<br>` until a paragraph is assembled
+const LINE_BREAK: char = '\0';
+
+/// A rendered block; lists nest tightly inside list items
+struct Block {
+    text: String,
+    list: bool,
+}
+
+/// Renders parts of a parsed document as Markdown
+pub struct MarkdownRenderer<'p, 'a> {
+    parser: &'p tl::Parser<'a>,
+    flavor: MarkdownFlavor,
+    base_url: Option<Url>,
+}
+
+impl<'p, 'a> MarkdownRenderer<'p, 'a> {
+    /// Relative link and image URLs are resolved against `base_url`
+    pub fn new(parser: &'p tl::Parser<'a>, flavor: MarkdownFlavor, base_url: &str) -> Self {
+        Self {
+            parser,
+            flavor,
+            base_url: Url::parse(base_url).ok(),
+        }
+    }
+
+    /// Render the nodes behind `roots`, e.g. the article container
+    pub fn render(&self, roots: &[tl::NodeHandle]) -> String {
+        roots
+            .iter()
+            .filter_map(|handle| handle.get(self.parser))
+            .map(|node| self.node(node))
+            .filter(|markdown| !markdown.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    fn node(&self, node: &tl::Node) -> String {
+        match node {
+            tl::Node::Tag(tag) => {
+                let name = tag_name(tag);
+                if self.is_skipped(tag, &name) {
+                    return String::new();
+                }
+                let blocks = if BLOCK_TAGS.contains(&name.as_str()) {
+                    self.block(tag, &name)
+                } else {
+                    paragraph(&self.inline(tag, &name, false))
+                        .into_iter()
+                        .collect()
+                };
+                join_blocks(blocks)
+            }
+            tl::Node::Raw(text) => paragraph(&self.text(&text.as_utf8_str()))
+                .map(|block| block.text)
+                .unwrap_or_default(),
+            tl::Node::Comment(_) => String::new(),
+        }
+    }
+
+    fn children(&self, tag: &tl::HTMLTag) -> Vec<&'p tl::Node<'a>> {
+        tag.children()
+            .top()
+            .iter()
+            .filter_map(|handle| handle.get(self.parser))
+            .collect()
+    }
+
+    fn blocks(&self, tag: &tl::HTMLTag) -> Vec<Block> {
+        let in_list_item = tag_name(tag) == "li";
+        let mut blocks = Vec::new();
+        let mut inline = String::new();
+        for child in self.children(tag) {
+            match child {
+                tl::Node::Raw(text) => inline.push_str(&self.text(&text.as_utf8_str())),
+                tl::Node::Tag(child) => {
+                    let name = tag_name(child);
+                    if self.is_skipped(child, &name) {
+                        continue;
+                    }
+                    if BLOCK_TAGS.contains(&name.as_str()) {
+                        blocks.extend(paragraph(&inline));
+                        inline.clear();
+                        blocks.extend(self.block(child, &name));
+                    } else {
+                        inline.push_str(&self.inline(child, &name, in_list_item));
+                    }
+                }
+                tl::Node::Comment(_) => {}
+            }
+        }
+        blocks.extend(paragraph(&inline));
+        blocks
+    }
+
+    fn block(&self, tag: &tl::HTMLTag, name: &str) -> Vec<Block> {
+        let text = match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let content = clean_inline(&self.inline_children(tag), " ");
+                if content.is_empty() {
+                    return Vec::new();
+                }
+                let level = name[1..].parse::<usize>().unwrap_or(1);
+                format!("{} {}", "#".repeat(level), content)
+            }
+            "p" | "dt" | "figcaption" | "summary" => {
+                return paragraph(&self.inline_children(tag)).into_iter().collect();
+            }
+            "hr" => "---".to_string(),
+            "pre" => self.code_block(tag),
+            "blockquote" => {
+                let inner = join_blocks(self.blocks(tag));
+                inner
+                    .lines()
+                    .map(|line| {
+                        if line.is_empty() {
+                            ">".to_string()
+                        } else {
+                            format!("> {}", line)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            "ul" | "ol" => return self.list(tag, name).into_iter().collect(),
+            "table" => self.table(tag),
+            _ => return self.blocks(tag),
+        };
+        if text.is_empty() {
+            Vec::new()
+        } else {
+            vec![Block { text, list: false }]
+        }
+    }
+
+    fn inline(&self, tag: &tl::HTMLTag, name: &str, in_list_item: bool) -> String {
+        match name {
+            "br" => LINE_BREAK.to_string(),
+            "strong" | "b" => wrap(&self.inline_children(tag), "**"),
+            "em" | "i" | "cite" | "dfn" => wrap(&self.inline_children(tag), "*"),
+            "del" | "s" | "strike" if self.flavor == MarkdownFlavor::Gfm => {
+                wrap(&self.inline_children(tag), "~~")
+            }
+            "code" | "kbd" | "samp" | "tt" => {
+                code_span(&decode_entities(&tag.inner_text(self.parser)))
+            }
+            "a" => self.link(tag),
+            "img" => self.image(tag),
+            "input" => self.checkbox(tag, in_list_item),
+            _ => self.inline_children(tag),
+        }
+    }
+
+    fn inline_children(&self, tag: &tl::HTMLTag) -> String {
+        let in_list_item = tag_name(tag) == "li";
+        let mut out = String::new();
+        for child in self.children(tag) {
+            match child {
+                tl::Node::Raw(text) => out.push_str(&self.text(&text.as_utf8_str())),
+                tl::Node::Tag(child) => {
+                    let name = tag_name(child);
+                    if self.is_skipped(child, &name) {
+                        continue;
+                    }
+                    // Blocks inside inline content are flattened into it
+                    if BLOCK_TAGS.contains(&name.as_str()) {
+                        out.push(' ');
+                        out.push_str(&self.inline(child, &name, in_list_item));
+                        out.push(' ');
+                    } else {
+                        out.push_str(&self.inline(child, &name, in_list_item));
+                    }
+                }
+                tl::Node::Comment(_) => {}
+            }
+        }
+        out
+    }
+
+    /// Decode entities, collapse whitespace like a browser and escape Markdown syntax
+    fn text(&self, raw: &str) -> String {
+        let text = decode_entities(raw);
+        let mut out = String::with_capacity(text.len());
+        let mut space = false;
+        for c in text.chars() {
+            if c.is_whitespace() {
+                space = true;
+                continue;
+            }
+            if space {
+                out.push(' ');
+                space = false;
+            }
+            if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '<')
+                || (c == '~' && self.flavor == MarkdownFlavor::Gfm)
+            {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+        if space {
+            out.push(' ');
+        }
+        out
+    }
+
+    fn link(&self, tag: &tl::HTMLTag) -> String {
+        let text = self.inline_children(tag);
+        let href = attr(tag, "href").unwrap_or_default();
+        let href = href.trim();
+        if href.is_empty() || href.to_ascii_lowercase().starts_with("javascript:") {
+            return text;
+        }
+        let href = self.resolve(href);
+        if text.trim().is_empty() {
+            return if href.starts_with("http://") || href.starts_with("https://") {
+                format!("<{}>", href)
+            } else {
+                text
+            };
+        }
+        let title = attr(tag, "title")
+            .map(|title| format!(" \"{}\"", title.trim().replace('"', "\\\"")))
+            .unwrap_or_default();
+        let link = format!(
+            "[{}]({}{})",
+            clean_inline(&text, " "),
+            destination(&href),
+            title
+        );
+        surround_like(&text, link)
+    }
+
+    fn image(&self, tag: &tl::HTMLTag) -> String {
+        let src = attr(tag, "src")
+            .filter(|src| !src.trim().is_empty())
+            .or_else(|| attr(tag, "data-src"))
+            .unwrap_or_default();
+        let src = src.trim();
+        // Inline data URIs would bloat the document with base64
+        if src.is_empty() || src.starts_with("data:") {
+            return String::new();
+        }
+        let alt = self.text(&attr(tag, "alt").unwrap_or_default());
+        let title = attr(tag, "title")
+            .filter(|title| !title.trim().is_empty())
+            .map(|title| format!(" \"{}\"", title.trim().replace('"', "\\\"")))
+            .unwrap_or_default();
+        format!(
+            "![{}]({}{})",
+            alt.trim(),
+            destination(&self.resolve(src)),
+            title
+        )
+    }
+
+    /// Task list marker for a checkbox at the start of a list item
+    fn checkbox(&self, tag: &tl::HTMLTag, in_list_item: bool) -> String {
+        let is_checkbox =
+            attr(tag, "type").is_some_and(|kind| kind.eq_ignore_ascii_case("checkbox"));
+        if self.flavor != MarkdownFlavor::Gfm || !in_list_item || !is_checkbox {
+            return String::new();
+        }
+        if tag.attributes().get("checked").is_some() {
+            "[x] ".to_string()
+        } else {
+            "[ ] ".to_string()
+        }
+    }
+
+    fn code_block(&self, tag: &tl::HTMLTag) -> String {
+        let code = decode_entities(&tag.inner_text(self.parser)).into_owned();
+        // Browsers drop a newline right after <pre>
+        let code = code
+            .strip_prefix("\r\n")
+            .or_else(|| code.strip_prefix('\n'))
+            .unwrap_or(&code)
+            .trim_end();
+        if code.trim().is_empty() {
+            return String::new();
+        }
+        let code_children = self.children(tag).into_iter().filter_map(|child| {
+            child
+                .as_tag()
+                .filter(|child| tag_name(child) == "code")
+                .and_then(|child| attr(child, "class"))
+        });
+        let language = attr(tag, "class")
+            .into_iter()
+            .chain(code_children)
+            .find_map(|classes| {
+                classes.split_whitespace().find_map(|class| {
+                    class
+                        .strip_prefix("language-")
+                        .or_else(|| class.strip_prefix("lang-"))
+                        .map(str::to_string)
+                })
+            })
+            .unwrap_or_default();
+        let fence = "`".repeat(longest_run(code, '`').max(2) + 1);
+        format!("{}{}\n{}\n{}", fence, language, code, fence)
+    }
+
+    fn list(&self, tag: &tl::HTMLTag, name: &str) -> Option<Block> {
+        let ordered = name == "ol";
+        let mut number = attr(tag, "start")
+            .and_then(|start| start.trim().parse::<u64>().ok())
+            .unwrap_or(1);
+        let mut items: Vec<(String, String)> = Vec::new();
+        for child in self.children(tag).into_iter().filter_map(tl::Node::as_tag) {
+            match tag_name(child).as_str() {
+                "li" => {
+                    let marker = if ordered {
+                        number += 1;
+                        format!("{}.", number - 1)
+                    } else {
+                        "-".to_string()
+                    };
+                    items.push((marker, join_list_item(self.blocks(child))));
+                }
+                // A list directly inside a list belongs to the previous item
+                nested @ ("ul" | "ol") => {
+                    if let (Some(nested), Some((_, content))) =
+                        (self.list(child, nested), items.last_mut())
+                    {
+                        content.push('\n');
+                        content.push_str(&nested.text);
+                    }
+                }
+                _ => {}
+            }
+        }
+        if items.is_empty() {
+            return None;
+        }
+
+        let text = items
+            .iter()
+            .map(|(marker, content)| {
+                let indent = " ".repeat(marker.len() + 1);
+                let mut lines = content.lines();
+                let mut item = format!("{} {}", marker, lines.next().unwrap_or(""))
+                    .trim_end()
+                    .to_string();
+                for line in lines {
+                    item.push('\n');
+                    if !line.is_empty() {
+                        item.push_str(&indent);
+                        item.push_str(line);
+                    }
+                }
+                item
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some(Block { text, list: true })
+    }
+
+    fn table(&self, tag: &tl::HTMLTag) -> String {
+        if self.flavor == MarkdownFlavor::Commonmark {
+            return self.html_table(tag);
+        }
+
+        let mut caption = String::new();
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        for child in self.children(tag).into_iter().filter_map(tl::Node::as_tag) {
+            match tag_name(child).as_str() {
+                "caption" => caption = clean_inline(&self.inline_children(child), " "),
+                "thead" | "tbody" | "tfoot" => rows.extend(
+                    self.children(child)
+                        .into_iter()
+                        .filter_map(tl::Node::as_tag)
+                        .filter(|row| tag_name(row) == "tr")
+                        .map(|row| self.table_row(row)),
+                ),
+                "tr" => rows.push(self.table_row(child)),
+                _ => {}
+            }
+        }
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if width == 0 {
+            return caption;
+        }
+
+        let line = |cells: &[String]| {
+            let cells: Vec<&str> = (0..width)
+                .map(|i| cells.get(i).map(String::as_str).unwrap_or(""))
+                .collect();
+            format!("| {} |", cells.join(" | "))
+        };
+        let mut lines = vec![line(&rows[0]), line(&vec!["---".to_string(); width])];
+        lines.extend(rows[1..].iter().map(|row| line(row)));
+        let table = lines.join("\n");
+        if caption.is_empty() {
+            table
+        } else {
+            format!("{}\n\n{}", caption, table)
+        }
+    }
+
+    fn table_row(&self, row: &tl::HTMLTag) -> Vec<String> {
+        let mut cells = Vec::new();
+        for cell in self
+            .children(row)
+            .into_iter()
+            .filter_map(tl::Node::as_tag)
+            .filter(|cell| matches!(tag_name(cell).as_str(), "td" | "th"))
+        {
+            cells.push(clean_inline(&self.inline_children(cell), " ").replace('|', "\\|"));
+            let span = attr(cell, "colspan")
+                .and_then(|span| span.trim().parse::<usize>().ok())
+                .unwrap_or(1);
+            cells.resize(cells.len() + span.clamp(1, 100) - 1, String::new());
+        }
+        cells
+    }
+
+    /// A table as a raw HTML block of its rows and escaped cell text, one
+    /// line per row since a blank line would end the block early
+    fn html_table(&self, tag: &tl::HTMLTag) -> String {
+        let mut lines = vec!["<table>".to_string()];
+        let mut rows = Vec::new();
+        for child in self.children(tag).into_iter().filter_map(tl::Node::as_tag) {
+            match tag_name(child).as_str() {
+                "caption" => lines.push(format!("<caption>{}</caption>", self.cell_text(child))),
+                "thead" | "tbody" | "tfoot" => rows.extend(
+                    self.children(child)
+                        .into_iter()
+                        .filter_map(tl::Node::as_tag)
+                        .filter(|row| tag_name(row) == "tr"),
+                ),
+                "tr" => rows.push(child),
+                _ => {}
+            }
+        }
+        for row in rows {
+            let mut line = "<tr>".to_string();
+            for cell in self.children(row).into_iter().filter_map(tl::Node::as_tag) {
+                let name = tag_name(cell);
+                if !matches!(name.as_str(), "td" | "th") {
+                    continue;
+                }
+                line.push('<');
+                line.push_str(&name);
+                for span_name in ["colspan", "rowspan"] {
+                    if let Some(span) =
+                        attr(cell, span_name).and_then(|span| span.trim().parse::<usize>().ok())
+                    {
+                        line.push_str(&format!(" {}=\"{}\"", span_name, span.clamp(1, 100)));
+                    }
+                }
+                line.push_str(&format!(">{}</{}>", self.cell_text(cell), name));
+            }
+            line.push_str("</tr>");
+            lines.push(line);
+        }
+        lines.push("</table>".to_string());
+        lines.join("\n")
+    }
+
+    /// Visible text of a table cell, whitespace collapsed and HTML-escaped
+    fn cell_text(&self, tag: &tl::HTMLTag) -> String {
+        fn collect(renderer: &MarkdownRenderer<'_, '_>, tag: &tl::HTMLTag, out: &mut String) {
+            for child in renderer.children(tag) {
+                match child {
+                    tl::Node::Raw(text) => {
+                        out.push_str(&decode_entities(&text.as_utf8_str()));
+                        out.push(' ');
+                    }
+                    tl::Node::Tag(child) if !renderer.is_skipped(child, &tag_name(child)) => {
+                        collect(renderer, child, out)
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut text = String::new();
+        collect(self, tag, &mut text);
+        let mut out = String::with_capacity(text.len());
+        for (i, word) in text.split_whitespace().enumerate() {
+            if i > 0 {
+                out.push(' ');
+            }
+            for c in word.chars() {
+                match c {
+                    '&' => out.push_str("&amp;"),
+                    '<' => out.push_str("&lt;"),
+                    '>' => out.push_str("&gt;"),
+                    _ => out.push(c),
+                }
+            }
+        }
+        out
+    }
+
+    fn is_skipped(&self, tag: &tl::HTMLTag, name: &str) -> bool {
+        let attributes = tag.attributes();
+        SKIPPED_TAGS.contains(&name)
+            || attributes.get("hidden").is_some()
+            || attr(tag, "aria-hidden").as_deref() == Some("true")
+            || attr(tag, "style").is_some_and(|style| {
+                style
+                    .chars()
+                    .filter(|c| !c.is_whitespace())
+                    .collect::<String>()
+                    .to_ascii_lowercase()
+                    .contains("display:none")
+            })
+    }
+
+    fn resolve(&self, url: &str) -> String {
+        self.base_url
+            .as_ref()
+            .and_then(|base| base.join(url).ok())
+            .map(|url| url.to_string())
+            .unwrap_or_else(|| url.to_string())
+    }
+}
+
+fn tag_name(tag: &tl::HTMLTag) -> String {
+    tag.name().as_utf8_str().to_ascii_lowercase()
+}
+
+fn attr(tag: &tl::HTMLTag, name: &str) -> Option<String> {
+    get_attr_string(tag.attributes(), name).map(|value| decode_entities(&value).into_owned())
+}
+
+/// Collapse the whitespace of assembled inline content, rendering `<br>` as `line_break`
+fn clean_inline(inline: &str, line_break: &str) -> String {
+    inline
+        .split(LINE_BREAK)
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(line_break)
+}
+
+/// Paragraph block, escaping a start that would read as other syntax
+fn paragraph(inline: &str) -> Option<Block> {
+    let mut text = clean_inline(inline, "\\\n");
+    if text.is_empty() {
+        return None;
+    }
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    if text.starts_with(['#', '>', '-', '+', '=']) {
+        text.insert(0, '\\');
+    } else if digits > 0 && text[digits..].starts_with(['.', ')']) {
+        text.insert(digits, '\\');
+    }
+    Some(Block { text, list: false })
+}
+
+fn join_blocks(blocks: Vec<Block>) -> String {
+    blocks
+        .into_iter()
+        .map(|block| block.text)
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Join the blocks of a list item, keeping nested lists tight
+fn join_list_item(blocks: Vec<Block>) -> String {
+    let mut out = String::new();
+    for (i, block) in blocks.into_iter().enumerate() {
+        if i > 0 {
+            out.push_str(if block.list { "\n" } else { "\n\n" });
+        }
+        out.push_str(&block.text);
+    }
+    out
+}
+
+/// Wrap inline content in emphasis markers, keeping its outer whitespace outside
+fn wrap(content: &str, marker: &str) -> String {
+    let trimmed = content.trim();
+    if trimmed.is_empty() {
+        return content.to_string();
+    }
+    surround_like(content, format!("{}{}{}", marker, trimmed, marker))
+}
+
+/// `rendered` with the leading and trailing whitespace of `content`
+fn surround_like(content: &str, rendered: String) -> String {
+    let lead = if content.starts_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    let trail = if content.ends_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    format!("{}{}{}", lead, rendered, trail)
+}
+
+fn code_span(code: &str) -> String {
+    let code = code.replace(['\n', '\r'], " ");
+    if code.trim().is_empty() {
+        return String::new();
+    }
+    let ticks = "`".repeat(longest_run(&code, '`') + 1);
+    let pad = if code.starts_with('`') || code.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+    format!("{}{}{}{}{}", ticks, pad, code, pad, ticks)
+}
+
+/// Link destination, in angle brackets when it contains spaces or parentheses
+fn destination(url: &str) -> String {
+    if url.contains(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+        format!("<{}>", url.replace('<', "%3C").replace('>', "%3E"))
+    } else {
+        url.to_string()
+    }
+}
+
+fn longest_run(text: &str, target: char) -> usize {
+    let mut longest = 0;
+    let mut current = 0;
+    for c in text.chars() {
+        if c == target {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    longest
+}
+
+/// Decode HTML character references; `tl` hands out text undecoded
+fn decode_entities(text: &str) -> Cow<'_, str> {
+    if !text.contains('&') {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find('&') {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        let decoded = rest[1..]
+            .find(';')
+            .filter(|&end| end > 0 && end <= 10)
+            .and_then(|end| decode_entity(&rest[1..1 + end]).map(|c| (c, end + 2)));
+        match decoded {
+            Some((c, len)) => {
+                out.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    Cow::Owned(out)
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    if let Some(number) = name.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        return char::from_u32(code).filter(|&c| c != '\0');
+    }
+    Some(match name {
+        "amp" => '&',
+        "lt" => '<',
+        "gt" => '>',
+        "quot" => '"',
+        "apos" => '\'',
+        "nbsp" => '\u{a0}',
+        "ndash" => '\u{2013}',
+        "mdash" => '\u{2014}',
+        "hellip" => '\u{2026}',
+        "lsquo" => '\u{2018}',
+        "rsquo" => '\u{2019}',
+        "ldquo" => '\u{201c}',
+        "rdquo" => '\u{201d}',
+        "laquo" => '\u{ab}',
+        "raquo" => '\u{bb}',
+        "copy" => '\u{a9}',
+        "reg" => '\u{ae}',
+        "trade" => '\u{2122}',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(html: &str, flavor: MarkdownFlavor) -> String {
+        let dom = tl::parse(html, tl::ParserOptions::default()).unwrap();
+        let roots: Vec<tl::NodeHandle> = dom.children().to_vec();
+        MarkdownRenderer::new(dom.parser(), flavor, "https://example.com/blog/post").render(&roots)
+    }
+
+    #[test]
+    fn test_matches_host_converter_output() {
+        let html = "<h1>Title &amp; more</h1><p>Some <strong>bold</strong> and \
+                    <a href=\"../about\">a link</a>.</p>\
+                    <ul><li>one<ul><li>inner</li></ul></li><li>two</li></ul>\
+                    <pre><code class=\"language-rust\">let x = 1;\n</code></pre>";
+        assert_eq!(
+            render(html, MarkdownFlavor::Gfm),
+            "# Title & more\n\nSome **bold** and [a link](https://example.com/about).\n\n\
+             - one\n  - inner\n- two\n\n```rust\nlet x = 1;\n```"
+        );
+    }
+
+    #[test]
+    fn test_tables_by_flavor() {
+        let html = "<table><tr><th>Name</th><th>Note</th></tr>\
+                    <tr><td>a|b</td><td>x</td></tr></table>";
+        assert_eq!(
+            render(html, MarkdownFlavor::Gfm),
+            "| Name | Note |\n| --- | --- |\n| a\\|b | x |"
+        );
+        assert_eq!(
+            render(html, MarkdownFlavor::Commonmark),
+            "<table>\n<tr><th>Name</th><th>Note</th></tr>\n<tr><td>a|b</td><td>x</td></tr>\n</table>"
+        );
+
+        // Page markup never reaches the raw HTML block
+        let html = "<table><tr><td onclick=\"steal()\"><b>1 &lt; 2</b>\
+                    <script>alert(1)</script><img src=x onerror=alert(1)></td></tr></table>";
+        assert_eq!(
+            render(html, MarkdownFlavor::Commonmark),
+            "<table>\n<tr><td>1 &lt; 2</td></tr>\n</table>"
+        );
+    }
+
+    #[test]
+    fn test_task_lists_and_skipped_elements() {
+        let html = "<div><script>x()</script><ul><li><input type=\"checkbox\" checked> done</li>\
+                    </ul><p hidden>secret</p><p><del>old</del> new</p></div>";
+        assert_eq!(
+            render(html, MarkdownFlavor::Gfm),
+            "- [x] done\n\n~~old~~ new"
+        );
+        assert_eq!(
+            render(html, MarkdownFlavor::Commonmark),
+            "- done\n\nold new"
+        );
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(
+            decode_entities("a &lt;b&gt; &#169; &#x41; &bogus; &"),
+            "a <b> © A &bogus; &"
+        );
+    }
+}
@@ -0,0 +1,199 @@
+//! Property-based fuzzing of the WASM extractor's guest code
+//!
+//! Runs the component's extraction natively on malformed and hostile HTML
+//! (deep nesting, broken encodings, enormous attributes, hostile selectors)
+//! and checks that it never panics (a panic traps the instance in
+//! production), finishes within `TIME_BUDGET`, and keeps its peak heap growth
+//! under `MEMORY_BASE + MEMORY_PER_BYTE * input`. `MEMORY_BASE` matches the
+//! pool's default linear memory limit of 256 pages.
+//!
+//! The native extractors have the same harness in
+//! `crates/riptide-extraction/tests/extraction_fuzz_tests.rs`.
+
+use proptest::prelude::*;
+use riptide_extractor_wasm::{Component, ExtractionMode};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Mutex};
+use std::time::Duration;
+
+/// Wall-clock limit per extraction, generous for unoptimized builds
+const TIME_BUDGET: Duration = Duration::from_secs(20);
+
+/// Fixed heap allowance per extraction (256 WASM pages)
+const MEMORY_BASE: usize = 256 * 64 * 1024;
+
+/// Heap allowance per input byte
+const MEMORY_PER_BYTE: usize = 32;
+
+const URL: &str = "https://fuzz.example/page";
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static SERIAL: Mutex<()> = Mutex::new(());
+
+/// System allocator tracking live and peak heap bytes
+struct PeakAllocator;
+
+impl PeakAllocator {
+    fn grow(size: usize) {
+        let now = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(now, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                Self::grow(new_size - layout.size());
+            } else {
+                CURRENT.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+/// Extract `html` on a worker thread and check the time and memory budgets
+fn extract_bounded(html: String, mode: ExtractionMode) {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let input_len = html.len();
+    let label = format!("{:?} extraction", mode);
+    let baseline = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+
+    let (done_tx, done_rx) = mpsc::channel();
+    let worker = std::thread::spawn(move || {
+        let _ = Component::new().extract(html, URL.to_string(), mode);
+        let _ = done_tx.send(());
+    });
+    if let Err(mpsc::RecvTimeoutError::Timeout) = done_rx.recv_timeout(TIME_BUDGET) {
+        panic!(
+            "{} did not finish within {:?} on a {} byte input",
+            label, TIME_BUDGET, input_len
+        );
+    }
+    if let Err(payload) = worker.join() {
+        let message = payload
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| payload.downcast_ref::<&str>().copied())
+            .unwrap_or("non-string panic");
+        panic!(
+            "{} panicked on a {} byte input: {}",
+            label, input_len, message
+        );
+    }
+
+    let peak = PEAK.load(Ordering::SeqCst).saturating_sub(baseline);
+    let limit = MEMORY_BASE + MEMORY_PER_BYTE * input_len;
+    assert!(
+        peak <= limit,
+        "{} used {} bytes of heap on a {} byte input (limit {})",
+        label,
+        peak,
+        input_len,
+        limit
+    );
+}
+
+const TAGS: &[&str] = &[
+    "div", "span", "p", "a", "b", "table", "td", "li", "article", "main", "svg", "template",
+];
+
+#[rustfmt::skip]
+const SOUP: &[&str] = &[
+    "<", ">", "</", "/>", "<!--", "-->", "<![CDATA[", "<script>", "</script>", "<style>",
+    "<title>", "</title>", "<p>", "<a href=", "\"", "'", "=", "&", "&#", "&#xD800;",
+    "&#x110000;", "\u{0}", "\u{feff}", "\r\n", " ", "text", "日本語", "🦀",
+];
+
+fn hostile_html() -> impl Strategy<Value = String> {
+    prop_oneof![
+        // Deep nesting, closed or left open
+        (prop::collection::vec(prop::sample::select(TAGS), 1..4000), any::<bool>()).prop_map(
+            |(tags, close)| {
+                let open: String = tags.iter().map(|t| format!("<{}>", t)).collect();
+                let closing: String = if close {
+                    tags.iter().rev().map(|t| format!("</{}>", t)).collect()
+                } else {
+                    String::new()
+                };
+                format!("<html><body>{}deep text{}</body></html>", open, closing)
+            }
+        ),
+        // One enormous attribute value
+        (1usize..262_144, prop::sample::select(&['a', '"', '<', '&', ' ', '🦀'][..])).prop_map(
+            |(len, fill)| {
+                let value = fill.to_string().repeat(len);
+                format!(
+                    "<html><head><meta name=\"description\" content=\"{0}\"></head>\
+                     <body><article><a href=\"{0}\">link</a><img src=\"{0}\"></article></body></html>",
+                    value
+                )
+            }
+        ),
+        // Arbitrary bytes decoded lossily
+        prop::collection::vec(any::<u8>(), 0..16_384).prop_map(|bytes| format!(
+            "<html><body><p>{}</p></body></html>",
+            String::from_utf8_lossy(&bytes)
+        )),
+        // Tag soup
+        prop::collection::vec(prop::sample::select(SOUP), 0..4000)
+            .prop_map(|parts| format!("<html><body>{}</body></html>", parts.concat())),
+    ]
+}
+
+fn extraction_mode() -> impl Strategy<Value = ExtractionMode> {
+    prop_oneof![
+        Just(ExtractionMode::Article),
+        Just(ExtractionMode::Full),
+        Just(ExtractionMode::Metadata),
+        prop::collection::vec(
+            prop_oneof![
+                Just("article p".to_string()),
+                Just(":not(".to_string()),
+                Just("a[href*=\"".to_string()),
+                "\\PC{0,64}",
+            ],
+            0..8
+        )
+        .prop_map(ExtractionMode::Custom),
+    ]
+}
+
+/// 64 cases unless `PROPTEST_CASES` is set
+fn config() -> ProptestConfig {
+    let cases = std::env::var("PROPTEST_CASES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(64);
+    ProptestConfig::with_cases(cases)
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn extractor_survives_hostile_html(html in hostile_html(), mode in extraction_mode()) {
+        extract_bounded(html, mode);
+    }
+}